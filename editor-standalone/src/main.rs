@@ -2,6 +2,10 @@ use clap::Parser;
 use fyrox::event_loop::EventLoop;
 use fyroxed_base::{Editor, StartupData};
 
+#[cfg(feature = "memory_tracking")]
+#[global_allocator]
+static ALLOCATOR: fyrox::core::memory::TrackingAllocator = fyrox::core::memory::TrackingAllocator;
+
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
@@ -4,7 +4,7 @@ use crate::fyrox::{
     generic_animation::{
         machine::{
             event::Event, node::blendspace::BlendSpacePoint, BlendPose, IndexedBlendInput, Machine,
-            PoseNode, State,
+            MachineLayer, Parameter, PoseNode, State,
         },
         AnimationContainer,
     },
@@ -37,15 +37,15 @@ use crate::{
 use std::{any::Any, fmt::Debug};
 
 mod blendspace;
-mod canvas;
+pub(crate) mod canvas;
 pub mod command;
-mod connection;
-mod node;
+pub(crate) mod connection;
+pub(crate) mod node;
 mod parameter;
 mod segment;
 mod selectable;
 pub mod selection;
-mod socket;
+pub(crate) mod socket;
 mod state_graph;
 mod state_viewer;
 mod toolbar;
@@ -54,14 +54,55 @@ mod transition;
 const NORMAL_BACKGROUND: Color = Color::opaque(60, 60, 60);
 const SELECTED_BACKGROUND: Color = Color::opaque(80, 80, 80);
 const BORDER_COLOR: Color = Color::opaque(70, 70, 70);
-const NORMAL_ROOT_COLOR: Color = Color::opaque(40, 80, 0);
-const SELECTED_ROOT_COLOR: Color = Color::opaque(60, 100, 0);
+pub(crate) const NORMAL_ROOT_COLOR: Color = Color::opaque(40, 80, 0);
+pub(crate) const SELECTED_ROOT_COLOR: Color = Color::opaque(60, 100, 0);
 
 struct PreviewModeData<N: 'static> {
     machine: Machine<Handle<N>>,
     nodes: Vec<(Handle<N>, N)>,
 }
 
+fn debug_info<N>(machine: &Machine<Handle<N>>, layer: &MachineLayer<Handle<N>>) -> String
+where
+    N: 'static,
+{
+    let state_name = |handle| {
+        layer
+            .states()
+            .try_borrow(handle)
+            .map_or("<None>", |state| state.name.as_str())
+    };
+
+    let mut text = format!(
+        "Layer: {}\nActive State: {}\n",
+        layer.name(),
+        state_name(layer.active_state())
+    );
+
+    if let Some(transition) = layer.transitions().try_borrow(layer.active_transition()) {
+        text += &format!(
+            "Transition: {} ({} -> {}) {:.0}%\n",
+            transition.name(),
+            state_name(transition.source()),
+            state_name(transition.dest()),
+            transition.blend_factor() * 100.0
+        );
+    }
+
+    text += "Parameters:\n";
+    for definition in machine.parameters().iter() {
+        let value = match definition.value {
+            Parameter::Weight(weight) => format!("{weight:.3}"),
+            Parameter::Rule(rule) => rule.to_string(),
+            Parameter::Index(index) => index.to_string(),
+            Parameter::SamplingPoint(point) => format!("({:.3}; {:.3})", point.x, point.y),
+        };
+        text += &format!("    {} = {}\n", definition.name, value);
+    }
+
+    text
+}
+
 fn fetch_selection<N>(editor_selection: &Selection) -> AbsmSelection<N>
 where
     N: Debug,
@@ -446,6 +487,48 @@ impl AbsmEditor {
         N: SceneGraphNode<SceneGraph = G, ResourceData = P>,
     {
         self.handle_machine_events(editor_selection, graph, ui);
+        self.update_debug_overlay(editor_selection, graph, ui);
+    }
+
+    fn update_debug_overlay<P, G, N>(
+        &mut self,
+        editor_selection: &Selection,
+        graph: &G,
+        ui: &mut UserInterface,
+    ) where
+        P: PrefabData<Graph = G>,
+        G: SceneGraph<Node = N, Prefab = P>,
+        N: SceneGraphNode<SceneGraph = G, ResourceData = P>,
+    {
+        let selection = fetch_selection(editor_selection);
+
+        let machine = machine_container_ref(graph, selection.absm_node_handle)
+            .filter(|_| self.preview_mode_data.is_some());
+        let layer = machine.and_then(|machine| {
+            selection
+                .layer
+                .and_then(|layer_index| machine.layers().get(layer_index))
+        });
+
+        if let (Some(machine), Some(layer)) = (machine, layer) {
+            let active_transition = layer
+                .transitions()
+                .try_borrow(layer.active_transition())
+                .map(|transition| (layer.active_transition(), transition.blend_factor()));
+
+            self.state_graph_viewer
+                .set_transition_progress(ui, active_transition);
+            self.state_graph_viewer
+                .set_debug_info(ui, Some(debug_info(machine, layer)));
+
+            // Parameters could be changed by the machine itself or by scripts, keep the panel
+            // up-to-date, so it always shows actual values.
+            self.parameter_panel.sync_to_model(ui, machine.parameters());
+        } else if self.state_graph_viewer.is_debugging() {
+            self.state_graph_viewer
+                .set_transition_progress::<N>(ui, None);
+            self.state_graph_viewer.set_debug_info(ui, None);
+        }
     }
 
     pub fn handle_machine_events<P, G, N>(
@@ -44,7 +44,6 @@ pub struct Socket {
     click_position: Option<Vector2<f32>>,
     pub parent_node: ErasedHandle,
     pub direction: SocketDirection,
-    #[allow(dead_code)] // TODO
     editor: Handle<UiNode>,
     pin: Handle<UiNode>,
     pub index: usize,
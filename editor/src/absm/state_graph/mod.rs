@@ -5,10 +5,12 @@ use crate::fyrox::{
     graph::BaseSceneGraph,
     gui::{
         border::BorderBuilder,
+        grid::GridBuilder,
         message::{MessageDirection, UiMessage},
+        text::{TextBuilder, TextMessage},
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowTitle},
-        BuildContext, Thickness, UiNode, UserInterface,
+        BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
 };
 use crate::{
@@ -39,6 +41,8 @@ pub struct StateGraphViewer {
     transition_context_menu: TransitionContextMenu,
     prev_absm: ErasedHandle,
     prev_layer: Option<usize>,
+    debug_overlay: Handle<UiNode>,
+    debug_info: Option<String>,
 }
 
 fn fetch_state_node_model_handle<N>(
@@ -65,6 +69,16 @@ impl StateGraphViewer {
         )
         .build(ctx);
 
+        let debug_overlay = TextBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_hit_test_visibility(false)
+                .with_margin(Thickness::uniform(4.0))
+                .with_horizontal_alignment(HorizontalAlignment::Left)
+                .with_vertical_alignment(VerticalAlignment::Top),
+        )
+        .build(ctx);
+
         let window = WindowBuilder::new(WidgetBuilder::new())
             .with_title(WindowTitle::text("State Graph"))
             .can_close(false)
@@ -73,7 +87,14 @@ impl StateGraphViewer {
                 BorderBuilder::new(
                     WidgetBuilder::new()
                         .with_margin(Thickness::uniform(1.0))
-                        .with_child(canvas),
+                        .with_child(
+                            GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_child(canvas)
+                                    .with_child(debug_overlay),
+                            )
+                            .build(ctx),
+                        ),
                 )
                 .build(ctx),
             )
@@ -91,6 +112,59 @@ impl StateGraphViewer {
             transition_context_menu,
             prev_absm: Default::default(),
             prev_layer: None,
+            debug_overlay,
+            debug_info: None,
+        }
+    }
+
+    /// Returns `true` if the debug overlay is currently shown.
+    pub fn is_debugging(&self) -> bool {
+        self.debug_info.is_some()
+    }
+
+    /// Sets the text of the debug overlay shown on top of the state graph, `None` hides the overlay.
+    pub fn set_debug_info(&mut self, ui: &UserInterface, info: Option<String>) {
+        if self.debug_info == info {
+            return;
+        }
+
+        ui.send_message(WidgetMessage::visibility(
+            self.debug_overlay,
+            MessageDirection::ToWidget,
+            info.is_some(),
+        ));
+        if let Some(text) = info.as_ref() {
+            ui.send_message(TextMessage::text(
+                self.debug_overlay,
+                MessageDirection::ToWidget,
+                text.clone(),
+            ));
+        }
+
+        self.debug_info = info;
+    }
+
+    /// Shows progress of the given transition on the graph, progress of every other transition
+    /// is hidden.
+    pub fn set_transition_progress<N>(
+        &self,
+        ui: &UserInterface,
+        active: Option<(Handle<Transition<Handle<N>>>, f32)>,
+    ) where
+        N: 'static,
+    {
+        for &child in ui.node(self.canvas).children() {
+            if let Some(transition_view_ref) = ui.node(child).query_component::<TransitionView>() {
+                let model_handle: Handle<Transition<Handle<N>>> =
+                    transition_view_ref.model_handle.into();
+                ui.send_message(TransitionMessage::progress(
+                    child,
+                    MessageDirection::ToWidget,
+                    active.and_then(|(transition, progress)| {
+                        (transition == model_handle).then_some(progress)
+                    }),
+                ));
+            }
         }
     }
 
@@ -35,14 +35,18 @@ const SELECTED_COLOR: Color = Color::opaque(120, 120, 120);
 const PICKED_BRUSH: Brush = Brush::Solid(PICKED_COLOR);
 const NORMAL_BRUSH: Brush = Brush::Solid(NORMAL_COLOR);
 const SELECTED_BRUSH: Brush = Brush::Solid(SELECTED_COLOR);
+const PROGRESS_BRUSH: Brush = Brush::Solid(Color::opaque(255, 180, 0));
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TransitionMessage {
     Activate,
+    /// Sets progress of the transition in `[0; 1]` range, `None` hides the progress bar.
+    Progress(Option<f32>),
 }
 
 impl TransitionMessage {
     define_constructor!(TransitionMessage:Activate => fn activate(), layout: false);
+    define_constructor!(TransitionMessage:Progress => fn progress(Option<f32>), layout: false);
 }
 
 #[derive(Clone, Debug, Visit, Reflect, ComponentProvider)]
@@ -53,6 +57,9 @@ pub struct TransitionView {
     #[component(include)]
     selectable: Selectable,
     activity_factor: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    progress: Option<f32>,
 }
 
 impl TransitionView {
@@ -112,6 +119,22 @@ impl Control for TransitionView {
             self.segment.source_pos,
             self.segment.dest_pos,
         );
+
+        if let Some(progress) = self.progress {
+            let source_pos = self.segment.source_pos;
+            let dest_pos = self.segment.dest_pos;
+            drawing_context.push_line(
+                source_pos,
+                source_pos + (dest_pos - source_pos).scale(progress.clamp(0.0, 1.0)),
+                6.0,
+            );
+            drawing_context.commit(
+                self.clip_bounds(),
+                PROGRESS_BRUSH,
+                CommandTexture::None,
+                None,
+            );
+        }
     }
 
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
@@ -140,8 +163,19 @@ impl Control for TransitionView {
             {
                 self.handle_selection_change(ui);
             }
-        } else if let Some(TransitionMessage::Activate) = message.data() {
-            self.activity_factor = 1.0;
+        } else if let Some(msg) = message.data::<TransitionMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    TransitionMessage::Activate => {
+                        self.activity_factor = 1.0;
+                    }
+                    TransitionMessage::Progress(progress) => {
+                        self.progress = *progress;
+                    }
+                }
+            }
         }
     }
 
@@ -193,6 +227,7 @@ impl TransitionBuilder {
             model_handle,
             selectable: Selectable::default(),
             activity_factor: 0.0,
+            progress: None,
         };
 
         ctx.add_node(UiNode::new(transition))
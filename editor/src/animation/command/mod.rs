@@ -1,4 +1,5 @@
 use crate::fyrox::{
+    asset::ResourceData,
     core::{
         log::Log,
         math::curve::Curve,
@@ -11,6 +12,7 @@ use crate::fyrox::{
         RootMotionSettings,
     },
     graph::{BaseSceneGraph, SceneGraphNode},
+    scene::animation::pose::{PoseLibraryResource, PoseSnapshot},
 };
 use crate::{
     animation::selection::AnimationSelection,
@@ -21,6 +23,7 @@ use crate::{
 use std::{
     fmt::Debug,
     ops::{IndexMut, Range},
+    path::PathBuf,
 };
 
 pub fn fetch_animations_container<N: Debug + 'static>(
@@ -473,6 +476,13 @@ define_animation_swap_command!(SetAnimationEnabledCommand<bool>(self, context) {
     self.value = old;
 });
 
+define_animation_swap_command!(SetAnimationAudioClipCommand<Option<PathBuf>>(self, context) {
+    let animation = fetch_animation(self.node_handle, self.animation_handle, context);
+    let old = animation.audio_clip().map(|path| path.to_path_buf());
+    animation.set_audio_clip(self.value.clone());
+    self.value = old;
+});
+
 define_animation_swap_command!(SetAnimationRootMotionSettingsCommand<Option<RootMotionSettings<Handle<N>>>>(self, context) {
     let animation = fetch_animation(self.node_handle, self.animation_handle, context);
     let old = animation.root_motion_settings_ref().cloned();
@@ -672,3 +682,39 @@ impl<N: Debug + 'static> CommandTrait for SetTrackBindingCommand<N> {
         self.swap(context)
     }
 }
+
+#[derive(Debug)]
+pub struct SetPoseLibraryPosesCommand {
+    library: PoseLibraryResource,
+    poses: Vec<PoseSnapshot>,
+}
+
+impl SetPoseLibraryPosesCommand {
+    pub fn new(library: PoseLibraryResource, poses: Vec<PoseSnapshot>) -> Self {
+        Self { library, poses }
+    }
+
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.library.data_ref().poses, &mut self.poses);
+
+        let header = self.library.header();
+        if let Some(path) = header.kind.path_owned() {
+            drop(header);
+            Log::verify(self.library.data_ref().save(&path));
+        }
+    }
+}
+
+impl CommandTrait for SetPoseLibraryPosesCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Set Pose Library Poses".to_string()
+    }
+
+    fn execute(&mut self, _context: &mut dyn CommandContext) {
+        self.swap()
+    }
+
+    fn revert(&mut self, _context: &mut dyn CommandContext) {
+        self.swap()
+    }
+}
@@ -1,24 +1,28 @@
 use crate::{
     animation::{
         command::{
-            AddAnimationSignal, MoveAnimationSignal, RemoveAnimationSignal,
+            AddAnimationSignal, AddTrackCommand, MoveAnimationSignal, RemoveAnimationSignal,
             ReplaceTrackCurveCommand,
         },
         ruler::{RulerBuilder, RulerMessage, SignalView},
         selection::{AnimationSelection, SelectedEntity},
         thumb::{ThumbBuilder, ThumbMessage},
         toolbar::{Toolbar, ToolbarAction},
-        track::TrackList,
+        track::{property_value_components, type_id_to_supported_type, TrackList},
+        waveform::load_waveform,
     },
     command::{Command, CommandGroup},
     fyrox::{
         asset::manager::ResourceManager,
         core::{
-            algebra::Vector2, log::Log, math::Rect, pool::ErasedHandle, pool::Handle, uuid::Uuid,
-            variable::InheritableVariable,
+            algebra::Vector2, log::Log, math::Rect, pool::ErasedHandle, pool::Handle,
+            reflect::ResolvePath, uuid::Uuid, variable::InheritableVariable,
         },
         fxhash::FxHashSet,
-        generic_animation::{signal::AnimationSignal, AnimationContainer},
+        generic_animation::{
+            container::TrackDataContainer, signal::AnimationSignal, track::Track,
+            value::ValueBinding, AnimationContainer,
+        },
         graph::{BaseSceneGraph, PrefabData, SceneGraph, SceneGraphNode},
         gui::{
             border::BorderBuilder,
@@ -31,22 +35,40 @@ use crate::{
             BuildContext, UiNode, UserInterface, BRUSH_DARK, BRUSH_PRIMARY,
         },
         resource::model::AnimationSource,
+        scene::animation::pose::BoneTransform,
     },
     message::MessageSender,
     scene::{commands::ChangeSelectionCommand, Selection},
     send_sync_message, Message,
 };
 use fyrox::core::color::Color;
-use fyrox::core::math::curve::Curve;
+use fyrox::core::math::curve::{Curve, CurveKey, CurveKeyKind};
 use fyrox::gui::brush::Brush;
-use std::any::{Any, TypeId};
+use std::{
+    any::{Any, TypeId},
+    path::PathBuf,
+};
 
 pub mod command;
-mod ruler;
+pub mod pose;
+pub(crate) mod ruler;
 pub mod selection;
-mod thumb;
+pub(crate) mod thumb;
 mod toolbar;
-mod track;
+pub(crate) mod track;
+mod waveform;
+
+/// Adds a new key at the given location or replaces the value of an existing one.
+fn set_key(curve: &mut Curve, location: f32, value: f32) {
+    let mut keys = curve.keys().to_vec();
+    curve.clear();
+    for key in keys.drain(..) {
+        if (key.location - location).abs() > f32::EPSILON {
+            curve.add_key(key);
+        }
+    }
+    curve.add_key(CurveKey::new(location, value, CurveKeyKind::Linear));
+}
 
 pub trait PreviewData {
     fn enter(&mut self);
@@ -67,6 +89,7 @@ pub struct AnimationEditor {
     ruler: Handle<UiNode>,
     preview_mode_data: Option<Box<dyn Any>>,
     thumb: Handle<UiNode>,
+    audio_clip: Option<PathBuf>,
 }
 
 fn fetch_selection<G, N>(
@@ -262,6 +285,7 @@ impl AnimationEditor {
             content,
             ruler,
             preview_mode_data: None,
+            audio_clip: None,
             thumb,
         }
     }
@@ -595,6 +619,199 @@ impl AnimationEditor {
         self.preview_mode_data.is_some()
     }
 
+    /// Returns `true` if the editor is opened and is in preview mode, which means that animation
+    /// keys can be recorded from the inspector.
+    pub fn can_record_keys(&self, ui: &UserInterface) -> bool {
+        self.is_in_preview_mode() && ui.node(self.window).visibility()
+    }
+
+    /// Writes a key with the current value of a property of the given node into the selected
+    /// animation at its current time. A track for the property is created if the animation does
+    /// not have one yet. Does nothing if the editor is not in preview mode.
+    pub fn record_property<G, N>(
+        &mut self,
+        node: Handle<N>,
+        property_path: &str,
+        editor_selection: &Selection,
+        graph: &G,
+        sender: &MessageSender,
+    ) where
+        G: SceneGraph<Node = N>,
+        N: SceneGraphNode<SceneGraph = G>,
+    {
+        if !self.is_in_preview_mode() {
+            return;
+        }
+
+        let selection = fetch_selection(self, graph, editor_selection);
+
+        let Some(animation) = animation_container_ref(graph, selection.animation_player)
+            .and_then(|container| container.try_get(selection.animation))
+        else {
+            Log::warn("Unable to record a key: there's no selected animation!");
+            return;
+        };
+
+        let Some(node_ref) = graph.try_get(node) else {
+            return;
+        };
+
+        let mut value = None;
+        node_ref.resolve_path(property_path, &mut |result| match result {
+            Ok(property) => {
+                let mut property_type = TypeId::of::<()>();
+                property.as_any(&mut |any| property_type = any.type_id());
+
+                value = type_id_to_supported_type(property_type)
+                    .zip(property_value_components(property));
+            }
+            Err(e) => Log::err(format!(
+                "Invalid property path {}. Error: {:?}!",
+                property_path, e
+            )),
+        });
+
+        let Some(((kind, value_type), components)) = value else {
+            Log::warn(format!(
+                "Property {} cannot be animated, because its type is not supported!",
+                property_path
+            ));
+            return;
+        };
+
+        let time = animation.time_position();
+
+        let existing_track = animation.tracks().iter().find(|track| {
+            track.target() == node
+                && matches!(track.binding(), ValueBinding::Property { name, .. } if name == property_path)
+        });
+
+        if let Some(track) = existing_track {
+            let commands = track
+                .data_container()
+                .curves_ref()
+                .iter()
+                .zip(components)
+                .map(|(curve, value)| {
+                    let mut curve = curve.clone();
+                    set_key(&mut curve, time, value);
+                    Command::new(ReplaceTrackCurveCommand {
+                        animation_player: selection.animation_player,
+                        animation: selection.animation,
+                        curve,
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            sender.do_command(CommandGroup::from(commands).with_custom_name("Record Key"));
+        } else {
+            let mut container = TrackDataContainer::new(kind);
+            for (curve, value) in container.curves_mut().iter_mut().zip(components) {
+                set_key(curve, time, value);
+            }
+
+            let mut track = Track::new(
+                container,
+                ValueBinding::Property {
+                    name: property_path.to_string(),
+                    value_type,
+                },
+            );
+            track.set_target(node);
+
+            sender.do_command(AddTrackCommand::new(
+                selection.animation_player,
+                selection.animation,
+                track,
+            ));
+        }
+    }
+
+    /// Returns a handle of the animation player, that is currently edited.
+    pub fn animation_player(&self) -> ErasedHandle {
+        self.animation_player
+    }
+
+    /// Writes position, rotation and scale keys of the given nodes into the selected animation at
+    /// its current time. Tracks are created for the nodes, that are not animated yet.
+    pub fn insert_transform_keys<G, N>(
+        &mut self,
+        transforms: &[(Handle<N>, BoneTransform)],
+        editor_selection: &Selection,
+        graph: &G,
+        sender: &MessageSender,
+    ) where
+        G: SceneGraph<Node = N>,
+        N: SceneGraphNode<SceneGraph = G>,
+    {
+        let selection = fetch_selection(self, graph, editor_selection);
+
+        let Some(animation) = animation_container_ref(graph, selection.animation_player)
+            .and_then(|container| container.try_get(selection.animation))
+        else {
+            Log::warn("Unable to insert keys: there's no selected animation!");
+            return;
+        };
+
+        let time = animation.time_position();
+
+        let mut commands = Vec::new();
+        for (node, transform) in transforms {
+            let (x, y, z) = transform.rotation.euler_angles();
+            for (binding, components) in [
+                (
+                    ValueBinding::Position,
+                    transform.position.as_slice().to_vec(),
+                ),
+                (ValueBinding::Rotation, vec![x, y, z]),
+                (ValueBinding::Scale, transform.scale.as_slice().to_vec()),
+            ] {
+                let existing_track = animation
+                    .tracks()
+                    .iter()
+                    .find(|track| track.target() == *node && *track.binding() == binding);
+
+                if let Some(track) = existing_track {
+                    for (curve, value) in track.data_container().curves_ref().iter().zip(components)
+                    {
+                        let mut curve = curve.clone();
+                        set_key(&mut curve, time, value);
+                        commands.push(Command::new(ReplaceTrackCurveCommand {
+                            animation_player: selection.animation_player,
+                            animation: selection.animation,
+                            curve,
+                        }));
+                    }
+                } else {
+                    let mut track = match binding {
+                        ValueBinding::Position => Track::new_position(),
+                        ValueBinding::Rotation => Track::new_rotation(),
+                        _ => Track::new_scale(),
+                    };
+                    for (curve, value) in track
+                        .data_container_mut()
+                        .curves_mut()
+                        .iter_mut()
+                        .zip(components)
+                    {
+                        set_key(curve, time, value);
+                    }
+                    track.set_target(*node);
+
+                    commands.push(Command::new(AddTrackCommand::new(
+                        selection.animation_player,
+                        selection.animation,
+                        track,
+                    )));
+                }
+            }
+        }
+
+        if !commands.is_empty() {
+            sender.do_command(CommandGroup::from(commands).with_custom_name("Insert Pose Keys"));
+        }
+    }
+
     pub fn handle_message<G, N>(
         &mut self,
         message: &Message,
@@ -646,12 +863,25 @@ impl AnimationEditor {
         editor_selection: &Selection,
         ui: &mut UserInterface,
         graph: &G,
+        resource_manager: &ResourceManager,
     ) where
         G: SceneGraph<Node = N>,
         N: SceneGraphNode<SceneGraph = G>,
     {
         let selection = fetch_selection(self, graph, editor_selection);
 
+        let audio_clip = animation_container_ref(graph, selection.animation_player)
+            .and_then(|animations| animations.try_get(selection.animation))
+            .and_then(|animation| animation.audio_clip());
+        if self.audio_clip.as_deref() != audio_clip {
+            self.audio_clip = audio_clip.map(|path| path.to_path_buf());
+            ui.send_message(CurveEditorMessage::waveform(
+                self.curve_editor,
+                MessageDirection::ToWidget,
+                audio_clip.and_then(|path| load_waveform(path, resource_manager)),
+            ));
+        }
+
         let mut is_animation_player_selected = false;
         let mut is_animation_selected = false;
         let mut is_curve_selected = false;
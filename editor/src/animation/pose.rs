@@ -0,0 +1,509 @@
+//! Pose library panel allows to capture poses of bones into a pose library and to apply them back,
+//! either to the scene (or to the animation preview) or as animation keys. See [`PoseLibrary`] docs
+//! for more info.
+//!
+//! [`PoseLibrary`]: crate::fyrox::scene::animation::pose::PoseLibrary
+
+use crate::{
+    animation::{
+        command::SetPoseLibraryPosesCommand, selection::AnimationSelection, AnimationEditor,
+    },
+    command::{Command, CommandGroup},
+    fyrox::{
+        core::{
+            algebra::Vector2, log::Log, pool::Handle, reflect::prelude::*, scope_profile,
+            type_traits::prelude::*, uuid_provider, visitor::prelude::*,
+        },
+        engine::Engine,
+        graph::{BaseSceneGraph, SceneGraph, SceneGraphNode},
+        gui::{
+            border::BorderBuilder,
+            button::{ButtonBuilder, ButtonMessage},
+            define_widget_deref,
+            draw::{CommandTexture, Draw, DrawingContext},
+            grid::{Column, GridBuilder, Row},
+            inspector::{
+                Inspector, InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+            },
+            message::{MessageDirection, UiMessage},
+            scroll_viewer::ScrollViewerBuilder,
+            stack_panel::StackPanelBuilder,
+            text::TextBuilder,
+            utils::make_simple_tooltip,
+            widget::{Widget, WidgetBuilder, WidgetMessage},
+            window::{WindowBuilder, WindowTitle},
+            wrap_panel::WrapPanelBuilder,
+            BuildContext, Control, HorizontalAlignment, Orientation, Thickness, UiNode,
+            UserInterface, BRUSH_BRIGHT, BRUSH_DARKER,
+        },
+        scene::{
+            animation::{
+                pose::{PoseLibraryResource, PoseSnapshot},
+                AnimationPlayer,
+            },
+            graph::Graph,
+            node::Node,
+        },
+    },
+    inspector::editors::make_property_editors_container,
+    message::MessageSender,
+    scene::{commands::graph::SetNodeTransformCommand, GameScene, Selection},
+    send_sync_message, MSG_SYNC_FLAG,
+};
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+#[derive(Clone, Debug, Reflect)]
+struct PoseLibrarySettings {
+    #[reflect(description = "A pose library, that is used to save and load poses.")]
+    library: Option<PoseLibraryResource>,
+    #[reflect(description = "Name of a pose in the pose library.")]
+    pose_name: String,
+    #[reflect(
+        description = "How much of the pose is blended with the current transforms of the bones \
+    when the pose is applied, in percents.",
+        min_value = 0.0,
+        max_value = 100.0
+    )]
+    blend: f32,
+}
+
+impl Default for PoseLibrarySettings {
+    fn default() -> Self {
+        Self {
+            library: None,
+            pose_name: Default::default(),
+            blend: 100.0,
+        }
+    }
+}
+
+/// A widget, that draws bones of a pose as a set of lines.
+#[derive(Clone, Debug, Visit, Reflect, ComponentProvider)]
+struct PoseThumbnail {
+    widget: Widget,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    lines: Vec<Vector2<f32>>,
+}
+
+define_widget_deref!(PoseThumbnail);
+
+uuid_provider!(PoseThumbnail = "aa625f13-5a82-4cae-aef3-91be71dbf75a");
+
+impl Control for PoseThumbnail {
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        let bounds = self.bounding_rect();
+        drawing_context.push_rect_filled(&bounds, None);
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        let padding = 4.0;
+        let size = Vector2::new(bounds.w(), bounds.h()) - Vector2::repeat(2.0 * padding);
+        let to_screen = |point: Vector2<f32>| {
+            Vector2::new(
+                bounds.x() + padding + point.x * size.x,
+                bounds.y() + padding + (1.0 - point.y) * size.y,
+            )
+        };
+        for line in self.lines.chunks_exact(2) {
+            drawing_context.push_line(to_screen(line[0]), to_screen(line[1]), 2.0);
+        }
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.foreground(),
+            CommandTexture::None,
+            None,
+        );
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+    }
+}
+
+fn make_pose_view(pose: &PoseSnapshot, ctx: &mut BuildContext) -> Handle<UiNode> {
+    let thumbnail = ctx.add_node(UiNode::new(PoseThumbnail {
+        widget: WidgetBuilder::new()
+            .with_width(64.0)
+            .with_height(64.0)
+            .with_background(BRUSH_DARKER)
+            .with_foreground(BRUSH_BRIGHT)
+            .build(),
+        lines: pose.thumbnail.clone(),
+    }));
+
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(72.0)
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, &pose.name)),
+    )
+    .with_content(
+        StackPanelBuilder::new(
+            WidgetBuilder::new().with_child(thumbnail).with_child(
+                TextBuilder::new(
+                    WidgetBuilder::new().with_horizontal_alignment(HorizontalAlignment::Center),
+                )
+                .with_text(&pose.name)
+                .build(ctx),
+            ),
+        )
+        .build(ctx),
+    )
+    .build(ctx)
+}
+
+fn make_button(text: &str, tooltip: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(80.0)
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+pub struct PoseLibraryPanel {
+    pub window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    capture: Handle<UiNode>,
+    remove: Handle<UiNode>,
+    apply: Handle<UiNode>,
+    insert_keys: Handle<UiNode>,
+    poses_panel: Handle<UiNode>,
+    pose_views: Vec<(Handle<UiNode>, PoseSnapshot)>,
+    settings: PoseLibrarySettings,
+}
+
+impl PoseLibraryPanel {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
+        let settings = PoseLibrarySettings::default();
+
+        let context = InspectorContext::from_object(
+            &settings,
+            ctx,
+            Arc::new(make_property_editors_container(sender)),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+            Default::default(),
+        );
+        let inspector = InspectorBuilder::new(
+            WidgetBuilder::new()
+                .on_row(0)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_context(context)
+        .build(ctx);
+
+        let capture = make_button(
+            "Capture",
+            "Saves transforms of the selected nodes (or the targets of the selected animation \
+            tracks) as a pose with the given name.",
+            ctx,
+        );
+        let remove = make_button(
+            "Remove",
+            "Removes a pose with the given name from the library.",
+            ctx,
+        );
+        let apply = make_button(
+            "Apply",
+            "Blends the pose with the current transforms of the bones. In preview mode of the \
+            animation editor, the preview pose is changed, otherwise the scene is changed.",
+            ctx,
+        );
+        let insert_keys = make_button(
+            "Insert Keys",
+            "Blends the pose with the current transforms of the bones and writes the result as \
+            keys of the selected animation at its current time.",
+            ctx,
+        );
+        let buttons = WrapPanelBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .with_child(capture)
+                .with_child(remove)
+                .with_child(apply)
+                .with_child(insert_keys),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
+        let poses_panel = WrapPanelBuilder::new(WidgetBuilder::new())
+            .with_orientation(Orientation::Horizontal)
+            .build(ctx);
+
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("PoseLibraryPanel")
+                .with_width(300.0)
+                .with_height(400.0),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Pose Library"))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(inspector)
+                    .with_child(buttons)
+                    .with_child(
+                        BorderBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(2)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child(
+                                    ScrollViewerBuilder::new(WidgetBuilder::new())
+                                        .with_content(poses_panel)
+                                        .build(ctx),
+                                ),
+                        )
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::auto())
+            .add_row(Row::auto())
+            .add_row(Row::stretch())
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            inspector,
+            capture,
+            remove,
+            apply,
+            insert_keys,
+            poses_panel,
+            pose_views: Default::default(),
+            settings,
+        }
+    }
+
+    fn sync_settings(&self, ui: &mut UserInterface) {
+        let ctx = ui
+            .node(self.inspector)
+            .cast::<Inspector>()
+            .unwrap()
+            .context()
+            .clone();
+
+        if let Err(sync_errors) = ctx.sync(&self.settings, ui, 0, true, Default::default()) {
+            for error in sync_errors {
+                Log::err(format!("Failed to sync property. Reason: {:?}", error))
+            }
+        }
+    }
+
+    pub fn sync_to_model(&mut self, ui: &mut UserInterface) {
+        let poses = match self.settings.library.as_ref() {
+            Some(library) if library.is_ok() => library.data_ref().poses.clone(),
+            _ => Vec::new(),
+        };
+
+        if poses.len() == self.pose_views.len()
+            && poses
+                .iter()
+                .zip(self.pose_views.iter())
+                .all(|(pose, (_, view_pose))| pose == view_pose)
+        {
+            return;
+        }
+
+        for (view, _) in self.pose_views.drain(..) {
+            ui.send_message(WidgetMessage::remove(view, MessageDirection::ToWidget));
+        }
+
+        for pose in poses {
+            let view = make_pose_view(&pose, &mut ui.build_ctx());
+            send_sync_message(
+                ui,
+                WidgetMessage::link(view, MessageDirection::ToWidget, self.poses_panel),
+            );
+            self.pose_views.push((view, pose));
+        }
+    }
+
+    /// Returns a handle of a node, that is used to search bones of a pose by their names.
+    fn pose_root(
+        selection: &Selection,
+        game_scene: &GameScene,
+        graph: &Graph,
+        animation_editor: &AnimationEditor,
+    ) -> Handle<Node> {
+        if let Some(first) = selection
+            .as_graph()
+            .and_then(|selection| selection.nodes.first())
+        {
+            return *first;
+        }
+
+        // Animation player is usually a child of a model root.
+        graph
+            .try_get(animation_editor.animation_player().into())
+            .map_or(game_scene.scene_content_root, |player| player.parent())
+    }
+
+    fn capture_bones(selection: &Selection, graph: &Graph) -> Vec<Handle<Node>> {
+        if let Some(selection) = selection.as_graph() {
+            selection.nodes.clone()
+        } else if let Some(selection) = selection.as_animation::<Node>() {
+            Self::animated_bones(selection, graph)
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn animated_bones(selection: &AnimationSelection<Node>, graph: &Graph) -> Vec<Handle<Node>> {
+        let Some(animation) = graph
+            .try_get_of_type::<AnimationPlayer>(selection.animation_player)
+            .and_then(|player| player.animations().try_get(selection.animation))
+        else {
+            return Vec::new();
+        };
+
+        let selected_tracks = selection.selected_tracks().collect::<Vec<_>>();
+        let mut bones = Vec::new();
+        for track in animation.tracks() {
+            if (selected_tracks.is_empty() || selected_tracks.contains(&track.id()))
+                && !bones.contains(&track.target())
+            {
+                bones.push(track.target());
+            }
+        }
+        bones
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        selection: &Selection,
+        game_scene: &GameScene,
+        engine: &mut Engine,
+        animation_editor: &mut AnimationEditor,
+        sender: &MessageSender,
+    ) {
+        scope_profile!();
+
+        if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
+            if message.destination() == self.inspector {
+                PropertyAction::from_field_kind(&property_changed.value).apply(
+                    &property_changed.path(),
+                    &mut self.settings,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+                self.sync_to_model(engine.user_interfaces.first_mut());
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if let Some((_, pose)) = self
+                .pose_views
+                .iter()
+                .find(|(view, _)| *view == message.destination())
+            {
+                self.settings.pose_name = pose.name.clone();
+                self.sync_settings(engine.user_interfaces.first_mut());
+                return;
+            }
+
+            if ![self.capture, self.remove, self.apply, self.insert_keys]
+                .contains(&message.destination())
+            {
+                return;
+            }
+
+            let Some(library) = self.settings.library.clone() else {
+                Log::warn("Select a pose library first!");
+                return;
+            };
+            if !library.is_ok() {
+                Log::warn("Pose library is not loaded!");
+                return;
+            }
+
+            let graph = &mut engine.scenes[game_scene.scene].graph;
+
+            if message.destination() == self.capture {
+                if self.settings.pose_name.is_empty() {
+                    Log::warn("A pose must have a name!");
+                    return;
+                }
+
+                let bones = Self::capture_bones(selection, graph);
+                if bones.is_empty() {
+                    Log::warn("Select bones or animation tracks to capture a pose!");
+                    return;
+                }
+
+                let mut new_library = library.data_ref().clone();
+                new_library.set(PoseSnapshot::capture(
+                    &self.settings.pose_name,
+                    graph,
+                    &bones,
+                ));
+                sender.do_command(SetPoseLibraryPosesCommand::new(library, new_library.poses));
+            } else if message.destination() == self.remove {
+                let mut new_library = library.data_ref().clone();
+                if new_library.remove(&self.settings.pose_name).is_some() {
+                    sender.do_command(SetPoseLibraryPosesCommand::new(library, new_library.poses));
+                }
+            } else {
+                let Some(pose) = library.data_ref().find(&self.settings.pose_name).cloned() else {
+                    Log::warn(format!(
+                        "There's no pose with {} name in the library!",
+                        self.settings.pose_name
+                    ));
+                    return;
+                };
+
+                let root = Self::pose_root(selection, game_scene, graph, animation_editor);
+                let weight = self.settings.blend / 100.0;
+
+                if message.destination() == self.apply {
+                    if animation_editor.is_in_preview_mode() {
+                        // Preview mode restores the nodes on exit, so there's no need for commands.
+                        pose.apply(graph, root, weight);
+                    } else {
+                        let commands = pose
+                            .blended_transforms(graph, root, weight)
+                            .into_iter()
+                            .map(|(handle, transform)| {
+                                let old_transform = graph[handle].local_transform().clone();
+                                let mut new_transform = old_transform.clone();
+                                new_transform
+                                    .set_position(transform.position)
+                                    .set_rotation(transform.rotation)
+                                    .set_scale(transform.scale);
+                                Command::new(SetNodeTransformCommand::new(
+                                    handle,
+                                    old_transform,
+                                    new_transform,
+                                ))
+                            })
+                            .collect::<Vec<_>>();
+                        if !commands.is_empty() {
+                            sender.do_command(
+                                CommandGroup::from(commands).with_custom_name("Apply Pose"),
+                            );
+                        }
+                    }
+                } else if message.destination() == self.insert_keys {
+                    let transforms = pose.blended_transforms(graph, root, weight);
+                    animation_editor.insert_transform_keys(&transforms, selection, graph, sender);
+                }
+            }
+        }
+    }
+}
@@ -73,7 +73,11 @@ where
     N: 'static,
 {
     pub fn first_selected_track(&self) -> Option<Uuid> {
-        self.entities.iter().find_map(|e| {
+        self.selected_tracks().next()
+    }
+
+    pub fn selected_tracks(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.entities.iter().filter_map(|e| {
             if let SelectedEntity::Track(id) = e {
                 Some(*id)
             } else {
@@ -1,8 +1,8 @@
 use crate::fyrox::{
     asset::manager::ResourceManager,
     core::{
-        algebra::Vector2, futures::executor::block_on, log::Log, math::Rect, pool::ErasedHandle,
-        pool::Handle,
+        algebra::Vector2, futures::executor::block_on, log::Log, make_relative_path, math::Rect,
+        pool::ErasedHandle, pool::Handle,
     },
     generic_animation::{Animation, AnimationContainer, RootMotionSettings},
     graph::{BaseSceneGraph, PrefabData, SceneGraph, SceneGraphNode},
@@ -33,9 +33,9 @@ use crate::{
     animation::{
         command::{
             AddAnimationCommand, RemoveAnimationCommand, ReplaceAnimationCommand,
-            SetAnimationEnabledCommand, SetAnimationLoopingCommand, SetAnimationNameCommand,
-            SetAnimationRootMotionSettingsCommand, SetAnimationSpeedCommand,
-            SetAnimationTimeSliceCommand,
+            SetAnimationAudioClipCommand, SetAnimationEnabledCommand, SetAnimationLoopingCommand,
+            SetAnimationNameCommand, SetAnimationRootMotionSettingsCommand,
+            SetAnimationSpeedCommand, SetAnimationTimeSliceCommand,
         },
         selection::AnimationSelection,
     },
@@ -80,6 +80,9 @@ pub struct Toolbar {
     pub enabled: Handle<UiNode>,
     root_motion_dropdown_area: RootMotionDropdownArea,
     pub root_motion: Handle<UiNode>,
+    pub audio_clip: Handle<UiNode>,
+    pub remove_audio_clip: Handle<UiNode>,
+    pub audio_clip_file_selector: Handle<UiNode>,
     import_mode: ImportMode,
 }
 
@@ -394,6 +397,8 @@ impl Toolbar {
         let looping;
         let enabled;
         let root_motion;
+        let audio_clip;
+        let remove_audio_clip;
         let panel = BorderBuilder::new(
             WidgetBuilder::new()
                 .on_row(0)
@@ -694,6 +699,37 @@ impl Toolbar {
                                     .build(ctx);
                                 root_motion
                             })
+                            .with_child({
+                                audio_clip = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Attach an audio clip to the animation. Its waveform \
+                                            will be shown behind the curves to help syncing keys \
+                                            with sound cues.",
+                                        )),
+                                )
+                                .with_text("Audio")
+                                .build(ctx);
+                                audio_clip
+                            })
+                            .with_child({
+                                remove_audio_clip = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_width(20.0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Detach the audio clip from the animation.",
+                                        )),
+                                )
+                                .with_content(make_cross(ctx, 12.0, 2.0))
+                                .build(ctx);
+                                remove_audio_clip
+                            })
                             .with_child({
                                 preview = CheckBoxBuilder::new(
                                     WidgetBuilder::new().with_enabled(false).with_margin(
@@ -803,6 +839,18 @@ impl Toolbar {
         }))
         .build(ctx);
 
+        let audio_clip_file_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::text("Select Audio Clip")),
+        )
+        .with_filter(Filter::new(|p: &Path| {
+            p.is_dir()
+                || p.extension()
+                    .is_some_and(|ext| matches!(ext.to_string_lossy().as_ref(), "wav" | "ogg"))
+        }))
+        .build(ctx);
+
         let root_motion_dropdown_area = RootMotionDropdownArea::new(ctx);
 
         Self {
@@ -827,6 +875,9 @@ impl Toolbar {
             looping,
             enabled,
             root_motion,
+            audio_clip,
+            remove_audio_clip,
+            audio_clip_file_selector,
             root_motion_dropdown_area,
             import_mode: ImportMode::Import,
         }
@@ -889,6 +940,24 @@ impl Toolbar {
                     self.root_motion_dropdown_area.popup,
                     MessageDirection::ToWidget,
                 ));
+            } else if message.destination() == self.audio_clip {
+                ui.send_message(WindowMessage::open_modal(
+                    self.audio_clip_file_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                    true,
+                ));
+                ui.send_message(FileSelectorMessage::root(
+                    self.audio_clip_file_selector,
+                    MessageDirection::ToWidget,
+                    Some(std::env::current_dir().unwrap()),
+                ));
+            } else if message.destination() == self.remove_audio_clip {
+                sender.do_command(SetAnimationAudioClipCommand {
+                    node_handle: animation_player_handle,
+                    animation_handle: selection.animation,
+                    value: None,
+                });
             } else if message.destination() == self.remove_current_animation {
                 if animations.try_get(selection.animation).is_some() {
                     let group = vec![
@@ -959,6 +1028,21 @@ impl Toolbar {
                     });
                 }
             }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.audio_clip_file_selector {
+                match make_relative_path(path) {
+                    Ok(path) => sender.do_command(SetAnimationAudioClipCommand {
+                        node_handle: animation_player_handle,
+                        animation_handle: selection.animation,
+                        value: Some(path),
+                    }),
+                    Err(err) => Log::err(format!(
+                        "Unable to attach {} audio clip. Reason: {:?}",
+                        path.display(),
+                        err
+                    )),
+                }
+            }
         } else if let Some(NumericUpDownMessage::<f32>::Value(value)) = message.data() {
             if message.direction() == MessageDirection::FromWidget {
                 if message.destination() == self.time_slice_start {
@@ -1254,6 +1338,7 @@ impl Toolbar {
             self.looping,
             self.enabled,
             self.root_motion,
+            self.audio_clip,
         ] {
             send_sync_message(
                 ui,
@@ -1275,5 +1360,16 @@ impl Toolbar {
                 ),
             );
         }
+
+        send_sync_message(
+            ui,
+            WidgetMessage::enabled(
+                self.remove_audio_clip,
+                MessageDirection::ToWidget,
+                animations
+                    .try_get(selection.animation)
+                    .is_some_and(|animation| animation.audio_clip().is_some()),
+            ),
+        );
     }
 }
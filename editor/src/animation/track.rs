@@ -75,6 +75,7 @@ use std::{
     any::TypeId,
     cmp::Ordering,
     collections::hash_map::Entry,
+    fmt::Debug,
     ops::{Deref, DerefMut},
     sync::{mpsc::Sender, Arc},
 };
@@ -95,6 +96,8 @@ struct TrackContextMenu {
     target_node_selector: Handle<UiNode>,
     property_rebinding_selector: Handle<UiNode>,
     duplicate: Handle<UiNode>,
+    enable: Handle<UiNode>,
+    disable: Handle<UiNode>,
 }
 
 impl TrackContextMenu {
@@ -103,6 +106,8 @@ impl TrackContextMenu {
         let set_target;
         let rebind;
         let duplicate;
+        let enable;
+        let disable;
         let menu = ContextMenuBuilder::new(
             PopupBuilder::new(WidgetBuilder::new().with_visibility(false)).with_content(
                 StackPanelBuilder::new(
@@ -122,6 +127,14 @@ impl TrackContextMenu {
                         .with_child({
                             duplicate = create_menu_item("Duplicate", vec![], ctx);
                             duplicate
+                        })
+                        .with_child({
+                            enable = create_menu_item("Enable Selected Tracks", vec![], ctx);
+                            enable
+                        })
+                        .with_child({
+                            disable = create_menu_item("Disable Selected Tracks", vec![], ctx);
+                            disable
                         }),
                 )
                 .build(ctx),
@@ -138,11 +151,15 @@ impl TrackContextMenu {
             target_node_selector: Default::default(),
             property_rebinding_selector: Default::default(),
             duplicate,
+            enable,
+            disable,
         }
     }
 }
 
-fn type_id_to_supported_type(property_type: TypeId) -> Option<(TrackValueKind, ValueType)> {
+pub(crate) fn type_id_to_supported_type(
+    property_type: TypeId,
+) -> Option<(TrackValueKind, ValueType)> {
     if property_type == TypeId::of::<f32>() {
         Some((TrackValueKind::Real, ValueType::F32))
     } else if property_type == TypeId::of::<f64>() {
@@ -240,6 +257,51 @@ fn type_id_to_supported_type(property_type: TypeId) -> Option<(TrackValueKind, V
     }
 }
 
+/// Splits a value of a property into components, one component per curve of a track. See
+/// [`TrackValueKind`] docs for the layout of components. Returns `None` if the property has
+/// unsupported type.
+pub(crate) fn property_value_components(property: &dyn Reflect) -> Option<Vec<f32>> {
+    let mut components = None;
+
+    property.as_any(&mut |any| {
+        macro_rules! try_numeric {
+            ($($ty:ty),*) => {
+                $(
+                    if let Some(value) = any.downcast_ref::<$ty>() {
+                        components = Some(vec![*value as f32]);
+                    } else if let Some(value) = any.downcast_ref::<Vector2<$ty>>() {
+                        components = Some(value.iter().map(|c| *c as f32).collect());
+                    } else if let Some(value) = any.downcast_ref::<Vector3<$ty>>() {
+                        components = Some(value.iter().map(|c| *c as f32).collect());
+                    } else if let Some(value) = any.downcast_ref::<Vector4<$ty>>() {
+                        components = Some(value.iter().map(|c| *c as f32).collect());
+                    }
+                )*
+            };
+        }
+
+        try_numeric!(f32, f64, u64, i64, u32, i32, u16, i16, u8, i8);
+
+        if let Some(value) = any.downcast_ref::<bool>() {
+            components = Some(vec![*value as u8 as f32]);
+        } else if let Some(value) = any.downcast_ref::<Vector2<bool>>() {
+            components = Some(value.iter().map(|c| *c as u8 as f32).collect());
+        } else if let Some(value) = any.downcast_ref::<Vector3<bool>>() {
+            components = Some(value.iter().map(|c| *c as u8 as f32).collect());
+        } else if let Some(value) = any.downcast_ref::<Vector4<bool>>() {
+            components = Some(value.iter().map(|c| *c as u8 as f32).collect());
+        } else if let Some(value) = any.downcast_ref::<UnitQuaternion<f32>>() {
+            let (x, y, z) = value.euler_angles();
+            components = Some(vec![x, y, z]);
+        } else if let Some(value) = any.downcast_ref::<UnitQuaternion<f64>>() {
+            let (x, y, z) = value.euler_angles();
+            components = Some(vec![x as f32, y as f32, z as f32]);
+        }
+    });
+
+    components
+}
+
 #[allow(clippy::enum_variant_names)] // GTFO
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TrackViewMessage {
@@ -1055,6 +1117,14 @@ impl TrackList {
 
                     sender.do_command(CommandGroup::from(commands));
                 }
+            } else if message.destination() == self.context_menu.enable
+                || message.destination() == self.context_menu.disable
+            {
+                Self::set_selected_tracks_enabled(
+                    selection,
+                    message.destination() == self.context_menu.enable,
+                    sender,
+                );
             }
         } else if let Some(TrackViewMessage::TrackEnabled(enabled)) = message.data() {
             if message.direction() == MessageDirection::FromWidget {
@@ -1063,7 +1133,14 @@ impl TrackList {
                         .node(message.destination())
                         .query_component::<TrackView>()
                     {
-                        if animation
+                        if selection.entities.len() > 1
+                            && selection
+                                .selected_tracks()
+                                .any(|id| id == track_view_ref.id)
+                        {
+                            // Toggling one of the selected tracks toggles the whole selection.
+                            Self::set_selected_tracks_enabled(selection, *enabled, sender);
+                        } else if animation
                             .tracks()
                             .iter()
                             .any(|t| t.id() == track_view_ref.id)
@@ -1081,7 +1158,35 @@ impl TrackList {
         }
     }
 
-    fn open_property_selector<G, N>(
+    fn set_selected_tracks_enabled<N>(
+        selection: &AnimationSelection<N>,
+        enabled: bool,
+        sender: &MessageSender,
+    ) where
+        N: Debug,
+    {
+        let commands = selection
+            .selected_tracks()
+            .map(|track| {
+                Command::new(SetTrackEnabledCommand {
+                    animation_player_handle: selection.animation_player,
+                    animation_handle: selection.animation,
+                    track,
+                    enabled,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if !commands.is_empty() {
+            sender.do_command(CommandGroup::from(commands).with_custom_name(if enabled {
+                "Enable Tracks"
+            } else {
+                "Disable Tracks"
+            }));
+        }
+    }
+
+    pub(crate) fn open_property_selector<G, N>(
         graph: &G,
         node: Handle<N>,
         ui: &mut UserInterface,
@@ -1170,46 +1275,48 @@ impl TrackList {
         G: SceneGraph<Node = N>,
         N: SceneGraphNode,
     {
-        let Some(first_selected_track) = selection.first_selected_track() else {
-            return;
-        };
-
-        let Some(track) = animation
-            .tracks()
-            .iter()
-            .find(|t| t.id() == first_selected_track)
-        else {
-            return;
-        };
-
-        let Some(node) = graph.try_get(track.target()) else {
-            Log::err("Invalid node handle!");
-            return;
-        };
-
-        node.resolve_path(&desc.path, &mut |result| match result {
-            Ok(property) => {
-                let mut property_type = TypeId::of::<u32>();
-                property.as_any(&mut |any| property_type = any.type_id());
-
-                let types = type_id_to_supported_type(property_type);
-
-                if let Some((_, actual_value_type)) = types {
-                    sender.do_command(SetTrackBindingCommand {
-                        animation_player_handle: selection.animation_player,
-                        animation_handle: selection.animation,
-                        track: first_selected_track,
-                        binding: ValueBinding::Property {
-                            name: desc.path.clone(),
-                            value_type: actual_value_type,
-                        },
-                    });
+        let mut commands = Vec::new();
+
+        // Every selected track is rebound to the same property of its own target, tracks which
+        // targets have no such property are left intact.
+        for track_id in selection.selected_tracks() {
+            let Some(track) = animation.tracks().iter().find(|t| t.id() == track_id) else {
+                continue;
+            };
+
+            let Some(node) = graph.try_get(track.target()) else {
+                Log::err("Invalid node handle!");
+                continue;
+            };
+
+            node.resolve_path(&desc.path, &mut |result| match result {
+                Ok(property) => {
+                    let mut property_type = TypeId::of::<u32>();
+                    property.as_any(&mut |any| property_type = any.type_id());
+
+                    let types = type_id_to_supported_type(property_type);
+
+                    if let Some((_, actual_value_type)) = types {
+                        commands.push(Command::new(SetTrackBindingCommand {
+                            animation_player_handle: selection.animation_player,
+                            animation_handle: selection.animation,
+                            track: track_id,
+                            binding: ValueBinding::Property {
+                                name: desc.path.clone(),
+                                value_type: actual_value_type,
+                            },
+                        }));
+                    }
                 }
-            }
-            Err(e) => {
-                Log::err(format!("Invalid property path {:?}. Error: {:?}!", desc, e));
-            }
-        })
+                Err(e) => {
+                    Log::err(format!("Invalid property path {:?}. Error: {:?}!", desc, e));
+                }
+            })
+        }
+
+        if !commands.is_empty() {
+            sender.do_command(CommandGroup::from(commands).with_custom_name("Rebind Tracks"));
+        }
     }
 
     pub fn clear(&mut self, ui: &UserInterface) {
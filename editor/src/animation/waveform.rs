@@ -0,0 +1,54 @@
+//! Waveform of an audio clip attached to an animation. It is shown behind the curves of the
+//! animation editor to help syncing keys with sound cues.
+
+use crate::fyrox::{
+    asset::manager::ResourceManager,
+    core::{color::Color, futures::executor::block_on, log::Log},
+    gui::{brush::Brush, curve::Waveform},
+    scene::sound::SoundBuffer,
+};
+use std::path::Path;
+
+/// Amount of peaks per one second of audio.
+const PEAKS_PER_SECOND: usize = 100;
+
+/// Loads an audio clip and calculates its waveform. Time of the first sample is zero.
+pub fn load_waveform(path: &Path, resource_manager: &ResourceManager) -> Option<Waveform> {
+    let buffer = match block_on(resource_manager.request::<SoundBuffer>(path)) {
+        Ok(buffer) => buffer,
+        Err(err) => {
+            Log::err(format!(
+                "Unable to load {} audio clip. Reason: {:?}",
+                path.display(),
+                err
+            ));
+            return None;
+        }
+    };
+
+    let data = buffer.data_ref();
+    let channel_count = data.channel_count().max(1);
+    let sample_rate = data.sample_rate();
+    if sample_rate == 0 {
+        return None;
+    }
+
+    // Each peak covers a block of frames, where a frame is a set of samples for every channel.
+    let frames_per_peak = (sample_rate / PEAKS_PER_SECOND).max(1);
+    let peaks = data
+        .samples()
+        .chunks(frames_per_peak * channel_count)
+        .map(|block| {
+            block
+                .iter()
+                .fold(0.0f32, |peak, sample| peak.max(sample.abs()))
+        })
+        .collect();
+
+    Some(Waveform {
+        start: 0.0,
+        step: frames_per_peak as f32 / sample_rate as f32,
+        peaks,
+        brush: Brush::Solid(Color::from_rgba(90, 130, 170, 140)),
+    })
+}
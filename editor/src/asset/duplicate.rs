@@ -0,0 +1,328 @@
+//! A tool that finds assets with identical content and allows to consolidate all references to
+//! a group of duplicates into a single asset of the group.
+
+use crate::{
+    asset::can_fix_references,
+    fyrox::{
+        core::{futures::executor::block_on, log::Log, make_relative_path, pool::Handle},
+        graph::BaseSceneGraph,
+        gui::{
+            button::{ButtonBuilder, ButtonMessage},
+            grid::{Column, GridBuilder, Row},
+            message::{MessageDirection, UiMessage},
+            scroll_viewer::ScrollViewerBuilder,
+            stack_panel::StackPanelBuilder,
+            text::{TextBuilder, TextMessage},
+            widget::{WidgetBuilder, WidgetMessage},
+            window::{WindowBuilder, WindowMessage, WindowTitle},
+            BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+            VerticalAlignment,
+        },
+    },
+    Engine,
+};
+use std::path::{Path, PathBuf};
+
+struct DuplicateEntry {
+    path: PathBuf,
+    keep: Handle<UiNode>,
+}
+
+struct DuplicateGroup {
+    entries: Vec<DuplicateEntry>,
+}
+
+pub struct DuplicateFinder {
+    pub window: Handle<UiNode>,
+    groups_panel: Handle<UiNode>,
+    status: Handle<UiNode>,
+    scan: Handle<UiNode>,
+    close: Handle<UiNode>,
+    groups: Vec<DuplicateGroup>,
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(100.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+fn referencers_text(path: &Path, engine: &Engine) -> String {
+    let Ok(resource) = block_on(engine.resource_manager.request_untyped(path)) else {
+        return "Unable to load".to_string();
+    };
+    match block_on(
+        engine
+            .resource_manager
+            .find_references(&resource, "./", |_| true),
+    ) {
+        Ok(references) if references.is_empty() => "Not referenced".to_string(),
+        Ok(references) => format!(
+            "Referenced by: {}",
+            references
+                .iter()
+                .map(|r| r.kind().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Err(err) => format!("Unable to find references: {err:?}"),
+    }
+}
+
+impl DuplicateFinder {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let groups_panel;
+        let status;
+        let scan;
+        let close;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(500.0).with_height(400.0))
+            .open(false)
+            .with_title(WindowTitle::text("Duplicate Assets"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            ScrollViewerBuilder::new(WidgetBuilder::new().on_row(0))
+                                .with_content({
+                                    groups_panel = StackPanelBuilder::new(
+                                        WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .build(ctx);
+                                    groups_panel
+                                })
+                                .build(ctx),
+                        )
+                        .with_child(
+                            GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(2.0))
+                                    .with_child({
+                                        status = TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_vertical_alignment(VerticalAlignment::Center)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Press Scan to find duplicates.")
+                                        .build(ctx);
+                                        status
+                                    })
+                                    .with_child(
+                                        StackPanelBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(1)
+                                                .with_horizontal_alignment(
+                                                    HorizontalAlignment::Right,
+                                                )
+                                                .with_child({
+                                                    scan = make_button(ctx, "Scan");
+                                                    scan
+                                                })
+                                                .with_child({
+                                                    close = make_button(ctx, "Close");
+                                                    close
+                                                }),
+                                        )
+                                        .with_orientation(Orientation::Horizontal)
+                                        .build(ctx),
+                                    ),
+                            )
+                            .add_row(Row::stretch())
+                            .add_column(Column::stretch())
+                            .add_column(Column::auto())
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::stretch())
+                .add_row(Row::strict(26.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            groups_panel,
+            status,
+            scan,
+            close,
+            groups: Default::default(),
+        }
+    }
+
+    pub fn open(&mut self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+            true,
+        ));
+    }
+
+    fn clear(&mut self, ui: &UserInterface) {
+        for &child in ui.node(self.groups_panel).children() {
+            ui.send_message(WidgetMessage::remove(child, MessageDirection::ToWidget));
+        }
+        self.groups.clear();
+    }
+
+    fn scan(&mut self, engine: &mut Engine) {
+        self.clear(engine.user_interfaces.first());
+
+        let duplicates = match block_on(engine.resource_manager.find_duplicates("./")) {
+            Ok(duplicates) => duplicates,
+            Err(err) => {
+                Log::err(format!("Unable to find duplicate assets. Reason: {err:?}"));
+                return;
+            }
+        };
+
+        let mut views = Vec::new();
+        for paths in duplicates {
+            let paths = paths
+                .into_iter()
+                .map(|path| make_relative_path(&path).unwrap_or(path))
+                .collect::<Vec<_>>();
+            let texts = paths
+                .iter()
+                .map(|path| referencers_text(path, engine))
+                .collect::<Vec<_>>();
+            views.push((paths, texts));
+        }
+
+        let ui = engine.user_interfaces.first_mut();
+        let status = if views.is_empty() {
+            "No duplicates found.".to_string()
+        } else {
+            format!("Found {} group(s) of identical assets.", views.len())
+        };
+        ui.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            status,
+        ));
+
+        let ctx = &mut ui.build_ctx();
+        let mut group_views = Vec::new();
+        for (paths, texts) in views {
+            let mut entries = Vec::new();
+            let mut rows = Vec::new();
+            for (path, text) in paths.into_iter().zip(texts) {
+                let keep = ButtonBuilder::new(
+                    WidgetBuilder::new()
+                        .on_column(1)
+                        .with_width(60.0)
+                        .with_margin(Thickness::uniform(1.0)),
+                )
+                .with_text("Keep")
+                .build(ctx);
+                rows.push(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .with_child(
+                                TextBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_vertical_alignment(VerticalAlignment::Center),
+                                )
+                                .with_text(format!("{}\n    {}", path.display(), text))
+                                .build(ctx),
+                            )
+                            .with_child(keep),
+                    )
+                    .add_row(Row::auto())
+                    .add_column(Column::stretch())
+                    .add_column(Column::auto())
+                    .build(ctx),
+                );
+                entries.push(DuplicateEntry { path, keep });
+            }
+
+            group_views.push(
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::top(4.0))
+                        .with_children(rows),
+                )
+                .build(ctx),
+            );
+            self.groups.push(DuplicateGroup { entries });
+        }
+
+        for group_view in group_views {
+            ui.send_message(WidgetMessage::link(
+                group_view,
+                MessageDirection::ToWidget,
+                self.groups_panel,
+            ));
+        }
+    }
+
+    fn consolidate(&mut self, group_index: usize, entry_index: usize, engine: &mut Engine) {
+        let group = &self.groups[group_index];
+        let canonical_path = &group.entries[entry_index].path;
+        let Ok(canonical) = block_on(engine.resource_manager.request_untyped(canonical_path))
+        else {
+            Log::err(format!("Unable to load {}", canonical_path.display()));
+            return;
+        };
+        let duplicates = group
+            .entries
+            .iter()
+            .filter(|entry| entry.path != *canonical_path)
+            .filter_map(|entry| block_on(engine.resource_manager.request_untyped(&entry.path)).ok())
+            .collect::<Vec<_>>();
+
+        match block_on(engine.resource_manager.consolidate_resources(
+            &canonical,
+            &duplicates,
+            "./",
+            can_fix_references,
+        )) {
+            Ok(()) => Log::info(format!(
+                "All references to the duplicates of {} were consolidated.",
+                canonical_path.display()
+            )),
+            Err(err) => Log::err(format!(
+                "Unable to consolidate duplicates of {}. Reason: {err:?}",
+                canonical_path.display()
+            )),
+        }
+
+        self.scan(engine);
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.close {
+                engine
+                    .user_interfaces
+                    .first()
+                    .send_message(WindowMessage::close(
+                        self.window,
+                        MessageDirection::ToWidget,
+                    ));
+            } else if message.destination() == self.scan {
+                self.scan(engine);
+            } else {
+                let position = self.groups.iter().enumerate().find_map(|(i, group)| {
+                    group
+                        .entries
+                        .iter()
+                        .position(|entry| entry.keep == message.destination())
+                        .map(|j| (i, j))
+                });
+                if let Some((group_index, entry_index)) = position {
+                    self.consolidate(group_index, entry_index, engine);
+                }
+            }
+        } else if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                self.clear(engine.user_interfaces.first());
+            }
+        }
+    }
+}
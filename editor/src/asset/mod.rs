@@ -2,9 +2,11 @@ use crate::asset::preview::cache::{AssetPreviewCache, IconRequest};
 use crate::{
     asset::{
         dependency::DependencyViewer,
+        duplicate::DuplicateFinder,
         inspector::AssetInspector,
         item::{AssetItem, AssetItemBuilder, AssetItemMessage},
         preview::AssetPreviewGeneratorsCollection,
+        registry::AssetTypeRegistry,
     },
     fyrox::{
         asset::{
@@ -42,18 +44,20 @@ use crate::{
             UserInterface, VerticalAlignment, BRUSH_DARK,
         },
         material::Material,
-        resource::{model::Model, texture::Texture},
+        resource::{curve::CurveResourceState, model::Model, texture::Texture},
         scene::sound::SoundBuffer,
         walkdir,
     },
     gui::make_dropdown_list_option,
     message::MessageSender,
     preview::PreviewPanel,
-    utils::window_content,
+    utils::{rename::BatchRenameTarget, window_content},
     Message, Mode,
 };
 use fyrox::core::Uuid;
-use fyrox::scene::tilemap::tileset::TileSet;
+use fyrox::material::shader::graph::ShaderGraph;
+use fyrox::scene::animation::sequencer::sequence::Sequence;
+use fyrox::scene::animation::sprite::sheet::SpriteSheet;
 use std::sync::mpsc;
 use std::sync::mpsc::Sender;
 use std::{
@@ -64,9 +68,11 @@ use std::{
 };
 
 mod dependency;
+mod duplicate;
 mod inspector;
 pub mod item;
 pub mod preview;
+pub mod registry;
 
 struct ContextMenu {
     menu: RcUiNodeHandle,
@@ -77,6 +83,7 @@ struct ContextMenu {
     delete: Handle<UiNode>,
     placement_target: Handle<UiNode>,
     dependencies: Handle<UiNode>,
+    batch_rename: Handle<UiNode>,
 }
 
 fn execute_command(command: &mut Command) {
@@ -101,6 +108,55 @@ fn open_in_explorer<P: AsRef<Path>>(path: P) {
     }
 }
 
+/// Loads an asset at the given path, but only if there's a custom editor for the asset type.
+fn request_asset_with_editor(
+    path: &Path,
+    resource_manager: &ResourceManager,
+    asset_types: &AssetTypeRegistry,
+) -> Option<UntypedResource> {
+    let ext = path.extension()?.to_string_lossy();
+    let data_type_uuid = resource_manager
+        .state()
+        .loaders
+        .iter()
+        .find(|loader| loader.supports_extension(&ext))
+        .map(|loader| loader.data_type_uuid())?;
+    if !asset_types.has_editor(&data_type_uuid) {
+        return None;
+    }
+    let path = make_relative_path(path).ok()?;
+    block_on(resource_manager.request_untyped(path)).ok()
+}
+
+/// Returns `true` if the references in the given resource could be fixed when other resources are
+/// moved, see [`ResourceManager::move_resource`].
+pub fn can_fix_references(res: &UntypedResource) -> bool {
+    if [Texture::type_uuid(), SoundBuffer::type_uuid()].contains(&res.type_uuid()) {
+        return false;
+    };
+
+    // The engine cannot write FBX resources, so we must filter out these and warn the user
+    // that resource references cannot be automatically fixed.
+    if let Some(model) = res.try_cast::<Model>() {
+        let kind = model.kind();
+        if let Some(ext) = kind.path().and_then(|path| {
+            path.extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase())
+        }) {
+            if ext == "fbx" {
+                Log::warn(format!(
+                    "Resource {} cannot be scanned for \
+                    references, because FBX cannot be exported.",
+                    kind
+                ));
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
 fn put_path_to_clipboard(engine: &mut Engine, path: &OsStr) {
     if let Some(mut clipboard) = engine.user_interfaces.first_mut().clipboard_mut() {
         Log::verify(clipboard.set_contents(path.to_string_lossy().to_string()));
@@ -115,6 +171,7 @@ impl ContextMenu {
         let copy_path;
         let copy_file_name;
         let dependencies;
+        let batch_rename;
         let menu = ContextMenuBuilder::new(
             PopupBuilder::new(WidgetBuilder::new()).with_content(
                 StackPanelBuilder::new(
@@ -154,6 +211,12 @@ impl ContextMenu {
                                 .with_content(MenuItemContent::text("Dependencies"))
                                 .build(ctx);
                             dependencies
+                        })
+                        .with_child({
+                            batch_rename = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Batch Rename..."))
+                                .build(ctx);
+                            batch_rename
                         }),
                 )
                 .build(ctx),
@@ -171,6 +234,7 @@ impl ContextMenu {
             placement_target: Default::default(),
             copy_file_name,
             dependencies,
+            batch_rename,
         }
     }
 
@@ -179,6 +243,7 @@ impl ContextMenu {
         message: &UiMessage,
         sender: &MessageSender,
         engine: &mut Engine,
+        asset_types: &AssetTypeRegistry,
     ) {
         if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
             if message.destination() == self.menu.handle() {
@@ -212,14 +277,54 @@ impl ContextMenu {
                                 sender.send(Message::OpenMaterialEditor(material));
                             }
                         }
-                    } else if item.path.extension().map_or(false, |ext| ext == "tileset") {
+                    } else if item
+                        .path
+                        .extension()
+                        .is_some_and(|ext| ext == "spritesheet")
+                    {
+                        if let Ok(path) = make_relative_path(&item.path) {
+                            if let Ok(sprite_sheet) =
+                                block_on(engine.resource_manager.request::<SpriteSheet>(path))
+                            {
+                                sender.send(Message::OpenSpriteSheetEditor(sprite_sheet));
+                            }
+                        }
+                    } else if item.path.extension().is_some_and(|ext| ext == "sequence") {
+                        if let Ok(path) = make_relative_path(&item.path) {
+                            if let Ok(sequence) =
+                                block_on(engine.resource_manager.request::<Sequence>(path))
+                            {
+                                sender.send(Message::OpenSequencer(sequence));
+                            }
+                        }
+                    } else if item
+                        .path
+                        .extension()
+                        .is_some_and(|ext| ext == "shadergraph")
+                    {
                         if let Ok(path) = make_relative_path(&item.path) {
-                            if let Ok(tile_set) =
-                                block_on(engine.resource_manager.request::<TileSet>(path))
+                            if let Ok(graph) =
+                                block_on(engine.resource_manager.request::<ShaderGraph>(path))
                             {
-                                sender.send(Message::OpenTileSetEditor(tile_set));
+                                sender.send(Message::OpenShaderGraphEditor(graph));
                             }
                         }
+                    } else if item
+                        .path
+                        .extension()
+                        .is_some_and(|ext| ext == "crv" || ext == "curve")
+                    {
+                        if let Ok(path) = make_relative_path(&item.path) {
+                            if let Ok(curve) = block_on(
+                                engine.resource_manager.request::<CurveResourceState>(path),
+                            ) {
+                                sender.send(Message::OpenCurveEditor(curve));
+                            }
+                        }
+                    } else if let Some(resource) =
+                        request_asset_with_editor(&item.path, &engine.resource_manager, asset_types)
+                    {
+                        sender.send(Message::OpenAssetEditor(resource));
                     } else {
                         open_in_explorer(&item.path)
                     }
@@ -231,6 +336,27 @@ impl ContextMenu {
                     if let Some(file_name) = item.path.clone().file_name() {
                         put_path_to_clipboard(engine, file_name)
                     }
+                } else if message.destination() == self.batch_rename {
+                    // Every asset of the current folder is renamed.
+                    let panel = item.parent();
+                    let ui = engine.user_interfaces.first();
+                    let paths = ui
+                        .try_get(panel)
+                        .map(|panel| {
+                            panel
+                                .children()
+                                .iter()
+                                .filter_map(|child| ui.try_get(*child)?.cast::<AssetItem>())
+                                .filter(|item| item.path.is_file())
+                                .map(|item| item.path.clone())
+                                .collect::<Vec<_>>()
+                        })
+                        .unwrap_or_default();
+                    if !paths.is_empty() {
+                        sender.send(Message::OpenBatchRenameDialog(BatchRenameTarget::Assets(
+                            paths,
+                        )));
+                    }
                 }
             }
         }
@@ -474,6 +600,7 @@ pub struct AssetBrowser {
     scroll_panel: Handle<UiNode>,
     search_bar: Handle<UiNode>,
     add_resource: Handle<UiNode>,
+    find_duplicates: Handle<UiNode>,
     preview: PreviewPanel,
     items: Vec<Handle<UiNode>>,
     item_to_select: Option<PathBuf>,
@@ -481,13 +608,15 @@ pub struct AssetBrowser {
     context_menu: ContextMenu,
     selected_path: PathBuf,
     dependency_viewer: DependencyViewer,
+    duplicate_finder: DuplicateFinder,
     resource_creator: Option<ResourceCreator>,
     preview_cache: AssetPreviewCache,
     preview_sender: Sender<IconRequest>,
     pub preview_generators: AssetPreviewGeneratorsCollection,
+    pub asset_types: AssetTypeRegistry,
 }
 
-fn is_supported_resource(ext: &OsStr, resource_manager: &ResourceManager) -> bool {
+pub(crate) fn is_supported_resource(ext: &OsStr, resource_manager: &ResourceManager) -> bool {
     resource_manager
         .state()
         .loaders
@@ -507,6 +636,7 @@ impl AssetBrowser {
         let search_bar;
         let scroll_panel;
         let add_resource;
+        let find_duplicates;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("AssetBrowser"))
             .can_minimize(false)
             .with_title(WindowTitle::text("Asset Browser"))
@@ -564,10 +694,29 @@ impl AssetBrowser {
                                                     )
                                                     .build(ctx);
                                                     search_bar
+                                                })
+                                                .with_child({
+                                                    find_duplicates = ButtonBuilder::new(
+                                                        WidgetBuilder::new()
+                                                            .on_column(2)
+                                                            .with_tab_index(Some(3))
+                                                            .with_height(20.0)
+                                                            .with_margin(Thickness::uniform(1.0))
+                                                            .with_tooltip(make_simple_tooltip(
+                                                                ctx,
+                                                                "Find assets with identical \
+                                                                content and consolidate \
+                                                                references to them",
+                                                            )),
+                                                    )
+                                                    .with_text("Duplicates...")
+                                                    .build(ctx);
+                                                    find_duplicates
                                                 }),
                                         )
                                         .add_column(Column::auto())
                                         .add_column(Column::stretch())
+                                        .add_column(Column::auto())
                                         .add_row(Row::auto())
                                         .build(ctx),
                                     )
@@ -630,10 +779,13 @@ impl AssetBrowser {
 
         let dependency_viewer = DependencyViewer::new(ctx);
 
+        let duplicate_finder = DuplicateFinder::new(ctx);
+
         let (preview_sender, preview_receiver) = mpsc::channel();
 
         Self {
             dependency_viewer,
+            duplicate_finder,
             window,
             content_panel,
             folder_browser,
@@ -646,10 +798,12 @@ impl AssetBrowser {
             context_menu,
             selected_path: Default::default(),
             add_resource,
+            find_duplicates,
             resource_creator: None,
             preview_cache: AssetPreviewCache::new(preview_receiver, 4),
             preview_sender,
             preview_generators: AssetPreviewGeneratorsCollection::new(),
+            asset_types: Default::default(),
         }
     }
 
@@ -810,9 +964,10 @@ impl AssetBrowser {
         self.inspector.handle_ui_message(message, engine);
         self.preview.handle_message(message, engine);
         self.context_menu
-            .handle_ui_message(message, &sender, engine);
+            .handle_ui_message(message, &sender, engine, &self.asset_types);
         self.dependency_viewer
             .handle_ui_message(message, engine.user_interfaces.first_mut());
+        self.duplicate_finder.handle_ui_message(message, engine);
         if let Some(resource_creator) = self.resource_creator.as_mut() {
             let asset_added = resource_creator.handle_ui_message(
                 message,
@@ -989,6 +1144,8 @@ impl AssetBrowser {
                 resource_creator.open(engine.user_interfaces.first());
 
                 self.resource_creator = Some(resource_creator);
+            } else if message.destination() == self.find_duplicates {
+                self.duplicate_finder.open(engine.user_interfaces.first());
             }
         }
     }
@@ -1025,33 +1182,6 @@ impl AssetBrowser {
         ui: &mut UserInterface,
         resource_manager: &ResourceManager,
     ) {
-        fn filter(res: &UntypedResource) -> bool {
-            if [Texture::type_uuid(), SoundBuffer::type_uuid()].contains(&res.type_uuid()) {
-                return false;
-            };
-
-            // The engine cannot write FBX resources, so we must filter out these and warn the user
-            // that resource references cannot be automatically fixed.
-            if let Some(model) = res.try_cast::<Model>() {
-                let kind = model.kind();
-                if let Some(ext) = kind.path().and_then(|path| {
-                    path.extension()
-                        .map(|ext| ext.to_string_lossy().to_lowercase())
-                }) {
-                    if ext == "fbx" {
-                        Log::warn(format!(
-                            "Resource {} cannot be scanned for \
-                        references, because FBX cannot be exported.",
-                            kind
-                        ));
-                        return false;
-                    }
-                }
-            }
-
-            true
-        }
-
         if let Some(item) = ui.try_get(dropped).and_then(|n| n.cast::<AssetItem>()) {
             if let Ok(relative_path) = make_relative_path(target_dir) {
                 if let Ok(resource) = block_on(resource_manager.request_untyped(&item.path)) {
@@ -1062,7 +1192,7 @@ impl AssetBrowser {
                                 resource,
                                 new_full_path,
                                 "./",
-                                filter,
+                                can_fix_references,
                             )));
 
                             self.refresh(ui, resource_manager);
@@ -1109,7 +1239,7 @@ impl AssetBrowser {
                                                         resource,
                                                         new_full_path,
                                                         "./",
-                                                        filter,
+                                                        can_fix_references,
                                                     ),
                                                 ));
                                             }
@@ -0,0 +1,193 @@
+//! Asset type registry allows editor plugins to add new asset types to the editor. See
+//! [`AssetTypeDefinition`] docs for more info.
+
+use crate::{
+    asset::preview::AssetPreviewGenerator,
+    fyrox::{
+        asset::{
+            constructor::ResourceDataConstructor,
+            loader::{ResourceLoader, ResourceLoadersContainer},
+            untyped::UntypedResource,
+            Resource, ResourceData, TypedResourceData,
+        },
+        core::{uuid::Uuid, TypeUuidProvider},
+        fxhash::FxHashMap,
+    },
+    Editor,
+};
+use std::{marker::PhantomData, rc::Rc};
+
+/// A callback, that opens an editor for a resource.
+pub type AssetEditorCallback = Rc<dyn Fn(UntypedResource, &mut Editor)>;
+
+/// A callback, that registers a resource loader in the given container.
+pub type AssetLoaderRegistrar = Box<dyn FnOnce(&mut ResourceLoadersContainer)>;
+
+/// Asset type definition describes how the editor should work with a custom asset type. It
+/// consists of a few optional parts:
+///
+/// - Loader - a resource loader, that will be registered in the resource manager of the engine. It
+///   is not needed, if the engine already knows how to load the asset.
+/// - Preview generator - generates previews of the asset for the asset browser.
+/// - Template - creates new instances of the asset, that are used in the resource creator of the
+///   asset browser (`Add Resource` button).
+/// - Editor - opens an editor for the asset, when a user opens it in the asset browser.
+///
+/// Use [`Editor::register_asset_type`] to register the definition. Usually it is done in
+/// [`crate::plugin::EditorPlugin::on_start`]:
+///
+/// ```rust
+/// # use fyroxed_base::{
+/// #     asset::registry::AssetTypeDefinition,
+/// #     fyrox::{
+/// #         asset::{Resource, ResourceData},
+/// #         core::{reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*},
+/// #     },
+/// #     plugin::EditorPlugin,
+/// #     Editor,
+/// # };
+/// # use std::{any::Any, error::Error, path::Path};
+/// #
+/// #[derive(Default, Debug, Clone, Visit, Reflect, TypeUuidProvider)]
+/// #[type_uuid(id = "f1b3a4a2-7e46-4e83-a55a-b7b2d6c3d1a1")]
+/// struct MyAsset {
+///     value: f32,
+/// }
+///
+/// impl ResourceData for MyAsset {
+///     fn as_any(&self) -> &dyn Any {
+///         self
+///     }
+///
+///     fn as_any_mut(&mut self) -> &mut dyn Any {
+///         self
+///     }
+///
+///     fn type_uuid(&self) -> Uuid {
+///         <Self as TypeUuidProvider>::type_uuid()
+///     }
+///
+///     fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+///         let mut visitor = Visitor::new();
+///         self.visit("MyAsset", &mut visitor)?;
+///         visitor.save_binary(path)?;
+///         Ok(())
+///     }
+///
+///     fn can_be_saved(&self) -> bool {
+///         true
+///     }
+/// }
+///
+/// struct MyPlugin;
+///
+/// impl EditorPlugin for MyPlugin {
+///     fn on_start(&mut self, editor: &mut Editor) {
+///         editor.register_asset_type(
+///             AssetTypeDefinition::<MyAsset>::new()
+///                 .with_template("My Asset", || MyAsset { value: 1.0 })
+///                 .with_editor(|resource: Resource<MyAsset>, _editor: &mut Editor| {
+///                     // Open an editor window for the asset.
+///                 }),
+///         );
+///     }
+/// }
+/// ```
+pub struct AssetTypeDefinition<T: TypedResourceData> {
+    pub(crate) loader: Option<AssetLoaderRegistrar>,
+    pub(crate) preview_generator: Option<Box<dyn AssetPreviewGenerator>>,
+    pub(crate) template: Option<ResourceDataConstructor>,
+    pub(crate) editor: Option<AssetEditorCallback>,
+    phantom: PhantomData<T>,
+}
+
+impl<T: TypedResourceData> Default for AssetTypeDefinition<T> {
+    fn default() -> Self {
+        Self {
+            loader: None,
+            preview_generator: None,
+            template: None,
+            editor: None,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: TypedResourceData> AssetTypeDefinition<T> {
+    /// Creates a new empty asset type definition.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a resource loader, that will be registered in the resource manager.
+    pub fn with_loader<L: ResourceLoader>(mut self, loader: L) -> Self {
+        self.loader = Some(Box::new(move |loaders| {
+            loaders.set(loader);
+        }));
+        self
+    }
+
+    /// Sets a preview generator, that will be used by the asset browser.
+    pub fn with_preview_generator<G: AssetPreviewGenerator>(mut self, generator: G) -> Self {
+        self.preview_generator = Some(Box::new(generator));
+        self
+    }
+
+    /// Sets a template, that will be used to create new instances of the asset. The name is shown
+    /// in the resource creator of the asset browser.
+    pub fn with_template<F>(mut self, name: &str, mut template: F) -> Self
+    where
+        F: FnMut() -> T + Send + 'static,
+    {
+        self.template = Some(ResourceDataConstructor {
+            type_name: name.to_string(),
+            callback: Box::new(move || Box::new(template()) as Box<dyn ResourceData>),
+        });
+        self
+    }
+
+    /// Sets a callback, that opens an editor for the asset.
+    pub fn with_editor<F>(mut self, editor: F) -> Self
+    where
+        F: Fn(Resource<T>, &mut Editor) + 'static,
+    {
+        self.editor = Some(Rc::new(move |resource: UntypedResource, e: &mut Editor| {
+            if let Some(resource) = resource.try_cast::<T>() {
+                editor(resource, e)
+            }
+        }));
+        self
+    }
+
+    /// Returns a unique id of the asset type.
+    pub fn type_uuid(&self) -> Uuid {
+        <T as TypeUuidProvider>::type_uuid()
+    }
+}
+
+/// A set of custom asset editors, that were registered using [`Editor::register_asset_type`].
+#[derive(Default)]
+pub struct AssetTypeRegistry {
+    editors: FxHashMap<Uuid, AssetEditorCallback>,
+}
+
+impl AssetTypeRegistry {
+    /// Registers an editor for assets of the given type. Returns the previous editor (if any).
+    pub fn add_editor(
+        &mut self,
+        type_uuid: Uuid,
+        editor: AssetEditorCallback,
+    ) -> Option<AssetEditorCallback> {
+        self.editors.insert(type_uuid, editor)
+    }
+
+    /// Tries to find an editor for assets of the given type.
+    pub fn editor(&self, type_uuid: &Uuid) -> Option<AssetEditorCallback> {
+        self.editors.get(type_uuid).cloned()
+    }
+
+    /// Checks whether there's an editor for assets of the given type.
+    pub fn has_editor(&self, type_uuid: &Uuid) -> bool {
+        self.editors.contains_key(type_uuid)
+    }
+}
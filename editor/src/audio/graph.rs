@@ -0,0 +1,363 @@
+//! Audio bus graph window shows the hierarchy of audio buses of a scene together with their effect
+//! chains and sends, and allows to route the samples of audio buses to other buses. Effects of an
+//! audio bus could be edited in the inspector, the bus could be selected by clicking its name.
+
+use crate::{
+    audio::AudioBusSelection,
+    fyrox::{
+        core::pool::Handle,
+        gui::{
+            button::{ButtonBuilder, ButtonMessage},
+            dropdown_list::{DropdownListBuilder, DropdownListMessage},
+            grid::{Column, GridBuilder, Row},
+            message::{MessageDirection, UiMessage},
+            numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+            scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
+            stack_panel::StackPanelBuilder,
+            text::TextBuilder,
+            widget::WidgetBuilder,
+            window::{WindowBuilder, WindowTitle},
+            BuildContext, Thickness, UiNode, UserInterface, VerticalAlignment,
+        },
+        scene::sound::{AudioBus, AudioBusGraph, AudioBusSend},
+    },
+    gui::make_dropdown_list_option,
+    message::MessageSender,
+    scene::{
+        commands::effect::{
+            AddAudioBusSendCommand, RemoveAudioBusSendCommand, SetAudioBusSendCommand,
+        },
+        GameScene, Selection,
+    },
+    send_sync_message, ChangeSelectionCommand, Engine,
+};
+
+/// Everything that defines the layout of the window. Gains of sends are not included, they're
+/// synchronized without rebuilding the content, so the numeric fields won't lose focus while editing.
+#[derive(PartialEq)]
+struct BusLayout {
+    bus: Handle<AudioBus>,
+    depth: usize,
+    name: String,
+    effects: Vec<String>,
+    send_targets: Vec<String>,
+}
+
+struct BusView {
+    bus: Handle<AudioBus>,
+    select: Handle<UiNode>,
+    add_send: Handle<UiNode>,
+}
+
+struct SendView {
+    bus: Handle<AudioBus>,
+    index: usize,
+    targets: Vec<String>,
+    target: Handle<UiNode>,
+    gain: Handle<UiNode>,
+    remove: Handle<UiNode>,
+}
+
+pub struct AudioBusGraphWindow {
+    pub window: Handle<UiNode>,
+    scroll_viewer: Handle<UiNode>,
+    layout: Vec<BusLayout>,
+    bus_views: Vec<BusView>,
+    send_views: Vec<SendView>,
+}
+
+fn collect_layout(graph: &AudioBusGraph) -> Vec<BusLayout> {
+    let mut layout = Vec::new();
+    let mut stack = vec![(graph.primary_bus_handle(), 0)];
+    while let Some((handle, depth)) = stack.pop() {
+        let Some(bus) = graph.try_get_bus_ref(handle) else {
+            continue;
+        };
+        layout.push(BusLayout {
+            bus: handle,
+            depth,
+            name: bus.name().to_owned(),
+            effects: bus
+                .effects()
+                .map(|e| AsRef::<str>::as_ref(e).to_owned())
+                .collect(),
+            send_targets: bus.sends().iter().map(|send| send.bus.clone()).collect(),
+        });
+        stack.extend(bus.children().iter().rev().map(|child| (*child, depth + 1)));
+    }
+    layout
+}
+
+fn make_text(ctx: &mut BuildContext, text: &str, column: usize) -> Handle<UiNode> {
+    TextBuilder::new(
+        WidgetBuilder::new()
+            .on_column(column)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .build(ctx)
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str, column: usize) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .on_column(column)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl AudioBusGraphWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let scroll_viewer =
+            ScrollViewerBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+                .build(ctx);
+
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("AudioBusGraph")
+                .with_width(450.0)
+                .with_height(350.0),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Audio Bus Graph"))
+        .with_content(scroll_viewer)
+        .build(ctx);
+
+        Self {
+            window,
+            scroll_viewer,
+            layout: Default::default(),
+            bus_views: Default::default(),
+            send_views: Default::default(),
+        }
+    }
+
+    fn rebuild(&mut self, layout: Vec<BusLayout>, ui: &mut UserInterface) {
+        self.bus_views.clear();
+        self.send_views.clear();
+
+        let ctx = &mut ui.build_ctx();
+        let mut rows = Vec::new();
+        for bus_layout in layout.iter() {
+            let select = make_button(ctx, &bus_layout.name, 0);
+            let add_send = make_button(ctx, "Add Send", 2);
+            let effects = if bus_layout.effects.is_empty() {
+                "No Effects".to_string()
+            } else {
+                bus_layout.effects.join(" > ")
+            };
+            rows.push(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::left(bus_layout.depth as f32 * 16.0))
+                        .with_child(select)
+                        .with_child(make_text(ctx, &effects, 1))
+                        .with_child(add_send),
+                )
+                .add_row(Row::strict(24.0))
+                .add_column(Column::strict(120.0))
+                .add_column(Column::stretch())
+                .add_column(Column::strict(80.0))
+                .build(ctx),
+            );
+            self.bus_views.push(BusView {
+                bus: bus_layout.bus,
+                select,
+                add_send,
+            });
+
+            // A bus cannot send its samples to itself.
+            let targets = layout
+                .iter()
+                .filter(|other| other.bus != bus_layout.bus)
+                .map(|other| other.name.clone())
+                .collect::<Vec<_>>();
+
+            for (index, send_target) in bus_layout.send_targets.iter().enumerate() {
+                let target = DropdownListBuilder::new(
+                    WidgetBuilder::new()
+                        .on_column(1)
+                        .with_margin(Thickness::uniform(1.0)),
+                )
+                .with_items(
+                    targets
+                        .iter()
+                        .map(|name| make_dropdown_list_option(ctx, name))
+                        .collect(),
+                )
+                .with_opt_selected(targets.iter().position(|name| name == send_target))
+                .build(ctx);
+                let gain = NumericUpDownBuilder::<f32>::new(
+                    WidgetBuilder::new()
+                        .on_column(2)
+                        .with_margin(Thickness::uniform(1.0)),
+                )
+                .with_min_value(0.0)
+                .with_step(0.05)
+                .build(ctx);
+                let remove = make_button(ctx, "Remove", 3);
+                rows.push(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::left(bus_layout.depth as f32 * 16.0 + 16.0))
+                            .with_child(make_text(ctx, "Send To", 0))
+                            .with_child(target)
+                            .with_child(gain)
+                            .with_child(remove),
+                    )
+                    .add_row(Row::strict(24.0))
+                    .add_column(Column::strict(60.0))
+                    .add_column(Column::stretch())
+                    .add_column(Column::strict(70.0))
+                    .add_column(Column::strict(60.0))
+                    .build(ctx),
+                );
+                self.send_views.push(SendView {
+                    bus: bus_layout.bus,
+                    index,
+                    targets: targets.clone(),
+                    target,
+                    gain,
+                    remove,
+                });
+            }
+        }
+
+        let content = StackPanelBuilder::new(WidgetBuilder::new().with_children(rows)).build(ctx);
+        send_sync_message(
+            ui,
+            ScrollViewerMessage::content(self.scroll_viewer, MessageDirection::ToWidget, content),
+        );
+
+        self.layout = layout;
+    }
+
+    pub fn sync_to_model(&mut self, game_scene: &GameScene, engine: &mut Engine) {
+        let state = engine.scenes[game_scene.scene].graph.sound_context.state();
+        let ui = engine.user_interfaces.first_mut();
+
+        let graph = state.bus_graph_ref();
+        let layout = collect_layout(graph);
+        if layout != self.layout {
+            self.rebuild(layout, ui);
+        }
+
+        for send_view in self.send_views.iter() {
+            if let Some(send) = graph
+                .try_get_bus_ref(send_view.bus)
+                .and_then(|bus| bus.sends().get(send_view.index))
+            {
+                send_sync_message(
+                    ui,
+                    NumericUpDownMessage::value(
+                        send_view.gain,
+                        MessageDirection::ToWidget,
+                        send.gain,
+                    ),
+                );
+            }
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        game_scene: &GameScene,
+        engine: &Engine,
+        sender: &MessageSender,
+    ) {
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        let state = engine.scenes[game_scene.scene].graph.sound_context.state();
+        let graph = state.bus_graph_ref();
+        let current_send = |send_view: &SendView| {
+            graph
+                .try_get_bus_ref(send_view.bus)
+                .and_then(|bus| bus.sends().get(send_view.index))
+                .cloned()
+        };
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            if let Some(bus_view) = self
+                .bus_views
+                .iter()
+                .find(|v| v.select == message.destination())
+            {
+                sender.do_command(ChangeSelectionCommand::new(Selection::new(
+                    AudioBusSelection {
+                        buses: vec![bus_view.bus],
+                    },
+                )));
+            } else if let Some(bus_view) = self
+                .bus_views
+                .iter()
+                .find(|v| v.add_send == message.destination())
+            {
+                // Send to the first bus other than the bus itself, the target can be changed later.
+                let target = self
+                    .layout
+                    .iter()
+                    .find(|layout| layout.bus != bus_view.bus)
+                    .map(|layout| layout.name.clone());
+                if let Some(target) = target {
+                    sender.do_command(AddAudioBusSendCommand::new(
+                        bus_view.bus,
+                        AudioBusSend::new(target, 1.0),
+                    ));
+                }
+            } else if let Some(send_view) = self
+                .send_views
+                .iter()
+                .find(|v| v.remove == message.destination())
+            {
+                sender.do_command(RemoveAudioBusSendCommand::new(
+                    send_view.bus,
+                    send_view.index,
+                ));
+            }
+        } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if let Some(send_view) = self
+                .send_views
+                .iter()
+                .find(|v| v.target == message.destination())
+            {
+                if let (Some(mut send), Some(target)) =
+                    (current_send(send_view), send_view.targets.get(*index))
+                {
+                    if &send.bus != target {
+                        send.bus = target.clone();
+                        sender.do_command(SetAudioBusSendCommand::new(
+                            send_view.bus,
+                            send_view.index,
+                            send,
+                        ));
+                    }
+                }
+            }
+        } else if let Some(NumericUpDownMessage::Value(gain)) =
+            message.data::<NumericUpDownMessage<f32>>()
+        {
+            if let Some(send_view) = self
+                .send_views
+                .iter()
+                .find(|v| v.gain == message.destination())
+            {
+                if let Some(mut send) = current_send(send_view) {
+                    if send.gain != *gain {
+                        send.gain = *gain;
+                        sender.do_command(SetAudioBusSendCommand::new(
+                            send_view.bus,
+                            send_view.index,
+                            send,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{
-    audio::bus::{AudioBusView, AudioBusViewBuilder, AudioBusViewMessage},
+    audio::{
+        bus::{AudioBusView, AudioBusViewBuilder, AudioBusViewMessage},
+        graph::AudioBusGraphWindow,
+    },
     command::CommandGroup,
     fyrox::{
         asset::manager::ResourceManager,
@@ -16,7 +19,7 @@ use crate::{
             text::TextBuilder,
             utils::make_simple_tooltip,
             widget::{WidgetBuilder, WidgetMessage},
-            window::{WindowBuilder, WindowTitle},
+            window::{WindowBuilder, WindowMessage, WindowTitle},
             Orientation, Thickness, UiNode, VerticalAlignment,
         },
         scene::sound::{AudioBus, AudioBusGraph, DistanceModel, HrirSphereResourceData, Renderer},
@@ -42,6 +45,7 @@ use std::{cmp::Ordering, path::Path, sync::Arc};
 use strum::VariantNames;
 
 mod bus;
+pub mod graph;
 pub mod preview;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -63,6 +67,8 @@ pub struct AudioPanel {
     distance_model: Handle<UiNode>,
     renderer: Handle<UiNode>,
     hrir_resource: Handle<UiNode>,
+    open_bus_graph: Handle<UiNode>,
+    pub bus_graph: AudioBusGraphWindow,
 }
 
 fn item_bus(item: Handle<UiNode>, ui: &UserInterface) -> Handle<AudioBus> {
@@ -89,6 +95,12 @@ fn audio_bus_effect_names(audio_bus: &AudioBus) -> Vec<String> {
     audio_bus
         .effects()
         .map(|e| AsRef::<str>::as_ref(e).to_owned())
+        .chain(
+            audio_bus
+                .sends()
+                .iter()
+                .map(|send| format!("Send -> {} ({:.2})", send.bus, send.gain)),
+        )
         .collect::<Vec<_>>()
 }
 
@@ -102,6 +114,7 @@ impl AudioPanel {
         let distance_model;
         let renderer;
         let hrir_resource;
+        let open_bus_graph;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("AudioPanel"))
             .with_content(
                 GridBuilder::new(
@@ -226,6 +239,17 @@ impl AudioPanel {
                                         .with_text("Remove Bus")
                                         .build(ctx);
                                         remove_bus
+                                    })
+                                    .with_child({
+                                        open_bus_graph = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_tab_index(Some(6))
+                                                .with_width(100.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Bus Graph...")
+                                        .build(ctx);
+                                        open_bus_graph
                                     }),
                             )
                             .with_orientation(Orientation::Horizontal)
@@ -241,6 +265,8 @@ impl AudioPanel {
             .with_title(WindowTitle::text("Audio Context"))
             .build(ctx);
 
+        let bus_graph = AudioBusGraphWindow::new(ctx);
+
         Self {
             window,
             audio_buses: buses,
@@ -249,6 +275,8 @@ impl AudioPanel {
             remove_bus,
             renderer,
             hrir_resource,
+            open_bus_graph,
+            bus_graph,
         }
     }
 
@@ -276,6 +304,16 @@ impl AudioPanel {
 
                     sender.do_command(CommandGroup::from(commands));
                 }
+            } else if message.destination() == self.open_bus_graph {
+                engine
+                    .user_interfaces
+                    .first()
+                    .send_message(WindowMessage::open(
+                        self.bus_graph.window,
+                        MessageDirection::ToWidget,
+                        true,
+                        true,
+                    ));
             }
         } else if let Some(ListViewMessage::SelectionChanged(Some(effect_index))) = message.data() {
             if message.destination() == self.audio_buses
@@ -0,0 +1,227 @@
+use crate::{
+    fyrox::{
+        core::{
+            algebra::Vector2, color::Color, math::Rect, pool::Handle, reflect::prelude::*,
+            type_traits::prelude::*, uuid_provider, visitor::prelude::*,
+        },
+        gui::{
+            brush::Brush,
+            define_constructor, define_widget_deref,
+            draw::{CommandTexture, Draw, DrawingContext},
+            message::{MessageDirection, UiMessage},
+            widget::{Widget, WidgetBuilder},
+            BuildContext, Control, UiNode, UserInterface,
+        },
+    },
+    settings::framing::FramingSettings,
+};
+use std::ops::{Deref, DerefMut};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FramingOverlayMessage {
+    Settings(FramingSettings),
+    /// Normalized viewport rectangle (in OpenGL coordinates, Y goes up) of a camera, that is
+    /// shown as picture-in-picture.
+    PictureInPicture(Option<Rect<f32>>),
+}
+
+impl FramingOverlayMessage {
+    define_constructor!(FramingOverlayMessage:Settings => fn settings(FramingSettings), layout: false);
+    define_constructor!(FramingOverlayMessage:PictureInPicture => fn picture_in_picture(Option<Rect<f32>>), layout: false);
+}
+
+/// A widget, that is drawn on top of the scene preview and shows aspect ratio masks, safe areas and
+/// a frame around picture-in-picture camera preview.
+#[derive(Clone, Visit, Reflect, Debug, ComponentProvider)]
+pub struct FramingOverlay {
+    widget: Widget,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    settings: FramingSettings,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    picture_in_picture: Option<Rect<f32>>,
+}
+
+define_widget_deref!(FramingOverlay);
+
+uuid_provider!(FramingOverlay = "e2b7c4d1-8f3a-4b6e-9c05-71d2a8f4e6b3");
+
+/// Calculates the largest rectangle with the given aspect ratio, that fits into the bounds and
+/// centered in them.
+pub fn fit_aspect_ratio(bounds: Rect<f32>, aspect_ratio: f32) -> Rect<f32> {
+    let bounds_aspect_ratio = bounds.w() / bounds.h().max(f32::EPSILON);
+    let size = if bounds_aspect_ratio > aspect_ratio {
+        Vector2::new(bounds.h() * aspect_ratio, bounds.h())
+    } else {
+        Vector2::new(bounds.w(), bounds.w() / aspect_ratio)
+    };
+    Rect::new(
+        bounds.x() + (bounds.w() - size.x) * 0.5,
+        bounds.y() + (bounds.h() - size.y) * 0.5,
+        size.x,
+        size.y,
+    )
+}
+
+fn scale_rect(rect: Rect<f32>, scale: f32) -> Rect<f32> {
+    let size = rect.size.scale(scale);
+    Rect::new(
+        rect.x() + (rect.w() - size.x) * 0.5,
+        rect.y() + (rect.h() - size.y) * 0.5,
+        size.x,
+        size.y,
+    )
+}
+
+impl FramingOverlay {
+    fn commit(&self, ctx: &mut DrawingContext, color: Color) {
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(color),
+            CommandTexture::None,
+            None,
+        );
+    }
+}
+
+impl Control for FramingOverlay {
+    fn draw(&self, ctx: &mut DrawingContext) {
+        let bounds = self.widget.bounding_rect();
+
+        let framed = match self.settings.aspect_ratio.value() {
+            Some(aspect_ratio) => {
+                let framed = fit_aspect_ratio(bounds, aspect_ratio);
+
+                // Letterbox (or pillarbox) masks.
+                for mask in [
+                    Rect::new(bounds.x(), bounds.y(), bounds.w(), framed.y() - bounds.y()),
+                    Rect::new(
+                        bounds.x(),
+                        framed.y() + framed.h(),
+                        bounds.w(),
+                        bounds.y() + bounds.h() - framed.y() - framed.h(),
+                    ),
+                    Rect::new(bounds.x(), framed.y(), framed.x() - bounds.x(), framed.h()),
+                    Rect::new(
+                        framed.x() + framed.w(),
+                        framed.y(),
+                        bounds.x() + bounds.w() - framed.x() - framed.w(),
+                        framed.h(),
+                    ),
+                ] {
+                    if mask.w() > 0.0 && mask.h() > 0.0 {
+                        ctx.push_rect_filled(&mask, None);
+                    }
+                }
+                let opacity = (self.settings.mask_opacity.clamp(0.0, 1.0) * 255.0) as u8;
+                self.commit(ctx, Color::from_rgba(0, 0, 0, opacity));
+
+                ctx.push_rect(&framed, 1.0);
+                self.commit(ctx, Color::opaque(200, 200, 200));
+
+                framed
+            }
+            None => bounds,
+        };
+
+        if self.settings.show_safe_areas {
+            ctx.push_rect(&scale_rect(framed, self.settings.action_safe), 1.0);
+            self.commit(ctx, Color::opaque(80, 200, 255));
+
+            ctx.push_rect(&scale_rect(framed, self.settings.title_safe), 1.0);
+            self.commit(ctx, Color::opaque(255, 200, 80));
+
+            // Center mark.
+            let center = framed.center();
+            let size = framed.w().min(framed.h()) * 0.02;
+            ctx.push_line(
+                center - Vector2::new(size, 0.0),
+                center + Vector2::new(size, 0.0),
+                1.0,
+            );
+            ctx.push_line(
+                center - Vector2::new(0.0, size),
+                center + Vector2::new(0.0, size),
+                1.0,
+            );
+            self.commit(ctx, Color::opaque(200, 200, 200));
+        }
+
+        if let Some(viewport) = self.picture_in_picture {
+            let rect = Rect::new(
+                bounds.x() + viewport.x() * bounds.w(),
+                bounds.y() + (1.0 - viewport.y() - viewport.h()) * bounds.h(),
+                viewport.w() * bounds.w(),
+                viewport.h() * bounds.h(),
+            );
+            ctx.push_rect(&rect, 2.0);
+            self.commit(ctx, Color::opaque(255, 255, 255));
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if message.destination() == self.handle && message.direction() == MessageDirection::ToWidget
+        {
+            if let Some(msg) = message.data::<FramingOverlayMessage>() {
+                match msg {
+                    FramingOverlayMessage::Settings(settings) => {
+                        self.settings = settings.clone();
+                    }
+                    FramingOverlayMessage::PictureInPicture(viewport) => {
+                        self.picture_in_picture = *viewport;
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub struct FramingOverlayBuilder {
+    widget_builder: WidgetBuilder,
+    settings: FramingSettings,
+}
+
+impl FramingOverlayBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            settings: Default::default(),
+        }
+    }
+
+    pub fn with_settings(mut self, settings: FramingSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let overlay = FramingOverlay {
+            widget: self.widget_builder.with_hit_test_visibility(false).build(),
+            settings: self.settings,
+            picture_in_picture: None,
+        };
+        ctx.add_node(UiNode::new(overlay))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{camera::framing::fit_aspect_ratio, fyrox::core::math::Rect};
+
+    #[test]
+    fn test_fit_aspect_ratio() {
+        // Letterbox.
+        assert_eq!(
+            fit_aspect_ratio(Rect::new(0.0, 0.0, 160.0, 120.0), 2.0),
+            Rect::new(0.0, 20.0, 160.0, 80.0)
+        );
+        // Pillarbox.
+        assert_eq!(
+            fit_aspect_ratio(Rect::new(10.0, 0.0, 160.0, 90.0), 1.0),
+            Rect::new(45.0, 0.0, 90.0, 90.0)
+        );
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
         graph::{BaseSceneGraph, SceneGraph, SceneGraphNode},
         gui::message::{KeyCode, KeyboardModifiers, MouseButton},
         scene::{
-            base::BaseBuilder,
+            base::{BaseBuilder, LAYER_COUNT},
             camera::{Camera, CameraBuilder, Exposure, FitParameters, Projection},
             graph::Graph,
             mesh::{
@@ -39,6 +39,7 @@ use std::{
     path::Path,
 };
 
+pub mod framing;
 pub mod panel;
 
 pub const DEFAULT_Z_OFFSET: f32 = -3.0;
@@ -76,6 +77,9 @@ pub struct CameraController {
     pub editor_objects_root: Handle<Node>,
     pub scene_content_root: Handle<Node>,
     pub screen_size: Vector2<f32>,
+    /// Bit mask of locked layers. Nodes on locked layers are ignored when picking scene content,
+    /// which makes them impossible to select or edit in the scene viewer.
+    pub locked_layers: u32,
 }
 
 #[derive(Clone, Debug)]
@@ -105,6 +109,10 @@ pub struct PickingOptions<'a> {
     pub only_meshes: bool,
 }
 
+fn is_layer_in_mask(mask: u32, layer: u8) -> bool {
+    layer < LAYER_COUNT && mask & (1 << layer) != 0
+}
+
 impl CameraController {
     pub fn new(
         graph: &mut Graph,
@@ -174,6 +182,7 @@ impl CameraController {
             editor_objects_root,
             scene_content_root,
             screen_size: Default::default(),
+            locked_layers: 0,
         }
     }
 
@@ -582,6 +591,20 @@ impl CameraController {
         }
     }
 
+    pub fn is_layer_locked(&self, layer: u8) -> bool {
+        is_layer_in_mask(self.locked_layers, layer)
+    }
+
+    pub fn set_layer_locked(&mut self, layer: u8, locked: bool) {
+        if layer < LAYER_COUNT {
+            if locked {
+                self.locked_layers |= 1 << layer;
+            } else {
+                self.locked_layers &= !(1 << layer);
+            }
+        }
+    }
+
     pub fn pick(&mut self, graph: &Graph, options: PickingOptions) -> Option<CameraPickResult> {
         let PickingOptions {
             cursor_pos,
@@ -596,6 +619,7 @@ impl CameraController {
             let ray = camera.make_ray(cursor_pos, self.screen_size);
 
             self.stack.clear();
+            let locked_layers = self.locked_layers;
             let context = if editor_only {
                 // In case if we want to pick stuff from editor scene only, we have to
                 // start traversing graph from editor root.
@@ -627,6 +651,7 @@ impl CameraController {
                 self.stack.extend_from_slice(node.children());
 
                 if !node.global_visibility()
+                    || (!editor_only && is_layer_in_mask(locked_layers, node.layer()))
                     || !filter.as_mut().map_or(true, |func| func(handle, node))
                 {
                     continue;
@@ -751,11 +776,39 @@ fn read_triangle(
     Some([a, b, c])
 }
 
+/// Returns a world-space position of the vertex of the given mesh node, that is the closest to the
+/// given point.
+pub fn closest_mesh_vertex(node: &Node, point: Vector3<f32>) -> Option<Vector3<f32>> {
+    let mesh = node.query_component_ref::<Mesh>()?;
+    let transform = mesh.global_transform();
+
+    let mut closest = None;
+    let mut closest_distance = f32::MAX;
+    for surface in mesh.surfaces().iter() {
+        let data = surface.data();
+        let data = data.data_ref();
+
+        for vertex in data
+            .vertex_buffer
+            .iter()
+            .filter_map(|v| v.read_3_f32(VertexAttributeUsage::Position).ok())
+        {
+            let vertex = transform_vertex(vertex, &transform);
+            let distance = vertex.metric_distance(&point);
+            if distance < closest_distance {
+                closest_distance = distance;
+                closest = Some(vertex);
+            }
+        }
+    }
+    closest
+}
+
 fn has_hull(node: &Node) -> bool {
     node.query_component_ref::<Mesh>().is_some()
 }
 
-fn precise_ray_test(
+pub fn precise_ray_test(
     node: &Node,
     ray: &Ray,
     ignore_back_faces: bool,
@@ -4,7 +4,7 @@ use crate::fyrox::{
     core::pool::Handle,
     engine::Engine,
     gui::{
-        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        check_box::{CheckBox, CheckBoxBuilder, CheckBoxMessage},
         message::{MessageDirection, UiMessage},
         stack_panel::StackPanelBuilder,
         text::TextBuilder,
@@ -15,6 +15,7 @@ use crate::fyrox::{
     scene::{camera::Camera, node::Node},
 };
 use crate::{
+    camera::framing::FramingOverlayMessage,
     scene::{GameScene, Selection},
     send_sync_message, Message,
 };
@@ -22,13 +23,20 @@ use crate::{
 pub struct CameraPreviewControlPanel {
     pub window: Handle<UiNode>,
     preview: Handle<UiNode>,
+    picture_in_picture: Handle<UiNode>,
     cameras_state: Vec<(Handle<Node>, Node)>,
     scene_viewer_frame: Handle<UiNode>,
+    framing_overlay: Handle<UiNode>,
 }
 
 impl CameraPreviewControlPanel {
-    pub fn new(scene_viewer_frame: Handle<UiNode>, ctx: &mut BuildContext) -> Self {
+    pub fn new(
+        scene_viewer_frame: Handle<UiNode>,
+        framing_overlay: Handle<UiNode>,
+        ctx: &mut BuildContext,
+    ) -> Self {
         let preview;
+        let picture_in_picture;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("CameraPanel"))
             .with_title(WindowTitle::text("Camera Preview"))
             .with_content(
@@ -47,6 +55,20 @@ impl CameraPreviewControlPanel {
                                 )
                                 .build(ctx);
                             preview
+                        })
+                        .with_child({
+                            picture_in_picture = CheckBoxBuilder::new(WidgetBuilder::new())
+                                .checked(Some(true))
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Picture-in-Picture")
+                                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                                    .build(ctx),
+                                )
+                                .build(ctx);
+                            picture_in_picture
                         }),
                 )
                 .with_orientation(Orientation::Vertical)
@@ -59,7 +81,9 @@ impl CameraPreviewControlPanel {
             window,
             cameras_state: Default::default(),
             preview,
+            picture_in_picture,
             scene_viewer_frame,
+            framing_overlay,
         }
     }
 
@@ -78,6 +102,8 @@ impl CameraPreviewControlPanel {
         }
 
         if let Message::SelectionChanged { .. } = message {
+            self.update_picture_in_picture(editor_selection, game_scene, engine);
+
             let scene = &engine.scenes[game_scene.scene];
             if let Some(selection) = editor_selection.as_graph() {
                 let any_camera = selection
@@ -111,6 +137,44 @@ impl CameraPreviewControlPanel {
         }
     }
 
+    fn update_picture_in_picture(
+        &self,
+        editor_selection: &Selection,
+        game_scene: &mut GameScene,
+        engine: &mut Engine,
+    ) {
+        let ui = engine.user_interfaces.first();
+        let enabled = ui
+            .try_get_of_type::<CheckBox>(self.picture_in_picture)
+            .and_then(|check_box| *check_box.checked)
+            .unwrap_or_default();
+
+        let scene = &engine.scenes[game_scene.scene];
+        game_scene.picture_in_picture = if enabled && !self.is_in_preview_mode() {
+            editor_selection
+                .as_graph()
+                .and_then(|selection| {
+                    selection
+                        .nodes
+                        .iter()
+                        .find(|n| scene.graph.try_get_of_type::<Camera>(**n).is_some())
+                        .cloned()
+                })
+                .unwrap_or_default()
+        } else {
+            Handle::NONE
+        };
+
+        ui.send_message(FramingOverlayMessage::picture_in_picture(
+            self.framing_overlay,
+            MessageDirection::ToWidget,
+            game_scene
+                .picture_in_picture
+                .is_some()
+                .then_some(GameScene::PICTURE_IN_PICTURE_VIEWPORT),
+        ));
+    }
+
     fn enter_preview_mode(
         &mut self,
         editor_selection: &Selection,
@@ -175,6 +239,11 @@ impl CameraPreviewControlPanel {
                 } else {
                     self.leave_preview_mode(game_scene, engine);
                 }
+                self.update_picture_in_picture(editor_selection, game_scene, engine);
+            } else if message.destination() == self.picture_in_picture
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.update_picture_in_picture(editor_selection, game_scene, engine);
             }
         }
     }
@@ -4,6 +4,7 @@ use crate::fyrox::{
         ComponentProvider,
     },
     gui::inspector::{PropertyAction, PropertyChanged},
+    resource::texture::TextureResource,
 };
 use std::{
     any::{type_name, TypeId},
@@ -62,6 +63,11 @@ pub trait CommandTrait: Debug + 'static {
     fn execute(&mut self, context: &mut dyn CommandContext);
     fn revert(&mut self, context: &mut dyn CommandContext);
     fn finalize(&mut self, _: &mut dyn CommandContext) {}
+    /// Returns a small preview of the changes made by the command, that is shown in the command stack
+    /// viewer.
+    fn thumbnail(&self) -> Option<TextureResource> {
+        None
+    }
 }
 
 #[derive(Debug)]
@@ -153,11 +159,18 @@ impl CommandTrait for CommandGroup {
             cmd.finalize(context);
         }
     }
+
+    fn thumbnail(&self) -> Option<TextureResource> {
+        self.commands.iter().find_map(|cmd| cmd.thumbnail())
+    }
 }
 
 pub struct CommandStack {
     pub commands: Vec<Command>,
     pub top: Option<usize>,
+    /// Names of the actions (including undo/redo), that were performed since the last save. It is
+    /// stored in crash recovery data.
+    pub journal: Vec<String>,
     max_capacity: usize,
     debug: bool,
 }
@@ -167,6 +180,7 @@ impl CommandStack {
         Self {
             commands: Default::default(),
             top: None,
+            journal: Default::default(),
             max_capacity,
             debug,
         }
@@ -220,9 +234,18 @@ impl CommandStack {
 
         command.execute(context);
 
+        let name = command.name(&*context);
+        Self::record_to(&mut self.journal, self.max_capacity, name);
         self.commands.push(command);
     }
 
+    fn record_to(journal: &mut Vec<String>, max_capacity: usize, name: String) {
+        if journal.len() >= max_capacity {
+            journal.remove(0);
+        }
+        journal.push(name);
+    }
+
     pub fn undo(&mut self, context: &mut dyn CommandContext) {
         if !self.commands.is_empty() {
             if let Some(top) = self.top.as_mut() {
@@ -230,7 +253,9 @@ impl CommandStack {
                     if self.debug {
                         println!("Undo command {:?}", command);
                     }
-                    command.revert(context)
+                    command.revert(context);
+                    let name = format!("Undo {}", command.name(&*context));
+                    Self::record_to(&mut self.journal, self.max_capacity, name);
                 }
                 if *top == 0 {
                     self.top = None;
@@ -263,7 +288,9 @@ impl CommandStack {
                 if self.debug {
                     println!("Redo command {:?}", command);
                 }
-                command.execute(context)
+                command.execute(context);
+                let name = format!("Redo {}", command.name(&*context));
+                Self::record_to(&mut self.journal, self.max_capacity, name);
             }
         }
     }
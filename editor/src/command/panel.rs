@@ -4,6 +4,7 @@ use crate::fyrox::{
         brush::Brush,
         button::ButtonMessage,
         grid::{Column, GridBuilder, Row},
+        image::ImageBuilder,
         list_view::{ListViewBuilder, ListViewMessage},
         message::{MessageDirection, UiMessage},
         scroll_viewer::ScrollViewerBuilder,
@@ -11,8 +12,9 @@ use crate::fyrox::{
         text::TextBuilder,
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowTitle},
-        BuildContext, Orientation, Thickness, UiNode, UserInterface,
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
+    resource::texture::TextureResource,
 };
 use crate::{
     gui::make_image_button_with_tooltip, load_image, message::MessageSender, send_sync_message,
@@ -127,15 +129,17 @@ impl CommandStackViewer {
         &mut self,
         top: Option<usize>,
         command_names: Vec<String>,
+        thumbnails: Vec<Option<TextureResource>>,
         ui: &mut UserInterface,
     ) {
         scope_profile!();
 
         let items = command_names
             .into_iter()
+            .zip(thumbnails)
             .enumerate()
             .rev() // First command in list is last on stack.
-            .map(|(i, name)| {
+            .map(|(i, (name, thumbnail))| {
                 let brush = if let Some(top) = top {
                     if (0..=top).contains(&i) {
                         Brush::Solid(Color::opaque(255, 255, 255))
@@ -146,8 +150,11 @@ impl CommandStackViewer {
                     Brush::Solid(Color::opaque(100, 100, 100))
                 };
 
-                TextBuilder::new(
+                let ctx = &mut ui.build_ctx();
+
+                let text = TextBuilder::new(
                     WidgetBuilder::new()
+                        .with_vertical_alignment(VerticalAlignment::Center)
                         .with_margin(Thickness {
                             left: 2.0,
                             top: 1.0,
@@ -157,7 +164,28 @@ impl CommandStackViewer {
                         .with_foreground(brush),
                 )
                 .with_text(name)
-                .build(&mut ui.build_ctx())
+                .build(ctx);
+
+                match thumbnail {
+                    // Show a preview of the changes, so the command could be found visually.
+                    Some(thumbnail) => StackPanelBuilder::new(
+                        WidgetBuilder::new()
+                            .with_child(
+                                ImageBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_width(32.0)
+                                        .with_height(32.0)
+                                        .with_margin(Thickness::uniform(1.0)),
+                                )
+                                .with_texture(thumbnail.into())
+                                .build(ctx),
+                            )
+                            .with_child(text),
+                    )
+                    .with_orientation(Orientation::Horizontal)
+                    .build(ctx),
+                    None => text,
+                }
             })
             .collect();
 
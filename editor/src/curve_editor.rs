@@ -1,8 +1,8 @@
 use crate::fyrox::{
-    asset::{untyped::ResourceKind, Resource},
+    asset::{untyped::ResourceKind, Resource, ResourceData},
     core::{
-        color::Color, futures::executor::block_on, math::curve::Curve, pool::Handle,
-        type_traits::prelude::*, visitor::prelude::*,
+        color::Color, futures::executor::block_on, log::Log, math::curve::Curve, pool::Handle,
+        type_traits::prelude::*,
     },
     engine::Engine,
     gui::{
@@ -299,6 +299,17 @@ impl CurveEditorWindow {
         ));
     }
 
+    /// Opens the editor for the given curve resource. Changes are applied to the resource
+    /// directly, so every user of the resource sees them immediately.
+    pub fn edit(&mut self, curve: CurveResource, ui: &UserInterface) {
+        self.path = match curve.kind() {
+            ResourceKind::External(path) => path,
+            ResourceKind::Embedded => Default::default(),
+        };
+        self.set_curve(curve, ui);
+        self.open(ui);
+    }
+
     fn sync_to_model(&mut self, ui: &UserInterface) {
         if let Some(curve_resource) = self.curve_resource.as_ref() {
             send_sync_message(
@@ -315,9 +326,7 @@ impl CurveEditorWindow {
     fn save(&self) {
         if let Some(curve_resource) = self.curve_resource.as_ref() {
             if let Some(state) = curve_resource.state().data() {
-                let mut visitor = Visitor::new();
-                state.curve.visit("Curve", &mut visitor).unwrap();
-                visitor.save_binary(&self.path).unwrap();
+                Log::verify(state.save(&self.path));
             }
         }
     }
@@ -1,37 +1,42 @@
 use crate::{
     fyrox::{
+        asset::preload::{PreloadManifest, PRELOAD_MANIFEST_FILE},
         core::{
             color::Color,
             log::{Log, LogMessage, MessageKind},
             pool::Handle,
             reflect::prelude::*,
+            type_traits::prelude::*,
         },
+        engine::executor::QUALITY_SETTINGS_FILE,
         graph::BaseSceneGraph,
         gui::{
             border::BorderBuilder,
             brush::Brush,
             button::{ButtonBuilder, ButtonMessage},
-            decorator::DecoratorBuilder,
-            dropdown_list::{DropdownListBuilder, DropdownListMessage},
             formatted_text::WrapMode,
             grid::{Column, GridBuilder, Row},
             inspector::{
-                editors::PropertyEditorDefinitionContainer, Inspector, InspectorBuilder,
-                InspectorContext, InspectorMessage, PropertyAction,
+                editors::{
+                    collection::VecCollectionPropertyEditorDefinition,
+                    enumeration::EnumPropertyEditorDefinition,
+                    inspectable::InspectablePropertyEditorDefinition,
+                    PropertyEditorDefinitionContainer,
+                },
+                Inspector, InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
             },
-            list_view::{ListViewBuilder, ListViewMessage},
             message::{MessageDirection, UiMessage},
+            progress_bar::{ProgressBarBuilder, ProgressBarMessage},
             scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
             stack_panel::StackPanelBuilder,
-            text::TextBuilder,
+            text::{TextBuilder, TextMessage},
             widget::{WidgetBuilder, WidgetMessage},
             window::{WindowBuilder, WindowMessage, WindowTitle},
-            wrap_panel::WrapPanelBuilder,
             BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
             VerticalAlignment, BRUSH_DARKER, BRUSH_LIGHT,
         },
+        renderer::QualitySettings,
     },
-    gui::make_dropdown_list_option,
     message::MessageSender,
     Message,
 };
@@ -46,26 +51,23 @@ use std::{
     process::Stdio,
     sync::{
         atomic::{AtomicBool, Ordering},
-        mpsc::{self, Receiver},
+        mpsc::{self, Receiver, Sender},
         Arc,
     },
     time::Duration,
 };
-use strum::VariantNames;
-use strum_macros::VariantNames;
+use strum_macros::{AsRefStr, EnumString, VariantNames};
 
 #[derive(Reflect, Debug, Clone)]
 struct ExportOptions {
-    #[reflect(hidden)]
-    target_platform: TargetPlatform,
     destination_folder: PathBuf,
+    /// A set of profiles, that will be built one after another. Every profile is placed in a
+    /// separate sub-folder (named after the profile) of the destination folder.
+    profiles: Vec<ExportProfile>,
     include_used_assets: bool,
     assets_folders: Vec<PathBuf>,
     ignored_extensions: Vec<String>,
-    #[reflect(hidden)]
-    build_targets: Vec<String>,
-    #[reflect(hidden)]
-    selected_build_target: usize,
+    /// Runs the first successfully built profile.
     run_after_build: bool,
     open_destination_folder: bool,
 }
@@ -73,20 +75,60 @@ struct ExportOptions {
 impl Default for ExportOptions {
     fn default() -> Self {
         Self {
-            target_platform: Default::default(),
             destination_folder: "./build/".into(),
+            profiles: vec![
+                ExportProfile {
+                    name: "Windows".to_string(),
+                    enabled: cfg!(windows),
+                    target_platform: TargetPlatform::PC,
+                    build_target: "x86_64-pc-windows-msvc".to_string(),
+                    quality: None,
+                },
+                ExportProfile {
+                    name: "Linux".to_string(),
+                    enabled: cfg!(target_os = "linux"),
+                    target_platform: TargetPlatform::PC,
+                    build_target: "x86_64-unknown-linux-gnu".to_string(),
+                    quality: None,
+                },
+                ExportProfile {
+                    name: "Web".to_string(),
+                    enabled: false,
+                    target_platform: TargetPlatform::WebAssembly,
+                    build_target: "wasm32-unknown-unknown".to_string(),
+                    quality: Some(QualityPreset::Low),
+                },
+                ExportProfile {
+                    name: "Android".to_string(),
+                    enabled: false,
+                    target_platform: TargetPlatform::Android,
+                    build_target: "aarch64-linux-android".to_string(),
+                    quality: Some(QualityPreset::Medium),
+                },
+            ],
             assets_folders: vec!["./data/".into()],
             include_used_assets: false,
             ignored_extensions: vec!["log".to_string()],
-            build_targets: vec!["default".to_string()],
-            selected_build_target: 0,
             run_after_build: false,
             open_destination_folder: true,
         }
     }
 }
 
-#[derive(Copy, Clone, VariantNames, Default, Debug, Eq, PartialEq)]
+#[derive(
+    Copy,
+    Clone,
+    Reflect,
+    VariantNames,
+    EnumString,
+    AsRefStr,
+    TypeUuidProvider,
+    Default,
+    Debug,
+    Eq,
+    PartialEq,
+)]
+#[type_uuid(id = "0e4bd1c5-6d2a-4bb8-9f7c-2a5d3f6e1b90")]
 enum TargetPlatform {
     #[default]
     PC,
@@ -108,6 +150,64 @@ impl Display for TargetPlatform {
     }
 }
 
+/// A set of predefined renderer quality settings, that could be baked into a build.
+#[derive(
+    Copy,
+    Clone,
+    Reflect,
+    VariantNames,
+    EnumString,
+    AsRefStr,
+    TypeUuidProvider,
+    Default,
+    Debug,
+    Eq,
+    PartialEq,
+)]
+#[type_uuid(id = "7a1c9e42-3f58-4d0b-b6e2-91c4d8a05f37")]
+enum QualityPreset {
+    Ultra,
+    #[default]
+    High,
+    Medium,
+    Low,
+}
+
+impl QualityPreset {
+    fn settings(self) -> QualitySettings {
+        match self {
+            QualityPreset::Ultra => QualitySettings::ultra(),
+            QualityPreset::High => QualitySettings::high(),
+            QualityPreset::Medium => QualitySettings::medium(),
+            QualityPreset::Low => QualitySettings::low(),
+        }
+    }
+}
+
+#[derive(Reflect, Debug, Clone, Default, TypeUuidProvider)]
+#[type_uuid(id = "c3f0a8d6-52e1-4b7a-8e9d-6b1f24c7a0e5")]
+struct ExportProfile {
+    name: String,
+    /// Disabled profiles are skipped when building.
+    enabled: bool,
+    target_platform: TargetPlatform,
+    /// Rust target triple, `default` means the target of the current machine.
+    build_target: String,
+    /// Quality settings, that will be applied by the executor on start. `None` keeps the defaults
+    /// of the engine.
+    quality: Option<QualityPreset>,
+}
+
+/// Build target, that means the target of the current machine.
+const DEFAULT_BUILD_TARGET: &str = "default";
+
+/// Progress of a build, that is sent by the worker thread.
+struct BuildProgress {
+    profile: String,
+    index: usize,
+    count: usize,
+}
+
 pub struct ExportWindow {
     pub window: Handle<UiNode>,
     log: Handle<UiNode>,
@@ -117,10 +217,11 @@ pub struct ExportWindow {
     cancel_flag: Arc<AtomicBool>,
     log_message_receiver: Option<Receiver<LogMessage>>,
     build_result_receiver: Option<Receiver<Result<(), String>>>,
-    target_platform_list: Handle<UiNode>,
+    progress_receiver: Option<Receiver<BuildProgress>>,
     export_options: ExportOptions,
     inspector: Handle<UiNode>,
-    build_targets_selector: Handle<UiNode>,
+    progress_bar: Handle<UiNode>,
+    progress_text: Handle<UiNode>,
 }
 
 fn copy_dir<F>(src: impl AsRef<Path>, dst: impl AsRef<Path>, filter: &F) -> io::Result<()>
@@ -277,8 +378,12 @@ fn configure_build_environment(
 ) -> Result<(), String> {
     match target_platform {
         TargetPlatform::PC => {
-            // Assume that rustup have installed the correct toolchain.
-            Ok(())
+            if build_target == DEFAULT_BUILD_TARGET {
+                // Assume that rustup have installed the correct toolchain.
+                Ok(())
+            } else {
+                install_build_target(build_target)
+            }
         }
         TargetPlatform::WebAssembly => {
             // Check if the user have `wasm-pack` installed.
@@ -312,6 +417,9 @@ fn build_package(
                 .arg("--package")
                 .arg(package_name)
                 .arg("--release");
+            if build_target != DEFAULT_BUILD_TARGET {
+                process.arg("--target").arg(build_target);
+            }
             process
         }
         TargetPlatform::WebAssembly => {
@@ -352,8 +460,7 @@ fn build_package(
     loop {
         if cancel_flag.load(Ordering::Relaxed) {
             Log::verify(handle.kill());
-            Log::warn("Build was cancelled.");
-            return Ok(());
+            return Err("Build was cancelled.".to_string());
         }
 
         for line in BufReader::new(&mut stderr).lines().take(10).flatten() {
@@ -386,11 +493,17 @@ fn build_package(
 fn copy_binaries_pc(
     metadata: &Metadata,
     package_name: &str,
+    build_target: &str,
     destination_folder: &Path,
 ) -> Result<(), String> {
+    let release_dir = if build_target == DEFAULT_BUILD_TARGET {
+        metadata.target_directory.join("release")
+    } else {
+        metadata.target_directory.join(build_target).join("release")
+    };
     let mut binary_paths = vec![];
-    for entry in fs::read_dir(metadata.target_directory.join("release"))
-        .unwrap()
+    for entry in fs::read_dir(&release_dir)
+        .map_err(|err| format!("Unable to read {release_dir}. Reason: {err:?}"))?
         .flatten()
     {
         if let Ok(file_metadata) = entry.metadata() {
@@ -495,13 +608,81 @@ fn copy_binaries_wasm(package_dir_path: &Path, destination_folder: &Path) -> Res
     .map_err(|e| e.to_string())
 }
 
-fn export(export_options: ExportOptions, cancel_flag: Arc<AtomicBool>) -> Result<(), String> {
-    Log::info("Building the game...");
+fn write_quality_settings(preset: QualityPreset, folder: &Path) -> Result<(), String> {
+    let path = folder.join(QUALITY_SETTINGS_FILE);
+    let content = ron::ser::to_string_pretty(&preset.settings(), Default::default())
+        .map_err(|err| format!("Unable to serialize quality settings. Reason: {err:?}"))?;
+    fs::write(&path, content)
+        .map_err(|err| format!("Unable to write {}. Reason: {err:?}", path.display()))?;
+    Log::info(format!(
+        "{:?} quality settings were written to {}.",
+        preset,
+        path.display()
+    ));
+    Ok(())
+}
 
-    prepare_build_dir(&export_options.destination_folder)?;
-    let metadata = read_metadata()?;
+fn write_preload_manifest(
+    export_options: &ExportOptions,
+    destination_folder: &Path,
+) -> Result<(), String> {
+    let manifest = PreloadManifest::scan(destination_folder, &export_options.assets_folders)
+        .map_err(|err| format!("Unable to make preload manifest. Reason: {err:?}"))?;
+    let content = manifest
+        .to_string_pretty()
+        .map_err(|err| format!("Unable to serialize preload manifest. Reason: {err:?}"))?;
+    let path = destination_folder.join(PRELOAD_MANIFEST_FILE);
+    fs::write(&path, content)
+        .map_err(|err| format!("Unable to write {}. Reason: {err:?}", path.display()))?;
+    Log::info(format!(
+        "Preload manifest with {} assets was written to {}.",
+        manifest.entries.len(),
+        path.display()
+    ));
+    Ok(())
+}
+
+fn copy_assets(export_options: &ExportOptions, destination_folder: &Path) -> Result<(), String> {
+    let ignored_extensions = &export_options.ignored_extensions;
+    let filter = |path: &Path| {
+        !path.is_file()
+            || !path.extension().is_some_and(|ext| {
+                ignored_extensions
+                    .iter()
+                    .any(|ignored| ext == OsStr::new(ignored))
+            })
+    };
 
-    let package_name = match export_options.target_platform {
+    for folder in export_options.assets_folders.iter() {
+        Log::info(format!(
+            "Trying to copy assets from {} to {}...",
+            folder.display(),
+            destination_folder.display()
+        ));
+
+        copy_dir(folder, destination_folder.join(folder), &filter).map_err(|err| {
+            format!(
+                "Unable to copy assets from {}. Reason: {err:?}",
+                folder.display()
+            )
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Builds the given profile and puts the results in the destination folder. Returns the name of the
+/// package, that was built.
+fn export_profile(
+    profile: &ExportProfile,
+    export_options: &ExportOptions,
+    metadata: &Metadata,
+    destination_folder: &Path,
+    cancel_flag: Arc<AtomicBool>,
+) -> Result<&'static str, String> {
+    prepare_build_dir(destination_folder)?;
+
+    let package_name = match profile.target_platform {
         TargetPlatform::PC => "executor",
         TargetPlatform::WebAssembly => "executor-wasm",
         TargetPlatform::Android => "executor-android",
@@ -519,22 +700,25 @@ fn export(export_options: ExportOptions, cancel_flag: Arc<AtomicBool>) -> Result
     let mut temp_folders = Vec::new();
 
     // Copy assets
-    match export_options.target_platform {
+    match profile.target_platform {
         TargetPlatform::PC | TargetPlatform::WebAssembly => {
             Log::info("Trying to copy the assets...");
 
-            for folder in export_options.assets_folders {
-                Log::info(format!(
-                    "Trying to copy assets from {} to {}...",
-                    folder.display(),
-                    export_options.destination_folder.display()
-                ));
+            copy_assets(export_options, destination_folder)?;
 
-                Log::verify(copy_dir(
-                    &folder,
-                    export_options.destination_folder.join(&folder),
-                    &|_| true,
-                ));
+            if profile.target_platform == TargetPlatform::WebAssembly {
+                write_preload_manifest(export_options, destination_folder)?;
+            }
+
+            if let Some(quality) = profile.quality {
+                if profile.target_platform == TargetPlatform::WebAssembly {
+                    Log::warn(
+                        "Quality settings are not applied automatically on WebAssembly, \
+                        load them manually in your game.",
+                    );
+                }
+
+                write_quality_settings(quality, destination_folder)?;
             }
         }
         TargetPlatform::Android => {
@@ -573,18 +757,10 @@ fn export(export_options: ExportOptions, cancel_flag: Arc<AtomicBool>) -> Result
 
                 temp_folders.push(temp_assets_storage.clone());
 
-                for folder in export_options.assets_folders {
-                    Log::info(format!(
-                        "Trying to copy assets from {} to {}...",
-                        folder.display(),
-                        temp_assets_storage.display()
-                    ));
+                copy_assets(export_options, &temp_assets_storage)?;
 
-                    Log::verify(copy_dir(
-                        &folder,
-                        temp_assets_storage.join(&folder),
-                        &|_| true,
-                    ));
+                if let Some(quality) = profile.quality {
+                    write_quality_settings(quality, &temp_assets_storage)?;
                 }
             } else {
                 return Err("Android executor must specify assets folder in \
@@ -594,98 +770,210 @@ fn export(export_options: ExportOptions, cancel_flag: Arc<AtomicBool>) -> Result
         }
     }
 
-    build_package(
+    let build_result = build_package(
         package_name,
-        &export_options.build_targets[export_options.selected_build_target],
+        &profile.build_target,
         package_dir_path,
-        export_options.target_platform,
+        profile.target_platform,
         cancel_flag,
-    )?;
-
-    match export_options.target_platform {
+    )
+    .and_then(|_| match profile.target_platform {
         TargetPlatform::PC => {
             // TODO: This should be replaced with `--out-dir` flag to cargo when it is stabilized.
             Log::info("Trying to copy the executable...");
-            copy_binaries_pc(&metadata, package_name, &export_options.destination_folder)?;
+            copy_binaries_pc(
+                metadata,
+                package_name,
+                &profile.build_target,
+                destination_folder,
+            )
         }
         TargetPlatform::WebAssembly => {
             Log::info("Trying to copy the executable...");
-            copy_binaries_wasm(
-                package_dir_path.as_std_path(),
-                &export_options.destination_folder,
-            )?;
+            copy_binaries_wasm(package_dir_path.as_std_path(), destination_folder)?;
+
+            if !destination_folder.join("index.html").exists() {
+                Log::info("The project does not have a web page, generating the default one...");
+                fyrox_template_core::init_web_shell(destination_folder)?;
+            }
+
+            Ok(())
         }
         TargetPlatform::Android => {
             Log::info("Trying to copy the apk...");
-            copy_binaries_android(&metadata, package_name, &export_options.destination_folder)?;
+            copy_binaries_android(metadata, package_name, destination_folder)
         }
-    }
+    });
 
     // Remove all temp folders.
     for temp_folder in temp_folders {
         Log::verify(std::fs::remove_dir_all(temp_folder));
     }
 
-    if let Ok(destination_folder) = export_options.destination_folder.canonicalize() {
-        if export_options.run_after_build {
-            match export_options.target_platform {
-                TargetPlatform::PC => {
-                    #[allow(unused_mut)]
-                    let mut path = destination_folder.join(package_name);
-                    #[cfg(windows)]
-                    {
-                        path.set_extension("exe");
+    build_result.map(|_| package_name)
+}
+
+fn run_build(target_platform: TargetPlatform, package_name: &str, destination_folder: &Path) {
+    match target_platform {
+        TargetPlatform::PC => {
+            #[allow(unused_mut)]
+            let mut path = destination_folder.join(package_name);
+            #[cfg(windows)]
+            {
+                path.set_extension("exe");
+            }
+            Log::verify(open::that_detached(path))
+        }
+        TargetPlatform::WebAssembly => {
+            Log::verify(cargo_install("basic-http-server"));
+
+            Log::verify(
+                std::process::Command::new("basic-http-server")
+                    .arg("--addr")
+                    .arg("127.0.0.1:4000")
+                    .current_dir(destination_folder)
+                    .spawn(),
+            );
+
+            Log::verify(open::that_detached("http://127.0.0.1:4000"));
+        }
+        TargetPlatform::Android => {
+            if let Ok(adb) = std::process::Command::new("adb")
+                .current_dir(destination_folder)
+                .arg("install")
+                .arg(format!("{package_name}.apk"))
+                .spawn()
+            {
+                match adb.wait_with_output() {
+                    Ok(_) => {
+                        let compatible_package_name = package_name.replace('-', "_");
+                        Log::verify(
+                            std::process::Command::new("adb")
+                                .arg("shell")
+                                .arg("am")
+                                .arg("start")
+                                .arg("-n")
+                                .arg(format!(
+                                    "rust.{compatible_package_name}/android.app.NativeActivity"
+                                ))
+                                .spawn(),
+                        );
                     }
-                    Log::verify(open::that_detached(path))
+                    Err(err) => Log::err(format!("ADB error: {:?}", err)),
                 }
-                TargetPlatform::WebAssembly => {
-                    Log::verify(cargo_install("basic-http-server"));
-
-                    Log::verify(
-                        std::process::Command::new("basic-http-server")
-                            .arg("--addr")
-                            .arg("127.0.0.1:4000")
-                            .current_dir(&destination_folder)
-                            .spawn(),
-                    );
+            }
+        }
+    }
+}
 
-                    Log::verify(open::that_detached("http://127.0.0.1:4000"));
-                }
-                TargetPlatform::Android => {
-                    if let Ok(adb) = std::process::Command::new("adb")
-                        .current_dir(&destination_folder)
-                        .arg("install")
-                        .arg(format!("{package_name}.apk"))
-                        .spawn()
-                    {
-                        match adb.wait_with_output() {
-                            Ok(_) => {
-                                let compatible_package_name = package_name.replace('-', "_");
-                                Log::verify(
-                                    std::process::Command::new("adb")
-                                        .arg("shell")
-                                        .arg("am")
-                                        .arg("start")
-                                        .arg("-n")
-                                        .arg(format!(
-                                            "rust.{compatible_package_name}/android.app.NativeActivity"
-                                        ))
-                                        .spawn(),
-                                );
-                            }
-                            Err(err) => Log::err(format!("ADB error: {:?}", err)),
-                        }
-                    }
+fn export(
+    export_options: ExportOptions,
+    cancel_flag: Arc<AtomicBool>,
+    progress_sender: Sender<BuildProgress>,
+) -> Result<(), String> {
+    let profiles = export_options
+        .profiles
+        .iter()
+        .filter(|profile| profile.enabled)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if profiles.is_empty() {
+        return Err("There are no enabled profiles to build.".to_string());
+    }
+
+    Log::info("Building the game...");
+
+    prepare_build_dir(&export_options.destination_folder)?;
+    let metadata = read_metadata()?;
+
+    let count = profiles.len();
+    let mut errors = Vec::new();
+    let mut first_build = None;
+    for (index, profile) in profiles.iter().enumerate() {
+        if cancel_flag.load(Ordering::Relaxed) {
+            break;
+        }
+
+        Log::verify(progress_sender.send(BuildProgress {
+            profile: profile.name.clone(),
+            index,
+            count,
+        }));
+
+        Log::info(format!(
+            "Building {} profile ({}/{}) for {} platform...",
+            profile.name,
+            index + 1,
+            count,
+            profile.target_platform
+        ));
+
+        let destination_folder = export_options.destination_folder.join(&profile.name);
+
+        match export_profile(
+            profile,
+            &export_options,
+            &metadata,
+            &destination_folder,
+            cancel_flag.clone(),
+        ) {
+            Ok(package_name) => {
+                Log::info(format!("{} profile was built successfully.", profile.name));
+
+                if first_build.is_none() {
+                    first_build = Some((profile.target_platform, package_name, destination_folder));
                 }
             }
+            Err(err) => {
+                Log::err(format!(
+                    "Failed to build {} profile. Reason: {}",
+                    profile.name, err
+                ));
+                errors.push(format!("{}: {}", profile.name, err));
+            }
         }
+    }
+
+    Log::verify(progress_sender.send(BuildProgress {
+        profile: Default::default(),
+        index: count,
+        count,
+    }));
+
+    if export_options.run_after_build && !cancel_flag.load(Ordering::Relaxed) {
+        if let Some((target_platform, package_name, destination_folder)) = first_build {
+            if let Ok(destination_folder) = destination_folder.canonicalize() {
+                run_build(target_platform, package_name, &destination_folder);
+            }
+        }
+    }
 
-        if export_options.open_destination_folder {
+    if export_options.open_destination_folder {
+        if let Ok(destination_folder) = export_options.destination_folder.canonicalize() {
             Log::verify(open::that_detached(destination_folder));
         }
     }
 
-    Ok(())
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} of {} profiles failed to build. {}",
+            errors.len(),
+            count,
+            errors.join(" ")
+        ))
+    }
+}
+
+fn make_property_editors() -> PropertyEditorDefinitionContainer {
+    let container = PropertyEditorDefinitionContainer::with_default_editors();
+    container.insert(InspectablePropertyEditorDefinition::<ExportProfile>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<ExportProfile>::new());
+    container.insert(EnumPropertyEditorDefinition::<TargetPlatform>::new());
+    container.insert(EnumPropertyEditorDefinition::<QualityPreset>::new_optional());
+    container
 }
 
 fn make_title_text(text: &str, row: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
@@ -703,100 +991,24 @@ fn make_title_text(text: &str, row: usize, ctx: &mut BuildContext) -> Handle<UiN
 impl ExportWindow {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let instructions =
-            "Select the target directory in which you want to export the current project. You can \
-            also specify the assets, that will be included in the final build. Previous content of \
-            the build folder will be completely erased when you press Export.";
+            "Select the target directory in which you want to export the current project and \
+            enable the profiles, that should be built. Every profile is built into its own \
+            sub-folder with its own target and quality settings. You can also specify the assets, \
+            that will be included in the final build. Previous content of the build folder will \
+            be completely erased when you press Export.";
 
         let export;
         let cancel;
         let log;
         let log_scroll_viewer;
-        let target_platform_list;
+        let progress_bar;
+        let progress_text;
         let export_options = ExportOptions::default();
 
-        let platform_section = StackPanelBuilder::new(
-            WidgetBuilder::new()
-                .on_row(1)
-                .with_child(make_title_text("Target Platform", 0, ctx))
-                .with_child({
-                    target_platform_list = ListViewBuilder::new(
-                        WidgetBuilder::new()
-                            .with_margin(Thickness::uniform(2.0))
-                            .with_height(60.0),
-                    )
-                    .with_items_panel(
-                        WrapPanelBuilder::new(WidgetBuilder::new())
-                            .with_orientation(Orientation::Horizontal)
-                            .build(ctx),
-                    )
-                    .with_items(
-                        TargetPlatform::VARIANTS
-                            .iter()
-                            .enumerate()
-                            .map(|(i, p)| {
-                                DecoratorBuilder::new(BorderBuilder::new(
-                                    WidgetBuilder::new()
-                                        .with_width(100.0)
-                                        .with_height(50.0)
-                                        .with_margin(Thickness::uniform(1.0))
-                                        .with_child(
-                                            TextBuilder::new(WidgetBuilder::new())
-                                                .with_vertical_text_alignment(
-                                                    VerticalAlignment::Center,
-                                                )
-                                                .with_horizontal_text_alignment(
-                                                    HorizontalAlignment::Center,
-                                                )
-                                                .with_text(p)
-                                                .with_font_size(14.0)
-                                                .build(ctx),
-                                        ),
-                                ))
-                                .with_selected(i == 0)
-                                .build(ctx)
-                            })
-                            .collect::<Vec<_>>(),
-                    )
-                    .build(ctx);
-                    target_platform_list
-                }),
-        )
-        .build(ctx);
-
-        let build_targets_selector;
-        let grid = GridBuilder::new(
-            WidgetBuilder::new()
-                .on_row(2)
-                .with_child(
-                    TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
-                        .with_vertical_text_alignment(VerticalAlignment::Center)
-                        .with_text("Build Target")
-                        .build(ctx),
-                )
-                .with_child({
-                    build_targets_selector =
-                        DropdownListBuilder::new(WidgetBuilder::new().on_column(1))
-                            .with_items(
-                                export_options
-                                    .build_targets
-                                    .iter()
-                                    .map(|opt| make_dropdown_list_option(ctx, opt))
-                                    .collect::<Vec<_>>(),
-                            )
-                            .with_selected(0)
-                            .build(ctx);
-                    build_targets_selector
-                }),
-        )
-        .add_column(Column::auto())
-        .add_column(Column::stretch())
-        .add_row(Row::strict(22.0))
-        .build(ctx);
-
         let inspector;
         let export_options_section = BorderBuilder::new(
             WidgetBuilder::new()
-                .on_row(3)
+                .on_row(1)
                 .with_margin(Thickness::uniform(2.0))
                 .with_background(BRUSH_LIGHT)
                 .with_child(
@@ -807,7 +1019,7 @@ impl ExportWindow {
                         let context = InspectorContext::from_object(
                             &export_options,
                             ctx,
-                            Arc::new(PropertyEditorDefinitionContainer::with_default_editors()),
+                            Arc::new(make_property_editors()),
                             None,
                             1,
                             0,
@@ -825,9 +1037,38 @@ impl ExportWindow {
         )
         .build(ctx);
 
+        let progress_section = GridBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
+                .with_child({
+                    progress_text = TextBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(2.0))
+                            .with_width(150.0),
+                    )
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .with_text("Idle")
+                    .build(ctx);
+                    progress_text
+                })
+                .with_child({
+                    progress_bar = ProgressBarBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(1)
+                            .with_margin(Thickness::uniform(2.0)),
+                    )
+                    .build(ctx);
+                    progress_bar
+                }),
+        )
+        .add_row(Row::strict(22.0))
+        .add_column(Column::auto())
+        .add_column(Column::stretch())
+        .build(ctx);
+
         let log_section = GridBuilder::new(
             WidgetBuilder::new()
-                .on_row(4)
+                .on_row(3)
                 .with_child(make_title_text("Export Log", 0, ctx))
                 .with_child(
                     BorderBuilder::new(
@@ -857,7 +1098,7 @@ impl ExportWindow {
 
         let buttons_section = StackPanelBuilder::new(
             WidgetBuilder::new()
-                .on_row(5)
+                .on_row(4)
                 .with_horizontal_alignment(HorizontalAlignment::Right)
                 .with_child({
                     export = ButtonBuilder::new(
@@ -898,22 +1139,20 @@ impl ExportWindow {
                             .with_text(instructions)
                             .build(ctx),
                         )
-                        .with_child(platform_section)
-                        .with_child(grid)
                         .with_child(export_options_section)
+                        .with_child(progress_section)
                         .with_child(log_section)
                         .with_child(buttons_section),
                 )
                 .add_row(Row::auto())
+                .add_row(Row::strict(300.0))
                 .add_row(Row::auto())
-                .add_row(Row::auto())
-                .add_row(Row::strict(200.0))
                 .add_row(Row::stretch())
                 .add_row(Row::strict(32.0))
                 .add_column(Column::auto())
                 .build(ctx),
             )
-            .with_title(WindowTitle::text("Export Project"))
+            .with_title(WindowTitle::text("Build & Deploy"))
             .build(ctx);
 
         Self {
@@ -925,10 +1164,11 @@ impl ExportWindow {
             cancel_flag: Arc::new(AtomicBool::new(false)),
             log_message_receiver: None,
             build_result_receiver: None,
-            target_platform_list,
+            progress_receiver: None,
             export_options,
             inspector,
-            build_targets_selector,
+            progress_bar,
+            progress_text,
         }
     }
 
@@ -950,8 +1190,10 @@ impl ExportWindow {
             self.window,
             MessageDirection::ToWidget,
         ));
+        self.cancel_flag.store(true, Ordering::Relaxed);
         self.log_message_receiver = None;
         self.build_result_receiver = None;
+        self.progress_receiver = None;
     }
 
     fn clear_log(&self, ui: &UserInterface) {
@@ -975,6 +1217,9 @@ impl ExportWindow {
                 let (tx, rx) = mpsc::channel();
                 self.build_result_receiver = Some(rx);
 
+                let (progress_sender, rx) = mpsc::channel();
+                self.progress_receiver = Some(rx);
+
                 ui.send_message(WidgetMessage::enabled(
                     self.export,
                     MessageDirection::ToWidget,
@@ -991,7 +1236,7 @@ impl ExportWindow {
                         .name("ExportWorkerThread".to_string())
                         .spawn(move || {
                             if std::panic::catch_unwind(|| {
-                                tx.send(export(export_options, cancel_flag))
+                                tx.send(export(export_options, cancel_flag, progress_sender))
                                     .expect("Channel must exist!")
                             })
                             .is_err()
@@ -1003,44 +1248,6 @@ impl ExportWindow {
             } else if message.destination() == self.cancel {
                 self.close_and_destroy(ui);
             }
-        } else if let Some(ListViewMessage::SelectionChanged(Some(index))) = message.data() {
-            if message.destination() == self.target_platform_list
-                && message.direction() == MessageDirection::FromWidget
-            {
-                match *index {
-                    0 => self.export_options.target_platform = TargetPlatform::PC,
-                    1 => self.export_options.target_platform = TargetPlatform::WebAssembly,
-                    2 => self.export_options.target_platform = TargetPlatform::Android,
-                    _ => Log::err("Unhandled platform index!"),
-                }
-
-                // TODO: move this to settings.
-                let build_targets = match self.export_options.target_platform {
-                    TargetPlatform::PC => vec!["default".to_string()],
-                    TargetPlatform::WebAssembly => vec!["wasm32-unknown-unknown".to_string()],
-                    TargetPlatform::Android => {
-                        vec![
-                            "armv7-linux-androideabi".to_string(),
-                            "aarch64-linux-android".to_string(),
-                        ]
-                    }
-                };
-
-                self.export_options.build_targets = build_targets;
-
-                let ui_items = self
-                    .export_options
-                    .build_targets
-                    .iter()
-                    .map(|name| make_dropdown_list_option(&mut ui.build_ctx(), name))
-                    .collect::<Vec<_>>();
-
-                ui.send_message(DropdownListMessage::items(
-                    self.build_targets_selector,
-                    MessageDirection::ToWidget,
-                    ui_items,
-                ));
-            }
         } else if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
             if message.destination() == self.inspector
                 && message.direction() == MessageDirection::FromWidget
@@ -1054,12 +1261,6 @@ impl ExportWindow {
                 );
                 sender.send(Message::ForceSync);
             }
-        } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
-            if message.destination() == self.build_targets_selector
-                && message.direction() == MessageDirection::FromWidget
-            {
-                self.export_options.selected_build_target = *index;
-            }
         }
     }
 
@@ -1107,6 +1308,31 @@ impl ExportWindow {
             }
         }
 
+        if let Some(receiver) = self.progress_receiver.as_ref() {
+            while let Ok(progress) = receiver.try_recv() {
+                let text = if progress.index < progress.count {
+                    format!(
+                        "Building {} ({}/{})",
+                        progress.profile,
+                        progress.index + 1,
+                        progress.count
+                    )
+                } else {
+                    "Finished".to_string()
+                };
+                ui.send_message(TextMessage::text(
+                    self.progress_text,
+                    MessageDirection::ToWidget,
+                    text,
+                ));
+                ui.send_message(ProgressBarMessage::progress(
+                    self.progress_bar,
+                    MessageDirection::ToWidget,
+                    progress.index as f32 / progress.count.max(1) as f32,
+                ));
+            }
+        }
+
         if let Some(receiver) = self.build_result_receiver.as_ref() {
             if let Ok(result) = receiver.try_recv() {
                 match result {
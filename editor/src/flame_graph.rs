@@ -0,0 +1,655 @@
+//! Flame graph window visualizes frames, that were captured by the built-in profiler of the engine
+//! (see [`crate::fyrox::core::profiler`]). The top part of the window shows durations of the captured
+//! frames, a click on a bar selects a frame. The bottom part shows the scopes of the selected frame:
+//! the width of a bar is proportional to the duration of a scope, nested scopes are placed below
+//! their parents.
+//!
+//! The profiler is available only if the editor was built with `enable_profiler` feature.
+
+use crate::{
+    fyrox::{
+        core::{
+            algebra::Vector2,
+            color::Color,
+            math::Rect,
+            pool::Handle,
+            profiler::{self, CapturedFrame, CapturedScope},
+            reflect::prelude::*,
+            type_traits::prelude::*,
+            uuid_provider,
+            visitor::prelude::*,
+        },
+        gui::{
+            brush::Brush,
+            button::{ButtonBuilder, ButtonMessage},
+            check_box::{CheckBoxBuilder, CheckBoxMessage},
+            define_constructor, define_widget_deref,
+            draw::{CommandTexture, Draw, DrawingContext},
+            grid::{Column, GridBuilder, Row},
+            message::{MessageDirection, MouseButton, UiMessage},
+            stack_panel::StackPanelBuilder,
+            text::{TextBuilder, TextMessage},
+            widget::{Widget, WidgetBuilder, WidgetMessage},
+            window::{WindowBuilder, WindowMessage, WindowTitle},
+            BuildContext, Control, Orientation, Thickness, UiNode, UserInterface,
+            VerticalAlignment,
+        },
+    },
+    stats::StatisticsWindowAction,
+};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+};
+
+/// Height of a single row of the flame graph.
+const ROW_HEIGHT: f32 = 18.0;
+
+/// Interval (in seconds) between updates of the list of the captured frames.
+const REFRESH_INTERVAL: f32 = 0.5;
+
+/// Strips the module path and the name of the helper function, that is used by `scope_profile!`
+/// macro, from a scope name: `fyrox_impl::engine::Engine::render::scope` becomes `Engine::render`.
+pub fn short_scope_name(name: &str) -> &str {
+    let name = name.strip_suffix("::scope").unwrap_or(name);
+    match name.rmatch_indices("::").nth(1) {
+        Some((position, _)) => &name[position + 2..],
+        None => name,
+    }
+}
+
+fn scope_rect(frame: &CapturedFrame, scope: &CapturedScope, bounds: &Rect<f32>) -> Rect<f32> {
+    let duration = frame.duration.max(f64::EPSILON);
+    let x = (scope.start / duration) as f32 * bounds.w();
+    let w = ((scope.duration / duration) as f32 * bounds.w()).max(1.0);
+    Rect::new(
+        bounds.x() + x,
+        bounds.y() + scope.depth as f32 * ROW_HEIGHT,
+        w,
+        ROW_HEIGHT - 1.0,
+    )
+}
+
+/// Returns an index of the scope of the frame, that contains the given point.
+pub fn scope_at(frame: &CapturedFrame, bounds: &Rect<f32>, point: Vector2<f32>) -> Option<usize> {
+    frame
+        .scopes
+        .iter()
+        .position(|scope| scope_rect(frame, scope, bounds).contains(point))
+}
+
+fn scope_color(name: &str) -> Color {
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    let hash = hasher.finish();
+    // Warm colors, the same scope always has the same color.
+    Color::opaque(
+        180 + (hash % 60) as u8,
+        80 + ((hash >> 8) % 120) as u8,
+        30 + ((hash >> 16) % 40) as u8,
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlameGraphMessage {
+    /// Sets a frame to show.
+    Frame(Option<CapturedFrame>),
+    /// Emitted when a scope under the mouse cursor has changed.
+    Hovered(Option<CapturedScope>),
+}
+
+impl FlameGraphMessage {
+    define_constructor!(FlameGraphMessage:Frame => fn frame(Option<CapturedFrame>), layout: false);
+    define_constructor!(FlameGraphMessage:Hovered => fn hovered(Option<CapturedScope>), layout: false);
+}
+
+/// A widget, that draws scopes of a single frame.
+#[derive(Debug, Clone, Visit, Reflect, ComponentProvider)]
+pub struct FlameGraph {
+    widget: Widget,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    frame: Option<CapturedFrame>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    hovered: Option<usize>,
+}
+
+define_widget_deref!(FlameGraph);
+
+uuid_provider!(FlameGraph = "b2a5c9e1-47d3-4f0a-9c6e-8e1f3d72a954");
+
+impl FlameGraph {
+    fn set_hovered(&mut self, ui: &UserInterface, hovered: Option<usize>) {
+        if self.hovered == hovered {
+            return;
+        }
+        self.hovered = hovered;
+        let scope = self
+            .frame
+            .as_ref()
+            .and_then(|frame| hovered.and_then(|index| frame.scopes.get(index)))
+            .cloned();
+        ui.send_message(FlameGraphMessage::hovered(
+            self.handle,
+            MessageDirection::FromWidget,
+            scope,
+        ));
+    }
+}
+
+impl Control for FlameGraph {
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        let bounds = self.bounding_rect();
+
+        drawing_context.push_rect_filled(&bounds, None);
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        let Some(frame) = self.frame.as_ref() else {
+            return;
+        };
+
+        for (index, scope) in frame.scopes.iter().enumerate() {
+            drawing_context.push_rect_filled(&scope_rect(frame, scope, &bounds), None);
+            let color = if self.hovered == Some(index) {
+                Color::WHITE
+            } else {
+                scope_color(scope.name)
+            };
+            drawing_context.commit(
+                self.clip_bounds(),
+                Brush::Solid(color),
+                CommandTexture::None,
+                None,
+            );
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if message.destination() != self.handle() {
+            return;
+        }
+
+        if let Some(FlameGraphMessage::Frame(frame)) = message.data() {
+            if message.direction() == MessageDirection::ToWidget {
+                self.frame.clone_from(frame);
+                self.hovered = None;
+            }
+        } else if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::MouseMove { pos, .. } => {
+                    let hovered = self.frame.as_ref().and_then(|frame| {
+                        scope_at(frame, &self.bounding_rect(), self.screen_to_local(*pos))
+                    });
+                    self.set_hovered(ui, hovered);
+                }
+                WidgetMessage::MouseLeave => self.set_hovered(ui, None),
+                _ => (),
+            }
+        }
+    }
+}
+
+pub struct FlameGraphBuilder {
+    widget_builder: WidgetBuilder,
+}
+
+impl FlameGraphBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self { widget_builder }
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let flame_graph = FlameGraph {
+            widget: self
+                .widget_builder
+                .with_background(Brush::Solid(Color::opaque(30, 30, 30)))
+                .build(),
+            frame: None,
+            hovered: None,
+        };
+
+        ctx.add_node(UiNode::new(flame_graph))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameChartMessage {
+    /// Sets durations of the frames.
+    Durations(Vec<f32>),
+    /// Sets (when sent to the widget) or reports (when sent from the widget) an index of the
+    /// selected frame.
+    Selection(Option<usize>),
+}
+
+impl FrameChartMessage {
+    define_constructor!(FrameChartMessage:Durations => fn durations(Vec<f32>), layout: false);
+    define_constructor!(FrameChartMessage:Selection => fn selection(Option<usize>), layout: false);
+}
+
+/// A bar chart of frame durations, that allows to select a frame.
+#[derive(Debug, Clone, Visit, Reflect, ComponentProvider)]
+pub struct FrameChart {
+    widget: Widget,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    durations: Vec<f32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    selection: Option<usize>,
+}
+
+define_widget_deref!(FrameChart);
+
+uuid_provider!(FrameChart = "5d18e4f7-0c92-4b3a-a6d1-73f9b2e0c841");
+
+impl FrameChart {
+    fn bar_width(&self, bounds: &Rect<f32>) -> f32 {
+        bounds.w() / profiler::MAX_CAPTURED_FRAMES as f32
+    }
+}
+
+impl Control for FrameChart {
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        let bounds = self.bounding_rect();
+
+        drawing_context.push_rect_filled(&bounds, None);
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        let max = self.durations.iter().cloned().fold(f32::EPSILON, f32::max);
+        let bar_width = self.bar_width(&bounds);
+        for (index, duration) in self.durations.iter().enumerate() {
+            let height = bounds.h() * duration / max;
+            drawing_context.push_rect_filled(
+                &Rect::new(
+                    bounds.x() + index as f32 * bar_width,
+                    bounds.y() + bounds.h() - height,
+                    (bar_width - 1.0).max(1.0),
+                    height,
+                ),
+                None,
+            );
+            let brush = if self.selection == Some(index) {
+                Brush::Solid(Color::WHITE)
+            } else {
+                self.foreground()
+            };
+            drawing_context.commit(self.clip_bounds(), brush, CommandTexture::None, None);
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<FrameChartMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    FrameChartMessage::Durations(durations) => {
+                        self.durations.clone_from(durations);
+                    }
+                    FrameChartMessage::Selection(selection) => {
+                        self.selection = *selection;
+                    }
+                }
+            }
+        } else if let Some(WidgetMessage::MouseDown {
+            pos,
+            button: MouseButton::Left,
+        }) = message.data()
+        {
+            let bounds = self.bounding_rect();
+            let local = self.screen_to_local(*pos);
+            let index = ((local.x - bounds.x()) / self.bar_width(&bounds)) as usize;
+            if index < self.durations.len() {
+                self.selection = Some(index);
+                ui.send_message(FrameChartMessage::selection(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                    self.selection,
+                ));
+            }
+        }
+    }
+}
+
+pub struct FrameChartBuilder {
+    widget_builder: WidgetBuilder,
+}
+
+impl FrameChartBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self { widget_builder }
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let chart = FrameChart {
+            widget: self
+                .widget_builder
+                .with_background(Brush::Solid(Color::opaque(30, 30, 30)))
+                .with_foreground(Brush::Solid(Color::opaque(120, 220, 90)))
+                .build(),
+            durations: Default::default(),
+            selection: None,
+        };
+
+        ctx.add_node(UiNode::new(chart))
+    }
+}
+
+pub struct FlameGraphWindow {
+    pub window: Handle<UiNode>,
+    capture: Handle<UiNode>,
+    clear: Handle<UiNode>,
+    chart: Handle<UiNode>,
+    graph: Handle<UiNode>,
+    info: Handle<UiNode>,
+    frames: Vec<CapturedFrame>,
+    /// Index of the selected frame, `None` means that the latest frame is shown.
+    selection: Option<usize>,
+    time_since_refresh: f32,
+}
+
+impl FlameGraphWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let capture;
+        let clear;
+        let chart;
+        let graph;
+        let info;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(700.0).with_height(400.0))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_child({
+                                        capture = CheckBoxBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_enabled(profiler::is_available())
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_content(
+                                            TextBuilder::new(
+                                                WidgetBuilder::new().with_vertical_alignment(
+                                                    VerticalAlignment::Center,
+                                                ),
+                                            )
+                                            .with_text("Capture")
+                                            .build(ctx),
+                                        )
+                                        .checked(Some(profiler::is_capture_enabled()))
+                                        .build(ctx);
+                                        capture
+                                    })
+                                    .with_child({
+                                        clear = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(60.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Clear")
+                                        .build(ctx);
+                                        clear
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        )
+                        .with_child({
+                            chart = FrameChartBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .build(ctx);
+                            chart
+                        })
+                        .with_child({
+                            graph = FlameGraphBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .build(ctx);
+                            graph
+                        })
+                        .with_child({
+                            info = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(3)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_text(if profiler::is_available() {
+                                "Enable capturing to record frames."
+                            } else {
+                                "The profiler is not available, build the editor with \
+                                `enable_profiler` feature to use it."
+                            })
+                            .build(ctx);
+                            info
+                        }),
+                )
+                .add_row(Row::strict(26.0))
+                .add_row(Row::strict(60.0))
+                .add_row(Row::stretch())
+                .add_row(Row::strict(22.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .with_title(WindowTitle::text("Flame Graph"))
+            .build(ctx);
+
+        ctx.sender()
+            .send(WindowMessage::open(
+                window,
+                MessageDirection::ToWidget,
+                true,
+                true,
+            ))
+            .unwrap();
+
+        Self {
+            window,
+            capture,
+            clear,
+            chart,
+            graph,
+            info,
+            frames: Default::default(),
+            selection: None,
+            time_since_refresh: REFRESH_INTERVAL,
+        }
+    }
+
+    fn selected_frame(&self) -> Option<&CapturedFrame> {
+        match self.selection {
+            Some(index) => self.frames.get(index),
+            None => self.frames.last(),
+        }
+    }
+
+    fn frame_info(frame: &CapturedFrame) -> String {
+        format!(
+            "Frame #{}: {:.3} ms, {} scopes",
+            frame.index,
+            frame.duration * 1000.0,
+            frame.scopes.len()
+        )
+    }
+
+    fn sync(&self, ui: &UserInterface) {
+        ui.send_message(FrameChartMessage::durations(
+            self.chart,
+            MessageDirection::ToWidget,
+            self.frames.iter().map(|f| f.duration as f32).collect(),
+        ));
+        ui.send_message(FrameChartMessage::selection(
+            self.chart,
+            MessageDirection::ToWidget,
+            self.selection,
+        ));
+        let frame = self.selected_frame();
+        ui.send_message(FlameGraphMessage::frame(
+            self.graph,
+            MessageDirection::ToWidget,
+            frame.cloned(),
+        ));
+        if let Some(frame) = frame {
+            ui.send_message(TextMessage::text(
+                self.info,
+                MessageDirection::ToWidget,
+                Self::frame_info(frame),
+            ));
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+    ) -> StatisticsWindowAction {
+        if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                profiler::set_capture_enabled(false);
+                ui.send_message(WidgetMessage::remove(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+
+                return StatisticsWindowAction::Remove;
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.capture
+                && message.direction() == MessageDirection::FromWidget
+            {
+                profiler::set_capture_enabled(*value);
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.clear {
+                profiler::clear_captured_frames();
+                self.frames.clear();
+                self.selection = None;
+                self.sync(ui);
+            }
+        } else if let Some(FrameChartMessage::Selection(selection)) = message.data() {
+            if message.destination() == self.chart
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.selection = *selection;
+                self.sync(ui);
+            }
+        } else if let Some(FlameGraphMessage::Hovered(scope)) = message.data() {
+            if message.destination() == self.graph
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let text = match (scope, self.selected_frame()) {
+                    (Some(scope), Some(frame)) => format!(
+                        "{} (line {}): {:.3} ms, {:.1}% of the frame",
+                        short_scope_name(scope.name),
+                        scope.line,
+                        scope.duration * 1000.0,
+                        100.0 * scope.duration / frame.duration.max(f64::EPSILON)
+                    ),
+                    (None, Some(frame)) => Self::frame_info(frame),
+                    _ => String::new(),
+                };
+                ui.send_message(TextMessage::text(
+                    self.info,
+                    MessageDirection::ToWidget,
+                    text,
+                ));
+            }
+        }
+        StatisticsWindowAction::None
+    }
+
+    pub fn update(&mut self, dt: f32, ui: &UserInterface) {
+        // Frames are fetched only while capturing, so the user could look at the captured frames
+        // after the capturing was stopped.
+        if !profiler::is_capture_enabled() {
+            return;
+        }
+
+        self.time_since_refresh += dt;
+        if self.time_since_refresh < REFRESH_INTERVAL {
+            return;
+        }
+        self.time_since_refresh = 0.0;
+
+        // Keep the selected frame selected, while it is still in the list.
+        let selected_index = self
+            .selection
+            .and_then(|selection| self.frames.get(selection))
+            .map(|frame| frame.index);
+        self.frames = profiler::captured_frames();
+        self.selection = selected_index
+            .and_then(|index| self.frames.iter().position(|frame| frame.index == index));
+        self.sync(ui);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        flame_graph::{scope_at, short_scope_name, ROW_HEIGHT},
+        fyrox::core::{
+            algebra::Vector2,
+            math::Rect,
+            profiler::{CapturedFrame, CapturedScope},
+        },
+    };
+
+    #[test]
+    fn test_short_scope_name() {
+        assert_eq!(
+            short_scope_name("fyrox_impl::engine::Engine::render::scope"),
+            "Engine::render"
+        );
+        assert_eq!(short_scope_name("Physics"), "Physics");
+    }
+
+    #[test]
+    fn test_scope_at() {
+        let scope = |depth, start, duration| CapturedScope {
+            name: "scope",
+            line: 0,
+            depth,
+            start,
+            duration,
+        };
+        let frame = CapturedFrame {
+            index: 0,
+            duration: 1.0,
+            scopes: vec![scope(1, 0.5, 0.5), scope(0, 0.0, 1.0)],
+        };
+        let bounds = Rect::new(0.0, 0.0, 100.0, 100.0);
+
+        assert_eq!(scope_at(&frame, &bounds, Vector2::new(10.0, 5.0)), Some(1));
+        assert_eq!(
+            scope_at(&frame, &bounds, Vector2::new(75.0, ROW_HEIGHT + 5.0)),
+            Some(0)
+        );
+        assert_eq!(
+            scope_at(&frame, &bounds, Vector2::new(25.0, ROW_HEIGHT + 5.0)),
+            None
+        );
+    }
+}
@@ -25,6 +25,7 @@ use crate::{
                 },
                 state::{BlendFactor, BlendFunc, PipelineState},
             },
+            instance::{upload_bundle_instances, InstanceUniforms},
             MaterialContext, RenderPassStatistics, SceneRenderPass, SceneRenderPassContext,
         },
         scene::{mesh::surface::SurfaceData, node::Node, Scene},
@@ -235,7 +236,44 @@ impl SceneRenderPass for HighlightRenderPass {
             let camera_up = inv_view.up();
             let camera_side = inv_view.side();
 
-            for bundle in render_bundle_storage.bundles.iter() {
+            let camera_position = ctx.camera.global_position();
+            let z_near = ctx.camera.projection().z_near();
+            let z_far = ctx.camera.projection().z_far();
+
+            let bundles = upload_bundle_instances(
+                ctx.pipeline_state,
+                ctx.uniform_buffer_ring,
+                render_bundle_storage.bundles.iter(),
+                |bundle, instance| {
+                    let view_projection = if instance.depth_offset != 0.0 {
+                        let mut projection = ctx.camera.projection_matrix();
+                        projection[14] -= instance.depth_offset;
+                        projection * ctx.camera.view_matrix()
+                    } else {
+                        initial_view_projection
+                    };
+
+                    InstanceUniforms {
+                        world_matrix: instance.world_transform,
+                        view_projection_matrix: view_projection,
+                        use_skeletal_animation: bundle.is_skinned,
+                        use_pom: false,
+                        camera_position,
+                        camera_up_vector: camera_up,
+                        camera_side_vector: camera_side,
+                        z_near,
+                        z_far,
+                        light_position: Default::default(),
+                        blend_shapes_count: instance.blend_shapes_weights.len(),
+                        vertex_animation: instance.vertex_animation.as_ref(),
+                        ambient_light: Default::default(),
+                        light_link_mask: 0,
+                        precipitation_occlusion_matrix: None,
+                    }
+                },
+            );
+
+            for (bundle, instance_uniforms) in bundles.iter() {
                 let mut material_state = bundle.material.state();
 
                 let Some(material) = material_state.data() else {
@@ -264,15 +302,8 @@ impl SceneRenderPass for HighlightRenderPass {
                     continue;
                 };
 
-                for instance in bundle.instances.iter() {
-                    let view_projection = if instance.depth_offset != 0.0 {
-                        let mut projection = ctx.camera.projection_matrix();
-                        projection[14] -= instance.depth_offset;
-                        projection * ctx.camera.view_matrix()
-                    } else {
-                        initial_view_projection
-                    };
-
+                for (instance, instance_uniforms) in bundle.instances.iter().zip(instance_uniforms)
+                {
                     self.framebuffer.draw(
                         geometry,
                         ctx.pipeline_state,
@@ -285,20 +316,12 @@ impl SceneRenderPass for HighlightRenderPass {
                                 material,
                                 program_binding: &mut program_binding,
                                 texture_cache: ctx.texture_cache,
-                                world_matrix: &instance.world_transform,
-                                view_projection_matrix: &view_projection,
-                                wvp_matrix: &(view_projection * instance.world_transform),
+                                uniform_buffer_ring: ctx.uniform_buffer_ring,
+                                instance_uniforms,
                                 bone_matrices: &instance.bone_matrices,
-                                use_skeletal_animation: bundle.is_skinned,
-                                camera_position: &ctx.camera.global_position(),
-                                camera_up_vector: &camera_up,
-                                camera_side_vector: &camera_side,
-                                z_near: ctx.camera.projection().z_near(),
-                                z_far: ctx.camera.projection().z_far(),
-                                use_pom: false,
-                                light_position: &Default::default(),
                                 blend_shapes_storage: blend_shapes_storage.as_ref(),
                                 blend_shapes_weights: &instance.blend_shapes_weights,
+                                vertex_animation: instance.vertex_animation.as_ref(),
                                 normal_dummy: &ctx.normal_dummy,
                                 white_dummy: &ctx.white_dummy,
                                 black_dummy: &ctx.black_dummy,
@@ -306,8 +329,9 @@ impl SceneRenderPass for HighlightRenderPass {
                                 matrix_storage: ctx.matrix_storage,
                                 persistent_identifier: instance.persistent_identifier,
                                 light_data: None,
-                                ambient_light: Default::default(),
                                 scene_depth: Some(&ctx.depth_texture),
+                                scene_color: None,
+                                precipitation_occlusion: None,
                             });
                         },
                     )?;
@@ -0,0 +1,333 @@
+//! Performance HUD shows frame time breakdown of the editor, entity counts and usage statistics of
+//! the renderer caches. All the data is collected and shown locally, nothing is sent anywhere.
+
+use crate::{
+    fyrox::{
+        core::{
+            algebra::Vector2, color::Color, pool::Handle, reflect::prelude::*,
+            type_traits::prelude::*, uuid_provider, visitor::prelude::*,
+        },
+        engine::{Engine, GraphicsContext},
+        gui::{
+            brush::Brush,
+            define_constructor, define_widget_deref,
+            draw::{CommandTexture, Draw, DrawingContext},
+            grid::{Column, GridBuilder, Row},
+            message::{MessageDirection, UiMessage},
+            text::{TextBuilder, TextMessage},
+            widget::{Widget, WidgetBuilder, WidgetMessage},
+            window::{WindowBuilder, WindowMessage, WindowTitle},
+            BuildContext, Control, HorizontalAlignment, Thickness, UiNode, UserInterface,
+            VerticalAlignment,
+        },
+        renderer::cache::CacheStatistics,
+        scene::Scene,
+    },
+    stats::StatisticsWindowAction,
+};
+use std::{
+    collections::VecDeque,
+    fmt::Write,
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+
+/// Amount of frames stored in the history.
+const HISTORY_LENGTH: usize = 120;
+
+/// Interval (in seconds) between updates of the text of the HUD. Updating it every frame makes it
+/// unreadable.
+const REFRESH_INTERVAL: f32 = 0.25;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SparklineMessage {
+    Values(Vec<f32>),
+}
+
+impl SparklineMessage {
+    define_constructor!(SparklineMessage:Values => fn values(Vec<f32>), layout: false);
+}
+
+/// A tiny line chart without axes, it is scaled to fit the maximum value.
+#[derive(Debug, Clone, Visit, Reflect, ComponentProvider)]
+pub struct Sparkline {
+    widget: Widget,
+    values: Vec<f32>,
+}
+
+define_widget_deref!(Sparkline);
+
+uuid_provider!(Sparkline = "6f0d2c4b-9a3e-4e71-b5c8-2d7a1e9f4b60");
+
+impl Control for Sparkline {
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        let bounds = self.bounding_rect();
+
+        drawing_context.push_rect_filled(&bounds, None);
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        if self.values.len() < 2 {
+            return;
+        }
+
+        let max = self.values.iter().cloned().fold(f32::EPSILON, f32::max);
+        let step = bounds.w() / (self.values.len() - 1) as f32;
+        let point = |i: usize, value: f32| {
+            Vector2::new(
+                bounds.x() + i as f32 * step,
+                bounds.y() + bounds.h() * (1.0 - value / max),
+            )
+        };
+        for (i, pair) in self.values.windows(2).enumerate() {
+            drawing_context.push_line(point(i, pair[0]), point(i + 1, pair[1]), 1.0);
+        }
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.foreground(),
+            CommandTexture::None,
+            None,
+        );
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(SparklineMessage::Values(values)) = message.data() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                self.values.clone_from(values);
+            }
+        }
+    }
+}
+
+pub struct SparklineBuilder {
+    widget_builder: WidgetBuilder,
+}
+
+impl SparklineBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self { widget_builder }
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let sparkline = Sparkline {
+            widget: self
+                .widget_builder
+                .with_background(Brush::Solid(Color::opaque(30, 30, 30)))
+                .with_foreground(Brush::Solid(Color::opaque(120, 220, 90)))
+                .build(),
+            values: Default::default(),
+        };
+
+        ctx.add_node(UiNode::new(sparkline))
+    }
+}
+
+/// Time (in milliseconds) spent on the stages of a frame.
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+pub struct FrameTimings {
+    /// Update of the engine, the editor and the scenes.
+    pub update: f32,
+    /// Update of the user interfaces, including layout.
+    pub ui_layout: f32,
+    /// Submission of the rendering commands.
+    pub render_submit: f32,
+}
+
+impl FrameTimings {
+    pub fn total(&self) -> f32 {
+        self.update + self.ui_layout + self.render_submit
+    }
+}
+
+fn format_cache(text: &mut String, name: &str, stats: &CacheStatistics) {
+    let requests = stats.hits + stats.misses;
+    let hit_rate = if requests > 0 {
+        100.0 * stats.hits as f32 / requests as f32
+    } else {
+        100.0
+    };
+    let _ = writeln!(
+        text,
+        "  {}: {} alive, {:.1}% hits\n    ({} hits / {} misses / {} evicted)",
+        name, stats.alive, hit_rate, stats.hits, stats.misses, stats.evictions
+    );
+}
+
+pub struct PerformanceHud {
+    pub window: Handle<UiNode>,
+    text: Handle<UiNode>,
+    sparkline: Handle<UiNode>,
+    /// Total frame times of the last frames.
+    history: VecDeque<f32>,
+    /// Timings of the frame, that is being processed.
+    current: FrameTimings,
+    /// Timings of the last complete frame.
+    last: FrameTimings,
+    time_since_refresh: f32,
+}
+
+impl PerformanceHud {
+    pub fn new(ctx: &mut BuildContext, anchor: Handle<UiNode>) -> Self {
+        let text;
+        let sparkline;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(250.0).with_height(380.0))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            sparkline = SparklineBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .build(ctx);
+                            sparkline
+                        })
+                        .with_child({
+                            text = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .build(ctx);
+                            text
+                        }),
+                )
+                .add_row(Row::strict(50.0))
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .with_title(WindowTitle::text("Performance HUD"))
+            .build(ctx);
+
+        ctx.sender()
+            .send(WindowMessage::open_and_align(
+                window,
+                MessageDirection::ToWidget,
+                anchor,
+                HorizontalAlignment::Left,
+                VerticalAlignment::Top,
+                Thickness::uniform(2.0),
+                false,
+                false,
+            ))
+            .unwrap();
+
+        Self {
+            window,
+            text,
+            sparkline,
+            history: VecDeque::with_capacity(HISTORY_LENGTH),
+            current: Default::default(),
+            last: Default::default(),
+            time_since_refresh: REFRESH_INTERVAL,
+        }
+    }
+
+    pub fn handle_ui_message(
+        &self,
+        message: &UiMessage,
+        ui: &UserInterface,
+    ) -> StatisticsWindowAction {
+        if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                ui.send_message(WidgetMessage::remove(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+
+                return StatisticsWindowAction::Remove;
+            }
+        }
+        StatisticsWindowAction::None
+    }
+
+    /// Adds the time of a single update iteration. There could be multiple iterations per frame.
+    pub fn record_update(&mut self, update: Duration, ui_layout: Duration) {
+        self.current.update += update.as_secs_f32() * 1000.0;
+        self.current.ui_layout += ui_layout.as_secs_f32() * 1000.0;
+    }
+
+    /// Adds the time of rendering and finishes the current frame.
+    pub fn record_render(&mut self, render_submit: Duration) {
+        self.current.render_submit += render_submit.as_secs_f32() * 1000.0;
+
+        self.last = std::mem::take(&mut self.current);
+        if self.history.len() >= HISTORY_LENGTH {
+            self.history.pop_front();
+        }
+        self.history.push_back(self.last.total());
+    }
+
+    pub fn update(&mut self, dt: f32, current_scene: Option<Handle<Scene>>, engine: &Engine) {
+        self.time_since_refresh += dt;
+        if self.time_since_refresh < REFRESH_INTERVAL {
+            return;
+        }
+        self.time_since_refresh = 0.0;
+
+        let ui = engine.user_interfaces.first();
+        let mut text = String::new();
+
+        let average = if self.history.is_empty() {
+            0.0
+        } else {
+            self.history.iter().sum::<f32>() / self.history.len() as f32
+        };
+        let max = self.history.iter().cloned().fold(0.0, f32::max);
+        let _ = writeln!(
+            text,
+            "Frame: {:.2} ms (avg. {:.2}, max. {:.2})\n  Update: {:.2} ms\n  UI Layout: {:.2} ms\n  \
+            Render Submit: {:.2} ms",
+            self.last.total(),
+            average,
+            max,
+            self.last.update,
+            self.last.ui_layout,
+            self.last.render_submit
+        );
+
+        let _ = writeln!(text, "Entities:");
+        if let Some(scene) = current_scene.and_then(|s| engine.scenes.try_get(s)) {
+            let _ = writeln!(text, "  Scene Nodes: {}", scene.graph.node_count());
+        }
+        let _ = writeln!(text, "  Editor Widgets: {}", ui.nodes().alive_count());
+        let resource_manager = engine.resource_manager.state();
+        let _ = writeln!(
+            text,
+            "  Resources: {} loaded / {} total",
+            resource_manager.count_loaded_resources(),
+            resource_manager.count_registered_resources()
+        );
+        drop(resource_manager);
+
+        if let GraphicsContext::Initialized(ref graphics_context) = engine.graphics_context {
+            let stats = graphics_context.renderer.get_cache_statistics();
+            let _ = writeln!(text, "Caches:");
+            format_cache(&mut text, "Textures", &stats.textures);
+            format_cache(&mut text, "Geometry", &stats.geometry);
+            format_cache(&mut text, "Shaders", &stats.shaders);
+        }
+
+        ui.send_message(TextMessage::text(
+            self.text,
+            MessageDirection::ToWidget,
+            text,
+        ));
+        ui.send_message(SparklineMessage::values(
+            self.sparkline,
+            MessageDirection::ToWidget,
+            self.history.iter().cloned().collect(),
+        ));
+    }
+}
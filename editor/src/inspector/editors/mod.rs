@@ -2,7 +2,9 @@ use crate::{
     fyrox::{
         asset::{manager::ResourceManager, untyped::UntypedResource, Resource},
         core::{
+            color::ColorSpace,
             futures::executor::block_on,
+            noise::{Noise, NoiseKind},
             parking_lot::Mutex,
             pool::{ErasedHandle, Handle},
         },
@@ -34,18 +36,23 @@ use crate::{
         },
         scene::{
             self,
+            animation::{
+                pose::PoseLibrary, sequencer::sequence::Sequence, sprite::sheet::SpriteSheet,
+            },
             base::{
-                Base, LevelOfDetail, LodGroup, Mobility, Property, PropertyValue, ScriptRecord,
+                Base, LevelOfDetail, LodGroup, LodMetric, Mobility, Property, PropertyValue,
+                ScriptRecord,
             },
             camera::{
-                ColorGradingLut, Exposure, OrthographicProjection, PerspectiveProjection,
-                Projection, SkyBox,
+                CameraClearFlags, ColorGradingLut, Exposure, OrthographicProjection,
+                PerspectiveProjection, Projection, SkyBox,
             },
             collider::{
                 BallShape, BitMask, CapsuleShape, ColliderShape, ConeShape, ConvexPolyhedronShape,
                 CuboidShape, CylinderShape, GeometrySource, HeightfieldShape, InteractionGroups,
                 SegmentShape, TriangleShape, TrimeshShape,
             },
+            crowd::vat::VertexAnimation,
             dim2,
             graph::physics::CoefficientCombineRule,
             joint::*,
@@ -65,21 +72,34 @@ use crate::{
                 },
                 ParticleSystemRng,
             },
+            point_cloud::resource::PointCloudData,
             ragdoll::Limb,
+            replication::ReplicationSettings,
             rigidbody::RigidBodyType,
             sound::{
                 self,
+                compressor::Compressor,
+                equalizer::Equalizer,
                 filter::{
                     AllPassFilterEffect, BandPassFilterEffect, HighPassFilterEffect,
                     HighShelfFilterEffect, LowPassFilterEffect, LowShelfFilterEffect,
                 },
                 reverb::Reverb,
-                Attenuate, AudioBus, Biquad, DistanceModel, Effect, SoundBuffer,
+                Attenuate, AudioBus, AudioBusSend, Biquad, DistanceModel, Effect, SoundBuffer,
                 SoundBufferResource, Status,
             },
             terrain::{Chunk, Layer},
-            tilemap::{tileset::TileSet, Tile},
+            text3d::Text3DOrientation,
+            tilemap::{
+                brush::{BrushTile, BrushTransform, SymmetryMode, TileMapBrush, TileMapSymmetry},
+                lod::TileMapLod,
+                stamp::TileMapStampLibrary,
+                tileset::TileSet,
+                Tile,
+            },
             transform::Transform,
+            water::{fft::FftWaveSettings, GerstnerWave, WaveModel},
+            weather::precipitation::{PrecipitationKind, PrecipitationLayer},
         },
     },
     inspector::editors::{
@@ -90,6 +110,7 @@ use crate::{
         font::FontPropertyEditorDefinition,
         handle::{EntityKind, NodeHandlePropertyEditorDefinition},
         material::MaterialPropertyEditorDefinition,
+        noise::NoisePropertyEditorDefinition,
         resource::ResourceFieldPropertyEditorDefinition,
         script::ScriptPropertyEditorDefinition,
         spritesheet::SpriteSheetFramesContainerEditorDefinition,
@@ -104,6 +125,7 @@ pub mod animation;
 pub mod font;
 pub mod handle;
 pub mod material;
+pub mod noise;
 pub mod resource;
 pub mod script;
 pub mod spritesheet;
@@ -245,10 +267,14 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
 
     container.register_inheritable_vec_collection::<GeometrySource>();
     container.register_inheritable_inspectable::<GeometrySource>();
+    container.register_inheritable_inspectable::<dim2::collider::GeometrySource>();
+
+    container.register_inheritable_inspectable::<ReplicationSettings>();
 
     container.insert(make_status_enum_editor_definition());
 
     container.insert(EnumPropertyEditorDefinition::<LodGroup>::new_optional());
+    container.insert(EnumPropertyEditorDefinition::<LodMetric>::new());
     container.insert(InheritablePropertyEditorDefinition::<Option<LodGroup>>::new());
 
     {
@@ -261,6 +287,66 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
         container.register_inheritable_vec_collection::<Signal>();
     }
 
+    {
+        use crate::fyrox::{
+            core::math::Rect,
+            scene::animation::sprite::sheet::{SpriteClip, SpriteClipEvent},
+        };
+        container.register_inheritable_inspectable::<SpriteClip>();
+        container.register_inheritable_vec_collection::<SpriteClip>();
+        container.register_inheritable_inspectable::<SpriteClipEvent>();
+        container.register_inheritable_vec_collection::<SpriteClipEvent>();
+        container.register_inheritable_vec_collection::<u32>();
+        container.register_inheritable_vec_collection::<Rect<u32>>();
+    }
+
+    {
+        use crate::fyrox::scene::animation::sequencer::{
+            sequence::{
+                AnimationSection, AudioSection, CameraCut, PropertyTrack, SequenceTrack,
+                SequenceTrackKind,
+            },
+            SequenceBinding,
+        };
+        container.register_inheritable_inspectable::<SequenceTrack>();
+        container.register_inheritable_vec_collection::<SequenceTrack>();
+        container.register_inheritable_enum::<SequenceTrackKind, _>();
+        container.register_inheritable_inspectable::<CameraCut>();
+        container.register_inheritable_vec_collection::<CameraCut>();
+        container.register_inheritable_inspectable::<AnimationSection>();
+        container.register_inheritable_vec_collection::<AnimationSection>();
+        container.register_inheritable_inspectable::<AudioSection>();
+        container.register_inheritable_vec_collection::<AudioSection>();
+        container.register_inheritable_inspectable::<PropertyTrack>();
+        container.register_inheritable_inspectable::<SequenceBinding>();
+        container.register_inheritable_vec_collection::<SequenceBinding>();
+    }
+
+    {
+        use crate::fyrox::scene::crowd::{vat::VertexAnimationClip, CrowdInstance};
+        container.register_inheritable_inspectable::<CrowdInstance>();
+        container.register_inheritable_vec_collection::<CrowdInstance>();
+        container.register_inheritable_inspectable::<VertexAnimationClip>();
+        container.register_inheritable_vec_collection::<VertexAnimationClip>();
+    }
+
+    {
+        use crate::fyrox::material::shader::{
+            graph::{ShaderGraphChannel, ShaderGraphNodeKind},
+            SamplerFallback,
+        };
+        container.register_inheritable_enum::<ShaderGraphNodeKind, _>();
+        container.register_inheritable_enum::<ShaderGraphChannel, _>();
+        container.register_inheritable_enum::<SamplerFallback, _>();
+    }
+
+    container.register_inheritable_inspectable::<dim2::skin::Bone2D>();
+    container.register_inheritable_vec_collection::<dim2::skin::Bone2D>();
+    container.register_inheritable_enum::<dim2::light::Light2DKind, _>();
+    container.register_inheritable_enum::<scene::light::area::AreaLightShape, _>();
+    container.register_inheritable_inspectable::<dim2::occluder::OccluderSegment>();
+    container.register_inheritable_vec_collection::<dim2::occluder::OccluderSegment>();
+
     container.insert(ResourceFieldPropertyEditorDefinition::<Model>::new(
         Arc::new(Mutex::new(
             |resource_manager: &ResourceManager, path: &Path| {
@@ -330,6 +416,100 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     >::new());
     container.register_inheritable_vec_collection::<Option<TileSet>>();
 
+    container.insert(
+        ResourceFieldPropertyEditorDefinition::<TileMapStampLibrary>::new(
+            Arc::new(Mutex::new(
+                |resource_manager: &ResourceManager, path: &Path| {
+                    resource_manager
+                        .try_request::<TileMapStampLibrary>(path)
+                        .map(block_on)
+                },
+            )),
+            sender.clone(),
+        ),
+    );
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<Resource<TileMapStampLibrary>>,
+    >::new());
+    container.register_inheritable_vec_collection::<Option<TileMapStampLibrary>>();
+
+    container.insert(ResourceFieldPropertyEditorDefinition::<SpriteSheet>::new(
+        Arc::new(Mutex::new(
+            |resource_manager: &ResourceManager, path: &Path| {
+                resource_manager
+                    .try_request::<SpriteSheet>(path)
+                    .map(block_on)
+            },
+        )),
+        sender.clone(),
+    ));
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<Resource<SpriteSheet>>,
+    >::new());
+    container.register_inheritable_vec_collection::<Option<SpriteSheet>>();
+
+    container.insert(ResourceFieldPropertyEditorDefinition::<PoseLibrary>::new(
+        Arc::new(Mutex::new(
+            |resource_manager: &ResourceManager, path: &Path| {
+                resource_manager
+                    .try_request::<PoseLibrary>(path)
+                    .map(block_on)
+            },
+        )),
+        sender.clone(),
+    ));
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<Resource<PoseLibrary>>,
+    >::new());
+    container.register_inheritable_vec_collection::<Option<PoseLibrary>>();
+
+    container.insert(ResourceFieldPropertyEditorDefinition::<Sequence>::new(
+        Arc::new(Mutex::new(
+            |resource_manager: &ResourceManager, path: &Path| {
+                resource_manager.try_request::<Sequence>(path).map(block_on)
+            },
+        )),
+        sender.clone(),
+    ));
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<Resource<Sequence>>,
+    >::new());
+    container.register_inheritable_vec_collection::<Option<Sequence>>();
+
+    container.insert(
+        ResourceFieldPropertyEditorDefinition::<VertexAnimation>::new(
+            Arc::new(Mutex::new(
+                |resource_manager: &ResourceManager, path: &Path| {
+                    resource_manager
+                        .try_request::<VertexAnimation>(path)
+                        .map(block_on)
+                },
+            )),
+            sender.clone(),
+        ),
+    );
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<Resource<VertexAnimation>>,
+    >::new());
+    container.register_inheritable_vec_collection::<Option<VertexAnimation>>();
+
+    container.insert(
+        ResourceFieldPropertyEditorDefinition::<PointCloudData>::new(
+            Arc::new(Mutex::new(
+                |resource_manager: &ResourceManager, path: &Path| {
+                    resource_manager
+                        .try_request::<PointCloudData>(path)
+                        .map(block_on)
+                },
+            )),
+            sender.clone(),
+        ),
+    );
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<Resource<PointCloudData>>,
+    >::new());
+    container.register_inheritable_vec_collection::<Option<PointCloudData>>();
+
     container.insert(ResourceFieldPropertyEditorDefinition::<Shader>::new(
         Arc::new(Mutex::new(
             |resource_manager: &ResourceManager, path: &Path| {
@@ -369,6 +549,11 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(InspectablePropertyEditorDefinition::<LowShelfFilterEffect>::new());
     container.insert(InspectablePropertyEditorDefinition::<HighShelfFilterEffect>::new());
     container.insert(InspectablePropertyEditorDefinition::<Reverb>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Equalizer>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Compressor>::new());
+
+    container.insert(InspectablePropertyEditorDefinition::<AudioBusSend>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<AudioBusSend>::new());
 
     container.register_inheritable_enum::<Emitter, _>();
 
@@ -395,6 +580,13 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
 
     container.register_inheritable_inspectable::<SkyBox>();
 
+    container.register_inheritable_vec_collection::<GerstnerWave>();
+    container.register_inheritable_inspectable::<GerstnerWave>();
+    container.register_inheritable_inspectable::<FftWaveSettings>();
+
+    container.register_inheritable_vec_collection::<PrecipitationLayer>();
+    container.register_inheritable_inspectable::<PrecipitationLayer>();
+
     container.register_inheritable_enum::<dim2::collider::ColliderShape, _>();
     container.register_inheritable_enum::<CoefficientCombineRule, _>();
     container.register_inheritable_enum::<CompressionOptions, _>();
@@ -407,11 +599,14 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_enum::<Mobility, _>();
     container.register_inheritable_enum::<RigidBodyType, _>();
     container.register_inheritable_enum::<Exposure, _>();
+    container.register_inheritable_enum::<CameraClearFlags, _>();
     container.register_inheritable_enum::<FrustumSplitOptions, _>();
     container.register_inheritable_enum::<MaterialSearchOptions, _>();
     container.register_inheritable_enum::<DistanceModel, _>();
     container.register_inheritable_enum::<sound::Renderer, _>();
     container.register_inheritable_enum::<RenderPath, _>();
+    container.register_inheritable_enum::<WaveModel, _>();
+    container.register_inheritable_enum::<PrecipitationKind, _>();
 
     container.insert(EnumPropertyEditorDefinition::<Vec<ScriptRecord>>::new_optional());
     container.insert(VecCollectionPropertyEditorDefinition::<ScriptRecord>::new());
@@ -436,6 +631,7 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<dim2::collider::TrimeshShape>();
     container.register_inheritable_inspectable::<HeightfieldShape>();
     container.register_inheritable_inspectable::<dim2::collider::HeightfieldShape>();
+    container.register_inheritable_inspectable::<dim2::collider::TileMapShape>();
     container.register_inheritable_inspectable::<ConvexPolyhedronShape>();
     container.insert(SpriteSheetFramesContainerEditorDefinition);
 
@@ -479,13 +675,26 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(EnumPropertyEditorDefinition::<PolygonFillMode>::new());
 
     container.insert(EnumPropertyEditorDefinition::<MipFilter>::new());
+    container.insert(EnumPropertyEditorDefinition::<ColorSpace>::new());
+    container.insert(EnumPropertyEditorDefinition::<NoiseKind>::new());
+    container.insert(NoisePropertyEditorDefinition);
+    container.insert(InheritablePropertyEditorDefinition::<Noise>::new());
 
     container.register_inheritable_inspectable::<Limb>();
     container.insert(VecCollectionPropertyEditorDefinition::<Limb>::new());
 
     container.register_inheritable_enum::<BatchingMode, _>();
 
+    container.register_inheritable_enum::<Text3DOrientation, _>();
+
     container.register_inheritable_inspectable::<Tile>();
+    container.register_inheritable_inspectable::<TileMapLod>();
+    container.register_inheritable_inspectable::<BrushTransform>();
+    container.register_inheritable_inspectable::<BrushTile>();
+    container.register_inheritable_vec_collection::<BrushTile>();
+    container.register_inheritable_inspectable::<TileMapBrush>();
+    container.register_inheritable_inspectable::<TileMapSymmetry>();
+    container.register_inheritable_enum::<SymmetryMode, _>();
     container.register_inheritable_vec_collection::<Tile>();
 
     container
@@ -0,0 +1,275 @@
+//! Property editor for [`Noise`], that shows properties of the noise together with a live preview
+//! of it.
+
+use crate::fyrox::{
+    asset::untyped::ResourceKind,
+    core::{
+        algebra::Vector2, noise::Noise, pool::Handle, reflect::prelude::*, type_traits::prelude::*,
+        uuid_provider, visitor::prelude::*,
+    },
+    graph::BaseSceneGraph,
+    gui::{
+        define_constructor,
+        image::{ImageBuilder, ImageMessage},
+        inspector::{
+            editors::{
+                PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+                PropertyEditorMessageContext, PropertyEditorTranslationContext,
+            },
+            make_expander_container, FieldKind, Inspector, InspectorBuilder, InspectorContext,
+            InspectorError, InspectorMessage, PropertyChanged,
+        },
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        widget::{Widget, WidgetBuilder},
+        BuildContext, Control, HorizontalAlignment, Thickness, UiNode, UserInterface,
+    },
+    resource::texture::{TextureKind, TexturePixelKind, TextureResource, TextureResourceExtension},
+};
+use std::{
+    any::TypeId,
+    ops::{Deref, DerefMut},
+};
+
+/// Size of the preview image in pixels.
+const PREVIEW_SIZE: u32 = 64;
+
+/// Size of the area (in noise space units), that is shown in the preview.
+const PREVIEW_EXTENT: f32 = 32.0;
+
+/// Creates a grayscale image of the noise. The image covers [0; 32] range of the noise space on
+/// both axes, so the preview shows the same pattern as a 32x32 meters patch of a terrain would
+/// have, for example.
+pub fn make_noise_preview(noise: &Noise) -> Option<TextureResource> {
+    let scale = PREVIEW_EXTENT / PREVIEW_SIZE as f32;
+    let mut bytes = Vec::with_capacity((PREVIEW_SIZE * PREVIEW_SIZE * 4) as usize);
+    for y in 0..PREVIEW_SIZE {
+        for x in 0..PREVIEW_SIZE {
+            let value = noise.sample2(Vector2::new(x as f32, y as f32).scale(scale));
+            let brightness = ((value * 0.5 + 0.5) * 255.0) as u8;
+            bytes.extend_from_slice(&[brightness, brightness, brightness, 255]);
+        }
+    }
+    TextureResource::from_bytes(
+        TextureKind::Rectangle {
+            width: PREVIEW_SIZE,
+            height: PREVIEW_SIZE,
+        },
+        TexturePixelKind::RGBA8,
+        bytes,
+        ResourceKind::Embedded,
+    )
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum NoiseEditorMessage {
+    /// Sets the noise, that is shown in the preview.
+    Noise(Noise),
+}
+
+impl NoiseEditorMessage {
+    define_constructor!(NoiseEditorMessage:Noise => fn noise(Noise), layout: false);
+}
+
+/// A nested inspector for noise properties with a preview image below it.
+#[derive(Clone, Debug, Visit, Reflect, ComponentProvider)]
+pub struct NoiseEditor {
+    widget: Widget,
+    inspector: Handle<UiNode>,
+    image: Handle<UiNode>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    noise: Noise,
+}
+
+impl Deref for NoiseEditor {
+    type Target = Widget;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl DerefMut for NoiseEditor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+uuid_provider!(NoiseEditor = "0b8f6e52-7d1c-4c8e-b0a9-3e5f2d7c6a14");
+
+impl Control for NoiseEditor {
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
+            // Re-emit changes of the inner inspector on behalf of the editor, so the outer
+            // inspector could find the property, that is edited by this editor.
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                ui.send_message(InspectorMessage::property_changed(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                    property_changed.clone(),
+                ));
+            }
+        } else if let Some(NoiseEditorMessage::Noise(noise)) = message.data() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+                && &self.noise != noise
+            {
+                self.noise = noise.clone();
+                ui.send_message(ImageMessage::texture(
+                    self.image,
+                    MessageDirection::ToWidget,
+                    make_noise_preview(&self.noise).map(Into::into),
+                ));
+            }
+        }
+    }
+}
+
+pub struct NoiseEditorBuilder {
+    widget_builder: WidgetBuilder,
+    noise: Noise,
+}
+
+impl NoiseEditorBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            noise: Default::default(),
+        }
+    }
+
+    pub fn with_noise(mut self, noise: Noise) -> Self {
+        self.noise = noise;
+        self
+    }
+
+    pub fn build(self, context: InspectorContext, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let inspector = InspectorBuilder::new(WidgetBuilder::new())
+            .with_context(context)
+            .build(ctx);
+        let image = ImageBuilder::new(
+            WidgetBuilder::new()
+                .with_width(128.0)
+                .with_height(128.0)
+                .with_margin(Thickness::uniform(2.0))
+                .with_horizontal_alignment(HorizontalAlignment::Left),
+        )
+        .with_opt_texture(make_noise_preview(&self.noise).map(Into::into))
+        .build(ctx);
+
+        let editor = NoiseEditor {
+            widget: self
+                .widget_builder
+                .with_child(
+                    StackPanelBuilder::new(
+                        WidgetBuilder::new().with_child(inspector).with_child(image),
+                    )
+                    .build(ctx),
+                )
+                .build(),
+            inspector,
+            image,
+            noise: self.noise,
+        };
+
+        ctx.add_node(UiNode::new(editor))
+    }
+}
+
+#[derive(Debug)]
+pub struct NoisePropertyEditorDefinition;
+
+impl PropertyEditorDefinition for NoisePropertyEditorDefinition {
+    fn value_type_id(&self) -> TypeId {
+        TypeId::of::<Noise>()
+    }
+
+    fn create_instance(
+        &self,
+        ctx: PropertyEditorBuildContext,
+    ) -> Result<PropertyEditorInstance, InspectorError> {
+        let value = ctx.property_info.cast_value::<Noise>()?;
+
+        let inspector_context = InspectorContext::from_object(
+            value,
+            ctx.build_context,
+            ctx.definition_container.clone(),
+            ctx.environment.clone(),
+            ctx.sync_flag,
+            ctx.layer_index + 1,
+            ctx.generate_property_string_values,
+            ctx.filter,
+        );
+
+        let editor = NoiseEditorBuilder::new(WidgetBuilder::new())
+            .with_noise(value.clone())
+            .build(inspector_context, ctx.build_context);
+
+        let container = make_expander_container(
+            ctx.layer_index,
+            ctx.property_info.display_name,
+            ctx.property_info.description,
+            Handle::NONE,
+            editor,
+            ctx.build_context,
+        );
+
+        Ok(PropertyEditorInstance::Custom { container, editor })
+    }
+
+    fn create_message(
+        &self,
+        ctx: PropertyEditorMessageContext,
+    ) -> Result<Option<UiMessage>, InspectorError> {
+        let value = ctx.property_info.cast_value::<Noise>()?;
+
+        let inspector = ctx
+            .ui
+            .node(ctx.instance)
+            .cast::<NoiseEditor>()
+            .expect("Must be NoiseEditor!")
+            .inspector;
+        let inspector_context = ctx
+            .ui
+            .node(inspector)
+            .cast::<Inspector>()
+            .expect("Must be Inspector!")
+            .context()
+            .clone();
+        inspector_context
+            .sync(
+                value,
+                ctx.ui,
+                ctx.layer_index + 1,
+                ctx.generate_property_string_values,
+                ctx.filter,
+            )
+            .map_err(InspectorError::Group)?;
+
+        Ok(Some(NoiseEditorMessage::noise(
+            ctx.instance,
+            MessageDirection::ToWidget,
+            value.clone(),
+        )))
+    }
+
+    fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
+        if let Some(InspectorMessage::PropertyChanged(msg)) = ctx.message.data::<InspectorMessage>()
+        {
+            if ctx.message.direction() == MessageDirection::FromWidget {
+                return Some(PropertyChanged {
+                    name: ctx.name.to_owned(),
+                    owner_type_id: ctx.owner_type_id,
+                    value: FieldKind::Inspectable(Box::new(msg.clone())),
+                });
+            }
+        }
+
+        None
+    }
+}
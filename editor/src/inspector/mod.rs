@@ -10,6 +10,7 @@ use crate::{
             reflect::prelude::*,
         },
         engine::SerializationContext,
+        fxhash::FxHashMap,
         graph::BaseSceneGraph,
         gui::{
             button::ButtonMessage,
@@ -20,6 +21,8 @@ use crate::{
             },
             message::{MessageDirection, UiMessage},
             scroll_viewer::ScrollViewerBuilder,
+            searchbar::{SearchBarBuilder, SearchBarMessage},
+            stack_panel::StackPanelBuilder,
             text::{TextBuilder, TextMessage},
             widget::WidgetBuilder,
             window::{WindowBuilder, WindowTitle},
@@ -28,11 +31,15 @@ use crate::{
         scene::SceneContainer,
     },
     gui::make_image_button_with_tooltip,
-    inspector::editors::make_property_editors_container,
+    inspector::{
+        editors::make_property_editors_container, handlers::node::SceneNodePropertyChangedHandler,
+        prefab::PrefabParametersSection,
+    },
     load_image,
     message::MessageSender,
     scene::{controller::SceneController, GameScene, Selection},
     send_sync_message,
+    settings::Settings,
     ui_scene::UiScene,
     utils::window_content,
     Brush, Engine, Message, Mode, WidgetMessage, WrapMode, MSG_SYNC_FLAG,
@@ -41,6 +48,7 @@ use std::{any::Any, sync::Arc};
 
 pub mod editors;
 pub mod handlers;
+pub mod prefab;
 
 #[derive(Clone, Debug)]
 pub struct AnimationDefinition {
@@ -79,6 +87,61 @@ pub struct Inspector {
     warning_text: Handle<UiNode>,
     type_name_text: Handle<UiNode>,
     docs_button: Handle<UiNode>,
+    search_bar: Handle<UiNode>,
+    record_buttons_visible: bool,
+    /// Type name of the inspected object.
+    type_name: String,
+    has_mixed_values: bool,
+    prefab_parameters: PrefabParametersSection,
+}
+
+/// Maximum nesting level of properties, that are compared to find mixed values.
+const MIXED_VALUES_MAX_DEPTH: usize = 4;
+
+/// Collects string representations of the properties of the given object. Nested properties are
+/// collected recursively, the keys are paths of the properties.
+fn collect_property_values(
+    object: &dyn Reflect,
+    prefix: &str,
+    depth: usize,
+    values: &mut FxHashMap<String, String>,
+) {
+    object.fields_info(&mut |fields_info| {
+        for info in fields_info {
+            let path = if prefix.is_empty() {
+                info.name.to_string()
+            } else {
+                format!("{}.{}", prefix, info.name)
+            };
+
+            let mut has_fields = false;
+            if depth < MIXED_VALUES_MAX_DEPTH {
+                info.reflect_value
+                    .fields(&mut |fields| has_fields = !fields.is_empty());
+            }
+
+            if has_fields {
+                collect_property_values(info.reflect_value, &path, depth + 1, values);
+            } else {
+                values.insert(path, format!("{:?}", info.reflect_value));
+            }
+        }
+    })
+}
+
+/// Returns sorted paths of the properties, that have different values in the given objects.
+fn find_mixed_values(objects: &[FxHashMap<String, String>]) -> Vec<String> {
+    let Some((first, rest)) = objects.split_first() else {
+        return Default::default();
+    };
+
+    let mut mixed = first
+        .iter()
+        .filter(|(path, value)| rest.iter().any(|other| other.get(*path) != Some(value)))
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<_>>();
+    mixed.sort();
+    mixed
 }
 
 fn fetch_available_animations(
@@ -142,13 +205,15 @@ impl Inspector {
         let property_editors = Arc::new(make_property_editors_container(sender));
 
         let warning_text_str =
-            "Multiple objects are selected, showing properties of the first object only!\
-            Only common properties will be editable!";
+            "Multiple objects are selected, properties with different values are highlighted! \
+            Changes are applied to every selected object!";
 
         let warning_text;
         let type_name_text;
         let inspector;
         let docs_button;
+        let search_bar;
+        let prefab_parameters = PrefabParametersSection::new(ctx);
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("Inspector"))
             .with_title(WindowTitle::text("Inspector"))
             .with_content(
@@ -200,18 +265,37 @@ impl Inspector {
                             .add_column(Column::auto())
                             .build(ctx),
                         )
+                        .with_child({
+                            search_bar = SearchBarBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_height(22.0)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .build(ctx);
+                            search_bar
+                        })
                         .with_child(
-                            ScrollViewerBuilder::new(WidgetBuilder::new().on_row(2))
-                                .with_content({
-                                    inspector =
-                                        InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
-                                    inspector
-                                })
+                            ScrollViewerBuilder::new(WidgetBuilder::new().on_row(3))
+                                .with_content(
+                                    StackPanelBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_child(prefab_parameters.section)
+                                            .with_child({
+                                                inspector =
+                                                    InspectorBuilder::new(WidgetBuilder::new())
+                                                        .build(ctx);
+                                                inspector
+                                            }),
+                                    )
+                                    .build(ctx),
+                                )
                                 .build(ctx),
                         ),
                 )
                 .add_row(Row::auto())
                 .add_row(Row::auto())
+                .add_row(Row::auto())
                 .add_row(Row::stretch())
                 .add_column(Column::stretch())
                 .build(ctx),
@@ -225,6 +309,11 @@ impl Inspector {
             warning_text,
             type_name_text,
             docs_button,
+            search_bar,
+            record_buttons_visible: false,
+            type_name: Default::default(),
+            has_mixed_values: false,
+            prefab_parameters,
         }
     }
 
@@ -249,6 +338,7 @@ impl Inspector {
         controller: &dyn SceneController,
         engine: &mut Engine,
         sender: &MessageSender,
+        settings: &Settings,
     ) {
         let mut need_clear = true;
 
@@ -273,6 +363,7 @@ impl Inspector {
                         engine.serialization_context.clone(),
                         &available_animations,
                         sender,
+                        settings,
                     );
 
                     need_clear = false;
@@ -286,6 +377,76 @@ impl Inspector {
 
         if need_clear {
             self.clear(ui);
+        } else {
+            self.sync_mixed_values(editor_selection, controller, engine);
+        }
+
+        self.sync_prefab_parameters(editor_selection, controller, engine, sender);
+    }
+
+    /// Shows parameters of the selected prefab instance (if any).
+    fn sync_prefab_parameters(
+        &mut self,
+        editor_selection: &Selection,
+        controller: &dyn SceneController,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) {
+        let ui = engine.user_interfaces.first_mut();
+
+        let selected = controller
+            .downcast_ref::<GameScene>()
+            .zip(editor_selection.as_graph())
+            .filter(|(_, selection)| selection.nodes.len() == 1);
+
+        if let Some((game_scene, selection)) = selected {
+            let environment = Arc::new(EditorEnvironment {
+                resource_manager: engine.resource_manager.clone(),
+                serialization_context: engine.serialization_context.clone(),
+                available_animations: Default::default(),
+                sender: sender.clone(),
+            });
+
+            self.prefab_parameters.sync(
+                &engine.scenes[game_scene.scene].graph,
+                selection.nodes[0],
+                ui,
+                &self.property_editors,
+                Some(environment),
+            );
+        } else {
+            self.prefab_parameters.reset(ui);
+        }
+    }
+
+    /// Highlights the properties, that have different values in the selected entities.
+    fn sync_mixed_values(
+        &mut self,
+        editor_selection: &Selection,
+        controller: &dyn SceneController,
+        engine: &mut Engine,
+    ) {
+        let mut mixed_values = Vec::new();
+        if editor_selection.len() > 1 {
+            let mut objects = Vec::new();
+            controller.for_each_selected_entity(editor_selection, &engine.scenes, &mut |entity| {
+                let mut values = FxHashMap::default();
+                collect_property_values(entity, "", 0, &mut values);
+                objects.push(values);
+            });
+            mixed_values = find_mixed_values(&objects);
+        }
+
+        if !mixed_values.is_empty() || self.has_mixed_values {
+            self.has_mixed_values = !mixed_values.is_empty();
+            engine
+                .user_interfaces
+                .first()
+                .send_message(InspectorMessage::mixed_values(
+                    self.inspector,
+                    MessageDirection::ToWidget,
+                    mixed_values,
+                ));
         }
     }
 
@@ -297,6 +458,7 @@ impl Inspector {
         serialization_context: Arc<SerializationContext>,
         available_animations: &[AnimationDefinition],
         sender: &MessageSender,
+        settings: &Settings,
     ) {
         let environment = Arc::new(EditorEnvironment {
             resource_manager,
@@ -322,6 +484,14 @@ impl Inspector {
             context,
         ));
 
+        self.type_name = obj.type_name().to_string();
+        self.has_mixed_values = false;
+        ui.send_message(InspectorMessage::favorites(
+            self.inspector,
+            MessageDirection::ToWidget,
+            settings.inspector.favorites(&self.type_name).to_vec(),
+        ));
+
         send_sync_message(
             ui,
             TextMessage::text(
@@ -340,6 +510,31 @@ impl Inspector {
         ));
     }
 
+    /// Shows or hides "record" buttons next to the properties of the inspected object. The buttons
+    /// are used to record animation keys, see [`Self::recorded_property`].
+    pub fn set_record_buttons_visible(&mut self, ui: &UserInterface, visible: bool) {
+        if self.record_buttons_visible != visible {
+            self.record_buttons_visible = visible;
+            ui.send_message(InspectorMessage::record_buttons_visibility(
+                self.inspector,
+                MessageDirection::ToWidget,
+                visible,
+            ));
+        }
+    }
+
+    /// Returns a path of a property, which "record" button was clicked.
+    pub fn recorded_property<'a>(&self, message: &'a UiMessage) -> Option<&'a str> {
+        if message.destination() == self.inspector
+            && message.direction() == MessageDirection::FromWidget
+        {
+            if let Some(InspectorMessage::RecordProperty(path)) = message.data() {
+                return Some(path);
+            }
+        }
+        None
+    }
+
     pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
         ui.send_message(WidgetMessage::enabled(
             window_content(self.window, ui),
@@ -355,6 +550,7 @@ impl Inspector {
         controller: &mut dyn SceneController,
         engine: &mut Engine,
         sender: &MessageSender,
+        settings: &mut Settings,
     ) {
         if message.destination() == self.inspector
             && message.direction() == MessageDirection::FromWidget
@@ -363,6 +559,47 @@ impl Inspector {
                 message.data::<InspectorMessage>()
             {
                 controller.on_property_changed(args, editor_selection, engine);
+            } else if let Some(InspectorMessage::ToggleFavorite(property)) =
+                message.data::<InspectorMessage>()
+            {
+                settings
+                    .inspector
+                    .toggle_favorite(&self.type_name, property);
+                engine
+                    .user_interfaces
+                    .first()
+                    .send_message(InspectorMessage::favorites(
+                        self.inspector,
+                        MessageDirection::ToWidget,
+                        settings.inspector.favorites(&self.type_name).to_vec(),
+                    ));
+            }
+        } else if let Some(SearchBarMessage::Text(text)) = message.data() {
+            if message.destination() == self.search_bar
+                && message.direction() == MessageDirection::FromWidget
+            {
+                engine
+                    .user_interfaces
+                    .first()
+                    .send_message(InspectorMessage::search(
+                        self.inspector,
+                        MessageDirection::ToWidget,
+                        text.clone(),
+                    ));
+            }
+        } else if let Some((node, args)) = self
+            .prefab_parameters
+            .translate_message(message, &self.property_editors)
+        {
+            if let Some(game_scene) = controller.downcast_ref::<GameScene>() {
+                let graph = &mut engine.scenes[game_scene.scene].graph;
+                if graph.is_valid_handle(node) {
+                    if let Some(command) =
+                        SceneNodePropertyChangedHandler.handle(&args, node, &mut graph[node])
+                    {
+                        sender.send(Message::DoCommand(command));
+                    }
+                }
             }
         } else if let Some(ButtonMessage::Click) = message.data() {
             if message.destination() == self.docs_button {
@@ -373,3 +610,53 @@ impl Inspector {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        fyrox::{core::reflect::prelude::*, fxhash::FxHashMap},
+        inspector::{collect_property_values, find_mixed_values},
+    };
+
+    #[derive(Reflect, Debug, Default)]
+    struct Inner {
+        a: u32,
+        b: String,
+    }
+
+    #[derive(Reflect, Debug, Default)]
+    struct Outer {
+        name: String,
+        inner: Inner,
+    }
+
+    fn values(object: &Outer) -> FxHashMap<String, String> {
+        let mut values = FxHashMap::default();
+        collect_property_values(object, "", 0, &mut values);
+        values
+    }
+
+    #[test]
+    fn test_find_mixed_values() {
+        let first = Outer {
+            name: "First".to_string(),
+            inner: Inner {
+                a: 1,
+                b: "Same".to_string(),
+            },
+        };
+        let second = Outer {
+            name: "Second".to_string(),
+            inner: Inner {
+                a: 2,
+                b: "Same".to_string(),
+            },
+        };
+
+        assert_eq!(find_mixed_values(&[values(&first)]), Vec::<String>::new());
+        assert_eq!(
+            find_mixed_values(&[values(&first), values(&second)]),
+            vec!["inner.a".to_string(), "name".to_string()]
+        );
+    }
+}
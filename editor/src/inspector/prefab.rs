@@ -0,0 +1,318 @@
+//! A compact section of the inspector, that shows parameters of a prefab instance. See
+//! [`crate::fyrox::resource::model::parameter::PrefabParameters`] docs for more info about the parameters.
+
+use crate::{
+    fyrox::{
+        core::{log::Log, pool::Handle, reflect::prelude::*},
+        graph::BaseSceneGraph,
+        gui::{
+            grid::{Column, GridBuilder, Row},
+            inspector::{
+                editors::{
+                    PropertyEditorBuildContext, PropertyEditorDefinitionContainer,
+                    PropertyEditorInstance, PropertyEditorMessageContext,
+                    PropertyEditorTranslationContext,
+                },
+                InspectorEnvironment, PropertyChanged,
+            },
+            message::{MessageDirection, UiMessage},
+            stack_panel::StackPanelBuilder,
+            text::TextBuilder,
+            widget::{WidgetBuilder, WidgetMessage},
+            BuildContext, Thickness, UiNode, UserInterface, VerticalAlignment,
+        },
+        resource::model::parameter::{instance_parameters, resolve_parameter},
+        scene::{graph::Graph, node::Node},
+    },
+    MSG_SYNC_FLAG,
+};
+use std::{any::TypeId, sync::Arc};
+
+struct ParameterEntry {
+    name: String,
+    node: Handle<Node>,
+    property: String,
+    row: Handle<UiNode>,
+    editor: Handle<UiNode>,
+    value_type_id: TypeId,
+    owner_type_id: TypeId,
+}
+
+/// Calls the given function with the field info of the property at the given path. Only paths to fields
+/// of structures are supported (no indices).
+fn with_field_info(object: &dyn Reflect, path: &str, func: &mut dyn FnMut(&FieldInfo)) {
+    let (parent_path, field) = match path.rsplit_once('.') {
+        Some((parent_path, field)) => (Some(parent_path), field),
+        None => (None, path),
+    };
+
+    let mut visit_parent = |parent: &dyn Reflect| {
+        parent.fields_info(&mut |fields_info| {
+            if let Some(info) = fields_info.iter().find(|info| info.name == field) {
+                func(info)
+            }
+        })
+    };
+
+    match parent_path {
+        Some(parent_path) => object.resolve_path(parent_path, &mut |result| {
+            if let Ok(parent) = result {
+                visit_parent(parent)
+            }
+        }),
+        None => visit_parent(object),
+    }
+}
+
+/// Shows parameters of the selected prefab instance with the editors of the properties, that are bound
+/// to the parameters. Bindings to nested prefabs are resolved, so the section always edits the actual
+/// nodes of the instance.
+pub struct PrefabParametersSection {
+    pub section: Handle<UiNode>,
+    list: Handle<UiNode>,
+    instance: Handle<Node>,
+    entries: Vec<ParameterEntry>,
+}
+
+impl PrefabParametersSection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let list = StackPanelBuilder::new(WidgetBuilder::new()).build(ctx);
+        let section = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_child(
+                    TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(4.0)))
+                        .with_text("Prefab Parameters")
+                        .build(ctx),
+                )
+                .with_child(list),
+        )
+        .build(ctx);
+
+        Self {
+            section,
+            list,
+            instance: Default::default(),
+            entries: Default::default(),
+        }
+    }
+
+    fn clear(&mut self, ui: &UserInterface) {
+        for entry in self.entries.drain(..) {
+            ui.send_message(WidgetMessage::remove(entry.row, MessageDirection::ToWidget));
+        }
+        self.instance = Handle::NONE;
+    }
+
+    fn is_up_to_date(
+        &self,
+        instance: Handle<Node>,
+        bindings: &[(String, Handle<Node>, String)],
+    ) -> bool {
+        self.instance == instance
+            && self.entries.len() == bindings.len()
+            && self
+                .entries
+                .iter()
+                .zip(bindings)
+                .all(|(entry, (name, node, property))| {
+                    &entry.name == name && entry.node == *node && &entry.property == property
+                })
+    }
+
+    /// Synchronizes the section with the given node. The section is shown only if the node is a root of
+    /// a prefab instance and the prefab has at least one parameter.
+    pub fn sync(
+        &mut self,
+        graph: &Graph,
+        instance: Handle<Node>,
+        ui: &mut UserInterface,
+        definition_container: &Arc<PropertyEditorDefinitionContainer>,
+        environment: Option<Arc<dyn InspectorEnvironment>>,
+    ) {
+        let parameters = graph
+            .try_get(instance)
+            .filter(|node| node.is_resource_instance_root())
+            .and_then(|_| instance_parameters(graph, instance).ok())
+            .unwrap_or_default();
+
+        let bindings = parameters
+            .iter()
+            .filter_map(
+                |parameter| match resolve_parameter(graph, instance, &parameter.name) {
+                    Ok((node, property)) => Some((parameter.name.clone(), node, property)),
+                    Err(err) => {
+                        Log::warn(format!(
+                            "Unable to resolve prefab parameter {}: {}",
+                            parameter.name, err
+                        ));
+                        None
+                    }
+                },
+            )
+            .collect::<Vec<_>>();
+
+        ui.send_message(WidgetMessage::visibility(
+            self.section,
+            MessageDirection::ToWidget,
+            !bindings.is_empty(),
+        ));
+
+        if self.is_up_to_date(instance, &bindings) {
+            let definitions = definition_container.definitions();
+            for entry in self.entries.iter() {
+                let Some(definition) = definitions.get(&entry.value_type_id) else {
+                    continue;
+                };
+
+                with_field_info(
+                    &graph[entry.node],
+                    &entry.property,
+                    &mut |info| match definition.property_editor.create_message(
+                        PropertyEditorMessageContext {
+                            sync_flag: MSG_SYNC_FLAG,
+                            instance: entry.editor,
+                            ui,
+                            property_info: info,
+                            definition_container: definition_container.clone(),
+                            layer_index: 0,
+                            environment: environment.clone(),
+                            generate_property_string_values: false,
+                            filter: Default::default(),
+                        },
+                    ) {
+                        Ok(Some(mut message)) => {
+                            message.flags = MSG_SYNC_FLAG;
+                            ui.send_message(message);
+                        }
+                        Ok(None) => (),
+                        Err(err) => Log::err(format!(
+                            "Unable to sync prefab parameter {}: {:?}",
+                            entry.name, err
+                        )),
+                    },
+                );
+            }
+            return;
+        }
+
+        self.clear(ui);
+        self.instance = instance;
+
+        for (name, node, property) in bindings {
+            with_field_info(&graph[node], &property, &mut |info| {
+                let definitions = definition_container.definitions();
+                let Some(definition) = definitions.get(&info.value.type_id()) else {
+                    return;
+                };
+
+                let ctx = &mut ui.build_ctx();
+                let info = FieldInfo {
+                    display_name: &name,
+                    unit: info.unit,
+                    ..*info
+                };
+                let instance =
+                    match definition
+                        .property_editor
+                        .create_instance(PropertyEditorBuildContext {
+                            build_context: ctx,
+                            property_info: &info,
+                            environment: environment.clone(),
+                            definition_container: definition_container.clone(),
+                            sync_flag: MSG_SYNC_FLAG,
+                            layer_index: 0,
+                            generate_property_string_values: false,
+                            filter: Default::default(),
+                        }) {
+                        Ok(instance) => instance,
+                        Err(err) => {
+                            Log::err(format!(
+                                "Unable to create an editor for prefab parameter {name}: {err:?}"
+                            ));
+                            return;
+                        }
+                    };
+
+                let (row, editor) = match instance {
+                    PropertyEditorInstance::Simple { editor } => {
+                        ctx[editor].set_column(1);
+                        let row = GridBuilder::new(
+                            WidgetBuilder::new()
+                                .with_child(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new().with_margin(Thickness::left(4.0)),
+                                    )
+                                    .with_text(&name)
+                                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                                    .build(ctx),
+                                )
+                                .with_child(editor),
+                        )
+                        .add_row(Row::auto())
+                        .add_column(Column::strict(150.0))
+                        .add_column(Column::stretch())
+                        .build(ctx);
+                        (row, editor)
+                    }
+                    PropertyEditorInstance::Custom { container, editor } => (container, editor),
+                };
+
+                ui.send_message(WidgetMessage::link(
+                    row,
+                    MessageDirection::ToWidget,
+                    self.list,
+                ));
+
+                self.entries.push(ParameterEntry {
+                    name: name.clone(),
+                    node,
+                    property: property.clone(),
+                    row,
+                    editor,
+                    value_type_id: definition.property_editor.value_type_id(),
+                    owner_type_id: info.owner_type_id,
+                });
+            });
+        }
+    }
+
+    /// Hides the section.
+    pub fn reset(&mut self, ui: &UserInterface) {
+        self.clear(ui);
+        ui.send_message(WidgetMessage::visibility(
+            self.section,
+            MessageDirection::ToWidget,
+            false,
+        ));
+    }
+
+    /// Converts a message from an editor of a parameter to a change of the property of the respective
+    /// node.
+    pub fn translate_message(
+        &self,
+        message: &UiMessage,
+        definition_container: &Arc<PropertyEditorDefinitionContainer>,
+    ) -> Option<(Handle<Node>, PropertyChanged)> {
+        if message.direction() != MessageDirection::FromWidget || message.flags == MSG_SYNC_FLAG {
+            return None;
+        }
+
+        let entry = self
+            .entries
+            .iter()
+            .find(|entry| entry.editor == message.destination())?;
+        let definitions = definition_container.definitions();
+        let definition = definitions.get(&entry.value_type_id)?;
+        definition
+            .property_editor
+            .translate_message(PropertyEditorTranslationContext {
+                environment: None,
+                name: &entry.property,
+                owner_type_id: entry.owner_type_id,
+                message,
+                definition_container: definition_container.clone(),
+            })
+            .map(|property_changed| (entry.node, property_changed))
+    }
+}
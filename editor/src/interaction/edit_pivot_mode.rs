@@ -0,0 +1,269 @@
+use crate::{
+    camera::PickingOptions,
+    fyrox::{
+        core::{
+            algebra::Vector2,
+            color::Color,
+            pool::Handle,
+            uuid::{uuid, Uuid},
+            TypeUuidProvider,
+        },
+        graph::BaseSceneGraph,
+        gui::{message::KeyCode, BuildContext, UiNode},
+        scene::{graph::Graph, node::Node},
+    },
+    interaction::{
+        calculate_gizmo_distance_scaling, gizmo::move_gizmo::MoveGizmo,
+        make_interaction_mode_button, plane::PlaneKind, InteractionMode,
+    },
+    message::MessageSender,
+    scene::{
+        commands::graph::SetNodePivotCommand, controller::SceneController, GameScene, Selection,
+    },
+    settings::Settings,
+    Engine,
+};
+
+struct DragContext {
+    node: Handle<Node>,
+    plane_kind: PlaneKind,
+}
+
+/// Allows to move the origin (pivot) of a node without moving its children and mesh geometry. The
+/// first selected node is edited, the new pivot is applied when the gizmo is released.
+pub struct EditPivotInteractionMode {
+    move_gizmo: MoveGizmo,
+    message_sender: MessageSender,
+    drag_context: Option<DragContext>,
+}
+
+impl EditPivotInteractionMode {
+    pub fn new(game_scene: &GameScene, engine: &mut Engine, message_sender: MessageSender) -> Self {
+        Self {
+            move_gizmo: MoveGizmo::new(game_scene, engine),
+            message_sender,
+            drag_context: None,
+        }
+    }
+
+    fn edited_node(editor_selection: &Selection, graph: &Graph) -> Handle<Node> {
+        editor_selection
+            .as_graph()
+            .and_then(|selection| selection.nodes().first().cloned())
+            .filter(|node| graph.is_valid_handle(*node) && *node != graph.get_root())
+            .unwrap_or_default()
+    }
+}
+
+impl TypeUuidProvider for EditPivotInteractionMode {
+    fn type_uuid() -> Uuid {
+        uuid!("4c1f3d8e-2a6b-4e57-9f0d-7b3c5a1e8d92")
+    }
+}
+
+impl InteractionMode for EditPivotInteractionMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let scene = &mut engine.scenes[game_scene.scene];
+
+        let node = Self::edited_node(editor_selection, &scene.graph);
+        if node.is_none() {
+            return;
+        }
+
+        if let Some(result) = game_scene.camera_controller.pick(
+            &scene.graph,
+            PickingOptions {
+                cursor_pos: mouse_pos,
+                editor_only: true,
+                ..Default::default()
+            },
+        ) {
+            if let Some(plane_kind) = self.move_gizmo.handle_pick(result.node, &mut scene.graph) {
+                self.drag_context = Some(DragContext { node, plane_kind });
+            }
+        }
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+
+        if let Some(drag_context) = self.drag_context.take() {
+            let Some(node) = graph.try_get(drag_context.node) else {
+                return;
+            };
+
+            let new_pivot = **graph[self.move_gizmo.origin].local_transform().position();
+            let local_offset = node
+                .global_transform()
+                .try_inverse()
+                .unwrap_or_default()
+                .transform_vector(&(new_pivot - node.global_position()));
+
+            if local_offset.norm() > f32::EPSILON {
+                self.message_sender
+                    .do_command(SetNodePivotCommand::new(drag_context.node, local_offset));
+            }
+        }
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        mouse_offset: Vector2<f32>,
+        mouse_position: Vector2<f32>,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+
+        if let Some(drag_context) = self.drag_context.as_ref() {
+            let offset = self.move_gizmo.calculate_offset(
+                graph,
+                game_scene.camera_controller.camera,
+                mouse_offset,
+                mouse_position,
+                frame_size,
+                drag_context.plane_kind,
+            );
+            let transform = self.move_gizmo.transform(graph);
+            let position = **transform.position();
+            transform.set_position(position + offset);
+        } else {
+            let picked = game_scene
+                .camera_controller
+                .pick(
+                    graph,
+                    PickingOptions {
+                        cursor_pos: mouse_position,
+                        editor_only: true,
+                        ..Default::default()
+                    },
+                )
+                .map(|r| r.node)
+                .unwrap_or_default();
+            self.move_gizmo.handle_pick(picked, graph);
+        }
+    }
+
+    fn update(
+        &mut self,
+        editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let scene = &mut engine.scenes[game_scene.scene];
+
+        let node = Self::edited_node(editor_selection, &scene.graph);
+        if node.is_none() || game_scene.preview_camera.is_some() {
+            self.move_gizmo.set_visible(&mut scene.graph, false);
+            return;
+        }
+
+        let origin = scene.graph[node].global_position();
+        if self.drag_context.is_some() {
+            // Show where the origin of the node was before the drag.
+            let pivot = **scene.graph[self.move_gizmo.origin]
+                .local_transform()
+                .position();
+            scene
+                .drawing_context
+                .draw_sphere(origin, 8, 8, 0.05, Color::ORANGE);
+            scene.drawing_context.add_line(fyrox::scene::debug::Line {
+                begin: origin,
+                end: pivot,
+                color: Color::ORANGE,
+            });
+        } else {
+            self.move_gizmo
+                .transform(&mut scene.graph)
+                .set_position(origin);
+        }
+
+        let scale = calculate_gizmo_distance_scaling(
+            &scene.graph,
+            game_scene.camera_controller.camera,
+            self.move_gizmo.origin,
+        );
+        self.move_gizmo.set_visible(&mut scene.graph, true);
+        self.move_gizmo
+            .transform(&mut scene.graph)
+            .set_scale(scale)
+            .set_rotation(Default::default());
+    }
+
+    fn deactivate(&mut self, controller: &dyn SceneController, engine: &mut Engine) {
+        let Some(game_scene) = controller.downcast_ref::<GameScene>() else {
+            return;
+        };
+
+        self.drag_context = None;
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        self.move_gizmo.set_visible(graph, false);
+    }
+
+    fn on_key_down(
+        &mut self,
+        key: KeyCode,
+        _editor_selection: &Selection,
+        _controller: &mut dyn SceneController,
+        _engine: &mut Engine,
+    ) -> bool {
+        if key == KeyCode::Escape && self.drag_context.is_some() {
+            self.drag_context = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn make_button(&mut self, ctx: &mut BuildContext, selected: bool) -> Handle<UiNode> {
+        let tooltip = "Edit Pivot\n\nMoves the origin of the first selected object without \
+        moving its children and mesh geometry. Useful to fix bad pivots of imported models.";
+
+        make_interaction_mode_button(
+            ctx,
+            include_bytes!("../../resources/locate.png"),
+            tooltip,
+            selected,
+        )
+    }
+
+    fn uuid(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
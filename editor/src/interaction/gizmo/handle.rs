@@ -0,0 +1,657 @@
+//! Reusable building blocks for interactive gizmos. A [`Gizmo`] is a set of handles (axes, planes,
+//! arcs and points) that can be hit tested with the mouse and dragged. The gizmo does all the ray
+//! math and reports the result of a drag as [`GizmoDragDelta`], so a custom interaction mode only
+//! needs to apply the delta to whatever it edits.
+//!
+//! Typical usage in an interaction mode looks like this:
+//!
+//! - create a gizmo in [`crate::interaction::InteractionMode::activate`] and fill it with handles
+//!   using [`Gizmo::add_handle`];
+//! - call [`Gizmo::begin_drag`] on mouse down and remember the state of the edited entity, if a
+//!   handle was hit;
+//! - call [`Gizmo::drag`] on mouse move and apply the delta to the remembered state (the delta is
+//!   always relative to the point where the drag has started);
+//! - call [`Gizmo::end_drag`] on mouse up and commit the change using a command;
+//! - call [`Gizmo::hit_test`] and [`Gizmo::highlight`] on mouse move to highlight hovered handles;
+//! - destroy the gizmo in [`crate::interaction::InteractionMode::deactivate`].
+
+use crate::fyrox::{
+    asset::untyped::ResourceKind,
+    core::{
+        algebra::{Matrix4, Point3, Unit, UnitQuaternion, Vector2, Vector3},
+        color::Color,
+        math::{plane::Plane, ray::Ray, Matrix4Ext},
+        pool::Handle,
+    },
+    graph::BaseSceneGraph,
+    scene::{
+        base::BaseBuilder,
+        camera::Camera,
+        graph::Graph,
+        mesh::{
+            surface::{Surface, SurfaceBuilder, SurfaceData, SurfaceResource},
+            MeshBuilder, RenderPath,
+        },
+        node::Node,
+        pivot::PivotBuilder,
+    },
+};
+use crate::{
+    interaction::calculate_gizmo_distance_scaling, make_color_material, set_mesh_diffuse_color,
+};
+use std::f32::consts::PI;
+
+/// Default distance (in local space of a gizmo) at which a handle is considered hit by a ray.
+pub const DEFAULT_HIT_TOLERANCE: f32 = 0.05;
+
+const HIGHLIGHT_COLOR: Color = Color::opaque(255, 255, 0);
+
+/// Shape of a gizmo handle. All the values are defined in local space of the gizmo.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GizmoHandleShape {
+    /// Translation along an axis. Visualized as an arrow, that starts at the origin of the gizmo.
+    Axis {
+        direction: Vector3<f32>,
+        length: f32,
+    },
+    /// Translation in a plane. Visualized as a square with the given side size.
+    Plane {
+        normal: Vector3<f32>,
+        center: Vector3<f32>,
+        size: f32,
+    },
+    /// Rotation around an axis, that goes through the origin of the gizmo. Visualized as a ring.
+    Arc { axis: Vector3<f32>, radius: f32 },
+    /// Free translation in the view plane of a camera. Visualized as a sphere.
+    Point { center: Vector3<f32>, radius: f32 },
+}
+
+/// Result of a drag. The delta is relative to the point where the drag has started and it is
+/// defined in world space.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GizmoDragDelta {
+    /// Translation, produced by [`GizmoHandleShape::Axis`], [`GizmoHandleShape::Plane`] and
+    /// [`GizmoHandleShape::Point`] handles.
+    Translation(Vector3<f32>),
+    /// Rotation around the axis of a [`GizmoHandleShape::Arc`] handle. The angle is in radians.
+    Rotation {
+        axis: Unit<Vector3<f32>>,
+        angle: f32,
+    },
+}
+
+impl GizmoDragDelta {
+    /// Returns the translation part of the delta, rotations produce zero vector.
+    pub fn translation(&self) -> Vector3<f32> {
+        match self {
+            GizmoDragDelta::Translation(translation) => *translation,
+            GizmoDragDelta::Rotation { .. } => Vector3::default(),
+        }
+    }
+
+    /// Returns the rotation part of the delta, translations produce identity rotation.
+    pub fn rotation(&self) -> UnitQuaternion<f32> {
+        match self {
+            GizmoDragDelta::Translation(_) => UnitQuaternion::identity(),
+            GizmoDragDelta::Rotation { axis, angle } => {
+                UnitQuaternion::from_axis_angle(axis, *angle)
+            }
+        }
+    }
+}
+
+fn perpendicular(v: &Vector3<f32>) -> Vector3<f32> {
+    let other = if v.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    v.cross(&other)
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(Vector3::z)
+}
+
+fn rotation_between(from: &Vector3<f32>, to: &Vector3<f32>) -> UnitQuaternion<f32> {
+    UnitQuaternion::rotation_between(from, to).unwrap_or_else(|| {
+        // Opposite vectors, any perpendicular axis works.
+        UnitQuaternion::from_axis_angle(&Unit::new_normalize(perpendicular(from)), PI)
+    })
+}
+
+fn in_ray_range(t: f32) -> Option<f32> {
+    // Rays made by cameras span from the near to the far clipping plane.
+    (0.0..=1.0).contains(&t).then_some(t)
+}
+
+impl GizmoHandleShape {
+    /// Rotation, that aligns default orientation of a visual mesh (Y for arrows, Z for squares and
+    /// rings) with the handle.
+    fn mesh_rotation(&self) -> UnitQuaternion<f32> {
+        match self {
+            GizmoHandleShape::Axis { direction, .. } => rotation_between(&Vector3::y(), direction),
+            GizmoHandleShape::Plane { normal, .. } => rotation_between(&Vector3::z(), normal),
+            GizmoHandleShape::Arc { axis, .. } => rotation_between(&Vector3::z(), axis),
+            GizmoHandleShape::Point { .. } => UnitQuaternion::identity(),
+        }
+    }
+
+    fn make_surfaces(&self, color: Color) -> Vec<Surface> {
+        let rotation = self.mesh_rotation().to_homogeneous();
+        let data = match self {
+            GizmoHandleShape::Axis { length, .. } => vec![
+                SurfaceData::make_cylinder(10, 0.015, *length, true, &rotation),
+                SurfaceData::make_cone(
+                    10,
+                    0.05,
+                    0.1,
+                    &(rotation * Matrix4::new_translation(&Vector3::new(0.0, *length, 0.0))),
+                ),
+            ],
+            GizmoHandleShape::Plane { center, size, .. } => vec![SurfaceData::make_quad(
+                &(Matrix4::new_translation(center) * rotation * Matrix4::new_scaling(*size)),
+            )],
+            GizmoHandleShape::Arc { radius, .. } => {
+                vec![SurfaceData::make_torus(*radius, 0.025, 16, 32, &rotation)]
+            }
+            GizmoHandleShape::Point { center, radius } => vec![SurfaceData::make_sphere(
+                8,
+                8,
+                *radius,
+                &Matrix4::new_translation(center),
+            )],
+        };
+
+        data.into_iter()
+            .map(|data| {
+                SurfaceBuilder::new(SurfaceResource::new_ok(ResourceKind::Embedded, data))
+                    .with_material(make_color_material(color))
+                    .build()
+            })
+            .collect()
+    }
+
+    /// Checks whether the given ray hits the handle. The ray must be in local space of the gizmo.
+    /// Returns the ray parameter of the hit point, it could be used to find the closest handle.
+    pub fn hit_test(&self, ray: &Ray, tolerance: f32) -> Option<f32> {
+        match self {
+            GizmoHandleShape::Axis { direction, length } => {
+                let segment = direction.try_normalize(f32::EPSILON)? * *length;
+                // Closest points between the ray and the segment.
+                let a = ray.dir.norm_squared();
+                let e = segment.norm_squared();
+                let b = ray.dir.dot(&segment);
+                let c = ray.dir.dot(&ray.origin);
+                let f = segment.dot(&ray.origin);
+                let denom = a * e - b * b;
+                let t = if denom.abs() > f32::EPSILON {
+                    (b * f - c * e) / denom
+                } else {
+                    0.0
+                };
+                let s = ((b * t + f) / e).clamp(0.0, 1.0);
+                let t = ((b * s - c) / a).clamp(0.0, 1.0);
+                let distance = ray.get_point(t).metric_distance(&segment.scale(s));
+                (distance <= tolerance).then_some(t)
+            }
+            GizmoHandleShape::Plane {
+                normal,
+                center,
+                size,
+            } => {
+                let plane = Plane::from_normal_and_point(normal, center)?;
+                let t = in_ray_range(ray.plane_intersection(&plane))?;
+                let offset = ray.get_point(t) - center;
+                let rotation = self.mesh_rotation();
+                let half_size = *size * 0.5 + tolerance;
+                (offset.dot(&(rotation * Vector3::x())).abs() <= half_size
+                    && offset.dot(&(rotation * Vector3::y())).abs() <= half_size)
+                    .then_some(t)
+            }
+            GizmoHandleShape::Arc { axis, radius } => {
+                let plane = Plane::from_normal_and_point(axis, &Vector3::default())?;
+                let t = in_ray_range(ray.plane_intersection(&plane))?;
+                ((ray.get_point(t).norm() - *radius).abs() <= tolerance).then_some(t)
+            }
+            GizmoHandleShape::Point { center, radius } => {
+                let result = ray.sphere_intersection(center, *radius + tolerance)?;
+                in_ray_range(result.min).or_else(|| in_ray_range(result.max))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum DragKind {
+    Translation {
+        axis: Option<Vector3<f32>>,
+    },
+    Rotation {
+        origin: Vector3<f32>,
+        axis: Unit<Vector3<f32>>,
+    },
+}
+
+/// State of an active drag. All the values are in world space.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GizmoDrag {
+    handle: usize,
+    kind: DragKind,
+    plane: Plane,
+    start: Vector3<f32>,
+}
+
+impl GizmoDrag {
+    /// Starts a drag of the handle with the given shape. `transform` is the global transform of
+    /// the gizmo, `look` is the look direction of the camera, `ray` is the world-space ray made by
+    /// the camera at the mouse position.
+    pub fn new(
+        handle: usize,
+        shape: &GizmoHandleShape,
+        transform: &Matrix4<f32>,
+        look: &Vector3<f32>,
+        ray: &Ray,
+    ) -> Option<Self> {
+        let origin = transform.position();
+        let (kind, plane) = match shape {
+            GizmoHandleShape::Axis { direction, .. } => {
+                let axis = transform
+                    .transform_vector(direction)
+                    .try_normalize(f32::EPSILON)?;
+                // The plane contains the axis and faces the camera as much as possible.
+                let normal = (look - axis.scale(look.dot(&axis)))
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(|| perpendicular(&axis));
+                (
+                    DragKind::Translation { axis: Some(axis) },
+                    Plane::from_normal_and_point(&normal, &origin)?,
+                )
+            }
+            GizmoHandleShape::Plane { normal, center, .. } => (
+                DragKind::Translation { axis: None },
+                Plane::from_normal_and_point(
+                    &transform.transform_vector(normal),
+                    &transform.transform_point(&Point3::from(*center)).coords,
+                )?,
+            ),
+            GizmoHandleShape::Arc { axis, .. } => {
+                let axis = Unit::try_new(transform.transform_vector(axis), f32::EPSILON)?;
+                (
+                    DragKind::Rotation { origin, axis },
+                    Plane::from_normal_and_point(&axis, &origin)?,
+                )
+            }
+            GizmoHandleShape::Point { center, .. } => (
+                DragKind::Translation { axis: None },
+                Plane::from_normal_and_point(
+                    look,
+                    &transform.transform_point(&Point3::from(*center)).coords,
+                )?,
+            ),
+        };
+
+        Some(Self {
+            handle,
+            kind,
+            start: ray.plane_intersection_point(&plane)?,
+            plane,
+        })
+    }
+
+    /// Index of the dragged handle.
+    pub fn handle(&self) -> usize {
+        self.handle
+    }
+
+    /// Calculates the delta between the point where the drag has started and the point, defined
+    /// by the given world-space ray. Returns `None` if the ray does not intersect the drag plane
+    /// (for example, when the mouse is above the horizon).
+    pub fn delta(&self, ray: &Ray) -> Option<GizmoDragDelta> {
+        let current = ray.plane_intersection_point(&self.plane)?;
+        match self.kind {
+            DragKind::Translation { axis } => {
+                let offset = current - self.start;
+                Some(GizmoDragDelta::Translation(match axis {
+                    Some(axis) => axis.scale(offset.dot(&axis)),
+                    None => offset,
+                }))
+            }
+            DragKind::Rotation { origin, axis } => {
+                let from = self.start - origin;
+                let to = current - origin;
+                let angle = axis.dot(&from.cross(&to)).atan2(from.dot(&to));
+                Some(GizmoDragDelta::Rotation { axis, angle })
+            }
+        }
+    }
+}
+
+/// A single handle of a [`Gizmo`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GizmoHandle {
+    pub shape: GizmoHandleShape,
+    pub color: Color,
+    node: Handle<Node>,
+}
+
+impl GizmoHandle {
+    /// Returns a handle of the mesh, that visualizes the gizmo handle.
+    pub fn node(&self) -> Handle<Node> {
+        self.node
+    }
+}
+
+/// A set of handles, that could be hit tested and dragged. See [module docs](self) for more info.
+pub struct Gizmo {
+    pub origin: Handle<Node>,
+    /// Distance (in local space of the gizmo) at which handles are considered hit by a ray. The
+    /// gizmo is scaled by the distance to the camera, so the tolerance is constant on screen.
+    pub hit_tolerance: f32,
+    handles: Vec<GizmoHandle>,
+    highlighted: Option<usize>,
+    drag: Option<GizmoDrag>,
+}
+
+impl Gizmo {
+    /// Creates an empty gizmo and attaches it to the given parent node. Usually the parent is
+    /// [`crate::scene::GameScene::editor_objects_root`].
+    pub fn new(graph: &mut Graph, parent: Handle<Node>) -> Self {
+        let origin = PivotBuilder::new(BaseBuilder::new().with_name("GizmoOrigin")).build(graph);
+        graph.link_nodes(origin, parent);
+
+        Self {
+            origin,
+            hit_tolerance: DEFAULT_HIT_TOLERANCE,
+            handles: Default::default(),
+            highlighted: None,
+            drag: None,
+        }
+    }
+
+    /// Adds a new handle and returns its index.
+    pub fn add_handle(
+        &mut self,
+        graph: &mut Graph,
+        shape: GizmoHandleShape,
+        color: Color,
+    ) -> usize {
+        let node = MeshBuilder::new(
+            BaseBuilder::new()
+                .with_cast_shadows(false)
+                .with_name(format!("GizmoHandle{}", self.handles.len())),
+        )
+        .with_render_path(RenderPath::Forward)
+        .with_surfaces(shape.make_surfaces(color))
+        .build(graph);
+        graph.link_nodes(node, self.origin);
+
+        self.handles.push(GizmoHandle { shape, color, node });
+        self.handles.len() - 1
+    }
+
+    pub fn handles(&self) -> &[GizmoHandle] {
+        &self.handles
+    }
+
+    pub fn set_visible(&self, graph: &mut Graph, visible: bool) {
+        graph[self.origin].set_visibility(visible);
+    }
+
+    /// Places the gizmo at the given position and scales it by the distance to the camera, so the
+    /// gizmo has constant size on screen.
+    pub fn sync_transform(
+        &self,
+        graph: &mut Graph,
+        camera: Handle<Node>,
+        position: Vector3<f32>,
+        rotation: UnitQuaternion<f32>,
+    ) {
+        let scale = calculate_gizmo_distance_scaling(graph, camera, self.origin);
+        graph[self.origin]
+            .local_transform_mut()
+            .set_position(position)
+            .set_rotation(rotation)
+            .set_scale(scale);
+    }
+
+    fn world_ray(
+        graph: &Graph,
+        camera: Handle<Node>,
+        mouse_position: Vector2<f32>,
+        frame_size: Vector2<f32>,
+    ) -> Option<(Ray, Vector3<f32>)> {
+        let camera = graph.try_get(camera)?.cast::<Camera>()?;
+        Some((
+            camera.make_ray(mouse_position, frame_size),
+            camera.global_transform().look(),
+        ))
+    }
+
+    /// Returns an index of the closest handle under the mouse, if any.
+    pub fn hit_test(
+        &self,
+        graph: &Graph,
+        camera: Handle<Node>,
+        mouse_position: Vector2<f32>,
+        frame_size: Vector2<f32>,
+    ) -> Option<usize> {
+        let origin = graph.try_get(self.origin)?;
+        if !origin.global_visibility() {
+            return None;
+        }
+
+        let (ray, _) = Self::world_ray(graph, camera, mouse_position, frame_size)?;
+        let local_ray = ray.transform(origin.global_transform().try_inverse()?);
+
+        self.handles
+            .iter()
+            .enumerate()
+            .filter_map(|(index, handle)| {
+                handle
+                    .shape
+                    .hit_test(&local_ray, self.hit_tolerance)
+                    .map(|t| (index, t))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index)
+    }
+
+    /// Highlights the given handle and restores colors of the other handles.
+    pub fn highlight(&mut self, graph: &mut Graph, handle: Option<usize>) {
+        if self.highlighted == handle {
+            return;
+        }
+
+        if let Some(previous) = self.highlighted.and_then(|i| self.handles.get(i)) {
+            set_mesh_diffuse_color(graph[previous.node].as_mesh_mut(), previous.color);
+        }
+        if let Some(current) = handle.and_then(|i| self.handles.get(i)) {
+            set_mesh_diffuse_color(graph[current.node].as_mesh_mut(), HIGHLIGHT_COLOR);
+        }
+
+        self.highlighted = handle;
+    }
+
+    /// Starts dragging of a handle under the mouse. Returns an index of the dragged handle, if any.
+    pub fn begin_drag(
+        &mut self,
+        graph: &mut Graph,
+        camera: Handle<Node>,
+        mouse_position: Vector2<f32>,
+        frame_size: Vector2<f32>,
+    ) -> Option<usize> {
+        let index = self.hit_test(graph, camera, mouse_position, frame_size)?;
+        let (ray, look) = Self::world_ray(graph, camera, mouse_position, frame_size)?;
+        self.drag = GizmoDrag::new(
+            index,
+            &self.handles[index].shape,
+            &graph[self.origin].global_transform(),
+            &look,
+            &ray,
+        );
+        if self.drag.is_some() {
+            self.highlight(graph, Some(index));
+        }
+        self.drag.as_ref().map(|drag| drag.handle)
+    }
+
+    /// Calculates the delta of an active drag. Returns `None` if there is no active drag.
+    pub fn drag(
+        &self,
+        graph: &Graph,
+        camera: Handle<Node>,
+        mouse_position: Vector2<f32>,
+        frame_size: Vector2<f32>,
+    ) -> Option<GizmoDragDelta> {
+        let drag = self.drag.as_ref()?;
+        let (ray, _) = Self::world_ray(graph, camera, mouse_position, frame_size)?;
+        drag.delta(&ray)
+    }
+
+    /// Returns an index of the dragged handle, if any.
+    pub fn dragged_handle(&self) -> Option<usize> {
+        self.drag.as_ref().map(|drag| drag.handle)
+    }
+
+    /// Finishes an active drag and returns an index of the dragged handle, if any.
+    pub fn end_drag(&mut self, graph: &mut Graph) -> Option<usize> {
+        self.highlight(graph, None);
+        self.drag.take().map(|drag| drag.handle)
+    }
+
+    pub fn destroy(self, graph: &mut Graph) {
+        graph.remove_node(self.origin)
+    }
+}
+
+/// Finds a point, where a ray from the camera at the given mouse position intersects the plane
+/// of the node. The plane goes through the origin of the node, `normal` is defined in local space
+/// of the node. The point is returned in local space of the node.
+pub fn pick_node_plane_point(
+    graph: &Graph,
+    camera: Handle<Node>,
+    node: Handle<Node>,
+    normal: Vector3<f32>,
+    mouse_position: Vector2<f32>,
+    frame_size: Vector2<f32>,
+) -> Option<Vector3<f32>> {
+    let camera = graph.try_get(camera)?.cast::<Camera>()?;
+    let ray = camera.make_ray(mouse_position, frame_size);
+    let node = graph.try_get(node)?;
+    let global_transform = node.global_transform();
+    let plane = Plane::from_normal_and_point(
+        &global_transform.transform_vector(&normal),
+        &node.global_position(),
+    )?;
+    let point = ray.plane_intersection_point(&plane)?;
+    Some(
+        global_transform
+            .try_inverse()?
+            .transform_point(&Point3::from(point))
+            .coords,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ray_towards(origin: Vector3<f32>, target: Vector3<f32>) -> Ray {
+        // Long enough to behave like a camera ray.
+        Ray::new(origin, (target - origin).scale(10.0))
+    }
+
+    #[test]
+    fn test_hit_test() {
+        let axis = GizmoHandleShape::Axis {
+            direction: Vector3::x(),
+            length: 1.0,
+        };
+        let from = Vector3::new(0.5, 0.0, -5.0);
+        assert!(axis
+            .hit_test(&ray_towards(from, Vector3::new(0.5, 0.0, 0.0)), 0.05)
+            .is_some());
+        assert!(axis
+            .hit_test(&ray_towards(from, Vector3::new(0.5, 0.2, 0.0)), 0.05)
+            .is_none());
+        assert!(axis
+            .hit_test(&ray_towards(from, Vector3::new(1.5, 0.0, 0.0)), 0.05)
+            .is_none());
+
+        let plane = GizmoHandleShape::Plane {
+            normal: Vector3::z(),
+            center: Vector3::new(1.0, 1.0, 0.0),
+            size: 0.5,
+        };
+        assert!(plane
+            .hit_test(&ray_towards(from, Vector3::new(1.1, 0.9, 0.0)), 0.0)
+            .is_some());
+        assert!(plane
+            .hit_test(&ray_towards(from, Vector3::new(0.5, 0.5, 0.0)), 0.0)
+            .is_none());
+
+        let arc = GizmoHandleShape::Arc {
+            axis: Vector3::z(),
+            radius: 1.0,
+        };
+        assert!(arc
+            .hit_test(&ray_towards(from, Vector3::new(0.0, 1.0, 0.0)), 0.05)
+            .is_some());
+        assert!(arc
+            .hit_test(&ray_towards(from, Vector3::new(0.0, 0.5, 0.0)), 0.05)
+            .is_none());
+
+        let point = GizmoHandleShape::Point {
+            center: Vector3::default(),
+            radius: 0.1,
+        };
+        assert!(point
+            .hit_test(&ray_towards(from, Vector3::default()), 0.0)
+            .is_some());
+        assert!(point
+            .hit_test(&ray_towards(from, Vector3::new(0.5, 0.0, 0.0)), 0.0)
+            .is_none());
+    }
+
+    #[test]
+    fn test_drag_delta() {
+        let transform = Matrix4::new_translation(&Vector3::new(1.0, 2.0, 3.0));
+        let look = Vector3::z();
+        let eye = Vector3::new(1.0, 2.0, -5.0);
+        let target = Vector3::new(1.0, 2.0, 3.0);
+
+        let axis = GizmoHandleShape::Axis {
+            direction: Vector3::x(),
+            length: 1.0,
+        };
+        let drag = GizmoDrag::new(0, &axis, &transform, &look, &ray_towards(eye, target)).unwrap();
+        let delta = drag
+            .delta(&ray_towards(eye, target + Vector3::new(0.5, 0.5, 0.0)))
+            .unwrap();
+        assert!(delta
+            .translation()
+            .metric_distance(&Vector3::new(0.5, 0.0, 0.0))
+            .lt(&1.0e-4));
+
+        let arc = GizmoHandleShape::Arc {
+            axis: Vector3::z(),
+            radius: 1.0,
+        };
+        let drag = GizmoDrag::new(
+            1,
+            &arc,
+            &transform,
+            &look,
+            &ray_towards(eye, target + Vector3::x()),
+        )
+        .unwrap();
+        match drag
+            .delta(&ray_towards(eye, target + Vector3::y()))
+            .unwrap()
+        {
+            GizmoDragDelta::Rotation { angle, .. } => {
+                assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1.0e-4)
+            }
+            delta => panic!("unexpected delta {delta:?}"),
+        }
+    }
+}
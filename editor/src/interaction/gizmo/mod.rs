@@ -1,3 +1,4 @@
+pub mod handle;
 pub mod move_gizmo;
 pub mod rotate_gizmo;
 pub mod scale_gizmo;
@@ -23,7 +23,9 @@ use crate::{
     interaction::plane::PlaneKind,
     make_color_material,
     scene::{GameScene, Selection},
-    set_mesh_diffuse_color, Engine,
+    set_mesh_diffuse_color,
+    settings::pivot::PivotSettings,
+    Engine,
 };
 use fyrox::asset::untyped::ResourceKind;
 
@@ -342,10 +344,16 @@ impl MoveGizmo {
         node.local_transform_mut().set_position(position);
     }
 
-    pub fn sync_transform(&self, scene: &mut Scene, selection: &Selection, scale: Vector3<f32>) {
+    pub fn sync_transform(
+        &self,
+        scene: &mut Scene,
+        selection: &Selection,
+        pivot: &PivotSettings,
+        scale: Vector3<f32>,
+    ) {
         let graph = &mut scene.graph;
         if let Some(selection) = selection.as_graph() {
-            if let Some((rotation, position)) = selection.global_rotation_position(graph) {
+            if let Some((rotation, position)) = selection.pivot_rotation_position(graph, pivot) {
                 let node = &mut graph[self.origin];
                 node.set_visibility(true);
                 node.local_transform_mut()
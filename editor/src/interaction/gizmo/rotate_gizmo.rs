@@ -1,7 +1,7 @@
 use crate::fyrox::graph::BaseSceneGraph;
 use crate::fyrox::{
     core::{
-        algebra::{Matrix4, UnitQuaternion, Vector2, Vector3},
+        algebra::{Matrix4, Unit, UnitQuaternion, Vector2, Vector3},
         color::Color,
         math::{plane::Plane, Matrix4Ext},
         pool::Handle,
@@ -19,15 +19,19 @@ use crate::fyrox::{
     },
 };
 use crate::{
-    make_color_material, scene::GameScene, set_mesh_diffuse_color,
+    make_color_material, scene::GameScene, set_mesh_diffuse_color, settings::pivot::PivotSettings,
     world::graph::selection::GraphSelection, Engine,
 };
 use fyrox::asset::untyped::ResourceKind;
 
+const SCREEN_AXIS_COLOR: Color = Color::opaque(200, 200, 200);
+
 pub enum RotateGizmoMode {
     Pitch,
     Yaw,
     Roll,
+    /// Rotation around the view direction of a camera.
+    Screen,
 }
 
 pub struct RotationGizmo {
@@ -36,11 +40,13 @@ pub struct RotationGizmo {
     x_axis: Handle<Node>,
     y_axis: Handle<Node>,
     z_axis: Handle<Node>,
+    screen_axis: Handle<Node>,
 }
 
 fn make_rotation_ribbon(
     graph: &mut Graph,
     rotation: UnitQuaternion<f32>,
+    radius: f32,
     color: Color,
     name: &str,
 ) -> Handle<Node> {
@@ -57,7 +63,7 @@ fn make_rotation_ribbon(
     .with_render_path(RenderPath::Forward)
     .with_surfaces(vec![SurfaceBuilder::new(SurfaceResource::new_ok(
         ResourceKind::Embedded,
-        SurfaceData::make_torus(0.5, 0.025, 16, 32, &Matrix4::identity()),
+        SurfaceData::make_torus(radius, 0.025, 16, 32, &Matrix4::identity()),
     ))
     .with_material(make_color_material(color))
     .build()])
@@ -81,6 +87,7 @@ impl RotationGizmo {
         let x_axis = make_rotation_ribbon(
             graph,
             UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 90.0f32.to_radians()),
+            0.5,
             Color::RED,
             "X",
         );
@@ -88,6 +95,7 @@ impl RotationGizmo {
         let y_axis = make_rotation_ribbon(
             graph,
             UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 0.0f32.to_radians()),
+            0.5,
             Color::GREEN,
             "Y",
         );
@@ -95,10 +103,14 @@ impl RotationGizmo {
         let z_axis = make_rotation_ribbon(
             graph,
             UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 90.0f32.to_radians()),
+            0.5,
             Color::BLUE,
             "Z",
         );
         graph.link_nodes(z_axis, origin);
+        let screen_axis =
+            make_rotation_ribbon(graph, Default::default(), 0.65, SCREEN_AXIS_COLOR, "Screen");
+        graph.link_nodes(screen_axis, origin);
 
         Self {
             mode: RotateGizmoMode::Pitch,
@@ -106,6 +118,7 @@ impl RotationGizmo {
             x_axis,
             y_axis,
             z_axis,
+            screen_axis,
         }
     }
 
@@ -113,6 +126,7 @@ impl RotationGizmo {
         set_mesh_diffuse_color(graph[self.x_axis].as_mesh_mut(), Color::RED);
         set_mesh_diffuse_color(graph[self.y_axis].as_mesh_mut(), Color::GREEN);
         set_mesh_diffuse_color(graph[self.z_axis].as_mesh_mut(), Color::BLUE);
+        set_mesh_diffuse_color(graph[self.screen_axis].as_mesh_mut(), SCREEN_AXIS_COLOR);
     }
 
     pub fn set_mode(&mut self, mode: RotateGizmoMode, graph: &mut Graph) {
@@ -132,6 +146,9 @@ impl RotationGizmo {
             RotateGizmoMode::Roll => {
                 set_mesh_diffuse_color(graph[self.z_axis].as_mesh_mut(), yellow);
             }
+            RotateGizmoMode::Screen => {
+                set_mesh_diffuse_color(graph[self.screen_axis].as_mesh_mut(), yellow);
+            }
         }
     }
 
@@ -145,6 +162,9 @@ impl RotationGizmo {
         } else if picked == self.z_axis {
             self.set_mode(RotateGizmoMode::Roll, graph);
             true
+        } else if picked == self.screen_axis {
+            self.set_mode(RotateGizmoMode::Screen, graph);
+            true
         } else {
             false
         }
@@ -160,6 +180,11 @@ impl RotationGizmo {
     ) -> UnitQuaternion<f32> {
         let camera = &graph[camera].as_camera();
         let transform = graph[self.origin].global_transform();
+        let view_direction = camera
+            .global_transform()
+            .look()
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector3::z);
 
         let initial_ray = camera.make_ray(mouse_position, frame_size);
         let offset_ray = camera.make_ray(mouse_position + mouse_offset, frame_size);
@@ -168,6 +193,7 @@ impl RotationGizmo {
             RotateGizmoMode::Pitch => transform.side(),
             RotateGizmoMode::Yaw => transform.up(),
             RotateGizmoMode::Roll => transform.look(),
+            RotateGizmoMode::Screen => view_direction,
         };
 
         let plane =
@@ -190,6 +216,15 @@ impl RotationGizmo {
                     RotateGizmoMode::Pitch => Vector3::x_axis(),
                     RotateGizmoMode::Yaw => Vector3::y_axis(),
                     RotateGizmoMode::Roll => Vector3::z_axis(),
+                    RotateGizmoMode::Screen => {
+                        // Convert the rotation around the view direction to the gizmo space.
+                        let gizmo_rotation = graph.global_rotation(self.origin);
+                        let world_rotation = UnitQuaternion::from_axis_angle(
+                            &Unit::new_normalize(oriented_axis),
+                            sign * angle_delta,
+                        );
+                        return gizmo_rotation.inverse() * world_rotation * gizmo_rotation;
+                    }
                 };
                 return UnitQuaternion::from_axis_angle(&static_axis, sign * angle_delta);
             }
@@ -202,15 +237,27 @@ impl RotationGizmo {
         &self,
         graph: &mut Graph,
         selection: &GraphSelection,
+        pivot: &PivotSettings,
+        camera: Handle<Node>,
         scale: Vector3<f32>,
     ) {
-        if let Some((rotation, position)) = selection.global_rotation_position(graph) {
+        if let Some((rotation, position)) = selection.pivot_rotation_position(graph, pivot) {
             let node = &mut graph[self.origin];
             node.set_visibility(true);
             node.local_transform_mut()
                 .set_rotation(rotation)
                 .set_position(position)
                 .set_scale(scale);
+
+            // Keep the screen ring facing the camera.
+            let view_direction = graph[camera].global_transform().look();
+            let screen_rotation = UnitQuaternion::rotation_between(&Vector3::y(), &view_direction)
+                .unwrap_or_else(|| {
+                    UnitQuaternion::from_axis_angle(&Vector3::x_axis(), std::f32::consts::PI)
+                });
+            graph[self.screen_axis]
+                .local_transform_mut()
+                .set_rotation(rotation.inverse() * screen_rotation);
         }
     }
 
@@ -18,7 +18,7 @@ use crate::fyrox::{
     },
 };
 use crate::{
-    make_color_material, scene::GameScene, set_mesh_diffuse_color,
+    make_color_material, scene::GameScene, set_mesh_diffuse_color, settings::pivot::PivotSettings,
     world::graph::selection::GraphSelection, Engine,
 };
 use fyrox::asset::untyped::ResourceKind;
@@ -276,9 +276,10 @@ impl ScaleGizmo {
         &self,
         graph: &mut Graph,
         selection: &GraphSelection,
+        pivot: &PivotSettings,
         scale: Vector3<f32>,
     ) {
-        if let Some((rotation, position)) = selection.global_rotation_position(graph) {
+        if let Some((rotation, position)) = selection.pivot_rotation_position(graph, pivot) {
             let node = &mut graph[self.origin];
             node.set_visibility(true);
             node.local_transform_mut()
@@ -30,9 +30,11 @@ use crate::{
 };
 use std::any::Any;
 
+pub mod edit_pivot_mode;
 pub mod gizmo;
 pub mod move_mode;
 pub mod navmesh;
+pub mod pivot;
 pub mod plane;
 pub mod rotate_mode;
 pub mod scale_mode;
@@ -1,5 +1,5 @@
 use crate::{
-    camera::{CameraController, PickingOptions},
+    camera::{closest_mesh_vertex, CameraController, PickingOptions},
     command::{Command, CommandGroup},
     fyrox::{
         core::{
@@ -162,7 +162,7 @@ impl MoveContext {
             }
             _ => self.update_plane_move(
                 graph,
-                &game_scene.camera_controller,
+                &mut game_scene.camera_controller,
                 settings,
                 mouse_position,
                 frame_size,
@@ -178,12 +178,7 @@ impl MoveContext {
         mouse_position: Vector2<f32>,
         frame_size: Vector2<f32>,
     ) {
-        let preview_nodes = self
-            .objects
-            .iter()
-            .map(|f| f.node)
-            .flat_map(|node| graph.traverse_handle_iter(node))
-            .collect::<FxHashSet<Handle<Node>>>();
+        let preview_nodes = self.moved_nodes(graph);
 
         let new_position = if let Some(result) = game_scene.camera_controller.pick(
             graph,
@@ -227,14 +222,70 @@ impl MoveContext {
         }
     }
 
+    fn moved_nodes(&self, graph: &Graph) -> FxHashSet<Handle<Node>> {
+        self.objects
+            .iter()
+            .map(|f| f.node)
+            .flat_map(|node| graph.traverse_handle_iter(node))
+            .collect()
+    }
+
+    // Tries to find a position on a surface (or a closest vertex of it) under the cursor.
+    fn pick_object_snap_position(
+        &self,
+        graph: &Graph,
+        camera_controller: &mut CameraController,
+        settings: &Settings,
+        mouse_position: Vector2<f32>,
+    ) -> Option<Vector3<f32>> {
+        let moved_nodes = self.moved_nodes(graph);
+
+        let result = camera_controller.pick(
+            graph,
+            PickingOptions {
+                cursor_pos: mouse_position,
+                editor_only: false,
+                filter: Some(&mut |handle, _| !moved_nodes.contains(&handle)),
+                ignore_back_faces: settings.selection.ignore_back_faces,
+                use_picking_loop: false,
+                only_meshes: true,
+            },
+        )?;
+
+        if settings.move_mode_settings.vertex_snapping {
+            if let Some(vertex) = closest_mesh_vertex(&graph[result.node], result.position) {
+                return Some(vertex);
+            }
+        }
+
+        settings
+            .move_mode_settings
+            .surface_snapping
+            .then_some(result.position)
+    }
+
     pub fn update_plane_move(
         &mut self,
         graph: &Graph,
-        camera_controller: &CameraController,
+        camera_controller: &mut CameraController,
         settings: &Settings,
         mouse_position: Vector2<f32>,
         frame_size: Vector2<f32>,
     ) {
+        if settings.move_mode_settings.is_snapping_to_objects() {
+            if let Some(position) =
+                self.pick_object_snap_position(graph, camera_controller, settings, mouse_position)
+            {
+                for entry in self.objects.iter_mut() {
+                    entry.new_local_position = entry
+                        .initial_parent_inv_global_transform
+                        .transform_point(&Point3::from(position))
+                        .coords;
+                }
+                return;
+            }
+        }
+
         if let Some(picked_position_gizmo_space) = camera_controller
             .pick_on_plane(
                 self.plane.unwrap(),
@@ -246,7 +297,8 @@ impl MoveContext {
             .map(|p| self.plane_kind.project_point(p))
         {
             for entry in self.objects.iter_mut() {
-                entry.new_local_position = settings.move_mode_settings.try_snap_vector_to_grid(
+                entry.new_local_position = settings.move_mode_settings.try_snap_position(
+                    entry.initial_local_position,
                     entry.initial_local_position
                         + entry.initial_parent_inv_global_transform.transform_vector(
                             &self.gizmo_local_transform.transform_vector(
@@ -463,7 +515,7 @@ impl InteractionMode for MoveInteractionMode {
         editor_selection: &Selection,
         controller: &mut dyn SceneController,
         engine: &mut Engine,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
             return;
@@ -481,7 +533,7 @@ impl InteractionMode for MoveInteractionMode {
             );
             self.move_gizmo.set_visible(graph, true);
             self.move_gizmo
-                .sync_transform(scene, editor_selection, scale);
+                .sync_transform(scene, editor_selection, &settings.pivot, scale);
         }
     }
 
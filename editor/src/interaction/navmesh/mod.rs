@@ -4,6 +4,7 @@ use crate::fyrox::{
     core::{
         algebra::{Vector2, Vector3},
         color::Color,
+        log::Log,
         math::{ray::CylinderKind, TriangleEdge},
         pool::Handle,
         scope_profile,
@@ -15,13 +16,20 @@ use crate::fyrox::{
         button::{ButtonBuilder, ButtonMessage},
         grid::{Column, GridBuilder, Row},
         message::{KeyCode, MessageDirection, UiMessage},
+        progress_bar::{ProgressBarBuilder, ProgressBarMessage},
         stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, Orientation, Thickness, UiNode, UserInterface,
     },
     gui::{HorizontalAlignment, VerticalAlignment},
-    scene::{camera::Camera, navmesh::NavigationalMesh},
+    scene::{camera::Camera, navmesh::NavigationalMesh, node::Node, Scene},
+    utils::{
+        lightmap::CancellationToken,
+        navmesh::Navmesh,
+        navmesh_baker::{NavmeshBakingError, NavmeshBakingInput, NavmeshBakingProgress},
+    },
 };
 use crate::scene::SelectionContainer;
 use crate::{
@@ -39,7 +47,7 @@ use crate::{
         commands::{
             navmesh::{
                 AddNavmeshEdgeCommand, ConnectNavmeshEdgesCommand, DeleteNavmeshVertexCommand,
-                MoveNavmeshVertexCommand,
+                MoveNavmeshVertexCommand, SetNavmeshCommand,
             },
             ChangeSelectionCommand,
         },
@@ -50,15 +58,32 @@ use crate::{
     utils::window_content,
     Mode,
 };
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::mpsc::{self, Receiver},
+};
 
 pub mod selection;
 
+struct BakingTask {
+    scene: Handle<Scene>,
+    navmesh_node: Handle<Node>,
+    progress: NavmeshBakingProgress,
+    cancellation_token: CancellationToken,
+    receiver: Receiver<Result<Navmesh, NavmeshBakingError>>,
+}
+
 pub struct NavmeshPanel {
     pub window: Handle<UiNode>,
     connect_edges: Handle<UiNode>,
+    bake: Handle<UiNode>,
+    progress_panel: Handle<UiNode>,
+    progress_bar: Handle<UiNode>,
+    progress_text: Handle<UiNode>,
+    cancel: Handle<UiNode>,
     sender: MessageSender,
     scene_frame: Handle<UiNode>,
+    baking_task: Option<BakingTask>,
 }
 
 fn fetch_selection(editor_selection: &Selection) -> Option<NavmeshSelection> {
@@ -74,26 +99,90 @@ fn fetch_selection(editor_selection: &Selection) -> Option<NavmeshSelection> {
 impl NavmeshPanel {
     pub fn new(scene_frame: Handle<UiNode>, ctx: &mut BuildContext, sender: MessageSender) -> Self {
         let connect_edges;
+        let bake;
+        let progress_panel;
+        let progress_bar;
+        let progress_text;
+        let cancel;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("NavmeshPanel"))
             .open(false)
             .with_title(WindowTitle::text("Navmesh"))
             .with_content(
                 GridBuilder::new(
-                    WidgetBuilder::new().with_child(
-                        StackPanelBuilder::new(WidgetBuilder::new().with_child({
-                            connect_edges = ButtonBuilder::new(
-                                WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                    WidgetBuilder::new()
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_child({
+                                        connect_edges = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Connect Edges")
+                                        .build(ctx);
+                                        connect_edges
+                                    })
+                                    .with_child({
+                                        bake = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Bake")
+                                        .build(ctx);
+                                        bake
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        )
+                        .with_child({
+                            progress_panel = GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_visibility(false)
+                                    .with_child({
+                                        progress_bar = ProgressBarBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .build(ctx);
+                                        progress_bar
+                                    })
+                                    .with_child({
+                                        progress_text = TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(0)
+                                                .with_horizontal_alignment(
+                                                    HorizontalAlignment::Center,
+                                                )
+                                                .with_vertical_alignment(VerticalAlignment::Center),
+                                        )
+                                        .build(ctx);
+                                        progress_text
+                                    })
+                                    .with_child({
+                                        cancel = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(1)
+                                                .with_width(60.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Cancel")
+                                        .build(ctx);
+                                        cancel
+                                    }),
                             )
-                            .with_text("Connect Edges")
+                            .add_column(Column::stretch())
+                            .add_column(Column::auto())
+                            .add_row(Row::stretch())
                             .build(ctx);
-                            connect_edges
-                        }))
-                        .with_orientation(Orientation::Horizontal)
-                        .build(ctx),
-                    ),
+                            progress_panel
+                        }),
                 )
                 .add_column(Column::stretch())
                 .add_row(Row::strict(20.0))
+                .add_row(Row::strict(22.0))
                 .build(ctx),
             )
             .build(ctx);
@@ -102,11 +191,24 @@ impl NavmeshPanel {
             window,
             sender,
             connect_edges,
+            bake,
+            progress_panel,
+            progress_bar,
+            progress_text,
+            cancel,
             scene_frame,
+            baking_task: None,
         }
     }
 
-    pub fn handle_message(&mut self, message: &UiMessage, editor_selection: &Selection) {
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        editor_selection: &Selection,
+        game_scene: &GameScene,
+        engine: &Engine,
+        settings: &Settings,
+    ) {
         scope_profile!();
 
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
@@ -129,7 +231,130 @@ impl NavmeshPanel {
                         [vertices[0], vertices[1]],
                     ));
                 }
+            } else if message.destination() == self.bake {
+                if let Some(selection) = fetch_selection(editor_selection) {
+                    self.start_baking(selection.navmesh_node(), game_scene, engine, settings);
+                }
+            } else if message.destination() == self.cancel {
+                if let Some(task) = self.baking_task.as_ref() {
+                    task.cancellation_token.cancel();
+                }
+            }
+        }
+    }
+
+    fn start_baking(
+        &mut self,
+        navmesh_node: Handle<Node>,
+        game_scene: &GameScene,
+        engine: &Engine,
+        settings: &Settings,
+    ) {
+        if self.baking_task.is_some() {
+            return;
+        }
+
+        let scene = &engine.scenes[game_scene.scene];
+
+        // Collect geometry on the main thread, the heavy part is done on the task pool.
+        let input = NavmeshBakingInput::from_scene(scene, |handle, node| {
+            handle != game_scene.editor_objects_root
+                && handle != navmesh_node
+                && node.global_visibility()
+        });
+        let baking_settings = settings.navmesh.baking.clone();
+        let progress = NavmeshBakingProgress::new();
+        let cancellation_token = CancellationToken::new();
+        let (sender, receiver) = mpsc::channel();
+
+        let task_progress = progress.clone();
+        let task_cancellation_token = cancellation_token.clone();
+        engine.resource_manager.task_pool().spawn_task(async move {
+            let result = input.bake(&baking_settings, task_cancellation_token, task_progress);
+            Log::verify(sender.send(result));
+        });
+
+        self.baking_task = Some(BakingTask {
+            scene: game_scene.scene,
+            navmesh_node,
+            progress,
+            cancellation_token,
+            receiver,
+        });
+
+        let ui = engine.user_interfaces.first();
+        ui.send_message(WidgetMessage::visibility(
+            self.progress_panel,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.bake,
+            MessageDirection::ToWidget,
+            false,
+        ));
+    }
+
+    pub fn update(&mut self, engine: &Engine) {
+        let Some(task) = self.baking_task.as_ref() else {
+            return;
+        };
+
+        let ui = engine.user_interfaces.first();
+
+        ui.send_message(ProgressBarMessage::progress(
+            self.progress_bar,
+            MessageDirection::ToWidget,
+            task.progress.progress_percent() as f32 / 100.0,
+        ));
+        let stage = task.progress.stage();
+        ui.send_message(TextMessage::text(
+            self.progress_text,
+            MessageDirection::ToWidget,
+            format!("Stage {} out of 4: {}", stage as u32 + 1, stage),
+        ));
+
+        if let Ok(result) = task.receiver.try_recv() {
+            match result {
+                Ok(navmesh) => {
+                    // The navmesh node (or the whole scene) could be deleted while baking.
+                    let navmesh_node_exists = engine.scenes.try_get(task.scene).is_some_and(|s| {
+                        s.graph
+                            .try_get_of_type::<NavigationalMesh>(task.navmesh_node)
+                            .is_some()
+                    });
+                    if navmesh_node_exists {
+                        Log::info(format!(
+                            "Navmesh was baked successfully. Triangles: {}, vertices: {}.",
+                            navmesh.triangles().len(),
+                            navmesh.vertices().len()
+                        ));
+                        self.sender
+                            .do_command(SetNavmeshCommand::new(task.navmesh_node, navmesh));
+                    } else {
+                        Log::warn(
+                            "Baked navmesh was discarded, because its navmesh node \
+                            does not exist anymore.",
+                        );
+                    }
+                }
+                Err(err) => {
+                    Log::err(format!("Failed to bake a navmesh. Reason: {err}"));
+                }
             }
+
+            self.baking_task = None;
+
+            ui.send_message(WidgetMessage::visibility(
+                self.progress_panel,
+                MessageDirection::ToWidget,
+                false,
+            ));
+            ui.send_message(WidgetMessage::enabled(
+                self.bake,
+                MessageDirection::ToWidget,
+                true,
+            ));
         }
     }
 
@@ -0,0 +1,177 @@
+//! Rotation and scaling of multiple objects around a common pivot point. See [`PivotContext`] docs
+//! for more info.
+
+use crate::{
+    command::Command,
+    fyrox::{
+        core::{
+            algebra::{Matrix4, Point3, UnitQuaternion, Vector3},
+            pool::Handle,
+        },
+        graph::BaseSceneGraph,
+        scene::{graph::Graph, node::Node},
+    },
+    scene::commands::graph::MoveNodeCommand,
+    settings::pivot::{PivotMode, PivotSettings},
+    world::graph::selection::GraphSelection,
+};
+
+struct PivotEntry {
+    node: Handle<Node>,
+    initial_local_position: Vector3<f32>,
+    initial_global_position: Vector3<f32>,
+    parent_inv_global_transform: Matrix4<f32>,
+}
+
+/// Initial state of selected objects, that is used to move them around a pivot point while they're
+/// being rotated or scaled. Positions of objects are not changed in [`PivotMode::IndividualOrigins`]
+/// mode, so there's no context for it.
+pub struct PivotContext {
+    pivot: Vector3<f32>,
+    pivot_rotation: UnitQuaternion<f32>,
+    entries: Vec<PivotEntry>,
+}
+
+impl PivotContext {
+    pub fn new(
+        graph: &Graph,
+        selection: &GraphSelection,
+        settings: &PivotSettings,
+    ) -> Option<Self> {
+        if settings.mode == PivotMode::IndividualOrigins {
+            return None;
+        }
+
+        let (pivot_rotation, pivot) = selection.pivot_rotation_position(graph, settings)?;
+
+        // Children of selected nodes are moved by their parents.
+        let entries = selection
+            .root_nodes(graph)
+            .into_iter()
+            .filter(|&node| graph.is_valid_handle(node))
+            .map(|node| {
+                let node_ref = &graph[node];
+                PivotEntry {
+                    node,
+                    initial_local_position: **node_ref.local_transform().position(),
+                    initial_global_position: node_ref.global_position(),
+                    parent_inv_global_transform: graph
+                        .try_get(node_ref.parent())
+                        .and_then(|parent| parent.global_transform().try_inverse())
+                        .unwrap_or_else(Matrix4::identity),
+                }
+            })
+            .collect();
+
+        Some(Self {
+            pivot,
+            pivot_rotation,
+            entries,
+        })
+    }
+
+    fn set_global_position(graph: &mut Graph, entry: &PivotEntry, position: Vector3<f32>) {
+        let local_position = entry
+            .parent_inv_global_transform
+            .transform_point(&Point3::from(position))
+            .coords;
+        graph[entry.node]
+            .local_transform_mut()
+            .set_position(local_position);
+    }
+
+    /// Moves objects around the pivot point using the given rotation in the space of the pivot.
+    pub fn rotate(&self, graph: &mut Graph, rotation: UnitQuaternion<f32>) {
+        let world_rotation = self.pivot_rotation * rotation * self.pivot_rotation.inverse();
+        for entry in self.entries.iter() {
+            let position =
+                self.pivot + world_rotation * (entry.initial_global_position - self.pivot);
+            Self::set_global_position(graph, entry, position);
+        }
+    }
+
+    /// Moves objects away from (or towards) the pivot point using the given scale in the space of the
+    /// pivot.
+    pub fn scale(&self, graph: &mut Graph, scale: Vector3<f32>) {
+        for entry in self.entries.iter() {
+            let offset =
+                self.pivot_rotation.inverse() * (entry.initial_global_position - self.pivot);
+            let position = self.pivot + self.pivot_rotation * offset.component_mul(&scale);
+            Self::set_global_position(graph, entry, position);
+        }
+    }
+
+    /// Creates commands, that move objects from their initial positions to the current ones.
+    pub fn make_commands(&self, graph: &Graph) -> Vec<Command> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let new_position = **graph[entry.node].local_transform().position();
+                (new_position != entry.initial_local_position).then(|| {
+                    Command::new(MoveNodeCommand::new(
+                        entry.node,
+                        entry.initial_local_position,
+                        new_position,
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        fyrox::{
+            core::{
+                algebra::{UnitQuaternion, Vector3},
+                pool::Handle,
+            },
+            scene::{
+                base::BaseBuilder, graph::Graph, node::Node, pivot::PivotBuilder,
+                transform::TransformBuilder,
+            },
+        },
+        interaction::pivot::PivotContext,
+        settings::pivot::{PivotMode, PivotSettings},
+        world::graph::selection::GraphSelection,
+    };
+
+    #[test]
+    fn test_rotate_around_median_point() {
+        let mut graph = Graph::new();
+        let mut make_node = |x: f32| {
+            PivotBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(x, 0.0, 0.0))
+                        .build(),
+                ),
+            )
+            .build(&mut graph)
+        };
+        let a = make_node(1.0);
+        let b = make_node(3.0);
+        graph.update_hierarchical_data();
+
+        let selection = GraphSelection::from_list(vec![a, b]);
+        let individual = PivotSettings::default();
+        assert!(PivotContext::new(&graph, &selection, &individual).is_none());
+
+        let median = PivotSettings {
+            mode: PivotMode::MedianPoint,
+            ..Default::default()
+        };
+        let context = PivotContext::new(&graph, &selection, &median).unwrap();
+        context.rotate(
+            &mut graph,
+            UnitQuaternion::from_axis_angle(&Vector3::y_axis(), std::f32::consts::PI),
+        );
+
+        let position =
+            |graph: &Graph, node: Handle<Node>| **graph[node].local_transform().position();
+        assert!((position(&graph, a) - Vector3::new(3.0, 0.0, 0.0)).norm() < 1.0e-5);
+        assert!((position(&graph, b) - Vector3::new(1.0, 0.0, 0.0)).norm() < 1.0e-5);
+        assert_eq!(context.make_commands(&graph).len(), 2);
+    }
+}
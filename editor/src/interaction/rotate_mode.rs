@@ -2,7 +2,6 @@ use crate::command::{Command, CommandGroup};
 use crate::fyrox::{
     core::{
         algebra::{UnitQuaternion, Vector2},
-        math::round_to_step,
         pool::Handle,
         uuid::{uuid, Uuid},
         TypeUuidProvider,
@@ -14,7 +13,7 @@ use crate::{
     camera::PickingOptions,
     interaction::{
         calculate_gizmo_distance_scaling, gizmo::rotate_gizmo::RotationGizmo,
-        make_interaction_mode_button, InteractionMode,
+        make_interaction_mode_button, pivot::PivotContext, InteractionMode,
     },
     message::MessageSender,
     scene::{
@@ -29,6 +28,11 @@ use crate::{
 
 pub struct RotateInteractionMode {
     initial_rotations: Vec<UnitQuaternion<f32>>,
+    // Rotations without snapping, accumulated while the gizmo is dragged.
+    unsnapped_rotations: Vec<UnitQuaternion<f32>>,
+    // Total rotation of the selection in the gizmo space, used to move objects around a pivot.
+    accumulated_rotation: UnitQuaternion<f32>,
+    pivot_context: Option<PivotContext>,
     rotation_gizmo: RotationGizmo,
     interacting: bool,
     message_sender: MessageSender,
@@ -38,6 +42,9 @@ impl RotateInteractionMode {
     pub fn new(game_scene: &GameScene, engine: &mut Engine, message_sender: MessageSender) -> Self {
         Self {
             initial_rotations: Default::default(),
+            unsnapped_rotations: Default::default(),
+            accumulated_rotation: Default::default(),
+            pivot_context: None,
             rotation_gizmo: RotationGizmo::new(game_scene, engine),
             interacting: false,
             message_sender,
@@ -59,7 +66,7 @@ impl InteractionMode for RotateInteractionMode {
         engine: &mut Engine,
         mouse_pos: Vector2<f32>,
         _frame_size: Vector2<f32>,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
             return;
@@ -84,6 +91,9 @@ impl InteractionMode for RotateInteractionMode {
                 if let Some(selection) = editor_selection.as_graph() {
                     self.interacting = true;
                     self.initial_rotations = selection.local_rotations(graph);
+                    self.unsnapped_rotations = self.initial_rotations.clone();
+                    self.accumulated_rotation = Default::default();
+                    self.pivot_context = PivotContext::new(graph, selection, &settings.pivot);
                 }
             }
         }
@@ -112,22 +122,23 @@ impl InteractionMode for RotateInteractionMode {
                     self.interacting = false;
                     let current_rotation = selection.local_rotations(graph);
                     if current_rotation != self.initial_rotations {
-                        let commands = CommandGroup::from(
-                            selection
-                                .nodes()
-                                .iter()
-                                .zip(self.initial_rotations.iter().zip(current_rotation.iter()))
-                                .map(|(&node, (&old_rotation, &new_rotation))| {
-                                    Command::new(RotateNodeCommand::new(
-                                        node,
-                                        old_rotation,
-                                        new_rotation,
-                                    ))
-                                })
-                                .collect::<Vec<Command>>(),
-                        );
+                        let mut commands = selection
+                            .nodes()
+                            .iter()
+                            .zip(self.initial_rotations.iter().zip(current_rotation.iter()))
+                            .map(|(&node, (&old_rotation, &new_rotation))| {
+                                Command::new(RotateNodeCommand::new(
+                                    node,
+                                    old_rotation,
+                                    new_rotation,
+                                ))
+                            })
+                            .collect::<Vec<Command>>();
+                        if let Some(pivot_context) = self.pivot_context.take() {
+                            commands.extend(pivot_context.make_commands(graph));
+                        }
                         // Commit changes.
-                        self.message_sender.do_command(commands);
+                        self.message_sender.do_command(CommandGroup::from(commands));
                     }
                 }
             }
@@ -195,26 +206,26 @@ impl InteractionMode for RotateInteractionMode {
                     graph,
                     frame_size,
                 );
-                for &node in selection.nodes().iter() {
-                    let transform = graph[node].local_transform_mut();
-                    let rotation = **transform.rotation();
-                    let final_rotation = rotation * rotation_delta;
-                    let (mut roll, mut pitch, mut yaw) = final_rotation.euler_angles();
-                    if settings.rotate_mode_settings.angle_snapping {
-                        pitch = round_to_step(
-                            pitch,
-                            settings.rotate_mode_settings.x_snap_step.to_radians(),
-                        );
-                        yaw = round_to_step(
-                            yaw,
-                            settings.rotate_mode_settings.y_snap_step.to_radians(),
-                        );
-                        roll = round_to_step(
-                            roll,
-                            settings.rotate_mode_settings.z_snap_step.to_radians(),
-                        );
-                    }
-                    transform.set_rotation(UnitQuaternion::from_euler_angles(roll, pitch, yaw));
+                for ((&node, initial_rotation), unsnapped_rotation) in selection
+                    .nodes()
+                    .iter()
+                    .zip(self.initial_rotations.iter())
+                    .zip(self.unsnapped_rotations.iter_mut())
+                {
+                    *unsnapped_rotation *= rotation_delta;
+                    graph[node].local_transform_mut().set_rotation(
+                        settings
+                            .rotate_mode_settings
+                            .try_snap_rotation(*initial_rotation, *unsnapped_rotation),
+                    );
+                }
+
+                self.accumulated_rotation *= rotation_delta;
+                if let Some(pivot_context) = self.pivot_context.as_ref() {
+                    let rotation = settings
+                        .rotate_mode_settings
+                        .try_snap_rotation(Default::default(), self.accumulated_rotation);
+                    pivot_context.rotate(graph, rotation);
                 }
             } else {
                 let picked = game_scene
@@ -246,7 +257,7 @@ impl InteractionMode for RotateInteractionMode {
         editor_selection: &Selection,
         controller: &mut dyn SceneController,
         engine: &mut Engine,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
             return;
@@ -262,7 +273,13 @@ impl InteractionMode for RotateInteractionMode {
                     game_scene.camera_controller.camera,
                     self.rotation_gizmo.origin,
                 );
-                self.rotation_gizmo.sync_transform(graph, selection, scale);
+                self.rotation_gizmo.sync_transform(
+                    graph,
+                    selection,
+                    &settings.pivot,
+                    game_scene.camera_controller.camera,
+                    scale,
+                );
                 self.rotation_gizmo.set_visible(graph, true);
             }
         }
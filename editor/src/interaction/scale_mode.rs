@@ -13,7 +13,7 @@ use crate::{
     camera::PickingOptions,
     interaction::{
         calculate_gizmo_distance_scaling, gizmo::scale_gizmo::ScaleGizmo,
-        make_interaction_mode_button, InteractionMode,
+        make_interaction_mode_button, pivot::PivotContext, InteractionMode,
     },
     message::MessageSender,
     scene::{
@@ -28,6 +28,11 @@ use crate::{
 
 pub struct ScaleInteractionMode {
     initial_scales: Vec<Vector3<f32>>,
+    // Scales without snapping, accumulated while the gizmo is dragged.
+    unsnapped_scales: Vec<Vector3<f32>>,
+    // Total scale of the selection in the gizmo space, used to move objects relative to a pivot.
+    accumulated_scale: Vector3<f32>,
+    pivot_context: Option<PivotContext>,
     scale_gizmo: ScaleGizmo,
     interacting: bool,
     message_sender: MessageSender,
@@ -37,6 +42,9 @@ impl ScaleInteractionMode {
     pub fn new(game_scene: &GameScene, engine: &mut Engine, message_sender: MessageSender) -> Self {
         Self {
             initial_scales: Default::default(),
+            unsnapped_scales: Default::default(),
+            accumulated_scale: Vector3::repeat(1.0),
+            pivot_context: None,
             scale_gizmo: ScaleGizmo::new(game_scene, engine),
             interacting: false,
             message_sender,
@@ -58,7 +66,7 @@ impl InteractionMode for ScaleInteractionMode {
         engine: &mut Engine,
         mouse_pos: Vector2<f32>,
         _frame_size: Vector2<f32>,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
             return;
@@ -82,6 +90,9 @@ impl InteractionMode for ScaleInteractionMode {
                 if self.scale_gizmo.handle_pick(result.node, graph) {
                     self.interacting = true;
                     self.initial_scales = selection.local_scales(graph);
+                    self.unsnapped_scales = self.initial_scales.clone();
+                    self.accumulated_scale = Vector3::repeat(1.0);
+                    self.pivot_context = PivotContext::new(graph, selection, &settings.pivot);
                 }
             }
         }
@@ -111,17 +122,18 @@ impl InteractionMode for ScaleInteractionMode {
                     let current_scales = selection.local_scales(graph);
                     if current_scales != self.initial_scales {
                         // Commit changes.
-                        let commands = CommandGroup::from(
-                            selection
-                                .nodes()
-                                .iter()
-                                .zip(self.initial_scales.iter().zip(current_scales.iter()))
-                                .map(|(&node, (&old_scale, &new_scale))| {
-                                    Command::new(ScaleNodeCommand::new(node, old_scale, new_scale))
-                                })
-                                .collect::<Vec<_>>(),
-                        );
-                        self.message_sender.do_command(commands);
+                        let mut commands = selection
+                            .nodes()
+                            .iter()
+                            .zip(self.initial_scales.iter().zip(current_scales.iter()))
+                            .map(|(&node, (&old_scale, &new_scale))| {
+                                Command::new(ScaleNodeCommand::new(node, old_scale, new_scale))
+                            })
+                            .collect::<Vec<_>>();
+                        if let Some(pivot_context) = self.pivot_context.take() {
+                            commands.extend(pivot_context.make_commands(graph));
+                        }
+                        self.message_sender.do_command(CommandGroup::from(commands));
                     }
                 }
             }
@@ -172,7 +184,7 @@ impl InteractionMode for ScaleInteractionMode {
         controller: &mut dyn SceneController,
         engine: &mut Engine,
         frame_size: Vector2<f32>,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
             return;
@@ -189,13 +201,31 @@ impl InteractionMode for ScaleInteractionMode {
                     graph,
                     frame_size,
                 );
-                for &node in selection.nodes().iter() {
-                    let transform = graph[node].local_transform_mut();
-                    let initial_scale = transform.scale();
-                    let sx = (initial_scale.x * (1.0 + scale_delta.x)).max(f32::EPSILON);
-                    let sy = (initial_scale.y * (1.0 + scale_delta.y)).max(f32::EPSILON);
-                    let sz = (initial_scale.z * (1.0 + scale_delta.z)).max(f32::EPSILON);
-                    transform.set_scale(Vector3::new(sx, sy, sz));
+                for ((&node, initial_scale), unsnapped_scale) in selection
+                    .nodes()
+                    .iter()
+                    .zip(self.initial_scales.iter())
+                    .zip(self.unsnapped_scales.iter_mut())
+                {
+                    *unsnapped_scale = unsnapped_scale
+                        .component_mul(&(Vector3::repeat(1.0) + scale_delta))
+                        .sup(&Vector3::repeat(f32::EPSILON));
+                    let scale = settings
+                        .scale_mode_settings
+                        .try_snap_scale(*initial_scale, *unsnapped_scale)
+                        .sup(&Vector3::repeat(f32::EPSILON));
+                    graph[node].local_transform_mut().set_scale(scale);
+                }
+
+                self.accumulated_scale = self
+                    .accumulated_scale
+                    .component_mul(&(Vector3::repeat(1.0) + scale_delta))
+                    .sup(&Vector3::repeat(f32::EPSILON));
+                if let Some(pivot_context) = self.pivot_context.as_ref() {
+                    let scale = settings
+                        .scale_mode_settings
+                        .try_snap_scale(Vector3::repeat(1.0), self.accumulated_scale);
+                    pivot_context.scale(graph, scale);
                 }
             } else {
                 let picked = game_scene
@@ -223,7 +253,7 @@ impl InteractionMode for ScaleInteractionMode {
         editor_selection: &Selection,
         controller: &mut dyn SceneController,
         engine: &mut Engine,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
             return;
@@ -239,7 +269,8 @@ impl InteractionMode for ScaleInteractionMode {
                     game_scene.camera_controller.camera,
                     self.scale_gizmo.origin,
                 );
-                self.scale_gizmo.sync_transform(graph, selection, scale);
+                self.scale_gizmo
+                    .sync_transform(graph, selection, &settings.pivot, scale);
                 self.scale_gizmo.set_visible(graph, true);
             }
         }
@@ -3,17 +3,29 @@ use crate::fyrox::core::TypeUuidProvider;
 use crate::fyrox::graph::BaseSceneGraph;
 use crate::fyrox::gui::BuildContext;
 use crate::fyrox::{
-    core::{algebra::Vector2, pool::Handle},
-    gui::{message::MessageDirection, widget::WidgetMessage, UiNode},
-    scene::node::Node,
+    core::{
+        algebra::{Vector2, Vector3},
+        pool::Handle,
+    },
+    gui::{
+        message::MessageDirection,
+        vector_image::{Primitive, VectorImage},
+        widget::WidgetMessage,
+        UiNode, UserInterface,
+    },
+    scene::{camera::Camera, graph::Graph, node::Node},
 };
 use crate::interaction::make_interaction_mode_button;
 use crate::scene::controller::SceneController;
 use crate::{
+    camera::{CameraController, PickingOptions},
     interaction::InteractionMode,
     message::MessageSender,
     scene::{commands::ChangeSelectionCommand, GameScene, Selection},
-    settings::Settings,
+    settings::{
+        selection::{SelectionOcclusion, SelectionShape},
+        Settings,
+    },
     world::graph::selection::GraphSelection,
     Engine,
 };
@@ -21,25 +33,73 @@ use crate::{
 pub struct SelectInteractionMode {
     preview: Handle<UiNode>,
     selection_frame: Handle<UiNode>,
+    lasso: Handle<UiNode>,
     message_sender: MessageSender,
     stack: Vec<Handle<Node>>,
     click_pos: Vector2<f32>,
+    lasso_points: Vec<Vector2<f32>>,
 }
 
 impl SelectInteractionMode {
     pub fn new(
         preview: Handle<UiNode>,
         selection_frame: Handle<UiNode>,
+        lasso: Handle<UiNode>,
         message_sender: MessageSender,
     ) -> Self {
         Self {
             preview,
             selection_frame,
+            lasso,
             message_sender,
             stack: Vec::new(),
             click_pos: Vector2::default(),
+            lasso_points: Vec::new(),
         }
     }
+
+    fn sync_lasso(&self, ui: &mut UserInterface) {
+        let Some(first) = self.lasso_points.first() else {
+            return;
+        };
+
+        let (min, max) = self
+            .lasso_points
+            .iter()
+            .fold((*first, *first), |(min, max), p| (min.inf(p), max.sup(p)));
+
+        // Close the polygon, so it is clear which region will be used for selection.
+        let primitives = self
+            .lasso_points
+            .iter()
+            .zip(self.lasso_points.iter().cycle().skip(1))
+            .map(|(begin, end)| Primitive::Line {
+                begin: begin - min,
+                end: end - min,
+                thickness: 1.0,
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(lasso) = ui.node_mut(self.lasso).cast_mut::<VectorImage>() {
+            lasso.primitives.set_value_and_mark_modified(primitives);
+        }
+
+        ui.send_message(WidgetMessage::desired_position(
+            self.lasso,
+            MessageDirection::ToWidget,
+            min,
+        ));
+        ui.send_message(WidgetMessage::width(
+            self.lasso,
+            MessageDirection::ToWidget,
+            max.x - min.x,
+        ));
+        ui.send_message(WidgetMessage::height(
+            self.lasso,
+            MessageDirection::ToWidget,
+            max.y - min.y,
+        ));
+    }
 }
 
 impl TypeUuidProvider for SelectInteractionMode {
@@ -48,6 +108,58 @@ impl TypeUuidProvider for SelectInteractionMode {
     }
 }
 
+/// Checks whether the point is inside the polygon using the crossing number algorithm.
+fn is_point_inside_polygon(point: Vector2<f32>, polygon: &[Vector2<f32>]) -> bool {
+    let mut inside = false;
+    for (a, b) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Checks whether a point of a node is not hidden behind other objects.
+fn is_point_visible(
+    graph: &Graph,
+    camera_controller: &mut CameraController,
+    camera: &Camera,
+    node: Handle<Node>,
+    point: Vector3<f32>,
+    screen_point: Vector2<f32>,
+    ignore_back_faces: bool,
+) -> bool {
+    let Some(result) = camera_controller.pick(
+        graph,
+        PickingOptions {
+            cursor_pos: screen_point,
+            editor_only: false,
+            filter: None,
+            ignore_back_faces,
+            use_picking_loop: false,
+            only_meshes: true,
+        },
+    ) else {
+        return true;
+    };
+
+    // The node itself (or one of its descendants) is the closest one.
+    let mut ancestor = result.node;
+    while ancestor.is_some() {
+        if ancestor == node {
+            return true;
+        }
+        ancestor = graph[ancestor].parent();
+    }
+
+    // Allow a small bias to not reject objects lying on surfaces.
+    let camera_position = camera.global_position();
+    result.position.metric_distance(&camera_position)
+        >= point.metric_distance(&camera_position) - 0.01
+}
+
 impl InteractionMode for SelectInteractionMode {
     fn on_left_mouse_button_down(
         &mut self,
@@ -56,30 +168,44 @@ impl InteractionMode for SelectInteractionMode {
         engine: &mut Engine,
         mouse_pos: Vector2<f32>,
         _frame_size: Vector2<f32>,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         self.click_pos = mouse_pos;
+        self.lasso_points.clear();
+        self.lasso_points.push(mouse_pos);
         let ui = &mut engine.user_interfaces.first_mut();
-        ui.send_message(WidgetMessage::visibility(
-            self.selection_frame,
-            MessageDirection::ToWidget,
-            true,
-        ));
-        ui.send_message(WidgetMessage::desired_position(
-            self.selection_frame,
-            MessageDirection::ToWidget,
-            mouse_pos,
-        ));
-        ui.send_message(WidgetMessage::width(
-            self.selection_frame,
-            MessageDirection::ToWidget,
-            0.0,
-        ));
-        ui.send_message(WidgetMessage::height(
-            self.selection_frame,
-            MessageDirection::ToWidget,
-            0.0,
-        ));
+        match settings.selection.shape {
+            SelectionShape::Box => {
+                ui.send_message(WidgetMessage::visibility(
+                    self.selection_frame,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+                ui.send_message(WidgetMessage::desired_position(
+                    self.selection_frame,
+                    MessageDirection::ToWidget,
+                    mouse_pos,
+                ));
+                ui.send_message(WidgetMessage::width(
+                    self.selection_frame,
+                    MessageDirection::ToWidget,
+                    0.0,
+                ));
+                ui.send_message(WidgetMessage::height(
+                    self.selection_frame,
+                    MessageDirection::ToWidget,
+                    0.0,
+                ));
+            }
+            SelectionShape::Lasso => {
+                self.sync_lasso(ui);
+                ui.send_message(WidgetMessage::visibility(
+                    self.lasso,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            }
+        }
     }
 
     fn on_left_mouse_button_up(
@@ -89,7 +215,7 @@ impl InteractionMode for SelectInteractionMode {
         engine: &mut Engine,
         _mouse_pos: Vector2<f32>,
         frame_size: Vector2<f32>,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
             return;
@@ -108,48 +234,94 @@ impl InteractionMode for SelectInteractionMode {
             .node(self.selection_frame)
             .screen_bounds();
         let relative_bounds = frame_screen_bounds.translate(-preview_screen_bounds.position);
+        let is_inside_region = |point: Vector2<f32>| match settings.selection.shape {
+            SelectionShape::Box => relative_bounds.contains(point),
+            SelectionShape::Lasso => is_point_inside_polygon(point, &self.lasso_points),
+        };
+        let modifiers = engine.user_interfaces.first().keyboard_modifiers();
+
         self.stack.clear();
         self.stack.push(scene.graph.get_root());
-        let mut graph_selection = GraphSelection::default();
+        let mut nodes = Vec::new();
         while let Some(handle) = self.stack.pop() {
             let node = &scene.graph[handle];
             if handle == game_scene.editor_objects_root {
                 continue;
             }
-            if handle == scene.graph.get_root() {
+            if handle == scene.graph.get_root()
+                || game_scene.camera_controller.is_layer_locked(node.layer())
+            {
                 self.stack.extend_from_slice(node.children());
                 continue;
             }
 
-            for screen_corner in node
-                .local_bounding_box()
+            let bounding_box = node.local_bounding_box();
+            for point in bounding_box
                 .corners()
                 .iter()
-                .filter_map(|&p| camera.project(p + node.global_position(), frame_size))
+                .chain(std::iter::once(&bounding_box.center()))
+                .map(|&p| p + node.global_position())
             {
-                if relative_bounds.contains(screen_corner) {
-                    graph_selection.insert_or_exclude(handle);
-                    break;
+                let Some(screen_point) = camera.project(point, frame_size) else {
+                    continue;
+                };
+
+                if !is_inside_region(screen_point) {
+                    continue;
                 }
+
+                if settings.selection.occlusion == SelectionOcclusion::VisibleOnly
+                    && !is_point_visible(
+                        &scene.graph,
+                        &mut game_scene.camera_controller,
+                        camera,
+                        handle,
+                        point,
+                        screen_point,
+                        settings.selection.ignore_back_faces,
+                    )
+                {
+                    continue;
+                }
+
+                nodes.push(handle);
+                break;
             }
 
             self.stack.extend_from_slice(node.children());
         }
 
+        // Shift adds nodes to the current selection, Ctrl removes them from it.
+        let mut graph_selection = if modifiers.shift || modifiers.control {
+            editor_selection.as_graph().cloned().unwrap_or_default()
+        } else {
+            GraphSelection::default()
+        };
+        for node in nodes {
+            if modifiers.control {
+                if graph_selection.contains(node) {
+                    graph_selection.insert_or_exclude(node);
+                }
+            } else if !graph_selection.contains(node) {
+                graph_selection.insert_or_exclude(node);
+            }
+        }
+
         let new_selection = Selection::new(graph_selection);
 
         if &new_selection != editor_selection {
             self.message_sender
                 .do_command(ChangeSelectionCommand::new(new_selection));
         }
-        engine
-            .user_interfaces
-            .first_mut()
-            .send_message(WidgetMessage::visibility(
-                self.selection_frame,
+        let ui = engine.user_interfaces.first_mut();
+        for widget in [self.selection_frame, self.lasso] {
+            ui.send_message(WidgetMessage::visibility(
+                widget,
                 MessageDirection::ToWidget,
                 false,
             ));
+        }
+        self.lasso_points.clear();
     }
 
     fn on_mouse_move(
@@ -160,9 +332,23 @@ impl InteractionMode for SelectInteractionMode {
         _controller: &mut dyn SceneController,
         engine: &mut Engine,
         _frame_size: Vector2<f32>,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         let ui = &mut engine.user_interfaces.first_mut();
+
+        if settings.selection.shape == SelectionShape::Lasso {
+            // Skip tiny movements to keep the amount of points reasonable.
+            if self
+                .lasso_points
+                .last()
+                .map_or(true, |last| last.metric_distance(&mouse_position) > 2.0)
+            {
+                self.lasso_points.push(mouse_position);
+                self.sync_lasso(ui);
+            }
+            return;
+        }
+
         let width = mouse_position.x - self.click_pos.x;
         let height = mouse_position.y - self.click_pos.y;
 
@@ -209,7 +395,8 @@ impl InteractionMode for SelectInteractionMode {
     fn make_button(&mut self, ctx: &mut BuildContext, selected: bool) -> Handle<UiNode> {
         let select_mode_tooltip = "Select Object(s) - Shortcut: [1]\n\nSelection interaction mode \
         allows you to select an object by a single left mouse button click or multiple objects using either \
-        frame selection (click and drag) or by holding Ctrl+Click";
+        frame (or lasso) selection (click and drag) or by holding Ctrl+Click. Hold Shift while dragging \
+        to add objects to the selection, or Ctrl to remove them from it";
 
         make_interaction_mode_button(
             ctx,
@@ -223,3 +410,26 @@ impl InteractionMode for SelectInteractionMode {
         Self::type_uuid()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{fyrox::core::algebra::Vector2, interaction::select_mode::is_point_inside_polygon};
+
+    #[test]
+    fn test_point_inside_lasso() {
+        // L-shaped polygon.
+        let polygon = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(2.0, 1.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(1.0, 2.0),
+            Vector2::new(0.0, 2.0),
+        ];
+        assert!(is_point_inside_polygon(Vector2::new(0.5, 0.5), &polygon));
+        assert!(is_point_inside_polygon(Vector2::new(0.5, 1.5), &polygon));
+        assert!(!is_point_inside_polygon(Vector2::new(1.5, 1.5), &polygon));
+        assert!(!is_point_inside_polygon(Vector2::new(3.0, 0.5), &polygon));
+        assert!(!is_point_inside_polygon(Vector2::new(0.5, 0.5), &[]));
+    }
+}
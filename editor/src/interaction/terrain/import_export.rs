@@ -0,0 +1,392 @@
+//! Import and export of terrain height maps and splat maps, it allows to exchange terrains with
+//! external terrain generators (World Machine, Gaea, etc.).
+
+use crate::command::CommandGroup;
+use crate::fyrox::{
+    core::{log::Log, pool::Handle, reflect::prelude::*},
+    graph::SceneGraph,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::PropertyEditorDefinitionContainer, InspectorBuilder, InspectorContext,
+            InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Thickness, UiNode, UserInterface,
+    },
+    scene::{
+        graph::Graph,
+        node::Node,
+        terrain::{
+            heightmap::{HeightMapImage, SplatMapImage},
+            Terrain,
+        },
+    },
+};
+use crate::{
+    interaction::terrain::copy_layer_masks,
+    message::MessageSender,
+    scene::commands::terrain::{ModifyTerrainHeightCommand, ModifyTerrainLayerMaskCommand},
+    MSG_SYNC_FLAG,
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+#[derive(Reflect, Debug, Clone)]
+struct ImportExportOptions {
+    #[reflect(
+        description = "Height (in meters) of the maximum value of a 16-bit height map. Float \
+        height maps (EXR) are multiplied by this value."
+    )]
+    height_scale: f32,
+    #[reflect(description = "Height (in meters) of the minimum value of a height map.")]
+    height_offset: f32,
+    #[reflect(
+        description = "Index of the terrain layer, that corresponds to the red channel of a splat \
+        map. Green, blue and alpha channels go to the next three layers."
+    )]
+    first_layer: usize,
+}
+
+impl Default for ImportExportOptions {
+    fn default() -> Self {
+        Self {
+            height_scale: 100.0,
+            height_offset: 0.0,
+            first_layer: 0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Action {
+    ImportHeight,
+    ExportHeight,
+    ImportSplat,
+    ExportSplat,
+}
+
+impl Action {
+    fn name(self) -> &'static str {
+        match self {
+            Action::ImportHeight => "import height map",
+            Action::ExportHeight => "export height map",
+            Action::ImportSplat => "import splat map",
+            Action::ExportSplat => "export splat map",
+        }
+    }
+
+    fn is_height_map(self) -> bool {
+        matches!(self, Action::ImportHeight | Action::ExportHeight)
+    }
+}
+
+fn make_filter(height_map: bool) -> Filter {
+    Filter::new(move |p: &Path| {
+        p.is_dir()
+            || p.extension().is_some_and(|ext| {
+                let ext = ext.to_string_lossy().to_lowercase();
+                if height_map {
+                    matches!(ext.as_str(), "png" | "raw" | "r16" | "exr" | "tif" | "tiff")
+                } else {
+                    matches!(ext.as_str(), "png" | "tga" | "tif" | "tiff" | "bmp")
+                }
+            })
+    })
+}
+
+pub struct ImportExportPanel {
+    pub window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    import_height_map: Handle<UiNode>,
+    export_height_map: Handle<UiNode>,
+    import_splat_map: Handle<UiNode>,
+    export_splat_map: Handle<UiNode>,
+    file_selector: Handle<UiNode>,
+    action: Option<Action>,
+    options: ImportExportOptions,
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str, row: usize, column: usize) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(column)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl ImportExportPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let options = ImportExportOptions::default();
+        let context = InspectorContext::from_object(
+            &options,
+            ctx,
+            Arc::new(PropertyEditorDefinitionContainer::with_default_editors()),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+            Default::default(),
+        );
+
+        let inspector = InspectorBuilder::new(WidgetBuilder::new().on_row(0))
+            .with_context(context)
+            .build(ctx);
+        let import_height_map = make_button(ctx, "Import Height Map...", 0, 0);
+        let export_height_map = make_button(ctx, "Export Height Map...", 0, 1);
+        let import_splat_map = make_button(ctx, "Import Splat Map...", 1, 0);
+        let export_splat_map = make_button(ctx, "Export Splat Map...", 1, 1);
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(320.0).with_height(160.0))
+            .can_minimize(false)
+            .can_maximize(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new().with_child(inspector).with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .with_child(import_height_map)
+                                .with_child(export_height_map)
+                                .with_child(import_splat_map)
+                                .with_child(export_splat_map),
+                        )
+                        .add_row(Row::strict(24.0))
+                        .add_row(Row::strict(24.0))
+                        .add_column(Column::stretch())
+                        .add_column(Column::stretch())
+                        .build(ctx),
+                    ),
+                )
+                .add_row(Row::stretch())
+                .add_row(Row::auto())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .open(false)
+            .with_title(WindowTitle::text("Terrain Import/Export"))
+            .build(ctx);
+
+        Self {
+            window,
+            inspector,
+            import_height_map,
+            export_height_map,
+            import_splat_map,
+            export_splat_map,
+            file_selector: Handle::NONE,
+            action: None,
+            options,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+            true,
+        ));
+    }
+
+    fn open_file_selector(&mut self, action: Action, ui: &mut UserInterface) {
+        let (title, mode) = match action {
+            Action::ImportHeight => ("Select a Height Map", FileBrowserMode::Open),
+            Action::ImportSplat => ("Select a Splat Map", FileBrowserMode::Open),
+            Action::ExportHeight => (
+                "Export Height Map As",
+                FileBrowserMode::Save {
+                    default_file_name: PathBuf::from("heightmap.png"),
+                },
+            ),
+            Action::ExportSplat => (
+                "Export Splat Map As",
+                FileBrowserMode::Save {
+                    default_file_name: PathBuf::from("splatmap.png"),
+                },
+            ),
+        };
+
+        self.action = Some(action);
+        self.file_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::text(title)),
+        )
+        .with_mode(mode)
+        .with_filter(make_filter(action.is_height_map()))
+        .build(&mut ui.build_ctx());
+
+        ui.send_message(WindowMessage::open_modal(
+            self.file_selector,
+            MessageDirection::ToWidget,
+            true,
+            true,
+        ));
+        ui.send_message(FileSelectorMessage::root(
+            self.file_selector,
+            MessageDirection::ToWidget,
+            Some(std::env::current_dir().unwrap()),
+        ));
+    }
+
+    fn destroy_file_selector(&mut self, ui: &UserInterface) {
+        ui.send_message(WidgetMessage::remove(
+            self.file_selector,
+            MessageDirection::ToWidget,
+        ));
+        self.file_selector = Handle::NONE;
+        self.action = None;
+    }
+
+    fn perform(
+        &self,
+        action: Action,
+        path: &Path,
+        terrain_handle: Handle<Node>,
+        graph: &mut Graph,
+        sender: &MessageSender,
+    ) {
+        let Some(terrain) = graph.try_get_mut_of_type::<Terrain>(terrain_handle) else {
+            return;
+        };
+        let options = &self.options;
+
+        let result = match action {
+            Action::ImportHeight => HeightMapImage::from_file(path).map(|height_map| {
+                let old_heightmaps = terrain
+                    .chunks_ref()
+                    .iter()
+                    .map(|c| c.heightmap_owned())
+                    .collect();
+                terrain.import_height_map(&height_map, options.height_scale, options.height_offset);
+                let new_heightmaps = terrain
+                    .chunks_ref()
+                    .iter()
+                    .map(|c| c.heightmap_owned())
+                    .collect();
+                sender.do_command(ModifyTerrainHeightCommand::new(
+                    terrain_handle,
+                    old_heightmaps,
+                    new_heightmaps,
+                ));
+            }),
+            Action::ImportSplat => SplatMapImage::from_file(path).map(|splat_map| {
+                let layers = options.first_layer..(options.first_layer + 4);
+                let layers = layers.start.min(terrain.layers().len())
+                    ..layers.end.min(terrain.layers().len());
+                let old_masks = layers
+                    .clone()
+                    .map(|layer| copy_layer_masks(terrain, layer))
+                    .collect::<Vec<_>>();
+                terrain.import_splat_map(&splat_map, options.first_layer);
+                let mut group = CommandGroup::default().with_custom_name("Import Splat Map");
+                for (layer, old_masks) in layers.zip(old_masks) {
+                    group.push(ModifyTerrainLayerMaskCommand::new(
+                        terrain_handle,
+                        old_masks,
+                        copy_layer_masks(terrain, layer),
+                        layer,
+                    ));
+                }
+                if !group.is_empty() {
+                    sender.do_command(group);
+                }
+            }),
+            Action::ExportHeight => terrain
+                .export_height_map(options.height_scale, options.height_offset)
+                .save(path),
+            Action::ExportSplat => terrain.export_splat_map(options.first_layer).save(path),
+        };
+
+        match result {
+            Ok(()) => Log::info(format!(
+                "Successfully performed {} using {}.",
+                action.name(),
+                path.display()
+            )),
+            Err(err) => Log::err(format!(
+                "Unable to {} using {}. Reason: {}",
+                action.name(),
+                path.display(),
+                err
+            )),
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        terrain: Handle<Node>,
+        graph: &mut Graph,
+        ui: &mut UserInterface,
+        sender: &MessageSender,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            let action = if message.destination() == self.import_height_map {
+                Some(Action::ImportHeight)
+            } else if message.destination() == self.export_height_map {
+                Some(Action::ExportHeight)
+            } else if message.destination() == self.import_splat_map {
+                Some(Action::ImportSplat)
+            } else if message.destination() == self.export_splat_map {
+                Some(Action::ExportSplat)
+            } else {
+                None
+            };
+            if let Some(action) = action {
+                self.open_file_selector(action, ui);
+            }
+        } else if let Some(msg) = message.data::<FileSelectorMessage>() {
+            if message.destination() == self.file_selector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                match msg {
+                    FileSelectorMessage::Commit(path) => {
+                        if let Some(action) = self.action {
+                            self.perform(action, path, terrain, graph, sender);
+                        }
+                        self.destroy_file_selector(ui);
+                    }
+                    FileSelectorMessage::Cancel => self.destroy_file_selector(ui),
+                    _ => (),
+                }
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(msg)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                PropertyAction::from_field_kind(&msg.value).apply(
+                    &msg.path(),
+                    &mut self.options,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+            }
+        }
+    }
+
+    pub fn close(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::close(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    pub fn destroy(&self, ui: &UserInterface) {
+        ui.send_message(WidgetMessage::remove(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+}
@@ -13,6 +13,8 @@ use crate::fyrox::{
     },
     engine::Engine,
     gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
         inspector::{
             editors::{
                 enumeration::EnumPropertyEditorDefinition, PropertyEditorDefinitionContainer,
@@ -52,8 +54,11 @@ use crate::{
     MSG_SYNC_FLAG,
 };
 use fyrox::asset::untyped::ResourceKind;
+use import_export::ImportExportPanel;
 use std::sync::Arc;
 
+mod import_export;
+
 pub struct TerrainInteractionMode {
     heightmaps: Vec<Vec<f32>>,
     masks: Vec<Vec<u8>>,
@@ -62,6 +67,7 @@ pub struct TerrainInteractionMode {
     brush_gizmo: BrushGizmo,
     brush: Brush,
     brush_panel: BrushPanel,
+    import_export_panel: ImportExportPanel,
     scene_viewer_frame: Handle<UiNode>,
 }
 
@@ -78,11 +84,13 @@ impl TerrainInteractionMode {
             mode: BrushMode::ModifyHeightMap { amount: 1.0 },
         };
 
-        let brush_panel =
-            BrushPanel::new(&mut engine.user_interfaces.first_mut().build_ctx(), &brush);
+        let ctx = &mut engine.user_interfaces.first_mut().build_ctx();
+        let brush_panel = BrushPanel::new(ctx, &brush);
+        let import_export_panel = ImportExportPanel::new(ctx);
 
         Self {
             brush_panel,
+            import_export_panel,
             heightmaps: Default::default(),
             brush_gizmo: BrushGizmo::new(game_scene, engine),
             interacting: false,
@@ -375,37 +383,55 @@ impl InteractionMode for TerrainInteractionMode {
         self.brush_gizmo
             .set_visible(&mut engine.scenes[game_scene.scene].graph, false);
 
-        engine
-            .user_interfaces
-            .first_mut()
-            .send_message(WindowMessage::close(
-                self.brush_panel.window,
-                MessageDirection::ToWidget,
-            ));
+        let ui = engine.user_interfaces.first_mut();
+        ui.send_message(WindowMessage::close(
+            self.brush_panel.window,
+            MessageDirection::ToWidget,
+        ));
+        self.import_export_panel.close(ui);
     }
 
     fn handle_ui_message(
         &mut self,
         message: &UiMessage,
         editor_selection: &Selection,
-        _controller: &mut dyn SceneController,
-        _engine: &mut Engine,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
     ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
         if let Some(selection) = editor_selection.as_graph() {
             if selection.is_single_selection() {
+                let ui = engine.user_interfaces.first_mut();
+
                 self.brush_panel.handle_ui_message(message, &mut self.brush);
+
+                if let Some(ButtonMessage::Click) = message.data() {
+                    if message.destination() == self.brush_panel.import_export {
+                        self.import_export_panel.open(ui);
+                    }
+                }
+
+                self.import_export_panel.handle_ui_message(
+                    message,
+                    selection.nodes()[0],
+                    &mut engine.scenes[game_scene.scene].graph,
+                    ui,
+                    &self.message_sender,
+                );
             }
         }
     }
 
     fn on_drop(&mut self, engine: &mut Engine) {
-        engine
-            .user_interfaces
-            .first_mut()
-            .send_message(WidgetMessage::remove(
-                self.brush_panel.window,
-                MessageDirection::ToWidget,
-            ));
+        let ui = engine.user_interfaces.first_mut();
+        ui.send_message(WidgetMessage::remove(
+            self.brush_panel.window,
+            MessageDirection::ToWidget,
+        ));
+        self.import_export_panel.destroy(ui);
     }
 
     fn on_hot_key(
@@ -501,7 +527,7 @@ impl InteractionMode for TerrainInteractionMode {
 
         make_interaction_mode_button(
             ctx,
-            include_bytes!("../../resources/terrain.png"),
+            include_bytes!("../../../resources/terrain.png"),
             terrain_mode_tooltip,
             selected,
         )
@@ -515,6 +541,7 @@ impl InteractionMode for TerrainInteractionMode {
 struct BrushPanel {
     window: Handle<UiNode>,
     inspector: Handle<UiNode>,
+    import_export: Handle<UiNode>,
 }
 
 fn make_brush_mode_enum_property_editor_definition() -> EnumPropertyEditorDefinition<BrushMode> {
@@ -578,21 +605,40 @@ impl BrushPanel {
             Default::default(),
         );
 
-        let inspector;
-        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(150.0))
+        let inspector = InspectorBuilder::new(WidgetBuilder::new().on_row(0))
+            .with_context(context)
+            .build(ctx);
+        let import_export = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .with_height(24.0)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_text("Import/Export...")
+        .build(ctx);
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(180.0))
             .can_minimize(false)
             .can_maximize(false)
-            .with_content({
-                inspector = InspectorBuilder::new(WidgetBuilder::new())
-                    .with_context(context)
-                    .build(ctx);
-                inspector
-            })
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(inspector)
+                        .with_child(import_export),
+                )
+                .add_row(Row::stretch())
+                .add_row(Row::auto())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
             .open(false)
             .with_title(WindowTitle::text("Brush Options"))
             .build(ctx);
 
-        Self { window, inspector }
+        Self {
+            window,
+            inspector,
+            import_export,
+        }
     }
 
     fn sync_to_model(&self, ui: &mut UserInterface, brush: &Brush) {
@@ -0,0 +1,195 @@
+//! Layers panel allows to hide or lock every node on a layer at once. See [`Base::set_layer`] for
+//! more info about layers.
+//!
+//! [`Base::set_layer`]: crate::fyrox::scene::base::Base::set_layer
+
+use crate::{
+    fyrox::{
+        core::{pool::Handle, scope_profile},
+        graph::{BaseSceneGraph, SceneGraph},
+        gui::{
+            check_box::{CheckBoxBuilder, CheckBoxMessage},
+            grid::{Column, GridBuilder, Row},
+            message::{MessageDirection, UiMessage},
+            scroll_viewer::ScrollViewerBuilder,
+            text::{TextBuilder, TextMessage},
+            widget::WidgetBuilder,
+            window::{WindowBuilder, WindowTitle},
+            BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
+        },
+        scene::base::LAYER_COUNT,
+    },
+    message::MessageSender,
+    scene::{commands::ChangeSelectionCommand, GameScene, Selection},
+    send_sync_message, Engine,
+};
+
+struct LayerView {
+    count: Handle<UiNode>,
+    visible: Handle<UiNode>,
+    locked: Handle<UiNode>,
+}
+
+pub struct LayersPanel {
+    pub window: Handle<UiNode>,
+    views: Vec<LayerView>,
+}
+
+fn make_text(ctx: &mut BuildContext, text: &str, row: usize, column: usize) -> Handle<UiNode> {
+    TextBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(column)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .build(ctx)
+}
+
+fn make_check_box(
+    ctx: &mut BuildContext,
+    checked: bool,
+    row: usize,
+    column: usize,
+) -> Handle<UiNode> {
+    CheckBoxBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(column)
+            .with_horizontal_alignment(HorizontalAlignment::Center)
+            .with_vertical_alignment(VerticalAlignment::Center)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .checked(Some(checked))
+    .build(ctx)
+}
+
+impl LayersPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let mut children = vec![
+            make_text(ctx, "Layer", 0, 0),
+            make_text(ctx, "Nodes", 0, 1),
+            make_text(ctx, "Visible", 0, 2),
+            make_text(ctx, "Locked", 0, 3),
+        ];
+        let mut views = Vec::new();
+        for layer in 0..LAYER_COUNT as usize {
+            let row = layer + 1;
+            let view = LayerView {
+                count: make_text(ctx, "0", row, 1),
+                visible: make_check_box(ctx, true, row, 2),
+                locked: make_check_box(ctx, false, row, 3),
+            };
+            children.extend([
+                make_text(ctx, &format!("Layer {layer}"), row, 0),
+                view.count,
+                view.visible,
+                view.locked,
+            ]);
+            views.push(view);
+        }
+
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("LayersPanel")
+                .with_width(300.0)
+                .with_height(400.0),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Layers"))
+        .with_content(
+            ScrollViewerBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+                .with_content(
+                    GridBuilder::new(WidgetBuilder::new().with_children(children))
+                        .add_rows((0..=LAYER_COUNT).map(|_| Row::strict(22.0)).collect())
+                        .add_column(Column::stretch())
+                        .add_column(Column::strict(50.0))
+                        .add_column(Column::strict(50.0))
+                        .add_column(Column::strict(50.0))
+                        .build(ctx),
+                )
+                .build(ctx),
+        )
+        .build(ctx);
+
+        Self { window, views }
+    }
+
+    pub fn sync_to_model(&self, game_scene: &GameScene, engine: &mut Engine) {
+        let graph = &engine.scenes[game_scene.scene].graph;
+
+        let mut counts = [0usize; LAYER_COUNT as usize];
+        for handle in graph.traverse_handle_iter(game_scene.scene_content_root) {
+            if handle != game_scene.scene_content_root {
+                if let Some(count) = counts.get_mut(graph[handle].layer() as usize) {
+                    *count += 1;
+                }
+            }
+        }
+
+        let ui = engine.user_interfaces.first();
+        for (layer, (view, count)) in self.views.iter().zip(counts).enumerate() {
+            let layer = layer as u8;
+            send_sync_message(
+                ui,
+                TextMessage::text(view.count, MessageDirection::ToWidget, count.to_string()),
+            );
+            sync_check_box(ui, view.visible, graph.is_layer_visible(layer));
+            sync_check_box(
+                ui,
+                view.locked,
+                game_scene.camera_controller.is_layer_locked(layer),
+            );
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        selection: &Selection,
+        game_scene: &mut GameScene,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) {
+        scope_profile!();
+
+        let Some(CheckBoxMessage::Check(Some(value))) = message.data() else {
+            return;
+        };
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        for (layer, view) in self.views.iter().enumerate() {
+            let layer = layer as u8;
+            if message.destination() == view.visible {
+                engine.scenes[game_scene.scene]
+                    .graph
+                    .set_layer_visibility(layer, *value);
+            } else if message.destination() == view.locked {
+                game_scene.camera_controller.set_layer_locked(layer, *value);
+
+                // Locked nodes cannot be edited, so they must be removed from the selection.
+                if let (true, Some(graph_selection)) = (*value, selection.as_graph()) {
+                    let graph = &engine.scenes[game_scene.scene].graph;
+                    let mut new_selection = graph_selection.clone();
+                    new_selection
+                        .nodes
+                        .retain(|node| graph.try_get(*node).map_or(true, |n| n.layer() != layer));
+                    if &new_selection != graph_selection {
+                        sender
+                            .do_command(ChangeSelectionCommand::new(Selection::new(new_selection)));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn sync_check_box(ui: &UserInterface, check_box: Handle<UiNode>, value: bool) {
+    send_sync_message(
+        ui,
+        CheckBoxMessage::checked(check_box, MessageDirection::ToWidget, Some(value)),
+    );
+}
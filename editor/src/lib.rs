@@ -19,36 +19,48 @@ pub mod command;
 pub mod configurator;
 pub mod curve_editor;
 pub mod export;
+pub mod flame_graph;
 pub mod gui;
 pub mod highlight;
+pub mod hud;
 pub mod inspector;
 pub mod interaction;
+pub mod layers;
 pub mod light;
+pub mod localization;
+pub mod lod;
 pub mod log;
 pub mod material;
+pub mod memory;
 pub mod menu;
 pub mod mesh;
 pub mod message;
 pub mod overlay;
+pub mod palette;
 pub mod particle;
 pub mod physics;
 pub mod plugin;
+pub mod plugin_ui;
 pub mod plugins;
 pub mod preview;
+pub mod recovery;
+pub mod remote;
+pub mod replication;
 pub mod scene;
 pub mod scene_viewer;
 pub mod settings;
 pub mod stats;
 pub mod ui_scene;
 pub mod utils;
+pub mod validation;
 pub mod world;
 
 pub use fyrox;
 
 use crate::{
     absm::AbsmEditor,
-    animation::AnimationEditor,
-    asset::AssetBrowser,
+    animation::{pose::PoseLibraryPanel, AnimationEditor},
+    asset::{registry::AssetTypeDefinition, AssetBrowser},
     audio::{preview::AudioPreviewPanel, AudioPanel},
     build::BuildWindow,
     camera::panel::CameraPreviewControlPanel,
@@ -56,8 +68,11 @@ use crate::{
     configurator::Configurator,
     curve_editor::CurveEditorWindow,
     export::ExportWindow,
+    flame_graph::FlameGraphWindow,
     fyrox::{
-        asset::{io::FsResourceIo, manager::ResourceManager, untyped::UntypedResource},
+        asset::{
+            io::FsResourceIo, manager::ResourceManager, untyped::UntypedResource, TypedResourceData,
+        },
         core::{
             algebra::{Matrix3, Vector2},
             color::Color,
@@ -108,11 +123,14 @@ use crate::{
             CompressionOptions, TextureImportOptions, TextureKind, TextureMinificationFilter,
             TextureResource, TextureResourceExtension,
         },
-        scene::{graph::GraphUpdateSwitches, mesh::Mesh, Scene, SceneLoader},
+        scene::{
+            graph::GraphUpdateSwitches, mesh::Mesh, tilemap::tileset::TileSet, Scene, SceneLoader,
+        },
         utils::{translate_cursor_icon, translate_event},
         window::{Icon, WindowAttributes},
     },
     highlight::HighlightRenderPass,
+    hud::PerformanceHud,
     inspector::Inspector,
     interaction::{
         move_mode::MoveInteractionMode,
@@ -122,17 +140,26 @@ use crate::{
         select_mode::SelectInteractionMode,
         terrain::TerrainInteractionMode,
     },
+    layers::LayersPanel,
     light::LightPanel,
+    localization::LocalizationEditor,
+    lod::LodControlPanel,
     log::LogPanel,
     material::MaterialEditor,
+    memory::MemoryWindow,
     menu::{Menu, MenuContext, Panels},
     mesh::{MeshControlPanel, SurfaceDataViewer},
     message::MessageSender,
     overlay::OverlayRenderPass,
+    palette::ProjectColorPalettes,
     particle::ParticleSystemPreviewControlPanel,
     physics::ColliderControlPanel,
     plugin::EditorPlugin,
+    plugin_ui::PluginUi,
     plugins::collider::ColliderShapePlugin,
+    recovery::{CrashRecovery, RecoveredWindow, RecoverySession},
+    remote::{runtime::RuntimeInspector, RemoteControl},
+    replication::ReplicationPanel,
     scene::{
         commands::{
             make_delete_selection_command, ChangeSelectionCommand, GameSceneContext, PasteCommand,
@@ -150,7 +177,16 @@ use crate::{
         commands::graph::PasteWidgetCommand, menu::WidgetContextMenu,
         utils::UiSceneWorldViewerDataProvider, UiScene,
     },
-    utils::{doc::DocWindow, path_fixer::PathFixer, ragdoll::RagdollWizard},
+    utils::{
+        capture::CaptureTool,
+        doc::DocWindow,
+        path_fixer::PathFixer,
+        ragdoll::RagdollWizard,
+        rename::{BatchRenameDialog, BatchRenameTarget},
+        skybox::SkyboxEditor,
+        vertex_animation::VertexAnimationBaker,
+    },
+    validation::ValidationPanel,
     world::{graph::menu::SceneNodeContextMenu, graph::EditorSceneWrapper, WorldViewer},
 };
 use std::{
@@ -168,6 +204,10 @@ use std::{
     time::{Duration, Instant},
 };
 
+use crate::plugins::sequencer::SequencerPlugin;
+use crate::plugins::shader_graph::ShaderGraphPlugin;
+use crate::plugins::skin2d::SkinWeightPaintPlugin;
+use crate::plugins::sprite_sheet::SpriteSheetEditorPlugin;
 use crate::plugins::tilemap::TileMapEditorPlugin;
 pub use message::Message;
 
@@ -492,6 +532,9 @@ pub struct Editor {
     pub exit_message_box: Handle<UiNode>,
     pub save_scene_dialog: SaveSceneConfirmationDialog,
     pub light_panel: LightPanel,
+    pub validation_panel: ValidationPanel,
+    pub layers_panel: LayersPanel,
+    pub pose_library_panel: PoseLibraryPanel,
     pub menu: Menu,
     pub exit: bool,
     pub configurator: Configurator,
@@ -504,6 +547,10 @@ pub struct Editor {
     pub material_editor: MaterialEditor,
     pub inspector: Inspector,
     pub curve_editor: CurveEditorWindow,
+    pub localization_editor: LocalizationEditor,
+    pub replication_panel: ReplicationPanel,
+    pub remote_control: RemoteControl,
+    pub runtime_inspector: RuntimeInspector,
     pub audio_panel: AudioPanel,
     pub absm_editor: AbsmEditor,
     pub mode: Mode,
@@ -513,16 +560,24 @@ pub struct Editor {
     pub particle_system_control_panel: ParticleSystemPreviewControlPanel,
     pub camera_control_panel: CameraPreviewControlPanel,
     pub mesh_control_panel: MeshControlPanel,
+    pub lod_control_panel: LodControlPanel,
     pub audio_preview_panel: AudioPreviewPanel,
     pub doc_window: DocWindow,
     pub docking_manager: Handle<UiNode>,
     pub node_removal_dialog: NodeRemovalDialog,
     pub engine: Engine,
     pub plugins: Vec<Option<Box<dyn EditorPlugin>>>,
+    pub plugin_ui: PluginUi,
+    pub color_palettes: ProjectColorPalettes,
     pub focused: bool,
     pub update_loop_state: UpdateLoopState,
     pub is_suspended: bool,
     pub ragdoll_wizard: RagdollWizard,
+    pub vertex_animation_baker: VertexAnimationBaker,
+    pub skybox_editor: SkyboxEditor,
+    pub batch_rename_dialog: BatchRenameDialog,
+    pub capture_tool: CaptureTool,
+    pub crash_recovery: CrashRecovery,
     pub scene_node_context_menu: Rc<RefCell<SceneNodeContextMenu>>,
     pub widget_context_menu: Rc<RefCell<WidgetContextMenu>>,
     pub collider_control_panel: ColliderControlPanel,
@@ -530,6 +585,9 @@ pub struct Editor {
     pub highlighter: Option<Rc<RefCell<HighlightRenderPass>>>,
     pub export_window: Option<ExportWindow>,
     pub statistics_window: Option<StatisticsWindow>,
+    pub performance_hud: Option<PerformanceHud>,
+    pub flame_graph: Option<FlameGraphWindow>,
+    pub memory_window: Option<MemoryWindow>,
     pub surface_data_viewer: Option<SurfaceDataViewer>,
 }
 
@@ -606,6 +664,15 @@ impl Editor {
         let asset_browser = AssetBrowser::new(&mut engine);
         let menu = Menu::new(&mut engine, message_sender.clone(), &settings);
         let light_panel = LightPanel::new(&mut engine, message_sender.clone());
+        let validation_panel =
+            ValidationPanel::new(&mut engine.user_interfaces.first_mut().build_ctx());
+        let runtime_inspector =
+            RuntimeInspector::new(&mut engine.user_interfaces.first_mut().build_ctx());
+        let layers_panel = LayersPanel::new(&mut engine.user_interfaces.first_mut().build_ctx());
+        let pose_library_panel = PoseLibraryPanel::new(
+            &mut engine.user_interfaces.first_mut().build_ctx(),
+            message_sender.clone(),
+        );
         let audio_panel = AudioPanel::new(&mut engine, message_sender.clone());
 
         let ctx = &mut engine.user_interfaces.first_mut().build_ctx();
@@ -614,19 +681,29 @@ impl Editor {
         let widget_context_menu = Rc::new(RefCell::new(WidgetContextMenu::new(ctx)));
         let world_outliner = WorldViewer::new(ctx, message_sender.clone(), &settings);
         let command_stack_viewer = CommandStackViewer::new(ctx, message_sender.clone());
-        let log = LogPanel::new(ctx, log_message_receiver);
+        let log = LogPanel::new(ctx, log_message_receiver, message_sender.clone());
         let inspector = Inspector::new(ctx, message_sender.clone());
         let animation_editor = AnimationEditor::new(ctx);
         let absm_editor = AbsmEditor::new(ctx, message_sender.clone());
         let particle_system_control_panel =
             ParticleSystemPreviewControlPanel::new(scene_viewer.frame(), ctx);
-        let camera_control_panel = CameraPreviewControlPanel::new(scene_viewer.frame(), ctx);
+        let camera_control_panel = CameraPreviewControlPanel::new(
+            scene_viewer.frame(),
+            scene_viewer.framing_overlay(),
+            ctx,
+        );
         let mesh_control_panel = MeshControlPanel::new(scene_viewer.frame(), ctx);
+        let lod_control_panel = LodControlPanel::new(scene_viewer.frame(), ctx);
         let audio_preview_panel = AudioPreviewPanel::new(scene_viewer.frame(), ctx);
         let collider_control_panel = ColliderControlPanel::new(scene_viewer.frame(), ctx);
         let doc_window = DocWindow::new(ctx);
         let node_removal_dialog = NodeRemovalDialog::new(ctx);
         let ragdoll_wizard = RagdollWizard::new(ctx, message_sender.clone());
+        let vertex_animation_baker = VertexAnimationBaker::new(ctx, message_sender.clone());
+        let skybox_editor = SkyboxEditor::new(ctx, message_sender.clone());
+        let batch_rename_dialog = BatchRenameDialog::new(ctx, message_sender.clone());
+        let capture_tool = CaptureTool::new(ctx, message_sender.clone());
+        let crash_recovery = CrashRecovery::new(ctx);
 
         let docking_manager;
         let root_grid = GridBuilder::new(
@@ -752,11 +829,17 @@ impl Editor {
                             particle_system_control_panel.window,
                             camera_control_panel.window,
                             mesh_control_panel.window,
+                            lod_control_panel.window,
                             audio_preview_panel.window,
                             collider_control_panel.window,
                             navmesh_panel.window,
                             doc_window.window,
                             light_panel.window,
+                            validation_panel.window,
+                            runtime_inspector.window,
+                            layers_panel.window,
+                            pose_library_panel.window,
+                            audio_panel.bus_graph.window,
                         ])
                         .build(ctx);
                     docking_manager
@@ -791,6 +874,10 @@ impl Editor {
 
         let curve_editor = CurveEditorWindow::new(ctx);
 
+        let localization_editor = LocalizationEditor::new(ctx);
+
+        let replication_panel = ReplicationPanel::new(ctx);
+
         let save_scene_dialog = SaveSceneConfirmationDialog::new(ctx);
 
         let build_window = BuildWindow::new(ctx);
@@ -810,6 +897,12 @@ impl Editor {
                 ));
         }
 
+        let plugin_ui = PluginUi::new(
+            menu.plugins_menu,
+            docking_manager,
+            scene_viewer.contextual_actions(),
+        );
+
         let editor = Self {
             docking_manager,
             animation_editor,
@@ -828,6 +921,9 @@ impl Editor {
             configurator,
             log,
             light_panel,
+            validation_panel,
+            layers_panel,
+            pose_library_panel,
             command_stack_viewer,
             validation_message_box,
             settings,
@@ -835,6 +931,10 @@ impl Editor {
             material_editor,
             inspector,
             curve_editor,
+            localization_editor,
+            replication_panel,
+            remote_control: Default::default(),
+            runtime_inspector,
             audio_panel,
             save_scene_dialog,
             mode: Mode::Edit,
@@ -848,13 +948,20 @@ impl Editor {
             particle_system_control_panel,
             camera_control_panel,
             mesh_control_panel,
+            lod_control_panel,
             audio_preview_panel,
             node_removal_dialog,
             doc_window,
             plugins: vec![
                 Some(Box::new(ColliderShapePlugin::default())),
                 Some(Box::new(TileMapEditorPlugin::default())),
+                Some(Box::new(SpriteSheetEditorPlugin::default())),
+                Some(Box::new(SkinWeightPaintPlugin::default())),
+                Some(Box::new(SequencerPlugin::default())),
+                Some(Box::new(ShaderGraphPlugin::default())),
             ],
+            plugin_ui,
+            color_palettes: ProjectColorPalettes::load(),
             // Apparently, some window managers (like Wayland), does not send `Focused` event after the window
             // was created. So we must assume that the editor is focused by default, otherwise editor's thread
             // will sleep forever and the window won't come up.
@@ -862,6 +969,11 @@ impl Editor {
             update_loop_state: UpdateLoopState::default(),
             is_suspended: false,
             ragdoll_wizard,
+            vertex_animation_baker,
+            skybox_editor,
+            batch_rename_dialog,
+            capture_tool,
+            crash_recovery,
             scene_node_context_menu,
             widget_context_menu,
             collider_control_panel,
@@ -869,6 +981,9 @@ impl Editor {
             highlighter: None,
             export_window: None,
             statistics_window: None,
+            performance_hud: None,
+            flame_graph: None,
+            memory_window: None,
             surface_data_viewer: None,
         };
 
@@ -1146,6 +1261,10 @@ impl Editor {
         let current_scene_entry = self.scenes.current_scene_entry_mut();
 
         self.configurator.handle_ui_message(message, engine);
+        self.plugin_ui
+            .handle_ui_message(message, engine.user_interfaces.first());
+        self.color_palettes
+            .handle_ui_message(message, engine.user_interfaces.first());
         self.menu.handle_ui_message(
             message,
             MenuContext {
@@ -1157,19 +1276,32 @@ impl Editor {
                     world_outliner_window: self.world_viewer.window,
                     asset_window: self.asset_browser.window,
                     light_panel: self.light_panel.window,
+                    validation_panel: self.validation_panel.window,
+                    runtime_inspector: self.runtime_inspector.window,
+                    layers_panel: self.layers_panel.window,
+                    pose_library_panel: self.pose_library_panel.window,
                     log_panel: self.log.window,
                     navmesh_panel: self.navmesh_panel.window,
                     audio_panel: self.audio_panel.window,
+                    audio_bus_graph: self.audio_panel.bus_graph.window,
                     configurator_window: self.configurator.window,
                     path_fixer: self.path_fixer.window,
                     curve_editor: &self.curve_editor,
+                    localization_editor: &self.localization_editor,
+                    replication_panel: &self.replication_panel,
                     absm_editor: &self.absm_editor,
                     command_stack_panel: self.command_stack_viewer.window,
                     scene_settings: &self.scene_settings,
                     animation_editor: &self.animation_editor,
                     ragdoll_wizard: &self.ragdoll_wizard,
+                    vertex_animation_baker: &self.vertex_animation_baker,
+                    skybox_editor: &self.skybox_editor,
+                    capture_tool: &self.capture_tool,
                     export_window: &mut self.export_window,
                     statistics_window: &mut self.statistics_window,
+                    performance_hud: &mut self.performance_hud,
+                    flame_graph: &mut self.flame_graph,
+                    memory_window: &mut self.memory_window,
                 },
                 settings: &mut self.settings,
             },
@@ -1188,7 +1320,23 @@ impl Editor {
         self.asset_browser
             .handle_ui_message(message, engine, self.message_sender.clone());
         self.command_stack_viewer.handle_ui_message(message);
+        self.runtime_inspector
+            .handle_ui_message(message, engine.user_interfaces.first());
         self.curve_editor.handle_ui_message(message, engine);
+        self.localization_editor.handle_ui_message(message, engine);
+        self.capture_tool.handle_ui_message(message, engine);
+        self.batch_rename_dialog.handle_ui_message(
+            message,
+            engine.user_interfaces.first_mut(),
+            &engine.resource_manager,
+            &self.message_sender,
+        );
+        self.replication_panel.handle_ui_message(
+            message,
+            &self.scenes,
+            engine,
+            &self.message_sender,
+        );
         self.path_fixer.handle_ui_message(
             message,
             engine.user_interfaces.first_mut(),
@@ -1216,6 +1364,27 @@ impl Editor {
                 self.statistics_window.take();
             }
         }
+        if let Some(hud) = self.performance_hud.as_ref() {
+            if let StatisticsWindowAction::Remove =
+                hud.handle_ui_message(message, engine.user_interfaces.first())
+            {
+                self.performance_hud.take();
+            }
+        }
+        if let Some(flame_graph) = self.flame_graph.as_mut() {
+            if let StatisticsWindowAction::Remove =
+                flame_graph.handle_ui_message(message, engine.user_interfaces.first())
+            {
+                self.flame_graph.take();
+            }
+        }
+        if let Some(memory_window) = self.memory_window.as_mut() {
+            if let StatisticsWindowAction::Remove =
+                memory_window.handle_ui_message(message, engine.user_interfaces.first())
+            {
+                self.memory_window.take();
+            }
+        }
 
         let current_scene_entry = self.scenes.current_scene_entry_mut();
 
@@ -1226,6 +1395,7 @@ impl Editor {
                 &mut *current_scene_entry.controller,
                 engine,
                 &self.message_sender,
+                &mut self.settings,
             );
 
             if let Some(game_scene) = current_scene_entry.controller.downcast_mut::<GameScene>() {
@@ -1240,6 +1410,21 @@ impl Editor {
                     &self.message_sender,
                     game_scene.graph_switches.node_overrides.as_mut().unwrap(),
                 );
+                if let Some(property_path) = self.inspector.recorded_property(message) {
+                    if let Some(node) = current_scene_entry
+                        .selection
+                        .as_graph()
+                        .and_then(|selection| selection.nodes.first())
+                    {
+                        self.animation_editor.record_property(
+                            *node,
+                            property_path,
+                            &current_scene_entry.selection,
+                            graph,
+                            &self.message_sender,
+                        );
+                    }
+                }
                 self.absm_editor.handle_ui_message(
                     message,
                     &self.message_sender,
@@ -1255,6 +1440,21 @@ impl Editor {
                     game_scene,
                     &self.message_sender,
                 );
+                self.vertex_animation_baker.handle_ui_message(
+                    message,
+                    engine.user_interfaces.first_mut(),
+                    graph,
+                    &current_scene_entry.selection,
+                    &engine.resource_manager,
+                    &self.message_sender,
+                );
+                self.skybox_editor.handle_ui_message(
+                    message,
+                    engine.user_interfaces.first_mut(),
+                    graph,
+                    &current_scene_entry.selection,
+                    &self.message_sender,
+                );
                 self.particle_system_control_panel.handle_ui_message(
                     message,
                     &current_scene_entry.selection,
@@ -1274,6 +1474,12 @@ impl Editor {
                     engine,
                     &self.message_sender,
                 );
+                self.lod_control_panel.handle_ui_message(
+                    message,
+                    game_scene,
+                    engine,
+                    &self.message_sender,
+                );
                 self.collider_control_panel.handle_ui_message(
                     message,
                     engine,
@@ -1294,6 +1500,12 @@ impl Editor {
                     &self.message_sender,
                     engine,
                 );
+                self.audio_panel.bus_graph.handle_ui_message(
+                    message,
+                    game_scene,
+                    engine,
+                    &self.message_sender,
+                );
                 self.node_removal_dialog.handle_ui_message(
                     &current_scene_entry.selection,
                     game_scene,
@@ -1304,8 +1516,13 @@ impl Editor {
                 self.scene_settings
                     .handle_ui_message(message, &self.message_sender);
 
-                self.navmesh_panel
-                    .handle_message(message, &current_scene_entry.selection);
+                self.navmesh_panel.handle_message(
+                    message,
+                    &current_scene_entry.selection,
+                    game_scene,
+                    engine,
+                    &self.settings,
+                );
 
                 if let Some(interaction_mode) = current_scene_entry
                     .current_interaction_mode
@@ -1340,12 +1557,33 @@ impl Editor {
                         resource_manager: &engine.resource_manager,
                         instantiation_scale: self.settings.model.instantiation_scale,
                     },
-                    engine.user_interfaces.first(),
+                    engine.user_interfaces.first_mut(),
                     &mut self.settings,
                 );
 
                 self.light_panel
                     .handle_ui_message(message, game_scene, engine);
+                self.validation_panel.handle_ui_message(
+                    message,
+                    game_scene,
+                    engine,
+                    &self.message_sender,
+                );
+                self.layers_panel.handle_ui_message(
+                    message,
+                    &current_scene_entry.selection,
+                    game_scene,
+                    engine,
+                    &self.message_sender,
+                );
+                self.pose_library_panel.handle_ui_message(
+                    message,
+                    &current_scene_entry.selection,
+                    game_scene,
+                    engine,
+                    &mut self.animation_editor,
+                    &self.message_sender,
+                );
             } else if let Some(ui_scene) = current_scene_entry.controller.downcast_mut::<UiScene>()
             {
                 let ui_root = ui_scene.ui.root();
@@ -1359,6 +1597,21 @@ impl Editor {
                     &self.message_sender,
                     ui_scene.ui_update_switches.node_overrides.as_mut().unwrap(),
                 );
+                if let Some(property_path) = self.inspector.recorded_property(message) {
+                    if let Some(widget) = current_scene_entry
+                        .selection
+                        .as_ui()
+                        .and_then(|selection| selection.widgets.first())
+                    {
+                        self.animation_editor.record_property(
+                            *widget,
+                            property_path,
+                            &current_scene_entry.selection,
+                            &ui_scene.ui,
+                            &self.message_sender,
+                        );
+                    }
+                }
                 self.absm_editor.handle_ui_message(
                     message,
                     &self.message_sender,
@@ -1376,7 +1629,7 @@ impl Editor {
                         sender: &self.message_sender,
                         resource_manager: &engine.resource_manager,
                     },
-                    engine.user_interfaces.first(),
+                    engine.user_interfaces.first_mut(),
                     &mut self.settings,
                 );
 
@@ -1394,7 +1647,15 @@ impl Editor {
         }
 
         if let Some(MessageBoxMessage::Close(result)) = message.data() {
-            if message.destination() == self.exit_message_box {
+            if message.destination() == self.crash_recovery.message_box() {
+                if let Some(session) = self.crash_recovery.take_pending() {
+                    if *result == MessageBoxResult::Yes {
+                        self.restore_session(session);
+                    } else {
+                        RecoverySession::clear();
+                    }
+                }
+            } else if message.destination() == self.exit_message_box {
                 match result {
                     MessageBoxResult::No => {
                         self.message_sender.send(Message::Exit { force: true });
@@ -1473,6 +1734,21 @@ impl Editor {
 
         process.arg("--").arg("--override-scene").arg(path);
 
+        let remote_settings = &self.settings.remote_control;
+        if remote_settings.runtime_inspection {
+            process
+                .arg("--inspection-port")
+                .arg(remote_settings.runtime_inspection_port.to_string())
+                .arg("--inspection-token")
+                .arg(&remote_settings.token)
+                .arg("--inspection-access")
+                .arg(if remote_settings.allow_runtime_tweaks {
+                    "tweak"
+                } else {
+                    "read-only"
+                });
+        }
+
         match process.spawn() {
             Ok(mut process) => {
                 let active = Arc::new(AtomicBool::new(true));
@@ -1564,6 +1840,8 @@ impl Editor {
         self.inspector.on_mode_changed(ui, &self.mode);
         self.audio_panel.on_mode_changed(ui, &self.mode);
         self.navmesh_panel.on_mode_changed(ui, &self.mode);
+        self.runtime_inspector
+            .on_mode_changed(ui, &self.mode, &self.settings.remote_control);
         self.menu.on_mode_changed(ui, &self.mode);
     }
 
@@ -1592,6 +1870,12 @@ impl Editor {
                     &mut current_scene_entry.selection,
                     engine,
                 ),
+                current_scene_entry
+                    .command_stack
+                    .commands
+                    .iter()
+                    .map(|command| command.thumbnail())
+                    .collect(),
                 engine.user_interfaces.first_mut(),
             );
             self.inspector.sync_to_model(
@@ -1599,6 +1883,7 @@ impl Editor {
                 &*current_scene_entry.controller,
                 engine,
                 &self.message_sender,
+                &self.settings,
             );
 
             if let Some(game_scene) = current_scene_entry.controller.downcast_mut::<GameScene>() {
@@ -1606,6 +1891,7 @@ impl Editor {
                     &current_scene_entry.selection,
                     engine.user_interfaces.first_mut(),
                     &engine.scenes[game_scene.scene].graph,
+                    &engine.resource_manager,
                 );
                 self.absm_editor.sync_to_model(
                     &current_scene_entry.selection,
@@ -1613,6 +1899,10 @@ impl Editor {
                     engine.user_interfaces.first_mut(),
                 );
                 self.scene_settings.sync_to_model(game_scene, engine);
+                self.layers_panel.sync_to_model(game_scene, engine);
+                self.lod_control_panel.sync_to_model(game_scene, engine);
+                self.pose_library_panel
+                    .sync_to_model(engine.user_interfaces.first_mut());
                 let sender = &self.message_sender;
                 self.world_viewer.sync_to_model(
                     &EditorSceneWrapper {
@@ -1631,6 +1921,7 @@ impl Editor {
                     .sync_to_model(engine.user_interfaces.first_mut());
                 self.audio_panel
                     .sync_to_model(&current_scene_entry.selection, game_scene, engine);
+                self.audio_panel.bus_graph.sync_to_model(game_scene, engine);
                 self.navmesh_panel.sync_to_model(
                     engine,
                     &current_scene_entry.selection,
@@ -1642,6 +1933,7 @@ impl Editor {
                     &current_scene_entry.selection,
                     engine.user_interfaces.first_mut(),
                     &ui_scene.ui,
+                    &engine.resource_manager,
                 );
                 self.absm_editor.sync_to_model(
                     &current_scene_entry.selection,
@@ -1943,6 +2235,66 @@ impl Editor {
         }
     }
 
+    fn autosave(&mut self) {
+        let mut windows = Vec::new();
+        for plugin in self.plugins.iter().flatten() {
+            plugin.on_save_session(&mut windows);
+        }
+
+        self.crash_recovery.autosave(
+            self.scenes.entries.iter_mut(),
+            windows,
+            &self.settings,
+            &mut self.engine,
+        );
+    }
+
+    fn restore_session(&mut self, session: RecoverySession) {
+        for scene in session.scenes {
+            let Some(load_path) = scene.recovery_path.clone().or_else(|| scene.path.clone()) else {
+                continue;
+            };
+
+            self.load_scene(load_path.clone());
+
+            if scene.recovery_path.is_some() {
+                // The scene was loaded from its autosaved copy, so it must be bound to its
+                // original path again.
+                if let Some(entry) = self
+                    .scenes
+                    .entries
+                    .iter_mut()
+                    .find(|e| e.path.as_ref() == Some(&load_path))
+                {
+                    entry.path = scene.path;
+                    entry.has_unsaved_changes = true;
+                    entry.command_stack.journal = scene.journal;
+                }
+            }
+        }
+
+        for window in session.windows {
+            match window {
+                RecoveredWindow::TileSetEditor(path) => {
+                    match block_on(self.engine.resource_manager.request::<TileSet>(&path)) {
+                        Ok(tile_set) => self
+                            .message_sender
+                            .send(Message::OpenTileSetEditor(tile_set)),
+                        Err(e) => Log::err(format!(
+                            "Unable to restore tile set editor for {}. Reason: {:?}",
+                            path.display(),
+                            e
+                        )),
+                    }
+                }
+            }
+        }
+
+        self.sync_to_model();
+
+        Log::info("Previous session was successfully restored!");
+    }
+
     fn exit(&mut self, force: bool) {
         let engine = &mut self.engine;
         if force {
@@ -2075,13 +2427,22 @@ impl Editor {
 
         self.asset_browser.clear_preview(&mut self.engine);
 
+        self.crash_recovery.on_exit();
+
         std::env::set_current_dir(working_directory.clone()).unwrap();
 
         // We must re-read settings, because each project have its own unique settings.
         self.reload_settings();
 
+        self.color_palettes = ProjectColorPalettes::load();
+        self.color_palettes
+            .sync_to_pickers(self.engine.user_interfaces.first());
+
         self.load_layout();
 
+        self.crash_recovery
+            .on_configure(self.engine.user_interfaces.first());
+
         let engine = &mut self.engine;
 
         let graphics_context = engine.graphics_context.as_initialized_mut();
@@ -2248,8 +2609,25 @@ impl Editor {
         self.handle_modes();
 
         self.log.update(&mut self.engine);
+        self.remote_control.update(
+            &self.settings.remote_control,
+            &self.scenes,
+            &mut self.engine,
+            &self.message_sender,
+        );
+        self.runtime_inspector
+            .update(dt, self.engine.user_interfaces.first_mut());
         self.material_editor.update(&mut self.engine);
         self.asset_browser.update(&mut self.engine);
+        self.capture_tool
+            .update(self.engine.user_interfaces.first());
+        if self.capture_tool.is_capturing() {
+            // Frames are captured on every redraw, so the editor must not sleep.
+            self.update_loop_state.request_update_in_next_frame();
+        }
+        if self.crash_recovery.update(dt, &self.settings) && !self.is_in_preview_mode() {
+            self.autosave();
+        }
         if let Some(export_window) = self.export_window.as_mut() {
             export_window.update(self.engine.user_interfaces.first_mut());
         }
@@ -2257,6 +2635,23 @@ impl Editor {
             surface_data_viewer.update(&mut self.engine);
         }
 
+        if let Some(hud) = self.performance_hud.as_mut() {
+            let current_scene = self
+                .scenes
+                .current_scene_controller_ref()
+                .and_then(|c| c.downcast_ref::<GameScene>())
+                .map(|s| s.scene);
+            hud.update(dt, current_scene, &self.engine);
+        }
+
+        if let Some(flame_graph) = self.flame_graph.as_mut() {
+            flame_graph.update(dt, self.engine.user_interfaces.first());
+        }
+
+        if let Some(memory_window) = self.memory_window.as_mut() {
+            memory_window.update(dt, self.engine.user_interfaces.first());
+        }
+
         self.scene_viewer
             .pre_update(&self.settings, &mut self.engine);
         if let Some(entry) = self.scenes.current_scene_entry_ref() {
@@ -2266,6 +2661,7 @@ impl Editor {
                 }
 
                 self.light_panel.update(game_scene, &mut self.engine);
+                self.navmesh_panel.update(&self.engine);
                 self.animation_editor.update(
                     &entry.selection,
                     self.engine.user_interfaces.first(),
@@ -2283,6 +2679,10 @@ impl Editor {
             }
         }
 
+        let ui = self.engine.user_interfaces.first();
+        self.inspector
+            .set_record_buttons_visible(ui, self.animation_editor.can_record_keys(ui));
+
         if let Some(overlay_pass) = self.overlay_pass.as_ref() {
             overlay_pass.borrow_mut().pictogram_size = self.settings.debugging.pictogram_size;
         }
@@ -2326,6 +2726,12 @@ impl Editor {
                             game_scene,
                             &mut self.engine,
                         );
+                        self.lod_control_panel.handle_message(
+                            &message,
+                            &entry.selection,
+                            game_scene,
+                            &mut self.engine,
+                        );
                         self.collider_control_panel.handle_message(
                             &message,
                             &self.engine,
@@ -2429,6 +2835,16 @@ impl Editor {
                         );
                     }
                     Message::OpenMaterialEditor(material) => self.open_material_editor(material),
+                    Message::OpenCurveEditor(curve) => self
+                        .curve_editor
+                        .edit(curve, self.engine.user_interfaces.first()),
+                    Message::OpenAssetEditor(resource) => {
+                        if let Some(editor) =
+                            self.asset_browser.asset_types.editor(&resource.type_uuid())
+                        {
+                            editor(resource, self);
+                        }
+                    }
                     Message::OpenNodeRemovalDialog => {
                         if let Some(entry) = self.scenes.current_scene_entry_ref() {
                             // TODO
@@ -2441,6 +2857,25 @@ impl Editor {
                             }
                         }
                     }
+                    Message::OpenBatchRenameDialog(target) => match target {
+                        BatchRenameTarget::SelectedNodes => {
+                            if let Some(entry) = self.scenes.current_scene_entry_ref() {
+                                if let (Some(game_scene), Some(selection)) = (
+                                    entry.controller.downcast_ref::<GameScene>(),
+                                    entry.selection.as_graph(),
+                                ) {
+                                    self.batch_rename_dialog.open_for_nodes(
+                                        &selection.nodes,
+                                        &self.engine.scenes[game_scene.scene].graph,
+                                        self.engine.user_interfaces.first(),
+                                    );
+                                }
+                            }
+                        }
+                        BatchRenameTarget::Assets(paths) => self
+                            .batch_rename_dialog
+                            .open_for_assets(&paths, self.engine.user_interfaces.first()),
+                    },
                     Message::ShowInAssetBrowser(path) => {
                         self.asset_browser
                             .locate_path(self.engine.user_interfaces.first(), path);
@@ -2595,6 +3030,37 @@ impl Editor {
         }
     }
 
+    /// Registers a custom asset type in the editor. See [`AssetTypeDefinition`] docs for more info.
+    pub fn register_asset_type<T: TypedResourceData>(
+        &mut self,
+        definition: AssetTypeDefinition<T>,
+    ) {
+        let type_uuid = definition.type_uuid();
+
+        if let Some(loader) = definition.loader {
+            loader(&mut self.engine.resource_manager.state().loaders);
+        }
+
+        if let Some(template) = definition.template {
+            self.engine
+                .resource_manager
+                .state()
+                .constructors_container
+                .add_custom(type_uuid, template);
+        }
+
+        if let Some(preview_generator) = definition.preview_generator {
+            self.asset_browser
+                .preview_generators
+                .map
+                .insert(type_uuid, preview_generator);
+        }
+
+        if let Some(editor) = definition.editor {
+            self.asset_browser.asset_types.add_editor(type_uuid, editor);
+        }
+    }
+
     pub fn add_game_plugin<P>(&mut self, plugin: P)
     where
         P: Plugin + 'static,
@@ -2822,7 +3288,23 @@ impl Editor {
                                         .on_before_render(&entry.selection, &mut self.engine);
                                 }
 
+                                let render_start = Instant::now();
                                 self.engine.render().unwrap();
+                                if let Some(hud) = self.performance_hud.as_mut() {
+                                    hud.record_render(render_start.elapsed());
+                                }
+
+                                if let Some(game_scene) = self
+                                    .scenes
+                                    .current_scene_controller_ref()
+                                    .and_then(|c| c.downcast_ref::<GameScene>())
+                                {
+                                    self.capture_tool.capture(
+                                        game_scene,
+                                        &mut self.engine,
+                                        self.overlay_pass.as_ref(),
+                                    );
+                                }
 
                                 if let Some(scene) = self.scenes.current_scene_controller_mut() {
                                     scene.on_after_render(&mut self.engine);
@@ -2853,6 +3335,7 @@ impl Editor {
                     }
 
                     self.settings.force_save();
+                    self.crash_recovery.on_exit();
 
                     for_each_plugin!(self.plugins => on_exit(&mut self));
                 }
@@ -2924,6 +3407,8 @@ fn update(editor: &mut Editor, window_target: &EventLoopWindowTarget<()>) {
             }
         }
 
+        let update_start = Instant::now();
+
         editor.engine.pre_update(
             FIXED_TIMESTEP,
             window_target,
@@ -2983,10 +3468,17 @@ fn update(editor: &mut Editor, window_target: &EventLoopWindowTarget<()>) {
 
         editor.update(FIXED_TIMESTEP);
 
+        let update_time = update_start.elapsed();
+        let ui_layout_start = Instant::now();
+
         editor
             .engine
             .post_update(FIXED_TIMESTEP, &Default::default());
 
+        if let Some(hud) = editor.performance_hud.as_mut() {
+            hud.record_update(update_time, ui_layout_start.elapsed());
+        }
+
         if need_reload_plugins {
             let on_plugin_reloaded = |plugin: &dyn Plugin| {
                 *editor.inspector.property_editors.context_type_id.lock() = plugin.type_id();
@@ -15,7 +15,8 @@ use crate::fyrox::{
         BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
     utils::lightmap::{
-        CancellationToken, Lightmap, LightmapGenerationError, LightmapInputData, ProgressIndicator,
+        CancellationToken, EmissionBakeSettings, Lightmap, LightmapGenerationError,
+        LightmapInputData, ProgressIndicator,
     },
 };
 use crate::{
@@ -49,6 +50,32 @@ struct LightmapperSettings {
         step = 0.001
     )]
     spacing: f32,
+    #[reflect(
+        description = "Whether emissive materials should light their surroundings or not. Emissive surfaces \
+    are approximated by a set of virtual lights scattered over the surface, so neon signs and screens can light \
+    nearby objects without placing proxy lights."
+    )]
+    bake_emission: bool,
+    #[reflect(
+        description = "Multiplier of the intensity of the light emitted by emissive materials. Default value is 1.0.",
+        min_value = 0.0,
+        step = 0.1
+    )]
+    emission_intensity: f32,
+    #[reflect(
+        description = "Distance at which the light of emissive surfaces fades out completely. Default value is 4.0.",
+        min_value = 0.0,
+        step = 0.1
+    )]
+    emission_range: f32,
+    #[reflect(
+        description = "Amount of virtual lights per unit of area (square meters) of emissive surfaces. The more \
+    the value, the smoother the lighting from large emissive surfaces will be, but the generation will take more \
+    time. Default value is 16.",
+        min_value = 0.1,
+        max_value = 256.0
+    )]
+    emitters_per_square_unit: f32,
     #[reflect(
         description = "Path to the directory which will be used to save the generated light maps. Keep in mind, that \
     the lightmapper automatically generates names for the files."
@@ -61,6 +88,10 @@ impl Default for LightmapperSettings {
         Self {
             texels_per_unit: 64,
             spacing: 0.005,
+            bake_emission: true,
+            emission_intensity: 1.0,
+            emission_range: 4.0,
+            emitters_per_square_unit: 16.0,
             path: Default::default(),
         }
     }
@@ -293,9 +324,18 @@ impl LightPanel {
                 progress_window.open(engine.user_interfaces.first());
                 self.progress_window = Some(progress_window);
 
-                if let Ok(input_data) = LightmapInputData::from_scene(
+                let emission = EmissionBakeSettings {
+                    enabled: self.settings.bake_emission,
+                    lights_per_square_unit: self.settings.emitters_per_square_unit,
+                    range: self.settings.emission_range,
+                    intensity: self.settings.emission_intensity,
+                    ..Default::default()
+                };
+
+                if let Ok(input_data) = LightmapInputData::from_scene_with_emission(
                     scene,
                     |handle, _| handle != game_scene.editor_objects_root,
+                    &emission,
                     cancellation_token.clone(),
                     progress_indicator.clone(),
                 ) {
@@ -0,0 +1,540 @@
+//! Localization editor is used to manage keys and translations of string tables. It shows a table where each
+//! row is a key and each column is a language, missing translations are highlighted.
+
+use crate::fyrox::{
+    asset::{untyped::ResourceKind, Resource, ResourceData},
+    core::{color::Color, futures::executor::block_on, log::Log, pool::Handle},
+    engine::Engine,
+    fxhash::FxHashMap,
+    gui::{
+        brush::Brush,
+        button::{ButtonBuilder, ButtonMessage},
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        file_browser::{FileBrowserMode, FileSelectorMessage},
+        grid::{Column, GridBuilder, Row},
+        localization::{PluralRule, StringTable, StringTableResource},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        text_box::{TextBoxBuilder, TextCommitMode},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+};
+use crate::{gui::make_dropdown_list_option, utils::create_file_selector};
+use std::{collections::BTreeSet, path::PathBuf, str::FromStr};
+use strum::VariantNames;
+
+const ROW_HEIGHT: f32 = 24.0;
+
+struct TableEntry {
+    path: PathBuf,
+    table: StringTableResource,
+    modified: bool,
+}
+
+pub struct LocalizationEditor {
+    pub window: Handle<UiNode>,
+    add_table: Handle<UiNode>,
+    new_table: Handle<UiNode>,
+    save_all: Handle<UiNode>,
+    report_missing: Handle<UiNode>,
+    new_key: Handle<UiNode>,
+    add_key: Handle<UiNode>,
+    scroll_viewer: Handle<UiNode>,
+    content: Handle<UiNode>,
+    status: Handle<UiNode>,
+    load_file_selector: Handle<UiNode>,
+    save_file_selector: Handle<UiNode>,
+    tables: Vec<TableEntry>,
+    new_key_name: String,
+    cells: FxHashMap<Handle<UiNode>, (usize, String)>,
+    remove_key_buttons: FxHashMap<Handle<UiNode>, String>,
+    plural_rule_selectors: FxHashMap<Handle<UiNode>, usize>,
+}
+
+fn cell_brush(missing: bool) -> Brush {
+    if missing {
+        Brush::Solid(Color::opaque(90, 30, 30))
+    } else {
+        Brush::Solid(Color::opaque(40, 40, 40))
+    }
+}
+
+impl LocalizationEditor {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let load_file_selector = create_file_selector(ctx, "strings", FileBrowserMode::Open);
+        let save_file_selector = create_file_selector(
+            ctx,
+            "strings",
+            FileBrowserMode::Save {
+                default_file_name: PathBuf::from("en.strings"),
+            },
+        );
+
+        let make_button = |text: &str, ctx: &mut BuildContext| {
+            ButtonBuilder::new(
+                WidgetBuilder::new()
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_width(100.0),
+            )
+            .with_text(text)
+            .build(ctx)
+        };
+
+        let add_table = make_button("Add Table...", ctx);
+        let new_table = make_button("New Table...", ctx);
+        let save_all = make_button("Save All", ctx);
+        let report_missing = make_button("Report Missing", ctx);
+        let add_key = make_button("Add Key", ctx);
+
+        let new_key = TextBoxBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(1.0))
+                .with_width(200.0),
+        )
+        .with_text_commit_mode(TextCommitMode::Immediate)
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .build(ctx);
+
+        let content = GridBuilder::new(WidgetBuilder::new()).build(ctx);
+        let scroll_viewer = ScrollViewerBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_content(content)
+        .build(ctx);
+
+        let status = TextBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
+                .with_margin(Thickness::uniform(2.0)),
+        )
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .build(ctx);
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(700.0).with_height(500.0))
+            .open(false)
+            .with_title(WindowTitle::text("Localization Editor"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_child(add_table)
+                                    .with_child(new_table)
+                                    .with_child(save_all)
+                                    .with_child(report_missing)
+                                    .with_child(new_key)
+                                    .with_child(add_key),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        )
+                        .with_child(scroll_viewer)
+                        .with_child(status),
+                )
+                .add_row(Row::strict(26.0))
+                .add_row(Row::stretch())
+                .add_row(Row::strict(22.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            add_table,
+            new_table,
+            save_all,
+            report_missing,
+            new_key,
+            add_key,
+            scroll_viewer,
+            content,
+            status,
+            load_file_selector,
+            save_file_selector,
+            tables: Default::default(),
+            new_key_name: Default::default(),
+            cells: Default::default(),
+            remove_key_buttons: Default::default(),
+            plural_rule_selectors: Default::default(),
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+            true,
+        ));
+    }
+
+    /// Returns a sorted set of keys of all the tables.
+    fn keys(&self) -> BTreeSet<String> {
+        let mut keys = BTreeSet::new();
+        for entry in self.tables.iter() {
+            keys.extend(entry.table.data_ref().keys().map(|key| key.to_owned()));
+        }
+        keys
+    }
+
+    /// Returns a table with all the keys of all the tables, it is used to find missing translations.
+    fn reference_table(&self) -> StringTable {
+        let mut reference = StringTable::default();
+        for key in self.keys() {
+            reference.set(key, "-");
+        }
+        reference
+    }
+
+    fn is_missing(&self, table_index: usize, key: &str) -> bool {
+        self.tables[table_index]
+            .table
+            .data_ref()
+            .entries
+            .get(key)
+            .map_or(true, |value| value.is_empty())
+    }
+
+    fn sync_status(&self, ui: &UserInterface) {
+        let reference = self.reference_table();
+        let missing = self
+            .tables
+            .iter()
+            .map(|entry| entry.table.data_ref().missing_keys(&reference).len())
+            .sum::<usize>();
+        let modified = self.tables.iter().filter(|entry| entry.modified).count();
+
+        ui.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            format!(
+                "Languages: {}, Keys: {}, Missing Translations: {}, Unsaved Tables: {}",
+                self.tables.len(),
+                reference.entries.len(),
+                missing,
+                modified
+            ),
+        ));
+    }
+
+    fn sync_to_model(&mut self, ui: &mut UserInterface) {
+        self.cells.clear();
+        self.remove_key_buttons.clear();
+        self.plural_rule_selectors.clear();
+
+        let keys = self.keys();
+
+        let ctx = &mut ui.build_ctx();
+        let mut children = Vec::new();
+
+        let make_header = |text: &str, column: usize, ctx: &mut BuildContext| {
+            TextBuilder::new(
+                WidgetBuilder::new()
+                    .on_row(0)
+                    .on_column(column)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .with_text(text)
+            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .build(ctx)
+        };
+
+        children.push(make_header("Key", 0, ctx));
+        for (table_index, entry) in self.tables.iter().enumerate() {
+            let table = entry.table.data_ref();
+            let column = table_index + 1;
+            let selector = DropdownListBuilder::new(
+                WidgetBuilder::new()
+                    .with_width(110.0)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .with_items(
+                PluralRule::VARIANTS
+                    .iter()
+                    .map(|name| make_dropdown_list_option(ctx, name))
+                    .collect(),
+            )
+            .with_selected(
+                PluralRule::VARIANTS
+                    .iter()
+                    .position(|name| *name == table.plural_rule.as_ref())
+                    .unwrap_or_default(),
+            )
+            .build(ctx);
+            self.plural_rule_selectors.insert(selector, table_index);
+
+            children.push(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .on_row(0)
+                        .on_column(column)
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_text(&table.language)
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .build(ctx),
+                        )
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_column(1)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child(selector),
+                            )
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .add_column(Column::auto())
+                .build(ctx),
+            );
+        }
+
+        for (key_index, key) in keys.iter().enumerate() {
+            let row = key_index + 1;
+
+            children.push(
+                TextBuilder::new(
+                    WidgetBuilder::new()
+                        .on_row(row)
+                        .on_column(0)
+                        .with_margin(Thickness::uniform(1.0)),
+                )
+                .with_text(key)
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .build(ctx),
+            );
+
+            for (table_index, entry) in self.tables.iter().enumerate() {
+                let table = entry.table.data_ref();
+                let value = table.entries.get(key).cloned().unwrap_or_default();
+                let cell = TextBoxBuilder::new(
+                    WidgetBuilder::new()
+                        .on_row(row)
+                        .on_column(table_index + 1)
+                        .with_margin(Thickness::uniform(1.0))
+                        .with_background(cell_brush(value.is_empty())),
+                )
+                .with_text(value)
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .build(ctx);
+                self.cells.insert(cell, (table_index, key.clone()));
+                children.push(cell);
+            }
+
+            let remove = ButtonBuilder::new(
+                WidgetBuilder::new()
+                    .on_row(row)
+                    .on_column(self.tables.len() + 1)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .with_text("X")
+            .build(ctx);
+            self.remove_key_buttons.insert(remove, key.clone());
+            children.push(remove);
+        }
+
+        let mut grid = GridBuilder::new(WidgetBuilder::new().with_children(children))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_rows(vec![Row::strict(ROW_HEIGHT); keys.len()])
+            .add_column(Column::strict(180.0));
+        for _ in 0..self.tables.len() {
+            grid = grid.add_column(Column::stretch());
+        }
+        let content = grid.add_column(Column::strict(ROW_HEIGHT)).build(ctx);
+
+        ui.send_message(WidgetMessage::remove(
+            self.content,
+            MessageDirection::ToWidget,
+        ));
+        ui.send_message(ScrollViewerMessage::content(
+            self.scroll_viewer,
+            MessageDirection::ToWidget,
+            content,
+        ));
+        self.content = content;
+
+        self.sync_status(ui);
+    }
+
+    fn add_table(&mut self, path: PathBuf, table: StringTableResource, ui: &mut UserInterface) {
+        if self.tables.iter().any(|entry| entry.path == path) {
+            Log::warn(format!("{} is already opened!", path.display()));
+            return;
+        }
+        self.tables.push(TableEntry {
+            path,
+            table,
+            modified: false,
+        });
+        self.sync_to_model(ui);
+    }
+
+    fn save(&mut self) {
+        for entry in self.tables.iter_mut().filter(|entry| entry.modified) {
+            let mut state = entry.table.state();
+            if let Some(table) = state.data() {
+                match table.save(&entry.path) {
+                    Ok(_) => {
+                        entry.modified = false;
+                        Log::info(format!("{} was saved successfully!", entry.path.display()));
+                    }
+                    Err(err) => Log::err(format!(
+                        "Unable to save {}. Reason: {err}",
+                        entry.path.display()
+                    )),
+                }
+            }
+        }
+    }
+
+    fn report_missing(&self) {
+        let reference = self.reference_table();
+        let mut total = 0;
+        for entry in self.tables.iter() {
+            let table = entry.table.data_ref();
+            let missing = table.missing_keys(&reference);
+            if !missing.is_empty() {
+                total += missing.len();
+                Log::warn(format!(
+                    "Language `{}` is missing {} translation(s): {}",
+                    table.language,
+                    missing.len(),
+                    missing.join(", ")
+                ));
+            }
+        }
+        if total == 0 {
+            Log::info("There are no missing translations.");
+        }
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+        let ui = engine.user_interfaces.first_mut();
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.add_table {
+                ui.send_message(FileSelectorMessage::root(
+                    self.load_file_selector,
+                    MessageDirection::ToWidget,
+                    Some(std::env::current_dir().unwrap()),
+                ));
+                ui.send_message(WindowMessage::open_modal(
+                    self.load_file_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                    true,
+                ));
+            } else if message.destination() == self.new_table {
+                ui.send_message(FileSelectorMessage::root(
+                    self.save_file_selector,
+                    MessageDirection::ToWidget,
+                    Some(std::env::current_dir().unwrap()),
+                ));
+                ui.send_message(WindowMessage::open_modal(
+                    self.save_file_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                    true,
+                ));
+            } else if message.destination() == self.save_all {
+                self.save();
+                self.sync_status(ui);
+            } else if message.destination() == self.report_missing {
+                self.report_missing();
+            } else if message.destination() == self.add_key {
+                let key = self.new_key_name.trim().to_owned();
+                if !key.is_empty() && !self.tables.is_empty() && !self.keys().contains(&key) {
+                    for entry in self.tables.iter_mut() {
+                        entry.table.data_ref().set(key.as_str(), "");
+                        entry.modified = true;
+                    }
+                    self.sync_to_model(ui);
+                }
+            } else if let Some(key) = self.remove_key_buttons.get(&message.destination()).cloned() {
+                for entry in self.tables.iter_mut() {
+                    if entry.table.data_ref().remove(key.as_str()).is_some() {
+                        entry.modified = true;
+                    }
+                }
+                self.sync_to_model(ui);
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if message.destination() == self.new_key {
+                    self.new_key_name.clone_from(text);
+                } else if let Some((table_index, key)) = self.cells.get(&message.destination()) {
+                    let entry = &mut self.tables[*table_index];
+                    let mut table = entry.table.data_ref();
+                    if table.entries.get(key) != Some(text) {
+                        table.set(key, text);
+                        drop(table);
+                        entry.modified = true;
+                        ui.send_message(WidgetMessage::background(
+                            message.destination(),
+                            MessageDirection::ToWidget,
+                            cell_brush(self.is_missing(*table_index, key)),
+                        ));
+                        self.sync_status(ui);
+                    }
+                }
+            }
+        } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(table_index) = self.plural_rule_selectors.get(&message.destination()) {
+                    if let Ok(rule) = PluralRule::from_str(PluralRule::VARIANTS[*index]) {
+                        let entry = &mut self.tables[*table_index];
+                        let mut table = entry.table.data_ref();
+                        if table.plural_rule != rule {
+                            table.plural_rule = rule;
+                            entry.modified = true;
+                        }
+                    }
+                    self.sync_status(ui);
+                }
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.load_file_selector {
+                match block_on(engine.resource_manager.request::<StringTable>(path)) {
+                    Ok(table) => {
+                        self.add_table(path.clone(), table, engine.user_interfaces.first_mut())
+                    }
+                    Err(err) => Log::err(format!(
+                        "Unable to load {}. Reason: {err:?}",
+                        path.display()
+                    )),
+                }
+            } else if message.destination() == self.save_file_selector {
+                let language = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let mut table = StringTable::new(language);
+                // Copy keys from existing tables, so the new table will have a full set of strings
+                // to translate.
+                for key in self.keys() {
+                    table.set(key, "");
+                }
+                let table = Resource::new_ok(ResourceKind::External(path.clone()), table);
+                self.add_table(path.clone(), table, ui);
+                if let Some(entry) = self.tables.last_mut() {
+                    entry.modified = true;
+                }
+                self.save();
+                self.sync_status(ui);
+            }
+        }
+    }
+}
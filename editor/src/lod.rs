@@ -0,0 +1,533 @@
+//! LOD control panel shows the levels of the LOD group of a selected node, allows to edit the
+//! switch thresholds of the levels and to preview a specific level in the scene viewer. See
+//! [`LodGroup`] docs for more info about levels of detail.
+//!
+//! [`LodGroup`]: crate::fyrox::scene::base::LodGroup
+
+use crate::{
+    fyrox::{
+        core::{pool::Handle, scope_profile},
+        graph::{BaseSceneGraph, SceneGraph},
+        gui::{
+            dropdown_list::{DropdownListBuilder, DropdownListMessage},
+            grid::{Column, GridBuilder, Row},
+            message::{MessageDirection, UiMessage},
+            numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+            scroll_bar::{ScrollBarBuilder, ScrollBarMessage},
+            stack_panel::StackPanelBuilder,
+            text::{TextBuilder, TextMessage},
+            utils::make_simple_tooltip,
+            widget::{WidgetBuilder, WidgetMessage},
+            window::{WindowBuilder, WindowMessage, WindowTitle},
+            BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
+        },
+        scene::{
+            base::{LodGroup, LodMetric},
+            graph::Graph,
+            mesh::Mesh,
+            node::Node,
+        },
+    },
+    gui::make_dropdown_list_option,
+    message::MessageSender,
+    scene::{commands::graph::SetNodeLodGroupCommand, GameScene, Selection},
+    send_sync_message, Engine, Message,
+};
+use std::str::FromStr;
+use strum::VariantNames;
+
+struct LevelView {
+    triangles: Handle<UiNode>,
+    // The last level does not have a threshold, it is visible up to the end of the range.
+    threshold: Handle<UiNode>,
+}
+
+pub struct LodControlPanel {
+    scene_viewer_frame: Handle<UiNode>,
+    pub window: Handle<UiNode>,
+    metric: Handle<UiNode>,
+    levels_container: Handle<UiNode>,
+    levels_grid: Handle<UiNode>,
+    views: Vec<LevelView>,
+    preview: Handle<UiNode>,
+    preview_text: Handle<UiNode>,
+    node: Handle<Node>,
+}
+
+fn make_text(ctx: &mut BuildContext, text: &str, row: usize, column: usize) -> Handle<UiNode> {
+    TextBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(column)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .build(ctx)
+}
+
+/// Returns the switch thresholds between adjacent levels. Distance-based levels go from the
+/// closest to the farthest, so a level switches to the next one at the end of its range.
+/// Screen-size-based levels go from the largest to the smallest, so a level switches to the next
+/// one at the beginning of its range.
+fn level_thresholds(lod_group: &LodGroup) -> Vec<f32> {
+    let count = lod_group.levels.len().saturating_sub(1);
+    lod_group.levels[..count]
+        .iter()
+        .map(|level| match lod_group.metric {
+            LodMetric::Distance => level.end(),
+            LodMetric::ScreenSize => level.begin(),
+        })
+        .collect()
+}
+
+/// Moves the threshold between the level with the given index and the next one, keeping the
+/// levels adjacent.
+fn set_level_threshold(lod_group: &mut LodGroup, index: usize, value: f32) {
+    let metric = lod_group.metric;
+    if let Some(level) = lod_group.levels.get_mut(index) {
+        match metric {
+            LodMetric::Distance => level.set_end(value),
+            LodMetric::ScreenSize => level.set_begin(value),
+        }
+    }
+    if let Some(next) = lod_group.levels.get_mut(index + 1) {
+        match metric {
+            LodMetric::Distance => next.set_begin(value),
+            LodMetric::ScreenSize => next.set_end(value),
+        }
+    }
+}
+
+/// Assigns default ranges to the levels of the group according to its metric. Distance ranges are
+/// spread evenly, while screen size is halved with every level.
+fn distribute_levels(lod_group: &mut LodGroup) {
+    let count = lod_group.levels.len();
+    let metric = lod_group.metric;
+    for (index, level) in lod_group.levels.iter_mut().enumerate() {
+        let (begin, end) = match metric {
+            LodMetric::Distance => (
+                index as f32 / count as f32,
+                (index + 1) as f32 / count as f32,
+            ),
+            LodMetric::ScreenSize => {
+                let begin = if index + 1 == count {
+                    0.0
+                } else {
+                    0.5f32.powi(index as i32 + 1)
+                };
+                (begin, 0.5f32.powi(index as i32))
+            }
+        };
+        // Reset the range first, because the setters keep begin <= end.
+        level.set_begin(0.0);
+        level.set_end(end);
+        level.set_begin(begin);
+    }
+}
+
+/// Counts triangles of every mesh in the hierarchies of the given objects.
+fn triangle_count(graph: &Graph, objects: &[Handle<Node>]) -> usize {
+    objects
+        .iter()
+        .filter(|object| graph.is_valid_handle(**object))
+        .flat_map(|object| graph.traverse_iter(*object))
+        .filter_map(|node| node.cast::<Mesh>())
+        .flat_map(|mesh| mesh.surfaces())
+        .map(|surface| surface.data().data_ref().geometry_buffer.len())
+        .sum()
+}
+
+fn preview_text(level: Option<usize>) -> String {
+    match level {
+        Some(level) => format!("Level {level}"),
+        None => "Auto".to_string(),
+    }
+}
+
+fn metric_index(metric: LodMetric) -> usize {
+    LodMetric::VARIANTS
+        .iter()
+        .position(|name| *name == metric.as_ref())
+        .unwrap_or_default()
+}
+
+impl LodControlPanel {
+    pub fn new(scene_viewer_frame: Handle<UiNode>, ctx: &mut BuildContext) -> Self {
+        let items = LodMetric::VARIANTS
+            .iter()
+            .map(|name| make_dropdown_list_option(ctx, name))
+            .collect::<Vec<_>>();
+        let metric = DropdownListBuilder::new(
+            WidgetBuilder::new()
+                .on_column(1)
+                .with_margin(Thickness::uniform(1.0))
+                .with_tooltip(make_simple_tooltip(
+                    ctx,
+                    "A metric that is used to select a level. Changing the metric resets \
+                    the thresholds of the levels.",
+                )),
+        )
+        .with_items(items)
+        .with_selected(0)
+        .build(ctx);
+
+        let levels_grid = Handle::NONE;
+        let levels_container = StackPanelBuilder::new(WidgetBuilder::new()).build(ctx);
+
+        let preview = ScrollBarBuilder::new(
+            WidgetBuilder::new()
+                .on_column(1)
+                .with_height(20.0)
+                .with_margin(Thickness::uniform(1.0))
+                .with_tooltip(make_simple_tooltip(
+                    ctx,
+                    "Forces the scene viewer to show a specific level. The leftmost position \
+                    restores automatic level selection.",
+                )),
+        )
+        .with_min(0.0)
+        .with_max(0.0)
+        .with_step(1.0)
+        .build(ctx);
+        let preview_text = make_text(ctx, &preview_text(None), 0, 2);
+
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("LodControlPanel")
+                .with_width(300.0)
+                .with_height(220.0),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("LOD Control Panel"))
+        .with_content(
+            StackPanelBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .with_child(make_text(ctx, "Metric", 0, 0))
+                                .with_child(metric),
+                        )
+                        .add_row(Row::strict(24.0))
+                        .add_column(Column::strict(70.0))
+                        .add_column(Column::stretch())
+                        .build(ctx),
+                    )
+                    .with_child(levels_container)
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .with_child(make_text(ctx, "Preview", 0, 0))
+                                .with_child(preview)
+                                .with_child(preview_text),
+                        )
+                        .add_row(Row::strict(24.0))
+                        .add_column(Column::strict(70.0))
+                        .add_column(Column::stretch())
+                        .add_column(Column::strict(60.0))
+                        .build(ctx),
+                    ),
+            )
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            scene_viewer_frame,
+            window,
+            metric,
+            levels_container,
+            levels_grid,
+            views: Default::default(),
+            preview,
+            preview_text,
+            node: Handle::NONE,
+        }
+    }
+
+    fn rebuild_levels(&mut self, level_count: usize, ui: &mut UserInterface) {
+        if self.levels_grid.is_some() {
+            ui.send_message(WidgetMessage::remove(
+                self.levels_grid,
+                MessageDirection::ToWidget,
+            ));
+        }
+
+        let ctx = &mut ui.build_ctx();
+        let mut children = vec![
+            make_text(ctx, "Level", 0, 0),
+            make_text(ctx, "Triangles", 0, 1),
+            make_text(ctx, "Switch At", 0, 2),
+        ];
+        self.views.clear();
+        for index in 0..level_count {
+            let row = index + 1;
+            let view = LevelView {
+                triangles: make_text(ctx, "0", row, 1),
+                threshold: if index + 1 < level_count {
+                    NumericUpDownBuilder::<f32>::new(
+                        WidgetBuilder::new()
+                            .on_row(row)
+                            .on_column(2)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .with_min_value(0.0)
+                    .with_max_value(1.0)
+                    .with_step(0.01)
+                    .with_precision(3)
+                    .build(ctx)
+                } else {
+                    make_text(ctx, "-", row, 2)
+                },
+            };
+            children.extend([
+                make_text(ctx, &format!("Level {index}"), row, 0),
+                view.triangles,
+                view.threshold,
+            ]);
+            self.views.push(view);
+        }
+
+        self.levels_grid = GridBuilder::new(WidgetBuilder::new().with_children(children))
+            .add_rows((0..=level_count).map(|_| Row::strict(22.0)).collect())
+            .add_column(Column::strict(70.0))
+            .add_column(Column::stretch())
+            .add_column(Column::strict(80.0))
+            .build(ctx);
+
+        ui.send_message(WidgetMessage::link(
+            self.levels_grid,
+            MessageDirection::ToWidget,
+            self.levels_container,
+        ));
+        ui.send_message(ScrollBarMessage::max_value(
+            self.preview,
+            MessageDirection::ToWidget,
+            level_count as f32,
+        ));
+    }
+
+    pub fn sync_to_model(&mut self, game_scene: &GameScene, engine: &mut Engine) {
+        let graph = &engine.scenes[game_scene.scene].graph;
+        let Some(node) = graph.try_get(self.node) else {
+            return;
+        };
+        let Some(lod_group) = node.lod_group() else {
+            return;
+        };
+
+        let ui = engine.user_interfaces.first_mut();
+        if self.views.len() != lod_group.levels.len() {
+            self.rebuild_levels(lod_group.levels.len(), ui);
+        }
+
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(
+                self.metric,
+                MessageDirection::ToWidget,
+                Some(metric_index(lod_group.metric)),
+            ),
+        );
+
+        let thresholds = level_thresholds(lod_group);
+        for (index, (view, level)) in self.views.iter().zip(lod_group.levels.iter()).enumerate() {
+            send_sync_message(
+                ui,
+                TextMessage::text(
+                    view.triangles,
+                    MessageDirection::ToWidget,
+                    triangle_count(graph, &level.objects).to_string(),
+                ),
+            );
+            if let Some(threshold) = thresholds.get(index) {
+                send_sync_message(
+                    ui,
+                    NumericUpDownMessage::value(
+                        view.threshold,
+                        MessageDirection::ToWidget,
+                        *threshold,
+                    ),
+                );
+            }
+        }
+    }
+
+    fn set_preview_level(&self, level: Option<usize>, graph: &mut Graph, ui: &UserInterface) {
+        if let Some(node) = graph.try_get_mut(self.node) {
+            node.force_lod_level(level);
+        }
+        ui.send_message(TextMessage::text(
+            self.preview_text,
+            MessageDirection::ToWidget,
+            preview_text(level),
+        ));
+    }
+
+    fn reset_preview(&self, graph: &mut Graph, ui: &UserInterface) {
+        self.set_preview_level(None, graph, ui);
+        send_sync_message(
+            ui,
+            ScrollBarMessage::value(self.preview, MessageDirection::ToWidget, 0.0),
+        );
+    }
+
+    fn change_lod_group<F>(&self, graph: &Graph, sender: &MessageSender, func: F)
+    where
+        F: FnOnce(&mut LodGroup),
+    {
+        let Some(mut lod_group) = graph
+            .try_get(self.node)
+            .and_then(|node| node.lod_group().cloned())
+        else {
+            return;
+        };
+        func(&mut lod_group);
+        sender.do_command(SetNodeLodGroupCommand::new(self.node, Some(lod_group)));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        game_scene: &mut GameScene,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) {
+        scope_profile!();
+
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        let ui = engine.user_interfaces.first();
+
+        if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.metric {
+                let Some(metric) = LodMetric::VARIANTS
+                    .get(*index)
+                    .and_then(|name| LodMetric::from_str(name).ok())
+                else {
+                    return;
+                };
+                self.change_lod_group(graph, sender, |lod_group| {
+                    if lod_group.metric != metric {
+                        lod_group.metric = metric;
+                        distribute_levels(lod_group);
+                    }
+                });
+            }
+        } else if let Some(NumericUpDownMessage::<f32>::Value(value)) = message.data() {
+            if let Some(index) = self
+                .views
+                .iter()
+                .position(|view| view.threshold == message.destination())
+            {
+                self.change_lod_group(graph, sender, |lod_group| {
+                    set_level_threshold(lod_group, index, *value)
+                });
+            }
+        } else if let Some(ScrollBarMessage::Value(value)) = message.data() {
+            if message.destination() == self.preview {
+                let position = value.round() as usize;
+                let level = position.checked_sub(1);
+                self.set_preview_level(level, graph, ui);
+            }
+        } else if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                self.reset_preview(graph, ui);
+            }
+        }
+    }
+
+    pub fn handle_message(
+        &mut self,
+        message: &Message,
+        editor_selection: &Selection,
+        game_scene: &mut GameScene,
+        engine: &mut Engine,
+    ) {
+        let Message::SelectionChanged { .. } = message else {
+            return;
+        };
+
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        let ui = engine.user_interfaces.first_mut();
+
+        let node = editor_selection
+            .as_graph()
+            .and_then(|selection| {
+                selection.nodes.iter().cloned().find(|handle| {
+                    graph
+                        .try_get(*handle)
+                        .is_some_and(|node| node.lod_group().is_some())
+                })
+            })
+            .unwrap_or_default();
+
+        if node != self.node {
+            self.reset_preview(graph, ui);
+            self.node = node;
+            // Force the levels to be rebuilt on the next sync.
+            self.views.clear();
+        }
+
+        if self.node.is_some() {
+            ui.send_message(WindowMessage::open_and_align(
+                self.window,
+                MessageDirection::ToWidget,
+                self.scene_viewer_frame,
+                HorizontalAlignment::Right,
+                VerticalAlignment::Top,
+                Thickness::top_right(5.0),
+                false,
+                false,
+            ));
+        } else {
+            ui.send_message(WindowMessage::close(
+                self.window,
+                MessageDirection::ToWidget,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        fyrox::scene::base::{LevelOfDetail, LodGroup, LodMetric},
+        lod::{distribute_levels, level_thresholds, set_level_threshold},
+    };
+
+    fn lod_group(metric: LodMetric) -> LodGroup {
+        let mut lod_group = LodGroup {
+            levels: vec![LevelOfDetail::new(0.0, 1.0, vec![]); 3],
+            metric,
+        };
+        distribute_levels(&mut lod_group);
+        lod_group
+    }
+
+    #[test]
+    fn test_distance_thresholds() {
+        let mut lod_group = lod_group(LodMetric::Distance);
+        assert_eq!(level_thresholds(&lod_group), vec![1.0 / 3.0, 2.0 / 3.0]);
+
+        set_level_threshold(&mut lod_group, 0, 0.2);
+        assert_eq!(lod_group.levels[0].end(), 0.2);
+        assert_eq!(lod_group.levels[1].begin(), 0.2);
+    }
+
+    #[test]
+    fn test_screen_size_thresholds() {
+        let mut lod_group = lod_group(LodMetric::ScreenSize);
+        assert_eq!(level_thresholds(&lod_group), vec![0.5, 0.25]);
+        assert_eq!(lod_group.levels[0].end(), 1.0);
+        assert_eq!(lod_group.levels[2].begin(), 0.0);
+
+        set_level_threshold(&mut lod_group, 1, 0.1);
+        assert_eq!(lod_group.levels[1].begin(), 0.1);
+        assert_eq!(lod_group.levels[2].end(), 0.1);
+    }
+}
@@ -1,35 +1,252 @@
+//! Log panel shows messages of the engine log. Messages could be filtered by their severity,
+//! category and by a search pattern (a regular expression), repeated messages could be collapsed
+//! into a single entry. A click on a message, that mentions a resource or a scene node, shows the
+//! resource in the asset browser or selects the node.
+
 use crate::fyrox::graph::BaseSceneGraph;
 use crate::fyrox::{
     core::{
         log::{LogMessage, MessageKind},
-        pool::Handle,
+        pool::{ErasedHandle, Handle},
         scope_profile,
     },
     gui::{
         border::BorderBuilder,
         button::ButtonMessage,
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
         copypasta::ClipboardProvider,
         dropdown_list::DropdownListMessage,
         formatted_text::WrapMode,
         grid::{Column, GridBuilder, Row},
-        list_view::{ListView, ListViewBuilder, ListViewMessage},
+        list_view::{ListViewBuilder, ListViewMessage},
         menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
         message::{MessageDirection, UiMessage},
         popup::{Placement, PopupBuilder, PopupMessage},
         scroll_viewer::ScrollViewerBuilder,
+        searchbar::{SearchBarBuilder, SearchBarMessage},
         stack_panel::StackPanelBuilder,
-        text::{Text, TextBuilder},
+        text::{Text, TextBuilder, TextMessage},
+        utils::make_simple_tooltip,
         widget::WidgetBuilder,
         window::{WindowBuilder, WindowTitle},
         BuildContext, HorizontalAlignment, Orientation, RcUiNodeHandle, Thickness, UiNode,
+        UserInterface, VerticalAlignment,
     },
 };
 use crate::{
+    asset::is_supported_resource,
     gui::{make_dropdown_list_option, make_image_button_with_tooltip},
-    load_image, Brush, Color, DropdownListBuilder, Engine,
+    load_image,
+    message::MessageSender,
+    Brush, Color, DropdownListBuilder, Engine, Message,
 };
 use fyrox::gui::menu::ContextMenuBuilder;
-use std::sync::mpsc::Receiver;
+use lazy_static::lazy_static;
+use regex::{Regex, RegexBuilder};
+use std::{path::PathBuf, sync::mpsc::Receiver, time::Duration};
+
+lazy_static! {
+    // Handles are printed as `[Idx: 1; Gen: 2]` by `Debug` and as `1:2` by `Display`. The latter
+    // form is too ambiguous, so it is recognized only after a `handle` or `node` word.
+    static ref HANDLE_REGEX: Regex = RegexBuilder::new(
+        r"\[Idx: (\d+); Gen: (\d+)\]|\b(?:handle|node)\s+(\d+):(\d+)\b"
+    )
+    .case_insensitive(true)
+    .build()
+    .unwrap();
+    static ref PATH_REGEX: Regex =
+        Regex::new(r"(?:[A-Za-z]:)?[\w\-./\\]*[\w\-]\.[A-Za-z][A-Za-z0-9]{0,7}\b").unwrap();
+}
+
+/// Category of a log message. The engine log does not have categories, so they're deduced from
+/// the content of the messages.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LogCategory {
+    General,
+    Resources,
+    Renderer,
+    Scripts,
+    Scene,
+    Sound,
+}
+
+impl LogCategory {
+    /// All the categories in the order of their appearance in the category selector.
+    pub const ALL: [LogCategory; 6] = [
+        LogCategory::General,
+        LogCategory::Resources,
+        LogCategory::Renderer,
+        LogCategory::Scripts,
+        LogCategory::Scene,
+        LogCategory::Sound,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LogCategory::General => "General",
+            LogCategory::Resources => "Resources",
+            LogCategory::Renderer => "Renderer",
+            LogCategory::Scripts => "Scripts",
+            LogCategory::Scene => "Scene",
+            LogCategory::Sound => "Sound",
+        }
+    }
+
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            LogCategory::General => &[],
+            LogCategory::Resources => &["resource", "asset", "file"],
+            LogCategory::Renderer => &[
+                "render",
+                "shader",
+                "texture",
+                "gpu",
+                "opengl",
+                "framebuffer",
+                "frame buffer",
+            ],
+            LogCategory::Scripts => &["script", "plugin"],
+            LogCategory::Scene => &["scene", "node", "graph", "[idx:"],
+            LogCategory::Sound => &["sound", "audio"],
+        }
+    }
+
+    /// Deduces a category of the given message. The first category, that has a keyword that is
+    /// present in the message, wins.
+    pub fn classify(content: &str) -> Self {
+        let content = content.to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|category| {
+                category
+                    .keywords()
+                    .iter()
+                    .any(|keyword| content.contains(keyword))
+            })
+            .unwrap_or(LogCategory::General)
+    }
+}
+
+/// A thing, that is mentioned in a log message and could be shown to a user.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LogLink {
+    /// A path to a resource.
+    Resource(PathBuf),
+    /// A handle of a scene node.
+    Object(ErasedHandle),
+}
+
+/// Returns the first handle mentioned in the given message.
+pub fn find_handle(content: &str) -> Option<ErasedHandle> {
+    let captures = HANDLE_REGEX.captures(content)?;
+    let mut numbers = captures
+        .iter()
+        .skip(1)
+        .flatten()
+        .filter_map(|m| m.as_str().parse::<u32>().ok());
+    Some(ErasedHandle::new(numbers.next()?, numbers.next()?))
+}
+
+/// Returns every path-like substring (a sequence of path characters, that ends with an
+/// extension) of the given message.
+pub fn find_paths(content: &str) -> impl Iterator<Item = PathBuf> + '_ {
+    PATH_REGEX
+        .find_iter(content)
+        .map(|m| PathBuf::from(m.as_str()))
+}
+
+/// Creates a case-insensitive search pattern. If the text is not a valid regular expression, it is
+/// searched as is. Returns `None` if the text is empty.
+pub fn make_search_pattern(text: &str) -> Option<Regex> {
+    if text.is_empty() {
+        return None;
+    }
+    RegexBuilder::new(text)
+        .case_insensitive(true)
+        .build()
+        .or_else(|_| {
+            RegexBuilder::new(&regex::escape(text))
+                .case_insensitive(true)
+                .build()
+        })
+        .ok()
+}
+
+struct LogEntry {
+    kind: MessageKind,
+    content: String,
+    time: Duration,
+    category: LogCategory,
+    link: Option<LogLink>,
+}
+
+impl LogEntry {
+    fn new(message: LogMessage, engine: &Engine) -> Self {
+        let resource_path = find_paths(&message.content).find(|path| {
+            path.extension()
+                .is_some_and(|ext| is_supported_resource(ext, &engine.resource_manager))
+        });
+        let link = resource_path
+            .map(LogLink::Resource)
+            .or_else(|| find_handle(&message.content).map(LogLink::Object));
+        let category = if matches!(link, Some(LogLink::Resource(_))) {
+            LogCategory::Resources
+        } else {
+            LogCategory::classify(&message.content)
+        };
+        Self {
+            kind: message.kind,
+            content: message.content,
+            time: message.time,
+            category,
+            link,
+        }
+    }
+
+    fn is_repetition_of(&self, other: &LogEntry) -> bool {
+        self.kind == other.kind && self.content == other.content
+    }
+}
+
+struct LogFilter {
+    severity: MessageKind,
+    category: Option<LogCategory>,
+    pattern: Option<Regex>,
+    collapse: bool,
+}
+
+impl LogFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        entry.kind >= self.severity
+            && self.category.map_or(true, |c| c == entry.category)
+            && self
+                .pattern
+                .as_ref()
+                .map_or(true, |pattern| pattern.is_match(&entry.content))
+    }
+}
+
+/// A visible item of the list, it could represent a few collapsed entries.
+struct LogRow {
+    /// Index of the most recent entry of the row.
+    entry: usize,
+    count: usize,
+    text: Handle<UiNode>,
+}
+
+fn format_row(entry: &LogEntry, count: usize) -> String {
+    let content = entry.content.trim_end();
+    if count > 1 {
+        format!(
+            "[{:.2}s] {} (x{})",
+            entry.time.as_secs_f32(),
+            content,
+            count
+        )
+    } else {
+        format!("[{:.2}s] {}", entry.time.as_secs_f32(), content)
+    }
+}
 
 struct ContextMenu {
     menu: RcUiNodeHandle,
@@ -90,16 +307,31 @@ pub struct LogPanel {
     messages: Handle<UiNode>,
     clear: Handle<UiNode>,
     receiver: Receiver<LogMessage>,
-    severity: MessageKind,
+    sender: MessageSender,
     severity_list: Handle<UiNode>,
+    category_list: Handle<UiNode>,
+    collapse: Handle<UiNode>,
+    search_bar: Handle<UiNode>,
     context_menu: ContextMenu,
+    resource_tooltip: RcUiNodeHandle,
+    object_tooltip: RcUiNodeHandle,
+    filter: LogFilter,
+    entries: Vec<LogEntry>,
+    rows: Vec<LogRow>,
 }
 
 impl LogPanel {
-    pub fn new(ctx: &mut BuildContext, message_receiver: Receiver<LogMessage>) -> Self {
+    pub fn new(
+        ctx: &mut BuildContext,
+        message_receiver: Receiver<LogMessage>,
+        sender: MessageSender,
+    ) -> Self {
         let messages;
         let clear;
         let severity_list;
+        let category_list;
+        let collapse;
+        let search_bar;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("LogPanel"))
             .can_minimize(false)
             .with_title(WindowTitle::text("Message Log"))
@@ -107,41 +339,110 @@ impl LogPanel {
                 GridBuilder::new(
                     WidgetBuilder::new()
                         .with_child(
-                            StackPanelBuilder::new(
+                            GridBuilder::new(
                                 WidgetBuilder::new()
-                                    .with_horizontal_alignment(HorizontalAlignment::Left)
                                     .on_row(0)
                                     .on_column(0)
+                                    .with_child(
+                                        StackPanelBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(0)
+                                                .with_horizontal_alignment(
+                                                    HorizontalAlignment::Left,
+                                                )
+                                                .with_child({
+                                                    clear = make_image_button_with_tooltip(
+                                                        ctx,
+                                                        24.0,
+                                                        24.0,
+                                                        load_image(include_bytes!(
+                                                            "../resources/clear.png"
+                                                        )),
+                                                        "Clear the log.",
+                                                        Some(0),
+                                                    );
+                                                    clear
+                                                })
+                                                .with_child({
+                                                    severity_list = DropdownListBuilder::new(
+                                                        WidgetBuilder::new()
+                                                            .with_tab_index(Some(1))
+                                                            .with_width(120.0)
+                                                            .with_margin(Thickness::uniform(1.0)),
+                                                    )
+                                                    .with_items(vec![
+                                                        make_dropdown_list_option(ctx, "Info+"),
+                                                        make_dropdown_list_option(ctx, "Warnings+"),
+                                                        make_dropdown_list_option(ctx, "Errors"),
+                                                    ])
+                                                    // Warnings+
+                                                    .with_selected(1)
+                                                    .build(ctx);
+                                                    severity_list
+                                                })
+                                                .with_child({
+                                                    let mut items =
+                                                        vec![make_dropdown_list_option(
+                                                            ctx,
+                                                            "All Categories",
+                                                        )];
+                                                    items.extend(LogCategory::ALL.iter().map(
+                                                        |category| {
+                                                            make_dropdown_list_option(
+                                                                ctx,
+                                                                category.name(),
+                                                            )
+                                                        },
+                                                    ));
+                                                    category_list = DropdownListBuilder::new(
+                                                        WidgetBuilder::new()
+                                                            .with_tab_index(Some(2))
+                                                            .with_width(120.0)
+                                                            .with_margin(Thickness::uniform(1.0)),
+                                                    )
+                                                    .with_items(items)
+                                                    .with_selected(0)
+                                                    .build(ctx);
+                                                    category_list
+                                                })
+                                                .with_child({
+                                                    collapse = CheckBoxBuilder::new(
+                                                        WidgetBuilder::new()
+                                                            .with_tab_index(Some(3))
+                                                            .with_margin(Thickness::uniform(1.0)),
+                                                    )
+                                                    .with_content(
+                                                        TextBuilder::new(
+                                                            WidgetBuilder::new()
+                                                                .with_vertical_alignment(
+                                                                    VerticalAlignment::Center,
+                                                                ),
+                                                        )
+                                                        .with_text("Collapse")
+                                                        .build(ctx),
+                                                    )
+                                                    .checked(Some(true))
+                                                    .build(ctx);
+                                                    collapse
+                                                }),
+                                        )
+                                        .with_orientation(Orientation::Horizontal)
+                                        .build(ctx),
+                                    )
                                     .with_child({
-                                        clear = make_image_button_with_tooltip(
-                                            ctx,
-                                            24.0,
-                                            24.0,
-                                            load_image(include_bytes!("../resources/clear.png")),
-                                            "Clear the log.",
-                                            Some(0),
-                                        );
-                                        clear
-                                    })
-                                    .with_child({
-                                        severity_list = DropdownListBuilder::new(
+                                        search_bar = SearchBarBuilder::new(
                                             WidgetBuilder::new()
-                                                .with_tab_index(Some(1))
-                                                .with_width(120.0)
+                                                .on_column(1)
+                                                .with_tab_index(Some(4))
                                                 .with_margin(Thickness::uniform(1.0)),
                                         )
-                                        .with_items(vec![
-                                            make_dropdown_list_option(ctx, "Info+"),
-                                            make_dropdown_list_option(ctx, "Warnings+"),
-                                            make_dropdown_list_option(ctx, "Errors"),
-                                        ])
-                                        // Warnings+
-                                        .with_selected(1)
                                         .build(ctx);
-                                        severity_list
+                                        search_bar
                                     }),
                             )
-                            .with_orientation(Orientation::Horizontal)
+                            .add_row(Row::stretch())
+                            .add_column(Column::auto())
+                            .add_column(Column::stretch())
                             .build(ctx),
                         )
                         .with_child({
@@ -171,15 +472,31 @@ impl LogPanel {
             .build(ctx);
 
         let context_menu = ContextMenu::new(ctx);
+        let resource_tooltip =
+            make_simple_tooltip(ctx, "Click to show the resource in the asset browser.");
+        let object_tooltip = make_simple_tooltip(ctx, "Click to select the object.");
 
         Self {
             window,
             messages,
             clear,
             receiver: message_receiver,
-            severity: MessageKind::Warning,
+            sender,
             severity_list,
+            category_list,
+            collapse,
+            search_bar,
             context_menu,
+            resource_tooltip,
+            object_tooltip,
+            filter: LogFilter {
+                severity: MessageKind::Warning,
+                category: None,
+                pattern: None,
+                collapse: true,
+            },
+            entries: Default::default(),
+            rows: Default::default(),
         }
     }
 
@@ -188,100 +505,252 @@ impl LogPanel {
 
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             if message.destination() == self.clear {
-                engine
-                    .user_interfaces
-                    .first_mut()
-                    .send_message(ListViewMessage::items(
-                        self.messages,
-                        MessageDirection::ToWidget,
-                        vec![],
-                    ));
+                self.entries.clear();
+                self.rebuild(engine.user_interfaces.first_mut());
             }
         } else if let Some(DropdownListMessage::SelectionChanged(Some(idx))) =
             message.data::<DropdownListMessage>()
         {
-            if message.destination() == self.severity_list
+            if message.direction() == MessageDirection::FromWidget {
+                if message.destination() == self.severity_list {
+                    match idx {
+                        0 => self.filter.severity = MessageKind::Information,
+                        1 => self.filter.severity = MessageKind::Warning,
+                        2 => self.filter.severity = MessageKind::Error,
+                        _ => (),
+                    };
+                    self.rebuild(engine.user_interfaces.first_mut());
+                } else if message.destination() == self.category_list {
+                    // The first item is "All Categories".
+                    self.filter.category = idx
+                        .checked_sub(1)
+                        .and_then(|i| LogCategory::ALL.get(i).copied());
+                    self.rebuild(engine.user_interfaces.first_mut());
+                }
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.collapse
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.filter.collapse = *value;
+                self.rebuild(engine.user_interfaces.first_mut());
+            }
+        } else if let Some(SearchBarMessage::Text(text)) = message.data() {
+            if message.destination() == self.search_bar
                 && message.direction() == MessageDirection::FromWidget
             {
-                match idx {
-                    0 => self.severity = MessageKind::Information,
-                    1 => self.severity = MessageKind::Warning,
-                    2 => self.severity = MessageKind::Error,
-                    _ => (),
-                };
+                self.filter.pattern = make_search_pattern(text);
+                self.rebuild(engine.user_interfaces.first_mut());
+            }
+        } else if let Some(ListViewMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.messages
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.open_link(*index);
+                // Reset the selection, so the same entry could be clicked again.
+                engine
+                    .user_interfaces
+                    .first_mut()
+                    .send_message(ListViewMessage::selection(
+                        self.messages,
+                        MessageDirection::ToWidget,
+                        None,
+                    ));
             }
         }
 
         self.context_menu.handle_ui_message(message, engine);
     }
 
-    pub fn update(&mut self, engine: &mut Engine) {
-        let mut count = engine
-            .user_interfaces
-            .first_mut()
-            .node(self.messages)
-            .cast::<ListView>()
-            .map(|v| v.items().len())
-            .unwrap_or_default();
-
-        let mut item_to_bring_into_view = Handle::NONE;
+    fn open_link(&self, row_index: usize) {
+        let Some(link) = self
+            .rows
+            .get(row_index)
+            .and_then(|row| self.entries[row.entry].link.as_ref())
+        else {
+            return;
+        };
 
-        while let Ok(msg) = self.receiver.try_recv() {
-            if msg.kind < self.severity {
-                continue;
+        match link {
+            LogLink::Resource(path) => {
+                self.sender.send(Message::ShowInAssetBrowser(path.clone()));
+            }
+            LogLink::Object(handle) => {
+                self.sender.send(Message::SelectObject { handle: *handle });
+                self.sender.send(Message::LocateObject { handle: *handle });
             }
+        }
+    }
 
-            let text = format!("[{:.2}s] {}", msg.time.as_secs_f32(), msg.content);
-
-            let ctx = &mut engine.user_interfaces.first_mut().build_ctx();
-            let item = BorderBuilder::new(
-                WidgetBuilder::new()
-                    .with_background(Brush::Solid(if count % 2 == 0 {
-                        Color::opaque(70, 70, 70)
-                    } else {
-                        Color::opaque(40, 40, 40)
-                    }))
-                    .with_child(
-                        TextBuilder::new(
-                            WidgetBuilder::new()
-                                .with_context_menu(self.context_menu.menu.clone())
-                                .with_margin(Thickness::uniform(1.0))
-                                .with_foreground(Brush::Solid(match msg.kind {
-                                    MessageKind::Information => Color::ANTIQUE_WHITE,
-                                    MessageKind::Warning => Color::GOLD,
-                                    MessageKind::Error => Color::RED,
-                                })),
-                        )
-                        .with_text(text)
-                        .with_wrap(WrapMode::Word)
-                        .build(ctx),
-                    ),
-            )
+    fn make_row(&self, index: usize, ui: &mut UserInterface) -> LogRow {
+        let entry = &self.entries[index];
+        let ctx = &mut ui.build_ctx();
+        let mut text_builder = WidgetBuilder::new()
+            .with_context_menu(self.context_menu.menu.clone())
+            .with_margin(Thickness::uniform(1.0))
+            .with_foreground(Brush::Solid(match entry.kind {
+                MessageKind::Information => Color::ANTIQUE_WHITE,
+                MessageKind::Warning => Color::GOLD,
+                MessageKind::Error => Color::RED,
+            }));
+        match entry.link {
+            Some(LogLink::Resource(_)) => {
+                text_builder = text_builder.with_tooltip(self.resource_tooltip.clone())
+            }
+            Some(LogLink::Object(_)) => {
+                text_builder = text_builder.with_tooltip(self.object_tooltip.clone())
+            }
+            None => (),
+        }
+        let text = TextBuilder::new(text_builder)
+            .with_text(format_row(entry, 1))
+            .with_wrap(WrapMode::Word)
             .build(ctx);
+        let item = BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_background(Brush::Solid(if self.rows.len() % 2 == 0 {
+                    Color::opaque(70, 70, 70)
+                } else {
+                    Color::opaque(40, 40, 40)
+                }))
+                .with_child(text),
+        )
+        .build(ctx);
+
+        ui.send_message(ListViewMessage::add_item(
+            self.messages,
+            MessageDirection::ToWidget,
+            item,
+        ));
+
+        LogRow {
+            entry: index,
+            count: 1,
+            text,
+        }
+    }
+
+    /// Shows the entry with the given index, if it passes the filter. Returns `true` if a new item
+    /// was added to the list.
+    fn show_entry(&mut self, index: usize, ui: &mut UserInterface) -> bool {
+        let entry = &self.entries[index];
+        if !self.filter.matches(entry) {
+            return false;
+        }
 
-            engine
-                .user_interfaces
-                .first_mut()
-                .send_message(ListViewMessage::add_item(
+        if self.filter.collapse {
+            if let Some(last) = self.rows.last_mut() {
+                if entry.is_repetition_of(&self.entries[last.entry]) {
+                    last.entry = index;
+                    last.count += 1;
+                    ui.send_message(TextMessage::text(
+                        last.text,
+                        MessageDirection::ToWidget,
+                        format_row(entry, last.count),
+                    ));
+                    return false;
+                }
+            }
+        }
+
+        let row = self.make_row(index, ui);
+        self.rows.push(row);
+        true
+    }
+
+    /// Removes every item from the list and fills it again using the current filter.
+    fn rebuild(&mut self, ui: &mut UserInterface) {
+        ui.send_message(ListViewMessage::items(
+            self.messages,
+            MessageDirection::ToWidget,
+            vec![],
+        ));
+        self.rows.clear();
+        for index in 0..self.entries.len() {
+            self.show_entry(index, ui);
+        }
+        self.bring_last_item_into_view(ui);
+    }
+
+    fn bring_last_item_into_view(&self, ui: &UserInterface) {
+        if let Some(row) = self.rows.last() {
+            if let Some(item) = ui.try_get(row.text).map(|text| text.parent()) {
+                ui.send_message(ListViewMessage::bring_item_into_view(
                     self.messages,
                     MessageDirection::ToWidget,
                     item,
                 ));
+            }
+        }
+    }
 
-            item_to_bring_into_view = item;
+    pub fn update(&mut self, engine: &mut Engine) {
+        let mut any_added = false;
 
-            count += 1;
+        while let Ok(msg) = self.receiver.try_recv() {
+            let entry = LogEntry::new(msg, engine);
+            self.entries.push(entry);
+            any_added |=
+                self.show_entry(self.entries.len() - 1, engine.user_interfaces.first_mut());
         }
 
-        if item_to_bring_into_view.is_some() {
-            engine
-                .user_interfaces
-                .first_mut()
-                .send_message(ListViewMessage::bring_item_into_view(
-                    self.messages,
-                    MessageDirection::ToWidget,
-                    item_to_bring_into_view,
-                ));
+        if any_added {
+            self.bring_last_item_into_view(engine.user_interfaces.first());
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        fyrox::core::pool::ErasedHandle,
+        log::{find_handle, find_paths, make_search_pattern, LogCategory},
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_find_handle() {
+        assert_eq!(
+            find_handle("Node [Idx: 12; Gen: 3] has no parent"),
+            Some(ErasedHandle::new(12, 3))
+        );
+        assert_eq!(
+            find_handle("Unable to find node 5:1 in the graph"),
+            Some(ErasedHandle::new(5, 1))
+        );
+        assert_eq!(find_handle("Loading took 12:30"), None);
+    }
+
+    #[test]
+    fn test_find_paths() {
+        assert_eq!(
+            find_paths("Unable to load \"data/models/barrel.fbx\". Reason: NotFound")
+                .collect::<Vec<_>>(),
+            vec![PathBuf::from("data/models/barrel.fbx")]
+        );
+        assert_eq!(find_paths("Took 0.5s to load").count(), 0);
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            LogCategory::classify("Failed to compile shader Standard"),
+            LogCategory::Renderer
+        );
+        assert_eq!(
+            LogCategory::classify("Plugin was reloaded"),
+            LogCategory::Scripts
+        );
+        assert_eq!(LogCategory::classify("Hello"), LogCategory::General);
+    }
+
+    #[test]
+    fn test_search_pattern() {
+        assert!(make_search_pattern("").is_none());
+        let pattern = make_search_pattern("err(or)?").unwrap();
+        assert!(pattern.is_match("An ERROR occurred"));
+        // Invalid expressions are searched as is.
+        let pattern = make_search_pattern("foo(").unwrap();
+        assert!(pattern.is_match("call foo(bar)"));
+    }
+}
@@ -2,6 +2,7 @@ use crate::fyrox::{
     asset::manager::ResourceManager,
     core::{
         algebra::{Matrix4, Vector2, Vector3, Vector4},
+        color::ColorSpace,
         futures::executor::block_on,
         make_relative_path,
         parking_lot::Mutex,
@@ -23,7 +24,9 @@ use crate::fyrox::{
         popup::{Placement, PopupBuilder, PopupMessage},
         scroll_viewer::ScrollViewerBuilder,
         stack_panel::StackPanelBuilder,
-        text::TextBuilder,
+        text::{TextBuilder, TextMessage},
+        text_box::TextBoxBuilder,
+        utils::make_simple_tooltip,
         vec::{
             Vec2EditorBuilder, Vec2EditorMessage, Vec3EditorBuilder, Vec3EditorMessage,
             Vec4EditorBuilder, Vec4EditorMessage,
@@ -32,7 +35,7 @@ use crate::fyrox::{
         window::{WindowBuilder, WindowTitle},
         BuildContext, RcUiNodeHandle, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
-    material::{shader::Shader, MaterialResource, PropertyValue},
+    material::{shader::Shader, Material, MaterialResource, PropertyValue},
     resource::texture::Texture,
     scene::{
         base::BaseBuilder,
@@ -47,7 +50,10 @@ use crate::{
     inspector::editors::resource::{ResourceFieldBuilder, ResourceFieldMessage},
     message::MessageSender,
     preview::PreviewPanel,
-    scene::commands::material::{SetMaterialPropertyValueCommand, SetMaterialShaderCommand},
+    scene::commands::material::{
+        SetMaterialPassTagsCommand, SetMaterialPropertyValueCommand, SetMaterialRenderQueueCommand,
+        SetMaterialShaderCommand,
+    },
     send_sync_message, Engine, Message,
 };
 use fyrox::asset::untyped::ResourceKind;
@@ -104,9 +110,26 @@ pub struct MaterialEditor {
     preview: PreviewPanel,
     material: Option<MaterialResource>,
     shader: Handle<UiNode>,
+    render_queue: Handle<UiNode>,
+    pass_tags: Handle<UiNode>,
     texture_context_menu: TextureContextMenu,
 }
 
+fn parse_pass_tags(text: &str) -> Vec<ImmutableString> {
+    text.split(',')
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty())
+        .map(ImmutableString::new)
+        .collect()
+}
+
+fn format_pass_tags(tags: &[ImmutableString]) -> String {
+    tags.iter()
+        .map(|tag| tag.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn create_item_container(
     ctx: &mut BuildContext,
     name: &str,
@@ -266,6 +289,8 @@ impl MaterialEditor {
         let panel;
         let properties_panel;
         let shader;
+        let render_queue;
+        let pass_tags;
         let window = WindowBuilder::new(WidgetBuilder::new().with_width(350.0))
             .open(false)
             .with_title(WindowTitle::text("Material Editor"))
@@ -298,11 +323,57 @@ impl MaterialEditor {
                                         )
                                         .build(ctx, engine.resource_manager.clone());
                                         shader
+                                    })
+                                    .with_child(
+                                        TextBuilder::new(
+                                            WidgetBuilder::new().on_row(1).on_column(0),
+                                        )
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .with_text("Render Queue")
+                                        .build(ctx),
+                                    )
+                                    .with_child({
+                                        render_queue = NumericUpDownBuilder::<i32>::new(
+                                            WidgetBuilder::new()
+                                                .on_row(1)
+                                                .on_column(1)
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Materials with lower values are drawn first",
+                                                )),
+                                        )
+                                        .with_value(Material::RENDER_QUEUE_GEOMETRY)
+                                        .build(ctx);
+                                        render_queue
+                                    })
+                                    .with_child(
+                                        TextBuilder::new(
+                                            WidgetBuilder::new().on_row(2).on_column(0),
+                                        )
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .with_text("Pass Tags")
+                                        .build(ctx),
+                                    )
+                                    .with_child({
+                                        pass_tags = TextBoxBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_row(2)
+                                                .on_column(1)
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Comma-separated list of pass tags",
+                                                )),
+                                        )
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .build(ctx);
+                                        pass_tags
                                     }),
                             )
                             .add_column(Column::strict(150.0))
                             .add_column(Column::stretch())
                             .add_row(Row::strict(25.0))
+                            .add_row(Row::strict(25.0))
+                            .add_row(Row::strict(25.0))
                             .build(ctx),
                         )
                         .with_child(
@@ -320,7 +391,7 @@ impl MaterialEditor {
                             panel
                         }),
                 )
-                .add_row(Row::strict(26.0))
+                .add_row(Row::strict(76.0))
                 .add_row(Row::stretch())
                 .add_row(Row::strict(300.0))
                 .add_column(Column::stretch())
@@ -338,6 +409,8 @@ impl MaterialEditor {
             properties: Default::default(),
             material: None,
             shader,
+            render_queue,
+            pass_tags,
         }
     }
 
@@ -451,8 +524,11 @@ impl MaterialEditor {
                         PropertyValue::Bool(value) => CheckBoxBuilder::new(WidgetBuilder::new())
                             .checked(Some(*value))
                             .build(ctx),
+                        // Material colors are always in sRGB space, the renderer converts them
+                        // to linear space when binding them to shaders.
                         PropertyValue::Color(value) => ColorFieldBuilder::new(WidgetBuilder::new())
                             .with_color(*value)
+                            .with_color_space(ColorSpace::Srgb)
                             .build(ctx),
                         PropertyValue::Sampler { value, .. } => ImageBuilder::new(
                             WidgetBuilder::new()
@@ -603,6 +679,22 @@ impl MaterialEditor {
                     Some(material.shader().clone()),
                 ),
             );
+            send_sync_message(
+                ui,
+                NumericUpDownMessage::value(
+                    self.render_queue,
+                    MessageDirection::ToWidget,
+                    material.render_queue(),
+                ),
+            );
+            send_sync_message(
+                ui,
+                TextMessage::text(
+                    self.pass_tags,
+                    MessageDirection::ToWidget,
+                    format_pass_tags(material.pass_tags()),
+                ),
+            );
         } else {
             send_sync_message(
                 ui,
@@ -631,6 +723,34 @@ impl MaterialEditor {
                         ));
                     }
                 }
+            } else if let Some(NumericUpDownMessage::Value(value)) =
+                message.data::<NumericUpDownMessage<i32>>()
+            {
+                if message.destination() == self.render_queue
+                    && message.direction() == MessageDirection::FromWidget
+                    && material
+                        .state()
+                        .data()
+                        .is_some_and(|m| m.render_queue() != *value)
+                {
+                    sender.do_command(SetMaterialRenderQueueCommand::new(material.clone(), *value));
+                }
+            } else if let Some(TextMessage::Text(text)) = message.data::<TextMessage>() {
+                if message.destination() == self.pass_tags
+                    && message.direction() == MessageDirection::FromWidget
+                {
+                    let pass_tags = parse_pass_tags(text);
+                    if material
+                        .state()
+                        .data()
+                        .is_some_and(|m| m.pass_tags() != pass_tags.as_slice())
+                    {
+                        sender.do_command(SetMaterialPassTagsCommand::new(
+                            material.clone(),
+                            pass_tags,
+                        ));
+                    }
+                }
             } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) =
                 message.data::<PopupMessage>()
             {
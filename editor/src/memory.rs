@@ -0,0 +1,236 @@
+//! Memory window shows live memory usage and peaks of engine subsystems. See
+//! [`crate::fyrox::core::memory`] docs for more info about memory tracking.
+
+use crate::{
+    fyrox::{
+        core::{
+            memory::{self, MemorySubsystem, MemoryUsage},
+            pool::Handle,
+        },
+        gui::{
+            button::{ButtonBuilder, ButtonMessage},
+            formatted_text::WrapMode,
+            grid::{Column, GridBuilder, Row},
+            message::{MessageDirection, UiMessage},
+            text::{TextBuilder, TextMessage},
+            widget::{WidgetBuilder, WidgetMessage},
+            window::{WindowBuilder, WindowMessage, WindowTitle},
+            BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
+        },
+    },
+    stats::StatisticsWindowAction,
+};
+
+/// Interval (in seconds) between updates of the values.
+const REFRESH_INTERVAL: f32 = 0.5;
+
+/// Formats the given amount of bytes using the most suitable unit.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.2} {}", UNITS[unit])
+    }
+}
+
+struct UsageRow {
+    current: Handle<UiNode>,
+    peak: Handle<UiNode>,
+    allocations: Handle<UiNode>,
+}
+
+impl UsageRow {
+    fn sync(&self, usage: MemoryUsage, ui: &UserInterface) {
+        for (text, value) in [
+            (self.current, format_bytes(usage.current)),
+            (self.peak, format_bytes(usage.peak)),
+            (self.allocations, usage.allocations.to_string()),
+        ] {
+            ui.send_message(TextMessage::text(text, MessageDirection::ToWidget, value));
+        }
+    }
+}
+
+fn make_cell(text: &str, row: usize, column: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
+    TextBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(column)
+            .with_margin(Thickness::uniform(2.0)),
+    )
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .with_text(text)
+    .build(ctx)
+}
+
+pub struct MemoryWindow {
+    pub window: Handle<UiNode>,
+    reset_peaks: Handle<UiNode>,
+    subsystems: Vec<UsageRow>,
+    total: UsageRow,
+    time_since_refresh: f32,
+}
+
+impl MemoryWindow {
+    pub fn new(ctx: &mut BuildContext, anchor: Handle<UiNode>) -> Self {
+        let mut children = Vec::new();
+        for (column, header) in ["Subsystem", "Current", "Peak", "Blocks"]
+            .into_iter()
+            .enumerate()
+        {
+            children.push(make_cell(header, 0, column, ctx));
+        }
+
+        let mut make_row = |name: &str, row: usize, ctx: &mut BuildContext| {
+            let cells = [
+                make_cell(name, row, 0, ctx),
+                make_cell("", row, 1, ctx),
+                make_cell("", row, 2, ctx),
+                make_cell("", row, 3, ctx),
+            ];
+            children.extend_from_slice(&cells);
+            UsageRow {
+                current: cells[1],
+                peak: cells[2],
+                allocations: cells[3],
+            }
+        };
+
+        let subsystems = MemorySubsystem::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, subsystem)| make_row(subsystem.name(), i + 1, ctx))
+            .collect::<Vec<_>>();
+        let total_row = MemorySubsystem::ALL.len() + 1;
+        let total = make_row("Total", total_row, ctx);
+
+        let mut table = GridBuilder::new(WidgetBuilder::new().on_row(0).with_children(children))
+            .add_column(Column::strict(90.0))
+            .add_column(Column::stretch())
+            .add_column(Column::stretch())
+            .add_column(Column::stretch());
+        for _ in 0..=total_row {
+            table = table.add_row(Row::strict(22.0));
+        }
+        let table = table.build(ctx);
+
+        let reset_peaks = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .with_width(90.0)
+                .with_horizontal_alignment(HorizontalAlignment::Left)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_text("Reset Peaks")
+        .build(ctx);
+
+        let notice = TextBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
+                .with_margin(Thickness::uniform(2.0)),
+        )
+        .with_wrap(WrapMode::Word)
+        .with_text(if memory::is_available() {
+            ""
+        } else {
+            "Memory tracking is not available, build the editor with `memory_tracking` feature \
+            to use it."
+        })
+        .build(ctx);
+
+        let grid = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(table)
+                .with_child(reset_peaks)
+                .with_child(notice),
+        )
+        .add_column(Column::stretch())
+        .add_row(Row::auto())
+        .add_row(Row::strict(26.0))
+        .add_row(Row::stretch())
+        .build(ctx);
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(250.0))
+            .open(false)
+            .with_content(grid)
+            .with_title(WindowTitle::text("Memory Usage"))
+            .build(ctx);
+
+        ctx.sender()
+            .send(WindowMessage::open_and_align(
+                window,
+                MessageDirection::ToWidget,
+                anchor,
+                HorizontalAlignment::Right,
+                VerticalAlignment::Top,
+                Thickness::uniform(2.0),
+                false,
+                false,
+            ))
+            .unwrap();
+
+        Self {
+            window,
+            reset_peaks,
+            subsystems,
+            total,
+            time_since_refresh: REFRESH_INTERVAL,
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+    ) -> StatisticsWindowAction {
+        if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                ui.send_message(WidgetMessage::remove(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+
+                return StatisticsWindowAction::Remove;
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.reset_peaks {
+                memory::reset_peaks();
+                // Show new values immediately.
+                self.time_since_refresh = REFRESH_INTERVAL;
+            }
+        }
+        StatisticsWindowAction::None
+    }
+
+    pub fn update(&mut self, dt: f32, ui: &UserInterface) {
+        self.time_since_refresh += dt;
+        if self.time_since_refresh < REFRESH_INTERVAL {
+            return;
+        }
+        self.time_since_refresh = 0.0;
+
+        for (row, subsystem) in self.subsystems.iter().zip(MemorySubsystem::ALL) {
+            row.sync(memory::subsystem_usage(subsystem), ui);
+        }
+        self.total.sync(memory::total_usage(), ui);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::memory::format_bytes;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(100), "100 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.00 MiB");
+    }
+}
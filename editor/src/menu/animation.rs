@@ -2,7 +2,10 @@ use crate::fyrox::{
     core::pool::Handle,
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
     scene::{
-        animation::{absm::prelude::*, prelude::*},
+        animation::{
+            absm::prelude::*, prelude::*, sequencer::SequencePlayerBuilder,
+            sprite::SpriteAnimationPlayerBuilder,
+        },
         base::BaseBuilder,
         node::Node,
     },
@@ -13,12 +16,16 @@ pub struct AnimationMenu {
     pub menu: Handle<UiNode>,
     create_animation_player: Handle<UiNode>,
     create_absm: Handle<UiNode>,
+    create_sprite_animation_player: Handle<UiNode>,
+    create_sequence_player: Handle<UiNode>,
 }
 
 impl AnimationMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_animation_player;
         let create_absm;
+        let create_sprite_animation_player;
+        let create_sequence_player;
 
         let menu = create_menu_item(
             "Animation",
@@ -31,6 +38,15 @@ impl AnimationMenu {
                     create_absm = create_menu_item("Animation Blending State Machine", vec![], ctx);
                     create_absm
                 },
+                {
+                    create_sprite_animation_player =
+                        create_menu_item("Sprite Animation Player", vec![], ctx);
+                    create_sprite_animation_player
+                },
+                {
+                    create_sequence_player = create_menu_item("Sequence Player", vec![], ctx);
+                    create_sequence_player
+                },
             ],
             ctx,
         );
@@ -39,6 +55,8 @@ impl AnimationMenu {
             menu,
             create_animation_player,
             create_absm,
+            create_sprite_animation_player,
+            create_sequence_player,
         }
     }
 
@@ -63,6 +81,17 @@ impl AnimationMenu {
                 .with_machine(machine)
                 .build_node();
                 Some(node)
+            } else if message.destination() == self.create_sprite_animation_player {
+                let node = SpriteAnimationPlayerBuilder::new(
+                    BaseBuilder::new().with_name("Sprite Animation Player"),
+                )
+                .build_node();
+                Some(node)
+            } else if message.destination() == self.create_sequence_player {
+                let node =
+                    SequencePlayerBuilder::new(BaseBuilder::new().with_name("Sequence Player"))
+                        .build_node();
+                Some(node)
             } else {
                 None
             }
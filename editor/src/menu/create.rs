@@ -4,7 +4,7 @@ use crate::{
     fyrox::{
         asset::untyped::ResourceKind,
         core::{
-            algebra::{Matrix4, Vector3},
+            algebra::{Matrix4, Vector2, Vector3},
             math::TriangleDefinition,
             pool::Handle,
         },
@@ -17,10 +17,11 @@ use crate::{
         scene::{
             base::BaseBuilder,
             camera::CameraBuilder,
+            crowd::CrowdBuilder,
             decal::DecalBuilder,
             light::{
-                directional::DirectionalLightBuilder, point::PointLightBuilder,
-                spot::SpotLightBuilder, BaseLightBuilder,
+                area::AreaLightBuilder, directional::DirectionalLightBuilder,
+                point::PointLightBuilder, spot::SpotLightBuilder, BaseLightBuilder,
             },
             mesh::{
                 surface::{SurfaceBuilder, SurfaceData, SurfaceResource},
@@ -33,9 +34,13 @@ use crate::{
                 ParticleSystemBuilder,
             },
             pivot::PivotBuilder,
+            point_cloud::PointCloudBuilder,
             sound::{listener::ListenerBuilder, SoundBuilder},
             sprite::SpriteBuilder,
             terrain::{Layer, TerrainBuilder},
+            text3d::Text3DBuilder,
+            water::WaterBuilder,
+            weather::WeatherBuilder,
         },
         utils::navmesh::Navmesh,
     },
@@ -123,11 +128,17 @@ pub struct CreateEntityMenu {
     create_decal: Handle<UiNode>,
     create_point_light: Handle<UiNode>,
     create_spot_light: Handle<UiNode>,
+    create_area_light: Handle<UiNode>,
     create_directional_light: Handle<UiNode>,
     create_navmesh: Handle<UiNode>,
     create_terrain: Handle<UiNode>,
+    create_crowd: Handle<UiNode>,
+    create_point_cloud: Handle<UiNode>,
+    create_water: Handle<UiNode>,
+    create_weather: Handle<UiNode>,
     create_camera: Handle<UiNode>,
     create_sprite: Handle<UiNode>,
+    create_text3d: Handle<UiNode>,
     create_particle_system: Handle<UiNode>,
     create_listener: Handle<UiNode>,
     create_sound_source: Handle<UiNode>,
@@ -157,13 +168,19 @@ impl CreateEntityMenu {
         let create_quad;
         let create_point_light;
         let create_spot_light;
+        let create_area_light;
         let create_directional_light;
         let create_camera;
         let create_sprite;
+        let create_text3d;
         let create_decal;
         let create_navmesh;
         let create_particle_system;
         let create_terrain;
+        let create_crowd;
+        let create_point_cloud;
+        let create_water;
+        let create_weather;
         let create_pivot;
         let create_sound_source;
         let create_listener;
@@ -246,6 +263,10 @@ impl CreateEntityMenu {
                             create_point_light = create_menu_item("Point Light", vec![], ctx);
                             create_point_light
                         },
+                        {
+                            create_area_light = create_menu_item("Area Light", vec![], ctx);
+                            create_area_light
+                        },
                     ],
                     ctx,
                 );
@@ -263,6 +284,10 @@ impl CreateEntityMenu {
                 create_sprite = create_menu_item("Sprite (3D)", vec![], ctx);
                 create_sprite
             },
+            {
+                create_text3d = create_menu_item("Text (3D)", vec![], ctx);
+                create_text3d
+            },
             {
                 create_particle_system = create_menu_item("Particle System", vec![], ctx);
                 create_particle_system
@@ -271,6 +296,22 @@ impl CreateEntityMenu {
                 create_terrain = create_menu_item("Terrain", vec![], ctx);
                 create_terrain
             },
+            {
+                create_crowd = create_menu_item("Crowd", vec![], ctx);
+                create_crowd
+            },
+            {
+                create_point_cloud = create_menu_item("Point Cloud", vec![], ctx);
+                create_point_cloud
+            },
+            {
+                create_water = create_menu_item("Water", vec![], ctx);
+                create_water
+            },
+            {
+                create_weather = create_menu_item("Weather", vec![], ctx);
+                create_weather
+            },
             {
                 create_decal = create_menu_item("Decal", vec![], ctx);
                 create_decal
@@ -290,12 +331,18 @@ impl CreateEntityMenu {
                 create_quad,
                 create_point_light,
                 create_spot_light,
+                create_area_light,
                 create_directional_light,
                 create_camera,
                 create_sprite,
+                create_text3d,
                 create_particle_system,
                 create_pivot,
                 create_terrain,
+                create_crowd,
+                create_point_cloud,
+                create_water,
+                create_weather,
                 create_sound_source,
                 create_listener,
                 create_navmesh,
@@ -327,9 +374,14 @@ impl CreateEntityMenu {
             self.light_menu,
             self.create_camera,
             self.create_sprite,
+            self.create_text3d,
             self.create_particle_system,
             self.create_pivot,
             self.create_terrain,
+            self.create_crowd,
+            self.create_point_cloud,
+            self.create_water,
+            self.create_weather,
             self.sound_menu,
             self.create_navmesh,
             self.create_decal,
@@ -396,6 +448,15 @@ impl CreateEntityMenu {
                             .with_radius(10.0)
                             .build_node(),
                         )
+                    } else if message.destination() == self.create_area_light {
+                        Some(
+                            AreaLightBuilder::new(BaseLightBuilder::new(
+                                BaseBuilder::new().with_name("AreaLight"),
+                            ))
+                            .with_size(Vector2::new(1.0, 1.0))
+                            .with_radius(10.0)
+                            .build_node(),
+                        )
                     } else if message.destination() == self.create_directional_light {
                         Some(
                             DirectionalLightBuilder::new(BaseLightBuilder::new(
@@ -476,6 +537,12 @@ impl CreateEntityMenu {
                         Some(
                             SpriteBuilder::new(BaseBuilder::new().with_name("Sprite")).build_node(),
                         )
+                    } else if message.destination() == self.create_text3d {
+                        Some(
+                            Text3DBuilder::new(BaseBuilder::new().with_name("Text"))
+                                .with_text("Text")
+                                .build_node(),
+                        )
                     } else if message.destination() == self.create_sound_source {
                         Some(SoundBuilder::new(BaseBuilder::new().with_name("Sound")).build_node())
                     } else if message.destination() == self.create_particle_system {
@@ -501,6 +568,20 @@ impl CreateEntityMenu {
                                 }])
                                 .build_node(),
                         )
+                    } else if message.destination() == self.create_crowd {
+                        Some(CrowdBuilder::new(BaseBuilder::new().with_name("Crowd")).build_node())
+                    } else if message.destination() == self.create_point_cloud {
+                        Some(
+                            PointCloudBuilder::new(BaseBuilder::new().with_name("Point Cloud"))
+                                .build_node(),
+                        )
+                    } else if message.destination() == self.create_water {
+                        Some(WaterBuilder::new(BaseBuilder::new().with_name("Water")).build_node())
+                    } else if message.destination() == self.create_weather {
+                        Some(
+                            WeatherBuilder::new(BaseBuilder::new().with_name("Weather"))
+                                .build_node(),
+                        )
                     } else if message.destination() == self.create_decal {
                         Some(DecalBuilder::new(BaseBuilder::new().with_name("Decal")).build_node())
                     } else if message.destination() == self.create_listener {
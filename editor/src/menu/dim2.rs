@@ -1,9 +1,14 @@
 use crate::{
     fyrox::{
-        core::pool::Handle,
+        core::{algebra::Vector2, pool::Handle},
         gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
         scene::{
-            base::BaseBuilder, dim2::rectangle::RectangleBuilder, node::Node,
+            base::BaseBuilder,
+            dim2::{
+                light::Light2DBuilder, occluder::Occluder2DBuilder, parallax::ParallaxLayerBuilder,
+                rectangle::RectangleBuilder, skin::SkinnedSpriteBuilder,
+            },
+            node::Node,
             tilemap::TileMapBuilder,
         },
     },
@@ -14,12 +19,20 @@ pub struct Dim2Menu {
     pub menu: Handle<UiNode>,
     create_sprite: Handle<UiNode>,
     create_tile_map: Handle<UiNode>,
+    create_skinned_sprite: Handle<UiNode>,
+    create_light: Handle<UiNode>,
+    create_occluder: Handle<UiNode>,
+    create_parallax_layer: Handle<UiNode>,
 }
 
 impl Dim2Menu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_sprite;
         let create_tile_map;
+        let create_skinned_sprite;
+        let create_light;
+        let create_occluder;
+        let create_parallax_layer;
 
         let menu = create_menu_item(
             "2D",
@@ -32,6 +45,22 @@ impl Dim2Menu {
                     create_tile_map = create_menu_item("Tile Map", vec![], ctx);
                     create_tile_map
                 },
+                {
+                    create_skinned_sprite = create_menu_item("Skinned Sprite", vec![], ctx);
+                    create_skinned_sprite
+                },
+                {
+                    create_light = create_menu_item("Light (2D)", vec![], ctx);
+                    create_light
+                },
+                {
+                    create_occluder = create_menu_item("Light Occluder (2D)", vec![], ctx);
+                    create_occluder
+                },
+                {
+                    create_parallax_layer = create_menu_item("Parallax Layer", vec![], ctx);
+                    create_parallax_layer
+                },
             ],
             ctx,
         );
@@ -40,6 +69,10 @@ impl Dim2Menu {
             menu,
             create_sprite,
             create_tile_map,
+            create_skinned_sprite,
+            create_light,
+            create_occluder,
+            create_parallax_layer,
         }
     }
 
@@ -53,6 +86,26 @@ impl Dim2Menu {
                 let node =
                     TileMapBuilder::new(BaseBuilder::new().with_name("Tile Map")).build_node();
                 Some(node)
+            } else if message.destination() == self.create_skinned_sprite {
+                let node =
+                    SkinnedSpriteBuilder::new(BaseBuilder::new().with_name("Skinned Sprite"))
+                        .with_grid(Vector2::new(1.0, 1.0), 4, 4)
+                        .build_node();
+                Some(node)
+            } else if message.destination() == self.create_light {
+                let node =
+                    Light2DBuilder::new(BaseBuilder::new().with_name("Light (2D)")).build_node();
+                Some(node)
+            } else if message.destination() == self.create_occluder {
+                let node = Occluder2DBuilder::new(BaseBuilder::new().with_name("Occluder (2D)"))
+                    .with_rectangle(Vector2::new(1.0, 1.0))
+                    .build_node();
+                Some(node)
+            } else if message.destination() == self.create_parallax_layer {
+                let node =
+                    ParallaxLayerBuilder::new(BaseBuilder::new().with_name("Parallax Layer"))
+                        .build_node();
+                Some(node)
             } else {
                 None
             }
@@ -9,9 +9,13 @@ use crate::fyrox::{
 };
 use crate::scene::controller::SceneController;
 use crate::{
-    menu::{create_menu_item_shortcut, create_root_menu_item},
+    menu::{create_menu_item, create_menu_item_shortcut, create_root_menu_item},
     message::MessageSender,
-    scene::{commands::PasteCommand, GameScene, Selection},
+    scene::{
+        align::{make_align_command, AlignAction},
+        commands::PasteCommand,
+        GameScene, Selection,
+    },
     Engine, Message, Mode,
 };
 
@@ -21,6 +25,7 @@ pub struct EditMenu {
     redo: Handle<UiNode>,
     copy: Handle<UiNode>,
     paste: Handle<UiNode>,
+    align_items: Vec<(Handle<UiNode>, AlignAction)>,
 }
 
 impl EditMenu {
@@ -29,6 +34,15 @@ impl EditMenu {
         let undo;
         let copy;
         let paste;
+        let align_items = AlignAction::all()
+            .into_iter()
+            .map(|(action, name)| (create_menu_item(&name, vec![], ctx), action))
+            .collect::<Vec<_>>();
+        let align = create_menu_item(
+            "Align",
+            align_items.iter().map(|(item, _)| *item).collect(),
+            ctx,
+        );
         let menu = create_root_menu_item(
             "Edit",
             vec![
@@ -48,6 +62,7 @@ impl EditMenu {
                     paste = create_menu_item_shortcut("Paste", "Ctrl+V", vec![], ctx);
                     paste
                 },
+                align,
             ],
             ctx,
         );
@@ -58,6 +73,7 @@ impl EditMenu {
             redo,
             copy,
             paste,
+            align_items,
         }
     }
 
@@ -90,6 +106,24 @@ impl EditMenu {
                 sender.send(Message::UndoCurrentSceneCommand);
             } else if message.destination() == self.redo {
                 sender.send(Message::RedoCurrentSceneCommand);
+            } else if let Some((_, action)) = self
+                .align_items
+                .iter()
+                .find(|(item, _)| *item == message.destination())
+            {
+                if let (Some(selection), Some(game_scene)) = (
+                    editor_selection.as_graph(),
+                    controller.downcast_mut::<GameScene>(),
+                ) {
+                    if let Some(command) = make_align_command(
+                        &engine.scenes[game_scene.scene].graph,
+                        selection,
+                        *action,
+                        game_scene.editor_objects_root,
+                    ) {
+                        sender.do_command(command);
+                    }
+                }
             }
         }
     }
@@ -1,6 +1,7 @@
 use crate::{
     animation::AnimationEditor,
     export::ExportWindow,
+    flame_graph::FlameGraphWindow,
     fyrox::{
         core::{algebra::Vector2, pool::Handle, scope_profile},
         gui::{
@@ -10,17 +11,23 @@ use crate::{
             BuildContext, Thickness, UiNode, UserInterface,
         },
     },
+    hud::PerformanceHud,
+    memory::MemoryWindow,
     menu::{
         create::CreateEntityRootMenu, edit::EditMenu, file::FileMenu, help::HelpMenu,
         utils::UtilsMenu, view::ViewMenu,
     },
     message::MessageSender,
+    replication::ReplicationPanel,
     scene::{container::EditorSceneEntry, controller::SceneController},
     send_sync_message,
     settings::Settings,
     stats::StatisticsWindow,
-    utils::ragdoll::RagdollWizard,
-    AbsmEditor, CurveEditorWindow, Engine, Mode, SceneSettingsWindow,
+    utils::{
+        capture::CaptureTool, ragdoll::RagdollWizard, skybox::SkyboxEditor,
+        vertex_animation::VertexAnimationBaker,
+    },
+    AbsmEditor, CurveEditorWindow, Engine, LocalizationEditor, Mode, SceneSettingsWindow,
 };
 use std::path::PathBuf;
 
@@ -45,14 +52,22 @@ pub struct Menu {
     message_sender: MessageSender,
     utils_menu: UtilsMenu,
     help_menu: HelpMenu,
+    /// Root menu item for the items added by editor plugins. It is hidden until some plugin adds
+    /// an item to it. See [`crate::plugin_ui::PluginUi`] for more info.
+    pub plugins_menu: Handle<UiNode>,
 }
 
 pub struct Panels<'b> {
     pub scene_frame: Handle<UiNode>,
     pub light_panel: Handle<UiNode>,
+    pub validation_panel: Handle<UiNode>,
+    pub runtime_inspector: Handle<UiNode>,
+    pub layers_panel: Handle<UiNode>,
+    pub pose_library_panel: Handle<UiNode>,
     pub log_panel: Handle<UiNode>,
     pub navmesh_panel: Handle<UiNode>,
     pub audio_panel: Handle<UiNode>,
+    pub audio_bus_graph: Handle<UiNode>,
     pub command_stack_panel: Handle<UiNode>,
     pub inspector_window: Handle<UiNode>,
     pub world_outliner_window: Handle<UiNode>,
@@ -60,12 +75,20 @@ pub struct Panels<'b> {
     pub configurator_window: Handle<UiNode>,
     pub path_fixer: Handle<UiNode>,
     pub curve_editor: &'b CurveEditorWindow,
+    pub localization_editor: &'b LocalizationEditor,
+    pub replication_panel: &'b ReplicationPanel,
     pub absm_editor: &'b AbsmEditor,
     pub scene_settings: &'b SceneSettingsWindow,
     pub animation_editor: &'b AnimationEditor,
     pub ragdoll_wizard: &'b RagdollWizard,
+    pub vertex_animation_baker: &'b VertexAnimationBaker,
+    pub skybox_editor: &'b SkyboxEditor,
+    pub capture_tool: &'b CaptureTool,
     pub export_window: &'b mut Option<ExportWindow>,
     pub statistics_window: &'b mut Option<StatisticsWindow>,
+    pub performance_hud: &'b mut Option<PerformanceHud>,
+    pub flame_graph: &'b mut Option<FlameGraphWindow>,
+    pub memory_window: &'b mut Option<MemoryWindow>,
 }
 
 pub struct MenuContext<'a, 'b> {
@@ -118,6 +141,13 @@ impl Menu {
         let view_menu = ViewMenu::new(ctx);
         let utils_menu = UtilsMenu::new(ctx);
         let help_menu = HelpMenu::new(ctx);
+        let plugins_menu = MenuItemBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_margin(Thickness::right(10.0)),
+        )
+        .with_content(MenuItemContent::text_centered("Plugins"))
+        .build(ctx);
 
         let menu = MenuBuilder::new(WidgetBuilder::new().on_row(0))
             .with_items(vec![
@@ -126,6 +156,7 @@ impl Menu {
                 create_entity_menu.menu,
                 view_menu.menu,
                 utils_menu.menu,
+                plugins_menu,
                 help_menu.menu,
             ])
             .build(ctx);
@@ -139,6 +170,7 @@ impl Menu {
             view_menu,
             utils_menu,
             help_menu,
+            plugins_menu,
         }
     }
 
@@ -35,6 +35,10 @@ use crate::fyrox::{
         tree::{TreeBuilder, TreeRootBuilder},
         uuid::UuidEditorBuilder,
         vector_image::VectorImageBuilder,
+        virtual_controls::{
+            dpad::VirtualDPadBuilder, joystick::VirtualJoystickBuilder,
+            touch_button::TouchButtonBuilder,
+        },
         widget::WidgetBuilder,
         window::WindowBuilder,
         wrap_panel::WrapPanelBuilder,
@@ -186,6 +190,33 @@ impl UiMenu {
             UiMenuEntry::new("VectorImage", |name, ctx| {
                 VectorImageBuilder::new(WidgetBuilder::new().with_name(name)).build(ctx)
             }),
+            UiMenuEntry::new("VirtualJoystick", |name, ctx| {
+                VirtualJoystickBuilder::new(
+                    WidgetBuilder::new()
+                        .with_width(150.0)
+                        .with_height(150.0)
+                        .with_name(name),
+                )
+                .build(ctx)
+            }),
+            UiMenuEntry::new("VirtualDPad", |name, ctx| {
+                VirtualDPadBuilder::new(
+                    WidgetBuilder::new()
+                        .with_width(150.0)
+                        .with_height(150.0)
+                        .with_name(name),
+                )
+                .build(ctx)
+            }),
+            UiMenuEntry::new("TouchButton", |name, ctx| {
+                TouchButtonBuilder::new(
+                    WidgetBuilder::new()
+                        .with_width(80.0)
+                        .with_height(80.0)
+                        .with_name(name),
+                )
+                .build(ctx)
+            }),
             UiMenuEntry::new("Window", |name, ctx| {
                 WindowBuilder::new(WidgetBuilder::new().with_name(name)).build(ctx)
             }),
@@ -8,26 +8,45 @@ use crate::fyrox::{
     },
 };
 use crate::menu::{create_menu_item, create_root_menu_item, Panels};
-use crate::stats::StatisticsWindow;
+use crate::{
+    flame_graph::FlameGraphWindow, hud::PerformanceHud, memory::MemoryWindow,
+    stats::StatisticsWindow,
+};
 
 pub struct UtilsMenu {
     pub menu: Handle<UiNode>,
     open_path_fixer: Handle<UiNode>,
     open_curve_editor: Handle<UiNode>,
+    open_localization_editor: Handle<UiNode>,
+    open_replication_panel: Handle<UiNode>,
     absm_editor: Handle<UiNode>,
     animation_editor: Handle<UiNode>,
     ragdoll_wizard: Handle<UiNode>,
+    vertex_animation_baker: Handle<UiNode>,
+    skybox_editor: Handle<UiNode>,
+    capture: Handle<UiNode>,
     rendering_statistics: Handle<UiNode>,
+    performance_hud: Handle<UiNode>,
+    flame_graph: Handle<UiNode>,
+    memory_usage: Handle<UiNode>,
 }
 
 impl UtilsMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let open_path_fixer;
         let open_curve_editor;
+        let open_localization_editor;
+        let open_replication_panel;
         let absm_editor;
         let animation_editor;
         let ragdoll_wizard;
+        let vertex_animation_baker;
+        let skybox_editor;
+        let capture;
         let rendering_statistics;
+        let performance_hud;
+        let flame_graph;
+        let memory_usage;
         let menu = create_root_menu_item(
             "Utils",
             vec![
@@ -39,6 +58,14 @@ impl UtilsMenu {
                     open_curve_editor = create_menu_item("Curve Editor", vec![], ctx);
                     open_curve_editor
                 },
+                {
+                    open_localization_editor = create_menu_item("Localization Editor", vec![], ctx);
+                    open_localization_editor
+                },
+                {
+                    open_replication_panel = create_menu_item("Replication", vec![], ctx);
+                    open_replication_panel
+                },
                 {
                     absm_editor = create_menu_item("ABSM Editor", vec![], ctx);
                     absm_editor
@@ -51,10 +78,35 @@ impl UtilsMenu {
                     ragdoll_wizard = create_menu_item("Ragdoll Wizard", vec![], ctx);
                     ragdoll_wizard
                 },
+                {
+                    vertex_animation_baker =
+                        create_menu_item("Vertex Animation Baker", vec![], ctx);
+                    vertex_animation_baker
+                },
+                {
+                    skybox_editor = create_menu_item("Skybox Editor", vec![], ctx);
+                    skybox_editor
+                },
+                {
+                    capture = create_menu_item("Capture", vec![], ctx);
+                    capture
+                },
                 {
                     rendering_statistics = create_menu_item("Rendering Statistics", vec![], ctx);
                     rendering_statistics
                 },
+                {
+                    performance_hud = create_menu_item("Performance HUD", vec![], ctx);
+                    performance_hud
+                },
+                {
+                    flame_graph = create_menu_item("Flame Graph", vec![], ctx);
+                    flame_graph
+                },
+                {
+                    memory_usage = create_menu_item("Memory Usage", vec![], ctx);
+                    memory_usage
+                },
             ],
             ctx,
         );
@@ -63,10 +115,18 @@ impl UtilsMenu {
             menu,
             open_path_fixer,
             open_curve_editor,
+            open_localization_editor,
+            open_replication_panel,
             absm_editor,
             animation_editor,
             ragdoll_wizard,
+            vertex_animation_baker,
+            skybox_editor,
+            capture,
             rendering_statistics,
+            performance_hud,
+            flame_graph,
+            memory_usage,
         }
     }
 
@@ -86,17 +146,37 @@ impl UtilsMenu {
                 ));
             } else if message.destination() == self.open_curve_editor {
                 panels.curve_editor.open(ui);
+            } else if message.destination() == self.open_localization_editor {
+                panels.localization_editor.open(ui);
+            } else if message.destination() == self.open_replication_panel {
+                panels.replication_panel.open(ui);
             } else if message.destination() == self.absm_editor {
                 panels.absm_editor.open(ui);
             } else if message.destination() == self.animation_editor {
                 panels.animation_editor.open(ui);
             } else if message.destination() == self.ragdoll_wizard {
                 panels.ragdoll_wizard.open(ui);
+            } else if message.destination() == self.vertex_animation_baker {
+                panels.vertex_animation_baker.open(ui);
+            } else if message.destination() == self.skybox_editor {
+                panels.skybox_editor.open(ui);
+            } else if message.destination() == self.capture {
+                panels.capture_tool.open(ui);
             } else if message.destination() == self.rendering_statistics {
                 *panels.statistics_window = Some(StatisticsWindow::new(
                     &mut ui.build_ctx(),
                     panels.scene_frame,
                 ))
+            } else if message.destination() == self.performance_hud
+                && panels.performance_hud.is_none()
+            {
+                *panels.performance_hud =
+                    Some(PerformanceHud::new(&mut ui.build_ctx(), panels.scene_frame))
+            } else if message.destination() == self.flame_graph && panels.flame_graph.is_none() {
+                *panels.flame_graph = Some(FlameGraphWindow::new(&mut ui.build_ctx()))
+            } else if message.destination() == self.memory_usage && panels.memory_window.is_none() {
+                *panels.memory_window =
+                    Some(MemoryWindow::new(&mut ui.build_ctx(), panels.scene_frame))
             }
         }
     }
@@ -20,9 +20,14 @@ pub struct ViewMenu {
     world_viewer: Handle<UiNode>,
     asset_browser: Handle<UiNode>,
     light_panel: Handle<UiNode>,
+    validation_panel: Handle<UiNode>,
+    runtime_inspector: Handle<UiNode>,
+    layers_panel: Handle<UiNode>,
+    pose_library_panel: Handle<UiNode>,
     log_panel: Handle<UiNode>,
     nav_mesh: Handle<UiNode>,
     audio: Handle<UiNode>,
+    audio_bus_graph: Handle<UiNode>,
     command_stack: Handle<UiNode>,
     save_layout: Handle<UiNode>,
     load_layout: Handle<UiNode>,
@@ -43,9 +48,14 @@ impl ViewMenu {
         let asset_browser;
         let world_viewer;
         let light_panel;
+        let validation_panel;
+        let runtime_inspector;
+        let layers_panel;
+        let pose_library_panel;
         let log_panel;
         let nav_mesh;
         let audio;
+        let audio_bus_graph;
         let command_stack;
         let save_layout;
         let load_layout;
@@ -68,6 +78,22 @@ impl ViewMenu {
                     light_panel = create_menu_item("Light Panel", vec![], ctx);
                     light_panel
                 },
+                {
+                    validation_panel = create_menu_item("Validation Panel", vec![], ctx);
+                    validation_panel
+                },
+                {
+                    runtime_inspector = create_menu_item("Runtime Inspector", vec![], ctx);
+                    runtime_inspector
+                },
+                {
+                    layers_panel = create_menu_item("Layers Panel", vec![], ctx);
+                    layers_panel
+                },
+                {
+                    pose_library_panel = create_menu_item("Pose Library", vec![], ctx);
+                    pose_library_panel
+                },
                 {
                     log_panel = create_menu_item("Log Panel", vec![], ctx);
                     log_panel
@@ -80,6 +106,10 @@ impl ViewMenu {
                     audio = create_menu_item("Audio Panel", vec![], ctx);
                     audio
                 },
+                {
+                    audio_bus_graph = create_menu_item("Audio Bus Graph", vec![], ctx);
+                    audio_bus_graph
+                },
                 {
                     command_stack = create_menu_item("Command Stack Panel", vec![], ctx);
                     command_stack
@@ -102,9 +132,14 @@ impl ViewMenu {
             world_viewer,
             asset_browser,
             light_panel,
+            validation_panel,
+            runtime_inspector,
+            layers_panel,
+            pose_library_panel,
             log_panel,
             nav_mesh,
             audio,
+            audio_bus_graph,
             command_stack,
             save_layout,
             load_layout,
@@ -127,12 +162,22 @@ impl ViewMenu {
                 switch_window_state(panels.world_outliner_window, ui, false);
             } else if message.destination() == self.inspector {
                 switch_window_state(panels.inspector_window, ui, false);
+            } else if message.destination() == self.validation_panel {
+                switch_window_state(panels.validation_panel, ui, true);
+            } else if message.destination() == self.runtime_inspector {
+                switch_window_state(panels.runtime_inspector, ui, true);
+            } else if message.destination() == self.layers_panel {
+                switch_window_state(panels.layers_panel, ui, true);
+            } else if message.destination() == self.pose_library_panel {
+                switch_window_state(panels.pose_library_panel, ui, true);
             } else if message.destination() == self.log_panel {
                 switch_window_state(panels.log_panel, ui, false);
             } else if message.destination() == self.nav_mesh {
                 switch_window_state(panels.navmesh_panel, ui, false);
             } else if message.destination() == self.audio {
                 switch_window_state(panels.audio_panel, ui, false);
+            } else if message.destination() == self.audio_bus_graph {
+                switch_window_state(panels.audio_bus_graph, ui, true);
             } else if message.destination() == self.command_stack {
                 switch_window_state(panels.command_stack_panel, ui, false);
             } else if message.destination() == self.save_layout {
@@ -1,6 +1,7 @@
 use crate::{
     command::{Command, CommandTrait},
     fyrox::{
+        asset::untyped::UntypedResource,
         core::{
             log::Log,
             pool::{ErasedHandle, Handle},
@@ -8,11 +9,16 @@ use crate::{
         },
         gui::UiNode,
         material::MaterialResource,
+        resource::curve::CurveResource,
         scene::{camera::Projection, mesh::surface::SurfaceResource, node::Node},
     },
     scene::Selection,
+    utils::rename::BatchRenameTarget,
     SaveSceneConfirmationDialogAction,
 };
+use fyrox::material::shader::graph::ShaderGraphResource;
+use fyrox::scene::animation::sequencer::sequence::SequenceResource;
+use fyrox::scene::animation::sprite::sheet::SpriteSheetResource;
 use fyrox::scene::tilemap::tileset::TileSetResource;
 use std::{path::PathBuf, sync::mpsc::Sender};
 
@@ -45,7 +51,15 @@ pub enum Message {
     OpenAbsmEditor,
     OpenMaterialEditor(MaterialResource),
     OpenTileSetEditor(TileSetResource),
+    OpenSpriteSheetEditor(SpriteSheetResource),
+    OpenSequencer(SequenceResource),
+    OpenShaderGraphEditor(ShaderGraphResource),
+    OpenCurveEditor(CurveResource),
+    /// Opens an editor of a custom asset type, that was registered using
+    /// [`crate::Editor::register_asset_type`].
+    OpenAssetEditor(UntypedResource),
     OpenNodeRemovalDialog,
+    OpenBatchRenameDialog(BatchRenameTarget),
     ShowInAssetBrowser(PathBuf),
     LocateObject {
         handle: ErasedHandle,
@@ -59,6 +59,9 @@ pub struct OverlayRenderPass {
     sound_icon: TextureResource,
     light_icon: TextureResource,
     pub pictogram_size: f32,
+    /// Pictograms are not drawn when the pass is disabled, for example when capturing a screenshot
+    /// without gizmos.
+    pub enabled: bool,
 }
 
 impl OverlayRenderPass {
@@ -88,6 +91,7 @@ impl OverlayRenderPass {
             )
             .unwrap(),
             pictogram_size: 0.33,
+            enabled: true,
         }))
     }
 }
@@ -97,6 +101,10 @@ impl SceneRenderPass for OverlayRenderPass {
         &mut self,
         ctx: SceneRenderPassContext,
     ) -> Result<RenderPassStatistics, FrameworkError> {
+        if !self.enabled {
+            return Ok(Default::default());
+        }
+
         let view_projection = ctx.camera.view_projection_matrix();
         let shader = &self.shader;
         let inv_view = ctx.camera.inv_view_matrix().unwrap();
@@ -113,14 +121,17 @@ impl SceneRenderPass for OverlayRenderPass {
             .unwrap();
 
         for node in ctx.scene.graph.linear_iter() {
-            let icon =
-                if node.is_directional_light() || node.is_spot_light() || node.is_point_light() {
-                    light_icon.clone()
-                } else if node.is_sound() {
-                    sound_icon.clone()
-                } else {
-                    continue;
-                };
+            let icon = if node.is_directional_light()
+                || node.is_spot_light()
+                || node.is_point_light()
+                || node.is_area_light()
+            {
+                light_icon.clone()
+            } else if node.is_sound() {
+                sound_icon.clone()
+            } else {
+                continue;
+            };
 
             let position = node.global_position();
             let world_matrix = Matrix4::new_translation(&position);
@@ -0,0 +1,157 @@
+//! Color palettes, that are shared by every color picker of the editor and saved per project.
+
+use crate::fyrox::{
+    core::{color::Color, log::Log, pool::Handle},
+    graph::BaseSceneGraph,
+    gui::{
+        color::{ColorPalette, ColorPicker, ColorPickerMessage},
+        message::{MessageDirection, UiMessage},
+        popup::{Popup, PopupMessage},
+        UiNode, UserInterface,
+    },
+};
+use ron::ser::PrettyConfig;
+use std::{fs::File, io::Write, path::Path};
+
+/// A set of color palettes of the current project. Palettes are stored in the root folder of a
+/// project, so they could be shared with other people working on the project.
+pub struct ProjectColorPalettes {
+    pub palettes: Vec<ColorPalette>,
+}
+
+impl Default for ProjectColorPalettes {
+    fn default() -> Self {
+        Self {
+            palettes: vec![ColorPalette {
+                name: "Project".to_string(),
+                colors: Default::default(),
+            }],
+        }
+    }
+}
+
+impl ProjectColorPalettes {
+    /// Name of the file with palettes. The file is located in the root folder of a project.
+    pub const FILE_NAME: &'static str = "color_palettes.ron";
+
+    /// Loads palettes from the current working directory. Missing file means that the project
+    /// has only the default (empty) palette.
+    pub fn load() -> Self {
+        let path = Path::new(Self::FILE_NAME);
+        if !path.exists() {
+            return Default::default();
+        }
+
+        match File::open(path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| ron::de::from_reader(file).map_err(|e| e.to_string()))
+        {
+            Ok(palettes) => Self { palettes },
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to load color palettes from {}. Reason: {}",
+                    Self::FILE_NAME,
+                    e
+                ));
+                Default::default()
+            }
+        }
+    }
+
+    /// Saves palettes to the current working directory.
+    pub fn save(&self) {
+        let result = File::create(Self::FILE_NAME)
+            .map_err(|e| e.to_string())
+            .and_then(|mut file| {
+                let text = ron::ser::to_string_pretty(&self.palettes, PrettyConfig::default())
+                    .map_err(|e| e.to_string())?;
+                file.write_all(text.as_bytes()).map_err(|e| e.to_string())
+            });
+
+        if let Err(e) = result {
+            Log::err(format!(
+                "Unable to save color palettes to {}. Reason: {}",
+                Self::FILE_NAME,
+                e
+            ));
+        }
+    }
+
+    fn add_color(&mut self, palette: usize, color: Color) -> bool {
+        if let Some(palette) = self.palettes.get_mut(palette) {
+            if !palette.colors.contains(&color) {
+                palette.colors.push(color);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn remove_color(&mut self, palette: usize, index: usize) -> bool {
+        if let Some(palette) = self.palettes.get_mut(palette) {
+            if index < palette.colors.len() {
+                palette.colors.remove(index);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn send_to(&self, picker: Handle<UiNode>, ui: &UserInterface) {
+        ui.send_message(ColorPickerMessage::palettes(
+            picker,
+            MessageDirection::ToWidget,
+            self.palettes.clone(),
+        ));
+    }
+
+    /// Sends the palettes to every color picker of the user interface.
+    pub fn sync_to_pickers(&self, ui: &UserInterface) {
+        for (handle, node) in ui.nodes().pair_iter() {
+            if node.cast::<ColorPicker>().is_some() {
+                self.send_to(handle, ui);
+            }
+        }
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, ui: &UserInterface) {
+        if let Some(PopupMessage::Open) = message.data() {
+            if message.direction() == MessageDirection::ToWidget {
+                // Color fields create their pickers in popups, the pickers must get up-to-date
+                // palettes each time they're shown.
+                if let Some(popup) = ui
+                    .try_get(message.destination())
+                    .and_then(|n| n.cast::<Popup>())
+                {
+                    let content = *popup.content;
+                    if ui
+                        .try_get(content)
+                        .and_then(|n| n.cast::<ColorPicker>())
+                        .is_some()
+                    {
+                        self.send_to(content, ui);
+                    }
+                }
+            }
+        } else if let Some(msg) = message.data::<ColorPickerMessage>() {
+            if message.direction() != MessageDirection::FromWidget {
+                return;
+            }
+
+            let changed = match *msg {
+                ColorPickerMessage::AddToPalette { palette, color } => {
+                    self.add_color(palette, color)
+                }
+                ColorPickerMessage::RemoveFromPalette { palette, index } => {
+                    self.remove_color(palette, index)
+                }
+                _ => false,
+            };
+
+            if changed {
+                self.save();
+                self.sync_to_pickers(ui);
+            }
+        }
+    }
+}
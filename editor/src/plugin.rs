@@ -1,5 +1,5 @@
 use crate::fyrox::gui::message::UiMessage;
-use crate::{Editor, Message};
+use crate::{recovery::RecoveredWindow, Editor, Message};
 
 /// Editor plugin allows you to extend editor functionality with custom tools. It provides a standard way of interaction
 /// between your plugin and built-in editor's functionality.
@@ -85,6 +85,10 @@ pub trait EditorPlugin {
         #[allow(unused_variables)] editor: &mut Editor,
     ) {
     }
+
+    /// This method is called when the editor writes crash recovery data. It could be used to store the windows of your
+    /// plugin, that should be re-opened when the previous session is restored after a crash.
+    fn on_save_session(&self, #[allow(unused_variables)] windows: &mut Vec<RecoveredWindow>) {}
 }
 
 #[macro_export]
@@ -0,0 +1,228 @@
+//! Plugin UI allows editor plugins to extend the user interface of the editor in a standard way. See
+//! [`PluginUi`] docs for more info.
+
+use crate::{
+    fyrox::{
+        asset::untyped::UntypedResource,
+        core::{log::Log, pool::Handle},
+        graph::BaseSceneGraph,
+        gui::{
+            dock::DockingManagerMessage,
+            menu::MenuItemMessage,
+            message::{MessageDirection, UiMessage},
+            widget::WidgetMessage,
+            window::WindowMessage,
+            UiNode, UserInterface,
+        },
+    },
+    gui::make_image_button_with_tooltip,
+    menu::create_menu_item,
+};
+
+/// A window, that was registered by an editor plugin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PluginWindow {
+    /// A handle of the window widget.
+    pub window: Handle<UiNode>,
+    /// A handle of the menu item, that opens the window. It is [`Handle::NONE`] if the window does
+    /// not have a menu item.
+    pub menu_item: Handle<UiNode>,
+}
+
+/// Plugin UI allows editor plugins to add dockable windows, main menu items and toolbar buttons to
+/// the editor, without managing their placement manually. All the menu items are added to the
+/// `Plugins` menu of the main menu, all the toolbar buttons are added to the toolbar of the scene
+/// viewer. Registered windows are managed by the docking manager of the editor, which means that
+/// they can be docked and their placement is saved together with the layout of the editor.
+///
+/// ## Window Names
+///
+/// The layout of the editor refers windows by their names, so every registered window must have a
+/// unique name. Also, the placement of a window is restored only if the window was registered in
+/// [`crate::plugin::EditorPlugin::on_start`], windows that are registered later will be opened at
+/// the center of the screen.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyroxed_base::{
+/// #     fyrox::{
+/// #         core::pool::Handle,
+/// #         gui::{button::ButtonMessage, message::UiMessage, widget::WidgetBuilder,
+/// #               window::{WindowBuilder, WindowTitle}, UiNode},
+/// #     },
+/// #     plugin::EditorPlugin,
+/// #     Editor,
+/// # };
+/// #
+/// #[derive(Default)]
+/// struct MyPlugin {
+///     button: Handle<UiNode>,
+/// }
+///
+/// impl EditorPlugin for MyPlugin {
+///     fn on_start(&mut self, editor: &mut Editor) {
+///         let ui = editor.engine.user_interfaces.first_mut();
+///         let window = WindowBuilder::new(WidgetBuilder::new().with_name("MyPluginWindow"))
+///             .with_title(WindowTitle::text("My Plugin"))
+///             .open(false)
+///             .build(&mut ui.build_ctx());
+///         editor.plugin_ui.add_window(ui, window, Some("My Plugin"));
+///         self.button = editor.plugin_ui.add_toolbar_button(ui, None, "Do Something");
+///     }
+///
+///     fn on_ui_message(&mut self, message: &mut UiMessage, _editor: &mut Editor) {
+///         if let Some(ButtonMessage::Click) = message.data() {
+///             if message.destination() == self.button {
+///                 // Do something.
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub struct PluginUi {
+    plugins_menu: Handle<UiNode>,
+    docking_manager: Handle<UiNode>,
+    toolbar: Handle<UiNode>,
+    windows: Vec<PluginWindow>,
+}
+
+impl PluginUi {
+    /// Creates new plugin UI, that uses the given root menu item, docking manager and toolbar.
+    pub fn new(
+        plugins_menu: Handle<UiNode>,
+        docking_manager: Handle<UiNode>,
+        toolbar: Handle<UiNode>,
+    ) -> Self {
+        Self {
+            plugins_menu,
+            docking_manager,
+            toolbar,
+            windows: Default::default(),
+        }
+    }
+
+    /// Returns a list of all registered windows.
+    pub fn windows(&self) -> &[PluginWindow] {
+        &self.windows
+    }
+
+    /// Adds a new item with the given text to the `Plugins` menu and returns its handle. Use
+    /// [`MenuItemMessage::Click`] message to react to clicks on the item.
+    pub fn add_menu_item(&mut self, ui: &mut UserInterface, text: &str) -> Handle<UiNode> {
+        let item = create_menu_item(text, vec![], &mut ui.build_ctx());
+        ui.send_message(MenuItemMessage::add_item(
+            self.plugins_menu,
+            MessageDirection::ToWidget,
+            item,
+        ));
+        ui.send_message(WidgetMessage::visibility(
+            self.plugins_menu,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        item
+    }
+
+    /// Removes the given item from the `Plugins` menu and destroys it.
+    pub fn remove_menu_item(&mut self, ui: &mut UserInterface, item: Handle<UiNode>) {
+        ui.send_message(MenuItemMessage::remove_item(
+            self.plugins_menu,
+            MessageDirection::ToWidget,
+            item,
+        ));
+        ui.send_message(WidgetMessage::remove(item, MessageDirection::ToWidget));
+    }
+
+    /// Adds a new button with the given icon and tooltip to the toolbar of the scene viewer and
+    /// returns its handle. Use [`crate::fyrox::gui::button::ButtonMessage::Click`] message to react
+    /// to clicks on the button.
+    pub fn add_toolbar_button(
+        &mut self,
+        ui: &mut UserInterface,
+        icon: Option<UntypedResource>,
+        tooltip: &str,
+    ) -> Handle<UiNode> {
+        let button =
+            make_image_button_with_tooltip(&mut ui.build_ctx(), 18.0, 18.0, icon, tooltip, None);
+        ui.send_message(WidgetMessage::link(
+            button,
+            MessageDirection::ToWidget,
+            self.toolbar,
+        ));
+        button
+    }
+
+    /// Removes the given button from the toolbar and destroys it.
+    pub fn remove_toolbar_button(&mut self, ui: &mut UserInterface, button: Handle<UiNode>) {
+        ui.send_message(WidgetMessage::remove(button, MessageDirection::ToWidget));
+    }
+
+    /// Registers the given window in the docking manager of the editor. If `menu_text` is set, a
+    /// new item with the text will be added to the `Plugins` menu, the item opens the window on
+    /// click. The window must have a unique name, see [`PluginUi`] docs for more info.
+    pub fn add_window(
+        &mut self,
+        ui: &mut UserInterface,
+        window: Handle<UiNode>,
+        menu_text: Option<&str>,
+    ) {
+        if ui.try_get(window).is_some_and(|w| w.name.is_empty()) {
+            Log::warn(
+                "Plugin window name is empty, its placement won't be restored correctly. \
+            Assign a unique name to the window!",
+            );
+        }
+
+        ui.send_message(DockingManagerMessage::add_floating_window(
+            self.docking_manager,
+            MessageDirection::ToWidget,
+            window,
+        ));
+
+        let menu_item = menu_text
+            .map(|text| self.add_menu_item(ui, text))
+            .unwrap_or_default();
+
+        self.windows.push(PluginWindow { window, menu_item });
+    }
+
+    /// Opens the given window and brings it to front.
+    pub fn open_window(&self, ui: &UserInterface, window: Handle<UiNode>) {
+        ui.send_message(WindowMessage::open(
+            window,
+            MessageDirection::ToWidget,
+            true,
+            true,
+        ));
+    }
+
+    /// Unregisters the given window, removes its menu item (if any) and destroys the window.
+    pub fn remove_window(&mut self, ui: &mut UserInterface, window: Handle<UiNode>) {
+        if let Some(position) = self.windows.iter().position(|w| w.window == window) {
+            let plugin_window = self.windows.remove(position);
+            if plugin_window.menu_item.is_some() {
+                self.remove_menu_item(ui, plugin_window.menu_item);
+            }
+        }
+
+        ui.send_message(DockingManagerMessage::remove_floating_window(
+            self.docking_manager,
+            MessageDirection::ToWidget,
+            window,
+        ));
+        ui.send_message(WidgetMessage::remove(window, MessageDirection::ToWidget));
+    }
+
+    pub fn handle_ui_message(&self, message: &UiMessage, ui: &UserInterface) {
+        if let Some(MenuItemMessage::Click) = message.data() {
+            if let Some(plugin_window) = self
+                .windows
+                .iter()
+                .find(|w| w.menu_item.is_some() && w.menu_item == message.destination())
+            {
+                self.open_window(ui, plugin_window.window);
+            }
+        }
+    }
+}
@@ -1,2 +1,6 @@
 pub mod collider;
+pub mod sequencer;
+pub mod shader_graph;
+pub mod skin2d;
+pub mod sprite_sheet;
 pub mod tilemap;
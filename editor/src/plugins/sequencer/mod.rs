@@ -0,0 +1,1037 @@
+//! Sequencer is an editor for cutscene timelines ([`Sequence`] resources). It shows the tracks of a
+//! sequence on a timeline, allows editing property curves with the same curve editor the animation editor
+//! uses, and previews the sequence in the scene using the bindings of a selected sequence player.
+
+mod timeline;
+
+use crate::fyrox::{
+    asset::{untyped::ResourceKind, ResourceData},
+    core::{color::Color, log::Log, pool::Handle, reflect::prelude::*},
+    engine::Engine,
+    fxhash::FxHashSet,
+    generic_animation::{container::TrackDataContainer, value::ValueBinding},
+    graph::{BaseSceneGraph, SceneGraph},
+    gui::{
+        border::BorderBuilder,
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        curve::{CurveEditorBuilder, CurveEditorMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            Inspector, InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+        },
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        utils::make_simple_tooltip,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment, BRUSH_DARK,
+    },
+    scene::{
+        animation::{
+            sequencer::{
+                sample_sequence,
+                sequence::{SequenceResource, SequenceTrack, SequenceTrackKind},
+                SequenceBinding, SequencePlayer,
+            },
+            AnimationPlayer,
+        },
+        graph::Graph,
+        node::Node,
+    },
+};
+use crate::{
+    animation::{
+        ruler::{RulerBuilder, RulerMessage},
+        thumb::{ThumbBuilder, ThumbMessage},
+        track::{type_id_to_supported_type, TrackList},
+    },
+    camera::framing::FramingOverlayMessage,
+    inspector::editors::make_property_editors_container,
+    message::MessageSender,
+    plugin::EditorPlugin,
+    scene::{property::PropertySelectorMessage, GameScene},
+    send_sync_message, Editor, Message, FIXED_TIMESTEP, MSG_SYNC_FLAG,
+};
+use std::{any::TypeId, sync::Arc};
+use timeline::{TimelineBuilder, TimelineItem, TimelineMessage, TimelineRow, TRACK_HEIGHT};
+
+fn make_button(text: &str, tooltip: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(90.0)
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+fn track_row(track: &SequenceTrack) -> TimelineRow {
+    let (items, color) = match track.kind {
+        SequenceTrackKind::CameraCuts(ref cuts) => (
+            cuts.iter()
+                .map(|cut| TimelineItem::Marker { time: cut.time })
+                .collect(),
+            Color::opaque(220, 170, 60),
+        ),
+        SequenceTrackKind::Animation(ref sections) => (
+            sections
+                .iter()
+                .map(|section| TimelineItem::Section {
+                    start: section.start,
+                    duration: section.duration,
+                })
+                .collect(),
+            Color::opaque(80, 150, 220),
+        ),
+        SequenceTrackKind::Audio(ref sections) => (
+            sections
+                .iter()
+                .map(|section| TimelineItem::Section {
+                    start: section.start,
+                    duration: section.duration,
+                })
+                .collect(),
+            Color::opaque(90, 190, 110),
+        ),
+        SequenceTrackKind::Property(ref property) => {
+            let mut keys = property
+                .curves
+                .curves_ref()
+                .iter()
+                .flat_map(|curve| curve.keys().iter().map(|key| key.location()))
+                .collect::<Vec<_>>();
+            keys.sort_by(f32::total_cmp);
+            keys.dedup();
+            (
+                keys.into_iter()
+                    .map(|time| TimelineItem::Marker { time })
+                    .collect(),
+                Color::opaque(190, 100, 200),
+            )
+        }
+    };
+
+    TimelineRow {
+        items,
+        color,
+        enabled: track.enabled,
+    }
+}
+
+fn track_title(track: &SequenceTrack) -> String {
+    let kind = match track.kind {
+        SequenceTrackKind::Property(ref property) => property.binding.to_string(),
+        ref kind => kind.as_ref().to_string(),
+    };
+    if track.binding.is_empty() {
+        format!("{} ({})", track.name, kind)
+    } else {
+        format!("{} - {} ({})", track.name, track.binding, kind)
+    }
+}
+
+/// State of the scene nodes before the preview mode. It is used to revert every change made by the
+/// preview.
+struct PreviewModeData {
+    bindings: Vec<SequenceBinding>,
+    nodes: Vec<(Handle<Node>, Node)>,
+    last_sampled_time: Option<f32>,
+}
+
+pub struct Sequencer {
+    window: Handle<UiNode>,
+    preview: Handle<UiNode>,
+    play_pause: Handle<UiNode>,
+    stop: Handle<UiNode>,
+    fit: Handle<UiNode>,
+    bind_property: Handle<UiNode>,
+    save: Handle<UiNode>,
+    status: Handle<UiNode>,
+    track_list: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    ruler: Handle<UiNode>,
+    timeline: Handle<UiNode>,
+    curve_editor: Handle<UiNode>,
+    thumb: Handle<UiNode>,
+    property_selector: Handle<UiNode>,
+    sequence: SequenceResource,
+    player: Handle<Node>,
+    selected_track: Option<usize>,
+    time: f32,
+    playing: bool,
+    preview_mode_data: Option<PreviewModeData>,
+}
+
+impl Sequencer {
+    pub fn new(sequence: SequenceResource, ctx: &mut BuildContext) -> Self {
+        let preview = CheckBoxBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(1.0))
+                .with_tooltip(make_simple_tooltip(
+                    ctx,
+                    "Previews the sequence in the scene using the bindings of the selected \
+                    sequence player. Every change will be reverted when leaving the preview mode.",
+                )),
+        )
+        .with_content(
+            TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+                .with_text("Preview")
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .build(ctx),
+        )
+        .checked(Some(false))
+        .build(ctx);
+        let play_pause = make_button("Play/Pause", "Plays or pauses the preview.", ctx);
+        let stop = make_button("Stop", "Stops the preview and rewinds the sequence.", ctx);
+        let fit = make_button(
+            "Fit Duration",
+            "Sets the duration of the sequence to the end of its content.",
+            ctx,
+        );
+        let bind_property = make_button(
+            "Bind Property",
+            "Selects a property of the bound node for the selected property track.",
+            ctx,
+        );
+        let save = make_button("Save", "Saves the sequence.", ctx);
+
+        let toolbar = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .on_row(0)
+                .with_child(preview)
+                .with_child(play_pause)
+                .with_child(stop)
+                .with_child(fit)
+                .with_child(bind_property)
+                .with_child(save),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
+        let track_list = ListViewBuilder::new(WidgetBuilder::new().on_row(1)).build(ctx);
+        let inspector = InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
+
+        let ruler = RulerBuilder::new(WidgetBuilder::new().on_row(0))
+            .with_value(0.0)
+            .build(ctx);
+        let timeline = TimelineBuilder::new(WidgetBuilder::new().on_row(1)).build(ctx);
+        let curve_editor =
+            CurveEditorBuilder::new(WidgetBuilder::new().with_background(BRUSH_DARK).on_row(2))
+                .with_show_x_values(false)
+                .build(ctx);
+        let thumb = ThumbBuilder::new(WidgetBuilder::new()).build(ctx);
+
+        let status = TextBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
+                .with_margin(Thickness::uniform(2.0)),
+        )
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .build(ctx);
+
+        let content = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(toolbar)
+                .with_child(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(1)
+                            .with_child(
+                                GridBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_column(0)
+                                        .with_child(
+                                            TextBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .on_row(0)
+                                                    .with_margin(Thickness::left(2.0)),
+                                            )
+                                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                                            .with_text("Tracks")
+                                            .build(ctx),
+                                        )
+                                        .with_child(track_list)
+                                        .with_child(
+                                            ScrollViewerBuilder::new(
+                                                WidgetBuilder::new().on_row(2),
+                                            )
+                                            .with_content(inspector)
+                                            .build(ctx),
+                                        ),
+                                )
+                                .add_row(Row::strict(TRACK_HEIGHT))
+                                .add_row(Row::stretch())
+                                .add_row(Row::stretch())
+                                .add_column(Column::stretch())
+                                .build(ctx),
+                            )
+                            .with_child(
+                                BorderBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_column(1)
+                                        .with_child(
+                                            GridBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .with_child(ruler)
+                                                    .with_child(timeline)
+                                                    .with_child(curve_editor),
+                                            )
+                                            .add_row(Row::strict(TRACK_HEIGHT))
+                                            .add_row(Row::stretch())
+                                            .add_row(Row::stretch())
+                                            .add_column(Column::stretch())
+                                            .build(ctx),
+                                        )
+                                        .with_child(thumb),
+                                )
+                                .build(ctx),
+                            ),
+                    )
+                    .add_row(Row::stretch())
+                    .add_column(Column::strict(300.0))
+                    .add_column(Column::stretch())
+                    .build(ctx),
+                )
+                .with_child(status),
+        )
+        .add_row(Row::strict(26.0))
+        .add_row(Row::stretch())
+        .add_row(Row::strict(22.0))
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("Sequencer")
+                .with_width(900.0)
+                .with_height(550.0),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Sequencer"))
+        .with_content(content)
+        .build(ctx);
+
+        ctx.sender()
+            .send(WindowMessage::open(
+                window,
+                MessageDirection::ToWidget,
+                true,
+                true,
+            ))
+            .unwrap();
+
+        Self {
+            window,
+            preview,
+            play_pause,
+            stop,
+            fit,
+            bind_property,
+            save,
+            status,
+            track_list,
+            inspector,
+            ruler,
+            timeline,
+            curve_editor,
+            thumb,
+            property_selector: Handle::NONE,
+            sequence,
+            player: Handle::NONE,
+            selected_track: None,
+            time: 0.0,
+            playing: false,
+            preview_mode_data: None,
+        }
+    }
+
+    fn set_status(&self, text: impl Into<String>, ui: &UserInterface) {
+        ui.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            text.into(),
+        ));
+    }
+
+    fn sync_to_model(&self, ui: &mut UserInterface, sender: &MessageSender) {
+        let mut state = self.sequence.state();
+        let Some(sequence) = state.data() else {
+            return;
+        };
+
+        let items = sequence
+            .tracks
+            .iter()
+            .map(|track| {
+                TextBuilder::new(WidgetBuilder::new().with_height(TRACK_HEIGHT))
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .with_text(track_title(track))
+                    .build(&mut ui.build_ctx())
+            })
+            .collect::<Vec<_>>();
+        ui.send_message(ListViewMessage::items(
+            self.track_list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+        ui.send_message(ListViewMessage::selection(
+            self.track_list,
+            MessageDirection::ToWidget,
+            self.selected_track,
+        ));
+
+        ui.send_message(TimelineMessage::sync(
+            self.timeline,
+            MessageDirection::ToWidget,
+            sequence.tracks.iter().map(track_row).collect(),
+            sequence.duration,
+        ));
+
+        let inspector_context = ui
+            .node(self.inspector)
+            .cast::<Inspector>()
+            .unwrap()
+            .context()
+            .clone();
+        if inspector_context
+            .sync(&*sequence, ui, 0, true, Default::default())
+            .is_err()
+        {
+            let context = InspectorContext::from_object(
+                &*sequence,
+                &mut ui.build_ctx(),
+                Arc::new(make_property_editors_container(sender.clone())),
+                None,
+                MSG_SYNC_FLAG,
+                0,
+                true,
+                Default::default(),
+            );
+            ui.send_message(InspectorMessage::context(
+                self.inspector,
+                MessageDirection::ToWidget,
+                context,
+            ));
+        }
+
+        drop(state);
+        self.sync_curves(ui);
+    }
+
+    fn sync_curves(&self, ui: &UserInterface) {
+        let mut state = self.sequence.state();
+        let Some(sequence) = state.data() else {
+            return;
+        };
+
+        let curves = self
+            .selected_track
+            .and_then(|index| sequence.tracks.get(index))
+            .and_then(|track| match track.kind {
+                SequenceTrackKind::Property(ref property) => {
+                    Some(property.curves.curves_ref().to_vec())
+                }
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        send_sync_message(
+            ui,
+            CurveEditorMessage::sync(self.curve_editor, MessageDirection::ToWidget, curves),
+        );
+    }
+
+    fn save(&self, ui: &UserInterface) {
+        let ResourceKind::External(path) = self.sequence.kind() else {
+            self.set_status("Embedded sequences cannot be saved!", ui);
+            return;
+        };
+
+        let mut state = self.sequence.state();
+        let Some(sequence) = state.data() else {
+            return;
+        };
+
+        let status = match sequence.save(&path) {
+            Ok(_) => format!("Sequence was saved to {}", path.display()),
+            Err(err) => format!("Unable to save sequence. Reason: {err}"),
+        };
+        drop(state);
+        self.set_status(status, ui);
+    }
+
+    fn set_time(&mut self, time: f32, ui: &UserInterface) {
+        self.time = time.max(0.0);
+        ui.send_message(ThumbMessage::position(
+            self.thumb,
+            MessageDirection::ToWidget,
+            self.time,
+        ));
+        send_sync_message(
+            ui,
+            RulerMessage::value(self.ruler, MessageDirection::ToWidget, self.time),
+        );
+    }
+
+    fn enter_preview_mode(&mut self, graph: &Graph, ui: &UserInterface) -> bool {
+        assert!(self.preview_mode_data.is_none());
+
+        let Some(player) = graph.try_get_of_type::<SequencePlayer>(self.player) else {
+            self.set_status("Select a sequence player to preview the sequence.", ui);
+            return false;
+        };
+
+        let bindings = player.bindings().to_vec();
+
+        // Save the state of every node, that could be changed by the sequence, including the targets of
+        // animations of bound animation players.
+        let mut nodes = FxHashSet::default();
+        for binding in bindings.iter() {
+            if let Some(animation_player) = graph.try_get_of_type::<AnimationPlayer>(binding.node) {
+                for animation in animation_player.animations().iter() {
+                    nodes.extend(animation.tracks().iter().map(|track| track.target()));
+                }
+            }
+            nodes.insert(binding.node);
+        }
+
+        self.preview_mode_data = Some(PreviewModeData {
+            bindings,
+            nodes: nodes
+                .into_iter()
+                .filter_map(|handle| graph.try_get(handle).map(|node| (handle, node.clone_box())))
+                .collect(),
+            last_sampled_time: None,
+        });
+
+        self.set_status("Preview mode.", ui);
+
+        true
+    }
+
+    fn leave_preview_mode(
+        &mut self,
+        game_scene: &mut GameScene,
+        engine: &mut Engine,
+        framing_overlay: Handle<UiNode>,
+    ) {
+        let Some(preview_mode_data) = self.preview_mode_data.take() else {
+            return;
+        };
+
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        for (handle, node) in preview_mode_data.nodes {
+            if let Some(node_ref) = graph.try_get_mut(handle) {
+                *node_ref = node;
+            }
+        }
+
+        game_scene.picture_in_picture = Handle::NONE;
+        self.playing = false;
+
+        let ui = engine.user_interfaces.first();
+        ui.send_message(FramingOverlayMessage::picture_in_picture(
+            framing_overlay,
+            MessageDirection::ToWidget,
+            None,
+        ));
+        send_sync_message(
+            ui,
+            CheckBoxMessage::checked(self.preview, MessageDirection::ToWidget, Some(false)),
+        );
+        self.set_status("", ui);
+    }
+
+    /// Applies the state of the sequence at the current time to the scene and shows the view of the active
+    /// camera as picture-in-picture.
+    fn sample(
+        &mut self,
+        game_scene: &mut GameScene,
+        engine: &mut Engine,
+        framing_overlay: Handle<UiNode>,
+    ) {
+        let Some(preview_mode_data) = self.preview_mode_data.as_mut() else {
+            return;
+        };
+
+        let mut state = self.sequence.state();
+        let Some(sequence) = state.data() else {
+            return;
+        };
+
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        sample_sequence(
+            sequence,
+            &preview_mode_data.bindings,
+            preview_mode_data.last_sampled_time,
+            self.time,
+            graph,
+        );
+        preview_mode_data.last_sampled_time = Some(self.time);
+
+        game_scene.picture_in_picture = sequence
+            .active_camera(self.time)
+            .and_then(|name| {
+                preview_mode_data
+                    .bindings
+                    .iter()
+                    .find(|binding| binding.name == name)
+            })
+            .map(|binding| binding.node)
+            .unwrap_or_default();
+
+        engine
+            .user_interfaces
+            .first()
+            .send_message(FramingOverlayMessage::picture_in_picture(
+                framing_overlay,
+                MessageDirection::ToWidget,
+                game_scene
+                    .picture_in_picture
+                    .is_some()
+                    .then_some(GameScene::PICTURE_IN_PICTURE_VIEWPORT),
+            ));
+    }
+
+    fn modify_sequence(
+        &self,
+        ui: &mut UserInterface,
+        sender: &MessageSender,
+        func: impl FnOnce(&mut SequenceTrack),
+    ) {
+        let mut state = self.sequence.state();
+        if let Some(track) = state
+            .data()
+            .and_then(|sequence| sequence.tracks.get_mut(self.selected_track?))
+        {
+            func(track);
+        }
+        drop(state);
+        self.sync_to_model(ui, sender);
+    }
+
+    fn open_property_selector(&mut self, graph: &Graph, ui: &mut UserInterface) {
+        let node = {
+            let mut state = self.sequence.state();
+            let track = state
+                .data()
+                .and_then(|sequence| sequence.tracks.get(self.selected_track?).cloned());
+            match track {
+                Some(track) if matches!(track.kind, SequenceTrackKind::Property(_)) => graph
+                    .try_get_of_type::<SequencePlayer>(self.player)
+                    .map(|player| player.binding(&track.binding))
+                    .unwrap_or_default(),
+                _ => {
+                    self.set_status("Select a property track first.", ui);
+                    return;
+                }
+            }
+        };
+
+        if graph.try_get(node).is_none() {
+            self.set_status(
+                "The track is not bound to a node of the selected sequence player.",
+                ui,
+            );
+            return;
+        }
+
+        self.property_selector = TrackList::open_property_selector(graph, node, ui);
+    }
+
+    fn bind_property(
+        &self,
+        path: &str,
+        graph: &Graph,
+        ui: &mut UserInterface,
+        sender: &MessageSender,
+    ) {
+        let node = {
+            let mut state = self.sequence.state();
+            let Some(track) = state
+                .data()
+                .and_then(|sequence| sequence.tracks.get(self.selected_track?).cloned())
+            else {
+                return;
+            };
+            graph
+                .try_get_of_type::<SequencePlayer>(self.player)
+                .map(|player| player.binding(&track.binding))
+                .unwrap_or_default()
+        };
+
+        let Some(node) = graph.try_get(node) else {
+            return;
+        };
+
+        let mut types = None;
+        node.resolve_path(path, &mut |result| match result {
+            Ok(property) => {
+                let mut property_type = TypeId::of::<u32>();
+                property.as_any(&mut |any| property_type = any.type_id());
+                types = type_id_to_supported_type(property_type);
+            }
+            Err(e) => {
+                Log::err(format!("Invalid property path {path:?}. Error: {e:?}!"));
+            }
+        });
+
+        if let Some((track_value_kind, value_type)) = types {
+            self.modify_sequence(ui, sender, |track| {
+                if let SequenceTrackKind::Property(ref mut property) = track.kind {
+                    property.binding = ValueBinding::Property {
+                        name: path.to_string(),
+                        value_type,
+                    };
+                    if property.curves.value_kind() != track_value_kind {
+                        property.curves = TrackDataContainer::new(track_value_kind);
+                    }
+                }
+            });
+        }
+    }
+
+    fn update(&mut self, editor: &mut Editor) {
+        if !self.playing || self.preview_mode_data.is_none() {
+            return;
+        }
+
+        let duration = self
+            .sequence
+            .state()
+            .data()
+            .map(|sequence| sequence.duration)
+            .unwrap_or_default();
+        let mut time = self.time + FIXED_TIMESTEP;
+        if time >= duration {
+            time = duration;
+            self.playing = false;
+        }
+        self.set_time(time, editor.engine.user_interfaces.first());
+
+        with_game_scene(editor, |game_scene, engine, framing_overlay| {
+            self.sample(game_scene, engine, framing_overlay)
+        });
+    }
+
+    fn destroy(mut self, editor: &mut Editor) {
+        with_game_scene(editor, |game_scene, engine, framing_overlay| {
+            self.leave_preview_mode(game_scene, engine, framing_overlay)
+        });
+        editor
+            .engine
+            .user_interfaces
+            .first()
+            .send_message(WidgetMessage::remove(
+                self.window,
+                MessageDirection::ToWidget,
+            ));
+    }
+
+    fn handle_ui_message(mut self, message: &UiMessage, editor: &mut Editor) -> Option<Self> {
+        let sender = editor.message_sender.clone();
+
+        if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.destroy(editor);
+                return None;
+            }
+        } else if let Some(WindowMessage::Open { .. }) = message.data() {
+            if message.destination() == self.window {
+                self.sync_to_model(editor.engine.user_interfaces.first_mut(), &sender);
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.preview
+                && message.direction() == MessageDirection::FromWidget
+            {
+                with_game_scene(editor, |game_scene, engine, framing_overlay| {
+                    if *value && self.preview_mode_data.is_none() {
+                        let graph = &engine.scenes[game_scene.scene].graph;
+                        if self.enter_preview_mode(graph, engine.user_interfaces.first()) {
+                            self.sample(game_scene, engine, framing_overlay);
+                        } else {
+                            send_sync_message(
+                                engine.user_interfaces.first(),
+                                CheckBoxMessage::checked(
+                                    self.preview,
+                                    MessageDirection::ToWidget,
+                                    Some(false),
+                                ),
+                            );
+                        }
+                    } else if !*value {
+                        self.leave_preview_mode(game_scene, engine, framing_overlay);
+                    }
+                });
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            let ui = editor.engine.user_interfaces.first_mut();
+            if message.destination() == self.play_pause {
+                if self.preview_mode_data.is_some() {
+                    self.playing = !self.playing;
+                } else {
+                    self.set_status("Enable the preview mode first.", ui);
+                }
+            } else if message.destination() == self.stop {
+                self.playing = false;
+                self.set_time(0.0, ui);
+                if let Some(preview_mode_data) = self.preview_mode_data.as_mut() {
+                    preview_mode_data.last_sampled_time = None;
+                }
+                with_game_scene(editor, |game_scene, engine, framing_overlay| {
+                    self.sample(game_scene, engine, framing_overlay)
+                });
+            } else if message.destination() == self.fit {
+                if let Some(sequence) = self.sequence.state().data() {
+                    sequence.fit_duration_to_content();
+                }
+                self.sync_to_model(ui, &sender);
+            } else if message.destination() == self.bind_property {
+                with_game_scene(editor, |game_scene, engine, _| {
+                    self.open_property_selector(
+                        &engine.scenes[game_scene.scene].graph,
+                        engine.user_interfaces.first_mut(),
+                    )
+                });
+            } else if message.destination() == self.save {
+                self.save(ui);
+            }
+        } else if let Some(ListViewMessage::SelectionChanged(selection)) = message.data() {
+            if message.destination() == self.track_list
+                && message.direction() == MessageDirection::FromWidget
+                && self.selected_track != *selection
+            {
+                self.selected_track = *selection;
+                let ui = editor.engine.user_interfaces.first();
+                ui.send_message(TimelineMessage::select_row(
+                    self.timeline,
+                    MessageDirection::ToWidget,
+                    *selection,
+                ));
+                self.sync_curves(ui);
+            }
+        } else if let Some(msg) = message.data::<CurveEditorMessage>() {
+            if message.destination() == self.curve_editor
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let ui = editor.engine.user_interfaces.first_mut();
+                match msg {
+                    CurveEditorMessage::Sync(curves) => {
+                        self.modify_sequence(ui, &sender, |track| {
+                            if let SequenceTrackKind::Property(ref mut property) = track.kind {
+                                for curve in property.curves.curves_mut() {
+                                    if let Some(new_curve) =
+                                        curves.iter().find(|c| c.id == curve.id)
+                                    {
+                                        curve.clone_from(new_curve);
+                                    }
+                                }
+                            }
+                        });
+                        if let Some(preview_mode_data) = self.preview_mode_data.as_mut() {
+                            preview_mode_data.last_sampled_time = None;
+                        }
+                        with_game_scene(editor, |game_scene, engine, framing_overlay| {
+                            self.sample(game_scene, engine, framing_overlay)
+                        });
+                    }
+                    CurveEditorMessage::ViewPosition(position) => {
+                        ui.send_message(RulerMessage::view_position(
+                            self.ruler,
+                            MessageDirection::ToWidget,
+                            position.x,
+                        ));
+                        ui.send_message(ThumbMessage::view_position(
+                            self.thumb,
+                            MessageDirection::ToWidget,
+                            position.x,
+                        ));
+                        ui.send_message(TimelineMessage::view_position(
+                            self.timeline,
+                            MessageDirection::ToWidget,
+                            position.x,
+                        ));
+                    }
+                    CurveEditorMessage::Zoom(zoom) => {
+                        ui.send_message(RulerMessage::zoom(
+                            self.ruler,
+                            MessageDirection::ToWidget,
+                            zoom.x,
+                        ));
+                        ui.send_message(ThumbMessage::zoom(
+                            self.thumb,
+                            MessageDirection::ToWidget,
+                            zoom.x,
+                        ));
+                        ui.send_message(TimelineMessage::zoom(
+                            self.timeline,
+                            MessageDirection::ToWidget,
+                            zoom.x,
+                        ));
+                    }
+                    _ => (),
+                }
+            }
+        } else if let Some(RulerMessage::Value(value)) = message.data() {
+            if message.destination() == self.ruler
+                && message.direction() == MessageDirection::FromWidget
+                && *value != self.time
+            {
+                // Scrubbing.
+                self.set_time(*value, editor.engine.user_interfaces.first());
+                with_game_scene(editor, |game_scene, engine, framing_overlay| {
+                    self.sample(game_scene, engine, framing_overlay)
+                });
+            }
+        } else if let Some(PropertySelectorMessage::Selection(selected_properties)) = message.data()
+        {
+            if message.destination() == self.property_selector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(property) = selected_properties.first() {
+                    with_game_scene(editor, |game_scene, engine, _| {
+                        self.bind_property(
+                            &property.path,
+                            &engine.scenes[game_scene.scene].graph,
+                            engine.user_interfaces.first_mut(),
+                            &sender,
+                        )
+                    });
+                }
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
+            if message.destination() == self.inspector {
+                if let Some(sequence) = self.sequence.state().data() {
+                    PropertyAction::from_field_kind(&property_changed.value).apply(
+                        &property_changed.path(),
+                        sequence as &mut dyn Reflect,
+                        &mut |result| {
+                            Log::verify(result);
+                        },
+                    );
+                }
+                self.sync_to_model(editor.engine.user_interfaces.first_mut(), &sender);
+            }
+        }
+
+        Some(self)
+    }
+}
+
+fn with_game_scene(
+    editor: &mut Editor,
+    func: impl FnOnce(&mut GameScene, &mut Engine, Handle<UiNode>),
+) {
+    let framing_overlay = editor.scene_viewer.framing_overlay();
+    let Some(entry) = editor.scenes.current_scene_entry_mut() else {
+        return;
+    };
+    if let Some(game_scene) = entry.controller.downcast_mut::<GameScene>() {
+        func(game_scene, &mut editor.engine, framing_overlay);
+    }
+}
+
+#[derive(Default)]
+pub struct SequencerPlugin {
+    sequencer: Option<Sequencer>,
+}
+
+impl SequencerPlugin {
+    fn selected_sequence_player(editor: &Editor) -> Option<(Handle<Node>, SequenceResource)> {
+        let entry = editor.scenes.current_scene_entry_ref()?;
+        let game_scene = entry.controller.downcast_ref::<GameScene>()?;
+        let graph = &editor.engine.scenes[game_scene.scene].graph;
+        entry
+            .selection
+            .as_graph()?
+            .nodes()
+            .iter()
+            .find_map(|handle| {
+                graph
+                    .try_get_of_type::<SequencePlayer>(*handle)
+                    .and_then(|player| player.sequence())
+                    .map(|sequence| (*handle, sequence))
+            })
+    }
+}
+
+impl EditorPlugin for SequencerPlugin {
+    fn on_ui_message(&mut self, message: &mut UiMessage, editor: &mut Editor) {
+        if let Some(sequencer) = self.sequencer.take() {
+            self.sequencer = sequencer.handle_ui_message(message, editor);
+        }
+    }
+
+    fn on_update(&mut self, editor: &mut Editor) {
+        if let Some(sequencer) = self.sequencer.as_mut() {
+            sequencer.update(editor);
+        }
+    }
+
+    fn is_in_preview_mode(&self, _editor: &Editor) -> bool {
+        self.sequencer
+            .as_ref()
+            .is_some_and(|sequencer| sequencer.preview_mode_data.is_some())
+    }
+
+    fn on_message(&mut self, message: &Message, editor: &mut Editor) {
+        match message {
+            Message::OpenSequencer(sequence) => {
+                if let Some(previous) = self.sequencer.take() {
+                    previous.destroy(editor);
+                }
+                let mut sequencer = Sequencer::new(
+                    sequence.clone(),
+                    &mut editor.engine.user_interfaces.first_mut().build_ctx(),
+                );
+                if let Some((player, player_sequence)) = Self::selected_sequence_player(editor) {
+                    if &player_sequence == sequence {
+                        sequencer.player = player;
+                    }
+                }
+                self.sequencer = Some(sequencer);
+            }
+            Message::SelectionChanged { .. } => {
+                let Some((player, sequence)) = Self::selected_sequence_player(editor) else {
+                    return;
+                };
+                match self.sequencer.as_mut() {
+                    // Bind the sequencer to the selected player.
+                    Some(sequencer) if sequencer.sequence == sequence => {
+                        sequencer.player = player;
+                    }
+                    _ => {
+                        if let Some(previous) = self.sequencer.take() {
+                            previous.destroy(editor);
+                        }
+                        let mut sequencer = Sequencer::new(
+                            sequence,
+                            &mut editor.engine.user_interfaces.first_mut().build_ctx(),
+                        );
+                        sequencer.player = player;
+                        self.sequencer = Some(sequencer);
+                    }
+                }
+            }
+            // Leave preview mode before execution of any scene command.
+            Message::DoCommand(_)
+            | Message::UndoCurrentSceneCommand
+            | Message::RedoCurrentSceneCommand
+            | Message::SaveScene { .. } => {
+                if let Some(sequencer) = self.sequencer.as_mut() {
+                    with_game_scene(editor, |game_scene, engine, framing_overlay| {
+                        sequencer.leave_preview_mode(game_scene, engine, framing_overlay)
+                    });
+                }
+            }
+            _ => (),
+        }
+    }
+}
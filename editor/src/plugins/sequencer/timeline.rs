@@ -0,0 +1,207 @@
+//! Timeline shows the content of sequence tracks (sections and markers) in rows, that are aligned with the
+//! time ruler and the curve editor of the sequencer.
+
+use crate::fyrox::{
+    core::{
+        algebra::{Point2, Vector2},
+        color::Color,
+        math::Rect,
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        uuid_provider,
+        visitor::prelude::*,
+    },
+    gui::{
+        brush::Brush,
+        curve::CurveTransformCell,
+        define_constructor, define_widget_deref,
+        draw::{CommandTexture, Draw, DrawingContext},
+        message::{MessageDirection, UiMessage},
+        widget::{Widget, WidgetBuilder},
+        BuildContext, Control, UiNode, UserInterface,
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// Height of a single track row in pixels.
+pub const TRACK_HEIGHT: f32 = 22.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimelineItem {
+    Section { start: f32, duration: f32 },
+    Marker { time: f32 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineRow {
+    pub items: Vec<TimelineItem>,
+    pub color: Color,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimelineMessage {
+    Zoom(f32),
+    ViewPosition(f32),
+    Sync {
+        rows: Vec<TimelineRow>,
+        duration: f32,
+    },
+    SelectRow(Option<usize>),
+}
+
+impl TimelineMessage {
+    define_constructor!(TimelineMessage:Zoom => fn zoom(f32), layout: false);
+    define_constructor!(TimelineMessage:ViewPosition => fn view_position(f32), layout: false);
+    define_constructor!(TimelineMessage:Sync => fn sync(rows: Vec<TimelineRow>, duration: f32), layout: false);
+    define_constructor!(TimelineMessage:SelectRow => fn select_row(Option<usize>), layout: false);
+}
+
+#[derive(Clone, Visit, Reflect, Debug, ComponentProvider)]
+pub struct Timeline {
+    widget: Widget,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    transform: CurveTransformCell,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    rows: Vec<TimelineRow>,
+    duration: f32,
+    selected_row: Option<usize>,
+}
+
+define_widget_deref!(Timeline);
+
+uuid_provider!(Timeline = "4b7e2d19-c3a8-4f56-9e01-d8a6b5f3c247");
+
+impl Timeline {
+    fn time_to_local(&self, time: f32) -> f32 {
+        self.transform
+            .curve_to_local()
+            .transform_point(&Point2::new(time, 0.0))
+            .x
+    }
+
+    fn commit(&self, ctx: &mut DrawingContext, color: Color) {
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(color),
+            CommandTexture::None,
+            None,
+        );
+    }
+}
+
+impl Control for Timeline {
+    fn draw(&self, ctx: &mut DrawingContext) {
+        self.transform.set_bounds(self.screen_bounds());
+        self.transform.update_transform();
+        let bounds = self.bounding_rect();
+
+        ctx.push_rect_filled(&bounds, None);
+        self.commit(ctx, Color::opaque(35, 35, 35));
+
+        if let Some(selected_row) = self.selected_row {
+            ctx.push_rect_filled(
+                &Rect::new(
+                    0.0,
+                    selected_row as f32 * TRACK_HEIGHT,
+                    bounds.w(),
+                    TRACK_HEIGHT,
+                ),
+                None,
+            );
+            self.commit(ctx, Color::opaque(55, 55, 65));
+        }
+
+        for (i, row) in self.rows.iter().enumerate() {
+            let y = i as f32 * TRACK_HEIGHT;
+
+            for item in row.items.iter() {
+                let rect = match item {
+                    TimelineItem::Section { start, duration } => {
+                        let begin = self.time_to_local(*start);
+                        let end = self.time_to_local(start + duration);
+                        Rect::new(begin, y + 2.0, (end - begin).max(1.0), TRACK_HEIGHT - 4.0)
+                    }
+                    TimelineItem::Marker { time } => Rect::new(
+                        self.time_to_local(*time) - 2.0,
+                        y + 2.0,
+                        4.0,
+                        TRACK_HEIGHT - 4.0,
+                    ),
+                };
+                ctx.push_rect_filled(&rect, None);
+            }
+            let color = if row.enabled {
+                row.color
+            } else {
+                Color::opaque(90, 90, 90)
+            };
+            self.commit(ctx, color);
+
+            ctx.push_line(
+                Vector2::new(0.0, y + TRACK_HEIGHT),
+                Vector2::new(bounds.w(), y + TRACK_HEIGHT),
+                1.0,
+            );
+            self.commit(ctx, Color::opaque(50, 50, 50));
+        }
+
+        // Darken everything after the end of the sequence.
+        let end = self.time_to_local(self.duration).max(0.0);
+        if end < bounds.w() {
+            ctx.push_rect_filled(&Rect::new(end, 0.0, bounds.w() - end, bounds.h()), None);
+            self.commit(ctx, Color::from_rgba(0, 0, 0, 100));
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<TimelineMessage>() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    TimelineMessage::Zoom(zoom) => {
+                        self.transform.set_scale(Vector2::new(*zoom, 1.0));
+                    }
+                    TimelineMessage::ViewPosition(position) => {
+                        self.transform.set_position(Vector2::new(*position, 0.0));
+                    }
+                    TimelineMessage::Sync { rows, duration } => {
+                        self.rows.clone_from(rows);
+                        self.duration = *duration;
+                    }
+                    TimelineMessage::SelectRow(row) => {
+                        self.selected_row = *row;
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub struct TimelineBuilder {
+    widget_builder: WidgetBuilder,
+}
+
+impl TimelineBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self { widget_builder }
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let timeline = Timeline {
+            widget: self.widget_builder.with_hit_test_visibility(false).build(),
+            transform: CurveTransformCell::default(),
+            rows: Default::default(),
+            duration: 0.0,
+            selected_row: None,
+        };
+
+        ctx.add_node(UiNode::new(timeline))
+    }
+}
@@ -0,0 +1,172 @@
+use crate::fyrox::{
+    core::pool::Handle,
+    gui::{
+        menu::{ContextMenuBuilder, MenuItemMessage},
+        message::UiMessage,
+        popup::{Placement, PopupBuilder, PopupMessage},
+        stack_panel::StackPanelBuilder,
+        widget::WidgetBuilder,
+        BuildContext, RcUiNodeHandle, UiNode,
+    },
+    material::shader::graph::ShaderGraphNodeKind,
+};
+use crate::menu::create_menu_item;
+use std::str::FromStr;
+
+const GROUPS: [(&str, &[&str]); 3] = [
+    (
+        "Input",
+        &[
+            "Float",
+            "Vector2",
+            "Vector3",
+            "Vector4",
+            "Color",
+            "Texture",
+            "TexCoord",
+            "SecondTexCoord",
+            "WorldPosition",
+            "WorldNormal",
+            "ViewDirection",
+        ],
+    ),
+    (
+        "Math",
+        &[
+            "Add",
+            "Subtract",
+            "Multiply",
+            "Divide",
+            "Lerp",
+            "Power",
+            "Dot",
+            "Normalize",
+            "OneMinus",
+            "Saturate",
+            "Abs",
+            "Sine",
+            "Cosine",
+            "Channel",
+            "Combine",
+        ],
+    ),
+    ("Lighting", &["Fresnel", "NormalMap"]),
+];
+
+pub struct CanvasContextMenu {
+    pub menu: RcUiNodeHandle,
+    items: Vec<(Handle<UiNode>, &'static str)>,
+}
+
+impl CanvasContextMenu {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let mut items = Vec::new();
+        let mut groups = Vec::new();
+        for (group, kinds) in GROUPS {
+            let group_items = kinds
+                .iter()
+                .map(|kind| {
+                    let item = create_menu_item(kind, vec![], ctx);
+                    items.push((item, *kind));
+                    item
+                })
+                .collect::<Vec<_>>();
+            groups.push(create_menu_item(group, group_items, ctx));
+        }
+
+        let menu = ContextMenuBuilder::new(
+            PopupBuilder::new(WidgetBuilder::new().with_visibility(false)).with_content(
+                StackPanelBuilder::new(WidgetBuilder::new().with_children(groups)).build(ctx),
+            ),
+        )
+        .build(ctx);
+        let menu = RcUiNodeHandle::new(menu, ctx.sender());
+
+        Self { menu, items }
+    }
+
+    /// Returns a kind of the node, that should be created.
+    pub fn handle_ui_message(&self, message: &UiMessage) -> Option<ShaderGraphNodeKind> {
+        if let Some(MenuItemMessage::Click) = message.data() {
+            self.items
+                .iter()
+                .find(|(item, _)| *item == message.destination())
+                .and_then(|(_, kind)| ShaderGraphNodeKind::from_str(kind).ok())
+        } else {
+            None
+        }
+    }
+}
+
+pub struct NodeContextMenu {
+    pub menu: RcUiNodeHandle,
+    remove: Handle<UiNode>,
+}
+
+impl NodeContextMenu {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let remove;
+        let menu = ContextMenuBuilder::new(
+            PopupBuilder::new(WidgetBuilder::new().with_visibility(false)).with_content(
+                StackPanelBuilder::new(WidgetBuilder::new().with_child({
+                    remove = create_menu_item("Remove", vec![], ctx);
+                    remove
+                }))
+                .build(ctx),
+            ),
+        )
+        .build(ctx);
+        let menu = RcUiNodeHandle::new(menu, ctx.sender());
+
+        Self { menu, remove }
+    }
+
+    /// Returns `true` if selected nodes should be removed.
+    pub fn handle_ui_message(&self, message: &UiMessage) -> bool {
+        matches!(message.data(), Some(MenuItemMessage::Click))
+            && message.destination() == self.remove
+    }
+}
+
+pub struct ConnectionContextMenu {
+    pub menu: RcUiNodeHandle,
+    remove: Handle<UiNode>,
+    placement_target: Handle<UiNode>,
+}
+
+impl ConnectionContextMenu {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let remove;
+        let menu = ContextMenuBuilder::new(
+            PopupBuilder::new(WidgetBuilder::new().with_visibility(false)).with_content(
+                StackPanelBuilder::new(WidgetBuilder::new().with_child({
+                    remove = create_menu_item("Remove Connection", vec![], ctx);
+                    remove
+                }))
+                .build(ctx),
+            ),
+        )
+        .build(ctx);
+        let menu = RcUiNodeHandle::new(menu, ctx.sender());
+
+        Self {
+            menu,
+            remove,
+            placement_target: Default::default(),
+        }
+    }
+
+    /// Returns a handle of the connection, that should be removed.
+    pub fn handle_ui_message(&mut self, message: &UiMessage) -> Option<Handle<UiNode>> {
+        if let Some(MenuItemMessage::Click) = message.data() {
+            if message.destination() == self.remove {
+                return Some(self.placement_target);
+            }
+        } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
+            if message.destination() == self.menu.handle() {
+                self.placement_target = *target;
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,786 @@
+//! Shader graph editor allows to author surface shaders by connecting texture, math and lighting nodes.
+//! The graph is compiled into the engine's shader format on every change and the result is previewed
+//! on a sphere or a custom model. Saving the graph also writes a `.shader` file next to it, so the shader
+//! could be used by any material and promoted parameters become material properties.
+
+mod menu;
+
+use crate::fyrox::{
+    asset::{manager::ResourceManager, untyped::ResourceKind, ResourceData},
+    core::{
+        algebra::Matrix4,
+        futures::executor::block_on,
+        log::Log,
+        parking_lot::Mutex,
+        pool::{ErasedHandle, Handle},
+        reflect::prelude::*,
+    },
+    engine::Engine,
+    graph::{BaseSceneGraph, SceneGraph},
+    gui::{
+        border::BorderBuilder,
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            Inspector, InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        utils::make_simple_tooltip,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+    material::{
+        shader::{
+            graph::{ShaderGraph, ShaderGraphNode, ShaderGraphResource},
+            Shader, ShaderResource,
+        },
+        Material, MaterialResource,
+    },
+    resource::model::{Model, ModelResourceExtension},
+    scene::{
+        base::BaseBuilder,
+        mesh::{
+            surface::{SurfaceBuilder, SurfaceData, SurfaceResource},
+            Mesh, MeshBuilder,
+        },
+        node::Node,
+    },
+};
+use crate::{
+    absm::{
+        canvas::{AbsmCanvasBuilder, AbsmCanvasMessage},
+        connection::{Connection, ConnectionBuilder},
+        node::{AbsmNode, AbsmNodeBuilder},
+        socket::{Socket, SocketBuilder, SocketDirection},
+        NORMAL_ROOT_COLOR, SELECTED_ROOT_COLOR,
+    },
+    inspector::editors::{
+        make_property_editors_container,
+        resource::{ResourceFieldBuilder, ResourceFieldMessage},
+    },
+    message::MessageSender,
+    plugin::EditorPlugin,
+    preview::PreviewPanel,
+    send_sync_message, Editor, Message, MSG_SYNC_FLAG,
+};
+use menu::{CanvasContextMenu, ConnectionContextMenu, NodeContextMenu};
+use std::{path::Path, sync::Arc};
+
+fn make_button(text: &str, tooltip: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(90.0)
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+fn make_socket(
+    direction: SocketDirection,
+    index: usize,
+    label: &str,
+    parent_node: Handle<ShaderGraphNode>,
+    ctx: &mut BuildContext,
+) -> Handle<UiNode> {
+    let label = if label.is_empty() {
+        Handle::NONE
+    } else {
+        TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left(2.0)))
+            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .with_text(label)
+            .build(ctx)
+    };
+    SocketBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+        .with_direction(direction)
+        .with_parent_node(ErasedHandle::from(parent_node))
+        .with_index(index)
+        .with_show_index(false)
+        .with_editor(label)
+        .build(ctx)
+}
+
+fn fetch_model_handle(node: Handle<UiNode>, ui: &UserInterface) -> Handle<ShaderGraphNode> {
+    ui.try_get(node)
+        .and_then(|n| n.query_component::<AbsmNode<ShaderGraphNode>>())
+        .map(|n| n.model_handle)
+        .unwrap_or_default()
+}
+
+fn fetch_socket(
+    socket: Handle<UiNode>,
+    ui: &UserInterface,
+) -> Option<(Handle<ShaderGraphNode>, usize)> {
+    ui.try_get(socket)
+        .and_then(|n| n.query_component::<Socket>())
+        .map(|socket| (socket.parent_node.into(), socket.index))
+}
+
+fn make_sphere(engine: &mut Engine, preview: &PreviewPanel) -> Handle<Node> {
+    MeshBuilder::new(BaseBuilder::new())
+        .with_surfaces(vec![SurfaceBuilder::new(SurfaceResource::new_ok(
+            ResourceKind::Embedded,
+            SurfaceData::make_sphere(30, 30, 1.0, &Matrix4::identity()),
+        ))
+        .build()])
+        .build(&mut engine.scenes[preview.scene()].graph)
+}
+
+pub struct ShaderGraphEditor {
+    window: Handle<UiNode>,
+    canvas: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    save: Handle<UiNode>,
+    sphere: Handle<UiNode>,
+    model: Handle<UiNode>,
+    status: Handle<UiNode>,
+    canvas_context_menu: CanvasContextMenu,
+    node_context_menu: NodeContextMenu,
+    connection_context_menu: ConnectionContextMenu,
+    preview: PreviewPanel,
+    graph: ShaderGraphResource,
+    selection: Vec<Handle<ShaderGraphNode>>,
+    material: MaterialResource,
+}
+
+impl ShaderGraphEditor {
+    pub fn new(graph: ShaderGraphResource, engine: &mut Engine, sender: MessageSender) -> Self {
+        let mut preview = PreviewPanel::new(engine, 300, 300);
+        let sphere = make_sphere(engine, &preview);
+        preview.set_model(sphere, engine);
+
+        let resource_manager = engine.resource_manager.clone();
+        let ctx = &mut engine.user_interfaces.first_mut().build_ctx();
+
+        let canvas_context_menu = CanvasContextMenu::new(ctx);
+        let node_context_menu = NodeContextMenu::new(ctx);
+        let connection_context_menu = ConnectionContextMenu::new(ctx);
+
+        let save = make_button(
+            "Save",
+            "Saves the graph and writes the compiled shader next to it.",
+            ctx,
+        );
+        let sphere = make_button("Sphere", "Previews the shader on a sphere.", ctx);
+        let model = ResourceFieldBuilder::<Model>::new(
+            WidgetBuilder::new()
+                .with_width(200.0)
+                .with_margin(Thickness::uniform(1.0))
+                .with_tooltip(make_simple_tooltip(
+                    ctx,
+                    "Previews the shader on a custom model.",
+                )),
+            Arc::new(Mutex::new(
+                |resource_manager: &ResourceManager, path: &Path| {
+                    resource_manager.try_request::<Model>(path).map(block_on)
+                },
+            )),
+            sender,
+        )
+        .build(ctx, resource_manager);
+
+        let canvas = AbsmCanvasBuilder::new(
+            WidgetBuilder::new().with_context_menu(canvas_context_menu.menu.clone()),
+        )
+        .build(ctx);
+
+        let inspector = InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
+
+        let status = TextBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
+                .with_margin(Thickness::uniform(2.0)),
+        )
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .build(ctx);
+
+        let preview_panel;
+        let content = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(
+                    StackPanelBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(0)
+                            .with_child(save)
+                            .with_child(sphere)
+                            .with_child(model),
+                    )
+                    .with_orientation(Orientation::Horizontal)
+                    .build(ctx),
+                )
+                .with_child(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(1)
+                            .with_child(
+                                BorderBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_column(0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_child(canvas),
+                                )
+                                .build(ctx),
+                            )
+                            .with_child(
+                                GridBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_column(1)
+                                        .with_child({
+                                            preview_panel =
+                                                BorderBuilder::new(WidgetBuilder::new().on_row(0))
+                                                    .build(ctx);
+                                            preview_panel
+                                        })
+                                        .with_child(
+                                            ScrollViewerBuilder::new(
+                                                WidgetBuilder::new().on_row(1),
+                                            )
+                                            .with_content(inspector)
+                                            .build(ctx),
+                                        ),
+                                )
+                                .add_row(Row::strict(300.0))
+                                .add_row(Row::stretch())
+                                .add_column(Column::stretch())
+                                .build(ctx),
+                            ),
+                    )
+                    .add_row(Row::stretch())
+                    .add_column(Column::stretch())
+                    .add_column(Column::strict(320.0))
+                    .build(ctx),
+                )
+                .with_child(status),
+        )
+        .add_row(Row::strict(26.0))
+        .add_row(Row::stretch())
+        .add_row(Row::strict(22.0))
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        ctx.link(preview.root, preview_panel);
+
+        let title = match graph.kind() {
+            ResourceKind::External(path) => format!("Shader Graph Editor - {}", path.display()),
+            ResourceKind::Embedded => "Shader Graph Editor".to_string(),
+        };
+
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("ShaderGraphEditor")
+                .with_width(1100.0)
+                .with_height(650.0),
+        )
+        .open(false)
+        .with_title(WindowTitle::text(title))
+        .with_content(content)
+        .build(ctx);
+
+        ctx.sender()
+            .send(WindowMessage::open(
+                window,
+                MessageDirection::ToWidget,
+                true,
+                true,
+            ))
+            .unwrap();
+
+        Self {
+            window,
+            canvas,
+            inspector,
+            save,
+            sphere,
+            model,
+            status,
+            canvas_context_menu,
+            node_context_menu,
+            connection_context_menu,
+            preview,
+            graph,
+            selection: Default::default(),
+            material: MaterialResource::new_ok(ResourceKind::Embedded, Material::standard()),
+        }
+    }
+
+    fn set_status(&self, text: impl Into<String>, ui: &UserInterface) {
+        ui.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            text.into(),
+        ));
+    }
+
+    fn sync_canvas(&mut self, ui: &mut UserInterface) {
+        let mut state = self.graph.state();
+        let Some(graph) = state.data() else {
+            return;
+        };
+
+        // Shader graphs are small, so it is much easier to re-create every view than to sync them.
+        for &child in ui.node(self.canvas).children() {
+            send_sync_message(ui, WidgetMessage::remove(child, MessageDirection::ToWidget));
+        }
+
+        let output = graph.output();
+        let mut views = Vec::new();
+        for (handle, node) in graph.nodes().pair_iter() {
+            let ctx = &mut ui.build_ctx();
+            let input_sockets = node
+                .kind
+                .inputs()
+                .iter()
+                .enumerate()
+                .map(|(i, input)| make_socket(SocketDirection::Input, i, input.name, handle, ctx))
+                .collect::<Vec<_>>();
+            let output_socket = if node.kind.has_output() {
+                make_socket(SocketDirection::Output, 0, "", handle, ctx)
+            } else {
+                Handle::NONE
+            };
+
+            let mut builder = AbsmNodeBuilder::new(
+                WidgetBuilder::new()
+                    .with_desired_position(node.position)
+                    .with_context_menu(self.node_context_menu.menu.clone()),
+            )
+            .with_name(node.kind.parameter().unwrap_or_default().to_string())
+            .with_title(node.kind.as_ref().to_string())
+            .with_input_sockets(input_sockets)
+            .with_output_socket(output_socket)
+            .with_model_handle(handle);
+            if handle == output {
+                builder = builder
+                    .with_normal_color(NORMAL_ROOT_COLOR)
+                    .with_selected_color(SELECTED_ROOT_COLOR);
+            }
+            let view = builder.build(ctx);
+
+            send_sync_message(
+                ui,
+                WidgetMessage::link(view, MessageDirection::ToWidget, self.canvas),
+            );
+
+            views.push((handle, view));
+        }
+
+        // Force update layout to be able to fetch positions of sockets for connections.
+        ui.update_layout(ui.screen_size());
+
+        let find_view = |handle: Handle<ShaderGraphNode>, ui: &UserInterface| {
+            views
+                .iter()
+                .find(|(h, _)| *h == handle)
+                .and_then(|(_, view)| {
+                    ui.node(*view)
+                        .query_component::<AbsmNode<ShaderGraphNode>>()
+                })
+                .map(|view| (view.handle(), view.base.clone()))
+        };
+
+        for (handle, node) in graph.nodes().pair_iter() {
+            let Some((dest_view, dest_base)) = find_view(handle, ui) else {
+                continue;
+            };
+            for (i, source) in node.inputs.iter().enumerate() {
+                let Some((source_view, source_base)) = find_view(*source, ui) else {
+                    continue;
+                };
+                let connection = ConnectionBuilder::new(
+                    WidgetBuilder::new()
+                        .with_context_menu(self.connection_context_menu.menu.clone()),
+                )
+                .with_source_socket(source_base.output_socket)
+                .with_source_node(source_view)
+                .with_dest_socket(dest_base.input_sockets[i])
+                .with_dest_node(dest_view)
+                .build(self.canvas, &mut ui.build_ctx());
+
+                send_sync_message(
+                    ui,
+                    WidgetMessage::link(connection, MessageDirection::ToWidget, self.canvas),
+                );
+                send_sync_message(
+                    ui,
+                    WidgetMessage::lowermost(connection, MessageDirection::ToWidget),
+                );
+            }
+        }
+
+        self.selection.retain(|h| graph.nodes().is_valid_handle(*h));
+        let selection = views
+            .iter()
+            .filter(|(h, _)| self.selection.contains(h))
+            .map(|(_, view)| *view)
+            .collect::<Vec<_>>();
+        send_sync_message(
+            ui,
+            AbsmCanvasMessage::selection_changed(
+                self.canvas,
+                MessageDirection::ToWidget,
+                selection,
+            ),
+        );
+        send_sync_message(
+            ui,
+            AbsmCanvasMessage::force_sync_dependent_objects(
+                self.canvas,
+                MessageDirection::ToWidget,
+            ),
+        );
+    }
+
+    fn sync_inspector(&self, ui: &mut UserInterface, sender: &MessageSender) {
+        let mut state = self.graph.state();
+        let Some(graph) = state.data() else {
+            return;
+        };
+
+        // The inspector shows the first selected node or the graph settings if nothing is selected.
+        let object = match self.selection.first().and_then(|h| graph.node(*h)) {
+            Some(node) => node as &dyn Reflect,
+            None => &*graph as &dyn Reflect,
+        };
+
+        let inspector_context = ui
+            .node(self.inspector)
+            .cast::<Inspector>()
+            .unwrap()
+            .context()
+            .clone();
+        if inspector_context
+            .sync(object, ui, 0, true, Default::default())
+            .is_err()
+        {
+            let context = InspectorContext::from_object(
+                object,
+                &mut ui.build_ctx(),
+                Arc::new(make_property_editors_container(sender.clone())),
+                None,
+                MSG_SYNC_FLAG,
+                0,
+                true,
+                Default::default(),
+            );
+            ui.send_message(InspectorMessage::context(
+                self.inspector,
+                MessageDirection::ToWidget,
+                context,
+            ));
+        }
+    }
+
+    fn compile(&mut self, engine: &mut Engine) {
+        let result = {
+            let mut state = self.graph.state();
+            let Some(graph) = state.data() else {
+                return;
+            };
+            graph.compile("ShaderGraph")
+        };
+
+        let ui = engine.user_interfaces.first();
+        match result {
+            Ok(definition) => {
+                self.set_status(
+                    format!(
+                        "Compiled successfully. Material properties: {}",
+                        definition.properties.len()
+                    ),
+                    ui,
+                );
+                let shader = ShaderResource::new_ok(
+                    ResourceKind::Embedded,
+                    Shader::from_definition(definition),
+                );
+                self.material = MaterialResource::new_ok(
+                    ResourceKind::Embedded,
+                    Material::from_shader(shader, Some(engine.resource_manager.clone())),
+                );
+                self.apply_preview_material(engine);
+            }
+            Err(err) => self.set_status(format!("Compilation failed: {err}"), ui),
+        }
+    }
+
+    fn apply_preview_material(&self, engine: &mut Engine) {
+        let graph = &mut engine.scenes[self.preview.scene()].graph;
+        let meshes = graph
+            .traverse_handle_iter(self.preview.model())
+            .collect::<Vec<_>>();
+        for handle in meshes {
+            if let Some(mesh) = graph[handle].cast_mut::<Mesh>() {
+                for surface in mesh.surfaces_mut() {
+                    surface.set_material(self.material.clone());
+                }
+            }
+        }
+    }
+
+    fn sync_to_model(&mut self, engine: &mut Engine, sender: &MessageSender) {
+        let ui = engine.user_interfaces.first_mut();
+        self.sync_canvas(ui);
+        self.sync_inspector(ui, sender);
+        self.compile(engine);
+    }
+
+    fn save(&self, engine: &mut Engine) {
+        let ui = engine.user_interfaces.first();
+        let ResourceKind::External(path) = self.graph.kind() else {
+            self.set_status("Embedded shader graphs cannot be saved!", ui);
+            return;
+        };
+
+        let mut state = self.graph.state();
+        let Some(graph) = state.data() else {
+            return;
+        };
+
+        if let Err(err) = graph.save(&path) {
+            drop(state);
+            self.set_status(format!("Unable to save shader graph. Reason: {err}"), ui);
+            return;
+        }
+
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let result = graph.compile(&name);
+        drop(state);
+
+        let shader_path = path.with_extension("shader");
+        let status = match result {
+            Ok(definition) => match Shader::from_definition(definition).save(&shader_path) {
+                Ok(_) => {
+                    // Materials that already use the shader must see the changes.
+                    let resource_manager = &engine.resource_manager;
+                    let shader = resource_manager.state().find(&shader_path).cloned();
+                    if let Some(shader) = shader {
+                        resource_manager.state().reload_resource(shader);
+                    }
+                    format!(
+                        "Shader graph was saved to {} and compiled to {}",
+                        path.display(),
+                        shader_path.display()
+                    )
+                }
+                Err(err) => format!("Unable to save shader. Reason: {err}"),
+            },
+            Err(err) => format!(
+                "Shader graph was saved to {}, but it cannot be compiled: {err}",
+                path.display()
+            ),
+        };
+        self.set_status(status, ui);
+    }
+
+    fn modify_graph(
+        &mut self,
+        engine: &mut Engine,
+        sender: &MessageSender,
+        func: impl FnOnce(&mut ShaderGraph),
+    ) {
+        if let Some(graph) = self.graph.state().data() {
+            func(graph);
+        }
+        self.sync_to_model(engine, sender);
+    }
+
+    fn destroy(self, engine: &mut Engine) {
+        engine
+            .user_interfaces
+            .first()
+            .send_message(WidgetMessage::remove(
+                self.window,
+                MessageDirection::ToWidget,
+            ));
+        self.preview.destroy(engine);
+    }
+
+    fn handle_ui_message(mut self, message: &UiMessage, editor: &mut Editor) -> Option<Self> {
+        let sender = editor.message_sender.clone();
+        let engine = &mut editor.engine;
+
+        self.preview.handle_message(message, engine);
+
+        if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.destroy(engine);
+                return None;
+            }
+        } else if let Some(WindowMessage::Open { .. }) = message.data() {
+            if message.destination() == self.window {
+                self.sync_to_model(engine, &sender);
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.save {
+                self.save(engine);
+            } else if message.destination() == self.sphere {
+                let sphere = make_sphere(engine, &self.preview);
+                self.preview.set_model(sphere, engine);
+                self.apply_preview_material(engine);
+            }
+        } else if let Some(ResourceFieldMessage::Value(Some(model))) =
+            message.data::<ResourceFieldMessage<Model>>()
+        {
+            if message.destination() == self.model
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let instance = model.instantiate(&mut engine.scenes[self.preview.scene()]);
+                self.preview.set_model(instance, engine);
+                self.apply_preview_material(engine);
+            }
+        } else if let Some(msg) = message.data::<AbsmCanvasMessage>() {
+            if message.destination() == self.canvas
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let ui = engine.user_interfaces.first();
+                match msg {
+                    AbsmCanvasMessage::CommitDrag { entries } => {
+                        let positions = entries
+                            .iter()
+                            .map(|e| {
+                                (
+                                    fetch_model_handle(e.node, ui),
+                                    ui.node(e.node).actual_local_position(),
+                                )
+                            })
+                            .collect::<Vec<_>>();
+                        if let Some(graph) = self.graph.state().data() {
+                            for (handle, position) in positions {
+                                if let Some(node) = graph.node_mut(handle) {
+                                    node.position = position;
+                                }
+                            }
+                        }
+                    }
+                    AbsmCanvasMessage::SelectionChanged(selection) => {
+                        let selection = selection
+                            .iter()
+                            .map(|n| fetch_model_handle(*n, ui))
+                            .filter(|h| h.is_some())
+                            .collect::<Vec<_>>();
+                        if selection != self.selection {
+                            self.selection = selection;
+                            self.sync_inspector(engine.user_interfaces.first_mut(), &sender);
+                        }
+                    }
+                    AbsmCanvasMessage::CommitConnection {
+                        source_socket,
+                        dest_socket,
+                    } => {
+                        if let (Some((source, _)), Some((dest, index))) = (
+                            fetch_socket(*source_socket, ui),
+                            fetch_socket(*dest_socket, ui),
+                        ) {
+                            self.modify_graph(engine, &sender, |graph| {
+                                graph.connect(source, dest, index)
+                            });
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
+            if message.destination() == self.inspector {
+                let selected = self.selection.first().cloned();
+                self.modify_graph(engine, &sender, |graph| {
+                    let object = match selected {
+                        Some(handle) => match graph.node_mut(handle) {
+                            Some(node) => node as &mut dyn Reflect,
+                            None => return,
+                        },
+                        None => graph as &mut dyn Reflect,
+                    };
+                    PropertyAction::from_field_kind(&property_changed.value).apply(
+                        &property_changed.path(),
+                        object,
+                        &mut |result| {
+                            Log::verify(result);
+                        },
+                    );
+                    if let Some(node) = selected.and_then(|handle| graph.node_mut(handle)) {
+                        // The kind of the node could be changed, so the amount of inputs as well.
+                        node.sync_inputs();
+                    }
+                });
+            }
+        }
+
+        let ui = engine.user_interfaces.first();
+        if let Some(kind) = self.canvas_context_menu.handle_ui_message(message) {
+            let position = ui.node(self.canvas).screen_to_local(
+                ui.node(self.canvas_context_menu.menu.handle())
+                    .screen_position(),
+            );
+            self.modify_graph(engine, &sender, |graph| {
+                graph.add_node(ShaderGraphNode::new(kind, position));
+            });
+        } else if self.node_context_menu.handle_ui_message(message) {
+            let selection = self.selection.clone();
+            self.modify_graph(engine, &sender, |graph| {
+                let output = graph.output();
+                for handle in selection {
+                    // The output node is mandatory.
+                    if handle != output {
+                        graph.remove_node(handle);
+                    }
+                }
+            });
+        } else if let Some(connection) = self.connection_context_menu.handle_ui_message(message) {
+            if let Some((dest, index)) = ui
+                .try_get(connection)
+                .and_then(|n| n.query_component::<Connection>())
+                .and_then(|connection| fetch_socket(connection.segment.dest, ui))
+            {
+                self.modify_graph(engine, &sender, |graph| graph.disconnect(dest, index));
+            }
+        }
+
+        Some(self)
+    }
+
+    fn update(&mut self, engine: &mut Engine) {
+        self.preview.update(engine);
+    }
+}
+
+#[derive(Default)]
+pub struct ShaderGraphPlugin {
+    editor: Option<ShaderGraphEditor>,
+}
+
+impl EditorPlugin for ShaderGraphPlugin {
+    fn on_ui_message(&mut self, message: &mut UiMessage, editor: &mut Editor) {
+        if let Some(shader_graph_editor) = self.editor.take() {
+            self.editor = shader_graph_editor.handle_ui_message(message, editor);
+        }
+    }
+
+    fn on_update(&mut self, editor: &mut Editor) {
+        if let Some(shader_graph_editor) = self.editor.as_mut() {
+            shader_graph_editor.update(&mut editor.engine);
+        }
+    }
+
+    fn on_message(&mut self, message: &Message, editor: &mut Editor) {
+        if let Message::OpenShaderGraphEditor(graph) = message {
+            if let Some(previous) = self.editor.take() {
+                previous.destroy(&mut editor.engine);
+            }
+            self.editor = Some(ShaderGraphEditor::new(
+                graph.clone(),
+                &mut editor.engine,
+                editor.message_sender.clone(),
+            ));
+        }
+    }
+}
@@ -0,0 +1,553 @@
+//! Weight painting for skinned sprites. When a single skinned sprite is selected, a special interaction mode
+//! is available, that allows you to paint weights of its bones directly in the scene.
+
+use crate::fyrox::{
+    core::{
+        algebra::{Matrix4, Point3, Vector2, Vector3},
+        color::Color,
+        math::plane::Plane,
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        Uuid,
+    },
+    engine::Engine,
+    graph::{BaseSceneGraph, SceneGraphNode},
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::PropertyEditorDefinitionContainer, InspectorBuilder, InspectorContext,
+            InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        utils::make_simple_tooltip,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, VerticalAlignment,
+    },
+    scene::{
+        camera::Camera,
+        dim2::skin::{Bone2D, SkinVertex, SkinnedSprite},
+        graph::Graph,
+        node::Node,
+    },
+};
+use crate::{
+    command::{CommandContext, CommandTrait},
+    interaction::{make_interaction_mode_button, InteractionMode},
+    message::MessageSender,
+    plugin::EditorPlugin,
+    scene::{
+        commands::GameSceneContext, controller::SceneController, GameScene, Selection,
+        SelectionContainer,
+    },
+    settings::Settings,
+    Editor, Message, MSG_SYNC_FLAG,
+};
+use std::sync::Arc;
+
+#[derive(Debug)]
+pub struct SetSkinDataCommand {
+    node: Handle<Node>,
+    bones: Vec<Bone2D>,
+    vertices: Vec<SkinVertex>,
+}
+
+impl SetSkinDataCommand {
+    pub fn new(node: Handle<Node>, bones: Vec<Bone2D>, vertices: Vec<SkinVertex>) -> Self {
+        Self {
+            node,
+            bones,
+            vertices,
+        }
+    }
+
+    fn swap(&mut self, context: &mut dyn CommandContext) {
+        let context = context.get_mut::<GameSceneContext>();
+        if let Some(sprite) = context.scene.graph[self.node].cast_mut::<SkinnedSprite>() {
+            self.bones = sprite.set_bones(std::mem::take(&mut self.bones));
+            self.vertices = sprite.set_vertices(std::mem::take(&mut self.vertices));
+        }
+    }
+}
+
+impl CommandTrait for SetSkinDataCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Modify Skinned Sprite".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context);
+    }
+}
+
+#[derive(Clone, Debug, Reflect)]
+struct WeightBrush {
+    #[reflect(description = "Index of a bone in the list of bones of the skinned sprite.")]
+    bone: u8,
+    #[reflect(min_value = 0.0)]
+    radius: f32,
+    #[reflect(
+        min_value = 0.0,
+        max_value = 1.0,
+        description = "Amount of weight per stroke step. Hold Shift to erase."
+    )]
+    strength: f32,
+}
+
+impl Default for WeightBrush {
+    fn default() -> Self {
+        Self {
+            bone: 0,
+            radius: 0.25,
+            strength: 0.1,
+        }
+    }
+}
+
+struct WeightBrushPanel {
+    window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    bind: Handle<UiNode>,
+    auto_weights: Handle<UiNode>,
+}
+
+fn make_button(text: &str, tooltip: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(120.0)
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl WeightBrushPanel {
+    fn new(ctx: &mut BuildContext, brush: &WeightBrush) -> Self {
+        let context = InspectorContext::from_object(
+            brush,
+            ctx,
+            Arc::new(PropertyEditorDefinitionContainer::with_default_editors()),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+            Default::default(),
+        );
+
+        let inspector = InspectorBuilder::new(WidgetBuilder::new().on_row(0))
+            .with_context(context)
+            .build(ctx);
+        let bind = make_button(
+            "Bind Pose",
+            "Uses current transforms of the bones as their bind pose.",
+            ctx,
+        );
+        let auto_weights = make_button(
+            "Auto Weights",
+            "Calculates weights of every vertex using distances to the bones.",
+            ctx,
+        );
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(150.0))
+            .can_minimize(false)
+            .can_maximize(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new().with_child(inspector).with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .with_child(bind)
+                                .with_child(auto_weights),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    ),
+                )
+                .add_row(Row::stretch())
+                .add_row(Row::strict(26.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .open(false)
+            .with_title(WindowTitle::text("Weight Brush"))
+            .build(ctx);
+
+        Self {
+            window,
+            inspector,
+            bind,
+            auto_weights,
+        }
+    }
+}
+
+#[derive(TypeUuidProvider)]
+#[type_uuid(id = "1f7c2b9e-5a3d-4e8f-b6c1-0d9a4e2f8b73")]
+pub struct SkinWeightPaintInteractionMode {
+    skinned_sprite: Handle<Node>,
+    brush: WeightBrush,
+    brush_center: Option<Vector2<f32>>,
+    panel: WeightBrushPanel,
+    old_vertices: Option<Vec<SkinVertex>>,
+    message_sender: MessageSender,
+    scene_viewer_frame: Handle<UiNode>,
+}
+
+impl SkinWeightPaintInteractionMode {
+    fn pick(
+        &self,
+        graph: &Graph,
+        game_scene: &GameScene,
+        mouse_position: Vector2<f32>,
+        frame_size: Vector2<f32>,
+    ) -> Option<Vector2<f32>> {
+        let camera = graph[game_scene.camera_controller.camera].cast::<Camera>()?;
+        let ray = camera.make_ray(mouse_position, frame_size);
+        let sprite = graph.try_get(self.skinned_sprite)?;
+        let global_transform = sprite.global_transform();
+        let plane = Plane::from_normal_and_point(
+            &global_transform.transform_vector(&Vector3::z()),
+            &sprite.global_position(),
+        )?;
+        let point = ray.plane_intersection_point(&plane)?;
+        let local = global_transform
+            .try_inverse()?
+            .transform_point(&Point3::from(point));
+        Some(Vector2::new(local.x, local.y))
+    }
+
+    fn paint(&self, graph: &mut Graph, erase: bool) {
+        let (Some(center), Some(sprite)) = (
+            self.brush_center,
+            graph
+                .try_get_mut(self.skinned_sprite)
+                .and_then(|n| n.cast_mut::<SkinnedSprite>()),
+        ) else {
+            return;
+        };
+
+        let strength = if erase {
+            -self.brush.strength
+        } else {
+            self.brush.strength
+        };
+        sprite.paint_weight(self.brush.bone, center, self.brush.radius, strength);
+    }
+
+    fn modify(
+        &self,
+        graph: &mut Graph,
+        func: impl FnOnce(&mut SkinnedSprite, &[(Handle<Node>, Matrix4<f32>)]),
+    ) {
+        let Some(sprite) = graph
+            .try_get(self.skinned_sprite)
+            .and_then(|n| n.cast::<SkinnedSprite>())
+        else {
+            return;
+        };
+
+        let bind_poses = sprite
+            .bones()
+            .iter()
+            .filter_map(|bone| {
+                graph
+                    .try_get(bone.node)
+                    .map(|node| (bone.node, node.global_transform()))
+            })
+            .collect::<Vec<_>>();
+
+        let mut copy = sprite.clone();
+        func(&mut copy, &bind_poses);
+
+        self.message_sender.do_command(SetSkinDataCommand::new(
+            self.skinned_sprite,
+            copy.bones().to_vec(),
+            copy.vertices().to_vec(),
+        ));
+    }
+}
+
+impl InteractionMode for SkinWeightPaintInteractionMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let erase = engine.user_interfaces.first().keyboard_modifiers().shift;
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        if let Some(sprite) = graph
+            .try_get(self.skinned_sprite)
+            .and_then(|n| n.cast::<SkinnedSprite>())
+        {
+            self.old_vertices = Some(sprite.vertices().to_vec());
+            self.brush_center = self.pick(graph, game_scene, mouse_pos, frame_size);
+            self.paint(graph, erase);
+        }
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let Some(old_vertices) = self.old_vertices.take() else {
+            return;
+        };
+
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        if let Some(sprite) = graph
+            .try_get_mut(self.skinned_sprite)
+            .and_then(|n| n.cast_mut::<SkinnedSprite>())
+        {
+            // Restore the original state and let the command apply the stroke, so it could be undone.
+            let new_vertices = sprite.set_vertices(old_vertices);
+            self.message_sender.do_command(SetSkinDataCommand::new(
+                self.skinned_sprite,
+                sprite.bones().to_vec(),
+                new_vertices,
+            ));
+        }
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        _mouse_offset: Vector2<f32>,
+        mouse_position: Vector2<f32>,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let erase = engine.user_interfaces.first().keyboard_modifiers().shift;
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        self.brush_center = self.pick(graph, game_scene, mouse_position, frame_size);
+        if self.old_vertices.is_some() {
+            self.paint(graph, erase);
+        }
+    }
+
+    fn update(
+        &mut self,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        _settings: &Settings,
+    ) {
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let scene = &mut engine.scenes[game_scene.scene];
+        let Some(sprite) = scene
+            .graph
+            .try_get(self.skinned_sprite)
+            .and_then(|n| n.cast::<SkinnedSprite>())
+        else {
+            return;
+        };
+
+        let transform = sprite.global_transform();
+
+        // Visualize weights of the current bone: black - no influence, red - full influence.
+        for vertex in sprite.vertices() {
+            let weight = vertex
+                .bone_indices
+                .iter()
+                .zip(vertex.bone_weights.iter())
+                .filter(|(index, _)| **index == self.brush.bone)
+                .map(|(_, weight)| *weight)
+                .sum::<f32>();
+            scene.drawing_context.draw_circle(
+                vertex.position.to_homogeneous(),
+                self.brush.radius * 0.05,
+                6,
+                transform,
+                Color::BLACK.lerp(Color::RED, weight.clamp(0.0, 1.0)),
+            );
+        }
+
+        if let Some(center) = self.brush_center {
+            scene.drawing_context.draw_circle(
+                center.to_homogeneous(),
+                self.brush.radius,
+                32,
+                transform,
+                Color::GREEN,
+            );
+        }
+    }
+
+    fn activate(&mut self, _controller: &dyn SceneController, engine: &mut Engine) {
+        engine
+            .user_interfaces
+            .first_mut()
+            .send_message(WindowMessage::open_and_align(
+                self.panel.window,
+                MessageDirection::ToWidget,
+                self.scene_viewer_frame,
+                HorizontalAlignment::Right,
+                VerticalAlignment::Top,
+                Thickness::top_right(5.0),
+                false,
+                false,
+            ));
+    }
+
+    fn deactivate(&mut self, _controller: &dyn SceneController, engine: &mut Engine) {
+        self.brush_center = None;
+        engine
+            .user_interfaces
+            .first_mut()
+            .send_message(WindowMessage::close(
+                self.panel.window,
+                MessageDirection::ToWidget,
+            ));
+    }
+
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+    ) {
+        if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
+            if message.destination() == self.panel.inspector {
+                PropertyAction::from_field_kind(&property_changed.value).apply(
+                    &property_changed.path(),
+                    &mut self.brush,
+                    &mut |result| {
+                        crate::fyrox::core::log::Log::verify(result);
+                    },
+                );
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+                return;
+            };
+            let graph = &mut engine.scenes[game_scene.scene].graph;
+
+            if message.destination() == self.panel.bind {
+                self.modify(graph, |sprite, bind_poses| {
+                    let global_transform = sprite.global_transform();
+                    sprite.bind(bind_poses, global_transform);
+                });
+            } else if message.destination() == self.panel.auto_weights {
+                self.modify(graph, |sprite, _| sprite.compute_weights_by_distance());
+            }
+        }
+    }
+
+    fn on_drop(&mut self, engine: &mut Engine) {
+        engine
+            .user_interfaces
+            .first_mut()
+            .send_message(WidgetMessage::remove(
+                self.panel.window,
+                MessageDirection::ToWidget,
+            ));
+    }
+
+    fn make_button(&mut self, ctx: &mut BuildContext, selected: bool) -> Handle<UiNode> {
+        make_interaction_mode_button(
+            ctx,
+            include_bytes!("../../resources/terrain.png"),
+            "Paint Bone Weights\n\nPaints weights of the bones of the selected skinned sprite. \
+            Hold Shift to erase.",
+            selected,
+        )
+    }
+
+    fn uuid(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+#[derive(Default)]
+pub struct SkinWeightPaintPlugin {}
+
+impl EditorPlugin for SkinWeightPaintPlugin {
+    fn on_message(&mut self, message: &Message, editor: &mut Editor) {
+        let Message::SelectionChanged { .. } = message else {
+            return;
+        };
+
+        let scene_viewer_frame = editor.scene_viewer.frame();
+
+        let Some(entry) = editor.scenes.current_scene_entry_mut() else {
+            return;
+        };
+
+        let Some(game_scene) = entry.controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        if let Some(mut mode) = entry
+            .interaction_modes
+            .remove_typed::<SkinWeightPaintInteractionMode>()
+        {
+            mode.on_drop(&mut editor.engine);
+        }
+
+        let Some(selection) = entry.selection.as_graph() else {
+            return;
+        };
+
+        if !selection.is_single_selection() {
+            return;
+        }
+
+        let node_handle = selection.nodes()[0];
+        let scene = &editor.engine.scenes[game_scene.scene];
+        if scene
+            .graph
+            .try_get(node_handle)
+            .and_then(|n| n.component_ref::<SkinnedSprite>())
+            .is_some()
+        {
+            let ui = editor.engine.user_interfaces.first_mut();
+            let brush = WeightBrush::default();
+            let panel = WeightBrushPanel::new(&mut ui.build_ctx(), &brush);
+            entry.interaction_modes.add(SkinWeightPaintInteractionMode {
+                skinned_sprite: node_handle,
+                brush,
+                brush_center: None,
+                panel,
+                old_vertices: None,
+                message_sender: editor.message_sender.clone(),
+                scene_viewer_frame,
+            });
+        }
+    }
+}
@@ -0,0 +1,512 @@
+//! Sprite sheet editor allows you to slice a sprite sheet into frames (using a uniform grid or by automatic
+//! detection of opaque regions), to define named clips and to preview them.
+
+use crate::fyrox::{
+    asset::{untyped::ResourceKind, ResourceData},
+    core::{algebra::Vector2, log::Log, pool::Handle, reflect::prelude::*},
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        image::{ImageBuilder, ImageMessage},
+        inspector::{InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        utils::make_simple_tooltip,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        wrap_panel::WrapPanelBuilder,
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    resource::texture::TextureKind,
+    scene::animation::sprite::sheet::{detect_texture_frames, slice_grid, SpriteSheetResource},
+};
+use crate::{
+    inspector::editors::make_property_editors_container, message::MessageSender,
+    plugin::EditorPlugin, Editor, Message, FIXED_TIMESTEP, MSG_SYNC_FLAG,
+};
+use std::sync::Arc;
+
+/// Pixels with alpha less or equal to this value are considered transparent by the frame detection.
+const ALPHA_THRESHOLD: u8 = 8;
+
+const THUMBNAIL_SIZE: f32 = 48.0;
+
+fn make_button(text: &str, tooltip: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(90.0)
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+fn make_label(text: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .with_text(text)
+        .build(ctx)
+}
+
+fn make_count_field(value: u32, ctx: &mut BuildContext) -> Handle<UiNode> {
+    NumericUpDownBuilder::<u32>::new(
+        WidgetBuilder::new()
+            .with_width(50.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_min_value(1)
+    .with_value(value)
+    .build(ctx)
+}
+
+pub struct SpriteSheetEditor {
+    window: Handle<UiNode>,
+    columns: Handle<UiNode>,
+    rows: Handle<UiNode>,
+    slice: Handle<UiNode>,
+    detect: Handle<UiNode>,
+    save: Handle<UiNode>,
+    frames: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    preview: Handle<UiNode>,
+    preview_clip: Handle<UiNode>,
+    play: Handle<UiNode>,
+    stop: Handle<UiNode>,
+    status: Handle<UiNode>,
+    grid_size: Vector2<u32>,
+    clip_index: usize,
+    playing: bool,
+    time: f32,
+    sprite_sheet: SpriteSheetResource,
+}
+
+impl SpriteSheetEditor {
+    pub fn new(sprite_sheet: SpriteSheetResource, ctx: &mut BuildContext) -> Self {
+        let grid_size = Vector2::new(4, 4);
+
+        let columns = make_count_field(grid_size.x, ctx);
+        let rows = make_count_field(grid_size.y, ctx);
+        let slice = make_button(
+            "Slice Grid",
+            "Slices the texture into a uniform grid of frames.",
+            ctx,
+        );
+        let detect = make_button(
+            "Auto Detect",
+            "Detects frames as separate groups of opaque pixels of the texture.",
+            ctx,
+        );
+        let save = make_button("Save", "Saves the sprite sheet.", ctx);
+
+        let toolbar = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .on_row(0)
+                .with_child(make_label("Columns", ctx))
+                .with_child(columns)
+                .with_child(make_label("Rows", ctx))
+                .with_child(rows)
+                .with_child(slice)
+                .with_child(detect)
+                .with_child(save),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
+        let frames = ListViewBuilder::new(
+            WidgetBuilder::new()
+                .on_column(0)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_items_panel(
+            WrapPanelBuilder::new(WidgetBuilder::new())
+                .with_orientation(Orientation::Horizontal)
+                .build(ctx),
+        )
+        .build(ctx);
+
+        let inspector = InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
+
+        let preview = ImageBuilder::new(
+            WidgetBuilder::new()
+                .with_width(128.0)
+                .with_height(128.0)
+                .with_margin(Thickness::uniform(2.0)),
+        )
+        .with_checkerboard_background(true)
+        .build(ctx);
+        let preview_clip = NumericUpDownBuilder::<u32>::new(
+            WidgetBuilder::new()
+                .with_width(50.0)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_value(0)
+        .build(ctx);
+        let play = make_button("Play", "Plays the selected clip.", ctx);
+        let stop = make_button("Stop", "Stops the playback.", ctx);
+
+        let preview_panel = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .on_column(2)
+                .with_child(preview)
+                .with_child(
+                    StackPanelBuilder::new(
+                        WidgetBuilder::new()
+                            .with_horizontal_alignment(HorizontalAlignment::Center)
+                            .with_child(make_label("Clip", ctx))
+                            .with_child(preview_clip),
+                    )
+                    .with_orientation(Orientation::Horizontal)
+                    .build(ctx),
+                )
+                .with_child(play)
+                .with_child(stop),
+        )
+        .build(ctx);
+
+        let status = TextBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
+                .with_margin(Thickness::uniform(2.0)),
+        )
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .build(ctx);
+
+        let content = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(toolbar)
+                .with_child(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(1)
+                            .with_child(frames)
+                            .with_child(
+                                ScrollViewerBuilder::new(WidgetBuilder::new().on_column(1))
+                                    .with_content(inspector)
+                                    .build(ctx),
+                            )
+                            .with_child(preview_panel),
+                    )
+                    .add_row(Row::stretch())
+                    .add_column(Column::stretch())
+                    .add_column(Column::stretch())
+                    .add_column(Column::strict(140.0))
+                    .build(ctx),
+                )
+                .with_child(status),
+        )
+        .add_row(Row::strict(26.0))
+        .add_row(Row::stretch())
+        .add_row(Row::strict(22.0))
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(800.0).with_height(500.0))
+            .open(false)
+            .with_title(WindowTitle::text("Sprite Sheet Editor"))
+            .with_content(content)
+            .build(ctx);
+
+        ctx.sender()
+            .send(WindowMessage::open(
+                window,
+                MessageDirection::ToWidget,
+                true,
+                true,
+            ))
+            .unwrap();
+
+        Self {
+            window,
+            columns,
+            rows,
+            slice,
+            detect,
+            save,
+            frames,
+            inspector,
+            preview,
+            preview_clip,
+            play,
+            stop,
+            status,
+            grid_size,
+            clip_index: 0,
+            playing: false,
+            time: 0.0,
+            sprite_sheet,
+        }
+    }
+
+    fn set_status(&self, text: impl Into<String>, ui: &UserInterface) {
+        ui.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            text.into(),
+        ));
+    }
+
+    fn sync_to_model(&self, ui: &mut UserInterface, sender: &MessageSender) {
+        let mut state = self.sprite_sheet.state();
+        let Some(sheet) = state.data() else {
+            return;
+        };
+
+        let texture = sheet.texture.clone().map(|t| t.into_untyped());
+        let items = (0..sheet.frames.len())
+            .filter_map(|index| sheet.frame_uv_rect(index))
+            .map(|uv_rect| {
+                ImageBuilder::new(
+                    WidgetBuilder::new()
+                        .with_width(THUMBNAIL_SIZE)
+                        .with_height(THUMBNAIL_SIZE)
+                        .with_margin(Thickness::uniform(1.0)),
+                )
+                .with_opt_texture(texture.clone())
+                .with_uv_rect(uv_rect)
+                .with_checkerboard_background(true)
+                .build(&mut ui.build_ctx())
+            })
+            .collect::<Vec<_>>();
+        ui.send_message(ListViewMessage::items(
+            self.frames,
+            MessageDirection::ToWidget,
+            items,
+        ));
+
+        ui.send_message(ImageMessage::texture(
+            self.preview,
+            MessageDirection::ToWidget,
+            texture,
+        ));
+
+        let context = InspectorContext::from_object(
+            sheet,
+            &mut ui.build_ctx(),
+            Arc::new(make_property_editors_container(sender.clone())),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+            Default::default(),
+        );
+        ui.send_message(InspectorMessage::context(
+            self.inspector,
+            MessageDirection::ToWidget,
+            context,
+        ));
+
+        let status = format!(
+            "{} frame(s), {} clip(s)",
+            sheet.frames.len(),
+            sheet.clips.len()
+        );
+        drop(state);
+        self.set_status(status, ui);
+    }
+
+    fn update_frames(&self, detect: bool, ui: &mut UserInterface, sender: &MessageSender) {
+        let mut state = self.sprite_sheet.state();
+        let Some(sheet) = state.data() else {
+            return;
+        };
+
+        let Some(texture) = sheet.texture.clone() else {
+            drop(state);
+            self.set_status("Sprite sheet has no texture!", ui);
+            return;
+        };
+        let mut texture_state = texture.state();
+        let Some(texture) = texture_state.data() else {
+            drop(state);
+            self.set_status("Texture is not loaded!", ui);
+            return;
+        };
+        let TextureKind::Rectangle { width, height } = texture.kind() else {
+            drop(state);
+            self.set_status("Only rectangular textures are supported!", ui);
+            return;
+        };
+
+        sheet.image_size = Vector2::new(width, height);
+        if detect {
+            match detect_texture_frames(texture, ALPHA_THRESHOLD) {
+                Some(frames) => sheet.frames = frames,
+                None => {
+                    drop(state);
+                    self.set_status("Frame detection requires RGBA8 texture!", ui);
+                    return;
+                }
+            }
+        } else {
+            sheet.frames = slice_grid(sheet.image_size, self.grid_size.x, self.grid_size.y);
+        }
+
+        drop(texture_state);
+        drop(state);
+        self.sync_to_model(ui, sender);
+    }
+
+    fn save(&self, ui: &UserInterface) {
+        let ResourceKind::External(path) = self.sprite_sheet.kind() else {
+            self.set_status("Embedded sprite sheets cannot be saved!", ui);
+            return;
+        };
+
+        let mut state = self.sprite_sheet.state();
+        let Some(sheet) = state.data() else {
+            return;
+        };
+
+        let status = match sheet.save(&path) {
+            Ok(_) => format!("Sprite sheet was saved to {}", path.display()),
+            Err(err) => format!("Unable to save sprite sheet. Reason: {err}"),
+        };
+        drop(state);
+        self.set_status(status, ui);
+    }
+
+    fn update(&mut self, dt: f32, ui: &UserInterface) {
+        if !self.playing {
+            return;
+        }
+
+        let mut state = self.sprite_sheet.state();
+        let Some(sheet) = state.data() else {
+            return;
+        };
+
+        let Some(clip) = sheet.clips.get(self.clip_index) else {
+            return;
+        };
+
+        if clip.frames.is_empty() || clip.fps <= 0.0 {
+            return;
+        }
+
+        self.time += dt;
+        let mut position = (self.time * clip.fps) as usize;
+        if clip.looping {
+            position %= clip.frames.len();
+        } else {
+            position = position.min(clip.frames.len() - 1);
+        }
+
+        if let Some(uv_rect) = sheet.frame_uv_rect(clip.frames[position] as usize) {
+            ui.send_message(ImageMessage::uv_rect(
+                self.preview,
+                MessageDirection::ToWidget,
+                uv_rect,
+            ));
+        }
+    }
+
+    fn destroy(self, ui: &UserInterface) {
+        ui.send_message(WidgetMessage::remove(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    pub fn handle_ui_message(
+        mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        sender: &MessageSender,
+    ) -> Option<Self> {
+        if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.destroy(ui);
+                return None;
+            }
+        } else if let Some(WindowMessage::Open { .. }) = message.data() {
+            if message.destination() == self.window {
+                self.sync_to_model(ui, sender);
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.slice {
+                self.update_frames(false, ui, sender);
+            } else if message.destination() == self.detect {
+                self.update_frames(true, ui, sender);
+            } else if message.destination() == self.save {
+                self.save(ui);
+            } else if message.destination() == self.play {
+                self.playing = true;
+                self.time = 0.0;
+            } else if message.destination() == self.stop {
+                self.playing = false;
+            }
+        } else if let Some(NumericUpDownMessage::<u32>::Value(value)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if message.destination() == self.columns {
+                    self.grid_size.x = *value;
+                } else if message.destination() == self.rows {
+                    self.grid_size.y = *value;
+                } else if message.destination() == self.preview_clip {
+                    self.clip_index = *value as usize;
+                    self.time = 0.0;
+                }
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
+            if message.destination() == self.inspector {
+                let mut state = self.sprite_sheet.state();
+                if let Some(sheet) = state.data() {
+                    PropertyAction::from_field_kind(&property_changed.value).apply(
+                        &property_changed.path(),
+                        sheet as &mut dyn Reflect,
+                        &mut |result| {
+                            Log::verify(result);
+                        },
+                    );
+                }
+                drop(state);
+                self.sync_to_model(ui, sender);
+            }
+        }
+
+        Some(self)
+    }
+}
+
+#[derive(Default)]
+pub struct SpriteSheetEditorPlugin {
+    sprite_sheet_editor: Option<SpriteSheetEditor>,
+}
+
+impl EditorPlugin for SpriteSheetEditorPlugin {
+    fn on_ui_message(&mut self, message: &mut UiMessage, editor: &mut Editor) {
+        if let Some(sprite_sheet_editor) = self.sprite_sheet_editor.take() {
+            self.sprite_sheet_editor = sprite_sheet_editor.handle_ui_message(
+                message,
+                editor.engine.user_interfaces.first_mut(),
+                &editor.message_sender,
+            );
+        }
+    }
+
+    fn on_update(&mut self, editor: &mut Editor) {
+        if let Some(sprite_sheet_editor) = self.sprite_sheet_editor.as_mut() {
+            sprite_sheet_editor.update(FIXED_TIMESTEP, editor.engine.user_interfaces.first());
+        }
+    }
+
+    fn on_message(&mut self, message: &Message, editor: &mut Editor) {
+        if let Message::OpenSpriteSheetEditor(sprite_sheet) = message {
+            let ui = editor.engine.user_interfaces.first_mut();
+            if let Some(previous) = self.sprite_sheet_editor.take() {
+                previous.destroy(ui);
+            }
+            self.sprite_sheet_editor = Some(SpriteSheetEditor::new(
+                sprite_sheet.clone(),
+                &mut ui.build_ctx(),
+            ));
+        }
+    }
+}
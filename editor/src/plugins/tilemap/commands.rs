@@ -0,0 +1,231 @@
+use crate::command::{CommandContext, CommandTrait};
+use crate::fyrox::{
+    asset::{Resource, TypedResourceData},
+    core::{algebra::Vector2, log::Log, pool::Handle},
+    fxhash::FxHashMap,
+    resource::texture::TextureResource,
+    scene::{
+        node::Node,
+        tilemap::{
+            stamp::{TileMapStamp, TileMapStampLibraryResource},
+            tileset::{TileDefinition, TileSetResource},
+            Tile, TileMap,
+        },
+    },
+};
+use crate::scene::commands::GameSceneContext;
+
+fn try_save<T: TypedResourceData>(resource: &Resource<T>) {
+    let header = resource.header();
+    if let Some(path) = header.kind.path_owned() {
+        drop(header);
+        Log::verify(resource.data_ref().save(&path));
+    }
+}
+
+#[derive(Debug)]
+pub struct SetTileSetTilesCommand {
+    tile_set: TileSetResource,
+    tiles: Vec<TileDefinition>,
+}
+
+impl SetTileSetTilesCommand {
+    pub fn new(tile_set: TileSetResource, tiles: Vec<TileDefinition>) -> Self {
+        Self { tile_set, tiles }
+    }
+
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.tile_set.data_ref().tiles, &mut self.tiles);
+        try_save(&self.tile_set);
+    }
+}
+
+impl CommandTrait for SetTileSetTilesCommand {
+    fn name(&mut self, _: &dyn CommandContext) -> String {
+        "Set Tile Set Tiles".to_owned()
+    }
+
+    fn execute(&mut self, _: &mut dyn CommandContext) {
+        self.swap();
+    }
+
+    fn revert(&mut self, _: &mut dyn CommandContext) {
+        self.swap();
+    }
+}
+
+#[derive(Debug)]
+pub struct SetStampLibraryStampsCommand {
+    library: TileMapStampLibraryResource,
+    stamps: Vec<TileMapStamp>,
+}
+
+impl SetStampLibraryStampsCommand {
+    pub fn new(library: TileMapStampLibraryResource, stamps: Vec<TileMapStamp>) -> Self {
+        Self { library, stamps }
+    }
+
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.library.data_ref().stamps, &mut self.stamps);
+        try_save(&self.library);
+    }
+}
+
+impl CommandTrait for SetStampLibraryStampsCommand {
+    fn name(&mut self, _: &dyn CommandContext) -> String {
+        "Set Stamp Library Stamps".to_owned()
+    }
+
+    fn execute(&mut self, _: &mut dyn CommandContext) {
+        self.swap();
+    }
+
+    fn revert(&mut self, _: &mut dyn CommandContext) {
+        self.swap();
+    }
+}
+
+/// Max size (in texels) of thumbnails of tile map commands.
+const THUMBNAIL_SIZE: u32 = 64;
+
+/// Returns a bounding rectangle (position of the bottom-left cell and size) of cells, that differ in
+/// the given sets of tiles.
+fn changed_region(a: &[Tile], b: &[Tile]) -> Option<(Vector2<i32>, Vector2<u32>)> {
+    let a = a
+        .iter()
+        .map(|t| (t.position(), t.definition_index()))
+        .collect::<FxHashMap<_, _>>();
+    let b = b
+        .iter()
+        .map(|t| (t.position(), t.definition_index()))
+        .collect::<FxHashMap<_, _>>();
+
+    let mut changed = a
+        .iter()
+        .filter(|(position, index)| b.get(*position) != Some(*index))
+        .chain(
+            b.iter()
+                .filter(|(position, index)| a.get(*position) != Some(*index)),
+        )
+        .map(|(position, _)| *position);
+
+    let first = changed.next()?;
+    let (min, max) = changed.fold((first, first), |(min, max), p| (min.inf(&p), max.sup(&p)));
+    // Add a bit of surroundings, so the change could be recognized.
+    let min = min.add_scalar(-1);
+    let max = max.add_scalar(1);
+    Some((min, (max - min).add_scalar(1).map(|c| c as u32)))
+}
+
+#[derive(Debug)]
+pub struct SetTileMapTilesCommand {
+    tile_map: Handle<Node>,
+    tiles: Vec<Tile>,
+    thumbnail: Option<TextureResource>,
+}
+
+impl SetTileMapTilesCommand {
+    pub fn new(tile_map: Handle<Node>, tiles: Vec<Tile>) -> Self {
+        Self {
+            tile_map,
+            tiles,
+            thumbnail: None,
+        }
+    }
+
+    fn swap(&mut self, context: &mut dyn CommandContext) {
+        let context = context.get_mut::<GameSceneContext>();
+        if let Some(tile_map) = context.scene.graph[self.tile_map].cast_mut::<TileMap>() {
+            self.tiles = tile_map.set_tiles(std::mem::take(&mut self.tiles));
+        }
+    }
+}
+
+impl CommandTrait for SetTileMapTilesCommand {
+    fn name(&mut self, _: &dyn CommandContext) -> String {
+        "Draw Tiles".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context);
+
+        if self.thumbnail.is_none() {
+            let context = context.get::<GameSceneContext>();
+            if let Some(tile_map) = context.scene.graph[self.tile_map].cast::<TileMap>() {
+                if let Some((origin, size)) = changed_region(tile_map.tiles(), &self.tiles) {
+                    let texels_per_cell = (THUMBNAIL_SIZE / size.max()).clamp(1, 8);
+                    let size = size.map(|c| c.min(THUMBNAIL_SIZE));
+                    self.thumbnail = tile_map.render_preview(origin, size, texels_per_cell);
+                }
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context);
+    }
+
+    fn thumbnail(&self) -> Option<TextureResource> {
+        self.thumbnail.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        command::CommandTrait,
+        fyrox::{
+            asset::manager::ResourceManager,
+            core::{algebra::Vector2, task::TaskPool},
+            engine::SerializationContext,
+            scene::{
+                base::BaseBuilder,
+                snapshot::SceneSnapshot,
+                tilemap::{Tile, TileMapBuilder},
+                Scene,
+            },
+        },
+        message::MessageSender,
+        plugins::tilemap::commands::SetTileMapTilesCommand,
+        scene::{clipboard::Clipboard, commands::GameSceneContext, Selection},
+    };
+    use std::sync::{mpsc::channel, Arc};
+
+    #[test]
+    fn test_set_tile_map_tiles() {
+        let mut scene = Scene::new();
+        let tile_map =
+            TileMapBuilder::new(BaseBuilder::new().with_name("TileMap")).build(&mut scene.graph);
+        let initial = SceneSnapshot::new(&scene.graph);
+
+        let mut command =
+            SetTileMapTilesCommand::new(tile_map, vec![Tile::new(Vector2::new(5, -2), 0)]);
+
+        let mut scene_content_root = scene.graph.get_root();
+        let (sender, _receiver) = channel();
+        GameSceneContext::exec(
+            &mut Selection::new_empty(),
+            &mut scene,
+            &mut scene_content_root,
+            &mut Clipboard::default(),
+            MessageSender(sender),
+            ResourceManager::new(Arc::new(TaskPool::new())),
+            Arc::new(SerializationContext::new()),
+            |ctx| {
+                command.execute(ctx);
+                let modified = SceneSnapshot::new(&ctx.scene.graph);
+                assert_eq!(modified.get("__ROOT__/TileMap.tiles"), Some("[1]"));
+                assert_eq!(
+                    modified.get("__ROOT__/TileMap.tiles[0].position"),
+                    Some("(5, -2)")
+                );
+
+                command.revert(ctx);
+                SceneSnapshot::new(&ctx.scene.graph).assert_eq(&initial);
+
+                command.execute(ctx);
+                SceneSnapshot::new(&ctx.scene.graph).assert_eq(&modified);
+            },
+        );
+    }
+}
@@ -1,77 +1,510 @@
+pub mod commands;
 pub mod tileset;
 
 use crate::{
+    asset::registry::AssetTypeDefinition,
     fyrox::{
-        core::{algebra::Vector2, pool::Handle, type_traits::prelude::*, Uuid},
+        core::{
+            algebra::{Matrix4, Vector2, Vector3},
+            color::Color,
+            log::Log,
+            pool::Handle,
+            reflect::prelude::*,
+            type_traits::prelude::*,
+            Uuid,
+        },
         engine::Engine,
         graph::{BaseSceneGraph, SceneGraphNode},
-        gui::{message::UiMessage, BuildContext, UiNode},
-        scene::{node::Node, tilemap::TileMap},
+        gui::{
+            border::BorderBuilder,
+            brush::Brush,
+            button::{ButtonBuilder, ButtonMessage},
+            grid::{Column, GridBuilder, Row},
+            inspector::{
+                Inspector, InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+            },
+            message::{MessageDirection, UiMessage},
+            stack_panel::StackPanelBuilder,
+            text::TextBuilder,
+            utils::make_simple_tooltip,
+            widget::{WidgetBuilder, WidgetMessage},
+            window::{WindowBuilder, WindowMessage, WindowTitle},
+            wrap_panel::WrapPanelBuilder,
+            BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+            VerticalAlignment,
+        },
+        rand::thread_rng,
+        scene::{
+            graph::Graph,
+            node::Node,
+            tilemap::{
+                brush::{random_stamp, stamp, BrushTransform, TileMapBrush, TileMapSymmetry},
+                grid::{mapping_from_csv, TileGrid},
+                stamp::TileMapStampLibraryResource,
+                tileset::TileSet,
+                Tile, TileMap,
+            },
+        },
     },
-    interaction::{make_interaction_mode_button, InteractionMode},
+    inspector::editors::make_property_editors_container,
+    interaction::{
+        gizmo::handle::pick_node_plane_point, make_interaction_mode_button, InteractionMode,
+    },
+    message::MessageSender,
     plugin::EditorPlugin,
-    plugins::tilemap::tileset::TileSetEditor,
+    plugins::tilemap::{
+        commands::{SetStampLibraryStampsCommand, SetTileMapTilesCommand},
+        tileset::TileSetEditor,
+    },
+    recovery::RecoveredWindow,
     scene::{controller::SceneController, GameScene, Selection},
     settings::Settings,
-    Editor, Message,
+    Editor, Message, MSG_SYNC_FLAG,
+};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 
+#[derive(Clone, Debug, Reflect)]
+struct TileMapDrawSettings {
+    #[reflect(
+        description = "Tiles, that will be painted. Positions of the tiles are relative to the \
+    cursor. Hold Shift to erase."
+    )]
+    brush: TileMapBrush,
+    #[reflect(
+        description = "When set, every stroke paints a single tile of the brush, picked randomly \
+    according to the weights of the tiles."
+    )]
+    random: bool,
+    #[reflect(description = "Flipping and rotation of the brush.")]
+    transform: BrushTransform,
+    #[reflect(
+        description = "Symmetry of strokes. Every stroke is replicated around the origin \
+    according to the mode, mirrored strokes respect flipping and rotation of the brush."
+    )]
+    symmetry: TileMapSymmetry,
+    #[reflect(
+        description = "Name of a property layer of the tile set. When set, every tile of the \
+    tile map is colored by the value of the property, blue is the minimum and red is the maximum."
+    )]
+    overlay: String,
+    #[reflect(description = "A stamp library, that is used to save and load brushes.")]
+    stamp_library: Option<TileMapStampLibraryResource>,
+    #[reflect(description = "Name of a stamp in the stamp library.")]
+    stamp_name: String,
+    #[reflect(
+        description = "A path to a file (*.json or *.csv) with a grid of tile handles, that is used \
+    to exchange tile maps with external tools."
+    )]
+    grid_path: PathBuf,
+    #[reflect(
+        description = "An optional path to a CSV file with a handle-to-index mapping table (two \
+    columns: handle, tile index), that is used with CSV grids."
+    )]
+    grid_mapping_path: PathBuf,
+}
+
+impl Default for TileMapDrawSettings {
+    fn default() -> Self {
+        Self {
+            brush: TileMapBrush::single(0),
+            random: false,
+            transform: Default::default(),
+            symmetry: Default::default(),
+            overlay: Default::default(),
+            stamp_library: None,
+            stamp_name: Default::default(),
+            grid_path: "tile_map.json".into(),
+            grid_mapping_path: Default::default(),
+        }
+    }
+}
+
+/// Maps the given normalized value to a color of blue-green-yellow-red gradient.
+fn overlay_color(t: f32) -> Color {
+    const STOPS: [Color; 4] = [Color::BLUE, Color::GREEN, Color::YELLOW, Color::RED];
+    let t = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f32;
+    let index = (t.floor() as usize).min(STOPS.len() - 2);
+    STOPS[index].lerp(STOPS[index + 1], t - index as f32)
+}
+
+/// Amount of entries of the overlay legend.
+const LEGEND_STEPS: usize = 5;
+
+struct TileMapDrawPanel {
+    window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    legend: Handle<UiNode>,
+    save_stamp: Handle<UiNode>,
+    load_stamp: Handle<UiNode>,
+    export_grid: Handle<UiNode>,
+    import_grid: Handle<UiNode>,
+}
+
+fn make_button(text: &str, tooltip: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(100.0)
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl TileMapDrawPanel {
+    fn new(ctx: &mut BuildContext, settings: &TileMapDrawSettings, sender: MessageSender) -> Self {
+        let context = InspectorContext::from_object(
+            settings,
+            ctx,
+            Arc::new(make_property_editors_container(sender)),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+            Default::default(),
+        );
+
+        let inspector =
+            InspectorBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+                .with_context(context)
+                .build(ctx);
+
+        let save_stamp = make_button(
+            "Save Stamp",
+            "Saves the current brush in the stamp library with the given name.",
+            ctx,
+        );
+        let load_stamp = make_button(
+            "Load Stamp",
+            "Loads a brush with the given name from the stamp library.",
+            ctx,
+        );
+        let export_grid = make_button(
+            "Export Grid",
+            "Exports tiles of the tile map to the grid file.",
+            ctx,
+        );
+        let import_grid = make_button(
+            "Import Grid",
+            "Replaces tiles of the tile map with the tiles from the grid file.",
+            ctx,
+        );
+        let buttons = WrapPanelBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .with_horizontal_alignment(HorizontalAlignment::Right)
+                .with_child(save_stamp)
+                .with_child(load_stamp)
+                .with_child(export_grid)
+                .with_child(import_grid),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
+        let legend = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .build(ctx);
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(300.0))
+            .can_minimize(false)
+            .can_maximize(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(inspector)
+                        .with_child(buttons)
+                        .with_child(legend),
+                )
+                .add_row(Row::stretch())
+                .add_row(Row::auto())
+                .add_row(Row::auto())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .open(false)
+            .with_title(WindowTitle::text("Tile Map Drawing"))
+            .build(ctx);
+
+        Self {
+            window,
+            inspector,
+            legend,
+            save_stamp,
+            load_stamp,
+            export_grid,
+            import_grid,
+        }
+    }
+
+    fn sync_to_model(&self, settings: &TileMapDrawSettings, ui: &mut UserInterface) {
+        let ctx = ui
+            .node(self.inspector)
+            .cast::<Inspector>()
+            .unwrap()
+            .context()
+            .clone();
+
+        if let Err(sync_errors) = ctx.sync(settings, ui, 0, true, Default::default()) {
+            for error in sync_errors {
+                Log::err(format!("Failed to sync property. Reason: {:?}", error))
+            }
+        }
+    }
+
+    /// Fills the legend of the property overlay with color samples and their values. Empty range
+    /// clears the legend.
+    fn sync_legend(&self, range: Option<(f32, f32)>, ui: &mut UserInterface) {
+        for child in ui.node(self.legend).children().to_vec() {
+            ui.send_message(WidgetMessage::remove(child, MessageDirection::ToWidget));
+        }
+
+        let Some((min, max)) = range else {
+            return;
+        };
+
+        for step in 0..LEGEND_STEPS {
+            let t = step as f32 / (LEGEND_STEPS - 1) as f32;
+            let ctx = &mut ui.build_ctx();
+            let entry = StackPanelBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        BorderBuilder::new(
+                            WidgetBuilder::new()
+                                .with_width(16.0)
+                                .with_height(16.0)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_background(Brush::Solid(overlay_color(t))),
+                        )
+                        .build(ctx),
+                    )
+                    .with_child(
+                        TextBuilder::new(
+                            WidgetBuilder::new()
+                                .with_vertical_alignment(VerticalAlignment::Center)
+                                .with_margin(Thickness::left(4.0)),
+                        )
+                        .with_text(format!("{:.2}", min + (max - min) * t))
+                        .build(ctx),
+                    ),
+            )
+            .with_orientation(Orientation::Horizontal)
+            .build(ctx);
+            ui.send_message(WidgetMessage::link(
+                entry,
+                MessageDirection::ToWidget,
+                self.legend,
+            ));
+        }
+    }
+}
+
 #[derive(TypeUuidProvider)]
 #[type_uuid(id = "33fa8ef9-a29c-45d4-a493-79571edd870a")]
 pub struct TileMapInteractionMode {
-    #[allow(dead_code)]
     tile_map: Handle<Node>,
+    settings: TileMapDrawSettings,
+    panel: TileMapDrawPanel,
+    cursor: Option<Vector2<i32>>,
+    old_tiles: Option<Vec<Tile>>,
+    legend_range: Option<(f32, f32)>,
+    message_sender: MessageSender,
+    scene_viewer_frame: Handle<UiNode>,
+}
+
+impl TileMapInteractionMode {
+    fn pick(
+        &self,
+        graph: &Graph,
+        game_scene: &GameScene,
+        mouse_position: Vector2<f32>,
+        frame_size: Vector2<f32>,
+    ) -> Option<Vector2<i32>> {
+        let local = pick_node_plane_point(
+            graph,
+            game_scene.camera_controller.camera,
+            self.tile_map,
+            Vector3::z(),
+            mouse_position,
+            frame_size,
+        )?;
+        Some(Vector2::new(local.x.floor() as i32, local.y.floor() as i32))
+    }
+
+    fn stamp(&self, position: Vector2<i32>) -> Vec<Tile> {
+        if self.settings.random {
+            random_stamp(
+                &self.settings.brush,
+                &self.settings.symmetry,
+                position,
+                &mut thread_rng(),
+            )
+        } else {
+            stamp(
+                &self.settings.brush,
+                &self.settings.transform,
+                &self.settings.symmetry,
+                position,
+            )
+        }
+    }
+
+    /// Returns positions of cells, that will be painted by a stroke at the given position.
+    fn stamp_cells(&self, position: Vector2<i32>) -> Vec<Vector2<i32>> {
+        if self.settings.random {
+            self.settings.symmetry.images(position)
+        } else {
+            self.stamp(position).iter().map(|t| t.position()).collect()
+        }
+    }
+
+    fn paint(&self, graph: &mut Graph, erase: bool) {
+        let Some(cursor) = self.cursor else {
+            return;
+        };
+
+        let tiles = self.stamp(cursor);
+        let Some(tile_map) = graph
+            .try_get_mut(self.tile_map)
+            .and_then(|n| n.cast_mut::<TileMap>())
+        else {
+            return;
+        };
+
+        for tile in tiles {
+            let definition_index = if erase {
+                None
+            } else {
+                Some(tile.definition_index())
+            };
+            if tile_map.tile(tile.position()).map(|t| t.definition_index()) != definition_index {
+                tile_map.set_tile(tile.position(), definition_index);
+            }
+        }
+    }
+}
+
+impl TileMapDrawSettings {
+    fn read_grid(&self) -> Result<TileGrid, Box<dyn Error>> {
+        let data = std::fs::read_to_string(&self.grid_path)?;
+        if is_csv(&self.grid_path) {
+            let mapping = if self.grid_mapping_path.as_os_str().is_empty() {
+                Vec::new()
+            } else {
+                mapping_from_csv(&std::fs::read_to_string(&self.grid_mapping_path)?)?
+            };
+            Ok(TileGrid::from_csv(&data, Vector2::default(), mapping)?)
+        } else {
+            Ok(serde_json::from_str(&data)?)
+        }
+    }
+
+    fn write_grid(&self, tiles: &[Tile]) -> Result<(), Box<dyn Error>> {
+        if is_csv(&self.grid_path) {
+            let mapping = if self.grid_mapping_path.as_os_str().is_empty() {
+                Vec::new()
+            } else {
+                mapping_from_csv(&std::fs::read_to_string(&self.grid_mapping_path)?)?
+            };
+            let grid = TileGrid::from_tiles(tiles, mapping)?;
+            std::fs::write(&self.grid_path, grid.to_csv())?;
+        } else {
+            let grid = TileGrid::from_tiles(tiles, Vec::new())?;
+            std::fs::write(&self.grid_path, serde_json::to_string_pretty(&grid)?)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_csv(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
 }
 
 impl InteractionMode for TileMapInteractionMode {
     fn on_left_mouse_button_down(
         &mut self,
         _editor_selection: &Selection,
-        _controller: &mut dyn SceneController,
-        _engine: &mut Engine,
-        _mouse_pos: Vector2<f32>,
-        _frame_size: Vector2<f32>,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
         _settings: &Settings,
     ) {
-        // TODO
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let erase = engine.user_interfaces.first().keyboard_modifiers().shift;
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        if let Some(tile_map) = graph
+            .try_get(self.tile_map)
+            .and_then(|n| n.cast::<TileMap>())
+        {
+            self.old_tiles = Some(tile_map.tiles().to_vec());
+            self.cursor = self.pick(graph, game_scene, mouse_pos, frame_size);
+            self.paint(graph, erase);
+        }
     }
 
     fn on_left_mouse_button_up(
         &mut self,
         _editor_selection: &Selection,
-        _controller: &mut dyn SceneController,
-        _engine: &mut Engine,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
         _mouse_pos: Vector2<f32>,
         _frame_size: Vector2<f32>,
         _settings: &Settings,
     ) {
-        // TODO
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
+
+        let Some(old_tiles) = self.old_tiles.take() else {
+            return;
+        };
+
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        if let Some(tile_map) = graph
+            .try_get_mut(self.tile_map)
+            .and_then(|n| n.cast_mut::<TileMap>())
+        {
+            // Restore the original state and let the command apply the stroke, so it could be undone.
+            let new_tiles = tile_map.set_tiles(old_tiles);
+            self.message_sender
+                .do_command(SetTileMapTilesCommand::new(self.tile_map, new_tiles));
+        }
     }
 
     fn on_mouse_move(
         &mut self,
         _mouse_offset: Vector2<f32>,
-        _mouse_position: Vector2<f32>,
+        mouse_position: Vector2<f32>,
         _editor_selection: &Selection,
-        _controller: &mut dyn SceneController,
-        _engine: &mut Engine,
-        _frame_size: Vector2<f32>,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+        frame_size: Vector2<f32>,
         _settings: &Settings,
     ) {
-        // TODO
-    }
-
-    fn deactivate(&mut self, _controller: &dyn SceneController, _engine: &mut Engine) {
-        // TODO
-    }
+        let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+            return;
+        };
 
-    fn make_button(&mut self, ctx: &mut BuildContext, selected: bool) -> Handle<UiNode> {
-        make_interaction_mode_button(
-            ctx,
-            include_bytes!("../../../resources/tile.png"),
-            "Edit Tile Map",
-            selected,
-        )
+        let erase = engine.user_interfaces.first().keyboard_modifiers().shift;
+        let graph = &mut engine.scenes[game_scene.scene].graph;
+        let cursor = self.pick(graph, game_scene, mouse_position, frame_size);
+        if cursor != self.cursor {
+            self.cursor = cursor;
+            if self.old_tiles.is_some() {
+                self.paint(graph, erase);
+            }
+        }
     }
 
     fn update(
@@ -85,7 +518,220 @@ impl InteractionMode for TileMapInteractionMode {
             return;
         };
 
-        let _scene = &mut engine.scenes[game_scene.scene];
+        let scene = &mut engine.scenes[game_scene.scene];
+        let Some(tile_map) = scene
+            .graph
+            .try_get(self.tile_map)
+            .and_then(|n| n.cast::<TileMap>())
+        else {
+            return;
+        };
+
+        let transform = tile_map.global_transform();
+
+        // Color every tile by the value of the chosen property layer.
+        let mut range = None;
+        if let Some(tile_set) = tile_map
+            .tile_set()
+            .filter(|_| !self.settings.overlay.is_empty())
+        {
+            if let Some(tile_set) = tile_set.state().data() {
+                let layer = self.settings.overlay.as_str();
+                range = tile_set.property_range(layer);
+                if let Some((min, max)) = range {
+                    for tile in tile_map.tiles() {
+                        let Some(value) = tile_set.tile_property(tile.definition_index(), layer)
+                        else {
+                            continue;
+                        };
+                        let t = if max > min {
+                            (value - min) / (max - min)
+                        } else {
+                            0.0
+                        };
+                        let color = overlay_color(t);
+                        let center = tile.position().cast::<f32>().add_scalar(0.5);
+                        let cell = transform * Matrix4::new_translation(&center.to_homogeneous());
+                        scene
+                            .drawing_context
+                            .draw_rectangle(0.45, 0.45, cell, color);
+                        scene.drawing_context.draw_rectangle(0.3, 0.3, cell, color);
+                        scene
+                            .drawing_context
+                            .draw_rectangle(0.15, 0.15, cell, color);
+                    }
+                }
+            }
+        }
+
+        if range != self.legend_range {
+            self.legend_range = range;
+            self.panel
+                .sync_legend(range, engine.user_interfaces.first_mut());
+        }
+
+        // Highlight every cell, that will be painted, including mirrored ones.
+        if let Some(cursor) = self.cursor {
+            for cell in self.stamp_cells(cursor) {
+                let center = cell.cast::<f32>().add_scalar(0.5);
+                scene.drawing_context.draw_rectangle(
+                    0.5,
+                    0.5,
+                    transform * Matrix4::new_translation(&center.to_homogeneous()),
+                    Color::GREEN,
+                );
+            }
+        }
+
+        if self.settings.symmetry.mode != Default::default() {
+            let origin = self.settings.symmetry.origin;
+            scene.drawing_context.draw_circle(
+                origin.to_homogeneous(),
+                0.25,
+                16,
+                transform,
+                Color::ORANGE,
+            );
+        }
+    }
+
+    fn activate(&mut self, _controller: &dyn SceneController, engine: &mut Engine) {
+        engine
+            .user_interfaces
+            .first_mut()
+            .send_message(WindowMessage::open_and_align(
+                self.panel.window,
+                MessageDirection::ToWidget,
+                self.scene_viewer_frame,
+                HorizontalAlignment::Right,
+                VerticalAlignment::Top,
+                Thickness::top_right(5.0),
+                false,
+                false,
+            ));
+    }
+
+    fn deactivate(&mut self, _controller: &dyn SceneController, engine: &mut Engine) {
+        self.cursor = None;
+        engine
+            .user_interfaces
+            .first_mut()
+            .send_message(WindowMessage::close(
+                self.panel.window,
+                MessageDirection::ToWidget,
+            ));
+    }
+
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        _editor_selection: &Selection,
+        controller: &mut dyn SceneController,
+        engine: &mut Engine,
+    ) {
+        if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
+            if message.destination() == self.panel.inspector {
+                PropertyAction::from_field_kind(&property_changed.value).apply(
+                    &property_changed.path(),
+                    &mut self.settings,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.panel.export_grid
+                || message.destination() == self.panel.import_grid
+            {
+                let Some(game_scene) = controller.downcast_mut::<GameScene>() else {
+                    return;
+                };
+                let graph = &engine.scenes[game_scene.scene].graph;
+                let Some(tile_map) = graph
+                    .try_get(self.tile_map)
+                    .and_then(|n| n.cast::<TileMap>())
+                else {
+                    return;
+                };
+
+                if message.destination() == self.panel.export_grid {
+                    match self.settings.write_grid(tile_map.tiles()) {
+                        Ok(()) => Log::info(format!(
+                            "Tile map was successfully exported to {}",
+                            self.settings.grid_path.display()
+                        )),
+                        Err(err) => Log::err(format!("Unable to export tile map. Reason: {err}")),
+                    }
+                } else {
+                    match self
+                        .settings
+                        .read_grid()
+                        .and_then(|grid| Ok(grid.to_tiles()?))
+                    {
+                        Ok(tiles) => self
+                            .message_sender
+                            .do_command(SetTileMapTilesCommand::new(self.tile_map, tiles)),
+                        Err(err) => Log::err(format!("Unable to import tile map. Reason: {err}")),
+                    }
+                }
+                return;
+            }
+
+            let Some(library) = self.settings.stamp_library.clone() else {
+                Log::warn("Select a stamp library first!");
+                return;
+            };
+
+            if message.destination() == self.panel.save_stamp {
+                if self.settings.stamp_name.is_empty() {
+                    Log::warn("A stamp must have a name!");
+                    return;
+                }
+
+                let mut new_library = library.data_ref().clone();
+                new_library.set(&self.settings.stamp_name, self.settings.brush.clone());
+                self.message_sender
+                    .do_command(SetStampLibraryStampsCommand::new(
+                        library,
+                        new_library.stamps,
+                    ));
+            } else if message.destination() == self.panel.load_stamp {
+                let brush = library
+                    .data_ref()
+                    .find(&self.settings.stamp_name)
+                    .map(|stamp| stamp.brush.clone());
+                match brush {
+                    Some(brush) => {
+                        self.settings.brush = brush;
+                        self.panel
+                            .sync_to_model(&self.settings, engine.user_interfaces.first_mut());
+                    }
+                    None => Log::warn(format!(
+                        "There's no stamp with {} name in the library!",
+                        self.settings.stamp_name
+                    )),
+                }
+            }
+        }
+    }
+
+    fn on_drop(&mut self, engine: &mut Engine) {
+        engine
+            .user_interfaces
+            .first_mut()
+            .send_message(WidgetMessage::remove(
+                self.panel.window,
+                MessageDirection::ToWidget,
+            ));
+    }
+
+    fn make_button(&mut self, ctx: &mut BuildContext, selected: bool) -> Handle<UiNode> {
+        make_interaction_mode_button(
+            ctx,
+            include_bytes!("../../../resources/tile.png"),
+            "Edit Tile Map\n\nPaints tiles of the selected tile map. Hold Shift to erase.",
+            selected,
+        )
     }
 
     fn uuid(&self) -> Uuid {
@@ -99,25 +745,56 @@ pub struct TileMapEditorPlugin {
 }
 
 impl EditorPlugin for TileMapEditorPlugin {
+    fn on_start(&mut self, editor: &mut Editor) {
+        editor.register_asset_type(
+            AssetTypeDefinition::<TileSet>::new()
+                .with_template("Tile Set", TileSet::default)
+                .with_editor(|tile_set, editor| {
+                    editor
+                        .message_sender
+                        .send(Message::OpenTileSetEditor(tile_set))
+                }),
+        );
+    }
+
     fn on_ui_message(&mut self, message: &mut UiMessage, editor: &mut Editor) {
         if let Some(tile_set_editor) = self.tile_set_editor.take() {
-            self.tile_set_editor =
-                tile_set_editor.handle_ui_message(message, editor.engine.user_interfaces.first());
+            self.tile_set_editor = tile_set_editor.handle_ui_message(
+                message,
+                editor.engine.user_interfaces.first_mut(),
+                &mut editor.plugin_ui,
+                &editor.message_sender,
+            );
+        }
+    }
+
+    fn on_save_session(&self, windows: &mut Vec<RecoveredWindow>) {
+        if let Some(path) = self
+            .tile_set_editor
+            .as_ref()
+            .and_then(|tile_set_editor| tile_set_editor.tile_set().kind().into_path())
+        {
+            windows.push(RecoveredWindow::TileSetEditor(path));
         }
     }
 
     fn on_message(&mut self, message: &Message, editor: &mut Editor) {
         if let Message::OpenTileSetEditor(tile_set) = message {
             let ui = editor.engine.user_interfaces.first_mut();
-            let tile_set_editor = TileSetEditor::new(tile_set.clone(), &mut ui.build_ctx());
+            if let Some(tile_set_editor) = self.tile_set_editor.take() {
+                tile_set_editor.destroy(ui, &mut editor.plugin_ui);
+            }
+            let tile_set_editor = TileSetEditor::new(tile_set.clone(), ui, &mut editor.plugin_ui);
             self.tile_set_editor = Some(tile_set_editor);
         }
 
-        let Some(entry) = editor.scenes.current_scene_entry_mut() else {
+        let Message::SelectionChanged { .. } = message else {
             return;
         };
 
-        let Some(selection) = entry.selection.as_graph() else {
+        let scene_viewer_frame = editor.scene_viewer.frame();
+
+        let Some(entry) = editor.scenes.current_scene_entry_mut() else {
             return;
         };
 
@@ -125,25 +802,44 @@ impl EditorPlugin for TileMapEditorPlugin {
             return;
         };
 
-        let scene = &mut editor.engine.scenes[game_scene.scene];
-
-        if let Message::SelectionChanged { .. } = message {
-            entry
-                .interaction_modes
-                .remove_typed::<TileMapInteractionMode>();
+        if let Some(mut mode) = entry
+            .interaction_modes
+            .remove_typed::<TileMapInteractionMode>()
+        {
+            mode.on_drop(&mut editor.engine);
+        }
 
-            for node_handle in selection.nodes().iter() {
-                if let Some(collider) = scene.graph.try_get(*node_handle) {
-                    if collider.component_ref::<TileMap>().is_none() {
-                        continue;
-                    }
+        let Some(selection) = entry.selection.as_graph() else {
+            return;
+        };
 
-                    entry.interaction_modes.add(TileMapInteractionMode {
-                        tile_map: *node_handle,
-                    });
+        let scene = &editor.engine.scenes[game_scene.scene];
 
-                    break;
+        for node_handle in selection.nodes().iter() {
+            if let Some(node) = scene.graph.try_get(*node_handle) {
+                if node.component_ref::<TileMap>().is_none() {
+                    continue;
                 }
+
+                let ui = editor.engine.user_interfaces.first_mut();
+                let settings = TileMapDrawSettings::default();
+                let panel = TileMapDrawPanel::new(
+                    &mut ui.build_ctx(),
+                    &settings,
+                    editor.message_sender.clone(),
+                );
+                entry.interaction_modes.add(TileMapInteractionMode {
+                    tile_map: *node_handle,
+                    settings,
+                    panel,
+                    cursor: None,
+                    old_tiles: None,
+                    legend_range: None,
+                    message_sender: editor.message_sender.clone(),
+                    scene_viewer_frame,
+                });
+
+                break;
             }
         }
     }
@@ -1,51 +1,190 @@
 use crate::fyrox::{
-    core::pool::Handle,
+    asset::{untyped::ResourceKind, Resource},
+    core::{log::Log, pool::Handle},
     gui::{
-        button::ButtonBuilder,
+        border::BorderBuilder,
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        decorator::DecoratorBuilder,
         grid::{Column, GridBuilder, Row},
-        list_view::ListViewBuilder,
+        list_view::{ListViewBuilder, ListViewMessage},
         message::{MessageDirection, UiMessage},
+        searchbar::{SearchBarBuilder, SearchBarMessage},
         stack_panel::StackPanelBuilder,
+        text::TextBuilder,
         utils::make_simple_tooltip,
-        widget::{WidgetBuilder, WidgetMessage},
+        widget::WidgetBuilder,
         window::{WindowBuilder, WindowMessage, WindowTitle},
         wrap_panel::WrapPanelBuilder,
-        BuildContext, Orientation, Thickness, UiNode, UserInterface,
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
-    scene::tilemap::tileset::TileSetResource,
+    scene::tilemap::{
+        atlas::{repack_atlas, TileSetAtlasOptions},
+        tileset::{TileSet, TileSetResource},
+    },
+};
+use crate::{
+    message::MessageSender, plugin_ui::PluginUi, plugins::tilemap::commands::SetTileSetTilesCommand,
 };
 
 #[allow(dead_code)]
 pub struct TileSetEditor {
     window: Handle<UiNode>,
     tiles: Handle<UiNode>,
+    repack_atlas: Handle<UiNode>,
+    search_bar: Handle<UiNode>,
+    favorites_only: Handle<UiNode>,
     tile_set: TileSetResource,
+    filter: String,
+    show_favorites_only: bool,
 }
 
-impl TileSetEditor {
-    pub fn new(tile_set: TileSetResource, ctx: &mut BuildContext) -> Self {
-        let import;
-        let buttons = StackPanelBuilder::new(WidgetBuilder::new().on_row(0).with_child({
-            import = ButtonBuilder::new(
+fn make_tile_items(
+    tile_set: &TileSet,
+    filter: &str,
+    favorites_only: bool,
+    ctx: &mut BuildContext,
+) -> Vec<Handle<UiNode>> {
+    tile_set
+        .search(filter, favorites_only)
+        .map(|index| {
+            let tile = &tile_set.tiles[index];
+            let name = if tile.name.is_empty() {
+                format!("Tile {index}")
+            } else {
+                tile.name.clone()
+            };
+            let tooltip = if tile.tags.is_empty() {
+                "No tags".to_string()
+            } else {
+                tile.tags
+                    .iter()
+                    .map(|tag| format!("#{tag}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+
+            DecoratorBuilder::new(BorderBuilder::new(
                 WidgetBuilder::new()
-                    .with_width(100.0)
-                    .with_height(24.0)
+                    .with_width(90.0)
                     .with_margin(Thickness::uniform(1.0))
-                    .with_tooltip(make_simple_tooltip(
-                        ctx,
-                        "Import tile set from a sprite sheet.",
-                    )),
-            )
-            .with_text("Import...")
-            .build(ctx);
-            import
-        }))
+                    .with_tooltip(make_simple_tooltip(ctx, &tooltip))
+                    .with_child(
+                        TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+                            .with_text(if tile.favorite {
+                                format!("{name} (favorite)")
+                            } else {
+                                name
+                            })
+                            .build(ctx),
+                    ),
+            ))
+            .build(ctx)
+        })
+        .collect()
+}
+
+impl TileSetEditor {
+    pub fn tile_set(&self) -> &TileSetResource {
+        &self.tile_set
+    }
+
+    pub fn new(
+        tile_set: TileSetResource,
+        ui: &mut UserInterface,
+        plugin_ui: &mut PluginUi,
+    ) -> Self {
+        let ctx = &mut ui.build_ctx();
+        let import;
+        let repack_atlas;
+        let buttons = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .on_row(0)
+                .with_child({
+                    import = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_width(100.0)
+                            .with_height(24.0)
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Import tile set from a sprite sheet.",
+                            )),
+                    )
+                    .with_text("Import...")
+                    .build(ctx);
+                    import
+                })
+                .with_child({
+                    repack_atlas = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_width(100.0)
+                            .with_height(24.0)
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Repacks textures of every tile into a single atlas texture \
+                                with padding around each tile to prevent bleeding.",
+                            )),
+                    )
+                    .with_text("Repack Atlas")
+                    .build(ctx);
+                    repack_atlas
+                }),
+        )
         .with_orientation(Orientation::Horizontal)
         .build(ctx);
 
-        let tiles = ListViewBuilder::new(
+        let search_bar;
+        let favorites_only;
+        let filter_panel = GridBuilder::new(
             WidgetBuilder::new()
                 .on_row(1)
+                .with_child({
+                    search_bar = SearchBarBuilder::new(
+                        WidgetBuilder::new()
+                            .with_height(22.0)
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Search tiles by name or tag. Use #tag to match a tag exactly.",
+                            )),
+                    )
+                    .build(ctx);
+                    search_bar
+                })
+                .with_child({
+                    favorites_only = CheckBoxBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(1)
+                            .with_vertical_alignment(VerticalAlignment::Center)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .with_content(
+                        TextBuilder::new(WidgetBuilder::new())
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .with_text("Favorites")
+                            .build(ctx),
+                    )
+                    .checked(Some(false))
+                    .build(ctx);
+                    favorites_only
+                }),
+        )
+        .add_row(Row::auto())
+        .add_column(Column::stretch())
+        .add_column(Column::auto())
+        .build(ctx);
+
+        let items = tile_set
+            .state()
+            .data()
+            .map(|tile_set| make_tile_items(tile_set, "", false, ctx))
+            .unwrap_or_default();
+
+        let tiles = ListViewBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
                 .with_margin(Thickness::uniform(1.0)),
         )
         .with_items_panel(
@@ -53,49 +192,122 @@ impl TileSetEditor {
                 .with_orientation(Orientation::Horizontal)
                 .build(ctx),
         )
+        .with_items(items)
         .build(ctx);
 
-        let content = GridBuilder::new(WidgetBuilder::new().with_child(buttons).with_child(tiles))
-            .add_row(Row::auto())
-            .add_row(Row::stretch())
-            .add_column(Column::stretch())
-            .build(ctx);
-
-        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
-            .open(false)
-            .with_title(WindowTitle::text("Tile Set Editor"))
-            .with_content(content)
-            .build(ctx);
-
-        ctx.sender()
-            .send(WindowMessage::open(
-                window,
-                MessageDirection::ToWidget,
-                true,
-                true,
-            ))
-            .unwrap();
+        let content = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(buttons)
+                .with_child(filter_panel)
+                .with_child(tiles),
+        )
+        .add_row(Row::auto())
+        .add_row(Row::auto())
+        .add_row(Row::stretch())
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("TileSetEditor")
+                .with_width(300.0)
+                .with_height(400.0),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Tile Set Editor"))
+        .with_content(content)
+        .build(ctx);
+
+        plugin_ui.add_window(ui, window, None);
+        plugin_ui.open_window(ui, window);
 
         Self {
             window,
             tiles,
+            repack_atlas,
+            search_bar,
+            favorites_only,
             tile_set,
+            filter: Default::default(),
+            show_favorites_only: false,
         }
     }
 
-    fn destroy(self, ui: &UserInterface) {
-        ui.send_message(WidgetMessage::remove(
-            self.window,
+    fn sync_tiles(&self, ui: &mut UserInterface) {
+        let mut state = self.tile_set.state();
+        let Some(tile_set) = state.data() else {
+            return;
+        };
+        let items = make_tile_items(
+            tile_set,
+            &self.filter,
+            self.show_favorites_only,
+            &mut ui.build_ctx(),
+        );
+        drop(state);
+
+        ui.send_message(ListViewMessage::items(
+            self.tiles,
             MessageDirection::ToWidget,
+            items,
         ));
     }
 
-    pub fn handle_ui_message(self, message: &UiMessage, ui: &UserInterface) -> Option<Self> {
-        if let Some(WindowMessage::Close) = message.data() {
+    pub fn destroy(self, ui: &mut UserInterface, plugin_ui: &mut PluginUi) {
+        plugin_ui.remove_window(ui, self.window);
+    }
+
+    fn repack_atlas(&self, sender: &MessageSender) {
+        let mut state = self.tile_set.state();
+        let Some(tile_set) = state.data() else {
+            return;
+        };
+        let atlas = repack_atlas(tile_set, &TileSetAtlasOptions::default());
+        drop(state);
+
+        match atlas {
+            Ok(mut atlas) => {
+                let texture =
+                    Resource::new_ok(ResourceKind::Embedded, std::mem::take(&mut atlas.texture));
+                sender.do_command(SetTileSetTilesCommand::new(
+                    self.tile_set.clone(),
+                    atlas.into_tiles(texture),
+                ));
+            }
+            Err(err) => Log::err(format!("Unable to repack tile set atlas. Reason: {err}")),
+        }
+    }
+
+    pub fn handle_ui_message(
+        mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        plugin_ui: &mut PluginUi,
+        sender: &MessageSender,
+    ) -> Option<Self> {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.repack_atlas {
+                self.repack_atlas(sender);
+            }
+        } else if let Some(SearchBarMessage::Text(text)) = message.data() {
+            if message.destination() == self.search_bar
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.filter = text.clone();
+                self.sync_tiles(ui);
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.favorites_only
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.show_favorites_only = *value;
+                self.sync_tiles(ui);
+            }
+        } else if let Some(WindowMessage::Close) = message.data() {
             if message.destination() == self.window
                 && message.direction() == MessageDirection::FromWidget
             {
-                self.destroy(ui);
+                self.destroy(ui, plugin_ui);
                 return None;
             }
         }
@@ -0,0 +1,304 @@
+//! Crash recovery. The editor periodically writes the state of the current session (open scenes and
+//! windows) to the recovery folder of a project, scenes with unsaved changes are saved there as well.
+//! The session file is removed when the editor is closed normally, so if it exists on next start, the
+//! previous session was terminated abnormally and the editor offers to restore it.
+
+use crate::{
+    fyrox::{
+        core::{log::Log, pool::Handle},
+        gui::{
+            message::MessageDirection,
+            messagebox::{MessageBoxBuilder, MessageBoxButtons, MessageBoxMessage},
+            widget::WidgetBuilder,
+            window::{WindowBuilder, WindowTitle},
+            BuildContext, UiNode, UserInterface,
+        },
+    },
+    scene::container::EditorSceneEntry,
+    settings::{Settings, SettingsError},
+    Engine,
+};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// A scene, that was open in the previous session.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct RecoveredScene {
+    /// Original path of the scene, `None` if the scene was never saved.
+    pub path: Option<PathBuf>,
+    /// Path of the autosaved copy of the scene, `None` if the scene had no unsaved changes.
+    pub recovery_path: Option<PathBuf>,
+    /// Names of the actions, that were performed since the last save.
+    pub journal: Vec<String>,
+}
+
+/// An editor window, that was open in the previous session.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum RecoveredWindow {
+    TileSetEditor(PathBuf),
+}
+
+/// State of an editor session, that is enough to restore it after a crash.
+#[derive(Deserialize, Serialize, Clone, Default, Debug, PartialEq)]
+pub struct RecoverySession {
+    pub scenes: Vec<RecoveredScene>,
+    pub windows: Vec<RecoveredWindow>,
+}
+
+impl RecoverySession {
+    /// Name of the recovery folder, it is located in the root folder of a project.
+    pub const FOLDER: &'static str = ".recovery";
+    const FILE_NAME: &'static str = "session.ron";
+
+    fn file_path() -> PathBuf {
+        Path::new(Self::FOLDER).join(Self::FILE_NAME)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty() && self.windows.is_empty()
+    }
+
+    pub fn load() -> Result<Self, SettingsError> {
+        let file = File::open(Self::file_path())?;
+        Ok(ron::de::from_reader(file)?)
+    }
+
+    pub fn save(&self) -> Result<(), SettingsError> {
+        std::fs::create_dir_all(Self::FOLDER)?;
+        let mut file = File::create(Self::file_path())?;
+        file.write_all(ron::ser::to_string_pretty(self, PrettyConfig::default())?.as_bytes())?;
+        Ok(())
+    }
+
+    /// Removes the recovery folder with every autosaved scene in it.
+    pub fn clear() {
+        if Path::new(Self::FOLDER).exists() {
+            Log::verify(std::fs::remove_dir_all(Self::FOLDER));
+        }
+    }
+
+    /// Creates a human-readable description of the session.
+    pub fn describe(&self) -> String {
+        let mut text = String::new();
+        for scene in self.scenes.iter() {
+            let name = scene
+                .path
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|| String::from("Unnamed Scene"));
+            if scene.recovery_path.is_some() {
+                let _ = writeln!(text, "{} - {} unsaved change(s)", name, scene.journal.len());
+            } else {
+                let _ = writeln!(text, "{}", name);
+            }
+        }
+        for window in self.windows.iter() {
+            match window {
+                RecoveredWindow::TileSetEditor(path) => {
+                    let _ = writeln!(text, "Tile Set Editor - {}", path.display());
+                }
+            }
+        }
+        text
+    }
+}
+
+pub struct CrashRecovery {
+    message_box: Handle<UiNode>,
+    /// Session, that waits for the user's decision.
+    pending: Option<RecoverySession>,
+    /// `true` if the recovery is enabled for the current working directory.
+    active: bool,
+    time_since_autosave: f32,
+}
+
+impl CrashRecovery {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let message_box = MessageBoxBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(250.0))
+                .can_close(false)
+                .can_minimize(false)
+                .open(false)
+                .with_title(WindowTitle::text("Restore Previous Session")),
+        )
+        .with_buttons(MessageBoxButtons::YesNo)
+        .build(ctx);
+
+        Self {
+            message_box,
+            pending: None,
+            active: false,
+            time_since_autosave: 0.0,
+        }
+    }
+
+    pub fn message_box(&self) -> Handle<UiNode> {
+        self.message_box
+    }
+
+    /// Checks whether the previous session in the current working directory was terminated
+    /// abnormally and asks the user to restore it.
+    pub fn on_configure(&mut self, ui: &UserInterface) {
+        self.active = true;
+        self.time_since_autosave = 0.0;
+
+        let session = match RecoverySession::load() {
+            Ok(session) => session,
+            // There's no session file, which means that the previous session was closed normally.
+            Err(SettingsError::Io(_)) => return,
+            Err(e) => {
+                Log::warn(format!(
+                    "Unable to read crash recovery data, it will be discarded. Reason: {:?}",
+                    e
+                ));
+                RecoverySession::clear();
+                return;
+            }
+        };
+
+        if session.is_empty() {
+            RecoverySession::clear();
+            return;
+        }
+
+        ui.send_message(MessageBoxMessage::open(
+            self.message_box,
+            MessageDirection::ToWidget,
+            None,
+            Some(format!(
+                "The editor was not closed properly. Do you want to restore the previous \
+                session?\n\n{}",
+                session.describe()
+            )),
+        ));
+
+        self.pending = Some(session);
+    }
+
+    /// Takes the session, that was offered to restore. Must be called when the user has answered
+    /// the prompt.
+    pub fn take_pending(&mut self) -> Option<RecoverySession> {
+        self.pending.take()
+    }
+
+    /// Must be called when the editor is about to close normally or to change its working
+    /// directory.
+    pub fn on_exit(&mut self) {
+        if self.active && self.pending.is_none() {
+            RecoverySession::clear();
+        }
+        self.active = false;
+    }
+
+    /// Returns `true` if an autosave should be performed.
+    pub fn update(&mut self, dt: f32, settings: &Settings) -> bool {
+        // Recovery data must not be overwritten until the user decides what to do with it.
+        if !self.active || self.pending.is_some() || !settings.general.autosave {
+            return false;
+        }
+
+        self.time_since_autosave += dt;
+        if self.time_since_autosave >= settings.general.autosave_interval {
+            self.time_since_autosave = 0.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Saves the scenes with unsaved changes to the recovery folder and writes the session file.
+    pub fn autosave<'a>(
+        &self,
+        scenes: impl Iterator<Item = &'a mut EditorSceneEntry>,
+        windows: Vec<RecoveredWindow>,
+        settings: &Settings,
+        engine: &mut Engine,
+    ) {
+        if let Err(e) = std::fs::create_dir_all(RecoverySession::FOLDER) {
+            Log::err(format!(
+                "Unable to create the recovery folder. Reason: {:?}",
+                e
+            ));
+            return;
+        }
+
+        let mut session = RecoverySession {
+            scenes: Default::default(),
+            windows,
+        };
+
+        for entry in scenes {
+            let recovery_path = if entry.need_save() {
+                let path = Path::new(RecoverySession::FOLDER).join(format!(
+                    "{}.{}",
+                    entry.id,
+                    entry.controller.extension()
+                ));
+                // The entry must keep its original path, so the controller is used directly.
+                match entry.controller.save(&path, settings, engine) {
+                    Ok(_) => Some(path),
+                    Err(e) => {
+                        Log::err(format!("Autosave failed! Reason: {}", e));
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+
+            session.scenes.push(RecoveredScene {
+                path: entry.path.clone(),
+                recovery_path,
+                journal: entry.command_stack.journal.clone(),
+            });
+        }
+
+        if let Err(e) = session.save() {
+            Log::err(format!(
+                "Unable to save crash recovery data. Reason: {:?}",
+                e
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::recovery::{RecoveredScene, RecoveredWindow, RecoverySession};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_session_serialization() {
+        let session = RecoverySession {
+            scenes: vec![
+                RecoveredScene {
+                    path: Some(PathBuf::from("data/scene.rgs")),
+                    recovery_path: Some(PathBuf::from(".recovery/scene.rgs")),
+                    journal: vec!["Move Node".to_string(), "Undo Move Node".to_string()],
+                },
+                RecoveredScene {
+                    path: None,
+                    recovery_path: None,
+                    journal: Default::default(),
+                },
+            ],
+            windows: vec![RecoveredWindow::TileSetEditor(PathBuf::from(
+                "data/tiles.tileset",
+            ))],
+        };
+
+        let text = ron::to_string(&session).unwrap();
+        assert_eq!(ron::from_str::<RecoverySession>(&text).unwrap(), session);
+
+        let description = session.describe();
+        assert!(description.contains("data/scene.rgs - 2 unsaved change(s)"));
+        assert!(description.contains("Unnamed Scene"));
+        assert!(description.contains("Tile Set Editor - data/tiles.tileset"));
+    }
+}
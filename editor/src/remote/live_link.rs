@@ -0,0 +1,302 @@
+//! Live link allows DCC tools (like Blender) to push mesh, transform and material updates into the editor.
+//! Nodes are identified by their names, so the names in the DCC tool must match the names in the scene.
+//! All the data must be in engine's coordinate system, it is up to the DCC add-on to perform conversion.
+//!
+//! Transform and mesh updates are performed using regular editor commands, so they could be undone. Mesh
+//! updates replace surface data in place and material updates patch materials in place, so every node that
+//! shares the data is changed too. If the resource is loaded from a model file, the changes are temporary and
+//! will be discarded when the model is reimported (see `Reimport` remote command).
+//!
+//! A Blender add-on, that sends selected objects using this protocol, is located in `editor/tools/blender`.
+
+use crate::fyrox::{
+    asset::manager::ResourceManager,
+    core::{
+        algebra::{Quaternion, UnitQuaternion, Vector2, Vector3, Vector4},
+        color::Color,
+        math::TriangleDefinition,
+        pool::Handle,
+        sstorage::ImmutableString,
+    },
+    material::PropertyValue,
+    resource::texture::Texture,
+    scene::{
+        mesh::{
+            buffer::{TriangleBuffer, VertexBuffer},
+            surface::SurfaceData,
+            vertex::StaticVertex,
+            Mesh,
+        },
+        node::Node,
+        Scene,
+    },
+};
+use crate::{
+    command::{Command, CommandGroup},
+    message::MessageSender,
+    scene::commands::{
+        graph::{MoveNodeCommand, RotateNodeCommand, ScaleNodeCommand},
+        mesh::SetMeshSurfaceDataCommand,
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// New local transform of a node. Missing parts of the transform are left unchanged.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct TransformUpdate {
+    pub node: String,
+    #[serde(default)]
+    pub position: Option<[f32; 3]>,
+    /// Rotation quaternion in `[x, y, z, w]` order.
+    #[serde(default)]
+    pub rotation: Option<[f32; 4]>,
+    #[serde(default)]
+    pub scale: Option<[f32; 3]>,
+}
+
+/// New geometry of a single surface. Normals and texture coordinates are optional, if normals are
+/// missing, they will be calculated automatically.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct SurfaceUpdate {
+    pub positions: Vec<[f32; 3]>,
+    #[serde(default)]
+    pub normals: Vec<[f32; 3]>,
+    #[serde(default)]
+    pub uvs: Vec<[f32; 2]>,
+    pub triangles: Vec<[u32; 3]>,
+}
+
+/// New geometry of every surface of a mesh node.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct MeshUpdate {
+    pub node: String,
+    pub surfaces: Vec<SurfaceUpdate>,
+}
+
+/// New values of material properties of a surface of a mesh node. Texture properties expect a path
+/// to a texture. Color properties expect `[r, g, b, a]` with components in `[0; 1]` range (the same as
+/// colors in Blender), components out of the range are clamped.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct MaterialUpdate {
+    pub node: String,
+    #[serde(default)]
+    pub surface: usize,
+    pub properties: BTreeMap<String, Value>,
+}
+
+pub fn update_transform(
+    update: TransformUpdate,
+    node_handle: Handle<Node>,
+    scene: &Scene,
+    sender: &MessageSender,
+) {
+    let transform = scene.graph[node_handle].local_transform();
+
+    let mut commands = Vec::new();
+    if let Some(position) = update.position {
+        commands.push(Command::new(MoveNodeCommand::new(
+            node_handle,
+            **transform.position(),
+            Vector3::from(position),
+        )));
+    }
+    if let Some([x, y, z, w]) = update.rotation {
+        commands.push(Command::new(RotateNodeCommand::new(
+            node_handle,
+            **transform.rotation(),
+            UnitQuaternion::from_quaternion(Quaternion::new(w, x, y, z)),
+        )));
+    }
+    if let Some(scale) = update.scale {
+        commands.push(Command::new(ScaleNodeCommand::new(
+            node_handle,
+            **transform.scale(),
+            Vector3::from(scale),
+        )));
+    }
+
+    if !commands.is_empty() {
+        sender.do_command(CommandGroup::from(commands));
+    }
+}
+
+fn make_surface_data(update: SurfaceUpdate) -> Result<SurfaceData, String> {
+    let vertex_count = update.positions.len();
+    if !update.normals.is_empty() && update.normals.len() != vertex_count {
+        return Err("Normal count does not match vertex count.".to_string());
+    }
+    if !update.uvs.is_empty() && update.uvs.len() != vertex_count {
+        return Err("Texture coordinate count does not match vertex count.".to_string());
+    }
+    if update
+        .triangles
+        .iter()
+        .flatten()
+        .any(|&index| index as usize >= vertex_count)
+    {
+        return Err("Triangle index is out of bounds.".to_string());
+    }
+
+    let vertices = update
+        .positions
+        .iter()
+        .enumerate()
+        .map(|(i, position)| {
+            StaticVertex::from_pos_uv_normal(
+                Vector3::from(*position),
+                update
+                    .uvs
+                    .get(i)
+                    .map(|uv| Vector2::from(*uv))
+                    .unwrap_or_default(),
+                update
+                    .normals
+                    .get(i)
+                    .map(|normal| Vector3::from(*normal))
+                    .unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut data = SurfaceData::new(
+        VertexBuffer::new(vertex_count, vertices).map_err(|err| format!("{err:?}"))?,
+        TriangleBuffer::new(
+            update
+                .triangles
+                .into_iter()
+                .map(TriangleDefinition)
+                .collect(),
+        ),
+    );
+    if update.normals.is_empty() {
+        data.calculate_normals().map_err(|err| format!("{err:?}"))?;
+    }
+    data.calculate_tangents()
+        .map_err(|err| format!("{err:?}"))?;
+    Ok(data)
+}
+
+fn mesh_ref(scene: &Scene, node_handle: Handle<Node>) -> Result<&Mesh, String> {
+    scene.graph[node_handle]
+        .cast::<Mesh>()
+        .ok_or_else(|| "The node is not a mesh.".to_string())
+}
+
+pub fn update_mesh(
+    update: MeshUpdate,
+    node_handle: Handle<Node>,
+    scene: &Scene,
+    sender: &MessageSender,
+) -> Result<(), String> {
+    let mesh = mesh_ref(scene, node_handle)?;
+    if mesh.surfaces().len() != update.surfaces.len() {
+        return Err(format!(
+            "Surface count mismatch: the mesh has {} surfaces, but {} were sent.",
+            mesh.surfaces().len(),
+            update.surfaces.len()
+        ));
+    }
+
+    // Validate everything first, so the mesh won't be patched partially.
+    let surfaces = update
+        .surfaces
+        .into_iter()
+        .map(make_surface_data)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    sender.do_command(SetMeshSurfaceDataCommand::new(node_handle, surfaces));
+
+    Ok(())
+}
+
+fn floats<const N: usize>(value: &Value) -> Option<[f32; N]> {
+    let array = value.as_array().filter(|array| array.len() == N)?;
+    let mut result = [0.0; N];
+    for (dest, item) in result.iter_mut().zip(array) {
+        *dest = item.as_f64()? as f32;
+    }
+    Some(result)
+}
+
+/// Converts a JSON value to a material property value of the same kind as the given one.
+fn convert_property(
+    current: &PropertyValue,
+    value: &Value,
+    resource_manager: &ResourceManager,
+) -> Option<PropertyValue> {
+    Some(match current {
+        PropertyValue::Float(_) => PropertyValue::Float(value.as_f64()? as f32),
+        PropertyValue::Int(_) => PropertyValue::Int(i32::try_from(value.as_i64()?).ok()?),
+        PropertyValue::UInt(_) => PropertyValue::UInt(u32::try_from(value.as_u64()?).ok()?),
+        PropertyValue::Bool(_) => PropertyValue::Bool(value.as_bool()?),
+        PropertyValue::Vector2(_) => PropertyValue::Vector2(Vector2::from(floats::<2>(value)?)),
+        PropertyValue::Vector3(_) => PropertyValue::Vector3(Vector3::from(floats::<3>(value)?)),
+        PropertyValue::Vector4(_) => PropertyValue::Vector4(Vector4::from(floats::<4>(value)?)),
+        PropertyValue::Color(_) => {
+            let [r, g, b, a] =
+                floats::<4>(value)?.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+            PropertyValue::Color(Color::from_rgba(r, g, b, a))
+        }
+        PropertyValue::Sampler { fallback, .. } => PropertyValue::Sampler {
+            value: match value {
+                Value::Null => None,
+                _ => Some(resource_manager.request::<Texture>(value.as_str()?)),
+            },
+            fallback: *fallback,
+        },
+        _ => return None,
+    })
+}
+
+pub fn update_material(
+    update: MaterialUpdate,
+    node_handle: Handle<Node>,
+    scene: &Scene,
+    resource_manager: &ResourceManager,
+) -> Result<(), String> {
+    let mesh = mesh_ref(scene, node_handle)?;
+    let surface = mesh
+        .surfaces()
+        .get(update.surface)
+        .ok_or_else(|| format!("There's no surface with {} index.", update.surface))?;
+
+    let mut material = surface.material().data_ref();
+    for (name, value) in update.properties.iter() {
+        let name = ImmutableString::new(name);
+        let current = material
+            .property_ref(&name)
+            .ok_or_else(|| format!("The material has no {name} property."))?;
+        let new_value = convert_property(current, value, resource_manager)
+            .ok_or_else(|| format!("Invalid value of {name} property."))?;
+        material
+            .set_property(&name, new_value)
+            .map_err(|err| format!("{err:?}"))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fyrox::{
+        asset::manager::ResourceManager,
+        core::{color::Color, task::TaskPool},
+        material::PropertyValue,
+    };
+    use crate::remote::live_link::convert_property;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_convert_color_property() {
+        let resource_manager = ResourceManager::new(Arc::new(TaskPool::new()));
+        let current = PropertyValue::Color(Color::WHITE);
+        assert!(matches!(
+            convert_property(&current, &json!([1.0, 0.5, 0.0, 2.0]), &resource_manager),
+            Some(PropertyValue::Color(color)) if color == Color::from_rgba(255, 128, 0, 255)
+        ));
+        assert!(convert_property(&current, &json!([1.0, 0.5]), &resource_manager).is_none());
+    }
+}
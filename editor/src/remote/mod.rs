@@ -0,0 +1,302 @@
+//! Remote control server allows external tools (DCC bridge plugins, like Blender live link) to push changes
+//! into a running editor session. The server listens on a local TCP port and uses a simple line-based protocol:
+//! each request is a JSON object on a single line, for example:
+//!
+//! ```text
+//! {"command":"OpenScene","path":"data/level.rgs"}
+//! {"command":"SelectNode","name":"Crate"}
+//! {"command":"SetProperty","node":"Crate","path":"local_transform.local_position","value":[1.0,2.0,3.0]}
+//! {"command":"Reimport","path":"data/crate.fbx"}
+//! ```
+//!
+//! Live link commands (`UpdateTransform`, `UpdateMesh`, `UpdateMaterial`) are described in [`live_link`] module.
+//!
+//! The server answers each request with a single line JSON object: `{"ok":true}` or
+//! `{"ok":false,"error":"..."}`. Every connection must start with a handshake line with the token from
+//! the editor settings (`{"token":"..."}`), the connection is closed if the token is wrong or if a
+//! request is malformed. See [`LineServer`] docs for more info.
+
+use crate::fyrox::{
+    core::{log::Log, make_relative_path, pool::Handle, reflect::prelude::*},
+    engine::{inspection::json_to_reflect, Engine},
+    graph::{BaseSceneGraph, SceneGraph},
+    scene::node::Node,
+    utils::line_server::{LineResponse, LineServer},
+};
+use crate::{
+    command::{Command, SetPropertyCommand},
+    message::MessageSender,
+    remote::live_link::{MaterialUpdate, MeshUpdate, TransformUpdate},
+    scene::{commands::GameSceneContext, container::SceneContainer, GameScene},
+    settings::remote::RemoteControlSettings,
+    Message,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+
+pub mod live_link;
+pub mod runtime;
+
+/// A command, that could be sent to the editor by an external tool.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "command")]
+pub enum RemoteCommand {
+    /// Opens a scene at the given path.
+    OpenScene { path: PathBuf },
+    /// Selects a node with the given name in the current scene.
+    SelectNode { name: String },
+    /// Sets a new value of a property of a node with the given name in the current scene. The path is
+    /// a reflection path of the property (`local_transform.local_position`, for example).
+    SetProperty {
+        node: String,
+        path: String,
+        value: Value,
+    },
+    /// Reloads an already loaded resource at the given path.
+    Reimport { path: PathBuf },
+    /// Sets new local transform of a node.
+    UpdateTransform(TransformUpdate),
+    /// Replaces geometry of a mesh node.
+    UpdateMesh(MeshUpdate),
+    /// Sets new values of material properties of a mesh node.
+    UpdateMaterial(MaterialUpdate),
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RemoteResponse {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RemoteResponse {
+    fn from_result(result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => Self {
+                ok: true,
+                error: None,
+            },
+            Err(error) => Self {
+                ok: false,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+impl LineResponse for RemoteResponse {
+    fn success() -> Self {
+        Self::from_result(Ok(()))
+    }
+
+    fn failure(error: String) -> Self {
+        Self::from_result(Err(error))
+    }
+}
+
+struct RemoteControlServer {
+    server: LineServer<RemoteCommand, RemoteResponse>,
+    token: String,
+}
+
+/// Runs the remote control server according to the editor settings and executes incoming commands.
+#[derive(Default)]
+pub struct RemoteControl {
+    server: Option<RemoteControlServer>,
+    failed_port: Option<u16>,
+}
+
+impl RemoteControl {
+    pub fn is_running(&self) -> bool {
+        self.server.is_some()
+    }
+
+    fn sync_with_settings(&mut self, settings: &RemoteControlSettings) {
+        if !settings.enabled {
+            self.server = None;
+            self.failed_port = None;
+            return;
+        }
+
+        if self
+            .server
+            .as_ref()
+            .is_some_and(|s| s.server.port() == settings.port && s.token == settings.token)
+            || self.failed_port == Some(settings.port)
+        {
+            return;
+        }
+
+        self.server = None;
+        match LineServer::start("RemoteControlServer", settings.port, settings.token.clone()) {
+            Ok(server) => {
+                Log::info(format!(
+                    "Remote control server is listening on port {}.",
+                    settings.port
+                ));
+                self.server = Some(RemoteControlServer {
+                    server,
+                    token: settings.token.clone(),
+                });
+                self.failed_port = None;
+            }
+            Err(err) => {
+                Log::err(format!(
+                    "Unable to start remote control server on port {}. Reason: {err}",
+                    settings.port
+                ));
+                // Do not try to start the server every frame.
+                self.failed_port = Some(settings.port);
+            }
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        settings: &RemoteControlSettings,
+        scenes: &SceneContainer,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) {
+        self.sync_with_settings(settings);
+
+        let Some(server) = self.server.as_ref() else {
+            return;
+        };
+
+        while let Some(pending) = server.server.try_recv() {
+            let result = execute(pending.request, scenes, engine, sender);
+            if let Err(err) = result.as_ref() {
+                Log::warn(format!("Remote control command failed: {err}"));
+            }
+            pending
+                .responder
+                .respond(RemoteResponse::from_result(result));
+        }
+    }
+}
+
+fn current_game_scene(scenes: &SceneContainer) -> Result<&GameScene, String> {
+    scenes
+        .current_scene_controller_ref()
+        .and_then(|controller| controller.downcast_ref::<GameScene>())
+        .ok_or_else(|| "There's no opened game scene.".to_string())
+}
+
+fn find_node(scenes: &SceneContainer, engine: &Engine, name: &str) -> Result<Handle<Node>, String> {
+    let game_scene = current_game_scene(scenes)?;
+    engine.scenes[game_scene.scene]
+        .graph
+        .find_by_name_from_root(name)
+        .map(|(handle, _)| handle)
+        .ok_or_else(|| format!("There's no node with {name} name."))
+}
+
+fn execute(
+    command: RemoteCommand,
+    scenes: &SceneContainer,
+    engine: &mut Engine,
+    sender: &MessageSender,
+) -> Result<(), String> {
+    match command {
+        RemoteCommand::OpenScene { path } => {
+            sender.send(Message::LoadScene(path));
+        }
+        RemoteCommand::SelectNode { name } => {
+            let handle = find_node(scenes, engine, &name)?;
+            sender.send(Message::SelectObject {
+                handle: handle.into(),
+            });
+        }
+        RemoteCommand::SetProperty { node, path, value } => {
+            let handle = find_node(scenes, engine, &node)?;
+            let game_scene = current_game_scene(scenes)?;
+
+            let mut new_value = None;
+            engine.scenes[game_scene.scene].graph[handle].resolve_path(&path, &mut |result| {
+                new_value = Some(match result {
+                    Ok(property) => json_to_reflect(property, &value),
+                    Err(err) => Err(format!("Invalid property path {path}: {err}")),
+                });
+            });
+            let new_value = new_value.unwrap_or_else(|| Err("Unknown property.".to_string()))?;
+
+            sender.send(Message::DoCommand(Command::new(SetPropertyCommand::new(
+                path,
+                new_value,
+                move |ctx| {
+                    ctx.get_mut::<GameSceneContext>()
+                        .scene
+                        .graph
+                        .node_mut(handle)
+                },
+            ))));
+        }
+        RemoteCommand::Reimport { path } => {
+            let path = make_relative_path(&path).unwrap_or(path);
+            if !engine
+                .resource_manager
+                .state()
+                .try_reload_resource_from_path(&path)
+            {
+                return Err(format!("Resource {} is not loaded.", path.display()));
+            }
+        }
+        RemoteCommand::UpdateTransform(update) => {
+            let handle = find_node(scenes, engine, &update.node)?;
+            let scene = &engine.scenes[current_game_scene(scenes)?.scene];
+            live_link::update_transform(update, handle, scene, sender);
+        }
+        RemoteCommand::UpdateMesh(update) => {
+            let handle = find_node(scenes, engine, &update.node)?;
+            let scene = &engine.scenes[current_game_scene(scenes)?.scene];
+            live_link::update_mesh(update, handle, scene, sender)?;
+        }
+        RemoteCommand::UpdateMaterial(update) => {
+            let handle = find_node(scenes, engine, &update.node)?;
+            let scene = &engine.scenes[current_game_scene(scenes)?.scene];
+            live_link::update_material(update, handle, scene, &engine.resource_manager)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::remote::{live_link::TransformUpdate, RemoteCommand};
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_command() {
+        let command = serde_json::from_str::<RemoteCommand>(
+            r#"{"command":"SetProperty","node":"Crate","path":"local_transform.local_position","value":[1.0,2.0,3.0]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            command,
+            RemoteCommand::SetProperty {
+                node: "Crate".to_string(),
+                path: "local_transform.local_position".to_string(),
+                value: json!([1.0, 2.0, 3.0]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_live_link_command() {
+        let command = serde_json::from_str::<RemoteCommand>(
+            r#"{"command":"UpdateTransform","node":"Crate","position":[1.0,2.0,3.0]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            command,
+            RemoteCommand::UpdateTransform(TransformUpdate {
+                node: "Crate".to_string(),
+                position: Some([1.0, 2.0, 3.0]),
+                rotation: None,
+                scale: None,
+            })
+        );
+    }
+}
@@ -0,0 +1,714 @@
+//! Runtime inspector attaches to a game, that was launched from the editor, and shows its scenes and
+//! properties of their nodes while the game is running. It is a client of the inspection server of the
+//! game (see [`crate::fyrox::engine::inspection`] module docs for more info about the protocol).
+//! Properties could be changed only if the game allows it.
+
+use crate::fyrox::{
+    core::{color::Color, log::Log, pool::Handle, scope_profile},
+    engine::inspection::{
+        InspectionAccess, InspectionData, InspectionRequest, InspectionResponse, PropertyInfo,
+        SceneInfo,
+    },
+    graph::BaseSceneGraph,
+    gui::{
+        border::BorderBuilder,
+        brush::Brush,
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        text_box::TextBoxBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowTitle},
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+    scene::{node::Node, Scene},
+    utils::line_server::Handshake,
+};
+use crate::{settings::remote::RemoteControlSettings, Mode};
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Write},
+    net::{Ipv4Addr, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+enum ClientEvent {
+    Connected,
+    Response(InspectionResponse),
+    Disconnected,
+}
+
+/// A connection to the inspection server of a running game. All the networking is done in a separate
+/// thread, because the game could start listening a bit later than its process was started.
+struct InspectionClient {
+    stop: Arc<AtomicBool>,
+    sender: Sender<InspectionRequest>,
+    receiver: Receiver<ClientEvent>,
+}
+
+impl InspectionClient {
+    const CONNECTION_ATTEMPT_INTERVAL: Duration = Duration::from_millis(500);
+    const MAX_CONNECTION_ATTEMPTS: usize = 60;
+
+    fn connect(port: u16, token: String) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let (request_sender, request_receiver) = mpsc::channel::<InspectionRequest>();
+        let (event_sender, event_receiver) = mpsc::channel();
+
+        let thread_stop = stop.clone();
+        let result = thread::Builder::new()
+            .name("InspectionClient".to_string())
+            .spawn(move || {
+                let mut stream = None;
+                for _ in 0..Self::MAX_CONNECTION_ATTEMPTS {
+                    if thread_stop.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    match TcpStream::connect((Ipv4Addr::LOCALHOST, port)) {
+                        Ok(connection) => {
+                            stream = Some(connection);
+                            break;
+                        }
+                        Err(_) => thread::sleep(Self::CONNECTION_ATTEMPT_INTERVAL),
+                    }
+                }
+
+                let Some(stream) = stream else {
+                    let _ = event_sender.send(ClientEvent::Disconnected);
+                    return;
+                };
+                let _ = event_sender.send(ClientEvent::Connected);
+
+                if let Err(err) =
+                    Self::serve(stream, &token, request_receiver, &event_sender, thread_stop)
+                {
+                    Log::warn(format!("Runtime inspection connection error: {err}"));
+                }
+                let _ = event_sender.send(ClientEvent::Disconnected);
+            });
+        if let Err(err) = result {
+            Log::err(format!("Unable to start runtime inspection client: {err}"));
+        }
+
+        Self {
+            stop,
+            sender: request_sender,
+            receiver: event_receiver,
+        }
+    }
+
+    fn serve(
+        stream: TcpStream,
+        token: &str,
+        requests: Receiver<InspectionRequest>,
+        events: &Sender<ClientEvent>,
+        stop: Arc<AtomicBool>,
+    ) -> std::io::Result<()> {
+        let mut writer = stream.try_clone()?;
+        let mut reader = BufReader::new(stream);
+
+        let mut line = serde_json::to_string(&Handshake {
+            token: token.to_string(),
+        })?;
+        line.push('\n');
+        writer.write_all(line.as_bytes())?;
+        line.clear();
+        reader.read_line(&mut line)?;
+        if !serde_json::from_str::<InspectionResponse>(&line).is_ok_and(|response| response.ok) {
+            return Err(std::io::Error::new(
+                ErrorKind::PermissionDenied,
+                "The game has rejected the token.",
+            ));
+        }
+        // The loop ends when the inspector drops the client (and the request sender with it).
+        while let Ok(request) = requests.recv() {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let mut line = serde_json::to_string(&request)?;
+            line.push('\n');
+            writer.write_all(line.as_bytes())?;
+
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            match serde_json::from_str::<InspectionResponse>(&line) {
+                Ok(response) => {
+                    if events.send(ClientEvent::Response(response)).is_err() {
+                        break;
+                    }
+                }
+                Err(err) => Log::warn(format!("Malformed inspection response: {err}")),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for InspectionClient {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum ConnectionState {
+    Connecting,
+    Connected,
+    Disconnected,
+}
+
+/// A window, that shows the scenes of a running game and properties of the selected node.
+pub struct RuntimeInspector {
+    pub window: Handle<UiNode>,
+    status: Handle<UiNode>,
+    refresh: Handle<UiNode>,
+    nodes: Handle<UiNode>,
+    properties: Handle<UiNode>,
+    path: Handle<UiNode>,
+    value: Handle<UiNode>,
+    apply: Handle<UiNode>,
+    client: Option<InspectionClient>,
+    state: ConnectionState,
+    pending_requests: usize,
+    time_to_refresh: f32,
+    access: InspectionAccess,
+    scenes: Vec<SceneInfo>,
+    node_items: Vec<(Handle<Scene>, Handle<Node>)>,
+    selected: Option<(Handle<Scene>, Handle<Node>)>,
+    property_infos: Vec<PropertyInfo>,
+    path_str: String,
+    value_str: String,
+}
+
+impl RuntimeInspector {
+    /// Interval (in seconds) between automatic updates of the shown data.
+    const REFRESH_INTERVAL: f32 = 1.0;
+    const PLAY_MODE_ONLY: &'static str = "Runtime inspection is available in play mode only.";
+
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let status;
+        let refresh;
+        let nodes;
+        let properties;
+        let path;
+        let value;
+        let apply;
+
+        let make_list = |ctx: &mut BuildContext, row: usize, column: usize| {
+            ListViewBuilder::new(
+                WidgetBuilder::new()
+                    .on_row(row)
+                    .on_column(column)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .with_scroll_viewer(
+                ScrollViewerBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(3.0)))
+                    .with_horizontal_scroll_allowed(true)
+                    .with_vertical_scroll_allowed(true)
+                    .build(ctx),
+            )
+            .build(ctx)
+        };
+
+        let property_editor = GridBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .with_child({
+                    path = TextBoxBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(0)
+                            .with_enabled(false)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .build(ctx);
+                    path
+                })
+                .with_child({
+                    value = TextBoxBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(1)
+                            .with_enabled(false)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .build(ctx);
+                    value
+                })
+                .with_child({
+                    apply = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(2)
+                            .with_enabled(false)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .with_text("Set")
+                    .build(ctx);
+                    apply
+                }),
+        )
+        .add_row(Row::stretch())
+        .add_column(Column::stretch())
+        .add_column(Column::stretch())
+        .add_column(Column::strict(50.0))
+        .build(ctx);
+
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("RuntimeInspector")
+                .with_width(600.0)
+                .with_height(400.0),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Runtime Inspector"))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(0)
+                                .with_child({
+                                    refresh = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(80.0)
+                                            .with_enabled(false)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Refresh")
+                                    .build(ctx);
+                                    refresh
+                                })
+                                .with_child({
+                                    status = TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_margin(Thickness::uniform(2.0))
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text(Self::PLAY_MODE_ONLY)
+                                    .build(ctx);
+                                    status
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    )
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .with_child({
+                                    nodes = make_list(ctx, 0, 0);
+                                    nodes
+                                })
+                                .with_child(
+                                    GridBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(1)
+                                            .with_child({
+                                                properties = make_list(ctx, 0, 0);
+                                                properties
+                                            })
+                                            .with_child(property_editor),
+                                    )
+                                    .add_row(Row::stretch())
+                                    .add_row(Row::strict(26.0))
+                                    .add_column(Column::stretch())
+                                    .build(ctx),
+                                ),
+                        )
+                        .add_row(Row::stretch())
+                        .add_column(Column::strict(200.0))
+                        .add_column(Column::stretch())
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::strict(26.0))
+            .add_row(Row::stretch())
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            status,
+            refresh,
+            nodes,
+            properties,
+            path,
+            value,
+            apply,
+            client: None,
+            state: ConnectionState::Disconnected,
+            pending_requests: 0,
+            time_to_refresh: 0.0,
+            access: InspectionAccess::ReadOnly,
+            scenes: Default::default(),
+            node_items: Default::default(),
+            selected: None,
+            property_infos: Default::default(),
+            path_str: Default::default(),
+            value_str: Default::default(),
+        }
+    }
+
+    fn set_status(&self, ui: &UserInterface, status: impl Into<String>) {
+        ui.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            status.into(),
+        ));
+    }
+
+    fn set_enabled(&self, ui: &UserInterface, widget: Handle<UiNode>, enabled: bool) {
+        ui.send_message(WidgetMessage::enabled(
+            widget,
+            MessageDirection::ToWidget,
+            enabled,
+        ));
+    }
+
+    fn clear(&mut self, ui: &UserInterface) {
+        self.scenes.clear();
+        self.node_items.clear();
+        self.selected = None;
+        self.property_infos.clear();
+        self.pending_requests = 0;
+        for list in [self.nodes, self.properties] {
+            ui.send_message(ListViewMessage::items(
+                list,
+                MessageDirection::ToWidget,
+                vec![],
+            ));
+        }
+        for widget in [self.refresh, self.path, self.value, self.apply] {
+            self.set_enabled(ui, widget, false);
+        }
+    }
+
+    /// Connects to the game when it is launched and disconnects when it is closed.
+    pub fn on_mode_changed(
+        &mut self,
+        ui: &UserInterface,
+        mode: &Mode,
+        settings: &RemoteControlSettings,
+    ) {
+        self.client = None;
+        self.clear(ui);
+
+        match mode {
+            Mode::Play { .. } if settings.runtime_inspection => {
+                self.client = Some(InspectionClient::connect(
+                    settings.runtime_inspection_port,
+                    settings.token.clone(),
+                ));
+                self.state = ConnectionState::Connecting;
+                self.set_status(ui, "Connecting to the game...");
+            }
+            Mode::Play { .. } => {
+                self.state = ConnectionState::Disconnected;
+                self.set_status(
+                    ui,
+                    "Runtime inspection is disabled in the editor settings (Remote Control section).",
+                );
+            }
+            _ => {
+                self.state = ConnectionState::Disconnected;
+                self.set_status(ui, Self::PLAY_MODE_ONLY);
+            }
+        }
+    }
+
+    fn send(&mut self, request: InspectionRequest) {
+        if let Some(client) = self.client.as_ref() {
+            if client.sender.send(request).is_ok() {
+                self.pending_requests += 1;
+            }
+        }
+    }
+
+    fn request_update(&mut self) {
+        self.send(InspectionRequest::GetHierarchy);
+        if let Some((scene, node)) = self.selected {
+            self.send(InspectionRequest::GetNode { scene, node });
+        }
+    }
+
+    pub fn update(&mut self, dt: f32, ui: &mut UserInterface) {
+        scope_profile!();
+
+        let mut events = Vec::new();
+        if let Some(client) = self.client.as_ref() {
+            events.extend(client.receiver.try_iter());
+        }
+
+        for event in events {
+            match event {
+                ClientEvent::Connected => {
+                    self.state = ConnectionState::Connected;
+                    self.set_enabled(ui, self.refresh, true);
+                    self.request_update();
+                }
+                ClientEvent::Disconnected => {
+                    if self.state == ConnectionState::Connecting {
+                        self.set_status(ui, "Unable to connect to the game.");
+                    } else {
+                        self.set_status(ui, "The game has been disconnected.");
+                    }
+                    self.state = ConnectionState::Disconnected;
+                    self.client = None;
+                    self.set_enabled(ui, self.refresh, false);
+                    self.set_enabled(ui, self.apply, false);
+                }
+                ClientEvent::Response(response) => {
+                    self.pending_requests = self.pending_requests.saturating_sub(1);
+                    self.handle_response(response, ui);
+                }
+            }
+        }
+
+        if self.state != ConnectionState::Connected {
+            return;
+        }
+
+        // Do not spend time on the game if nobody looks at the data.
+        if !ui.node(self.window).visibility() {
+            return;
+        }
+
+        self.time_to_refresh -= dt;
+        if self.time_to_refresh <= 0.0 && self.pending_requests == 0 {
+            self.time_to_refresh = Self::REFRESH_INTERVAL;
+            self.request_update();
+        }
+    }
+
+    fn handle_response(&mut self, response: InspectionResponse, ui: &mut UserInterface) {
+        if !response.ok {
+            self.set_status(
+                ui,
+                format!(
+                    "Error: {}",
+                    response.error.as_deref().unwrap_or("Unknown error.")
+                ),
+            );
+            return;
+        }
+
+        match response.data {
+            Some(InspectionData::Hierarchy { access, scenes }) => {
+                if access != self.access {
+                    self.access = access;
+                    self.sync_editor(ui);
+                }
+                self.set_status(
+                    ui,
+                    match access {
+                        InspectionAccess::ReadOnly => "Connected (read-only).",
+                        InspectionAccess::Tweak => "Connected (tweaks are allowed).",
+                    },
+                );
+                if scenes != self.scenes {
+                    self.scenes = scenes;
+                    self.rebuild_nodes(ui);
+                }
+            }
+            Some(InspectionData::Node { properties }) if properties != self.property_infos => {
+                self.property_infos = properties;
+                self.rebuild_properties(ui);
+            }
+            _ => {}
+        }
+    }
+
+    fn make_item(
+        ctx: &mut BuildContext,
+        index: usize,
+        text: String,
+        foreground: Color,
+    ) -> Handle<UiNode> {
+        BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_background(Brush::Solid(if index % 2 == 0 {
+                    Color::opaque(70, 70, 70)
+                } else {
+                    Color::opaque(40, 40, 40)
+                }))
+                .with_child(
+                    TextBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(2.0))
+                            .with_foreground(Brush::Solid(foreground)),
+                    )
+                    .with_text(text)
+                    .build(ctx),
+                ),
+        )
+        .build(ctx)
+    }
+
+    fn rebuild_nodes(&mut self, ui: &mut UserInterface) {
+        let selected = self.selected;
+        self.node_items.clear();
+
+        let mut items = Vec::new();
+        let mut selection = None;
+        let ctx = &mut ui.build_ctx();
+        for scene in self.scenes.iter() {
+            for node in scene.nodes.iter() {
+                let index = items.len();
+                let name = if node.depth == 0 {
+                    format!("Scene {}", scene.handle)
+                } else {
+                    format!("{}{}", "  ".repeat(node.depth - 1), node.name)
+                };
+                let foreground = if node.enabled {
+                    Color::WHITE
+                } else {
+                    Color::GRAY
+                };
+                items.push(Self::make_item(ctx, index, name, foreground));
+                if selected == Some((scene.handle, node.handle)) {
+                    selection = Some(index);
+                }
+                self.node_items.push((scene.handle, node.handle));
+            }
+        }
+
+        ui.send_message(ListViewMessage::items(
+            self.nodes,
+            MessageDirection::ToWidget,
+            items,
+        ));
+        ui.send_message(ListViewMessage::selection(
+            self.nodes,
+            MessageDirection::ToWidget,
+            selection,
+        ));
+    }
+
+    fn rebuild_properties(&mut self, ui: &mut UserInterface) {
+        let ctx = &mut ui.build_ctx();
+        let items = self
+            .property_infos
+            .iter()
+            .enumerate()
+            .map(|(index, property)| {
+                let foreground = if property.editable {
+                    Color::WHITE
+                } else {
+                    Color::GRAY
+                };
+                Self::make_item(
+                    ctx,
+                    index,
+                    format!("{}: {}", property.path, property.value),
+                    foreground,
+                )
+            })
+            .collect::<Vec<_>>();
+        ui.send_message(ListViewMessage::items(
+            self.properties,
+            MessageDirection::ToWidget,
+            items,
+        ));
+    }
+
+    fn sync_editor(&self, ui: &UserInterface) {
+        let editable = self.access == InspectionAccess::Tweak && self.selected.is_some();
+        for widget in [self.path, self.value, self.apply] {
+            self.set_enabled(ui, widget, editable);
+        }
+    }
+
+    fn set_text_box(&self, ui: &UserInterface, text_box: Handle<UiNode>, text: String) {
+        ui.send_message(TextMessage::text(
+            text_box,
+            MessageDirection::ToWidget,
+            text,
+        ));
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, ui: &UserInterface) {
+        scope_profile!();
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.refresh {
+                if self.pending_requests == 0 {
+                    self.request_update();
+                }
+            } else if message.destination() == self.apply {
+                let Some((scene, node)) = self.selected else {
+                    return;
+                };
+                match serde_json::from_str(&self.value_str) {
+                    Ok(value) => {
+                        self.send(InspectionRequest::SetProperty {
+                            scene,
+                            node,
+                            path: self.path_str.clone(),
+                            value,
+                        });
+                        self.request_update();
+                    }
+                    Err(err) => self.set_status(ui, format!("Invalid JSON value: {err}")),
+                }
+            }
+        } else if let Some(ListViewMessage::SelectionChanged(selection)) = message.data() {
+            if message.direction() != MessageDirection::FromWidget {
+                return;
+            }
+
+            if message.destination() == self.nodes {
+                let selected = selection.and_then(|index| self.node_items.get(index).cloned());
+                if selected != self.selected {
+                    self.selected = selected;
+                    self.property_infos.clear();
+                    ui.send_message(ListViewMessage::items(
+                        self.properties,
+                        MessageDirection::ToWidget,
+                        vec![],
+                    ));
+                    if let Some((scene, node)) = selected {
+                        self.send(InspectionRequest::GetNode { scene, node });
+                    }
+                    self.sync_editor(ui);
+                }
+            } else if message.destination() == self.properties {
+                if let Some(property) = selection.and_then(|index| self.property_infos.get(index)) {
+                    self.path_str.clone_from(&property.path);
+                    self.value_str = property
+                        .json
+                        .as_ref()
+                        .map(|json| json.to_string())
+                        .unwrap_or_default();
+                    self.set_text_box(ui, self.path, self.path_str.clone());
+                    self.set_text_box(ui, self.value, self.value_str.clone());
+                }
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data() {
+            if message.direction() != MessageDirection::FromWidget {
+                return;
+            }
+
+            if message.destination() == self.path {
+                self.path_str.clone_from(text);
+            } else if message.destination() == self.value {
+                self.value_str.clone_from(text);
+            }
+        }
+    }
+}
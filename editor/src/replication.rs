@@ -0,0 +1,186 @@
+//! Replication panel shows every replicated node of the current scene along with its replication settings.
+//! Settings itself are edited in the inspector (`Replication` property of a node).
+
+use crate::fyrox::{
+    core::pool::Handle,
+    engine::Engine,
+    graph::SceneGraph,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+    scene::{node::Node, replication::ReplicationSettings},
+};
+use crate::{
+    message::MessageSender,
+    scene::{container::SceneContainer, GameScene},
+    Message,
+};
+
+pub struct ReplicationPanel {
+    pub window: Handle<UiNode>,
+    refresh: Handle<UiNode>,
+    list: Handle<UiNode>,
+    status: Handle<UiNode>,
+    nodes: Vec<Handle<Node>>,
+}
+
+fn describe(name: &str, settings: &ReplicationSettings) -> String {
+    let mut parts = Vec::new();
+    if settings.transform {
+        parts.push("transform".to_string());
+    }
+    parts.extend(settings.fields.iter().cloned());
+    let interpolation = if settings.interpolate {
+        "interpolated"
+    } else {
+        "not interpolated"
+    };
+    format!("{name}: {} ({interpolation})", parts.join(", "))
+}
+
+impl ReplicationPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let refresh = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(1.0))
+                .with_width(100.0),
+        )
+        .with_text("Refresh")
+        .build(ctx);
+
+        let list = ListViewBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .build(ctx);
+
+        let status = TextBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
+                .with_margin(Thickness::uniform(2.0)),
+        )
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .build(ctx);
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(400.0))
+            .open(false)
+            .with_title(WindowTitle::text("Replication"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            StackPanelBuilder::new(WidgetBuilder::new().with_child(refresh))
+                                .with_orientation(Orientation::Horizontal)
+                                .build(ctx),
+                        )
+                        .with_child(list)
+                        .with_child(status),
+                )
+                .add_row(Row::strict(26.0))
+                .add_row(Row::stretch())
+                .add_row(Row::strict(22.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            refresh,
+            list,
+            status,
+            nodes: Default::default(),
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+            true,
+        ));
+    }
+
+    fn sync_to_model(&mut self, scenes: &SceneContainer, engine: &mut Engine) {
+        self.nodes.clear();
+
+        let ui = engine.user_interfaces.first_mut();
+
+        let game_scene = scenes
+            .current_scene_controller_ref()
+            .and_then(|controller| controller.downcast_ref::<GameScene>());
+
+        let mut items = Vec::new();
+        if let Some(game_scene) = game_scene {
+            let graph = &engine.scenes[game_scene.scene].graph;
+            for (handle, node) in graph.pair_iter() {
+                let settings = node.replication();
+                if !settings.enabled {
+                    continue;
+                }
+
+                self.nodes.push(handle);
+                items.push(
+                    TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+                        .with_text(describe(node.name(), settings))
+                        .build(&mut ui.build_ctx()),
+                );
+            }
+        }
+
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+
+        let status = if game_scene.is_some() {
+            format!("{} replicated node(s)", self.nodes.len())
+        } else {
+            "There's no opened game scene.".to_string()
+        };
+        ui.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            status,
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        scenes: &SceneContainer,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.refresh {
+                self.sync_to_model(scenes, engine);
+            }
+        } else if let Some(WindowMessage::Open { .. }) = message.data() {
+            if message.destination() == self.window {
+                self.sync_to_model(scenes, engine);
+            }
+        } else if let Some(ListViewMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.list
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(handle) = self.nodes.get(*index) {
+                    sender.send(Message::SelectObject {
+                        handle: (*handle).into(),
+                    });
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! Alignment and distribution of selected scene nodes. See [`AlignAction`] docs for more info.
+
+use crate::{
+    camera::precise_ray_test,
+    command::{Command, CommandGroup},
+    fyrox::{
+        core::{
+            algebra::{Point3, Vector3},
+            math::{aabb::AxisAlignedBoundingBox, ray::Ray},
+            pool::Handle,
+        },
+        fxhash::FxHashSet,
+        graph::{BaseSceneGraph, SceneGraph},
+        scene::{graph::Graph, node::Node},
+    },
+    scene::commands::graph::MoveNodeCommand,
+    world::graph::selection::GraphSelection,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AlignAxis {
+    X,
+    Y,
+    Z,
+}
+
+impl AlignAxis {
+    pub const ALL: [AlignAxis; 3] = [AlignAxis::X, AlignAxis::Y, AlignAxis::Z];
+
+    fn index(self) -> usize {
+        match self {
+            AlignAxis::X => 0,
+            AlignAxis::Y => 1,
+            AlignAxis::Z => 2,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AlignAxis::X => "X",
+            AlignAxis::Y => "Y",
+            AlignAxis::Z => "Z",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AlignAnchor {
+    Min,
+    Center,
+    Max,
+}
+
+impl AlignAnchor {
+    pub const ALL: [AlignAnchor; 3] = [AlignAnchor::Min, AlignAnchor::Center, AlignAnchor::Max];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AlignAnchor::Min => "Min",
+            AlignAnchor::Center => "Center",
+            AlignAnchor::Max => "Max",
+        }
+    }
+}
+
+/// An action, that moves selected nodes relative to each other (or to the ground) using their
+/// world-space bounding boxes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AlignAction {
+    /// Moves nodes along the axis, so the given side (or center) of their bounding boxes matches the
+    /// same side of the bounding box of the whole selection.
+    Align {
+        axis: AlignAxis,
+        anchor: AlignAnchor,
+    },
+    /// Moves nodes along the axis, so their centers are evenly spaced between the two outermost
+    /// nodes.
+    Distribute { axis: AlignAxis },
+    /// Moves nodes down, so they lie on the first surface below them.
+    DropToGround,
+}
+
+impl AlignAction {
+    /// Returns every available action with its name.
+    pub fn all() -> Vec<(AlignAction, String)> {
+        let mut actions = Vec::new();
+        for axis in AlignAxis::ALL {
+            for anchor in AlignAnchor::ALL {
+                actions.push((
+                    AlignAction::Align { axis, anchor },
+                    format!("Align {} {}", anchor.name(), axis.name()),
+                ));
+            }
+        }
+        for axis in AlignAxis::ALL {
+            actions.push((
+                AlignAction::Distribute { axis },
+                format!("Distribute {}", axis.name()),
+            ));
+        }
+        actions.push((AlignAction::DropToGround, "Drop To Ground".to_string()));
+        actions
+    }
+}
+
+fn anchor_value(aabb: &AxisAlignedBoundingBox, axis: AlignAxis, anchor: AlignAnchor) -> f32 {
+    let i = axis.index();
+    match anchor {
+        AlignAnchor::Min => aabb.min[i],
+        AlignAnchor::Center => aabb.center()[i],
+        AlignAnchor::Max => aabb.max[i],
+    }
+}
+
+/// Calculates world-space offsets of the given bounding boxes for alignment or distribution. Drop to
+/// ground requires the scene, so it is not handled here and zero offsets are returned for it.
+pub fn calculate_offsets(
+    bounds: &[AxisAlignedBoundingBox],
+    action: AlignAction,
+) -> Vec<Vector3<f32>> {
+    let mut offsets = vec![Vector3::default(); bounds.len()];
+
+    match action {
+        AlignAction::Align { axis, anchor } => {
+            let Some(total) = bounds.iter().copied().reduce(|mut total, aabb| {
+                total.add_box(aabb);
+                total
+            }) else {
+                return offsets;
+            };
+            let target = anchor_value(&total, axis, anchor);
+            for (offset, aabb) in offsets.iter_mut().zip(bounds) {
+                offset[axis.index()] = target - anchor_value(aabb, axis, anchor);
+            }
+        }
+        AlignAction::Distribute { axis } => {
+            if bounds.len() < 3 {
+                return offsets;
+            }
+            let i = axis.index();
+            let mut order = (0..bounds.len()).collect::<Vec<_>>();
+            order.sort_by(|a, b| bounds[*a].center()[i].total_cmp(&bounds[*b].center()[i]));
+            let first = bounds[order[0]].center()[i];
+            let last = bounds[order[order.len() - 1]].center()[i];
+            let step = (last - first) / (order.len() - 1) as f32;
+            for (n, index) in order.into_iter().enumerate() {
+                offsets[index][i] = first + step * n as f32 - bounds[index].center()[i];
+            }
+        }
+        AlignAction::DropToGround => (),
+    }
+
+    offsets
+}
+
+fn ground_offset(
+    graph: &Graph,
+    aabb: &AxisAlignedBoundingBox,
+    ignored: &FxHashSet<Handle<Node>>,
+) -> Option<Vector3<f32>> {
+    let bottom = Vector3::new(aabb.center().x, aabb.min.y, aabb.center().z);
+    let ray = Ray::new(bottom, Vector3::new(0.0, -10000.0, 0.0));
+
+    graph
+        .pair_iter()
+        .filter(|(handle, node)| !ignored.contains(handle) && node.global_visibility())
+        .filter_map(|(_, node)| precise_ray_test(node, &ray, false))
+        .map(|(_, point)| point.y)
+        .reduce(f32::max)
+        .map(|ground| Vector3::new(0.0, ground - bottom.y, 0.0))
+}
+
+/// Creates a command, that applies the given action to the selected nodes. Returns [`None`] if
+/// there's nothing to move. Nodes under `editor_objects_root` are never treated as ground.
+pub fn make_align_command(
+    graph: &Graph,
+    selection: &GraphSelection,
+    action: AlignAction,
+    editor_objects_root: Handle<Node>,
+) -> Option<CommandGroup> {
+    // Children of selected nodes are moved by their parents.
+    let nodes = selection
+        .root_nodes(graph)
+        .into_iter()
+        .filter(|&node| graph.is_valid_handle(node))
+        .collect::<Vec<_>>();
+
+    let bounds = nodes
+        .iter()
+        .filter_map(|&node| graph.aabb_of_descendants(node, |_, _| true))
+        .collect::<Vec<_>>();
+
+    let offsets = if action == AlignAction::DropToGround {
+        let ignored = nodes
+            .iter()
+            .chain(std::iter::once(&editor_objects_root))
+            .flat_map(|&node| graph.traverse_handle_iter(node))
+            .collect::<FxHashSet<_>>();
+        bounds
+            .iter()
+            .map(|aabb| ground_offset(graph, aabb, &ignored).unwrap_or_default())
+            .collect()
+    } else {
+        calculate_offsets(&bounds, action)
+    };
+
+    let commands = nodes
+        .iter()
+        .zip(offsets)
+        .filter(|(_, offset)| *offset != Vector3::default())
+        .map(|(&node, offset)| {
+            let node_ref = &graph[node];
+            let old_position = **node_ref.local_transform().position();
+            let new_global_position = node_ref.global_position() + offset;
+            let new_position = graph
+                .try_get(node_ref.parent())
+                .and_then(|parent| parent.global_transform().try_inverse())
+                .map(|inv| {
+                    inv.transform_point(&Point3::from(new_global_position))
+                        .coords
+                })
+                .unwrap_or(new_global_position);
+            Command::new(MoveNodeCommand::new(node, old_position, new_position))
+        })
+        .collect::<Vec<_>>();
+
+    if commands.is_empty() {
+        None
+    } else {
+        Some(CommandGroup::from(commands).with_custom_name("Align Nodes"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        fyrox::core::{algebra::Vector3, math::aabb::AxisAlignedBoundingBox},
+        scene::align::{calculate_offsets, AlignAction, AlignAnchor, AlignAxis},
+    };
+
+    fn unit_box(x: f32) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(x, 0.0, 0.0),
+            Vector3::new(x + 1.0, 1.0, 1.0),
+        )
+    }
+
+    #[test]
+    fn test_align_and_distribute() {
+        let bounds = [unit_box(0.0), unit_box(4.0), unit_box(1.0)];
+
+        let offsets = calculate_offsets(
+            &bounds,
+            AlignAction::Align {
+                axis: AlignAxis::X,
+                anchor: AlignAnchor::Max,
+            },
+        );
+        assert_eq!(offsets[0].x, 4.0);
+        assert_eq!(offsets[1].x, 0.0);
+        assert_eq!(offsets[2].x, 3.0);
+
+        // The middle box should be moved to the center between the outermost ones.
+        let offsets = calculate_offsets(&bounds, AlignAction::Distribute { axis: AlignAxis::X });
+        assert_eq!(offsets[0].x, 0.0);
+        assert_eq!(offsets[1].x, 0.0);
+        assert_eq!(offsets[2].x, 1.0);
+        assert!(offsets.iter().all(|o| o.y == 0.0 && o.z == 0.0));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::fyrox::{
     core::pool::{Handle, Ticket},
-    scene::sound::AudioBus,
+    scene::sound::{AudioBus, AudioBusSend},
 };
 use crate::{command::CommandContext, CommandTrait, GameSceneContext};
 
@@ -152,3 +152,119 @@ impl CommandTrait for LinkAudioBuses {
         self.swap(context)
     }
 }
+
+#[derive(Debug)]
+pub struct AddAudioBusSendCommand {
+    bus: Handle<AudioBus>,
+    send: Option<AudioBusSend>,
+}
+
+impl AddAudioBusSendCommand {
+    pub fn new(bus: Handle<AudioBus>, send: AudioBusSend) -> Self {
+        Self {
+            bus,
+            send: Some(send),
+        }
+    }
+}
+
+impl CommandTrait for AddAudioBusSendCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Add Audio Bus Send".to_string()
+    }
+
+    fn execute(&mut self, context: &mut dyn CommandContext) {
+        let context = context.get_mut::<GameSceneContext>();
+        let mut state = context.scene.graph.sound_context.state();
+        if let Some(bus) = state.bus_graph_mut().try_get_bus_mut(self.bus) {
+            bus.add_send(self.send.take().unwrap());
+        }
+    }
+
+    fn revert(&mut self, context: &mut dyn CommandContext) {
+        let context = context.get_mut::<GameSceneContext>();
+        let mut state = context.scene.graph.sound_context.state();
+        if let Some(bus) = state.bus_graph_mut().try_get_bus_mut(self.bus) {
+            let last = bus.sends().len() - 1;
+            self.send = Some(bus.remove_send(last));
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RemoveAudioBusSendCommand {
+    bus: Handle<AudioBus>,
+    index: usize,
+    send: Option<AudioBusSend>,
+}
+
+impl RemoveAudioBusSendCommand {
+    pub fn new(bus: Handle<AudioBus>, index: usize) -> Self {
+        Self {
+            bus,
+            index,
+            send: None,
+        }
+    }
+}
+
+impl CommandTrait for RemoveAudioBusSendCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Remove Audio Bus Send".to_string()
+    }
+
+    fn execute(&mut self, context: &mut dyn CommandContext) {
+        let context = context.get_mut::<GameSceneContext>();
+        let mut state = context.scene.graph.sound_context.state();
+        if let Some(bus) = state.bus_graph_mut().try_get_bus_mut(self.bus) {
+            self.send = Some(bus.remove_send(self.index));
+        }
+    }
+
+    fn revert(&mut self, context: &mut dyn CommandContext) {
+        let context = context.get_mut::<GameSceneContext>();
+        let mut state = context.scene.graph.sound_context.state();
+        if let Some(bus) = state.bus_graph_mut().try_get_bus_mut(self.bus) {
+            bus.insert_send(self.index, self.send.take().unwrap());
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SetAudioBusSendCommand {
+    bus: Handle<AudioBus>,
+    index: usize,
+    send: AudioBusSend,
+}
+
+impl SetAudioBusSendCommand {
+    pub fn new(bus: Handle<AudioBus>, index: usize, send: AudioBusSend) -> Self {
+        Self { bus, index, send }
+    }
+
+    fn swap(&mut self, context: &mut dyn CommandContext) {
+        let context = context.get_mut::<GameSceneContext>();
+        let mut state = context.scene.graph.sound_context.state();
+        if let Some(send) = state
+            .bus_graph_mut()
+            .try_get_bus_mut(self.bus)
+            .and_then(|bus| bus.send_mut(self.index))
+        {
+            std::mem::swap(send, &mut self.send);
+        }
+    }
+}
+
+impl CommandTrait for SetAudioBusSendCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Set Audio Bus Send".to_string()
+    }
+
+    fn execute(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context)
+    }
+
+    fn revert(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context)
+    }
+}
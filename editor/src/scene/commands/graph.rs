@@ -1,14 +1,20 @@
 use crate::{
     command::{CommandContext, CommandTrait},
     fyrox::{
+        asset::untyped::ResourceKind,
         core::{
             algebra::{UnitQuaternion, Vector3},
             pool::{Handle, Ticket},
         },
         graph::{BaseSceneGraph, LinkScheme, SceneGraphNode},
         scene::{
-            base::Base,
+            base::{Base, LodGroup},
             graph::{Graph, SubGraph},
+            mesh::{
+                buffer::{VertexAttributeUsage, VertexReadTrait, VertexWriteTrait},
+                surface::{Surface, SurfaceData, SurfaceResource},
+                Mesh,
+            },
             node::Node,
             transform::Transform,
         },
@@ -110,6 +116,72 @@ impl CommandTrait for SetNodeTransformCommand {
     }
 }
 
+#[derive(Debug)]
+pub struct SetNodeNameCommand {
+    node: Handle<Node>,
+    name: String,
+}
+
+impl SetNodeNameCommand {
+    pub fn new(node: Handle<Node>, name: String) -> Self {
+        Self { node, name }
+    }
+
+    fn swap(&mut self, context: &mut dyn CommandContext) {
+        let context = context.get_mut::<GameSceneContext>();
+        let node = &mut context.scene.graph[self.node];
+        let old_name = node.name_owned();
+        node.set_name(&self.name);
+        self.name = old_name;
+    }
+}
+
+impl CommandTrait for SetNodeNameCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Set Node Name".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context);
+    }
+}
+
+#[derive(Debug)]
+pub struct SetNodeLodGroupCommand {
+    node: Handle<Node>,
+    lod_group: Option<LodGroup>,
+}
+
+impl SetNodeLodGroupCommand {
+    pub fn new(node: Handle<Node>, lod_group: Option<LodGroup>) -> Self {
+        Self { node, lod_group }
+    }
+
+    fn swap(&mut self, context: &mut dyn CommandContext) {
+        let context = context.get_mut::<GameSceneContext>();
+        let lod_group = self.lod_group.take();
+        self.lod_group = context.scene.graph[self.node].set_lod_group(lod_group);
+    }
+}
+
+impl CommandTrait for SetNodeLodGroupCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Set Node LOD Group".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context);
+    }
+}
+
 #[derive(Debug)]
 pub struct ScaleNodeCommand {
     node: Handle<Node>,
@@ -206,6 +278,94 @@ impl CommandTrait for RotateNodeCommand {
     }
 }
 
+/// Moves the origin of a node by the given offset (in local space of the node), while keeping its
+/// children and mesh geometry in place. The offset is compensated in local positions of the children
+/// and in vertex positions of the surfaces (skinned surfaces are left intact). Surfaces get their own
+/// embedded copies of the data, so instanced data of other meshes is not affected.
+#[derive(Debug)]
+pub struct SetNodePivotCommand {
+    node: Handle<Node>,
+    offset: Vector3<f32>,
+    surface_data: Option<Vec<SurfaceResource>>,
+}
+
+impl SetNodePivotCommand {
+    pub fn new(node: Handle<Node>, offset: Vector3<f32>) -> Self {
+        Self {
+            node,
+            offset,
+            surface_data: None,
+        }
+    }
+
+    fn apply(&mut self, graph: &mut Graph, offset: Vector3<f32>) {
+        let node = &mut graph[self.node];
+
+        let parent_space_offset = node.local_transform().matrix().transform_vector(&offset);
+        let position = **node.local_transform().position();
+        node.local_transform_mut()
+            .set_position(position + parent_space_offset);
+
+        if let Some(mesh) = node.cast_mut::<Mesh>() {
+            let new_data = self.surface_data.take().unwrap_or_else(|| {
+                mesh.surfaces()
+                    .iter()
+                    .map(|surface| offset_surface_data(surface, -offset))
+                    .collect()
+            });
+            self.surface_data = Some(
+                mesh.surfaces_mut()
+                    .iter_mut()
+                    .zip(new_data)
+                    .map(|(surface, data)| surface.set_data(data))
+                    .collect(),
+            );
+        }
+
+        for child in node.children().to_vec() {
+            let transform = graph[child].local_transform_mut();
+            let position = **transform.position();
+            transform.set_position(position - offset);
+        }
+    }
+}
+
+fn offset_surface_data(surface: &Surface, offset: Vector3<f32>) -> SurfaceResource {
+    let data = surface.data();
+    if !surface.bones().is_empty() || !data.is_ok() {
+        return data;
+    }
+
+    let guard = data.data_ref();
+    let mut new_data = SurfaceData::new(guard.vertex_buffer.clone(), guard.geometry_buffer.clone());
+    new_data.blend_shapes_container = guard.blend_shapes_container.clone();
+    drop(guard);
+
+    for mut vertex in new_data.vertex_buffer.modify().iter_mut() {
+        if let Ok(position) = vertex.read_3_f32(VertexAttributeUsage::Position) {
+            let _ = vertex.write_3_f32(VertexAttributeUsage::Position, position + offset);
+        }
+    }
+
+    SurfaceResource::new_ok(ResourceKind::Embedded, new_data)
+}
+
+impl CommandTrait for SetNodePivotCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Set Node Pivot".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut dyn CommandContext) {
+        let context = context.get_mut::<GameSceneContext>();
+        self.apply(&mut context.scene.graph, self.offset);
+    }
+
+    fn revert(&mut self, context: &mut dyn CommandContext) {
+        let context = context.get_mut::<GameSceneContext>();
+        self.apply(&mut context.scene.graph, -self.offset);
+    }
+}
+
 #[derive(Debug)]
 pub struct LinkNodesCommand {
     child: Handle<Node>,
@@ -567,3 +727,94 @@ impl CommandTrait for SetGraphRootCommand {
         self.root = std::mem::replace(context.scene_content_root, self.root);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        fyrox::{
+            asset::untyped::ResourceKind,
+            core::algebra::{Matrix4, Vector3},
+            scene::{
+                base::BaseBuilder,
+                graph::Graph,
+                mesh::{
+                    buffer::{VertexAttributeUsage, VertexReadTrait},
+                    surface::{SurfaceBuilder, SurfaceData, SurfaceResource},
+                    MeshBuilder,
+                },
+                pivot::PivotBuilder,
+                transform::TransformBuilder,
+            },
+        },
+        scene::commands::graph::SetNodePivotCommand,
+    };
+
+    #[test]
+    fn test_set_node_pivot() {
+        let mut graph = Graph::new();
+        let child = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(1.0, 1.0, 1.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        let surface_data = SurfaceResource::new_ok(
+            ResourceKind::Embedded,
+            SurfaceData::make_cube(Matrix4::identity()),
+        );
+        let mesh = MeshBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[child])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(1.0, 0.0, 0.0))
+                        .with_local_scale(Vector3::repeat(2.0))
+                        .build(),
+                ),
+        )
+        .with_surfaces(vec![SurfaceBuilder::new(surface_data.clone()).build()])
+        .build(&mut graph);
+
+        let first_vertex = |graph: &Graph| {
+            graph[mesh].as_mesh().surfaces()[0]
+                .data()
+                .data_ref()
+                .vertex_buffer
+                .get(0)
+                .unwrap()
+                .read_3_f32(VertexAttributeUsage::Position)
+                .unwrap()
+        };
+        let initial_vertex = first_vertex(&graph);
+
+        let mut command = SetNodePivotCommand::new(mesh, Vector3::new(0.5, 0.0, 0.0));
+        command.apply(&mut graph, command.offset);
+        assert_eq!(
+            **graph[mesh].local_transform().position(),
+            Vector3::new(2.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            **graph[child].local_transform().position(),
+            Vector3::new(0.5, 1.0, 1.0)
+        );
+        assert_eq!(
+            first_vertex(&graph),
+            initial_vertex - Vector3::new(0.5, 0.0, 0.0)
+        );
+        // Shared data must stay intact.
+        assert_ne!(graph[mesh].as_mesh().surfaces()[0].data(), surface_data);
+
+        command.apply(&mut graph, -command.offset);
+        assert_eq!(
+            **graph[mesh].local_transform().position(),
+            Vector3::new(1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            **graph[child].local_transform().position(),
+            Vector3::new(1.0, 1.0, 1.0)
+        );
+        assert_eq!(graph[mesh].as_mesh().surfaces()[0].data(), surface_data);
+    }
+}
@@ -58,6 +58,75 @@ impl CommandTrait for SetMaterialPropertyValueCommand {
     }
 }
 
+#[derive(Debug)]
+pub struct SetMaterialRenderQueueCommand {
+    material: MaterialResource,
+    render_queue: i32,
+}
+
+impl SetMaterialRenderQueueCommand {
+    pub fn new(material: MaterialResource, render_queue: i32) -> Self {
+        Self {
+            material,
+            render_queue,
+        }
+    }
+
+    fn swap(&mut self) {
+        self.render_queue = self.material.data_ref().set_render_queue(self.render_queue);
+        try_save(&self.material);
+    }
+}
+
+impl CommandTrait for SetMaterialRenderQueueCommand {
+    fn name(&mut self, _: &dyn CommandContext) -> String {
+        "Set Material Render Queue".to_owned()
+    }
+
+    fn execute(&mut self, _: &mut dyn CommandContext) {
+        self.swap();
+    }
+
+    fn revert(&mut self, _: &mut dyn CommandContext) {
+        self.swap();
+    }
+}
+
+#[derive(Debug)]
+pub struct SetMaterialPassTagsCommand {
+    material: MaterialResource,
+    pass_tags: Vec<ImmutableString>,
+}
+
+impl SetMaterialPassTagsCommand {
+    pub fn new(material: MaterialResource, pass_tags: Vec<ImmutableString>) -> Self {
+        Self {
+            material,
+            pass_tags,
+        }
+    }
+
+    fn swap(&mut self) {
+        let pass_tags = std::mem::take(&mut self.pass_tags);
+        self.pass_tags = self.material.data_ref().set_pass_tags(pass_tags);
+        try_save(&self.material);
+    }
+}
+
+impl CommandTrait for SetMaterialPassTagsCommand {
+    fn name(&mut self, _: &dyn CommandContext) -> String {
+        "Set Material Pass Tags".to_owned()
+    }
+
+    fn execute(&mut self, _: &mut dyn CommandContext) {
+        self.swap();
+    }
+
+    fn revert(&mut self, _: &mut dyn CommandContext) {
+        self.swap();
+    }
+}
+
 #[derive(Debug)]
 enum SetMaterialShaderCommandState {
     Undefined,
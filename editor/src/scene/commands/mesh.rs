@@ -3,7 +3,10 @@ use crate::fyrox::{
     core::{pool::Handle, sstorage::ImmutableString},
     material::{shader::SamplerFallback, PropertyValue},
     resource::texture::TextureResource,
-    scene::{mesh::Mesh, node::Node},
+    scene::{
+        mesh::{surface::SurfaceData, Mesh},
+        node::Node,
+    },
 };
 use crate::{command::CommandTrait, scene::commands::GameSceneContext};
 
@@ -108,3 +111,39 @@ impl CommandTrait for SetMeshTextureCommand {
         }
     }
 }
+
+/// Replaces the data of every surface of a mesh. The data is swapped in place, so every other mesh
+/// that shares the surface data will be changed too.
+#[derive(Debug)]
+pub struct SetMeshSurfaceDataCommand {
+    node: Handle<Node>,
+    data: Vec<SurfaceData>,
+}
+
+impl SetMeshSurfaceDataCommand {
+    pub fn new(node: Handle<Node>, data: Vec<SurfaceData>) -> Self {
+        Self { node, data }
+    }
+
+    fn swap(&mut self, context: &mut dyn CommandContext) {
+        let context = context.get_mut::<GameSceneContext>();
+        let mesh: &mut Mesh = context.scene.graph[self.node].as_mesh_mut();
+        for (surface, data) in mesh.surfaces().iter().zip(self.data.iter_mut()) {
+            std::mem::swap(&mut *surface.data().data_ref(), data);
+        }
+    }
+}
+
+impl CommandTrait for SetMeshSurfaceDataCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Set Mesh Surface Data".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context);
+    }
+}
@@ -321,3 +321,38 @@ impl CommandTrait for MoveNavmeshVertexCommand {
         self.set_position(fetch_navmesh(context, self.navmesh_node), position);
     }
 }
+
+#[derive(Debug)]
+pub struct SetNavmeshCommand {
+    navmesh_node: Handle<Node>,
+    navmesh: Navmesh,
+}
+
+impl SetNavmeshCommand {
+    pub fn new(navmesh_node: Handle<Node>, navmesh: Navmesh) -> Self {
+        Self {
+            navmesh_node,
+            navmesh,
+        }
+    }
+
+    fn swap(&mut self, context: &mut dyn CommandContext) {
+        let context = context.get_mut::<GameSceneContext>();
+        let mut navmesh = fetch_navmesh(context, self.navmesh_node);
+        std::mem::swap(&mut *navmesh, &mut self.navmesh);
+    }
+}
+
+impl CommandTrait for SetNavmeshCommand {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Set Navmesh".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context);
+    }
+}
@@ -11,10 +11,10 @@ use crate::fyrox::{
 use crate::{
     highlight::HighlightRenderPass,
     interaction::{
-        move_mode::MoveInteractionMode, navmesh::EditNavmeshMode,
-        rotate_mode::RotateInteractionMode, scale_mode::ScaleInteractionMode,
-        select_mode::SelectInteractionMode, terrain::TerrainInteractionMode,
-        InteractionModeContainer,
+        edit_pivot_mode::EditPivotInteractionMode, move_mode::MoveInteractionMode,
+        navmesh::EditNavmeshMode, rotate_mode::RotateInteractionMode,
+        scale_mode::ScaleInteractionMode, select_mode::SelectInteractionMode,
+        terrain::TerrainInteractionMode, InteractionModeContainer,
     },
     message::MessageSender,
     scene::{controller::SceneController, GameScene, Selection},
@@ -65,6 +65,7 @@ impl EditorSceneEntry {
         interaction_modes.add(SelectInteractionMode::new(
             scene_viewer.frame(),
             scene_viewer.selection_frame(),
+            scene_viewer.lasso(),
             message_sender.clone(),
         ));
         interaction_modes.add(MoveInteractionMode::new(
@@ -82,6 +83,11 @@ impl EditorSceneEntry {
             engine,
             message_sender.clone(),
         ));
+        interaction_modes.add(EditPivotInteractionMode::new(
+            &game_scene,
+            engine,
+            message_sender.clone(),
+        ));
         interaction_modes.add(EditNavmeshMode::new(
             &game_scene,
             engine,
@@ -200,6 +206,9 @@ impl EditorSceneEntry {
         engine: &mut Engine,
     ) -> Result<String, String> {
         let result = self.controller.save(&path, settings, engine);
+        if result.is_ok() {
+            self.command_stack.journal.clear();
+        }
         self.path = Some(path);
         result
     }
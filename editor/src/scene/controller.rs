@@ -158,6 +158,18 @@ pub trait SceneController: 'static {
         callback: &mut dyn FnMut(&dyn Reflect),
     );
 
+    /// Calls the given callback for every selected entity. It is used to find the properties, that
+    /// have different values in the selected entities. The default implementation calls the
+    /// callback for the first selected entity only.
+    fn for_each_selected_entity(
+        &self,
+        selection: &Selection,
+        scenes: &SceneContainer,
+        callback: &mut dyn FnMut(&dyn Reflect),
+    ) {
+        self.first_selected_entity(selection, scenes, callback)
+    }
+
     fn on_property_changed(
         &mut self,
         args: &PropertyChanged,
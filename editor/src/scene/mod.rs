@@ -21,7 +21,7 @@ use crate::{
             reflect::Reflect,
             visitor::Visitor,
         },
-        engine::{Engine, SerializationContext},
+        engine::{Engine, GraphicsContext, SerializationContext},
         fxhash::FxHashSet,
         graph::{BaseSceneGraph, SceneGraph},
         gui::{
@@ -42,7 +42,7 @@ use crate::{
             camera::{Camera, Projection},
             debug::{Line, SceneDrawingContext},
             graph::{Graph, GraphUpdateSwitches},
-            light::{point::PointLight, spot::SpotLight},
+            light::{area::AreaLight, point::PointLight, spot::SpotLight},
             mesh::RenderPath,
             mesh::{
                 surface::{SurfaceBuilder, SurfaceData, SurfaceResource},
@@ -51,7 +51,7 @@ use crate::{
             navmesh::NavigationalMesh,
             node::Node,
             pivot::PivotBuilder,
-            sound::AudioBus,
+            sound::{AudioBus, Sound},
             terrain::Terrain,
             Scene, SceneContainer,
         },
@@ -72,7 +72,7 @@ use crate::{
         controller::SceneController,
         selector::HierarchyNode,
     },
-    settings::{keys::KeyBindings, SettingsMessage},
+    settings::{keys::KeyBindings, pivot::PivotMode, SettingsMessage},
     ui_scene::selection::UiSelection,
     world::graph::selection::GraphSelection,
     Message, Settings,
@@ -92,6 +92,7 @@ use std::{
     },
 };
 
+pub mod align;
 pub mod clipboard;
 pub mod dialog;
 pub mod property;
@@ -116,6 +117,9 @@ pub struct GameScene {
     pub clipboard: Clipboard,
     pub camera_controller: CameraController,
     pub preview_camera: Handle<Node>,
+    /// A camera, which view is shown in a small viewport on top of the editor camera view.
+    pub picture_in_picture: Handle<Node>,
+    picture_in_picture_state: Option<(Handle<Node>, Node)>,
     pub graph_switches: GraphUpdateSwitches,
     pub preview_instance: Option<PreviewInstance>,
     pub sender: MessageSender,
@@ -144,6 +148,13 @@ fn make_grid_material() -> MaterialResource {
 }
 
 impl GameScene {
+    /// Normalized viewport of picture-in-picture camera preview (bottom-right corner of the scene
+    /// preview).
+    pub const PICTURE_IN_PICTURE_VIEWPORT: Rect<f32> = Rect {
+        position: Vector2::new(0.69, 0.01),
+        size: Vector2::new(0.3, 0.3),
+    };
+
     pub fn from_native_scene(
         mut scene: Scene,
         engine: &mut Engine,
@@ -190,6 +201,12 @@ impl GameScene {
             scene_content_root,
         );
 
+        // Compile shaders of the scene beforehand, so the first frames of the scene won't hitch.
+        if let GraphicsContext::Initialized(ref mut graphics_context) = engine.graphics_context {
+            let ready = graphics_context.renderer.warm_up_shaders(&scene, |_, _| ());
+            Log::info(format!("{} shader(s) of the scene were warmed up.", ready));
+        }
+
         // Freeze physics simulation in while editing scene by setting time step to zero.
         scene.graph.physics.integration_parameters.dt = Some(0.0);
         scene.graph.physics2d.integration_parameters.dt = Some(0.0);
@@ -202,6 +219,8 @@ impl GameScene {
             scene: engine.scenes.add(scene),
             clipboard: Default::default(),
             preview_camera: Default::default(),
+            picture_in_picture: Default::default(),
+            picture_in_picture_state: None,
             graph_switches: GraphUpdateSwitches {
                 physics2d: true,
                 physics: true,
@@ -211,6 +230,7 @@ impl GameScene {
                 // Update only editor's camera.
                 node_overrides: Some(Default::default()),
                 paused: false,
+                parallel: true,
             },
             sender,
             camera_state: Default::default(),
@@ -287,9 +307,43 @@ impl GameScene {
             }
         }
 
+        if settings.pivot.mode == PivotMode::Cursor {
+            let cursor = settings.pivot.cursor;
+            for (axis, color) in [
+                (Vector3::x(), Color::RED),
+                (Vector3::y(), Color::GREEN),
+                (Vector3::z(), Color::BLUE),
+            ] {
+                scene.drawing_context.add_line(Line {
+                    begin: cursor - axis.scale(0.25),
+                    end: cursor + axis.scale(0.25),
+                    color,
+                });
+            }
+        }
+
         if debug_settings.show_physics {
-            scene.graph.physics.draw(&mut scene.drawing_context);
-            scene.graph.physics2d.draw(&mut scene.drawing_context);
+            scene
+                .graph
+                .physics
+                .draw_with_settings(&mut scene.drawing_context, &debug_settings.physics);
+            scene
+                .graph
+                .physics2d
+                .draw_with_settings(&mut scene.drawing_context, &debug_settings.physics);
+        }
+
+        if debug_settings.show_sound_occlusion {
+            let listener_position = scene.graph.sound_context.state().listener_position();
+            for sound in scene.graph.linear_iter().filter_map(|n| n.cast::<Sound>()) {
+                if sound.is_occlusion_enabled() {
+                    scene.drawing_context.add_line(Line {
+                        begin: listener_position,
+                        end: sound.global_position(),
+                        color: Color::GREEN.lerp(Color::RED, sound.occlusion()),
+                    });
+                }
+            }
         }
 
         fn draw_recursively(
@@ -327,6 +381,7 @@ impl GameScene {
                 }
             } else if node.query_component_ref::<PointLight>().is_some()
                 || node.query_component_ref::<SpotLight>().is_some()
+                || node.query_component_ref::<AreaLight>().is_some()
             {
                 if settings.debugging.show_light_bounds {
                     node.debug_draw(ctx);
@@ -818,9 +873,21 @@ impl SceneController for GameScene {
         // scene camera to interfere with the editor camera.
         let scene = &mut engine.scenes[self.scene];
         let has_preview_camera = scene.graph.is_valid_handle(self.preview_camera);
+        let picture_in_picture = if !has_preview_camera
+            && scene
+                .graph
+                .try_get_of_type::<Camera>(self.picture_in_picture)
+                .is_some()
+        {
+            self.picture_in_picture
+        } else {
+            Handle::NONE
+        };
         for (handle, camera) in scene.graph.pair_iter_mut().filter_map(|(h, n)| {
             if has_preview_camera && h != self.preview_camera
-                || !has_preview_camera && h != self.camera_controller.camera
+                || !has_preview_camera
+                    && h != self.camera_controller.camera
+                    && h != picture_in_picture
             {
                 n.cast_mut::<Camera>().map(|c| (h, c))
             } else {
@@ -830,10 +897,36 @@ impl SceneController for GameScene {
             self.camera_state.push((handle, camera.is_enabled()));
             camera.set_enabled(false);
         }
+
+        if picture_in_picture.is_some() {
+            let frame_size = match scene
+                .rendering_options
+                .render_target
+                .as_ref()
+                .map(|rt| rt.data_ref().kind())
+            {
+                Some(TextureKind::Rectangle { width, height }) => {
+                    Vector2::new(width as f32, height as f32)
+                }
+                _ => Vector2::repeat(1.0),
+            };
+
+            // The camera is temporarily modified, so keep its original state to revert it after
+            // rendering.
+            let original = scene.graph[picture_in_picture].clone_box();
+            let camera = scene.graph[picture_in_picture].as_camera_mut();
+            camera.set_enabled(true);
+            camera.set_viewport(Self::PICTURE_IN_PICTURE_VIEWPORT);
+            camera.calculate_matrices(frame_size);
+            self.picture_in_picture_state = Some((picture_in_picture, original));
+        }
     }
 
     fn on_after_render(&mut self, engine: &mut Engine) {
         // Revert state of the cameras.
+        if let Some((handle, original)) = self.picture_in_picture_state.take() {
+            engine.scenes[self.scene].graph[handle] = original;
+        }
         for (handle, enabled) in self.camera_state.drain(..) {
             engine.scenes[self.scene].graph[handle]
                 .as_camera_mut()
@@ -1111,6 +1204,22 @@ impl SceneController for GameScene {
         }
     }
 
+    fn for_each_selected_entity(
+        &self,
+        selection: &Selection,
+        scenes: &SceneContainer,
+        callback: &mut dyn FnMut(&dyn Reflect),
+    ) {
+        if let Some(selection) = selection.as_graph() {
+            let graph = &scenes[self.scene].graph;
+            for node in selection.nodes.iter().filter_map(|h| graph.try_get(*h)) {
+                (callback)(node as &dyn Reflect);
+            }
+        } else {
+            self.first_selected_entity(selection, scenes, callback)
+        }
+    }
+
     fn on_property_changed(
         &mut self,
         args: &PropertyChanged,
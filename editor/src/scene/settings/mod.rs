@@ -4,7 +4,10 @@ use crate::fyrox::{
     gui::{
         inspector::{
             editors::{
-                enumeration::EnumPropertyEditorDefinition, PropertyEditorDefinitionContainer,
+                collection::VecCollectionPropertyEditorDefinition,
+                enumeration::EnumPropertyEditorDefinition,
+                inspectable::InspectablePropertyEditorDefinition,
+                PropertyEditorDefinitionContainer,
             },
             InspectorBuilder, InspectorContext, InspectorMessage, PropertyFilter,
         },
@@ -14,9 +17,14 @@ use crate::fyrox::{
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, UiNode, UserInterface,
     },
-    resource::texture::TextureResource,
+    resource::{
+        model::parameter::{PrefabParameter, PrefabParameterBinding, PrefabParameters},
+        texture::TextureResource,
+    },
     scene::{
         dim2,
+        driver::{DriverVariable, PropertyDriver, PropertyDrivers},
+        floating_origin::FloatingOrigin,
         graph::{
             physics::{IntegrationParameters, PhysicsWorld},
             Graph, NodePool,
@@ -65,6 +73,16 @@ impl SceneSettingsWindow {
         container.register_inheritable_inspectable::<PhysicsWorld>();
         container.register_inheritable_inspectable::<dim2::physics::PhysicsWorld>();
         container.register_inheritable_inspectable::<SceneRenderingOptions>();
+        container.insert(InspectablePropertyEditorDefinition::<FloatingOrigin>::new());
+        container.insert(InspectablePropertyEditorDefinition::<PropertyDrivers>::new());
+        container.insert(InspectablePropertyEditorDefinition::<PropertyDriver>::new());
+        container.insert(VecCollectionPropertyEditorDefinition::<PropertyDriver>::new());
+        container.insert(InspectablePropertyEditorDefinition::<DriverVariable>::new());
+        container.insert(VecCollectionPropertyEditorDefinition::<DriverVariable>::new());
+        container.insert(InspectablePropertyEditorDefinition::<PrefabParameters>::new());
+        container.insert(InspectablePropertyEditorDefinition::<PrefabParameter>::new());
+        container.insert(VecCollectionPropertyEditorDefinition::<PrefabParameter>::new());
+        container.insert(EnumPropertyEditorDefinition::<PrefabParameterBinding>::new());
         container.insert(EnumPropertyEditorDefinition::<Color>::new_optional());
 
         Self {
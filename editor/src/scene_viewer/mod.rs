@@ -1,6 +1,7 @@
 use crate::{
+    camera::framing::{FramingOverlayBuilder, FramingOverlayMessage},
     fyrox::{
-        core::{color::Color, math::Rect, pool::Handle, uuid::Uuid},
+        core::{algebra::Vector2, color::Color, math::Rect, pool::Handle, uuid::Uuid},
         engine::Engine,
         fxhash::FxHashMap,
         graph::{BaseSceneGraph, SceneGraph, SceneGraphNode},
@@ -10,13 +11,14 @@ use crate::{
             button::{Button, ButtonBuilder, ButtonMessage},
             canvas::CanvasBuilder,
             check_box::{CheckBoxBuilder, CheckBoxMessage},
+            color::ColorPickerMessage,
             decorator::DecoratorMessage,
             dropdown_list::{DropdownList, DropdownListMessage},
             dropdown_menu::DropdownMenuBuilder,
             formatted_text::WrapMode,
             grid::{Column, GridBuilder, Row},
             image::{ImageBuilder, ImageMessage},
-            message::{MessageDirection, MouseButton, UiMessage},
+            message::{CursorIcon, MessageDirection, MouseButton, UiMessage},
             numeric::{NumericUpDownBuilder, NumericUpDownMessage},
             stack_panel::StackPanelBuilder,
             tab_control::{
@@ -25,13 +27,14 @@ use crate::{
             text::{TextBuilder, TextMessage},
             utils::make_simple_tooltip,
             vec::{Vec3EditorBuilder, Vec3EditorMessage},
+            vector_image::VectorImageBuilder,
             widget::{WidgetBuilder, WidgetMessage},
             window::{WindowBuilder, WindowMessage, WindowTitle},
             BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
             VerticalAlignment, BRUSH_BRIGHT_BLUE, BRUSH_DARKEST,
         },
         renderer::framework::state::PolygonFillMode,
-        resource::texture::TextureResource,
+        resource::texture::{TextureKind, TextureResource},
         scene::camera::Projection,
     },
     gui::{
@@ -40,10 +43,13 @@ use crate::{
     },
     load_image,
     message::MessageSender,
-    scene::container::EditorSceneEntry,
+    scene::{
+        align::{make_align_command, AlignAction, AlignAnchor, AlignAxis},
+        container::EditorSceneEntry,
+    },
     scene_viewer::gizmo::{SceneGizmo, SceneGizmoAction},
     send_sync_message,
-    settings::SettingsMessage,
+    settings::{move_mode::SnapSpace, pivot::PivotMode, SettingsMessage},
     utils::enable_widget,
     DropdownListBuilder, GameScene, Message, Mode, SaveSceneConfirmationDialogAction,
     SceneContainer, Settings,
@@ -65,28 +71,240 @@ pub enum GraphicsDebugSwitches {
     Wireframe,
 }
 
-struct GridSnappingMenu {
-    menu: Handle<UiNode>,
-    button: Handle<UiNode>,
+struct SnappingSection {
     enabled: Handle<UiNode>,
+    space: Handle<UiNode>,
     x_step: Handle<UiNode>,
     y_step: Handle<UiNode>,
     z_step: Handle<UiNode>,
+}
+
+impl SnappingSection {
+    fn new(
+        ctx: &mut BuildContext,
+        rows: &mut SnappingMenuRows,
+        title: &str,
+        enabled: bool,
+        space: SnapSpace,
+        steps: [f32; 3],
+    ) -> Self {
+        rows.add_header(ctx, title);
+        let enabled = rows.add_check_box(ctx, "Enabled", enabled);
+        let space = rows.add_space(ctx, space);
+        let x_step = rows.add_step(ctx, "X Step", steps[0]);
+        let y_step = rows.add_step(ctx, "Y Step", steps[1]);
+        let z_step = rows.add_step(ctx, "Z Step", steps[2]);
+        Self {
+            enabled,
+            space,
+            x_step,
+            y_step,
+            z_step,
+        }
+    }
+
+    fn sync(&self, ui: &UserInterface, enabled: bool, space: SnapSpace, steps: [f32; 3]) {
+        ui.send_message(CheckBoxMessage::checked(
+            self.enabled,
+            MessageDirection::ToWidget,
+            Some(enabled),
+        ));
+        ui.send_message(DropdownListMessage::selection(
+            self.space,
+            MessageDirection::ToWidget,
+            SnapSpace::iter().position(|s| s == space),
+        ));
+        for (widget, step) in [self.x_step, self.y_step, self.z_step]
+            .into_iter()
+            .zip(steps)
+        {
+            ui.send_message(NumericUpDownMessage::value(
+                widget,
+                MessageDirection::ToWidget,
+                step,
+            ));
+        }
+    }
+
+    fn handle_ui_message(
+        &self,
+        message: &UiMessage,
+        enabled: &mut bool,
+        space: &mut SnapSpace,
+        steps: [&mut f32; 3],
+    ) {
+        if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.enabled {
+                *enabled = *value;
+            }
+        } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.space {
+                if let Some(new_space) = SnapSpace::iter().nth(*index) {
+                    *space = new_space;
+                }
+            }
+        } else if let Some(NumericUpDownMessage::Value(value)) = message.data() {
+            for (widget, step) in [self.x_step, self.y_step, self.z_step]
+                .into_iter()
+                .zip(steps)
+            {
+                if message.destination() == widget {
+                    *step = *value;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct SnappingMenuRows {
+    children: Vec<Handle<UiNode>>,
+    count: usize,
+}
+
+impl SnappingMenuRows {
+    fn add(
+        &mut self,
+        ctx: &mut BuildContext,
+        name: &str,
+        editor: Handle<UiNode>,
+    ) -> Handle<UiNode> {
+        self.children.push(
+            TextBuilder::new(
+                WidgetBuilder::new()
+                    .with_margin(Thickness::left(4.0))
+                    .on_row(self.count)
+                    .on_column(0),
+            )
+            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .with_text(name)
+            .build(ctx),
+        );
+        ctx[editor].set_row(self.count).set_column(1);
+        self.children.push(editor);
+        self.count += 1;
+        editor
+    }
+
+    fn add_header(&mut self, ctx: &mut BuildContext, title: &str) {
+        self.children.push(
+            TextBuilder::new(WidgetBuilder::new().on_row(self.count).on_column(0))
+                .with_text(title)
+                .build(ctx),
+        );
+        self.count += 1;
+    }
+
+    fn add_check_box(&mut self, ctx: &mut BuildContext, name: &str, value: bool) -> Handle<UiNode> {
+        let check_box = CheckBoxBuilder::new(WidgetBuilder::new())
+            .checked(Some(value))
+            .build(ctx);
+        self.add(ctx, name, check_box)
+    }
+
+    fn add_space(&mut self, ctx: &mut BuildContext, space: SnapSpace) -> Handle<UiNode> {
+        let items = SnapSpace::VARIANTS
+            .iter()
+            .map(|name| make_dropdown_list_option(ctx, name))
+            .collect::<Vec<_>>();
+        let dropdown = DropdownListBuilder::new(WidgetBuilder::new().with_height(22.0))
+            .with_items(items)
+            .with_selected(
+                SnapSpace::iter()
+                    .position(|s| s == space)
+                    .unwrap_or_default(),
+            )
+            .build(ctx);
+        self.add(ctx, "Space", dropdown)
+    }
+
+    fn add_step(&mut self, ctx: &mut BuildContext, name: &str, value: f32) -> Handle<UiNode> {
+        let step = NumericUpDownBuilder::<f32>::new(WidgetBuilder::new())
+            .with_value(value)
+            .build(ctx);
+        self.add(ctx, name, step)
+    }
+
+    fn build(self, ctx: &mut BuildContext, editor_width: f32) -> Handle<UiNode> {
+        let mut grid = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(2.0))
+                .with_children(self.children),
+        )
+        .add_column(Column::stretch())
+        .add_column(Column::strict(editor_width));
+        for _ in 0..self.count {
+            grid = grid.add_row(Row::auto());
+        }
+        grid.build(ctx)
+    }
+}
+
+/// Per-mode snapping options for move, rotate and scale interaction modes.
+struct SnappingMenu {
+    menu: Handle<UiNode>,
+    button: Handle<UiNode>,
+    move_section: SnappingSection,
+    vertex_snapping: Handle<UiNode>,
+    surface_snapping: Handle<UiNode>,
+    rotate_section: SnappingSection,
+    scale_section: SnappingSection,
     receiver: Receiver<SettingsMessage>,
 }
 
-impl GridSnappingMenu {
+impl SnappingMenu {
     fn new(ctx: &mut BuildContext, settings: &mut Settings) -> Self {
         let (sender, receiver) = mpsc::channel();
 
         settings.subscribers.push(sender);
 
+        let mut rows = SnappingMenuRows::default();
+        let move_settings = &settings.move_mode_settings;
+        let move_section = SnappingSection::new(
+            ctx,
+            &mut rows,
+            "Move",
+            move_settings.grid_snapping,
+            move_settings.space,
+            [
+                move_settings.x_snap_step,
+                move_settings.y_snap_step,
+                move_settings.z_snap_step,
+            ],
+        );
+        let vertex_snapping =
+            rows.add_check_box(ctx, "Vertex Snapping", move_settings.vertex_snapping);
+        let surface_snapping =
+            rows.add_check_box(ctx, "Surface Snapping", move_settings.surface_snapping);
+        let rotate_settings = &settings.rotate_mode_settings;
+        let rotate_section = SnappingSection::new(
+            ctx,
+            &mut rows,
+            "Rotate (degrees)",
+            rotate_settings.angle_snapping,
+            rotate_settings.space,
+            [
+                rotate_settings.x_snap_step,
+                rotate_settings.y_snap_step,
+                rotate_settings.z_snap_step,
+            ],
+        );
+        let scale_settings = &settings.scale_mode_settings;
+        let scale_section = SnappingSection::new(
+            ctx,
+            &mut rows,
+            "Scale",
+            scale_settings.scale_snapping,
+            scale_settings.space,
+            [
+                scale_settings.x_snap_step,
+                scale_settings.y_snap_step,
+                scale_settings.z_snap_step,
+            ],
+        );
+
         let button;
-        let enabled;
-        let x_step;
-        let y_step;
-        let z_step;
-        let grid_snap_menu = DropdownMenuBuilder::new(WidgetBuilder::new())
+        let menu = DropdownMenuBuilder::new(WidgetBuilder::new())
             .with_header({
                 button = make_image_button_with_tooltip(
                     ctx,
@@ -98,92 +316,17 @@ impl GridSnappingMenu {
                 );
                 button
             })
-            .with_content(
-                GridBuilder::new(
-                    WidgetBuilder::new()
-                        .with_margin(Thickness::uniform(2.0))
-                        .with_child(
-                            TextBuilder::new(WidgetBuilder::new().on_row(0).on_column(0))
-                                .with_text("Grid Snapping")
-                                .build(ctx),
-                        )
-                        .with_child({
-                            enabled = CheckBoxBuilder::new(
-                                WidgetBuilder::new()
-                                    .on_row(0)
-                                    .on_column(1)
-                                    .with_tab_index(Some(0)),
-                            )
-                            .checked(Some(settings.move_mode_settings.grid_snapping))
-                            .build(ctx);
-                            enabled
-                        })
-                        .with_child(
-                            TextBuilder::new(WidgetBuilder::new().on_row(1).on_column(0))
-                                .with_text("X Step")
-                                .build(ctx),
-                        )
-                        .with_child({
-                            x_step = NumericUpDownBuilder::<f32>::new(
-                                WidgetBuilder::new()
-                                    .on_row(1)
-                                    .on_column(1)
-                                    .with_tab_index(Some(1)),
-                            )
-                            .with_value(settings.move_mode_settings.x_snap_step)
-                            .build(ctx);
-                            x_step
-                        })
-                        .with_child(
-                            TextBuilder::new(WidgetBuilder::new().on_row(2).on_column(0))
-                                .with_text("Y Step")
-                                .build(ctx),
-                        )
-                        .with_child({
-                            y_step = NumericUpDownBuilder::<f32>::new(
-                                WidgetBuilder::new()
-                                    .on_row(2)
-                                    .on_column(1)
-                                    .with_tab_index(Some(2)),
-                            )
-                            .with_value(settings.move_mode_settings.y_snap_step)
-                            .build(ctx);
-                            y_step
-                        })
-                        .with_child(
-                            TextBuilder::new(WidgetBuilder::new().on_row(3).on_column(0))
-                                .with_text("Z Step")
-                                .build(ctx),
-                        )
-                        .with_child({
-                            z_step = NumericUpDownBuilder::<f32>::new(
-                                WidgetBuilder::new()
-                                    .on_row(3)
-                                    .on_column(1)
-                                    .with_tab_index(Some(3)),
-                            )
-                            .with_value(settings.move_mode_settings.z_snap_step)
-                            .build(ctx);
-                            z_step
-                        }),
-                )
-                .add_row(Row::auto())
-                .add_row(Row::auto())
-                .add_row(Row::auto())
-                .add_row(Row::auto())
-                .add_column(Column::stretch())
-                .add_column(Column::auto())
-                .build(ctx),
-            )
+            .with_content(rows.build(ctx, 100.0))
             .build(ctx);
 
         Self {
-            menu: grid_snap_menu,
+            menu,
             button,
-            enabled,
-            x_step,
-            y_step,
-            z_step,
+            move_section,
+            vertex_snapping,
+            surface_snapping,
+            rotate_section,
+            scale_section,
             receiver,
         }
     }
@@ -192,6 +335,10 @@ impl GridSnappingMenu {
         for message in self.receiver.try_iter() {
             match message {
                 SettingsMessage::Changed => {
+                    let move_settings = &settings.move_mode_settings;
+                    let rotate_settings = &settings.rotate_mode_settings;
+                    let scale_settings = &settings.scale_mode_settings;
+
                     if let Some(button) = ui.try_get_of_type::<Button>(self.button) {
                         ui.send_message(DecoratorMessage::selected_brush(
                             *button.decorator,
@@ -202,53 +349,334 @@ impl GridSnappingMenu {
                         ui.send_message(DecoratorMessage::select(
                             *button.decorator,
                             MessageDirection::ToWidget,
-                            settings.move_mode_settings.grid_snapping,
+                            move_settings.grid_snapping
+                                || move_settings.is_snapping_to_objects()
+                                || rotate_settings.angle_snapping
+                                || scale_settings.scale_snapping,
                         ));
                     }
 
+                    self.move_section.sync(
+                        ui,
+                        move_settings.grid_snapping,
+                        move_settings.space,
+                        [
+                            move_settings.x_snap_step,
+                            move_settings.y_snap_step,
+                            move_settings.z_snap_step,
+                        ],
+                    );
                     ui.send_message(CheckBoxMessage::checked(
-                        self.enabled,
+                        self.vertex_snapping,
                         MessageDirection::ToWidget,
-                        Some(settings.move_mode_settings.grid_snapping),
+                        Some(move_settings.vertex_snapping),
                     ));
-
-                    ui.send_message(NumericUpDownMessage::value(
-                        self.x_step,
+                    ui.send_message(CheckBoxMessage::checked(
+                        self.surface_snapping,
                         MessageDirection::ToWidget,
-                        settings.move_mode_settings.x_snap_step,
+                        Some(move_settings.surface_snapping),
                     ));
-                    ui.send_message(NumericUpDownMessage::value(
-                        self.y_step,
+                    self.rotate_section.sync(
+                        ui,
+                        rotate_settings.angle_snapping,
+                        rotate_settings.space,
+                        [
+                            rotate_settings.x_snap_step,
+                            rotate_settings.y_snap_step,
+                            rotate_settings.z_snap_step,
+                        ],
+                    );
+                    self.scale_section.sync(
+                        ui,
+                        scale_settings.scale_snapping,
+                        scale_settings.space,
+                        [
+                            scale_settings.x_snap_step,
+                            scale_settings.y_snap_step,
+                            scale_settings.z_snap_step,
+                        ],
+                    );
+                }
+            }
+        }
+    }
+
+    fn handle_ui_message(&self, message: &UiMessage, settings: &mut Settings) {
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.vertex_snapping {
+                settings.move_mode_settings.vertex_snapping = *value;
+                return;
+            } else if message.destination() == self.surface_snapping {
+                settings.move_mode_settings.surface_snapping = *value;
+                return;
+            }
+        }
+
+        let move_settings = &mut settings.move_mode_settings;
+        self.move_section.handle_ui_message(
+            message,
+            &mut move_settings.grid_snapping,
+            &mut move_settings.space,
+            [
+                &mut move_settings.x_snap_step,
+                &mut move_settings.y_snap_step,
+                &mut move_settings.z_snap_step,
+            ],
+        );
+        let rotate_settings = &mut settings.rotate_mode_settings;
+        self.rotate_section.handle_ui_message(
+            message,
+            &mut rotate_settings.angle_snapping,
+            &mut rotate_settings.space,
+            [
+                &mut rotate_settings.x_snap_step,
+                &mut rotate_settings.y_snap_step,
+                &mut rotate_settings.z_snap_step,
+            ],
+        );
+        let scale_settings = &mut settings.scale_mode_settings;
+        self.scale_section.handle_ui_message(
+            message,
+            &mut scale_settings.scale_snapping,
+            &mut scale_settings.space,
+            [
+                &mut scale_settings.x_snap_step,
+                &mut scale_settings.y_snap_step,
+                &mut scale_settings.z_snap_step,
+            ],
+        );
+    }
+}
+
+/// Pivot point options of rotate and scale interaction modes.
+struct PivotMenu {
+    menu: Handle<UiNode>,
+    mode: Handle<UiNode>,
+    cursor: Handle<UiNode>,
+    cursor_to_selection: Handle<UiNode>,
+    receiver: Receiver<SettingsMessage>,
+}
+
+impl PivotMenu {
+    fn new(ctx: &mut BuildContext, settings: &mut Settings) -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        settings.subscribers.push(sender);
+
+        let mut rows = SnappingMenuRows::default();
+        let items = PivotMode::VARIANTS
+            .iter()
+            .map(|name| make_dropdown_list_option(ctx, name))
+            .collect::<Vec<_>>();
+        let mode = DropdownListBuilder::new(WidgetBuilder::new().with_height(22.0))
+            .with_items(items)
+            .with_selected(
+                PivotMode::iter()
+                    .position(|m| m == settings.pivot.mode)
+                    .unwrap_or_default(),
+            )
+            .build(ctx);
+        let mode = rows.add(ctx, "Pivot", mode);
+        let cursor = Vec3EditorBuilder::<f32>::new(WidgetBuilder::new().with_height(22.0))
+            .with_value(settings.pivot.cursor)
+            .build(ctx);
+        let cursor = rows.add(ctx, "3D Cursor", cursor);
+        let cursor_to_selection = ButtonBuilder::new(WidgetBuilder::new().with_height(22.0))
+            .with_text("Cursor To Selection")
+            .build(ctx);
+        let cursor_to_selection = rows.add(ctx, "", cursor_to_selection);
+
+        let menu = DropdownMenuBuilder::new(WidgetBuilder::new())
+            .with_header(make_image_button_with_tooltip(
+                ctx,
+                22.0,
+                22.0,
+                load_image(include_bytes!("../../resources/locate.png")),
+                "Pivot Options",
+                None,
+            ))
+            .with_content(rows.build(ctx, 200.0))
+            .build(ctx);
+
+        Self {
+            menu,
+            mode,
+            cursor,
+            cursor_to_selection,
+            receiver,
+        }
+    }
+
+    fn update(&self, settings: &Settings, ui: &UserInterface) {
+        for message in self.receiver.try_iter() {
+            match message {
+                SettingsMessage::Changed => {
+                    ui.send_message(DropdownListMessage::selection(
+                        self.mode,
                         MessageDirection::ToWidget,
-                        settings.move_mode_settings.y_snap_step,
+                        PivotMode::iter().position(|m| m == settings.pivot.mode),
                     ));
-                    ui.send_message(NumericUpDownMessage::value(
-                        self.z_step,
+                    ui.send_message(Vec3EditorMessage::value(
+                        self.cursor,
                         MessageDirection::ToWidget,
-                        settings.move_mode_settings.z_snap_step,
+                        settings.pivot.cursor,
                     ));
                 }
             }
         }
     }
 
-    fn handle_ui_message(&self, message: &UiMessage, settings: &mut Settings) {
+    fn handle_ui_message(
+        &self,
+        message: &UiMessage,
+        engine: &Engine,
+        scenes: &SceneContainer,
+        settings: &mut Settings,
+    ) {
         if message.direction() != MessageDirection::FromWidget {
             return;
         }
 
-        if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
-            if message.destination() == self.enabled {
-                settings.move_mode_settings.grid_snapping = *value;
+        if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.mode {
+                if let Some(mode) = PivotMode::iter().nth(*index) {
+                    settings.pivot.mode = mode;
+                }
             }
-        } else if let Some(NumericUpDownMessage::Value(value)) = message.data() {
-            if message.destination() == self.x_step {
-                settings.move_mode_settings.x_snap_step = *value;
-            } else if message.destination() == self.y_step {
-                settings.move_mode_settings.y_snap_step = *value;
-            } else if message.destination() == self.z_step {
-                settings.move_mode_settings.z_snap_step = *value;
+        } else if let Some(Vec3EditorMessage::Value(value)) = message.data() {
+            if message.destination() == self.cursor && settings.pivot.cursor != *value {
+                settings.pivot.cursor = *value;
             }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.cursor_to_selection {
+                let Some(entry) = scenes.current_scene_entry_ref() else {
+                    return;
+                };
+                let Some(game_scene) = entry.controller.downcast_ref::<GameScene>() else {
+                    return;
+                };
+                if let Some((_, position)) = entry.selection.as_graph().and_then(|s| {
+                    s.global_rotation_position(&engine.scenes[game_scene.scene].graph)
+                }) {
+                    settings.pivot.cursor = position;
+                }
+            }
+        }
+    }
+}
+
+/// Alignment and distribution actions for selected scene nodes.
+struct AlignMenu {
+    menu: Handle<UiNode>,
+    buttons: Vec<(Handle<UiNode>, AlignAction)>,
+}
+
+impl AlignMenu {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let buttons = AlignAction::all()
+            .into_iter()
+            .map(|(action, name)| {
+                let (row, column) = match action {
+                    AlignAction::Align { axis, anchor } => (
+                        AlignAxis::ALL
+                            .iter()
+                            .position(|a| *a == axis)
+                            .unwrap_or_default(),
+                        AlignAnchor::ALL
+                            .iter()
+                            .position(|a| *a == anchor)
+                            .unwrap_or_default(),
+                    ),
+                    AlignAction::Distribute { axis } => (
+                        AlignAxis::ALL
+                            .iter()
+                            .position(|a| *a == axis)
+                            .unwrap_or_default(),
+                        AlignAnchor::ALL.len(),
+                    ),
+                    AlignAction::DropToGround => (AlignAxis::ALL.len(), 0),
+                };
+                let button = ButtonBuilder::new(
+                    WidgetBuilder::new()
+                        .with_height(22.0)
+                        .with_margin(Thickness::uniform(1.0))
+                        .on_row(row)
+                        .on_column(column),
+                )
+                .with_text(&name)
+                .build(ctx);
+                (button, action)
+            })
+            .collect::<Vec<_>>();
+
+        let mut grid = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(2.0))
+                .with_children(buttons.iter().map(|(button, _)| *button)),
+        );
+        for _ in 0..=AlignAxis::ALL.len() {
+            grid = grid.add_row(Row::auto());
+        }
+        for _ in 0..=AlignAnchor::ALL.len() {
+            grid = grid.add_column(Column::strict(100.0));
+        }
+        let content = grid.build(ctx);
+
+        let menu = DropdownMenuBuilder::new(WidgetBuilder::new())
+            .with_header(
+                ButtonBuilder::new(
+                    WidgetBuilder::new()
+                        .with_width(50.0)
+                        .with_height(22.0)
+                        .with_margin(Thickness::uniform(1.0))
+                        .with_tooltip(make_simple_tooltip(ctx, "Align Selected Nodes")),
+                )
+                .with_text("Align")
+                .build(ctx),
+            )
+            .with_content(content)
+            .build(ctx);
+
+        Self { menu, buttons }
+    }
+
+    fn handle_ui_message(
+        &self,
+        message: &UiMessage,
+        engine: &Engine,
+        scenes: &SceneContainer,
+        sender: &MessageSender,
+    ) {
+        let Some(ButtonMessage::Click) = message.data() else {
+            return;
+        };
+        let Some((_, action)) = self
+            .buttons
+            .iter()
+            .find(|(button, _)| *button == message.destination())
+        else {
+            return;
+        };
+        let Some(entry) = scenes.current_scene_entry_ref() else {
+            return;
+        };
+        let (Some(selection), Some(game_scene)) = (
+            entry.selection.as_graph(),
+            entry.controller.downcast_ref::<GameScene>(),
+        ) else {
+            return;
+        };
+        if let Some(command) = make_align_command(
+            &engine.scenes[game_scene.scene].graph,
+            selection,
+            *action,
+            game_scene.editor_objects_root,
+        ) {
+            sender.do_command(command);
         }
     }
 }
@@ -257,6 +685,7 @@ pub struct SceneViewer {
     frame: Handle<UiNode>,
     window: Handle<UiNode>,
     selection_frame: Handle<UiNode>,
+    lasso: Handle<UiNode>,
     interaction_modes: FxHashMap<Uuid, Handle<UiNode>>,
     camera_projection: Handle<UiNode>,
     play: Handle<UiNode>,
@@ -271,7 +700,14 @@ pub struct SceneViewer {
     scene_gizmo: SceneGizmo,
     scene_gizmo_image: Handle<UiNode>,
     debug_switches: Handle<UiNode>,
-    grid_snap_menu: GridSnappingMenu,
+    grid_snap_menu: SnappingMenu,
+    pivot_menu: PivotMenu,
+    align_menu: AlignMenu,
+    framing_overlay: Handle<UiNode>,
+    settings_receiver: Receiver<SettingsMessage>,
+    /// A color picker that waits for a color to be sampled from the scene frame.
+    eyedropper_target: Handle<UiNode>,
+    eyedropper_click: bool,
 }
 
 impl SceneViewer {
@@ -282,6 +718,7 @@ impl SceneViewer {
 
         let frame;
         let selection_frame;
+        let lasso;
         let camera_projection;
         let play;
         let stop;
@@ -296,7 +733,15 @@ impl SceneViewer {
         .with_orientation(Orientation::Horizontal)
         .build(ctx);
 
-        let grid_snap_menu = GridSnappingMenu::new(ctx, settings);
+        let grid_snap_menu = SnappingMenu::new(ctx, settings);
+        let pivot_menu = PivotMenu::new(ctx, settings);
+        let align_menu = AlignMenu::new(ctx);
+
+        let framing_overlay = FramingOverlayBuilder::new(WidgetBuilder::new())
+            .with_settings(settings.framing.clone())
+            .build(ctx);
+        let (settings_sender, settings_receiver) = mpsc::channel();
+        settings.subscribers.push(settings_sender);
 
         let global_position_display;
         let debug_switches;
@@ -320,6 +765,8 @@ impl SceneViewer {
                     camera_projection
                 })
                 .with_child(grid_snap_menu.menu)
+                .with_child(pivot_menu.menu)
+                .with_child(align_menu.menu)
                 .with_child({
                     global_position_display = Vec3EditorBuilder::<f32>::new(
                         WidgetBuilder::new()
@@ -490,6 +937,7 @@ impl SceneViewer {
                                     .with_child({
                                         frame = ImageBuilder::new(
                                             WidgetBuilder::new()
+                                                .with_child(framing_overlay)
                                                 .with_child(no_scene_reminder)
                                                 .with_child(scene_gizmo_image)
                                                 .with_allow_drop(true),
@@ -499,21 +947,35 @@ impl SceneViewer {
                                         frame
                                     })
                                     .with_child(
-                                        CanvasBuilder::new(WidgetBuilder::new().with_child({
-                                            selection_frame = BorderBuilder::new(
-                                                WidgetBuilder::new()
-                                                    .with_visibility(false)
-                                                    .with_background(Brush::Solid(
-                                                        Color::from_rgba(255, 255, 255, 40),
-                                                    ))
-                                                    .with_foreground(Brush::Solid(Color::opaque(
-                                                        0, 255, 0,
-                                                    ))),
-                                            )
-                                            .with_stroke_thickness(Thickness::uniform(1.0))
-                                            .build(ctx);
-                                            selection_frame
-                                        }))
+                                        CanvasBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_child({
+                                                    selection_frame = BorderBuilder::new(
+                                                        WidgetBuilder::new()
+                                                            .with_visibility(false)
+                                                            .with_background(Brush::Solid(
+                                                                Color::from_rgba(255, 255, 255, 40),
+                                                            ))
+                                                            .with_foreground(Brush::Solid(
+                                                                Color::opaque(0, 255, 0),
+                                                            )),
+                                                    )
+                                                    .with_stroke_thickness(Thickness::uniform(1.0))
+                                                    .build(ctx);
+                                                    selection_frame
+                                                })
+                                                .with_child({
+                                                    lasso = VectorImageBuilder::new(
+                                                        WidgetBuilder::new()
+                                                            .with_visibility(false)
+                                                            .with_foreground(Brush::Solid(
+                                                                Color::opaque(0, 255, 0),
+                                                            )),
+                                                    )
+                                                    .build(ctx);
+                                                    lasso
+                                                }),
+                                        )
                                         .build(ctx),
                                     ),
                             )
@@ -537,6 +999,7 @@ impl SceneViewer {
             frame,
             interaction_modes: Default::default(),
             selection_frame,
+            lasso,
             camera_projection,
             play,
             interaction_mode_panel,
@@ -550,11 +1013,78 @@ impl SceneViewer {
             scene_gizmo_image,
             debug_switches,
             grid_snap_menu,
+            pivot_menu,
+            align_menu,
+            framing_overlay,
+            settings_receiver,
+            eyedropper_target: Handle::NONE,
+            eyedropper_click: false,
         }
     }
 }
 
 impl SceneViewer {
+    fn finish_eyedropper(
+        &mut self,
+        button: MouseButton,
+        pos: Vector2<f32>,
+        screen_bounds: Rect<f32>,
+        entry: &EditorSceneEntry,
+        engine: &Engine,
+    ) {
+        let target = std::mem::take(&mut self.eyedropper_target);
+        self.eyedropper_click = true;
+
+        let ui = engine.user_interfaces.first();
+        ui.send_message(WidgetMessage::cursor(
+            self.frame,
+            MessageDirection::ToWidget,
+            None,
+        ));
+
+        // Any other button cancels sampling.
+        if button != MouseButton::Left {
+            return;
+        }
+
+        if let Some(color) = Self::sample_scene_color(pos, screen_bounds, entry, engine) {
+            ui.send_message(ColorPickerMessage::sampled_color(
+                target,
+                MessageDirection::ToWidget,
+                color,
+            ));
+        }
+    }
+
+    fn sample_scene_color(
+        pos: Vector2<f32>,
+        screen_bounds: Rect<f32>,
+        entry: &EditorSceneEntry,
+        engine: &Engine,
+    ) -> Option<Color> {
+        let game_scene = entry.controller.downcast_ref::<GameScene>()?;
+        let render_target = engine.scenes[game_scene.scene]
+            .rendering_options
+            .render_target
+            .as_ref()?;
+        let TextureKind::Rectangle { width, height } = render_target.data_ref().kind() else {
+            return None;
+        };
+        if screen_bounds.w() <= 0.0 || screen_bounds.h() <= 0.0 {
+            return None;
+        }
+        let rel_pos = (pos - screen_bounds.position).component_div(&screen_bounds.size);
+        let pixel = Vector2::new(
+            (rel_pos.x * width as f32) as u32,
+            (rel_pos.y * height as f32) as u32,
+        );
+        engine
+            .graphics_context
+            .as_initialized_ref()
+            .renderer
+            .read_scene_pixel(game_scene.scene, pixel)
+    }
+
     pub fn window(&self) -> Handle<UiNode> {
         self.window
     }
@@ -563,10 +1093,24 @@ impl SceneViewer {
         self.frame
     }
 
+    /// Returns a handle of the panel with contextual actions (the toolbar at the top of the scene
+    /// viewer).
+    pub fn contextual_actions(&self) -> Handle<UiNode> {
+        self.contextual_actions
+    }
+
+    pub fn framing_overlay(&self) -> Handle<UiNode> {
+        self.framing_overlay
+    }
+
     pub fn selection_frame(&self) -> Handle<UiNode> {
         self.selection_frame
     }
 
+    pub fn lasso(&self) -> Handle<UiNode> {
+        self.lasso
+    }
+
     pub fn handle_message(&mut self, message: &Message, engine: &mut Engine) {
         if let Message::SetInteractionMode(mode) = message {
             if let Some(&active_button) = self.interaction_modes.get(mode) {
@@ -636,9 +1180,24 @@ impl SceneViewer {
         mode: &Mode,
     ) {
         self.grid_snap_menu.handle_ui_message(message, settings);
+        self.pivot_menu
+            .handle_ui_message(message, engine, scenes, settings);
+        self.align_menu
+            .handle_ui_message(message, engine, scenes, &self.sender);
 
         let ui = &engine.user_interfaces.first();
 
+        if let Some(ColorPickerMessage::EyedropperRequested) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                self.eyedropper_target = message.destination();
+                ui.send_message(WidgetMessage::cursor(
+                    self.frame,
+                    MessageDirection::ToWidget,
+                    Some(CursorIcon::Crosshair),
+                ));
+            }
+        }
+
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             for (mode_id, mode_button) in self.interaction_modes.iter() {
                 if message.destination() == *mode_button {
@@ -740,17 +1299,26 @@ impl SceneViewer {
                     let screen_bounds = self.frame_bounds(engine.user_interfaces.first());
                     match *msg {
                         WidgetMessage::MouseDown { button, pos, .. } => {
-                            engine
-                                .user_interfaces
-                                .first_mut()
-                                .capture_mouse(self.frame());
+                            if self.eyedropper_target.is_some() {
+                                self.finish_eyedropper(button, pos, screen_bounds, entry, engine);
+                            } else {
+                                engine
+                                    .user_interfaces
+                                    .first_mut()
+                                    .capture_mouse(self.frame());
 
-                            entry.on_mouse_down(button, pos, screen_bounds, engine, settings)
+                                entry.on_mouse_down(button, pos, screen_bounds, engine, settings)
+                            }
                         }
                         WidgetMessage::MouseUp { button, pos, .. } => {
-                            engine.user_interfaces.first_mut().release_mouse_capture();
+                            // Mouse up that finishes eyedropper sampling must not reach the
+                            // interaction modes, they haven't seen the mouse down.
+                            let finishes_eyedropper = std::mem::take(&mut self.eyedropper_click);
+                            if !finishes_eyedropper {
+                                engine.user_interfaces.first_mut().release_mouse_capture();
 
-                            entry.on_mouse_up(button, pos, screen_bounds, engine, settings)
+                                entry.on_mouse_up(button, pos, screen_bounds, engine, settings)
+                            }
                         }
                         WidgetMessage::MouseWheel { amount, .. } => {
                             entry.on_mouse_wheel(amount, engine, settings);
@@ -1049,6 +1617,23 @@ impl SceneViewer {
     pub fn pre_update(&self, settings: &Settings, engine: &mut Engine) {
         self.grid_snap_menu
             .update(settings, engine.user_interfaces.first());
+        self.pivot_menu
+            .update(settings, engine.user_interfaces.first());
+
+        for message in self.settings_receiver.try_iter() {
+            match message {
+                SettingsMessage::Changed => {
+                    engine
+                        .user_interfaces
+                        .first()
+                        .send_message(FramingOverlayMessage::settings(
+                            self.framing_overlay,
+                            MessageDirection::ToWidget,
+                            settings.framing.clone(),
+                        ));
+                }
+            }
+        }
     }
 
     pub fn update(&self, game_scene: &GameScene, engine: &mut Engine) {
@@ -1,9 +1,15 @@
-use crate::fyrox::core::reflect::prelude::*;
+use crate::fyrox::{core::reflect::prelude::*, scene::debug::PhysicsDebugDrawSettings};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
 pub struct DebuggingSettings {
     pub show_physics: bool,
+    #[reflect(
+        description = "Defines which parts of the physics world (collider shapes, contacts, joints, etc.) \
+        will be drawn when physics is shown, and what colors will be used for them."
+    )]
+    #[serde(default)]
+    pub physics: PhysicsDebugDrawSettings,
     pub show_bounds: bool,
     pub show_tbn: bool,
     #[serde(default)]
@@ -12,6 +18,12 @@ pub struct DebuggingSettings {
     pub show_light_bounds: bool,
     #[serde(default)]
     pub show_camera_bounds: bool,
+    #[reflect(
+        description = "Shows lines from the listener to each sound with enabled occlusion. \
+        Color of each line changes from green to red depending on the amount of occlusion."
+    )]
+    #[serde(default)]
+    pub show_sound_occlusion: bool,
     #[reflect(description = "Size of pictograms in meters. It is used for objects like lights.")]
     #[serde(default)]
     pub pictogram_size: f32,
@@ -26,11 +38,13 @@ impl Default for DebuggingSettings {
     fn default() -> Self {
         Self {
             show_physics: true,
+            physics: Default::default(),
             show_bounds: true,
             show_tbn: false,
             show_terrains: false,
             show_light_bounds: true,
             show_camera_bounds: true,
+            show_sound_occlusion: false,
             pictogram_size: 0.33,
             save_scene_in_text_form: false,
         }
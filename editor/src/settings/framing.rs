@@ -0,0 +1,78 @@
+use crate::fyrox::core::{reflect::prelude::*, uuid_provider};
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    PartialEq,
+    Debug,
+    Serialize,
+    Deserialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum FramingAspectRatio {
+    #[default]
+    Free,
+    Widescreen16x9,
+    Standard4x3,
+    Ultrawide21x9,
+    Anamorphic2_39,
+    Square,
+    Portrait9x16,
+}
+
+uuid_provider!(FramingAspectRatio = "0f6a3f0e-54a4-4d8e-a3a1-3e1c9b7f2d58");
+
+impl FramingAspectRatio {
+    /// Returns width-to-height ratio or `None` if there's no target aspect ratio.
+    pub fn value(self) -> Option<f32> {
+        match self {
+            Self::Free => None,
+            Self::Widescreen16x9 => Some(16.0 / 9.0),
+            Self::Standard4x3 => Some(4.0 / 3.0),
+            Self::Ultrawide21x9 => Some(21.0 / 9.0),
+            Self::Anamorphic2_39 => Some(2.39),
+            Self::Square => Some(1.0),
+            Self::Portrait9x16 => Some(9.0 / 16.0),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
+pub struct FramingSettings {
+    #[reflect(
+        description = "Target aspect ratio of the final picture. Everything outside of it is masked \
+        in the scene preview."
+    )]
+    pub aspect_ratio: FramingAspectRatio,
+    #[reflect(
+        description = "Opacity of the mask, that covers the area outside of the target aspect ratio."
+    )]
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub mask_opacity: f32,
+    #[reflect(description = "Show action and title safe areas in the scene preview.")]
+    pub show_safe_areas: bool,
+    #[reflect(description = "Size of the action safe area relative to the framed picture.")]
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub action_safe: f32,
+    #[reflect(description = "Size of the title safe area relative to the framed picture.")]
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub title_safe: f32,
+}
+
+impl Default for FramingSettings {
+    fn default() -> Self {
+        Self {
+            aspect_ratio: Default::default(),
+            mask_opacity: 0.85,
+            show_safe_areas: false,
+            action_safe: 0.93,
+            title_safe: 0.9,
+        }
+    }
+}
@@ -34,6 +34,20 @@ pub struct GeneralSettings {
     )]
     #[serde(default = "default_generate_previews")]
     pub generate_previews: bool,
+
+    #[reflect(
+        description = "Enables or disables periodic saving of open scenes to the recovery folder. \
+    If the editor crashes, it will offer to restore the previous session on next start."
+    )]
+    #[serde(default = "default_autosave")]
+    pub autosave: bool,
+
+    #[reflect(
+        description = "Interval (in seconds) between autosaves.",
+        min_value = 5.0
+    )]
+    #[serde(default = "default_autosave_interval")]
+    pub autosave_interval: f32,
 }
 
 fn default_suspension_state() -> bool {
@@ -52,6 +66,14 @@ fn default_generate_previews() -> bool {
     true
 }
 
+fn default_autosave() -> bool {
+    true
+}
+
+fn default_autosave_interval() -> f32 {
+    60.0
+}
+
 #[derive(
     Copy,
     Clone,
@@ -85,6 +107,8 @@ impl Default for GeneralSettings {
             script_editor: default_script_editor(),
             max_history_entries: default_max_history_entries(),
             generate_previews: default_generate_previews(),
+            autosave: default_autosave(),
+            autosave_interval: default_autosave_interval(),
         }
     }
 }
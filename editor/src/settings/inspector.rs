@@ -0,0 +1,39 @@
+use crate::fyrox::core::reflect::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Default, Reflect)]
+pub struct InspectorSettings {
+    /// Favorite properties of every object type. The key is a type name of an object, the value is
+    /// a list of names of its favorite properties.
+    #[serde(default)]
+    #[reflect(hidden)]
+    pub favorite_properties: HashMap<String, Vec<String>>,
+}
+
+impl InspectorSettings {
+    /// Returns the favorite properties of the object type with the given name.
+    pub fn favorites(&self, type_name: &str) -> &[String] {
+        self.favorite_properties
+            .get(type_name)
+            .map(|favorites| favorites.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Adds the given property to the favorites of the object type, or removes it if it is already
+    /// there.
+    pub fn toggle_favorite(&mut self, type_name: &str, property: &str) {
+        let favorites = self
+            .favorite_properties
+            .entry(type_name.to_string())
+            .or_default();
+        if let Some(position) = favorites.iter().position(|p| p == property) {
+            favorites.remove(position);
+        } else {
+            favorites.push(property.to_string());
+        }
+        if favorites.is_empty() {
+            self.favorite_properties.remove(type_name);
+        }
+    }
+}
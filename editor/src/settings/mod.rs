@@ -4,6 +4,7 @@ use crate::{
         core::{log::Log, pool::Handle, reflect::prelude::*, scope_profile},
         gui::{
             button::{ButtonBuilder, ButtonMessage},
+            file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
             grid::{Column, GridBuilder, Row},
             inspector::{
                 editors::{
@@ -17,11 +18,16 @@ use crate::{
             message::{MessageDirection, UiMessage},
             scroll_viewer::ScrollViewerBuilder,
             stack_panel::StackPanelBuilder,
-            widget::WidgetBuilder,
+            widget::{WidgetBuilder, WidgetMessage},
             window::{WindowBuilder, WindowMessage, WindowTitle},
             HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
         },
-        renderer::{CsmSettings, QualitySettings, ShadowMapPrecision},
+        renderer::{
+            AmbientOcclusionQuality, CsmSettings, QualitySettings, ShadowMapPrecision,
+            VolumetricLightQuality,
+        },
+        scene::debug::PhysicsDebugDrawSettings,
+        utils::navmesh_baker::NavmeshBakingSettings,
     },
     inspector::editors::make_property_editors_container,
     message::MessageSender,
@@ -29,16 +35,22 @@ use crate::{
         build::BuildSettings,
         camera::CameraSettings,
         debugging::DebuggingSettings,
+        framing::{FramingAspectRatio, FramingSettings},
         general::{GeneralSettings, ScriptEditor},
         graphics::GraphicsSettings,
+        inspector::InspectorSettings,
         keys::{KeyBindings, TerrainKeyBindings},
         model::ModelSettings,
-        move_mode::MoveInteractionModeSettings,
+        move_mode::{MoveInteractionModeSettings, SnapSpace},
         navmesh::NavmeshSettings,
+        pivot::{PivotMode, PivotSettings},
+        profile::SettingsProfile,
         recent::RecentFiles,
+        remote::RemoteControlSettings,
         rotate_mode::RotateInteractionModeSettings,
+        scale_mode::ScaleInteractionModeSettings,
         scene::SceneSettings,
-        selection::SelectionSettings,
+        selection::{SelectionOcclusion, SelectionSettings, SelectionShape},
         windows::WindowsSettings,
     },
     Engine, MSG_SYNC_FLAG,
@@ -52,30 +64,46 @@ use std::{
     fs::File,
     io::Write,
     ops::{Deref, DerefMut},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
 pub mod build;
 pub mod camera;
 pub mod debugging;
+pub mod framing;
 pub mod general;
 pub mod graphics;
+pub mod inspector;
 pub mod keys;
 pub mod model;
 pub mod move_mode;
 pub mod navmesh;
+pub mod pivot;
+pub mod profile;
 pub mod recent;
+pub mod remote;
 pub mod rotate_mode;
+pub mod scale_mode;
 pub mod scene;
 pub mod selection;
 pub mod windows;
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum ProfileAction {
+    Import,
+    Export,
+}
+
 pub struct SettingsWindow {
     window: Handle<UiNode>,
     ok: Handle<UiNode>,
     default: Handle<UiNode>,
+    import: Handle<UiNode>,
+    export: Handle<UiNode>,
     inspector: Handle<UiNode>,
+    file_selector: Handle<UiNode>,
+    profile_action: Option<ProfileAction>,
 }
 
 #[derive(Deserialize, Serialize, PartialEq, Clone, Default, Debug, Reflect)]
@@ -89,10 +117,18 @@ pub struct SettingsData {
     pub debugging: DebuggingSettings,
     pub move_mode_settings: MoveInteractionModeSettings,
     pub rotate_mode_settings: RotateInteractionModeSettings,
+    #[serde(default)]
+    pub scale_mode_settings: ScaleInteractionModeSettings,
+    #[serde(default)]
+    pub pivot: PivotSettings,
     pub model: ModelSettings,
     pub camera: CameraSettings,
+    #[serde(default)]
+    pub framing: FramingSettings,
     pub navmesh: NavmeshSettings,
     pub key_bindings: KeyBindings,
+    #[serde(default)]
+    pub remote_control: RemoteControlSettings,
     #[reflect(hidden)]
     pub scene_settings: HashMap<PathBuf, SceneSettings>,
     #[reflect(hidden)]
@@ -100,15 +136,24 @@ pub struct SettingsData {
     #[serde(default)]
     #[reflect(hidden)]
     pub windows: WindowsSettings,
+    #[serde(default)]
+    #[reflect(hidden)]
+    pub inspector: InspectorSettings,
 }
 
 pub enum SettingsMessage {
     Changed,
 }
 
+/// Editor settings. Effective settings are machine-level settings with project-level overrides
+/// applied on top of them, see [`SettingsProfile`] docs for more info.
 #[derive(Default)]
 pub struct Settings {
     settings: SettingsData,
+    /// Project-level overrides.
+    project: SettingsProfile,
+    /// Machine-level values of the sections, that are overridden by the project.
+    machine_sections: SettingsProfile,
     need_save: bool,
     pub subscribers: Vec<Sender<SettingsMessage>>,
 }
@@ -123,10 +168,7 @@ impl Deref for Settings {
 
 impl DerefMut for Settings {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        self.need_save = true;
-
-        self.subscribers
-            .retain_mut(|subscriber| subscriber.send(SettingsMessage::Changed).is_ok());
+        self.notify_changed();
 
         &mut self.settings
     }
@@ -134,22 +176,90 @@ impl DerefMut for Settings {
 
 impl Settings {
     pub fn load() -> Result<Self, SettingsError> {
-        Ok(Settings {
-            settings: SettingsData::load()?,
+        let project = match SettingsProfile::load_project() {
+            Ok(project) => project,
+            Err(e) => {
+                Log::warn(format!(
+                    "Failed to load project settings, they will be ignored. Reason: {:?}",
+                    e
+                ));
+                Default::default()
+            }
+        };
+
+        if !project.is_empty() {
+            Log::info(format!(
+                "Project settings override the following sections: {}",
+                project.sections().join(", ")
+            ));
+        }
+
+        let mut settings = Settings {
+            settings: Default::default(),
+            project,
+            machine_sections: Default::default(),
             need_save: false,
             subscribers: Default::default(),
-        })
+        };
+        settings.set_machine_settings(SettingsData::load()?);
+        Ok(settings)
+    }
+
+    /// Returns project-level overrides.
+    pub fn project_overrides(&self) -> &SettingsProfile {
+        &self.project
+    }
+
+    /// Returns machine-level settings, without project-level overrides.
+    pub fn machine_settings(&self) -> SettingsData {
+        let mut settings = self.settings.clone();
+        self.machine_sections.apply(&mut settings);
+        settings
+    }
+
+    fn set_machine_settings(&mut self, settings: SettingsData) {
+        self.machine_sections = self.project.capture(&settings);
+        self.settings = settings;
+        self.project.apply(&mut self.settings);
+    }
+
+    /// Resets machine-level settings to defaults. Project-level overrides are kept.
+    pub fn reset_to_default(&mut self) {
+        self.set_machine_settings(Default::default());
+        self.notify_changed();
+    }
+
+    /// Applies the sections of the given profile to machine-level settings. Sections, that are
+    /// overridden by the project, are stored, but won't have any effect while the project is
+    /// opened.
+    pub fn import_profile(&mut self, profile: &SettingsProfile) {
+        let mut settings = self.machine_settings();
+        profile.apply(&mut settings);
+        self.set_machine_settings(settings);
+        self.notify_changed();
+    }
+
+    /// Creates a profile with every section of the effective settings.
+    pub fn export_profile(&self) -> SettingsProfile {
+        SettingsProfile::from_settings(&self.settings)
+    }
+
+    fn notify_changed(&mut self) {
+        self.need_save = true;
+
+        self.subscribers
+            .retain_mut(|subscriber| subscriber.send(SettingsMessage::Changed).is_ok());
     }
 
     pub fn force_save(&mut self) {
         self.need_save = false;
-        Log::verify(self.settings.save());
+        self.settings.recent.deduplicate_and_refresh();
+        Log::verify(self.machine_settings().save());
     }
 
     pub fn update(&mut self) {
         if self.need_save {
-            self.need_save = false;
-            Log::verify(self.settings.save());
+            self.force_save();
         }
     }
 }
@@ -209,21 +319,38 @@ impl SettingsData {
         container.insert(InspectablePropertyEditorDefinition::<GeneralSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<GraphicsSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<SelectionSettings>::new());
+        container.insert(EnumPropertyEditorDefinition::<SelectionShape>::new());
+        container.insert(EnumPropertyEditorDefinition::<SelectionOcclusion>::new());
         container.insert(EnumPropertyEditorDefinition::<ShadowMapPrecision>::new());
+        container.insert(EnumPropertyEditorDefinition::<VolumetricLightQuality>::new());
+        container.insert(EnumPropertyEditorDefinition::<AmbientOcclusionQuality>::new());
         container.insert(EnumPropertyEditorDefinition::<ScriptEditor>::new());
         container.insert(InspectablePropertyEditorDefinition::<DebuggingSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<
+            PhysicsDebugDrawSettings,
+        >::new());
         container.insert(InspectablePropertyEditorDefinition::<CsmSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<QualitySettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<CameraSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<FramingSettings>::new());
+        container.insert(EnumPropertyEditorDefinition::<FramingAspectRatio>::new());
         container.insert(InspectablePropertyEditorDefinition::<
             MoveInteractionModeSettings,
         >::new());
         container.insert(InspectablePropertyEditorDefinition::<
             RotateInteractionModeSettings,
         >::new());
+        container.insert(InspectablePropertyEditorDefinition::<
+            ScaleInteractionModeSettings,
+        >::new());
+        container.insert(EnumPropertyEditorDefinition::<SnapSpace>::new());
+        container.insert(InspectablePropertyEditorDefinition::<PivotSettings>::new());
+        container.insert(EnumPropertyEditorDefinition::<PivotMode>::new());
         container.insert(InspectablePropertyEditorDefinition::<ModelSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<NavmeshSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<NavmeshBakingSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<KeyBindings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<RemoteControlSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<TerrainKeyBindings>::new());
         container.insert(InspectablePropertyEditorDefinition::<BuildSettings>::new());
         container.insert(VecCollectionPropertyEditorDefinition::<EnvironmentVariable>::new());
@@ -250,6 +377,8 @@ impl SettingsWindow {
     pub fn new(engine: &mut Engine) -> Self {
         let ok;
         let default;
+        let import;
+        let export;
 
         let ctx = &mut engine.user_interfaces.first_mut().build_ctx();
 
@@ -275,6 +404,26 @@ impl SettingsWindow {
                                 WidgetBuilder::new()
                                     .on_row(1)
                                     .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        import = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(80.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Import...")
+                                        .build(ctx);
+                                        import
+                                    })
+                                    .with_child({
+                                        export = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(80.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Export...")
+                                        .build(ctx);
+                                        export
+                                    })
                                     .with_child({
                                         default = ButtonBuilder::new(
                                             WidgetBuilder::new()
@@ -311,10 +460,59 @@ impl SettingsWindow {
             window,
             ok,
             default,
+            import,
+            export,
             inspector,
+            file_selector: Handle::NONE,
+            profile_action: None,
         }
     }
 
+    fn open_file_selector(&mut self, action: ProfileAction, ui: &mut UserInterface) {
+        let (title, mode) = match action {
+            ProfileAction::Import => ("Import Settings Profile", FileBrowserMode::Open),
+            ProfileAction::Export => (
+                "Export Settings Profile As",
+                FileBrowserMode::Save {
+                    default_file_name: PathBuf::from("settings_profile.ron"),
+                },
+            ),
+        };
+
+        self.profile_action = Some(action);
+        self.file_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::text(title)),
+        )
+        .with_mode(mode)
+        .with_filter(Filter::new(|p: &Path| {
+            p.is_dir() || p.extension().is_some_and(|ext| ext == "ron")
+        }))
+        .build(&mut ui.build_ctx());
+
+        ui.send_message(WindowMessage::open_modal(
+            self.file_selector,
+            MessageDirection::ToWidget,
+            true,
+            true,
+        ));
+        ui.send_message(FileSelectorMessage::root(
+            self.file_selector,
+            MessageDirection::ToWidget,
+            Some(std::env::current_dir().unwrap()),
+        ));
+    }
+
+    fn destroy_file_selector(&mut self, ui: &UserInterface) {
+        ui.send_message(WidgetMessage::remove(
+            self.file_selector,
+            MessageDirection::ToWidget,
+        ));
+        self.file_selector = Handle::NONE;
+        self.profile_action = None;
+    }
+
     pub fn open(&self, ui: &mut UserInterface, settings: &Settings, sender: &MessageSender) {
         ui.send_message(WindowMessage::open(
             self.window,
@@ -363,12 +561,76 @@ impl SettingsWindow {
                         MessageDirection::ToWidget,
                     ));
             } else if message.destination() == self.default {
-                **settings = Default::default();
+                settings.reset_to_default();
 
                 self.sync_to_model(engine.user_interfaces.first_mut(), settings, sender);
+            } else if message.destination() == self.import {
+                self.open_file_selector(ProfileAction::Import, engine.user_interfaces.first_mut());
+            } else if message.destination() == self.export {
+                self.open_file_selector(ProfileAction::Export, engine.user_interfaces.first_mut());
+            }
+        } else if let Some(msg) = message.data::<FileSelectorMessage>() {
+            if message.destination() == self.file_selector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                match msg {
+                    FileSelectorMessage::Commit(path) => {
+                        match self.profile_action {
+                            Some(ProfileAction::Import) => match SettingsProfile::load(path) {
+                                Ok(profile) => {
+                                    settings.import_profile(&profile);
+                                    Log::info(format!(
+                                        "Settings profile {} was successfully imported!",
+                                        path.display()
+                                    ));
+                                    self.sync_to_model(
+                                        engine.user_interfaces.first_mut(),
+                                        settings,
+                                        sender,
+                                    );
+                                }
+                                Err(e) => Log::err(format!(
+                                    "Unable to import settings profile {}. Reason: {:?}",
+                                    path.display(),
+                                    e
+                                )),
+                            },
+                            Some(ProfileAction::Export) => {
+                                match settings.export_profile().save(path) {
+                                    Ok(()) => Log::info(format!(
+                                        "Settings profile {} was successfully exported!",
+                                        path.display()
+                                    )),
+                                    Err(e) => Log::err(format!(
+                                        "Unable to export settings profile {}. Reason: {:?}",
+                                        path.display(),
+                                        e
+                                    )),
+                                }
+                            }
+                            None => (),
+                        }
+                        self.destroy_file_selector(engine.user_interfaces.first());
+                    }
+                    FileSelectorMessage::Cancel => {
+                        self.destroy_file_selector(engine.user_interfaces.first())
+                    }
+                    _ => (),
+                }
             }
         } else if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
             if message.destination() == self.inspector {
+                if settings
+                    .project_overrides()
+                    .has_section(&property_changed.name)
+                {
+                    Log::warn(format!(
+                        "The {} section is overridden by {}, the change will not be saved.",
+                        property_changed.name,
+                        SettingsProfile::PROJECT_FILE_NAME
+                    ));
+                }
+
                 settings.handle_property_changed(property_changed);
             }
         }
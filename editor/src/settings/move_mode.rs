@@ -1,5 +1,47 @@
-use crate::fyrox::core::{algebra::Vector3, math, reflect::prelude::*};
+use crate::fyrox::core::{algebra::Vector3, math, reflect::prelude::*, uuid_provider};
 use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumIter, EnumString, VariantNames};
+
+/// Defines how snapping increments are applied to a transformed value.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Debug,
+    Serialize,
+    Deserialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumIter,
+    VariantNames,
+)]
+pub enum SnapSpace {
+    /// A value is snapped to the absolute grid, so it always becomes a multiple of a step.
+    #[default]
+    World,
+    /// An offset from the initial value is snapped, so a value changes in steps, but keeps its
+    /// original fractional part.
+    Local,
+}
+
+uuid_provider!(SnapSpace = "9a3c0d57-2f4e-4b8a-a6c1-7e5d2b9f0c36");
+
+impl SnapSpace {
+    pub fn snap_vector(
+        self,
+        initial: Vector3<f32>,
+        value: Vector3<f32>,
+        snap: impl Fn(Vector3<f32>) -> Vector3<f32>,
+    ) -> Vector3<f32> {
+        match self {
+            SnapSpace::World => snap(value),
+            SnapSpace::Local => initial + snap(value - initial),
+        }
+    }
+}
 
 #[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Reflect)]
 pub struct MoveInteractionModeSettings {
@@ -7,6 +49,14 @@ pub struct MoveInteractionModeSettings {
     pub x_snap_step: f32,
     pub y_snap_step: f32,
     pub z_snap_step: f32,
+    #[serde(default)]
+    pub space: SnapSpace,
+    /// If enabled, moved objects are snapped to the closest vertex of a mesh under the cursor.
+    #[serde(default)]
+    pub vertex_snapping: bool,
+    /// If enabled, moved objects are placed on a surface under the cursor.
+    #[serde(default)]
+    pub surface_snapping: bool,
 }
 
 impl Default for MoveInteractionModeSettings {
@@ -16,6 +66,9 @@ impl Default for MoveInteractionModeSettings {
             x_snap_step: 0.05,
             y_snap_step: 0.05,
             z_snap_step: 0.05,
+            space: Default::default(),
+            vertex_snapping: false,
+            surface_snapping: false,
         }
     }
 }
@@ -32,4 +85,47 @@ impl MoveInteractionModeSettings {
             v
         }
     }
+
+    /// Snaps a new position of an object, that was moved from the `initial` position, taking the
+    /// snapping space into account.
+    pub fn try_snap_position(&self, initial: Vector3<f32>, v: Vector3<f32>) -> Vector3<f32> {
+        self.space
+            .snap_vector(initial, v, |v| self.try_snap_vector_to_grid(v))
+    }
+
+    pub fn is_snapping_to_objects(&self) -> bool {
+        self.vertex_snapping || self.surface_snapping
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        fyrox::core::algebra::Vector3,
+        settings::move_mode::{MoveInteractionModeSettings, SnapSpace},
+    };
+
+    #[test]
+    fn test_snap_space() {
+        let mut settings = MoveInteractionModeSettings {
+            grid_snapping: true,
+            x_snap_step: 1.0,
+            y_snap_step: 1.0,
+            z_snap_step: 1.0,
+            ..Default::default()
+        };
+        let initial = Vector3::new(0.25, 0.0, 0.0);
+        let moved = Vector3::new(1.4, 0.0, 0.0);
+
+        assert_eq!(
+            settings.try_snap_position(initial, moved),
+            Vector3::new(1.0, 0.0, 0.0)
+        );
+
+        settings.space = SnapSpace::Local;
+        assert_eq!(
+            settings.try_snap_position(initial, moved),
+            Vector3::new(1.25, 0.0, 0.0)
+        );
+    }
 }
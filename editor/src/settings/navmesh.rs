@@ -1,4 +1,4 @@
-use crate::fyrox::core::reflect::prelude::*;
+use crate::fyrox::{core::reflect::prelude::*, utils::navmesh_baker::NavmeshBakingSettings};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
@@ -10,6 +10,10 @@ pub struct NavmeshSettings {
 
     #[reflect(description = "Radius of a nav mesh vertex.")]
     pub vertex_radius: f32,
+
+    #[reflect(description = "Settings that are used to bake a navmesh from scene geometry.")]
+    #[serde(default)]
+    pub baking: NavmeshBakingSettings,
 }
 
 impl Default for NavmeshSettings {
@@ -17,6 +21,7 @@ impl Default for NavmeshSettings {
         Self {
             draw_all: true,
             vertex_radius: 0.2,
+            baking: Default::default(),
         }
     }
 }
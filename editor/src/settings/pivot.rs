@@ -0,0 +1,40 @@
+use crate::fyrox::core::{algebra::Vector3, reflect::prelude::*, uuid_provider};
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumIter, EnumString, VariantNames};
+
+/// Defines a point around which selected objects are rotated and scaled.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Debug,
+    Serialize,
+    Deserialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumIter,
+    VariantNames,
+)]
+pub enum PivotMode {
+    /// Every object is rotated and scaled around its own origin.
+    #[default]
+    IndividualOrigins,
+    /// Objects are rotated and scaled around the average position of their origins.
+    MedianPoint,
+    /// Objects are rotated and scaled around the origin of the last selected object.
+    ActiveObject,
+    /// Objects are rotated and scaled around the 3D cursor.
+    Cursor,
+}
+
+uuid_provider!(PivotMode = "4c1e8b2d-6f3a-4e95-b7d0-2a9c5f8e1b63");
+
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Default, Reflect)]
+pub struct PivotSettings {
+    pub mode: PivotMode,
+    /// Position of the 3D cursor in world coordinates.
+    pub cursor: Vector3<f32>,
+}
@@ -0,0 +1,166 @@
+//! Settings profiles and project-level settings overrides.
+//!
+//! Editor settings consist of two layers: machine-level settings (`settings.ron`), that are edited
+//! and saved by the editor, and project-level settings (`project_settings.ron`), that are meant
+//! to be shipped with a project. Project-level settings are stored as a [`SettingsProfile`] and
+//! every section defined in it overrides the respective section of the machine-level settings.
+//! The same format is used to import/export settings profiles.
+
+use crate::settings::{
+    build::BuildSettings, camera::CameraSettings, debugging::DebuggingSettings,
+    framing::FramingSettings, general::GeneralSettings, graphics::GraphicsSettings,
+    keys::KeyBindings, model::ModelSettings, move_mode::MoveInteractionModeSettings,
+    navmesh::NavmeshSettings, pivot::PivotSettings, remote::RemoteControlSettings,
+    rotate_mode::RotateInteractionModeSettings, scale_mode::ScaleInteractionModeSettings,
+    selection::SelectionSettings, SettingsData, SettingsError,
+};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::Write, path::Path};
+
+macro_rules! define_profile {
+    ($($field:ident: $ty:ty),* $(,)?) => {
+        /// A set of optional settings sections. Only the sections, that are defined in a profile,
+        /// are applied to the settings. Per-project state (recent files, scene settings, window
+        /// layout) is never a part of a profile.
+        #[derive(Deserialize, Serialize, PartialEq, Clone, Default, Debug)]
+        pub struct SettingsProfile {
+            $(
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub $field: Option<$ty>,
+            )*
+        }
+
+        impl SettingsProfile {
+            /// Creates a profile, that contains every section of the given settings.
+            pub fn from_settings(settings: &SettingsData) -> Self {
+                Self {
+                    $($field: Some(settings.$field.clone()),)*
+                }
+            }
+
+            /// Creates a profile, that contains the sections of the given settings, which are
+            /// defined in this profile.
+            pub fn capture(&self, settings: &SettingsData) -> Self {
+                Self {
+                    $($field: self.$field.as_ref().map(|_| settings.$field.clone()),)*
+                }
+            }
+
+            /// Overwrites the sections of the given settings with the sections of the profile.
+            pub fn apply(&self, settings: &mut SettingsData) {
+                $(
+                    if let Some(section) = self.$field.as_ref() {
+                        settings.$field = section.clone();
+                    }
+                )*
+            }
+
+            /// Returns the names of the sections, that are defined in the profile.
+            pub fn sections(&self) -> Vec<&'static str> {
+                let mut sections = Vec::new();
+                $(
+                    if self.$field.is_some() {
+                        sections.push(stringify!($field));
+                    }
+                )*
+                sections
+            }
+        }
+    };
+}
+
+define_profile! {
+    general: GeneralSettings,
+    graphics: GraphicsSettings,
+    selection: SelectionSettings,
+    debugging: DebuggingSettings,
+    move_mode_settings: MoveInteractionModeSettings,
+    rotate_mode_settings: RotateInteractionModeSettings,
+    scale_mode_settings: ScaleInteractionModeSettings,
+    pivot: PivotSettings,
+    model: ModelSettings,
+    camera: CameraSettings,
+    framing: FramingSettings,
+    navmesh: NavmeshSettings,
+    key_bindings: KeyBindings,
+    remote_control: RemoteControlSettings,
+    build: BuildSettings,
+}
+
+impl SettingsProfile {
+    /// Name of the file with project-level settings overrides. The file is located in the root
+    /// folder of a project.
+    pub const PROJECT_FILE_NAME: &'static str = "project_settings.ron";
+
+    /// Returns `true` if the profile does not define any section.
+    pub fn is_empty(&self) -> bool {
+        self.sections().is_empty()
+    }
+
+    /// Returns `true` if the profile defines a section with the given name.
+    pub fn has_section(&self, name: &str) -> bool {
+        self.sections().contains(&name)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SettingsError> {
+        let file = File::open(path)?;
+        Ok(ron::de::from_reader(file)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SettingsError> {
+        let mut file = File::create(path)?;
+        file.write_all(ron::ser::to_string_pretty(self, PrettyConfig::default())?.as_bytes())?;
+        Ok(())
+    }
+
+    /// Loads project-level settings overrides from the current working directory. Missing file
+    /// means that there's no overrides.
+    pub fn load_project() -> Result<Self, SettingsError> {
+        let path = Path::new(Self::PROJECT_FILE_NAME);
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Default::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::settings::{profile::SettingsProfile, SettingsData};
+
+    #[test]
+    fn test_override() {
+        let mut project = SettingsProfile::default();
+        let mut graphics = SettingsData::default().graphics;
+        graphics.z_far = 1000.0;
+        project.graphics = Some(graphics);
+        assert_eq!(project.sections(), vec!["graphics"]);
+
+        let mut machine = SettingsData::default();
+        machine.graphics.z_far = 50.0;
+        machine.camera.speed = 20.0;
+        let machine_sections = project.capture(&machine);
+
+        let mut effective = machine.clone();
+        project.apply(&mut effective);
+        assert_eq!(effective.graphics.z_far, 1000.0);
+        assert_eq!(effective.camera.speed, 20.0);
+
+        // Overridden sections must not leak into machine-level settings.
+        machine_sections.apply(&mut effective);
+        assert_eq!(effective, machine);
+    }
+
+    #[test]
+    fn test_serialization() {
+        let profile = SettingsProfile::from_settings(&SettingsData::default());
+        let text = ron::to_string(&profile).unwrap();
+        let loaded: SettingsProfile = ron::from_str(&text).unwrap();
+        assert_eq!(loaded, profile);
+
+        let partial: SettingsProfile = ron::from_str("(camera: None)").unwrap();
+        assert!(partial.is_empty());
+    }
+}
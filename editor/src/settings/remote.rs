@@ -0,0 +1,62 @@
+use crate::fyrox::{core::reflect::prelude::*, utils::line_server::generate_token};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
+pub struct RemoteControlSettings {
+    #[reflect(
+        description = "Enables local remote control server, that allows external tools (DCC bridges, for example) \
+        to open scenes, select nodes, change properties and reimport assets in the running editor."
+    )]
+    pub enabled: bool,
+
+    #[reflect(
+        description = "A TCP port on the local host (127.0.0.1), that the server listens to."
+    )]
+    pub port: u16,
+
+    #[serde(default = "generate_token")]
+    #[reflect(
+        description = "A secret, that clients must send before any request. Copy it to the settings of \
+        the client (Blender add-on, for example). The server is not started if the token is empty. \
+        It is also used by runtime inspection."
+    )]
+    pub token: String,
+
+    #[serde(default)]
+    #[reflect(
+        description = "Starts runtime inspection server in the game, when it is launched from the editor. \
+        It allows to look at the scenes of the running game in Runtime Inspector window. The game must \
+        use the standard executor of the engine."
+    )]
+    pub runtime_inspection: bool,
+
+    #[serde(default = "default_runtime_inspection_port")]
+    #[reflect(
+        description = "A TCP port on the local host (127.0.0.1), that the runtime inspection server of the game listens to."
+    )]
+    pub runtime_inspection_port: u16,
+
+    #[serde(default)]
+    #[reflect(
+        description = "Allows Runtime Inspector to change properties of scene nodes in the running game. \
+        Otherwise the game could only be observed."
+    )]
+    pub allow_runtime_tweaks: bool,
+}
+
+fn default_runtime_inspection_port() -> u16 {
+    9528
+}
+
+impl Default for RemoteControlSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 9527,
+            token: generate_token(),
+            runtime_inspection: false,
+            runtime_inspection_port: default_runtime_inspection_port(),
+            allow_runtime_tweaks: false,
+        }
+    }
+}
@@ -1,4 +1,11 @@
-use crate::fyrox::core::reflect::prelude::*;
+use crate::{
+    fyrox::core::{
+        algebra::{UnitQuaternion, Vector3},
+        math::round_to_step,
+        reflect::prelude::*,
+    },
+    settings::move_mode::SnapSpace,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Reflect)]
@@ -7,6 +14,8 @@ pub struct RotateInteractionModeSettings {
     pub x_snap_step: f32,
     pub y_snap_step: f32,
     pub z_snap_step: f32,
+    #[serde(default)]
+    pub space: SnapSpace,
 }
 
 impl Default for RotateInteractionModeSettings {
@@ -16,6 +25,36 @@ impl Default for RotateInteractionModeSettings {
             x_snap_step: 2.5,
             y_snap_step: 2.5,
             z_snap_step: 2.5,
+            space: Default::default(),
+        }
+    }
+}
+
+impl RotateInteractionModeSettings {
+    fn snap_euler_angles(&self, rotation: UnitQuaternion<f32>) -> UnitQuaternion<f32> {
+        let (roll, pitch, yaw) = rotation.euler_angles();
+        let snapped = Vector3::new(
+            round_to_step(pitch, self.x_snap_step.to_radians()),
+            round_to_step(yaw, self.y_snap_step.to_radians()),
+            round_to_step(roll, self.z_snap_step.to_radians()),
+        );
+        UnitQuaternion::from_euler_angles(snapped.z, snapped.x, snapped.y)
+    }
+
+    /// Snaps a new rotation of an object, that was rotated from the `initial` rotation, taking the
+    /// snapping space into account.
+    pub fn try_snap_rotation(
+        &self,
+        initial: UnitQuaternion<f32>,
+        rotation: UnitQuaternion<f32>,
+    ) -> UnitQuaternion<f32> {
+        if !self.angle_snapping {
+            return rotation;
+        }
+
+        match self.space {
+            SnapSpace::World => self.snap_euler_angles(rotation),
+            SnapSpace::Local => initial * self.snap_euler_angles(initial.inverse() * rotation),
         }
     }
 }
@@ -0,0 +1,44 @@
+use crate::{
+    fyrox::core::{algebra::Vector3, math::round_to_step, reflect::prelude::*},
+    settings::move_mode::SnapSpace,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Reflect)]
+pub struct ScaleInteractionModeSettings {
+    pub scale_snapping: bool,
+    pub x_snap_step: f32,
+    pub y_snap_step: f32,
+    pub z_snap_step: f32,
+    pub space: SnapSpace,
+}
+
+impl Default for ScaleInteractionModeSettings {
+    fn default() -> Self {
+        Self {
+            scale_snapping: false,
+            x_snap_step: 0.1,
+            y_snap_step: 0.1,
+            z_snap_step: 0.1,
+            space: Default::default(),
+        }
+    }
+}
+
+impl ScaleInteractionModeSettings {
+    /// Snaps a new scale of an object, that was scaled from the `initial` scale, taking the snapping
+    /// space into account.
+    pub fn try_snap_scale(&self, initial: Vector3<f32>, scale: Vector3<f32>) -> Vector3<f32> {
+        if !self.scale_snapping {
+            return scale;
+        }
+
+        self.space.snap_vector(initial, scale, |v| {
+            Vector3::new(
+                round_to_step(v.x, self.x_snap_step),
+                round_to_step(v.y, self.y_snap_step),
+                round_to_step(v.z, self.z_snap_step),
+            )
+        })
+    }
+}
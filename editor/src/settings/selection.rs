@@ -1,5 +1,57 @@
-use crate::fyrox::core::reflect::prelude::*;
+use crate::fyrox::core::{reflect::prelude::*, uuid_provider};
 use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Shape of a region, that is used to select multiple objects in the scene viewer.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Debug,
+    Serialize,
+    Deserialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum SelectionShape {
+    /// A rectangle between the point where the mouse button was pressed and the current position of
+    /// the cursor.
+    #[default]
+    Box,
+    /// A free-form polygon that follows the cursor.
+    Lasso,
+}
+
+uuid_provider!(SelectionShape = "e5b8f1c3-0a7d-4d2e-9c46-8b3f1a5d7e92");
+
+/// Defines which objects inside a selection region are selected.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Debug,
+    Serialize,
+    Deserialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum SelectionOcclusion {
+    /// Every object inside a region is selected, even if it is hidden behind other objects.
+    #[default]
+    ThroughGeometry,
+    /// Only objects, that are not fully hidden behind other objects, are selected.
+    VisibleOnly,
+}
+
+uuid_provider!(SelectionOcclusion = "2d6a9e4b-7c13-4f58-a0b2-c5e8d1f37a64");
 
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect, Eq)]
 pub struct SelectionSettings {
@@ -8,6 +60,17 @@ pub struct SelectionSettings {
     // Hidden because there's a separate switch in world viewer for this.
     #[reflect(hidden)]
     pub track_selection: bool,
+
+    #[serde(default)]
+    pub shape: SelectionShape,
+
+    #[serde(default)]
+    pub occlusion: SelectionOcclusion,
+
+    // Edited in the world viewer.
+    #[serde(default)]
+    #[reflect(hidden)]
+    pub saved_filters: Vec<String>,
 }
 
 impl Default for SelectionSettings {
@@ -15,6 +78,9 @@ impl Default for SelectionSettings {
         Self {
             ignore_back_faces: false,
             track_selection: true,
+            shape: Default::default(),
+            occlusion: Default::default(),
+            saved_filters: Default::default(),
         }
     }
 }
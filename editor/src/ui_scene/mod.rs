@@ -551,6 +551,21 @@ impl SceneController for UiScene {
         }
     }
 
+    fn for_each_selected_entity(
+        &self,
+        selection: &Selection,
+        scenes: &SceneContainer,
+        callback: &mut dyn FnMut(&dyn Reflect),
+    ) {
+        if let Some(selection) = selection.as_ui() {
+            for node in selection.widgets.iter().filter_map(|h| self.ui.try_get(*h)) {
+                (callback)(node as &dyn Reflect);
+            }
+        } else {
+            self.first_selected_entity(selection, scenes, callback)
+        }
+    }
+
     fn on_property_changed(
         &mut self,
         args: &PropertyChanged,
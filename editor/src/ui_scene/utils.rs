@@ -91,6 +91,29 @@ impl<'a> WorldViewerDataProvider for UiSceneWorldViewerDataProvider<'a> {
         })
     }
 
+    fn type_name_of(&self, node: ErasedHandle) -> Option<Cow<'_, str>> {
+        self.ui
+            .try_get(node.into())
+            .map(|n| Cow::Borrowed(make_pretty_type_name(Reflect::type_name(n))))
+    }
+
+    fn tags_of(&self, _node: ErasedHandle) -> Vec<String> {
+        Default::default()
+    }
+
+    fn component_names_of(&self, node: ErasedHandle) -> Vec<String> {
+        self.ui
+            .try_get(node.into())
+            .map(|n| {
+                n.children()
+                    .iter()
+                    .filter_map(|child| self.ui.try_get(*child))
+                    .map(|child| make_pretty_type_name(Reflect::type_name(child)).to_owned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     fn is_valid_handle(&self, node: ErasedHandle) -> bool {
         self.ui.try_get(node.into()).is_some()
     }
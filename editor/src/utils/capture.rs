@@ -0,0 +1,709 @@
+//! Capture tool renders high-resolution screenshots and image sequences (turntables and flythroughs)
+//! of the current scene using the viewport camera. Frames are rendered on the main thread, while
+//! downsampling and PNG encoding is done on the task pool, so the editor stays responsive.
+
+use crate::fyrox::{
+    core::{
+        algebra::{UnitQuaternion, Vector2, Vector3},
+        log::Log,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid_provider,
+    },
+    engine::{Engine, GraphicsContext},
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::enumeration::EnumPropertyEditorDefinition, InspectorBuilder, InspectorContext,
+            InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    resource::texture::{TextureKind, TextureResource, TextureResourceExtension},
+    scene::Scene,
+};
+use crate::{
+    inspector::editors::make_property_editors_container, message::MessageSender,
+    overlay::OverlayRenderPass, scene::GameScene, MSG_SYNC_FLAG,
+};
+use image::{Rgba, RgbaImage};
+use std::{
+    cell::RefCell,
+    path::PathBuf,
+    rc::Rc,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    time::Instant,
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+#[derive(
+    Copy, Clone, Default, PartialEq, Eq, Debug, Reflect, AsRefStr, EnumString, VariantNames,
+)]
+pub enum CaptureMode {
+    /// A single frame.
+    #[default]
+    Screenshot,
+    /// A sequence of frames, where the camera orbits around its pivot.
+    Turntable,
+    /// A sequence of frames, that is recorded while the viewport camera is moved by the user.
+    Flythrough,
+}
+
+uuid_provider!(CaptureMode = "6c1e9b3a-5d7f-4a28-b4e0-2f8d1c9a7e63");
+
+impl CaptureMode {
+    fn name(self) -> &'static str {
+        match self {
+            CaptureMode::Screenshot => "screenshot",
+            CaptureMode::Turntable => "turntable",
+            CaptureMode::Flythrough => "flythrough",
+        }
+    }
+}
+
+#[derive(Reflect, Debug, Clone)]
+pub struct CaptureSettings {
+    #[reflect(description = "Defines what will be captured.")]
+    mode: CaptureMode,
+    #[reflect(
+        description = "Width of the resulting images in pixels.",
+        min_value = 1.0
+    )]
+    width: u32,
+    #[reflect(
+        description = "Height of the resulting images in pixels.",
+        min_value = 1.0
+    )]
+    height: u32,
+    #[reflect(
+        description = "Every frame is rendered at this many times larger resolution and then \
+    downsampled, which gives smooth edges. Large values may exceed the limits of the GPU.",
+        min_value = 1.0,
+        max_value = 4.0
+    )]
+    supersampling: u32,
+    #[reflect(
+        description = "Whether to draw editor objects (grid, gizmos, light and sound icons, debug \
+    geometry) or not."
+    )]
+    include_gizmos: bool,
+    #[reflect(description = "A folder, where captured images will be written to.")]
+    output_folder: PathBuf,
+    #[reflect(
+        description = "Amount of frames of a turntable sequence.",
+        min_value = 1.0
+    )]
+    turntable_frame_count: u32,
+    #[reflect(
+        description = "An angle (in degrees), that the camera travels around its pivot during a \
+    turntable sequence.",
+        min_value = -3600.0,
+        max_value = 3600.0
+    )]
+    turntable_angle: f32,
+    #[reflect(
+        description = "Amount of frames per second of a flythrough sequence.",
+        min_value = 1.0,
+        max_value = 120.0
+    )]
+    flythrough_frame_rate: f32,
+    #[reflect(
+        description = "Duration (in seconds) of a flythrough sequence. The recording could be \
+    stopped earlier.",
+        min_value = 0.0
+    )]
+    flythrough_duration: f32,
+}
+
+impl Default for CaptureSettings {
+    fn default() -> Self {
+        Self {
+            mode: CaptureMode::Screenshot,
+            width: 1920,
+            height: 1080,
+            supersampling: 2,
+            include_gizmos: false,
+            output_folder: "captures".into(),
+            turntable_frame_count: 120,
+            turntable_angle: 360.0,
+            flythrough_frame_rate: 30.0,
+            flythrough_duration: 10.0,
+        }
+    }
+}
+
+impl CaptureSettings {
+    fn frame_count(&self) -> u32 {
+        match self.mode {
+            CaptureMode::Screenshot => 1,
+            CaptureMode::Turntable => self.turntable_frame_count.max(1),
+            CaptureMode::Flythrough => {
+                ((self.flythrough_duration * self.flythrough_frame_rate).ceil() as u32).max(1)
+            }
+        }
+    }
+
+    fn render_size(&self) -> Vector2<u32> {
+        Vector2::new(self.width.max(1), self.height.max(1)) * self.supersampling.clamp(1, 4)
+    }
+}
+
+/// Returns the first path, produced by the given function, that does not exist yet.
+fn unique_path(make_path: impl Fn(usize) -> PathBuf) -> PathBuf {
+    (0..)
+        .map(make_path)
+        .find(|path| !path.exists())
+        .expect("there must be a free path")
+}
+
+/// Converts pixels of a rendered frame (RGBA8, bottom-to-top rows) to an image, that is `factor` times
+/// smaller, each pixel of the image is an average of a block of pixels of the frame.
+fn resolve_frame(pixels: &[u8], width: u32, height: u32, factor: u32) -> Option<RgbaImage> {
+    if factor == 0 || pixels.len() != (width * height * 4) as usize {
+        return None;
+    }
+
+    let (out_width, out_height) = (width / factor, height / factor);
+    let sample_count = factor * factor;
+    Some(RgbaImage::from_fn(out_width, out_height, |x, y| {
+        let mut sum = [0u32; 4];
+        for sy in 0..factor {
+            // Rows of the frame go from bottom to top.
+            let row = height - 1 - (y * factor + sy);
+            for sx in 0..factor {
+                let index = ((row * width + x * factor + sx) * 4) as usize;
+                for (channel, value) in sum.iter_mut().zip(&pixels[index..index + 4]) {
+                    *channel += *value as u32;
+                }
+            }
+        }
+        // Captured images are always opaque.
+        Rgba([
+            (sum[0] / sample_count) as u8,
+            (sum[1] / sample_count) as u8,
+            (sum[2] / sample_count) as u8,
+            255,
+        ])
+    }))
+}
+
+fn capture_scene_handle() -> Handle<Scene> {
+    // Asset previews use (u32::MAX, u32::MAX), so the capture uses a different handle to not
+    // interfere with them.
+    Handle::new(u32::MAX - 1, u32::MAX)
+}
+
+fn frame_size_of(render_target: Option<&TextureResource>) -> Option<Vector2<f32>> {
+    match render_target?.data_ref().kind() {
+        TextureKind::Rectangle { width, height } => Some(Vector2::new(width as f32, height as f32)),
+        _ => None,
+    }
+}
+
+/// Renders the scene of the given game scene from the viewport camera into an offscreen frame of
+/// the given size and returns its pixels. Every modified state of the scene is reverted after
+/// rendering.
+fn render_frame(
+    engine: &mut Engine,
+    game_scene: &GameScene,
+    size: Vector2<u32>,
+    include_gizmos: bool,
+    overlay_pass: Option<&Rc<RefCell<OverlayRenderPass>>>,
+) -> Option<Vec<u8>> {
+    let GraphicsContext::Initialized(ref mut graphics_context) = engine.graphics_context else {
+        Log::warn("Cannot capture a frame when the renderer is not initialized!");
+        return None;
+    };
+    let scene = &mut engine.scenes[game_scene.scene];
+
+    let prev_render_target = scene
+        .rendering_options
+        .render_target
+        .replace(TextureResource::new_render_target(size.x, size.y));
+    let prev_drawing_context =
+        (!include_gizmos).then(|| std::mem::take(&mut scene.drawing_context));
+    let prev_visibility = scene.graph[game_scene.editor_objects_root].visibility();
+    scene.graph[game_scene.editor_objects_root].set_visibility(include_gizmos && prev_visibility);
+    scene.graph.update_hierarchical_data();
+    if let Some(overlay_pass) = overlay_pass {
+        overlay_pass.borrow_mut().enabled = include_gizmos;
+    }
+
+    let camera = game_scene.camera_controller.camera;
+    scene.graph[camera]
+        .as_camera_mut()
+        .calculate_matrices(size.cast::<f32>());
+
+    let texture = graphics_context
+        .renderer
+        .render_scene(capture_scene_handle(), scene, 0.0)
+        .ok()
+        .and_then(|data| {
+            data.ldr_scene_framebuffer
+                .color_attachments()
+                .first()
+                .map(|a| a.texture.clone())
+        });
+    let pixels = texture.map(|texture| {
+        let pipeline_state = graphics_context.renderer.pipeline_state();
+        texture
+            .borrow_mut()
+            .bind_mut(pipeline_state, 0)
+            .read_pixels(pipeline_state)
+    });
+
+    // Revert everything back.
+    if let Some(overlay_pass) = overlay_pass {
+        overlay_pass.borrow_mut().enabled = true;
+    }
+    scene.graph[game_scene.editor_objects_root].set_visibility(prev_visibility);
+    scene.graph.update_hierarchical_data();
+    if let Some(drawing_context) = prev_drawing_context {
+        scene.drawing_context = drawing_context;
+    }
+    if let Some(frame_size) = frame_size_of(prev_render_target.as_ref()) {
+        scene.graph[camera]
+            .as_camera_mut()
+            .calculate_matrices(frame_size);
+    }
+    scene.rendering_options.render_target = prev_render_target;
+
+    pixels
+}
+
+struct CaptureSession {
+    settings: CaptureSettings,
+    directory: PathBuf,
+    frame_count: u32,
+    rendered: u32,
+    start_time: Instant,
+    start_yaw: Option<f32>,
+}
+
+impl CaptureSession {
+    fn frame_path(&self, frame: u32) -> PathBuf {
+        match self.settings.mode {
+            CaptureMode::Screenshot => unique_path(|i| {
+                self.directory
+                    .join(format!("{}_{:03}.png", self.settings.mode.name(), i))
+            }),
+            CaptureMode::Turntable | CaptureMode::Flythrough => {
+                self.directory.join(format!("frame_{:04}.png", frame))
+            }
+        }
+    }
+
+    fn is_frame_due(&self) -> bool {
+        match self.settings.mode {
+            CaptureMode::Screenshot | CaptureMode::Turntable => true,
+            CaptureMode::Flythrough => {
+                self.start_time.elapsed().as_secs_f32()
+                    >= self.rendered as f32 / self.settings.flythrough_frame_rate.max(1.0)
+            }
+        }
+    }
+}
+
+pub struct CaptureTool {
+    pub window: Handle<UiNode>,
+    settings: CaptureSettings,
+    inspector: Handle<UiNode>,
+    status: Handle<UiNode>,
+    start: Handle<UiNode>,
+    stop: Handle<UiNode>,
+    session: Option<CaptureSession>,
+    /// Amount of frames rendered by the last session and the folder, where they're written to.
+    last_capture: Option<(u32, PathBuf)>,
+    written: u32,
+    sender: Sender<Result<(), String>>,
+    receiver: Receiver<Result<(), String>>,
+}
+
+impl CaptureTool {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
+        let settings = CaptureSettings::default();
+        let container = make_property_editors_container(sender);
+        container.insert(EnumPropertyEditorDefinition::<CaptureMode>::new());
+        let container = Arc::new(container);
+
+        let inspector;
+        let status;
+        let start;
+        let stop;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_width(400.0)
+                .with_height(340.0)
+                .with_name("CaptureTool"),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Capture"))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        ScrollViewerBuilder::new(
+                            WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_content({
+                            inspector = InspectorBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_context(InspectorContext::from_object(
+                                &settings,
+                                ctx,
+                                container,
+                                None,
+                                MSG_SYNC_FLAG,
+                                0,
+                                true,
+                                Default::default(),
+                            ))
+                            .build(ctx);
+                            inspector
+                        })
+                        .build(ctx),
+                    )
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child({
+                                    status = TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Idle")
+                                    .build(ctx);
+                                    status
+                                })
+                                .with_child(
+                                    StackPanelBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(1)
+                                            .with_horizontal_alignment(HorizontalAlignment::Right)
+                                            .with_child({
+                                                start = ButtonBuilder::new(
+                                                    WidgetBuilder::new()
+                                                        .with_width(100.0)
+                                                        .with_margin(Thickness::uniform(1.0)),
+                                                )
+                                                .with_text("Capture")
+                                                .build(ctx);
+                                                start
+                                            })
+                                            .with_child({
+                                                stop = ButtonBuilder::new(
+                                                    WidgetBuilder::new()
+                                                        .with_enabled(false)
+                                                        .with_width(100.0)
+                                                        .with_margin(Thickness::uniform(1.0)),
+                                                )
+                                                .with_text("Stop")
+                                                .build(ctx);
+                                                stop
+                                            }),
+                                    )
+                                    .with_orientation(Orientation::Horizontal)
+                                    .build(ctx),
+                                ),
+                        )
+                        .add_row(Row::stretch())
+                        .add_column(Column::stretch())
+                        .add_column(Column::auto())
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::stretch())
+            .add_row(Row::strict(26.0))
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        let (result_sender, receiver) = mpsc::channel();
+
+        Self {
+            window,
+            settings,
+            inspector,
+            status,
+            start,
+            stop,
+            session: None,
+            last_capture: None,
+            written: 0,
+            sender: result_sender,
+            receiver,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+            true,
+        ));
+    }
+
+    /// Returns `true` if the tool is capturing frames, the editor must be updated every frame in
+    /// this case.
+    pub fn is_capturing(&self) -> bool {
+        self.session.is_some()
+    }
+
+    fn set_capturing(&self, capturing: bool, ui: &UserInterface) {
+        ui.send_message(WidgetMessage::enabled(
+            self.start,
+            MessageDirection::ToWidget,
+            !capturing,
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.stop,
+            MessageDirection::ToWidget,
+            capturing,
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.inspector,
+            MessageDirection::ToWidget,
+            !capturing,
+        ));
+    }
+
+    fn refresh_status(&self, ui: &UserInterface) {
+        let text = if let Some(session) = self.session.as_ref() {
+            format!(
+                "Rendered {} of {} frame(s), {} written",
+                session.rendered, session.frame_count, self.written
+            )
+        } else if let Some((rendered, directory)) = self.last_capture.as_ref() {
+            if self.written >= *rendered {
+                format!(
+                    "Done, {} frame(s) saved to {}",
+                    self.written,
+                    directory.display()
+                )
+            } else {
+                format!("Written {} of {} frame(s)", self.written, rendered)
+            }
+        } else {
+            "Idle".to_string()
+        };
+        ui.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            text,
+        ));
+    }
+
+    fn start_session(&mut self, ui: &UserInterface) {
+        let settings = self.settings.clone();
+        let directory = match settings.mode {
+            CaptureMode::Screenshot => settings.output_folder.clone(),
+            CaptureMode::Turntable | CaptureMode::Flythrough => unique_path(|i| {
+                settings
+                    .output_folder
+                    .join(format!("{}_{:03}", settings.mode.name(), i))
+            }),
+        };
+        if let Err(err) = std::fs::create_dir_all(&directory) {
+            Log::err(format!(
+                "Unable to create capture folder {}. Reason: {err}",
+                directory.display()
+            ));
+            return;
+        }
+
+        self.session = Some(CaptureSession {
+            frame_count: settings.frame_count(),
+            settings,
+            directory,
+            rendered: 0,
+            start_time: Instant::now(),
+            start_yaw: None,
+        });
+        self.written = 0;
+        self.set_capturing(true, ui);
+        self.refresh_status(ui);
+    }
+
+    fn stop_session(&mut self, engine: &mut Engine) {
+        let Some(session) = self.session.take() else {
+            return;
+        };
+
+        if let GraphicsContext::Initialized(ref mut graphics_context) = engine.graphics_context {
+            graphics_context
+                .renderer
+                .scene_data_map
+                .remove(&capture_scene_handle());
+        }
+
+        Log::info(format!(
+            "{} frame(s) of {} were rendered, they're written to {} in background.",
+            session.rendered,
+            session.settings.mode.name(),
+            session.directory.display()
+        ));
+        self.last_capture = Some((session.rendered, session.directory));
+
+        let ui = engine.user_interfaces.first();
+        self.set_capturing(false, ui);
+        self.refresh_status(ui);
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+        if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                PropertyAction::from_field_kind(&args.value).apply(
+                    &args.path(),
+                    &mut self.settings,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.start {
+                if self.session.is_none() {
+                    self.start_session(engine.user_interfaces.first());
+                }
+            } else if message.destination() == self.stop {
+                self.stop_session(engine);
+            }
+        } else if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                self.stop_session(engine);
+            }
+        }
+    }
+
+    /// Renders the next frame of the current capture session (if any). Must be called right after
+    /// the scene was rendered, when every camera except the viewport camera is disabled.
+    pub fn capture(
+        &mut self,
+        game_scene: &GameScene,
+        engine: &mut Engine,
+        overlay_pass: Option<&Rc<RefCell<OverlayRenderPass>>>,
+    ) {
+        let Some(session) = self.session.as_mut() else {
+            return;
+        };
+        if !session.is_frame_due() {
+            return;
+        }
+
+        let frame = session.rendered;
+        let pivot = game_scene.camera_controller.pivot;
+        let prev_rotation = if session.settings.mode == CaptureMode::Turntable {
+            let start_yaw = *session
+                .start_yaw
+                .get_or_insert(game_scene.camera_controller.yaw);
+            let yaw = start_yaw
+                + session.settings.turntable_angle.to_radians() * frame as f32
+                    / session.frame_count as f32;
+            let graph = &mut engine.scenes[game_scene.scene].graph;
+            let transform = graph[pivot].local_transform_mut();
+            let prev_rotation = **transform.rotation();
+            transform.set_rotation(UnitQuaternion::from_axis_angle(&Vector3::y_axis(), yaw));
+            Some(prev_rotation)
+        } else {
+            None
+        };
+
+        let size = session.settings.render_size();
+        let pixels = render_frame(
+            engine,
+            game_scene,
+            size,
+            session.settings.include_gizmos,
+            overlay_pass,
+        );
+
+        if let Some(prev_rotation) = prev_rotation {
+            let graph = &mut engine.scenes[game_scene.scene].graph;
+            graph[pivot]
+                .local_transform_mut()
+                .set_rotation(prev_rotation);
+            graph.update_hierarchical_data();
+        }
+
+        let Some(pixels) = pixels else {
+            Log::err("Unable to render a frame, the capture was stopped.");
+            self.stop_session(engine);
+            return;
+        };
+
+        let path = session.frame_path(frame);
+        let factor = session.settings.supersampling.clamp(1, 4);
+        let sender = self.sender.clone();
+        engine.resource_manager.task_pool().spawn_task(async move {
+            let result = resolve_frame(&pixels, size.x, size.y, factor)
+                .ok_or_else(|| "Frame has invalid size.".to_string())
+                .and_then(|image| image.save(&path).map_err(|err| err.to_string()));
+            Log::verify(sender.send(result));
+        });
+
+        session.rendered += 1;
+        if session.rendered >= session.frame_count {
+            self.stop_session(engine);
+        } else {
+            self.refresh_status(engine.user_interfaces.first());
+        }
+    }
+
+    /// Handles results of the background tasks, that write captured frames.
+    pub fn update(&mut self, ui: &UserInterface) {
+        let mut changed = false;
+        for result in self.receiver.try_iter() {
+            match result {
+                Ok(_) => self.written += 1,
+                Err(err) => {
+                    Log::err(format!("Unable to save a captured frame. Reason: {err}"));
+                }
+            }
+            changed = true;
+        }
+        if changed {
+            self.refresh_status(ui);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::resolve_frame;
+
+    #[test]
+    fn test_resolve_frame() {
+        // 2x2 frame, bottom row is black, top row is white.
+        let pixels = [
+            0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+        ];
+        let image = resolve_frame(&pixels, 2, 2, 1).unwrap();
+        assert_eq!(image.get_pixel(0, 0).0, [255, 255, 255, 255]);
+        assert_eq!(image.get_pixel(1, 1).0, [0, 0, 0, 255]);
+
+        let image = resolve_frame(&pixels, 2, 2, 2).unwrap();
+        assert_eq!(image.dimensions(), (1, 1));
+        assert_eq!(image.get_pixel(0, 0).0, [127, 127, 127, 255]);
+
+        assert!(resolve_frame(&pixels[..4], 2, 2, 1).is_none());
+    }
+}
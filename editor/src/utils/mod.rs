@@ -11,9 +11,13 @@ use crate::fyrox::{
 };
 use std::{fs::File, io::Read, path::Path};
 
+pub mod capture;
 pub mod doc;
 pub mod path_fixer;
 pub mod ragdoll;
+pub mod rename;
+pub mod skybox;
+pub mod vertex_animation;
 
 /// True if `a` and `b` have the same length, and every element of `a` is equal to some element of `b`
 /// and every element of `b` is equal to some element of `a`.
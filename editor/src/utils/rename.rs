@@ -0,0 +1,514 @@
+//! Batch rename dialog allows to rename multiple scene nodes or assets at once using a rename
+//! pattern, see [`RenamePattern`] for more info.
+
+use crate::fyrox::{
+    asset::manager::ResourceManager,
+    core::{
+        futures::executor::block_on, log::Log, make_relative_path, pool::Handle,
+        reflect::prelude::*, type_traits::prelude::*,
+    },
+    graph::BaseSceneGraph,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::enumeration::EnumPropertyEditorDefinition, InspectorBuilder, InspectorContext,
+            InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+    },
+    scene::{graph::Graph, node::Node},
+};
+use crate::{
+    asset::can_fix_references,
+    command::{Command, CommandGroup},
+    inspector::editors::make_property_editors_container,
+    message::MessageSender,
+    scene::commands::graph::SetNodeNameCommand,
+    Message, MSG_SYNC_FLAG,
+};
+use std::{path::PathBuf, sync::Arc};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Case transformation, that is applied to new names.
+#[derive(
+    Reflect,
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "b1f8f3a2-6d4e-4b7a-9c1e-2f5d8a0c7e34")]
+pub enum NameCase {
+    /// Keeps the case as is.
+    #[default]
+    Keep,
+    /// `enemy_01`
+    Lower,
+    /// `ENEMY_01`
+    Upper,
+    /// `Enemy_01`, every word starts with a capital letter.
+    Title,
+}
+
+impl NameCase {
+    fn apply(self, name: &str) -> String {
+        match self {
+            NameCase::Keep => name.to_string(),
+            NameCase::Lower => name.to_lowercase(),
+            NameCase::Upper => name.to_uppercase(),
+            NameCase::Title => {
+                let mut result = String::with_capacity(name.len());
+                let mut word_start = true;
+                for c in name.chars() {
+                    if c.is_alphanumeric() {
+                        if word_start {
+                            result.extend(c.to_uppercase());
+                        } else {
+                            result.extend(c.to_lowercase());
+                        }
+                        word_start = false;
+                    } else {
+                        result.push(c);
+                        word_start = true;
+                    }
+                }
+                result
+            }
+        }
+    }
+}
+
+/// A set of rules to produce a new name from an old one. The rules are applied in the following
+/// order: name template, find/replace, prefix/suffix, case transform, numbering. Numbering tokens
+/// could be used in every text field: `{}` is replaced with the number of an item, while `{3}` or
+/// `{03}` are replaced with the number padded with zeros to three digits. For example, `Enemy_{03}`
+/// produces `Enemy_001`, `Enemy_002`, etc.
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub struct RenamePattern {
+    #[reflect(
+        description = "A new name for every item. Leave it empty to keep the original names. \
+    Use `{}` to insert the number of an item or `{03}` to pad the number with zeros to three digits."
+    )]
+    pub name: String,
+    #[reflect(description = "A text to search for in the names. Case-sensitive.")]
+    pub find: String,
+    #[reflect(description = "A text, that replaces every occurrence of the searched text.")]
+    pub replace: String,
+    #[reflect(description = "A text, that will be added at the beginning of the names.")]
+    pub prefix: String,
+    #[reflect(description = "A text, that will be added at the end of the names.")]
+    pub suffix: String,
+    #[reflect(description = "Case transformation of the names.")]
+    pub case: NameCase,
+    #[reflect(description = "A number of the first item.")]
+    pub start: u32,
+    #[reflect(description = "Increment of the number between adjacent items.")]
+    pub step: u32,
+}
+
+impl Default for RenamePattern {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            find: Default::default(),
+            replace: Default::default(),
+            prefix: Default::default(),
+            suffix: Default::default(),
+            case: Default::default(),
+            start: 1,
+            step: 1,
+        }
+    }
+}
+
+/// Replaces every numbering token (`{}`, `{3}`, `{03}`) in the given string with the given number.
+/// Anything else in curly braces is left as is.
+fn expand_numbering(text: &str, number: u32) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(open) = rest.find('{') {
+        result.push_str(&rest[..open]);
+        let after_open = &rest[open + 1..];
+        match after_open.find('}') {
+            Some(close) if after_open[..close].chars().all(|c| c.is_ascii_digit()) => {
+                let width = after_open[..close].parse::<usize>().unwrap_or_default();
+                result.push_str(&format!("{number:0width$}"));
+                rest = &after_open[close + 1..];
+            }
+            _ => {
+                result.push('{');
+                rest = after_open;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+impl RenamePattern {
+    /// Produces a new name for an item with the given original name and the given index in the list
+    /// of renamed items.
+    pub fn apply(&self, name: &str, index: usize) -> String {
+        let mut new_name = if self.name.is_empty() {
+            name.to_string()
+        } else {
+            self.name.clone()
+        };
+        if !self.find.is_empty() {
+            new_name = new_name.replace(&self.find, &self.replace);
+        }
+        new_name = format!("{}{}{}", self.prefix, new_name, self.suffix);
+        new_name = self.case.apply(&new_name);
+        let number = self
+            .start
+            .saturating_add(self.step.saturating_mul(index as u32));
+        expand_numbering(&new_name, number)
+    }
+}
+
+/// A set of objects, that should be renamed.
+#[derive(Debug, Clone)]
+pub enum BatchRenameTarget {
+    /// Nodes selected in the current scene.
+    SelectedNodes,
+    /// A list of asset files. Only the file names (without extensions) are renamed.
+    Assets(Vec<PathBuf>),
+}
+
+#[derive(Debug, Clone)]
+enum RenameItem {
+    Node(Handle<Node>),
+    Asset(PathBuf),
+}
+
+/// A window, that renames multiple scene nodes or assets at once. Renaming of scene nodes is done
+/// in a single command, so it could be undone at once. Renaming of assets moves the files and fixes
+/// references to them in other assets, this operation cannot be undone.
+pub struct BatchRenameDialog {
+    pub window: Handle<UiNode>,
+    pattern: RenamePattern,
+    inspector: Handle<UiNode>,
+    preview: Handle<UiNode>,
+    rename: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    items: Vec<(RenameItem, String)>,
+}
+
+impl BatchRenameDialog {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
+        let pattern = RenamePattern::default();
+        let container = make_property_editors_container(sender);
+        container.insert(EnumPropertyEditorDefinition::<NameCase>::new());
+        let container = Arc::new(container);
+
+        let inspector;
+        let preview;
+        let rename;
+        let cancel;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_width(400.0)
+                .with_height(450.0)
+                .with_name("BatchRenameDialog"),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Batch Rename"))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child({
+                        inspector = InspectorBuilder::new(
+                            WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_context(InspectorContext::from_object(
+                            &pattern,
+                            ctx,
+                            container,
+                            None,
+                            MSG_SYNC_FLAG,
+                            0,
+                            true,
+                            Default::default(),
+                        ))
+                        .build(ctx);
+                        inspector
+                    })
+                    .with_child(
+                        ScrollViewerBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_content({
+                            preview = TextBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_wrap(WrapMode::Word)
+                            .build(ctx);
+                            preview
+                        })
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .on_row(2)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child({
+                                    rename = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Rename")
+                                    .build(ctx);
+                                    rename
+                                })
+                                .with_child({
+                                    cancel = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Cancel")
+                                    .build(ctx);
+                                    cancel
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::auto())
+            .add_row(Row::stretch())
+            .add_row(Row::strict(24.0))
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            pattern,
+            inspector,
+            preview,
+            rename,
+            cancel,
+            items: Default::default(),
+        }
+    }
+
+    /// Opens the dialog for the given scene nodes.
+    pub fn open_for_nodes(&mut self, nodes: &[Handle<Node>], graph: &Graph, ui: &UserInterface) {
+        self.items = nodes
+            .iter()
+            .filter_map(|handle| {
+                graph
+                    .try_get(*handle)
+                    .map(|node| (RenameItem::Node(*handle), node.name_owned()))
+            })
+            .collect();
+        self.open(ui);
+    }
+
+    /// Opens the dialog for the given asset files.
+    pub fn open_for_assets(&mut self, paths: &[PathBuf], ui: &UserInterface) {
+        self.items = paths
+            .iter()
+            .filter_map(|path| {
+                path.file_stem().map(|stem| {
+                    (
+                        RenameItem::Asset(path.clone()),
+                        stem.to_string_lossy().to_string(),
+                    )
+                })
+            })
+            .collect();
+        self.open(ui);
+    }
+
+    fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+            true,
+        ));
+        self.sync_preview(ui);
+    }
+
+    fn new_names(&self) -> impl Iterator<Item = (&RenameItem, &String, String)> + '_ {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(index, (item, name))| (item, name, self.pattern.apply(name, index)))
+    }
+
+    fn sync_preview(&self, ui: &UserInterface) {
+        let mut text = String::new();
+        for (_, old_name, new_name) in self.new_names() {
+            text += &format!("{old_name} -> {new_name}\n");
+        }
+        ui.send_message(TextMessage::text(
+            self.preview,
+            MessageDirection::ToWidget,
+            text,
+        ));
+    }
+
+    fn rename_nodes(&self, sender: &MessageSender) {
+        let commands = self
+            .new_names()
+            .filter_map(|(item, old_name, new_name)| {
+                let RenameItem::Node(handle) = *item else {
+                    return None;
+                };
+                if *old_name == new_name {
+                    return None;
+                }
+                Some(Command::new(SetNodeNameCommand::new(handle, new_name)))
+            })
+            .collect::<Vec<_>>();
+        if !commands.is_empty() {
+            sender.do_command(CommandGroup::from(commands).with_custom_name("Batch Rename"));
+        }
+    }
+
+    fn rename_assets(&self, resource_manager: &ResourceManager, sender: &MessageSender) {
+        let mut last_path = None;
+        for (item, old_name, new_name) in self.new_names() {
+            let RenameItem::Asset(path) = item else {
+                continue;
+            };
+            if *old_name == new_name {
+                continue;
+            }
+            let mut new_path = path.with_file_name(new_name);
+            if let Some(extension) = path.extension() {
+                new_path.set_extension(extension);
+            }
+            if new_path.exists() {
+                Log::err(format!(
+                    "Unable to rename {} to {}, because the file already exists.",
+                    path.display(),
+                    new_path.display()
+                ));
+                continue;
+            }
+            // Resources are moved using the resource manager, so the references to them in other
+            // resources are fixed as well.
+            let relative_path = make_relative_path(path).ok();
+            let resource = relative_path
+                .as_ref()
+                .and_then(|path| block_on(resource_manager.request_untyped(path)).ok());
+            match (resource, relative_path, new_path.file_name()) {
+                (Some(resource), Some(relative_path), Some(file_name)) => {
+                    Log::verify(block_on(resource_manager.move_resource(
+                        resource,
+                        relative_path.with_file_name(file_name),
+                        "./",
+                        can_fix_references,
+                    )));
+                }
+                _ => Log::verify(std::fs::rename(path, &new_path)),
+            }
+            last_path = Some(new_path);
+        }
+        if let Some(last_path) = last_path {
+            sender.send(Message::ShowInAssetBrowser(last_path));
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        resource_manager: &ResourceManager,
+        sender: &MessageSender,
+    ) {
+        if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                PropertyAction::from_field_kind(&args.value).apply(
+                    &args.path(),
+                    &mut self.pattern,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+                self.sync_preview(ui);
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.rename {
+                self.rename_nodes(sender);
+                self.rename_assets(resource_manager, sender);
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.cancel {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        } else if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                self.items.clear();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::utils::rename::{expand_numbering, NameCase, RenamePattern};
+
+    #[test]
+    fn test_numbering() {
+        assert_eq!(expand_numbering("Enemy_{03}", 7), "Enemy_007");
+        assert_eq!(expand_numbering("Enemy_{}", 12), "Enemy_12");
+        assert_eq!(expand_numbering("{2}-{x}", 5), "05-{x}");
+        assert_eq!(expand_numbering("{", 5), "{");
+    }
+
+    #[test]
+    fn test_rename_pattern() {
+        let pattern = RenamePattern {
+            name: "Enemy_{03}".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(pattern.apply("Cube", 0), "Enemy_001");
+        assert_eq!(pattern.apply("Sphere", 1), "Enemy_002");
+
+        let pattern = RenamePattern {
+            find: "Old".to_string(),
+            replace: "New".to_string(),
+            prefix: "sm_".to_string(),
+            suffix: "_{2}".to_string(),
+            case: NameCase::Upper,
+            start: 0,
+            step: 10,
+            ..Default::default()
+        };
+        assert_eq!(pattern.apply("OldTree", 2), "SM_NEWTREE_20");
+
+        assert_eq!(NameCase::Title.apply("big red_barrel"), "Big Red_Barrel");
+    }
+}
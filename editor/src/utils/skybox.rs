@@ -0,0 +1,334 @@
+use crate::fyrox::graph::{BaseSceneGraph, SceneGraph};
+use crate::fyrox::{
+    core::{futures::executor::block_on, log::Log, pool::Handle, reflect::prelude::*},
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        utils::make_simple_tooltip,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+    },
+    resource::texture::TextureResource,
+    scene::{
+        camera::{Camera, SkyBox},
+        graph::Graph,
+        node::Node,
+    },
+};
+use crate::{
+    command::{Command, SetPropertyCommand},
+    inspector::editors::make_property_editors_container,
+    message::MessageSender,
+    scene::{commands::GameSceneContext, Selection},
+    Message, MSG_SYNC_FLAG,
+};
+use std::sync::Arc;
+
+#[derive(Reflect, Debug)]
+pub struct SkyboxSettings {
+    #[reflect(description = "A handle of a camera, which skybox will be edited.")]
+    camera: Handle<Node>,
+    #[reflect(
+        description = "An equirectangular panorama (usually a high dynamic range image in .hdr or \
+    .exr format), that will be converted to a skybox. Irradiance and prefiltered specular maps will \
+    be generated from it as well to light the scene."
+    )]
+    hdri: Option<TextureResource>,
+    #[reflect(
+        description = "Brightness multiplier of the skybox and the environment lighting.",
+        min_value = 0.0,
+        max_value = 16.0,
+        step = 0.05
+    )]
+    exposure: f32,
+    #[reflect(
+        description = "Rotation of the skybox around vertical axis (in degrees).",
+        min_value = -180.0,
+        max_value = 180.0
+    )]
+    rotation: f32,
+}
+
+impl Default for SkyboxSettings {
+    fn default() -> Self {
+        Self {
+            camera: Default::default(),
+            hdri: None,
+            exposure: 1.0,
+            rotation: 0.0,
+        }
+    }
+}
+
+impl SkyboxSettings {
+    fn skybox<'a>(&self, graph: &'a Graph) -> Option<&'a SkyBox> {
+        graph
+            .try_get_of_type::<Camera>(self.camera)
+            .and_then(|camera| camera.skybox_ref())
+    }
+
+    fn fetch(&mut self, graph: &Graph) {
+        if let Some(skybox) = self.skybox(graph) {
+            self.hdri = skybox.hdri();
+            self.exposure = skybox.exposure();
+            self.rotation = skybox.rotation().to_degrees();
+        }
+    }
+
+    fn apply(&self, skybox: &mut SkyBox) {
+        skybox.set_exposure(self.exposure);
+        skybox.set_rotation(self.rotation.to_radians());
+    }
+
+    fn set_skybox(&self, skybox: SkyBox, sender: &MessageSender) {
+        let camera = self.camera;
+        sender.send(Message::DoCommand(Command::new(SetPropertyCommand::new(
+            "sky_box".into(),
+            Box::new(Some(skybox)) as Box<dyn Reflect>,
+            move |ctx| {
+                ctx.get_mut::<GameSceneContext>()
+                    .scene
+                    .graph
+                    .node_mut(camera)
+            },
+        ))));
+    }
+
+    fn import(&self, graph: &Graph, sender: &MessageSender) {
+        let Some(hdri) = self.hdri.clone() else {
+            Log::warn("Select a panorama to import.");
+            return;
+        };
+
+        if graph.try_get_of_type::<Camera>(self.camera).is_none() {
+            Log::warn("Select a camera, which skybox will be replaced by the panorama.");
+            return;
+        }
+
+        if let Err(err) = block_on(hdri.clone()) {
+            Log::err(format!("Unable to load the panorama. Reason: {err:?}"));
+            return;
+        }
+
+        match SkyBox::from_hdri(hdri) {
+            Ok(mut skybox) => {
+                self.apply(&mut skybox);
+                self.set_skybox(skybox, sender);
+            }
+            Err(err) => {
+                Log::err(format!("Unable to create a skybox. Reason: {err:?}"));
+            }
+        }
+    }
+}
+
+/// A window, that allows you to import panoramas as skyboxes and to adjust exposure and rotation of
+/// the skybox of a camera. Exposure and rotation changes are applied immediately.
+pub struct SkyboxEditor {
+    pub window: Handle<UiNode>,
+    pub settings: SkyboxSettings,
+    inspector: Handle<UiNode>,
+    import: Handle<UiNode>,
+    close: Handle<UiNode>,
+    use_selection: Handle<UiNode>,
+}
+
+impl SkyboxEditor {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
+        let settings = SkyboxSettings::default();
+        let container = Arc::new(make_property_editors_container(sender));
+
+        let inspector;
+        let import;
+        let close;
+        let use_selection;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_width(400.0)
+                .with_height(200.0)
+                .with_name("SkyboxEditor"),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Skybox Editor"))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        ScrollViewerBuilder::new(
+                            WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_content({
+                            inspector = InspectorBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_context(InspectorContext::from_object(
+                                &settings,
+                                ctx,
+                                container,
+                                None,
+                                MSG_SYNC_FLAG,
+                                0,
+                                true,
+                                Default::default(),
+                            ))
+                            .build(ctx);
+                            inspector
+                        })
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .on_row(1)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child({
+                                    use_selection = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Uses selected camera and fetches the settings \
+                                                of its skybox.",
+                                            )),
+                                    )
+                                    .with_text("Use Selection")
+                                    .build(ctx);
+                                    use_selection
+                                })
+                                .with_child({
+                                    import = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Replaces the skybox of the camera with the \
+                                                panorama. It may take some time to generate \
+                                                lighting maps.",
+                                            )),
+                                    )
+                                    .with_text("Import")
+                                    .build(ctx);
+                                    import
+                                })
+                                .with_child({
+                                    close = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Close")
+                                    .build(ctx);
+                                    close
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::stretch())
+            .add_row(Row::strict(24.0))
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            settings,
+            inspector,
+            import,
+            close,
+            use_selection,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+            true,
+        ));
+    }
+
+    fn sync_to_model(&self, ui: &mut UserInterface) {
+        let ctx = ui
+            .node(self.inspector)
+            .cast::<fyrox::gui::inspector::Inspector>()
+            .unwrap()
+            .context()
+            .clone();
+
+        if let Err(sync_errors) = ctx.sync(&self.settings, ui, 0, true, Default::default()) {
+            for error in sync_errors {
+                Log::err(format!("Failed to sync property. Reason: {:?}", error))
+            }
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        graph: &Graph,
+        editor_selection: &Selection,
+        sender: &MessageSender,
+    ) {
+        if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                PropertyAction::from_field_kind(&args.value).apply(
+                    &args.path(),
+                    &mut self.settings,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+
+                match args.name.as_str() {
+                    "camera" => {
+                        self.settings.fetch(graph);
+                        self.sync_to_model(ui);
+                    }
+                    "exposure" | "rotation" => {
+                        if let Some(mut skybox) = self.settings.skybox(graph).cloned() {
+                            self.settings.apply(&mut skybox);
+                            self.settings.set_skybox(skybox, sender);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.import {
+                self.settings.import(graph, sender);
+            } else if message.destination() == self.close {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.use_selection {
+                if let Some(camera) = editor_selection.as_graph().and_then(|selection| {
+                    selection
+                        .nodes()
+                        .iter()
+                        .find(|handle| graph.try_get_of_type::<Camera>(**handle).is_some())
+                        .cloned()
+                }) {
+                    self.settings.camera = camera;
+                    self.settings.fetch(graph);
+                    self.sync_to_model(ui);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use crate::fyrox::graph::{BaseSceneGraph, SceneGraph};
+use crate::fyrox::{
+    asset::{manager::ResourceManager, ResourceData},
+    core::{log::Log, pool::Handle, reflect::prelude::*},
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        utils::make_simple_tooltip,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+    },
+    scene::{
+        animation::AnimationPlayer,
+        base::BaseBuilder,
+        crowd::{
+            vat::{VertexAnimation, VertexAnimationBakeError},
+            CrowdBuilder, CrowdInstance,
+        },
+        graph::Graph,
+        mesh::Mesh,
+        node::Node,
+    },
+};
+use crate::{
+    inspector::editors::make_property_editors_container,
+    message::MessageSender,
+    scene::{commands::graph::AddNodeCommand, Selection},
+    MSG_SYNC_FLAG,
+};
+use std::{error::Error, path::PathBuf, sync::Arc};
+
+#[derive(Reflect, Debug)]
+pub struct VertexAnimationBakeSettings {
+    #[reflect(description = "A handle of a mesh, which surface will be baked.")]
+    mesh: Handle<Node>,
+    #[reflect(description = "Index of the surface of the mesh, that will be baked.")]
+    surface_index: usize,
+    #[reflect(
+        description = "A handle of an animation player, that contains the animations of the mesh. \
+    Every animation of the player becomes a separate clip of the vertex animation."
+    )]
+    animation_player: Handle<Node>,
+    #[reflect(
+        description = "Amount of frames per second, that will be baked. Higher values give smoother \
+    animations, but take more memory.",
+        min_value = 1.0,
+        max_value = 120.0
+    )]
+    frame_rate: f32,
+    #[reflect(description = "A path to the resulting vertex animation file (*.vat).")]
+    path: PathBuf,
+    #[reflect(
+        description = "A flag, that defines whether to create a crowd node, that uses the baked \
+    vertex animation, or not."
+    )]
+    create_crowd: bool,
+}
+
+impl Default for VertexAnimationBakeSettings {
+    fn default() -> Self {
+        Self {
+            mesh: Default::default(),
+            surface_index: 0,
+            animation_player: Default::default(),
+            frame_rate: 30.0,
+            path: "vertex_animation.vat".into(),
+            create_crowd: true,
+        }
+    }
+}
+
+impl VertexAnimationBakeSettings {
+    fn bake(&self, graph: &mut Graph) -> Result<VertexAnimation, VertexAnimationBakeError> {
+        let animations = graph
+            .try_get_of_type::<AnimationPlayer>(self.animation_player)
+            .map(|player| player.animations().iter().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        VertexAnimation::bake(
+            graph,
+            self.mesh,
+            self.surface_index,
+            &animations.iter().collect::<Vec<_>>(),
+            self.frame_rate,
+        )
+    }
+
+    fn bake_and_save(
+        &self,
+        graph: &mut Graph,
+        resource_manager: &ResourceManager,
+        sender: &MessageSender,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut vertex_animation = self.bake(graph)?;
+        vertex_animation.save(&self.path)?;
+
+        Log::info(format!(
+            "Vertex animation with {} clip(s) was successfully baked to {}",
+            vertex_animation.clips().len(),
+            self.path.display()
+        ));
+
+        if self.create_crowd {
+            let crowd = CrowdBuilder::new(BaseBuilder::new().with_name("Crowd"))
+                .with_animation(resource_manager.request::<VertexAnimation>(&self.path))
+                .with_instances(vec![CrowdInstance::default()])
+                .build_node();
+
+            sender.do_command(AddNodeCommand::new(crowd, Handle::NONE, true));
+        }
+
+        Ok(())
+    }
+}
+
+pub struct VertexAnimationBaker {
+    pub window: Handle<UiNode>,
+    pub settings: VertexAnimationBakeSettings,
+    inspector: Handle<UiNode>,
+    bake: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    use_selection: Handle<UiNode>,
+}
+
+impl VertexAnimationBaker {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
+        let settings = VertexAnimationBakeSettings::default();
+        let container = Arc::new(make_property_editors_container(sender));
+
+        let inspector;
+        let bake;
+        let cancel;
+        let use_selection;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_width(400.0)
+                .with_height(260.0)
+                .with_name("VertexAnimationBaker"),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Vertex Animation Baker"))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        ScrollViewerBuilder::new(
+                            WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_content({
+                            inspector = InspectorBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_context(InspectorContext::from_object(
+                                &settings,
+                                ctx,
+                                container,
+                                None,
+                                MSG_SYNC_FLAG,
+                                0,
+                                true,
+                                Default::default(),
+                            ))
+                            .build(ctx);
+                            inspector
+                        })
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .on_row(1)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child({
+                                    use_selection = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Uses selected mesh and the first animation \
+                                                player of the scene.",
+                                            )),
+                                    )
+                                    .with_text("Use Selection")
+                                    .build(ctx);
+                                    use_selection
+                                })
+                                .with_child({
+                                    bake = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Bake")
+                                    .build(ctx);
+                                    bake
+                                })
+                                .with_child({
+                                    cancel = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Cancel")
+                                    .build(ctx);
+                                    cancel
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::stretch())
+            .add_row(Row::strict(24.0))
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            settings,
+            inspector,
+            bake,
+            cancel,
+            use_selection,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+            true,
+        ));
+    }
+
+    fn sync_to_model(&self, ui: &mut UserInterface) {
+        let ctx = ui
+            .node(self.inspector)
+            .cast::<fyrox::gui::inspector::Inspector>()
+            .unwrap()
+            .context()
+            .clone();
+
+        if let Err(sync_errors) = ctx.sync(&self.settings, ui, 0, true, Default::default()) {
+            for error in sync_errors {
+                Log::err(format!("Failed to sync property. Reason: {:?}", error))
+            }
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        graph: &mut Graph,
+        editor_selection: &Selection,
+        resource_manager: &ResourceManager,
+        sender: &MessageSender,
+    ) {
+        if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                PropertyAction::from_field_kind(&args.value).apply(
+                    &args.path(),
+                    &mut self.settings,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.bake {
+                match self.settings.bake_and_save(graph, resource_manager, sender) {
+                    Ok(()) => {
+                        ui.send_message(WindowMessage::close(
+                            self.window,
+                            MessageDirection::ToWidget,
+                        ));
+                    }
+                    Err(err) => {
+                        Log::err(format!("Unable to bake vertex animation. Reason: {err}"));
+                    }
+                }
+            } else if message.destination() == self.cancel {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.use_selection {
+                if let Some(mesh) = editor_selection.as_graph().and_then(|selection| {
+                    selection
+                        .nodes()
+                        .iter()
+                        .find(|handle| graph.try_get_of_type::<Mesh>(**handle).is_some())
+                        .cloned()
+                }) {
+                    self.settings.mesh = mesh;
+                }
+
+                self.settings.animation_player = graph
+                    .find(graph.get_root(), &mut |n| {
+                        n.query_component_ref::<AnimationPlayer>().is_some()
+                    })
+                    .map(|(h, _)| h)
+                    .unwrap_or_default();
+
+                self.sync_to_model(ui);
+            }
+        }
+    }
+}
@@ -0,0 +1,622 @@
+//! Scene validation: a set of checks, that look for common problems in a scene (missing resources,
+//! broken transforms, misconfigured lights, etc.) and a panel, that shows found issues and allows to
+//! select problematic nodes and to fix some of the issues automatically.
+
+use crate::{
+    command::{Command, CommandGroup, SetPropertyCommand},
+    fyrox::{
+        asset::{self, state::ResourceState, untyped::ResourceKind},
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            color::Color,
+            pool::Handle,
+            scope_profile,
+        },
+        fxhash::FxHashSet,
+        graph::{BaseSceneGraph, SceneGraph},
+        gui::{
+            border::BorderBuilder,
+            brush::Brush,
+            button::{ButtonBuilder, ButtonMessage},
+            formatted_text::WrapMode,
+            grid::{Column, GridBuilder, Row},
+            list_view::{ListViewBuilder, ListViewMessage},
+            message::{MessageDirection, UiMessage},
+            scroll_viewer::ScrollViewerBuilder,
+            stack_panel::StackPanelBuilder,
+            text::{TextBuilder, TextMessage},
+            widget::{WidgetBuilder, WidgetMessage},
+            window::{WindowBuilder, WindowTitle},
+            BuildContext, Orientation, Thickness, UiNode, VerticalAlignment,
+        },
+        scene::{
+            graph::Graph,
+            light::{area::AreaLight, point::PointLight, spot::SpotLight},
+            mesh::{buffer::VertexAttributeUsage, Mesh},
+            node::Node,
+            tilemap::TileMap,
+            transform::Transform,
+        },
+    },
+    message::MessageSender,
+    plugins::tilemap::commands::SetTileMapTilesCommand,
+    scene::{
+        commands::{graph::SetNodeTransformCommand, ChangeSelectionCommand, GameSceneContext},
+        GameScene, Selection,
+    },
+    world::graph::selection::GraphSelection,
+    Engine, Message,
+};
+use std::{
+    fmt::{Display, Formatter},
+    path::PathBuf,
+};
+
+/// Radius (or distance for spot lights), that is used to fix lights with zero radius.
+pub const DEFAULT_LIGHT_RADIUS: f32 = 10.0;
+
+/// Kind of a problem found by the validator.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SceneIssueKind {
+    /// A node uses an external resource, that failed to load.
+    MissingResource(PathBuf),
+    /// Local transform of a node contains NaN or infinite values.
+    InvalidTransform,
+    /// A point light with zero radius or a spot light with zero distance.
+    ZeroLightRadius,
+    /// A mesh has a light map, but some of its surfaces do not have second texture coordinates.
+    MissingSecondTexCoords,
+    /// A tile map has tiles, that refer to tile definitions that do not exist in its tile set.
+    InvalidTiles {
+        /// Amount of invalid tiles.
+        count: usize,
+    },
+}
+
+impl Display for SceneIssueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingResource(path) => {
+                write!(f, "Resource {} is missing or failed to load.", path.display())
+            }
+            Self::InvalidTransform => {
+                write!(f, "Local transform contains NaN or infinite values.")
+            }
+            Self::ZeroLightRadius => write!(f, "Light has zero radius and does not light anything."),
+            Self::MissingSecondTexCoords => write!(
+                f,
+                "Mesh is lightmapped, but has no second texture coordinates. Regenerate the light map."
+            ),
+            Self::InvalidTiles { count } => write!(
+                f,
+                "Tile map has {count} tile(s) referencing deleted tile definitions."
+            ),
+        }
+    }
+}
+
+/// A problem of a particular node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SceneIssue {
+    pub node: Handle<Node>,
+    pub kind: SceneIssueKind,
+}
+
+impl SceneIssue {
+    /// Returns `true` if the issue could be fixed automatically.
+    pub fn can_be_fixed(&self) -> bool {
+        matches!(
+            self.kind,
+            SceneIssueKind::InvalidTransform
+                | SceneIssueKind::ZeroLightRadius
+                | SceneIssueKind::InvalidTiles { .. }
+        )
+    }
+
+    /// Creates a command, that fixes the issue. Returns `None` if the issue cannot be fixed
+    /// automatically.
+    pub fn make_fix_command(&self, graph: &Graph) -> Option<Command> {
+        let node_ref = graph.try_get(self.node)?;
+        let node = self.node;
+        match self.kind {
+            SceneIssueKind::InvalidTransform => {
+                let old_transform = node_ref.local_transform().clone();
+                let new_transform = sanitize_transform(&old_transform);
+                Some(Command::new(SetNodeTransformCommand::new(
+                    node,
+                    old_transform,
+                    new_transform,
+                )))
+            }
+            SceneIssueKind::ZeroLightRadius => {
+                let path = if node_ref.cast::<SpotLight>().is_some() {
+                    "distance"
+                } else {
+                    "radius"
+                };
+                Some(Command::new(SetPropertyCommand::new(
+                    path.into(),
+                    Box::new(DEFAULT_LIGHT_RADIUS),
+                    move |ctx| ctx.get_mut::<GameSceneContext>().scene.graph.node_mut(node),
+                )))
+            }
+            SceneIssueKind::InvalidTiles { .. } => {
+                let tile_map = node_ref.cast::<TileMap>()?;
+                let definition_count = tile_definition_count(tile_map)?;
+                let tiles = tile_map
+                    .tiles()
+                    .iter()
+                    .filter(|tile| tile.definition_index() < definition_count)
+                    .cloned()
+                    .collect();
+                Some(Command::new(SetTileMapTilesCommand::new(node, tiles)))
+            }
+            SceneIssueKind::MissingResource(_) | SceneIssueKind::MissingSecondTexCoords => None,
+        }
+    }
+}
+
+fn is_finite_vector(v: &Vector3<f32>) -> bool {
+    v.iter().all(|c| c.is_finite())
+}
+
+fn is_valid_transform(transform: &Transform) -> bool {
+    is_finite_vector(transform.position())
+        && is_finite_vector(transform.scale())
+        && transform.rotation().coords.iter().all(|c| c.is_finite())
+}
+
+/// Replaces invalid parts of the given transform with their default values.
+pub fn sanitize_transform(transform: &Transform) -> Transform {
+    let mut transform = transform.clone();
+    if !is_finite_vector(transform.position()) {
+        transform.set_position(Vector3::default());
+    }
+    if !is_finite_vector(transform.scale()) {
+        transform.set_scale(Vector3::repeat(1.0));
+    }
+    if !transform.rotation().coords.iter().all(|c| c.is_finite()) {
+        transform.set_rotation(UnitQuaternion::identity());
+    }
+    transform
+}
+
+fn tile_definition_count(tile_map: &TileMap) -> Option<usize> {
+    let tile_set = tile_map.tile_set()?;
+    if !tile_set.is_ok() {
+        return None;
+    }
+    let count = tile_set.data_ref().tiles.len();
+    Some(count)
+}
+
+// Hashing of resources does not depend on their (mutable) state.
+#[allow(clippy::mutable_key_type)]
+fn missing_resources(node: &Node) -> Vec<PathBuf> {
+    let mut resources = FxHashSet::default();
+    asset::collect_used_resources(node, &mut resources);
+
+    let mut paths = resources
+        .into_iter()
+        .filter_map(|resource| {
+            let header = resource.0.lock();
+            match (&header.kind, &header.state) {
+                (ResourceKind::External(path), ResourceState::LoadError { .. }) => {
+                    Some(path.clone())
+                }
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+/// Checks every node of the sub-graph starting from the given root and returns a list of found issues.
+pub fn validate_graph(graph: &Graph, root: Handle<Node>) -> Vec<SceneIssue> {
+    scope_profile!();
+
+    let mut issues = Vec::new();
+    for handle in graph.traverse_handle_iter(root) {
+        let node = &graph[handle];
+        let mut add = |kind| issues.push(SceneIssue { node: handle, kind });
+
+        for path in missing_resources(node) {
+            add(SceneIssueKind::MissingResource(path));
+        }
+
+        if !is_valid_transform(node.local_transform()) {
+            add(SceneIssueKind::InvalidTransform);
+        }
+
+        if let Some(point_light) = node.cast::<PointLight>() {
+            if point_light.radius() <= 0.0 {
+                add(SceneIssueKind::ZeroLightRadius);
+            }
+        } else if let Some(spot_light) = node.cast::<SpotLight>() {
+            if spot_light.distance() <= 0.0 {
+                add(SceneIssueKind::ZeroLightRadius);
+            }
+        } else if let Some(area_light) = node.cast::<AreaLight>() {
+            if area_light.radius() <= 0.0 {
+                add(SceneIssueKind::ZeroLightRadius);
+            }
+        }
+
+        if let Some(mesh) = node.cast::<Mesh>() {
+            let is_lightmapped = graph
+                .lightmap()
+                .is_some_and(|lightmap| lightmap.map.contains_key(&handle));
+            if is_lightmapped
+                && mesh.surfaces().iter().any(|surface| {
+                    let data = surface.data();
+                    data.is_ok()
+                        && !data
+                            .data_ref()
+                            .vertex_buffer
+                            .has_attribute(VertexAttributeUsage::TexCoord1)
+                })
+            {
+                add(SceneIssueKind::MissingSecondTexCoords);
+            }
+        }
+
+        if let Some(tile_map) = node.cast::<TileMap>() {
+            if let Some(definition_count) = tile_definition_count(tile_map) {
+                let count = tile_map
+                    .tiles()
+                    .iter()
+                    .filter(|tile| tile.definition_index() >= definition_count)
+                    .count();
+                if count > 0 {
+                    add(SceneIssueKind::InvalidTiles { count });
+                }
+            }
+        }
+    }
+    issues
+}
+
+struct IssueView {
+    select: Handle<UiNode>,
+    fix: Handle<UiNode>,
+}
+
+pub struct ValidationPanel {
+    pub window: Handle<UiNode>,
+    validate: Handle<UiNode>,
+    fix_all: Handle<UiNode>,
+    summary: Handle<UiNode>,
+    list: Handle<UiNode>,
+    issues: Vec<SceneIssue>,
+    views: Vec<IssueView>,
+}
+
+impl ValidationPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let validate;
+        let fix_all;
+        let summary;
+        let list;
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("ValidationPanel")
+                .with_width(400.0)
+                .with_height(300.0),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Scene Validation"))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(0)
+                                .with_child({
+                                    validate = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(80.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Validate")
+                                    .build(ctx);
+                                    validate
+                                })
+                                .with_child({
+                                    fix_all = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(80.0)
+                                            .with_enabled(false)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Fix All")
+                                    .build(ctx);
+                                    fix_all
+                                })
+                                .with_child({
+                                    summary = TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_margin(Thickness::uniform(2.0))
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .build(ctx);
+                                    summary
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    )
+                    .with_child({
+                        list = ListViewBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_scroll_viewer(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(3.0)),
+                            )
+                            .with_horizontal_scroll_allowed(false)
+                            .with_vertical_scroll_allowed(true)
+                            .build(ctx),
+                        )
+                        .build(ctx);
+                        list
+                    }),
+            )
+            .add_row(Row::strict(26.0))
+            .add_row(Row::stretch())
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .build(ctx);
+
+        Self {
+            window,
+            validate,
+            fix_all,
+            summary,
+            list,
+            issues: Default::default(),
+            views: Default::default(),
+        }
+    }
+
+    fn make_issue_view(
+        ctx: &mut BuildContext,
+        index: usize,
+        issue: &SceneIssue,
+        node_name: &str,
+    ) -> (Handle<UiNode>, IssueView) {
+        let select;
+        let fix;
+        let item = BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_background(Brush::Solid(if index % 2 == 0 {
+                    Color::opaque(70, 70, 70)
+                } else {
+                    Color::opaque(40, 40, 40)
+                }))
+                .with_child(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .with_child(
+                                TextBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_column(0)
+                                        .with_margin(Thickness::uniform(2.0))
+                                        .with_foreground(Brush::Solid(Color::GOLD)),
+                                )
+                                .with_text(format!("{node_name}: {}", issue.kind))
+                                .with_wrap(WrapMode::Word)
+                                .with_vertical_text_alignment(VerticalAlignment::Center)
+                                .build(ctx),
+                            )
+                            .with_child({
+                                select = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_column(1)
+                                        .with_margin(Thickness::uniform(1.0)),
+                                )
+                                .with_text("Select")
+                                .build(ctx);
+                                select
+                            })
+                            .with_child({
+                                fix = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_column(2)
+                                        .with_enabled(issue.can_be_fixed())
+                                        .with_margin(Thickness::uniform(1.0)),
+                                )
+                                .with_text("Fix")
+                                .build(ctx);
+                                fix
+                            }),
+                    )
+                    .add_row(Row::auto())
+                    .add_column(Column::stretch())
+                    .add_column(Column::strict(50.0))
+                    .add_column(Column::strict(40.0))
+                    .build(ctx),
+                ),
+        )
+        .build(ctx);
+
+        (item, IssueView { select, fix })
+    }
+
+    /// Runs the validation of the given scene and shows the found issues.
+    pub fn validate(&mut self, game_scene: &GameScene, engine: &mut Engine) {
+        let graph = &engine.scenes[game_scene.scene].graph;
+        self.issues = validate_graph(graph, game_scene.scene_content_root);
+
+        let names = self
+            .issues
+            .iter()
+            .map(|issue| graph[issue.node].name_owned())
+            .collect::<Vec<_>>();
+        let fixable = self.issues.iter().any(|issue| issue.can_be_fixed());
+
+        let ui = engine.user_interfaces.first_mut();
+        let ctx = &mut ui.build_ctx();
+        let (items, views): (Vec<_>, Vec<_>) = self
+            .issues
+            .iter()
+            .zip(names.iter())
+            .enumerate()
+            .map(|(index, (issue, name))| Self::make_issue_view(ctx, index, issue, name))
+            .unzip();
+        self.views = views;
+
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.fix_all,
+            MessageDirection::ToWidget,
+            fixable,
+        ));
+        ui.send_message(TextMessage::text(
+            self.summary,
+            MessageDirection::ToWidget,
+            if self.issues.is_empty() {
+                "No issues found.".to_string()
+            } else {
+                format!("{} issue(s) found.", self.issues.len())
+            },
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        game_scene: &GameScene,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) {
+        scope_profile!();
+
+        let Some(ButtonMessage::Click) = message.data() else {
+            return;
+        };
+
+        if message.destination() == self.validate {
+            self.validate(game_scene, engine);
+            return;
+        }
+
+        let graph = &engine.scenes[game_scene.scene].graph;
+        if message.destination() == self.fix_all {
+            let commands = self
+                .issues
+                .iter()
+                .filter_map(|issue| issue.make_fix_command(graph))
+                .collect::<Vec<_>>();
+            if !commands.is_empty() {
+                sender
+                    .do_command(CommandGroup::from(commands).with_custom_name("Fix Scene Issues"));
+            }
+        } else if let Some(index) = self
+            .views
+            .iter()
+            .position(|view| view.select == message.destination())
+        {
+            let node = self.issues[index].node;
+            if graph.is_valid_handle(node) {
+                sender.do_command(ChangeSelectionCommand::new(Selection::new(
+                    GraphSelection::single_or_empty(node),
+                )));
+                sender.send(Message::FocusObject(node));
+            }
+            return;
+        } else if let Some(index) = self
+            .views
+            .iter()
+            .position(|view| view.fix == message.destination())
+        {
+            if let Some(command) = self.issues[index].make_fix_command(graph) {
+                sender.send(Message::DoCommand(command));
+            }
+        } else {
+            return;
+        }
+
+        // Commands are executed later, so clear the list to not show stale issues.
+        self.issues.clear();
+        self.views.clear();
+        let ui = engine.user_interfaces.first();
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            vec![],
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.fix_all,
+            MessageDirection::ToWidget,
+            false,
+        ));
+        ui.send_message(TextMessage::text(
+            self.summary,
+            MessageDirection::ToWidget,
+            "Issues were fixed, validate again to check the scene.".to_string(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        fyrox::{
+            core::algebra::Vector3,
+            scene::{
+                base::BaseBuilder,
+                graph::Graph,
+                light::{point::PointLightBuilder, BaseLightBuilder},
+                pivot::PivotBuilder,
+                transform::TransformBuilder,
+            },
+        },
+        validation::{sanitize_transform, validate_graph, SceneIssue, SceneIssueKind},
+    };
+
+    #[test]
+    fn test_validate_graph() {
+        let mut graph = Graph::new();
+        let broken = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(f32::NAN, 1.0, 2.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        let light = PointLightBuilder::new(BaseLightBuilder::new(BaseBuilder::new()))
+            .with_radius(0.0)
+            .build(&mut graph);
+        PointLightBuilder::new(BaseLightBuilder::new(BaseBuilder::new())).build(&mut graph);
+
+        let mut issues = validate_graph(&graph, graph.get_root());
+        issues.sort_by_key(|issue| issue.node);
+        assert_eq!(
+            issues,
+            vec![
+                SceneIssue {
+                    node: broken,
+                    kind: SceneIssueKind::InvalidTransform
+                },
+                SceneIssue {
+                    node: light,
+                    kind: SceneIssueKind::ZeroLightRadius
+                }
+            ]
+        );
+        assert!(issues.iter().all(|issue| issue.can_be_fixed()));
+
+        let fixed = sanitize_transform(graph[broken].local_transform());
+        assert_eq!(**fixed.position(), Vector3::default());
+    }
+}
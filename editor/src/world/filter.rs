@@ -0,0 +1,102 @@
+//! Filter queries for the world viewer. A query is a whitespace-separated list of terms, every term
+//! must match for a node to pass the filter:
+//!
+//! - `type:TileMap` - type name of a node contains the given string,
+//! - `tag:Enemy` - any tag of a node contains the given string,
+//! - `has:Collider` - a node has a component (a script or a child node of a special type) whose
+//!   name contains the given string,
+//! - any other term is matched against the name of a node.
+//!
+//! Every comparison is case-insensitive.
+
+use crate::{fyrox::core::pool::ErasedHandle, world::WorldViewerDataProvider};
+use rust_fuzzy_search::fuzzy_compare;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FilterTerm {
+    Name(String),
+    Type(String),
+    Tag(String),
+    Has(String),
+}
+
+impl FilterTerm {
+    fn parse(term: &str) -> Self {
+        let term = term.to_lowercase();
+        if let Some(type_name) = term.strip_prefix("type:") {
+            Self::Type(type_name.to_owned())
+        } else if let Some(tag) = term.strip_prefix("tag:") {
+            Self::Tag(tag.to_owned())
+        } else if let Some(component) = term.strip_prefix("has:") {
+            Self::Has(component.to_owned())
+        } else {
+            Self::Name(term)
+        }
+    }
+
+    fn matches(&self, node: ErasedHandle, data_provider: &dyn WorldViewerDataProvider) -> bool {
+        match self {
+            Self::Name(filter) => data_provider.name_of(node).is_some_and(|name| {
+                let name = name.to_lowercase();
+                name.contains(filter.as_str()) || fuzzy_compare(filter, &name) >= 0.33
+            }),
+            Self::Type(filter) => data_provider
+                .type_name_of(node)
+                .is_some_and(|type_name| type_name.to_lowercase().contains(filter)),
+            Self::Tag(filter) => data_provider
+                .tags_of(node)
+                .iter()
+                .any(|tag| tag.to_lowercase().contains(filter)),
+            Self::Has(filter) => data_provider
+                .component_names_of(node)
+                .iter()
+                .any(|component| component.to_lowercase().contains(filter)),
+        }
+    }
+}
+
+/// Parsed filter query of the world viewer.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WorldViewerFilter {
+    pub terms: Vec<FilterTerm>,
+}
+
+impl WorldViewerFilter {
+    pub fn parse(query: &str) -> Self {
+        Self {
+            terms: query.split_whitespace().map(FilterTerm::parse).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Returns `true` if the node matches every term of the filter. Empty filter matches everything.
+    pub fn matches(&self, node: ErasedHandle, data_provider: &dyn WorldViewerDataProvider) -> bool {
+        self.terms
+            .iter()
+            .all(|term| term.matches(node, data_provider))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::world::filter::{FilterTerm, WorldViewerFilter};
+
+    #[test]
+    fn test_parse_filter() {
+        assert!(WorldViewerFilter::parse("  ").is_empty());
+        assert_eq!(
+            WorldViewerFilter::parse("type:TileMap has:Collider  Enemy tag:Boss"),
+            WorldViewerFilter {
+                terms: vec![
+                    FilterTerm::Type("tilemap".to_string()),
+                    FilterTerm::Has("collider".to_string()),
+                    FilterTerm::Name("enemy".to_string()),
+                    FilterTerm::Tag("boss".to_string()),
+                ]
+            }
+        );
+    }
+}
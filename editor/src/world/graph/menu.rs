@@ -30,7 +30,7 @@ use crate::{
         GameScene, Selection,
     },
     settings::Settings,
-    utils,
+    utils::{self, rename::BatchRenameTarget},
     world::WorldViewerItemContextMenu,
     Engine, Message, MessageDirection, PasteCommand,
 };
@@ -50,6 +50,7 @@ pub struct SceneNodeContextMenu {
     make_root: Handle<UiNode>,
     open_asset: Handle<UiNode>,
     reset_inheritable_properties: Handle<UiNode>,
+    batch_rename: Handle<UiNode>,
 }
 
 impl WorldViewerItemContextMenu for SceneNodeContextMenu {
@@ -83,6 +84,7 @@ impl SceneNodeContextMenu {
         let make_root;
         let open_asset;
         let reset_inheritable_properties;
+        let batch_rename;
 
         let (create_child_entity_menu, create_child_entity_menu_root_items) =
             CreateEntityMenu::new(ctx);
@@ -148,6 +150,10 @@ impl SceneNodeContextMenu {
                             reset_inheritable_properties =
                                 create_menu_item("Reset Inheritable Properties", vec![], ctx);
                             reset_inheritable_properties
+                        })
+                        .with_child({
+                            batch_rename = create_menu_item("Batch Rename...", vec![], ctx);
+                            batch_rename
                         }),
                 )
                 .build(ctx),
@@ -173,6 +179,7 @@ impl SceneNodeContextMenu {
             open_asset,
             reset_inheritable_properties,
             create_parent_entity_menu,
+            batch_rename,
         }
     }
 
@@ -349,6 +356,10 @@ impl SceneNodeContextMenu {
                             sender.send(Message::LoadScene(path));
                         }
                     }
+                } else if message.destination() == self.batch_rename {
+                    sender.send(Message::OpenBatchRenameDialog(
+                        BatchRenameTarget::SelectedNodes,
+                    ));
                 } else if message.destination() == self.reset_inheritable_properties {
                     if let Some(graph_selection) = editor_selection.as_graph() {
                         let scene = &engine.scenes[game_scene.scene];
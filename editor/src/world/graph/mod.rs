@@ -5,8 +5,9 @@ use crate::fyrox::{
     core::{
         algebra::Vector3,
         futures::executor::block_on,
-        make_relative_path,
+        make_pretty_type_name, make_relative_path,
         pool::{ErasedHandle, Handle},
+        reflect::Reflect,
     },
     graph::SceneGraph,
     resource::model::{Model, ModelResourceExtension},
@@ -106,13 +107,53 @@ impl<'a> WorldViewerDataProvider for EditorSceneWrapper<'a> {
             .map(|n| Cow::Borrowed(n.name()))
     }
 
+    fn type_name_of(&self, node: ErasedHandle) -> Option<Cow<'_, str>> {
+        self.scene
+            .graph
+            .try_get(node.into())
+            .map(|n| Cow::Borrowed(make_pretty_type_name(Reflect::type_name(n))))
+    }
+
+    fn tags_of(&self, node: ErasedHandle) -> Vec<String> {
+        self.scene
+            .graph
+            .try_get(node.into())
+            .map(|n| {
+                std::iter::once(n.tag())
+                    .filter(|tag| !tag.is_empty())
+                    .chain(n.tags().iter().map(|tag| tag.as_str()))
+                    .map(|tag| tag.to_owned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn component_names_of(&self, node: ErasedHandle) -> Vec<String> {
+        let Some(node) = self.scene.graph.try_get(node.into()) else {
+            return Default::default();
+        };
+        node.scripts()
+            .map(|script| make_pretty_type_name(Reflect::type_name(script)).to_owned())
+            .chain(node.children().iter().filter_map(|child| {
+                self.scene
+                    .graph
+                    .try_get(*child)
+                    .map(|child| make_pretty_type_name(Reflect::type_name(child)).to_owned())
+            }))
+            .collect()
+    }
+
     fn is_valid_handle(&self, node: ErasedHandle) -> bool {
         self.scene.graph.is_valid_handle(node.into())
     }
 
     fn icon_of(&self, node: ErasedHandle) -> Option<UntypedResource> {
         let node = self.scene.graph.try_get(node.into()).unwrap();
-        if node.is_point_light() || node.is_directional_light() || node.is_spot_light() {
+        if node.is_point_light()
+            || node.is_directional_light()
+            || node.is_spot_light()
+            || node.is_area_light()
+        {
             load_image(include_bytes!("../../../resources/light.png"))
         } else if node.is_joint() || node.is_joint2d() {
             load_image(include_bytes!("../../../resources/joint.png"))
@@ -244,6 +285,20 @@ impl<'a> WorldViewerDataProvider for EditorSceneWrapper<'a> {
     fn on_selection_changed(&self, selection: &[ErasedHandle]) {
         let mut new_selection = Selection::default();
         for &selected_item in selection {
+            // Nodes on locked layers cannot be selected.
+            if self
+                .scene
+                .graph
+                .try_get(selected_item.into())
+                .is_some_and(|node| {
+                    self.game_scene
+                        .camera_controller
+                        .is_layer_locked(node.layer())
+                })
+            {
+                continue;
+            }
+
             match new_selection.as_graph_mut() {
                 Some(selection) => selection.insert_or_exclude(selected_item.into()),
                 None => {
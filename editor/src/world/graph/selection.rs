@@ -4,6 +4,7 @@ use crate::fyrox::{
     scene::{graph::Graph, node::Node},
 };
 use crate::scene::SelectionContainer;
+use crate::settings::pivot::{PivotMode, PivotSettings};
 use crate::utils;
 use fyrox::graph::BaseSceneGraph;
 
@@ -121,6 +122,25 @@ impl GraphSelection {
         }
     }
 
+    /// Returns rotation and position of a pivot point of the selection, that is used to place
+    /// transform gizmos and to rotate and scale objects around it.
+    pub fn pivot_rotation_position(
+        &self,
+        graph: &Graph,
+        pivot: &PivotSettings,
+    ) -> Option<(UnitQuaternion<f32>, Vector3<f32>)> {
+        let (rotation, position) = self.global_rotation_position(graph)?;
+        match pivot.mode {
+            PivotMode::IndividualOrigins | PivotMode::MedianPoint => Some((rotation, position)),
+            PivotMode::ActiveObject => self
+                .nodes
+                .last()
+                .filter(|&&node| graph.is_valid_handle(node))
+                .map(|&node| graph.global_rotation_position_no_scale(node)),
+            PivotMode::Cursor => Some((rotation, pivot.cursor)),
+        }
+    }
+
     pub fn offset(&self, graph: &mut Graph, offset: Vector3<f32>) {
         for &handle in self.nodes.iter() {
             let mut chain_scale = Vector3::new(1.0, 1.0, 1.0);
@@ -14,16 +14,18 @@ use crate::{
             button::{ButtonBuilder, ButtonMessage},
             check_box::{CheckBoxBuilder, CheckBoxMessage},
             decorator::{Decorator, DecoratorBuilder, DecoratorMessage},
+            dropdown_list::{DropdownList, DropdownListBuilder, DropdownListMessage},
             grid::{Column, GridBuilder, Row},
             message::{MessageDirection, UiMessage},
             scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
             searchbar::{SearchBarBuilder, SearchBarMessage},
             stack_panel::StackPanelBuilder,
-            text::TextBuilder,
+            text::{TextBuilder, TextMessage},
             tree::{
                 TreeBuilder, TreeExpansionStrategy, TreeMessage, TreeRoot, TreeRootBuilder,
                 TreeRootMessage,
             },
+            utils::make_simple_tooltip,
             widget::{WidgetBuilder, WidgetMessage},
             window::{WindowBuilder, WindowTitle},
             wrap_panel::WrapPanelBuilder,
@@ -31,15 +33,17 @@ use crate::{
             VerticalAlignment, BRUSH_BRIGHT_BLUE, BRUSH_PRIMARY,
         },
     },
-    gui::make_image_button_with_tooltip,
+    gui::{make_dropdown_list_option, make_image_button_with_tooltip},
     load_image,
     message::MessageSender,
     send_sync_message,
     utils::window_content,
-    world::graph::item::{DropAnchor, SceneItem, SceneItemBuilder, SceneItemMessage},
+    world::{
+        filter::WorldViewerFilter,
+        graph::item::{DropAnchor, SceneItem, SceneItemBuilder, SceneItemMessage},
+    },
     Mode, Settings,
 };
-use rust_fuzzy_search::fuzzy_compare;
 use std::{
     borrow::Cow,
     cell::RefCell,
@@ -49,6 +53,7 @@ use std::{
     rc::Rc,
 };
 
+pub mod filter;
 pub mod graph;
 
 pub trait WorldViewerDataProvider {
@@ -68,6 +73,14 @@ pub trait WorldViewerDataProvider {
 
     fn name_of(&self, node: ErasedHandle) -> Option<Cow<str>>;
 
+    fn type_name_of(&self, node: ErasedHandle) -> Option<Cow<'_, str>>;
+
+    fn tags_of(&self, node: ErasedHandle) -> Vec<String>;
+
+    /// Returns names of every component of the node. Components are scripts and child nodes,
+    /// that extend the functionality of their parent (for example, colliders of rigid bodies).
+    fn component_names_of(&self, node: ErasedHandle) -> Vec<String>;
+
     fn is_valid_handle(&self, node: ErasedHandle) -> bool;
 
     fn icon_of(&self, node: ErasedHandle) -> Option<UntypedResource>;
@@ -101,6 +114,13 @@ pub struct WorldViewer {
     track_selection: Handle<UiNode>,
     search_bar: Handle<UiNode>,
     filter: String,
+    saved_filters: Handle<UiNode>,
+    save_filter: Handle<UiNode>,
+    delete_filter: Handle<UiNode>,
+    match_count: Handle<UiNode>,
+    select_all_matches: Handle<UiNode>,
+    /// Nodes, that match current filter. Empty if there's no filter.
+    matches: Vec<ErasedHandle>,
     stack: Vec<(Handle<UiNode>, ErasedHandle)>,
     /// Hack. Due to delayed execution of UI code we can't sync immediately after we
     /// did sync_to_model, instead we defer selection syncing to post_update() - at
@@ -193,6 +213,33 @@ fn fetch_expanded_state(
         .map_or(true, |i| i.is_expanded)
 }
 
+fn make_saved_filter_items(settings: &Settings, ctx: &mut BuildContext) -> Vec<Handle<UiNode>> {
+    settings
+        .selection
+        .saved_filters
+        .iter()
+        .map(|filter| make_dropdown_list_option(ctx, filter))
+        .collect()
+}
+
+fn make_text_button(
+    text: &str,
+    tooltip: &str,
+    column: usize,
+    tab_index: usize,
+    ctx: &mut BuildContext,
+) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .on_column(column)
+            .with_tab_index(Some(tab_index))
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
 impl WorldViewer {
     pub fn new(ctx: &mut BuildContext, sender: MessageSender, settings: &Settings) -> Self {
         let tree_root;
@@ -205,9 +252,60 @@ impl WorldViewer {
         let search_bar = SearchBarBuilder::new(
             WidgetBuilder::new()
                 .with_tab_index(Some(4))
+                .on_column(0)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .build(ctx);
+        let saved_filters = DropdownListBuilder::new(
+            WidgetBuilder::new()
+                .with_tab_index(Some(5))
+                .on_column(1)
+                .with_margin(Thickness::uniform(1.0))
+                .with_tooltip(make_simple_tooltip(ctx, "Saved Filters")),
+        )
+        .with_items(make_saved_filter_items(settings, ctx))
+        .build(ctx);
+        let save_filter = make_text_button("Save", "Save Current Filter", 2, 6, ctx);
+        let delete_filter = make_text_button("Delete", "Delete Selected Saved Filter", 3, 7, ctx);
+        let filter_bar = GridBuilder::new(
+            WidgetBuilder::new()
                 .on_row(1)
+                .with_child(search_bar)
+                .with_child(saved_filters)
+                .with_child(save_filter)
+                .with_child(delete_filter),
+        )
+        .add_row(Row::stretch())
+        .add_column(Column::stretch())
+        .add_column(Column::strict(100.0))
+        .add_column(Column::strict(40.0))
+        .add_column(Column::strict(45.0))
+        .build(ctx);
+        let match_count = TextBuilder::new(
+            WidgetBuilder::new()
+                .on_column(0)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .build(ctx);
+        let select_all_matches = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .on_column(1)
+                .with_enabled(false)
+                .with_tab_index(Some(8))
                 .with_margin(Thickness::uniform(1.0)),
         )
+        .with_text("Select All Matches")
+        .build(ctx);
+        let match_bar = GridBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
+                .with_child(match_count)
+                .with_child(select_all_matches),
+        )
+        .add_row(Row::stretch())
+        .add_column(Column::stretch())
+        .add_column(Column::strict(120.0))
         .build(ctx);
         let size = 15.0;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("WorldOutliner"))
@@ -283,12 +381,13 @@ impl WorldViewer {
                             .with_orientation(Orientation::Horizontal)
                             .build(ctx),
                         )
-                        .with_child(search_bar)
+                        .with_child(filter_bar)
+                        .with_child(match_bar)
                         .with_child({
-                            scroll_view = ScrollViewerBuilder::new(WidgetBuilder::new().on_row(2))
+                            scroll_view = ScrollViewerBuilder::new(WidgetBuilder::new().on_row(3))
                                 .with_content({
                                     tree_root = TreeRootBuilder::new(
-                                        WidgetBuilder::new().with_tab_index(Some(5)),
+                                        WidgetBuilder::new().with_tab_index(Some(9)),
                                     )
                                     .build(ctx);
                                     tree_root
@@ -299,7 +398,7 @@ impl WorldViewer {
                         .with_child({
                             node_path = WrapPanelBuilder::new(
                                 WidgetBuilder::new()
-                                    .on_row(3)
+                                    .on_row(4)
                                     .with_vertical_alignment(VerticalAlignment::Top),
                             )
                             .with_orientation(Orientation::Horizontal)
@@ -310,6 +409,7 @@ impl WorldViewer {
                 .add_column(Column::stretch())
                 .add_row(Row::strict(25.0))
                 .add_row(Row::strict(22.0))
+                .add_row(Row::strict(22.0))
                 .add_row(Row::stretch())
                 .add_row(Row::auto())
                 .build(ctx),
@@ -333,6 +433,12 @@ impl WorldViewer {
             item_context_menu: None,
             node_to_view_map: Default::default(),
             filter: Default::default(),
+            saved_filters,
+            save_filter,
+            delete_filter,
+            match_count,
+            select_all_matches,
+            matches: Default::default(),
         }
     }
 
@@ -344,6 +450,10 @@ impl WorldViewer {
     ) {
         self.sync_graph(ui, data_provider, settings);
         self.validate(data_provider, ui);
+        if !self.filter.is_empty() {
+            // New nodes could appear or existing ones could change, so the filter must be re-applied.
+            self.apply_filter(data_provider, ui);
+        }
     }
 
     fn build_breadcrumb(
@@ -595,21 +705,33 @@ impl WorldViewer {
         colorize(self.tree_root, ui, &mut index);
     }
 
-    fn apply_filter(&self, data_provider: &dyn WorldViewerDataProvider, ui: &UserInterface) {
-        fn apply_filter_recursive(node: Handle<UiNode>, filter: &str, ui: &UserInterface) -> bool {
+    fn apply_filter(&mut self, data_provider: &dyn WorldViewerDataProvider, ui: &UserInterface) {
+        fn apply_filter_recursive(
+            node: Handle<UiNode>,
+            filter: &WorldViewerFilter,
+            data_provider: &dyn WorldViewerDataProvider,
+            matches: &mut Vec<ErasedHandle>,
+            ui: &UserInterface,
+        ) -> bool {
             let node_ref = ui.node(node);
 
+            let item = node_ref.cast::<SceneItem>();
+
             let mut is_any_match = false;
-            for &child in node_ref.children() {
-                is_any_match |= apply_filter_recursive(child, filter, ui)
+            if let Some(item) = item {
+                if filter.matches(item.entity_handle, data_provider) {
+                    is_any_match = true;
+                    if !filter.is_empty() {
+                        matches.push(item.entity_handle);
+                    }
+                }
             }
 
-            let name = node_ref.cast::<SceneItem>().map(|i| i.name());
-
-            if let Some(name) = name {
-                is_any_match |= name.to_lowercase().contains(filter)
-                    || fuzzy_compare(filter, name.to_lowercase().as_str()) >= 0.33;
+            for &child in node_ref.children() {
+                is_any_match |= apply_filter_recursive(child, filter, data_provider, matches, ui)
+            }
 
+            if item.is_some() {
                 ui.send_message(WidgetMessage::visibility(
                     node,
                     MessageDirection::ToWidget,
@@ -620,7 +742,30 @@ impl WorldViewer {
             is_any_match
         }
 
-        apply_filter_recursive(self.tree_root, &self.filter.to_lowercase(), ui);
+        let filter = WorldViewerFilter::parse(&self.filter);
+        self.matches.clear();
+        apply_filter_recursive(
+            self.tree_root,
+            &filter,
+            data_provider,
+            &mut self.matches,
+            ui,
+        );
+
+        ui.send_message(TextMessage::text(
+            self.match_count,
+            MessageDirection::ToWidget,
+            if filter.is_empty() {
+                Default::default()
+            } else {
+                format!("{} match(es)", self.matches.len())
+            },
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.select_all_matches,
+            MessageDirection::ToWidget,
+            !self.matches.is_empty(),
+        ));
 
         if self.filter.is_empty() {
             if let Some(first) = data_provider.selection().first() {
@@ -645,11 +790,25 @@ impl WorldViewer {
         self.apply_filter(data_provider, ui)
     }
 
+    fn sync_saved_filters(&self, ui: &mut UserInterface, settings: &Settings) {
+        let items = make_saved_filter_items(settings, &mut ui.build_ctx());
+        ui.send_message(DropdownListMessage::items(
+            self.saved_filters,
+            MessageDirection::ToWidget,
+            items,
+        ));
+        ui.send_message(DropdownListMessage::selection(
+            self.saved_filters,
+            MessageDirection::ToWidget,
+            None,
+        ));
+    }
+
     pub fn handle_ui_message(
         &mut self,
         message: &UiMessage,
         data_provider: &mut dyn WorldViewerDataProvider,
-        ui: &UserInterface,
+        ui: &mut UserInterface,
         settings: &mut Settings,
     ) {
         scope_profile!();
@@ -679,6 +838,27 @@ impl WorldViewer {
                 ));
             } else if message.destination() == self.locate_selection {
                 self.locate_selection(&data_provider.selection(), ui)
+            } else if message.destination() == self.select_all_matches {
+                data_provider.on_selection_changed(&self.matches);
+            } else if message.destination() == self.save_filter {
+                let filter = self.filter.trim();
+                if !filter.is_empty()
+                    && !settings.selection.saved_filters.iter().any(|f| f == filter)
+                {
+                    settings.selection.saved_filters.push(filter.to_owned());
+                    self.sync_saved_filters(ui, settings);
+                }
+            } else if message.destination() == self.delete_filter {
+                let selection = ui
+                    .node(self.saved_filters)
+                    .cast::<DropdownList>()
+                    .and_then(|list| *list.selection);
+                if let Some(index) = selection {
+                    if index < settings.selection.saved_filters.len() {
+                        settings.selection.saved_filters.remove(index);
+                        self.sync_saved_filters(ui, settings);
+                    }
+                }
             }
         } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data::<CheckBoxMessage>()
         {
@@ -694,6 +874,18 @@ impl WorldViewer {
             {
                 self.set_filter(text.clone(), data_provider, ui);
             }
+        } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.saved_filters
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(filter) = settings.selection.saved_filters.get(*index) {
+                    ui.send_message(SearchBarMessage::text(
+                        self.search_bar,
+                        MessageDirection::ToWidget,
+                        filter.clone(),
+                    ));
+                }
+            }
         } else if let Some(TreeMessage::Expand { expand, .. }) = message.data() {
             if let Some(scene_view_item) = ui
                 .node(message.destination())
@@ -0,0 +1,227 @@
+# Blender add-on, that sends transforms, meshes and materials of selected objects to the running
+# Fyrox editor using its remote control server (see `editor/src/remote/live_link.rs`).
+#
+# Installation: Edit -> Preferences -> Add-ons -> Install... and pick this file. Then enable
+# remote control server in the editor settings (Remote Control -> Enabled), make sure that the
+# port matches the port in the add-on preferences and copy the token from the editor settings
+# (Remote Control -> Token) to the add-on preferences.
+#
+# Objects are matched with the scene nodes by their names. Meshes are sent with one surface per
+# material slot, so the node in the editor must have the same amount of surfaces (it is the case for
+# meshes imported from FBX or glTF files exported from Blender).
+
+import json
+import socket
+
+import bpy
+
+bl_info = {
+    "name": "Fyrox Live Link",
+    "description": "Sends selected objects to the running Fyrox editor.",
+    "author": "Fyrox Engine",
+    "version": (0, 1, 0),
+    "blender": (2, 93, 0),
+    "location": "View3D > Sidebar > Fyrox",
+    "category": "Import-Export",
+}
+
+
+def to_fyrox_vector(v):
+    # Blender is Z-up, Fyrox is Y-up, both are right-handed, so it is just a rotation around X axis.
+    return [v[0], v[2], -v[1]]
+
+
+def to_fyrox_scale(v):
+    return [v[0], v[2], v[1]]
+
+
+def to_fyrox_quaternion(q):
+    # Blender stores quaternions as (w, x, y, z), Fyrox expects [x, y, z, w].
+    return [q.x, q.z, -q.y, q.w]
+
+
+class LiveLinkClient:
+    def __init__(self, host, port, token):
+        self.socket = socket.create_connection((host, port), timeout=5.0)
+        self.reader = self.socket.makefile("r", encoding="utf-8")
+        try:
+            # The editor closes the connection if the token is wrong.
+            self.send({"token": token})
+        except RuntimeError:
+            self.close()
+            raise
+
+    def send(self, request):
+        self.socket.sendall((json.dumps(request) + "\n").encode("utf-8"))
+        line = self.reader.readline()
+        if not line:
+            raise OSError("The editor has closed the connection.")
+        response = json.loads(line)
+        if not response.get("ok", False):
+            raise RuntimeError(response.get("error", "Unknown error."))
+
+    def close(self):
+        self.reader.close()
+        self.socket.close()
+
+
+def make_transform_update(obj):
+    location, rotation, scale = obj.matrix_local.decompose()
+    return {
+        "command": "UpdateTransform",
+        "node": obj.name,
+        "position": to_fyrox_vector(location),
+        "rotation": to_fyrox_quaternion(rotation),
+        "scale": to_fyrox_scale(scale),
+    }
+
+
+def make_mesh_update(obj, depsgraph):
+    evaluated = obj.evaluated_get(depsgraph)
+    mesh = evaluated.to_mesh()
+    try:
+        mesh.calc_loop_triangles()
+        uv_layer = mesh.uv_layers.active
+
+        surface_count = max(len(obj.material_slots), 1)
+        surfaces = [
+            {"positions": [], "normals": [], "uvs": [], "triangles": []}
+            for _ in range(surface_count)
+        ]
+        # Maps a loop index to a vertex index in a surface, every loop becomes a separate vertex
+        # to keep split normals and seams of texture coordinates.
+        loop_maps = [{} for _ in range(surface_count)]
+
+        for triangle in mesh.loop_triangles:
+            index = min(triangle.material_index, surface_count - 1)
+            surface = surfaces[index]
+            loop_map = loop_maps[index]
+            indices = []
+            for loop_index, normal in zip(triangle.loops, triangle.split_normals):
+                vertex = loop_map.get(loop_index)
+                if vertex is None:
+                    vertex = len(surface["positions"])
+                    loop_map[loop_index] = vertex
+                    position = mesh.vertices[mesh.loops[loop_index].vertex_index].co
+                    surface["positions"].append(to_fyrox_vector(position))
+                    surface["normals"].append(to_fyrox_vector(normal))
+                    if uv_layer is not None:
+                        u, v = uv_layer.data[loop_index].uv
+                        surface["uvs"].append([u, 1.0 - v])
+                indices.append(vertex)
+            surface["triangles"].append(indices)
+
+        return {"command": "UpdateMesh", "node": obj.name, "surfaces": surfaces}
+    finally:
+        evaluated.to_mesh_clear()
+
+
+def make_material_updates(obj):
+    updates = []
+    for surface, slot in enumerate(obj.material_slots):
+        material = slot.material
+        if material is None:
+            continue
+        color = list(material.diffuse_color)
+        if material.use_nodes:
+            bsdf = material.node_tree.nodes.get("Principled BSDF")
+            if bsdf is not None:
+                color = list(bsdf.inputs["Base Color"].default_value)
+        updates.append(
+            {
+                "command": "UpdateMaterial",
+                "node": obj.name,
+                "surface": surface,
+                "properties": {"diffuseColor": color},
+            }
+        )
+    return updates
+
+
+class FyroxLiveLinkPreferences(bpy.types.AddonPreferences):
+    bl_idname = __name__
+
+    host: bpy.props.StringProperty(name="Host", default="127.0.0.1")
+    port: bpy.props.IntProperty(name="Port", default=9527, min=1, max=65535)
+    token: bpy.props.StringProperty(name="Token", subtype="PASSWORD")
+
+    def draw(self, context):
+        self.layout.prop(self, "host")
+        self.layout.prop(self, "port")
+        self.layout.prop(self, "token")
+
+
+class FYROX_OT_send_selected(bpy.types.Operator):
+    """Send transforms, meshes and materials of the selected objects to the Fyrox editor"""
+
+    bl_idname = "fyrox.send_selected"
+    bl_label = "Send to Fyrox"
+
+    send_transform: bpy.props.BoolProperty(name="Transform", default=True)
+    send_mesh: bpy.props.BoolProperty(name="Mesh", default=True)
+    send_material: bpy.props.BoolProperty(name="Material", default=True)
+
+    def execute(self, context):
+        preferences = context.preferences.addons[__name__].preferences
+        try:
+            client = LiveLinkClient(preferences.host, preferences.port, preferences.token)
+        except (OSError, RuntimeError) as err:
+            self.report({"ERROR"}, f"Unable to connect to the Fyrox editor: {err}")
+            return {"CANCELLED"}
+
+        depsgraph = context.evaluated_depsgraph_get()
+        failed = 0
+        try:
+            for obj in context.selected_objects:
+                requests = []
+                if self.send_transform:
+                    requests.append(make_transform_update(obj))
+                if obj.type == "MESH":
+                    if self.send_mesh:
+                        requests.append(make_mesh_update(obj, depsgraph))
+                    if self.send_material:
+                        requests.extend(make_material_updates(obj))
+                for request in requests:
+                    try:
+                        client.send(request)
+                    except RuntimeError as err:
+                        failed += 1
+                        self.report(
+                            {"WARNING"}, f"{request['command']} of {obj.name} failed: {err}"
+                        )
+        except OSError as err:
+            self.report({"ERROR"}, f"Connection to the Fyrox editor was lost: {err}")
+            return {"CANCELLED"}
+        finally:
+            client.close()
+
+        if failed == 0:
+            self.report({"INFO"}, "Selected objects were sent to the Fyrox editor.")
+        return {"FINISHED"}
+
+
+class FYROX_PT_live_link(bpy.types.Panel):
+    bl_label = "Fyrox Live Link"
+    bl_space_type = "VIEW_3D"
+    bl_region_type = "UI"
+    bl_category = "Fyrox"
+
+    def draw(self, context):
+        self.layout.operator(FYROX_OT_send_selected.bl_idname)
+
+
+classes = (FyroxLiveLinkPreferences, FYROX_OT_send_selected, FYROX_PT_live_link)
+
+
+def register():
+    for cls in classes:
+        bpy.utils.register_class(cls)
+
+
+def unregister():
+    for cls in reversed(classes):
+        bpy.utils.unregister_class(cls)
+
+
+if __name__ == "__main__":
+    register()
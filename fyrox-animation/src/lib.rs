@@ -21,6 +21,7 @@ use std::{
     collections::VecDeque,
     fmt::Debug,
     ops::{Index, IndexMut, Range},
+    path::{Path, PathBuf},
 };
 
 pub use fyrox_core as core;
@@ -193,6 +194,9 @@ pub struct Animation<T: EntityId> {
     events: VecDeque<AnimationEvent>,
     #[visit(optional)]
     max_event_capacity: usize,
+
+    #[visit(optional)]
+    audio_clip: Option<PathBuf>,
 }
 
 impl<T: EntityId> TypeUuidProvider for Animation<T> {
@@ -272,6 +276,7 @@ impl<T: EntityId> Clone for Animation<T> {
             time_slice: self.time_slice.clone(),
             root_motion: self.root_motion.clone(),
             max_event_capacity: 32,
+            audio_clip: self.audio_clip.clone(),
         }
     }
 }
@@ -652,6 +657,20 @@ impl<T: EntityId> Animation<T> {
         self.enabled
     }
 
+    /// Attaches an audio clip to the animation. The clip does not affect playback in any way, it is used by
+    /// the editor to show the waveform of the clip behind the timeline, so the keys could be synced with sound
+    /// cues.
+    pub fn set_audio_clip(&mut self, audio_clip: Option<PathBuf>) -> &mut Self {
+        self.audio_clip = audio_clip;
+        self
+    }
+
+    /// Returns a path to the audio clip attached to the animation (if any). See [`Self::set_audio_clip`] for
+    /// more info.
+    pub fn audio_clip(&self) -> Option<&Path> {
+        self.audio_clip.as_deref()
+    }
+
     /// Returns a mutable reference to the track container.
     pub fn tracks_mut(&mut self) -> &mut [Track<T>] {
         &mut self.tracks
@@ -783,6 +802,7 @@ impl<T: EntityId> Default for Animation<T> {
             time_slice: Default::default(),
             root_motion: None,
             max_event_capacity: 32,
+            audio_clip: None,
         }
     }
 }
@@ -179,4 +179,9 @@ impl ParameterContainer {
             .get(name)
             .and_then(|i| self.parameters.parameters.get_mut(*i).map(|d| &mut d.value))
     }
+
+    /// Returns an iterator over every parameter definition in the container.
+    pub fn iter(&self) -> impl Iterator<Item = &ParameterDefinition> {
+        self.parameters.parameters.iter()
+    }
 }
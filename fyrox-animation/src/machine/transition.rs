@@ -78,6 +78,7 @@ macro_rules! define_two_args_node {
                         max_value: None,
                         step: None,
                         precision: None,
+                        unit: None,
                         doc: "",
                     },
                     FieldInfo {
@@ -93,7 +94,9 @@ macro_rules! define_two_args_node {
                         min_value: None,
                         max_value: None,
                         step: None,
-                        precision: None,doc: "",
+                        precision: None,
+                        unit: None,
+                        doc: "",
                     },
                 ])
             }
@@ -231,6 +234,7 @@ impl<T: EntityId> Reflect for NotNode<T> {
             max_value: None,
             step: None,
             precision: None,
+            unit: None,
             doc: "",
         }])
     }
@@ -99,6 +99,18 @@ fn quote_field_prop(
         Some(v) => quote! { Some(#v) },
     };
 
+    let unit = match field.unit.as_deref() {
+        None => quote! { None },
+        Some("meters") => quote! { Some(PropertyUnit::Meters) },
+        Some("degrees") => quote! { Some(PropertyUnit::Degrees) },
+        Some("seconds") => quote! { Some(PropertyUnit::Seconds) },
+        Some("percent") => quote! { Some(PropertyUnit::Percent) },
+        Some(other) => panic!(
+            "unknown unit `{}`, expected one of: meters, degrees, seconds, percent",
+            other
+        ),
+    };
+
     let ty = field.ty.clone();
 
     let read_only = field.read_only;
@@ -121,6 +133,7 @@ fn quote_field_prop(
             reflect_value: #field_getter,
             step: #step,
             precision: #precision,
+            unit: #unit,
             description: #description,
             type_name: std::any::type_name::<#ty>()
         }
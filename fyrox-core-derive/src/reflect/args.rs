@@ -205,6 +205,13 @@ pub struct FieldArgs {
     #[darling(default)]
     pub precision: Option<usize>,
 
+    /// `#[reflect(unit = "meters")]`
+    ///
+    /// A unit of a numeric property. Supported units are `meters`, `degrees`, `seconds` and
+    /// `percent`. Works only for numeric fields!
+    #[darling(default)]
+    pub unit: Option<String>,
+
     /// `#[reflect(description = "This is a property description.")]`
     ///
     /// Description of the property.
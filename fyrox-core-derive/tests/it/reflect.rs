@@ -323,6 +323,7 @@ fn default_prop() -> FieldInfo<'static, 'static> {
         max_value: None,
         step: None,
         precision: None,
+        unit: None,
         description: "",
         type_name: "",
         doc: "",
@@ -380,6 +381,7 @@ fn inspect_attributes() {
             max_value = 1.1,
             step = 0.1,
             precision = 3,
+            unit = "meters",
             description = "This is a property description."
         )]
         y: f32,
@@ -408,6 +410,7 @@ fn inspect_attributes() {
             max_value: Some(1.1),
             step: Some(0.1),
             precision: Some(3),
+            unit: Some(PropertyUnit::Meters),
             description: "This is a property description.",
             type_name: std::any::type_name::<f32>(),
             doc: "",
@@ -0,0 +1,112 @@
+//! Benchmarks of the [`Visitor`] serialization. Run with `cargo bench -p fyrox-core --bench visitor`,
+//! add `--features mmap` to measure loading of memory-mapped files.
+//! It does not use any benchmarking framework, every stage is measured a few times and the best time
+//! is printed.
+//!
+//! Reference results (release build, same machine) before and after indexed region lookup and name
+//! interning were introduced. Before is the same benchmark built against the previous implementation.
+//!
+//! | Stage         | Before     | After     |
+//! |---------------|------------|-----------|
+//! | Write         | 1900.08 ms | 67.16 ms  |
+//! | Save (memory) | 24.67 ms   | 29.68 ms  |
+//! | Save (file)   | 32.06 ms   | 31.70 ms  |
+//! | Load (memory) | 35.61 ms   | 50.15 ms  |
+//! | Load (file)   | 36.49 ms   | 50.00 ms  |
+//! | Read          | 2099.93 ms | 109.59 ms |
+//!
+//! Loading is slightly slower, because names are interned while loading, but it is paid back many
+//! times when the data is read.
+
+use fyrox_core::{algebra::Vector3, futures::executor::block_on, visitor::prelude::*};
+use std::time::{Duration, Instant};
+
+const ENTITY_COUNT: usize = 20_000;
+const ITERATIONS: usize = 5;
+
+#[derive(Default, Visit)]
+struct Entity {
+    name: String,
+    position: Vector3<f32>,
+    scale: Vector3<f32>,
+    health: f32,
+    enabled: bool,
+    tags: Vec<u32>,
+    parent: Option<u32>,
+}
+
+#[derive(Default, Visit)]
+struct World {
+    entities: Vec<Entity>,
+}
+
+fn make_world() -> World {
+    World {
+        entities: (0..ENTITY_COUNT)
+            .map(|i| Entity {
+                name: format!("Entity{}", i),
+                position: Vector3::new(i as f32, 1.0, 2.0),
+                scale: Vector3::repeat(1.0),
+                health: 100.0,
+                enabled: i % 2 == 0,
+                tags: vec![i as u32, 1, 2],
+                parent: i.checked_sub(1).map(|p| p as u32),
+            })
+            .collect(),
+    }
+}
+
+fn measure<T>(name: &str, mut func: impl FnMut() -> T) -> T {
+    let mut best = Duration::MAX;
+    let mut result = None;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        let value = func();
+        best = best.min(start.elapsed());
+        result = Some(value);
+    }
+    println!("{:<24}{:>10.2} ms", name, best.as_secs_f64() * 1000.0);
+    result.unwrap()
+}
+
+fn main() {
+    let mut world = make_world();
+
+    let visitor = measure("Write", || {
+        let mut visitor = Visitor::new();
+        world.visit("World", &mut visitor).unwrap();
+        visitor
+    });
+
+    let data = measure("Save (memory)", || visitor.save_binary_to_vec().unwrap());
+
+    let path = std::env::temp_dir().join("fyrox_visitor_bench.bin");
+    measure("Save (file)", || visitor.save_binary(&path).unwrap());
+
+    measure("Load (memory)", || {
+        Visitor::load_from_memory(&data).unwrap()
+    });
+
+    measure("Load (file)", || {
+        block_on(Visitor::load_binary(&path)).unwrap()
+    });
+
+    #[cfg(feature = "mmap")]
+    measure("Load (mapped file)", || {
+        Visitor::load_binary_mapped(&path).unwrap()
+    });
+
+    measure("Read", || {
+        let mut visitor = Visitor::load_from_memory(&data).unwrap();
+        let mut world = World::default();
+        world.visit("World", &mut visitor).unwrap();
+        world
+    });
+
+    println!(
+        "Data size: {:.2} MiB",
+        data.len() as f64 / (1024.0 * 1024.0)
+    );
+
+    let _ = std::fs::remove_file(path);
+}
@@ -5,9 +5,11 @@ use crate::{
     visitor::{Visit, VisitResult, Visitor},
 };
 use num_traits::Zero;
+use serde::{Deserialize, Serialize};
 use std::ops::{Add, AddAssign, Sub, SubAssign};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
 
-#[derive(Copy, Clone, Debug, PartialOrd, PartialEq, Eq, Visit, Reflect)]
+#[derive(Copy, Clone, Debug, PartialOrd, PartialEq, Eq, Visit, Reflect, Serialize, Deserialize)]
 #[repr(C)]
 pub struct Color {
     // Do not change order! OpenGL requires this order!
@@ -681,6 +683,73 @@ impl Color {
     }
 }
 
+/// Color space defines how the values of color components must be interpreted.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Visit,
+    Reflect,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum ColorSpace {
+    /// Components are encoded using sRGB transfer function. This is the color space of the images
+    /// on screen, color pickers, the most of color textures (albedo, emission, etc.) and material
+    /// colors. The renderer decodes such values to linear space before lighting.
+    #[default]
+    Srgb,
+    /// Components are proportional to the light intensity. This is the color space of lighting
+    /// calculations and data textures (normal maps, roughness maps, etc.).
+    Linear,
+}
+
+uuid_provider!(ColorSpace = "9a6b3b8e-0a2a-4c0c-9e7e-5c1c0b7f6a51");
+
+/// sRGB opto-electronic transfer function (OETF), converts a linear value in `[0; 1]` range to
+/// sRGB-encoded value.
+#[inline]
+pub fn srgb_oetf(linear: f32) -> f32 {
+    if linear <= 0.0031308 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// sRGB electro-optical transfer function (EOTF), converts an sRGB-encoded value in `[0; 1]` range
+/// to linear value. It is the inverse of [`srgb_oetf`].
+#[inline]
+pub fn srgb_eotf(encoded: f32) -> f32 {
+    if encoded <= 0.04045 {
+        encoded / 12.92
+    } else {
+        ((encoded + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+impl Color {
+    /// Converts the color from one color space to another using exact sRGB transfer functions.
+    /// Alpha is always linear and remains untouched.
+    #[must_use]
+    pub fn convert_color_space(self, from: ColorSpace, to: ColorSpace) -> Self {
+        let transfer: fn(f32) -> f32 = match (from, to) {
+            (ColorSpace::Srgb, ColorSpace::Linear) => srgb_eotf,
+            (ColorSpace::Linear, ColorSpace::Srgb) => srgb_oetf,
+            _ => return self,
+        };
+        let convert = |c: u8| (transfer(c as f32 / 255.0).clamp(0.0, 1.0) * 255.0).round() as u8;
+        Self::from_rgba(convert(self.r), convert(self.g), convert(self.b), self.a)
+    }
+}
+
 impl Add for Color {
     type Output = Self;
 
@@ -722,7 +791,7 @@ impl SubAssign for Color {
 #[cfg(test)]
 mod test {
     use crate::algebra::{Vector3, Vector4};
-    use crate::color::{Color, Hsl, Hsv};
+    use crate::color::{Color, ColorSpace, Hsl, Hsv};
 
     #[test]
     fn test_hsl() {
@@ -1022,4 +1091,23 @@ mod test {
         color -= Color::opaque(155, 155, 155);
         assert_eq!(color, Color::from_rgba(100, 100, 100, 0));
     }
+
+    #[test]
+    fn test_color_space_conversion() {
+        let color = Color::from_rgba(188, 128, 0, 100);
+        let linear = color.convert_color_space(ColorSpace::Srgb, ColorSpace::Linear);
+        assert_eq!(linear, Color::from_rgba(128, 55, 0, 100));
+        assert_eq!(
+            linear.convert_color_space(ColorSpace::Linear, ColorSpace::Srgb),
+            color
+        );
+        assert_eq!(
+            color.convert_color_space(ColorSpace::Srgb, ColorSpace::Srgb),
+            color
+        );
+        assert_eq!(
+            Color::WHITE.convert_color_space(ColorSpace::Srgb, ColorSpace::Linear),
+            Color::WHITE
+        );
+    }
 }
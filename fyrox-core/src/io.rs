@@ -64,6 +64,13 @@ pub async fn load_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, FileLoadError
                     JsFuture::from(window.fetch_with_str(path.as_ref().to_str().unwrap())).await?;
 
                 let resp: web_sys::Response = resp_value.dyn_into().unwrap();
+                if !resp.ok() {
+                    return Err(FileLoadError::Custom(format!(
+                        "Unable to fetch {}. Status: {}",
+                        path.as_ref().display(),
+                        resp.status()
+                    )));
+                }
                 let data = JsFuture::from(resp.array_buffer().unwrap()).await?;
                 let bytes = Uint8Array::new(&data).to_vec();
                 Ok(bytes)
@@ -35,7 +35,9 @@ pub mod color_gradient;
 pub mod io;
 pub mod log;
 pub mod math;
+pub mod memory;
 pub mod net;
+pub mod noise;
 pub mod numeric_range;
 pub mod pool;
 pub mod profiler;
@@ -12,6 +12,7 @@ use crate::{
     math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, plane::Plane},
     num_traits::NumAssign,
     reflect::prelude::*,
+    type_traits::{combine_uuids, TypeUuidProvider},
     visitor::prelude::*,
 };
 use fyrox_core_derive::{impl_reflect, impl_visit};
@@ -37,6 +38,18 @@ where
     }
 }
 
+impl<T> TypeUuidProvider for Rect<T>
+where
+    T: TypeUuidProvider + Debug,
+{
+    fn type_uuid() -> Uuid {
+        combine_uuids(
+            uuid::uuid!("3a5ad5cb-4de4-4c6d-a0f5-7e2f4b9c0d61"),
+            T::type_uuid(),
+        )
+    }
+}
+
 impl Visit for TriangleDefinition {
     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
         let mut region = visitor.enter_region(name)?;
@@ -0,0 +1,345 @@
+//! Memory tracker attributes heap usage to engine subsystems (see [`MemorySubsystem`]). It is useful
+//! to find leaks, for example, geometry buffers that were never released by the renderer.
+//!
+//! The tracker is disabled by default, because it adds some overhead to every allocation. To use it,
+//! compile with `memory_tracking` feature and install [`TrackingAllocator`] as the global allocator
+//! in the main crate of your game (or the editor):
+//!
+//! ```rust,ignore
+//! #[global_allocator]
+//! static ALLOCATOR: fyrox::core::memory::TrackingAllocator = fyrox::core::memory::TrackingAllocator;
+//! ```
+//!
+//! Every allocation is attributed to the subsystem of the innermost [`scope`] of the current thread,
+//! allocations outside any scope belong to [`MemorySubsystem::Other`]. A memory block is always
+//! accounted for the subsystem, that allocated it, no matter which subsystem frees it. Async tasks
+//! could be attributed to a subsystem with [`tracked`].
+
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    task::{Context, Poll},
+};
+
+/// A part of the engine, that allocated some memory.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum MemorySubsystem {
+    /// Everything, that was allocated outside of any memory scope.
+    Other = 0,
+    /// User interfaces: widgets, layout and drawing commands.
+    Ui = 1,
+    /// The renderer and its caches (geometry buffers, textures, shaders).
+    Renderer = 2,
+    /// Scenes and their graphs.
+    Scene = 3,
+    /// Resource manager and resource loading.
+    Resources = 4,
+}
+
+impl MemorySubsystem {
+    /// All the subsystems in the order of their ids.
+    pub const ALL: [MemorySubsystem; 5] = [
+        MemorySubsystem::Other,
+        MemorySubsystem::Ui,
+        MemorySubsystem::Renderer,
+        MemorySubsystem::Scene,
+        MemorySubsystem::Resources,
+    ];
+
+    /// Returns a human-readable name of the subsystem.
+    pub fn name(self) -> &'static str {
+        match self {
+            MemorySubsystem::Other => "Other",
+            MemorySubsystem::Ui => "UI",
+            MemorySubsystem::Renderer => "Renderer",
+            MemorySubsystem::Scene => "Scene",
+            MemorySubsystem::Resources => "Resources",
+        }
+    }
+
+    fn from_id(id: u8) -> Self {
+        Self::ALL
+            .get(id as usize)
+            .copied()
+            .unwrap_or(MemorySubsystem::Other)
+    }
+}
+
+/// Memory usage of a subsystem (or of the whole process).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Amount of bytes, that is currently allocated.
+    pub current: usize,
+    /// Maximum amount of bytes, that was allocated at the same time since the start (or since the
+    /// last call of [`reset_peaks`]).
+    pub peak: usize,
+    /// Amount of memory blocks, that are currently allocated.
+    pub allocations: usize,
+}
+
+struct Counter {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+    allocations: AtomicUsize,
+}
+
+impl Counter {
+    const fn new() -> Self {
+        Self {
+            current: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            allocations: AtomicUsize::new(0),
+        }
+    }
+
+    #[cfg_attr(not(feature = "memory_tracking"), allow(dead_code))]
+    fn allocated(&self, size: usize) {
+        let current = self.current.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak.fetch_max(current, Ordering::Relaxed);
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[cfg_attr(not(feature = "memory_tracking"), allow(dead_code))]
+    fn freed(&self, size: usize) {
+        self.current.fetch_sub(size, Ordering::Relaxed);
+        self.allocations.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    fn reset_peak(&self) {
+        self.peak
+            .store(self.current.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+
+    fn usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            current: self.current.load(Ordering::Relaxed),
+            peak: self.peak.load(Ordering::Relaxed),
+            allocations: self.allocations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+static SUBSYSTEM_COUNTERS: [Counter; 5] = [
+    Counter::new(),
+    Counter::new(),
+    Counter::new(),
+    Counter::new(),
+    Counter::new(),
+];
+
+static TOTAL_COUNTER: Counter = Counter::new();
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static CURRENT_SUBSYSTEM: Cell<u8> = const { Cell::new(0) };
+}
+
+/// Returns `true` if the tracker was compiled in (`memory_tracking` feature is enabled) and
+/// [`TrackingAllocator`] is used as the global allocator.
+pub fn is_available() -> bool {
+    cfg!(feature = "memory_tracking") && INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Returns memory usage of the given subsystem.
+pub fn subsystem_usage(subsystem: MemorySubsystem) -> MemoryUsage {
+    SUBSYSTEM_COUNTERS[subsystem as usize].usage()
+}
+
+/// Returns memory usage of the whole process. The peak could be less than the sum of the peaks of
+/// the subsystems, because subsystems could reach their peaks at different times.
+pub fn total_usage() -> MemoryUsage {
+    TOTAL_COUNTER.usage()
+}
+
+/// Sets peaks of every subsystem to their current memory usage.
+pub fn reset_peaks() {
+    for counter in SUBSYSTEM_COUNTERS.iter() {
+        counter.reset_peak();
+    }
+    TOTAL_COUNTER.reset_peak();
+}
+
+/// Returns the subsystem, that the allocations of the current thread are attributed to.
+pub fn current_subsystem() -> MemorySubsystem {
+    MemorySubsystem::from_id(CURRENT_SUBSYSTEM.try_with(|c| c.get()).unwrap_or(0))
+}
+
+/// A guard, that restores the previous subsystem of the current thread when dropped. See [`scope`].
+#[must_use = "the scope ends when the guard is dropped"]
+pub struct MemoryScope {
+    #[cfg(feature = "memory_tracking")]
+    previous: u8,
+}
+
+impl Drop for MemoryScope {
+    fn drop(&mut self) {
+        #[cfg(feature = "memory_tracking")]
+        {
+            let _ = CURRENT_SUBSYSTEM.try_with(|c| c.set(self.previous));
+        }
+    }
+}
+
+/// Attributes every allocation of the current thread to the given subsystem, until the returned
+/// guard is dropped. Scopes could be nested. Does nothing if `memory_tracking` feature is disabled.
+#[allow(unused_variables)]
+pub fn scope(subsystem: MemorySubsystem) -> MemoryScope {
+    #[cfg(feature = "memory_tracking")]
+    {
+        MemoryScope {
+            previous: CURRENT_SUBSYSTEM
+                .try_with(|c| c.replace(subsystem as u8))
+                .unwrap_or(0),
+        }
+    }
+
+    #[cfg(not(feature = "memory_tracking"))]
+    {
+        MemoryScope {}
+    }
+}
+
+/// A future, that attributes every allocation, that is made while polling the inner future, to a
+/// subsystem. See [`tracked`].
+pub struct Tracked<F> {
+    subsystem: MemorySubsystem,
+    future: F,
+}
+
+impl<F: Future> Future for Tracked<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let _scope = scope(self.subsystem);
+        // SAFETY: the inner future is never moved out of the pinned wrapper.
+        unsafe { self.map_unchecked_mut(|this| &mut this.future) }.poll(cx)
+    }
+}
+
+/// Wraps the given future, so every allocation, that is made by the future, is attributed to the
+/// given subsystem. Thread-local [`scope`] cannot be used for async tasks, because a thread of a
+/// thread pool executes many tasks.
+pub fn tracked<F: Future>(subsystem: MemorySubsystem, future: F) -> Tracked<F> {
+    Tracked { subsystem, future }
+}
+
+/// Returns a layout of a memory block with a header, that stores an id of the subsystem, and an
+/// offset of the user data in the block. The header has the size of the alignment of the original
+/// layout, so the user data stays aligned.
+#[cfg(feature = "memory_tracking")]
+fn layout_with_header(layout: std::alloc::Layout) -> Option<(std::alloc::Layout, usize)> {
+    let offset = layout.align();
+    let size = layout.size().checked_add(offset)?;
+    std::alloc::Layout::from_size_align(size, layout.align())
+        .ok()
+        .map(|layout| (layout, offset))
+}
+
+/// Global allocator, that tracks memory usage of every subsystem. It uses the system allocator
+/// to actually allocate memory. See [module docs](self) for more info.
+#[cfg(feature = "memory_tracking")]
+pub struct TrackingAllocator;
+
+#[cfg(feature = "memory_tracking")]
+impl TrackingAllocator {
+    unsafe fn allocate(
+        &self,
+        layout: std::alloc::Layout,
+        allocate: impl FnOnce(std::alloc::Layout) -> *mut u8,
+    ) -> *mut u8 {
+        let Some((full_layout, offset)) = layout_with_header(layout) else {
+            return std::ptr::null_mut();
+        };
+        let ptr = allocate(full_layout);
+        if ptr.is_null() {
+            return ptr;
+        }
+        INSTALLED.store(true, Ordering::Relaxed);
+        let subsystem = CURRENT_SUBSYSTEM.try_with(|c| c.get()).unwrap_or(0);
+        let user_ptr = ptr.add(offset);
+        user_ptr.sub(1).write(subsystem);
+        SUBSYSTEM_COUNTERS[subsystem as usize].allocated(layout.size());
+        TOTAL_COUNTER.allocated(layout.size());
+        user_ptr
+    }
+}
+
+#[cfg(feature = "memory_tracking")]
+unsafe impl std::alloc::GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        self.allocate(layout, |layout| std::alloc::System.alloc(layout))
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: std::alloc::Layout) -> *mut u8 {
+        self.allocate(layout, |layout| std::alloc::System.alloc_zeroed(layout))
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        // The layout is the same as the one that was used to allocate the block, so it is valid.
+        let (full_layout, offset) = layout_with_header(layout).unwrap_unchecked();
+        let subsystem = MemorySubsystem::from_id(ptr.sub(1).read());
+        SUBSYSTEM_COUNTERS[subsystem as usize].freed(layout.size());
+        TOTAL_COUNTER.freed(layout.size());
+        std::alloc::System.dealloc(ptr.sub(offset), full_layout);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_counter() {
+        let counter = Counter::new();
+        counter.allocated(100);
+        counter.allocated(50);
+        counter.freed(100);
+        assert_eq!(
+            counter.usage(),
+            MemoryUsage {
+                current: 50,
+                peak: 150,
+                allocations: 1
+            }
+        );
+
+        counter.reset_peak();
+        assert_eq!(counter.usage().peak, 50);
+    }
+
+    #[cfg(feature = "memory_tracking")]
+    #[test]
+    fn test_tracking_allocator() {
+        use std::alloc::{GlobalAlloc, Layout};
+
+        // The allocator is not installed in tests, so it could be used directly without noise from
+        // other allocations.
+        let layout = Layout::from_size_align(1000, 16).unwrap();
+        let before = subsystem_usage(MemorySubsystem::Renderer);
+        unsafe {
+            let ptr = {
+                let _scope = scope(MemorySubsystem::Renderer);
+                assert_eq!(current_subsystem(), MemorySubsystem::Renderer);
+                TrackingAllocator.alloc(layout)
+            };
+            assert_eq!(current_subsystem(), MemorySubsystem::Other);
+            assert_eq!(ptr as usize % 16, 0);
+            assert_eq!(
+                subsystem_usage(MemorySubsystem::Renderer).current,
+                before.current + 1000
+            );
+
+            // Freed memory is accounted for the subsystem, that allocated it.
+            let _scope = scope(MemorySubsystem::Ui);
+            TrackingAllocator.dealloc(ptr, layout);
+        }
+        assert_eq!(
+            subsystem_usage(MemorySubsystem::Renderer).current,
+            before.current
+        );
+    }
+}
@@ -0,0 +1,442 @@
+//! Seeded procedural noise functions: Perlin, simplex and Worley (cellular) noise in 2D and 3D,
+//! and fractal Brownian motion (fBm) on top of them. See [`Noise`] for a ready-to-use, configurable
+//! noise generator, that could be stored as a property of an object and edited in the editor.
+//!
+//! All the functions are deterministic - the same seed and the same input always produce the same
+//! output, on any platform.
+
+use crate::{
+    algebra::{Vector2, Vector3},
+    reflect::prelude::*,
+    uuid_provider,
+    visitor::prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+#[inline]
+fn hash(seed: u32, x: i32, y: i32, z: i32) -> u32 {
+    let mut h = seed.wrapping_mul(0x27d4_eb2d)
+        ^ (x as u32).wrapping_mul(0x8da6_b343)
+        ^ (y as u32).wrapping_mul(0xd816_3841)
+        ^ (z as u32).wrapping_mul(0xcb1a_b31f);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2c1b_3c6d);
+    h ^= h >> 12;
+    h = h.wrapping_mul(0x297a_2d39);
+    h ^= h >> 15;
+    h
+}
+
+/// Maps a hash to [0; 1) range.
+#[inline]
+fn unit(h: u32) -> f32 {
+    (h >> 8) as f32 / (1u32 << 24) as f32
+}
+
+#[inline]
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+#[inline]
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+#[inline]
+fn grad2(h: u32, x: f32, y: f32) -> f32 {
+    match h & 7 {
+        0 => x + y,
+        1 => -x + y,
+        2 => x - y,
+        3 => -x - y,
+        4 => x,
+        5 => -x,
+        6 => y,
+        _ => -y,
+    }
+}
+
+#[inline]
+fn grad3(h: u32, x: f32, y: f32, z: f32) -> f32 {
+    let h = h & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+/// 2D Perlin noise. Returns a value in [-1; 1] range, the value is zero at integer coordinates.
+pub fn perlin2(seed: u32, x: f32, y: f32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let (fx, fy) = (x - x0, y - y0);
+    let g = |dx: i32, dy: i32| {
+        grad2(
+            hash(seed, ix + dx, iy + dy, 0),
+            fx - dx as f32,
+            fy - dy as f32,
+        )
+    };
+    let (u, v) = (fade(fx), fade(fy));
+    lerp(lerp(g(0, 0), g(1, 0), u), lerp(g(0, 1), g(1, 1), u), v).clamp(-1.0, 1.0)
+}
+
+/// 3D Perlin noise. Returns a value in [-1; 1] range, the value is zero at integer coordinates.
+pub fn perlin3(seed: u32, x: f32, y: f32, z: f32) -> f32 {
+    let (x0, y0, z0) = (x.floor(), y.floor(), z.floor());
+    let (ix, iy, iz) = (x0 as i32, y0 as i32, z0 as i32);
+    let (fx, fy, fz) = (x - x0, y - y0, z - z0);
+    let g = |dx: i32, dy: i32, dz: i32| {
+        grad3(
+            hash(seed, ix + dx, iy + dy, iz + dz),
+            fx - dx as f32,
+            fy - dy as f32,
+            fz - dz as f32,
+        )
+    };
+    let (u, v, w) = (fade(fx), fade(fy), fade(fz));
+    lerp(
+        lerp(
+            lerp(g(0, 0, 0), g(1, 0, 0), u),
+            lerp(g(0, 1, 0), g(1, 1, 0), u),
+            v,
+        ),
+        lerp(
+            lerp(g(0, 0, 1), g(1, 0, 1), u),
+            lerp(g(0, 1, 1), g(1, 1, 1), u),
+            v,
+        ),
+        w,
+    )
+    .clamp(-1.0, 1.0)
+}
+
+/// 2D simplex noise. Returns a value in [-1; 1] range. Simplex noise has fewer directional
+/// artifacts than Perlin noise and it is a bit faster.
+pub fn simplex2(seed: u32, x: f32, y: f32) -> f32 {
+    const F2: f32 = 0.366_025_42; // (sqrt(3) - 1) / 2
+    const G2: f32 = 0.211_324_87; // (3 - sqrt(3)) / 6
+
+    let s = (x + y) * F2;
+    let (i, j) = ((x + s).floor(), (y + s).floor());
+    let t = (i + j) * G2;
+    let (x0, y0) = (x - (i - t), y - (j - t));
+    let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+    let corner = |di: i32, dj: i32, x: f32, y: f32| {
+        let t = 0.5 - x * x - y * y;
+        if t < 0.0 {
+            0.0
+        } else {
+            let t2 = t * t;
+            t2 * t2 * grad2(hash(seed, i as i32 + di, j as i32 + dj, 0), x, y)
+        }
+    };
+
+    let n = corner(0, 0, x0, y0)
+        + corner(i1, j1, x0 - i1 as f32 + G2, y0 - j1 as f32 + G2)
+        + corner(1, 1, x0 - 1.0 + 2.0 * G2, y0 - 1.0 + 2.0 * G2);
+    (70.0 * n).clamp(-1.0, 1.0)
+}
+
+/// 3D simplex noise. Returns a value in [-1; 1] range.
+pub fn simplex3(seed: u32, x: f32, y: f32, z: f32) -> f32 {
+    const F3: f32 = 1.0 / 3.0;
+    const G3: f32 = 1.0 / 6.0;
+
+    let s = (x + y + z) * F3;
+    let (i, j, k) = ((x + s).floor(), (y + s).floor(), (z + s).floor());
+    let t = (i + j + k) * G3;
+    let (x0, y0, z0) = (x - (i - t), y - (j - t), z - (k - t));
+
+    let ((i1, j1, k1), (i2, j2, k2)) = if x0 >= y0 {
+        if y0 >= z0 {
+            ((1, 0, 0), (1, 1, 0))
+        } else if x0 >= z0 {
+            ((1, 0, 0), (1, 0, 1))
+        } else {
+            ((0, 0, 1), (1, 0, 1))
+        }
+    } else if y0 < z0 {
+        ((0, 0, 1), (0, 1, 1))
+    } else if x0 < z0 {
+        ((0, 1, 0), (0, 1, 1))
+    } else {
+        ((0, 1, 0), (1, 1, 0))
+    };
+
+    let corner = |di: i32, dj: i32, dk: i32, g: f32| {
+        let (x, y, z) = (
+            x0 - di as f32 + g * G3,
+            y0 - dj as f32 + g * G3,
+            z0 - dk as f32 + g * G3,
+        );
+        let t = 0.6 - x * x - y * y - z * z;
+        if t < 0.0 {
+            0.0
+        } else {
+            let t2 = t * t;
+            t2 * t2
+                * grad3(
+                    hash(seed, i as i32 + di, j as i32 + dj, k as i32 + dk),
+                    x,
+                    y,
+                    z,
+                )
+        }
+    };
+
+    let n = corner(0, 0, 0, 0.0)
+        + corner(i1, j1, k1, 1.0)
+        + corner(i2, j2, k2, 2.0)
+        + corner(1, 1, 1, 3.0);
+    (32.0 * n).clamp(-1.0, 1.0)
+}
+
+/// 2D Worley (cellular) noise. Returns a distance to the closest feature point, there is one
+/// feature point per unit cell. The value is in [0; 1] range.
+pub fn worley2(seed: u32, x: f32, y: f32) -> f32 {
+    let (ix, iy) = (x.floor() as i32, y.floor() as i32);
+    let mut min_distance_sqr = f32::MAX;
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let (cx, cy) = (ix + dx, iy + dy);
+            let feature = Vector2::new(
+                cx as f32 + unit(hash(seed, cx, cy, 0)),
+                cy as f32 + unit(hash(seed, cx, cy, 1)),
+            );
+            min_distance_sqr = min_distance_sqr.min((feature - Vector2::new(x, y)).norm_squared());
+        }
+    }
+    min_distance_sqr.sqrt().min(1.0)
+}
+
+/// 3D Worley (cellular) noise. Returns a distance to the closest feature point, there is one
+/// feature point per unit cell. The value is in [0; 1] range.
+pub fn worley3(seed: u32, x: f32, y: f32, z: f32) -> f32 {
+    let (ix, iy, iz) = (x.floor() as i32, y.floor() as i32, z.floor() as i32);
+    let mut min_distance_sqr = f32::MAX;
+    for dz in -1..=1 {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let (cx, cy, cz) = (ix + dx, iy + dy, iz + dz);
+                let h = hash(seed, cx, cy, cz);
+                let feature = Vector3::new(
+                    cx as f32 + unit(h),
+                    cy as f32 + unit(hash(h, 1, 0, 0)),
+                    cz as f32 + unit(hash(h, 0, 1, 0)),
+                );
+                min_distance_sqr =
+                    min_distance_sqr.min((feature - Vector3::new(x, y, z)).norm_squared());
+            }
+        }
+    }
+    min_distance_sqr.sqrt().min(1.0)
+}
+
+/// Kind of noise, that is used by [`Noise`].
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Visit,
+    Reflect,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum NoiseKind {
+    /// Classic gradient noise. See [`perlin2`].
+    #[default]
+    Perlin,
+    /// Gradient noise with fewer directional artifacts. See [`simplex2`].
+    Simplex,
+    /// Cellular noise. See [`worley2`].
+    Worley,
+}
+
+uuid_provider!(NoiseKind = "5f0a4b8e-3c59-4d2b-9b7e-2f1d6c8a9e41");
+
+impl NoiseKind {
+    /// Samples the noise of this kind in 2D. Returns a value in [-1; 1] range.
+    pub fn sample2(self, seed: u32, x: f32, y: f32) -> f32 {
+        match self {
+            NoiseKind::Perlin => perlin2(seed, x, y),
+            NoiseKind::Simplex => simplex2(seed, x, y),
+            NoiseKind::Worley => worley2(seed, x, y) * 2.0 - 1.0,
+        }
+    }
+
+    /// Samples the noise of this kind in 3D. Returns a value in [-1; 1] range.
+    pub fn sample3(self, seed: u32, x: f32, y: f32, z: f32) -> f32 {
+        match self {
+            NoiseKind::Perlin => perlin3(seed, x, y, z),
+            NoiseKind::Simplex => simplex3(seed, x, y, z),
+            NoiseKind::Worley => worley3(seed, x, y, z) * 2.0 - 1.0,
+        }
+    }
+}
+
+/// Configurable noise generator. It sums a few layers (octaves) of noise of the same kind, every
+/// next octave has higher frequency and lower amplitude (fractal Brownian motion). A single octave
+/// produces the plain noise.
+///
+/// ```rust
+/// use fyrox_core::{
+///     algebra::Vector2,
+///     noise::{Noise, NoiseKind},
+/// };
+///
+/// let noise = Noise {
+///     kind: NoiseKind::Simplex,
+///     seed: 42,
+///     frequency: 0.1,
+///     octaves: 4,
+///     ..Default::default()
+/// };
+///
+/// let height = noise.sample2(Vector2::new(12.5, 7.0));
+/// assert!((-1.0..=1.0).contains(&height));
+/// ```
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, Serialize, Deserialize)]
+pub struct Noise {
+    /// Kind of the noise.
+    pub kind: NoiseKind,
+    /// Seed of the noise. Different seeds produce different patterns.
+    pub seed: u32,
+    /// Frequency of the first octave. Larger values produce smaller features.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub frequency: f32,
+    /// Amount of octaves. Every octave adds finer details.
+    #[reflect(min_value = 1.0, max_value = 16.0, step = 1.0)]
+    pub octaves: u32,
+    /// Frequency multiplier between successive octaves.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub lacunarity: f32,
+    /// Amplitude multiplier between successive octaves.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub gain: f32,
+}
+
+uuid_provider!(Noise = "b1f3a0d6-8e2c-4f57-a9d4-6c0e5b7f2a18");
+
+impl Default for Noise {
+    fn default() -> Self {
+        Self {
+            kind: Default::default(),
+            seed: 0,
+            frequency: 1.0,
+            octaves: 1,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+}
+
+impl Noise {
+    fn fbm(&self, sample: impl Fn(u32, f32) -> f32) -> f32 {
+        let mut frequency = self.frequency;
+        let mut amplitude = 1.0;
+        let mut sum = 0.0;
+        let mut total_amplitude = 0.0;
+        for octave in 0..self.octaves.max(1) {
+            sum += amplitude * sample(self.seed.wrapping_add(octave), frequency);
+            total_amplitude += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+        if total_amplitude > 0.0 {
+            sum / total_amplitude
+        } else {
+            0.0
+        }
+    }
+
+    /// Samples the noise at the given point. Returns a value in [-1; 1] range.
+    pub fn sample2(&self, point: Vector2<f32>) -> f32 {
+        self.fbm(|seed, frequency| {
+            self.kind
+                .sample2(seed, point.x * frequency, point.y * frequency)
+        })
+    }
+
+    /// Samples the noise at the given point. Returns a value in [-1; 1] range.
+    pub fn sample3(&self, point: Vector3<f32>) -> f32 {
+        self.fbm(|seed, frequency| {
+            self.kind.sample3(
+                seed,
+                point.x * frequency,
+                point.y * frequency,
+                point.z * frequency,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid() -> impl Iterator<Item = (f32, f32)> {
+        (0..64).flat_map(|i| (0..64).map(move |j| (i as f32 * 0.173 - 5.0, j as f32 * 0.291 - 9.0)))
+    }
+
+    #[test]
+    fn test_noise_range_and_determinism() {
+        for kind in [NoiseKind::Perlin, NoiseKind::Simplex, NoiseKind::Worley] {
+            let mut min = f32::MAX;
+            let mut max = f32::MIN;
+            for (x, y) in grid() {
+                let a = kind.sample2(7, x, y);
+                assert_eq!(a, kind.sample2(7, x, y));
+                assert!((-1.0..=1.0).contains(&a));
+                let b = kind.sample3(7, x, y, 0.5 * x);
+                assert!((-1.0..=1.0).contains(&b));
+                min = min.min(a);
+                max = max.max(a);
+            }
+            // The noise must not be degenerate.
+            assert!(max - min > 0.5, "{kind:?}: {min} {max}");
+        }
+    }
+
+    #[test]
+    fn test_seeds() {
+        let differs = grid().any(|(x, y)| simplex2(1, x, y) != simplex2(2, x, y));
+        assert!(differs);
+        assert_eq!(perlin2(1, 3.0, -4.0), 0.0);
+        assert_eq!(perlin3(1, 3.0, -4.0, 5.0), 0.0);
+    }
+
+    #[test]
+    fn test_fbm() {
+        let noise = Noise {
+            seed: 3,
+            frequency: 0.5,
+            ..Default::default()
+        };
+        let point = Vector2::new(1.3, 2.7);
+        assert_eq!(noise.sample2(point), perlin2(3, 0.65, 1.35));
+
+        let fbm = Noise {
+            octaves: 5,
+            ..noise
+        };
+        for (x, y) in grid() {
+            assert!((-1.0..=1.0).contains(&fbm.sample2(Vector2::new(x, y))));
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Debugging facilities of [`super::Pool`]. They help to find out where a dangling handle came from:
+//! when `pool_debug` feature is enabled, every pool record remembers where its object was spawned
+//! and where it was freed, and the panics caused by dangling handles include this information. The
+//! feature is quite expensive (a backtrace is captured on every spawn and free), so it should be
+//! used only for debugging. When the feature is disabled, all the types in this module are
+//! zero-sized and do nothing.
+
+#[cfg(feature = "pool_debug")]
+use std::{backtrace::Backtrace, sync::Arc};
+use std::{
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+};
+
+/// An object, that was freed from a pool record.
+#[cfg(feature = "pool_debug")]
+#[derive(Debug)]
+struct FreedObject {
+    generation: u32,
+    description: String,
+    spawned_at: Option<Arc<Backtrace>>,
+    freed_at: Arc<Backtrace>,
+}
+
+/// Debug information of a single pool record.
+#[derive(Default, Debug)]
+pub(super) struct RecordDebugInfo {
+    #[cfg(feature = "pool_debug")]
+    spawned_at: Option<Arc<Backtrace>>,
+    #[cfg(feature = "pool_debug")]
+    freed: Option<FreedObject>,
+}
+
+impl RecordDebugInfo {
+    #[inline]
+    pub(super) fn on_spawn(&mut self) {
+        #[cfg(feature = "pool_debug")]
+        {
+            self.spawned_at = Some(Arc::new(Backtrace::force_capture()));
+        }
+    }
+
+    #[inline]
+    #[allow(unused_variables)]
+    pub(super) fn on_free(&mut self, generation: u32, description: String) {
+        #[cfg(feature = "pool_debug")]
+        {
+            self.freed = Some(FreedObject {
+                generation,
+                description,
+                spawned_at: self.spawned_at.take(),
+                freed_at: Arc::new(Backtrace::force_capture()),
+            });
+        }
+    }
+
+    /// Creates a description of the object, that was pointed by a dangling handle with the given
+    /// generation.
+    #[cfg(feature = "pool_debug")]
+    pub(super) fn describe_dangling(&self, handle_generation: u32) -> String {
+        match self.freed.as_ref() {
+            Some(freed) if freed.generation == handle_generation => format!(
+                "\nThe handle points to {}, that was spawned at:\n{}\nand freed at:\n{}",
+                freed.description,
+                freed
+                    .spawned_at
+                    .as_ref()
+                    .map_or_else(|| "<unknown>".to_string(), |b| b.to_string()),
+                freed.freed_at
+            ),
+            _ => match self.spawned_at.as_ref() {
+                Some(spawned_at) => format!(
+                    "\nThe handle points to an object, that was freed long ago. The current \
+                    object of the record was spawned at:\n{}",
+                    spawned_at
+                ),
+                None => String::new(),
+            },
+        }
+    }
+
+    #[cfg(not(feature = "pool_debug"))]
+    #[inline]
+    pub(super) fn describe_dangling(&self, _handle_generation: u32) -> String {
+        String::new()
+    }
+}
+
+/// Debug settings of a pool.
+pub(super) struct PoolDebugInfo<T> {
+    #[cfg(feature = "pool_debug")]
+    describer: Option<fn(&T) -> String>,
+    marker: PhantomData<fn(&T) -> String>,
+}
+
+impl<T> Default for PoolDebugInfo<T> {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "pool_debug")]
+            describer: None,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for PoolDebugInfo<T> {
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(feature = "pool_debug")]
+            describer: self.describer,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for PoolDebugInfo<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoolDebugInfo").finish()
+    }
+}
+
+impl<T> PoolDebugInfo<T> {
+    #[inline]
+    #[allow(unused_variables)]
+    pub(super) fn set_describer(&mut self, describer: fn(&T) -> String) {
+        #[cfg(feature = "pool_debug")]
+        {
+            self.describer = Some(describer);
+        }
+    }
+
+    /// Creates a description of the given object.
+    #[cfg(feature = "pool_debug")]
+    pub(super) fn describe(&self, object: &T) -> String {
+        match self.describer {
+            Some(describer) => describer(object),
+            None => format!("an object of {} type", std::any::type_name::<T>()),
+        }
+    }
+
+    #[cfg(not(feature = "pool_debug"))]
+    #[inline]
+    pub(super) fn describe(&self, _object: &T) -> String {
+        String::new()
+    }
+}
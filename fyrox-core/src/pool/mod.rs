@@ -30,10 +30,13 @@ use std::{
     sync::atomic::{self, AtomicIsize},
 };
 
+mod debug;
 pub mod handle;
 pub mod multiborrow;
 pub mod payload;
 
+use debug::{PoolDebugInfo, RecordDebugInfo};
+
 pub use handle::*;
 pub use multiborrow::*;
 pub use payload::*;
@@ -52,6 +55,7 @@ where
 {
     records: Vec<PoolRecord<T, P>>,
     free_stack: Vec<u32>,
+    debug: PoolDebugInfo<T>,
 }
 
 impl<T, P> Reflect for Pool<T, P>
@@ -189,6 +193,8 @@ where
     generation: u32,
     // Actual payload.
     payload: Payload<P>,
+    // Spawn and free sites of the objects of the record (only with `pool_debug` feature).
+    debug: RecordDebugInfo,
 }
 
 impl<T, P> PartialEq for PoolRecord<T, P>
@@ -212,6 +218,7 @@ where
             ref_counter: Default::default(),
             generation: INVALID_GENERATION,
             payload: Payload::new_empty(),
+            debug: Default::default(),
         }
     }
 }
@@ -287,6 +294,7 @@ impl<T: Clone> Clone for PoolRecord<T> {
             ref_counter: Default::default(),
             generation: self.generation,
             payload: self.payload.clone(),
+            debug: Default::default(),
         }
     }
 }
@@ -297,6 +305,7 @@ impl<T: Clone> Clone for Pool<T> {
         Self {
             records: self.records.clone(),
             free_stack: self.free_stack.clone(),
+            debug: self.debug.clone(),
         }
     }
 }
@@ -310,6 +319,7 @@ where
         Pool {
             records: Vec::new(),
             free_stack: Vec::new(),
+            debug: Default::default(),
         }
     }
 
@@ -319,6 +329,7 @@ where
         Pool {
             records: Vec::with_capacity(capacity),
             free_stack: Vec::new(),
+            debug: Default::default(),
         }
     }
 
@@ -407,6 +418,7 @@ where
 
                     record.generation = generation;
                     record.payload = Payload::new(payload);
+                    record.debug.on_spawn();
 
                     Ok(Handle::new(index, generation))
                 }
@@ -418,6 +430,7 @@ where
                         ref_counter: Default::default(),
                         generation: 1,
                         payload: Payload::new_empty(),
+                        debug: Default::default(),
                     });
                     self.free_stack.push(i);
                 }
@@ -428,11 +441,14 @@ where
                     desired_generation
                 };
 
-                self.records.push(PoolRecord {
+                let mut record = PoolRecord {
                     ref_counter: Default::default(),
                     generation,
                     payload: Payload::new(payload),
-                });
+                    debug: Default::default(),
+                };
+                record.debug.on_spawn();
+                self.records.push(record);
 
                 Ok(Handle::new(index, generation))
             }
@@ -467,6 +483,7 @@ where
 
             record.generation = generation;
             record.payload.replace(payload);
+            record.debug.on_spawn();
             handle
         } else {
             // No free records, create new one
@@ -480,11 +497,13 @@ where
 
             let payload = callback(handle);
 
-            let record = PoolRecord {
+            let mut record = PoolRecord {
                 ref_counter: Default::default(),
                 generation,
                 payload: Payload::new(payload),
+                debug: Default::default(),
             };
+            record.debug.on_spawn();
 
             self.records.push(record);
 
@@ -523,6 +542,7 @@ where
 
             record.generation = generation;
             record.payload.replace(payload);
+            record.debug.on_spawn();
             handle
         } else {
             // No free records, create new one
@@ -536,11 +556,13 @@ where
 
             let payload = callback(handle).await;
 
-            let record = PoolRecord {
+            let mut record = PoolRecord {
                 generation,
                 ref_counter: Default::default(),
                 payload: Payload::new(payload),
+                debug: Default::default(),
             };
+            record.debug.on_spawn();
 
             self.records.push(record);
 
@@ -585,12 +607,18 @@ where
                 if let Some(payload) = record.payload.as_ref() {
                     payload
                 } else {
-                    panic!("Attempt to borrow destroyed object at {:?} handle.", handle);
+                    panic!(
+                        "Attempt to borrow destroyed object at {:?} handle.{}",
+                        handle,
+                        record.debug.describe_dangling(handle.generation)
+                    );
                 }
             } else {
                 panic!(
-                    "Attempt to use dangling handle {:?}. Record has generation {}!",
-                    handle, record.generation
+                    "Attempt to use dangling handle {:?}. Record has generation {}!{}",
+                    handle,
+                    record.generation,
+                    record.debug.describe_dangling(handle.generation)
                 );
             }
         } else {
@@ -628,10 +656,19 @@ where
                 if let Some(payload) = record.payload.as_mut() {
                     payload
                 } else {
-                    panic!("Attempt to borrow destroyed object at {:?} handle.", handle);
+                    panic!(
+                        "Attempt to borrow destroyed object at {:?} handle.{}",
+                        handle,
+                        record.debug.describe_dangling(handle.generation)
+                    );
                 }
             } else {
-                panic!("Attempt to borrow object using dangling handle {:?}. Record has {} generation!", handle, record.generation);
+                panic!(
+                    "Attempt to borrow object using dangling handle {:?}. Record has {} generation!{}",
+                    handle,
+                    record.generation,
+                    record.debug.describe_dangling(handle.generation)
+                );
             }
         } else {
             panic!(
@@ -830,14 +867,23 @@ where
                 self.free_stack.push(handle.index);
                 // Return current payload.
                 if let Some(payload) = record.payload.take() {
+                    record
+                        .debug
+                        .on_free(handle.generation, self.debug.describe(&payload));
                     payload
                 } else {
-                    panic!("Attempt to double free object at handle {:?}!", handle);
+                    panic!(
+                        "Attempt to double free object at handle {:?}!{}",
+                        handle,
+                        record.debug.describe_dangling(handle.generation)
+                    );
                 }
             } else {
                 panic!(
-                    "Attempt to free object using dangling handle {:?}! Record generation is {}",
-                    handle, record.generation
+                    "Attempt to free object using dangling handle {:?}! Record generation is {}{}",
+                    handle,
+                    record.generation,
+                    record.debug.describe_dangling(handle.generation)
                 );
             }
         } else {
@@ -854,6 +900,9 @@ where
         self.records.get_mut(index).and_then(|record| {
             if record.generation == handle.generation {
                 if let Some(payload) = record.payload.take() {
+                    record
+                        .debug
+                        .on_free(handle.generation, self.debug.describe(&payload));
                     self.free_stack.push(handle.index);
                     Some(payload)
                 } else {
@@ -903,8 +952,10 @@ where
                 }
             } else {
                 panic!(
-                    "Attempt to take object using dangling handle {:?}! Record generation is {}",
-                    handle, record.generation
+                    "Attempt to take object using dangling handle {:?}! Record generation is {}{}",
+                    handle,
+                    record.generation,
+                    record.debug.describe_dangling(handle.generation)
                 );
             }
         } else {
@@ -961,6 +1012,15 @@ where
         std::mem::forget(ticket);
     }
 
+    /// Sets a function, that is used to describe objects in the panic messages caused by dangling
+    /// handles (for example, to show the name and the actual type of an object). It is used only if
+    /// `pool_debug` feature is enabled, the feature also makes every record remember where its
+    /// objects were spawned and freed.
+    #[inline]
+    pub fn set_debug_describer(&mut self, describer: fn(&T) -> String) {
+        self.debug.set_describer(describer);
+    }
+
     /// Returns total capacity of pool. Capacity has nothing about real amount of objects in pool!
     #[inline]
     #[must_use]
@@ -1190,7 +1250,11 @@ where
 
             if !retain {
                 self.free_stack.push(i as u32);
-                record.payload.take(); // and Drop
+                if let Some(payload) = record.payload.take() {
+                    record
+                        .debug
+                        .on_free(record.generation, self.debug.describe(&payload));
+                } // and Drop
             }
         }
     }
@@ -1879,4 +1943,15 @@ mod test {
         assert_eq!(pool[h3], 3);
         assert_eq!(pool[h4], 4);
     }
+
+    #[cfg(feature = "pool_debug")]
+    #[test]
+    #[should_panic(expected = "The handle points to value 123, that was spawned at")]
+    fn test_pool_debug_use_after_free() {
+        let mut pool = Pool::<u32>::new();
+        pool.set_debug_describer(|value| format!("value {}", value));
+        let handle = pool.spawn(123);
+        pool.free(handle);
+        let _ = pool.borrow(handle);
+    }
 }
@@ -1,15 +1,26 @@
 //! Built-in scoped profiler. You must compile with feature "enable_profiler" to
 //! force profiler gather info! It is disabled by default because it is not cheap
 //! and takes 3-5% of performance for internal needs.
+//!
+//! Scopes are added by [`scope_profile`] macro. The macro without arguments uses the name of the
+//! enclosing function, `scope_profile!("Name")` creates a named scope, which is handy for user scripts
+//! and for separate parts of large functions.
+//!
+//! Besides accumulated statistics, the profiler could capture every frame separately (see
+//! [`set_capture_enabled`]). A frame is the time between two consecutive calls of [`next_frame`], only
+//! the scopes of the thread, that calls [`next_frame`], are captured. Captured frames could be fetched
+//! by [`captured_frames`] and visualized as a flame graph.
 
 #![allow(dead_code)]
 
 use fxhash::{FxHashMap, FxHashSet, FxHasher};
 use std::{
+    collections::VecDeque,
     fmt,
     fmt::Write,
     hash::{Hash, Hasher},
     sync::{Arc, Mutex},
+    thread::ThreadId,
 };
 
 pub fn print() -> Result<String, fmt::Error> {
@@ -40,6 +51,176 @@ pub fn print_hot_path() -> Result<String, fmt::Error> {
     }
 }
 
+/// Returns `true` if the profiler was compiled in (`enable_profiler` feature is enabled).
+pub fn is_available() -> bool {
+    cfg!(feature = "enable_profiler")
+}
+
+/// Maximum amount of frames, that is kept by the profiler when frame capturing is enabled. Older
+/// frames are discarded.
+pub const MAX_CAPTURED_FRAMES: usize = 300;
+
+/// A scope, that was executed during a captured frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapturedScope {
+    /// Name of the scope. It is either a name of a function or a custom name.
+    pub name: &'static str,
+    /// Line number, at which the scope was defined.
+    pub line: u32,
+    /// Nesting depth of the scope, top-level scopes have zero depth.
+    pub depth: usize,
+    /// Time (in seconds) from the beginning of the frame to the beginning of the scope.
+    pub start: f64,
+    /// Duration of the scope in seconds.
+    pub duration: f64,
+}
+
+/// A frame with all the scopes, that were executed during the frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CapturedFrame {
+    /// Sequential number of the frame.
+    pub index: u64,
+    /// Duration of the frame in seconds.
+    pub duration: f64,
+    /// Scopes of the frame in the order of their completion (children go before their parents).
+    pub scopes: Vec<CapturedScope>,
+}
+
+struct FrameCapture {
+    enabled: bool,
+    thread: Option<ThreadId>,
+    frame_index: u64,
+    frame_start: std::time::Instant,
+    depth: usize,
+    scopes: Vec<CapturedScope>,
+    frames: VecDeque<CapturedFrame>,
+}
+
+impl Default for FrameCapture {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            thread: None,
+            frame_index: 0,
+            frame_start: std::time::Instant::now(),
+            depth: 0,
+            scopes: Default::default(),
+            frames: Default::default(),
+        }
+    }
+}
+
+impl FrameCapture {
+    fn is_capturing_current_thread(&self) -> bool {
+        self.enabled && self.thread == Some(std::thread::current().id())
+    }
+
+    fn next_frame(&mut self) {
+        let now = std::time::Instant::now();
+        if self.enabled && self.thread.is_some() {
+            if self.frames.len() >= MAX_CAPTURED_FRAMES {
+                self.frames.pop_front();
+            }
+            self.frames.push_back(CapturedFrame {
+                index: self.frame_index,
+                duration: (now - self.frame_start).as_secs_f64(),
+                scopes: std::mem::take(&mut self.scopes),
+            });
+        }
+        self.scopes.clear();
+        self.frame_index += 1;
+        self.frame_start = now;
+        self.thread = Some(std::thread::current().id());
+    }
+
+    fn enter_scope(&mut self) -> Option<usize> {
+        if self.is_capturing_current_thread() {
+            let depth = self.depth;
+            self.depth += 1;
+            Some(depth)
+        } else {
+            None
+        }
+    }
+
+    fn leave_scope(
+        &mut self,
+        scope: &ScopeMark,
+        depth: usize,
+        start_time: std::time::Instant,
+        elapsed: f64,
+    ) {
+        self.depth = depth;
+        if self.is_capturing_current_thread() {
+            self.scopes.push(CapturedScope {
+                name: scope.function_name,
+                line: scope.line,
+                depth,
+                start: start_time
+                    .saturating_duration_since(self.frame_start)
+                    .as_secs_f64(),
+                duration: elapsed,
+            });
+        }
+    }
+}
+
+/// Enables or disables capturing of separate frames. Capturing does nothing if the profiler is not
+/// available (see [`is_available`]).
+pub fn set_capture_enabled(#[allow(unused_variables)] enabled: bool) {
+    #[cfg(feature = "enable_profiler")]
+    {
+        let mut capture = FRAME_CAPTURE.lock().unwrap();
+        capture.enabled = enabled;
+        capture.depth = 0;
+        capture.scopes.clear();
+    }
+}
+
+/// Returns `true` if the profiler captures separate frames.
+pub fn is_capture_enabled() -> bool {
+    #[cfg(feature = "enable_profiler")]
+    {
+        FRAME_CAPTURE.lock().unwrap().enabled
+    }
+
+    #[cfg(not(feature = "enable_profiler"))]
+    {
+        false
+    }
+}
+
+/// Finishes current frame and starts a new one. The engine calls it at the beginning of every update.
+pub fn next_frame() {
+    #[cfg(feature = "enable_profiler")]
+    FRAME_CAPTURE.lock().unwrap().next_frame();
+}
+
+/// Returns a copy of the captured frames, the oldest frame goes first.
+pub fn captured_frames() -> Vec<CapturedFrame> {
+    #[cfg(feature = "enable_profiler")]
+    {
+        FRAME_CAPTURE
+            .lock()
+            .unwrap()
+            .frames
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    #[cfg(not(feature = "enable_profiler"))]
+    {
+        Vec::new()
+    }
+}
+
+/// Removes all the captured frames.
+pub fn clear_captured_frames() {
+    #[cfg(feature = "enable_profiler")]
+    FRAME_CAPTURE.lock().unwrap().frames.clear();
+}
+
 struct Sample {
     count: u64,
     time: f64,
@@ -231,11 +412,13 @@ impl Profiler {
 
 lazy_static! {
     static ref PROFILER: Arc<Mutex<Profiler>> = Arc::new(Mutex::new(Profiler::default()));
+    static ref FRAME_CAPTURE: Mutex<FrameCapture> = Mutex::new(FrameCapture::default());
 }
 
 pub struct ScopeDefinition {
     scope: ScopeMark,
     start_time: std::time::Instant,
+    capture_depth: Option<usize>,
 }
 
 impl ScopeDefinition {
@@ -249,10 +432,12 @@ impl ScopeDefinition {
         };
 
         PROFILER.lock().unwrap().enter_scope(&mut scope);
+        let capture_depth = FRAME_CAPTURE.lock().unwrap().enter_scope();
 
         Self {
             scope,
             start_time: std::time::Instant::now(),
+            capture_depth,
         }
     }
 
@@ -266,6 +451,12 @@ impl Drop for ScopeDefinition {
     fn drop(&mut self) {
         let elapsed = self.elapsed();
         PROFILER.lock().unwrap().leave_scope(self.scope, elapsed);
+        if let Some(depth) = self.capture_depth {
+            FRAME_CAPTURE
+                .lock()
+                .unwrap()
+                .leave_scope(&self.scope, depth, self.start_time, elapsed);
+        }
     }
 }
 
@@ -284,12 +475,16 @@ macro_rules! scope_profile {
         };
         let _scope_guard = $crate::profiler::ScopeDefinition::new(function_name, line!());
     };
+    ($name:expr) => {
+        let _scope_guard = $crate::profiler::ScopeDefinition::new($name, line!());
+    };
 }
 
 #[cfg(not(feature = "enable_profiler"))]
 #[macro_export]
 macro_rules! scope_profile {
     () => {};
+    ($name:expr) => {};
 }
 
 #[cfg(test)]
@@ -433,6 +628,51 @@ Entry Point
 ========================================================================================================="#) );
     }
 
+    #[test]
+    fn frame_capture() {
+        let mut capture = FrameCapture {
+            enabled: true,
+            ..Default::default()
+        };
+        // The first call only starts a frame.
+        capture.next_frame();
+        assert!(capture.frames.is_empty());
+
+        let outer = ScopeMark {
+            parent_scope_hash: 0,
+            function_name: "outer",
+            line: 1,
+        };
+        let inner = ScopeMark {
+            parent_scope_hash: 0,
+            function_name: "inner",
+            line: 2,
+        };
+        let start_time = std::time::Instant::now();
+        let outer_depth = capture.enter_scope().unwrap();
+        let inner_depth = capture.enter_scope().unwrap();
+        capture.leave_scope(&inner, inner_depth, start_time, 1.0);
+        capture.leave_scope(&outer, outer_depth, start_time, 2.0);
+
+        // Scopes of other threads are ignored.
+        std::thread::scope(|s| {
+            s.spawn(|| assert_eq!(capture.enter_scope(), None));
+        });
+
+        capture.next_frame();
+        assert_eq!(capture.frames.len(), 1);
+        let frame = &capture.frames[0];
+        assert_eq!(
+            frame
+                .scopes
+                .iter()
+                .map(|s| (s.name, s.depth, s.duration))
+                .collect::<Vec<_>>(),
+            vec![("inner", 1, 1.0), ("outer", 0, 2.0)]
+        );
+        assert!(capture.scopes.is_empty());
+    }
+
     #[test]
     fn test_type_name_of() {
         assert_eq!(type_name_of(42), "i32");
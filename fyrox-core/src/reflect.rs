@@ -3,6 +3,7 @@
 mod external_impls;
 mod std_impls;
 
+use crate::visitor::{Visit, VisitResult, Visitor};
 pub use fyrox_core_derive::Reflect;
 use std::{
     any::{Any, TypeId},
@@ -12,8 +13,8 @@ use std::{
 
 pub mod prelude {
     pub use super::{
-        FieldInfo, Reflect, ReflectArray, ReflectHashMap, ReflectInheritableVariable, ReflectList,
-        ResolvePath, SetFieldByPathError,
+        FieldInfo, PropertyUnit, Reflect, ReflectArray, ReflectHashMap, ReflectInheritableVariable,
+        ReflectList, ResolvePath, SetFieldByPathError,
     };
 }
 
@@ -90,6 +91,94 @@ pub struct FieldInfo<'a, 'b> {
 
     /// Maximum amount of decimal places for a numeric property.
     pub precision: Option<usize>,
+
+    /// A unit of a numeric property. Editors use it to show a unit suffix and to convert values
+    /// typed in other units (for example `cm` for a property in meters).
+    pub unit: Option<PropertyUnit>,
+}
+
+/// A unit of a numeric property. It is used by the editors to show the unit suffix next to the
+/// value, to pick a reasonable drag step and to convert values typed in other units.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash, Visit, Reflect)]
+pub enum PropertyUnit {
+    /// Distance in meters. Accepts `mm`, `cm`, `dm`, `m` and `km` suffixes.
+    #[default]
+    Meters,
+    /// Angle in degrees. Accepts `deg`, `°` and `rad` suffixes.
+    Degrees,
+    /// Time in seconds. Accepts `ms`, `s`, `sec`, `min` and `h` suffixes.
+    Seconds,
+    /// Percentage. Accepts `%` suffix.
+    Percent,
+}
+
+impl PropertyUnit {
+    /// Returns a suffix, that is shown next to values in this unit.
+    pub fn suffix(self) -> &'static str {
+        match self {
+            PropertyUnit::Meters => "m",
+            PropertyUnit::Degrees => "°",
+            PropertyUnit::Seconds => "s",
+            PropertyUnit::Percent => "%",
+        }
+    }
+
+    /// Returns a default step for values in this unit. It is used when a property does not specify
+    /// its own step.
+    pub fn default_step(self) -> f64 {
+        match self {
+            PropertyUnit::Meters | PropertyUnit::Seconds => 0.01,
+            PropertyUnit::Degrees | PropertyUnit::Percent => 1.0,
+        }
+    }
+
+    /// Returns a multiplier, that converts a value with the given suffix to this unit. Empty suffix
+    /// means that the value is already in this unit. Returns `None` if the suffix is unknown.
+    pub fn conversion_factor(self, suffix: &str) -> Option<f64> {
+        let suffix = suffix.trim().to_lowercase();
+        if suffix.is_empty() {
+            return Some(1.0);
+        }
+        match self {
+            PropertyUnit::Meters => match suffix.as_str() {
+                "mm" => Some(0.001),
+                "cm" => Some(0.01),
+                "dm" => Some(0.1),
+                "m" => Some(1.0),
+                "km" => Some(1000.0),
+                _ => None,
+            },
+            PropertyUnit::Degrees => match suffix.as_str() {
+                "°" | "deg" => Some(1.0),
+                "rad" => Some(180.0 / std::f64::consts::PI),
+                _ => None,
+            },
+            PropertyUnit::Seconds => match suffix.as_str() {
+                "ms" => Some(0.001),
+                "s" | "sec" => Some(1.0),
+                "min" => Some(60.0),
+                "h" => Some(3600.0),
+                _ => None,
+            },
+            PropertyUnit::Percent => match suffix.as_str() {
+                "%" => Some(1.0),
+                _ => None,
+            },
+        }
+    }
+
+    /// Parses a text with an optional unit suffix (for example `25cm` or `1.5 rad`) and converts
+    /// the value to this unit. Returns `None` if the number or the suffix cannot be parsed.
+    pub fn parse(self, text: &str) -> Option<f64> {
+        let text = text.trim();
+        if let Ok(value) = text.parse::<f64>() {
+            return Some(value);
+        }
+        let number = text.trim_end_matches(|c: char| c.is_alphabetic() || c == '%' || c == '°');
+        let suffix = &text[number.len()..];
+        let value = number.trim().parse::<f64>().ok()?;
+        Some(value * self.conversion_factor(suffix)?)
+    }
 }
 
 impl<'a, 'b> FieldInfo<'a, 'b> {
@@ -118,6 +207,7 @@ impl<'a, 'b> fmt::Debug for FieldInfo<'a, 'b> {
             .field("max_value", &self.max_value)
             .field("step", &self.step)
             .field("precision", &self.precision)
+            .field("unit", &self.unit)
             .field("description", &self.description)
             .finish()
     }
@@ -137,6 +227,7 @@ impl<'a, 'b> PartialEq<Self> for FieldInfo<'a, 'b> {
             && self.max_value == other.max_value
             && self.step == other.step
             && self.precision == other.precision
+            && self.unit == other.unit
             && self.description == other.description
     }
 }
@@ -1370,4 +1461,21 @@ mod test {
         assert_eq!(names[8], "hash_map[Foobar]");
         assert_eq!(names[9], "hash_map[Foobar].payload");
     }
+
+    #[test]
+    fn property_unit_parse() {
+        assert_eq!(PropertyUnit::Meters.parse("1.5"), Some(1.5));
+        assert_eq!(PropertyUnit::Meters.parse("25cm"), Some(0.25));
+        assert_eq!(PropertyUnit::Meters.parse("2 km"), Some(2000.0));
+        assert_eq!(PropertyUnit::Degrees.parse("90°"), Some(90.0));
+        assert_eq!(PropertyUnit::Degrees.parse("45 deg"), Some(45.0));
+        assert_eq!(
+            PropertyUnit::Degrees.parse("3.141592653589793rad"),
+            Some(180.0)
+        );
+        assert_eq!(PropertyUnit::Seconds.parse("250ms"), Some(0.25));
+        assert_eq!(PropertyUnit::Percent.parse("50%"), Some(50.0));
+        assert_eq!(PropertyUnit::Meters.parse("10deg"), None);
+        assert_eq!(PropertyUnit::Meters.parse("abc"), None);
+    }
 }
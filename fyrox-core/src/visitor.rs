@@ -28,7 +28,7 @@ use crate::{
 use base64::Engine;
 use bitflags::bitflags;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 use std::any::TypeId;
 use std::error::Error;
 use std::{
@@ -574,7 +574,7 @@ where
 /// must have a unique name.
 pub struct Field {
     /// The key string that allows access to the field.
-    name: String,
+    name: Rc<str>,
     /// The data stored in the visitor for this field.
     kind: FieldKind,
 }
@@ -731,10 +731,34 @@ impl_visitable_elementary_field!(i32, write_i32, read_i32, LittleEndian);
 impl_visitable_elementary_field!(u64, write_u64, read_u64, LittleEndian);
 impl_visitable_elementary_field!(i64, write_i64, read_i64, LittleEndian);
 
+/// Stores unique names of nodes and fields while loading, so every distinct name is allocated only
+/// once, no matter how many times it is used in the data.
+#[derive(Default)]
+struct NameInterner {
+    names: FxHashSet<Rc<str>>,
+    buffer: Vec<u8>,
+}
+
+impl NameInterner {
+    fn read(&mut self, file: &mut dyn Read) -> Result<Rc<str>, VisitError> {
+        let len = file.read_u32::<LittleEndian>()? as usize;
+        self.buffer.resize(len, 0);
+        file.read_exact(&mut self.buffer)?;
+        let name = std::str::from_utf8(&self.buffer).map_err(|_| VisitError::InvalidName)?;
+        if let Some(name) = self.names.get(name) {
+            Ok(name.clone())
+        } else {
+            let name = Rc::<str>::from(name);
+            self.names.insert(name.clone());
+            Ok(name)
+        }
+    }
+}
+
 impl Field {
     pub fn new(name: &str, kind: FieldKind) -> Self {
         Self {
-            name: name.to_owned(),
+            name: Rc::from(name),
             kind,
         }
     }
@@ -955,7 +979,7 @@ impl Field {
         Ok(())
     }
 
-    fn load(file: &mut dyn Read) -> Result<Field, VisitError> {
+    fn load(file: &mut dyn Read, names: &mut NameInterner) -> Result<Field, VisitError> {
         fn read_vec_n<T, S, const N: usize>(
             file: &mut dyn Read,
         ) -> Result<Matrix<T, Const<N>, U1, S>, VisitError>
@@ -970,13 +994,11 @@ impl Field {
             Ok(vec)
         }
 
-        let name_len = file.read_u32::<LittleEndian>()? as usize;
-        let mut raw_name = vec![Default::default(); name_len];
-        file.read_exact(raw_name.as_mut_slice())?;
+        let name = names.read(file)?;
         let id = file.read_u8()?;
-        Ok(Field::new(
-            String::from_utf8(raw_name)?.as_str(),
-            match id {
+        Ok(Field {
+            name,
+            kind: match id {
                 1 => FieldKind::U8(file.read_u8()?),
                 2 => FieldKind::I8(file.read_i8()?),
                 3 => FieldKind::U16(file.read_u16::<LittleEndian>()?),
@@ -1100,7 +1122,7 @@ impl Field {
 
                 _ => return Err(VisitError::UnknownFieldType(id)),
             },
-        ))
+        })
     }
 
     fn as_string(&self) -> String {
@@ -1112,19 +1134,27 @@ impl Field {
 /// that allows a [Visitor] to store its data.
 /// Each node has a name, and may have a parent node and child nodes.
 pub struct VisitorNode {
-    name: String,
+    name: Rc<str>,
     fields: Vec<Field>,
     parent: Handle<VisitorNode>,
     children: Vec<Handle<VisitorNode>>,
+    /// Maps names of the children to their handles. It is filled only for nodes with at least
+    /// [`CHILD_INDEX_THRESHOLD`] children (collections, for example), linear search is faster for
+    /// the rest.
+    child_index: FxHashMap<Rc<str>, Handle<VisitorNode>>,
 }
 
+/// Minimal amount of children of a node to search its children by name using a hash map.
+const CHILD_INDEX_THRESHOLD: usize = 16;
+
 impl VisitorNode {
     fn new(name: &str, parent: Handle<VisitorNode>) -> Self {
         Self {
-            name: name.to_owned(),
+            name: Rc::from(name),
             fields: Vec::new(),
             parent,
             children: Vec::new(),
+            child_index: Default::default(),
         }
     }
 }
@@ -1132,10 +1162,11 @@ impl VisitorNode {
 impl Default for VisitorNode {
     fn default() -> Self {
         Self {
-            name: String::new(),
+            name: Rc::from(""),
             fields: Vec::new(),
             parent: Handle::NONE,
             children: Vec::new(),
+            child_index: Default::default(),
         }
     }
 }
@@ -1321,7 +1352,7 @@ impl Visitor {
             .borrow_mut(self.current_node)
             .fields
             .iter_mut()
-            .find(|field| field.name == name)
+            .find(|field| &*field.name == name)
     }
 
     /// True if this Visitor is changing the values that it visits.
@@ -1339,6 +1370,47 @@ impl Visitor {
         self.nodes.borrow_mut(self.current_node)
     }
 
+    fn find_child(&self, parent: Handle<VisitorNode>, name: &str) -> Option<Handle<VisitorNode>> {
+        let node = self.nodes.borrow(parent);
+        if node.children.len() >= CHILD_INDEX_THRESHOLD {
+            node.child_index.get(name).cloned()
+        } else {
+            node.children
+                .iter()
+                .find(|child| &*self.nodes.borrow(**child).name == name)
+                .cloned()
+        }
+    }
+
+    fn add_child(&mut self, parent: Handle<VisitorNode>, child: Handle<VisitorNode>) {
+        self.nodes.borrow_mut(parent).children.push(child);
+        let node = self.nodes.borrow(parent);
+        if node.children.len() == CHILD_INDEX_THRESHOLD {
+            self.build_child_index(parent);
+        } else if node.children.len() > CHILD_INDEX_THRESHOLD {
+            let name = self.nodes.borrow(child).name.clone();
+            self.nodes
+                .borrow_mut(parent)
+                .child_index
+                .entry(name)
+                .or_insert(child);
+        }
+    }
+
+    fn build_child_index(&mut self, parent: Handle<VisitorNode>) {
+        let node = self.nodes.borrow(parent);
+        if node.children.len() < CHILD_INDEX_THRESHOLD {
+            return;
+        }
+        let mut child_index = FxHashMap::default();
+        for child in node.children.iter() {
+            child_index
+                .entry(self.nodes.borrow(*child).name.clone())
+                .or_insert(*child);
+        }
+        self.nodes.borrow_mut(parent).child_index = child_index;
+    }
+
     /// If [Visitor::is_reading], find a node with the given name that is a child
     /// of the current node, and return a Visitor for the found node. Return an error
     /// if no node with that name exists.
@@ -1347,17 +1419,9 @@ impl Visitor {
     /// node, and return a visitor for the new node. Return an error if a node with
     /// that name already exists.
     pub fn enter_region(&mut self, name: &str) -> Result<RegionGuard, VisitError> {
-        let node = self.nodes.borrow(self.current_node);
+        let region = self.find_child(self.current_node, name);
         if self.reading {
-            let mut region = Handle::NONE;
-            for child_handle in node.children.iter() {
-                let child = self.nodes.borrow(*child_handle);
-                if child.name == name {
-                    region = *child_handle;
-                    break;
-                }
-            }
-            if region.is_some() {
+            if let Some(region) = region {
                 self.current_node = region;
                 Ok(RegionGuard(self))
             } else {
@@ -1365,18 +1429,12 @@ impl Visitor {
             }
         } else {
             // Make sure that node does not exists already.
-            for child_handle in node.children.iter() {
-                let child = self.nodes.borrow(*child_handle);
-                if child.name == name {
-                    return Err(VisitError::RegionAlreadyExists(name.to_owned()));
-                }
+            if region.is_some() {
+                return Err(VisitError::RegionAlreadyExists(name.to_owned()));
             }
 
             let node_handle = self.nodes.spawn(VisitorNode::new(name, self.current_node));
-            self.nodes
-                .borrow_mut(self.current_node)
-                .children
-                .push(node_handle);
+            self.add_child(self.current_node, node_handle);
             self.current_node = node_handle;
 
             Ok(RegionGuard(self))
@@ -1387,9 +1445,7 @@ impl Visitor {
     /// This should never be None if the Visitor is operating normally,
     /// because there should be no way to leave the initial `__ROOT__` region.
     pub fn current_region(&self) -> Option<&str> {
-        self.nodes
-            .try_borrow(self.current_node)
-            .map(|n| n.name.as_str())
+        self.nodes.try_borrow(self.current_node).map(|n| &*n.name)
     }
 
     fn leave_region(&mut self) -> VisitResult {
@@ -1477,26 +1533,27 @@ impl Visitor {
         self.save_binary_to_memory(writer)
     }
 
-    fn load_node_binary(&mut self, file: &mut dyn Read) -> Result<Handle<VisitorNode>, VisitError> {
-        let name_len = file.read_u32::<LittleEndian>()? as usize;
-        let mut raw_name = vec![Default::default(); name_len];
-        file.read_exact(raw_name.as_mut_slice())?;
-
+    fn load_node_binary(
+        &mut self,
+        file: &mut dyn Read,
+        names: &mut NameInterner,
+    ) -> Result<Handle<VisitorNode>, VisitError> {
         let mut node = VisitorNode {
-            name: String::from_utf8(raw_name)?,
+            name: names.read(file)?,
             ..VisitorNode::default()
         };
 
         let field_count = file.read_u32::<LittleEndian>()? as usize;
+        node.fields.reserve(field_count);
         for _ in 0..field_count {
-            let field = Field::load(file)?;
+            let field = Field::load(file, names)?;
             node.fields.push(field);
         }
 
         let child_count = file.read_u32::<LittleEndian>()? as usize;
         let mut children = Vec::with_capacity(child_count);
         for _ in 0..child_count {
-            children.push(self.load_node_binary(file)?);
+            children.push(self.load_node_binary(file, names)?);
         }
 
         node.children.clone_from(&children);
@@ -1506,6 +1563,7 @@ impl Visitor {
             let child = self.nodes.borrow_mut(*child_handle);
             child.parent = handle;
         }
+        self.build_child_index(handle);
 
         Ok(handle)
     }
@@ -1517,6 +1575,22 @@ impl Visitor {
         Self::load_from_memory(&io::load_file(path).await?)
     }
 
+    /// Create a visitor by reading data from the file at the given path using memory mapping,
+    /// assuming that the file was created using [Visitor::save_binary]. It is faster than
+    /// [Visitor::load_binary] for large files, because the file is not copied into memory, but it
+    /// works only with files on the file system (not in Android assets, for example).
+    /// Return a [VisitError::NotSupportedFormat] if [Visitor::MAGIC] is not the first bytes read from the file.
+    ///
+    /// The file must not be modified by other processes while it is being loaded.
+    #[cfg(all(feature = "mmap", not(target_arch = "wasm32")))]
+    pub fn load_binary_mapped<P: AsRef<Path>>(path: P) -> Result<Self, VisitError> {
+        let file = File::open(path)?;
+        // SAFETY: The mapping is used only while the data is being decoded and the file is not
+        // expected to be modified in the meantime (see the docs above).
+        let data = unsafe { memmap2::Mmap::map(&file)? };
+        Self::load_from_memory(&data)
+    }
+
     /// Create a visitor by decoding data from the given byte slice,
     /// assuming that the bytes are in the format that would be produced
     /// by [Visitor::save_binary_to_vec].
@@ -1538,7 +1612,7 @@ impl Visitor {
             blackboard: Blackboard::new(),
             flags: VisitorFlags::NONE,
         };
-        visitor.root = visitor.load_node_binary(&mut reader)?;
+        visitor.root = visitor.load_node_binary(&mut reader, &mut NameInterner::default())?;
         visitor.current_node = visitor.root;
         Ok(visitor)
     }
@@ -2201,6 +2275,36 @@ mod test {
         }
     }
 
+    #[test]
+    fn visitor_large_collection() {
+        let mut items = (0..CHILD_INDEX_THRESHOLD as u32 * 10)
+            .map(Some)
+            .collect::<Vec<_>>();
+
+        let mut visitor = Visitor::new();
+        items.visit("Items", &mut visitor).unwrap();
+        assert!(matches!(
+            items.visit("Items", &mut visitor),
+            Err(VisitError::RegionAlreadyExists(_))
+        ));
+        {
+            let mut region = visitor.enter_region("Other").unwrap();
+            for i in 0..CHILD_INDEX_THRESHOLD * 2 {
+                let _ = region.enter_region(&format!("Item{i}")).unwrap();
+            }
+            assert!(matches!(
+                region.enter_region("Item20"),
+                Err(VisitError::RegionAlreadyExists(_))
+            ));
+        }
+
+        let data = visitor.save_binary_to_vec().unwrap();
+        let mut visitor = Visitor::load_from_memory(&data).unwrap();
+        let mut loaded = Vec::<Option<u32>>::new();
+        loaded.visit("Items", &mut visitor).unwrap();
+        assert_eq!(loaded, items);
+    }
+
     #[test]
     fn pod_vec_view_from_pod_vec() {
         // Pod for u8
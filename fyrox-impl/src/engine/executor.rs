@@ -1,5 +1,7 @@
 //! Executor is a small wrapper that manages plugins and scripts for your game.
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::engine::inspection::{InspectionAccess, InspectionServer};
 use crate::{
     asset::manager::ResourceManager,
     core::{
@@ -14,6 +16,7 @@ use crate::{
     event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget},
     gui::constructor::WidgetConstructorContainer,
     plugin::Plugin,
+    renderer::QualitySettings,
     utils::translate_event,
     window::WindowAttributes,
 };
@@ -23,11 +26,35 @@ use std::{
     sync::Arc,
 };
 
+/// Name of the IndexedDB database, that is used to cache the assets of the game on WebAssembly.
+#[cfg(target_arch = "wasm32")]
+pub const WEB_CACHE_DATABASE: &str = "fyrox-assets";
+
+/// Name of the file with renderer quality settings, that is applied by the executor right after
+/// the graphics context was created. The file is optional and is written by the editor when the
+/// game is exported with a specific quality preset. It is not read on WebAssembly, because files
+/// there could be fetched only asynchronously.
+pub const QUALITY_SETTINGS_FILE: &str = "quality.ron";
+
 #[derive(Parser, Debug, Default)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
     #[clap(short, long, default_value = None)]
     override_scene: Option<String>,
+    /// A local port for runtime inspection clients (the editor, for example). The inspection server
+    /// is not started if the port is not specified.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[clap(long, default_value = None)]
+    inspection_port: Option<u16>,
+    /// Defines what inspection clients are allowed to do with the game.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[clap(long, value_enum, default_value_t)]
+    inspection_access: InspectionAccess,
+    /// A secret, that inspection clients must send before any request. Required to start the
+    /// inspection server.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[clap(long, default_value = None)]
+    inspection_token: Option<String>,
 }
 
 /// Executor is a small wrapper that manages plugins and scripts for your game.
@@ -185,13 +212,55 @@ impl Executor {
 
         let args = Args::try_parse().unwrap_or_default();
 
+        #[cfg(target_arch = "wasm32")]
+        {
+            use crate::asset::{preload::PRELOAD_MANIFEST_FILE, web::WebResourceIo};
+
+            // Cache fetched assets in the browser and stream the assets from the preload manifest
+            // in the background, so the game does not stall on network requests.
+            let io = Arc::new(WebResourceIo::new(WEB_CACHE_DATABASE));
+            engine.resource_manager.state().set_resource_io(io.clone());
+            crate::core::wasm_bindgen_futures::spawn_local(async move {
+                if let Err(err) = io
+                    .preload_from_file(std::path::Path::new(PRELOAD_MANIFEST_FILE))
+                    .await
+                {
+                    Log::warn(format!("Unable to preload assets. Reason: {:?}", err));
+                }
+            });
+        }
+
         engine.enable_plugins(args.override_scene.as_deref(), true, Some(&event_loop));
 
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut inspection_server = args.inspection_port.and_then(|port| {
+            let Some(token) = args.inspection_token.clone() else {
+                Log::err("Inspection server requires --inspection-token argument.");
+                return None;
+            };
+            match InspectionServer::start(port, args.inspection_access, token) {
+                Ok(server) => {
+                    Log::info(format!(
+                        "Inspection server is listening on port {port} ({:?} access).",
+                        args.inspection_access
+                    ));
+                    Some(server)
+                }
+                Err(err) => {
+                    Log::err(format!(
+                        "Unable to start inspection server on port {port}. Reason: {err}"
+                    ));
+                    None
+                }
+            }
+        });
+
         let mut previous = Instant::now();
         let fixed_time_step = 1.0 / self.desired_update_rate;
         let mut lag = 0.0;
         let mut frame_counter = 0usize;
         let mut last_throttle_frame_number = 0usize;
+        let mut quality_settings = load_quality_settings();
 
         run_executor(event_loop, move |event, window_target| {
             window_target.set_control_flow(ControlFlow::Wait);
@@ -214,26 +283,48 @@ impl Executor {
 
             match event {
                 Event::Resumed if !headless => {
-                    engine
-                        .initialize_graphics_context(window_target)
-                        .expect("Unable to initialize graphics context!");
-
-                    engine.handle_graphics_context_created_by_plugins(
-                        fixed_time_step,
-                        window_target,
-                        &mut lag,
-                    );
+                    // Some platforms may send this event more than once without suspension.
+                    if let GraphicsContext::Uninitialized(_) = engine.graphics_context {
+                        engine
+                            .initialize_graphics_context(window_target)
+                            .expect("Unable to initialize graphics context!");
+
+                        // The settings from the file are applied only once, the engine keeps the
+                        // current settings across suspensions by itself.
+                        if let (Some(settings), GraphicsContext::Initialized(ref mut ctx)) =
+                            (quality_settings.take(), &mut engine.graphics_context)
+                        {
+                            Log::verify(ctx.renderer.set_quality_settings(&settings));
+                        }
+
+                        // Do not try to catch up with the time spent in the background.
+                        previous = Instant::now();
+                        lag = 0.0;
+
+                        engine.handle_graphics_context_created_by_plugins(
+                            fixed_time_step,
+                            window_target,
+                            &mut lag,
+                        );
+                    }
                 }
                 Event::Suspended if !headless => {
-                    engine
-                        .destroy_graphics_context()
-                        .expect("Unable to destroy graphics context!");
-
-                    engine.handle_graphics_context_destroyed_by_plugins(
-                        fixed_time_step,
-                        window_target,
-                        &mut lag,
-                    );
+                    if let GraphicsContext::Initialized(_) = engine.graphics_context {
+                        engine
+                            .destroy_graphics_context()
+                            .expect("Unable to destroy graphics context!");
+
+                        // Touches won't be finished while the app is in the background.
+                        for ui in engine.user_interfaces.iter_mut() {
+                            ui.cancel_touches();
+                        }
+
+                        engine.handle_graphics_context_destroyed_by_plugins(
+                            fixed_time_step,
+                            window_target,
+                            &mut lag,
+                        );
+                    }
                 }
                 Event::AboutToWait => {
                     let elapsed = previous.elapsed();
@@ -271,6 +362,11 @@ impl Executor {
                         }
                     }
 
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if let Some(server) = inspection_server.as_mut() {
+                        server.update(&mut engine.scenes);
+                    }
+
                     if let GraphicsContext::Initialized(ref ctx) = engine.graphics_context {
                         ctx.window.request_redraw();
                     }
@@ -312,6 +408,35 @@ impl Executor {
     }
 }
 
+fn load_quality_settings() -> Option<QualitySettings> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let data = crate::core::futures::executor::block_on(crate::core::io::load_file(
+            QUALITY_SETTINGS_FILE,
+        ))
+        .ok()?;
+        match ron::de::from_bytes::<QualitySettings>(&data) {
+            Ok(settings) => {
+                Log::info(format!(
+                    "Quality settings were loaded from {QUALITY_SETTINGS_FILE}."
+                ));
+                Some(settings)
+            }
+            Err(err) => {
+                Log::err(format!(
+                    "Unable to parse {QUALITY_SETTINGS_FILE}. Reason: {err:?}"
+                ));
+                None
+            }
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        None
+    }
+}
+
 fn run_executor<F>(event_loop: EventLoop<()>, callback: F)
 where
     F: FnMut(Event<()>, &EventLoopWindowTarget<()>) + 'static,
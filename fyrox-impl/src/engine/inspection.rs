@@ -0,0 +1,599 @@
+//! Runtime inspection allows external tools (the editor, for example) to attach to a running game and
+//! look at its scenes while the game is running. The inspection server listens on a local TCP port and
+//! uses a simple line-based protocol: each request is a JSON object on a single line, for example:
+//!
+//! ```text
+//! {"command":"GetHierarchy"}
+//! {"command":"GetNode","scene":{"index":0,"generation":1},"node":{"index":5,"generation":1}}
+//! {"command":"SetProperty","scene":{"index":0,"generation":1},"node":{"index":5,"generation":1},"path":"local_transform.local_position","value":[1.0,2.0,3.0]}
+//! ```
+//!
+//! The server answers each request with a single line JSON object: `{"ok":true,"data":...}` or
+//! `{"ok":false,"error":"..."}`. Property changes are accepted only if the server was started with
+//! [`InspectionAccess::Tweak`] access, otherwise the game state could only be observed. Every
+//! connection must start with a handshake line with the token of the server (`{"token":"..."}`), see
+//! [`LineServer`] docs for more info.
+//!
+//! The executor starts the server automatically, if the game was launched with `--inspection-port`
+//! and `--inspection-token` command line arguments.
+
+use crate::{
+    core::{
+        algebra::{Quaternion, UnitQuaternion, Vector2, Vector3, Vector4},
+        color::Color,
+        pool::Handle,
+        reflect::prelude::*,
+    },
+    graph::BaseSceneGraph,
+    scene::{node::Node, Scene, SceneContainer},
+    utils::line_server::{LineResponse, LineServer},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{any::Any, fmt::Write as _};
+
+/// Defines what an inspection client is allowed to do with a running game.
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum,
+)]
+pub enum InspectionAccess {
+    /// The client could only observe the state of the game.
+    #[default]
+    ReadOnly,
+    /// The client could change properties of scene nodes.
+    Tweak,
+}
+
+/// A request, that could be sent to a running game by an inspection client.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "command")]
+pub enum InspectionRequest {
+    /// Requests a list of scenes and their nodes.
+    GetHierarchy,
+    /// Requests a list of properties of a node.
+    GetNode {
+        /// A handle of the scene, that contains the node.
+        scene: Handle<Scene>,
+        /// A handle of the node.
+        node: Handle<Node>,
+    },
+    /// Sets a new value of a property of a node. The path is a reflection path of the property
+    /// (`local_transform.local_position`, for example). Requires [`InspectionAccess::Tweak`] access.
+    SetProperty {
+        /// A handle of the scene, that contains the node.
+        scene: Handle<Scene>,
+        /// A handle of the node.
+        node: Handle<Node>,
+        /// A reflection path of the property.
+        path: String,
+        /// New value of the property.
+        value: Value,
+    },
+}
+
+/// A short description of a scene node.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct NodeInfo {
+    /// A handle of the node.
+    pub handle: Handle<Node>,
+    /// Name of the node.
+    pub name: String,
+    /// Depth of the node in the hierarchy, the root node has zero depth.
+    pub depth: usize,
+    /// Whether the node is enabled or not.
+    pub enabled: bool,
+}
+
+/// A short description of a scene.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct SceneInfo {
+    /// A handle of the scene.
+    pub handle: Handle<Scene>,
+    /// Nodes of the scene in depth-first order.
+    pub nodes: Vec<NodeInfo>,
+}
+
+/// A description of a single property of a scene node.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct PropertyInfo {
+    /// A reflection path of the property.
+    pub path: String,
+    /// Name of the type of the property.
+    pub type_name: String,
+    /// Human-readable representation of the value of the property.
+    pub value: String,
+    /// JSON representation of the value of the property. It is present only for the properties,
+    /// that could be set remotely (see [`json_to_reflect`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json: Option<Value>,
+    /// Whether the property could be changed or not.
+    pub editable: bool,
+}
+
+/// A payload of a successful response.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind")]
+pub enum InspectionData {
+    /// A response to [`InspectionRequest::GetHierarchy`].
+    Hierarchy {
+        /// Access level of the server.
+        access: InspectionAccess,
+        /// A list of scenes of the game.
+        scenes: Vec<SceneInfo>,
+    },
+    /// A response to [`InspectionRequest::GetNode`].
+    Node {
+        /// A list of properties of the node.
+        properties: Vec<PropertyInfo>,
+    },
+}
+
+/// A response of the inspection server.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct InspectionResponse {
+    /// `true` if the request was executed successfully.
+    pub ok: bool,
+    /// A reason of failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Requested data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<InspectionData>,
+}
+
+impl InspectionResponse {
+    fn from_result(result: Result<Option<InspectionData>, String>) -> Self {
+        match result {
+            Ok(data) => Self {
+                ok: true,
+                error: None,
+                data,
+            },
+            Err(error) => Self {
+                ok: false,
+                error: Some(error),
+                data: None,
+            },
+        }
+    }
+}
+
+impl LineResponse for InspectionResponse {
+    fn success() -> Self {
+        Self::from_result(Ok(None))
+    }
+
+    fn failure(error: String) -> Self {
+        Self::from_result(Err(error))
+    }
+}
+
+/// Inspection server accepts inspection clients on a local TCP port and executes their requests. See
+/// the [module docs](self) for more info about the protocol.
+pub struct InspectionServer {
+    server: LineServer<InspectionRequest, InspectionResponse>,
+    access: InspectionAccess,
+}
+
+impl InspectionServer {
+    /// Starts listening on the given port of the local host. Clients must send the given token in
+    /// the handshake. Requests are executed only in [`InspectionServer::update`], so it must be
+    /// called periodically.
+    pub fn start(port: u16, access: InspectionAccess, token: String) -> std::io::Result<Self> {
+        Ok(Self {
+            server: LineServer::start("InspectionServer", port, token)?,
+            access,
+        })
+    }
+
+    /// Returns the port, that is used by the server.
+    pub fn port(&self) -> u16 {
+        self.server.port()
+    }
+
+    /// Returns access level of the server.
+    pub fn access(&self) -> InspectionAccess {
+        self.access
+    }
+
+    /// Executes pending requests of the clients.
+    pub fn update(&mut self, scenes: &mut SceneContainer) {
+        while let Some(pending) = self.server.try_recv() {
+            let result = execute(pending.request, self.access, scenes);
+            pending
+                .responder
+                .respond(InspectionResponse::from_result(result));
+        }
+    }
+}
+
+fn find_node(
+    scenes: &mut SceneContainer,
+    scene: Handle<Scene>,
+    node: Handle<Node>,
+) -> Result<&mut Node, String> {
+    scenes
+        .try_get_mut(scene)
+        .ok_or_else(|| format!("There's no scene {scene}."))?
+        .graph
+        .try_get_mut(node)
+        .ok_or_else(|| format!("There's no node {node}."))
+}
+
+fn execute(
+    request: InspectionRequest,
+    access: InspectionAccess,
+    scenes: &mut SceneContainer,
+) -> Result<Option<InspectionData>, String> {
+    match request {
+        InspectionRequest::GetHierarchy => Ok(Some(InspectionData::Hierarchy {
+            access,
+            scenes: scenes
+                .pair_iter()
+                .map(|(handle, scene)| SceneInfo {
+                    handle,
+                    nodes: collect_nodes(scene),
+                })
+                .collect(),
+        })),
+        InspectionRequest::GetNode { scene, node } => {
+            let node = find_node(scenes, scene, node)?;
+            Ok(Some(InspectionData::Node {
+                properties: collect_properties(node as &dyn Reflect, access),
+            }))
+        }
+        InspectionRequest::SetProperty {
+            scene,
+            node,
+            path,
+            value,
+        } => {
+            if access != InspectionAccess::Tweak {
+                return Err("The game does not allow to change properties.".to_string());
+            }
+
+            let node = find_node(scenes, scene, node)?;
+
+            let mut new_value = None;
+            node.resolve_path(&path, &mut |result| {
+                new_value = Some(match result {
+                    Ok(property) => json_to_reflect(property, &value),
+                    Err(err) => Err(format!("Invalid property path {path}: {err}")),
+                });
+            });
+            let new_value = new_value.unwrap_or_else(|| Err("Unknown property.".to_string()))?;
+
+            let mut result = Err("Unknown property.".to_string());
+            (node as &mut dyn Reflect).set_field_by_path(&path, new_value, &mut |r| {
+                result = r
+                    .map(|_| ())
+                    .map_err(|_| format!("Unable to set {path} property."));
+            });
+            result.map(|_| None)
+        }
+    }
+}
+
+fn collect_nodes(scene: &Scene) -> Vec<NodeInfo> {
+    let mut nodes = Vec::new();
+    let mut stack = vec![(scene.graph.get_root(), 0)];
+    while let Some((handle, depth)) = stack.pop() {
+        let Some(node) = scene.graph.try_get(handle) else {
+            continue;
+        };
+        nodes.push(NodeInfo {
+            handle,
+            name: node.name().to_string(),
+            depth,
+            enabled: node.is_globally_enabled(),
+        });
+        // Reverse order keeps the children in the same order as in the graph.
+        for &child in node.children().iter().rev() {
+            stack.push((child, depth + 1));
+        }
+    }
+    nodes
+}
+
+/// Maximum nesting of properties, that are listed by [`collect_properties`].
+const MAX_PROPERTY_DEPTH: usize = 4;
+/// Maximum length of a human-readable representation of a property value.
+const MAX_VALUE_LENGTH: usize = 128;
+
+/// A writer, that stops formatting when the output becomes too long. It prevents the server from
+/// formatting huge values (like vertex buffers) only to throw the most of the text away.
+struct LimitedWriter {
+    text: String,
+    truncated: bool,
+}
+
+impl std::fmt::Write for LimitedWriter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let remaining = MAX_VALUE_LENGTH.saturating_sub(self.text.len());
+        if s.len() <= remaining {
+            self.text.push_str(s);
+            Ok(())
+        } else {
+            let mut end = remaining;
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            self.text.push_str(&s[..end]);
+            self.truncated = true;
+            Err(std::fmt::Error)
+        }
+    }
+}
+
+fn describe(value: &dyn Reflect) -> String {
+    let mut writer = LimitedWriter {
+        text: String::new(),
+        truncated: false,
+    };
+    let _ = write!(writer, "{value:?}");
+    if writer.truncated {
+        writer.text.push_str("...");
+    }
+    writer.text
+}
+
+fn collection_len(value: &dyn Reflect) -> Option<usize> {
+    let mut len = None;
+    value.as_list(&mut |list| len = len.or(list.map(|list| list.reflect_len())));
+    value.as_array(&mut |array| len = len.or(array.map(|array| array.reflect_len())));
+    value.as_hash_map(&mut |map| len = len.or(map.map(|map| map.reflect_len())));
+    len
+}
+
+/// Collects properties of the given entity recursively. Nested structures are expanded up to a few
+/// levels deep, collections are shown as a single property with the number of items.
+pub fn collect_properties(entity: &dyn Reflect, access: InspectionAccess) -> Vec<PropertyInfo> {
+    let mut properties = Vec::new();
+    collect_properties_recursively(entity, "", 0, access, &mut properties);
+    properties
+}
+
+fn collect_properties_recursively(
+    entity: &dyn Reflect,
+    path: &str,
+    depth: usize,
+    access: InspectionAccess,
+    properties: &mut Vec<PropertyInfo>,
+) {
+    entity.fields_info(&mut |fields| {
+        for field in fields {
+            let field_path = if path.is_empty() {
+                field.name.to_string()
+            } else {
+                format!("{path}.{}", field.name)
+            };
+            let value = field.reflect_value;
+
+            let json = reflect_to_json(value);
+            let mut has_fields = false;
+            value.fields_info(&mut |fields| has_fields = !fields.is_empty());
+            let collection_len = collection_len(value);
+
+            if json.is_none()
+                && collection_len.is_none()
+                && has_fields
+                && depth + 1 < MAX_PROPERTY_DEPTH
+            {
+                collect_properties_recursively(value, &field_path, depth + 1, access, properties);
+                continue;
+            }
+
+            properties.push(PropertyInfo {
+                path: field_path,
+                type_name: field.type_name.to_string(),
+                value: match collection_len {
+                    Some(len) => format!("[{len} items]"),
+                    None => describe(value),
+                },
+                editable: access == InspectionAccess::Tweak && !field.read_only && json.is_some(),
+                json,
+            });
+        }
+    });
+}
+
+fn floats<const N: usize>(value: &Value) -> Result<[f32; N], String> {
+    let array = value
+        .as_array()
+        .filter(|array| array.len() == N)
+        .ok_or_else(|| format!("Expected an array of {N} numbers."))?;
+    let mut result = [0.0; N];
+    for (dest, item) in result.iter_mut().zip(array) {
+        *dest = item
+            .as_f64()
+            .ok_or_else(|| format!("Expected an array of {N} numbers."))? as f32;
+    }
+    Ok(result)
+}
+
+/// Converts a JSON value to a value of the same type as the given property. Supported types are:
+/// `bool`, numbers, `String`, `Vector2/3/4<f32>`, `UnitQuaternion<f32>` (as `[x, y, z, w]`) and
+/// `Color` (as `[r, g, b, a]` with integer components in `[0; 255]` range).
+pub fn json_to_reflect(property: &dyn Reflect, value: &Value) -> Result<Box<dyn Reflect>, String> {
+    let mut result = None;
+    property.as_any(&mut |any| result = Some(convert(any, value)));
+    result
+        .unwrap_or_else(|| Err("Unknown property.".to_string()))?
+        .ok_or_else(|| {
+            format!(
+                "Properties of {} type cannot be set remotely.",
+                property.type_name()
+            )
+        })
+}
+
+fn convert(any: &dyn Any, value: &Value) -> Result<Option<Box<dyn Reflect>>, String> {
+    macro_rules! deserialize {
+        ($($ty:ty),*) => {
+            $(
+                if any.is::<$ty>() {
+                    return serde_json::from_value::<$ty>(value.clone())
+                        .map(|v| Some(Box::new(v) as Box<dyn Reflect>))
+                        .map_err(|err| err.to_string());
+                }
+            )*
+        };
+    }
+
+    deserialize!(bool, f32, f64, i8, u8, i16, u16, i32, u32, i64, u64, usize, isize, String);
+
+    let value: Box<dyn Reflect> = if any.is::<Vector2<f32>>() {
+        Box::new(Vector2::from(floats::<2>(value)?))
+    } else if any.is::<Vector3<f32>>() {
+        Box::new(Vector3::from(floats::<3>(value)?))
+    } else if any.is::<Vector4<f32>>() {
+        Box::new(Vector4::from(floats::<4>(value)?))
+    } else if any.is::<UnitQuaternion<f32>>() {
+        // Quaternions are expected to be in [x, y, z, w] order.
+        let [x, y, z, w] = floats::<4>(value)?;
+        Box::new(UnitQuaternion::from_quaternion(Quaternion::new(w, x, y, z)))
+    } else if any.is::<Color>() {
+        // Fractional or out-of-range components are rejected instead of being silently truncated.
+        let [r, g, b, a] = serde_json::from_value::<[u8; 4]>(value.clone())
+            .map_err(|_| "Expected an array of 4 integers in [0; 255] range.".to_string())?;
+        Box::new(Color::from_rgba(r, g, b, a))
+    } else {
+        return Ok(None);
+    };
+
+    Ok(Some(value))
+}
+
+/// Converts a value of a property to JSON. It is the inverse of [`json_to_reflect`], it returns
+/// `None` for the same types, that are not supported by [`json_to_reflect`].
+pub fn reflect_to_json(property: &dyn Reflect) -> Option<Value> {
+    let mut result = None;
+    property.as_any(&mut |any| result = to_json(any));
+    result
+}
+
+fn to_json(any: &dyn Any) -> Option<Value> {
+    macro_rules! serialize {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(value) = any.downcast_ref::<$ty>() {
+                    return serde_json::to_value(value).ok();
+                }
+            )*
+        };
+    }
+
+    serialize!(bool, f32, f64, i8, u8, i16, u16, i32, u32, i64, u64, usize, isize, String);
+
+    if let Some(v) = any.downcast_ref::<Vector2<f32>>() {
+        serde_json::to_value([v.x, v.y]).ok()
+    } else if let Some(v) = any.downcast_ref::<Vector3<f32>>() {
+        serde_json::to_value([v.x, v.y, v.z]).ok()
+    } else if let Some(v) = any.downcast_ref::<Vector4<f32>>() {
+        serde_json::to_value([v.x, v.y, v.z, v.w]).ok()
+    } else if let Some(q) = any.downcast_ref::<UnitQuaternion<f32>>() {
+        serde_json::to_value([q.i, q.j, q.k, q.w]).ok()
+    } else if let Some(c) = any.downcast_ref::<Color>() {
+        serde_json::to_value([c.r, c.g, c.b, c.a]).ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, color::Color, pool::Handle},
+        engine::inspection::{
+            collect_properties, execute, json_to_reflect, reflect_to_json, InspectionAccess,
+            InspectionData, InspectionRequest,
+        },
+        scene::{
+            base::BaseBuilder, pivot::PivotBuilder, sound::SoundEngine, Scene, SceneContainer,
+        },
+    };
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_request() {
+        let request = serde_json::from_str::<InspectionRequest>(
+            r#"{"command":"GetNode","scene":{"index":0,"generation":1},"node":{"index":2,"generation":3}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            InspectionRequest::GetNode {
+                scene: Handle::new(0, 1),
+                node: Handle::new(2, 3),
+            }
+        );
+    }
+
+    #[test]
+    fn test_json_to_reflect() {
+        let value = json_to_reflect(&Vector3::<f32>::default(), &json!([1.0, 2.0, 3.0])).unwrap();
+        value.downcast_ref::<Vector3<f32>>(&mut |v| {
+            assert_eq!(v, Some(&Vector3::new(1.0, 2.0, 3.0)))
+        });
+
+        let value = json_to_reflect(&0.0f32, &json!(5.0)).unwrap();
+        value.downcast_ref::<f32>(&mut |v| assert_eq!(v, Some(&5.0)));
+
+        assert!(json_to_reflect(&Vector3::<f32>::default(), &json!([1.0])).is_err());
+        assert!(json_to_reflect(&true, &json!("text")).is_err());
+
+        let value = json_to_reflect(&Color::default(), &json!([255, 128, 0, 255])).unwrap();
+        value.downcast_ref::<Color>(&mut |v| {
+            assert_eq!(v, Some(&Color::from_rgba(255, 128, 0, 255)))
+        });
+        assert!(json_to_reflect(&Color::default(), &json!([1.0, 0.5, 0.0, 1.0])).is_err());
+        assert!(json_to_reflect(&Color::default(), &json!([256, 0, 0, 255])).is_err());
+
+        assert_eq!(
+            reflect_to_json(&Vector3::new(1.0f32, 2.0, 3.0)),
+            Some(json!([1.0, 2.0, 3.0]))
+        );
+    }
+
+    #[test]
+    fn test_inspect_and_tweak_node() {
+        let mut scene = Scene::new();
+        let node = PivotBuilder::new(BaseBuilder::new().with_name("Pivot")).build(&mut scene.graph);
+        let mut scenes = SceneContainer::new(SoundEngine::without_device());
+        let scene = scenes.add(scene);
+
+        let Some(InspectionData::Hierarchy { scenes: infos, .. }) = execute(
+            InspectionRequest::GetHierarchy,
+            InspectionAccess::ReadOnly,
+            &mut scenes,
+        )
+        .unwrap() else {
+            panic!("Unexpected response.")
+        };
+        let info = infos[0].nodes.iter().find(|n| n.handle == node).unwrap();
+        assert_eq!(info.name, "Pivot");
+        assert_eq!(info.depth, 1);
+
+        let properties = collect_properties(&scenes[scene].graph[node], InspectionAccess::Tweak);
+        let position = properties
+            .iter()
+            .find(|p| p.path.ends_with("local_position"))
+            .unwrap();
+        assert!(position.editable);
+
+        let set_position = || InspectionRequest::SetProperty {
+            scene,
+            node,
+            path: position.path.clone(),
+            value: json!([1.0, 2.0, 3.0]),
+        };
+
+        assert!(execute(set_position(), InspectionAccess::ReadOnly, &mut scenes).is_err());
+
+        execute(set_position(), InspectionAccess::Tweak, &mut scenes).unwrap();
+        assert_eq!(
+            **scenes[scene].graph[node].local_transform().position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+    }
+}
@@ -5,6 +5,8 @@
 
 pub mod error;
 pub mod executor;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod inspection;
 pub mod task;
 
 mod hotreload;
@@ -17,14 +19,28 @@ use crate::{
         Resource,
     },
     core::{
-        algebra::Vector2, futures::executor::block_on, instant, log::Log, pool::Handle,
-        reflect::Reflect, task::TaskPool, variable::try_inherit_properties, visitor::VisitError,
+        algebra::Vector2,
+        futures::executor::block_on,
+        instant,
+        log::Log,
+        memory::{self, MemorySubsystem},
+        pool::Handle,
+        profiler,
+        reflect::Reflect,
+        scope_profile,
+        task::TaskPool,
+        variable::try_inherit_properties,
+        visitor::VisitError,
     },
     engine::{error::EngineError, task::TaskPoolHandler},
     event::Event,
     graph::{BaseSceneGraph, NodeMapping, SceneGraph},
     gui::{
-        font::loader::FontLoader, font::Font, font::BUILT_IN_FONT, loader::UserInterfaceLoader,
+        font::loader::FontLoader,
+        font::Font,
+        font::BUILT_IN_FONT,
+        loader::UserInterfaceLoader,
+        localization::{loader::StringTableLoader, StringTable},
         UiUpdateSwitches, UserInterface,
     },
     material::{
@@ -34,11 +50,15 @@ use crate::{
         Material,
     },
     plugin::{Plugin, PluginContext, PluginRegistrationContext},
-    renderer::{framework::error::FrameworkError, framework::state::GlKind, Renderer},
+    renderer::{
+        framework::error::FrameworkError, framework::program_binary::ProgramBinaryCache,
+        framework::state::GlKind, QualitySettings, Renderer, SceneRenderData,
+    },
     resource::{
         curve::{loader::CurveLoader, CurveResourceState},
         model::{loader::ModelLoader, Model, ModelResource},
         texture::{self, loader::TextureLoader, Texture, TextureKind},
+        video::{loader::VideoLoader, Video},
     },
     scene::{
         base::NodeScriptMessage,
@@ -55,6 +75,7 @@ use crate::{
         ScriptMessageSender,
     },
     script::{PluginsRefMut, UniversalScriptContext},
+    utils::jobs::JobSystem,
     window::{Window, WindowBuilder},
 };
 use fxhash::{FxHashMap, FxHashSet};
@@ -97,10 +118,17 @@ use std::{
     time::Duration,
 };
 
+use crate::material::shader::graph::{ShaderGraph, ShaderGraphLoader};
 use crate::plugin::dynamic::DynamicPlugin;
 use crate::plugin::{DynamicPluginState, PluginContainer};
+use crate::scene::animation::pose::{PoseLibrary, PoseLibraryLoader};
+use crate::scene::animation::sequencer::sequence::{Sequence, SequenceLoader};
+use crate::scene::animation::sprite::sheet::{SpriteSheet, SpriteSheetLoader};
+use crate::scene::crowd::vat::{VertexAnimation, VertexAnimationLoader};
 use crate::scene::mesh::surface;
 use crate::scene::mesh::surface::{SurfaceData, SurfaceDataLoader};
+use crate::scene::point_cloud::resource::{PointCloudData, PointCloudLoader};
+use crate::scene::tilemap::stamp::{TileMapStampLibrary, TileMapStampLibraryLoader};
 use crate::scene::tilemap::tileset::{TileSet, TileSetLoader};
 use fyrox_core::futures::future::join_all;
 use fyrox_core::notify;
@@ -351,7 +379,7 @@ impl AsyncSceneLoader {
             // Aquire the resource IO from the resource manager
             let io = resource_manager.resource_io();
 
-            let future = async move {
+            let future = memory::tracked(MemorySubsystem::Scene, async move {
                 match SceneLoader::from_file(
                     path.clone(),
                     io.as_ref(),
@@ -374,7 +402,7 @@ impl AsyncSceneLoader {
                         }));
                     }
                 }
-            };
+            });
 
             #[cfg(not(target_arch = "wasm32"))]
             {
@@ -447,6 +475,11 @@ pub struct Engine {
     // Amount of time (in seconds) that passed from creation of the engine.
     elapsed_time: f32,
 
+    // Quality settings of the renderer, that were used before the graphics context was destroyed.
+    // They're restored when the graphics context is created again (for example, when a mobile app
+    // returns from the background).
+    suspended_quality_settings: Option<QualitySettings>,
+
     /// A special container that is able to create nodes by their type UUID. Use a copy of this
     /// value whenever you need it as a parameter in other parts of the engine.
     pub serialization_context: Arc<SerializationContext>,
@@ -456,6 +489,11 @@ pub struct Engine {
 
     /// Script processor is used to run script methods in a strict order.
     pub script_processor: ScriptProcessor,
+
+    // Gamepad events are not reported by the windowing system, so they're polled separately and
+    // fed to user interfaces to allow navigating them using a gamepad.
+    #[cfg(feature = "gamepad")]
+    gamepads: Option<gilrs::Gilrs>,
 }
 
 /// Performs dispatch of script messages.
@@ -1167,6 +1205,15 @@ pub(crate) fn initialize_resource_manager_loaders(
         state.loaders.set(gltf_loader);
     }
 
+    #[cfg(feature = "usd")]
+    {
+        let usd_loader = super::resource::usd::UsdLoader {
+            resource_manager: resource_manager.clone(),
+            default_import_options: Default::default(),
+        };
+        state.loaders.set(usd_loader);
+    }
+
     for shader in ShaderResource::standard_shaders() {
         state
             .built_in_resources
@@ -1197,6 +1244,11 @@ pub(crate) fn initialize_resource_manager_loaders(
         material::STANDARD_TERRAIN.clone(),
         material::STANDARD_TWOSIDES.clone(),
         material::STANDARD_PARTICLE_SYSTEM.clone(),
+        material::STANDARD_VERTEX_ANIMATION.clone(),
+        material::STANDARD_POINT_CLOUD.clone(),
+        material::STANDARD_WATER.clone(),
+        material::STANDARD_SKY.clone(),
+        material::STANDARD_PRECIPITATION.clone(),
     ] {
         state.built_in_resources.insert(
             material.kind().path_owned().unwrap(),
@@ -1229,6 +1281,15 @@ pub(crate) fn initialize_resource_manager_loaders(
     state.constructors_container.add::<UserInterface>();
     state.constructors_container.add::<SurfaceData>();
     state.constructors_container.add::<TileSet>();
+    state.constructors_container.add::<TileMapStampLibrary>();
+    state.constructors_container.add::<PoseLibrary>();
+    state.constructors_container.add::<SpriteSheet>();
+    state.constructors_container.add::<Sequence>();
+    state.constructors_container.add::<VertexAnimation>();
+    state.constructors_container.add::<PointCloudData>();
+    state.constructors_container.add::<ShaderGraph>();
+    state.constructors_container.add::<StringTable>();
+    state.constructors_container.add::<Video>();
 
     let loaders = &mut state.loaders;
     loaders.set(model_loader);
@@ -1250,6 +1311,15 @@ pub(crate) fn initialize_resource_manager_loaders(
     });
     loaders.set(SurfaceDataLoader {});
     loaders.set(TileSetLoader);
+    loaders.set(TileMapStampLibraryLoader);
+    loaders.set(PoseLibraryLoader);
+    loaders.set(SpriteSheetLoader);
+    loaders.set(SequenceLoader);
+    loaders.set(VertexAnimationLoader);
+    loaders.set(PointCloudLoader);
+    loaders.set(ShaderGraphLoader);
+    loaders.set(StringTableLoader);
+    loaders.set(VideoLoader);
 }
 
 fn try_copy_library(source_lib_path: &Path, lib_path: &Path) -> Result<(), String> {
@@ -1363,7 +1433,16 @@ impl Engine {
             script_processor: Default::default(),
             plugins_enabled: false,
             elapsed_time: 0.0,
+            suspended_quality_settings: None,
             task_pool: TaskPoolHandler::new(task_pool),
+            #[cfg(feature = "gamepad")]
+            gamepads: match gilrs::Gilrs::new() {
+                Ok(gilrs) => Some(gilrs),
+                Err(err) => {
+                    Log::warn(format!("Unable to initialize gamepad input. Reason: {err}"));
+                    None
+                }
+            },
         })
     }
 
@@ -1415,7 +1494,7 @@ impl Engine {
                 .with_active(params.window_attributes.active);
 
             #[cfg(not(target_arch = "wasm32"))]
-            let (window, gl_context, gl_surface, glow_context, gl_kind) = {
+            let (window, gl_context, gl_surface, glow_context, gl_kind, program_binaries) = {
                 let mut template = ConfigTemplateBuilder::new()
                     .prefer_hardware_accelerated(Some(true))
                     .with_stencil_size(8)
@@ -1482,20 +1561,29 @@ impl Engine {
                         ));
                     }
 
+                    let glow_context = glow::Context::from_loader_function(|s| {
+                        gl_display.get_proc_address(&CString::new(s).unwrap())
+                    });
+
+                    let program_binaries = ProgramBinaryCache::new(
+                        &glow_context,
+                        ProgramBinaryCache::default_directory(),
+                        |s| gl_display.get_proc_address(&CString::new(s).unwrap()),
+                    );
+
                     (
                         window,
                         gl_context,
                         gl_surface,
-                        glow::Context::from_loader_function(|s| {
-                            gl_display.get_proc_address(&CString::new(s).unwrap())
-                        }),
+                        glow_context,
                         gl_kind,
+                        program_binaries,
                     )
                 }
             };
 
             #[cfg(target_arch = "wasm32")]
-            let (window, glow_context, gl_kind) = {
+            let (window, glow_context, gl_kind, program_binaries) = {
                 use crate::{
                     core::wasm_bindgen::JsCast,
                     dpi::{LogicalSize, PhysicalSize},
@@ -1570,6 +1658,8 @@ impl Engine {
                     window,
                     glow::Context::from_webgl2_context(webgl2_context),
                     GlKind::OpenGLES,
+                    // WebGL does not support program binaries.
+                    None,
                 )
             };
 
@@ -1589,17 +1679,23 @@ impl Engine {
                     .unwrap_or_else(|| NonZeroU32::new(1).unwrap()),
             );
 
+            let mut renderer = Renderer::new(
+                glow_context,
+                (window.inner_size().width, window.inner_size().height),
+                &self.resource_manager,
+                gl_kind,
+                program_binaries,
+            )?;
+            if let Some(quality_settings) = self.suspended_quality_settings.take() {
+                renderer.set_quality_settings(&quality_settings)?;
+            }
+
             self.graphics_context = GraphicsContext::Initialized(InitializedGraphicsContext {
                 #[cfg(not(target_arch = "wasm32"))]
                 gl_context,
                 #[cfg(not(target_arch = "wasm32"))]
                 gl_surface,
-                renderer: Renderer::new(
-                    glow_context,
-                    (window.inner_size().width, window.inner_size().height),
-                    &self.resource_manager,
-                    gl_kind,
-                )?,
+                renderer,
                 window,
                 params: params.clone(),
             });
@@ -1615,7 +1711,7 @@ impl Engine {
     }
 
     /// Tries to destroy current graphics context. It will succeed only if the `graphics_context` is fully initialized.
-    /// The method will try to save all possible runtime changes of the window, so the next [`Engine::initialize_graphics_context`]
+    /// The method will try to save all possible runtime changes of the window and the quality settings of the renderer, so the next [`Engine::initialize_graphics_context`]
     /// will result in the almost exact copy of the context that was made before destruction.
     ///
     /// This method should be called on [`Event::Suspended`] of your game loop, however if you do not use any graphics context
@@ -1647,6 +1743,8 @@ impl Engine {
                 .window_icon
                 .clone_from(&params.window_attributes.window_icon);
 
+            self.suspended_quality_settings = Some(ctx.renderer.get_quality_settings());
+
             self.graphics_context = GraphicsContext::Uninitialized(GraphicsContextParams {
                 window_attributes,
                 vsync: params.vsync,
@@ -1707,6 +1805,8 @@ impl Engine {
         lag: &mut f32,
         switches: FxHashMap<Handle<Scene>, GraphUpdateSwitches>,
     ) {
+        scope_profile!();
+
         self.handle_async_scene_loading(dt, lag, window_target);
         self.pre_update(dt, window_target, lag, switches);
         self.post_update(dt, &Default::default());
@@ -1745,6 +1845,8 @@ impl Engine {
         lag: &mut f32,
         window_target: &EventLoopWindowTarget<()>,
     ) {
+        scope_profile!();
+
         let len = self.async_scene_loader.loading_scenes.len();
         let mut n = 0;
         while n < len {
@@ -1875,6 +1977,20 @@ impl Engine {
                             }
                         }
 
+                        // Compile shaders of the scene before it is used, so the first frames of
+                        // the scene won't hitch.
+                        if let GraphicsContext::Initialized(ref mut graphics_context) =
+                            context.graphics_context
+                        {
+                            let ready =
+                                graphics_context.renderer.warm_up_shaders(&scene, |_, _| ());
+                            Log::info(format!(
+                                "{} shader(s) of scene {} were warmed up.",
+                                ready,
+                                loading_result.path.display()
+                            ));
+                        }
+
                         let scene_handle = context.scenes.add(scene);
 
                         // Notify plugins about newly loaded scene.
@@ -1934,12 +2050,21 @@ impl Engine {
         lag: &mut f32,
         switches: FxHashMap<Handle<Scene>, GraphUpdateSwitches>,
     ) {
-        self.resource_manager.state().update(dt);
-        self.handle_model_events();
+        // Pre-update is the first thing, that the engine does in every frame, so it is the right
+        // place to start a new frame of the profiler.
+        profiler::next_frame();
+        scope_profile!();
+
+        {
+            let _memory_scope = memory::scope(MemorySubsystem::Resources);
+            self.resource_manager.state().update(dt);
+            self.handle_model_events();
+        }
 
         let window_size = if let GraphicsContext::Initialized(ctx) = &mut self.graphics_context {
             let inner_size = ctx.window.inner_size();
             let window_size = Vector2::new(inner_size.width as f32, inner_size.height as f32);
+            let _memory_scope = memory::scope(MemorySubsystem::Renderer);
             ctx.renderer.update_caches(dt);
             window_size
         } else {
@@ -1960,6 +2085,7 @@ impl Engine {
                         }
                     });
 
+            let _memory_scope = memory::scope(MemorySubsystem::Scene);
             scene.update(
                 frame_size,
                 dt,
@@ -1967,20 +2093,44 @@ impl Engine {
             );
         }
 
+        self.poll_gamepads();
         self.update_plugins(dt, window_target, lag);
         self.handle_scripts(dt);
     }
 
+    /// Returns the gamepad input context, if it was initialized successfully. Its events are consumed
+    /// by the engine on every [`Self::pre_update`], use it to query the current state of gamepads.
+    #[cfg(feature = "gamepad")]
+    pub fn gamepads(&self) -> Option<&gilrs::Gilrs> {
+        self.gamepads.as_ref()
+    }
+
+    fn poll_gamepads(&mut self) {
+        #[cfg(feature = "gamepad")]
+        if let Some(gilrs) = self.gamepads.as_mut() {
+            while let Some(event) = gilrs.next_event() {
+                if let Some(os_event) = crate::utils::translate_gamepad_event(&event.event) {
+                    for ui in self.user_interfaces.iter_mut() {
+                        ui.process_os_event(&os_event);
+                    }
+                }
+            }
+        }
+    }
+
     /// Performs post update for the engine.
     ///
     /// Normally, this is called from `Engine::update()`.
     /// You should only call this manually if you don't use that method.
     pub fn post_update(&mut self, dt: f32, ui_update_switches: &UiUpdateSwitches) {
+        scope_profile!();
+
         if let GraphicsContext::Initialized(ref ctx) = self.graphics_context {
             let inner_size = ctx.window.inner_size();
             let window_size = Vector2::new(inner_size.width as f32, inner_size.height as f32);
 
             let time = instant::Instant::now();
+            let _memory_scope = memory::scope(MemorySubsystem::Ui);
             for ui in self.user_interfaces.iter_mut() {
                 ui.update(window_size, dt, ui_update_switches);
             }
@@ -2001,6 +2151,8 @@ impl Engine {
     }
 
     fn handle_scripts(&mut self, dt: f32) {
+        scope_profile!();
+
         let time = instant::Instant::now();
 
         self.script_processor.handle_scripts(
@@ -2116,6 +2268,8 @@ impl Engine {
         window_target: &EventLoopWindowTarget<()>,
         lag: &mut f32,
     ) {
+        scope_profile!();
+
         let time = instant::Instant::now();
 
         if self.plugins_enabled {
@@ -2376,15 +2530,39 @@ impl Engine {
     /// see anything.
     #[inline]
     pub fn render(&mut self) -> Result<(), FrameworkError> {
-        for ui in self.user_interfaces.iter_mut() {
-            ui.draw();
-        }
+        scope_profile!();
+
+        let is_graphics_context_initialized =
+            matches!(self.graphics_context, GraphicsContext::Initialized(_));
+        let scenes = &mut self.scenes;
+        let user_interfaces = &mut self.user_interfaces;
+        // Render data of the scenes is collected on a worker thread, while the user interfaces are
+        // drawn on the main thread. The scenes are borrowed exclusively, so the worker is the only
+        // one, that accesses them.
+        let (render_data, _) = JobSystem::global().overlap(
+            || {
+                let _memory_scope = memory::scope(MemorySubsystem::Renderer);
+                scenes
+                    .pair_iter_mut()
+                    .filter(|(_, scene)| is_graphics_context_initialized && *scene.enabled)
+                    .map(|(handle, scene)| (handle, SceneRenderData::collect(scene)))
+                    .collect::<FxHashMap<_, _>>()
+            },
+            || {
+                let _memory_scope = memory::scope(MemorySubsystem::Ui);
+                for ui in user_interfaces.iter_mut() {
+                    ui.draw();
+                }
+            },
+        );
 
         if let GraphicsContext::Initialized(ref mut ctx) = self.graphics_context {
+            let _memory_scope = memory::scope(MemorySubsystem::Renderer);
             #[cfg(not(target_arch = "wasm32"))]
             {
                 ctx.renderer.render_and_swap_buffers(
                     &self.scenes,
+                    render_data,
                     self.user_interfaces
                         .iter()
                         .map(|ui| ui.get_drawing_context()),
@@ -2397,6 +2575,7 @@ impl Engine {
             {
                 ctx.renderer.render_and_swap_buffers(
                     &self.scenes,
+                    render_data,
                     self.user_interfaces
                         .iter()
                         .map(|ui| ui.get_drawing_context()),
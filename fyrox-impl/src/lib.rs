@@ -20,6 +20,8 @@ pub mod utils;
 
 pub use crate::core::rand;
 pub use fxhash;
+#[cfg(feature = "gamepad")]
+pub use gilrs;
 pub use lazy_static;
 pub use tbc;
 pub use walkdir;
@@ -396,10 +396,31 @@ impl Default for PropertyValue {
 /// As you can see it is only a bit more hard that with the standard shader. The main difference here is
 /// that we using resource manager to get shader instance and the we just use the instance to create
 /// material instance. Then we populate properties as usual.
+///
+/// # Draw order
+///
+/// Every material has a render queue value, render data that uses materials with lower values is
+/// drawn first. Render data with the same render queue value is sorted using the sorting index
+/// provided by scene nodes (back-to-front for transparent things such as sprites and particles).
+/// Use [`Material::RENDER_QUEUE_TRANSPARENT`] or [`Material::RENDER_QUEUE_OVERLAY`] (or any value
+/// in between) to draw a material after the opaque geometry of the same render path:
+///
+/// ```no_run
+/// # use fyrox_impl::{core::sstorage::ImmutableString, material::Material};
+/// let mut material = Material::standard_sprite();
+/// material.set_render_queue(Material::RENDER_QUEUE_OVERLAY);
+/// // Custom scene render passes could select render data of the material by the tag.
+/// material.set_pass_tags(vec![ImmutableString::new("Outline")]);
+/// ```
+///
+/// Pass tags are arbitrary names that are used by custom scene render passes to select render data,
+/// see [`crate::renderer::bundle::RenderDataBundleStorage::bundles_with_tag`] for more info.
 #[derive(Debug, Clone, Reflect)]
 pub struct Material {
     shader: ShaderResource,
     properties: FxHashMap<ImmutableString, PropertyValue>,
+    render_queue: i32,
+    pass_tags: Vec<ImmutableString>,
 }
 
 impl Visit for Material {
@@ -418,6 +439,11 @@ impl Visit for Material {
         shader.visit("Shader", &mut region)?;
         self.shader = shader;
         self.properties.visit("Properties", &mut region)?;
+        // Backward compatibility.
+        if self.render_queue.visit("RenderQueue", &mut region).is_err() {
+            self.render_queue = Self::RENDER_QUEUE_GEOMETRY;
+        }
+        let _ = self.pass_tags.visit("PassTags", &mut region);
 
         Ok(())
     }
@@ -571,7 +597,63 @@ lazy_static! {
     );
 }
 
+lazy_static! {
+    /// Standard vertex animation material. Keep in mind that this material is global, any modification
+    /// of it will reflect on every other usage of it.
+    pub static ref STANDARD_VERTEX_ANIMATION: MaterialResource = MaterialResource::new_ok(
+        "__StandardVertexAnimationMaterial".into(),
+        Material::from_shader(ShaderResource::standard_vertex_animation(), None),
+    );
+}
+
+lazy_static! {
+    /// Standard point cloud material. Keep in mind that this material is global, any modification
+    /// of it will reflect on every other usage of it.
+    pub static ref STANDARD_POINT_CLOUD: MaterialResource = MaterialResource::new_ok(
+        "__StandardPointCloudMaterial".into(),
+        Material::from_shader(ShaderResource::standard_point_cloud(), None),
+    );
+}
+
+lazy_static! {
+    /// Standard water material. Keep in mind that this material is global, any modification
+    /// of it will reflect on every other usage of it.
+    pub static ref STANDARD_WATER: MaterialResource = MaterialResource::new_ok(
+        "__StandardWaterMaterial".into(),
+        Material::standard_water(),
+    );
+}
+
+lazy_static! {
+    /// Standard sky material. Keep in mind that this material is global, any modification
+    /// of it will reflect on every other usage of it.
+    pub static ref STANDARD_SKY: MaterialResource = MaterialResource::new_ok(
+        "__StandardSkyMaterial".into(),
+        Material::standard_sky(),
+    );
+}
+
+lazy_static! {
+    /// Standard precipitation material. Keep in mind that this material is global, any modification
+    /// of it will reflect on every other usage of it.
+    pub static ref STANDARD_PRECIPITATION: MaterialResource = MaterialResource::new_ok(
+        "__StandardPrecipitationMaterial".into(),
+        Material::standard_precipitation(),
+    );
+}
+
 impl Material {
+    /// Render queue of things that should be drawn before everything else (skies, backgrounds, etc.).
+    pub const RENDER_QUEUE_BACKGROUND: i32 = 1000;
+    /// Default render queue of every material.
+    pub const RENDER_QUEUE_GEOMETRY: i32 = 2000;
+    /// Render queue of alpha-tested geometry (foliage, fences, etc.).
+    pub const RENDER_QUEUE_ALPHA_TEST: i32 = 2450;
+    /// Render queue of transparent things, that should be drawn after opaque geometry.
+    pub const RENDER_QUEUE_TRANSPARENT: i32 = 3000;
+    /// Render queue of things that should be drawn on top of everything else (outlines, markers, etc.).
+    pub const RENDER_QUEUE_OVERLAY: i32 = 4000;
+
     /// Creates a new instance of material with the standard shader. For the full list
     /// of properties of the standard material see [shader module docs](self::shader).
     ///
@@ -629,6 +711,43 @@ impl Material {
         Self::from_shader(ShaderResource::standard_terrain(), None)
     }
 
+    /// Creates new instance of standard material that takes positions and normals of vertices from
+    /// vertex animation textures. See [`crate::scene::crowd`] docs for more info.
+    pub fn standard_vertex_animation() -> Self {
+        Self::from_shader(ShaderResource::standard_vertex_animation(), None)
+    }
+
+    /// Creates new instance of standard point cloud material. See [`crate::scene::point_cloud`] docs
+    /// for more info.
+    pub fn standard_point_cloud() -> Self {
+        Self::from_shader(ShaderResource::standard_point_cloud(), None)
+    }
+
+    /// Creates new instance of standard water material. The material is rendered in the transparent
+    /// render queue, so it is drawn after other forward geometry. See [`crate::scene::water`] docs
+    /// for more info.
+    pub fn standard_water() -> Self {
+        let mut material = Self::from_shader(ShaderResource::standard_water(), None);
+        material.set_render_queue(Self::RENDER_QUEUE_TRANSPARENT);
+        material
+    }
+
+    /// Creates new instance of standard sky material. The material is rendered in the background
+    /// render queue, behind everything else. See [`crate::scene::weather`] docs for more info.
+    pub fn standard_sky() -> Self {
+        let mut material = Self::from_shader(ShaderResource::standard_sky(), None);
+        material.set_render_queue(Self::RENDER_QUEUE_BACKGROUND);
+        material
+    }
+
+    /// Creates new instance of standard precipitation material. The material is rendered in the
+    /// transparent render queue. See [`crate::scene::weather`] docs for more info.
+    pub fn standard_precipitation() -> Self {
+        let mut material = Self::from_shader(ShaderResource::standard_precipitation(), None);
+        material.set_render_queue(Self::RENDER_QUEUE_TRANSPARENT);
+        material
+    }
+
     /// Creates a new material instance with given shader. Each property will have default values
     /// defined in the shader.
     ///
@@ -679,6 +798,8 @@ impl Material {
         Self {
             shader,
             properties: property_values,
+            render_queue: Self::RENDER_QUEUE_GEOMETRY,
+            pass_tags: Default::default(),
         }
     }
 
@@ -695,6 +816,8 @@ impl Material {
         let mut material = Material {
             shader: Default::default(),
             properties: Default::default(),
+            render_queue: Self::RENDER_QUEUE_GEOMETRY,
+            pass_tags: Default::default(),
         };
         let mut visitor = Visitor::load_from_memory(&content)?;
         visitor.blackboard.register(Arc::new(resource_manager));
@@ -914,6 +1037,33 @@ impl Material {
     pub fn properties(&self) -> &FxHashMap<ImmutableString, PropertyValue> {
         &self.properties
     }
+
+    /// Sets new render queue value of the material and returns the old one. Render data with lower
+    /// values is drawn first. See [`Material::RENDER_QUEUE_GEOMETRY`] and other constants for the
+    /// values used by the engine.
+    pub fn set_render_queue(&mut self, render_queue: i32) -> i32 {
+        std::mem::replace(&mut self.render_queue, render_queue)
+    }
+
+    /// Returns current render queue value of the material.
+    pub fn render_queue(&self) -> i32 {
+        self.render_queue
+    }
+
+    /// Sets new pass tags of the material and returns the old ones.
+    pub fn set_pass_tags(&mut self, pass_tags: Vec<ImmutableString>) -> Vec<ImmutableString> {
+        std::mem::replace(&mut self.pass_tags, pass_tags)
+    }
+
+    /// Returns a slice with the pass tags of the material.
+    pub fn pass_tags(&self) -> &[ImmutableString] {
+        &self.pass_tags
+    }
+
+    /// Returns `true` if the material has the given pass tag.
+    pub fn has_pass_tag(&self, tag: &str) -> bool {
+        self.pass_tags.iter().any(|t| t.as_str() == tag)
+    }
 }
 
 /// Shared material is a material instance that can be used across multiple objects. It is useful
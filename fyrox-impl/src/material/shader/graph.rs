@@ -0,0 +1,1239 @@
+//! Shader graph is a node-based way of authoring surface shaders. A graph of texture, math and lighting
+//! nodes is compiled into a regular [`ShaderDefinition`], so the result could be used by any material. See
+//! [`ShaderGraph`] docs for more info.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+        state::LoadError,
+        untyped::ResourceKind,
+        Resource, ResourceData,
+    },
+    core::{
+        algebra::{Vector2, Vector3, Vector4},
+        color::Color,
+        io::FileLoadError,
+        pool::{Handle, Pool},
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    fxhash::{FxHashMap, FxHashSet},
+    material::shader::{
+        include::ShaderIncludes, PropertyDefinition, PropertyKind, RenderPassDefinition,
+        SamplerFallback, ShaderDefinition,
+    },
+    renderer::framework::{
+        framebuffer::{BlendParameters, CullFace, DrawParameters},
+        state::{BlendFactor, BlendFunc, ColorMask},
+    },
+    resource::texture::TextureResource,
+};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// An error that may occur during shader graph resource loading.
+#[derive(Debug)]
+pub enum ShaderGraphError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for ShaderGraphError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            Self::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for ShaderGraphError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for ShaderGraphError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// An error that may occur during shader graph compilation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShaderGraphCompileError {
+    /// The graph does not have an output node.
+    NoOutput,
+    /// The graph has a cycle, that passes through the node.
+    Cycle(Handle<ShaderGraphNode>),
+    /// A parameter has invalid name. Parameter names must be valid GLSL identifiers.
+    InvalidParameterName(String),
+    /// Two parameters with the same name have different types.
+    ParameterTypeMismatch(String),
+}
+
+impl Display for ShaderGraphCompileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoOutput => write!(f, "The graph does not have an output node."),
+            Self::Cycle(node) => write!(f, "The graph has a cycle at node {node}."),
+            Self::InvalidParameterName(name) => {
+                write!(f, "{name:?} is not a valid parameter name.")
+            }
+            Self::ParameterTypeMismatch(name) => {
+                write!(f, "Parameter {name:?} is used with different types.")
+            }
+        }
+    }
+}
+
+/// Type of a value, that flows between nodes of a shader graph.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShaderGraphValueType {
+    /// A single real number.
+    Float,
+    /// Two-dimensional vector.
+    Vector2,
+    /// Three-dimensional vector.
+    Vector3,
+    /// Four-dimensional vector.
+    Vector4,
+}
+
+impl ShaderGraphValueType {
+    /// Returns the name of the respective GLSL type.
+    pub fn glsl_name(self) -> &'static str {
+        match self {
+            Self::Float => "float",
+            Self::Vector2 => "vec2",
+            Self::Vector3 => "vec3",
+            Self::Vector4 => "vec4",
+        }
+    }
+
+    /// Returns an expression, that converts the given expression of this type to the other type. Scalars
+    /// are splatted, vectors are either truncated or extended with zeros (the last component of a
+    /// four-dimensional vector is set to one).
+    pub fn convert(self, expression: &str, to: Self) -> String {
+        match (self, to) {
+            (from, to) if from == to => expression.to_string(),
+            (Self::Float, to) => format!("{}({expression})", to.glsl_name()),
+            (_, Self::Float) => format!("({expression}).x"),
+            (Self::Vector2, Self::Vector3) => format!("vec3({expression}, 0.0)"),
+            (Self::Vector2, Self::Vector4) => format!("vec4({expression}, 0.0, 1.0)"),
+            (Self::Vector3, Self::Vector4) => format!("vec4({expression}, 1.0)"),
+            (_, to) => format!("{}({expression})", to.glsl_name()),
+        }
+    }
+}
+
+/// Description of an input socket of a shader graph node.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ShaderGraphInput {
+    /// Name of the input.
+    pub name: &'static str,
+    /// Type of the input. `None` means that the input accepts any type and the node works with the widest
+    /// type of its generic inputs.
+    pub value_type: Option<ShaderGraphValueType>,
+    /// GLSL expression, that is used when the input is not connected.
+    pub default: &'static str,
+}
+
+const fn input(
+    name: &'static str,
+    value_type: ShaderGraphValueType,
+    default: &'static str,
+) -> ShaderGraphInput {
+    ShaderGraphInput {
+        name,
+        value_type: Some(value_type),
+        default,
+    }
+}
+
+const fn generic(name: &'static str, default: &'static str) -> ShaderGraphInput {
+    ShaderGraphInput {
+        name,
+        value_type: None,
+        default,
+    }
+}
+
+const OUTPUT_INPUTS: [ShaderGraphInput; 7] = [
+    input("Albedo", ShaderGraphValueType::Vector3, "vec3(1.0)"),
+    input("Alpha", ShaderGraphValueType::Float, "1.0"),
+    input(
+        "Normal",
+        ShaderGraphValueType::Vector3,
+        "vec3(0.0, 0.0, 1.0)",
+    ),
+    input("Metallic", ShaderGraphValueType::Float, "0.0"),
+    input("Roughness", ShaderGraphValueType::Float, "1.0"),
+    input("Ambient Occlusion", ShaderGraphValueType::Float, "1.0"),
+    input("Emission", ShaderGraphValueType::Vector3, "vec3(0.0)"),
+];
+
+const TEXTURE_INPUTS: [ShaderGraphInput; 1] =
+    [input("UV", ShaderGraphValueType::Vector2, "texCoord")];
+
+const ADDITIVE_INPUTS: [ShaderGraphInput; 2] = [generic("A", "0.0"), generic("B", "0.0")];
+
+const MULTIPLICATIVE_INPUTS: [ShaderGraphInput; 2] = [generic("A", "1.0"), generic("B", "1.0")];
+
+const LERP_INPUTS: [ShaderGraphInput; 3] = [
+    generic("A", "0.0"),
+    generic("B", "1.0"),
+    generic("T", "0.5"),
+];
+
+const UNARY_INPUTS: [ShaderGraphInput; 1] = [generic("A", "0.0")];
+
+const CHANNEL_INPUTS: [ShaderGraphInput; 1] =
+    [input("Value", ShaderGraphValueType::Vector4, "vec4(0.0)")];
+
+const COMBINE_INPUTS: [ShaderGraphInput; 4] = [
+    input("X", ShaderGraphValueType::Float, "0.0"),
+    input("Y", ShaderGraphValueType::Float, "0.0"),
+    input("Z", ShaderGraphValueType::Float, "0.0"),
+    input("W", ShaderGraphValueType::Float, "1.0"),
+];
+
+const FRESNEL_INPUTS: [ShaderGraphInput; 2] = [
+    input("Normal", ShaderGraphValueType::Vector3, "normalize(normal)"),
+    input("Power", ShaderGraphValueType::Float, "5.0"),
+];
+
+const NORMAL_MAP_INPUTS: [ShaderGraphInput; 1] = [input(
+    "Sample",
+    ShaderGraphValueType::Vector4,
+    "vec4(0.5, 0.5, 1.0, 1.0)",
+)];
+
+/// A component of a vector.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    Debug,
+    PartialEq,
+    Eq,
+    Reflect,
+    Visit,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "0b6f2d8e-3a71-4c95-8e24-d1f7a9c35b60")]
+pub enum ShaderGraphChannel {
+    /// First component (red).
+    #[default]
+    X,
+    /// Second component (green).
+    Y,
+    /// Third component (blue).
+    Z,
+    /// Fourth component (alpha).
+    W,
+}
+
+/// Kind of a shader graph node, that defines its inputs and what it computes.
+#[derive(
+    Clone, Debug, PartialEq, Reflect, Visit, AsRefStr, EnumString, VariantNames, TypeUuidProvider,
+)]
+#[type_uuid(id = "5d3a9e61-c8f2-4b07-a4d9-6e1b2c7f8a35")]
+pub enum ShaderGraphNodeKind {
+    /// Final surface properties. There should be exactly one output node in a graph.
+    Output,
+    /// A real number. If the parameter name is not empty, the value is promoted to a material property.
+    Float {
+        /// Value of the node.
+        value: f32,
+        /// Name of the material property.
+        parameter: String,
+    },
+    /// Two-dimensional vector. If the parameter name is not empty, the value is promoted to a material
+    /// property.
+    Vector2 {
+        /// Value of the node.
+        value: Vector2<f32>,
+        /// Name of the material property.
+        parameter: String,
+    },
+    /// Three-dimensional vector. If the parameter name is not empty, the value is promoted to a material
+    /// property.
+    Vector3 {
+        /// Value of the node.
+        value: Vector3<f32>,
+        /// Name of the material property.
+        parameter: String,
+    },
+    /// Four-dimensional vector. If the parameter name is not empty, the value is promoted to a material
+    /// property.
+    Vector4 {
+        /// Value of the node.
+        value: Vector4<f32>,
+        /// Name of the material property.
+        parameter: String,
+    },
+    /// An sRGB color, it is converted to linear color space. If the parameter name is not empty, the
+    /// value is promoted to a material property.
+    Color {
+        /// Value of the node.
+        value: Color,
+        /// Name of the material property.
+        parameter: String,
+    },
+    /// Samples a texture, that is always a material property.
+    Texture {
+        /// Name of the material property.
+        parameter: String,
+        /// A texture, that is used by default. Only textures loaded from files could be used as defaults.
+        default: Option<TextureResource>,
+        /// A value, that is used when there is no texture.
+        fallback: SamplerFallback,
+    },
+    /// Primary texture coordinates of a mesh.
+    TexCoord,
+    /// Secondary texture coordinates of a mesh (usually used for lightmaps).
+    SecondTexCoord,
+    /// World-space position of a fragment.
+    WorldPosition,
+    /// World-space normal of a fragment.
+    WorldNormal,
+    /// World-space direction from a fragment to the camera.
+    ViewDirection,
+    /// `A + B`
+    Add,
+    /// `A - B`
+    Subtract,
+    /// `A * B`
+    Multiply,
+    /// `A / B`
+    Divide,
+    /// Linear interpolation between `A` and `B`.
+    Lerp,
+    /// `A` raised to the power of `B`.
+    Power,
+    /// Dot product of `A` and `B`.
+    Dot,
+    /// Normalized vector.
+    Normalize,
+    /// `1 - A`
+    OneMinus,
+    /// Clamps the value to `[0; 1]` range.
+    Saturate,
+    /// Absolute value.
+    Abs,
+    /// Sine of the value.
+    Sine,
+    /// Cosine of the value.
+    Cosine,
+    /// A single component of a vector.
+    Channel {
+        /// Component to extract.
+        channel: ShaderGraphChannel,
+    },
+    /// Makes a four-dimensional vector from separate components.
+    Combine,
+    /// Fresnel term, which is close to one at grazing angles and close to zero when a surface faces the
+    /// camera.
+    Fresnel,
+    /// Unpacks a tangent-space normal from a normal map sample.
+    NormalMap,
+}
+
+impl Default for ShaderGraphNodeKind {
+    fn default() -> Self {
+        Self::Float {
+            value: 0.0,
+            parameter: Default::default(),
+        }
+    }
+}
+
+fn float_literal(value: f32) -> String {
+    format!("{value:?}")
+}
+
+fn vector_literal(components: &[f32]) -> String {
+    format!(
+        "vec{}({})",
+        components.len(),
+        components
+            .iter()
+            .map(|v| float_literal(*v))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+impl ShaderGraphNodeKind {
+    /// Returns a list of input sockets of the node.
+    pub fn inputs(&self) -> &'static [ShaderGraphInput] {
+        match self {
+            Self::Output => &OUTPUT_INPUTS,
+            Self::Float { .. }
+            | Self::Vector2 { .. }
+            | Self::Vector3 { .. }
+            | Self::Vector4 { .. }
+            | Self::Color { .. }
+            | Self::TexCoord
+            | Self::SecondTexCoord
+            | Self::WorldPosition
+            | Self::WorldNormal
+            | Self::ViewDirection => &[],
+            Self::Texture { .. } => &TEXTURE_INPUTS,
+            Self::Add | Self::Subtract | Self::Dot => &ADDITIVE_INPUTS,
+            Self::Multiply | Self::Divide | Self::Power => &MULTIPLICATIVE_INPUTS,
+            Self::Lerp => &LERP_INPUTS,
+            Self::Normalize
+            | Self::OneMinus
+            | Self::Saturate
+            | Self::Abs
+            | Self::Sine
+            | Self::Cosine => &UNARY_INPUTS,
+            Self::Channel { .. } => &CHANNEL_INPUTS,
+            Self::Combine => &COMBINE_INPUTS,
+            Self::Fresnel => &FRESNEL_INPUTS,
+            Self::NormalMap => &NORMAL_MAP_INPUTS,
+        }
+    }
+
+    /// Returns `true` if the node has an output socket.
+    pub fn has_output(&self) -> bool {
+        !matches!(self, Self::Output)
+    }
+
+    /// Returns a name of the material property, that is defined by the node (if any).
+    pub fn parameter(&self) -> Option<&str> {
+        match self {
+            Self::Float { parameter, .. }
+            | Self::Vector2 { parameter, .. }
+            | Self::Vector3 { parameter, .. }
+            | Self::Vector4 { parameter, .. }
+            | Self::Color { parameter, .. }
+            | Self::Texture { parameter, .. } => Some(parameter.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns a short human-readable description of the node.
+    pub fn title(&self) -> String {
+        match self.parameter() {
+            Some(parameter) if !parameter.is_empty() => {
+                format!("{}: {}", self.as_ref(), parameter)
+            }
+            _ => self.as_ref().to_string(),
+        }
+    }
+}
+
+/// A single node of a shader graph.
+#[derive(Clone, Debug, Default, PartialEq, Reflect, Visit)]
+pub struct ShaderGraphNode {
+    /// Position of the node in the editor.
+    pub position: Vector2<f32>,
+    /// Kind of the node.
+    pub kind: ShaderGraphNodeKind,
+    /// Source nodes of each input socket. [`Handle::NONE`] means that an input is not connected.
+    #[reflect(hidden)]
+    pub inputs: Vec<Handle<ShaderGraphNode>>,
+}
+
+impl ShaderGraphNode {
+    /// Creates new node of the given kind at the given position.
+    pub fn new(kind: ShaderGraphNodeKind, position: Vector2<f32>) -> Self {
+        let mut node = Self {
+            position,
+            kind,
+            inputs: Default::default(),
+        };
+        node.sync_inputs();
+        node
+    }
+
+    /// Makes sure that the node has exactly the same amount of inputs as its kind requires. Must be called
+    /// after the kind of the node was changed.
+    pub fn sync_inputs(&mut self) {
+        self.inputs
+            .resize(self.kind.inputs().len(), Default::default());
+    }
+}
+
+/// Shader graph is a node-based surface shader. It consists of nodes, that are connected with each other,
+/// the output node defines final surface properties (albedo, normal, metallic, roughness, etc.). The graph
+/// could be compiled into a shader definition using [`ShaderGraph::compile`], that has all the standard
+/// render passes, so the shader supports lighting and shadows. Constant nodes with non-empty parameter
+/// names and texture nodes are promoted to material properties.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::algebra::Vector2,
+/// #     material::shader::graph::{ShaderGraph, ShaderGraphNode, ShaderGraphNodeKind},
+/// # };
+/// fn make_graph() -> ShaderGraph {
+///     let mut graph = ShaderGraph::default();
+///     let texture = graph.add_node(ShaderGraphNode::new(
+///         ShaderGraphNodeKind::Texture {
+///             parameter: "diffuseTexture".to_string(),
+///             default: None,
+///             fallback: Default::default(),
+///         },
+///         Vector2::new(0.0, 0.0),
+///     ));
+///     // The first input of the output node is albedo.
+///     graph.connect(texture, graph.output(), 0);
+///     graph
+/// }
+///
+/// let definition = make_graph().compile("MyShader").unwrap();
+/// assert_eq!(definition.properties[0].name, "diffuseTexture");
+/// ```
+#[derive(Clone, Debug, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "9c4e2b7a-1f65-4d38-b0a9-3e8d5c2f7a16")]
+pub struct ShaderGraph {
+    /// Whether to render both sides of polygons or not.
+    pub two_sided: bool,
+    /// Fragments with alpha lower than this value are discarded.
+    #[reflect(min_value = 0.0, max_value = 1.0)]
+    pub alpha_cutoff: f32,
+    #[reflect(hidden)]
+    nodes: Pool<ShaderGraphNode>,
+}
+
+impl Default for ShaderGraph {
+    fn default() -> Self {
+        let mut nodes = Pool::new();
+        let _ = nodes.spawn(ShaderGraphNode::new(
+            ShaderGraphNodeKind::Output,
+            Vector2::new(400.0, 0.0),
+        ));
+        Self {
+            two_sided: false,
+            alpha_cutoff: 0.5,
+            nodes,
+        }
+    }
+}
+
+fn is_valid_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with("fyrox_")
+        && !name.starts_with("gl_")
+}
+
+fn variable_name(handle: Handle<ShaderGraphNode>) -> String {
+    format!("node{}", handle.index())
+}
+
+struct Compiler<'a> {
+    graph: &'a ShaderGraph,
+    code: String,
+    types: FxHashMap<Handle<ShaderGraphNode>, ShaderGraphValueType>,
+    visiting: FxHashSet<Handle<ShaderGraphNode>>,
+    uniforms: String,
+    properties: Vec<PropertyDefinition>,
+}
+
+impl<'a> Compiler<'a> {
+    fn add_parameter(
+        &mut self,
+        name: &str,
+        glsl_type: &str,
+        kind: PropertyKind,
+    ) -> Result<(), ShaderGraphCompileError> {
+        if !is_valid_identifier(name) {
+            return Err(ShaderGraphCompileError::InvalidParameterName(
+                name.to_string(),
+            ));
+        }
+
+        if let Some(existing) = self.properties.iter().find(|p| p.name == name) {
+            return if std::mem::discriminant(&existing.kind) == std::mem::discriminant(&kind) {
+                Ok(())
+            } else {
+                Err(ShaderGraphCompileError::ParameterTypeMismatch(
+                    name.to_string(),
+                ))
+            };
+        }
+
+        writeln!(self.uniforms, "uniform {glsl_type} {name};").unwrap();
+        self.properties.push(PropertyDefinition {
+            name: name.to_string(),
+            kind,
+        });
+
+        Ok(())
+    }
+
+    /// Returns an expression and its type for every input of the node.
+    fn inputs(
+        &mut self,
+        node: &ShaderGraphNode,
+    ) -> Result<Vec<(String, Option<ShaderGraphValueType>)>, ShaderGraphCompileError> {
+        let mut inputs = Vec::new();
+        for (i, descriptor) in node.kind.inputs().iter().enumerate() {
+            match node
+                .inputs
+                .get(i)
+                .cloned()
+                .filter(|source| self.graph.nodes.is_valid_handle(*source))
+            {
+                Some(source) => {
+                    let value_type = self.visit(source)?;
+                    inputs.push((variable_name(source), Some(value_type)));
+                }
+                None => inputs.push((descriptor.default.to_string(), None)),
+            }
+        }
+        Ok(inputs)
+    }
+
+    fn visit(
+        &mut self,
+        handle: Handle<ShaderGraphNode>,
+    ) -> Result<ShaderGraphValueType, ShaderGraphCompileError> {
+        if let Some(value_type) = self.types.get(&handle) {
+            return Ok(*value_type);
+        }
+
+        if !self.visiting.insert(handle) {
+            return Err(ShaderGraphCompileError::Cycle(handle));
+        }
+
+        let node = &self.graph.nodes[handle];
+        let inputs = self.inputs(node)?;
+        let (value_type, expression) = self.expression(handle, &node.kind, &inputs)?;
+
+        writeln!(
+            self.code,
+            "    {} {} = {};",
+            value_type.glsl_name(),
+            variable_name(handle),
+            expression
+        )
+        .unwrap();
+
+        self.visiting.remove(&handle);
+        self.types.insert(handle, value_type);
+
+        Ok(value_type)
+    }
+
+    fn expression(
+        &mut self,
+        handle: Handle<ShaderGraphNode>,
+        kind: &ShaderGraphNodeKind,
+        inputs: &[(String, Option<ShaderGraphValueType>)],
+    ) -> Result<(ShaderGraphValueType, String), ShaderGraphCompileError> {
+        use ShaderGraphNodeKind as Kind;
+        use ShaderGraphValueType as Type;
+
+        // Converts typed inputs to their declared types, generic inputs are converted to the widest type
+        // of connected generic inputs.
+        let generic_type = kind
+            .inputs()
+            .iter()
+            .zip(inputs)
+            .filter(|(descriptor, _)| descriptor.value_type.is_none())
+            .filter_map(|(_, (_, value_type))| *value_type)
+            .max()
+            .unwrap_or(Type::Float);
+        let args = kind
+            .inputs()
+            .iter()
+            .zip(inputs)
+            .map(|(descriptor, (expression, value_type))| {
+                let target = descriptor.value_type.unwrap_or(generic_type);
+                match value_type {
+                    Some(value_type) => value_type.convert(expression, target),
+                    None if descriptor.value_type.is_none() => {
+                        Type::Float.convert(expression, target)
+                    }
+                    None => expression.clone(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let constant = |this: &mut Self,
+                        parameter: &str,
+                        value_type: Type,
+                        literal: String,
+                        kind: PropertyKind| {
+            if parameter.is_empty() {
+                Ok((value_type, literal))
+            } else {
+                this.add_parameter(parameter, value_type.glsl_name(), kind)?;
+                Ok((value_type, parameter.to_string()))
+            }
+        };
+
+        match kind {
+            Kind::Output => unreachable!("output node cannot be used as a source"),
+            Kind::Float { value, parameter } => constant(
+                self,
+                parameter,
+                Type::Float,
+                float_literal(*value),
+                PropertyKind::Float(*value),
+            ),
+            Kind::Vector2 { value, parameter } => constant(
+                self,
+                parameter,
+                Type::Vector2,
+                vector_literal(value.as_slice()),
+                PropertyKind::Vector2(*value),
+            ),
+            Kind::Vector3 { value, parameter } => constant(
+                self,
+                parameter,
+                Type::Vector3,
+                vector_literal(value.as_slice()),
+                PropertyKind::Vector3(*value),
+            ),
+            Kind::Vector4 { value, parameter } => constant(
+                self,
+                parameter,
+                Type::Vector4,
+                vector_literal(value.as_slice()),
+                PropertyKind::Vector4(*value),
+            ),
+            Kind::Color { value, parameter } => constant(
+                self,
+                parameter,
+                Type::Vector4,
+                vector_literal(value.srgb_to_linear_f32().as_slice()),
+                PropertyKind::Color {
+                    r: value.r,
+                    g: value.g,
+                    b: value.b,
+                    a: value.a,
+                },
+            ),
+            Kind::Texture {
+                parameter,
+                default,
+                fallback,
+            } => {
+                let name = if parameter.is_empty() {
+                    format!("texture{}", handle.index())
+                } else {
+                    parameter.clone()
+                };
+                self.add_parameter(
+                    &name,
+                    "sampler2D",
+                    PropertyKind::Sampler {
+                        default: default.as_ref().and_then(|texture| {
+                            match texture.kind() {
+                                ResourceKind::External(path) => Some(path),
+                                ResourceKind::Embedded => None,
+                            }
+                        }),
+                        fallback: *fallback,
+                    },
+                )?;
+                Ok((Type::Vector4, format!("texture({name}, {})", args[0])))
+            }
+            Kind::TexCoord => Ok((Type::Vector2, "texCoord".to_string())),
+            Kind::SecondTexCoord => Ok((Type::Vector2, "secondTexCoord".to_string())),
+            Kind::WorldPosition => Ok((Type::Vector3, "position".to_string())),
+            Kind::WorldNormal => Ok((Type::Vector3, "normalize(normal)".to_string())),
+            Kind::ViewDirection => Ok((
+                Type::Vector3,
+                "normalize(fyrox_cameraPosition - position)".to_string(),
+            )),
+            Kind::Add => Ok((generic_type, format!("{} + {}", args[0], args[1]))),
+            Kind::Subtract => Ok((generic_type, format!("{} - {}", args[0], args[1]))),
+            Kind::Multiply => Ok((generic_type, format!("{} * {}", args[0], args[1]))),
+            Kind::Divide => Ok((generic_type, format!("{} / {}", args[0], args[1]))),
+            Kind::Lerp => Ok((
+                generic_type,
+                format!("mix({}, {}, {})", args[0], args[1], args[2]),
+            )),
+            Kind::Power => Ok((generic_type, format!("pow({}, {})", args[0], args[1]))),
+            Kind::Dot => Ok((Type::Float, format!("dot({}, {})", args[0], args[1]))),
+            Kind::Normalize => Ok((generic_type, format!("normalize({})", args[0]))),
+            Kind::OneMinus => Ok((
+                generic_type,
+                format!("{} - {}", Type::Float.convert("1.0", generic_type), args[0]),
+            )),
+            Kind::Saturate => Ok((generic_type, format!("clamp({}, 0.0, 1.0)", args[0]))),
+            Kind::Abs => Ok((generic_type, format!("abs({})", args[0]))),
+            Kind::Sine => Ok((generic_type, format!("sin({})", args[0]))),
+            Kind::Cosine => Ok((generic_type, format!("cos({})", args[0]))),
+            Kind::Channel { channel } => Ok((
+                Type::Float,
+                format!(
+                    "({}).{}",
+                    args[0],
+                    match channel {
+                        ShaderGraphChannel::X => "x",
+                        ShaderGraphChannel::Y => "y",
+                        ShaderGraphChannel::Z => "z",
+                        ShaderGraphChannel::W => "w",
+                    }
+                ),
+            )),
+            Kind::Combine => Ok((
+                Type::Vector4,
+                format!("vec4({}, {}, {}, {})", args[0], args[1], args[2], args[3]),
+            )),
+            Kind::Fresnel => Ok((
+                Type::Float,
+                format!(
+                    "pow(1.0 - clamp(dot({}, normalize(fyrox_cameraPosition - position)), 0.0, 1.0), {})",
+                    args[0], args[1]
+                ),
+            )),
+            Kind::NormalMap => Ok((
+                Type::Vector3,
+                format!("normalize(({}).xyz * 2.0 - 1.0)", args[0]),
+            )),
+        }
+    }
+}
+
+const VERTEX_SHADER: &str = r#"
+#include "fyrox/skinning.glsl"
+
+layout(location = 0) in vec3 vertexPosition;
+layout(location = 1) in vec2 vertexTexCoord;
+layout(location = 2) in vec3 vertexNormal;
+layout(location = 3) in vec4 vertexTangent;
+layout(location = 4) in vec4 boneWeights;
+layout(location = 5) in vec4 boneIndices;
+layout(location = 6) in vec2 vertexSecondTexCoord;
+
+uniform mat4 fyrox_worldMatrix;
+uniform mat4 fyrox_worldViewProjection;
+
+out vec3 position;
+out vec3 normal;
+out vec2 texCoord;
+out vec3 tangent;
+out vec3 binormal;
+out vec2 secondTexCoord;
+
+void main()
+{
+    TSkinnedVertex vertex = S_SkinVertex(vertexPosition, vertexNormal, vertexTangent.xyz, boneWeights, boneIndices);
+
+    mat3 nm = mat3(fyrox_worldMatrix);
+    normal = normalize(nm * vertex.normal);
+    tangent = normalize(nm * vertex.tangent);
+    binormal = normalize(vertexTangent.w * cross(normal, tangent));
+    texCoord = vertexTexCoord;
+    position = vec3(fyrox_worldMatrix * vertex.position);
+    secondTexCoord = vertexSecondTexCoord;
+
+    gl_Position = fyrox_worldViewProjection * vertex.position;
+}
+"#;
+
+const GBUFFER_MAIN: &str = r#"
+#include "fyrox/gbuffer.glsl"
+
+void main()
+{
+    TSurface surface = evaluateSurface();
+    if (surface.alpha < ALPHA_CUTOFF) {
+        discard;
+    }
+
+    S_WriteGBuffer(
+        surface.albedo,
+        S_TangentToWorld(surface.normal, tangent, binormal, normal),
+        surface.metallic,
+        surface.roughness,
+        surface.ambientOcclusion,
+        surface.emission,
+        0u
+    );
+}
+"#;
+
+const FORWARD_MAIN: &str = r#"
+out vec4 FragColor;
+
+void main()
+{
+    TSurface surface = evaluateSurface();
+    FragColor = vec4(surface.albedo + surface.emission, surface.alpha);
+}
+"#;
+
+const SHADOW_MAIN: &str = r#"
+void main()
+{
+    if (evaluateSurface().alpha < ALPHA_CUTOFF) discard;
+}
+"#;
+
+const POINT_SHADOW_MAIN: &str = r#"
+uniform vec3 fyrox_lightPosition;
+
+layout(location = 0) out float depth;
+
+void main()
+{
+    if (evaluateSurface().alpha < ALPHA_CUTOFF) discard;
+    depth = length(fyrox_lightPosition - position);
+}
+"#;
+
+impl ShaderGraph {
+    /// Load a shader graph resource from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, ShaderGraphError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut graph = ShaderGraph::default();
+        graph.visit("ShaderGraph", &mut visitor)?;
+        Ok(graph)
+    }
+
+    /// Returns a reference to the nodes container.
+    pub fn nodes(&self) -> &Pool<ShaderGraphNode> {
+        &self.nodes
+    }
+
+    /// Returns a reference to a node.
+    pub fn node(&self, handle: Handle<ShaderGraphNode>) -> Option<&ShaderGraphNode> {
+        self.nodes.try_borrow(handle)
+    }
+
+    /// Returns a reference to a node.
+    pub fn node_mut(&mut self, handle: Handle<ShaderGraphNode>) -> Option<&mut ShaderGraphNode> {
+        self.nodes.try_borrow_mut(handle)
+    }
+
+    /// Adds a new node to the graph.
+    pub fn add_node(&mut self, mut node: ShaderGraphNode) -> Handle<ShaderGraphNode> {
+        node.sync_inputs();
+        self.nodes.spawn(node)
+    }
+
+    /// Removes a node from the graph and disconnects it from every other node.
+    pub fn remove_node(&mut self, handle: Handle<ShaderGraphNode>) -> Option<ShaderGraphNode> {
+        if !self.nodes.is_valid_handle(handle) {
+            return None;
+        }
+        for node in self.nodes.iter_mut() {
+            for input in node.inputs.iter_mut() {
+                if *input == handle {
+                    *input = Handle::NONE;
+                }
+            }
+        }
+        Some(self.nodes.free(handle))
+    }
+
+    /// Returns a handle of the first output node of the graph.
+    pub fn output(&self) -> Handle<ShaderGraphNode> {
+        self.nodes
+            .pair_iter()
+            .find(|(_, node)| node.kind == ShaderGraphNodeKind::Output)
+            .map(|(handle, _)| handle)
+            .unwrap_or_default()
+    }
+
+    /// Connects the output of the source node with the given input of the destination node.
+    pub fn connect(
+        &mut self,
+        source: Handle<ShaderGraphNode>,
+        dest: Handle<ShaderGraphNode>,
+        input: usize,
+    ) {
+        if source != dest && self.nodes.is_valid_handle(source) {
+            if let Some(slot) = self
+                .nodes
+                .try_borrow_mut(dest)
+                .and_then(|dest| dest.inputs.get_mut(input))
+            {
+                *slot = source;
+            }
+        }
+    }
+
+    /// Disconnects the given input of the node.
+    pub fn disconnect(&mut self, dest: Handle<ShaderGraphNode>, input: usize) {
+        if let Some(slot) = self
+            .nodes
+            .try_borrow_mut(dest)
+            .and_then(|dest| dest.inputs.get_mut(input))
+        {
+            *slot = Handle::NONE;
+        }
+    }
+
+    /// Compiles the graph into a shader definition with the given name. The shader has all the standard
+    /// render passes (`GBuffer`, `Forward` and shadow passes).
+    pub fn compile(&self, name: &str) -> Result<ShaderDefinition, ShaderGraphCompileError> {
+        let output = self.output();
+        let output_node = self
+            .nodes
+            .try_borrow(output)
+            .ok_or(ShaderGraphCompileError::NoOutput)?;
+
+        let mut compiler = Compiler {
+            graph: self,
+            code: Default::default(),
+            types: Default::default(),
+            visiting: Default::default(),
+            uniforms: Default::default(),
+            properties: Default::default(),
+        };
+
+        let inputs = compiler.inputs(output_node)?;
+        let surface = OUTPUT_INPUTS
+            .iter()
+            .zip(inputs)
+            .map(|(descriptor, (expression, value_type))| {
+                let target = descriptor.value_type.unwrap();
+                match value_type {
+                    Some(value_type) => value_type.convert(&expression, target),
+                    None => expression,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut common = String::new();
+        writeln!(
+            common,
+            "#define ALPHA_CUTOFF {}",
+            float_literal(self.alpha_cutoff)
+        )
+        .unwrap();
+        common += &compiler.uniforms;
+        common += r#"
+#include "fyrox/utils.glsl"
+
+uniform vec3 fyrox_cameraPosition;
+
+in vec3 position;
+in vec3 normal;
+in vec2 texCoord;
+in vec3 tangent;
+in vec3 binormal;
+in vec2 secondTexCoord;
+
+struct TSurface {
+    vec3 albedo;
+    float alpha;
+    vec3 normal;
+    float metallic;
+    float roughness;
+    float ambientOcclusion;
+    vec3 emission;
+};
+
+TSurface evaluateSurface()
+{
+"#;
+        common += &compiler.code;
+        writeln!(common, "    return TSurface({});\n}}", surface.join(", ")).unwrap();
+
+        let cull_face = if self.two_sided {
+            None
+        } else {
+            Some(CullFace::Back)
+        };
+        let opaque = DrawParameters {
+            cull_face,
+            ..Default::default()
+        };
+        let shadow = DrawParameters {
+            cull_face,
+            color_write: ColorMask::all(false),
+            ..Default::default()
+        };
+        let pass = |name: &str, draw_parameters: DrawParameters, main: &str| RenderPassDefinition {
+            name: name.to_string(),
+            draw_parameters,
+            vertex_shader: VERTEX_SHADER.to_string(),
+            fragment_shader: format!("{common}{main}"),
+        };
+
+        let mut definition = ShaderDefinition {
+            name: name.to_string(),
+            passes: vec![
+                pass("GBuffer", opaque.clone(), GBUFFER_MAIN),
+                pass(
+                    "Forward",
+                    DrawParameters {
+                        blend: Some(BlendParameters {
+                            func: BlendFunc::new(
+                                BlendFactor::SrcAlpha,
+                                BlendFactor::OneMinusSrcAlpha,
+                            ),
+                            equation: Default::default(),
+                        }),
+                        ..opaque.clone()
+                    },
+                    FORWARD_MAIN,
+                ),
+                pass("DirectionalShadow", shadow.clone(), SHADOW_MAIN),
+                pass("SpotShadow", shadow, SHADOW_MAIN),
+                pass("PointShadow", opaque, POINT_SHADOW_MAIN),
+            ],
+            properties: compiler.properties,
+        };
+
+        // The graph uses only the standard library, which is always valid.
+        definition
+            .expand_includes(&ShaderIncludes::standard_library())
+            .expect("standard library includes must be valid");
+
+        Ok(definition)
+    }
+}
+
+impl ResourceData for ShaderGraph {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("ShaderGraph", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+/// Type alias for shader graph resources.
+pub type ShaderGraphResource = Resource<ShaderGraph>;
+
+/// Default implementation for shader graph loading.
+pub struct ShaderGraphLoader;
+
+impl ResourceLoader for ShaderGraphLoader {
+    fn extensions(&self) -> &[&str] {
+        &["shadergraph"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <ShaderGraph as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let graph = ShaderGraph::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(graph))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        material::shader::{
+            graph::{ShaderGraph, ShaderGraphCompileError, ShaderGraphNode, ShaderGraphNodeKind},
+            PropertyKind,
+        },
+    };
+
+    #[test]
+    fn test_shader_graph_compile() {
+        let mut graph = ShaderGraph::default();
+        let output = graph.output();
+        let color = graph.add_node(ShaderGraphNode::new(
+            ShaderGraphNodeKind::Float {
+                value: 0.25,
+                parameter: "tint".to_string(),
+            },
+            Vector2::default(),
+        ));
+        let multiply = graph.add_node(ShaderGraphNode::new(
+            ShaderGraphNodeKind::Multiply,
+            Vector2::default(),
+        ));
+        let uv = graph.add_node(ShaderGraphNode::new(
+            ShaderGraphNodeKind::TexCoord,
+            Vector2::default(),
+        ));
+        graph.connect(color, multiply, 0);
+        graph.connect(uv, multiply, 1);
+        graph.connect(multiply, output, 0);
+
+        let definition = graph.compile("Test").unwrap();
+        assert_eq!(definition.passes.len(), 5);
+        assert_eq!(definition.properties.len(), 1);
+        assert_eq!(definition.properties[0].name, "tint");
+        assert_eq!(definition.properties[0].kind, PropertyKind::Float(0.25));
+
+        let fragment_shader = &definition.passes[0].fragment_shader;
+        assert!(fragment_shader.contains("uniform float tint;"));
+        // The scalar is splatted to match the vector input, the result is extended to match albedo.
+        assert!(fragment_shader.contains(&format!(
+            "vec2 node{} = vec2(node{}) * node{};",
+            multiply.index(),
+            color.index(),
+            uv.index()
+        )));
+        assert!(fragment_shader.contains(&format!("vec3(node{}, 0.0)", multiply.index())));
+        // Library includes are expanded in place.
+        assert!(definition
+            .passes
+            .iter()
+            .all(|pass| !pass.vertex_shader.contains("#include")
+                && !pass.fragment_shader.contains("#include")));
+
+        // Cycles are not allowed.
+        graph.connect(multiply, color, 0);
+        let add = graph.add_node(ShaderGraphNode::new(
+            ShaderGraphNodeKind::Add,
+            Vector2::default(),
+        ));
+        graph.connect(add, multiply, 0);
+        graph.connect(multiply, add, 0);
+        assert!(matches!(
+            graph.compile("Test"),
+            Err(ShaderGraphCompileError::Cycle(_))
+        ));
+
+        // Removal breaks the cycle.
+        graph.remove_node(add);
+        assert_eq!(graph.node(multiply).unwrap().inputs[0], Default::default());
+        assert!(graph.compile("Test").is_ok());
+    }
+}
@@ -0,0 +1,278 @@
+//! Include system for shaders. Shader sources could contain `#include "name"` directives, that are
+//! replaced with the contents of either a snippet from the standard library (see [`STANDARD_LIBRARY`])
+//! or a file relative to the shader file. See [`ShaderIncludes`] docs for more info.
+
+use crate::{
+    asset::io::ResourceIo,
+    core::io::FileLoadError,
+    fxhash::{FxHashMap, FxHashSet},
+};
+use std::{
+    fmt::{Display, Formatter},
+    path::Path,
+};
+
+/// Standard library of shader snippets. Every snippet could be included into any shader using its
+/// name, for example `#include "fyrox/lighting.glsl"`.
+pub const STANDARD_LIBRARY: [(&str, &str); 7] = [
+    ("fyrox/skinning.glsl", include_str!("library/skinning.glsl")),
+    ("fyrox/gbuffer.glsl", include_str!("library/gbuffer.glsl")),
+    ("fyrox/lighting.glsl", include_str!("library/lighting.glsl")),
+    ("fyrox/utils.glsl", include_str!("library/utils.glsl")),
+    (
+        "fyrox/triplanar.glsl",
+        include_str!("library/triplanar.glsl"),
+    ),
+    (
+        "fyrox/vertex_animation.glsl",
+        include_str!("library/vertex_animation.glsl"),
+    ),
+    (
+        "fyrox/soft_particles.glsl",
+        include_str!("library/soft_particles.glsl"),
+    ),
+];
+
+/// An error that may occur during include resolution.
+#[derive(Debug)]
+pub enum IncludeError {
+    /// An `#include` directive has invalid syntax.
+    InvalidDirective {
+        /// Line number (starting from one) in the source, that contains the directive.
+        line: usize,
+    },
+    /// There is no such snippet in the standard library and there is no such file.
+    NotFound(String),
+    /// Included file cannot be loaded.
+    Io {
+        /// Name of the include.
+        name: String,
+        /// Actual error.
+        error: FileLoadError,
+    },
+    /// Included file is not a valid UTF-8 text.
+    InvalidUtf8(String),
+    /// Includes form a cycle. Contains the chain of includes, the last element is the same as one of
+    /// the previous ones.
+    Cycle(Vec<String>),
+}
+
+impl Display for IncludeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidDirective { line } => {
+                write!(f, "Invalid #include directive at line {line}.")
+            }
+            Self::NotFound(name) => write!(f, "Unable to find {name:?} include."),
+            Self::Io { name, error } => {
+                write!(f, "Unable to load {name:?} include. Reason: {error:?}")
+            }
+            Self::InvalidUtf8(name) => write!(f, "Include {name:?} is not a valid UTF-8 text."),
+            Self::Cycle(chain) => write!(f, "Cyclic include: {}", chain.join(" -> ")),
+        }
+    }
+}
+
+/// Parses the line and returns a name of the include, if the line is an `#include` directive. Both
+/// `#include "name"` and `#include <name>` forms are supported.
+fn parse_directive(line: &str, line_number: usize) -> Result<Option<&str>, IncludeError> {
+    let Some(rest) = line.trim().strip_prefix("#include") else {
+        return Ok(None);
+    };
+    let rest = rest.trim();
+    rest.strip_prefix('"')
+        .and_then(|r| r.strip_suffix('"'))
+        .or_else(|| rest.strip_prefix('<').and_then(|r| r.strip_suffix('>')))
+        .filter(|name| !name.is_empty())
+        .map(Some)
+        .ok_or(IncludeError::InvalidDirective { line: line_number })
+}
+
+/// Returns names of every include in the source.
+pub fn include_directives(source: &str) -> Result<Vec<&str>, IncludeError> {
+    let mut names = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        if let Some(name) = parse_directive(line, i + 1)? {
+            names.push(name);
+        }
+    }
+    Ok(names)
+}
+
+/// A set of named sources, that could be included into shaders. Every source is included at most once
+/// per shader (as if it has an include guard), so snippets could safely include each other. Cyclic
+/// includes are reported as errors.
+///
+/// ```rust
+/// # use fyrox_impl::material::shader::include::ShaderIncludes;
+/// let mut includes = ShaderIncludes::standard_library();
+/// includes.add("common.glsl", "#include \"fyrox/utils.glsl\"\nfloat twice(float x) { return x * 2.0; }");
+///
+/// let source = includes
+///     .expand("#include \"common.glsl\"\n#include \"fyrox/utils.glsl\"\nvoid main() {}")
+///     .unwrap();
+/// // The utilities are included only once.
+/// assert_eq!(source.matches("float S_Saturate(float x)").count(), 1);
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct ShaderIncludes {
+    sources: FxHashMap<String, String>,
+}
+
+impl ShaderIncludes {
+    /// Creates a new set of includes, that contains the [`STANDARD_LIBRARY`].
+    pub fn standard_library() -> Self {
+        let mut includes = Self::default();
+        for (name, source) in STANDARD_LIBRARY {
+            includes.add(name, source);
+        }
+        includes
+    }
+
+    /// Adds a new source with the given name. Previous source with the same name will be replaced.
+    pub fn add(&mut self, name: impl Into<String>, source: impl Into<String>) {
+        self.sources.insert(name.into(), source.into());
+    }
+
+    /// Returns a source with the given name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.sources.get(name).map(|s| s.as_str())
+    }
+
+    /// Loads every file, that is included by the source (directly or indirectly) and not yet in the set.
+    /// File names are relative to the given directory.
+    pub async fn load_dependencies(
+        &mut self,
+        source: &str,
+        directory: &Path,
+        io: &dyn ResourceIo,
+    ) -> Result<(), IncludeError> {
+        let mut queue = include_directives(source)?
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        while let Some(name) = queue.pop() {
+            if self.sources.contains_key(&name) {
+                continue;
+            }
+            let bytes =
+                io.load_file(&directory.join(&name))
+                    .await
+                    .map_err(|error| match error {
+                        FileLoadError::Io(ref io_error)
+                            if io_error.kind() == std::io::ErrorKind::NotFound =>
+                        {
+                            IncludeError::NotFound(name.clone())
+                        }
+                        error => IncludeError::Io {
+                            name: name.clone(),
+                            error,
+                        },
+                    })?;
+            let included =
+                String::from_utf8(bytes).map_err(|_| IncludeError::InvalidUtf8(name.clone()))?;
+            queue.extend(
+                include_directives(&included)?
+                    .into_iter()
+                    .map(|name| name.to_string()),
+            );
+            self.sources.insert(name, included);
+        }
+        Ok(())
+    }
+
+    /// Replaces every `#include` directive in the source with the respective source from the set.
+    pub fn expand(&self, source: &str) -> Result<String, IncludeError> {
+        let mut output = String::with_capacity(source.len());
+        let mut stack = Vec::new();
+        let mut included = FxHashSet::default();
+        self.expand_recursive(source, &mut stack, &mut included, &mut output)?;
+        Ok(output)
+    }
+
+    fn expand_recursive<'a>(
+        &'a self,
+        source: &'a str,
+        stack: &mut Vec<&'a str>,
+        included: &mut FxHashSet<&'a str>,
+        output: &mut String,
+    ) -> Result<(), IncludeError> {
+        for (i, line) in source.lines().enumerate() {
+            let Some(name) = parse_directive(line, i + 1)? else {
+                output.push_str(line);
+                output.push('\n');
+                continue;
+            };
+
+            if stack.contains(&name) {
+                let mut chain = stack.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+                chain.push(name.to_string());
+                return Err(IncludeError::Cycle(chain));
+            }
+
+            if !included.insert(name) {
+                continue;
+            }
+
+            let included_source = self
+                .get(name)
+                .ok_or_else(|| IncludeError::NotFound(name.to_string()))?;
+
+            output.push_str(&format!("// include \"{name}\"\n"));
+            stack.push(name);
+            self.expand_recursive(included_source, stack, included, output)?;
+            stack.pop();
+            output.push_str(&format!("// end of include \"{name}\"\n"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::material::shader::include::{IncludeError, ShaderIncludes, STANDARD_LIBRARY};
+
+    #[test]
+    fn test_include_expansion() {
+        let mut includes = ShaderIncludes::default();
+        includes.add("a.glsl", "#include \"b.glsl\"\nfloat a;");
+        includes.add("b.glsl", "float b;");
+
+        assert_eq!(
+            includes
+                .expand("#include <a.glsl>\n  #include \"b.glsl\"\nvoid main() {}")
+                .unwrap(),
+            "// include \"a.glsl\"\n// include \"b.glsl\"\nfloat b;\n// end of include \"b.glsl\"\n\
+            float a;\n// end of include \"a.glsl\"\nvoid main() {}\n"
+        );
+
+        assert!(matches!(
+            includes.expand("#include \"c.glsl\""),
+            Err(IncludeError::NotFound(name)) if name == "c.glsl"
+        ));
+        assert!(matches!(
+            includes.expand("void main() {}\n#include c.glsl"),
+            Err(IncludeError::InvalidDirective { line: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_include_cycle() {
+        let mut includes = ShaderIncludes::default();
+        includes.add("a.glsl", "#include \"b.glsl\"");
+        includes.add("b.glsl", "#include \"a.glsl\"");
+
+        match includes.expand("#include \"a.glsl\"") {
+            Err(IncludeError::Cycle(chain)) => assert_eq!(chain, ["a.glsl", "b.glsl", "a.glsl"]),
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_standard_library() {
+        let includes = ShaderIncludes::standard_library();
+        for (name, _) in STANDARD_LIBRARY {
+            assert!(includes.expand(&format!("#include \"{name}\"")).is_ok());
+        }
+    }
+}
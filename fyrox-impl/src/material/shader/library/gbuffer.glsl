@@ -0,0 +1,28 @@
+// Outputs of the GBuffer render pass. Use `S_WriteGBuffer` to fill every output at once.
+
+layout(location = 0) out vec4 outColor;
+layout(location = 1) out vec4 outNormal;
+layout(location = 2) out vec4 outAmbient;
+layout(location = 3) out vec4 outMaterial;
+layout(location = 4) out uint outDecalMask;
+
+// Mask of linked lights, that must not affect the surface. Set by the renderer.
+uniform uint fyrox_lightLinkMask;
+
+// Writes surface properties to the GBuffer. The normal must be in world space, emission is added
+// to the ambient lighting. Decals are projected only on the surfaces with the same decal layer.
+void S_WriteGBuffer(
+    vec3 albedo,
+    vec3 worldNormal,
+    float metallic,
+    float roughness,
+    float ambientOcclusion,
+    vec3 emission,
+    uint decalLayer)
+{
+    outColor = vec4(albedo, 1.0);
+    outNormal = vec4(normalize(worldNormal) * 0.5 + 0.5, 1.0);
+    outMaterial = vec4(metallic, roughness, ambientOcclusion, 1.0);
+    outAmbient = vec4(emission, 1.0);
+    outDecalMask = (decalLayer & 0xFFu) | fyrox_lightLinkMask;
+}
@@ -0,0 +1,39 @@
+// Simple lighting models for shaders that do their own lighting (usually in the Forward pass).
+// Physically-based lighting of the deferred renderer is available via `S_PBR_CalculateLight`.
+
+// Lambertian diffuse term.
+vec3 S_Lambert(vec3 normal, vec3 lightDirection, vec3 lightColor)
+{
+    return lightColor * max(dot(normal, lightDirection), 0.0);
+}
+
+// Wrapped diffuse term, `wrap` in [0; 1] range softens the terminator (useful for foliage or skin).
+vec3 S_WrappedLambert(vec3 normal, vec3 lightDirection, vec3 lightColor, float wrap)
+{
+    return lightColor * max((dot(normal, lightDirection) + wrap) / (1.0 + wrap), 0.0);
+}
+
+// Blinn-Phong specular term.
+float S_BlinnPhong(vec3 normal, vec3 lightDirection, vec3 viewDirection, float shininess)
+{
+    vec3 h = normalize(lightDirection + viewDirection);
+    return pow(max(dot(normal, h), 0.0), shininess);
+}
+
+// Ambient lighting, that is interpolated between ground and sky colors using the normal.
+vec3 S_HemisphereAmbient(vec3 normal, vec3 skyColor, vec3 groundColor)
+{
+    return mix(groundColor, skyColor, normal.y * 0.5 + 0.5);
+}
+
+// Fresnel term, which is close to one at grazing angles and close to zero when a surface faces the viewer.
+float S_FresnelFactor(vec3 normal, vec3 viewDirection, float power)
+{
+    return pow(1.0 - clamp(dot(normal, viewDirection), 0.0, 1.0), power);
+}
+
+// Cel shading - quantizes lighting intensity to the given amount of bands.
+float S_Quantize(float intensity, float bands)
+{
+    return floor(intensity * bands) / bands;
+}
@@ -0,0 +1,63 @@
+// Blend shapes and skeletal animation for vertex shaders. Declares every built-in uniform that
+// is needed to deform a vertex, so a shader must not declare them again.
+
+uniform bool fyrox_useSkeletalAnimation;
+uniform sampler2D fyrox_boneMatrices;
+uniform sampler3D fyrox_blendShapesStorage;
+uniform float fyrox_blendShapesWeights[128];
+uniform int fyrox_blendShapesCount;
+
+struct TSkinnedVertex {
+    vec4 position;
+    vec3 normal;
+    vec3 tangent;
+};
+
+// Applies blend shapes and then skinning to a vertex in local space.
+TSkinnedVertex S_SkinVertex(vec3 position, vec3 normal, vec3 tangent, vec4 boneWeights, vec4 boneIndices)
+{
+    vec4 inputPosition = vec4(position, 1.0);
+    vec3 inputNormal = normal;
+    vec3 inputTangent = tangent;
+
+    for (int i = 0; i < fyrox_blendShapesCount; ++i) {
+        TBlendShapeOffsets offsets = S_FetchBlendShapeOffsets(fyrox_blendShapesStorage, gl_VertexID, i);
+        float weight = fyrox_blendShapesWeights[i];
+        inputPosition.xyz += offsets.position * weight;
+        inputNormal += offsets.normal * weight;
+        inputTangent += offsets.tangent * weight;
+    }
+
+    TSkinnedVertex result;
+
+    if (fyrox_useSkeletalAnimation)
+    {
+        mat4 m0 = S_FetchMatrix(fyrox_boneMatrices, int(boneIndices.x));
+        mat4 m1 = S_FetchMatrix(fyrox_boneMatrices, int(boneIndices.y));
+        mat4 m2 = S_FetchMatrix(fyrox_boneMatrices, int(boneIndices.z));
+        mat4 m3 = S_FetchMatrix(fyrox_boneMatrices, int(boneIndices.w));
+
+        result.position = m0 * inputPosition * boneWeights.x
+            + m1 * inputPosition * boneWeights.y
+            + m2 * inputPosition * boneWeights.z
+            + m3 * inputPosition * boneWeights.w;
+
+        result.normal = mat3(m0) * inputNormal * boneWeights.x
+            + mat3(m1) * inputNormal * boneWeights.y
+            + mat3(m2) * inputNormal * boneWeights.z
+            + mat3(m3) * inputNormal * boneWeights.w;
+
+        result.tangent = mat3(m0) * inputTangent * boneWeights.x
+            + mat3(m1) * inputTangent * boneWeights.y
+            + mat3(m2) * inputTangent * boneWeights.z
+            + mat3(m3) * inputTangent * boneWeights.w;
+    }
+    else
+    {
+        result.position = inputPosition;
+        result.normal = inputNormal;
+        result.tangent = inputTangent;
+    }
+
+    return result;
+}
@@ -0,0 +1,23 @@
+// Soft particles. Fades out fragments of transparent geometry (sprites, particles, etc.) near
+// intersections with opaque geometry, which hides hard clipping of billboards. Fragment shaders only.
+
+// Converts a value from the depth buffer to linear depth in view space.
+float S_LinearizeDepth(float depth, float zNear, float zFar)
+{
+    return (zFar * zNear) / (zFar - depth * (zFar - zNear));
+}
+
+// Returns opacity in [0; 1] range for the current fragment. It goes from zero to one, when the distance
+// between the fragment and the scene behind it goes from zero to `fadeDistance` (in world units). Zero
+// or negative distance disables fading.
+float S_SoftFade(sampler2D sceneDepth, float zNear, float zFar, float fadeDistance)
+{
+    if (fadeDistance <= 0.0)
+    {
+        return 1.0;
+    }
+    vec2 texCoord = gl_FragCoord.xy / vec2(textureSize(sceneDepth, 0));
+    float sceneLinearDepth = S_LinearizeDepth(texture(sceneDepth, texCoord).r, zNear, zFar);
+    float fragmentLinearDepth = S_LinearizeDepth(gl_FragCoord.z, zNear, zFar);
+    return clamp((sceneLinearDepth - fragmentLinearDepth) / fadeDistance, 0.0, 1.0);
+}
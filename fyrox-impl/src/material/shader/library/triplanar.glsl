@@ -0,0 +1,48 @@
+// Triplanar projection. Textures are projected along the world axes and blended using the world-space
+// normal, so geometry without texture coordinates could be textured without visible stretching.
+
+struct TTriplanarCoords {
+    vec2 x;
+    vec2 y;
+    vec2 z;
+    vec3 weights;
+};
+
+// Computes texture coordinates for each projection and their blend weights. Higher sharpness makes the
+// transitions between the projections narrower.
+TTriplanarCoords S_TriplanarCoords(vec3 worldPosition, vec3 worldNormal, float scale, float sharpness)
+{
+    vec3 p = worldPosition * scale;
+    vec3 w = pow(abs(worldNormal), vec3(max(sharpness, 1.0)));
+
+    TTriplanarCoords coords;
+    coords.x = p.zy;
+    coords.y = p.xz;
+    coords.z = p.xy;
+    coords.weights = w / max(w.x + w.y + w.z, 0.00001);
+    return coords;
+}
+
+vec4 S_TriplanarSample(sampler2D tex, TTriplanarCoords coords)
+{
+    return texture(tex, coords.x) * coords.weights.x
+        + texture(tex, coords.y) * coords.weights.y
+        + texture(tex, coords.z) * coords.weights.z;
+}
+
+// Samples a normal map using triplanar projection and returns a world-space normal. Tangent-space
+// normals of each projection are combined with the world normal using "whiteout" blending.
+vec3 S_TriplanarNormal(sampler2D normalMap, TTriplanarCoords coords, vec3 worldNormal)
+{
+    vec3 nx = texture(normalMap, coords.x).xyz * 2.0 - 1.0;
+    vec3 ny = texture(normalMap, coords.y).xyz * 2.0 - 1.0;
+    vec3 nz = texture(normalMap, coords.z).xyz * 2.0 - 1.0;
+
+    nx = vec3(nx.xy + worldNormal.zy, abs(nx.z) * worldNormal.x);
+    ny = vec3(ny.xy + worldNormal.xz, abs(ny.z) * worldNormal.y);
+    nz = vec3(nz.xy + worldNormal.xy, abs(nz.z) * worldNormal.z);
+
+    return normalize(
+        nx.zyx * coords.weights.x + ny.xzy * coords.weights.y + nz.xyz * coords.weights.z
+    );
+}
@@ -0,0 +1,50 @@
+// General purpose utilities.
+
+float S_Saturate(float x)
+{
+    return clamp(x, 0.0, 1.0);
+}
+
+vec3 S_Saturate(vec3 x)
+{
+    return clamp(x, 0.0, 1.0);
+}
+
+// Maps a value from [inMin; inMax] range to [outMin; outMax] range.
+float S_Remap(float value, float inMin, float inMax, float outMin, float outMax)
+{
+    return outMin + (value - inMin) * (outMax - outMin) / (inMax - inMin);
+}
+
+// Unpacks a tangent-space normal stored in a normal map.
+vec3 S_UnpackNormal(vec4 normalMapSample)
+{
+    return normalize(normalMapSample.xyz * 2.0 - 1.0);
+}
+
+// Transforms a tangent-space normal to world space.
+vec3 S_TangentToWorld(vec3 tangentSpaceNormal, vec3 tangent, vec3 binormal, vec3 normal)
+{
+    return normalize(mat3(tangent, binormal, normal) * tangentSpaceNormal);
+}
+
+// Returns a pseudo-random number in [0; 1] range for the given point.
+float S_Hash(vec2 p)
+{
+    p = fract(p * vec2(123.34, 456.21));
+    p += dot(p, p + 45.32);
+    return fract(p.x * p.y);
+}
+
+// Smooth value noise in [0; 1] range.
+float S_ValueNoise(vec2 p)
+{
+    vec2 i = floor(p);
+    vec2 f = fract(p);
+    vec2 u = f * f * (3.0 - 2.0 * f);
+    float a = S_Hash(i);
+    float b = S_Hash(i + vec2(1.0, 0.0));
+    float c = S_Hash(i + vec2(0.0, 1.0));
+    float d = S_Hash(i + vec2(1.0, 1.0));
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
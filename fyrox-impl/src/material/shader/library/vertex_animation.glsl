@@ -0,0 +1,32 @@
+// Vertex animation textures. Positions and normals of every vertex in every frame are baked into a
+// texture, two texels per vertex (position, normal), frame after frame. Declares every built-in uniform
+// that is needed to animate a vertex, so a shader must not declare them again.
+
+uniform sampler2D fyrox_vertexAnimationTexture;
+// x - index of the first frame, y - index of the second frame, z - blending factor between them.
+uniform vec3 fyrox_vertexAnimationFrames;
+// Amount of vertices in a frame, zero means that there's no vertex animation.
+uniform int fyrox_vertexAnimationVertexCount;
+
+vec4 S_FetchVertexAnimationTexel(int index)
+{
+    int width = textureSize(fyrox_vertexAnimationTexture, 0).x;
+    return texelFetch(fyrox_vertexAnimationTexture, ivec2(index % width, index / width), 0);
+}
+
+// Replaces local position and normal of a vertex with the blended values from the vertex animation
+// texture. Does nothing if an instance has no vertex animation.
+void S_ApplyVertexAnimation(int vertexId, inout vec3 position, inout vec3 normal)
+{
+    if (fyrox_vertexAnimationVertexCount <= 0)
+    {
+        return;
+    }
+
+    int a = (int(fyrox_vertexAnimationFrames.x) * fyrox_vertexAnimationVertexCount + vertexId) * 2;
+    int b = (int(fyrox_vertexAnimationFrames.y) * fyrox_vertexAnimationVertexCount + vertexId) * 2;
+    float t = fyrox_vertexAnimationFrames.z;
+
+    position = mix(S_FetchVertexAnimationTexel(a).xyz, S_FetchVertexAnimationTexel(b).xyz, t);
+    normal = normalize(mix(S_FetchVertexAnimationTexel(a + 1).xyz, S_FetchVertexAnimationTexel(b + 1).xyz, t));
+}
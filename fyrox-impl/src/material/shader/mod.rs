@@ -171,6 +171,10 @@
 //! uniform vec3 fyrox_cameraPosition;
 //! ```
 //!
+//! Built-in properties that are not samplers or arrays are passed to the shader in a single uniform
+//! block per draw call. Declarations of such properties are replaced with this block automatically,
+//! so they must be declared one per line, as in the example above.
+//!
 //! This list will be extended in future releases.
 //!
 //! # Drawing parameters
@@ -214,6 +218,37 @@
 //!      - Stencil options.
 //!      - **Possible values:** [StencilOp](crate::renderer::framework::state::StencilOp)
 //!
+//! # Includes
+//!
+//! Source code of any pass could include shared code using `#include "name"` directive. The name is
+//! either a name of a snippet from the [standard library](include::STANDARD_LIBRARY) or a path to a file
+//! relative to the shader file:
+//!
+//! ```glsl
+//! #include "fyrox/lighting.glsl"
+//! #include "../common/wind.glsl"
+//!
+//! void main()
+//! {
+//!     FragColor = vec4(S_Lambert(normal, lightDirection, lightColor), 1.0);
+//! }
+//! ```
+//!
+//! Every include is inserted at most once per program, so included files could include each other
+//! without include guards. Cyclic includes are reported as errors when the shader is loaded. The
+//! standard library contains the following snippets:
+//!
+//! - `fyrox/skinning.glsl` - blend shapes and skeletal animation for vertex shaders (`S_SkinVertex`).
+//! - `fyrox/gbuffer.glsl` - outputs of the GBuffer pass and `S_WriteGBuffer` function to fill them.
+//! - `fyrox/lighting.glsl` - simple lighting models (Lambert, Blinn-Phong, hemisphere ambient, etc.).
+//! - `fyrox/utils.glsl` - utilities (normal unpacking, remapping, hashing, value noise, etc.).
+//! - `fyrox/triplanar.glsl` - triplanar projection of textures and normal maps.
+//! - `fyrox/vertex_animation.glsl` - positions and normals from vertex animation textures
+//!   (`S_ApplyVertexAnimation`).
+//! - `fyrox/soft_particles.glsl` - depth fade of transparent geometry near opaque geometry (`S_SoftFade`).
+//!
+//! Includes are resolved when the shader is loaded, so a saved shader contains already expanded code.
+//!
 //! # Standard shader
 //!
 //! By default Fyrox uses standard material for rendering, it covers 95% of uses cases and it is very
@@ -227,6 +262,40 @@
 //!
 //! Usually you don't need to get this shader manually, using of [Material::standard](super::Material::standard)
 //! is enough.
+//!
+//! Besides the usual PBR texture set, the standard shader supports:
+//!
+//! - Triplanar mapping (`useTriplanarMapping`, `triplanarScale`, `triplanarSharpness`) - textures are
+//!   projected along the world axes instead of using texture coordinates of a mesh. It is useful for
+//!   procedural geometry without texture coordinates and for meshes placed next to terrains.
+//! - Detail layers (`detailDiffuseTexture`, `detailNormalTexture`, `detailTexCoordScale`,
+//!   `detailStrength`) - additional albedo and normal maps with high tiling, that are blended over the
+//!   base textures. Detail layers fade out between `detailFadeStart` and `detailFadeEnd` distances from the camera to hide
+//!   tiling.
+//!
+//! There's also a standard vertex animation shader ([`ShaderResource::standard_vertex_animation`]), which
+//! has the same properties as the standard shader, but takes positions and normals of vertices from vertex
+//! animation textures. It is used by [crowds](crate::scene::crowd::Crowd).
+//!
+//! Point clouds use the standard point cloud shader ([`ShaderResource::standard_point_cloud`]), it draws
+//! unlit round points and has a single `tint` property.
+//!
+//! Water surfaces use the standard water shader ([`ShaderResource::standard_water`]). It refracts the
+//! scene behind the surface using `fyrox_sceneColor` and `fyrox_sceneDepth` built-in samplers, absorbs
+//! light with the depth of the water and adds foam on the crests of the waves.
+//!
+//! [Weather](crate::scene::weather::Weather) uses two standard shaders: the sky shader
+//! ([`ShaderResource::standard_sky`]) calculates Rayleigh and Mie scattering of the sun light in the
+//! atmosphere, and the precipitation shader ([`ShaderResource::standard_precipitation`]) animates rain
+//! and snow particles on GPU. Precipitation collides with the scene using
+//! `fyrox_precipitationOcclusionMap` (`sampler2D`) and `fyrox_precipitationOcclusionMatrix` (`mat4`)
+//! built-in uniforms, that contain a top-down depth map of the scene around the camera and its
+//! world-to-clip-space matrix.
+//!
+//! [3D text](crate::scene::text3d::Text3D) uses the standard 3D text shader ([`ShaderResource::standard_text3d`])
+//! and its variant without depth test ([`ShaderResource::standard_text3d_overlay`]). Both shaders take glyphs
+//! from a font atlas page (`fontTexture`) and could draw an outline around them (`outlineColor`,
+//! `outlineThickness`).
 
 use crate::{
     asset::{io::ResourceIo, untyped::ResourceKind, Resource, ResourceData, SHADER_RESOURCE_UUID},
@@ -235,11 +304,12 @@ use crate::{
         io::FileLoadError,
         reflect::prelude::*,
         sparse::AtomicIndex,
-        uuid::Uuid,
+        uuid::{uuid, Uuid},
         visitor::prelude::*,
         TypeUuidProvider,
     },
     lazy_static::lazy_static,
+    material::shader::include::{IncludeError, ShaderIncludes},
     renderer::framework::framebuffer::DrawParameters,
 };
 use ron::ser::PrettyConfig;
@@ -253,7 +323,10 @@ use std::{
     io::{Cursor, Write},
     path::{Path, PathBuf},
 };
+use strum_macros::{AsRefStr, EnumString, VariantNames};
 
+pub mod graph;
+pub mod include;
 pub mod loader;
 
 /// A name of the standard shader.
@@ -293,24 +366,81 @@ pub const STANDARD_SPRITE_SHADER_NAME: &str = "StandardSprite";
 /// A source code of the standard terrain shader.
 pub const STANDARD_TERRAIN_SHADER_SRC: &str = include_str!("standard/terrain.shader");
 
+/// A name of the standard vertex animation shader.
+pub const STANDARD_VERTEX_ANIMATION_SHADER_NAME: &str = "StandardVertexAnimation";
+
+/// A source code of the standard vertex animation shader.
+pub const STANDARD_VERTEX_ANIMATION_SHADER_SRC: &str =
+    include_str!("standard/vertex_animation.shader");
+
+/// A name of the standard point cloud shader.
+pub const STANDARD_POINT_CLOUD_SHADER_NAME: &str = "StandardPointCloud";
+
+/// A source code of the standard point cloud shader.
+pub const STANDARD_POINT_CLOUD_SHADER_SRC: &str = include_str!("standard/point_cloud.shader");
+
+/// A name of the standard water shader.
+pub const STANDARD_WATER_SHADER_NAME: &str = "StandardWater";
+
+/// A source code of the standard water shader.
+pub const STANDARD_WATER_SHADER_SRC: &str = include_str!("standard/water.shader");
+
+/// A name of the standard sky shader.
+pub const STANDARD_SKY_SHADER_NAME: &str = "StandardSky";
+
+/// A source code of the standard sky shader.
+pub const STANDARD_SKY_SHADER_SRC: &str = include_str!("standard/sky.shader");
+
+/// A name of the standard precipitation shader.
+pub const STANDARD_PRECIPITATION_SHADER_NAME: &str = "StandardPrecipitation";
+
+/// A source code of the standard precipitation shader.
+pub const STANDARD_PRECIPITATION_SHADER_SRC: &str = include_str!("standard/precipitation.shader");
+
+/// A name of the standard 3D text shader.
+pub const STANDARD_TEXT3D_SHADER_NAME: &str = "StandardText3D";
+
+/// A source code of the standard 3D text shader.
+pub const STANDARD_TEXT3D_SHADER_SRC: &str = include_str!("standard/text3d.shader");
+
+/// A name of the standard 3D text shader without depth test.
+pub const STANDARD_TEXT3D_OVERLAY_SHADER_NAME: &str = "StandardText3DOverlay";
+
+/// A source code of the standard 3D text shader without depth test.
+pub const STANDARD_TEXT3D_OVERLAY_SHADER_SRC: &str = include_str!("standard/text3d_overlay.shader");
+
 /// A list of names of standard shaders.
-pub const STANDARD_SHADER_NAMES: [&str; 6] = [
+pub const STANDARD_SHADER_NAMES: [&str; 13] = [
     STANDARD_SHADER_NAME,
     STANDARD_2D_SHADER_NAME,
     STANDARD_PARTICLE_SYSTEM_SHADER_NAME,
     STANDARD_SPRITE_SHADER_NAME,
     STANDARD_TWOSIDES_SHADER_NAME,
     STANDARD_TERRAIN_SHADER_NAME,
+    STANDARD_VERTEX_ANIMATION_SHADER_NAME,
+    STANDARD_POINT_CLOUD_SHADER_NAME,
+    STANDARD_WATER_SHADER_NAME,
+    STANDARD_SKY_SHADER_NAME,
+    STANDARD_PRECIPITATION_SHADER_NAME,
+    STANDARD_TEXT3D_SHADER_NAME,
+    STANDARD_TEXT3D_OVERLAY_SHADER_NAME,
 ];
 
 /// A list of source code of standard shaders.
-pub const STANDARD_SHADER_SOURCES: [&str; 6] = [
+pub const STANDARD_SHADER_SOURCES: [&str; 13] = [
     STANDARD_SHADER_SRC,
     STANDARD_2D_SHADER_SRC,
     STANDARD_PARTICLE_SYSTEM_SHADER_SRC,
     STANDARD_SPRITE_SHADER_SRC,
     STANDARD_TWOSIDES_SHADER_SRC,
     STANDARD_TERRAIN_SHADER_SRC,
+    STANDARD_VERTEX_ANIMATION_SHADER_SRC,
+    STANDARD_POINT_CLOUD_SHADER_SRC,
+    STANDARD_WATER_SHADER_SRC,
+    STANDARD_SKY_SHADER_SRC,
+    STANDARD_PRECIPITATION_SHADER_SRC,
+    STANDARD_TEXT3D_SHADER_SRC,
+    STANDARD_TEXT3D_OVERLAY_SHADER_SRC,
 ];
 
 /// Internal state of the shader.
@@ -351,7 +481,22 @@ impl TypeUuidProvider for Shader {
 ///
 /// Fallback value is also helpful to catch missing textures, you'll definitely know the texture is
 /// missing by very specific value in the fallback texture.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy, Visit, Eq, Reflect)]
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    PartialEq,
+    Clone,
+    Copy,
+    Visit,
+    Eq,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "2e6c0c1a-8b4f-4e5d-9d37-c51a7f0e9b42")]
 pub enum SamplerFallback {
     /// A 1x1px white texture.
     White,
@@ -506,6 +651,19 @@ impl ShaderDefinition {
     fn from_str(str: &str) -> Result<Self, ShaderError> {
         Ok(ron::de::from_str(str)?)
     }
+
+    /// Replaces `#include` directives in the source code of every render pass with the respective
+    /// sources from the given set. See [`ShaderIncludes`] docs for more info.
+    pub fn expand_includes(&mut self, includes: &ShaderIncludes) -> Result<(), IncludeError> {
+        for pass in self.passes.iter_mut() {
+            for source in [&mut pass.vertex_shader, &mut pass.fragment_shader] {
+                if source.contains("#include") {
+                    *source = includes.expand(source)?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Shader {
@@ -515,18 +673,35 @@ impl Shader {
         io: &dyn ResourceIo,
     ) -> Result<Self, ShaderError> {
         let content = io.load_file(path.as_ref()).await?;
-        Ok(Self {
-            definition: ShaderDefinition::from_buf(content)?,
-            cache_index: Default::default(),
-        })
+        let mut definition = ShaderDefinition::from_buf(content)?;
+
+        // Files are included relative to the shader file.
+        let mut includes = ShaderIncludes::standard_library();
+        let directory = path.as_ref().parent().unwrap_or_else(|| Path::new(""));
+        for pass in definition.passes.iter() {
+            for source in [&pass.vertex_shader, &pass.fragment_shader] {
+                includes.load_dependencies(source, directory, io).await?;
+            }
+        }
+        definition.expand_includes(&includes)?;
+
+        Ok(Self::from_definition(definition))
     }
 
-    /// Creates a shader from string.
+    /// Creates a shader from string. Only the [standard library](include::STANDARD_LIBRARY) could be
+    /// included by such shaders.
     pub fn from_string(str: &str) -> Result<Self, ShaderError> {
-        Ok(Self {
-            definition: ShaderDefinition::from_str(str)?,
+        let mut definition = ShaderDefinition::from_str(str)?;
+        definition.expand_includes(&ShaderIncludes::standard_library())?;
+        Ok(Self::from_definition(definition))
+    }
+
+    /// Creates a shader from the given definition.
+    pub fn from_definition(definition: ShaderDefinition) -> Self {
+        Self {
+            definition,
             cache_index: Default::default(),
-        })
+        }
     }
 }
 
@@ -564,6 +739,9 @@ pub enum ShaderError {
 
     /// A parsing error has occurred.
     ParseError(ron::error::SpannedError),
+
+    /// An include cannot be resolved.
+    Include(IncludeError),
 }
 
 impl Display for ShaderError {
@@ -575,6 +753,9 @@ impl Display for ShaderError {
             ShaderError::ParseError(v) => {
                 write!(f, "A parsing error has occurred {v:?}")
             }
+            ShaderError::Include(v) => {
+                write!(f, "An include error has occurred: {v}")
+            }
         }
     }
 }
@@ -585,6 +766,12 @@ impl From<ron::error::SpannedError> for ShaderError {
     }
 }
 
+impl From<IncludeError> for ShaderError {
+    fn from(e: IncludeError) -> Self {
+        Self::Include(e)
+    }
+}
+
 impl From<FileLoadError> for ShaderError {
     fn from(e: FileLoadError) -> Self {
         Self::Io(e)
@@ -618,6 +805,27 @@ pub trait ShaderResourceExtension: Sized {
     /// Returns an instance of standard two-sides terrain shader.
     fn standard_twosides() -> Self;
 
+    /// Returns an instance of standard vertex animation shader.
+    fn standard_vertex_animation() -> Self;
+
+    /// Returns an instance of standard point cloud shader.
+    fn standard_point_cloud() -> Self;
+
+    /// Returns an instance of standard water shader.
+    fn standard_water() -> Self;
+
+    /// Returns an instance of standard sky shader.
+    fn standard_sky() -> Self;
+
+    /// Returns an instance of standard precipitation shader.
+    fn standard_precipitation() -> Self;
+
+    /// Returns an instance of standard 3D text shader.
+    fn standard_text3d() -> Self;
+
+    /// Returns an instance of standard 3D text shader without depth test.
+    fn standard_text3d_overlay() -> Self;
+
     /// Returns a list of standard shader.
     fn standard_shaders() -> Vec<ShaderResource>;
 }
@@ -651,6 +859,34 @@ impl ShaderResourceExtension for ShaderResource {
         STANDARD_TWOSIDES.clone()
     }
 
+    fn standard_vertex_animation() -> Self {
+        STANDARD_VERTEX_ANIMATION.clone()
+    }
+
+    fn standard_point_cloud() -> Self {
+        STANDARD_POINT_CLOUD.clone()
+    }
+
+    fn standard_water() -> Self {
+        STANDARD_WATER.clone()
+    }
+
+    fn standard_sky() -> Self {
+        STANDARD_SKY.clone()
+    }
+
+    fn standard_precipitation() -> Self {
+        STANDARD_PRECIPITATION.clone()
+    }
+
+    fn standard_text3d() -> Self {
+        STANDARD_TEXT3D.clone()
+    }
+
+    fn standard_text3d_overlay() -> Self {
+        STANDARD_TEXT3D_OVERLAY.clone()
+    }
+
     fn standard_shaders() -> Vec<ShaderResource> {
         vec![
             Self::standard(),
@@ -659,6 +895,13 @@ impl ShaderResourceExtension for ShaderResource {
             Self::standard_sprite(),
             Self::standard_terrain(),
             Self::standard_twosides(),
+            Self::standard_vertex_animation(),
+            Self::standard_point_cloud(),
+            Self::standard_water(),
+            Self::standard_sky(),
+            Self::standard_precipitation(),
+            Self::standard_text3d(),
+            Self::standard_text3d_overlay(),
         ]
     }
 }
@@ -705,6 +948,55 @@ lazy_static! {
     );
 }
 
+lazy_static! {
+    static ref STANDARD_VERTEX_ANIMATION: ShaderResource = ShaderResource::new_ok(
+        STANDARD_VERTEX_ANIMATION_SHADER_NAME.into(),
+        Shader::from_string(STANDARD_VERTEX_ANIMATION_SHADER_SRC).unwrap(),
+    );
+}
+
+lazy_static! {
+    static ref STANDARD_POINT_CLOUD: ShaderResource = ShaderResource::new_ok(
+        STANDARD_POINT_CLOUD_SHADER_NAME.into(),
+        Shader::from_string(STANDARD_POINT_CLOUD_SHADER_SRC).unwrap(),
+    );
+}
+
+lazy_static! {
+    static ref STANDARD_WATER: ShaderResource = ShaderResource::new_ok(
+        STANDARD_WATER_SHADER_NAME.into(),
+        Shader::from_string(STANDARD_WATER_SHADER_SRC).unwrap(),
+    );
+}
+
+lazy_static! {
+    static ref STANDARD_SKY: ShaderResource = ShaderResource::new_ok(
+        STANDARD_SKY_SHADER_NAME.into(),
+        Shader::from_string(STANDARD_SKY_SHADER_SRC).unwrap(),
+    );
+}
+
+lazy_static! {
+    static ref STANDARD_PRECIPITATION: ShaderResource = ShaderResource::new_ok(
+        STANDARD_PRECIPITATION_SHADER_NAME.into(),
+        Shader::from_string(STANDARD_PRECIPITATION_SHADER_SRC).unwrap(),
+    );
+}
+
+lazy_static! {
+    static ref STANDARD_TEXT3D: ShaderResource = ShaderResource::new_ok(
+        STANDARD_TEXT3D_SHADER_NAME.into(),
+        Shader::from_string(STANDARD_TEXT3D_SHADER_SRC).unwrap(),
+    );
+}
+
+lazy_static! {
+    static ref STANDARD_TEXT3D_OVERLAY: ShaderResource = ShaderResource::new_ok(
+        STANDARD_TEXT3D_OVERLAY_SHADER_NAME.into(),
+        Shader::from_string(STANDARD_TEXT3D_OVERLAY_SHADER_SRC).unwrap(),
+    );
+}
+
 #[cfg(test)]
 mod test {
     use crate::material::shader::{
@@ -776,4 +1068,53 @@ mod test {
 
         assert_eq!(data.definition, reference_definition);
     }
+
+    #[test]
+    fn test_standard_shaders_includes() {
+        for shader in ShaderResource::standard_shaders() {
+            let data = shader.data_ref();
+            for pass in data.definition.passes.iter() {
+                assert!(!pass.vertex_shader.contains("#include"));
+                assert!(!pass.fragment_shader.contains("#include"));
+            }
+        }
+
+        let standard = ShaderResource::standard();
+        let data = standard.data_ref();
+        let fragment_shader = &data
+            .definition
+            .passes
+            .iter()
+            .find(|pass| pass.name == "GBuffer")
+            .unwrap()
+            .fragment_shader;
+        assert!(fragment_shader.contains("TTriplanarCoords S_TriplanarCoords("));
+        for property in data.definition.properties.iter() {
+            assert!(fragment_shader.contains(&format!(" {};", property.name)));
+        }
+
+        let vertex_animation = ShaderResource::standard_vertex_animation();
+        let data = vertex_animation.data_ref();
+        assert_eq!(data.definition.passes.len(), 5);
+        for pass in data.definition.passes.iter() {
+            assert!(pass
+                .vertex_shader
+                .contains("void S_ApplyVertexAnimation(int vertexId"));
+        }
+
+        for shader in [
+            ShaderResource::standard_sprite(),
+            ShaderResource::standard_particle_system(),
+        ] {
+            let data = shader.data_ref();
+            assert!(data
+                .definition
+                .properties
+                .iter()
+                .any(|property| property.name == "softFadeDistance"));
+            assert!(data.definition.passes[0]
+                .fragment_shader
+                .contains("float S_SoftFade(sampler2D sceneDepth"));
+        }
+    }
 }
@@ -0,0 +1,83 @@
+(
+    name: "StandardPointCloudShader",
+
+    properties: [
+        (
+            name: "tint",
+            kind: Color(r: 255, g: 255, b: 255, a: 255),
+        ),
+    ],
+
+    passes: [
+        (
+            name: "Forward",
+            draw_parameters: DrawParameters(
+                cull_face: None,
+                color_write: ColorMask(
+                    red: true,
+                    green: true,
+                    blue: true,
+                    alpha: true,
+                ),
+                depth_write: true,
+                stencil_test: None,
+                depth_test: true,
+                blend: None,
+                stencil_op: StencilOp(
+                    fail: Keep,
+                    zfail: Keep,
+                    zpass: Keep,
+                    write_mask: 0xFFFF_FFFF,
+                ),
+            ),
+            vertex_shader:
+               r#"
+               layout(location = 0) in vec3 vertexPosition;
+               layout(location = 1) in vec2 vertexTexCoord;
+               layout(location = 2) in float pointSize;
+               layout(location = 3) in float sizeAttenuation;
+               layout(location = 4) in vec4 vertexColor;
+
+               uniform mat4 fyrox_viewProjectionMatrix;
+               uniform mat4 fyrox_worldMatrix;
+               uniform vec3 fyrox_cameraUpVector;
+               uniform vec3 fyrox_cameraSideVector;
+               uniform vec3 fyrox_cameraPosition;
+
+               out vec2 texCoord;
+               out vec4 color;
+
+               void main()
+               {
+                   color = S_SRGBToLinear(vertexColor);
+                   texCoord = vertexTexCoord;
+                   vec4 worldPosition = fyrox_worldMatrix * vec4(vertexPosition, 1.0);
+                   // Without attenuation points keep the same on-screen size at any distance.
+                   float distanceScale = mix(distance(fyrox_cameraPosition, worldPosition.xyz), 1.0, sizeAttenuation);
+                   vec2 vertexOffset = vertexTexCoord * 2.0 - 1.0;
+                   vec3 offset = (vertexOffset.x * fyrox_cameraSideVector + vertexOffset.y * fyrox_cameraUpVector) * pointSize * distanceScale;
+                   gl_Position = fyrox_viewProjectionMatrix * (worldPosition + vec4(offset, 0.0));
+               }
+               "#,
+
+           fragment_shader:
+               r#"
+               uniform vec4 tint;
+
+               out vec4 FragColor;
+               in vec2 texCoord;
+               in vec4 color;
+
+               void main()
+               {
+                   // Round points.
+                   vec2 offset = texCoord * 2.0 - 1.0;
+                   if (dot(offset, offset) > 1.0) {
+                       discard;
+                   }
+                   FragColor = vec4(color.rgb * tint.rgb, 1.0);
+               }
+               "#,
+        )
+    ],
+)
@@ -0,0 +1,231 @@
+(
+    name: "StandardPrecipitationShader",
+
+    properties: [
+        (
+            name: "color",
+            kind: Color(r: 200, g: 210, b: 225, a: 110),
+        ),
+        (
+            name: "areaSize",
+            kind: Vector3((40.0, 30.0, 40.0)),
+        ),
+        (
+            name: "fallOffset",
+            kind: Vector3((0.0, 0.0, 0.0)),
+        ),
+        (
+            name: "velocity",
+            kind: Vector3((0.0, -9.0, 0.0)),
+        ),
+        (
+            name: "particleSize",
+            kind: Vector2((0.01, 0.4)),
+        ),
+        (
+            name: "sway",
+            kind: Float(0.0),
+        ),
+        (
+            name: "time",
+            kind: Float(0.0),
+        ),
+        (
+            name: "isSnow",
+            kind: Bool(false),
+        ),
+        (
+            name: "useCollision",
+            kind: Bool(true),
+        ),
+        (
+            name: "splashDuration",
+            kind: Float(0.1),
+        ),
+        (
+            name: "softFadeDistance",
+            kind: Float(0.1),
+        ),
+    ],
+
+    passes: [
+        (
+            name: "Forward",
+            draw_parameters: DrawParameters(
+                cull_face: None,
+                color_write: ColorMask(
+                    red: true,
+                    green: true,
+                    blue: true,
+                    alpha: true,
+                ),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: true,
+                blend: Some(BlendParameters(
+                    func: BlendFunc(
+                        sfactor: SrcAlpha,
+                        dfactor: OneMinusSrcAlpha,
+                        alpha_sfactor: SrcAlpha,
+                        alpha_dfactor: OneMinusSrcAlpha,
+                    ),
+                    equation: BlendEquation(
+                        rgb: Add,
+                        alpha: Add
+                    )
+                )),
+                stencil_op: StencilOp(
+                    fail: Keep,
+                    zfail: Keep,
+                    zpass: Keep,
+                    write_mask: 0xFFFF_FFFF,
+                ),
+            ),
+            vertex_shader:
+               r#"
+               layout(location = 0) in vec3 vertexSeed;
+               layout(location = 1) in vec2 vertexCorner;
+               layout(location = 2) in float vertexPhase;
+
+               uniform mat4 fyrox_viewProjectionMatrix;
+               uniform vec3 fyrox_cameraPosition;
+               uniform vec3 fyrox_cameraUpVector;
+               uniform vec3 fyrox_cameraSideVector;
+               uniform sampler2D fyrox_precipitationOcclusionMap;
+               uniform mat4 fyrox_precipitationOcclusionMatrix;
+
+               uniform vec3 areaSize;
+               uniform vec3 fallOffset;
+               uniform vec3 velocity;
+               uniform vec2 particleSize;
+               uniform float sway;
+               uniform float time;
+               uniform bool isSnow;
+               uniform bool useCollision;
+               uniform float splashDuration;
+
+               out vec2 texCoord;
+               out float opacity;
+               out float splash;
+
+               void main()
+               {
+                   // Particles live in a box around the camera. The box is anchored to the world, so
+                   // the particles do not move with the camera, but wrap around the edges of the box.
+                   vec3 origin = fyrox_cameraPosition - 0.5 * areaSize;
+                   vec3 position = origin + mod(vertexSeed * areaSize + fallOffset - origin, areaSize);
+                   position.xz += sway * sin(time * vec2(1.3, 1.7) + vertexPhase * 2.0 * PI);
+
+                   // Fade out the particles near the edges of the box to hide the wrapping.
+                   vec3 relative = (position - origin) / max(areaSize, vec3(0.0001));
+                   vec3 edge = min(relative, 1.0 - relative);
+                   opacity = clamp(min(edge.x, min(edge.y, edge.z)) * 10.0, 0.0, 1.0);
+
+                   splash = 0.0;
+                   if (useCollision) {
+                       vec4 occlusionPosition = fyrox_precipitationOcclusionMatrix * vec4(position, 1.0);
+                       vec3 occlusionCoords = occlusionPosition.xyz / occlusionPosition.w * 0.5 + 0.5;
+                       if (all(greaterThan(occlusionCoords.xy, vec2(0.0))) && all(lessThan(occlusionCoords.xy, vec2(1.0)))) {
+                           float occluderDepth = textureLod(fyrox_precipitationOcclusionMap, occlusionCoords.xy, 0.0).r;
+                           // The occlusion map is orthographic and looks down, so the depth changes
+                           // linearly with the height.
+                           float depthPerMeter = 0.5 * abs(fyrox_precipitationOcclusionMatrix[1][2]);
+                           float distanceBelow = (occlusionCoords.z - occluderDepth) / max(depthPerMeter, 0.000001);
+                           if (distanceBelow > 0.05) {
+                               float splashDistance = length(velocity) * splashDuration;
+                               if (distanceBelow < splashDistance) {
+                                   position.y += distanceBelow - 0.02;
+                                   splash = distanceBelow / splashDistance;
+                               } else {
+                                   opacity = 0.0;
+                               }
+                           }
+                       }
+                   }
+
+                   vec3 side;
+                   vec3 up;
+                   if (splash > 0.0) {
+                       // A flat ring (or a settled flake) on the surface, that grows and fades out.
+                       float radius = isSnow ? particleSize.x : particleSize.x * (3.0 + 10.0 * splash);
+                       side = vec3(radius, 0.0, 0.0);
+                       up = vec3(0.0, 0.0, radius);
+                       opacity *= 1.0 - splash;
+                   } else if (isSnow) {
+                       side = fyrox_cameraSideVector * particleSize.x;
+                       up = fyrox_cameraUpVector * particleSize.x;
+                   } else {
+                       // Streaks are stretched along the direction of the fall and face the camera.
+                       vec3 direction = normalize(velocity);
+                       vec3 toCamera = fyrox_cameraPosition - position;
+                       vec3 streakSide = cross(direction, toCamera);
+                       side = (dot(streakSide, streakSide) > 0.000001 ? normalize(streakSide) : fyrox_cameraSideVector) * particleSize.x;
+                       up = direction * particleSize.y;
+                   }
+
+                   texCoord = vertexCorner * 0.5 + 0.5;
+                   if (opacity <= 0.0) {
+                       // Collapse the quad, so the particle produces no fragments.
+                       gl_Position = vec4(0.0, 0.0, -2.0, 1.0);
+                   } else {
+                       vec3 worldPosition = position + side * vertexCorner.x + up * vertexCorner.y;
+                       gl_Position = fyrox_viewProjectionMatrix * vec4(worldPosition, 1.0);
+                   }
+               }
+               "#,
+
+           fragment_shader:
+               r#"
+               #include "fyrox/soft_particles.glsl"
+
+               uniform vec4 color;
+               uniform bool isSnow;
+               uniform float softFadeDistance;
+
+               uniform sampler2D fyrox_sceneDepth;
+               uniform float fyrox_zNear;
+               uniform float fyrox_zFar;
+               uniform int fyrox_lightCount;
+               uniform vec4 fyrox_lightsColorRadius[16]; // xyz - color, w = radius
+               uniform vec4 fyrox_lightsParameters2D[16]; // y - falloff (0 for 3D lights)
+               uniform vec4 fyrox_ambientLightColor;
+
+               in vec2 texCoord;
+               in float opacity;
+               in float splash;
+
+               out vec4 FragColor;
+
+               void main()
+               {
+                   vec2 p = texCoord * 2.0 - 1.0;
+                   float shape;
+                   if (splash > 0.0 && !isSnow) {
+                       shape = clamp(1.0 - abs(length(p) - 0.7) * 5.0, 0.0, 1.0);
+                   } else if (isSnow || splash > 0.0) {
+                       shape = 1.0 - smoothstep(0.3, 1.0, length(p));
+                   } else {
+                       shape = (1.0 - abs(p.x)) * (1.0 - p.y * p.y);
+                   }
+
+                   float depthOpacity = S_SoftFade(fyrox_sceneDepth, fyrox_zNear, fyrox_zFar, softFadeDistance);
+
+                   // Particles are tiny and translucent, so they're lit without normals.
+                   vec3 light = fyrox_ambientLightColor.rgb;
+                   for (int i = 0; i < fyrox_lightCount; ++i) {
+                       if (fyrox_lightsParameters2D[i].y > 0.0) {
+                           continue;
+                       }
+                       float radius = fyrox_lightsColorRadius[i].w;
+                       if (isinf(radius)) {
+                           light += fyrox_lightsColorRadius[i].xyz;
+                       }
+                   }
+
+                   vec4 linearColor = S_SRGBToLinear(color);
+                   FragColor = vec4(linearColor.rgb * light, linearColor.a * shape * opacity * depthOpacity);
+               }
+               "#,
+        )
+    ],
+)
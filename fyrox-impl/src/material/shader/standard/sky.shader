@@ -0,0 +1,249 @@
+(
+    name: "StandardSkyShader",
+
+    properties: [
+        (
+            name: "sunDirection",
+            kind: Vector3((0.0, 1.0, 0.0)),
+        ),
+        (
+            name: "moonDirection",
+            kind: Vector3((0.0, -1.0, 0.0)),
+        ),
+        (
+            name: "celestialPole",
+            kind: Vector3((0.0, 1.0, 0.0)),
+        ),
+        (
+            name: "siderealAngle",
+            kind: Float(0.0),
+        ),
+        (
+            name: "sunIntensity",
+            kind: Float(22.0),
+        ),
+        (
+            name: "moonIntensity",
+            kind: Float(0.0),
+        ),
+        (
+            name: "rayleighScale",
+            kind: Float(1.0),
+        ),
+        (
+            name: "mieScale",
+            kind: Float(1.0),
+        ),
+        (
+            name: "mieAnisotropy",
+            kind: Float(0.76),
+        ),
+        (
+            name: "overcast",
+            kind: Float(0.0),
+        ),
+        (
+            name: "starIntensity",
+            kind: Float(1.0),
+        ),
+    ],
+
+    passes: [
+        (
+            name: "Forward",
+            draw_parameters: DrawParameters(
+                cull_face: None,
+                color_write: ColorMask(
+                    red: true,
+                    green: true,
+                    blue: true,
+                    alpha: true,
+                ),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: true,
+                blend: None,
+                stencil_op: StencilOp(
+                    fail: Keep,
+                    zfail: Keep,
+                    zpass: Keep,
+                    write_mask: 0xFFFF_FFFF,
+                ),
+            ),
+            vertex_shader:
+               r#"
+               layout(location = 0) in vec3 vertexPosition;
+
+               uniform mat4 fyrox_viewProjectionMatrix;
+               uniform vec3 fyrox_cameraPosition;
+
+               out vec3 viewDirection;
+
+               void main()
+               {
+                   viewDirection = vertexPosition;
+                   vec4 clipPosition = fyrox_viewProjectionMatrix * vec4(fyrox_cameraPosition + vertexPosition, 1.0);
+                   // Put the dome on the far plane, so it is visible only where there's no geometry.
+                   gl_Position = clipPosition.xyww;
+               }
+               "#,
+
+           fragment_shader:
+               r#"
+               uniform vec3 sunDirection;
+               uniform vec3 moonDirection;
+               uniform vec3 celestialPole;
+               uniform float siderealAngle;
+               uniform float sunIntensity;
+               uniform float moonIntensity;
+               uniform float rayleighScale;
+               uniform float mieScale;
+               uniform float mieAnisotropy;
+               uniform float overcast;
+               uniform float starIntensity;
+
+               in vec3 viewDirection;
+
+               out vec4 FragColor;
+
+               const float PLANET_RADIUS = 6371e3;
+               const float ATMOSPHERE_RADIUS = 6471e3;
+               const vec3 RAYLEIGH_COEFFICIENT = vec3(5.5e-6, 13.0e-6, 22.4e-6);
+               const float MIE_COEFFICIENT = 21e-6;
+               const float RAYLEIGH_SCALE_HEIGHT = 8e3;
+               const float MIE_SCALE_HEIGHT = 1.2e3;
+               const int PRIMARY_STEPS = 16;
+               const int LIGHT_STEPS = 8;
+               const float SUN_ANGULAR_RADIUS = 0.0047;
+               const float MOON_ANGULAR_RADIUS = 0.012;
+
+               // Returns distances to the intersection points of the ray with the sphere centered at
+               // the origin. X is greater than Y if there's no intersection.
+               vec2 raySphere(vec3 origin, vec3 direction, float radius)
+               {
+                   float b = dot(origin, direction);
+                   float c = dot(origin, origin) - radius * radius;
+                   float d = b * b - c;
+                   if (d < 0.0) {
+                       return vec2(1e5, -1e5);
+                   }
+                   d = sqrt(d);
+                   return vec2(-b - d, -b + d);
+               }
+
+               // Single scattering of the light coming from the given direction along the view ray.
+               // Transmittance of the atmosphere along the view ray is written to the last argument.
+               vec3 scattering(vec3 direction, vec3 lightDirection, float intensity, out vec3 transmittance)
+               {
+                   vec3 origin = vec3(0.0, PLANET_RADIUS + 1.0, 0.0);
+                   vec3 rayleigh = RAYLEIGH_COEFFICIENT * rayleighScale;
+                   float mie = MIE_COEFFICIENT * mieScale;
+
+                   vec2 atmosphere = raySphere(origin, direction, ATMOSPHERE_RADIUS);
+                   vec2 ground = raySphere(origin, direction, PLANET_RADIUS);
+                   float rayLength = ground.x > 0.0 && ground.x < ground.y ? ground.x : atmosphere.y;
+                   float stepSize = rayLength / float(PRIMARY_STEPS);
+
+                   float mu = dot(direction, lightDirection);
+                   float g = mieAnisotropy;
+                   float phaseRayleigh = 3.0 / (16.0 * PI) * (1.0 + mu * mu);
+                   float phaseMie = 3.0 / (8.0 * PI) * ((1.0 - g * g) * (1.0 + mu * mu))
+                       / (pow(max(1.0 + g * g - 2.0 * mu * g, 0.0001), 1.5) * (2.0 + g * g));
+
+                   vec3 totalRayleigh = vec3(0.0);
+                   vec3 totalMie = vec3(0.0);
+                   float opticalDepthRayleigh = 0.0;
+                   float opticalDepthMie = 0.0;
+                   for (int i = 0; i < PRIMARY_STEPS; ++i) {
+                       vec3 position = origin + direction * (float(i) + 0.5) * stepSize;
+                       float height = length(position) - PLANET_RADIUS;
+                       float stepRayleigh = exp(-height / RAYLEIGH_SCALE_HEIGHT) * stepSize;
+                       float stepMie = exp(-height / MIE_SCALE_HEIGHT) * stepSize;
+                       opticalDepthRayleigh += stepRayleigh;
+                       opticalDepthMie += stepMie;
+
+                       // The planet casts a shadow on the atmosphere.
+                       vec2 lightGround = raySphere(position, lightDirection, PLANET_RADIUS);
+                       if (lightGround.x > 0.0 && lightGround.x < lightGround.y) {
+                           continue;
+                       }
+
+                       float lightStepSize = raySphere(position, lightDirection, ATMOSPHERE_RADIUS).y / float(LIGHT_STEPS);
+                       float lightDepthRayleigh = 0.0;
+                       float lightDepthMie = 0.0;
+                       for (int j = 0; j < LIGHT_STEPS; ++j) {
+                           vec3 lightPosition = position + lightDirection * (float(j) + 0.5) * lightStepSize;
+                           float lightHeight = max(length(lightPosition) - PLANET_RADIUS, 0.0);
+                           lightDepthRayleigh += exp(-lightHeight / RAYLEIGH_SCALE_HEIGHT) * lightStepSize;
+                           lightDepthMie += exp(-lightHeight / MIE_SCALE_HEIGHT) * lightStepSize;
+                       }
+
+                       vec3 attenuation = exp(-(rayleigh * (opticalDepthRayleigh + lightDepthRayleigh)
+                           + mie * (opticalDepthMie + lightDepthMie)));
+                       totalRayleigh += stepRayleigh * attenuation;
+                       totalMie += stepMie * attenuation;
+                   }
+
+                   transmittance = exp(-(rayleigh * opticalDepthRayleigh + mie * opticalDepthMie));
+                   return intensity * (phaseRayleigh * rayleigh * totalRayleigh + phaseMie * mie * totalMie);
+               }
+
+               vec3 rotateAroundAxis(vec3 v, vec3 axis, float angle)
+               {
+                   float c = cos(angle);
+                   float s = sin(angle);
+                   return v * c + cross(axis, v) * s + axis * dot(axis, v) * (1.0 - c);
+               }
+
+               float stars(vec3 direction)
+               {
+                   vec3 celestialDirection = rotateAroundAxis(direction, celestialPole, -siderealAngle);
+                   vec3 cell = floor(celestialDirection * 300.0);
+                   float random = fract(sin(dot(cell, vec3(12.9898, 78.233, 37.719))) * 43758.5453);
+                   float brightness = fract(random * 17.0);
+                   return step(0.9985, random) * brightness * brightness;
+               }
+
+               void main()
+               {
+                   vec3 direction = normalize(viewDirection);
+
+                   vec3 transmittance;
+                   vec3 color = scattering(direction, sunDirection, sunIntensity, transmittance);
+                   if (moonIntensity > 0.0 && moonDirection.y > -0.2) {
+                       vec3 moonTransmittance;
+                       color += scattering(direction, moonDirection, moonIntensity, moonTransmittance);
+                   }
+
+                   float clearSky = 1.0 - clamp(overcast, 0.0, 1.0);
+
+                   // Sun disk.
+                   float sunCos = dot(direction, sunDirection);
+                   float sunDisk = smoothstep(cos(SUN_ANGULAR_RADIUS * 1.2), cos(SUN_ANGULAR_RADIUS), sunCos);
+                   color += sunDisk * sunIntensity * 50.0 * transmittance * clearSky;
+
+                   // Moon disk, lit by the sun from the side, which gives the phase of the moon.
+                   float moonCos = dot(direction, moonDirection);
+                   if (moonCos > cos(MOON_ANGULAR_RADIUS)) {
+                       vec3 offset = (direction - moonDirection * moonCos) / MOON_ANGULAR_RADIUS;
+                       vec3 moonNormal = offset - moonDirection * sqrt(max(1.0 - dot(offset, offset), 0.0));
+                       float moonLight = max(dot(moonNormal, sunDirection), 0.0) * 0.9 + 0.02;
+                       float moonEdge = smoothstep(1.0, 0.9, length(offset));
+                       color += moonEdge * moonLight * 2.0 * transmittance * clearSky;
+                   }
+
+                   // Stars are visible only when the sky is dark enough.
+                   float skyLuminance = dot(color, vec3(0.2126, 0.7152, 0.0722));
+                   float starVisibility = clamp(1.0 - skyLuminance * 20.0, 0.0, 1.0) * step(0.0, direction.y);
+                   color += stars(direction) * starIntensity * starVisibility * transmittance * clearSky;
+
+                   // Clouds scatter the light uniformly, so overcast sky is grey and dimmer.
+                   vec3 overcastColor = vec3(skyLuminance * 0.7);
+                   color = mix(color, overcastColor, clamp(overcast, 0.0, 1.0));
+
+                   FragColor = vec4(color, 1.0);
+               }
+               "#,
+        )
+    ],
+)
@@ -59,6 +59,42 @@
             name: "parallaxScale",
             kind: Float(0.08),
         ),
+        (
+            name: "useTriplanarMapping",
+            kind: Bool(false),
+        ),
+        (
+            name: "triplanarScale",
+            kind: Float(1.0),
+        ),
+        (
+            name: "triplanarSharpness",
+            kind: Float(4.0),
+        ),
+        (
+            name: "detailDiffuseTexture",
+            kind: Sampler(default: None, fallback: White),
+        ),
+        (
+            name: "detailNormalTexture",
+            kind: Sampler(default: None, fallback: Normal),
+        ),
+        (
+            name: "detailTexCoordScale",
+            kind: Vector2((8.0, 8.0)),
+        ),
+        (
+            name: "detailStrength",
+            kind: Float(1.0),
+        ),
+        (
+            name: "detailFadeStart",
+            kind: Float(10.0),
+        ),
+        (
+            name: "detailFadeEnd",
+            kind: Float(30.0),
+        ),
     ],
 
     passes: [
@@ -162,6 +198,8 @@
                 "#,
             fragment_shader:
                 r#"
+                #include "fyrox/triplanar.glsl"
+
                 layout(location = 0) out vec4 outColor;
                 layout(location = 1) out vec4 outNormal;
                 layout(location = 2) out vec4 outAmbient;
@@ -179,10 +217,20 @@
                 uniform sampler2D aoTexture;
                 uniform vec2 texCoordScale;
                 uniform uint layerIndex;
+                uniform uint fyrox_lightLinkMask;
                 uniform vec3 emissionStrength;
                 uniform vec4 diffuseColor;
                 uniform float parallaxCenter;
                 uniform float parallaxScale;
+                uniform bool useTriplanarMapping;
+                uniform float triplanarScale;
+                uniform float triplanarSharpness;
+                uniform sampler2D detailDiffuseTexture;
+                uniform sampler2D detailNormalTexture;
+                uniform vec2 detailTexCoordScale;
+                uniform float detailStrength;
+                uniform float detailFadeStart;
+                uniform float detailFadeEnd;
 
                 // Define uniforms with reserved names. Fyrox will automatically provide
                 // required data to these uniforms.
@@ -201,21 +249,68 @@
                     mat3 tangentSpace = mat3(tangent, binormal, normal);
                     vec3 toFragment = normalize(position - fyrox_cameraPosition);
 
-                    vec2 tc;
-                    if (fyrox_usePOM) {
-                        vec3 toFragmentTangentSpace = normalize(transpose(tangentSpace) * toFragment);
-                        tc = S_ComputeParallaxTextureCoordinates(
-                            heightTexture,
-                            toFragmentTangentSpace,
-                            texCoord * texCoordScale,
-                            parallaxCenter,
-                            parallaxScale
+                    // Detail layers fade out with distance to hide tiling.
+                    float detailBlend = detailStrength
+                        * (1.0 - smoothstep(detailFadeStart, detailFadeEnd, distance(position, fyrox_cameraPosition)));
+
+                    vec4 albedo;
+                    vec3 worldNormal;
+                    float metallic;
+                    float roughness;
+                    float ambientOcclusion;
+                    vec3 emission;
+
+                    if (useTriplanarMapping) {
+                        TTriplanarCoords coords = S_TriplanarCoords(position, normal, triplanarScale, triplanarSharpness);
+                        TTriplanarCoords detailCoords = S_TriplanarCoords(
+                            position,
+                            normal,
+                            triplanarScale * detailTexCoordScale.x,
+                            triplanarSharpness
                         );
+
+                        albedo = diffuseColor * S_TriplanarSample(diffuseTexture, coords);
+                        albedo.rgb *= mix(vec3(1.0), S_TriplanarSample(detailDiffuseTexture, detailCoords).rgb, detailBlend);
+
+                        vec3 baseNormal = S_TriplanarNormal(normalTexture, coords, normal);
+                        vec3 detailNormal = S_TriplanarNormal(detailNormalTexture, detailCoords, normal);
+                        worldNormal = normalize(baseNormal + (detailNormal - normal) * detailBlend);
+
+                        metallic = S_TriplanarSample(metallicTexture, coords).r;
+                        roughness = S_TriplanarSample(roughnessTexture, coords).r;
+                        ambientOcclusion = S_TriplanarSample(aoTexture, coords).r;
+                        emission = S_TriplanarSample(emissionTexture, coords).rgb;
                     } else {
-                        tc = texCoord * texCoordScale;
+                        vec2 tc;
+                        if (fyrox_usePOM) {
+                            vec3 toFragmentTangentSpace = normalize(transpose(tangentSpace) * toFragment);
+                            tc = S_ComputeParallaxTextureCoordinates(
+                                heightTexture,
+                                toFragmentTangentSpace,
+                                texCoord * texCoordScale,
+                                parallaxCenter,
+                                parallaxScale
+                            );
+                        } else {
+                            tc = texCoord * texCoordScale;
+                        }
+                        vec2 detailTc = tc * detailTexCoordScale;
+
+                        albedo = diffuseColor * texture(diffuseTexture, tc);
+                        albedo.rgb *= mix(vec3(1.0), texture(detailDiffuseTexture, detailTc).rgb, detailBlend);
+
+                        vec3 n = texture(normalTexture, tc).xyz * 2.0 - 1.0;
+                        vec3 dn = texture(detailNormalTexture, detailTc).xyz * 2.0 - 1.0;
+                        n = vec3(n.xy + dn.xy * detailBlend, n.z);
+                        worldNormal = normalize(tangentSpace * normalize(n));
+
+                        metallic = texture(metallicTexture, tc).r;
+                        roughness = texture(roughnessTexture, tc).r;
+                        ambientOcclusion = texture(aoTexture, tc).r;
+                        emission = texture(emissionTexture, tc).rgb;
                     }
 
-                    outColor = diffuseColor * texture(diffuseTexture, tc);
+                    outColor = albedo;
 
                     // Alpha test.
                     if (outColor.a < 0.5) {
@@ -223,18 +318,17 @@
                     }
                     outColor.a = 1.0;
 
-                    vec4 n = normalize(texture(normalTexture, tc) * 2.0 - 1.0);
-                    outNormal = vec4(normalize(tangentSpace * n.xyz) * 0.5 + 0.5, 1.0);
+                    outNormal = vec4(worldNormal * 0.5 + 0.5, 1.0);
 
-                    outMaterial.x = texture(metallicTexture, tc).r;
-                    outMaterial.y = texture(roughnessTexture, tc).r;
-                    outMaterial.z = texture(aoTexture, tc).r;
+                    outMaterial.x = metallic;
+                    outMaterial.y = roughness;
+                    outMaterial.z = ambientOcclusion;
                     outMaterial.a = 1.0;
 
-                    outAmbient.xyz = emissionStrength * texture(emissionTexture, tc).rgb + texture(lightmapTexture, secondTexCoord).rgb;
+                    outAmbient.xyz = emissionStrength * emission + texture(lightmapTexture, secondTexCoord).rgb;
                     outAmbient.a = 1.0;
 
-                    outDecalMask = layerIndex;
+                    outDecalMask = (layerIndex & 0xFFu) | fyrox_lightLinkMask;
                 }
                 "#,
         ),
@@ -6,6 +6,10 @@
             name: "diffuseTexture",
             kind: Sampler(default: None, fallback: White),
         ),
+        (
+            name: "normalTexture",
+            kind: Sampler(default: None, fallback: Normal),
+        ),
     ],
 
     passes: [
@@ -66,12 +70,18 @@
            fragment_shader:
                r#"
                 uniform sampler2D diffuseTexture;
+                uniform sampler2D normalTexture;
+
+                uniform mat4 fyrox_worldMatrix;
 
                 uniform int fyrox_lightCount;
                 uniform vec4 fyrox_lightsColorRadius[16]; // xyz - color, w = radius
                 uniform vec3 fyrox_lightsPosition[16];
                 uniform vec3 fyrox_lightsDirection[16];
                 uniform vec2 fyrox_lightsParameters[16]; // x = hotspot angle, y - full cone angle delta
+                uniform vec4 fyrox_lightsParameters2D[16]; // x - height, y - falloff (0 for 3D lights), z - shadow softness, w - cast shadows
+                uniform int fyrox_occluderCount;
+                uniform vec4 fyrox_occluderSegments[64]; // xy - begin, zw - end
                 uniform vec4 fyrox_ambientLightColor;
 
                 out vec4 FragColor;
@@ -80,8 +90,57 @@
                 in vec4 color;
                 in vec3 fragmentPosition;
 
+                float HardShadow2D(vec2 lightPosition, vec2 point)
+                {
+                    vec2 r = point - lightPosition;
+                    for (int k = 0; k < fyrox_occluderCount; ++k) {
+                        vec2 a = fyrox_occluderSegments[k].xy;
+                        vec2 e = fyrox_occluderSegments[k].zw - a;
+                        vec2 d = a - lightPosition;
+
+                        // Only segments that face away from the light cast shadows, so occluders do not
+                        // shadow their own insides.
+                        if (dot(vec2(e.y, -e.x), d) <= 0.0) {
+                            continue;
+                        }
+
+                        float denominator = r.x * e.y - r.y * e.x;
+                        if (abs(denominator) < 0.000001) {
+                            continue;
+                        }
+
+                        float t = (d.x * e.y - d.y * e.x) / denominator;
+                        float u = (d.x * r.y - d.y * r.x) / denominator;
+                        if (t > 0.0 && t < 1.0 && u >= 0.0 && u <= 1.0) {
+                            return 0.0;
+                        }
+                    }
+                    return 1.0;
+                }
+
+                float Shadow2D(vec2 lightPosition, vec2 point, float softness)
+                {
+                    if (softness <= 0.0) {
+                        return HardShadow2D(lightPosition, point);
+                    }
+
+                    // Treat the light as a segment perpendicular to the ray to get the penumbra.
+                    vec2 toPoint = normalize(point - lightPosition);
+                    vec2 side = vec2(-toPoint.y, toPoint.x) * softness;
+                    float visibility = 0.0;
+                    for (int s = -2; s <= 2; ++s) {
+                        visibility += HardShadow2D(lightPosition + side * (float(s) * 0.5), point);
+                    }
+                    return visibility / 5.0;
+                }
+
                 void main()
                 {
+                    // Texture coordinates of sprites go along negative X axis, and the sprites are facing
+                    // negative Z axis.
+                    vec3 n = texture(normalTexture, texCoord).xyz * 2.0 - 1.0;
+                    vec3 normal = normalize(mat3(fyrox_worldMatrix) * vec3(-n.x, n.y, -n.z));
+
                     vec3 lighting = fyrox_ambientLightColor.xyz;
                     for(int i = 0; i < fyrox_lightCount; ++i) {
                         // "Unpack" light parameters.
@@ -91,15 +150,35 @@
                         float radius = fyrox_lightsColorRadius[i].w;
                         vec3 lightPosition = fyrox_lightsPosition[i];
                         vec3 direction = fyrox_lightsDirection[i];
+                        vec4 parameters2D = fyrox_lightsParameters2D[i];
 
-                        // Calculate lighting.
-                        vec3 toFragment = fragmentPosition - lightPosition;
-                        float distance = length(toFragment);
-                        vec3 toFragmentNormalized = toFragment / distance;
-                        float distanceAttenuation = S_LightDistanceAttenuation(distance, radius);
-                        float spotAngleCos = dot(toFragmentNormalized, direction);
-                        float directionalAttenuation = smoothstep(halfConeAngleCos, halfHotspotAngleCos, spotAngleCos);
-                        lighting += lightColor * (distanceAttenuation * directionalAttenuation);
+                        if (parameters2D.y > 0.0) {
+                            // 2D light, everything is calculated in XY plane.
+                            vec2 toFragment = fragmentPosition.xy - lightPosition.xy;
+                            float distance = length(toFragment);
+                            if (distance >= radius) {
+                                continue;
+                            }
+                            float distanceAttenuation = pow(1.0 - distance / radius, parameters2D.y);
+                            float directionalAttenuation = 1.0;
+                            if (halfConeAngleCos > -1.0) {
+                                float spotAngleCos = dot(toFragment / max(distance, 0.00001), normalize(direction.xy));
+                                directionalAttenuation = smoothstep(halfConeAngleCos, halfHotspotAngleCos, spotAngleCos);
+                            }
+                            vec3 toLight = normalize(vec3(-toFragment, -parameters2D.x));
+                            float diffuse = parameters2D.x > 0.0 ? max(dot(normal, toLight), 0.0) : 1.0;
+                            float shadow = parameters2D.w > 0.0 ? Shadow2D(lightPosition.xy, fragmentPosition.xy, parameters2D.z) : 1.0;
+                            lighting += lightColor * (distanceAttenuation * directionalAttenuation * diffuse * shadow);
+                        } else {
+                            // Calculate lighting.
+                            vec3 toFragment = fragmentPosition - lightPosition;
+                            float distance = length(toFragment);
+                            vec3 toFragmentNormalized = toFragment / distance;
+                            float distanceAttenuation = S_LightDistanceAttenuation(distance, radius);
+                            float spotAngleCos = dot(toFragmentNormalized, direction);
+                            float directionalAttenuation = smoothstep(halfConeAngleCos, halfHotspotAngleCos, spotAngleCos);
+                            lighting += lightColor * (distanceAttenuation * directionalAttenuation);
+                        }
                     }
 
                     FragColor = vec4(lighting, 1.0) * color * S_SRGBToLinear(texture(diffuseTexture, texCoord));
@@ -7,8 +7,8 @@
             kind: Sampler(default: None, fallback: White),
         ),
         (
-            name: "softBoundarySharpnessFactor",
-            kind: Float(100.0),
+            name: "softFadeDistance",
+            kind: Float(0.1),
         )
     ],
 
@@ -82,8 +82,10 @@
 
            fragment_shader:
                r#"
+               #include "fyrox/soft_particles.glsl"
+
                uniform sampler2D diffuseTexture;
-               uniform float softBoundarySharpnessFactor;
+               uniform float softFadeDistance;
 
                uniform sampler2D fyrox_sceneDepth;
                uniform float fyrox_zNear;
@@ -93,20 +95,10 @@
                in vec2 texCoord;
                in vec4 color;
 
-               float toProjSpace(float z)
-               {
-                   return (fyrox_zFar * fyrox_zNear) / (fyrox_zFar - z * (fyrox_zFar - fyrox_zNear));
-               }
-
                void main()
                {
-                   ivec2 depthTextureSize = textureSize(fyrox_sceneDepth, 0);
-                   vec2 pixelSize = vec2(1.0 / float(depthTextureSize.x), 1.0 / float(depthTextureSize.y));
-                   float sceneDepth = toProjSpace(texture(fyrox_sceneDepth, gl_FragCoord.xy * pixelSize).r);
-                   float fragmentDepth = toProjSpace(gl_FragCoord.z);
-                   float depthOpacity = smoothstep((sceneDepth - fragmentDepth) * softBoundarySharpnessFactor, 0.0, 1.0);
                    FragColor = color * S_SRGBToLinear(texture(diffuseTexture, texCoord)).r;
-                   FragColor.a *= depthOpacity;
+                   FragColor.a *= S_SoftFade(fyrox_sceneDepth, fyrox_zNear, fyrox_zFar, softFadeDistance);
                }
                "#,
         )
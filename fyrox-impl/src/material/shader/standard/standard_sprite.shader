@@ -6,6 +6,10 @@
             name: "diffuseTexture",
             kind: Sampler(default: None, fallback: White),
         ),
+        (
+            name: "softFadeDistance",
+            kind: Float(0.0),
+        ),
     ],
 
     passes: [
@@ -80,7 +84,14 @@
 
            fragment_shader:
                r#"
+                #include "fyrox/soft_particles.glsl"
+
                 uniform sampler2D diffuseTexture;
+                uniform float softFadeDistance;
+
+                uniform sampler2D fyrox_sceneDepth;
+                uniform float fyrox_zNear;
+                uniform float fyrox_zFar;
 
                 out vec4 FragColor;
 
@@ -90,6 +101,7 @@
                 void main()
                 {
                     FragColor = color * S_SRGBToLinear(texture(diffuseTexture, texCoord));
+                    FragColor.a *= S_SoftFade(fyrox_sceneDepth, fyrox_zNear, fyrox_zFar, softFadeDistance);
                 }
                "#,
         )
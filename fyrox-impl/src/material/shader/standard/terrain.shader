@@ -167,6 +167,7 @@
                 uniform sampler2D aoTexture;
                 uniform vec2 texCoordScale;
                 uniform uint layerIndex;
+                uniform uint fyrox_lightLinkMask;
                 uniform vec3 emissionStrength;
                 uniform sampler2D maskTexture;
                 uniform vec4 diffuseColor;
@@ -217,7 +218,7 @@
                     outAmbient.xyz = emissionStrength * texture(emissionTexture, tc).rgb + texture(lightmapTexture, secondTexCoord).rgb;
                     outAmbient.a = 1.0;
 
-                    outDecalMask = layerIndex;
+                    outDecalMask = (layerIndex & 0xFFu) | fyrox_lightLinkMask;
 
                     float mask = texture(maskTexture, texCoord).r;
 
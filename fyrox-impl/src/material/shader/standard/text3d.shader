@@ -0,0 +1,121 @@
+(
+    name: "StandardText3DShader",
+
+    properties: [
+        (
+            name: "fontTexture",
+            kind: Sampler(default: None, fallback: Black),
+        ),
+        (
+            name: "outlineColor",
+            kind: Color(r: 0, g: 0, b: 0, a: 255),
+        ),
+        (
+            name: "outlineThickness",
+            kind: Float(0.0),
+        ),
+    ],
+
+    passes: [
+        (
+            name: "Forward",
+            draw_parameters: DrawParameters(
+                cull_face: None,
+                color_write: ColorMask(
+                    red: true,
+                    green: true,
+                    blue: true,
+                    alpha: true,
+                ),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: true,
+                blend: Some(BlendParameters(
+                    func: BlendFunc(
+                        sfactor: SrcAlpha,
+                        dfactor: OneMinusSrcAlpha,
+                        alpha_sfactor: SrcAlpha,
+                        alpha_dfactor: OneMinusSrcAlpha,
+                    ),
+                    equation: BlendEquation(
+                        rgb: Add,
+                        alpha: Add
+                    )
+                )),
+                stencil_op: StencilOp(
+                    fail: Keep,
+                    zfail: Keep,
+                    zpass: Keep,
+                    write_mask: 0xFFFF_FFFF,
+                ),
+            ),
+            vertex_shader:
+               r#"
+               layout(location = 0) in vec3 vertexPosition;
+               layout(location = 1) in vec2 vertexTexCoord;
+               layout(location = 2) in vec4 vertexGlyphBounds;
+               layout(location = 3) in vec4 vertexColor;
+
+               uniform mat4 fyrox_worldViewProjection;
+
+               out vec2 texCoord;
+               out vec4 glyphBounds;
+               out vec4 color;
+
+               void main()
+               {
+                   texCoord = vertexTexCoord;
+                   glyphBounds = vertexGlyphBounds;
+                   color = S_SRGBToLinear(vertexColor);
+                   gl_Position = fyrox_worldViewProjection * vec4(vertexPosition, 1.0);
+               }
+               "#,
+
+           fragment_shader:
+               r#"
+               uniform sampler2D fontTexture;
+               uniform vec4 outlineColor;
+               uniform float outlineThickness;
+
+               out vec4 FragColor;
+
+               in vec2 texCoord;
+               in vec4 glyphBounds;
+               in vec4 color;
+
+               // Glyphs are packed tightly in the atlas, so every sample is limited by the bounds of
+               // the current glyph to not catch pixels of neighbour glyphs.
+               float glyphCoverage(vec2 uv)
+               {
+                   if (uv.x < glyphBounds.x || uv.y < glyphBounds.y || uv.x > glyphBounds.z || uv.y > glyphBounds.w)
+                   {
+                       return 0.0;
+                   }
+                   return texture(fontTexture, uv).r;
+               }
+
+               void main()
+               {
+                   float coverage = glyphCoverage(texCoord);
+
+                   float outline = 0.0;
+                   if (outlineThickness > 0.0)
+                   {
+                       vec2 offset = outlineThickness / vec2(textureSize(fontTexture, 0));
+                       for (int y = -1; y <= 1; ++y)
+                       {
+                           for (int x = -1; x <= 1; ++x)
+                           {
+                               outline = max(outline, glyphCoverage(texCoord + vec2(x, y) * offset));
+                           }
+                       }
+                   }
+
+                   vec4 textColor = vec4(color.rgb, color.a * coverage);
+                   vec4 outlineLayer = vec4(outlineColor.rgb, outlineColor.a * color.a * outline);
+                   FragColor = mix(outlineLayer, textColor, coverage);
+               }
+               "#,
+        )
+    ],
+)
\ No newline at end of file
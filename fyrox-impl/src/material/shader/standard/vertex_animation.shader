@@ -0,0 +1,561 @@
+(
+    name: "StandardVertexAnimationShader",
+
+    // Each property's name must match respective uniform name.
+    properties: [
+        (
+            name: "diffuseTexture",
+            kind: Sampler(default: None, fallback: White),
+        ),
+        (
+            name: "normalTexture",
+            kind: Sampler(default: None, fallback: Normal),
+        ),
+        (
+            name: "metallicTexture",
+            kind: Sampler(default: None, fallback: Black),
+        ),
+        (
+            name: "roughnessTexture",
+            kind: Sampler(default: None, fallback: White),
+        ),
+        (
+            name: "heightTexture",
+            kind: Sampler(default: None, fallback: Black),
+        ),
+        (
+            name: "emissionTexture",
+            kind: Sampler(default: None, fallback: Black),
+        ),
+        (
+            name: "lightmapTexture",
+            kind: Sampler(default: None, fallback: Black),
+        ),
+        (
+            name: "aoTexture",
+            kind: Sampler(default: None, fallback: White),
+        ),
+        (
+            name: "texCoordScale",
+            kind: Vector2((1.0, 1.0)),
+        ),
+        (
+            name: "layerIndex",
+            kind: UInt(0),
+        ),
+        (
+            name: "emissionStrength",
+            kind: Vector3((2.0, 2.0, 2.0)),
+        ),
+        (
+            name: "diffuseColor",
+            kind: Color(r: 255, g: 255, b: 255, a: 255),
+        ),
+        (
+            name: "parallaxCenter",
+            kind: Float(0.0),
+        ),
+        (
+            name: "parallaxScale",
+            kind: Float(0.08),
+        ),
+        (
+            name: "useTriplanarMapping",
+            kind: Bool(false),
+        ),
+        (
+            name: "triplanarScale",
+            kind: Float(1.0),
+        ),
+        (
+            name: "triplanarSharpness",
+            kind: Float(4.0),
+        ),
+        (
+            name: "detailDiffuseTexture",
+            kind: Sampler(default: None, fallback: White),
+        ),
+        (
+            name: "detailNormalTexture",
+            kind: Sampler(default: None, fallback: Normal),
+        ),
+        (
+            name: "detailTexCoordScale",
+            kind: Vector2((8.0, 8.0)),
+        ),
+        (
+            name: "detailStrength",
+            kind: Float(1.0),
+        ),
+        (
+            name: "detailFadeStart",
+            kind: Float(10.0),
+        ),
+        (
+            name: "detailFadeEnd",
+            kind: Float(30.0),
+        ),
+    ],
+
+    passes: [
+        (
+            name: "GBuffer",
+            draw_parameters: DrawParameters(
+                cull_face: Some(Back),
+                color_write: ColorMask(
+                    red: true,
+                    green: true,
+                    blue: true,
+                    alpha: true,
+                ),
+                depth_write: true,
+                stencil_test: None,
+                depth_test: true,
+                blend: None,
+                stencil_op: StencilOp(
+                    fail: Keep,
+                    zfail: Keep,
+                    zpass: Keep,
+                    write_mask: 0xFFFF_FFFF,
+                ),
+            ),
+            vertex_shader:
+                r#"
+                #include "fyrox/vertex_animation.glsl"
+
+                layout(location = 0) in vec3 vertexPosition;
+                layout(location = 1) in vec2 vertexTexCoord;
+                layout(location = 2) in vec3 vertexNormal;
+                layout(location = 3) in vec4 vertexTangent;
+                layout(location = 6) in vec2 vertexSecondTexCoord;
+
+                // Define uniforms with reserved names. Fyrox will automatically provide
+                // required data to these uniforms.
+                uniform mat4 fyrox_worldMatrix;
+                uniform mat4 fyrox_worldViewProjection;
+
+                out vec3 position;
+                out vec3 normal;
+                out vec2 texCoord;
+                out vec3 tangent;
+                out vec3 binormal;
+                out vec2 secondTexCoord;
+
+                void main()
+                {
+                    vec3 localPosition = vertexPosition;
+                    vec3 localNormal = vertexNormal;
+
+                    S_ApplyVertexAnimation(gl_VertexID, localPosition, localNormal);
+
+                    // Tangents are not baked, so re-orthogonalize the original tangent against the
+                    // animated normal.
+                    vec3 localTangent = vertexTangent.xyz - localNormal * dot(localNormal, vertexTangent.xyz);
+
+                    mat3 nm = mat3(fyrox_worldMatrix);
+                    normal = normalize(nm * localNormal);
+                    tangent = normalize(nm * localTangent);
+                    binormal = normalize(vertexTangent.w * cross(normal, tangent));
+                    texCoord = vertexTexCoord;
+                    position = vec3(fyrox_worldMatrix * vec4(localPosition, 1.0));
+                    secondTexCoord = vertexSecondTexCoord;
+
+                    gl_Position = fyrox_worldViewProjection * vec4(localPosition, 1.0);
+                }
+                "#,
+            fragment_shader:
+                r#"
+                #include "fyrox/triplanar.glsl"
+
+                layout(location = 0) out vec4 outColor;
+                layout(location = 1) out vec4 outNormal;
+                layout(location = 2) out vec4 outAmbient;
+                layout(location = 3) out vec4 outMaterial;
+                layout(location = 4) out uint outDecalMask;
+
+                // Properties.
+                uniform sampler2D diffuseTexture;
+                uniform sampler2D normalTexture;
+                uniform sampler2D metallicTexture;
+                uniform sampler2D roughnessTexture;
+                uniform sampler2D heightTexture;
+                uniform sampler2D emissionTexture;
+                uniform sampler2D lightmapTexture;
+                uniform sampler2D aoTexture;
+                uniform vec2 texCoordScale;
+                uniform uint layerIndex;
+                uniform uint fyrox_lightLinkMask;
+                uniform vec3 emissionStrength;
+                uniform vec4 diffuseColor;
+                uniform float parallaxCenter;
+                uniform float parallaxScale;
+                uniform bool useTriplanarMapping;
+                uniform float triplanarScale;
+                uniform float triplanarSharpness;
+                uniform sampler2D detailDiffuseTexture;
+                uniform sampler2D detailNormalTexture;
+                uniform vec2 detailTexCoordScale;
+                uniform float detailStrength;
+                uniform float detailFadeStart;
+                uniform float detailFadeEnd;
+
+                // Define uniforms with reserved names. Fyrox will automatically provide
+                // required data to these uniforms.
+                uniform vec3 fyrox_cameraPosition;
+                uniform bool fyrox_usePOM;
+
+                in vec3 position;
+                in vec3 normal;
+                in vec2 texCoord;
+                in vec3 tangent;
+                in vec3 binormal;
+                in vec2 secondTexCoord;
+
+                void main()
+                {
+                    mat3 tangentSpace = mat3(tangent, binormal, normal);
+                    vec3 toFragment = normalize(position - fyrox_cameraPosition);
+
+                    // Detail layers fade out with distance to hide tiling.
+                    float detailBlend = detailStrength
+                        * (1.0 - smoothstep(detailFadeStart, detailFadeEnd, distance(position, fyrox_cameraPosition)));
+
+                    vec4 albedo;
+                    vec3 worldNormal;
+                    float metallic;
+                    float roughness;
+                    float ambientOcclusion;
+                    vec3 emission;
+
+                    if (useTriplanarMapping) {
+                        TTriplanarCoords coords = S_TriplanarCoords(position, normal, triplanarScale, triplanarSharpness);
+                        TTriplanarCoords detailCoords = S_TriplanarCoords(
+                            position,
+                            normal,
+                            triplanarScale * detailTexCoordScale.x,
+                            triplanarSharpness
+                        );
+
+                        albedo = diffuseColor * S_TriplanarSample(diffuseTexture, coords);
+                        albedo.rgb *= mix(vec3(1.0), S_TriplanarSample(detailDiffuseTexture, detailCoords).rgb, detailBlend);
+
+                        vec3 baseNormal = S_TriplanarNormal(normalTexture, coords, normal);
+                        vec3 detailNormal = S_TriplanarNormal(detailNormalTexture, detailCoords, normal);
+                        worldNormal = normalize(baseNormal + (detailNormal - normal) * detailBlend);
+
+                        metallic = S_TriplanarSample(metallicTexture, coords).r;
+                        roughness = S_TriplanarSample(roughnessTexture, coords).r;
+                        ambientOcclusion = S_TriplanarSample(aoTexture, coords).r;
+                        emission = S_TriplanarSample(emissionTexture, coords).rgb;
+                    } else {
+                        vec2 tc;
+                        if (fyrox_usePOM) {
+                            vec3 toFragmentTangentSpace = normalize(transpose(tangentSpace) * toFragment);
+                            tc = S_ComputeParallaxTextureCoordinates(
+                                heightTexture,
+                                toFragmentTangentSpace,
+                                texCoord * texCoordScale,
+                                parallaxCenter,
+                                parallaxScale
+                            );
+                        } else {
+                            tc = texCoord * texCoordScale;
+                        }
+                        vec2 detailTc = tc * detailTexCoordScale;
+
+                        albedo = diffuseColor * texture(diffuseTexture, tc);
+                        albedo.rgb *= mix(vec3(1.0), texture(detailDiffuseTexture, detailTc).rgb, detailBlend);
+
+                        vec3 n = texture(normalTexture, tc).xyz * 2.0 - 1.0;
+                        vec3 dn = texture(detailNormalTexture, detailTc).xyz * 2.0 - 1.0;
+                        n = vec3(n.xy + dn.xy * detailBlend, n.z);
+                        worldNormal = normalize(tangentSpace * normalize(n));
+
+                        metallic = texture(metallicTexture, tc).r;
+                        roughness = texture(roughnessTexture, tc).r;
+                        ambientOcclusion = texture(aoTexture, tc).r;
+                        emission = texture(emissionTexture, tc).rgb;
+                    }
+
+                    outColor = albedo;
+
+                    // Alpha test.
+                    if (outColor.a < 0.5) {
+                        discard;
+                    }
+                    outColor.a = 1.0;
+
+                    outNormal = vec4(worldNormal * 0.5 + 0.5, 1.0);
+
+                    outMaterial.x = metallic;
+                    outMaterial.y = roughness;
+                    outMaterial.z = ambientOcclusion;
+                    outMaterial.a = 1.0;
+
+                    outAmbient.xyz = emissionStrength * emission + texture(lightmapTexture, secondTexCoord).rgb;
+                    outAmbient.a = 1.0;
+
+                    outDecalMask = (layerIndex & 0xFFu) | fyrox_lightLinkMask;
+                }
+                "#,
+        ),
+        (
+            name: "Forward",
+            draw_parameters: DrawParameters(
+                cull_face: Some(Back),
+                color_write: ColorMask(
+                    red: true,
+                    green: true,
+                    blue: true,
+                    alpha: true,
+                ),
+                depth_write: true,
+                stencil_test: None,
+                depth_test: true,
+                blend: Some(BlendParameters(
+                    func: BlendFunc(
+                        sfactor: SrcAlpha,
+                        dfactor: OneMinusSrcAlpha,
+                        alpha_sfactor: SrcAlpha,
+                        alpha_dfactor: OneMinusSrcAlpha,
+                    ),
+                    equation: BlendEquation(
+                        rgb: Add,
+                        alpha: Add
+                    )
+                )),
+                stencil_op: StencilOp(
+                    fail: Keep,
+                    zfail: Keep,
+                    zpass: Keep,
+                    write_mask: 0xFFFF_FFFF,
+                ),
+            ),
+            vertex_shader:
+               r#"
+                #include "fyrox/vertex_animation.glsl"
+
+                layout(location = 0) in vec3 vertexPosition;
+                layout(location = 1) in vec2 vertexTexCoord;
+                layout(location = 2) in vec3 vertexNormal;
+
+                uniform mat4 fyrox_worldViewProjection;
+
+                out vec3 position;
+                out vec2 texCoord;
+
+                void main()
+                {
+                    vec3 localPosition = vertexPosition;
+                    vec3 localNormal = vertexNormal;
+
+                    S_ApplyVertexAnimation(gl_VertexID, localPosition, localNormal);
+
+                    gl_Position = fyrox_worldViewProjection * vec4(localPosition, 1.0);
+                    texCoord = vertexTexCoord;
+                }
+               "#,
+
+           fragment_shader:
+               r#"
+                uniform sampler2D diffuseTexture;
+                uniform vec4 diffuseColor;
+
+                out vec4 FragColor;
+
+                in vec2 texCoord;
+
+                void main()
+                {
+                    FragColor = diffuseColor * texture(diffuseTexture, texCoord);
+                }
+               "#,
+        ),
+        (
+            name: "DirectionalShadow",
+
+            draw_parameters: DrawParameters (
+                cull_face: Some(Back),
+                color_write: ColorMask(
+                    red: false,
+                    green: false,
+                    blue: false,
+                    alpha: false,
+                ),
+                depth_write: true,
+                stencil_test: None,
+                depth_test: true,
+                blend: None,
+                stencil_op: StencilOp(
+                    fail: Keep,
+                    zfail: Keep,
+                    zpass: Keep,
+                    write_mask: 0xFFFF_FFFF,
+                ),
+            ),
+
+            vertex_shader:
+                r#"
+                #include "fyrox/vertex_animation.glsl"
+
+                layout(location = 0) in vec3 vertexPosition;
+                layout(location = 1) in vec2 vertexTexCoord;
+                layout(location = 2) in vec3 vertexNormal;
+
+                uniform mat4 fyrox_worldViewProjection;
+
+                out vec2 texCoord;
+
+                void main()
+                {
+                    vec3 localPosition = vertexPosition;
+                    vec3 localNormal = vertexNormal;
+
+                    S_ApplyVertexAnimation(gl_VertexID, localPosition, localNormal);
+
+                    gl_Position = fyrox_worldViewProjection * vec4(localPosition, 1.0);
+                    texCoord = vertexTexCoord;
+                }
+                "#,
+
+            fragment_shader:
+                r#"
+                uniform sampler2D diffuseTexture;
+
+                in vec2 texCoord;
+
+                void main()
+                {
+                    if (texture(diffuseTexture, texCoord).a < 0.2) discard;
+                }
+                "#,
+        ),
+        (
+            name: "SpotShadow",
+
+            draw_parameters: DrawParameters (
+                cull_face: Some(Back),
+                color_write: ColorMask(
+                    red: false,
+                    green: false,
+                    blue: false,
+                    alpha: false,
+                ),
+                depth_write: true,
+                stencil_test: None,
+                depth_test: true,
+                blend: None,
+                stencil_op: StencilOp(
+                    fail: Keep,
+                    zfail: Keep,
+                    zpass: Keep,
+                    write_mask: 0xFFFF_FFFF,
+                ),
+            ),
+
+            vertex_shader:
+                r#"
+                #include "fyrox/vertex_animation.glsl"
+
+                layout(location = 0) in vec3 vertexPosition;
+                layout(location = 1) in vec2 vertexTexCoord;
+                layout(location = 2) in vec3 vertexNormal;
+
+                uniform mat4 fyrox_worldViewProjection;
+
+                out vec2 texCoord;
+
+                void main()
+                {
+                    vec3 localPosition = vertexPosition;
+                    vec3 localNormal = vertexNormal;
+
+                    S_ApplyVertexAnimation(gl_VertexID, localPosition, localNormal);
+
+                    gl_Position = fyrox_worldViewProjection * vec4(localPosition, 1.0);
+                    texCoord = vertexTexCoord;
+                }
+                "#,
+
+            fragment_shader:
+                r#"
+                uniform sampler2D diffuseTexture;
+
+                in vec2 texCoord;
+
+                void main()
+                {
+                    if (texture(diffuseTexture, texCoord).a < 0.2) discard;
+                }
+                "#,
+        ),
+        (
+            name: "PointShadow",
+
+            draw_parameters: DrawParameters (
+                cull_face: Some(Back),
+                color_write: ColorMask(
+                    red: true,
+                    green: true,
+                    blue: true,
+                    alpha: true,
+                ),
+                depth_write: true,
+                stencil_test: None,
+                depth_test: true,
+                blend: None,
+                stencil_op: StencilOp(
+                    fail: Keep,
+                    zfail: Keep,
+                    zpass: Keep,
+                    write_mask: 0xFFFF_FFFF,
+                ),
+            ),
+
+            vertex_shader:
+                r#"
+                #include "fyrox/vertex_animation.glsl"
+
+                layout(location = 0) in vec3 vertexPosition;
+                layout(location = 1) in vec2 vertexTexCoord;
+                layout(location = 2) in vec3 vertexNormal;
+
+                uniform mat4 fyrox_worldMatrix;
+                uniform mat4 fyrox_worldViewProjection;
+
+                out vec2 texCoord;
+                out vec3 worldPosition;
+
+                void main()
+                {
+                    vec3 localPosition = vertexPosition;
+                    vec3 localNormal = vertexNormal;
+
+                    S_ApplyVertexAnimation(gl_VertexID, localPosition, localNormal);
+
+                    gl_Position = fyrox_worldViewProjection * vec4(localPosition, 1.0);
+                    worldPosition = (fyrox_worldMatrix * vec4(localPosition, 1.0)).xyz;
+                    texCoord = vertexTexCoord;
+                }
+                "#,
+
+            fragment_shader:
+                r#"
+                uniform sampler2D diffuseTexture;
+
+                uniform vec3 fyrox_lightPosition;
+
+                in vec2 texCoord;
+                in vec3 worldPosition;
+
+                layout(location = 0) out float depth;
+
+                void main()
+                {
+                    if (texture(diffuseTexture, texCoord).a < 0.2) discard;
+                    depth = length(fyrox_lightPosition - worldPosition);
+                }
+                "#,
+        )
+    ],
+)
\ No newline at end of file
@@ -0,0 +1,209 @@
+(
+    name: "StandardWaterShader",
+
+    properties: [
+        (
+            name: "shallowColor",
+            kind: Color(r: 40, g: 160, b: 150, a: 255),
+        ),
+        (
+            name: "deepColor",
+            kind: Color(r: 5, g: 35, b: 60, a: 255),
+        ),
+        (
+            name: "skyColor",
+            kind: Color(r: 150, g: 190, b: 230, a: 255),
+        ),
+        (
+            name: "foamColor",
+            kind: Color(r: 255, g: 255, b: 255, a: 255),
+        ),
+        (
+            name: "foamTexture",
+            kind: Sampler(default: None, fallback: White),
+        ),
+        (
+            name: "foamTexCoordScale",
+            kind: Vector2((0.25, 0.25)),
+        ),
+        (
+            name: "absorptionDistance",
+            kind: Float(6.0),
+        ),
+        (
+            name: "refractionStrength",
+            kind: Float(0.04),
+        ),
+        (
+            name: "roughness",
+            kind: Float(0.08),
+        ),
+        (
+            name: "edgeFadeDistance",
+            kind: Float(0.3),
+        ),
+    ],
+
+    passes: [
+        (
+            name: "Forward",
+            draw_parameters: DrawParameters(
+                cull_face: None,
+                color_write: ColorMask(
+                    red: true,
+                    green: true,
+                    blue: true,
+                    alpha: true,
+                ),
+                depth_write: true,
+                stencil_test: None,
+                depth_test: true,
+                blend: None,
+                stencil_op: StencilOp(
+                    fail: Keep,
+                    zfail: Keep,
+                    zpass: Keep,
+                    write_mask: 0xFFFF_FFFF,
+                ),
+            ),
+            vertex_shader:
+               r#"
+               layout(location = 0) in vec3 vertexPosition;
+               layout(location = 1) in vec3 vertexNormal;
+               layout(location = 2) in vec2 vertexTexCoord;
+               layout(location = 3) in float vertexFoam;
+
+               uniform mat4 fyrox_worldMatrix;
+               uniform mat4 fyrox_viewProjectionMatrix;
+
+               out vec3 worldPosition;
+               out vec3 normal;
+               out vec2 texCoord;
+               out float foam;
+
+               void main()
+               {
+                   worldPosition = (fyrox_worldMatrix * vec4(vertexPosition, 1.0)).xyz;
+                   normal = normalize(mat3(fyrox_worldMatrix) * vertexNormal);
+                   texCoord = vertexTexCoord;
+                   foam = vertexFoam;
+                   gl_Position = fyrox_viewProjectionMatrix * vec4(worldPosition, 1.0);
+               }
+               "#,
+
+           fragment_shader:
+               r#"
+               uniform vec4 shallowColor;
+               uniform vec4 deepColor;
+               uniform vec4 skyColor;
+               uniform vec4 foamColor;
+               uniform sampler2D foamTexture;
+               uniform vec2 foamTexCoordScale;
+               uniform float absorptionDistance;
+               uniform float refractionStrength;
+               uniform float roughness;
+               uniform float edgeFadeDistance;
+
+               uniform sampler2D fyrox_sceneDepth;
+               uniform sampler2D fyrox_sceneColor;
+               uniform float fyrox_zNear;
+               uniform float fyrox_zFar;
+               uniform vec3 fyrox_cameraPosition;
+               uniform int fyrox_lightCount;
+               uniform vec4 fyrox_lightsColorRadius[16]; // xyz - color, w = radius
+               uniform vec3 fyrox_lightsPosition[16];
+               uniform vec3 fyrox_lightsDirection[16];
+               uniform vec2 fyrox_lightsParameters[16]; // x = hotspot angle, y - full cone angle delta
+               uniform vec4 fyrox_lightsParameters2D[16]; // y - falloff (0 for 3D lights)
+               uniform vec4 fyrox_ambientLightColor;
+
+               in vec3 worldPosition;
+               in vec3 normal;
+               in vec2 texCoord;
+               in float foam;
+
+               out vec4 FragColor;
+
+               float toProjSpace(float z)
+               {
+                   return (fyrox_zFar * fyrox_zNear) / (fyrox_zFar - z * (fyrox_zFar - fyrox_zNear));
+               }
+
+               void main()
+               {
+                   vec3 N = normalize(gl_FrontFacing ? normal : -normal);
+                   vec3 V = normalize(fyrox_cameraPosition - worldPosition);
+                   float NdotV = max(dot(N, V), 0.0001);
+
+                   ivec2 sceneSize = textureSize(fyrox_sceneDepth, 0);
+                   vec2 pixelSize = vec2(1.0 / float(sceneSize.x), 1.0 / float(sceneSize.y));
+                   vec2 screenTexCoord = gl_FragCoord.xy * pixelSize;
+                   float fragmentDepth = toProjSpace(gl_FragCoord.z);
+
+                   // Screen-space refraction: offset the lookup by the normal of the surface, but
+                   // reject samples of objects that are in front of the water.
+                   float thickness = toProjSpace(texture(fyrox_sceneDepth, screenTexCoord).r) - fragmentDepth;
+                   vec2 refractedTexCoord = screenTexCoord + N.xz * refractionStrength * clamp(thickness, 0.0, 1.0);
+                   float refractedThickness = toProjSpace(texture(fyrox_sceneDepth, refractedTexCoord).r) - fragmentDepth;
+                   if (refractedThickness < 0.0) {
+                       refractedTexCoord = screenTexCoord;
+                       refractedThickness = thickness;
+                   }
+                   refractedThickness = max(refractedThickness, 0.0);
+
+                   // Absorption - the deeper the water, the less of the bottom is visible.
+                   vec3 waterColor = mix(S_SRGBToLinear(shallowColor).rgb, S_SRGBToLinear(deepColor).rgb,
+                       clamp(refractedThickness / max(absorptionDistance, 0.0001), 0.0, 1.0));
+                   float transmittance = exp(-refractedThickness / max(absorptionDistance, 0.0001) * 3.0);
+                   vec3 refracted = texture(fyrox_sceneColor, refractedTexCoord).rgb * waterColor * transmittance
+                       + waterColor * (1.0 - transmittance) * fyrox_ambientLightColor.rgb;
+
+                   // Reflection of the sky and specular highlights of the lights.
+                   float fresnel = S_FresnelSchlick(NdotV, vec3(0.02)).x;
+                   vec3 reflected = S_SRGBToLinear(skyColor).rgb * fyrox_ambientLightColor.rgb;
+                   vec3 lighting = vec3(0.0);
+                   vec3 diffuseLight = fyrox_ambientLightColor.rgb;
+                   for (int i = 0; i < fyrox_lightCount; ++i) {
+                       if (fyrox_lightsParameters2D[i].y > 0.0) {
+                           continue;
+                       }
+
+                       vec3 lightColor = fyrox_lightsColorRadius[i].xyz;
+                       float radius = fyrox_lightsColorRadius[i].w;
+                       vec3 direction = fyrox_lightsDirection[i];
+                       vec3 L;
+                       float attenuation;
+                       if (isinf(radius)) {
+                           L = normalize(direction);
+                           attenuation = 1.0;
+                       } else {
+                           vec3 toLight = fyrox_lightsPosition[i] - worldPosition;
+                           float distance = length(toLight);
+                           L = toLight / max(distance, 0.0001);
+                           float spotAngleCos = dot(direction, L);
+                           attenuation = S_LightDistanceAttenuation(distance, radius)
+                               * smoothstep(fyrox_lightsParameters[i].y, fyrox_lightsParameters[i].x, spotAngleCos);
+                       }
+
+                       vec3 H = normalize(L + V);
+                       float NdotL = max(dot(N, L), 0.0);
+                       float specular = S_DistributionGGX(N, H, max(roughness, 0.02)) * S_GeometrySmith(N, V, L, max(roughness, 0.02))
+                           / (4.0 * NdotV * max(NdotL, 0.0001));
+                       vec3 radiance = lightColor * attenuation * NdotL;
+                       lighting += radiance * (specular * fresnel + waterColor * (1.0 - transmittance) * (1.0 - fresnel) / PI);
+                       diffuseLight += radiance;
+                   }
+
+                   vec3 color = mix(refracted, reflected, fresnel) + lighting;
+
+                   // Foam on the crests of the waves and at the shore line.
+                   float shoreFoam = 1.0 - clamp(thickness / max(edgeFadeDistance, 0.0001), 0.0, 1.0);
+                   float foamFactor = clamp(foam + shoreFoam, 0.0, 1.0) * texture(foamTexture, worldPosition.xz * foamTexCoordScale).r;
+                   color = mix(color, S_SRGBToLinear(foamColor).rgb * diffuseLight, foamFactor);
+
+                   FragColor = vec4(color, 1.0);
+               }
+               "#,
+        )
+    ],
+)
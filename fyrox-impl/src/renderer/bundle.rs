@@ -9,9 +9,11 @@ use crate::{
         sstorage::ImmutableString,
     },
     graph::BaseSceneGraph,
-    material::MaterialResource,
+    material::{Material, MaterialResource},
     renderer::{cache::TimeToLive, framework::geometry_buffer::ElementRange},
+    resource::texture::TextureResource,
     scene::{
+        camera::is_layer_in_mask,
         graph::Graph,
         mesh::{
             buffer::{
@@ -45,6 +47,9 @@ pub struct ObserverInfo {
     pub view_matrix: Matrix4<f32>,
     /// Projection matrix of the observer.
     pub projection_matrix: Matrix4<f32>,
+    /// A mask of layers, that are visible to the observer. Nodes on other layers are ignored, but
+    /// their descendants are still collected (if their layers are in the mask).
+    pub layer_mask: u32,
 }
 
 /// Render context is used to collect render data from the scene nodes. It provides all required information about
@@ -108,6 +113,20 @@ impl PersistentIdentifier {
     }
 }
 
+/// Vertex animation data of a surface instance. Positions and normals of the vertices are fetched from
+/// a vertex animation texture instead of the vertex buffer, see [`crate::scene::crowd::vat`] for more info.
+#[derive(Clone, Debug)]
+pub struct VertexAnimationInstanceData {
+    /// A texture with baked positions and normals of every vertex in every frame.
+    pub texture: TextureResource,
+    /// Amount of vertices in a single frame.
+    pub vertex_count: u32,
+    /// Indices of two frames that will be blended.
+    pub frames: [u32; 2],
+    /// Blending factor between the frames in `[0; 1]` range.
+    pub blend: f32,
+}
+
 /// A set of data of a surface for rendering.  
 pub struct SurfaceInstanceData {
     /// A world matrix.
@@ -126,6 +145,8 @@ pub struct SurfaceInstanceData {
     pub persistent_identifier: PersistentIdentifier,
     /// A handle of a node that emitted this surface data. Could be none, if there's no info about scene node.
     pub node_handle: Handle<Node>,
+    /// Optional vertex animation data of the instance.
+    pub vertex_animation: Option<VertexAnimationInstanceData>,
 }
 
 /// A set of surface instances that share the same vertex/index data and a material.
@@ -145,9 +166,30 @@ pub struct RenderDataBundle {
     pub render_path: RenderPath,
     /// A decal layer index of the bundle.
     pub decal_layer_index: u8,
+    /// Render queue of the material of the bundle at the moment of bundle creation. See
+    /// [`Material::render_queue`] docs for more info.
+    pub render_queue: i32,
+    /// Pass tags of the material of the bundle at the moment of bundle creation. See
+    /// [`Material::pass_tags`] docs for more info.
+    pub pass_tags: Vec<ImmutableString>,
     sort_index: u64,
 }
 
+impl RenderDataBundle {
+    /// Returns `true` if the material of the bundle has the given pass tag.
+    pub fn has_pass_tag(&self, tag: &str) -> bool {
+        self.pass_tags.iter().any(|t| t.as_str() == tag)
+    }
+}
+
+fn material_draw_order(material: &MaterialResource) -> (i32, Vec<ImmutableString>) {
+    let mut state = material.state();
+    match state.data() {
+        Some(material) => (material.render_queue(), material.pass_tags().to_vec()),
+        None => (Material::RENDER_QUEUE_GEOMETRY, Vec::new()),
+    }
+}
+
 impl Debug for RenderDataBundle {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -238,16 +280,22 @@ impl RenderDataBundleStorage {
         let mut lod_filter = vec![true; graph.capacity() as usize];
         for node in graph.linear_iter() {
             if let Some(lod_group) = node.lod_group() {
-                for level in lod_group.levels.iter() {
+                let forced_level = node.forced_lod_level();
+                for (level_index, level) in lod_group.levels.iter().enumerate() {
                     for &object in level.objects.iter() {
                         if let Some(object_ref) = graph.try_get(object) {
-                            let distance = observer_info
-                                .observer_position
-                                .metric_distance(&object_ref.global_position());
-                            let z_range = observer_info.z_far - observer_info.z_near;
-                            let normalized_distance = (distance - observer_info.z_near) / z_range;
-                            let visible = normalized_distance >= level.begin()
-                                && normalized_distance <= level.end();
+                            let visible = if let Some(forced_level) = forced_level {
+                                forced_level == level_index
+                            } else {
+                                let metric = lod_group.normalized_metric(
+                                    object_ref,
+                                    &observer_info.observer_position,
+                                    &observer_info.projection_matrix,
+                                    observer_info.z_near,
+                                    observer_info.z_far,
+                                );
+                                metric >= level.begin() && metric <= level.end()
+                            };
                             lod_filter[object.index() as usize] = visible;
                         }
                     }
@@ -277,7 +325,12 @@ impl RenderDataBundleStorage {
         while let Some(handle) = stack.pop() {
             if lod_filter[handle.index() as usize] {
                 let node = graph.node(handle);
-                if let RdcControlFlow::Continue = node.collect_render_data(&mut ctx) {
+                let control_flow = if is_layer_in_mask(observer_info.layer_mask, node.layer()) {
+                    node.collect_render_data(&mut ctx)
+                } else {
+                    RdcControlFlow::Continue
+                };
+                if let RdcControlFlow::Continue = control_flow {
                     stack.extend_from_slice(node.children());
                 }
             }
@@ -288,9 +341,30 @@ impl RenderDataBundleStorage {
         storage
     }
 
-    /// Sorts the bundles by their respective sort index.
+    /// Sorts the bundles by render queue of their materials first and then by their respective sort
+    /// index.
     pub fn sort(&mut self) {
-        self.bundles.sort_unstable_by_key(|b| b.sort_index);
+        self.bundles
+            .sort_unstable_by_key(|b| (b.render_queue, b.sort_index));
+    }
+
+    /// Returns an iterator over the bundles (in the drawing order), whose materials have the given
+    /// pass tag. It could be used by custom scene render passes to draw only specific render data.
+    ///
+    /// ```rust
+    /// # use fyrox_impl::renderer::bundle::RenderDataBundleStorage;
+    /// fn count_outlined_instances(storage: &RenderDataBundleStorage) -> usize {
+    ///     storage
+    ///         .bundles_with_tag("Outline")
+    ///         .map(|bundle| bundle.instances.len())
+    ///         .sum()
+    /// }
+    /// ```
+    pub fn bundles_with_tag<'a>(
+        &'a self,
+        tag: &'a str,
+    ) -> impl Iterator<Item = &'a RenderDataBundle> + 'a {
+        self.bundles.iter().filter(move |b| b.has_pass_tag(tag))
     }
 }
 
@@ -359,6 +433,7 @@ impl RenderDataBundleStorageTrait for RenderDataBundleStorage {
             );
 
             self.bundle_map.insert(key, self.bundles.len());
+            let (render_queue, pass_tags) = material_draw_order(material);
             let persistent_identifier = PersistentIdentifier::new_combined(&data, node_handle, 0);
             self.bundles.push(RenderDataBundle {
                 data,
@@ -373,12 +448,15 @@ impl RenderDataBundleStorageTrait for RenderDataBundleStorage {
                         element_range: Default::default(),
                         persistent_identifier,
                         node_handle,
+                        vertex_animation: None,
                     },
                 ],
                 material: material.clone(),
                 is_skinned,
                 render_path,
                 decal_layer_index,
+                render_queue,
+                pass_tags,
                 // Temporary buffer lives one frame.
                 time_to_live: TimeToLive(0.0),
             });
@@ -420,6 +498,7 @@ impl RenderDataBundleStorageTrait for RenderDataBundleStorage {
             self.bundles.get_mut(bundle_index).unwrap()
         } else {
             self.bundle_map.insert(key, self.bundles.len());
+            let (render_queue, pass_tags) = material_draw_order(material);
             self.bundles.push(RenderDataBundle {
                 data: data.clone(),
                 sort_index,
@@ -428,6 +507,8 @@ impl RenderDataBundleStorageTrait for RenderDataBundleStorage {
                 is_skinned,
                 render_path,
                 decal_layer_index,
+                render_queue,
+                pass_tags,
                 time_to_live: Default::default(),
             });
             self.bundles.last_mut().unwrap()
@@ -1,6 +1,6 @@
 use crate::{
     renderer::{
-        cache::{TemporaryCache, TimeToLive},
+        cache::{CacheStatistics, TemporaryCache, TimeToLive},
         framework::{
             error::FrameworkError,
             geometry_buffer::{GeometryBuffer, GeometryBufferKind},
@@ -95,4 +95,8 @@ impl GeometryCache {
     pub fn clear(&mut self) {
         self.buffer.clear();
     }
+
+    pub fn statistics(&self) -> CacheStatistics {
+        self.buffer.statistics()
+    }
 }
@@ -68,14 +68,29 @@ impl<T> DerefMut for CacheEntry<T> {
     }
 }
 
+/// Cumulative usage statistics of a cache.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct CacheStatistics {
+    /// Amount of entries, that are currently alive in the cache.
+    pub alive: usize,
+    /// Total amount of requests, that have found an existing entry.
+    pub hits: usize,
+    /// Total amount of requests, that have caused creation of a new entry.
+    pub misses: usize,
+    /// Total amount of entries, that were removed because their lifetime has expired.
+    pub evictions: usize,
+}
+
 pub struct TemporaryCache<T> {
     pub buffer: SparseBuffer<CacheEntry<T>>,
+    statistics: CacheStatistics,
 }
 
 impl<T> Default for TemporaryCache<T> {
     fn default() -> Self {
         Self {
             buffer: Default::default(),
+            statistics: Default::default(),
         }
     }
 }
@@ -104,9 +119,11 @@ impl<T> TemporaryCache<T> {
 
     pub fn get_mut(&mut self, index: &AtomicIndex) -> Option<&mut CacheEntry<T>> {
         if let Some(entry) = self.buffer.get_mut(index) {
+            self.statistics.hits += 1;
             entry.time_to_live = TimeToLive::default();
             Some(entry)
         } else {
+            self.statistics.misses += 1;
             None
         }
     }
@@ -121,9 +138,11 @@ impl<T> TemporaryCache<T> {
         F: FnOnce() -> Result<T, E>,
     {
         if let Some(entry) = self.buffer.get_mut(index) {
+            self.statistics.hits += 1;
             entry.time_to_live = time_to_live;
             Ok(self.buffer.get_mut(index).unwrap())
         } else {
+            self.statistics.misses += 1;
             let value = func()?;
             let index = self.buffer.spawn(CacheEntry {
                 value,
@@ -171,6 +190,7 @@ impl<T> TemporaryCache<T> {
             if let Some(entry) = self.buffer.get_raw(i) {
                 if *entry.time_to_live <= 0.0 {
                     self.buffer.free_raw(i);
+                    self.statistics.evictions += 1;
                 }
             }
         }
@@ -180,7 +200,55 @@ impl<T> TemporaryCache<T> {
         self.buffer.clear();
     }
 
+    /// Returns cumulative usage statistics of the cache.
+    pub fn statistics(&self) -> CacheStatistics {
+        CacheStatistics {
+            alive: self.buffer.filled(),
+            ..self.statistics
+        }
+    }
+
     pub fn remove(&mut self, index: &AtomicIndex) {
         self.buffer.free(index);
     }
+
+    /// Removes an entry at the given raw index of the buffer and counts it as evicted.
+    pub fn evict_raw(&mut self, index: usize) {
+        if self.buffer.get_raw(index).is_some() {
+            self.buffer.free_raw(index);
+            self.statistics.evictions += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::sparse::AtomicIndex,
+        renderer::cache::{CacheStatistics, TemporaryCache, TimeToLive},
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn test_statistics() {
+        let mut cache = TemporaryCache::<u32>::default();
+        let index = Arc::new(AtomicIndex::unassigned());
+
+        for _ in 0..3 {
+            cache
+                .get_or_insert_with(&index, TimeToLive(1.0), || Ok::<_, ()>(123))
+                .unwrap();
+        }
+        cache.update(2.0);
+
+        assert_eq!(
+            cache.statistics(),
+            CacheStatistics {
+                alive: 0,
+                hits: 2,
+                misses: 1,
+                evictions: 1,
+            }
+        );
+    }
 }
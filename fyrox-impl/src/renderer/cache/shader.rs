@@ -1,10 +1,11 @@
-use crate::renderer::cache::TemporaryCache;
+use crate::renderer::cache::{CacheStatistics, TemporaryCache, TimeToLive};
 use crate::renderer::framework::error::FrameworkError;
 use crate::{
     core::sstorage::ImmutableString,
     material::shader::{Shader, ShaderResource},
-    renderer::framework::{
-        framebuffer::DrawParameters, gpu_program::GpuProgram, state::PipelineState,
+    renderer::{
+        framework::{framebuffer::DrawParameters, gpu_program::GpuProgram, state::PipelineState},
+        instance::{inject_instance_data_block, INSTANCE_DATA_BINDING, INSTANCE_DATA_BLOCK_NAME},
     },
 };
 use fxhash::FxHashMap;
@@ -27,10 +28,18 @@ impl ShaderSet {
             match GpuProgram::from_source(
                 state,
                 &program_name,
-                &render_pass.vertex_shader,
-                &render_pass.fragment_shader,
+                &inject_instance_data_block(&render_pass.vertex_shader),
+                &inject_instance_data_block(&render_pass.fragment_shader),
             ) {
                 Ok(gpu_program) => {
+                    // The block is missing if the shader does not use any of per-instance
+                    // built-in uniforms, which is fine.
+                    let _ = gpu_program.bind_uniform_block(
+                        state,
+                        INSTANCE_DATA_BLOCK_NAME,
+                        INSTANCE_DATA_BINDING,
+                    );
+
                     map.insert(
                         ImmutableString::new(&render_pass.name),
                         RenderPassData {
@@ -52,6 +61,11 @@ impl ShaderSet {
     }
 }
 
+/// Time (in seconds) for which warmed up shaders are kept in the cache if they're not used. It is
+/// larger than the default lifetime, because the scene could be loaded some time before it is
+/// rendered for the first time (for example, while a loading screen is shown).
+pub const WARM_UP_TIME_TO_LIVE: f32 = 300.0;
+
 #[derive(Default)]
 pub struct ShaderCache {
     pub(super) cache: TemporaryCache<ShaderSet>,
@@ -89,6 +103,37 @@ impl ShaderCache {
         }
     }
 
+    /// Compiles GPU programs for every render pass of the given shaders, so the first use of the
+    /// shaders won't cause hitches. Shaders that are not loaded yet are skipped. The progress
+    /// callback is called after every shader with the amount of processed shaders and the total
+    /// amount of shaders. Returns the amount of shaders, that are ready to use.
+    pub fn warm_up<F>(
+        &mut self,
+        pipeline_state: &PipelineState,
+        shaders: &[ShaderResource],
+        mut progress: F,
+    ) -> usize
+    where
+        F: FnMut(usize, usize),
+    {
+        let mut ready = 0;
+        for (i, shader) in shaders.iter().enumerate() {
+            let mut shader_state = shader.state();
+            if let Some(shader_state) = shader_state.data() {
+                match self.cache.get_or_insert_with(
+                    &shader_state.cache_index,
+                    TimeToLive(WARM_UP_TIME_TO_LIVE),
+                    || ShaderSet::new(pipeline_state, shader_state),
+                ) {
+                    Ok(_) => ready += 1,
+                    Err(error) => Log::err(format!("{}", error)),
+                }
+            }
+            progress(i + 1, shaders.len());
+        }
+        ready
+    }
+
     pub fn update(&mut self, dt: f32) {
         self.cache.update(dt)
     }
@@ -96,4 +141,8 @@ impl ShaderCache {
     pub fn clear(&mut self) {
         self.cache.clear();
     }
+
+    pub fn statistics(&self) -> CacheStatistics {
+        self.cache.statistics()
+    }
 }
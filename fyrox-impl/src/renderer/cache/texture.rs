@@ -5,61 +5,193 @@ use crate::{
         scope_profile,
     },
     renderer::{
-        cache::TemporaryCache,
+        cache::{CacheStatistics, TemporaryCache},
         framework::{
             error::FrameworkError,
-            gpu_texture::{Coordinate, GpuTexture, PixelKind},
+            gpu_texture::{Coordinate, GpuTexture, PendingTextureUpload, PixelKind},
             state::PipelineState,
         },
     },
     resource::texture::TextureResource,
 };
+use fyrox_resource::untyped::ResourceKind;
 use std::{cell::RefCell, rc::Rc};
 
 pub(crate) struct TextureRenderData {
     pub gpu_texture: Rc<RefCell<GpuTexture>>,
     pub modifications_counter: u64,
+    /// Size of the texture data in GPU memory. It is zero for render targets, such textures are
+    /// never evicted to fit the residency budget.
+    pub size_bytes: usize,
+    /// Index of the frame, when the texture was used last time.
+    pub last_used_frame: u64,
+    /// Asynchronous upload of the texture data, the texture cannot be used until it is finished.
+    pub pending_upload: Option<PendingTextureUpload>,
 }
 
-#[derive(Default)]
+/// Default amount of bytes of texture data, that could be uploaded to GPU in a single frame.
+pub const DEFAULT_UPLOAD_BUDGET: usize = 32 * 1024 * 1024;
+
+/// Texture cache holds GPU textures for texture resources. Data of new uncompressed 2D textures is
+/// uploaded asynchronously through pixel buffer objects, so the main thread does not wait until the
+/// driver copies the data to the textures. The renderer uses fallback textures until the upload is
+/// finished. The cache also has two budgets, that allows it to work smoothly with lots of large
+/// textures:
+///
+/// - Upload budget - maximum amount of bytes of new textures, that could be uploaded to GPU in a
+///   single frame. Textures, that do not fit in the budget are uploaded on next frames and the
+///   renderer uses fallback textures for them meanwhile. At least one texture is uploaded every
+///   frame, even if it is larger than the budget.
+/// - Residency budget - maximum amount of bytes of textures, that could stay in GPU memory. If the
+///   budget is exceeded, the least recently used textures are unloaded from GPU memory. Textures,
+///   that were used in the current or the previous frame are never unloaded. The budget is not
+///   set by default.
 pub struct TextureCache {
     pub(crate) map: TemporaryCache<TextureRenderData>,
+    upload_budget: usize,
+    residency_budget: Option<usize>,
+    uploaded_bytes: usize,
+    frame: u64,
+}
+
+impl Default for TextureCache {
+    fn default() -> Self {
+        Self {
+            map: Default::default(),
+            upload_budget: DEFAULT_UPLOAD_BUDGET,
+            residency_budget: None,
+            uploaded_bytes: 0,
+            frame: 0,
+        }
+    }
+}
+
+/// Selects the least recently used entries, that must be evicted to fit the given budget. Every
+/// candidate is a tuple of raw index, last used frame and size in bytes.
+fn select_evictions(
+    mut candidates: Vec<(usize, u64, usize)>,
+    resident_bytes: usize,
+    budget: usize,
+    frame: u64,
+) -> Vec<usize> {
+    candidates.retain(|(_, last_used_frame, size)| *size > 0 && *last_used_frame + 1 < frame);
+    candidates.sort_unstable_by_key(|(_, last_used_frame, _)| *last_used_frame);
+
+    let mut resident_bytes = resident_bytes;
+    let mut evictions = Vec::new();
+    for (index, _, size) in candidates {
+        if resident_bytes <= budget {
+            break;
+        }
+        resident_bytes -= size;
+        evictions.push(index);
+    }
+    evictions
 }
 
 fn create_gpu_texture(
     state: &PipelineState,
     texture: &Texture,
+    resource_kind: &ResourceKind,
 ) -> Result<TextureRenderData, FrameworkError> {
-    GpuTexture::new(
+    let kind = texture.kind().into();
+    let pixel_kind = PixelKind::from(texture.pixel_kind());
+    let mip_count = texture.mip_count() as usize;
+    let is_async =
+        !texture.data().is_empty() && PendingTextureUpload::is_supported(kind, pixel_kind);
+
+    let mut gpu_texture = GpuTexture::new(
         state,
-        texture.kind().into(),
-        PixelKind::from(texture.pixel_kind()),
+        kind,
+        pixel_kind,
         texture.minification_filter().into(),
         texture.magnification_filter().into(),
-        texture.mip_count() as usize,
-        Some(texture.data()),
-    )
-    .map(|gpu_texture| TextureRenderData {
+        mip_count,
+        if is_async { None } else { Some(texture.data()) },
+    )?;
+
+    // Name the texture after its source file, so it could be easily found in leak reports and
+    // graphics debuggers.
+    if let ResourceKind::External(path) = resource_kind {
+        gpu_texture.set_name(state, &path.to_string_lossy());
+    }
+
+    let pending_upload = if is_async {
+        gpu_texture.bind_mut(state, 0).set_data_async(
+            kind,
+            pixel_kind,
+            mip_count,
+            texture.data(),
+        )?
+    } else {
+        None
+    };
+
+    Ok(TextureRenderData {
         gpu_texture: Rc::new(RefCell::new(gpu_texture)),
         modifications_counter: texture.modifications_count(),
+        size_bytes: texture.data().len(),
+        last_used_frame: 0,
+        pending_upload,
     })
 }
 
 impl TextureCache {
+    /// Sets the maximum amount of bytes of new textures, that could be uploaded to GPU in a single
+    /// frame. See [`TextureCache`] docs for more info.
+    pub fn set_upload_budget(&mut self, budget: usize) {
+        self.upload_budget = budget;
+    }
+
+    pub fn upload_budget(&self) -> usize {
+        self.upload_budget
+    }
+
+    /// Sets the maximum amount of bytes of textures, that could stay in GPU memory. `None` means
+    /// that there's no limit. See [`TextureCache`] docs for more info.
+    pub fn set_residency_budget(&mut self, budget: Option<usize>) {
+        self.residency_budget = budget;
+    }
+
+    pub fn residency_budget(&self) -> Option<usize> {
+        self.residency_budget
+    }
+
+    /// Returns the total amount of bytes of textures in GPU memory (except render targets).
+    pub fn resident_bytes(&self) -> usize {
+        self.map.buffer.iter().map(|entry| entry.size_bytes).sum()
+    }
+
+    /// Returns `true` if the upload budget of the current frame is exhausted.
+    pub fn is_upload_budget_exhausted(&self) -> bool {
+        self.uploaded_bytes >= self.upload_budget
+    }
+
+    /// Checks whether a texture of the given size can be uploaded in the current frame.
+    fn can_upload(&self, size_bytes: usize) -> bool {
+        self.uploaded_bytes == 0 || self.uploaded_bytes + size_bytes <= self.upload_budget
+    }
+
     /// Unconditionally uploads requested texture into GPU memory, previous GPU texture will be automatically
-    /// destroyed.
+    /// destroyed. The upload could be asynchronous, see [`TextureCache`] docs for more info.
     pub fn upload(
         &mut self,
         state: &PipelineState,
         texture: &TextureResource,
     ) -> Result<(), FrameworkError> {
-        let mut texture = texture.state();
-        if let Some(texture) = texture.data() {
-            self.map.get_entry_mut_or_insert_with(
+        let texture = texture.state();
+        let resource_kind = texture.kind();
+        if let Some(texture) = texture.data_ref() {
+            let uploaded_bytes = &mut self.uploaded_bytes;
+            let entry = self.map.get_entry_mut_or_insert_with(
                 &texture.cache_index,
                 Default::default(),
-                || create_gpu_texture(state, texture),
+                || {
+                    *uploaded_bytes += texture.data().len();
+                    create_gpu_texture(state, texture, resource_kind)
+                },
             )?;
+            entry.last_used_frame = self.frame;
             Ok(())
         } else {
             Err(FrameworkError::Custom(
@@ -75,15 +207,36 @@ impl TextureCache {
     ) -> Option<&Rc<RefCell<GpuTexture>>> {
         scope_profile!();
 
-        let mut texture_data_guard = texture_resource.state();
+        let texture_data_guard = texture_resource.state();
+        let resource_kind = texture_data_guard.kind();
 
-        if let Some(texture) = texture_data_guard.data() {
+        if let Some(texture) = texture_data_guard.data_ref() {
+            if self.map.buffer.get(&texture.cache_index).is_none()
+                && !self.can_upload(texture.data().len())
+            {
+                // The texture will be uploaded on one of the next frames.
+                return None;
+            }
+
+            let uploaded_bytes = &mut self.uploaded_bytes;
             match self
                 .map
                 .get_mut_or_insert_with(&texture.cache_index, Default::default(), || {
-                    create_gpu_texture(state, texture)
+                    *uploaded_bytes += texture.data().len();
+                    create_gpu_texture(state, texture, resource_kind)
                 }) {
                 Ok(entry) => {
+                    entry.last_used_frame = self.frame;
+
+                    // The data of new textures is uploaded asynchronously, fallback textures are
+                    // used until the upload is finished.
+                    if let Some(pending_upload) = entry.pending_upload.as_ref() {
+                        if !pending_upload.is_finished(state) {
+                            return None;
+                        }
+                        entry.pending_upload = None;
+                    }
+
                     // Check if some value has changed in resource.
 
                     // Data might change from last frame, so we have to check it and upload new if so.
@@ -163,16 +316,67 @@ impl TextureCache {
     }
 
     pub fn update(&mut self, dt: f32) {
-        self.map.update(dt)
+        self.map.update(dt);
+
+        if let Some(budget) = self.residency_budget {
+            let resident_bytes = self.resident_bytes();
+            if resident_bytes > budget {
+                let candidates = (0..self.map.buffer.len())
+                    .filter_map(|i| {
+                        self.map
+                            .buffer
+                            .get_raw(i)
+                            .map(|entry| (i, entry.last_used_frame, entry.size_bytes))
+                    })
+                    .collect();
+                for index in select_evictions(candidates, resident_bytes, budget, self.frame) {
+                    self.map.evict_raw(index);
+                }
+            }
+        }
+
+        self.uploaded_bytes = 0;
+        self.frame += 1;
     }
 
     pub fn clear(&mut self) {
         self.map.clear();
     }
 
+    pub fn statistics(&self) -> CacheStatistics {
+        self.map.statistics()
+    }
+
     pub fn unload(&mut self, texture: TextureResource) {
         if let Some(texture) = texture.state().data() {
             self.map.remove(&texture.cache_index);
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::renderer::cache::texture::select_evictions;
+
+    #[test]
+    fn test_select_evictions() {
+        let candidates = vec![
+            // Render target.
+            (0, 0, 0),
+            (1, 5, 100),
+            (2, 1, 100),
+            // Used in the previous frame.
+            (3, 9, 100),
+            (4, 3, 100),
+        ];
+        assert_eq!(
+            select_evictions(candidates.clone(), 400, 500, 10),
+            Vec::<usize>::new()
+        );
+        assert_eq!(
+            select_evictions(candidates.clone(), 400, 250, 10),
+            vec![2, 4]
+        );
+        assert_eq!(select_evictions(candidates, 400, 0, 10), vec![2, 4, 1]);
+    }
+}
@@ -18,7 +18,7 @@ use crate::{
         state::PipelineState,
     },
     renderer::RenderPassStatistics,
-    scene::{camera::Camera, debug::SceneDrawingContext},
+    scene::{camera::Camera, debug::SceneDrawingContext, debug_draw::DebugDraw},
 };
 
 #[repr(C)]
@@ -33,6 +33,8 @@ pub struct DebugRenderer {
     vertices: Vec<Vertex>,
     line_indices: Vec<[u32; 2]>,
     shader: DebugShader,
+    /// Lines of the shapes of [`DebugDraw`], the buffer is reused across frames.
+    debug_draw_lines: SceneDrawingContext,
 }
 
 pub(crate) struct DebugShader {
@@ -79,6 +81,7 @@ impl DebugRenderer {
             shader: DebugShader::new(state)?,
             vertices: Default::default(),
             line_indices: Default::default(),
+            debug_draw_lines: Default::default(),
         })
     }
 
@@ -88,6 +91,7 @@ impl DebugRenderer {
         viewport: Rect<i32>,
         framebuffer: &mut FrameBuffer,
         drawing_context: &SceneDrawingContext,
+        debug_draw: &DebugDraw,
         camera: &Camera,
     ) -> Result<RenderPassStatistics, FrameworkError> {
         scope_profile!();
@@ -97,8 +101,15 @@ impl DebugRenderer {
         self.vertices.clear();
         self.line_indices.clear();
 
+        self.debug_draw_lines.clear_lines();
+        debug_draw.collect_lines(&camera.view_matrix(), &mut self.debug_draw_lines);
+
         let mut i = 0;
-        for line in drawing_context.lines.iter() {
+        for line in drawing_context
+            .lines
+            .iter()
+            .chain(self.debug_draw_lines.lines.iter())
+        {
             let color = line.color.into();
             self.vertices.push(Vertex {
                 position: line.begin,
@@ -20,21 +20,88 @@ use crate::{
         cache::{shader::ShaderCache, texture::TextureCache},
         framework::{
             error::FrameworkError, framebuffer::FrameBuffer, gpu_texture::GpuTexture,
-            state::PipelineState,
+            state::PipelineState, uniform::UniformBufferRing,
         },
+        instance::{upload_bundle_instances, InstanceUniforms},
         storage::MatrixStorageCache,
-        GeometryCache, LightData, MaterialContext, QualitySettings, RenderPassStatistics,
+        GeometryCache, LightData, MaterialContext, PrecipitationOcclusion, QualitySettings,
+        RenderPassStatistics, MAX_OCCLUDER_SEGMENTS,
     },
     scene::{
         camera::Camera,
+        dim2::{
+            light::{Light2D, Light2DKind},
+            occluder::Occluder2D,
+        },
         graph::Graph,
-        light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
+        light::{
+            area::AreaLight, directional::DirectionalLight, point::PointLight, spot::SpotLight,
+        },
         mesh::RenderPath,
     },
 };
 use fyrox_core::math::Matrix4Ext;
 use std::{cell::RefCell, rc::Rc};
 
+/// Collects world-space segments of 2D occluders, that are in the range of at least one shadow casting 2D
+/// light. Segments, that exceed [`MAX_OCCLUDER_SEGMENTS`] are discarded.
+fn collect_occluder_segments(graph: &Graph, light_data: &mut LightData) {
+    let shadow_casters = (0..light_data.count)
+        .filter(|i| light_data.parameters_2d[*i].w > 0.0)
+        .map(|i| {
+            (
+                light_data.position[i].xy(),
+                light_data.color_radius[i].w + light_data.parameters_2d[i].z,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if shadow_casters.is_empty() {
+        return;
+    }
+
+    for node in graph.linear_iter() {
+        let Some(occluder) = node.cast::<Occluder2D>() else {
+            continue;
+        };
+
+        if !occluder.global_visibility() || !occluder.is_globally_enabled() {
+            continue;
+        }
+
+        for segment in occluder.world_segments() {
+            if light_data.occluder_count == MAX_OCCLUDER_SEGMENTS {
+                return;
+            }
+
+            let in_range = shadow_casters.iter().any(|(position, radius)| {
+                distance_to_segment(*position, segment.begin, segment.end) < *radius
+            });
+
+            if in_range {
+                light_data.occluder_segments[light_data.occluder_count] = Vector4::new(
+                    segment.begin.x,
+                    segment.begin.y,
+                    segment.end.x,
+                    segment.end.y,
+                );
+                light_data.occluder_count += 1;
+            }
+        }
+    }
+}
+
+fn distance_to_segment(point: Vector2<f32>, begin: Vector2<f32>, end: Vector2<f32>) -> f32 {
+    let edge = end - begin;
+    let length_squared = edge.norm_squared();
+    let t = if length_squared > f32::EPSILON {
+        ((point - begin).dot(&edge) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (begin + edge.scale(t) - point).norm()
+}
+
 pub(crate) struct ForwardRenderer {
     render_pass_name: ImmutableString,
 }
@@ -55,7 +122,10 @@ pub(crate) struct ForwardRenderContext<'a, 'b> {
     pub black_dummy: Rc<RefCell<GpuTexture>>,
     pub volume_dummy: Rc<RefCell<GpuTexture>>,
     pub scene_depth: Rc<RefCell<GpuTexture>>,
+    pub scene_color: Rc<RefCell<GpuTexture>>,
+    pub precipitation_occlusion: Option<&'a PrecipitationOcclusion>,
     pub matrix_storage: &'a mut MatrixStorageCache,
+    pub uniform_buffer_ring: &'a mut UniformBufferRing,
     pub ambient_light: Color,
 }
 
@@ -90,7 +160,10 @@ impl ForwardRenderer {
             black_dummy,
             volume_dummy,
             scene_depth,
+            scene_color,
+            precipitation_occlusion,
             matrix_storage,
+            uniform_buffer_ring,
             ambient_light,
         } = args;
 
@@ -110,13 +183,14 @@ impl ForwardRenderer {
                 continue;
             }
 
-            let (radius, half_cone_angle_cos, half_hotspot_angle_cos, color) =
+            let (radius, half_cone_angle_cos, half_hotspot_angle_cos, color, parameters_2d) =
                 if let Some(point) = light.cast::<PointLight>() {
                     (
                         point.radius(),
                         std::f32::consts::PI.cos(),
                         std::f32::consts::PI.cos(),
                         point.base_light_ref().color().as_frgb(),
+                        Vector4::default(),
                     )
                 } else if let Some(spot) = light.cast::<SpotLight>() {
                     (
@@ -124,6 +198,22 @@ impl ForwardRenderer {
                         (spot.hotspot_cone_angle() * 0.5).cos(),
                         (spot.full_cone_angle() * 0.5).cos(),
                         spot.base_light_ref().color().as_frgb(),
+                        Vector4::default(),
+                    )
+                } else if let Some(area) = light.cast::<AreaLight>() {
+                    // Area lights are approximated by point or hemispherical spot lights.
+                    let (half_cone_angle_cos, half_hotspot_angle_cos) = if area.is_two_sided() {
+                        (std::f32::consts::PI.cos(), std::f32::consts::PI.cos())
+                    } else {
+                        // Same order as for spot lights: hotspot first, then the full cone.
+                        (80.0f32.to_radians().cos(), 0.0)
+                    };
+                    (
+                        area.radius(),
+                        half_cone_angle_cos,
+                        half_hotspot_angle_cos,
+                        area.base_light_ref().color().as_frgb(),
+                        Vector4::default(),
                     )
                 } else if let Some(directional) = light.cast::<DirectionalLight>() {
                     (
@@ -131,6 +221,29 @@ impl ForwardRenderer {
                         std::f32::consts::PI.cos(),
                         std::f32::consts::PI.cos(),
                         directional.base_light_ref().color().as_frgb(),
+                        Vector4::default(),
+                    )
+                } else if let Some(light_2d) = light.cast::<Light2D>() {
+                    let (half_cone_angle_cos, half_hotspot_angle_cos) = match light_2d.kind() {
+                        Light2DKind::Point => {
+                            (std::f32::consts::PI.cos(), std::f32::consts::PI.cos())
+                        }
+                        Light2DKind::Spot => (
+                            (light_2d.hotspot_cone_angle() * 0.5).cos(),
+                            (light_2d.full_cone_angle() * 0.5).cos(),
+                        ),
+                    };
+                    (
+                        light_2d.radius(),
+                        half_cone_angle_cos,
+                        half_hotspot_angle_cos,
+                        light_2d.color().as_frgb().scale(light_2d.intensity()),
+                        Vector4::new(
+                            light_2d.height(),
+                            light_2d.falloff(),
+                            light_2d.shadow_softness(),
+                            if light_2d.is_cast_shadows() { 1.0 } else { 0.0 },
+                        ),
                     )
                 } else {
                     continue;
@@ -145,16 +258,56 @@ impl ForwardRenderer {
                     Vector4::new(color.x, color.y, color.z, radius);
                 light_data.parameters[light_num] =
                     Vector2::new(half_cone_angle_cos, half_hotspot_angle_cos);
+                light_data.parameters_2d[light_num] = parameters_2d;
 
                 light_data.count += 1;
             }
         }
 
-        for bundle in bundle_storage
-            .bundles
-            .iter()
-            .filter(|b| b.render_path == RenderPath::Forward)
-        {
+        collect_occluder_segments(graph, &mut light_data);
+
+        let camera_position = camera.global_position();
+        let z_near = camera.projection().z_near();
+        let z_far = camera.projection().z_far();
+
+        let bundles = upload_bundle_instances(
+            state,
+            uniform_buffer_ring,
+            bundle_storage
+                .bundles
+                .iter()
+                .filter(|b| b.render_path == RenderPath::Forward),
+            |bundle, instance| {
+                let view_projection = if instance.depth_offset != 0.0 {
+                    let mut projection = camera.projection_matrix();
+                    projection[14] -= instance.depth_offset;
+                    projection * camera.view_matrix()
+                } else {
+                    initial_view_projection
+                };
+
+                InstanceUniforms {
+                    world_matrix: instance.world_transform,
+                    view_projection_matrix: view_projection,
+                    use_skeletal_animation: bundle.is_skinned,
+                    use_pom: quality_settings.use_parallax_mapping,
+                    camera_position,
+                    camera_up_vector: camera_up,
+                    camera_side_vector: camera_side,
+                    z_near,
+                    z_far,
+                    light_position: Default::default(),
+                    blend_shapes_count: instance.blend_shapes_weights.len(),
+                    vertex_animation: instance.vertex_animation.as_ref(),
+                    ambient_light,
+                    light_link_mask: 0,
+                    precipitation_occlusion_matrix: precipitation_occlusion
+                        .map(|occlusion| occlusion.view_projection_matrix),
+                }
+            },
+        );
+
+        for (bundle, instance_uniforms) in bundles.iter() {
             let mut material_state = bundle.material.state();
 
             let Some(material) = material_state.data() else {
@@ -179,15 +332,7 @@ impl ForwardRenderer {
                 continue;
             };
 
-            for instance in bundle.instances.iter() {
-                let view_projection = if instance.depth_offset != 0.0 {
-                    let mut projection = camera.projection_matrix();
-                    projection[14] -= instance.depth_offset;
-                    projection * camera.view_matrix()
-                } else {
-                    initial_view_projection
-                };
-
+            for (instance, instance_uniforms) in bundle.instances.iter().zip(instance_uniforms) {
                 statistics += framebuffer.draw(
                     geometry,
                     state,
@@ -200,20 +345,12 @@ impl ForwardRenderer {
                             material,
                             program_binding: &mut program_binding,
                             texture_cache,
-                            world_matrix: &instance.world_transform,
-                            view_projection_matrix: &view_projection,
-                            wvp_matrix: &(view_projection * instance.world_transform),
+                            uniform_buffer_ring,
+                            instance_uniforms,
                             bone_matrices: &instance.bone_matrices,
-                            use_skeletal_animation: bundle.is_skinned,
-                            camera_position: &camera.global_position(),
-                            camera_up_vector: &camera_up,
-                            camera_side_vector: &camera_side,
-                            z_near: camera.projection().z_near(),
-                            z_far: camera.projection().z_far(),
-                            use_pom: quality_settings.use_parallax_mapping,
-                            light_position: &Default::default(),
                             blend_shapes_storage: blend_shapes_storage.as_ref(),
                             blend_shapes_weights: &instance.blend_shapes_weights,
+                            vertex_animation: instance.vertex_animation.as_ref(),
                             normal_dummy: &normal_dummy,
                             white_dummy: &white_dummy,
                             black_dummy: &black_dummy,
@@ -221,8 +358,9 @@ impl ForwardRenderer {
                             matrix_storage,
                             persistent_identifier: instance.persistent_identifier,
                             light_data: Some(&light_data),
-                            ambient_light,
                             scene_depth: Some(&scene_depth),
+                            scene_color: Some(&scene_color),
+                            precipitation_occlusion,
                         });
                     },
                 )?;
@@ -0,0 +1,229 @@
+//! Debug facilities of the graphics server. Every GPU object (texture, geometry buffer, frame buffer,
+//! shader program, etc.) is registered in [`GpuObjectRegistry`] of the pipeline state when it is
+//! created and unregistered when it is destroyed. Objects, that are still alive when the pipeline
+//! state is destroyed, will never be freed, they're reported as leaks together with their names
+//! and source code locations, where they were created.
+//!
+//! Objects could be named using `set_name` methods. Names are also passed to the driver as debug
+//! labels (if `GL_KHR_debug` extension is supported), so they're visible in graphics debuggers
+//! such as RenderDoc and in debug messages of the driver.
+
+use fxhash::FxHashMap;
+use std::{fmt::Write, panic::Location};
+
+/// Maximum amount of objects, that will be listed in a leak report.
+const MAX_REPORTED_OBJECTS: usize = 100;
+
+/// Kind of a GPU object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum GpuObjectKind {
+    Texture,
+    GeometryBuffer,
+    FrameBuffer,
+    Program,
+    UniformBuffer,
+}
+
+impl GpuObjectKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            GpuObjectKind::Texture => "Texture",
+            GpuObjectKind::GeometryBuffer => "Geometry Buffer",
+            GpuObjectKind::FrameBuffer => "Frame Buffer",
+            GpuObjectKind::Program => "Program",
+            GpuObjectKind::UniformBuffer => "Uniform Buffer",
+        }
+    }
+}
+
+/// A unique id of a registered GPU object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GpuObjectId(u64);
+
+/// Information about a live GPU object.
+#[derive(Clone, Debug)]
+pub struct GpuObjectInfo {
+    pub kind: GpuObjectKind,
+    /// Name of the object, it is empty if the object wasn't named.
+    pub name: String,
+    /// Source code location, where the object was created.
+    pub location: &'static Location<'static>,
+}
+
+impl GpuObjectInfo {
+    /// Returns the name of the object or the location, where the object was created, if the object
+    /// has no name.
+    pub fn label(&self) -> String {
+        if self.name.is_empty() {
+            self.location.to_string()
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+/// A set of all live GPU objects.
+#[derive(Default, Debug)]
+pub struct GpuObjectRegistry {
+    next_id: u64,
+    objects: FxHashMap<GpuObjectId, GpuObjectInfo>,
+}
+
+impl GpuObjectRegistry {
+    pub fn register(
+        &mut self,
+        kind: GpuObjectKind,
+        location: &'static Location<'static>,
+    ) -> GpuObjectId {
+        let id = GpuObjectId(self.next_id);
+        self.next_id += 1;
+        self.objects.insert(
+            id,
+            GpuObjectInfo {
+                kind,
+                name: Default::default(),
+                location,
+            },
+        );
+        id
+    }
+
+    pub fn unregister(&mut self, id: GpuObjectId) {
+        self.objects.remove(&id);
+    }
+
+    pub fn set_name(&mut self, id: GpuObjectId, name: &str) {
+        if let Some(info) = self.objects.get_mut(&id) {
+            name.clone_into(&mut info.name);
+        }
+    }
+
+    pub fn get(&self, id: GpuObjectId) -> Option<&GpuObjectInfo> {
+        self.objects.get(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Returns all live objects sorted by their kind and creation order.
+    pub fn live_objects(&self) -> Vec<GpuObjectInfo> {
+        let mut objects = self.objects.iter().collect::<Vec<_>>();
+        objects.sort_by_key(|(id, info)| (info.kind, id.0));
+        objects.into_iter().map(|(_, info)| info.clone()).collect()
+    }
+
+    /// Creates a human-readable report about all live objects. Returns `None` if there are no
+    /// live objects.
+    pub fn leak_report(&self) -> Option<String> {
+        if self.objects.is_empty() {
+            return None;
+        }
+
+        let objects = self.live_objects();
+        let mut report = format!("{} GPU object(s) were never freed:", self.objects.len());
+        for info in objects.iter().take(MAX_REPORTED_OBJECTS) {
+            let _ = write!(report, "\n\t{}", info.kind.name());
+            if !info.name.is_empty() {
+                let _ = write!(report, " \"{}\"", info.name);
+            }
+            let _ = write!(report, " created at {}", info.location);
+        }
+        if objects.len() > MAX_REPORTED_OBJECTS {
+            let _ = write!(
+                report,
+                "\n\t...and {} more",
+                objects.len() - MAX_REPORTED_OBJECTS
+            );
+        }
+        Some(report)
+    }
+}
+
+/// An OpenGL object, that could have a debug label.
+pub trait GlObject: Copy {
+    /// OpenGL identifier of the object type (`GL_TEXTURE`, `GL_BUFFER`, etc.).
+    const IDENTIFIER: u32;
+
+    /// Returns the OpenGL name of the object or `None` if the object cannot be labelled on the
+    /// current platform.
+    fn gl_name(self) -> Option<u32>;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+macro_rules! impl_gl_object {
+    ($($ty:ty => $identifier:expr),*) => {
+        $(
+            impl GlObject for $ty {
+                const IDENTIFIER: u32 = $identifier;
+
+                fn gl_name(self) -> Option<u32> {
+                    Some(self.0.get())
+                }
+            }
+        )*
+    };
+}
+
+// WebGL does not support debug labels.
+#[cfg(target_arch = "wasm32")]
+macro_rules! impl_web_gl_object {
+    ($($ty:ty => $identifier:expr),*) => {
+        $(
+            impl GlObject for $ty {
+                const IDENTIFIER: u32 = $identifier;
+
+                fn gl_name(self) -> Option<u32> {
+                    None
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl_gl_object!(
+    glow::NativeTexture => glow::TEXTURE,
+    glow::NativeBuffer => glow::BUFFER,
+    glow::NativeVertexArray => glow::VERTEX_ARRAY,
+    glow::NativeFramebuffer => glow::FRAMEBUFFER,
+    glow::NativeProgram => glow::PROGRAM
+);
+
+#[cfg(target_arch = "wasm32")]
+impl_web_gl_object!(
+    glow::WebTextureKey => glow::TEXTURE,
+    glow::WebBufferKey => glow::BUFFER,
+    glow::WebVertexArrayKey => glow::VERTEX_ARRAY,
+    glow::WebFramebufferKey => glow::FRAMEBUFFER,
+    glow::WebProgramKey => glow::PROGRAM
+);
+
+#[cfg(test)]
+mod test {
+    use crate::renderer::framework::debug::{GpuObjectKind, GpuObjectRegistry};
+    use std::panic::Location;
+
+    #[test]
+    fn test_leak_report() {
+        let mut registry = GpuObjectRegistry::default();
+        assert!(registry.leak_report().is_none());
+
+        let texture = registry.register(GpuObjectKind::Texture, Location::caller());
+        let buffer = registry.register(GpuObjectKind::GeometryBuffer, Location::caller());
+        registry.set_name(texture, "GBuffer Depth");
+        assert_eq!(registry.get(texture).unwrap().label(), "GBuffer Depth");
+
+        registry.unregister(buffer);
+        assert_eq!(registry.len(), 1);
+
+        let report = registry.leak_report().unwrap();
+        assert!(report.starts_with("1 GPU object(s) were never freed:"));
+        assert!(report.contains("Texture \"GBuffer Depth\" created at "));
+        assert!(!report.contains("Geometry Buffer"));
+    }
+}
@@ -25,6 +25,8 @@ pub enum FrameworkError {
     FaultyShaderSource,
     /// There is no such shader uniform (could be optimized out).
     UnableToFindShaderUniform(String),
+    /// There is no such shader uniform block (could be optimized out).
+    UnableToFindShaderUniformBlock(String),
     /// Texture has invalid data - insufficient size.
     InvalidTextureData {
         /// Expected data size in bytes.
@@ -85,6 +87,9 @@ impl Display for FrameworkError {
             FrameworkError::UnableToFindShaderUniform(v) => {
                 write!(f, "There is no such shader uniform: {v}")
             }
+            FrameworkError::UnableToFindShaderUniformBlock(v) => {
+                write!(f, "There is no such shader uniform block: {v}")
+            }
             FrameworkError::InvalidTextureData {
                 expected_data_size,
                 actual_data_size,
@@ -1,6 +1,7 @@
 use crate::{
     core::{color::Color, math::Rect, reflect::prelude::*, scope_profile, visitor::prelude::*},
     renderer::framework::{
+        debug::{GpuObjectId, GpuObjectKind},
         error::FrameworkError,
         geometry_buffer::{DrawCallStatistics, ElementRange, GeometryBuffer},
         gpu_program::{GpuProgram, GpuProgramBinding},
@@ -11,7 +12,7 @@ use crate::{
 use glow::HasContext;
 use serde::{Deserialize, Serialize};
 use std::rc::Weak;
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, panic::Location, rc::Rc};
 
 #[derive(Copy, Clone, PartialOrd, PartialEq, Hash, Debug, Eq)]
 pub enum AttachmentKind {
@@ -30,6 +31,8 @@ pub struct FrameBuffer {
     fbo: Option<glow::Framebuffer>,
     depth_attachment: Option<Attachment>,
     color_attachments: Vec<Attachment>,
+    // Back buffer is not registered, because it is owned by the window.
+    debug_id: Option<GpuObjectId>,
 }
 
 #[derive(
@@ -120,11 +123,16 @@ unsafe fn set_attachment(state: &PipelineState, gl_attachment_kind: u32, texture
 }
 
 impl FrameBuffer {
+    /// Creates a new frame buffer. The frame buffer will have no name, use [`Self::set_name`] to
+    /// name it. Unnamed frame buffers are identified by the location, where they were created.
+    #[track_caller]
     pub fn new(
         state: &PipelineState,
         depth_attachment: Option<Attachment>,
         color_attachments: Vec<Attachment>,
     ) -> Result<Self, FrameworkError> {
+        let location = Location::caller();
+
         unsafe {
             let fbo = state.gl.create_framebuffer()?;
 
@@ -168,12 +176,14 @@ impl FrameBuffer {
             }
 
             state.set_framebuffer(None);
+            state.set_gl_object_label(fbo, location);
 
             Ok(Self {
                 state: state.weak(),
                 fbo: Some(fbo),
                 depth_attachment,
                 color_attachments,
+                debug_id: Some(state.register_object(GpuObjectKind::FrameBuffer, location)),
             })
         }
     }
@@ -184,6 +194,30 @@ impl FrameBuffer {
             fbo: None,
             depth_attachment: None,
             color_attachments: Default::default(),
+            debug_id: None,
+        }
+    }
+
+    /// Sets a name of the frame buffer, that is used in leak reports and as a debug label of the
+    /// frame buffer. Attachments, that have no names, are named too: their names are formed from the
+    /// given name and the kind of an attachment. Does nothing for the back buffer.
+    pub fn set_name(&self, state: &PipelineState, name: &str) {
+        let (Some(fbo), Some(debug_id)) = (self.fbo, self.debug_id) else {
+            return;
+        };
+        state.set_object_name(debug_id, name);
+        state.set_gl_object_label(fbo, name);
+        if let Some(depth_attachment) = self.depth_attachment.as_ref() {
+            let texture = depth_attachment.texture.borrow();
+            if !texture.has_name(state) {
+                texture.set_name(state, &format!("{name} Depth"));
+            }
+        }
+        for (i, color_attachment) in self.color_attachments.iter().enumerate() {
+            let texture = color_attachment.texture.borrow();
+            if !texture.has_name(state) {
+                texture.set_name(state, &format!("{name} Color {i}"));
+            }
         }
     }
 
@@ -217,11 +251,55 @@ impl FrameBuffer {
         self
     }
 
+    /// Attaches the given mip level of a rectangle texture of the color attachment with the given
+    /// index. It allows to render into mip levels of a texture one by one, for example to build
+    /// depth pyramids.
+    pub fn set_color_attachment_level(
+        &mut self,
+        state: &PipelineState,
+        attachment_index: usize,
+        level: usize,
+    ) -> &mut Self {
+        unsafe {
+            state.set_framebuffer(self.fbo);
+
+            let attachment = self.color_attachments.get(attachment_index).unwrap();
+            state.gl.framebuffer_texture_2d(
+                glow::FRAMEBUFFER,
+                glow::COLOR_ATTACHMENT0 + attachment_index as u32,
+                glow::TEXTURE_2D,
+                Some(attachment.texture.borrow().id()),
+                level as i32,
+            );
+        }
+
+        self
+    }
+
     /// None is possible only for back buffer.
     pub fn id(&self) -> Option<glow::Framebuffer> {
         self.fbo
     }
 
+    /// Reads a single RGBA8 pixel from the first color attachment of the frame buffer. Coordinates
+    /// are in OpenGL convention (the origin is at the bottom-left corner).
+    pub fn read_pixel(&self, state: &PipelineState, x: i32, y: i32) -> [u8; 4] {
+        state.set_framebuffer(self.id());
+        let mut pixel = [0u8; 4];
+        unsafe {
+            state.gl.read_pixels(
+                x,
+                y,
+                1,
+                1,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelPackData::Slice(&mut pixel),
+            );
+        }
+        pixel
+    }
+
     pub fn clear(
         &mut self,
         state: &PipelineState,
@@ -397,6 +475,9 @@ impl Drop for FrameBuffer {
                     state.gl.delete_framebuffer(id);
                 }
             }
+            if let Some(debug_id) = self.debug_id {
+                state.unregister_object(debug_id);
+            }
         }
     }
 }
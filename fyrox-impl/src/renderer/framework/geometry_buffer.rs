@@ -2,12 +2,16 @@ use crate::scene::mesh::surface::SurfaceData;
 use crate::{
     core::array_as_u8_slice,
     core::{math::TriangleDefinition, scope_profile},
-    renderer::framework::{error::FrameworkError, state::PipelineState},
+    renderer::framework::{
+        debug::{GpuObjectId, GpuObjectKind},
+        error::FrameworkError,
+        state::PipelineState,
+    },
     scene::mesh::buffer::{VertexAttributeDataType, VertexBuffer},
 };
 use glow::HasContext;
 use std::rc::Weak;
-use std::{cell::Cell, marker::PhantomData, mem::size_of};
+use std::{cell::Cell, fmt::Display, marker::PhantomData, mem::size_of, panic::Location};
 
 struct NativeBuffer {
     state: Weak<PipelineState>,
@@ -36,6 +40,7 @@ pub struct GeometryBuffer {
     element_buffer_object: glow::Buffer,
     element_count: Cell<usize>,
     element_kind: ElementKind,
+    debug_id: GpuObjectId,
     // Force compiler to not implement Send and Sync, because OpenGL is not thread-safe.
     thread_mark: PhantomData<*const u8>,
 }
@@ -293,6 +298,7 @@ impl<'a> GeometryBufferBinding<'a> {
 }
 
 impl GeometryBuffer {
+    #[track_caller]
     pub fn from_surface_data(
         data: &SurfaceData,
         kind: GeometryBufferKind,
@@ -358,6 +364,24 @@ impl GeometryBuffer {
     pub fn element_count(&self) -> usize {
         self.element_count.get()
     }
+
+    /// Sets a name of the buffer, that is used in leak reports and as a debug label of the native
+    /// objects of the buffer.
+    pub fn set_name(&self, state: &PipelineState, name: &str) {
+        state.set_object_name(self.debug_id, name);
+        self.set_gl_object_labels(state, &name);
+    }
+
+    fn set_gl_object_labels(&self, state: &PipelineState, label: &dyn Display) {
+        state.set_gl_object_label(self.vertex_array_object, label);
+        state.set_gl_object_label(
+            self.element_buffer_object,
+            format_args!("{label} (Indices)"),
+        );
+        for (i, buffer) in self.buffers.iter().enumerate() {
+            state.set_gl_object_label(buffer.id, format_args!("{label} (Vertices {i})"));
+        }
+    }
 }
 
 impl Drop for GeometryBuffer {
@@ -369,6 +393,7 @@ impl Drop for GeometryBuffer {
                 state.gl.delete_buffer(self.element_buffer_object);
                 state.gl.delete_vertex_array(self.vertex_array_object);
             }
+            state.unregister_object(self.debug_id);
         }
     }
 }
@@ -511,9 +536,13 @@ impl GeometryBufferBuilder {
         self
     }
 
+    /// Creates a new geometry buffer. The buffer will have no name, use [`GeometryBuffer::set_name`]
+    /// to name it. Unnamed buffers are identified by the location, where they were created.
+    #[track_caller]
     pub fn build(self, state: &PipelineState) -> Result<GeometryBuffer, FrameworkError> {
         scope_profile!();
 
+        let location = Location::caller();
         let vao = unsafe { state.gl.create_vertex_array()? };
         let ebo = unsafe { state.gl.create_buffer()? };
 
@@ -524,14 +553,18 @@ impl GeometryBufferBuilder {
             buffers.push(builder.build(state)?);
         }
 
-        Ok(GeometryBuffer {
+        let geometry_buffer = GeometryBuffer {
             state: state.weak(),
             vertex_array_object: vao,
             buffers,
             element_buffer_object: ebo,
             element_count: Cell::new(0),
             element_kind: self.element_kind,
+            debug_id: state.register_object(GpuObjectKind::GeometryBuffer, location),
             thread_mark: PhantomData,
-        })
+        };
+        geometry_buffer.set_gl_object_labels(state, location);
+
+        Ok(geometry_buffer)
     }
 }
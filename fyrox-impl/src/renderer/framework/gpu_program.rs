@@ -6,18 +6,25 @@ use crate::{
         log::{Log, MessageKind},
         sstorage::ImmutableString,
     },
-    renderer::framework::{error::FrameworkError, gpu_texture::GpuTexture, state::PipelineState},
+    renderer::framework::{
+        debug::{GpuObjectId, GpuObjectKind},
+        error::FrameworkError,
+        gpu_texture::GpuTexture,
+        state::PipelineState,
+        uniform::{UniformAllocation, UniformBufferRing},
+    },
 };
 use fxhash::FxHashMap;
 use glow::HasContext;
 use std::rc::Weak;
-use std::{cell::RefCell, marker::PhantomData, ops::Deref, rc::Rc};
+use std::{cell::RefCell, marker::PhantomData, ops::Deref, panic::Location, rc::Rc};
 
 pub struct GpuProgram {
     state: Weak<PipelineState>,
     id: glow::Program,
     // Force compiler to not implement Send and Sync, because OpenGL is not thread-safe.
     thread_mark: PhantomData<*const u8>,
+    debug_id: GpuObjectId,
     uniform_locations: RefCell<FxHashMap<ImmutableString, Option<UniformLocation>>>,
     pub(crate) built_in_uniform_locations:
         [Option<UniformLocation>; BuiltInUniform::Count as usize],
@@ -25,28 +32,21 @@ pub struct GpuProgram {
 
 #[repr(usize)]
 pub enum BuiltInUniform {
-    WorldMatrix,
-    ViewProjectionMatrix,
-    WorldViewProjectionMatrix,
     BoneMatrices,
-    UseSkeletalAnimation,
-    CameraPosition,
-    CameraUpVector,
-    CameraSideVector,
-    ZNear,
-    ZFar,
     SceneDepth,
-    UsePOM,
-    LightPosition,
+    SceneColor,
     BlendShapesStorage,
     BlendShapesWeights,
-    BlendShapesCount,
     LightCount,
     LightsColorRadius,
     LightsPosition,
     LightsDirection,
     LightsParameters,
-    AmbientLight,
+    LightsParameters2D,
+    OccluderCount,
+    OccluderSegments,
+    VertexAnimationTexture,
+    PrecipitationOcclusionMap,
     // Must be last.
     Count,
 }
@@ -58,17 +58,15 @@ pub struct UniformLocation {
     thread_mark: PhantomData<*const u8>,
 }
 
+// The source must be prepared by `prepare_source_code`.
 unsafe fn create_shader(
     state: &PipelineState,
     name: String,
     actual_type: u32,
     source: &str,
-    gl_kind: GlKind,
 ) -> Result<glow::Shader, FrameworkError> {
-    let merged_source = prepare_source_code(source, gl_kind);
-
     let shader = state.gl.create_shader(actual_type)?;
-    state.gl.shader_source(shader, &merged_source);
+    state.gl.shader_source(shader, source);
     state.gl.compile_shader(shader);
 
     let status = state.gl.get_shader_compile_status(shader);
@@ -154,6 +152,19 @@ impl<'a, 'b> GpuProgramBinding<'a, 'b> {
         self
     }
 
+    /// Binds the given allocation of the uniform buffer ring to the given binding point. See
+    /// [`GpuProgram::bind_uniform_block`] for more info.
+    #[inline(always)]
+    pub fn bind_uniform_allocation(
+        &mut self,
+        ring: &UniformBufferRing,
+        binding_point: u32,
+        allocation: &UniformAllocation,
+    ) -> &mut Self {
+        ring.bind(self.state, binding_point, allocation);
+        self
+    }
+
     #[inline(always)]
     pub fn set_bool(&mut self, location: &UniformLocation, value: bool) -> &mut Self {
         unsafe {
@@ -431,40 +442,18 @@ fn fetch_built_in_uniform_locations(
     const INIT: Option<UniformLocation> = None;
     let mut locations = [INIT; BuiltInUniform::Count as usize];
 
-    locations[BuiltInUniform::WorldMatrix as usize] =
-        fetch_uniform_location(state, program, "fyrox_worldMatrix");
-    locations[BuiltInUniform::ViewProjectionMatrix as usize] =
-        fetch_uniform_location(state, program, "fyrox_viewProjectionMatrix");
-    locations[BuiltInUniform::WorldViewProjectionMatrix as usize] =
-        fetch_uniform_location(state, program, "fyrox_worldViewProjection");
-
     locations[BuiltInUniform::BoneMatrices as usize] =
         fetch_uniform_location(state, program, "fyrox_boneMatrices");
-    locations[BuiltInUniform::UseSkeletalAnimation as usize] =
-        fetch_uniform_location(state, program, "fyrox_useSkeletalAnimation");
-
-    locations[BuiltInUniform::CameraPosition as usize] =
-        fetch_uniform_location(state, program, "fyrox_cameraPosition");
-    locations[BuiltInUniform::CameraUpVector as usize] =
-        fetch_uniform_location(state, program, "fyrox_cameraUpVector");
-    locations[BuiltInUniform::CameraSideVector as usize] =
-        fetch_uniform_location(state, program, "fyrox_cameraSideVector");
-    locations[BuiltInUniform::ZNear as usize] =
-        fetch_uniform_location(state, program, "fyrox_zNear");
-    locations[BuiltInUniform::ZFar as usize] = fetch_uniform_location(state, program, "fyrox_zFar");
 
     locations[BuiltInUniform::SceneDepth as usize] =
         fetch_uniform_location(state, program, "fyrox_sceneDepth");
-
-    locations[BuiltInUniform::UsePOM as usize] =
-        fetch_uniform_location(state, program, "fyrox_usePOM");
+    locations[BuiltInUniform::SceneColor as usize] =
+        fetch_uniform_location(state, program, "fyrox_sceneColor");
 
     locations[BuiltInUniform::BlendShapesStorage as usize] =
         fetch_uniform_location(state, program, "fyrox_blendShapesStorage");
     locations[BuiltInUniform::BlendShapesWeights as usize] =
         fetch_uniform_location(state, program, "fyrox_blendShapesWeights");
-    locations[BuiltInUniform::BlendShapesCount as usize] =
-        fetch_uniform_location(state, program, "fyrox_blendShapesCount");
 
     locations[BuiltInUniform::LightCount as usize] =
         fetch_uniform_location(state, program, "fyrox_lightCount");
@@ -476,41 +465,72 @@ fn fetch_built_in_uniform_locations(
         fetch_uniform_location(state, program, "fyrox_lightsDirection");
     locations[BuiltInUniform::LightsParameters as usize] =
         fetch_uniform_location(state, program, "fyrox_lightsParameters");
-    locations[BuiltInUniform::AmbientLight as usize] =
-        fetch_uniform_location(state, program, "fyrox_ambientLightColor");
-    locations[BuiltInUniform::LightPosition as usize] =
-        fetch_uniform_location(state, program, "fyrox_lightPosition");
+    locations[BuiltInUniform::LightsParameters2D as usize] =
+        fetch_uniform_location(state, program, "fyrox_lightsParameters2D");
+    locations[BuiltInUniform::OccluderCount as usize] =
+        fetch_uniform_location(state, program, "fyrox_occluderCount");
+    locations[BuiltInUniform::OccluderSegments as usize] =
+        fetch_uniform_location(state, program, "fyrox_occluderSegments");
+
+    locations[BuiltInUniform::VertexAnimationTexture as usize] =
+        fetch_uniform_location(state, program, "fyrox_vertexAnimationTexture");
+
+    locations[BuiltInUniform::PrecipitationOcclusionMap as usize] =
+        fetch_uniform_location(state, program, "fyrox_precipitationOcclusionMap");
 
     locations
 }
 
 impl GpuProgram {
+    /// Compiles and links a new program. The given name is used in error messages, leak reports
+    /// and as a debug label of the program. If the driver supports program binaries, the linked
+    /// program is loaded from the cache of program binaries (or stored to it after linking), so
+    /// the same program is compiled only once.
+    #[track_caller]
     pub fn from_source(
         state: &PipelineState,
         name: &str,
         vertex_source: &str,
         fragment_source: &str,
     ) -> Result<GpuProgram, FrameworkError> {
+        let location = Location::caller();
+
+        let vertex_source = prepare_source_code(vertex_source, state.gl_kind());
+        let fragment_source = prepare_source_code(fragment_source, state.gl_kind());
+
         unsafe {
+            if let Some(program) = state
+                .program_binaries()
+                .and_then(|cache| cache.load(&state.gl, &vertex_source, &fragment_source))
+            {
+                Log::writeln(
+                    MessageKind::Information,
+                    format!("Shader {} loaded from program binary!", name),
+                );
+
+                return Ok(Self::from_linked_program(state, program, name, location));
+            }
+
             let vertex_shader = create_shader(
                 state,
                 format!("{}_VertexShader", name),
                 glow::VERTEX_SHADER,
-                vertex_source,
-                state.gl_kind(),
+                &vertex_source,
             )?;
             let fragment_shader = create_shader(
                 state,
                 format!("{}_FragmentShader", name),
                 glow::FRAGMENT_SHADER,
-                fragment_source,
-                state.gl_kind(),
+                &fragment_source,
             )?;
             let program = state.gl.create_program()?;
             state.gl.attach_shader(program, vertex_shader);
             state.gl.delete_shader(vertex_shader);
             state.gl.attach_shader(program, fragment_shader);
             state.gl.delete_shader(fragment_shader);
+            if let Some(cache) = state.program_binaries() {
+                cache.prepare(program);
+            }
             state.gl.link_program(program);
             let status = state.gl.get_program_link_status(program);
             let link_message = state.gl.get_program_info_log(program);
@@ -537,17 +557,35 @@ impl GpuProgram {
 
                 Log::writeln(MessageKind::Information, msg);
 
-                Ok(Self {
-                    state: state.weak(),
-                    id: program,
-                    thread_mark: PhantomData,
-                    uniform_locations: Default::default(),
-                    built_in_uniform_locations: fetch_built_in_uniform_locations(state, program),
-                })
+                if let Some(cache) = state.program_binaries() {
+                    cache.save(program, &vertex_source, &fragment_source);
+                }
+
+                Ok(Self::from_linked_program(state, program, name, location))
             }
         }
     }
 
+    fn from_linked_program(
+        state: &PipelineState,
+        program: glow::Program,
+        name: &str,
+        location: &'static Location<'static>,
+    ) -> Self {
+        let debug_id = state.register_object(GpuObjectKind::Program, location);
+        state.set_object_name(debug_id, name);
+        state.set_gl_object_label(program, name);
+
+        Self {
+            state: state.weak(),
+            id: program,
+            thread_mark: PhantomData,
+            debug_id,
+            uniform_locations: Default::default(),
+            built_in_uniform_locations: fetch_built_in_uniform_locations(state, program),
+        }
+    }
+
     pub fn uniform_location_internal(
         &self,
         state: &PipelineState,
@@ -575,6 +613,27 @@ impl GpuProgram {
             .ok_or_else(|| FrameworkError::UnableToFindShaderUniform(name.deref().to_owned()))
     }
 
+    /// Assigns the given binding point to a uniform block with the given name. The data of the block
+    /// could then be set using [`GpuProgramBinding::bind_uniform_allocation`] with the same binding
+    /// point.
+    pub fn bind_uniform_block(
+        &self,
+        state: &PipelineState,
+        name: &str,
+        binding_point: u32,
+    ) -> Result<(), FrameworkError> {
+        unsafe {
+            let index = state
+                .gl
+                .get_uniform_block_index(self.id, name)
+                .ok_or_else(|| FrameworkError::UnableToFindShaderUniformBlock(name.to_owned()))?;
+            state
+                .gl
+                .uniform_block_binding(self.id, index, binding_point);
+        }
+        Ok(())
+    }
+
     pub fn bind<'a, 'b>(&'b self, state: &'a PipelineState) -> GpuProgramBinding<'a, 'b> {
         state.set_program(Some(self.id));
         GpuProgramBinding {
@@ -591,6 +650,7 @@ impl Drop for GpuProgram {
             unsafe {
                 state.gl.delete_program(self.id);
             }
+            state.unregister_object(self.debug_id);
         }
     }
 }
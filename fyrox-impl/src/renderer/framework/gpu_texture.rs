@@ -1,13 +1,18 @@
 use crate::{
     core::color::Color,
-    renderer::framework::{error::FrameworkError, state::PipelineState},
+    renderer::framework::{
+        debug::{GpuObjectId, GpuObjectKind},
+        error::FrameworkError,
+        state::PipelineState,
+    },
     resource::texture::{
         TextureKind, TextureMagnificationFilter, TextureMinificationFilter, TexturePixelKind,
         TextureWrapMode,
     },
 };
-use glow::{HasContext, PixelPackData, COMPRESSED_RED_RGTC1, COMPRESSED_RG_RGTC2};
+use glow::{HasContext, PixelPackData, PixelUnpackData, COMPRESSED_RED_RGTC1, COMPRESSED_RG_RGTC2};
 use std::marker::PhantomData;
+use std::panic::Location;
 use std::rc::Weak;
 
 #[derive(Copy, Clone)]
@@ -89,6 +94,7 @@ pub enum PixelKind {
     L8,
     L16,
     R8UI,
+    R32UI,
     R16,
     RGB16,
     RGBA16,
@@ -169,6 +175,7 @@ impl PixelKind {
             | Self::D24S8
             | Self::D32F
             | Self::R32F
+            | Self::R32UI
             | Self::RGB10A2 => Some(4),
             Self::RG8 | Self::LA8 | Self::D16 | Self::R16F | Self::L16 | Self::R16 => Some(2),
             Self::R8
@@ -217,6 +224,7 @@ impl PixelKind {
             | Self::R16F
             | Self::R8
             | Self::R8UI
+            | Self::R32UI
             | Self::RGB32F
             | Self::RGBA32F
             | Self::R11G11B10F
@@ -263,7 +271,7 @@ impl PixelKind {
             | Self::L8
             | Self::LA16
             | Self::L16 => PixelElementKind::NormalizedUnsignedInteger,
-            Self::R8UI => PixelElementKind::UnsignedInteger,
+            Self::R8UI | Self::R32UI => PixelElementKind::UnsignedInteger,
         }
     }
 
@@ -296,6 +304,7 @@ impl PixelKind {
             PixelKind::RG8 => (glow::UNSIGNED_BYTE, glow::RG, glow::RG8, None),
             PixelKind::R8 => (glow::UNSIGNED_BYTE, glow::RED, glow::R8, None),
             PixelKind::R8UI => (glow::UNSIGNED_BYTE, glow::RED_INTEGER, glow::R8UI, None),
+            PixelKind::R32UI => (glow::UNSIGNED_INT, glow::RED_INTEGER, glow::R32UI, None),
             PixelKind::BGRA8 => (glow::UNSIGNED_BYTE, glow::BGRA, glow::RGBA8, None),
             PixelKind::BGR8 => (glow::UNSIGNED_BYTE, glow::BGR, glow::RGB8, None),
             PixelKind::RG16 => (glow::UNSIGNED_SHORT, glow::RG, glow::RG16, None),
@@ -385,6 +394,7 @@ pub struct GpuTexture {
     r_wrap_mode: WrapMode,
     anisotropy: f32,
     pixel_kind: PixelKind,
+    debug_id: GpuObjectId,
     // Force compiler to not implement Send and Sync, because OpenGL is not thread-safe.
     thread_mark: PhantomData<*const u8>,
 }
@@ -408,6 +418,7 @@ fn image_3d_size_bytes(pixel_kind: PixelKind, width: usize, height: usize, depth
         | PixelKind::D24S8
         | PixelKind::D32F
         | PixelKind::R32F
+        | PixelKind::R32UI
         | PixelKind::R11G11B10F
         | PixelKind::RGB10A2 => 4 * pixel_count,
         PixelKind::RGB8 | PixelKind::SRGB8 | PixelKind::BGR8 => 3 * pixel_count,
@@ -444,6 +455,7 @@ fn image_2d_size_bytes(pixel_kind: PixelKind, width: usize, height: usize) -> us
         | PixelKind::D24S8
         | PixelKind::D32F
         | PixelKind::R32F
+        | PixelKind::R32UI
         | PixelKind::R11G11B10F
         | PixelKind::RGB10A2 => 4 * pixel_count,
         PixelKind::RGB8 | PixelKind::SRGB8 | PixelKind::BGR8 => 3 * pixel_count,
@@ -479,6 +491,7 @@ fn image_1d_size_bytes(pixel_kind: PixelKind, length: usize) -> usize {
         | PixelKind::D24S8
         | PixelKind::D32F
         | PixelKind::R32F
+        | PixelKind::R32UI
         | PixelKind::R11G11B10F
         | PixelKind::RGB10A2 => 4 * length,
         PixelKind::RGB8 | PixelKind::SRGB8 | PixelKind::BGR8 => 3 * length,
@@ -690,6 +703,32 @@ impl<'a> TextureBinding<'a> {
         self
     }
 
+    /// Sets the lowest mip level, that could be sampled from the texture. Together with
+    /// [`Self::set_max_level`] it allows to read one mip level of a texture, while rendering into
+    /// another one.
+    pub fn set_base_level(self, level: usize) -> Self {
+        unsafe {
+            self.state.gl.tex_parameter_i32(
+                self.texture.kind.gl_texture_target(),
+                glow::TEXTURE_BASE_LEVEL,
+                level as i32,
+            );
+        }
+        self
+    }
+
+    /// Sets the highest mip level, that could be sampled from the texture.
+    pub fn set_max_level(self, level: usize) -> Self {
+        unsafe {
+            self.state.gl.tex_parameter_i32(
+                self.texture.kind.gl_texture_target(),
+                glow::TEXTURE_MAX_LEVEL,
+                level as i32,
+            );
+        }
+        self
+    }
+
     pub fn set_border_color(self, #[allow(unused_variables)] color: Color) -> Self {
         #[cfg(not(target_arch = "wasm32"))]
         unsafe {
@@ -995,6 +1034,100 @@ impl<'a> TextureBinding<'a> {
         Ok(self)
     }
 
+    /// Same as [`Self::set_data`], but the data is transferred to the texture asynchronously through
+    /// a pixel buffer object, so the calling thread does not wait until the driver copies the data
+    /// to the texture. The texture must not be used until the returned upload is finished. Only
+    /// uncompressed rectangle textures are supported (see [`PendingTextureUpload::is_supported`]),
+    /// `None` is returned for any other texture and its data must be set using [`Self::set_data`].
+    pub fn set_data_async(
+        self,
+        kind: GpuTextureKind,
+        pixel_kind: PixelKind,
+        mip_count: usize,
+        data: &[u8],
+    ) -> Result<Option<PendingTextureUpload>, FrameworkError> {
+        if !PendingTextureUpload::is_supported(kind, pixel_kind) {
+            return Ok(None);
+        }
+        let GpuTextureKind::Rectangle { width, height } = kind else {
+            return Ok(None);
+        };
+
+        let mip_count = mip_count.max(1);
+
+        let mut mips = Vec::with_capacity(mip_count);
+        let mut mip_byte_offset = 0;
+        for mip in 0..mip_count {
+            if let (Some(width), Some(height)) = (
+                width.checked_shr(mip as u32),
+                height.checked_shr(mip as u32),
+            ) {
+                mips.push((mip, width, height, mip_byte_offset));
+                mip_byte_offset += image_2d_size_bytes(pixel_kind, width, height);
+            } else {
+                break;
+            }
+        }
+
+        if data.len() != mip_byte_offset {
+            return Err(FrameworkError::InvalidTextureData {
+                expected_data_size: mip_byte_offset,
+                actual_data_size: data.len(),
+            });
+        }
+
+        // Allocate storage for every mip, the data will be copied from the pixel buffer.
+        let binding = self.set_data(kind, pixel_kind, mip_count, None)?;
+        let state = binding.state;
+
+        let PixelDescriptor {
+            data_type, format, ..
+        } = pixel_kind.pixel_descriptor();
+
+        unsafe {
+            let buffer = state.gl.create_buffer()?;
+            state
+                .gl
+                .bind_buffer(glow::PIXEL_UNPACK_BUFFER, Some(buffer));
+            state
+                .gl
+                .buffer_data_u8_slice(glow::PIXEL_UNPACK_BUFFER, data, glow::STREAM_DRAW);
+
+            for (mip, width, height, offset) in mips {
+                state.gl.tex_sub_image_2d(
+                    glow::TEXTURE_2D,
+                    mip as i32,
+                    0,
+                    0,
+                    width as i32,
+                    height as i32,
+                    format,
+                    data_type,
+                    PixelUnpackData::BufferOffset(offset as u32),
+                );
+            }
+
+            state.gl.bind_buffer(glow::PIXEL_UNPACK_BUFFER, None);
+
+            let fence = match state.gl.fence_sync(glow::SYNC_GPU_COMMANDS_COMPLETE, 0) {
+                Ok(fence) => fence,
+                Err(err) => {
+                    state.gl.delete_buffer(buffer);
+                    return Err(FrameworkError::Custom(err));
+                }
+            };
+            // Make sure the commands are submitted, otherwise the fence could never be signaled.
+            state.gl.flush();
+
+            Ok(Some(PendingTextureUpload {
+                state: state.weak(),
+                buffer,
+                fence,
+                thread_mark: PhantomData,
+            }))
+        }
+    }
+
     pub fn read_pixels(&self, state: &PipelineState) -> Vec<u8> {
         unsafe {
             if let GpuTextureKind::Rectangle { width, height } = self.texture.kind {
@@ -1018,6 +1151,41 @@ impl<'a> TextureBinding<'a> {
     }
 }
 
+/// An asynchronous transfer of texture data, that was started by [`TextureBinding::set_data_async`].
+/// Dropping the upload releases the pixel buffer, but it does not cancel the transfer.
+pub struct PendingTextureUpload {
+    state: Weak<PipelineState>,
+    buffer: glow::Buffer,
+    fence: glow::Fence,
+    // Force compiler to not implement Send and Sync, because OpenGL is not thread-safe.
+    thread_mark: PhantomData<*const u8>,
+}
+
+impl PendingTextureUpload {
+    /// Returns `true` if the data of a texture with the given parameters could be uploaded
+    /// asynchronously.
+    pub fn is_supported(kind: GpuTextureKind, pixel_kind: PixelKind) -> bool {
+        matches!(kind, GpuTextureKind::Rectangle { .. }) && !pixel_kind.is_compressed()
+    }
+
+    /// Returns `true` if the GPU has finished the transfer and the texture could be used. This
+    /// method never blocks.
+    pub fn is_finished(&self, state: &PipelineState) -> bool {
+        unsafe { state.gl.get_sync_status(self.fence) == glow::SIGNALED }
+    }
+}
+
+impl Drop for PendingTextureUpload {
+    fn drop(&mut self) {
+        if let Some(state) = self.state.upgrade() {
+            unsafe {
+                state.gl.delete_sync(self.fence);
+                state.gl.delete_buffer(self.buffer);
+            }
+        }
+    }
+}
+
 const GL_COMPRESSED_RGB_S3TC_DXT1_EXT: u32 = 0x83F0;
 const GL_COMPRESSED_RGBA_S3TC_DXT1_EXT: u32 = 0x83F1;
 const GL_COMPRESSED_RGBA_S3TC_DXT3_EXT: u32 = 0x83F2;
@@ -1039,6 +1207,10 @@ impl GpuTexture {
     ///
     /// For compressed textures data must contain all mips, where each mip must be 2 times
     /// smaller than previous.
+    ///
+    /// The texture will have no name, use [`Self::set_name`] to name it. Unnamed textures are
+    /// identified by the location, where they were created.
+    #[track_caller]
     pub fn new(
         state: &PipelineState,
         kind: GpuTextureKind,
@@ -1051,9 +1223,11 @@ impl GpuTexture {
         let mip_count = mip_count.max(1);
 
         let target = kind.gl_texture_target();
+        let location = Location::caller();
 
         unsafe {
             let texture = state.gl.create_texture()?;
+            state.set_gl_object_label(texture, location);
 
             let mut result = Self {
                 state: state.weak(),
@@ -1066,6 +1240,7 @@ impl GpuTexture {
                 r_wrap_mode: WrapMode::Repeat,
                 anisotropy: 1.0,
                 pixel_kind,
+                debug_id: state.register_object(GpuObjectKind::Texture, location),
                 thread_mark: PhantomData,
             };
 
@@ -1129,6 +1304,18 @@ impl GpuTexture {
         }
     }
 
+    /// Sets a name of the texture, that is used in leak reports and as a debug label of the texture.
+    pub fn set_name(&self, state: &PipelineState, name: &str) {
+        state.set_object_name(self.debug_id, name);
+        state.set_gl_object_label(self.texture, name);
+    }
+
+    pub fn has_name(&self, state: &PipelineState) -> bool {
+        state
+            .object_info(self.debug_id)
+            .is_some_and(|info| !info.name.is_empty())
+    }
+
     pub fn kind(&self) -> GpuTextureKind {
         self.kind
     }
@@ -1168,6 +1355,7 @@ impl Drop for GpuTexture {
             unsafe {
                 state.gl.delete_texture(self.texture);
             }
+            state.unregister_object(self.debug_id);
         }
     }
 }
@@ -1,8 +1,11 @@
 #![allow(missing_docs)] // TODO
 
+pub mod debug;
 pub mod error;
 pub mod framebuffer;
 pub mod geometry_buffer;
 pub mod gpu_program;
 pub mod gpu_texture;
+pub mod program_binary;
 pub mod state;
+pub mod uniform;
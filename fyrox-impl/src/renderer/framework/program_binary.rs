@@ -0,0 +1,194 @@
+//! Persistent storage of linked GPU programs. Drivers, that support `GL_ARB_get_program_binary`
+//! (OpenGL 4.1+ or OpenGL ES 3.0+), could return a binary representation of a linked program,
+//! which could be loaded back much faster than compiling the program from the source. The binaries
+//! are stored on disk, so the programs are compiled only once per driver.
+//!
+//! glow does not expose the required functions, so they're loaded manually using the same loader,
+//! that was used to create the OpenGL context.
+
+use crate::{core::log::Log, renderer::framework::debug::GlObject};
+use fxhash::FxHasher64;
+use glow::HasContext;
+use std::{
+    ffi::c_void,
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+type GetProgramIvFn = unsafe extern "system" fn(u32, u32, *mut i32);
+type GetProgramBinaryFn = unsafe extern "system" fn(u32, i32, *mut i32, *mut u32, *mut c_void);
+type ProgramBinaryFn = unsafe extern "system" fn(u32, u32, *const c_void, i32);
+type ProgramParameteriFn = unsafe extern "system" fn(u32, u32, i32);
+
+/// Size of the binary format identifier, that is stored before the program binary in a file.
+const FORMAT_SIZE: usize = std::mem::size_of::<u32>();
+
+/// Loads and stores binaries of linked GPU programs in a directory on disk. Each binary is
+/// identified by a hash of the source code of the program and the driver info, so any change in
+/// the source code or a driver update will force the program to be compiled again.
+pub struct ProgramBinaryCache {
+    get_program_iv: GetProgramIvFn,
+    get_program_binary: GetProgramBinaryFn,
+    program_binary: ProgramBinaryFn,
+    program_parameteri: ProgramParameteriFn,
+    directory: PathBuf,
+    driver: String,
+}
+
+impl ProgramBinaryCache {
+    /// Returns the default directory of the cache, which is located in the temporary directory of
+    /// the OS.
+    pub fn default_directory() -> PathBuf {
+        std::env::temp_dir().join("fyrox").join("program_binaries")
+    }
+
+    /// Creates a new cache, that stores program binaries in the given directory. Returns `None` if
+    /// the driver does not support program binaries.
+    ///
+    /// # Safety
+    ///
+    /// The loader must return pointers to the functions of the given OpenGL context.
+    pub unsafe fn new<F>(gl: &glow::Context, directory: PathBuf, mut loader: F) -> Option<Self>
+    where
+        F: FnMut(&str) -> *const c_void,
+    {
+        // Some drivers expose the functions, but do not support any binary format.
+        if gl.get_parameter_i32(glow::NUM_PROGRAM_BINARY_FORMATS) <= 0 {
+            return None;
+        }
+
+        let mut load = |name: &str| {
+            let ptr = loader(name);
+            (!ptr.is_null()).then_some(ptr)
+        };
+
+        let get_program_iv = load("glGetProgramiv")?;
+        let get_program_binary = load("glGetProgramBinary")?;
+        let program_binary = load("glProgramBinary")?;
+        let program_parameteri = load("glProgramParameteri")?;
+
+        Some(Self {
+            get_program_iv: std::mem::transmute::<*const c_void, GetProgramIvFn>(get_program_iv),
+            get_program_binary: std::mem::transmute::<*const c_void, GetProgramBinaryFn>(
+                get_program_binary,
+            ),
+            program_binary: std::mem::transmute::<*const c_void, ProgramBinaryFn>(program_binary),
+            program_parameteri: std::mem::transmute::<*const c_void, ProgramParameteriFn>(
+                program_parameteri,
+            ),
+            directory,
+            driver: format!(
+                "{}|{}|{}",
+                gl.get_parameter_string(glow::VENDOR),
+                gl.get_parameter_string(glow::RENDERER),
+                gl.get_parameter_string(glow::VERSION)
+            ),
+        })
+    }
+
+    /// Returns the directory of the cache.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    fn binary_path(&self, vertex_source: &str, fragment_source: &str) -> PathBuf {
+        let mut hasher = FxHasher64::default();
+        self.driver.hash(&mut hasher);
+        vertex_source.hash(&mut hasher);
+        fragment_source.hash(&mut hasher);
+        self.directory.join(format!("{:016x}.bin", hasher.finish()))
+    }
+
+    /// Tries to create a linked program from a binary, that was stored for the given source code.
+    /// Returns `None` if there's no such binary or the driver rejected it (for example, because it
+    /// was created by another version of the driver).
+    pub(crate) unsafe fn load(
+        &self,
+        gl: &glow::Context,
+        vertex_source: &str,
+        fragment_source: &str,
+    ) -> Option<glow::Program> {
+        let data = fs::read(self.binary_path(vertex_source, fragment_source)).ok()?;
+        if data.len() <= FORMAT_SIZE {
+            return None;
+        }
+        let (format, binary) = data.split_at(FORMAT_SIZE);
+        let format = u32::from_le_bytes(format.try_into().ok()?);
+
+        let program = gl.create_program().ok()?;
+        let Some(name) = program.gl_name() else {
+            gl.delete_program(program);
+            return None;
+        };
+        (self.program_binary)(
+            name,
+            format,
+            binary.as_ptr() as *const c_void,
+            binary.len() as i32,
+        );
+        if gl.get_program_link_status(program) {
+            Some(program)
+        } else {
+            gl.delete_program(program);
+            None
+        }
+    }
+
+    /// Tells the driver, that the binary of the given program will be retrieved after linking. Must
+    /// be called before the program is linked.
+    pub(crate) unsafe fn prepare(&self, program: glow::Program) {
+        if let Some(name) = program.gl_name() {
+            (self.program_parameteri)(
+                name,
+                glow::PROGRAM_BINARY_RETRIEVABLE_HINT,
+                glow::TRUE as i32,
+            );
+        }
+    }
+
+    /// Stores the binary of the given linked program, so it could be loaded by [`Self::load`] for
+    /// the same source code. Failures are not critical and only reported to the log.
+    pub(crate) unsafe fn save(
+        &self,
+        program: glow::Program,
+        vertex_source: &str,
+        fragment_source: &str,
+    ) {
+        let Some(name) = program.gl_name() else {
+            return;
+        };
+
+        let mut length = 0;
+        (self.get_program_iv)(name, glow::PROGRAM_BINARY_LENGTH, &mut length);
+        if length <= 0 {
+            return;
+        }
+
+        let mut data = vec![0u8; FORMAT_SIZE + length as usize];
+        let mut written = 0;
+        let mut format = 0;
+        (self.get_program_binary)(
+            name,
+            length,
+            &mut written,
+            &mut format,
+            data[FORMAT_SIZE..].as_mut_ptr() as *mut c_void,
+        );
+        if written <= 0 {
+            return;
+        }
+        data.truncate(FORMAT_SIZE + written as usize);
+        data[..FORMAT_SIZE].copy_from_slice(&format.to_le_bytes());
+
+        let path = self.binary_path(vertex_source, fragment_source);
+        if let Err(error) = fs::create_dir_all(&self.directory).and_then(|_| fs::write(&path, data))
+        {
+            Log::warn(format!(
+                "Unable to save program binary to {}. Reason: {:?}",
+                path.display(),
+                error
+            ));
+        }
+    }
+}
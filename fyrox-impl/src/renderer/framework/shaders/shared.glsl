@@ -59,6 +59,16 @@ vec3 S_UnProject(vec3 screenPos, mat4 matrix)
     return position.xyz / position.w;
 }
 
+// Returns view space position of a point with the given screen position and positive linear (view
+// space) depth. Works with both perspective and orthographic projections.
+vec3 S_ViewSpacePosition(vec2 screenCoord, float linearDepth, mat4 inverseProjectionMatrix)
+{
+    vec3 nearPoint = S_UnProject(vec3(screenCoord, 0.0), inverseProjectionMatrix);
+    vec3 farPoint = S_UnProject(vec3(screenCoord, 1.0), inverseProjectionMatrix);
+    float t = (-linearDepth - nearPoint.z) / (farPoint.z - nearPoint.z);
+    return mix(nearPoint, farPoint, t);
+}
+
 float S_DistributionGGX(vec3 N, vec3 H, float roughness)
 {
     float a = roughness * roughness;
@@ -102,6 +112,25 @@ vec3 S_FresnelSchlick(float cosTheta, vec3 F0)
     return F0 + (1.0 - F0) * pow(max(1.0 - cosTheta, 0.0), 5.0);
 }
 
+// Analytical approximation of the split-sum environment BRDF by Brian Karis. Returns scale and
+// bias to F0, that gives directional albedo of the specular lobe: F0 * x + y.
+vec2 S_EnvBRDFApprox(float roughness, float NdotV)
+{
+    const vec4 c0 = vec4(-1.0, -0.0275, -0.572, 0.022);
+    const vec4 c1 = vec4(1.0, 0.0425, 1.04, -0.04);
+    vec4 r = roughness * c0 + c1;
+    float a004 = min(r.x * r.x, exp2(-9.28 * NdotV)) * r.x + r.y;
+    return vec2(-1.04, 1.04) * a004 + r.zw;
+}
+
+// Checks whether a linked light must not affect a pixel of the G-Buffer. `lightLinkTexture` is
+// the decal mask texture of the G-Buffer, that stores light link mask in the upper 24 bits.
+// `lightLinkBit` is zero for lights without links.
+bool S_IsLightExcluded(in usampler2D lightLinkTexture, vec2 texCoord, uint lightLinkBit)
+{
+    return lightLinkBit != 0u && (texture(lightLinkTexture, texCoord).r & lightLinkBit) != 0u;
+}
+
 struct TPBRContext {
     vec3 lightColor;
     vec3 viewVector;
@@ -168,6 +197,21 @@ vec3 S_RayleighScatter(vec3 start, vec3 dir, vec3 lightPos, float d)
     return vec3(0.55, 0.75, 1.0) * scatter;
 }
 
+// Henyey-Greenstein phase function, `g` defines anisotropy of scattering: positive values scatter
+// light forward, negative - backward, zero - uniformly in all directions.
+float S_HenyeyGreenstein(float cosTheta, float g)
+{
+    float g2 = g * g;
+    return (1.0 - g2) / (4.0 * PI * pow(max(1.0 + g2 - 2.0 * g * cosTheta, 1e-4), 1.5));
+}
+
+// Returns per-pixel noise in [0; 1) range, it is used to hide banding artifacts of ray marching.
+// See "Next Generation Post Processing in Call of Duty: Advanced Warfare" by Jorge Jimenez.
+float S_InterleavedGradientNoise(vec2 pixel)
+{
+    return fract(52.9829189 * fract(dot(pixel, vec2(0.06711056, 0.00583715))));
+}
+
 // Tries to find intersection of given ray with specified sphere. If there is an intersection, returns true.
 // In out parameters minT, maxT will be min and max ray parameters of intersection.
 bool S_RaySphereIntersection(vec3 origin, vec3 dir, vec3 center, float radius, out float minT, out float maxT)
@@ -1,12 +1,18 @@
 use crate::renderer::PipelineStatistics;
 use crate::{
-    core::{color::Color, math::Rect, reflect::prelude::*, visitor::prelude::*},
-    renderer::framework::framebuffer::{CullFace, DrawParameters},
+    core::{color::Color, log::Log, math::Rect, reflect::prelude::*, visitor::prelude::*},
+    renderer::framework::{
+        debug::{GlObject, GpuObjectId, GpuObjectInfo, GpuObjectKind, GpuObjectRegistry},
+        framebuffer::{CullFace, DrawParameters},
+        program_binary::ProgramBinaryCache,
+    },
 };
 use fyrox_core::uuid_provider;
 use glow::{Framebuffer, HasContext};
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
+use std::fmt::Display;
+use std::panic::Location;
 use std::rc::{Rc, Weak};
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 
@@ -275,6 +281,10 @@ pub struct PipelineState {
     pub gl: glow::Context,
     state: RefCell<InnerState>,
     this: RefCell<Option<Weak<PipelineState>>>,
+    objects: RefCell<GpuObjectRegistry>,
+    // `true` if GL_KHR_debug extension is supported and objects could have debug labels.
+    debug_labels: bool,
+    program_binaries: Option<ProgramBinaryCache>,
 }
 
 #[derive(Copy, Clone)]
@@ -481,15 +491,22 @@ impl PipelineState {
     pub fn new(
         #[allow(unused_mut)] mut context: glow::Context,
         gl_kind: GlKind,
+        program_binaries: Option<ProgramBinaryCache>,
     ) -> SharedPipelineState {
+        let debug_labels = context.supported_extensions().contains("GL_KHR_debug");
+
         unsafe {
             context.depth_func(CompareFunc::default() as u32);
 
             #[cfg(debug_assertions)]
             {
-                use crate::core::log::{Log, MessageKind};
+                use crate::core::log::MessageKind;
 
                 if context.supported_extensions().contains("GL_KHR_debug") {
+                    // Debug output is enabled by default only for debug contexts. Synchronous
+                    // output guarantees, that messages are reported from the call that caused them.
+                    context.enable(glow::DEBUG_OUTPUT);
+                    context.enable(glow::DEBUG_OUTPUT_SYNCHRONOUS);
                     context.debug_message_callback(|source, msg_type, id, severity, message| {
                         let message_kind = if severity == glow::DEBUG_SEVERITY_HIGH {
                             MessageKind::Error
@@ -555,6 +572,9 @@ impl PipelineState {
             gl: context,
             state: RefCell::new(InnerState::new(gl_kind)),
             this: Default::default(),
+            objects: Default::default(),
+            debug_labels,
+            program_binaries,
         };
 
         let shared = SharedPipelineState::new(state);
@@ -572,6 +592,52 @@ impl PipelineState {
         self.state.borrow().gl_kind
     }
 
+    /// Returns the cache of program binaries, `None` if the driver does not support them.
+    pub fn program_binaries(&self) -> Option<&ProgramBinaryCache> {
+        self.program_binaries.as_ref()
+    }
+
+    /// Registers a new GPU object, that was created at the given location. Every registered object
+    /// must be unregistered when it is destroyed, otherwise it will be reported as a leak.
+    pub fn register_object(
+        &self,
+        kind: GpuObjectKind,
+        location: &'static Location<'static>,
+    ) -> GpuObjectId {
+        self.objects.borrow_mut().register(kind, location)
+    }
+
+    pub fn unregister_object(&self, id: GpuObjectId) {
+        self.objects.borrow_mut().unregister(id)
+    }
+
+    pub fn set_object_name(&self, id: GpuObjectId, name: &str) {
+        self.objects.borrow_mut().set_name(id, name)
+    }
+
+    pub fn object_info(&self, id: GpuObjectId) -> Option<GpuObjectInfo> {
+        self.objects.borrow().get(id).cloned()
+    }
+
+    /// Sets a debug label of the given OpenGL object. The label is visible in graphics debuggers
+    /// and in debug messages of the driver. Does nothing if `GL_KHR_debug` is not supported.
+    pub fn set_gl_object_label<T: GlObject>(&self, object: T, label: impl Display) {
+        if !self.debug_labels {
+            return;
+        }
+        if let Some(name) = object.gl_name() {
+            unsafe {
+                self.gl
+                    .object_label(T::IDENTIFIER, name, Some(label.to_string()));
+            }
+        }
+    }
+
+    /// Returns information about every GPU object, that is currently alive.
+    pub fn live_objects(&self) -> Vec<GpuObjectInfo> {
+        self.objects.borrow().live_objects()
+    }
+
     pub fn set_polygon_fill_mode(
         &self,
         polygon_face: PolygonFace,
@@ -1000,3 +1066,13 @@ impl PipelineState {
         self.state.borrow().frame_statistics
     }
 }
+
+impl Drop for PipelineState {
+    fn drop(&mut self) {
+        // Every object holds a weak reference to the state, so objects, that are still alive, will
+        // never be freed.
+        if let Some(report) = self.objects.borrow().leak_report() {
+            Log::warn(report);
+        }
+    }
+}
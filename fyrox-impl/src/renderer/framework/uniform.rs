@@ -0,0 +1,296 @@
+//! Uniform buffers allow to pass uniforms to shaders in blocks of memory, instead of setting them
+//! one-by-one with separate API calls. See [`UniformBufferRing`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector2, Vector3, Vector4},
+        color::Color,
+        scope_profile,
+    },
+    renderer::framework::{
+        debug::{GpuObjectId, GpuObjectKind},
+        error::FrameworkError,
+        state::PipelineState,
+    },
+};
+use glow::HasContext;
+use std::{marker::PhantomData, panic::Location, rc::Weak};
+
+/// Minimal alignment of every block in a uniform buffer. The actual alignment could be larger, if
+/// the driver requires it.
+pub const MIN_UNIFORM_BLOCK_ALIGNMENT: usize = 256;
+
+/// Amount of GPU buffers in a ring. Every upload uses the next buffer, so the data that could still
+/// be used by the GPU won't be overwritten.
+const RING_SIZE: usize = 3;
+
+/// A writer of uniform data, that follows `std140` layout rules. The order of the writes must match
+/// the order of the members of a uniform block in a shader.
+pub struct UniformWriter<'a> {
+    bytes: &'a mut Vec<u8>,
+    start: usize,
+}
+
+impl<'a> UniformWriter<'a> {
+    fn new(bytes: &'a mut Vec<u8>) -> Self {
+        let start = bytes.len();
+        Self { bytes, start }
+    }
+
+    fn align(&mut self, alignment: usize) {
+        let len = self.bytes.len() - self.start;
+        let padding = (alignment - len % alignment) % alignment;
+        self.bytes.resize(self.bytes.len() + padding, 0);
+    }
+
+    fn write_raw(&mut self, alignment: usize, data: &[f32]) -> &mut Self {
+        self.align(alignment);
+        for value in data {
+            self.bytes.extend_from_slice(&value.to_ne_bytes());
+        }
+        self
+    }
+
+    /// Returns the amount of bytes written so far.
+    pub fn len(&self) -> usize {
+        self.bytes.len() - self.start
+    }
+
+    /// Returns `true` if nothing was written.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn write_f32(&mut self, value: f32) -> &mut Self {
+        self.write_raw(4, &[value])
+    }
+
+    pub fn write_i32(&mut self, value: i32) -> &mut Self {
+        self.align(4);
+        self.bytes.extend_from_slice(&value.to_ne_bytes());
+        self
+    }
+
+    pub fn write_u32(&mut self, value: u32) -> &mut Self {
+        self.align(4);
+        self.bytes.extend_from_slice(&value.to_ne_bytes());
+        self
+    }
+
+    /// Booleans are 4-byte integers in `std140` layout.
+    pub fn write_bool(&mut self, value: bool) -> &mut Self {
+        self.write_u32(value as u32)
+    }
+
+    pub fn write_vector2(&mut self, value: &Vector2<f32>) -> &mut Self {
+        self.write_raw(8, value.as_slice())
+    }
+
+    /// 3D vectors have 16-byte alignment in `std140` layout, but they occupy only 12 bytes, so a
+    /// scalar could be placed right after a vector.
+    pub fn write_vector3(&mut self, value: &Vector3<f32>) -> &mut Self {
+        self.write_raw(16, value.as_slice())
+    }
+
+    pub fn write_vector4(&mut self, value: &Vector4<f32>) -> &mut Self {
+        self.write_raw(16, value.as_slice())
+    }
+
+    pub fn write_matrix4(&mut self, value: &Matrix4<f32>) -> &mut Self {
+        self.write_raw(16, value.as_slice())
+    }
+
+    pub fn write_srgb_color(&mut self, value: &Color) -> &mut Self {
+        self.write_vector4(&value.as_frgba())
+    }
+
+    pub fn write_linear_color(&mut self, value: &Color) -> &mut Self {
+        self.write_vector4(&value.srgb_to_linear_f32())
+    }
+
+    /// Every element of an array has 16-byte stride in `std140` layout.
+    pub fn write_f32_array(&mut self, values: &[f32]) -> &mut Self {
+        for value in values {
+            self.write_raw(16, &[*value]);
+        }
+        self.align(16);
+        self
+    }
+
+    pub fn write_vector4_array(&mut self, values: &[Vector4<f32>]) -> &mut Self {
+        for value in values {
+            self.write_vector4(value);
+        }
+        self
+    }
+}
+
+/// A region of a uniform buffer, that holds data of a single uniform block.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UniformAllocation {
+    offset: usize,
+    size: usize,
+}
+
+struct RingBuffer {
+    id: glow::Buffer,
+    capacity: usize,
+}
+
+/// Uniform buffer ring is a persistent set of GPU buffers, that are used to pass uniform blocks to
+/// shaders. The data of every uniform block is written to CPU-side memory first ([`Self::write`]),
+/// then the data of all blocks is uploaded to the GPU at once ([`Self::upload`]) and then each
+/// block could be bound for a draw call by its allocation ([`Self::bind`]). This is much cheaper
+/// than setting uniforms one-by-one for every draw call. The buffers are never re-created unless
+/// they are too small for the data, and they're shared across render passes.
+///
+/// Every block is aligned to (at least) [`MIN_UNIFORM_BLOCK_ALIGNMENT`] bytes. Allocations are
+/// valid only until the next call of [`Self::upload`].
+pub struct UniformBufferRing {
+    state: Weak<PipelineState>,
+    buffers: Vec<RingBuffer>,
+    current: usize,
+    alignment: usize,
+    staging: Vec<u8>,
+    debug_id: GpuObjectId,
+    // Force compiler to not implement Send and Sync, because OpenGL is not thread-safe.
+    thread_mark: PhantomData<*const u8>,
+}
+
+impl UniformBufferRing {
+    #[track_caller]
+    pub fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let location = Location::caller();
+        let mut buffers = Vec::with_capacity(RING_SIZE);
+        for i in 0..RING_SIZE {
+            let id = unsafe { state.gl.create_buffer()? };
+            state.set_gl_object_label(id, format_args!("Uniform Buffer Ring {i}"));
+            buffers.push(RingBuffer { id, capacity: 0 });
+        }
+
+        let debug_id = state.register_object(GpuObjectKind::UniformBuffer, location);
+        state.set_object_name(debug_id, "Uniform Buffer Ring");
+
+        let driver_alignment = unsafe {
+            state
+                .gl
+                .get_parameter_i32(glow::UNIFORM_BUFFER_OFFSET_ALIGNMENT)
+        };
+
+        Ok(Self {
+            state: state.weak(),
+            buffers,
+            current: 0,
+            alignment: MIN_UNIFORM_BLOCK_ALIGNMENT.max(driver_alignment.max(0) as usize),
+            staging: Vec::new(),
+            debug_id,
+            thread_mark: PhantomData,
+        })
+    }
+
+    /// Writes the data of a uniform block using the given function and returns its allocation.
+    pub fn write<F>(&mut self, func: F) -> UniformAllocation
+    where
+        F: FnOnce(&mut UniformWriter),
+    {
+        let len = self.staging.len();
+        let offset = (len + self.alignment - 1) / self.alignment * self.alignment;
+        self.staging.resize(offset, 0);
+
+        let mut writer = UniformWriter::new(&mut self.staging);
+        func(&mut writer);
+        // Size of a uniform block is always a multiple of vec4 size.
+        writer.align(16);
+        let size = writer.len();
+
+        UniformAllocation { offset, size }
+    }
+
+    /// Uploads the data of all the blocks, that were written since the last upload, to the next GPU
+    /// buffer of the ring.
+    pub fn upload(&mut self, state: &PipelineState) {
+        scope_profile!();
+
+        if self.staging.is_empty() {
+            return;
+        }
+
+        self.current = (self.current + 1) % self.buffers.len();
+        let buffer = &mut self.buffers[self.current];
+
+        unsafe {
+            state.gl.bind_buffer(glow::UNIFORM_BUFFER, Some(buffer.id));
+            if buffer.capacity < self.staging.len() {
+                buffer.capacity = self.staging.len().next_power_of_two();
+                state.gl.buffer_data_size(
+                    glow::UNIFORM_BUFFER,
+                    buffer.capacity as i32,
+                    glow::DYNAMIC_DRAW,
+                );
+            }
+            state
+                .gl
+                .buffer_sub_data_u8_slice(glow::UNIFORM_BUFFER, 0, &self.staging);
+            state.gl.bind_buffer(glow::UNIFORM_BUFFER, None);
+        }
+
+        self.staging.clear();
+    }
+
+    /// Binds the given allocation to the given binding point. The allocation must be uploaded
+    /// first.
+    pub fn bind(&self, state: &PipelineState, binding_point: u32, allocation: &UniformAllocation) {
+        unsafe {
+            state.gl.bind_buffer_range(
+                glow::UNIFORM_BUFFER,
+                binding_point,
+                Some(self.buffers[self.current].id),
+                allocation.offset as i32,
+                allocation.size as i32,
+            );
+        }
+    }
+}
+
+impl Drop for UniformBufferRing {
+    fn drop(&mut self) {
+        if let Some(state) = self.state.upgrade() {
+            unsafe {
+                for buffer in self.buffers.iter() {
+                    state.gl.delete_buffer(buffer.id);
+                }
+            }
+            state.unregister_object(self.debug_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Matrix4, Vector2, Vector3, Vector4},
+        renderer::framework::uniform::UniformWriter,
+    };
+
+    #[test]
+    fn test_std140_layout() {
+        let mut bytes = vec![0; 7];
+        let mut writer = UniformWriter::new(&mut bytes);
+        writer.write_f32(1.0);
+        assert_eq!(writer.len(), 4);
+        writer.write_vector2(&Vector2::new(1.0, 2.0));
+        assert_eq!(writer.len(), 16);
+        writer.write_i32(3);
+        writer.write_vector4(&Vector4::default());
+        assert_eq!(writer.len(), 48);
+        writer.write_f32_array(&[1.0, 2.0]);
+        assert_eq!(writer.len(), 80);
+        writer.write_matrix4(&Matrix4::identity());
+        assert_eq!(writer.len(), 144);
+        writer.write_vector3(&Vector3::default());
+        writer.write_f32(1.0);
+        assert_eq!(writer.len(), 160);
+        // Offsets are relative to the beginning of the block.
+        assert_eq!(bytes.len(), 7 + 160);
+    }
+}
@@ -5,16 +5,13 @@ use crate::renderer::framework::{
     state::PipelineState,
 };
 
+/// Binding point of the uniform block with per-decal data.
+pub const DECAL_DATA_BINDING: u32 = 0;
+
 pub struct DecalShader {
-    pub world_view_projection: UniformLocation,
     pub scene_depth: UniformLocation,
     pub diffuse_texture: UniformLocation,
     pub normal_texture: UniformLocation,
-    pub inv_view_proj: UniformLocation,
-    pub inv_world_decal: UniformLocation,
-    pub resolution: UniformLocation,
-    pub color: UniformLocation,
-    pub layer_index: UniformLocation,
     pub decal_mask: UniformLocation,
     pub program: GpuProgram,
 }
@@ -26,20 +23,13 @@ impl DecalShader {
 
         let program =
             GpuProgram::from_source(state, "DecalShader", vertex_source, fragment_source)?;
+        program.bind_uniform_block(state, "DecalData", DECAL_DATA_BINDING)?;
         Ok(Self {
-            world_view_projection: program
-                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
             scene_depth: program.uniform_location(state, &ImmutableString::new("sceneDepth"))?,
             diffuse_texture: program
                 .uniform_location(state, &ImmutableString::new("diffuseTexture"))?,
             normal_texture: program
                 .uniform_location(state, &ImmutableString::new("normalTexture"))?,
-            inv_view_proj: program.uniform_location(state, &ImmutableString::new("invViewProj"))?,
-            inv_world_decal: program
-                .uniform_location(state, &ImmutableString::new("invWorldDecal"))?,
-            resolution: program.uniform_location(state, &ImmutableString::new("resolution"))?,
-            color: program.uniform_location(state, &ImmutableString::new("color"))?,
-            layer_index: program.uniform_location(state, &ImmutableString::new("layerIndex"))?,
             decal_mask: program.uniform_location(state, &ImmutableString::new("decalMask"))?,
             program,
         })
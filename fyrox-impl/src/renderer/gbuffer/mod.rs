@@ -4,7 +4,7 @@
 //! RT1: RGBA8 - Normal (xyz)
 //! RT2: RGBA16F - Ambient light + emission (both in xyz)
 //! RT3: RGBA8 - Metallic (x) + Roughness (y) + Ambient Occlusion (z)
-//! RT4: R8UI - Decal mask (x)
+//! RT4: R32UI - Decal layer (lowest 8 bits) + light link mask (other 24 bits)
 //!
 //! Every alpha channel is used for layer blending for terrains. This is inefficient, but for
 //! now I don't know better solution.
@@ -33,8 +33,11 @@ use crate::{
                 PixelKind, WrapMode,
             },
             state::{BlendFactor, BlendFunc, PipelineState},
+            uniform::UniformBufferRing,
         },
-        gbuffer::decal::DecalShader,
+        gbuffer::decal::{DecalShader, DECAL_DATA_BINDING},
+        instance::{upload_bundle_instances, InstanceUniforms},
+        light::link::LightLinks,
         storage::MatrixStorageCache,
         GeometryCache, MaterialContext, RenderPassStatistics, TextureCache,
     },
@@ -76,6 +79,8 @@ pub(crate) struct GBufferRenderContext<'a, 'b> {
     pub use_parallax_mapping: bool,
     pub graph: &'b Graph,
     pub matrix_storage: &'a mut MatrixStorageCache,
+    pub uniform_buffer_ring: &'a mut UniformBufferRing,
+    pub light_links: &'b LightLinks,
 }
 
 impl GBuffer {
@@ -95,6 +100,7 @@ impl GBuffer {
             .bind_mut(state, 0)
             .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
             .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+        depth_stencil_texture.set_name(state, "GBuffer Depth Stencil");
 
         let depth_stencil = Rc::new(RefCell::new(depth_stencil_texture));
 
@@ -111,6 +117,7 @@ impl GBuffer {
             .bind_mut(state, 0)
             .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
             .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+        diffuse_texture.set_name(state, "GBuffer Diffuse");
         let diffuse_texture = Rc::new(RefCell::new(diffuse_texture));
 
         let mut normal_texture = GpuTexture::new(
@@ -126,6 +133,7 @@ impl GBuffer {
             .bind_mut(state, 0)
             .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
             .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+        normal_texture.set_name(state, "GBuffer Normal");
         let normal_texture = Rc::new(RefCell::new(normal_texture));
 
         let mut ambient_texture = GpuTexture::new(
@@ -141,11 +149,12 @@ impl GBuffer {
             .bind_mut(state, 0)
             .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
             .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+        ambient_texture.set_name(state, "GBuffer Ambient");
 
         let mut decal_mask_texture = GpuTexture::new(
             state,
             GpuTextureKind::Rectangle { width, height },
-            PixelKind::R8UI,
+            PixelKind::R32UI,
             MinificationFilter::Nearest,
             MagnificationFilter::Nearest,
             1,
@@ -155,6 +164,7 @@ impl GBuffer {
             .bind_mut(state, 0)
             .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
             .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+        decal_mask_texture.set_name(state, "GBuffer Decal Mask");
 
         let mut material_texture = GpuTexture::new(
             state,
@@ -169,6 +179,7 @@ impl GBuffer {
             .bind_mut(state, 0)
             .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
             .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+        material_texture.set_name(state, "GBuffer Material");
 
         let framebuffer = FrameBuffer::new(
             state,
@@ -215,6 +226,9 @@ impl GBuffer {
             ],
         )?;
 
+        framebuffer.set_name(state, "GBuffer");
+        decal_framebuffer.set_name(state, "GBuffer Decals");
+
         Ok(Self {
             framebuffer,
             width: width as i32,
@@ -280,6 +294,8 @@ impl GBuffer {
             volume_dummy,
             graph,
             matrix_storage,
+            uniform_buffer_ring,
+            light_links,
             ..
         } = args;
 
@@ -299,11 +315,47 @@ impl GBuffer {
         let camera_up = inv_view.up();
         let camera_side = inv_view.side();
 
-        for bundle in bundle_storage
-            .bundles
-            .iter()
-            .filter(|b| b.render_path == RenderPath::Deferred)
-        {
+        let camera_position = camera.global_position();
+        let z_near = camera.projection().z_near();
+        let z_far = camera.projection().z_far();
+
+        let bundles = upload_bundle_instances(
+            state,
+            uniform_buffer_ring,
+            bundle_storage
+                .bundles
+                .iter()
+                .filter(|b| b.render_path == RenderPath::Deferred),
+            |bundle, instance| {
+                let view_projection = if instance.depth_offset != 0.0 {
+                    let mut projection = camera.projection_matrix();
+                    projection[14] -= instance.depth_offset;
+                    projection * camera.view_matrix()
+                } else {
+                    initial_view_projection
+                };
+
+                InstanceUniforms {
+                    world_matrix: instance.world_transform,
+                    view_projection_matrix: view_projection,
+                    use_skeletal_animation: bundle.is_skinned,
+                    use_pom: use_parallax_mapping,
+                    camera_position,
+                    camera_up_vector: camera_up,
+                    camera_side_vector: camera_side,
+                    z_near,
+                    z_far,
+                    light_position: Default::default(),
+                    blend_shapes_count: instance.blend_shapes_weights.len(),
+                    vertex_animation: instance.vertex_animation.as_ref(),
+                    ambient_light: Color::WHITE, // TODO
+                    light_link_mask: light_links.node_mask(graph, instance.node_handle),
+                    precipitation_occlusion_matrix: None,
+                }
+            },
+        );
+
+        for (bundle, instance_uniforms) in bundles.iter() {
             let mut material_state = bundle.material.state();
 
             let Some(material) = material_state.data() else {
@@ -328,43 +380,28 @@ impl GBuffer {
                 continue;
             };
 
-            for instance in bundle.instances.iter() {
+            for (instance, instance_uniforms) in bundle.instances.iter().zip(instance_uniforms) {
                 let apply_uniforms = |mut program_binding: GpuProgramBinding| {
-                    let view_projection = if instance.depth_offset != 0.0 {
-                        let mut projection = camera.projection_matrix();
-                        projection[14] -= instance.depth_offset;
-                        projection * camera.view_matrix()
-                    } else {
-                        initial_view_projection
-                    };
-
                     apply_material(MaterialContext {
                         material,
                         program_binding: &mut program_binding,
                         texture_cache,
                         matrix_storage,
-                        world_matrix: &instance.world_transform,
-                        view_projection_matrix: &view_projection,
-                        wvp_matrix: &(view_projection * instance.world_transform),
+                        uniform_buffer_ring,
+                        instance_uniforms,
                         bone_matrices: &instance.bone_matrices,
-                        use_skeletal_animation: bundle.is_skinned,
-                        camera_position: &camera.global_position(),
-                        camera_up_vector: &camera_up,
-                        camera_side_vector: &camera_side,
-                        z_near: camera.projection().z_near(),
-                        use_pom: use_parallax_mapping,
-                        light_position: &Default::default(),
                         blend_shapes_storage: blend_shapes_storage.as_ref(),
                         blend_shapes_weights: &instance.blend_shapes_weights,
+                        vertex_animation: instance.vertex_animation.as_ref(),
                         normal_dummy: &normal_dummy,
                         white_dummy: &white_dummy,
                         black_dummy: &black_dummy,
                         volume_dummy: &volume_dummy,
                         persistent_identifier: instance.persistent_identifier,
                         light_data: None,
-                        ambient_light: Color::WHITE, // TODO
-                        scene_depth: None,           // TODO. Add z-pre-pass.
-                        z_far: camera.projection().z_far(),
+                        scene_depth: None, // TODO. Add z-pre-pass.
+                        scene_color: None,
+                        precipitation_occlusion: None,
                     });
                 };
 
@@ -388,13 +425,29 @@ impl GBuffer {
         // Render decals after because we need to modify diffuse texture of G-Buffer and use depth texture
         // for rendering. We'll render in the G-Buffer, but depth will be used from final frame, since
         // decals do not modify depth (only diffuse and normal maps).
+        let decals = graph
+            .linear_iter()
+            .filter_map(|n| n.cast::<Decal>())
+            .map(|decal| {
+                let allocation = uniform_buffer_ring.write(|writer| {
+                    writer
+                        .write_matrix4(&(initial_view_projection * decal.global_transform()))
+                        .write_matrix4(&inv_view_proj)
+                        .write_matrix4(&decal.global_transform().try_inverse().unwrap_or_default())
+                        .write_linear_color(&decal.color())
+                        .write_vector2(&resolution)
+                        .write_u32(decal.layer() as u32);
+                });
+                (decal, allocation)
+            })
+            .collect::<Vec<_>>();
+        uniform_buffer_ring.upload(state);
+
         let unit_cube = &self.cube;
-        for decal in graph.linear_iter().filter_map(|n| n.cast::<Decal>()) {
+        for (decal, allocation) in decals {
             let shader = &self.decal_shader;
             let program = &self.decal_shader.program;
 
-            let world_view_proj = initial_view_projection * decal.global_transform();
-
             statistics += self.decal_framebuffer.draw(
                 unit_cube,
                 state,
@@ -415,13 +468,11 @@ impl GBuffer {
                 ElementRange::Full,
                 |mut program_binding| {
                     program_binding
-                        .set_matrix4(&shader.world_view_projection, &world_view_proj)
-                        .set_matrix4(&shader.inv_view_proj, &inv_view_proj)
-                        .set_matrix4(
-                            &shader.inv_world_decal,
-                            &decal.global_transform().try_inverse().unwrap_or_default(),
+                        .bind_uniform_allocation(
+                            uniform_buffer_ring,
+                            DECAL_DATA_BINDING,
+                            &allocation,
                         )
-                        .set_vector2(&shader.resolution, &resolution)
                         .set_texture(&shader.scene_depth, &depth)
                         .set_texture(
                             &shader.diffuse_texture,
@@ -437,9 +488,7 @@ impl GBuffer {
                                 .and_then(|t| texture_cache.get(state, t))
                                 .unwrap_or(&normal_dummy),
                         )
-                        .set_texture(&shader.decal_mask, &decal_mask)
-                        .set_u32(&shader.layer_index, decal.layer() as u32)
-                        .set_linear_color(&shader.color, &decal.color());
+                        .set_texture(&shader.decal_mask, &decal_mask);
                 },
             )?;
         }
@@ -0,0 +1,230 @@
+//! Per-instance built-in uniforms of material shaders. Instead of setting them one-by-one for every
+//! draw call, render passes write the data of every instance to a [`UniformBufferRing`] before
+//! drawing, upload it at once and then only bind a region of the buffer for each draw call.
+//!
+//! Material shaders still could declare the built-ins as plain uniforms (for example
+//! `uniform mat4 fyrox_worldMatrix;`), such declarations are replaced with a uniform block by
+//! [`inject_instance_data_block`] when a GPU program is created.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector3},
+        color::Color,
+    },
+    renderer::{
+        bundle::{RenderDataBundle, SurfaceInstanceData, VertexAnimationInstanceData},
+        framework::{
+            state::PipelineState,
+            uniform::{UniformAllocation, UniformBufferRing},
+        },
+    },
+};
+
+/// Name of the uniform block with per-instance built-in uniforms.
+pub const INSTANCE_DATA_BLOCK_NAME: &str = "FyroxInstanceData";
+
+/// Binding point of the uniform block with per-instance built-in uniforms.
+pub const INSTANCE_DATA_BINDING: u32 = 0;
+
+/// Names of the built-in uniforms, that are stored in the instance data block.
+const INSTANCE_UNIFORMS: [&str; 17] = [
+    "fyrox_worldMatrix",
+    "fyrox_viewProjectionMatrix",
+    "fyrox_worldViewProjection",
+    "fyrox_precipitationOcclusionMatrix",
+    "fyrox_ambientLightColor",
+    "fyrox_cameraPosition",
+    "fyrox_zNear",
+    "fyrox_cameraUpVector",
+    "fyrox_zFar",
+    "fyrox_cameraSideVector",
+    "fyrox_blendShapesCount",
+    "fyrox_lightPosition",
+    "fyrox_vertexAnimationVertexCount",
+    "fyrox_vertexAnimationFrames",
+    "fyrox_lightLinkMask",
+    "fyrox_useSkeletalAnimation",
+    "fyrox_usePOM",
+];
+
+/// The order of the members must match the order of the writes in [`InstanceUniforms::write`].
+/// Integers have explicit precision, because the default precision of integers differs between
+/// vertex and fragment shaders in GLSL ES, and the block must be identical in both of them.
+const INSTANCE_DATA_BLOCK: &str = r#"layout(std140) uniform FyroxInstanceData {
+    mat4 fyrox_worldMatrix;
+    mat4 fyrox_viewProjectionMatrix;
+    mat4 fyrox_worldViewProjection;
+    mat4 fyrox_precipitationOcclusionMatrix;
+    vec4 fyrox_ambientLightColor;
+    vec3 fyrox_cameraPosition;
+    float fyrox_zNear;
+    vec3 fyrox_cameraUpVector;
+    float fyrox_zFar;
+    vec3 fyrox_cameraSideVector;
+    highp int fyrox_blendShapesCount;
+    vec3 fyrox_lightPosition;
+    highp int fyrox_vertexAnimationVertexCount;
+    vec3 fyrox_vertexAnimationFrames;
+    highp uint fyrox_lightLinkMask;
+    bool fyrox_useSkeletalAnimation;
+    bool fyrox_usePOM;
+};
+"#;
+
+fn is_instance_uniform_declaration(line: &str) -> bool {
+    let code = line.split("//").next().unwrap_or_default().trim();
+    let Some(declaration) = code.strip_suffix(';') else {
+        return false;
+    };
+    let mut tokens = declaration.split_whitespace();
+    tokens.next() == Some("uniform")
+        && tokens
+            .last()
+            .is_some_and(|name| INSTANCE_UNIFORMS.contains(&name))
+}
+
+/// Removes declarations of per-instance built-in uniforms from the given shader source and adds
+/// the instance data block instead. Removed lines are left empty, so the line numbers in compiler
+/// messages are shifted only by the size of the block.
+pub(crate) fn inject_instance_data_block(source: &str) -> String {
+    let mut result = INSTANCE_DATA_BLOCK.to_owned();
+    for line in source.lines() {
+        if !is_instance_uniform_declaration(line) {
+            result += line;
+        }
+        result.push('\n');
+    }
+    result
+}
+
+/// Values of per-instance built-in uniforms. See [`crate::material::shader`] docs for the
+/// description of each uniform.
+pub struct InstanceUniforms<'a> {
+    /// Local-to-world transformation of the instance.
+    pub world_matrix: Matrix4<f32>,
+    /// World-to-clip-space transformation of the observer.
+    pub view_projection_matrix: Matrix4<f32>,
+    /// Whether the instance is skinned or not.
+    pub use_skeletal_animation: bool,
+    /// Whether to use parallax mapping or not.
+    pub use_pom: bool,
+    /// Position of the observer.
+    pub camera_position: Vector3<f32>,
+    /// Up vector of the observer.
+    pub camera_up_vector: Vector3<f32>,
+    /// Side vector of the observer.
+    pub camera_side_vector: Vector3<f32>,
+    /// Near clipping plane distance of the observer.
+    pub z_near: f32,
+    /// Far clipping plane distance of the observer.
+    pub z_far: f32,
+    /// Position of the light source, that is used by shadow passes.
+    pub light_position: Vector3<f32>,
+    /// Amount of blend shapes of the instance.
+    pub blend_shapes_count: usize,
+    /// Optional vertex animation data of the instance.
+    pub vertex_animation: Option<&'a VertexAnimationInstanceData>,
+    /// Ambient lighting color of the scene.
+    pub ambient_light: Color,
+    /// A mask of linked lights, that must not affect the instance.
+    pub light_link_mask: u32,
+    /// World to clip space matrix of the precipitation occlusion map, identity matrix is used if
+    /// there's no map.
+    pub precipitation_occlusion_matrix: Option<Matrix4<f32>>,
+}
+
+impl InstanceUniforms<'_> {
+    /// Writes the uniforms to the given ring and returns their allocation, that could be bound for
+    /// a draw call after the ring is uploaded.
+    pub fn write(&self, ring: &mut UniformBufferRing) -> UniformAllocation {
+        let (frames, vertex_count) = self
+            .vertex_animation
+            .map(|vertex_animation| {
+                (
+                    Vector3::new(
+                        vertex_animation.frames[0] as f32,
+                        vertex_animation.frames[1] as f32,
+                        vertex_animation.blend,
+                    ),
+                    vertex_animation.vertex_count as i32,
+                )
+            })
+            .unwrap_or_default();
+
+        ring.write(|writer| {
+            writer
+                .write_matrix4(&self.world_matrix)
+                .write_matrix4(&self.view_projection_matrix)
+                .write_matrix4(&(self.view_projection_matrix * self.world_matrix))
+                .write_matrix4(
+                    &self
+                        .precipitation_occlusion_matrix
+                        .unwrap_or_else(Matrix4::identity),
+                )
+                .write_srgb_color(&self.ambient_light)
+                .write_vector3(&self.camera_position)
+                .write_f32(self.z_near)
+                .write_vector3(&self.camera_up_vector)
+                .write_f32(self.z_far)
+                .write_vector3(&self.camera_side_vector)
+                .write_i32(self.blend_shapes_count as i32)
+                .write_vector3(&self.light_position)
+                .write_i32(vertex_count)
+                .write_vector3(&frames)
+                .write_u32(self.light_link_mask)
+                .write_bool(self.use_skeletal_animation)
+                .write_bool(self.use_pom);
+        })
+    }
+}
+
+/// Writes per-instance uniforms of every instance of the given bundles, that are provided by the
+/// given function, and uploads them at once. Returns the bundles along with the allocations of
+/// their instances, in the same order as [`RenderDataBundle::instances`].
+pub fn upload_bundle_instances<'a, B, F>(
+    state: &PipelineState,
+    ring: &mut UniformBufferRing,
+    bundles: B,
+    mut func: F,
+) -> Vec<(&'a RenderDataBundle, Vec<UniformAllocation>)>
+where
+    B: Iterator<Item = &'a RenderDataBundle>,
+    F: FnMut(&'a RenderDataBundle, &'a SurfaceInstanceData) -> InstanceUniforms<'a>,
+{
+    let bundles = bundles
+        .map(|bundle| {
+            let allocations = bundle
+                .instances
+                .iter()
+                .map(|instance| func(bundle, instance).write(ring))
+                .collect();
+            (bundle, allocations)
+        })
+        .collect();
+    ring.upload(state);
+    bundles
+}
+
+#[cfg(test)]
+mod test {
+    use crate::renderer::instance::{inject_instance_data_block, INSTANCE_DATA_BLOCK};
+
+    #[test]
+    fn test_inject_instance_data_block() {
+        let source = "uniform mat4 fyrox_worldMatrix;\n\
+            uniform highp int fyrox_blendShapesCount; // Comment.\n\
+            uniform sampler2D fyrox_boneMatrices;\n\
+            // uniform mat4 fyrox_worldViewProjection;\n\
+            uniform mat4 fyrox_worldMatrixInverse;\n";
+        let result = inject_instance_data_block(source);
+        let body = result.strip_prefix(INSTANCE_DATA_BLOCK).unwrap();
+        assert_eq!(
+            body,
+            "\n\
+            \n\
+            uniform sampler2D fyrox_boneMatrices;\n\
+            // uniform mat4 fyrox_worldViewProjection;\n\
+            uniform mat4 fyrox_worldMatrixInverse;\n"
+        );
+    }
+}
@@ -11,7 +11,19 @@ pub struct AmbientLightShader {
     pub diffuse_texture: UniformLocation,
     pub ambient_color: UniformLocation,
     pub ao_sampler: UniformLocation,
+    pub ao_enabled: UniformLocation,
     pub ambient_texture: UniformLocation,
+    pub ibl_enabled: UniformLocation,
+    pub depth_texture: UniformLocation,
+    pub normal_texture: UniformLocation,
+    pub material_texture: UniformLocation,
+    pub irradiance_map: UniformLocation,
+    pub specular_map: UniformLocation,
+    pub specular_map_max_level: UniformLocation,
+    pub environment_rotation: UniformLocation,
+    pub environment_exposure: UniformLocation,
+    pub inv_view_proj_matrix: UniformLocation,
+    pub camera_position: UniformLocation,
 }
 
 impl AmbientLightShader {
@@ -28,8 +40,29 @@ impl AmbientLightShader {
             ambient_color: program
                 .uniform_location(state, &ImmutableString::new("ambientColor"))?,
             ao_sampler: program.uniform_location(state, &ImmutableString::new("aoSampler"))?,
+            ao_enabled: program.uniform_location(state, &ImmutableString::new("aoEnabled"))?,
             ambient_texture: program
                 .uniform_location(state, &ImmutableString::new("ambientTexture"))?,
+            ibl_enabled: program.uniform_location(state, &ImmutableString::new("iblEnabled"))?,
+            depth_texture: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            normal_texture: program
+                .uniform_location(state, &ImmutableString::new("normalTexture"))?,
+            material_texture: program
+                .uniform_location(state, &ImmutableString::new("materialTexture"))?,
+            irradiance_map: program
+                .uniform_location(state, &ImmutableString::new("irradianceMap"))?,
+            specular_map: program.uniform_location(state, &ImmutableString::new("specularMap"))?,
+            specular_map_max_level: program
+                .uniform_location(state, &ImmutableString::new("specularMapMaxLevel"))?,
+            environment_rotation: program
+                .uniform_location(state, &ImmutableString::new("environmentRotation"))?,
+            environment_exposure: program
+                .uniform_location(state, &ImmutableString::new("environmentExposure"))?,
+            inv_view_proj_matrix: program
+                .uniform_location(state, &ImmutableString::new("invViewProj"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
             program,
         })
     }
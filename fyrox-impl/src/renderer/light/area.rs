@@ -0,0 +1,79 @@
+use crate::core::sstorage::ImmutableString;
+use crate::renderer::framework::{
+    error::FrameworkError,
+    gpu_program::{GpuProgram, UniformLocation},
+    state::PipelineState,
+};
+
+pub struct AreaLightShader {
+    pub program: GpuProgram,
+    pub wvp_matrix: UniformLocation,
+    pub depth_sampler: UniformLocation,
+    pub color_sampler: UniformLocation,
+    pub normal_sampler: UniformLocation,
+    pub material_sampler: UniformLocation,
+    pub light_link_texture: UniformLocation,
+    pub light_link_bit: UniformLocation,
+    pub light_vertices: UniformLocation,
+    pub light_vertex_count: UniformLocation,
+    pub light_position: UniformLocation,
+    pub light_direction: UniformLocation,
+    pub light_half_axis_x: UniformLocation,
+    pub light_half_axis_z: UniformLocation,
+    pub light_is_disk: UniformLocation,
+    pub two_sided: UniformLocation,
+    pub light_radius: UniformLocation,
+    pub light_color: UniformLocation,
+    pub light_intensity: UniformLocation,
+    pub use_ltc: UniformLocation,
+    pub inv_view_proj_matrix: UniformLocation,
+    pub camera_position: UniformLocation,
+}
+
+impl AreaLightShader {
+    pub fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("../shaders/deferred_area_light_fs.glsl");
+        let vertex_source = include_str!("../shaders/deferred_light_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "AreaLightShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_sampler: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            color_sampler: program
+                .uniform_location(state, &ImmutableString::new("colorTexture"))?,
+            normal_sampler: program
+                .uniform_location(state, &ImmutableString::new("normalTexture"))?,
+            material_sampler: program
+                .uniform_location(state, &ImmutableString::new("materialTexture"))?,
+            light_link_texture: program
+                .uniform_location(state, &ImmutableString::new("lightLinkTexture"))?,
+            light_link_bit: program
+                .uniform_location(state, &ImmutableString::new("lightLinkBit"))?,
+            light_vertices: program
+                .uniform_location(state, &ImmutableString::new("lightVertices"))?,
+            light_vertex_count: program
+                .uniform_location(state, &ImmutableString::new("lightVertexCount"))?,
+            light_position: program.uniform_location(state, &ImmutableString::new("lightPos"))?,
+            light_direction: program
+                .uniform_location(state, &ImmutableString::new("lightDirection"))?,
+            light_half_axis_x: program
+                .uniform_location(state, &ImmutableString::new("lightHalfAxisX"))?,
+            light_half_axis_z: program
+                .uniform_location(state, &ImmutableString::new("lightHalfAxisZ"))?,
+            light_is_disk: program.uniform_location(state, &ImmutableString::new("lightIsDisk"))?,
+            two_sided: program.uniform_location(state, &ImmutableString::new("twoSided"))?,
+            light_radius: program.uniform_location(state, &ImmutableString::new("lightRadius"))?,
+            light_color: program.uniform_location(state, &ImmutableString::new("lightColor"))?,
+            light_intensity: program
+                .uniform_location(state, &ImmutableString::new("lightIntensity"))?,
+            use_ltc: program.uniform_location(state, &ImmutableString::new("useLtc"))?,
+            inv_view_proj_matrix: program
+                .uniform_location(state, &ImmutableString::new("invViewProj"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
+            program,
+        })
+    }
+}
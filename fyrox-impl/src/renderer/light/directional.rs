@@ -14,6 +14,8 @@ pub struct DirectionalLightShader {
     pub color_sampler: UniformLocation,
     pub normal_sampler: UniformLocation,
     pub material_sampler: UniformLocation,
+    pub light_link_texture: UniformLocation,
+    pub light_link_bit: UniformLocation,
     pub light_direction: UniformLocation,
     pub light_color: UniformLocation,
     pub inv_view_proj_matrix: UniformLocation,
@@ -52,6 +54,10 @@ impl DirectionalLightShader {
                 .uniform_location(state, &ImmutableString::new("normalTexture"))?,
             material_sampler: program
                 .uniform_location(state, &ImmutableString::new("materialTexture"))?,
+            light_link_texture: program
+                .uniform_location(state, &ImmutableString::new("lightLinkTexture"))?,
+            light_link_bit: program
+                .uniform_location(state, &ImmutableString::new("lightLinkBit"))?,
             light_direction: program
                 .uniform_location(state, &ImmutableString::new("lightDirection"))?,
             light_color: program.uniform_location(state, &ImmutableString::new("lightColor"))?,
@@ -0,0 +1,77 @@
+//! Light linking support for the deferred renderer. Every linked light (a light with non-empty
+//! include or exclude sets) gets its own bit, and every rendered instance writes a mask of linked
+//! lights that must not affect it into the G-Buffer. Deferred light shaders then compare the bit
+//! of a light with the mask of a pixel and skip lighting it.
+
+use crate::{
+    core::pool::Handle,
+    graph::{BaseSceneGraph, SceneGraph},
+    scene::{graph::Graph, light::BaseLight, node::Node},
+};
+use fxhash::FxHashMap;
+use std::cell::RefCell;
+
+/// Max amount of linked lights that can be handled at once. The mask shares 32-bit G-Buffer
+/// texture with decal layer index (lowest 8 bits), so only 24 bits are left.
+pub const MAX_LINKED_LIGHTS: usize = 24;
+
+/// Bit offset of the light link mask in the decal mask texture of the G-Buffer. Every bit and mask
+/// returned by [`LightLinks`] is already shifted by this offset.
+const LIGHT_LINK_MASK_OFFSET: usize = 8;
+
+#[derive(Default)]
+pub(crate) struct LightLinks {
+    lights: Vec<Handle<Node>>,
+    masks: RefCell<FxHashMap<Handle<Node>, u32>>,
+}
+
+impl LightLinks {
+    pub fn from_graph(graph: &Graph) -> Self {
+        let lights = graph
+            .pair_iter()
+            .filter(|(_, node)| node.global_visibility() && node.is_globally_enabled())
+            .filter(|(_, node)| {
+                node.query_component_ref::<BaseLight>()
+                    .is_some_and(|base_light| base_light.is_linked())
+            })
+            .map(|(handle, _)| handle)
+            .take(MAX_LINKED_LIGHTS)
+            .collect();
+
+        Self {
+            lights,
+            masks: Default::default(),
+        }
+    }
+
+    /// Returns a bit assigned to the given light or zero if the light is not linked (or there's
+    /// too many linked lights).
+    pub fn light_bit(&self, light: Handle<Node>) -> u32 {
+        self.lights
+            .iter()
+            .position(|l| *l == light)
+            .map_or(0, |index| 1 << (index + LIGHT_LINK_MASK_OFFSET))
+    }
+
+    /// Returns a mask of linked lights, that must not affect the given node.
+    pub fn node_mask(&self, graph: &Graph, node: Handle<Node>) -> u32 {
+        if self.lights.is_empty() {
+            return 0;
+        }
+
+        *self.masks.borrow_mut().entry(node).or_insert_with(|| {
+            let mut mask = 0;
+            for (index, light) in self.lights.iter().enumerate() {
+                if let Some(base_light) = graph
+                    .try_get(*light)
+                    .and_then(|light| light.query_component_ref::<BaseLight>())
+                {
+                    if !base_light.affects(graph, node) {
+                        mask |= 1 << (index + LIGHT_LINK_MASK_OFFSET);
+                    }
+                }
+            }
+            mask
+        })
+    }
+}
@@ -1,9 +1,10 @@
 use crate::renderer::LightingStatistics;
 use crate::{
     core::{
-        algebra::{Matrix4, Point3, Vector2, Vector3},
+        algebra::{Matrix4, Point3, Rotation3, Vector2, Vector3},
         color::Color,
         math::{frustum::Frustum, Matrix4Ext, Rect, TriangleDefinition},
+        pool::Handle,
         scope_profile,
     },
     graph::SceneGraph,
@@ -19,11 +20,13 @@ use crate::{
                 BlendFactor, BlendFunc, ColorMask, CompareFunc, PipelineState, StencilAction,
                 StencilFunc, StencilOp,
             },
+            uniform::UniformBufferRing,
         },
         gbuffer::GBuffer,
         light::{
-            ambient::AmbientLightShader, directional::DirectionalLightShader,
-            point::PointLightShader, spot::SpotLightShader,
+            ambient::AmbientLightShader, area::AreaLightShader,
+            directional::DirectionalLightShader, link::LightLinks, point::PointLightShader,
+            spot::SpotLightShader,
         },
         light_volume::LightVolumeRenderer,
         shadow::{
@@ -32,13 +35,19 @@ use crate::{
             spot::SpotShadowMapRenderer,
         },
         skybox_shader::SkyboxShader,
-        ssao::ScreenSpaceAmbientOcclusionRenderer,
+        ssao::{AmbientOcclusionContext, ScreenSpaceAmbientOcclusionRenderer},
         storage::MatrixStorageCache,
-        GeometryCache, QualitySettings, RenderPassStatistics, TextureCache,
+        volumetric::{ShaftCameraData, VolumetricLightRenderer},
+        GeometryCache, QualitySettings, RenderPassStatistics, TextureCache, VolumetricLightQuality,
     },
     scene::{
-        camera::Camera,
-        light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
+        camera::{Camera, CameraClearFlags},
+        light::{
+            area::{AreaLight, AreaLightShape},
+            directional::DirectionalLight,
+            point::PointLight,
+            spot::SpotLight,
+        },
         mesh::{
             buffer::{TriangleBuffer, VertexBuffer},
             surface::SurfaceData,
@@ -50,7 +59,9 @@ use crate::{
 use std::{cell::RefCell, rc::Rc};
 
 pub mod ambient;
+pub mod area;
 pub mod directional;
+pub mod link;
 pub mod point;
 pub mod spot;
 
@@ -58,6 +69,7 @@ pub struct DeferredLightRenderer {
     pub ssao_renderer: ScreenSpaceAmbientOcclusionRenderer,
     spot_light_shader: SpotLightShader,
     point_light_shader: PointLightShader,
+    area_light_shader: AreaLightShader,
     directional_light_shader: DirectionalLightShader,
     ambient_light_shader: AmbientLightShader,
     quad: GeometryBuffer,
@@ -73,9 +85,11 @@ pub struct DeferredLightRenderer {
 
 pub(crate) struct DeferredRendererContext<'a> {
     pub state: &'a PipelineState,
+    pub scene_handle: Handle<Scene>,
     pub scene: &'a Scene,
     pub camera: &'a Camera,
     pub gbuffer: &'a mut GBuffer,
+    pub volumetric_light_renderer: &'a mut VolumetricLightRenderer,
     pub ambient_color: Color,
     pub settings: &'a QualitySettings,
     pub textures: &'a mut TextureCache,
@@ -85,8 +99,11 @@ pub(crate) struct DeferredRendererContext<'a> {
     pub normal_dummy: Rc<RefCell<GpuTexture>>,
     pub white_dummy: Rc<RefCell<GpuTexture>>,
     pub black_dummy: Rc<RefCell<GpuTexture>>,
+    pub environment_dummy: Rc<RefCell<GpuTexture>>,
     pub volume_dummy: Rc<RefCell<GpuTexture>>,
     pub matrix_storage: &'a mut MatrixStorageCache,
+    pub uniform_buffer_ring: &'a mut UniformBufferRing,
+    pub light_links: &'a LightLinks,
 }
 
 impl DeferredLightRenderer {
@@ -138,6 +155,7 @@ impl DeferredLightRenderer {
             )?,
             spot_light_shader: SpotLightShader::new(state)?,
             point_light_shader: PointLightShader::new(state)?,
+            area_light_shader: AreaLightShader::new(state)?,
             directional_light_shader: DirectionalLightShader::new(state)?,
             ambient_light_shader: AmbientLightShader::new(state)?,
             quad: GeometryBuffer::from_surface_data(
@@ -224,7 +242,6 @@ impl DeferredLightRenderer {
                 settings.csm_settings.precision,
             )?;
         }
-        self.ssao_renderer.set_radius(settings.ssao_radius);
         Ok(())
     }
 
@@ -252,9 +269,11 @@ impl DeferredLightRenderer {
 
         let DeferredRendererContext {
             state,
+            scene_handle,
             scene,
             camera,
             gbuffer,
+            volumetric_light_renderer,
             shader_cache,
             normal_dummy,
             white_dummy,
@@ -264,8 +283,11 @@ impl DeferredLightRenderer {
             geometry_cache,
             frame_buffer,
             black_dummy,
+            environment_dummy,
             volume_dummy,
             matrix_storage,
+            uniform_buffer_ring,
+            light_links,
         } = args;
 
         let viewport = Rect::new(0, 0, gbuffer.width, gbuffer.height);
@@ -293,16 +315,30 @@ impl DeferredLightRenderer {
 
         // Fill SSAO map.
         if settings.use_ssao {
-            pass_stats += self.ssao_renderer.render(
+            pass_stats += self.ssao_renderer.render(AmbientOcclusionContext {
                 state,
                 gbuffer,
+                camera_key: (scene_handle, camera.self_handle),
                 projection_matrix,
-                camera.view_matrix().basis(),
-            )?;
+                view_matrix: camera.view_matrix(),
+                radius: settings.ssao_radius,
+                quality: settings.ssao_quality,
+            })?;
         }
 
-        // Render skybox (if any).
-        if let Some(skybox) = camera.skybox_ref() {
+        // Inverse rotation of the environment is used to transform world-space directions to
+        // sample the cube maps of the skybox.
+        let environment_rotation = camera
+            .skybox_ref()
+            .map(|skybox| Rotation3::from_axis_angle(&Vector3::y_axis(), -skybox.rotation()))
+            .unwrap_or_default()
+            .into_inner();
+
+        // Render skybox (if any and if the camera needs it).
+        if let Some(skybox) = camera
+            .skybox_ref()
+            .filter(|_| camera.clear_flags() == CameraClearFlags::Skybox)
+        {
             let size = camera.projection().z_far() / 2.0f32.sqrt();
             let scale = Matrix4::new_scaling(size);
             let wvp = Matrix4::new_translation(&camera.global_position()) * scale;
@@ -333,7 +369,10 @@ impl DeferredLightRenderer {
                     |mut program_binding| {
                         program_binding
                             .set_texture(&shader.cubemap_texture, gpu_texture)
-                            .set_matrix4(&shader.wvp_matrix, &(view_projection * wvp));
+                            .set_matrix4(&shader.wvp_matrix, &(view_projection * wvp))
+                            .set_matrix3(&shader.rotation, &environment_rotation)
+                            .set_f32(&shader.exposure, skybox.exposure())
+                            .set_bool(&shader.is_linear, skybox.is_linear());
                     },
                 )?;
             }
@@ -344,9 +383,28 @@ impl DeferredLightRenderer {
         let gbuffer_diffuse_map = gbuffer.diffuse_texture();
         let gbuffer_normal_map = gbuffer.normal_texture();
         let gbuffer_material_map = gbuffer.material_texture();
+        let gbuffer_light_link_map = gbuffer.decal_mask_texture();
         let gbuffer_ambient_map = gbuffer.ambient_texture();
         let ao_map = self.ssao_renderer.ao_map();
 
+        // Image-based lighting is available only for skyboxes with generated irradiance and specular
+        // maps.
+        let ibl = camera.skybox_ref().and_then(|skybox| {
+            let irradiance_map = textures.get(state, skybox.irradiance_map_ref()?).cloned()?;
+            let specular_map_resource = skybox.specular_map_ref()?;
+            let specular_map_max_level = specular_map_resource
+                .data_ref()
+                .mip_count()
+                .saturating_sub(1) as f32;
+            let specular_map = textures.get(state, specular_map_resource).cloned()?;
+            Some((
+                irradiance_map,
+                specular_map,
+                specular_map_max_level,
+                skybox.exposure(),
+            ))
+        });
+
         pass_stats += frame_buffer.draw(
             &self.quad,
             state,
@@ -381,13 +439,68 @@ impl DeferredLightRenderer {
                             &white_dummy
                         },
                     )
+                    .set_bool(&self.ambient_light_shader.ao_enabled, settings.use_ssao)
                     .set_texture(
                         &self.ambient_light_shader.ambient_texture,
                         &gbuffer_ambient_map,
+                    )
+                    .set_bool(&self.ambient_light_shader.ibl_enabled, ibl.is_some())
+                    .set_texture(&self.ambient_light_shader.depth_texture, &gbuffer_depth_map)
+                    .set_texture(
+                        &self.ambient_light_shader.normal_texture,
+                        &gbuffer_normal_map,
+                    )
+                    .set_texture(
+                        &self.ambient_light_shader.material_texture,
+                        &gbuffer_material_map,
+                    )
+                    .set_texture(
+                        &self.ambient_light_shader.irradiance_map,
+                        ibl.as_ref()
+                            .map_or(&environment_dummy, |(irradiance_map, ..)| irradiance_map),
+                    )
+                    .set_texture(
+                        &self.ambient_light_shader.specular_map,
+                        ibl.as_ref()
+                            .map_or(&environment_dummy, |(_, specular_map, ..)| specular_map),
+                    )
+                    .set_f32(
+                        &self.ambient_light_shader.specular_map_max_level,
+                        ibl.as_ref().map_or(0.0, |(_, _, max_level, _)| *max_level),
+                    )
+                    .set_matrix3(
+                        &self.ambient_light_shader.environment_rotation,
+                        &environment_rotation,
+                    )
+                    .set_f32(
+                        &self.ambient_light_shader.environment_exposure,
+                        ibl.as_ref().map_or(1.0, |(.., exposure)| *exposure),
+                    )
+                    .set_matrix4(
+                        &self.ambient_light_shader.inv_view_proj_matrix,
+                        &inv_view_projection,
+                    )
+                    .set_vector3(
+                        &self.ambient_light_shader.camera_position,
+                        &camera_global_position,
                     );
             },
         )?;
 
+        volumetric_light_renderer.clear(state);
+        let shaft_camera = ShaftCameraData {
+            depth: gbuffer_depth_map.clone(),
+            view_matrix: camera.view_matrix(),
+            view_projection,
+            inv_view_projection,
+            position: camera_global_position,
+            look: camera
+                .look_vector()
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::z),
+            z_far: camera.projection().z_far(),
+        };
+
         for (light_handle, light) in scene.graph.pair_iter() {
             if !light.global_visibility() || !light.is_globally_enabled() {
                 continue;
@@ -414,6 +527,9 @@ impl DeferredLightRenderer {
                             && settings.point_shadows_enabled,
                         settings.point_shadows_fade_out_range,
                     )
+                } else if let Some(area_light) = light.cast::<AreaLight>() {
+                    // Area lights does not cast shadows.
+                    (area_light.radius(), 0.0, false, 0.0)
                 } else if let Some(directional) = light.cast::<DirectionalLight>() {
                     (
                         f32::MAX,
@@ -500,6 +616,7 @@ impl DeferredLightRenderer {
                         black_dummy.clone(),
                         volume_dummy.clone(),
                         matrix_storage,
+                        uniform_buffer_ring,
                     )?;
 
                     light_stats.spot_shadow_maps_rendered += 1;
@@ -520,6 +637,7 @@ impl DeferredLightRenderer {
                                 black_dummy: black_dummy.clone(),
                                 volume_dummy: volume_dummy.clone(),
                                 matrix_storage,
+                                uniform_buffer_ring,
                             })?;
 
                     light_stats.point_shadow_maps_rendered += 1;
@@ -538,6 +656,7 @@ impl DeferredLightRenderer {
                         black_dummy: black_dummy.clone(),
                         volume_dummy: volume_dummy.clone(),
                         matrix_storage,
+                        uniform_buffer_ring,
                     })?;
 
                     light_stats.csm_rendered += 1;
@@ -630,6 +749,7 @@ impl DeferredLightRenderer {
             };
 
             let quad = &self.quad;
+            let light_link_bit = light_links.light_bit(light_handle);
 
             pass_stats += if let Some(spot_light) = light.cast::<SpotLight>() {
                 let shader = &self.spot_light_shader;
@@ -686,6 +806,8 @@ impl DeferredLightRenderer {
                             .set_texture(&shader.color_sampler, &gbuffer_diffuse_map)
                             .set_texture(&shader.normal_sampler, &gbuffer_normal_map)
                             .set_texture(&shader.material_sampler, &gbuffer_material_map)
+                            .set_texture(&shader.light_link_texture, &gbuffer_light_link_map)
+                            .set_u32(&shader.light_link_bit, light_link_bit)
                             .set_texture(
                                 &shader.spot_shadow_texture,
                                 &self.spot_shadow_map_renderer.cascade_texture(cascade_index),
@@ -734,6 +856,8 @@ impl DeferredLightRenderer {
                             .set_texture(&shader.color_sampler, &gbuffer_diffuse_map)
                             .set_texture(&shader.normal_sampler, &gbuffer_normal_map)
                             .set_texture(&shader.material_sampler, &gbuffer_material_map)
+                            .set_texture(&shader.light_link_texture, &gbuffer_light_link_map)
+                            .set_u32(&shader.light_link_bit, light_link_bit)
                             .set_texture(
                                 &shader.point_shadow_texture,
                                 &self
@@ -743,6 +867,58 @@ impl DeferredLightRenderer {
                             .set_f32(&shader.shadow_alpha, shadows_alpha);
                     },
                 )?
+            } else if let Some(area_light) = light.cast::<AreaLight>() {
+                let shader = &self.area_light_shader;
+
+                light_stats.area_lights_rendered += 1;
+
+                let vertices = area_light.world_vertices();
+                let basis = area_light.global_transform().basis();
+                let half_size = area_light.size() * 0.5;
+                let half_axis_x = basis.column(0) * half_size.x;
+                let half_axis_z = basis.column(2) * half_size.y;
+
+                frame_buffer.draw(
+                    quad,
+                    state,
+                    viewport,
+                    &shader.program,
+                    &draw_params,
+                    ElementRange::Full,
+                    |mut program_binding| {
+                        program_binding
+                            .set_vector3_slice(&shader.light_vertices, &vertices)
+                            .set_i32(&shader.light_vertex_count, vertices.len() as i32)
+                            .set_vector3(&shader.light_position, &light_position)
+                            .set_vector3(&shader.light_direction, &area_light.emit_direction())
+                            .set_vector3(&shader.light_half_axis_x, &half_axis_x)
+                            .set_vector3(&shader.light_half_axis_z, &half_axis_z)
+                            .set_bool(
+                                &shader.light_is_disk,
+                                area_light.shape() == AreaLightShape::Disk,
+                            )
+                            .set_bool(&shader.two_sided, area_light.is_two_sided())
+                            .set_f32(&shader.light_radius, light_radius)
+                            .set_matrix4(&shader.inv_view_proj_matrix, &inv_view_projection)
+                            .set_linear_color(
+                                &shader.light_color,
+                                &area_light.base_light_ref().color(),
+                            )
+                            .set_f32(
+                                &shader.light_intensity,
+                                area_light.base_light_ref().intensity(),
+                            )
+                            .set_bool(&shader.use_ltc, settings.use_ltc_area_lights)
+                            .set_matrix4(&shader.wvp_matrix, &frame_matrix)
+                            .set_vector3(&shader.camera_position, &camera_global_position)
+                            .set_texture(&shader.depth_sampler, &gbuffer_depth_map)
+                            .set_texture(&shader.color_sampler, &gbuffer_diffuse_map)
+                            .set_texture(&shader.normal_sampler, &gbuffer_normal_map)
+                            .set_texture(&shader.material_sampler, &gbuffer_material_map)
+                            .set_texture(&shader.light_link_texture, &gbuffer_light_link_map)
+                            .set_u32(&shader.light_link_bit, light_link_bit);
+                    },
+                )?
             } else if let Some(directional) = light.cast::<DirectionalLight>() {
                 let shader = &self.directional_light_shader;
 
@@ -796,6 +972,8 @@ impl DeferredLightRenderer {
                             .set_texture(&shader.color_sampler, &gbuffer_diffuse_map)
                             .set_texture(&shader.normal_sampler, &gbuffer_normal_map)
                             .set_texture(&shader.material_sampler, &gbuffer_material_map)
+                            .set_texture(&shader.light_link_texture, &gbuffer_light_link_map)
+                            .set_u32(&shader.light_link_bit, light_link_bit)
                             .set_matrix4_array(&shader.light_view_proj_matrices, &matrices)
                             .set_texture(
                                 &shader.shadow_cascade0,
@@ -822,6 +1000,51 @@ impl DeferredLightRenderer {
             };
 
             if settings.light_scatter_enabled {
+                let quality = settings.volumetric_light_quality;
+
+                if let Some(spot) = light
+                    .cast::<SpotLight>()
+                    .filter(|spot| spot.base_light_ref().is_scatter_enabled())
+                    .filter(|_| quality.ray_march_steps().is_some())
+                {
+                    let shadow_map = shadows_enabled.then(|| {
+                        (
+                            self.spot_shadow_map_renderer.cascade_texture(cascade_index),
+                            light_view_projection,
+                        )
+                    });
+                    pass_stats += volumetric_light_renderer.render_spot_shaft(
+                        state,
+                        &self.quad,
+                        &shaft_camera,
+                        quality,
+                        spot,
+                        light_radius,
+                        shadow_map,
+                    )?;
+                    light_stats.light_shafts_rendered += 1;
+                    continue;
+                }
+
+                if let Some(directional) = light.cast::<DirectionalLight>() {
+                    if directional.is_shafts_enabled()
+                        && directional.base_light_ref().is_scatter_enabled()
+                        && quality != VolumetricLightQuality::Off
+                    {
+                        let cascades = shadows_enabled.then(|| self.csm_renderer.cascades());
+                        pass_stats += volumetric_light_renderer.render_directional_shafts(
+                            state,
+                            &self.quad,
+                            &shaft_camera,
+                            quality,
+                            directional,
+                            cascades,
+                        )?;
+                        light_stats.light_shafts_rendered += 1;
+                    }
+                    continue;
+                }
+
                 pass_stats += self.light_volume.render_volume(
                     state,
                     light,
@@ -12,6 +12,8 @@ pub struct PointLightShader {
     pub color_sampler: UniformLocation,
     pub normal_sampler: UniformLocation,
     pub material_sampler: UniformLocation,
+    pub light_link_texture: UniformLocation,
+    pub light_link_bit: UniformLocation,
     pub point_shadow_texture: UniformLocation,
     pub shadows_enabled: UniformLocation,
     pub soft_shadows: UniformLocation,
@@ -42,6 +44,10 @@ impl PointLightShader {
                 .uniform_location(state, &ImmutableString::new("normalTexture"))?,
             material_sampler: program
                 .uniform_location(state, &ImmutableString::new("materialTexture"))?,
+            light_link_texture: program
+                .uniform_location(state, &ImmutableString::new("lightLinkTexture"))?,
+            light_link_bit: program
+                .uniform_location(state, &ImmutableString::new("lightLinkBit"))?,
             point_shadow_texture: program
                 .uniform_location(state, &ImmutableString::new("pointShadowTexture"))?,
             shadows_enabled: program
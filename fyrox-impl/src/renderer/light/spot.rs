@@ -12,6 +12,8 @@ pub struct SpotLightShader {
     pub color_sampler: UniformLocation,
     pub normal_sampler: UniformLocation,
     pub material_sampler: UniformLocation,
+    pub light_link_texture: UniformLocation,
+    pub light_link_bit: UniformLocation,
     pub spot_shadow_texture: UniformLocation,
     pub cookie_enabled: UniformLocation,
     pub cookie_texture: UniformLocation,
@@ -49,6 +51,10 @@ impl SpotLightShader {
                 .uniform_location(state, &ImmutableString::new("normalTexture"))?,
             material_sampler: program
                 .uniform_location(state, &ImmutableString::new("materialTexture"))?,
+            light_link_texture: program
+                .uniform_location(state, &ImmutableString::new("lightLinkTexture"))?,
+            light_link_bit: program
+                .uniform_location(state, &ImmutableString::new("lightLinkBit"))?,
             spot_shadow_texture: program
                 .uniform_location(state, &ImmutableString::new("spotShadowTexture"))?,
             cookie_enabled: program
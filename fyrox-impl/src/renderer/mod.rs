@@ -16,6 +16,7 @@ pub mod framework;
 pub mod bundle;
 pub mod cache;
 pub mod debug_renderer;
+pub mod instance;
 pub mod storage;
 pub mod ui_renderer;
 
@@ -27,10 +28,12 @@ mod gbuffer;
 mod hdr;
 mod light;
 mod light_volume;
+mod precipitation;
 mod shadow;
 mod skybox_shader;
 mod ssao;
 mod stats;
+mod volumetric;
 
 use crate::renderer::cache::texture::TextureRenderData;
 
@@ -49,6 +52,7 @@ use crate::{
         scope_profile,
         sstorage::ImmutableString,
     },
+    graph::SceneGraph,
     gui::draw::DrawingContext,
     material::{
         shader::{SamplerFallback, Shader, ShaderResource, ShaderResourceExtension},
@@ -56,7 +60,10 @@ use crate::{
     },
     renderer::{
         bloom::BloomRenderer,
-        bundle::{ObserverInfo, PersistentIdentifier, RenderDataBundleStorage},
+        bundle::{
+            ObserverInfo, PersistentIdentifier, RenderDataBundleStorage,
+            VertexAnimationInstanceData,
+        },
         cache::{geometry::GeometryCache, shader::ShaderCache, texture::TextureCache},
         debug_renderer::DebugRenderer,
         flat_shader::FlatShader,
@@ -72,19 +79,26 @@ use crate::{
                 Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
                 PixelKind, WrapMode,
             },
+            program_binary::ProgramBinaryCache,
             state::{GlKind, PipelineState, PolygonFace, PolygonFillMode},
+            uniform::{UniformAllocation, UniformBufferRing},
         },
         fxaa::FxaaRenderer,
         gbuffer::{GBuffer, GBufferRenderContext},
         hdr::HighDynamicRangeRenderer,
-        light::{DeferredLightRenderer, DeferredRendererContext},
+        instance::INSTANCE_DATA_BINDING,
+        light::{link::LightLinks, DeferredLightRenderer, DeferredRendererContext},
+        precipitation::{PrecipitationOcclusionContext, PrecipitationOcclusionRenderer},
         storage::MatrixStorageCache,
         ui_renderer::{UiRenderContext, UiRenderer},
+        volumetric::VolumetricLightRenderer,
     },
     resource::texture::{Texture, TextureKind, TextureResource},
-    scene::{camera::Camera, mesh::surface::SurfaceData, Scene, SceneContainer},
+    scene::{
+        camera::Camera, graph::Graph, mesh::surface::SurfaceData, node::Node, Scene, SceneContainer,
+    },
 };
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 use fyrox_core::algebra::Vector4;
 use fyrox_core::uuid_provider;
 use glow::HasContext;
@@ -147,6 +161,121 @@ pub enum ShadowMapPrecision {
 
 uuid_provider!(ShadowMapPrecision = "f9b2755b-248e-46ba-bcab-473eac1acdb8");
 
+/// Defines how light scattering (light shafts) of spot and directional lights is rendered. Point
+/// lights always use analytic (unshadowed) scattering. Scattering could be disabled completely
+/// using [`QualitySettings::light_scatter_enabled`].
+#[derive(
+    Copy,
+    Clone,
+    Hash,
+    PartialOrd,
+    PartialEq,
+    Eq,
+    Ord,
+    Debug,
+    Serialize,
+    Deserialize,
+    Default,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum VolumetricLightQuality {
+    /// Spot lights use analytic scattering that ignores shadows, directional lights do not
+    /// scatter at all.
+    Off,
+    /// Spot lights use analytic scattering, directional lights use screen-space god rays. This
+    /// option is very cheap, but god rays are visible only when the light source is on screen.
+    ScreenSpace,
+    /// Light volumes are ray-marched through shadow maps using 16 steps per pixel.
+    Low,
+    /// Light volumes are ray-marched through shadow maps using 32 steps per pixel.
+    #[default]
+    Medium,
+    /// Light volumes are ray-marched through shadow maps using 64 steps per pixel.
+    High,
+}
+
+uuid_provider!(VolumetricLightQuality = "bcaaae3e-5bc4-494a-ba45-071e4b8a2c00");
+
+impl VolumetricLightQuality {
+    /// Returns amount of ray marching steps per pixel or `None` if ray marching is disabled.
+    pub fn ray_march_steps(self) -> Option<u32> {
+        match self {
+            Self::Off | Self::ScreenSpace => None,
+            Self::Low => Some(16),
+            Self::Medium => Some(32),
+            Self::High => Some(64),
+        }
+    }
+}
+
+/// Defines quality of screen-space ambient occlusion. Ambient occlusion is computed using
+/// ground-truth ambient occlusion (GTAO) - for every pixel the horizons of the surrounding depth are
+/// searched in a few directions (slices) around the pixel. Each quality tier defines the amount of
+/// slices and depth samples per slice. Ambient occlusion could be disabled completely using
+/// [`QualitySettings::use_ssao`].
+#[derive(
+    Copy,
+    Clone,
+    Hash,
+    PartialOrd,
+    PartialEq,
+    Eq,
+    Ord,
+    Debug,
+    Serialize,
+    Deserialize,
+    Default,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum AmbientOcclusionQuality {
+    /// One slice with 4 samples per side, without temporal filtering. Ambient occlusion is noisy,
+    /// but very cheap.
+    Low,
+    /// Two slices with 4 samples per side, the result is accumulated over multiple frames.
+    #[default]
+    Medium,
+    /// Three slices with 6 samples per side, the result is accumulated over multiple frames.
+    High,
+    /// Four slices with 8 samples per side, the result is accumulated over multiple frames.
+    Ultra,
+}
+
+uuid_provider!(AmbientOcclusionQuality = "2e4bd2d3-5a4a-4a3c-9d0c-6e8f0c0b7d51");
+
+impl AmbientOcclusionQuality {
+    /// Returns amount of directions (slices), in which horizons are searched for every pixel.
+    pub fn slice_count(self) -> u32 {
+        match self {
+            Self::Low => 1,
+            Self::Medium => 2,
+            Self::High => 3,
+            Self::Ultra => 4,
+        }
+    }
+
+    /// Returns amount of depth samples taken on each side of a slice.
+    pub fn steps_per_side(self) -> u32 {
+        match self {
+            Self::Low | Self::Medium => 4,
+            Self::High => 6,
+            Self::Ultra => 8,
+        }
+    }
+
+    /// Returns `true` if ambient occlusion is accumulated over multiple frames. Temporal filtering
+    /// effectively multiplies the amount of samples, because the directions of slices are rotated
+    /// every frame.
+    pub fn temporal_filtering(self) -> bool {
+        self != Self::Low
+    }
+}
+
 /// Cascaded-shadow maps settings.
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Reflect, Eq)]
 pub struct CsmSettings {
@@ -215,16 +344,32 @@ pub struct QualitySettings {
     /// Cascaded-shadow maps settings.
     pub csm_settings: CsmSettings,
 
-    /// Whether to use screen space ambient occlusion or not.
+    /// Whether to use screen space ambient occlusion or not. Besides darkening of ambient lighting,
+    /// ambient occlusion also provides bent normals, that are used for specular occlusion of
+    /// image-based lighting.
     pub use_ssao: bool,
-    /// Radius of sampling hemisphere used in SSAO, it defines much ambient
-    /// occlusion will be in your scene.
+    /// Radius (in world units) of the area around each pixel, that is checked for occluders. It
+    /// defines how much ambient occlusion will be in your scene.
     pub ssao_radius: f32,
+    /// Quality of screen space ambient occlusion. See [`AmbientOcclusionQuality`] docs for more
+    /// info.
+    #[serde(default)]
+    pub ssao_quality: AmbientOcclusionQuality,
 
     /// Global switch to enable or disable light scattering. Each light can have
     /// its own scatter switch, but this one is able to globally disable scatter.
     pub light_scatter_enabled: bool,
 
+    /// Defines how light shafts of spot and directional lights are rendered. See
+    /// [`VolumetricLightQuality`] docs for more info.
+    #[serde(default)]
+    pub volumetric_light_quality: VolumetricLightQuality,
+
+    /// Whether to use linearly transformed cosines (LTC) to calculate lighting from area lights
+    /// or not. When disabled, a cheaper "representative point" approximation is used.
+    #[serde(default = "default_use_ltc_area_lights")]
+    pub use_ltc_area_lights: bool,
+
     /// Whether to use Fast Approximate AntiAliasing or not.
     pub fxaa: bool,
 
@@ -235,6 +380,10 @@ pub struct QualitySettings {
     pub use_bloom: bool,
 }
 
+fn default_use_ltc_area_lights() -> bool {
+    true
+}
+
 impl Default for QualitySettings {
     fn default() -> Self {
         Self::high()
@@ -259,8 +408,12 @@ impl QualitySettings {
 
             use_ssao: true,
             ssao_radius: 0.5,
+            ssao_quality: AmbientOcclusionQuality::Ultra,
 
             light_scatter_enabled: true,
+            volumetric_light_quality: VolumetricLightQuality::High,
+
+            use_ltc_area_lights: true,
 
             point_shadow_map_precision: ShadowMapPrecision::Full,
             spot_shadow_map_precision: ShadowMapPrecision::Full,
@@ -292,8 +445,12 @@ impl QualitySettings {
 
             use_ssao: true,
             ssao_radius: 0.5,
+            ssao_quality: AmbientOcclusionQuality::High,
 
             light_scatter_enabled: true,
+            volumetric_light_quality: VolumetricLightQuality::Medium,
+
+            use_ltc_area_lights: true,
 
             point_shadow_map_precision: ShadowMapPrecision::Full,
             spot_shadow_map_precision: ShadowMapPrecision::Full,
@@ -330,8 +487,12 @@ impl QualitySettings {
 
             use_ssao: true,
             ssao_radius: 0.5,
+            ssao_quality: AmbientOcclusionQuality::Medium,
 
             light_scatter_enabled: false,
+            volumetric_light_quality: VolumetricLightQuality::Low,
+
+            use_ltc_area_lights: true,
 
             point_shadow_map_precision: ShadowMapPrecision::Half,
             spot_shadow_map_precision: ShadowMapPrecision::Half,
@@ -368,8 +529,12 @@ impl QualitySettings {
 
             use_ssao: false,
             ssao_radius: 0.5,
+            ssao_quality: AmbientOcclusionQuality::Low,
 
             light_scatter_enabled: false,
+            volumetric_light_quality: VolumetricLightQuality::Off,
+
+            use_ltc_area_lights: false,
 
             point_shadow_map_precision: ShadowMapPrecision::Half,
             spot_shadow_map_precision: ShadowMapPrecision::Half,
@@ -464,6 +629,13 @@ pub struct AssociatedSceneData {
     /// bleeding effect (glow effect).
     pub bloom_renderer: BloomRenderer,
 
+    /// Accumulates volumetric light shafts of the scene.
+    pub volumetric_light_renderer: VolumetricLightRenderer,
+
+    /// A copy of the high dynamic range frame, that is made right before the forward pass. It is
+    /// used by forward materials for screen-space refraction (water, glass, etc.).
+    pub scene_color_framebuffer: FrameBuffer,
+
     /// Rendering statistics for a scene.
     pub statistics: SceneStatistics,
 }
@@ -484,6 +656,7 @@ impl AssociatedSceneData {
             .bind_mut(state, 0)
             .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
             .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+        depth_stencil_texture.set_name(state, "Scene Depth Stencil");
 
         let depth_stencil = Rc::new(RefCell::new(depth_stencil_texture));
 
@@ -556,17 +729,65 @@ impl AssociatedSceneData {
             }],
         )?;
 
+        let mut scene_color_texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle { width, height },
+            PixelKind::RGBA16F,
+            MinificationFilter::Linear,
+            MagnificationFilter::Linear,
+            1,
+            None,
+        )?;
+        scene_color_texture
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+
+        let scene_color_framebuffer = FrameBuffer::new(
+            state,
+            None,
+            vec![Attachment {
+                kind: AttachmentKind::Color,
+                texture: Rc::new(RefCell::new(scene_color_texture)),
+            }],
+        )?;
+
+        hdr_scene_framebuffer.set_name(state, "Scene HDR");
+        ldr_scene_framebuffer.set_name(state, "Scene LDR");
+        ldr_temp_framebuffer.set_name(state, "Scene LDR Temp");
+        scene_color_framebuffer.set_name(state, "Scene Color Copy");
+
         Ok(Self {
             gbuffer: GBuffer::new(state, width, height)?,
             hdr_renderer: HighDynamicRangeRenderer::new(state)?,
             bloom_renderer: BloomRenderer::new(state, width, height)?,
+            volumetric_light_renderer: VolumetricLightRenderer::new(state, width, height)?,
             hdr_scene_framebuffer,
             ldr_scene_framebuffer,
             ldr_temp_framebuffer,
+            scene_color_framebuffer,
             statistics: Default::default(),
         })
     }
 
+    fn copy_scene_color(&mut self, state: &PipelineState) {
+        state.blit_framebuffer(
+            self.hdr_scene_framebuffer.id(),
+            self.scene_color_framebuffer.id(),
+            0,
+            0,
+            self.gbuffer.width,
+            self.gbuffer.height,
+            0,
+            0,
+            self.gbuffer.width,
+            self.gbuffer.height,
+            true,
+            false,
+            false,
+        );
+    }
+
     fn copy_depth_stencil_to_scene_framebuffer(&mut self, state: &PipelineState) {
         state.blit_framebuffer(
             self.gbuffer.framebuffer().id(),
@@ -592,6 +813,13 @@ impl AssociatedSceneData {
             .clone()
     }
 
+    /// Returns a copy of high-dynamic range frame, that was made right before the forward pass.
+    pub fn scene_color_texture(&self) -> Rc<RefCell<GpuTexture>> {
+        self.scene_color_framebuffer.color_attachments()[0]
+            .texture
+            .clone()
+    }
+
     /// Returns low-dynamic range frame buffer texture (final frame).
     pub fn ldr_scene_frame_texture(&self) -> Rc<RefCell<GpuTexture>> {
         self.ldr_scene_framebuffer.color_attachments()[0]
@@ -643,6 +871,7 @@ pub struct Renderer {
     volume_dummy: Rc<RefCell<GpuTexture>>,
     /// User interface renderer.
     pub ui_renderer: UiRenderer,
+    uniform_buffer_ring: UniformBufferRing,
     statistics: Statistics,
     quad: GeometryBuffer,
     frame_size: (u32, u32),
@@ -657,6 +886,7 @@ pub struct Renderer {
     shader_cache: ShaderCache,
     geometry_cache: GeometryCache,
     forward_renderer: ForwardRenderer,
+    precipitation_occlusion_renderer: PrecipitationOcclusionRenderer,
     fxaa_renderer: FxaaRenderer,
     texture_event_receiver: Receiver<ResourceEvent>,
     shader_event_receiver: Receiver<ResourceEvent>,
@@ -803,6 +1033,10 @@ pub struct SceneRenderPassContext<'a, 'b> {
 
     /// Matrix storage is container of procedural textures that stores matrices for bones.
     pub matrix_storage: &'a mut MatrixStorageCache,
+
+    /// Uniform buffer ring, that is shared across render passes. See [`UniformBufferRing`] docs
+    /// for more info.
+    pub uniform_buffer_ring: &'a mut UniformBufferRing,
 }
 
 /// A trait for custom scene rendering pass. It could be used to add your own rendering techniques.
@@ -831,6 +1065,54 @@ pub trait SceneRenderPass {
     fn source_type_id(&self) -> TypeId;
 }
 
+/// Returns an iterator over every enabled camera of the given graph and their handles.
+fn enabled_cameras(graph: &Graph) -> impl Iterator<Item = (Handle<Node>, &Camera)> {
+    graph
+        .pair_iter()
+        .filter(|(_, node)| node.is_globally_enabled())
+        .filter_map(|(handle, node)| {
+            node.cast::<Camera>()
+                .filter(|c| c.is_enabled())
+                .map(|camera| (handle, camera))
+        })
+}
+
+fn collect_camera_render_data(graph: &Graph, camera: &Camera) -> RenderDataBundleStorage {
+    RenderDataBundleStorage::from_graph(
+        graph,
+        ObserverInfo {
+            observer_position: camera.global_position(),
+            z_near: camera.projection().z_near(),
+            z_far: camera.projection().z_far(),
+            view_matrix: camera.view_matrix(),
+            projection_matrix: camera.projection_matrix(),
+            layer_mask: camera.layer_mask(),
+        },
+        GBUFFER_PASS_NAME.clone(),
+    )
+}
+
+/// Render data of every enabled camera of a scene, that was collected ahead of rendering. Unlike
+/// rendering, the collection does not need the graphics context, so it could be done on a worker
+/// thread while the main thread is busy with something else (see [`crate::engine::Engine::render`]).
+#[derive(Default)]
+pub(crate) struct SceneRenderData {
+    camera_bundles: FxHashMap<Handle<Node>, RenderDataBundleStorage>,
+}
+
+impl SceneRenderData {
+    /// Collects render data of every enabled camera of the given scene.
+    pub(crate) fn collect(scene: &Scene) -> Self {
+        scope_profile!();
+
+        Self {
+            camera_bundles: enabled_cameras(&scene.graph)
+                .map(|(handle, camera)| (handle, collect_camera_render_data(&scene.graph, camera)))
+                .collect(),
+        }
+    }
+}
+
 fn blit_pixels(
     state: &PipelineState,
     framebuffer: &mut FrameBuffer,
@@ -875,6 +1157,9 @@ fn blit_pixels(
     )
 }
 
+/// Maximum amount of 2D occluder segments, that could be used by 2D lights in a single frame.
+pub const MAX_OCCLUDER_SEGMENTS: usize = 64;
+
 #[allow(missing_docs)] // TODO
 pub struct LightData<const N: usize = 16> {
     pub count: usize,
@@ -882,6 +1167,12 @@ pub struct LightData<const N: usize = 16> {
     pub position: [Vector3<f32>; N],
     pub direction: [Vector3<f32>; N],
     pub parameters: [Vector2<f32>; N],
+    /// Parameters of 2D lights: x - height, y - falloff exponent (zero for 3D lights), z - shadow softness,
+    /// w - shadow casting flag.
+    pub parameters_2d: [Vector4<f32>; N],
+    pub occluder_count: usize,
+    /// World-space occluder segments: xy - beginning, zw - end.
+    pub occluder_segments: [Vector4<f32>; MAX_OCCLUDER_SEGMENTS],
 }
 
 impl<const N: usize> Default for LightData<N> {
@@ -892,10 +1183,22 @@ impl<const N: usize> Default for LightData<N> {
             position: [Default::default(); N],
             direction: [Default::default(); N],
             parameters: [Default::default(); N],
+            parameters_2d: [Default::default(); N],
+            occluder_count: 0,
+            occluder_segments: [Default::default(); MAX_OCCLUDER_SEGMENTS],
         }
     }
 }
 
+/// Top-down depth map of the scene around the camera, that is used by precipitation to collide with
+/// the geometry of the scene. See [`crate::scene::weather::Weather`] for more info.
+pub struct PrecipitationOcclusion {
+    /// Depth texture of the scene viewed from above.
+    pub depth: Rc<RefCell<GpuTexture>>,
+    /// World to clip space matrix, that was used to render the depth texture.
+    pub view_projection_matrix: Matrix4<f32>,
+}
+
 #[allow(missing_docs)] // TODO
 pub struct MaterialContext<'a, 'b, 'c> {
     pub material: &'a Material,
@@ -903,28 +1206,27 @@ pub struct MaterialContext<'a, 'b, 'c> {
     pub texture_cache: &'a mut TextureCache,
     pub matrix_storage: &'a mut MatrixStorageCache,
     pub persistent_identifier: PersistentIdentifier,
+    /// A ring with uploaded per-instance built-in uniforms. See [`instance`] module docs for more
+    /// info.
+    pub uniform_buffer_ring: &'a UniformBufferRing,
+    /// Allocation of per-instance built-in uniforms of the rendered instance.
+    pub instance_uniforms: &'a UniformAllocation,
 
     // Built-in uniforms.
-    pub world_matrix: &'a Matrix4<f32>,
-    pub view_projection_matrix: &'a Matrix4<f32>,
-    pub wvp_matrix: &'a Matrix4<f32>,
     pub bone_matrices: &'a [Matrix4<f32>],
-    pub use_skeletal_animation: bool,
-    pub use_pom: bool,
-    pub light_position: &'a Vector3<f32>,
     pub blend_shapes_storage: Option<&'a TextureResource>,
     pub blend_shapes_weights: &'a [f32],
+    pub vertex_animation: Option<&'a VertexAnimationInstanceData>,
     pub light_data: Option<&'a LightData>,
-    pub ambient_light: Color,
     // TODO: Add depth pre-pass to remove Option here. Current architecture allows only forward
     // renderer to have access to depth buffer that is available from G-Buffer.
     pub scene_depth: Option<&'a Rc<RefCell<GpuTexture>>>,
-
-    pub camera_position: &'a Vector3<f32>,
-    pub camera_up_vector: &'a Vector3<f32>,
-    pub camera_side_vector: &'a Vector3<f32>,
-    pub z_near: f32,
-    pub z_far: f32,
+    /// A copy of the scene frame, that was made right before the forward pass. Same as the scene
+    /// depth, it is available only for the forward renderer.
+    pub scene_color: Option<&'a Rc<RefCell<GpuTexture>>>,
+    /// Depth map for precipitation collisions. Same as the scene depth, it is available only for
+    /// the forward renderer.
+    pub precipitation_occlusion: Option<&'a PrecipitationOcclusion>,
 
     // Fallback samplers.
     pub normal_dummy: &'a Rc<RefCell<GpuTexture>>,
@@ -938,16 +1240,12 @@ pub fn apply_material(ctx: MaterialContext) {
     let built_in_uniforms = &ctx.program_binding.program.built_in_uniform_locations;
 
     // Apply values for built-in uniforms.
-    if let Some(location) = &built_in_uniforms[BuiltInUniform::WorldMatrix as usize] {
-        ctx.program_binding.set_matrix4(location, ctx.world_matrix);
-    }
-    if let Some(location) = &built_in_uniforms[BuiltInUniform::ViewProjectionMatrix as usize] {
-        ctx.program_binding
-            .set_matrix4(location, ctx.view_projection_matrix);
-    }
-    if let Some(location) = &built_in_uniforms[BuiltInUniform::WorldViewProjectionMatrix as usize] {
-        ctx.program_binding.set_matrix4(location, ctx.wvp_matrix);
-    }
+    ctx.program_binding.bind_uniform_allocation(
+        ctx.uniform_buffer_ring,
+        INSTANCE_DATA_BINDING,
+        ctx.instance_uniforms,
+    );
+
     if let Some(location) = &built_in_uniforms[BuiltInUniform::BoneMatrices as usize] {
         let active_sampler = ctx.program_binding.active_sampler();
 
@@ -963,42 +1261,16 @@ pub fn apply_material(ctx: MaterialContext) {
 
         ctx.program_binding.set_texture(location, storage.texture());
     }
-    if let Some(location) = &built_in_uniforms[BuiltInUniform::UseSkeletalAnimation as usize] {
-        ctx.program_binding
-            .set_bool(location, ctx.use_skeletal_animation);
-    }
-    if let Some(location) = &built_in_uniforms[BuiltInUniform::CameraPosition as usize] {
-        ctx.program_binding
-            .set_vector3(location, ctx.camera_position);
-    }
-
-    if let Some(location) = &built_in_uniforms[BuiltInUniform::CameraUpVector as usize] {
-        ctx.program_binding
-            .set_vector3(location, ctx.camera_up_vector);
-    }
-    if let Some(location) = &built_in_uniforms[BuiltInUniform::CameraSideVector as usize] {
-        ctx.program_binding
-            .set_vector3(location, ctx.camera_side_vector);
-    }
-    if let Some(location) = &built_in_uniforms[BuiltInUniform::ZNear as usize] {
-        ctx.program_binding.set_f32(location, ctx.z_near);
-    }
-    if let Some(location) = &built_in_uniforms[BuiltInUniform::ZFar as usize] {
-        ctx.program_binding.set_f32(location, ctx.z_far);
-    }
 
     if let Some(location) = &built_in_uniforms[BuiltInUniform::SceneDepth as usize] {
         if let Some(scene_depth) = ctx.scene_depth.as_ref() {
             ctx.program_binding.set_texture(location, scene_depth);
         }
     }
-
-    if let Some(location) = &built_in_uniforms[BuiltInUniform::UsePOM as usize] {
-        ctx.program_binding.set_bool(location, ctx.use_pom);
-    }
-    if let Some(location) = &built_in_uniforms[BuiltInUniform::LightPosition as usize] {
-        ctx.program_binding
-            .set_vector3(location, ctx.light_position);
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::SceneColor as usize] {
+        if let Some(scene_color) = ctx.scene_color.as_ref() {
+            ctx.program_binding.set_texture(location, scene_color);
+        }
     }
 
     if let Some(light_data) = ctx.light_data {
@@ -1026,11 +1298,21 @@ pub fn apply_material(ctx: MaterialContext) {
             ctx.program_binding
                 .set_vector2_slice(location, &light_data.parameters);
         }
-    }
 
-    if let Some(location) = &built_in_uniforms[BuiltInUniform::AmbientLight as usize] {
-        ctx.program_binding
-            .set_srgb_color(location, &ctx.ambient_light);
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::LightsParameters2D as usize] {
+            ctx.program_binding
+                .set_vector4_slice(location, &light_data.parameters_2d);
+        }
+
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::OccluderCount as usize] {
+            ctx.program_binding
+                .set_i32(location, light_data.occluder_count as i32);
+        }
+
+        if let Some(location) = &built_in_uniforms[BuiltInUniform::OccluderSegments as usize] {
+            ctx.program_binding
+                .set_vector4_slice(location, &light_data.occluder_segments);
+        }
     }
 
     if let Some(location) = &built_in_uniforms[BuiltInUniform::BlendShapesStorage as usize] {
@@ -1051,9 +1333,23 @@ pub fn apply_material(ctx: MaterialContext) {
         ctx.program_binding
             .set_f32_slice(location, ctx.blend_shapes_weights);
     }
-    if let Some(location) = &built_in_uniforms[BuiltInUniform::BlendShapesCount as usize] {
-        ctx.program_binding
-            .set_i32(location, ctx.blend_shapes_weights.len() as i32);
+
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::VertexAnimationTexture as usize] {
+        if let Some(texture) = ctx.vertex_animation.and_then(|vertex_animation| {
+            ctx.texture_cache
+                .get(ctx.program_binding.state, &vertex_animation.texture)
+        }) {
+            ctx.program_binding.set_texture(location, texture);
+        } else {
+            ctx.program_binding.set_texture(location, ctx.white_dummy);
+        }
+    }
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::PrecipitationOcclusionMap as usize] {
+        ctx.program_binding.set_texture(
+            location,
+            ctx.precipitation_occlusion
+                .map_or(ctx.white_dummy, |occlusion| &occlusion.depth),
+        );
     }
 
     // Apply material properties.
@@ -1143,6 +1439,7 @@ impl Renderer {
         frame_size: (u32, u32),
         resource_manager: &ResourceManager,
         gl_kind: GlKind,
+        program_binaries: Option<ProgramBinaryCache>,
     ) -> Result<Self, FrameworkError> {
         let settings = QualitySettings::default();
 
@@ -1160,7 +1457,7 @@ impl Renderer {
             .event_broadcaster
             .add(shader_event_sender);
 
-        let state = PipelineState::new(context, gl_kind);
+        let state = PipelineState::new(context, gl_kind, program_binaries);
 
         // Dump available GL extensions to the log, this will help debugging graphical issues.
         Log::info(format!(
@@ -1265,6 +1562,7 @@ impl Renderer {
                 &state,
             )?,
             ui_renderer: UiRenderer::new(&state)?,
+            uniform_buffer_ring: UniformBufferRing::new(&state)?,
             quality_settings: settings,
             debug_renderer: DebugRenderer::new(&state)?,
             scene_data_map: Default::default(),
@@ -1272,6 +1570,7 @@ impl Renderer {
             texture_cache: Default::default(),
             geometry_cache: Default::default(),
             forward_renderer: ForwardRenderer::new(),
+            precipitation_occlusion_renderer: PrecipitationOcclusionRenderer::new(&state)?,
             ui_frame_buffers: Default::default(),
             fxaa_renderer: FxaaRenderer::new(&state)?,
             statistics: Statistics::default(),
@@ -1315,6 +1614,48 @@ impl Renderer {
         self.statistics
     }
 
+    /// Returns usage statistics of the caches of GPU resources.
+    pub fn get_cache_statistics(&self) -> CacheStatisticsSet {
+        CacheStatisticsSet {
+            textures: self.texture_cache.statistics(),
+            geometry: self.geometry_cache.statistics(),
+            shaders: self.shader_cache.statistics(),
+        }
+    }
+
+    /// Compiles GPU programs for every render pass of every material used by the given scene. It
+    /// should be called while the scene is loading (for example, while a loading screen is shown),
+    /// so the first use of the materials during gameplay won't cause hitches. The progress callback
+    /// is called after every shader with the amount of processed shaders and the total amount of
+    /// shaders. Returns the amount of shaders, that are ready to use.
+    ///
+    /// The engine calls this method for every scene loaded by [`crate::engine::AsyncSceneLoader`],
+    /// so it is only needed for scenes, that are created or loaded in some other way. Linked
+    /// programs are also stored in the cache of program binaries (if the driver supports them),
+    /// so the next warm-up of the same shaders is much faster.
+    ///
+    /// Shaders of materials, that are still loading, are skipped and will be compiled on first use.
+    pub fn warm_up_shaders<F>(&mut self, scene: &Scene, progress: F) -> usize
+    where
+        F: FnMut(usize, usize),
+    {
+        scope_profile!();
+
+        let mut keys = FxHashSet::default();
+        let mut shaders = Vec::new();
+        for material in scene.graph.collect_used_materials() {
+            let mut material_state = material.state();
+            if let Some(material) = material_state.data() {
+                let shader = material.shader();
+                if keys.insert(shader.key()) {
+                    shaders.push(shader.clone());
+                }
+            }
+        }
+
+        self.shader_cache.warm_up(&self.state, &shaders, progress)
+    }
+
     /// Unloads texture from GPU memory.
     pub fn unload_texture(&mut self, texture: TextureResource) {
         self.texture_cache.unload(texture)
@@ -1330,6 +1671,35 @@ impl Renderer {
         &self.state
     }
 
+    /// Reads a color of a single pixel of the final frame (tone mapped and gamma corrected) of the
+    /// given scene. The color is in sRGB space. Position is in pixels and the origin is at the
+    /// top-left corner of the frame. Returns `None` if the scene wasn't rendered yet or if the
+    /// position is outside the frame.
+    ///
+    /// # Performance
+    ///
+    /// This method stalls the pipeline until the GPU finishes rendering, so it should be used
+    /// only for rare one-shot queries (such as color picking in the editor).
+    pub fn read_scene_pixel(&self, scene: Handle<Scene>, position: Vector2<u32>) -> Option<Color> {
+        let data = self.scene_data_map.get(&scene)?;
+        let GpuTextureKind::Rectangle { width, height } =
+            data.ldr_scene_frame_texture().borrow().kind()
+        else {
+            return None;
+        };
+        if position.x as usize >= width || position.y as usize >= height {
+            return None;
+        }
+
+        // OpenGL has the origin at the bottom-left corner.
+        let pixel = data.ldr_scene_framebuffer.read_pixel(
+            &self.state,
+            position.x as i32,
+            (height - 1 - position.y as usize) as i32,
+        );
+        Some(Color::from_rgba(pixel[0], pixel[1], pixel[2], pixel[3]))
+    }
+
     /// Sets new frame size. You should call the same method on [`crate::engine::Engine`]
     /// instead, which will update the size for the user interface and rendering context
     /// as well as this one.
@@ -1432,6 +1802,7 @@ impl Renderer {
             drawing_context,
             white_dummy: self.white_dummy.clone(),
             texture_cache: &mut self.texture_cache,
+            uniform_buffer_ring: &mut self.uniform_buffer_ring,
         })?;
 
         // Finally register texture in the cache so it will become available as texture in deferred/forward
@@ -1445,6 +1816,9 @@ impl Renderer {
                     .texture
                     .clone(),
                 modifications_counter: 0,
+                size_bytes: 0,
+                last_used_frame: 0,
+                pending_upload: None,
             },
             render_target.data_ref().cache_index.clone(),
             TimeToLive(f32::INFINITY),
@@ -1459,6 +1833,10 @@ impl Renderer {
         // requests, so this is some kind of work load balancer.
         const THROUGHPUT: usize = 5;
 
+        // Update the cache first, so the uploads below will be counted in the upload budget of the
+        // next frame.
+        self.texture_cache.update(dt);
+
         let mut uploaded = 0;
         while let Ok(event) = self.texture_event_receiver.try_recv() {
             if let ResourceEvent::Loaded(resource) | ResourceEvent::Reloaded(resource) = event {
@@ -1466,7 +1844,9 @@ impl Renderer {
                     match self.texture_cache.upload(&self.state, &texture) {
                         Ok(_) => {
                             uploaded += 1;
-                            if uploaded >= THROUGHPUT {
+                            if uploaded >= THROUGHPUT
+                                || self.texture_cache.is_upload_budget_exhausted()
+                            {
                                 break;
                             }
                         }
@@ -1480,8 +1860,6 @@ impl Renderer {
                 }
             }
         }
-
-        self.texture_cache.update(dt);
     }
 
     fn update_shader_cache(&mut self, dt: f32) {
@@ -1515,6 +1893,16 @@ impl Renderer {
         scene_handle: Handle<Scene>,
         scene: &Scene,
         dt: f32,
+    ) -> Result<&AssociatedSceneData, FrameworkError> {
+        self.render_scene_internal(scene_handle, scene, None, dt)
+    }
+
+    fn render_scene_internal(
+        &mut self,
+        scene_handle: Handle<Scene>,
+        scene: &Scene,
+        render_data: Option<SceneRenderData>,
+        dt: f32,
     ) -> Result<&AssociatedSceneData, FrameworkError> {
         let graph = &scene.graph;
 
@@ -1588,30 +1976,42 @@ impl Renderer {
                 TextureRenderData {
                     gpu_texture: scene_associated_data.ldr_scene_frame_texture(),
                     modifications_counter: 0,
+                    size_bytes: 0,
+                    last_used_frame: 0,
+                    pending_upload: None,
                 },
                 rt.data_ref().cache_index.clone(),
                 TimeToLive(f32::INFINITY),
             );
         }
 
-        for camera in graph
-            .linear_iter()
-            .filter(|&node| node.is_globally_enabled())
-            .filter_map(|node| node.cast::<Camera>().filter(|c| c.is_enabled()))
-        {
-            let viewport = camera.viewport_pixels(frame_size);
+        let mut cameras = enabled_cameras(graph).collect::<Vec<_>>();
+        // Cameras are rendered in the order of their render order. Cameras with the same render
+        // order are rendered from larger viewports to smaller ones, so smaller viewports
+        // (picture-in-picture, minimaps, etc.) are drawn on top of them. The sort is stable, so
+        // cameras with the same render order and viewport size are rendered in the order of the
+        // graph.
+        cameras.sort_by(|(_, a), (_, b)| {
+            let area = |c: &Camera| c.viewport().w() * c.viewport().h();
+            a.render_order()
+                .cmp(&b.render_order())
+                .then_with(|| area(b).total_cmp(&area(a)))
+        });
+
+        // Light links are shared across all cameras of the scene, per-node masks are cached.
+        let light_links = LightLinks::from_graph(graph);
+
+        let mut render_data = render_data.unwrap_or_default();
+        // The first camera of the stack of cameras, that is being rendered, and its render data.
+        let mut stack_base = None;
+        for (index, &(camera_handle, camera)) in cameras.iter().enumerate() {
+            // Render data could be collected ahead of time, otherwise it is collected right here.
+            let bundle_storage = render_data
+                .camera_bundles
+                .remove(&camera_handle)
+                .unwrap_or_else(|| collect_camera_render_data(graph, camera));
 
-            let bundle_storage = RenderDataBundleStorage::from_graph(
-                graph,
-                ObserverInfo {
-                    observer_position: camera.global_position(),
-                    z_near: camera.projection().z_near(),
-                    z_far: camera.projection().z_far(),
-                    view_matrix: camera.view_matrix(),
-                    projection_matrix: camera.projection_matrix(),
-                },
-                GBUFFER_PASS_NAME.clone(),
-            );
+            let viewport = camera.viewport_pixels(frame_size);
 
             state.set_polygon_fill_mode(
                 PolygonFace::FrontAndBack,
@@ -1634,21 +2034,30 @@ impl Renderer {
                     volume_dummy: self.volume_dummy.clone(),
                     graph,
                     matrix_storage: &mut self.matrix_storage,
+                    uniform_buffer_ring: &mut self.uniform_buffer_ring,
+                    light_links: &light_links,
                 })?;
 
             state.set_polygon_fill_mode(PolygonFace::FrontAndBack, PolygonFillMode::Fill);
 
             scene_associated_data.copy_depth_stencil_to_scene_framebuffer(state);
 
-            scene_associated_data.hdr_scene_framebuffer.clear(
-                state,
-                viewport,
+            // Cameras, that keep color, are drawn on top of the image of the previous cameras. The
+            // very first camera always clears color, otherwise the previous frame will be visible.
+            let clear_color = if camera.clear_flags().clears_color() || index == 0 {
                 Some(
                     scene
                         .rendering_options
                         .clear_color
                         .unwrap_or(self.backbuffer_clear_color),
-                ),
+                )
+            } else {
+                None
+            };
+            scene_associated_data.hdr_scene_framebuffer.clear(
+                state,
+                viewport,
+                clear_color,
                 None, // Keep depth, we've just copied valid data in it.
                 Some(0),
             );
@@ -1657,9 +2066,12 @@ impl Renderer {
                 self.deferred_light_renderer
                     .render(DeferredRendererContext {
                         state,
+                        scene_handle,
                         scene,
                         camera,
                         gbuffer: &mut scene_associated_data.gbuffer,
+                        volumetric_light_renderer: &mut scene_associated_data
+                            .volumetric_light_renderer,
                         white_dummy: self.white_dummy.clone(),
                         ambient_color: scene.rendering_options.ambient_lighting_color,
                         settings: &self.quality_settings,
@@ -1669,8 +2081,11 @@ impl Renderer {
                         shader_cache: &mut self.shader_cache,
                         normal_dummy: self.normal_dummy.clone(),
                         black_dummy: self.black_dummy.clone(),
+                        environment_dummy: self.environment_dummy.clone(),
                         volume_dummy: self.volume_dummy.clone(),
                         matrix_storage: &mut self.matrix_storage,
+                        uniform_buffer_ring: &mut self.uniform_buffer_ring,
+                        light_links: &light_links,
                     })?;
 
             scene_associated_data.statistics += light_stats;
@@ -1678,6 +2093,27 @@ impl Renderer {
 
             let depth = scene_associated_data.gbuffer.depth();
 
+            scene_associated_data.copy_scene_color(state);
+            let scene_color = scene_associated_data.scene_color_texture();
+
+            let (precipitation_occlusion, precipitation_stats) = self
+                .precipitation_occlusion_renderer
+                .render(PrecipitationOcclusionContext {
+                    state,
+                    graph,
+                    camera,
+                    geom_cache: &mut self.geometry_cache,
+                    shader_cache: &mut self.shader_cache,
+                    texture_cache: &mut self.texture_cache,
+                    normal_dummy: self.normal_dummy.clone(),
+                    white_dummy: self.white_dummy.clone(),
+                    black_dummy: self.black_dummy.clone(),
+                    volume_dummy: self.volume_dummy.clone(),
+                    matrix_storage: &mut self.matrix_storage,
+                    uniform_buffer_ring: &mut self.uniform_buffer_ring,
+                })?;
+            scene_associated_data.statistics += precipitation_stats;
+
             scene_associated_data.statistics +=
                 self.forward_renderer.render(ForwardRenderContext {
                     state,
@@ -1695,10 +2131,24 @@ impl Renderer {
                     black_dummy: self.black_dummy.clone(),
                     volume_dummy: self.volume_dummy.clone(),
                     scene_depth: depth,
+                    scene_color,
+                    precipitation_occlusion: precipitation_occlusion.as_ref(),
                     matrix_storage: &mut self.matrix_storage,
+                    uniform_buffer_ring: &mut self.uniform_buffer_ring,
                     ambient_light: scene.rendering_options.ambient_lighting_color,
                 })?;
 
+            // Light shafts are added after the forward pass, so transparent objects won't hide
+            // in-scattered light in front of them.
+            scene_associated_data.statistics +=
+                scene_associated_data.volumetric_light_renderer.composite(
+                    state,
+                    &self.quad,
+                    &self.flat_shader,
+                    &mut scene_associated_data.hdr_scene_framebuffer,
+                    viewport,
+                )?;
+
             for render_pass in self.scene_render_passes.iter() {
                 scene_associated_data.statistics +=
                     render_pass
@@ -1726,9 +2176,23 @@ impl Renderer {
                             framebuffer: &mut scene_associated_data.hdr_scene_framebuffer,
                             ui_renderer: &mut self.ui_renderer,
                             matrix_storage: &mut self.matrix_storage,
+                            uniform_buffer_ring: &mut self.uniform_buffer_ring,
                         })?;
             }
 
+            // Cameras, that keep color of the previous cameras, form a stack with them. The stack
+            // is post-processed at once, when its last camera is rendered, using the settings of
+            // its first camera.
+            let (camera, bundle_storage) = stack_base.take().unwrap_or((camera, bundle_storage));
+            if cameras
+                .get(index + 1)
+                .is_some_and(|(_, next)| !next.clear_flags().clears_color())
+            {
+                stack_base = Some((camera, bundle_storage));
+                continue;
+            }
+            let viewport = camera.viewport_pixels(frame_size);
+
             let quad = &self.quad;
 
             // Prepare glow map.
@@ -1780,6 +2244,7 @@ impl Renderer {
                 viewport,
                 &mut scene_associated_data.ldr_scene_framebuffer,
                 &scene.drawing_context,
+                &scene.debug_draw,
                 camera,
             )?;
 
@@ -1810,6 +2275,7 @@ impl Renderer {
                             framebuffer: &mut scene_associated_data.ldr_scene_framebuffer,
                             ui_renderer: &mut self.ui_renderer,
                             matrix_storage: &mut self.matrix_storage,
+                            uniform_buffer_ring: &mut self.uniform_buffer_ring,
                         })?;
             }
         }
@@ -1836,6 +2302,7 @@ impl Renderer {
     fn render_frame<'a>(
         &mut self,
         scenes: &SceneContainer,
+        mut render_data: FxHashMap<Handle<Scene>, SceneRenderData>,
         drawing_contexts: impl Iterator<Item = &'a DrawingContext>,
     ) -> Result<(), FrameworkError> {
         scope_profile!();
@@ -1871,7 +2338,8 @@ impl Renderer {
         let backbuffer_height = self.frame_size.1 as f32;
 
         for (scene_handle, scene) in scenes.pair_iter().filter(|(_, s)| *s.enabled) {
-            self.render_scene(scene_handle, scene, dt)?;
+            let scene_render_data = render_data.remove(&scene_handle);
+            self.render_scene_internal(scene_handle, scene, scene_render_data, dt)?;
         }
 
         self.pipeline_state()
@@ -1888,6 +2356,7 @@ impl Renderer {
                 drawing_context,
                 white_dummy: self.white_dummy.clone(),
                 texture_cache: &mut self.texture_cache,
+                uniform_buffer_ring: &mut self.uniform_buffer_ring,
             })?;
         }
 
@@ -1898,12 +2367,13 @@ impl Renderer {
     pub(crate) fn render_and_swap_buffers<'a>(
         &mut self,
         scenes: &SceneContainer,
+        render_data: FxHashMap<Handle<Scene>, SceneRenderData>,
         drawing_contexts: impl Iterator<Item = &'a DrawingContext>,
         surface: &Surface<WindowSurface>,
         context: &PossiblyCurrentContext,
         window: &Window,
     ) -> Result<(), FrameworkError> {
-        self.render_frame(scenes, drawing_contexts)?;
+        self.render_frame(scenes, render_data, drawing_contexts)?;
         self.statistics.end_frame();
         window.pre_present_notify();
         surface.swap_buffers(context)?;
@@ -1916,9 +2386,10 @@ impl Renderer {
     pub(crate) fn render_and_swap_buffers<'a>(
         &mut self,
         scenes: &SceneContainer,
+        render_data: FxHashMap<Handle<Scene>, SceneRenderData>,
         drawing_contexts: impl Iterator<Item = &'a DrawingContext>,
     ) -> Result<(), FrameworkError> {
-        self.render_frame(scenes, drawing_contexts)?;
+        self.render_frame(scenes, render_data, drawing_contexts)?;
         self.statistics.end_frame();
         self.statistics.finalize();
         self.statistics.pipeline = self.state.pipeline_statistics();
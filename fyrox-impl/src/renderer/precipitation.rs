@@ -0,0 +1,288 @@
+//! Renders a top-down depth map of the scene around the camera, that is used by precipitation to
+//! collide with the geometry of the scene. See [`Weather`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Vector3},
+        color::Color,
+        math::{Matrix4Ext, Rect},
+    },
+    renderer::{
+        apply_material,
+        bundle::{ObserverInfo, RenderDataBundleStorage},
+        cache::{geometry::GeometryCache, shader::ShaderCache, texture::TextureCache},
+        framework::{
+            error::FrameworkError,
+            framebuffer::{Attachment, AttachmentKind, DrawParameters, FrameBuffer},
+            gpu_texture::{
+                Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
+                PixelKind, WrapMode,
+            },
+            state::{ColorMask, PipelineState},
+            uniform::UniformBufferRing,
+        },
+        instance::{upload_bundle_instances, InstanceUniforms},
+        storage::MatrixStorageCache,
+        MaterialContext, PrecipitationOcclusion, RenderPassStatistics,
+        DIRECTIONAL_SHADOW_PASS_NAME,
+    },
+    scene::{camera::Camera, graph::Graph, weather::Weather},
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// Size of the occlusion map in texels.
+const OCCLUSION_MAP_SIZE: usize = 512;
+
+pub(crate) struct PrecipitationOcclusionRenderer {
+    framebuffer: FrameBuffer,
+}
+
+pub(crate) struct PrecipitationOcclusionContext<'a, 'c> {
+    pub state: &'a PipelineState,
+    pub graph: &'c Graph,
+    pub camera: &'c Camera,
+    pub geom_cache: &'a mut GeometryCache,
+    pub shader_cache: &'a mut ShaderCache,
+    pub texture_cache: &'a mut TextureCache,
+    pub normal_dummy: Rc<RefCell<GpuTexture>>,
+    pub white_dummy: Rc<RefCell<GpuTexture>>,
+    pub black_dummy: Rc<RefCell<GpuTexture>>,
+    pub volume_dummy: Rc<RefCell<GpuTexture>>,
+    pub matrix_storage: &'a mut MatrixStorageCache,
+    pub uniform_buffer_ring: &'a mut UniformBufferRing,
+}
+
+/// Calculates a view and a projection matrix, that look down at the area of the given size around
+/// the given point. The area is snapped to the texels of the map, so the map does not flicker when
+/// the camera moves.
+fn occlusion_view_projection(center: Vector3<f32>, extent: f32) -> (Matrix4<f32>, Matrix4<f32>) {
+    let texel_size = extent / OCCLUSION_MAP_SIZE as f32;
+    let center = Vector3::new(
+        (center.x / texel_size).floor() * texel_size,
+        center.y,
+        (center.z / texel_size).floor() * texel_size,
+    );
+    let eye = center + Vector3::new(0.0, extent, 0.0);
+    let view_matrix = Matrix4::look_at_rh(&Point3::from(eye), &Point3::from(center), &Vector3::z());
+    let half_extent = extent * 0.5;
+    let projection_matrix = Matrix4::new_orthographic(
+        -half_extent,
+        half_extent,
+        -half_extent,
+        half_extent,
+        0.0,
+        2.0 * extent,
+    );
+    (view_matrix, projection_matrix)
+}
+
+impl PrecipitationOcclusionRenderer {
+    pub fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let mut depth = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle {
+                width: OCCLUSION_MAP_SIZE,
+                height: OCCLUSION_MAP_SIZE,
+            },
+            PixelKind::D32F,
+            MinificationFilter::Nearest,
+            MagnificationFilter::Nearest,
+            1,
+            None,
+        )?;
+        depth
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge);
+
+        Ok(Self {
+            framebuffer: FrameBuffer::new(
+                state,
+                Some(Attachment {
+                    kind: AttachmentKind::Depth,
+                    texture: Rc::new(RefCell::new(depth)),
+                }),
+                Default::default(),
+            )?,
+        })
+    }
+
+    /// Renders the occlusion map, if there's a weather node in the graph with at least one
+    /// colliding precipitation layer.
+    pub(crate) fn render(
+        &mut self,
+        ctx: PrecipitationOcclusionContext,
+    ) -> Result<(Option<PrecipitationOcclusion>, RenderPassStatistics), FrameworkError> {
+        let mut stats = RenderPassStatistics::default();
+
+        let PrecipitationOcclusionContext {
+            state,
+            graph,
+            camera,
+            geom_cache,
+            shader_cache,
+            texture_cache,
+            normal_dummy,
+            white_dummy,
+            black_dummy,
+            volume_dummy,
+            matrix_storage,
+            uniform_buffer_ring,
+        } = ctx;
+
+        let Some(extent) = graph
+            .linear_iter()
+            .filter(|node| node.is_globally_enabled())
+            .filter_map(|node| node.cast::<Weather>())
+            .filter_map(|weather| weather.precipitation_collision_extent())
+            .max_by(|a, b| a.total_cmp(b))
+        else {
+            return Ok((None, stats));
+        };
+
+        let camera_position = camera.global_position();
+        let (view_matrix, projection_matrix) = occlusion_view_projection(camera_position, extent);
+        let view_projection_matrix = projection_matrix * view_matrix;
+        let z_far = 2.0 * extent;
+
+        let inv_view = view_matrix.try_inverse().unwrap_or_else(Matrix4::identity);
+        let camera_up = inv_view.up();
+        let camera_side = inv_view.side();
+
+        let viewport = Rect::new(0, 0, OCCLUSION_MAP_SIZE as i32, OCCLUSION_MAP_SIZE as i32);
+        self.framebuffer
+            .clear(state, viewport, None, Some(1.0), None);
+
+        let bundle_storage = RenderDataBundleStorage::from_graph(
+            graph,
+            ObserverInfo {
+                observer_position: inv_view.position(),
+                z_near: 0.0,
+                z_far,
+                view_matrix,
+                projection_matrix,
+                layer_mask: u32::MAX,
+            },
+            DIRECTIONAL_SHADOW_PASS_NAME.clone(),
+        );
+
+        let bundles = upload_bundle_instances(
+            state,
+            uniform_buffer_ring,
+            bundle_storage.bundles.iter(),
+            |bundle, instance| InstanceUniforms {
+                world_matrix: instance.world_transform,
+                view_projection_matrix,
+                use_skeletal_animation: bundle.is_skinned,
+                use_pom: false,
+                camera_position,
+                camera_up_vector: camera_up,
+                camera_side_vector: camera_side,
+                z_near: 0.0,
+                z_far,
+                light_position: Default::default(),
+                blend_shapes_count: instance.blend_shapes_weights.len(),
+                vertex_animation: instance.vertex_animation.as_ref(),
+                ambient_light: Color::WHITE,
+                light_link_mask: 0,
+                precipitation_occlusion_matrix: None,
+            },
+        );
+
+        for (bundle, instance_uniforms) in bundles.iter() {
+            let mut material_state = bundle.material.state();
+            let Some(material) = material_state.data() else {
+                continue;
+            };
+
+            let Some(geometry) = geom_cache.get(state, &bundle.data, bundle.time_to_live) else {
+                continue;
+            };
+
+            let blend_shapes_storage = bundle
+                .data
+                .data_ref()
+                .blend_shapes_container
+                .as_ref()
+                .and_then(|c| c.blend_shape_storage.clone());
+
+            let Some(render_pass) = shader_cache
+                .get(state, material.shader())
+                .and_then(|shader_set| shader_set.render_passes.get(&DIRECTIONAL_SHADOW_PASS_NAME))
+            else {
+                continue;
+            };
+
+            for (instance, instance_uniforms) in bundle.instances.iter().zip(instance_uniforms) {
+                stats += self.framebuffer.draw(
+                    geometry,
+                    state,
+                    viewport,
+                    &render_pass.program,
+                    &DrawParameters {
+                        // Both sides of the surfaces must block the precipitation, for example
+                        // single-sided roofs.
+                        cull_face: None,
+                        color_write: ColorMask::all(false),
+                        depth_write: true,
+                        stencil_test: None,
+                        depth_test: true,
+                        blend: None,
+                        stencil_op: Default::default(),
+                    },
+                    instance.element_range,
+                    |mut program_binding| {
+                        apply_material(MaterialContext {
+                            material,
+                            program_binding: &mut program_binding,
+                            texture_cache,
+                            matrix_storage,
+                            uniform_buffer_ring,
+                            instance_uniforms,
+                            bone_matrices: &instance.bone_matrices,
+                            blend_shapes_storage: blend_shapes_storage.as_ref(),
+                            blend_shapes_weights: &instance.blend_shapes_weights,
+                            vertex_animation: instance.vertex_animation.as_ref(),
+                            normal_dummy: &normal_dummy,
+                            white_dummy: &white_dummy,
+                            black_dummy: &black_dummy,
+                            volume_dummy: &volume_dummy,
+                            persistent_identifier: instance.persistent_identifier,
+                            light_data: None,
+                            scene_depth: None,
+                            scene_color: None,
+                            precipitation_occlusion: None,
+                        });
+                    },
+                )?;
+            }
+        }
+
+        Ok((
+            Some(PrecipitationOcclusion {
+                depth: self.framebuffer.depth_attachment().unwrap().texture.clone(),
+                view_projection_matrix,
+            }),
+            stats,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Point3, Vector3},
+        renderer::precipitation::occlusion_view_projection,
+    };
+
+    #[test]
+    fn test_occlusion_depth_increases_downwards() {
+        let (view, projection) = occlusion_view_projection(Vector3::new(3.0, 10.0, -4.0), 64.0);
+        let view_projection = projection * view;
+        let upper = view_projection.transform_point(&Point3::new(3.0, 20.0, -4.0));
+        let lower = view_projection.transform_point(&Point3::new(3.0, 0.0, -4.0));
+        assert!(upper.z > -1.0 && upper.z < 1.0);
+        assert!(lower.z > -1.0 && lower.z < 1.0);
+        assert!(lower.z > upper.z);
+    }
+}
@@ -1,18 +1,98 @@
 uniform sampler2D diffuseTexture;
+// Bent normal (world space) in RGB, visibility in A.
 uniform sampler2D aoSampler;
+uniform bool aoEnabled;
 uniform sampler2D ambientTexture;
 uniform vec4 ambientColor;
 
+// Image-based lighting.
+uniform bool iblEnabled;
+uniform sampler2D depthTexture;
+uniform sampler2D normalTexture;
+uniform sampler2D materialTexture;
+uniform samplerCube irradianceMap;
+uniform samplerCube specularMap;
+uniform float specularMapMaxLevel;
+uniform mat3 environmentRotation;
+uniform float environmentExposure;
+uniform mat4 invViewProj;
+uniform vec3 cameraPosition;
+
 out vec4 FragColor;
 in vec2 texCoord;
 
+// Returns solid angle of intersection of two spherical caps (normalized to [0; 1] range by the
+// solid angle of the hemisphere).
+float SphericalCapsIntersection(float cosCap1, float cosCap2, float cosDistance)
+{
+    float r1 = acos(cosCap1);
+    float r2 = acos(cosCap2);
+    float d = acos(cosDistance);
+
+    if (min(r1, r2) <= max(r1, r2) - d) {
+        // One cap is completely inside the other one.
+        return 1.0 - max(cosCap1, cosCap2);
+    } else if (r1 + r2 <= d) {
+        // No intersection.
+        return 0.0;
+    }
+
+    float delta = abs(r1 - r2);
+    float x = 1.0 - clamp((d - delta) / max(r1 + r2 - delta, 0.0001), 0.0, 1.0);
+    float area = x * x * (-2.0 * x + 3.0);
+    return area * (1.0 - max(cosCap1, cosCap2));
+}
+
+// Ground-truth specular occlusion: the ratio of the intersection of the visibility cone (around the
+// bent normal) with the specular cone (around the reflection vector) to the specular cone. See
+// "Practical Realtime Strategies for Accurate Indirect Occlusion" by Jorge Jimenez et al.
+float SpecularOcclusion(vec3 bentNormal, vec3 R, float visibility, float roughness)
+{
+    float cosVisibilityCone = sqrt(max(1.0 - visibility, 0.0));
+    float cosSpecularCone = exp2(-3.32193 * roughness * roughness);
+    float cosDistance = clamp(dot(bentNormal, R), -1.0, 1.0);
+    float intersection = SphericalCapsIntersection(cosVisibilityCone, cosSpecularCone, cosDistance);
+    return clamp(intersection / max(1.0 - cosSpecularCone, 0.0001), 0.0, 1.0);
+}
+
+vec3 ImageBasedLighting(vec3 albedo, vec3 N, vec3 bentNormal, float visibility)
+{
+    vec3 material = texture(materialTexture, texCoord).rgb;
+    float metallic = material.x;
+    float roughness = material.y;
+
+    vec3 fragmentPosition = S_UnProject(vec3(texCoord, texture(depthTexture, texCoord).r), invViewProj);
+    vec3 V = normalize(cameraPosition - fragmentPosition);
+    float NdotV = max(dot(N, V), 0.0001);
+    vec3 R = reflect(-V, N);
+
+    vec3 F0 = mix(vec3(0.04), albedo, metallic);
+    vec3 F = F0 + (max(vec3(1.0 - roughness), F0) - F0) * pow(1.0 - NdotV, 5.0);
+    vec3 kD = (1.0 - F) * (1.0 - metallic);
+
+    // Irradiance is fetched in the average unoccluded direction.
+    vec3 irradiance = texture(irradianceMap, environmentRotation * bentNormal).rgb;
+    vec3 prefiltered = textureLod(specularMap, environmentRotation * R, roughness * specularMapMaxLevel).rgb;
+    vec2 brdf = S_EnvBRDFApprox(roughness, NdotV);
+    float specularOcclusion = aoEnabled ? SpecularOcclusion(bentNormal, R, visibility, roughness) : 1.0;
+
+    return (kD * irradiance * albedo * visibility +
+        prefiltered * (F0 * brdf.x + brdf.y) * specularOcclusion) * environmentExposure;
+}
+
 void main()
 {
-    float ambientOcclusion = texture(aoSampler, texCoord).r;
+    vec4 ambientOcclusion = texture(aoSampler, texCoord);
+    float visibility = aoEnabled ? ambientOcclusion.a : 1.0;
     vec4 ambientPixel = texture(ambientTexture, texCoord);
-    FragColor = (ambientColor + ambientPixel) * S_SRGBToLinear(texture(diffuseTexture, texCoord));
-    FragColor.rgb *= ambientOcclusion;
+    vec4 diffuse = S_SRGBToLinear(texture(diffuseTexture, texCoord));
+    FragColor = (ambientColor + ambientPixel) * diffuse;
+    FragColor.rgb *= visibility;
+    if (iblEnabled) {
+        vec3 N = normalize(texture(normalTexture, texCoord).xyz * 2.0 - 1.0);
+        vec3 bentNormal = ambientOcclusion.rgb * 2.0 - 1.0;
+        bentNormal = aoEnabled && length(bentNormal) > 0.001 ? normalize(bentNormal) : N;
+        FragColor.rgb += ImageBasedLighting(diffuse.rgb, N, bentNormal, visibility);
+    }
     FragColor.a = ambientPixel.a;
-
-    // TODO: Implement IBL.
-}
\ No newline at end of file
+}
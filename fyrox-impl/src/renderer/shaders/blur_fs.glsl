@@ -1,22 +1,31 @@
-// Simple 4x4 box blur.
+// Depth-aware 4x4 blur of ambient occlusion. Samples, that lie on other surfaces (their depth
+// differs too much from the depth of the center pixel), are ignored to keep edges sharp.
 
 uniform sampler2D inputTexture;
+uniform sampler2D depthPyramid;
 
-out float FragColor;
+out vec4 FragColor;
 
 in vec2 texCoord;
 
 void main()
 {
     vec2 texelSize = 1.0 / vec2(textureSize(inputTexture, 0));
-    float result = 0.0;
+    float centerDepth = textureLod(depthPyramid, texCoord, 0.0).r;
+    float depthTolerance = 0.05 * centerDepth + 0.0001;
+    vec4 result = vec4(0.0);
+    float totalWeight = 0.0;
     for (int y = -2; y < 2; ++y)
     {
         for (int x = -2; x < 2; ++x)
         {
-            vec2 offset = vec2(float(x), float(y)) * texelSize;
-            result += texture(inputTexture, texCoord + offset).r;
+            vec2 sampleCoord = texCoord + vec2(float(x), float(y)) * texelSize;
+            float sampleDepth = textureLod(depthPyramid, sampleCoord, 0.0).r;
+            float weight = max(1.0 - abs(sampleDepth - centerDepth) / depthTolerance, 0.0);
+            result += texture(inputTexture, sampleCoord) * weight;
+            totalWeight += weight;
         }
     }
-    FragColor = result / 16.0;
-}
\ No newline at end of file
+    // The center pixel always has non-zero weight.
+    FragColor = result / totalWeight;
+}
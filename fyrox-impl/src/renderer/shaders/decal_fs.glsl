@@ -2,11 +2,15 @@ uniform sampler2D sceneDepth;
 uniform sampler2D diffuseTexture;
 uniform sampler2D normalTexture;
 uniform usampler2D decalMask;
-uniform mat4 invViewProj;
-uniform mat4 invWorldDecal;
-uniform vec2 resolution;
-uniform vec4 color;
-uniform uint layerIndex;
+
+layout(std140) uniform DecalData {
+    mat4 worldViewProjection;
+    mat4 invViewProj;
+    mat4 invWorldDecal;
+    vec4 color;
+    vec2 resolution;
+    highp uint layerIndex;
+};
 
 layout(location = 0) out vec4 outDiffuseMap;
 layout(location = 1) out vec4 outNormalMap;
@@ -24,8 +28,8 @@ void main()
 
     uvec4 maskIndex = texture(decalMask, texCoord);
 
-    // Masking.
-    if (maskIndex.r != layerIndex) {
+    // Masking. Lowest 8 bits is the decal layer, the rest is the light link mask.
+    if ((maskIndex.r & 0xFFu) != layerIndex) {
         discard;
     }
 
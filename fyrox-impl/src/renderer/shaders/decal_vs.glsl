@@ -1,6 +1,13 @@
 layout(location = 0) in vec3 vertexPosition;
 
-uniform mat4 worldViewProjection;
+layout(std140) uniform DecalData {
+    mat4 worldViewProjection;
+    mat4 invViewProj;
+    mat4 invWorldDecal;
+    vec4 color;
+    vec2 resolution;
+    highp uint layerIndex;
+};
 
 out vec4 clipSpacePosition;
 
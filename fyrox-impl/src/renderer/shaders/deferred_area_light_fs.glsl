@@ -0,0 +1,229 @@
+// Keep in sync with MAX_AREA_LIGHT_VERTICES.
+#define MAX_VERTICES 8
+
+uniform sampler2D depthTexture;
+uniform sampler2D colorTexture;
+uniform sampler2D normalTexture;
+uniform sampler2D materialTexture;
+uniform usampler2D lightLinkTexture;
+uniform uint lightLinkBit;
+
+uniform vec3 lightVertices[MAX_VERTICES];
+uniform int lightVertexCount;
+uniform vec3 lightPos;
+uniform vec3 lightDirection;
+uniform vec3 lightHalfAxisX;
+uniform vec3 lightHalfAxisZ;
+uniform bool lightIsDisk;
+uniform bool twoSided;
+uniform float lightRadius;
+uniform vec4 lightColor;
+uniform float lightIntensity;
+uniform bool useLtc;
+uniform mat4 invViewProj;
+uniform vec3 cameraPosition;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+// Integral of a clamped cosine over the spherical edge (v1, v2), uses the numerically stable
+// cubic fit of theta / sin(theta) from "Real-Time Area Lighting: a Journey from Research to
+// Production" by Stephen Hill and Eric Heitz.
+float IntegrateEdge(vec3 v1, vec3 v2)
+{
+    float x = dot(v1, v2);
+    float y = abs(x);
+
+    float a = 0.8543985 + (0.4965155 + 0.0145206 * y) * y;
+    float b = 3.4175940 + (4.1616724 + y) * y;
+    float v = a / b;
+
+    float thetaSinTheta = (x > 0.0) ? v : 0.5 * inversesqrt(max(1.0 - x * x, 1e-7)) - v;
+
+    return cross(v1, v2).z * thetaSinTheta;
+}
+
+// Integrates a clamped cosine distribution over the polygon of the light. Polygon vertices are
+// transformed into the space of the distribution by the given matrix and clipped by its horizon.
+float LTC_Evaluate(vec3 position, mat3 frame, mat3 minv)
+{
+    mat3 toDistribution = minv * transpose(frame);
+
+    vec3 points[MAX_VERTICES];
+    for (int i = 0; i < MAX_VERTICES; ++i) {
+        if (i >= lightVertexCount) {
+            break;
+        }
+        points[i] = toDistribution * (lightVertices[i] - position);
+    }
+
+    // Clip the polygon by the horizon (z = 0). Clipping a convex polygon by a plane adds
+    // one vertex at most.
+    vec3 clipped[MAX_VERTICES + 1];
+    int count = 0;
+    for (int i = 0; i < MAX_VERTICES; ++i) {
+        if (i >= lightVertexCount) {
+            break;
+        }
+        vec3 a = points[i];
+        vec3 b = points[(i + 1) % lightVertexCount];
+        if (a.z >= 0.0) {
+            clipped[count++] = a;
+        }
+        if ((a.z >= 0.0) != (b.z >= 0.0)) {
+            clipped[count++] = mix(a, b, a.z / (a.z - b.z));
+        }
+    }
+
+    if (count < 3) {
+        return 0.0;
+    }
+
+    float sum = 0.0;
+    for (int i = 0; i < MAX_VERTICES + 1; ++i) {
+        if (i >= count) {
+            break;
+        }
+        sum += IntegrateEdge(normalize(clipped[i]), normalize(clipped[(i + 1) % count]));
+    }
+
+    return abs(sum) / (2.0 * PI);
+}
+
+// Linearly transformed cosine, that approximates GGX lobe, is built from a clamped cosine scaled
+// by the roughness and rotated towards dominant direction of the lobe. Returns inverse matrix of
+// the transformation in the shading frame (view vector lies in XZ plane).
+mat3 LTC_InverseMatrix(float roughness, float NdotV)
+{
+    float alpha = clamp(roughness * roughness, 0.02, 1.0);
+
+    // Dominant direction of the specular lobe (Frostbite approximation).
+    vec3 reflected = vec3(-sqrt(max(1.0 - NdotV * NdotV, 0.0)), 0.0, NdotV);
+    float factor = (1.0 - alpha) * (sqrt(1.0 - alpha) + alpha);
+    vec3 dominant = normalize(mix(vec3(0.0, 0.0, 1.0), reflected, factor));
+
+    // Rotation around Y, that maps the dominant direction to Z.
+    mat3 inverseRotation = mat3(
+        vec3(dominant.z, 0.0, dominant.x),
+        vec3(0.0, 1.0, 0.0),
+        vec3(-dominant.x, 0.0, dominant.z));
+
+    return mat3(
+        vec3(1.0 / alpha, 0.0, 0.0),
+        vec3(0.0, 1.0 / alpha, 0.0),
+        vec3(0.0, 0.0, 1.0)) * inverseRotation;
+}
+
+vec3 LTC_Lighting(vec3 position, vec3 N, vec3 V, vec3 albedo, float metallic, float roughness)
+{
+    float NdotV = clamp(dot(N, V), 0.0001, 1.0);
+
+    vec3 T1 = V - N * dot(V, N);
+    if (dot(T1, T1) < 1e-6) {
+        T1 = abs(N.x) < 0.9 ? cross(N, vec3(1.0, 0.0, 0.0)) : cross(N, vec3(0.0, 1.0, 0.0));
+    }
+    T1 = normalize(T1);
+    vec3 T2 = cross(N, T1);
+    mat3 frame = mat3(T1, T2, N);
+
+    float diffuse = LTC_Evaluate(position, frame, mat3(1.0));
+    float specular = LTC_Evaluate(position, frame, LTC_InverseMatrix(roughness, NdotV));
+
+    vec3 F0 = mix(vec3(0.04), albedo, metallic);
+    vec2 brdf = S_EnvBRDFApprox(roughness, NdotV);
+    vec3 specularAlbedo = F0 * brdf.x + brdf.y;
+
+    return albedo * (1.0 - metallic) * diffuse + specularAlbedo * specular;
+}
+
+// Returns the closest point of the emitting surface to the given point in the plane of the light.
+vec3 ClosestPointOnLight(vec3 point)
+{
+    vec3 d = point - lightPos;
+    float lenX2 = max(dot(lightHalfAxisX, lightHalfAxisX), 1e-6);
+    float lenZ2 = max(dot(lightHalfAxisZ, lightHalfAxisZ), 1e-6);
+    vec2 local = vec2(dot(d, lightHalfAxisX) / lenX2, dot(d, lightHalfAxisZ) / lenZ2);
+    if (lightIsDisk) {
+        float len = length(local);
+        if (len > 1.0) {
+            local /= len;
+        }
+    } else {
+        local = clamp(local, vec2(-1.0), vec2(1.0));
+    }
+    return lightPos + lightHalfAxisX * local.x + lightHalfAxisZ * local.y;
+}
+
+// Cheap approximation, that lights a fragment by the "most representative" points of the light:
+// the closest point for the diffuse part and the point closest to the reflected ray for the
+// specular part. The specular lobe is widened to conserve energy.
+vec3 RepresentativePointLighting(vec3 position, vec3 N, vec3 V, vec3 albedo, float metallic, float roughness)
+{
+    float area = length(cross(lightHalfAxisX, lightHalfAxisZ)) * (lightIsDisk ? PI : 4.0);
+    vec3 lightNormal = twoSided ? sign(dot(position - lightPos, lightDirection)) * lightDirection : lightDirection;
+
+    // Diffuse.
+    vec3 toDiffusePoint = ClosestPointOnLight(position) - position;
+    float diffuseDistance2 = max(dot(toDiffusePoint, toDiffusePoint), 1e-4);
+    vec3 Ld = toDiffusePoint * inversesqrt(diffuseDistance2);
+    float formFactor = min(area * max(dot(-Ld, lightNormal), 0.0) * max(dot(N, Ld), 0.0) / (PI * diffuseDistance2), 1.0);
+    vec3 diffuse = albedo * (1.0 - metallic) * formFactor;
+
+    // Specular.
+    vec3 R = reflect(-V, N);
+    float denom = dot(R, lightDirection);
+    vec3 specularPoint = abs(denom) > 1e-4
+        ? position + R * max(dot(lightPos - position, lightDirection) / denom, 0.0)
+        : lightPos;
+    vec3 toSpecularPoint = ClosestPointOnLight(specularPoint) - position;
+    float specularDistance2 = max(dot(toSpecularPoint, toSpecularPoint), 1e-4);
+    vec3 Ls = toSpecularPoint * inversesqrt(specularDistance2);
+    float solidAngle = min(area * max(dot(-Ls, lightNormal), 0.0) / specularDistance2, 2.0 * PI);
+
+    float alpha = max(roughness * roughness, 0.001);
+    float widenedAlpha = clamp(alpha + sqrt(area / PI) / (2.0 * sqrt(specularDistance2)), 0.0, 1.0);
+    float normalization = (alpha / widenedAlpha) * (alpha / widenedAlpha);
+
+    vec3 F0 = mix(vec3(0.04), albedo, metallic);
+    vec3 H = normalize(V + Ls);
+    float NdotL = max(dot(N, Ls), 0.0);
+    float NDF = S_DistributionGGX(N, H, sqrt(widenedAlpha));
+    float G = S_GeometrySmith(N, V, Ls, roughness);
+    vec3 F = S_FresnelSchlick(max(dot(H, V), 0.0), F0);
+    vec3 specular = NDF * G * F / (4.0 * max(dot(N, V), 0.0) * NdotL + 0.001);
+
+    return diffuse + specular * NdotL * solidAngle * normalization;
+}
+
+void main()
+{
+    if (S_IsLightExcluded(lightLinkTexture, texCoord, lightLinkBit)) {
+        FragColor = vec4(0.0);
+        return;
+    }
+
+    vec3 material = texture(materialTexture, texCoord).rgb;
+
+    vec3 fragmentPosition = S_UnProject(vec3(texCoord, texture(depthTexture, texCoord).r), invViewProj);
+    vec4 diffuseColor = texture(colorTexture, texCoord);
+
+    float side = dot(fragmentPosition - lightPos, lightDirection);
+    if (!twoSided && side <= 0.0) {
+        FragColor = vec4(0.0, 0.0, 0.0, diffuseColor.a);
+        return;
+    }
+
+    vec3 albedo = S_SRGBToLinear(diffuseColor).rgb;
+    vec3 N = normalize(texture(normalTexture, texCoord).xyz * 2.0 - 1.0);
+    vec3 V = normalize(cameraPosition - fragmentPosition);
+    float metallic = material.x;
+    float roughness = material.y;
+
+    vec3 lighting = useLtc
+        ? LTC_Lighting(fragmentPosition, N, V, albedo, metallic, roughness)
+        : RepresentativePointLighting(fragmentPosition, N, V, albedo, metallic, roughness);
+
+    float distanceAttenuation = S_LightDistanceAttenuation(length(lightPos - fragmentPosition), lightRadius);
+
+    FragColor = vec4(lightIntensity * distanceAttenuation * lightColor.rgb * lighting, diffuseColor.a);
+}
@@ -2,6 +2,8 @@ uniform sampler2D depthTexture;
 uniform sampler2D colorTexture;
 uniform sampler2D normalTexture;
 uniform sampler2D materialTexture;
+uniform usampler2D lightLinkTexture;
+uniform uint lightLinkBit;
 
 uniform vec3 lightDirection;
 uniform vec4 lightColor;
@@ -35,9 +37,21 @@ float CsmGetShadow(in sampler2D sampler, in vec3 fragmentPosition, in mat4 light
 
 void main()
 {
+    if (S_IsLightExcluded(lightLinkTexture, texCoord, lightLinkBit)) {
+        FragColor = vec4(0.0);
+        return;
+    }
+
+    // Pixels without geometry (sky or image of the previous cameras of the stack) are not lit.
+    float depth = texture(depthTexture, texCoord).r;
+    if (depth >= 1.0) {
+        FragColor = vec4(0.0);
+        return;
+    }
+
     vec3 material = texture(materialTexture, texCoord).rgb;
 
-    vec3 fragmentPosition = S_UnProject(vec3(texCoord, texture(depthTexture, texCoord).r), invViewProj);
+    vec3 fragmentPosition = S_UnProject(vec3(texCoord, depth), invViewProj);
     vec4 diffuseColor = texture(colorTexture, texCoord);
 
     TPBRContext ctx;
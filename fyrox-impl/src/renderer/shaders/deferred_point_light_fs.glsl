@@ -2,6 +2,8 @@ uniform sampler2D depthTexture;
 uniform sampler2D colorTexture;
 uniform sampler2D normalTexture;
 uniform sampler2D materialTexture;
+uniform usampler2D lightLinkTexture;
+uniform uint lightLinkBit;
 uniform samplerCube pointShadowTexture;
 
 uniform vec3 lightPos;
@@ -20,6 +22,11 @@ out vec4 FragColor;
 
 void main()
 {
+    if (S_IsLightExcluded(lightLinkTexture, texCoord, lightLinkBit)) {
+        FragColor = vec4(0.0);
+        return;
+    }
+
     vec3 material = texture(materialTexture, texCoord).rgb;
 
     vec3 fragmentPosition = S_UnProject(vec3(texCoord, texture(depthTexture, texCoord).r), invViewProj);
@@ -0,0 +1,67 @@
+// Keep in sync with VolumetricLightQuality::ray_march_steps.
+#define MAX_STEPS 64
+#define NUM_CASCADES 3
+
+// Anisotropy of scattering, positive values makes shafts brighter when looking towards the light.
+#define ANISOTROPY 0.3
+
+uniform sampler2D depthTexture;
+uniform sampler2D shadowCascade0;
+uniform sampler2D shadowCascade1;
+uniform sampler2D shadowCascade2;
+
+uniform float cascadeDistances[NUM_CASCADES];
+uniform mat4 lightViewProjMatrices[NUM_CASCADES];
+uniform mat4 invViewProj;
+uniform mat4 viewMatrix;
+uniform vec3 cameraPosition;
+// Direction to the light.
+uniform vec3 lightDirection;
+uniform vec3 lightColor;
+uniform vec3 scatterFactor;
+uniform float intensity;
+uniform float shadowBias;
+uniform float maxDistance;
+uniform int stepCount;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+float CascadeShadow(vec3 position)
+{
+    float z = abs((viewMatrix * vec4(position, 1.0)).z);
+    if (z <= cascadeDistances[0]) {
+        return S_SpotShadowFactor(true, false, shadowBias, position, lightViewProjMatrices[0], 0.0, shadowCascade0);
+    } else if (z <= cascadeDistances[1]) {
+        return S_SpotShadowFactor(true, false, shadowBias, position, lightViewProjMatrices[1], 0.0, shadowCascade1);
+    } else if (z <= cascadeDistances[2]) {
+        return S_SpotShadowFactor(true, false, shadowBias, position, lightViewProjMatrices[2], 0.0, shadowCascade2);
+    }
+    return 1.0;
+}
+
+void main()
+{
+    vec3 fragmentPosition = S_UnProject(vec3(texCoord, texture(depthTexture, texCoord).r), invViewProj);
+    vec3 ray = fragmentPosition - cameraPosition;
+    float fragmentDistance = length(ray);
+    vec3 rayDirection = ray / fragmentDistance;
+
+    float rayLength = min(fragmentDistance, maxDistance);
+    float stepLength = rayLength / float(stepCount);
+    float offset = S_InterleavedGradientNoise(gl_FragCoord.xy);
+
+    float accumulator = 0.0;
+    for (int i = 0; i < MAX_STEPS; ++i) {
+        if (i >= stepCount) {
+            break;
+        }
+
+        accumulator += CascadeShadow(cameraPosition + rayDirection * ((float(i) + offset) * stepLength));
+    }
+
+    // Normalized, so isotropic scattering has the phase of 1.0.
+    float phase = 4.0 * PI * S_HenyeyGreenstein(dot(rayDirection, lightDirection), ANISOTROPY);
+
+    FragColor = vec4(intensity * phase * lightColor * scatterFactor * accumulator * stepLength, 0.0);
+}
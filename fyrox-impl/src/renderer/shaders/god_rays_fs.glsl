@@ -0,0 +1,37 @@
+// Screen-space god rays, based on "Volumetric Light Scattering as a Post-Process" by Kenny Mitchell
+// (GPU Gems 3). Every pixel is blurred radially towards the light source on screen, only pixels
+// of the sky (the ones with no geometry in the depth buffer) emit light.
+
+#define SAMPLE_COUNT 64
+#define DECAY 0.97
+
+uniform sampler2D depthTexture;
+
+uniform vec2 lightScreenPosition;
+uniform vec3 lightColor;
+uniform vec3 scatterFactor;
+uniform float intensity;
+uniform float maxDistance;
+uniform float visibility;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+void main()
+{
+    vec2 delta = (texCoord - lightScreenPosition) / float(SAMPLE_COUNT);
+    vec2 sampleTexCoord = texCoord - delta * S_InterleavedGradientNoise(gl_FragCoord.xy);
+
+    float illuminationDecay = 1.0;
+    float accumulator = 0.0;
+    for (int i = 0; i < SAMPLE_COUNT; ++i) {
+        sampleTexCoord -= delta;
+        float isSky = texture(depthTexture, sampleTexCoord).r >= 1.0 ? 1.0 : 0.0;
+        accumulator += isSky * illuminationDecay;
+        illuminationDecay *= DECAY;
+    }
+    accumulator /= float(SAMPLE_COUNT);
+
+    // The rays are scaled by the max distance to roughly match brightness of ray-marched shafts.
+    FragColor = vec4(intensity * visibility * maxDistance * lightColor * scatterFactor * accumulator, 0.0);
+}
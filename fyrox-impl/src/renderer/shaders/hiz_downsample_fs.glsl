@@ -0,0 +1,17 @@
+// Builds the next level of the depth pyramid. Every pixel contains the closest depth of the 2x2
+// block of pixels of the previous level, the previous level must be the base level of the texture.
+
+uniform sampler2D depthPyramid;
+
+out float linearDepth;
+
+void main()
+{
+    ivec2 maxPixel = textureSize(depthPyramid, 0) - 1;
+    ivec2 pixel = ivec2(gl_FragCoord.xy) * 2;
+    float d0 = texelFetch(depthPyramid, min(pixel, maxPixel), 0).r;
+    float d1 = texelFetch(depthPyramid, min(pixel + ivec2(1, 0), maxPixel), 0).r;
+    float d2 = texelFetch(depthPyramid, min(pixel + ivec2(0, 1), maxPixel), 0).r;
+    float d3 = texelFetch(depthPyramid, min(pixel + ivec2(1, 1), maxPixel), 0).r;
+    linearDepth = min(min(d0, d1), min(d2, d3));
+}
@@ -0,0 +1,15 @@
+// Converts hardware depth of the G-Buffer to positive linear (view space) depth. The result is the
+// first level of the depth pyramid, that is used by ambient occlusion.
+
+uniform sampler2D depthSampler;
+uniform mat4 inverseProjectionMatrix;
+
+out float linearDepth;
+
+in vec2 texCoord;
+
+void main()
+{
+    float depth = texture(depthSampler, texCoord).r;
+    linearDepth = -S_UnProject(vec3(texCoord, depth), inverseProjectionMatrix).z;
+}
@@ -1,11 +1,12 @@
 layout(location = 0) in vec3 vertexPosition;
 
 uniform mat4 worldViewProjection;
+uniform mat3 rotation;
 
 out vec3 texCoord;
 
 void main()
 {
-    texCoord = vertexPosition;
+    texCoord = rotation * vertexPosition;
     gl_Position = worldViewProjection * vec4(vertexPosition, 1.0);
 }
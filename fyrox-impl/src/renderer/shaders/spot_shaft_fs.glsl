@@ -0,0 +1,68 @@
+// Keep in sync with VolumetricLightQuality::ray_march_steps.
+#define MAX_STEPS 64
+
+uniform sampler2D depthTexture;
+uniform sampler2D spotShadowTexture;
+
+uniform mat4 invViewProj;
+uniform mat4 lightViewProjMatrix;
+uniform vec3 cameraPosition;
+uniform vec3 lightPosition;
+// Direction of emission.
+uniform vec3 lightDirection;
+uniform float lightRadius;
+uniform float halfHotspotConeAngleCos;
+uniform float halfConeAngleCos;
+uniform vec3 lightColor;
+uniform vec3 scatterFactor;
+uniform float intensity;
+uniform bool shadowsEnabled;
+uniform float shadowBias;
+uniform int stepCount;
+
+in vec2 texCoord;
+out vec4 FragColor;
+
+void main()
+{
+    vec3 fragmentPosition = S_UnProject(vec3(texCoord, texture(depthTexture, texCoord).r), invViewProj);
+    vec3 ray = fragmentPosition - cameraPosition;
+    float fragmentDistance = length(ray);
+    vec3 rayDirection = ray / fragmentDistance;
+
+    // The light affects only the points inside its sphere, so march only through this part of the ray.
+    float minT, maxT;
+    if (!S_RaySphereIntersection(cameraPosition, rayDirection, lightPosition, lightRadius, minT, maxT)) {
+        FragColor = vec4(0.0);
+        return;
+    }
+    minT = max(minT, 0.0);
+    maxT = min(maxT, fragmentDistance);
+    if (maxT <= minT) {
+        FragColor = vec4(0.0);
+        return;
+    }
+
+    float stepLength = (maxT - minT) / float(stepCount);
+    float offset = S_InterleavedGradientNoise(gl_FragCoord.xy);
+
+    float accumulator = 0.0;
+    for (int i = 0; i < MAX_STEPS; ++i) {
+        if (i >= stepCount) {
+            break;
+        }
+
+        vec3 samplePosition = cameraPosition + rayDirection * (minT + (float(i) + offset) * stepLength);
+        vec3 lightToSample = samplePosition - lightPosition;
+        float distance = length(lightToSample);
+        float spotAngleCos = dot(lightToSample / max(distance, 0.0001), lightDirection);
+        float coneFactor = smoothstep(halfConeAngleCos, halfHotspotConeAngleCos, spotAngleCos);
+
+        if (coneFactor > 0.0) {
+            float shadow = S_SpotShadowFactor(shadowsEnabled, false, shadowBias, samplePosition, lightViewProjMatrix, 0.0, spotShadowTexture);
+            accumulator += coneFactor * shadow * S_LightDistanceAttenuation(distance, lightRadius);
+        }
+    }
+
+    FragColor = vec4(intensity * lightColor * scatterFactor * accumulator * stepLength, 0.0);
+}
@@ -1,47 +1,136 @@
-#define KERNEL_SIZE 32
+// Ground-truth ambient occlusion (GTAO), based on "Practical Realtime Strategies for Accurate
+// Indirect Occlusion" by Jorge Jimenez et al. and XeGTAO. For every pixel, horizons of the
+// surrounding depth are searched in a few directions (slices) and the visible part of the
+// hemisphere is integrated analytically. Besides the visibility, the shader outputs a bent normal
+// (the average unoccluded direction) in world space, it is used for specular occlusion.
 
-uniform sampler2D depthSampler;
+#define PI 3.14159265
+#define HALF_PI 1.57079632
+
+uniform sampler2D depthPyramid;
 uniform sampler2D normalSampler;
-uniform sampler2D noiseSampler;
 
 uniform float radius;
-uniform mat4 inverseProjectionMatrix;
+uniform int sliceCount;
+uniform int stepsPerSide;
+uniform float maxMipLevel;
+uniform float frameIndex;
 uniform mat4 projectionMatrix;
-uniform vec3 kernel[KERNEL_SIZE];
-uniform vec2 noiseScale;
+uniform mat4 inverseProjectionMatrix;
 uniform mat3 viewMatrix;
+uniform mat3 inverseViewMatrix;
 
-out float finalOcclusion;
+// Bent normal (world space) in RGB, visibility in A.
+out vec4 bentNormalVisibility;
 
 in vec2 texCoord;
 
-vec3 GetViewSpacePosition(vec2 screenCoord) {
-    return S_UnProject(vec3(screenCoord, texture(depthSampler, screenCoord).r), inverseProjectionMatrix);
-}
+void main()
+{
+    vec3 worldSpaceNormal = normalize(texture(normalSampler, texCoord).xyz * 2.0 - 1.0);
+
+    float linearDepth = textureLod(depthPyramid, texCoord, 0.0).r;
+    float farPlaneDepth = -S_UnProject(vec3(0.5, 0.5, 1.0), inverseProjectionMatrix).z;
+    if (linearDepth >= farPlaneDepth * 0.9999) {
+        // Nothing to occlude (sky).
+        bentNormalVisibility = vec4(worldSpaceNormal * 0.5 + 0.5, 1.0);
+        return;
+    }
+
+    vec3 P = S_ViewSpacePosition(texCoord, linearDepth, inverseProjectionMatrix);
+    vec3 V = normalize(-P);
+    vec3 N = normalize(viewMatrix * worldSpaceNormal);
+
+    // Radius of the sampling area in texture coordinates (per axis, so aspect ratio and
+    // orthographic projections are handled automatically).
+    vec2 screenRadius = abs(S_Project(P + vec3(radius, radius, 0.0), projectionMatrix).xy - texCoord);
+    vec2 textureSizePixels = vec2(textureSize(depthPyramid, 0));
+    if (length(screenRadius * textureSizePixels) < 1.0) {
+        // The sampling area is less than a pixel.
+        bentNormalVisibility = vec4(worldSpaceNormal * 0.5 + 0.5, 1.0);
+        return;
+    }
+
+    float falloffRange = 0.615 * radius;
+    float falloffFrom = radius - falloffRange;
+    float falloffMul = -1.0 / falloffRange;
+    float falloffAdd = falloffFrom / falloffRange + 1.0;
+
+    // Directions of slices and positions of samples are rotated every frame, so the temporal
+    // filter accumulates different samples.
+    float sliceNoise = fract(S_InterleavedGradientNoise(gl_FragCoord.xy) + frameIndex * 0.618034);
+    float stepNoise = fract(S_InterleavedGradientNoise(gl_FragCoord.yx + vec2(7.0)) + frameIndex * 0.754878);
 
-void main() {
-    vec3 fragPos = GetViewSpacePosition(texCoord);
-    vec3 worldSpaceNormal = texture(normalSampler, texCoord).xyz * 2.0 - 1.0;
-    vec3 viewSpaceNormal = normalize(viewMatrix * worldSpaceNormal);
-    vec3 randomVec = normalize(texture(noiseSampler, texCoord * noiseScale).xyz * 2.0 - 1.0);
+    float visibility = 0.0;
+    vec3 bentNormal = vec3(0.0);
 
-    vec3 tangent = normalize(randomVec - viewSpaceNormal * dot(randomVec, viewSpaceNormal));
-    vec3 bitangent = normalize(cross(viewSpaceNormal, tangent));
-    mat3 TBN = mat3(tangent, bitangent, viewSpaceNormal);
+    for (int sliceIndex = 0; sliceIndex < sliceCount; ++sliceIndex) {
+        float phi = (float(sliceIndex) + sliceNoise) * PI / float(sliceCount);
+        vec2 omega = vec2(cos(phi), sin(phi));
 
-    float occlusion = 0.0;
-    for (int i = 0; i < KERNEL_SIZE; ++i) {
-        vec3 samplePoint = fragPos.xyz + TBN * kernel[i] * radius;
+        vec3 directionVec = vec3(omega, 0.0);
+        vec3 orthoDirectionVec = directionVec - dot(directionVec, V) * V;
+        vec3 axisVec = normalize(cross(orthoDirectionVec, V));
+        vec3 projectedNormalVec = N - axisVec * dot(N, axisVec);
+        float projectedNormalVecLength = max(length(projectedNormalVec), 0.0001);
 
-        vec4 offset = projectionMatrix * vec4(samplePoint, 1.0);
-        offset.xy /= offset.w;
-        offset.xy = offset.xy * 0.5 + 0.5;
+        float signNorm = sign(dot(orthoDirectionVec, projectedNormalVec));
+        float cosNorm = clamp(dot(projectedNormalVec, V) / projectedNormalVecLength, 0.0, 1.0);
+        float n = signNorm * acos(cosNorm);
 
-        vec3 position = GetViewSpacePosition(offset.xy);
+        float lowHorizonCos0 = cos(n + HALF_PI);
+        float lowHorizonCos1 = cos(n - HALF_PI);
+        float horizonCos0 = lowHorizonCos0;
+        float horizonCos1 = lowHorizonCos1;
 
-        float rangeCheck = smoothstep(0.0, 1.0, radius / abs(fragPos.z - position.z));
-        occlusion += rangeCheck * ((position.z > samplePoint.z + 0.04) ? 1.0 : 0.0);
+        for (int stepIndex = 0; stepIndex < stepsPerSide; ++stepIndex) {
+            // Quadratic distribution puts more samples close to the center.
+            float s = (float(stepIndex) + stepNoise) / float(stepsPerSide);
+            s *= s;
+
+            vec2 sampleOffset = s * omega * screenRadius;
+            // Distant samples read coarser levels of the depth pyramid.
+            float mipLevel = clamp(log2(length(sampleOffset * textureSizePixels)) - 3.0, 0.0, maxMipLevel);
+
+            vec2 sampleCoord0 = texCoord + sampleOffset;
+            vec2 sampleCoord1 = texCoord - sampleOffset;
+
+            vec3 sampleDelta0 = S_ViewSpacePosition(sampleCoord0, textureLod(depthPyramid, sampleCoord0, mipLevel).r, inverseProjectionMatrix) - P;
+            vec3 sampleDelta1 = S_ViewSpacePosition(sampleCoord1, textureLod(depthPyramid, sampleCoord1, mipLevel).r, inverseProjectionMatrix) - P;
+            float sampleDistance0 = max(length(sampleDelta0), 0.0001);
+            float sampleDistance1 = max(length(sampleDelta1), 0.0001);
+
+            float weight0 = clamp(sampleDistance0 * falloffMul + falloffAdd, 0.0, 1.0);
+            float weight1 = clamp(sampleDistance1 * falloffMul + falloffAdd, 0.0, 1.0);
+
+            float shc0 = mix(lowHorizonCos0, dot(sampleDelta0 / sampleDistance0, V), weight0);
+            float shc1 = mix(lowHorizonCos1, dot(sampleDelta1 / sampleDistance1, V), weight1);
+
+            horizonCos0 = max(horizonCos0, shc0);
+            horizonCos1 = max(horizonCos1, shc1);
+        }
+
+        float h0 = -acos(clamp(horizonCos1, -1.0, 1.0));
+        float h1 = acos(clamp(horizonCos0, -1.0, 1.0));
+        h0 = n + clamp(h0 - n, -HALF_PI, HALF_PI);
+        h1 = n + clamp(h1 - n, -HALF_PI, HALF_PI);
+
+        float iarc0 = (cosNorm + 2.0 * h0 * sin(n) - cos(2.0 * h0 - n)) / 4.0;
+        float iarc1 = (cosNorm + 2.0 * h1 * sin(n) - cos(2.0 * h1 - n)) / 4.0;
+        visibility += projectedNormalVecLength * (iarc0 + iarc1);
+
+        // Bent normal of the slice is expressed in the basis of the slice plane: tangent along
+        // the slice direction and the view vector.
+        float t0 = (6.0 * sin(h0 - n) - sin(3.0 * h0 - n) + 6.0 * sin(h1 - n) - sin(3.0 * h1 - n) +
+            16.0 * sin(n) - 3.0 * (sin(h0 + n) + sin(h1 + n))) / 12.0;
+        float t1 = (-cos(3.0 * h0 - n) - cos(3.0 * h1 - n) + 8.0 * cos(n) -
+            3.0 * (cos(h0 + n) + cos(h1 + n))) / 12.0;
+        vec3 tangent = normalize(orthoDirectionVec);
+        bentNormal += (tangent * t0 + V * t1) * projectedNormalVecLength;
     }
 
-    finalOcclusion = 1.0 - occlusion / float(KERNEL_SIZE);
-}
\ No newline at end of file
+    visibility = clamp(visibility / float(sliceCount), 0.0, 1.0);
+    bentNormal = length(bentNormal) > 0.0001 ? normalize(inverseViewMatrix * bentNormal) : worldSpaceNormal;
+
+    bentNormalVisibility = vec4(bentNormal * 0.5 + 0.5, visibility);
+}
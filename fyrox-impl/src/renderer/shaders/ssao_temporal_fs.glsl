@@ -0,0 +1,64 @@
+// Temporal accumulation of ambient occlusion. Every pixel is reprojected to the previous frame of
+// the camera and blended with the history. Directions of slices are rotated every frame, so the
+// accumulated result is equivalent to many more samples. The history is rejected if the pixel was
+// off-screen or occluded (the depth of the previous frame does not match) in the previous frame.
+
+uniform sampler2D currentTexture;
+uniform sampler2D historyTexture;
+uniform sampler2D historyDepthTexture;
+uniform sampler2D depthPyramid;
+
+uniform float historyWeight;
+uniform mat4 inverseProjectionMatrix;
+uniform mat4 inverseViewMatrix;
+uniform mat4 previousViewMatrix;
+uniform mat4 previousProjectionMatrix;
+
+layout(location = 0) out vec4 result;
+layout(location = 1) out float resultDepth;
+
+in vec2 texCoord;
+
+void main()
+{
+    vec4 current = texture(currentTexture, texCoord);
+    float linearDepth = textureLod(depthPyramid, texCoord, 0.0).r;
+
+    vec3 viewPosition = S_ViewSpacePosition(texCoord, linearDepth, inverseProjectionMatrix);
+    vec4 worldPosition = inverseViewMatrix * vec4(viewPosition, 1.0);
+    vec3 previousViewPosition = (previousViewMatrix * worldPosition).xyz;
+    vec2 previousTexCoord = S_Project(previousViewPosition, previousProjectionMatrix).xy;
+
+    float weight = historyWeight;
+    if (previousViewPosition.z >= 0.0 ||
+        any(lessThan(previousTexCoord, vec2(0.0))) ||
+        any(greaterThan(previousTexCoord, vec2(1.0)))) {
+        weight = 0.0;
+    }
+
+    float expectedDepth = -previousViewPosition.z;
+    float previousDepth = texture(historyDepthTexture, previousTexCoord).r;
+    if (abs(previousDepth - expectedDepth) > 0.05 * expectedDepth) {
+        weight = 0.0;
+    }
+
+    // Clamp visibility of the history to the neighborhood of the pixel to reduce ghosting.
+    vec2 texelSize = 1.0 / vec2(textureSize(currentTexture, 0));
+    float minVisibility = current.a;
+    float maxVisibility = current.a;
+    for (int y = -1; y <= 1; ++y)
+    {
+        for (int x = -1; x <= 1; ++x)
+        {
+            float visibility = texture(currentTexture, texCoord + vec2(float(x), float(y)) * texelSize).a;
+            minVisibility = min(minVisibility, visibility);
+            maxVisibility = max(maxVisibility, visibility);
+        }
+    }
+    vec4 history = texture(historyTexture, previousTexCoord);
+    history.a = clamp(history.a, minVisibility, maxVisibility);
+
+    // Content of a new history is undefined, so it must not be touched at all.
+    result = weight > 0.0 ? mix(current, history, weight) : current;
+    resultDepth = linearDepth;
+}
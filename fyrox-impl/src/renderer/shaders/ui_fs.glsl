@@ -2,26 +2,24 @@
 
 uniform sampler2D diffuseTexture;
 
-uniform bool isFont;
-uniform vec4 solidColor;
-uniform float opacity;
-
-uniform int brushType;
-
-uniform int gradientPointCount;
-uniform vec4 gradientColors[16];
-uniform float gradientStops[16];
-
-// Begin point of linear gradient *or* center of radial gradient
-// in normalized coordinates
-uniform vec2 gradientOrigin;
-
-// End point of linear gradient in normalized coordinates.
-uniform vec2 gradientEnd;
-
-uniform vec2 resolution;
-uniform vec2 boundsMin;
-uniform vec2 boundsMax;
+layout(std140) uniform UiData {
+    mat4 worldViewProjection;
+    vec4 solidColor;
+    vec4 gradientColors[16];
+    float gradientStops[16];
+    // Begin point of linear gradient *or* center of radial gradient
+    // in normalized coordinates
+    vec2 gradientOrigin;
+    // End point of linear gradient in normalized coordinates.
+    vec2 gradientEnd;
+    vec2 resolution;
+    vec2 boundsMin;
+    vec2 boundsMax;
+    bool isFont;
+    float opacity;
+    highp int brushType;
+    highp int gradientPointCount;
+};
 
 out vec4 fragColor;
 
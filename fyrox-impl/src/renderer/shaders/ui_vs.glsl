@@ -2,7 +2,24 @@ layout(location = 0) in vec3 vertexPosition;
 layout(location = 1) in vec2 vertexTexCoord;
 layout(location = 2) in vec4 vertexColor;
 
-uniform mat4 worldViewProjection;
+layout(std140) uniform UiData {
+    mat4 worldViewProjection;
+    vec4 solidColor;
+    vec4 gradientColors[16];
+    float gradientStops[16];
+    // Begin point of linear gradient *or* center of radial gradient
+    // in normalized coordinates
+    vec2 gradientOrigin;
+    // End point of linear gradient in normalized coordinates.
+    vec2 gradientEnd;
+    vec2 resolution;
+    vec2 boundsMin;
+    vec2 boundsMax;
+    bool isFont;
+    float opacity;
+    highp int brushType;
+    highp int gradientPointCount;
+};
 
 out vec2 texCoord;
 out vec4 color;
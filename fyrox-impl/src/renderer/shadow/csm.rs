@@ -15,7 +15,9 @@ use crate::{
                 PixelKind, WrapMode,
             },
             state::{ColorMask, PipelineState},
+            uniform::UniformBufferRing,
         },
+        instance::{upload_bundle_instances, InstanceUniforms},
         storage::MatrixStorageCache,
         MaterialContext, RenderPassStatistics, ShadowMapPrecision, DIRECTIONAL_SHADOW_PASS_NAME,
     },
@@ -107,6 +109,7 @@ pub(crate) struct CsmRenderContext<'a, 'c> {
     pub black_dummy: Rc<RefCell<GpuTexture>>,
     pub volume_dummy: Rc<RefCell<GpuTexture>>,
     pub matrix_storage: &'a mut MatrixStorageCache,
+    pub uniform_buffer_ring: &'a mut UniformBufferRing,
 }
 
 impl CsmRenderer {
@@ -158,6 +161,7 @@ impl CsmRenderer {
             black_dummy,
             volume_dummy,
             matrix_storage,
+            uniform_buffer_ring,
         } = ctx;
 
         let light_direction = -light
@@ -257,11 +261,36 @@ impl CsmRenderer {
                     z_far,
                     view_matrix: light_view_matrix,
                     projection_matrix: cascade_projection_matrix,
+                    layer_mask: u32::MAX,
                 },
                 DIRECTIONAL_SHADOW_PASS_NAME.clone(),
             );
 
-            for bundle in bundle_storage.bundles.iter() {
+            let camera_position = camera.global_position();
+            let bundles = upload_bundle_instances(
+                state,
+                uniform_buffer_ring,
+                bundle_storage.bundles.iter(),
+                |bundle, instance| InstanceUniforms {
+                    world_matrix: instance.world_transform,
+                    view_projection_matrix: light_view_projection,
+                    use_skeletal_animation: bundle.is_skinned,
+                    use_pom: false,
+                    camera_position,
+                    camera_up_vector: camera_up,
+                    camera_side_vector: camera_side,
+                    z_near,
+                    z_far,
+                    light_position: Default::default(),
+                    blend_shapes_count: instance.blend_shapes_weights.len(),
+                    vertex_animation: instance.vertex_animation.as_ref(),
+                    ambient_light: Color::WHITE, // TODO
+                    light_link_mask: 0,
+                    precipitation_occlusion_matrix: None,
+                },
+            );
+
+            for (bundle, instance_uniforms) in bundles.iter() {
                 let mut material_state = bundle.material.state();
                 let Some(material) = material_state.data() else {
                     continue;
@@ -289,7 +318,8 @@ impl CsmRenderer {
                     continue;
                 };
 
-                for instance in bundle.instances.iter() {
+                for (instance, instance_uniforms) in bundle.instances.iter().zip(instance_uniforms)
+                {
                     stats += framebuffer.draw(
                         geometry,
                         state,
@@ -311,28 +341,21 @@ impl CsmRenderer {
                                 program_binding: &mut program_binding,
                                 texture_cache,
                                 matrix_storage,
-                                world_matrix: &instance.world_transform,
-                                view_projection_matrix: &light_view_projection,
-                                wvp_matrix: &(light_view_projection * instance.world_transform),
+                                uniform_buffer_ring,
+                                instance_uniforms,
                                 bone_matrices: &instance.bone_matrices,
-                                use_skeletal_animation: bundle.is_skinned,
-                                camera_position: &camera.global_position(),
-                                camera_up_vector: &camera_up,
-                                camera_side_vector: &camera_side,
-                                z_near,
-                                use_pom: false,
-                                light_position: &Default::default(),
                                 blend_shapes_storage: blend_shapes_storage.as_ref(),
                                 blend_shapes_weights: &instance.blend_shapes_weights,
+                                vertex_animation: instance.vertex_animation.as_ref(),
                                 normal_dummy: &normal_dummy,
                                 white_dummy: &white_dummy,
                                 black_dummy: &black_dummy,
                                 volume_dummy: &volume_dummy,
                                 persistent_identifier: instance.persistent_identifier,
-                                light_data: None,            // TODO
-                                ambient_light: Color::WHITE, // TODO
+                                light_data: None, // TODO
                                 scene_depth: None,
-                                z_far,
+                                scene_color: None,
+                                precipitation_occlusion: None,
                             });
                         },
                     )?;
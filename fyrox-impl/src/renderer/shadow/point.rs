@@ -17,7 +17,9 @@ use crate::{
                 MinificationFilter, PixelKind, WrapMode,
             },
             state::PipelineState,
+            uniform::UniformBufferRing,
         },
+        instance::{upload_bundle_instances, InstanceUniforms},
         shadow::cascade_size,
         storage::MatrixStorageCache,
         GeometryCache, MaterialContext, RenderPassStatistics, ShadowMapPrecision,
@@ -55,6 +57,7 @@ pub(crate) struct PointShadowMapRenderContext<'a> {
     pub black_dummy: Rc<RefCell<GpuTexture>>,
     pub volume_dummy: Rc<RefCell<GpuTexture>>,
     pub matrix_storage: &'a mut MatrixStorageCache,
+    pub uniform_buffer_ring: &'a mut UniformBufferRing,
 }
 
 impl PointShadowMapRenderer {
@@ -208,6 +211,7 @@ impl PointShadowMapRenderer {
             black_dummy,
             volume_dummy,
             matrix_storage,
+            uniform_buffer_ring,
         } = args;
 
         let framebuffer = &mut self.cascades[cascade];
@@ -249,11 +253,35 @@ impl PointShadowMapRenderer {
                     z_far,
                     view_matrix: light_view_matrix,
                     projection_matrix: light_projection_matrix,
+                    layer_mask: u32::MAX,
                 },
                 POINT_SHADOW_PASS_NAME.clone(),
             );
 
-            for bundle in bundle_storage.bundles.iter() {
+            let bundles = upload_bundle_instances(
+                state,
+                uniform_buffer_ring,
+                bundle_storage.bundles.iter(),
+                |bundle, instance| InstanceUniforms {
+                    world_matrix: instance.world_transform,
+                    view_projection_matrix: light_view_projection_matrix,
+                    use_skeletal_animation: bundle.is_skinned,
+                    use_pom: false,
+                    camera_position: Default::default(),
+                    camera_up_vector: camera_up,
+                    camera_side_vector: camera_side,
+                    z_near,
+                    z_far,
+                    light_position: light_pos,
+                    blend_shapes_count: instance.blend_shapes_weights.len(),
+                    vertex_animation: instance.vertex_animation.as_ref(),
+                    ambient_light: Color::WHITE, // TODO
+                    light_link_mask: 0,
+                    precipitation_occlusion_matrix: None,
+                },
+            );
+
+            for (bundle, instance_uniforms) in bundles.iter() {
                 let mut material_state = bundle.material.state();
                 let Some(material) = material_state.data() else {
                     continue;
@@ -277,7 +305,8 @@ impl PointShadowMapRenderer {
                     continue;
                 };
 
-                for instance in bundle.instances.iter() {
+                for (instance, instance_uniforms) in bundle.instances.iter().zip(instance_uniforms)
+                {
                     statistics += framebuffer.draw(
                         geometry,
                         state,
@@ -291,29 +320,21 @@ impl PointShadowMapRenderer {
                                 program_binding: &mut program_binding,
                                 texture_cache,
                                 matrix_storage,
-                                world_matrix: &instance.world_transform,
-                                view_projection_matrix: &light_view_projection_matrix,
-                                wvp_matrix: &(light_view_projection_matrix
-                                    * instance.world_transform),
+                                uniform_buffer_ring,
+                                instance_uniforms,
                                 bone_matrices: &instance.bone_matrices,
-                                use_skeletal_animation: bundle.is_skinned,
-                                camera_position: &Default::default(),
-                                camera_up_vector: &camera_up,
-                                camera_side_vector: &camera_side,
-                                z_near,
-                                use_pom: false,
-                                light_position: &light_pos,
                                 blend_shapes_storage: blend_shapes_storage.as_ref(),
                                 blend_shapes_weights: &instance.blend_shapes_weights,
+                                vertex_animation: instance.vertex_animation.as_ref(),
                                 normal_dummy: &normal_dummy,
                                 white_dummy: &white_dummy,
                                 black_dummy: &black_dummy,
                                 volume_dummy: &volume_dummy,
                                 persistent_identifier: instance.persistent_identifier,
-                                light_data: None,            // TODO
-                                ambient_light: Color::WHITE, // TODO
+                                light_data: None, // TODO
                                 scene_depth: None,
-                                z_far,
+                                scene_color: None,
+                                precipitation_occlusion: None,
                             });
                         },
                     )?;
@@ -9,6 +9,9 @@ pub struct SkyboxShader {
     pub program: GpuProgram,
     pub wvp_matrix: UniformLocation,
     pub cubemap_texture: UniformLocation,
+    pub rotation: UniformLocation,
+    pub exposure: UniformLocation,
+    pub is_linear: UniformLocation,
 }
 
 impl SkyboxShader {
@@ -23,6 +26,9 @@ impl SkyboxShader {
                 .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
             cubemap_texture: program
                 .uniform_location(state, &ImmutableString::new("cubemapTexture"))?,
+            rotation: program.uniform_location(state, &ImmutableString::new("rotation"))?,
+            exposure: program.uniform_location(state, &ImmutableString::new("exposure"))?,
+            is_linear: program.uniform_location(state, &ImmutableString::new("isLinear"))?,
             program,
         })
     }
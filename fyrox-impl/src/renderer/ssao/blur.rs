@@ -23,6 +23,7 @@ struct Shader {
     program: GpuProgram,
     world_view_projection_matrix: UniformLocation,
     input_texture: UniformLocation,
+    depth_pyramid: UniformLocation,
 }
 
 impl Shader {
@@ -36,6 +37,8 @@ impl Shader {
                 .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
             input_texture: program
                 .uniform_location(state, &ImmutableString::new("inputTexture"))?,
+            depth_pyramid: program
+                .uniform_location(state, &ImmutableString::new("depthPyramid"))?,
             program,
         })
     }
@@ -56,7 +59,7 @@ impl Blur {
             let mut texture = GpuTexture::new(
                 state,
                 kind,
-                PixelKind::R32F,
+                PixelKind::RGBA16F,
                 MinificationFilter::Nearest,
                 MagnificationFilter::Nearest,
                 1,
@@ -97,6 +100,7 @@ impl Blur {
         &mut self,
         state: &PipelineState,
         input: Rc<RefCell<GpuTexture>>,
+        depth_pyramid: Rc<RefCell<GpuTexture>>,
     ) -> Result<DrawCallStatistics, FrameworkError> {
         scope_profile!();
 
@@ -124,7 +128,8 @@ impl Blur {
                         &shader.world_view_projection_matrix,
                         &(make_viewport_matrix(viewport)),
                     )
-                    .set_texture(&shader.input_texture, &input);
+                    .set_texture(&shader.input_texture, &input)
+                    .set_texture(&shader.depth_pyramid, &depth_pyramid);
             },
         )
     }
@@ -0,0 +1,231 @@
+use crate::{
+    core::{algebra::Matrix4, math::Rect, scope_profile, sstorage::ImmutableString},
+    renderer::{
+        framework::{
+            error::FrameworkError,
+            framebuffer::{Attachment, AttachmentKind, DrawParameters, FrameBuffer},
+            geometry_buffer::{ElementRange, GeometryBuffer, GeometryBufferKind},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
+                PixelKind, WrapMode,
+            },
+            state::PipelineState,
+        },
+        make_viewport_matrix, RenderPassStatistics,
+    },
+    scene::mesh::surface::SurfaceData,
+};
+use std::{cell::RefCell, rc::Rc};
+
+// Coarser levels are useless, because sampling radius rarely exceeds a few dozens of pixels.
+const MAX_MIP_COUNT: usize = 5;
+
+struct LinearizeShader {
+    program: GpuProgram,
+    world_view_projection_matrix: UniformLocation,
+    depth_sampler: UniformLocation,
+    inv_proj_matrix: UniformLocation,
+}
+
+impl LinearizeShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("../shaders/hiz_linearize_fs.glsl");
+        let vertex_source = include_str!("../shaders/ssao_vs.glsl");
+        let program = GpuProgram::from_source(
+            state,
+            "DepthLinearizeShader",
+            vertex_source,
+            fragment_source,
+        )?;
+        Ok(Self {
+            world_view_projection_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_sampler: program
+                .uniform_location(state, &ImmutableString::new("depthSampler"))?,
+            inv_proj_matrix: program
+                .uniform_location(state, &ImmutableString::new("inverseProjectionMatrix"))?,
+            program,
+        })
+    }
+}
+
+struct DownsampleShader {
+    program: GpuProgram,
+    world_view_projection_matrix: UniformLocation,
+    depth_pyramid: UniformLocation,
+}
+
+impl DownsampleShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("../shaders/hiz_downsample_fs.glsl");
+        let vertex_source = include_str!("../shaders/ssao_vs.glsl");
+        let program = GpuProgram::from_source(
+            state,
+            "DepthDownsampleShader",
+            vertex_source,
+            fragment_source,
+        )?;
+        Ok(Self {
+            world_view_projection_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_pyramid: program
+                .uniform_location(state, &ImmutableString::new("depthPyramid"))?,
+            program,
+        })
+    }
+}
+
+/// Hierarchical depth - a mip chain of linear (view space) depth, where every pixel of a level
+/// contains the closest depth of 2x2 block of pixels of the previous level.
+pub struct DepthPyramid {
+    linearize_shader: LinearizeShader,
+    downsample_shader: DownsampleShader,
+    framebuffer: FrameBuffer,
+    quad: GeometryBuffer,
+    width: usize,
+    height: usize,
+    mip_count: usize,
+}
+
+impl DepthPyramid {
+    pub fn new(state: &PipelineState, width: usize, height: usize) -> Result<Self, FrameworkError> {
+        // Every level must have at least one pixel.
+        let mip_count = (width.min(height).max(1).ilog2() as usize + 1).min(MAX_MIP_COUNT);
+
+        let mut texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle { width, height },
+            PixelKind::R32F,
+            MinificationFilter::NearestMipMapNearest,
+            MagnificationFilter::Nearest,
+            mip_count,
+            None,
+        )?;
+        texture
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+
+        let framebuffer = FrameBuffer::new(
+            state,
+            None,
+            vec![Attachment {
+                kind: AttachmentKind::Color,
+                texture: Rc::new(RefCell::new(texture)),
+            }],
+        )?;
+        framebuffer.set_name(state, "Depth Pyramid");
+
+        Ok(Self {
+            linearize_shader: LinearizeShader::new(state)?,
+            downsample_shader: DownsampleShader::new(state)?,
+            framebuffer,
+            quad: GeometryBuffer::from_surface_data(
+                &SurfaceData::make_unit_xy_quad(),
+                GeometryBufferKind::StaticDraw,
+                state,
+            )?,
+            width,
+            height,
+            mip_count,
+        })
+    }
+
+    pub fn texture(&self) -> Rc<RefCell<GpuTexture>> {
+        self.framebuffer.color_attachments()[0].texture.clone()
+    }
+
+    pub fn max_mip_level(&self) -> usize {
+        self.mip_count - 1
+    }
+
+    pub(crate) fn render(
+        &mut self,
+        state: &PipelineState,
+        depth: Rc<RefCell<GpuTexture>>,
+        inv_projection_matrix: &Matrix4<f32>,
+    ) -> Result<RenderPassStatistics, FrameworkError> {
+        scope_profile!();
+
+        let mut stats = RenderPassStatistics::default();
+
+        let draw_params = DrawParameters {
+            cull_face: None,
+            color_write: Default::default(),
+            depth_write: false,
+            stencil_test: None,
+            depth_test: false,
+            blend: None,
+            stencil_op: Default::default(),
+        };
+
+        let texture = self.texture();
+
+        // The first level contains linearized depth of the G-Buffer.
+        self.framebuffer.set_color_attachment_level(state, 0, 0);
+        let viewport = Rect::new(0, 0, self.width as i32, self.height as i32);
+        let shader = &self.linearize_shader;
+        stats += self.framebuffer.draw(
+            &self.quad,
+            state,
+            viewport,
+            &shader.program,
+            &draw_params,
+            ElementRange::Full,
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(
+                        &shader.world_view_projection_matrix,
+                        &make_viewport_matrix(viewport),
+                    )
+                    .set_texture(&shader.depth_sampler, &depth)
+                    .set_matrix4(&shader.inv_proj_matrix, inv_projection_matrix);
+            },
+        )?;
+
+        // Every next level is built from the previous one. Only the previous level is available
+        // for sampling, otherwise there will be a feedback loop.
+        for level in 1..self.mip_count {
+            texture
+                .borrow_mut()
+                .bind_mut(state, 0)
+                .set_base_level(level - 1)
+                .set_max_level(level - 1);
+
+            self.framebuffer.set_color_attachment_level(state, 0, level);
+            let viewport = Rect::new(
+                0,
+                0,
+                (self.width >> level).max(1) as i32,
+                (self.height >> level).max(1) as i32,
+            );
+            let shader = &self.downsample_shader;
+            stats += self.framebuffer.draw(
+                &self.quad,
+                state,
+                viewport,
+                &shader.program,
+                &draw_params,
+                ElementRange::Full,
+                |mut program_binding| {
+                    program_binding
+                        .set_matrix4(
+                            &shader.world_view_projection_matrix,
+                            &make_viewport_matrix(viewport),
+                        )
+                        .set_texture(&shader.depth_pyramid, &texture);
+                },
+            )?;
+        }
+
+        texture
+            .borrow_mut()
+            .bind_mut(state, 0)
+            .set_base_level(0)
+            .set_max_level(self.max_mip_level());
+        self.framebuffer.set_color_attachment_level(state, 0, 0);
+
+        Ok(stats)
+    }
+}
@@ -1,18 +1,36 @@
-use crate::renderer::framework::geometry_buffer::ElementRange;
+//! Screen-space ambient occlusion. Ambient occlusion is computed using ground-truth ambient
+//! occlusion (GTAO) in a few passes:
+//!
+//! 1. Depth pyramid - linear depth of the G-Buffer is written at half resolution and then
+//!    downsampled into mip levels, every pixel of a level contains the closest depth of 2x2 block
+//!    of pixels of the previous level. Distant samples of the horizon search read coarse levels,
+//!    which is much more cache-friendly.
+//! 2. Horizon search - horizons of the surrounding depth are searched in a few directions (slices)
+//!    around every pixel and the visible part of the hemisphere is integrated analytically. The
+//!    pass also computes a bent normal - the average unoccluded direction.
+//! 3. Spatial filtering - depth-aware blur, that removes the noise of randomly rotated slices.
+//! 4. Temporal filtering - the result is blended with the reprojected result of the previous frame
+//!    of the same camera. Directions of slices are rotated every frame, so the accumulation
+//!    effectively multiplies the amount of samples.
+//!
+//! The resulting texture contains the bent normal (in world space) in RGB channels and the
+//! visibility in the alpha channel. It is used by the ambient lighting pass for both diffuse and
+//! specular occlusion. See [`AmbientOcclusionQuality`] docs for available quality tiers.
+
 use crate::{
     core::{
-        algebra::{Matrix3, Matrix4, Vector2, Vector3},
+        algebra::Matrix4,
         color::Color,
-        math::{lerpf, Rect},
+        math::{Matrix4Ext, Rect},
+        pool::Handle,
         scope_profile,
         sstorage::ImmutableString,
     },
-    rand::Rng,
     renderer::{
         framework::{
             error::FrameworkError,
             framebuffer::{Attachment, AttachmentKind, DrawParameters, FrameBuffer},
-            geometry_buffer::{GeometryBuffer, GeometryBufferKind},
+            geometry_buffer::{ElementRange, GeometryBuffer, GeometryBufferKind},
             gpu_program::{GpuProgram, UniformLocation},
             gpu_texture::{
                 Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
@@ -21,33 +39,40 @@ use crate::{
             state::PipelineState,
         },
         gbuffer::GBuffer,
-        ssao::blur::Blur,
-        RenderPassStatistics,
+        make_viewport_matrix,
+        ssao::{
+            blur::Blur,
+            hiz::DepthPyramid,
+            temporal::{TemporalFilterContext, TemporalHistory, TemporalShader},
+        },
+        AmbientOcclusionQuality, RenderPassStatistics,
     },
-    scene::mesh::surface::SurfaceData,
+    scene::{mesh::surface::SurfaceData, node::Node, Scene},
 };
-use std::{cell::RefCell, rc::Rc};
+use fxhash::FxHashMap;
+use std::{cell::RefCell, collections::hash_map::Entry, rc::Rc};
 
 mod blur;
+mod hiz;
+mod temporal;
 
-// Keep in sync with shader define.
-const KERNEL_SIZE: usize = 32;
-
-// Size of noise texture.
-const NOISE_SIZE: usize = 4;
+/// Histories of cameras, that were not rendered during this amount of render calls, are destroyed.
+const MAX_HISTORY_IDLE_RENDER_CALLS: u64 = 64;
 
 struct Shader {
     program: GpuProgram,
-    depth_sampler: UniformLocation,
+    world_view_proj_matrix: UniformLocation,
+    depth_pyramid: UniformLocation,
     normal_sampler: UniformLocation,
-    noise_sampler: UniformLocation,
     radius: UniformLocation,
-    kernel: UniformLocation,
+    slice_count: UniformLocation,
+    steps_per_side: UniformLocation,
+    max_mip_level: UniformLocation,
+    frame_index: UniformLocation,
     projection_matrix: UniformLocation,
-    noise_scale: UniformLocation,
     inv_proj_matrix: UniformLocation,
-    world_view_proj_matrix: UniformLocation,
     view_matrix: UniformLocation,
+    inv_view_matrix: UniformLocation,
 }
 
 impl Shader {
@@ -56,37 +81,54 @@ impl Shader {
         let vertex_source = include_str!("../shaders/ssao_vs.glsl");
         let program = GpuProgram::from_source(state, "SsaoShader", vertex_source, fragment_source)?;
         Ok(Self {
-            depth_sampler: program
-                .uniform_location(state, &ImmutableString::new("depthSampler"))?,
+            world_view_proj_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_pyramid: program
+                .uniform_location(state, &ImmutableString::new("depthPyramid"))?,
             normal_sampler: program
                 .uniform_location(state, &ImmutableString::new("normalSampler"))?,
-            noise_sampler: program
-                .uniform_location(state, &ImmutableString::new("noiseSampler"))?,
-            kernel: program.uniform_location(state, &ImmutableString::new("kernel"))?,
             radius: program.uniform_location(state, &ImmutableString::new("radius"))?,
+            slice_count: program.uniform_location(state, &ImmutableString::new("sliceCount"))?,
+            steps_per_side: program
+                .uniform_location(state, &ImmutableString::new("stepsPerSide"))?,
+            max_mip_level: program.uniform_location(state, &ImmutableString::new("maxMipLevel"))?,
+            frame_index: program.uniform_location(state, &ImmutableString::new("frameIndex"))?,
             projection_matrix: program
                 .uniform_location(state, &ImmutableString::new("projectionMatrix"))?,
             inv_proj_matrix: program
                 .uniform_location(state, &ImmutableString::new("inverseProjectionMatrix"))?,
-            noise_scale: program.uniform_location(state, &ImmutableString::new("noiseScale"))?,
-            world_view_proj_matrix: program
-                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
             view_matrix: program.uniform_location(state, &ImmutableString::new("viewMatrix"))?,
+            inv_view_matrix: program
+                .uniform_location(state, &ImmutableString::new("inverseViewMatrix"))?,
             program,
         })
     }
 }
 
+pub(crate) struct AmbientOcclusionContext<'a> {
+    pub state: &'a PipelineState,
+    pub gbuffer: &'a GBuffer,
+    /// Scene and camera, that is used to render ambient occlusion. Every camera has its own history
+    /// for temporal filtering.
+    pub camera_key: (Handle<Scene>, Handle<Node>),
+    pub projection_matrix: Matrix4<f32>,
+    pub view_matrix: Matrix4<f32>,
+    pub radius: f32,
+    pub quality: AmbientOcclusionQuality,
+}
+
 pub struct ScreenSpaceAmbientOcclusionRenderer {
+    depth_pyramid: DepthPyramid,
     blur: Blur,
     shader: Shader,
+    temporal_shader: TemporalShader,
+    histories: FxHashMap<(Handle<Scene>, Handle<Node>), TemporalHistory>,
     framebuffer: FrameBuffer,
     quad: GeometryBuffer,
     width: i32,
     height: i32,
-    noise: Rc<RefCell<GpuTexture>>,
-    kernel: [Vector3<f32>; KERNEL_SIZE],
-    radius: f32,
+    render_calls: u64,
+    ao_map: Rc<RefCell<GpuTexture>>,
 }
 
 impl ScreenSpaceAmbientOcclusionRenderer {
@@ -105,7 +147,7 @@ impl ScreenSpaceAmbientOcclusionRenderer {
             let mut texture = GpuTexture::new(
                 state,
                 kind,
-                PixelKind::R32F,
+                PixelKind::RGBA16F,
                 MinificationFilter::Nearest,
                 MagnificationFilter::Nearest,
                 1,
@@ -113,16 +155,20 @@ impl ScreenSpaceAmbientOcclusionRenderer {
             )?;
             texture
                 .bind_mut(state, 0)
-                .set_minification_filter(MinificationFilter::Nearest)
-                .set_magnification_filter(MagnificationFilter::Nearest);
+                .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+                .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
             texture
         };
 
-        let mut rng = crate::rand::thread_rng();
+        let blur = Blur::new(state, width, height)?;
+        let ao_map = blur.result();
 
         Ok(Self {
-            blur: Blur::new(state, width, height)?,
+            depth_pyramid: DepthPyramid::new(state, width, height)?,
+            blur,
             shader: Shader::new(state)?,
+            temporal_shader: TemporalShader::new(state)?,
+            histories: Default::default(),
             framebuffer: FrameBuffer::new(
                 state,
                 None,
@@ -138,109 +184,81 @@ impl ScreenSpaceAmbientOcclusionRenderer {
             )?,
             width: width as i32,
             height: height as i32,
-            kernel: {
-                let mut kernel = [Default::default(); KERNEL_SIZE];
-                for (i, v) in kernel.iter_mut().enumerate() {
-                    let k = i as f32 / KERNEL_SIZE as f32;
-                    let scale = lerpf(0.1, 1.0, k * k);
-                    *v = Vector3::new(
-                        rng.gen_range(-1.0..1.0),
-                        rng.gen_range(-1.0..1.0),
-                        rng.gen_range(0.0..1.0),
-                    )
-                    // Make sphere
-                    .try_normalize(f32::EPSILON)
-                    .unwrap()
-                    // Use non-uniform distribution to shuffle points inside hemisphere.
-                    .scale(scale);
-                }
-                kernel
-            },
-            noise: Rc::new(RefCell::new({
-                const RGB_PIXEL_SIZE: usize = 3;
-                let mut pixels = [0u8; RGB_PIXEL_SIZE * NOISE_SIZE * NOISE_SIZE];
-                for pixel in pixels.chunks_exact_mut(RGB_PIXEL_SIZE) {
-                    pixel[0] = rng.gen_range(0u8..255u8); // R
-                    pixel[1] = rng.gen_range(0u8..255u8); // G
-                    pixel[2] = 0u8; // B
-                }
-                let kind = GpuTextureKind::Rectangle {
-                    width: NOISE_SIZE,
-                    height: NOISE_SIZE,
-                };
-                let mut texture = GpuTexture::new(
-                    state,
-                    kind,
-                    PixelKind::RGB8,
-                    MinificationFilter::Nearest,
-                    MagnificationFilter::Nearest,
-                    1,
-                    Some(&pixels),
-                )?;
-                texture
-                    .bind_mut(state, 0)
-                    .set_wrap(Coordinate::S, WrapMode::Repeat)
-                    .set_wrap(Coordinate::T, WrapMode::Repeat);
-                texture
-            })),
-            radius: 0.5,
+            render_calls: 0,
+            ao_map,
         })
     }
 
-    pub fn set_radius(&mut self, radius: f32) {
-        self.radius = radius.abs();
-    }
-
-    fn raw_ao_map(&self) -> Rc<RefCell<GpuTexture>> {
-        self.framebuffer.color_attachments()[0].texture.clone()
-    }
-
+    /// Returns ambient occlusion of the last rendered camera. The texture contains bent normals
+    /// (in world space, packed in `[0; 1]` range) in RGB channels and visibility in alpha channel.
     pub fn ao_map(&self) -> Rc<RefCell<GpuTexture>> {
-        self.blur.result()
+        self.ao_map.clone()
     }
 
     pub(crate) fn render(
         &mut self,
-        state: &PipelineState,
-        gbuffer: &GBuffer,
-        projection_matrix: Matrix4<f32>,
-        view_matrix: Matrix3<f32>,
+        ctx: AmbientOcclusionContext,
     ) -> Result<RenderPassStatistics, FrameworkError> {
         scope_profile!();
 
+        let AmbientOcclusionContext {
+            state,
+            gbuffer,
+            camera_key,
+            projection_matrix,
+            view_matrix,
+            radius,
+            quality,
+        } = ctx;
+
         let mut stats = RenderPassStatistics::default();
 
-        let viewport = Rect::new(0, 0, self.width, self.height);
+        self.render_calls += 1;
+        let render_calls = self.render_calls;
+        self.histories
+            .retain(|_, history| render_calls - history.last_used <= MAX_HISTORY_IDLE_RENDER_CALLS);
+
+        let history = if quality.temporal_filtering() {
+            let history = match self.histories.entry(camera_key) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(TemporalHistory::new(
+                    state,
+                    self.width as usize,
+                    self.height as usize,
+                )?),
+            };
+            history.last_used = render_calls;
+            history.frame_index = history.frame_index.wrapping_add(1);
+            Some(history)
+        } else {
+            None
+        };
+        // Rotation of slices repeats every 64 frames, it is more than enough for accumulation.
+        let frame_index = history
+            .as_ref()
+            .map_or(0, |history| history.frame_index % 64);
+
+        let inv_projection_matrix = projection_matrix.try_inverse().unwrap_or_default();
 
-        let frame_matrix = Matrix4::new_orthographic(
-            0.0,
-            viewport.w() as f32,
-            viewport.h() as f32,
-            0.0,
-            -1.0,
-            1.0,
-        ) * Matrix4::new_nonuniform_scaling(&Vector3::new(
-            viewport.w() as f32,
-            viewport.h() as f32,
-            0.0,
-        ));
+        stats += self
+            .depth_pyramid
+            .render(state, gbuffer.depth(), &inv_projection_matrix)?;
+
+        let viewport = Rect::new(0, 0, self.width, self.height);
 
         self.framebuffer.clear(
             state,
             viewport,
             Some(Color::from_rgba(0, 0, 0, 0)),
-            Some(1.0),
+            None,
             None,
         );
 
         let shader = &self.shader;
-        let noise = &self.noise;
-        let kernel = &self.kernel;
-        let noise_scale = Vector2::new(
-            self.width as f32 / NOISE_SIZE as f32,
-            self.height as f32 / NOISE_SIZE as f32,
-        );
-        let radius = self.radius;
+        let depth_pyramid = self.depth_pyramid.texture();
+        let max_mip_level = self.depth_pyramid.max_mip_level() as f32;
+        let view_basis = view_matrix.basis();
+        let inv_view_basis = view_basis.transpose();
         stats += self.framebuffer.draw(
             &self.quad,
             state,
@@ -258,24 +276,63 @@ impl ScreenSpaceAmbientOcclusionRenderer {
             ElementRange::Full,
             |mut program_binding| {
                 program_binding
-                    .set_texture(&shader.depth_sampler, &gbuffer.depth())
-                    .set_texture(&shader.normal_sampler, &gbuffer.normal_texture())
-                    .set_texture(&shader.noise_sampler, noise)
-                    .set_vector3_slice(&shader.kernel, kernel)
-                    .set_vector2(&shader.noise_scale, &noise_scale)
-                    .set_f32(&shader.radius, radius)
-                    .set_matrix4(&shader.world_view_proj_matrix, &frame_matrix)
-                    .set_matrix4(&shader.projection_matrix, &projection_matrix)
                     .set_matrix4(
-                        &shader.inv_proj_matrix,
-                        &projection_matrix.try_inverse().unwrap_or_default(),
+                        &shader.world_view_proj_matrix,
+                        &make_viewport_matrix(viewport),
                     )
-                    .set_matrix3(&shader.view_matrix, &view_matrix);
+                    .set_texture(&shader.depth_pyramid, &depth_pyramid)
+                    .set_texture(&shader.normal_sampler, &gbuffer.normal_texture())
+                    .set_f32(&shader.radius, radius.abs())
+                    .set_i32(&shader.slice_count, quality.slice_count() as i32)
+                    .set_i32(&shader.steps_per_side, quality.steps_per_side() as i32)
+                    .set_f32(&shader.max_mip_level, max_mip_level)
+                    .set_f32(&shader.frame_index, frame_index as f32)
+                    .set_matrix4(&shader.projection_matrix, &projection_matrix)
+                    .set_matrix4(&shader.inv_proj_matrix, &inv_projection_matrix)
+                    .set_matrix3(&shader.view_matrix, &view_basis)
+                    .set_matrix3(&shader.inv_view_matrix, &inv_view_basis);
             },
         )?;
 
-        self.blur.render(state, self.raw_ao_map())?;
+        let raw_ao_map = self.framebuffer.color_attachments()[0].texture.clone();
+        stats += self.blur.render(state, raw_ao_map, depth_pyramid.clone())?;
+
+        self.ao_map = if let Some(history) = history {
+            stats += history.render(TemporalFilterContext {
+                state,
+                shader: &self.temporal_shader,
+                quad: &self.quad,
+                current: self.blur.result(),
+                depth_pyramid,
+                view_matrix,
+                projection_matrix,
+            })?;
+            history.result()
+        } else {
+            self.blur.result()
+        };
 
         Ok(stats)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::renderer::AmbientOcclusionQuality;
+
+    #[test]
+    fn test_quality_tiers_are_ordered() {
+        let tiers = [
+            AmbientOcclusionQuality::Low,
+            AmbientOcclusionQuality::Medium,
+            AmbientOcclusionQuality::High,
+            AmbientOcclusionQuality::Ultra,
+        ];
+        for pair in tiers.windows(2) {
+            let samples = |q: AmbientOcclusionQuality| q.slice_count() * q.steps_per_side();
+            assert!(samples(pair[0]) < samples(pair[1]));
+        }
+        assert!(!AmbientOcclusionQuality::Low.temporal_filtering());
+        assert!(AmbientOcclusionQuality::default().temporal_filtering());
+    }
+}
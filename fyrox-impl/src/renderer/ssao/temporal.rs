@@ -0,0 +1,239 @@
+use crate::{
+    core::{algebra::Matrix4, math::Rect, scope_profile, sstorage::ImmutableString},
+    renderer::{
+        framework::{
+            error::FrameworkError,
+            framebuffer::{Attachment, AttachmentKind, DrawParameters, FrameBuffer},
+            geometry_buffer::{DrawCallStatistics, ElementRange, GeometryBuffer},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
+                PixelKind, WrapMode,
+            },
+            state::PipelineState,
+        },
+        make_viewport_matrix,
+    },
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// Weight of the history, the higher the value the smoother (and the more laggy) the result is.
+const HISTORY_WEIGHT: f32 = 0.9;
+
+pub struct TemporalShader {
+    program: GpuProgram,
+    world_view_projection_matrix: UniformLocation,
+    current_texture: UniformLocation,
+    history_texture: UniformLocation,
+    history_depth_texture: UniformLocation,
+    depth_pyramid: UniformLocation,
+    history_weight: UniformLocation,
+    inv_proj_matrix: UniformLocation,
+    inv_view_matrix: UniformLocation,
+    previous_view_matrix: UniformLocation,
+    previous_projection_matrix: UniformLocation,
+}
+
+impl TemporalShader {
+    pub fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("../shaders/ssao_temporal_fs.glsl");
+        let vertex_source = include_str!("../shaders/ssao_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "SsaoTemporalShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            world_view_projection_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            current_texture: program
+                .uniform_location(state, &ImmutableString::new("currentTexture"))?,
+            history_texture: program
+                .uniform_location(state, &ImmutableString::new("historyTexture"))?,
+            history_depth_texture: program
+                .uniform_location(state, &ImmutableString::new("historyDepthTexture"))?,
+            depth_pyramid: program
+                .uniform_location(state, &ImmutableString::new("depthPyramid"))?,
+            history_weight: program
+                .uniform_location(state, &ImmutableString::new("historyWeight"))?,
+            inv_proj_matrix: program
+                .uniform_location(state, &ImmutableString::new("inverseProjectionMatrix"))?,
+            inv_view_matrix: program
+                .uniform_location(state, &ImmutableString::new("inverseViewMatrix"))?,
+            previous_view_matrix: program
+                .uniform_location(state, &ImmutableString::new("previousViewMatrix"))?,
+            previous_projection_matrix: program
+                .uniform_location(state, &ImmutableString::new("previousProjectionMatrix"))?,
+            program,
+        })
+    }
+}
+
+fn make_history_framebuffer(
+    state: &PipelineState,
+    width: usize,
+    height: usize,
+) -> Result<FrameBuffer, FrameworkError> {
+    let make_texture = |pixel_kind, filter| {
+        let mut texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle { width, height },
+            pixel_kind,
+            MinificationFilter::Nearest,
+            filter,
+            1,
+            None,
+        )?;
+        texture
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+        Ok::<_, FrameworkError>(Rc::new(RefCell::new(texture)))
+    };
+
+    let framebuffer = FrameBuffer::new(
+        state,
+        None,
+        vec![
+            Attachment {
+                kind: AttachmentKind::Color,
+                texture: make_texture(PixelKind::RGBA16F, MagnificationFilter::Linear)?,
+            },
+            Attachment {
+                kind: AttachmentKind::Color,
+                texture: make_texture(PixelKind::R32F, MagnificationFilter::Nearest)?,
+            },
+        ],
+    )?;
+    framebuffer.set_name(state, "Ambient Occlusion History");
+    Ok(framebuffer)
+}
+
+/// Accumulated ambient occlusion of a single camera. Contains two frame buffers, one of them
+/// contains the result of the previous frame and the other one receives the result of the current
+/// frame, they're swapped every frame.
+pub struct TemporalHistory {
+    framebuffers: [FrameBuffer; 2],
+    width: usize,
+    height: usize,
+    current: usize,
+    previous_view_matrix: Matrix4<f32>,
+    previous_projection_matrix: Matrix4<f32>,
+    is_valid: bool,
+    /// Incremented every frame, it is used to rotate the directions of slices.
+    pub frame_index: u32,
+    /// Index of the last render call, that used the history. Unused histories are destroyed.
+    pub last_used: u64,
+}
+
+pub(crate) struct TemporalFilterContext<'a> {
+    pub state: &'a PipelineState,
+    pub shader: &'a TemporalShader,
+    pub quad: &'a GeometryBuffer,
+    pub current: Rc<RefCell<GpuTexture>>,
+    pub depth_pyramid: Rc<RefCell<GpuTexture>>,
+    pub view_matrix: Matrix4<f32>,
+    pub projection_matrix: Matrix4<f32>,
+}
+
+impl TemporalHistory {
+    pub fn new(state: &PipelineState, width: usize, height: usize) -> Result<Self, FrameworkError> {
+        Ok(Self {
+            framebuffers: [
+                make_history_framebuffer(state, width, height)?,
+                make_history_framebuffer(state, width, height)?,
+            ],
+            width,
+            height,
+            current: 0,
+            previous_view_matrix: Matrix4::identity(),
+            previous_projection_matrix: Matrix4::identity(),
+            is_valid: false,
+            frame_index: 0,
+            last_used: 0,
+        })
+    }
+
+    /// Returns the result of the last filtering.
+    pub fn result(&self) -> Rc<RefCell<GpuTexture>> {
+        self.framebuffers[self.current].color_attachments()[0]
+            .texture
+            .clone()
+    }
+
+    pub(crate) fn render(
+        &mut self,
+        ctx: TemporalFilterContext,
+    ) -> Result<DrawCallStatistics, FrameworkError> {
+        scope_profile!();
+
+        let TemporalFilterContext {
+            state,
+            shader,
+            quad,
+            current,
+            depth_pyramid,
+            view_matrix,
+            projection_matrix,
+        } = ctx;
+
+        let previous = self.current;
+        self.current = 1 - self.current;
+
+        let history = self.framebuffers[previous].color_attachments()[0]
+            .texture
+            .clone();
+        let history_depth = self.framebuffers[previous].color_attachments()[1]
+            .texture
+            .clone();
+        let history_weight = if self.is_valid { HISTORY_WEIGHT } else { 0.0 };
+        let previous_view_matrix = self.previous_view_matrix;
+        let previous_projection_matrix = self.previous_projection_matrix;
+
+        let viewport = Rect::new(0, 0, self.width as i32, self.height as i32);
+        let stats = self.framebuffers[self.current].draw(
+            quad,
+            state,
+            viewport,
+            &shader.program,
+            &DrawParameters {
+                cull_face: None,
+                color_write: Default::default(),
+                depth_write: false,
+                stencil_test: None,
+                depth_test: false,
+                blend: None,
+                stencil_op: Default::default(),
+            },
+            ElementRange::Full,
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(
+                        &shader.world_view_projection_matrix,
+                        &make_viewport_matrix(viewport),
+                    )
+                    .set_texture(&shader.current_texture, &current)
+                    .set_texture(&shader.history_texture, &history)
+                    .set_texture(&shader.history_depth_texture, &history_depth)
+                    .set_texture(&shader.depth_pyramid, &depth_pyramid)
+                    .set_f32(&shader.history_weight, history_weight)
+                    .set_matrix4(
+                        &shader.inv_proj_matrix,
+                        &projection_matrix.try_inverse().unwrap_or_default(),
+                    )
+                    .set_matrix4(
+                        &shader.inv_view_matrix,
+                        &view_matrix.try_inverse().unwrap_or_default(),
+                    )
+                    .set_matrix4(&shader.previous_view_matrix, &previous_view_matrix)
+                    .set_matrix4(
+                        &shader.previous_projection_matrix,
+                        &previous_projection_matrix,
+                    );
+            },
+        )?;
+
+        self.previous_view_matrix = view_matrix;
+        self.previous_projection_matrix = projection_matrix;
+        self.is_valid = true;
+
+        Ok(stats)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::renderer::framework::geometry_buffer::DrawCallStatistics;
+use crate::renderer::{cache::CacheStatistics, framework::geometry_buffer::DrawCallStatistics};
 use fyrox_core::instant;
 use std::fmt::{Display, Formatter};
 use std::ops::AddAssign;
@@ -83,6 +83,11 @@ pub struct LightingStatistics {
     pub spot_shadow_maps_rendered: usize,
     /// How many directional lights were rendered.
     pub directional_lights_rendered: usize,
+    /// How many area lights were rendered.
+    pub area_lights_rendered: usize,
+    /// How many volumetric light shafts (ray-marched volumes or screen-space god rays) were
+    /// rendered.
+    pub light_shafts_rendered: usize,
 }
 
 impl AddAssign for LightingStatistics {
@@ -92,6 +97,8 @@ impl AddAssign for LightingStatistics {
         self.spot_lights_rendered += rhs.spot_lights_rendered;
         self.spot_shadow_maps_rendered += rhs.spot_shadow_maps_rendered;
         self.directional_lights_rendered += rhs.directional_lights_rendered;
+        self.area_lights_rendered += rhs.area_lights_rendered;
+        self.light_shafts_rendered += rhs.light_shafts_rendered;
         self.csm_rendered += rhs.csm_rendered;
     }
 }
@@ -104,12 +111,16 @@ impl Display for LightingStatistics {
             \tPoint Lights: {}\n\
             \tSpot Lights: {}\n\
             \tDirectional Lights: {}\n\
+            \tArea Lights: {}\n\
+            \tLight Shafts: {}\n\
             \tPoint Shadow Maps: {}\n\
             \tSpot Shadow Maps: {}\n\
             \tSpot Shadow Maps: {}\n",
             self.point_lights_rendered,
             self.spot_lights_rendered,
             self.directional_lights_rendered,
+            self.area_lights_rendered,
+            self.light_shafts_rendered,
             self.point_shadow_maps_rendered,
             self.spot_shadow_maps_rendered,
             self.csm_rendered
@@ -164,6 +175,17 @@ impl AddAssign<LightingStatistics> for SceneStatistics {
     }
 }
 
+/// Usage statistics of the caches of GPU resources of the renderer.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CacheStatisticsSet {
+    /// Statistics of the cache of GPU textures.
+    pub textures: CacheStatistics,
+    /// Statistics of the cache of GPU geometry buffers.
+    pub geometry: CacheStatistics,
+    /// Statistics of the cache of compiled shaders.
+    pub shaders: CacheStatistics,
+}
+
 /// Renderer statistics for one frame, also includes current frames per second
 /// amount.
 #[derive(Debug, Copy, Clone)]
@@ -11,7 +11,7 @@ use crate::{
     },
     gui::{
         brush::Brush,
-        draw::{CommandTexture, DrawingContext},
+        draw::{Command, CommandTexture, DrawingContext},
     },
     renderer::{
         framework::{
@@ -27,6 +27,7 @@ use crate::{
                 BlendFactor, BlendFunc, ColorMask, CompareFunc, PipelineState, StencilAction,
                 StencilFunc, StencilOp,
             },
+            uniform::{UniformBufferRing, UniformWriter},
         },
         RenderPassStatistics, TextureCache,
     },
@@ -37,22 +38,12 @@ use crate::{
 };
 use std::{cell::RefCell, rc::Rc};
 
+/// Binding point of the uniform block with per-command data.
+const UI_DATA_BINDING: u32 = 0;
+
 struct UiShader {
     program: GpuProgram,
-    wvp_matrix: UniformLocation,
     diffuse_texture: UniformLocation,
-    is_font: UniformLocation,
-    solid_color: UniformLocation,
-    brush_type: UniformLocation,
-    gradient_point_count: UniformLocation,
-    gradient_colors: UniformLocation,
-    gradient_stops: UniformLocation,
-    gradient_origin: UniformLocation,
-    gradient_end: UniformLocation,
-    resolution: UniformLocation,
-    bounds_min: UniformLocation,
-    bounds_max: UniformLocation,
-    opacity: UniformLocation,
 }
 
 impl UiShader {
@@ -60,32 +51,66 @@ impl UiShader {
         let fragment_source = include_str!("shaders/ui_fs.glsl");
         let vertex_source = include_str!("shaders/ui_vs.glsl");
         let program = GpuProgram::from_source(state, "UIShader", vertex_source, fragment_source)?;
+        program.bind_uniform_block(state, "UiData", UI_DATA_BINDING)?;
         Ok(Self {
-            wvp_matrix: program
-                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
             diffuse_texture: program
                 .uniform_location(state, &ImmutableString::new("diffuseTexture"))?,
-            is_font: program.uniform_location(state, &ImmutableString::new("isFont"))?,
-            solid_color: program.uniform_location(state, &ImmutableString::new("solidColor"))?,
-            brush_type: program.uniform_location(state, &ImmutableString::new("brushType"))?,
-            gradient_point_count: program
-                .uniform_location(state, &ImmutableString::new("gradientPointCount"))?,
-            gradient_colors: program
-                .uniform_location(state, &ImmutableString::new("gradientColors"))?,
-            gradient_stops: program
-                .uniform_location(state, &ImmutableString::new("gradientStops"))?,
-            gradient_origin: program
-                .uniform_location(state, &ImmutableString::new("gradientOrigin"))?,
-            gradient_end: program.uniform_location(state, &ImmutableString::new("gradientEnd"))?,
-            bounds_min: program.uniform_location(state, &ImmutableString::new("boundsMin"))?,
-            bounds_max: program.uniform_location(state, &ImmutableString::new("boundsMax"))?,
-            resolution: program.uniform_location(state, &ImmutableString::new("resolution"))?,
-            opacity: program.uniform_location(state, &ImmutableString::new("opacity"))?,
             program,
         })
     }
 }
 
+/// Writes the data of the given command. The order must match the order of the members of `UiData`
+/// uniform block.
+fn write_command_data(
+    writer: &mut UniformWriter,
+    cmd: &Command,
+    ortho: &Matrix4<f32>,
+    resolution: &Vector2<f32>,
+) {
+    let mut raw_stops = [0.0; 16];
+    let mut raw_colors = [Vector4::default(); 16];
+    if let Brush::LinearGradient { stops, .. } | Brush::RadialGradient { stops, .. } = &cmd.brush {
+        for (i, point) in stops.iter().enumerate() {
+            raw_stops[i] = point.stop;
+            raw_colors[i] = point.color.as_frgba();
+        }
+    }
+
+    let (gradient_origin, gradient_end) = match cmd.brush {
+        Brush::Solid(_) => (Vector2::default(), Vector2::default()),
+        Brush::LinearGradient { from, to, .. } => (from, to),
+        Brush::RadialGradient { center, .. } => (center, Vector2::default()),
+    };
+
+    writer
+        .write_matrix4(ortho)
+        .write_srgb_color(&match cmd.brush {
+            Brush::Solid(color) => color,
+            _ => Color::WHITE,
+        })
+        .write_vector4_array(&raw_colors)
+        .write_f32_array(&raw_stops)
+        .write_vector2(&gradient_origin)
+        .write_vector2(&gradient_end)
+        .write_vector2(resolution)
+        .write_vector2(&cmd.bounds.position)
+        .write_vector2(&cmd.bounds.right_bottom_corner())
+        .write_bool(matches!(cmd.texture, CommandTexture::Font { .. }))
+        .write_f32(cmd.opacity)
+        .write_i32(match cmd.brush {
+            Brush::Solid(_) => 0,
+            Brush::LinearGradient { .. } => 1,
+            Brush::RadialGradient { .. } => 2,
+        })
+        .write_i32(match &cmd.brush {
+            Brush::Solid(_) => 0,
+            Brush::LinearGradient { stops, .. } | Brush::RadialGradient { stops, .. } => {
+                stops.len() as i32
+            }
+        });
+}
+
 /// User interface renderer allows you to render drawing context in specified render target.
 pub struct UiRenderer {
     shader: UiShader,
@@ -111,6 +136,8 @@ pub struct UiRenderContext<'a, 'b, 'c> {
     pub white_dummy: Rc<RefCell<GpuTexture>>,
     /// GPU texture cache.
     pub texture_cache: &'a mut TextureCache,
+    /// Uniform buffer ring, that is used to pass per-command data to the shader.
+    pub uniform_buffer_ring: &'a mut UniformBufferRing,
 }
 
 impl UiRenderer {
@@ -181,6 +208,7 @@ impl UiRenderer {
             drawing_context,
             white_dummy,
             texture_cache,
+            uniform_buffer_ring,
         } = args;
 
         let mut statistics = RenderPassStatistics::default();
@@ -194,11 +222,25 @@ impl UiRenderer {
         let ortho = Matrix4::new_orthographic(0.0, frame_width, frame_height, 0.0, -1.0, 1.0);
         let resolution = Vector2::new(frame_width, frame_height);
 
+        // Upload the data of all the commands at once.
+        let allocations = drawing_context
+            .get_commands()
+            .iter()
+            .map(|cmd| {
+                uniform_buffer_ring
+                    .write(|writer| write_command_data(writer, cmd, &ortho, &resolution))
+            })
+            .collect::<Vec<_>>();
+        uniform_buffer_ring.upload(state);
+
         state.set_scissor_test(true);
 
-        for cmd in drawing_context.get_commands() {
+        for (cmd, allocation) in drawing_context
+            .get_commands()
+            .iter()
+            .zip(allocations.iter())
+        {
             let mut diffuse_texture = &white_dummy;
-            let mut is_font_texture = false;
 
             let mut clip_bounds = cmd.clip_bounds;
             clip_bounds.position.x = clip_bounds.position.x.floor();
@@ -250,7 +292,11 @@ impl UiRenderer {
                     },
                     ElementRange::Full,
                     |mut program_binding| {
-                        program_binding.set_matrix4(&self.shader.wvp_matrix, &ortho);
+                        program_binding.bind_uniform_allocation(
+                            uniform_buffer_ring,
+                            UI_DATA_BINDING,
+                            allocation,
+                        );
                     },
                 )?;
 
@@ -302,7 +348,6 @@ impl UiRenderer {
                             ) {
                                 diffuse_texture = texture;
                             }
-                            is_font_texture = true;
                         }
                     }
                 }
@@ -316,16 +361,6 @@ impl UiRenderer {
                 _ => (),
             }
 
-            let mut raw_stops = [0.0; 16];
-            let mut raw_colors = [Vector4::default(); 16];
-            let bounds_max = cmd.bounds.right_bottom_corner();
-
-            let (gradient_origin, gradient_end) = match cmd.brush {
-                Brush::Solid(_) => (Vector2::default(), Vector2::default()),
-                Brush::LinearGradient { from, to, .. } => (from, to),
-                Brush::RadialGradient { center, .. } => (center, Vector2::default()),
-            };
-
             let params = DrawParameters {
                 cull_face: None,
                 color_write: ColorMask::all(true),
@@ -353,63 +388,7 @@ impl UiRenderer {
                 |mut program_binding| {
                     program_binding
                         .set_texture(&shader.diffuse_texture, diffuse_texture)
-                        .set_matrix4(&shader.wvp_matrix, &ortho)
-                        .set_vector2(&shader.resolution, &resolution)
-                        .set_vector2(&shader.bounds_min, &cmd.bounds.position)
-                        .set_vector2(&shader.bounds_max, &bounds_max)
-                        .set_bool(&shader.is_font, is_font_texture)
-                        .set_i32(
-                            &shader.brush_type,
-                            match cmd.brush {
-                                Brush::Solid(_) => 0,
-                                Brush::LinearGradient { .. } => 1,
-                                Brush::RadialGradient { .. } => 2,
-                            },
-                        )
-                        .set_srgb_color(
-                            &shader.solid_color,
-                            &match cmd.brush {
-                                Brush::Solid(color) => color,
-                                _ => Color::WHITE,
-                            },
-                        )
-                        .set_vector2(&shader.gradient_origin, &gradient_origin)
-                        .set_vector2(&shader.gradient_end, &gradient_end)
-                        .set_i32(
-                            &shader.gradient_point_count,
-                            match &cmd.brush {
-                                Brush::Solid(_) => 0,
-                                Brush::LinearGradient { stops, .. }
-                                | Brush::RadialGradient { stops, .. } => stops.len() as i32,
-                            },
-                        )
-                        .set_f32_slice(
-                            &shader.gradient_stops,
-                            match &cmd.brush {
-                                Brush::Solid(_) => &raw_stops,
-                                Brush::LinearGradient { stops, .. }
-                                | Brush::RadialGradient { stops, .. } => {
-                                    for (i, point) in stops.iter().enumerate() {
-                                        raw_stops[i] = point.stop;
-                                    }
-                                    &raw_stops
-                                }
-                            },
-                        )
-                        .set_vector4_slice(
-                            &shader.gradient_colors,
-                            match &cmd.brush {
-                                Brush::Solid(_) => &raw_colors,
-                                Brush::LinearGradient { stops, .. }
-                                | Brush::RadialGradient { stops, .. } => {
-                                    for (i, point) in stops.iter().enumerate() {
-                                        raw_colors[i] = point.color.as_frgba();
-                                    }
-                                    &raw_colors
-                                }
-                            },
-                        )
-                        .set_f32(&shader.opacity, cmd.opacity);
+                        .bind_uniform_allocation(uniform_buffer_ring, UI_DATA_BINDING, allocation);
                 },
             )?;
         }
@@ -0,0 +1,521 @@
+//! Volumetric light shafts of spot and directional lights. Shafts are accumulated in a separate
+//! HDR buffer during the lighting pass (right after a shadow map of a light was rendered) and
+//! then added to the scene frame after the forward pass, so transparent objects do not hide the
+//! in-scattered light in front of them. See [`VolumetricLightQuality`] docs for available
+//! rendering methods.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector2, Vector3, Vector4},
+        color::Color,
+        math::Rect,
+        scope_profile,
+        sstorage::ImmutableString,
+    },
+    renderer::{
+        flat_shader::FlatShader,
+        framework::{
+            error::FrameworkError,
+            framebuffer::{
+                Attachment, AttachmentKind, BlendParameters, DrawParameters, FrameBuffer,
+            },
+            geometry_buffer::{DrawCallStatistics, ElementRange, GeometryBuffer},
+            gpu_program::{GpuProgram, UniformLocation},
+            gpu_texture::{
+                Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
+                PixelKind, WrapMode,
+            },
+            state::{BlendFactor, BlendFunc, PipelineState},
+        },
+        make_viewport_matrix,
+        shadow::csm::Cascade,
+        VolumetricLightQuality,
+    },
+    scene::light::{directional::DirectionalLight, spot::SpotLight},
+};
+use std::{cell::RefCell, rc::Rc};
+
+struct SpotShaftShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    depth_sampler: UniformLocation,
+    shadow_sampler: UniformLocation,
+    inv_view_proj_matrix: UniformLocation,
+    light_view_proj_matrix: UniformLocation,
+    camera_position: UniformLocation,
+    light_position: UniformLocation,
+    light_direction: UniformLocation,
+    light_radius: UniformLocation,
+    half_hotspot_cone_angle_cos: UniformLocation,
+    half_cone_angle_cos: UniformLocation,
+    light_color: UniformLocation,
+    scatter_factor: UniformLocation,
+    intensity: UniformLocation,
+    shadows_enabled: UniformLocation,
+    shadow_bias: UniformLocation,
+    step_count: UniformLocation,
+}
+
+impl SpotShaftShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/spot_shaft_fs.glsl");
+        let vertex_source = include_str!("shaders/flat_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "SpotShaftShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_sampler: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            shadow_sampler: program
+                .uniform_location(state, &ImmutableString::new("spotShadowTexture"))?,
+            inv_view_proj_matrix: program
+                .uniform_location(state, &ImmutableString::new("invViewProj"))?,
+            light_view_proj_matrix: program
+                .uniform_location(state, &ImmutableString::new("lightViewProjMatrix"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
+            light_position: program
+                .uniform_location(state, &ImmutableString::new("lightPosition"))?,
+            light_direction: program
+                .uniform_location(state, &ImmutableString::new("lightDirection"))?,
+            light_radius: program.uniform_location(state, &ImmutableString::new("lightRadius"))?,
+            half_hotspot_cone_angle_cos: program
+                .uniform_location(state, &ImmutableString::new("halfHotspotConeAngleCos"))?,
+            half_cone_angle_cos: program
+                .uniform_location(state, &ImmutableString::new("halfConeAngleCos"))?,
+            light_color: program.uniform_location(state, &ImmutableString::new("lightColor"))?,
+            scatter_factor: program
+                .uniform_location(state, &ImmutableString::new("scatterFactor"))?,
+            intensity: program.uniform_location(state, &ImmutableString::new("intensity"))?,
+            shadows_enabled: program
+                .uniform_location(state, &ImmutableString::new("shadowsEnabled"))?,
+            shadow_bias: program.uniform_location(state, &ImmutableString::new("shadowBias"))?,
+            step_count: program.uniform_location(state, &ImmutableString::new("stepCount"))?,
+            program,
+        })
+    }
+}
+
+struct DirectionalShaftShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    depth_sampler: UniformLocation,
+    shadow_cascade0: UniformLocation,
+    shadow_cascade1: UniformLocation,
+    shadow_cascade2: UniformLocation,
+    cascade_distances: UniformLocation,
+    light_view_proj_matrices: UniformLocation,
+    inv_view_proj_matrix: UniformLocation,
+    view_matrix: UniformLocation,
+    camera_position: UniformLocation,
+    light_direction: UniformLocation,
+    light_color: UniformLocation,
+    scatter_factor: UniformLocation,
+    intensity: UniformLocation,
+    shadow_bias: UniformLocation,
+    max_distance: UniformLocation,
+    step_count: UniformLocation,
+}
+
+impl DirectionalShaftShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/directional_shaft_fs.glsl");
+        let vertex_source = include_str!("shaders/flat_vs.glsl");
+        let program = GpuProgram::from_source(
+            state,
+            "DirectionalShaftShader",
+            vertex_source,
+            fragment_source,
+        )?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_sampler: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            shadow_cascade0: program
+                .uniform_location(state, &ImmutableString::new("shadowCascade0"))?,
+            shadow_cascade1: program
+                .uniform_location(state, &ImmutableString::new("shadowCascade1"))?,
+            shadow_cascade2: program
+                .uniform_location(state, &ImmutableString::new("shadowCascade2"))?,
+            cascade_distances: program
+                .uniform_location(state, &ImmutableString::new("cascadeDistances"))?,
+            light_view_proj_matrices: program
+                .uniform_location(state, &ImmutableString::new("lightViewProjMatrices"))?,
+            inv_view_proj_matrix: program
+                .uniform_location(state, &ImmutableString::new("invViewProj"))?,
+            view_matrix: program.uniform_location(state, &ImmutableString::new("viewMatrix"))?,
+            camera_position: program
+                .uniform_location(state, &ImmutableString::new("cameraPosition"))?,
+            light_direction: program
+                .uniform_location(state, &ImmutableString::new("lightDirection"))?,
+            light_color: program.uniform_location(state, &ImmutableString::new("lightColor"))?,
+            scatter_factor: program
+                .uniform_location(state, &ImmutableString::new("scatterFactor"))?,
+            intensity: program.uniform_location(state, &ImmutableString::new("intensity"))?,
+            shadow_bias: program.uniform_location(state, &ImmutableString::new("shadowBias"))?,
+            max_distance: program.uniform_location(state, &ImmutableString::new("maxDistance"))?,
+            step_count: program.uniform_location(state, &ImmutableString::new("stepCount"))?,
+            program,
+        })
+    }
+}
+
+struct GodRaysShader {
+    program: GpuProgram,
+    wvp_matrix: UniformLocation,
+    depth_sampler: UniformLocation,
+    light_screen_position: UniformLocation,
+    light_color: UniformLocation,
+    scatter_factor: UniformLocation,
+    intensity: UniformLocation,
+    max_distance: UniformLocation,
+    visibility: UniformLocation,
+}
+
+impl GodRaysShader {
+    fn new(state: &PipelineState) -> Result<Self, FrameworkError> {
+        let fragment_source = include_str!("shaders/god_rays_fs.glsl");
+        let vertex_source = include_str!("shaders/flat_vs.glsl");
+        let program =
+            GpuProgram::from_source(state, "GodRaysShader", vertex_source, fragment_source)?;
+        Ok(Self {
+            wvp_matrix: program
+                .uniform_location(state, &ImmutableString::new("worldViewProjection"))?,
+            depth_sampler: program
+                .uniform_location(state, &ImmutableString::new("depthTexture"))?,
+            light_screen_position: program
+                .uniform_location(state, &ImmutableString::new("lightScreenPosition"))?,
+            light_color: program.uniform_location(state, &ImmutableString::new("lightColor"))?,
+            scatter_factor: program
+                .uniform_location(state, &ImmutableString::new("scatterFactor"))?,
+            intensity: program.uniform_location(state, &ImmutableString::new("intensity"))?,
+            max_distance: program.uniform_location(state, &ImmutableString::new("maxDistance"))?,
+            visibility: program.uniform_location(state, &ImmutableString::new("visibility"))?,
+            program,
+        })
+    }
+}
+
+/// Camera-related data, that is shared across all shafts of a frame.
+pub(crate) struct ShaftCameraData {
+    pub depth: Rc<RefCell<GpuTexture>>,
+    pub view_matrix: Matrix4<f32>,
+    pub view_projection: Matrix4<f32>,
+    pub inv_view_projection: Matrix4<f32>,
+    pub position: Vector3<f32>,
+    pub look: Vector3<f32>,
+    pub z_far: f32,
+}
+
+/// Max distance (from the camera) of the shafts of directional lights when there's no shadow
+/// cascades.
+const DEFAULT_SHAFTS_DISTANCE: f32 = 64.0;
+
+fn additive_draw_params() -> DrawParameters {
+    DrawParameters {
+        cull_face: None,
+        color_write: Default::default(),
+        depth_write: false,
+        stencil_test: None,
+        depth_test: false,
+        blend: Some(BlendParameters {
+            func: BlendFunc::new(BlendFactor::One, BlendFactor::One),
+            ..Default::default()
+        }),
+        stencil_op: Default::default(),
+    }
+}
+
+pub struct VolumetricLightRenderer {
+    spot_shader: SpotShaftShader,
+    directional_shader: DirectionalShaftShader,
+    god_rays_shader: GodRaysShader,
+    framebuffer: FrameBuffer,
+    width: usize,
+    height: usize,
+    // Whether there's something in the buffer since the last clear or not.
+    has_content: bool,
+}
+
+impl VolumetricLightRenderer {
+    pub fn new(state: &PipelineState, width: usize, height: usize) -> Result<Self, FrameworkError> {
+        let mut texture = GpuTexture::new(
+            state,
+            GpuTextureKind::Rectangle { width, height },
+            PixelKind::RGBA16F,
+            MinificationFilter::Nearest,
+            MagnificationFilter::Nearest,
+            1,
+            None,
+        )?;
+        texture
+            .bind_mut(state, 0)
+            .set_wrap(Coordinate::S, WrapMode::ClampToEdge)
+            .set_wrap(Coordinate::T, WrapMode::ClampToEdge);
+
+        Ok(Self {
+            spot_shader: SpotShaftShader::new(state)?,
+            directional_shader: DirectionalShaftShader::new(state)?,
+            god_rays_shader: GodRaysShader::new(state)?,
+            framebuffer: FrameBuffer::new(
+                state,
+                None,
+                vec![Attachment {
+                    kind: AttachmentKind::Color,
+                    texture: Rc::new(RefCell::new(texture)),
+                }],
+            )?,
+            width,
+            height,
+            has_content: false,
+        })
+    }
+
+    fn viewport(&self) -> Rect<i32> {
+        Rect::new(0, 0, self.width as i32, self.height as i32)
+    }
+
+    pub fn result(&self) -> Rc<RefCell<GpuTexture>> {
+        self.framebuffer.color_attachments()[0].texture.clone()
+    }
+
+    pub(crate) fn clear(&mut self, state: &PipelineState) {
+        if self.has_content {
+            let viewport = self.viewport();
+            self.framebuffer.clear(
+                state,
+                viewport,
+                Some(Color::from_rgba(0, 0, 0, 0)),
+                None,
+                None,
+            );
+            self.has_content = false;
+        }
+    }
+
+    /// Ray-marches the volume of the spot light. `shadow_map` is a shadow map of the light and its
+    /// view-projection matrix, if the light casts shadows.
+    pub(crate) fn render_spot_shaft(
+        &mut self,
+        state: &PipelineState,
+        quad: &GeometryBuffer,
+        camera: &ShaftCameraData,
+        quality: VolumetricLightQuality,
+        spot: &SpotLight,
+        radius: f32,
+        shadow_map: Option<(Rc<RefCell<GpuTexture>>, Matrix4<f32>)>,
+    ) -> Result<DrawCallStatistics, FrameworkError> {
+        scope_profile!();
+
+        let Some(steps) = quality.ray_march_steps() else {
+            return Ok(Default::default());
+        };
+
+        self.has_content = true;
+
+        let viewport = self.viewport();
+        let shader = &self.spot_shader;
+        let base_light = spot.base_light_ref();
+        let emit_direction = -spot
+            .up_vector()
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector3::y);
+        let (shadows_enabled, shadow_texture, light_view_projection) = match shadow_map {
+            Some((texture, matrix)) => (true, Some(texture), matrix),
+            None => (false, None, Matrix4::identity()),
+        };
+
+        self.framebuffer.draw(
+            quad,
+            state,
+            viewport,
+            &shader.program,
+            &additive_draw_params(),
+            ElementRange::Full,
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(&shader.wvp_matrix, &make_viewport_matrix(viewport))
+                    .set_texture(&shader.depth_sampler, &camera.depth)
+                    .set_matrix4(&shader.inv_view_proj_matrix, &camera.inv_view_projection)
+                    .set_matrix4(&shader.light_view_proj_matrix, &light_view_projection)
+                    .set_vector3(&shader.camera_position, &camera.position)
+                    .set_vector3(&shader.light_position, &spot.global_position())
+                    .set_vector3(&shader.light_direction, &emit_direction)
+                    .set_f32(&shader.light_radius, radius)
+                    .set_f32(
+                        &shader.half_hotspot_cone_angle_cos,
+                        (spot.hotspot_cone_angle() * 0.5).cos(),
+                    )
+                    .set_f32(
+                        &shader.half_cone_angle_cos,
+                        (spot.full_cone_angle() * 0.5).cos(),
+                    )
+                    .set_vector3(
+                        &shader.light_color,
+                        &base_light.color().srgb_to_linear_f32().xyz(),
+                    )
+                    .set_vector3(&shader.scatter_factor, &base_light.scatter())
+                    .set_f32(&shader.intensity, base_light.intensity())
+                    .set_bool(&shader.shadows_enabled, shadows_enabled)
+                    .set_f32(&shader.shadow_bias, spot.shadow_bias())
+                    .set_i32(&shader.step_count, steps as i32);
+                if let Some(shadow_texture) = shadow_texture.as_ref() {
+                    program_binding.set_texture(&shader.shadow_sampler, shadow_texture);
+                }
+            },
+        )
+    }
+
+    /// Renders light shafts of the directional light. If `cascades` are specified and ray marching
+    /// is enabled, the shafts are ray-marched through the cascades, otherwise screen-space god rays
+    /// are used.
+    pub(crate) fn render_directional_shafts(
+        &mut self,
+        state: &PipelineState,
+        quad: &GeometryBuffer,
+        camera: &ShaftCameraData,
+        quality: VolumetricLightQuality,
+        directional: &DirectionalLight,
+        cascades: Option<&[Cascade]>,
+    ) -> Result<DrawCallStatistics, FrameworkError> {
+        scope_profile!();
+
+        if quality == VolumetricLightQuality::Off {
+            return Ok(Default::default());
+        }
+
+        let viewport = self.viewport();
+        let base_light = directional.base_light_ref();
+        let to_light = directional
+            .up_vector()
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector3::y);
+        let color = base_light.color().srgb_to_linear_f32().xyz();
+        let max_distance = cascades
+            .and_then(|cascades| cascades.last())
+            .map_or(DEFAULT_SHAFTS_DISTANCE, |cascade| cascade.z_far)
+            .min(camera.z_far);
+
+        if let (Some(steps), Some(cascades)) = (quality.ray_march_steps(), cascades) {
+            self.has_content = true;
+
+            let shader = &self.directional_shader;
+            let distances = [cascades[0].z_far, cascades[1].z_far, cascades[2].z_far];
+            let matrices = [
+                cascades[0].view_proj_matrix,
+                cascades[1].view_proj_matrix,
+                cascades[2].view_proj_matrix,
+            ];
+
+            self.framebuffer.draw(
+                quad,
+                state,
+                viewport,
+                &shader.program,
+                &additive_draw_params(),
+                ElementRange::Full,
+                |mut program_binding| {
+                    program_binding
+                        .set_matrix4(&shader.wvp_matrix, &make_viewport_matrix(viewport))
+                        .set_texture(&shader.depth_sampler, &camera.depth)
+                        .set_texture(&shader.shadow_cascade0, &cascades[0].texture())
+                        .set_texture(&shader.shadow_cascade1, &cascades[1].texture())
+                        .set_texture(&shader.shadow_cascade2, &cascades[2].texture())
+                        .set_f32_slice(&shader.cascade_distances, &distances)
+                        .set_matrix4_array(&shader.light_view_proj_matrices, &matrices)
+                        .set_matrix4(&shader.inv_view_proj_matrix, &camera.inv_view_projection)
+                        .set_matrix4(&shader.view_matrix, &camera.view_matrix)
+                        .set_vector3(&shader.camera_position, &camera.position)
+                        .set_vector3(&shader.light_direction, &to_light)
+                        .set_vector3(&shader.light_color, &color)
+                        .set_vector3(&shader.scatter_factor, &base_light.scatter())
+                        .set_f32(&shader.intensity, base_light.intensity())
+                        .set_f32(&shader.shadow_bias, directional.csm_options.shadow_bias())
+                        .set_f32(&shader.max_distance, max_distance)
+                        .set_i32(&shader.step_count, steps as i32);
+                },
+            )
+        } else {
+            // Project a point far away in the direction of the light to get its position on screen.
+            let clip = camera.view_projection
+                * Vector4::new(
+                    camera.position.x + to_light.x * camera.z_far * 0.5,
+                    camera.position.y + to_light.y * camera.z_far * 0.5,
+                    camera.position.z + to_light.z * camera.z_far * 0.5,
+                    1.0,
+                );
+            if clip.w <= 0.0 {
+                // The light is behind the camera.
+                return Ok(Default::default());
+            }
+            let screen_position =
+                Vector2::new(clip.x / clip.w * 0.5 + 0.5, clip.y / clip.w * 0.5 + 0.5);
+
+            // Fade the rays out when the light leaves the screen or the camera turns away from it.
+            let edge_distance = (screen_position.x - 0.5)
+                .abs()
+                .max((screen_position.y - 0.5).abs());
+            let visibility = (1.0 - (edge_distance - 0.5) * 2.0).clamp(0.0, 1.0)
+                * camera.look.dot(&to_light).max(0.0);
+            if visibility <= 0.0 {
+                return Ok(Default::default());
+            }
+
+            self.has_content = true;
+
+            let shader = &self.god_rays_shader;
+            self.framebuffer.draw(
+                quad,
+                state,
+                viewport,
+                &shader.program,
+                &additive_draw_params(),
+                ElementRange::Full,
+                |mut program_binding| {
+                    program_binding
+                        .set_matrix4(&shader.wvp_matrix, &make_viewport_matrix(viewport))
+                        .set_texture(&shader.depth_sampler, &camera.depth)
+                        .set_vector2(&shader.light_screen_position, &screen_position)
+                        .set_vector3(&shader.light_color, &color)
+                        .set_vector3(&shader.scatter_factor, &base_light.scatter())
+                        .set_f32(&shader.intensity, base_light.intensity())
+                        .set_f32(&shader.max_distance, max_distance)
+                        .set_f32(&shader.visibility, visibility);
+                },
+            )
+        }
+    }
+
+    /// Adds accumulated light shafts to the given frame buffer.
+    pub(crate) fn composite(
+        &self,
+        state: &PipelineState,
+        quad: &GeometryBuffer,
+        flat_shader: &FlatShader,
+        frame_buffer: &mut FrameBuffer,
+        viewport: Rect<i32>,
+    ) -> Result<DrawCallStatistics, FrameworkError> {
+        scope_profile!();
+
+        if !self.has_content {
+            return Ok(Default::default());
+        }
+
+        let shafts = self.result();
+        frame_buffer.draw(
+            quad,
+            state,
+            viewport,
+            &flat_shader.program,
+            &additive_draw_params(),
+            ElementRange::Full,
+            |mut program_binding| {
+                program_binding
+                    .set_matrix4(&flat_shader.wvp_matrix, &make_viewport_matrix(viewport))
+                    .set_texture(&flat_shader.diffuse_texture, &shafts);
+            },
+        )
+    }
+}
@@ -1,7 +1,29 @@
-//! Curve resource holds a [`Curve`]
+//! Curve resource holds a [`Curve`]. Curve resources are standalone assets, that could be shared
+//! across many objects and sampled at runtime using [`CurveResourceExtension::sample`]. Typical use
+//! cases are damage falloffs, custom attenuation of sounds, parameters of particle systems, etc.
+//!
+//! ```rust
+//! use fyrox_impl::{
+//!     asset::untyped::ResourceKind,
+//!     core::math::curve::{Curve, CurveKey, CurveKeyKind},
+//!     resource::curve::{CurveResource, CurveResourceExtension},
+//! };
+//!
+//! let falloff = CurveResource::new_curve(
+//!     ResourceKind::Embedded,
+//!     Curve::from(vec![
+//!         CurveKey::new(0.0, 1.0, CurveKeyKind::Linear),
+//!         CurveKey::new(10.0, 0.0, CurveKeyKind::Linear),
+//!     ]),
+//! );
+//!
+//! // Damage at 5 meters from the center of an explosion.
+//! let damage = 100.0 * falloff.sample(5.0);
+//! assert_eq!(damage, 50.0);
+//! ```
 
 use crate::{
-    asset::{io::ResourceIo, Resource, ResourceData, CURVE_RESOURCE_UUID},
+    asset::{io::ResourceIo, untyped::ResourceKind, Resource, ResourceData, CURVE_RESOURCE_UUID},
     core::{
         io::FileLoadError, math::curve::Curve, reflect::prelude::*, uuid::Uuid,
         visitor::prelude::*, TypeUuidProvider,
@@ -74,13 +96,15 @@ impl ResourceData for CurveResourceState {
         <Self as TypeUuidProvider>::type_uuid()
     }
 
-    fn save(&mut self, _path: &Path) -> Result<(), Box<dyn Error>> {
-        // TODO: Add saving.
-        Err("Saving is not supported!".to_string().into())
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.curve.visit("Curve", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
     }
 
     fn can_be_saved(&self) -> bool {
-        false
+        true
     }
 }
 
@@ -99,7 +123,65 @@ impl CurveResourceState {
         curve.visit("Curve", &mut visitor)?;
         Ok(Self { curve })
     }
+
+    /// Returns a value of the curve at the given location.
+    pub fn sample(&self, location: f32) -> f32 {
+        self.curve.value_at(location)
+    }
 }
 
 /// Type alias for curve resources.
 pub type CurveResource = Resource<CurveResourceState>;
+
+/// Extension trait for curve resources.
+pub trait CurveResourceExtension {
+    /// Creates a new curve resource with the given curve.
+    fn new_curve(kind: ResourceKind, curve: Curve) -> Self;
+
+    /// Returns a value of the curve at the given location. Returns `None` if the resource is not
+    /// loaded (yet or due to a loading error).
+    fn try_sample(&self, location: f32) -> Option<f32>;
+
+    /// Returns a value of the curve at the given location. Returns zero if the resource is not
+    /// loaded (yet or due to a loading error), use [`Self::try_sample`] to distinguish this case.
+    fn sample(&self, location: f32) -> f32 {
+        self.try_sample(location).unwrap_or_default()
+    }
+}
+
+impl CurveResourceExtension for CurveResource {
+    fn new_curve(kind: ResourceKind, curve: Curve) -> Self {
+        Resource::new_ok(kind, CurveResourceState { curve })
+    }
+
+    fn try_sample(&self, location: f32) -> Option<f32> {
+        self.state().data().map(|state| state.sample(location))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::untyped::ResourceKind,
+        core::math::curve::{Curve, CurveKey, CurveKeyKind},
+        resource::curve::{CurveResource, CurveResourceExtension},
+    };
+
+    #[test]
+    fn test_curve_resource_sample() {
+        let curve = CurveResource::new_curve(
+            ResourceKind::Embedded,
+            Curve::from(vec![
+                CurveKey::new(0.0, 0.0, CurveKeyKind::Linear),
+                CurveKey::new(2.0, 4.0, CurveKeyKind::Constant),
+                CurveKey::new(3.0, 1.0, CurveKeyKind::Linear),
+            ]),
+        );
+
+        assert_eq!(curve.sample(-1.0), 0.0);
+        assert_eq!(curve.sample(1.0), 2.0);
+        assert_eq!(curve.sample(2.5), 4.0);
+        assert_eq!(curve.sample(10.0), 1.0);
+        assert_eq!(curve.try_sample(1.0), Some(2.0));
+    }
+}
@@ -8,6 +8,7 @@
 
 mod document;
 pub mod error;
+pub mod report;
 mod scene;
 
 use crate::resource::texture::{TextureImportOptions, TextureResource, TextureResourceExtension};
@@ -22,17 +23,23 @@ use crate::{
         pool::Handle,
         sstorage::ImmutableString,
     },
+    generic_animation::{
+        container::{TrackDataContainer, TrackValueKind},
+        value::{ValueBinding, ValueType},
+    },
     graph::BaseSceneGraph,
     material::{shader::SamplerFallback, PropertyValue},
     resource::{
         fbx::{
             document::FbxDocument,
             error::FbxError,
+            report::FbxConversionReport,
             scene::{
                 animation::{FbxAnimationCurveNode, FbxAnimationCurveNodeType},
                 geometry::FbxMeshGeometry,
                 model::FbxModel,
-                FbxComponent, FbxMapping, FbxScene,
+                texture::FbxTexture,
+                FbxComponent, FbxMapping, FbxMaterial, FbxScene,
             },
         },
         model::{MaterialSearchOptions, ModelImportOptions},
@@ -43,7 +50,7 @@ use crate::{
         base::BaseBuilder,
         graph::Graph,
         mesh::{
-            buffer::{VertexAttributeUsage, VertexBuffer, VertexWriteTrait},
+            buffer::{VertexAttributeUsage, VertexBuffer, VertexReadTrait, VertexWriteTrait},
             surface::{
                 BlendShape, BlendShapesContainer, InputBlendShapeData, Surface, SurfaceData,
                 SurfaceResource, VertexWeightSet,
@@ -61,7 +68,11 @@ use crate::{
 use fxhash::{FxHashMap, FxHashSet};
 use fyrox_resource::io::ResourceIo;
 use fyrox_resource::untyped::ResourceKind;
-use std::{cmp::Ordering, path::Path};
+use std::{
+    cmp::Ordering,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
 
 /// Input angles in degrees
 fn quat_from_euler(euler: Vector3<f32>) -> UnitQuaternion<f32> {
@@ -261,6 +272,82 @@ fn make_blend_shapes_container(
     }
 }
 
+/// Searches for a texture, that defines the transform of texture coordinates of a material. FBX
+/// allows every texture to have its own transform, but the engine has only one set of texture
+/// coordinates per surface, so the transform of the diffuse texture is used (if any).
+fn find_texture_transform<'a>(
+    fbx_scene: &'a FbxScene,
+    material: &FbxMaterial,
+    report: &mut FbxConversionReport,
+) -> Result<Option<&'a FbxTexture>, FbxError> {
+    let mut textures = Vec::new();
+    for (name, texture_handle) in material.textures.iter() {
+        textures.push((name, fbx_scene.get(*texture_handle).as_texture()?));
+    }
+
+    let Some(main_texture) = textures
+        .iter()
+        .find(|(name, _)| name.contains("DiffuseColor") || name.contains("diffuse_color"))
+        .or_else(|| textures.iter().find(|(_, t)| !t.has_identity_transform()))
+        .map(|(_, texture)| *texture)
+    else {
+        return Ok(None);
+    };
+
+    for (name, texture) in textures.iter() {
+        if texture.uv_translation != main_texture.uv_translation
+            || texture.uv_scale != main_texture.uv_scale
+        {
+            report.skip(format!("Texture transform of {name}"));
+        }
+    }
+
+    if main_texture.uv_rotation != 0.0 {
+        report.skip("Texture rotation");
+    }
+
+    Ok((!main_texture.has_identity_transform()).then_some(main_texture))
+}
+
+fn apply_texture_transform(surface_data: &mut SurfaceData, texture: &FbxTexture) {
+    let mut vertex_buffer = surface_data.vertex_buffer.modify();
+    for mut view in vertex_buffer.iter_mut() {
+        if let Ok(uv) = view.read_2_f32(VertexAttributeUsage::TexCoord0) {
+            // Vertical coordinate was inverted during conversion, so the transform must be
+            // applied in the original space of the FBX file.
+            let u = uv.x * texture.uv_scale.x + texture.uv_translation.x;
+            let v = 1.0 - ((1.0 - uv.y) * texture.uv_scale.y + texture.uv_translation.y);
+            view.write_2_f32(VertexAttributeUsage::TexCoord0, Vector2::new(u, v))
+                .unwrap();
+        }
+    }
+}
+
+/// Writes the content of an embedded texture to `<model name>.fbm` directory next to the model.
+/// Existing files are not overwritten, so the extracted textures could be edited. Returns the
+/// path of the extracted texture, or `None` if the texture cannot be written.
+fn extract_embedded_texture(
+    model_path: &Path,
+    filename: &OsStr,
+    texture: &FbxTexture,
+) -> Option<PathBuf> {
+    let directory = model_path.with_extension("fbm");
+    let path = directory.join(filename);
+    if !path.exists() {
+        if let Err(e) = std::fs::create_dir_all(&directory)
+            .and_then(|_| std::fs::write(&path, &texture.content))
+        {
+            Log::warn(format!(
+                "Unable to extract an embedded texture to {}. Reason: {:?}",
+                path.display(),
+                e
+            ));
+            return None;
+        }
+    }
+    Some(path)
+}
+
 async fn create_surfaces(
     fbx_scene: &FbxScene,
     data_set: Vec<FbxSurfaceData>,
@@ -268,6 +355,7 @@ async fn create_surfaces(
     model: &FbxModel,
     model_path: &Path,
     model_import_options: &ModelImportOptions,
+    report: &mut FbxConversionReport,
 ) -> Result<Vec<Surface>, FbxError> {
     let mut surfaces = Vec::new();
 
@@ -287,7 +375,11 @@ async fn create_surfaces(
     } else {
         assert_eq!(data_set.len(), model.materials.len());
         for (&material_handle, data) in model.materials.iter().zip(data_set.into_iter()) {
+            let material = fbx_scene.get(material_handle).as_material()?;
             let mut surface_data = data.base_mesh_builder.build();
+            if let Some(texture) = find_texture_transform(fbx_scene, material, report)? {
+                apply_texture_transform(&mut surface_data, texture);
+            }
             surface_data.blend_shapes_container =
                 make_blend_shapes_container(&surface_data.vertex_buffer, data.blend_shapes);
             let mut surface = Surface::new(SurfaceResource::new_ok(
@@ -295,7 +387,6 @@ async fn create_surfaces(
                 surface_data,
             ));
             surface.vertex_weights = data.skin_data;
-            let material = fbx_scene.get(material_handle).as_material()?;
             if let Err(e) = surface.material().data_ref().set_property(
                 &ImmutableString::new("diffuseColor"),
                 PropertyValue::Color(material.diffuse_color),
@@ -362,6 +453,12 @@ async fn create_surfaces(
                     if let Some(texture_path) = texture_path {
                         let texture = if texture.content.is_empty() {
                             resource_manager.request::<Texture>(texture_path.as_path())
+                        } else if let Some(extracted_path) = model_import_options
+                            .extract_embedded_media
+                            .then(|| extract_embedded_texture(model_path, filename, texture))
+                            .flatten()
+                        {
+                            resource_manager.request::<Texture>(extracted_path)
                         } else {
                             TextureResource::load_from_memory(
                                 ResourceKind::External(texture_path.clone()),
@@ -404,6 +501,7 @@ async fn create_surfaces(
                         {
                             Some(("emissionTexture", SamplerFallback::Black))
                         } else {
+                            report.skip(format!("Texture slot {name}"));
                             None
                         };
 
@@ -426,6 +524,7 @@ async fn create_surfaces(
                             }
                         }
                     } else {
+                        report.skip(format!("Missing texture {filename:?}"));
                         Log::writeln(
                             MessageKind::Warning,
                             format!(
@@ -451,6 +550,7 @@ async fn convert_mesh(
     graph: &mut Graph,
     model_path: &Path,
     model_import_options: &ModelImportOptions,
+    report: &mut FbxConversionReport,
 ) -> Result<Handle<Node>, FbxError> {
     let geometric_transform = Matrix4::new_translation(&model.geometric_translation)
         * quat_from_euler(model.geometric_rotation).to_homogeneous()
@@ -469,14 +569,24 @@ async fn convert_mesh(
     for &geom_handle in &model.geoms {
         let geom = fbx_scene.get(geom_handle).as_mesh_geometry()?;
         let skin_data = geom.get_skin_data(fbx_scene)?;
-        let blend_shapes = geom.collect_blend_shapes_refs(fbx_scene)?;
+        let mut blend_shapes = Vec::new();
+        for channel in geom.collect_blend_shapes_refs(fbx_scene)? {
+            if let Some(geometry) = channel.target_geometry() {
+                for _ in 1..channel.geometries.len() {
+                    report.skip("In-between blend shape");
+                }
+                blend_shapes.push((channel, geometry));
+            } else {
+                report.skip(format!("Blend shape {} without a shape", channel.name));
+            }
+        }
 
-        if !mesh_blend_shapes.is_empty() {
-            Log::warn("More than two geoms with blend shapes?");
+        if !mesh_blend_shapes.is_empty() && !blend_shapes.is_empty() {
+            report.skip("Blend shapes of an additional geometry of a model");
         }
         mesh_blend_shapes = blend_shapes
             .iter()
-            .map(|bs| BlendShape {
+            .map(|(bs, _)| BlendShape {
                 weight: bs.deform_percent,
                 name: bs.name.clone(),
             })
@@ -491,7 +601,7 @@ async fn convert_mesh(
                 },
                 blend_shapes: blend_shapes
                     .iter()
-                    .map(|bs_channel| {
+                    .map(|(bs_channel, _)| {
                         InputBlendShapeData {
                             name: bs_channel.name.clone(),
                             default_weight: bs_channel.deform_percent,
@@ -549,12 +659,11 @@ async fn convert_mesh(
 
                     // Fill each blend shape, but modify the vertex first using the "offsets" from blend shapes.
                     assert_eq!(blend_shapes.len(), data.blend_shapes.len());
-                    for (fbx_blend_shape, blend_shape) in
+                    for ((_, blend_shape_geometry), blend_shape) in
                         blend_shapes.iter().zip(data.blend_shapes.iter_mut())
                     {
-                        let blend_shape_geometry = fbx_scene
-                            .get(fbx_blend_shape.geometry)
-                            .as_shape_geometry()?;
+                        let blend_shape_geometry =
+                            fbx_scene.get(*blend_shape_geometry).as_shape_geometry()?;
 
                         // Only certain vertices are affected by a blend shape, because FBX stores only changed
                         // parts ("diff").
@@ -574,7 +683,7 @@ async fn convert_mesh(
                                 );
                             }
                             if let Some(tangents) = blend_shape_geometry.tangents.as_ref() {
-                                blend_shape.normals.insert(
+                                blend_shape.tangents.insert(
                                     final_index as u32,
                                     utils::vec3_f16_from_f32(tangents[*relative_index as usize]),
                                 );
@@ -597,6 +706,7 @@ async fn convert_mesh(
             model,
             model_path,
             model_import_options,
+            report,
         )
         .await?;
 
@@ -644,6 +754,7 @@ async fn convert_model(
     animation: &mut Animation,
     model_path: &Path,
     model_import_options: &ModelImportOptions,
+    report: &mut FbxConversionReport,
 ) -> Result<Handle<Node>, FbxError> {
     let base = convert_model_to_base(model);
 
@@ -657,6 +768,7 @@ async fn convert_model(
             graph,
             model_path,
             model_import_options,
+            report,
         )
         .await?
     } else if model.light.is_some() {
@@ -680,6 +792,8 @@ async fn convert_model(
                     lcl_translation = Some(curve_node);
                 } else if curve_node.actual_type == FbxAnimationCurveNodeType::Scale {
                     lcl_scale = Some(curve_node);
+                } else {
+                    report.skip(format!("Animated property {}", curve_node.property));
                 }
             }
         }
@@ -782,6 +896,61 @@ async fn convert_model(
         animation.add_track(scale_track);
     }
 
+    // Convert blend shape weight animations. Blend shapes are matched by names, because some
+    // channels might be skipped during mesh conversion.
+    if let Some(mesh) = graph[node_handle].cast::<Mesh>() {
+        for &geom_handle in model.geoms.iter() {
+            let geom = fbx_scene.get(geom_handle).as_mesh_geometry()?;
+            for channel in geom.collect_blend_shapes_refs(fbx_scene)? {
+                let Some(index) = mesh
+                    .blend_shapes()
+                    .iter()
+                    .position(|blend_shape| blend_shape.name == channel.name)
+                else {
+                    continue;
+                };
+
+                if let FbxComponent::AnimationCurveNode(curve_node) =
+                    fbx_scene.get(channel.deform_percent_curve_node)
+                {
+                    let mut data = TrackDataContainer::new(TrackValueKind::Real);
+                    let curve = &mut data.curves_mut()[0];
+                    // Blend shape channels have single curve, but its name depends on exporter.
+                    for curve_handle in curve_node.curves.values() {
+                        if let FbxComponent::AnimationCurve(fbx_curve) =
+                            fbx_scene.get(*curve_handle)
+                        {
+                            for pair in fbx_curve.keys.iter() {
+                                curve.add_key(CurveKey::new(
+                                    pair.time,
+                                    pair.value,
+                                    CurveKeyKind::Linear,
+                                ));
+                            }
+                        }
+                    }
+                    if curve.keys().is_empty() {
+                        curve.add_key(CurveKey::new(
+                            0.0,
+                            channel.deform_percent,
+                            CurveKeyKind::Constant,
+                        ));
+                    }
+
+                    let mut track = Track::new(
+                        data,
+                        ValueBinding::Property {
+                            name: format!("blend_shapes[{}].weight", index),
+                            value_type: ValueType::F32,
+                        },
+                    );
+                    track.set_target(node_handle);
+                    animation.add_track(track);
+                }
+            }
+        }
+    }
+
     animation.fit_length_to_content();
 
     Ok(node_handle)
@@ -796,6 +965,7 @@ async fn convert(
     scene: &mut Scene,
     model_path: &Path,
     model_import_options: &ModelImportOptions,
+    report: &mut FbxConversionReport,
 ) -> Result<(), FbxError> {
     let root = scene.graph.get_root();
 
@@ -813,6 +983,7 @@ async fn convert(
                 &mut animation,
                 model_path,
                 model_import_options,
+                report,
             )
             .await?;
             scene.graph.link_nodes(node, root);
@@ -915,8 +1086,10 @@ pub async fn load_to_scene<P: AsRef<Path>>(
     let fbx = FbxDocument::new(path.as_ref(), io).await?;
     let parsing_time = now.elapsed().as_millis();
 
+    let mut report = FbxConversionReport::default();
+
     let now = Instant::now();
-    let fbx_scene = FbxScene::new(&fbx)?;
+    let fbx_scene = FbxScene::new(&fbx, &mut report)?;
     let dom_prepare_time = now.elapsed().as_millis();
 
     let now = Instant::now();
@@ -926,6 +1099,7 @@ pub async fn load_to_scene<P: AsRef<Path>>(
         scene,
         path.as_ref(),
         model_import_options,
+        &mut report,
     )
     .await?;
     let conversion_time = now.elapsed().as_millis();
@@ -934,6 +1108,17 @@ pub async fn load_to_scene<P: AsRef<Path>>(
                  format!("FBX {:?} loaded in {} ms\n\t- Parsing - {} ms\n\t- DOM Prepare - {} ms\n\t- Conversion - {} ms",
                          path.as_ref(), start_time.elapsed().as_millis(), parsing_time, dom_prepare_time, conversion_time));
 
+    if !report.is_empty() {
+        Log::writeln(
+            MessageKind::Warning,
+            format!(
+                "Some parts of FBX {:?} were not converted:\n{}",
+                path.as_ref(),
+                report
+            ),
+        );
+    }
+
     // Check for multiple nodes with same name and throw a warning if any.
     // It seems that FBX was designed using ass, not brains. It has no unique **persistent**
     // IDs for entities, so the only way to find an entity is to use its name, but FBX also
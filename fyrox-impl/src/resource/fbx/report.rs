@@ -0,0 +1,38 @@
+//! Contains a report of everything, that was skipped during FBX conversion. See [`FbxConversionReport`]
+//! docs for more info.
+
+use fxhash::FxHashMap;
+use std::fmt::{Display, Formatter};
+
+/// A list of the parts of an FBX file, that were not converted to the engine's representation.
+/// DCC tools put lots of data in FBX files, and only a part of it could be converted. The report
+/// is written to the log after conversion, so the loss of data is not silent.
+#[derive(Default, Debug)]
+pub struct FbxConversionReport {
+    // Description -> count. Most of the skipped entities are repeated many times in a file
+    // (for example, one per mesh), so there's no need to list each one.
+    skipped: FxHashMap<String, usize>,
+}
+
+impl FbxConversionReport {
+    /// Registers an entity, that was skipped during conversion.
+    pub fn skip<S: Into<String>>(&mut self, description: S) {
+        *self.skipped.entry(description.into()).or_default() += 1;
+    }
+
+    /// Returns `true` if nothing was skipped.
+    pub fn is_empty(&self) -> bool {
+        self.skipped.is_empty()
+    }
+}
+
+impl Display for FbxConversionReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut skipped = self.skipped.iter().collect::<Vec<_>>();
+        skipped.sort();
+        for (description, count) in skipped {
+            writeln!(f, "\t- {description} (x{count})")?;
+        }
+        Ok(())
+    }
+}
@@ -57,6 +57,9 @@ pub enum FbxAnimationCurveNodeType {
 pub struct FbxAnimationCurveNode {
     pub actual_type: FbxAnimationCurveNodeType,
 
+    /// Name of the animated property of a parent object.
+    pub property: String,
+
     /// Parameter name to curve mapping, usually it has `d|X`, `d|Y`, `d|Z` as key.
     pub curves: FxHashMap<String, Handle<FbxComponent>>,
 }
@@ -71,6 +74,7 @@ impl FbxAnimationCurveNode {
                 "S" | "AnimCurveNode::S" => FbxAnimationCurveNodeType::Scale,
                 _ => FbxAnimationCurveNodeType::Unknown,
             },
+            property: Default::default(),
             curves: Default::default(),
         })
     }
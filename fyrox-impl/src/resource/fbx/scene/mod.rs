@@ -9,6 +9,7 @@ use crate::{
         document::{attribute::FbxAttribute, FbxDocument, FbxNode, FbxNodeContainer},
         error::FbxError,
         fix_index,
+        report::FbxConversionReport,
         scene::{
             animation::{FbxAnimationCurve, FbxAnimationCurveNode},
             geometry::{FbxMeshGeometry, FbxShapeGeometry},
@@ -33,8 +34,9 @@ pub struct FbxScene {
 
 impl FbxScene {
     /// Parses FBX DOM and filling internal lists to prepare
-    /// for conversion to engine format
-    pub fn new(document: &FbxDocument) -> Result<Self, FbxError> {
+    /// for conversion to engine format. Every unsupported object is registered in the
+    /// given report.
+    pub fn new(document: &FbxDocument, report: &mut FbxConversionReport) -> Result<Self, FbxError> {
         let mut components = Pool::new();
         let mut index_to_component = FxHashMap::default();
 
@@ -66,7 +68,7 @@ impl FbxScene {
                             FbxShapeGeometry::read(*object_handle, nodes)?,
                         )));
                     }
-                    _ => (),
+                    class => report.skip(format!("Geometry {class}")),
                 },
                 "Model" => {
                     component_handle = components.spawn(FbxComponent::Model(Box::new(
@@ -85,16 +87,26 @@ impl FbxScene {
                         nodes,
                     )?));
                 }
-                "Video" => {
-                    if object.get_attrib(2)?.as_string() == "Clip" {
+                "Video" => match object.get_attrib(2)?.as_string().as_str() {
+                    "Clip" => {
                         component_handle = components
                             .spawn(FbxComponent::Video(FbxVideo::read(*object_handle, nodes)?));
                     }
-                }
+                    class => report.skip(format!("Video {class}")),
+                },
                 "NodeAttribute" => {
-                    if object.attrib_count() > 2 && object.get_attrib(2)?.as_string() == "Light" {
-                        component_handle = components
-                            .spawn(FbxComponent::Light(FbxLight::read(*object_handle, nodes)?));
+                    if object.attrib_count() > 2 {
+                        match object.get_attrib(2)?.as_string().as_str() {
+                            "Light" => {
+                                component_handle = components.spawn(FbxComponent::Light(
+                                    FbxLight::read(*object_handle, nodes)?,
+                                ));
+                            }
+                            // Bones and empty nodes are converted from models, their attributes
+                            // contain nothing useful.
+                            "LimbNode" | "Null" | "Root" | "Skeleton" => (),
+                            class => report.skip(format!("Node attribute {class}")),
+                        }
                     }
                 }
                 "AnimationCurve" => {
@@ -123,9 +135,17 @@ impl FbxScene {
                             FbxDeformer::read(*object_handle, nodes),
                         ));
                     }
-                    _ => (),
+                    class => report.skip(format!("Deformer {class}")),
                 },
-                _ => (),
+                // Bind poses, animation stacks and layers, selection sets and display layers do
+                // not contain anything that must be converted.
+                "Pose"
+                | "AnimationStack"
+                | "AnimationLayer"
+                | "CollectionExclusive"
+                | "SelectionNode"
+                | "DisplayLayer" => (),
+                name => report.skip(format!("Object {name}")),
             }
             if !component_handle.is_none() {
                 index_to_component.insert(index, component_handle);
@@ -174,7 +194,10 @@ fn link_child_with_parent_component(
         FbxComponent::Model(model) => match child {
             FbxComponent::MeshGeometry(_) => model.geoms.push(child_handle),
             FbxComponent::Material(_) => model.materials.push(child_handle),
-            FbxComponent::AnimationCurveNode(_) => model.animation_curve_nodes.push(child_handle),
+            FbxComponent::AnimationCurveNode(curve_node) => {
+                curve_node.property = property;
+                model.animation_curve_nodes.push(child_handle)
+            }
             FbxComponent::Light(_) => model.light = child_handle,
             FbxComponent::Model(_) => model.children.push(child_handle),
             _ => (),
@@ -210,11 +233,12 @@ fn link_child_with_parent_component(
                 model.inv_bind_transform = sub_deformer.transform;
             }
         }
-        FbxComponent::BlendShapeChannel(channel) => {
-            if let FbxComponent::ShapeGeometry(_) = child {
-                channel.geometry = child_handle;
-            }
-        }
+        // Link blend shape channel with its shapes and weight animation
+        FbxComponent::BlendShapeChannel(channel) => match child {
+            FbxComponent::ShapeGeometry(_) => channel.geometries.push(child_handle),
+            FbxComponent::AnimationCurveNode(_) => channel.deform_percent_curve_node = child_handle,
+            _ => (),
+        },
         FbxComponent::Texture(texture) => {
             if let FbxComponent::Video(video) = child {
                 texture.content.clone_from(&video.content);
@@ -265,8 +289,11 @@ impl FbxComponent {
 const FBX_TIME_UNIT: f64 = 1.0 / 46_186_158_000.0;
 
 pub struct FbxBlendShapeChannel {
-    pub geometry: Handle<FbxComponent>,
+    /// Target shape and in-between shapes, they're sorted the same as `full_weights`.
+    pub geometries: Vec<Handle<FbxComponent>>,
+    pub full_weights: Vec<f32>,
     pub deform_percent: f32,
+    pub deform_percent_curve_node: Handle<FbxComponent>,
     pub name: String,
 }
 
@@ -277,6 +304,14 @@ impl FbxBlendShapeChannel {
             .and_then(|n| n.get_attrib(0).map(|a| a.as_f32().unwrap_or(100.0)))
             .unwrap_or(100.0);
 
+        let mut full_weights = Vec::new();
+        if let Ok(full_weights_handle) = nodes.find(channel, "FullWeights") {
+            let full_weights_node = nodes.get_by_name(full_weights_handle, "a")?;
+            for i in 0..full_weights_node.attrib_count() {
+                full_weights.push(full_weights_node.get_attrib(i)?.as_f32()?);
+            }
+        }
+
         let mut name = nodes.get(channel).get_attrib(1)?.as_string();
 
         if let Some(without_prefix) = name.strip_prefix("SubDeformer::") {
@@ -284,11 +319,27 @@ impl FbxBlendShapeChannel {
         }
 
         Ok(Self {
-            geometry: Default::default(),
+            geometries: Default::default(),
+            full_weights,
             deform_percent,
+            deform_percent_curve_node: Default::default(),
             name,
         })
     }
+
+    /// Returns a shape, that is fully applied at 100% of deform percent. The rest of the shapes
+    /// are in-between shapes, that cannot be represented by the engine.
+    pub fn target_geometry(&self) -> Option<Handle<FbxComponent>> {
+        if self.full_weights.len() == self.geometries.len() {
+            self.full_weights
+                .iter()
+                .zip(self.geometries.iter())
+                .max_by(|(a, _), (b, _)| a.total_cmp(b))
+                .map(|(_, geometry)| *geometry)
+        } else {
+            self.geometries.last().cloned()
+        }
+    }
 }
 
 pub struct FbxCluster {
@@ -1,5 +1,5 @@
 use crate::{
-    core::pool::Handle,
+    core::{algebra::Vector2, pool::Handle},
     resource::fbx::document::{FbxNode, FbxNodeContainer},
 };
 use std::path::PathBuf;
@@ -7,6 +7,12 @@ use std::path::PathBuf;
 pub struct FbxTexture {
     filename: PathBuf,
     pub content: Vec<u8>,
+    /// Offset of texture coordinates.
+    pub uv_translation: Vector2<f32>,
+    /// Scale of texture coordinates.
+    pub uv_scale: Vector2<f32>,
+    /// Rotation of texture coordinates in degrees.
+    pub uv_rotation: f32,
 }
 
 impl FbxTexture {
@@ -17,6 +23,9 @@ impl FbxTexture {
         let mut texture = FbxTexture {
             filename: PathBuf::new(),
             content: Default::default(),
+            uv_translation: Vector2::default(),
+            uv_scale: Vector2::new(1.0, 1.0),
+            uv_rotation: 0.0,
         };
         if let Ok(relative_file_name_node) =
             nodes.get_by_name(texture_node_handle, "RelativeFilename")
@@ -29,10 +38,28 @@ impl FbxTexture {
                 texture.filename = PathBuf::from(str_path);
             }
         }
+        if let Ok(properties70_node) = nodes.get_by_name(texture_node_handle, "Properties70") {
+            for property_handle in properties70_node.children() {
+                let property_node = nodes.get(*property_handle);
+                match property_node.get_attrib(0)?.as_string().as_str() {
+                    "Translation" => texture.uv_translation = property_node.get_vec3_at(4)?.xy(),
+                    "Scaling" => texture.uv_scale = property_node.get_vec3_at(4)?.xy(),
+                    "Rotation" => texture.uv_rotation = property_node.get_vec3_at(4)?.z,
+                    _ => (), // Unused properties
+                }
+            }
+        }
         Ok(texture)
     }
 
     pub(in crate::resource::fbx) fn get_file_path(&self) -> &PathBuf {
         &self.filename
     }
+
+    /// Returns `true` if the texture does not change texture coordinates.
+    pub fn has_identity_transform(&self) -> bool {
+        self.uv_translation == Vector2::default()
+            && self.uv_scale == Vector2::new(1.0, 1.0)
+            && self.uv_rotation == 0.0
+    }
 }
@@ -195,6 +195,7 @@
                 uniform sampler2D aoTexture;
                 uniform vec2 texCoordScale;
                 uniform uint layerIndex;
+                uniform uint fyrox_lightLinkMask;
                 uniform vec3 emissionStrength;
                 uniform vec4 diffuseColor;
                 uniform float metallicFactor;
@@ -252,7 +253,7 @@
                     outAmbient.xyz = emissionStrength * texture(emissionTexture, tc).rgb + texture(lightmapTexture, secondTexCoord).rgb;
                     outAmbient.a = 1.0;
 
-                    outDecalMask = layerIndex;
+                    outDecalMask = (layerIndex & 0xFFu) | fyrox_lightLinkMask;
                 }
                 "#,
         ),
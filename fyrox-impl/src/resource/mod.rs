@@ -8,3 +8,6 @@ pub mod fbx;
 pub mod gltf;
 pub mod model;
 pub mod texture;
+#[cfg(feature = "usd")]
+pub mod usd;
+pub mod video;
@@ -56,6 +56,7 @@ use std::{
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 
 pub mod loader;
+pub mod parameter;
 
 /// See module docs.
 #[derive(Debug, Visit, Reflect)]
@@ -92,6 +93,7 @@ pub struct InstantiationContext<'a, 'b, 'c> {
     dest_scene: &'b mut Scene,
     local_transform: Option<Transform>,
     ids: Option<&'c FxHashMap<Handle<Node>, SceneNodeId>>,
+    parameters: Vec<(String, Box<dyn Reflect>)>,
 }
 
 impl<'a, 'b, 'c> InstantiationContext<'a, 'b, 'c> {
@@ -138,6 +140,13 @@ impl<'a, 'b, 'c> InstantiationContext<'a, 'b, 'c> {
         self
     }
 
+    /// Sets the value of the given parameter of the instance (see [`parameter::PrefabParameters`] for more
+    /// info). The value must have exactly the same type as the property, that is bound to the parameter.
+    pub fn with_parameter<T: Reflect>(mut self, name: &str, value: T) -> Self {
+        self.parameters.push((name.to_string(), Box::new(value)));
+        self
+    }
+
     /// Finishes instantiation.
     pub fn finish(self) -> Handle<Node> {
         let model = self.model.clone();
@@ -172,6 +181,14 @@ impl<'a, 'b, 'c> InstantiationContext<'a, 'b, 'c> {
         // Explicitly mark as root node.
         self.dest_scene.graph[root].is_resource_instance_root = true;
 
+        for (name, value) in self.parameters {
+            if let Err(err) =
+                parameter::set_parameter(&mut self.dest_scene.graph, root, &name, value)
+            {
+                Log::err(format!("Unable to set prefab parameter {name}: {err}"));
+            }
+        }
+
         root
     }
 }
@@ -454,6 +471,7 @@ impl ModelResourceExtension for ModelResource {
             dest_scene,
             local_transform: None,
             ids: None,
+            parameters: Default::default(),
         }
     }
 
@@ -647,6 +665,14 @@ pub struct ModelImportOptions {
     /// See [`MaterialSearchOptions`] docs for more info.
     #[serde(default)]
     pub material_search_options: MaterialSearchOptions,
+
+    /// Defines whether textures embedded in a model file should be written to separate files
+    /// or not. Embedded textures are extracted in `<model name>.fbm` directory next to the model
+    /// file. Extracted textures are shared between the instances of the model and could be
+    /// edited as any other texture. When disabled, embedded textures are loaded from memory and
+    /// will be duplicated in every scene, that uses the model.
+    #[serde(default)]
+    pub extract_embedded_media: bool,
 }
 
 impl ImportOptions for ModelImportOptions {}
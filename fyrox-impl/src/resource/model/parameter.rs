@@ -0,0 +1,345 @@
+//! Prefab parameters allow to expose chosen properties of a prefab, so its instances could override them
+//! without digging through the hierarchy of the instance. See [`PrefabParameters`] docs for more info.
+
+use crate::{
+    core::{pool::Handle, reflect::prelude::*, uuid_provider, visitor::prelude::*},
+    graph::{BaseSceneGraph, SceneGraph},
+    scene::{graph::Graph, node::Node},
+};
+use std::fmt::{Display, Formatter};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Max nesting level of prefabs, that is used when resolving parameters. It prevents infinite recursion
+/// on malformed prefabs.
+const MAX_NESTING_LEVEL: usize = 32;
+
+/// An error, that may occur when resolving a prefab parameter.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PrefabParameterError {
+    /// The given handle does not point to a node.
+    InvalidNode(Handle<Node>),
+    /// The node is not an instance of a prefab.
+    NotAnInstance(Handle<Node>),
+    /// The prefab is not loaded (or failed to load).
+    PrefabNotLoaded,
+    /// The prefab does not have a parameter with the given name.
+    UnknownParameter(String),
+    /// The parameter is bound to a node, that does not exist in the instance.
+    UnboundParameter(String),
+    /// Prefabs are nested too deep (or form a cycle).
+    NestingTooDeep,
+    /// The value cannot be assigned to the property, that is bound to the parameter.
+    InvalidValue(String),
+}
+
+impl Display for PrefabParameterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrefabParameterError::InvalidNode(handle) => write!(f, "Node {handle} does not exist"),
+            PrefabParameterError::NotAnInstance(handle) => {
+                write!(f, "Node {handle} is not an instance of a prefab")
+            }
+            PrefabParameterError::PrefabNotLoaded => write!(f, "Prefab is not loaded"),
+            PrefabParameterError::UnknownParameter(name) => {
+                write!(f, "Prefab does not have {name} parameter")
+            }
+            PrefabParameterError::UnboundParameter(name) => {
+                write!(
+                    f,
+                    "Parameter {name} is bound to a node, that does not exist"
+                )
+            }
+            PrefabParameterError::NestingTooDeep => write!(f, "Prefabs are nested too deep"),
+            PrefabParameterError::InvalidValue(name) => {
+                write!(f, "Unable to assign the value to {name} parameter")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PrefabParameterError {}
+
+/// Defines where the value of a parameter goes to.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, VariantNames)]
+pub enum PrefabParameterBinding {
+    /// The parameter is bound to a property of a node of the prefab.
+    Property {
+        /// A node of the prefab.
+        #[reflect(description = "A node of the prefab.")]
+        node: Handle<Node>,
+        /// A path to the property, for example `base.visibility`.
+        #[reflect(description = "A path to the property, for example `base.visibility`.")]
+        property: String,
+    },
+    /// The parameter is bound to a parameter of a nested prefab instance. This way parameters of nested
+    /// prefabs could be propagated upwards.
+    Nested {
+        /// A root node of a nested prefab instance.
+        #[reflect(description = "A root node of a nested prefab instance.")]
+        instance: Handle<Node>,
+        /// A name of the parameter of the nested prefab.
+        #[reflect(description = "A name of the parameter of the nested prefab.")]
+        parameter: String,
+    },
+}
+
+uuid_provider!(PrefabParameterBinding = "4c3a7e1d-2f6b-4d8a-9e15-8b0c6d2f7a93");
+
+impl Default for PrefabParameterBinding {
+    fn default() -> Self {
+        Self::Property {
+            node: Default::default(),
+            property: Default::default(),
+        }
+    }
+}
+
+/// A named property of a prefab, that could be overridden by its instances.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct PrefabParameter {
+    /// A name of the parameter.
+    #[reflect(description = "A name of the parameter.")]
+    pub name: String,
+    /// A target of the parameter.
+    #[reflect(description = "A target of the parameter.")]
+    pub binding: PrefabParameterBinding,
+}
+
+uuid_provider!(PrefabParameter = "a1e6d93b-5c07-4f2e-b8d4-3f9a0c71e652");
+
+/// A set of parameters of a prefab. Parameters are declared in the prefab scene (see
+/// [`crate::scene::Scene::prefab_parameters`]) and every instance of the prefab could override them.
+///
+/// A parameter does not store any value, it is just a named "link" to a property of some node of the
+/// prefab. The default value of a parameter is the value of the property in the prefab itself, the value
+/// in an instance is stored in the respective instance node using standard property inheritance. This
+/// means that an overridden parameter could be reverted to its default value the same way as any other
+/// inherited property.
+///
+/// Prefabs could be nested in other prefabs, and a parameter of the outer prefab could be bound to a
+/// parameter of a nested instance (see [`PrefabParameterBinding::Nested`]), thus propagating it upwards:
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::pool::Handle,
+/// #     resource::model::parameter::{PrefabParameter, PrefabParameterBinding},
+/// #     scene::{node::Node, Scene},
+/// # };
+/// // A prefab of a house, that has a door prefab instance (which has `Locked` parameter) inside.
+/// fn expose_door_lock(house_prefab: &mut Scene, door_instance: Handle<Node>) {
+///     house_prefab.prefab_parameters.parameters.push(PrefabParameter {
+///         name: "DoorLocked".to_string(),
+///         binding: PrefabParameterBinding::Nested {
+///             instance: door_instance,
+///             parameter: "Locked".to_string(),
+///         },
+///     });
+/// }
+/// ```
+///
+/// Use [`resolve_parameter`] and [`set_parameter`] to access parameters of an instance, or
+/// [`super::InstantiationContext::with_parameter`] to set them when instantiating a prefab.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct PrefabParameters {
+    /// A list of the parameters.
+    #[reflect(description = "A list of the parameters of the prefab.")]
+    pub parameters: Vec<PrefabParameter>,
+}
+
+impl PrefabParameters {
+    /// Tries to find a parameter by its name.
+    pub fn find(&self, name: &str) -> Option<&PrefabParameter> {
+        self.parameters.iter().find(|p| p.name == name)
+    }
+}
+
+/// Returns a list of parameters of the prefab, that was used to create the given instance root.
+pub fn instance_parameters(
+    graph: &Graph,
+    instance: Handle<Node>,
+) -> Result<Vec<PrefabParameter>, PrefabParameterError> {
+    let node = graph
+        .try_get(instance)
+        .ok_or(PrefabParameterError::InvalidNode(instance))?;
+    let model = node
+        .resource()
+        .ok_or(PrefabParameterError::NotAnInstance(instance))?;
+    let mut state = model.state();
+    let data = state.data().ok_or(PrefabParameterError::PrefabNotLoaded)?;
+    Ok(data.get_scene().prefab_parameters.parameters.clone())
+}
+
+fn resolve_parameter_recursive(
+    graph: &Graph,
+    instance: Handle<Node>,
+    name: &str,
+    level: usize,
+) -> Result<(Handle<Node>, String), PrefabParameterError> {
+    if level > MAX_NESTING_LEVEL {
+        return Err(PrefabParameterError::NestingTooDeep);
+    }
+
+    let node = graph
+        .try_get(instance)
+        .ok_or(PrefabParameterError::InvalidNode(instance))?;
+    let model = node
+        .resource()
+        .ok_or(PrefabParameterError::NotAnInstance(instance))?;
+
+    let (prefab_node, property) = {
+        let mut state = model.state();
+        let data = state.data().ok_or(PrefabParameterError::PrefabNotLoaded)?;
+        let prefab_scene = data.get_scene();
+        let parameter = prefab_scene
+            .prefab_parameters
+            .find(name)
+            .ok_or_else(|| PrefabParameterError::UnknownParameter(name.to_string()))?;
+        match parameter.binding {
+            PrefabParameterBinding::Property { node, ref property } => (node, property.clone()),
+            PrefabParameterBinding::Nested {
+                instance,
+                ref parameter,
+            } => resolve_parameter_recursive(&prefab_scene.graph, instance, parameter, level + 1)?,
+        }
+    };
+
+    // Find a node of the instance, that corresponds to the node of the prefab.
+    graph
+        .find(instance, &mut |n| {
+            n.original_handle_in_resource() == prefab_node && n.resource().as_ref() == Some(&model)
+        })
+        .map(|(handle, _)| (handle, property))
+        .ok_or_else(|| PrefabParameterError::UnboundParameter(name.to_string()))
+}
+
+/// Finds a node and a path to its property, that is bound to the given parameter of the prefab instance.
+/// Bindings to parameters of nested prefabs are resolved recursively.
+pub fn resolve_parameter(
+    graph: &Graph,
+    instance: Handle<Node>,
+    name: &str,
+) -> Result<(Handle<Node>, String), PrefabParameterError> {
+    resolve_parameter_recursive(graph, instance, name, 0)
+}
+
+/// Sets a value of the given parameter of the prefab instance. The value must have exactly the same type as
+/// the property, that is bound to the parameter. Custom property setters are respected.
+pub fn set_parameter(
+    graph: &mut Graph,
+    instance: Handle<Node>,
+    name: &str,
+    value: Box<dyn Reflect>,
+) -> Result<(), PrefabParameterError> {
+    let (node, property) = resolve_parameter(graph, instance, name)?;
+
+    let mut result = Ok(());
+    let mut value = Some(value);
+    graph[node].as_reflect_mut(&mut |object| {
+        object.set_field_by_path(&property, value.take().unwrap(), &mut |r| {
+            if r.is_err() {
+                result = Err(PrefabParameterError::InvalidValue(name.to_string()));
+            }
+        })
+    });
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::untyped::ResourceKind,
+        core::pool::Handle,
+        resource::model::{
+            parameter::{
+                resolve_parameter, set_parameter, PrefabParameter, PrefabParameterBinding,
+                PrefabParameterError,
+            },
+            Model, ModelResource, ModelResourceExtension,
+        },
+        scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene},
+    };
+    use fyrox_core::reflect::Reflect;
+    use fyrox_graph::NodeMapping;
+
+    fn make_prefab(scene: Scene) -> ModelResource {
+        ModelResource::new_ok(
+            ResourceKind::Embedded,
+            Model {
+                mapping: NodeMapping::UseHandles,
+                scene,
+            },
+        )
+    }
+
+    #[test]
+    fn test_nested_parameters() {
+        // Door prefab: a root with a child `Handle`, the visibility of the handle is exposed.
+        let mut door_scene = Scene::new();
+        let door_handle =
+            PivotBuilder::new(BaseBuilder::new().with_name("Handle")).build(&mut door_scene.graph);
+        door_scene
+            .prefab_parameters
+            .parameters
+            .push(PrefabParameter {
+                name: "HandleVisible".to_string(),
+                binding: PrefabParameterBinding::Property {
+                    node: door_handle,
+                    property: "base.visibility".to_string(),
+                },
+            });
+        let door = make_prefab(door_scene);
+
+        // House prefab with a door instance, the parameter of the door is propagated upwards.
+        let mut house_scene = Scene::new();
+        let door_instance = door.instantiate(&mut house_scene);
+        house_scene
+            .prefab_parameters
+            .parameters
+            .push(PrefabParameter {
+                name: "DoorHandleVisible".to_string(),
+                binding: PrefabParameterBinding::Nested {
+                    instance: door_instance,
+                    parameter: "HandleVisible".to_string(),
+                },
+            });
+        let house = make_prefab(house_scene);
+
+        let mut scene = Scene::new();
+        let house_instance = house.instantiate(&mut scene);
+
+        let (node, property) =
+            resolve_parameter(&scene.graph, house_instance, "DoorHandleVisible").unwrap();
+        assert_eq!(scene.graph[node].name(), "Handle");
+        assert_eq!(property, "base.visibility");
+
+        set_parameter(
+            &mut scene.graph,
+            house_instance,
+            "DoorHandleVisible",
+            Box::new(false),
+        )
+        .unwrap();
+        assert!(!scene.graph[node].visibility());
+
+        assert_eq!(
+            set_parameter(
+                &mut scene.graph,
+                house_instance,
+                "DoorHandleVisible",
+                Box::new(1.0f32) as Box<dyn Reflect>,
+            ),
+            Err(PrefabParameterError::InvalidValue(
+                "DoorHandleVisible".to_string()
+            ))
+        );
+        assert_eq!(
+            resolve_parameter(&scene.graph, house_instance, "Foo"),
+            Err(PrefabParameterError::UnknownParameter("Foo".to_string()))
+        );
+        let not_instance: Handle<Node> = scene.graph.get_root();
+        assert_eq!(
+            resolve_parameter(&scene.graph, not_instance, "Foo"),
+            Err(PrefabParameterError::NotAnInstance(not_instance))
+        );
+    }
+}
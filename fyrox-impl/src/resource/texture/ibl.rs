@@ -0,0 +1,732 @@
+//! Image-based lighting (IBL) utilities. This module allows you to convert an equirectangular (also
+//! known as latitude-longitude) panorama, which is the most common format of HDR images (.hdr, .exr),
+//! to a cube map, and to generate two additional cube maps from it:
+//!
+//! - Irradiance map - a small cube map with diffuse (cosine-weighted) convolution of the environment.
+//!   It is calculated using 3rd order spherical harmonics, which is more than enough for low-frequency
+//!   diffuse lighting.
+//! - Prefiltered specular map - a cube map, where each mip level contains the environment convolved
+//!   with GGX distribution for increasing roughness (from 0.0 at the first level to 1.0 at the last one).
+//!
+//! All the maps are stored in linear color space using [`TexturePixelKind::RGB32F`] pixel format.
+//! Faces of the cube maps go in the following order: +X, -X, +Y, -Y, +Z, -Z, which matches the
+//! order of [`crate::scene::camera::SkyBox`] faces (left, right, top, bottom, front, back).
+//!
+//! ## Example
+//!
+//! ```rust
+//! # use fyrox_impl::resource::texture::{ibl::IblMaps, Texture, TextureKind, TexturePixelKind};
+//! // A tiny 4x2 gray panorama.
+//! let bytes = std::iter::repeat(0.5f32)
+//!     .take(4 * 2 * 3)
+//!     .flat_map(|v| v.to_le_bytes())
+//!     .collect::<Vec<_>>();
+//! let panorama = Texture::from_bytes(
+//!     TextureKind::Rectangle {
+//!         width: 4,
+//!         height: 2,
+//!     },
+//!     TexturePixelKind::RGB32F,
+//!     bytes,
+//! )
+//! .unwrap();
+//!
+//! let maps = IblMaps::from_equirectangular(&panorama, 16).unwrap();
+//! assert_eq!(maps.specular.data_ref().mip_count(), 5);
+//! ```
+
+use crate::{
+    asset::untyped::ResourceKind,
+    core::{algebra::Vector3, reflect::prelude::*, visitor::prelude::*},
+    resource::texture::{
+        Texture, TextureKind, TextureMagnificationFilter, TextureMinificationFilter,
+        TexturePixelKind, TextureResource, TextureWrapMode,
+    },
+};
+use rayon::prelude::*;
+use std::{
+    f32::consts::{FRAC_1_PI, PI},
+    fmt::{Display, Formatter},
+};
+
+/// Size of each face of the irradiance cube map.
+pub const IRRADIANCE_MAP_SIZE: u32 = 32;
+
+/// Max size of each face of the first mip level of the prefiltered specular cube map.
+pub const MAX_SPECULAR_MAP_SIZE: u32 = 128;
+
+/// Max amount of mip levels of the prefiltered specular cube map. The shaders map roughness in
+/// `[0.0; 1.0]` range to `[0; SPECULAR_MAP_LEVELS - 1]` range of mip levels.
+pub const SPECULAR_MAP_LEVELS: u32 = 5;
+
+/// Amount of samples that is used to convolve the environment with GGX distribution.
+const SPECULAR_SAMPLE_COUNT: u32 = 64;
+
+/// Max face size of the environment, that is used to calculate spherical harmonics. Larger sizes
+/// do not add any precision to such low-frequency function.
+const MAX_SH_SOURCE_SIZE: u32 = 64;
+
+/// An error that may occur during IBL maps generation.
+#[derive(Debug)]
+pub enum IblError {
+    /// Source texture must be a rectangle texture.
+    UnsupportedTextureKind(TextureKind),
+    /// Source texture has pixel format that cannot be read (compressed textures, for example).
+    UnsupportedPixelKind(TexturePixelKind),
+    /// Size of the resulting cube map must be greater than zero.
+    InvalidSize,
+}
+
+impl Display for IblError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IblError::UnsupportedTextureKind(kind) => {
+                write!(f, "Unsupported texture kind {kind:?}. Only rectangle textures can be used as a panorama.")
+            }
+            IblError::UnsupportedPixelKind(kind) => {
+                write!(f, "Unsupported pixel kind {kind:?}.")
+            }
+            IblError::InvalidSize => {
+                write!(f, "Size of a cube map must be greater than zero.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IblError {}
+
+/// Returns normalized direction that corresponds to the center of the given texel of a cube map face.
+fn texel_direction(face: usize, x: u32, y: u32, size: u32) -> Vector3<f32> {
+    let sc = 2.0 * (x as f32 + 0.5) / size as f32 - 1.0;
+    let tc = 2.0 * (y as f32 + 0.5) / size as f32 - 1.0;
+    let dir = match face {
+        0 => Vector3::new(1.0, -tc, -sc),
+        1 => Vector3::new(-1.0, -tc, sc),
+        2 => Vector3::new(sc, 1.0, tc),
+        3 => Vector3::new(sc, -1.0, -tc),
+        4 => Vector3::new(sc, -tc, 1.0),
+        _ => Vector3::new(-sc, -tc, -1.0),
+    };
+    dir.normalize()
+}
+
+/// Returns face index and texture coordinates in `[0.0; 1.0]` range for the given direction.
+fn direction_to_face_uv(dir: Vector3<f32>) -> (usize, f32, f32) {
+    let (ax, ay, az) = (dir.x.abs(), dir.y.abs(), dir.z.abs());
+    let (face, sc, tc, major) = if ax >= ay && ax >= az {
+        if dir.x > 0.0 {
+            (0, -dir.z, -dir.y, ax)
+        } else {
+            (1, dir.z, -dir.y, ax)
+        }
+    } else if ay >= az {
+        if dir.y > 0.0 {
+            (2, dir.x, dir.z, ay)
+        } else {
+            (3, dir.x, -dir.z, ay)
+        }
+    } else if dir.z > 0.0 {
+        (4, dir.x, -dir.y, az)
+    } else {
+        (5, -dir.x, -dir.y, az)
+    };
+    let major = major.max(f32::EPSILON);
+    (face, 0.5 * (sc / major + 1.0), 0.5 * (tc / major + 1.0))
+}
+
+/// Returns solid angle of the given texel of a cube map face.
+fn texel_solid_angle(x: u32, y: u32, size: u32) -> f32 {
+    fn area_element(x: f32, y: f32) -> f32 {
+        (x * y).atan2((x * x + y * y + 1.0).sqrt())
+    }
+
+    let inv_size = 1.0 / size as f32;
+    let sc = 2.0 * (x as f32 + 0.5) * inv_size - 1.0;
+    let tc = 2.0 * (y as f32 + 0.5) * inv_size - 1.0;
+    let (x0, y0) = (sc - inv_size, tc - inv_size);
+    let (x1, y1) = (sc + inv_size, tc + inv_size);
+    area_element(x0, y0) - area_element(x0, y1) - area_element(x1, y0) + area_element(x1, y1)
+}
+
+/// CPU-side cube map with linear RGB pixels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CubeImage {
+    size: u32,
+    faces: [Vec<Vector3<f32>>; 6],
+}
+
+impl CubeImage {
+    /// Creates new cube map of the given size, where each texel is calculated by the given function,
+    /// that takes normalized direction and returns color of the texel.
+    pub fn from_fn<F>(size: u32, func: F) -> Self
+    where
+        F: Fn(Vector3<f32>) -> Vector3<f32> + Sync,
+    {
+        let faces = (0..6)
+            .into_par_iter()
+            .map(|face| {
+                let mut pixels = Vec::with_capacity((size * size) as usize);
+                for y in 0..size {
+                    for x in 0..size {
+                        pixels.push(func(texel_direction(face, x, y, size)));
+                    }
+                }
+                pixels
+            })
+            .collect::<Vec<_>>();
+
+        let mut faces = faces.into_iter();
+        Self {
+            size,
+            faces: std::array::from_fn(|_| faces.next().unwrap()),
+        }
+    }
+
+    /// Returns size of each face of the cube map.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns pixels of the given face (see module docs for face order).
+    pub fn face(&self, index: usize) -> &[Vector3<f32>] {
+        &self.faces[index]
+    }
+
+    fn texel(&self, face: usize, x: i32, y: i32) -> Vector3<f32> {
+        let max = self.size as i32 - 1;
+        let x = x.clamp(0, max) as usize;
+        let y = y.clamp(0, max) as usize;
+        self.faces[face][y * self.size as usize + x]
+    }
+
+    /// Samples the cube map in the given direction using bilinear filtering. Filtering does not cross
+    /// face boundaries.
+    pub fn sample(&self, dir: Vector3<f32>) -> Vector3<f32> {
+        let (face, u, v) = direction_to_face_uv(dir);
+        let fx = u * self.size as f32 - 0.5;
+        let fy = v * self.size as f32 - 0.5;
+        let x = fx.floor();
+        let y = fy.floor();
+        let tx = fx - x;
+        let ty = fy - y;
+        let (x, y) = (x as i32, y as i32);
+        let top = self.texel(face, x, y).lerp(&self.texel(face, x + 1, y), tx);
+        let bottom = self
+            .texel(face, x, y + 1)
+            .lerp(&self.texel(face, x + 1, y + 1), tx);
+        top.lerp(&bottom, ty)
+    }
+
+    /// Creates a cube map of half size, where each texel is an average of 2x2 block of texels of
+    /// this cube map.
+    pub fn downsample(&self) -> Self {
+        let size = (self.size / 2).max(1);
+        let faces = std::array::from_fn(|face| {
+            let mut pixels = Vec::with_capacity((size * size) as usize);
+            for y in 0..size as i32 {
+                for x in 0..size as i32 {
+                    pixels.push(
+                        (self.texel(face, 2 * x, 2 * y)
+                            + self.texel(face, 2 * x + 1, 2 * y)
+                            + self.texel(face, 2 * x, 2 * y + 1)
+                            + self.texel(face, 2 * x + 1, 2 * y + 1))
+                            * 0.25,
+                    );
+                }
+            }
+            pixels
+        });
+        Self { size, faces }
+    }
+
+    /// Creates a cube map from the given equirectangular panorama. Top row of the panorama
+    /// corresponds to +Y direction.
+    pub fn from_equirectangular(texture: &Texture, size: u32) -> Result<Self, IblError> {
+        if size == 0 {
+            return Err(IblError::InvalidSize);
+        }
+
+        let panorama = LinearImage::new(texture)?;
+        Ok(Self::from_fn(size, |dir| panorama.sample_direction(dir)))
+    }
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        for face in self.faces.iter() {
+            for pixel in face {
+                for component in pixel.iter() {
+                    bytes.extend_from_slice(&component.to_le_bytes());
+                }
+            }
+        }
+    }
+
+    /// Converts the cube map to a texture resource with a single mip level.
+    pub fn to_texture(&self) -> TextureResource {
+        cube_texture(std::slice::from_ref(self))
+    }
+}
+
+fn cube_texture(levels: &[CubeImage]) -> TextureResource {
+    let size = levels[0].size;
+    let mut bytes = Vec::new();
+    for level in levels {
+        level.write_bytes(&mut bytes);
+    }
+
+    let texture = Texture {
+        kind: TextureKind::Cube {
+            width: size,
+            height: size,
+        },
+        bytes: bytes.into(),
+        pixel_kind: TexturePixelKind::RGB32F,
+        minification_filter: if levels.len() > 1 {
+            TextureMinificationFilter::LinearMipMapLinear
+        } else {
+            TextureMinificationFilter::Linear
+        },
+        magnification_filter: TextureMagnificationFilter::Linear,
+        s_wrap_mode: TextureWrapMode::ClampToEdge,
+        t_wrap_mode: TextureWrapMode::ClampToEdge,
+        mip_count: levels.len() as u32,
+        ..Default::default()
+    };
+
+    TextureResource::new_ok(ResourceKind::Embedded, texture)
+}
+
+/// Readable representation of the first mip level of a rectangle texture with pixels in linear color
+/// space. Low dynamic range textures are considered to be in sRGB color space and converted to linear.
+pub(crate) struct LinearImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<Vector3<f32>>,
+}
+
+impl LinearImage {
+    pub(crate) fn new(texture: &Texture) -> Result<Self, IblError> {
+        let TextureKind::Rectangle { width, height } = texture.kind() else {
+            return Err(IblError::UnsupportedTextureKind(texture.kind()));
+        };
+
+        let data = texture.mip_level_data(0);
+        let f32_at = |offset: usize| {
+            f32::from_le_bytes([
+                data[offset],
+                data[offset + 1],
+                data[offset + 2],
+                data[offset + 3],
+            ])
+        };
+        let f16_at =
+            |offset: usize| half::f16::from_le_bytes([data[offset], data[offset + 1]]).to_f32();
+        let u16_at = |offset: usize| {
+            (u16::from_le_bytes([data[offset], data[offset + 1]]) as f32 / u16::MAX as f32)
+                .powf(2.2)
+        };
+        let u8_at = |offset: usize| (data[offset] as f32 / 255.0).powf(2.2);
+
+        let pixel_kind = texture.pixel_kind();
+        let (stride, read): (usize, &dyn Fn(usize) -> f32) = match pixel_kind {
+            TexturePixelKind::RGB32F => (12, &|i| f32_at(i * 4)),
+            TexturePixelKind::RGBA32F => (16, &|i| f32_at(i * 4)),
+            TexturePixelKind::RGB16F => (6, &|i| f16_at(i * 2)),
+            TexturePixelKind::RGB16 => (6, &|i| u16_at(i * 2)),
+            TexturePixelKind::RGBA16 => (8, &|i| u16_at(i * 2)),
+            TexturePixelKind::RGB8 => (3, &u8_at),
+            TexturePixelKind::RGBA8 => (4, &u8_at),
+            _ => return Err(IblError::UnsupportedPixelKind(pixel_kind)),
+        };
+        let component_size = match pixel_kind {
+            TexturePixelKind::RGB32F | TexturePixelKind::RGBA32F => 4,
+            TexturePixelKind::RGB8 | TexturePixelKind::RGBA8 => 1,
+            _ => 2,
+        };
+        let components_per_pixel = stride / component_size;
+
+        let pixel_count = (width * height) as usize;
+        if data.len() < pixel_count * stride {
+            return Err(IblError::InvalidSize);
+        }
+
+        let pixels = (0..pixel_count)
+            .map(|i| {
+                let base = i * components_per_pixel;
+                Vector3::new(read(base), read(base + 1), read(base + 2))
+            })
+            .collect();
+
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    fn pixel(&self, x: i32, y: i32, wrap_y: bool) -> Vector3<f32> {
+        let x = x.rem_euclid(self.width as i32) as usize;
+        let y = if wrap_y {
+            y.rem_euclid(self.height as i32)
+        } else {
+            y.clamp(0, self.height as i32 - 1)
+        } as usize;
+        self.pixels[y * self.width as usize + x]
+    }
+
+    fn sample_bilinear(&self, u: f32, v: f32, wrap_v: bool) -> Vector3<f32> {
+        let fx = u * self.width as f32 - 0.5;
+        let fy = v * self.height as f32 - 0.5;
+        let x = fx.floor();
+        let y = fy.floor();
+        let tx = fx - x;
+        let ty = fy - y;
+        let (x, y) = (x as i32, y as i32);
+        let pixel = |x, y| self.pixel(x, y, wrap_v);
+        let top = pixel(x, y).lerp(&pixel(x + 1, y), tx);
+        let bottom = pixel(x, y + 1).lerp(&pixel(x + 1, y + 1), tx);
+        top.lerp(&bottom, ty)
+    }
+
+    /// Samples the image using bilinear filtering. Horizontal coordinate wraps around, vertical is
+    /// clamped, which is what an equirectangular panorama needs.
+    fn sample_uv(&self, u: f32, v: f32) -> Vector3<f32> {
+        self.sample_bilinear(u, v, false)
+    }
+
+    /// Samples the image using bilinear filtering. Both coordinates wrap around, just like texture
+    /// coordinates of a mesh with repeat wrapping mode.
+    pub(crate) fn sample_uv_repeat(&self, u: f32, v: f32) -> Vector3<f32> {
+        self.sample_bilinear(u, v, true)
+    }
+
+    /// Samples the image as an equirectangular panorama in the given direction.
+    fn sample_direction(&self, dir: Vector3<f32>) -> Vector3<f32> {
+        let u = 0.5 + dir.x.atan2(-dir.z) * 0.5 * FRAC_1_PI;
+        let v = dir.y.clamp(-1.0, 1.0).acos() * FRAC_1_PI;
+        self.sample_uv(u, v)
+    }
+}
+
+/// Projection of a function on a sphere to the first 9 real spherical harmonics basis functions.
+#[derive(Clone, Debug, Default, PartialEq, Reflect, Visit)]
+pub struct SphericalHarmonics {
+    /// Coefficients of the basis functions for each color channel.
+    pub coefficients: [Vector3<f32>; 9],
+}
+
+fn sh_basis(dir: Vector3<f32>) -> [f32; 9] {
+    let (x, y, z) = (dir.x, dir.y, dir.z);
+    [
+        0.282095,
+        0.488603 * y,
+        0.488603 * z,
+        0.488603 * x,
+        1.092548 * x * y,
+        1.092548 * y * z,
+        0.315392 * (3.0 * z * z - 1.0),
+        1.092548 * x * z,
+        0.546274 * (x * x - y * y),
+    ]
+}
+
+impl SphericalHarmonics {
+    /// Projects the given cube map onto the spherical harmonics basis.
+    pub fn project(cube: &CubeImage) -> Self {
+        let mut coefficients = [Vector3::default(); 9];
+        let mut total_weight = 0.0;
+        for (face, pixels) in cube.faces.iter().enumerate() {
+            for y in 0..cube.size {
+                for x in 0..cube.size {
+                    let dir = texel_direction(face, x, y, cube.size);
+                    let weight = texel_solid_angle(x, y, cube.size);
+                    let color = pixels[(y * cube.size + x) as usize];
+                    for (coefficient, basis) in coefficients.iter_mut().zip(sh_basis(dir)) {
+                        *coefficient += color * (basis * weight);
+                    }
+                    total_weight += weight;
+                }
+            }
+        }
+        // Compensate numerical error, so the sum of solid angles is exactly 4 * PI.
+        let normalization = 4.0 * PI / total_weight;
+        for coefficient in coefficients.iter_mut() {
+            *coefficient *= normalization;
+        }
+        Self { coefficients }
+    }
+
+    /// Returns irradiance divided by PI (which is the same as radiance of a diffuse surface with
+    /// white albedo) in the given direction.
+    pub fn irradiance(&self, dir: Vector3<f32>) -> Vector3<f32> {
+        // Convolution with clamped cosine lobe for each band.
+        const BAND_FACTORS: [f32; 9] = [
+            PI,
+            2.0 * PI / 3.0,
+            2.0 * PI / 3.0,
+            2.0 * PI / 3.0,
+            PI / 4.0,
+            PI / 4.0,
+            PI / 4.0,
+            PI / 4.0,
+            PI / 4.0,
+        ];
+
+        let mut result = Vector3::default();
+        for ((coefficient, basis), factor) in self
+            .coefficients
+            .iter()
+            .zip(sh_basis(dir))
+            .zip(BAND_FACTORS)
+        {
+            result += coefficient * (basis * factor);
+        }
+        result.map(|c| (c * FRAC_1_PI).max(0.0))
+    }
+}
+
+fn radical_inverse_vdc(mut bits: u32) -> f32 {
+    bits = bits.rotate_right(16);
+    bits = ((bits & 0x55555555) << 1) | ((bits & 0xAAAAAAAA) >> 1);
+    bits = ((bits & 0x33333333) << 2) | ((bits & 0xCCCCCCCC) >> 2);
+    bits = ((bits & 0x0F0F0F0F) << 4) | ((bits & 0xF0F0F0F0) >> 4);
+    bits = ((bits & 0x00FF00FF) << 8) | ((bits & 0xFF00FF00) >> 8);
+    bits as f32 * 2.328_306_4e-10
+}
+
+fn distribution_ggx(n_dot_h: f32, roughness: f32) -> f32 {
+    let a = roughness * roughness;
+    let a2 = a * a;
+    let d = n_dot_h * n_dot_h * (a2 - 1.0) + 1.0;
+    a2 / (PI * d * d).max(f32::EPSILON)
+}
+
+fn importance_sample_ggx(i: u32, count: u32, n: Vector3<f32>, roughness: f32) -> Vector3<f32> {
+    let a = roughness * roughness;
+    let xi_x = i as f32 / count as f32;
+    let xi_y = radical_inverse_vdc(i);
+
+    let phi = 2.0 * PI * xi_x;
+    let cos_theta = ((1.0 - xi_y) / (1.0 + (a * a - 1.0) * xi_y)).sqrt();
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let h = Vector3::new(phi.cos() * sin_theta, phi.sin() * sin_theta, cos_theta);
+
+    let up = if n.z.abs() < 0.999 {
+        Vector3::z()
+    } else {
+        Vector3::x()
+    };
+    let tangent = up.cross(&n).normalize();
+    let bitangent = n.cross(&tangent);
+    (tangent * h.x + bitangent * h.y + n * h.z).normalize()
+}
+
+/// Convolves the environment with GGX distribution of the given roughness. `chain` is the environment
+/// with all its downsampled versions, where the first one is the full-size environment.
+fn prefilter(chain: &[CubeImage], size: u32, roughness: f32) -> CubeImage {
+    let source_size = chain[0].size as f32;
+    let texel_solid_angle = 4.0 * PI / (6.0 * source_size * source_size);
+    let max_level = (chain.len() - 1) as f32;
+
+    CubeImage::from_fn(size, |n| {
+        let mut color = Vector3::default();
+        let mut total_weight = 0.0;
+        for i in 0..SPECULAR_SAMPLE_COUNT {
+            let h = importance_sample_ggx(i, SPECULAR_SAMPLE_COUNT, n, roughness);
+            let n_dot_h = n.dot(&h).max(0.0);
+            let l = h * (2.0 * n_dot_h) - n;
+            let n_dot_l = n.dot(&l);
+            if n_dot_l > 0.0 {
+                // Sample lower resolution version of the environment depending on the pdf of the
+                // sample to reduce aliasing.
+                let pdf = distribution_ggx(n_dot_h, roughness) * 0.25 + 0.0001;
+                let sample_solid_angle = 1.0 / (SPECULAR_SAMPLE_COUNT as f32 * pdf + 0.0001);
+                let level = (0.5 * (sample_solid_angle / texel_solid_angle).log2() + 1.0)
+                    .clamp(0.0, max_level);
+                color += chain[level.round() as usize].sample(l) * n_dot_l;
+                total_weight += n_dot_l;
+            }
+        }
+        if total_weight > 0.0 {
+            color / total_weight
+        } else {
+            color
+        }
+    })
+}
+
+/// A set of cube maps, that is used for image-based lighting.
+#[derive(Clone, Debug)]
+pub struct IblMaps {
+    /// Environment cube map that is used to draw a skybox.
+    pub environment: TextureResource,
+    /// Diffuse irradiance cube map (see module docs for more info).
+    pub irradiance: TextureResource,
+    /// Prefiltered specular cube map with [`SPECULAR_MAP_LEVELS`] mip levels (or less if the
+    /// environment is small).
+    pub specular: TextureResource,
+}
+
+impl IblMaps {
+    /// Generates IBL maps from the given environment cube map.
+    pub fn from_cube_image(environment: &CubeImage) -> Self {
+        let mut chain = vec![environment.clone()];
+        while chain.last().unwrap().size > 1 {
+            let next = chain.last().unwrap().downsample();
+            chain.push(next);
+        }
+
+        let sh_source = chain
+            .iter()
+            .find(|level| level.size <= MAX_SH_SOURCE_SIZE)
+            .unwrap_or_else(|| chain.last().unwrap());
+        let harmonics = SphericalHarmonics::project(sh_source);
+        let irradiance = CubeImage::from_fn(IRRADIANCE_MAP_SIZE, |dir| harmonics.irradiance(dir));
+
+        let specular_size = environment.size.min(MAX_SPECULAR_MAP_SIZE);
+        let level_count = SPECULAR_MAP_LEVELS.min(specular_size.ilog2() + 1);
+        let levels = (0..level_count)
+            .map(|level| {
+                let size = specular_size >> level;
+                if level == 0 {
+                    CubeImage::from_fn(size, |dir| environment.sample(dir))
+                } else {
+                    let roughness = level as f32 / (SPECULAR_MAP_LEVELS - 1) as f32;
+                    prefilter(&chain, size, roughness)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            environment: environment.to_texture(),
+            irradiance: irradiance.to_texture(),
+            specular: cube_texture(&levels),
+        }
+    }
+
+    /// Converts the given equirectangular panorama to a cube map with the given face size and generates
+    /// IBL maps from it.
+    pub fn from_equirectangular(texture: &Texture, face_size: u32) -> Result<Self, IblError> {
+        Ok(Self::from_cube_image(&CubeImage::from_equirectangular(
+            texture, face_size,
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        resource::texture::{
+            ibl::{
+                direction_to_face_uv, texel_direction, CubeImage, IblMaps, LinearImage,
+                SphericalHarmonics,
+            },
+            Texture, TextureKind, TexturePixelKind,
+        },
+    };
+
+    fn panorama(width: u32, height: u32, func: impl Fn(u32, u32) -> [f32; 3]) -> Texture {
+        let mut bytes = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                for c in func(x, y) {
+                    bytes.extend_from_slice(&c.to_le_bytes());
+                }
+            }
+        }
+        Texture::from_bytes(
+            TextureKind::Rectangle { width, height },
+            TexturePixelKind::RGB32F,
+            bytes,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_direction_round_trip() {
+        let size = 8;
+        for face in 0..6 {
+            for y in 0..size {
+                for x in 0..size {
+                    let dir = texel_direction(face, x, y, size);
+                    let (actual_face, u, v) = direction_to_face_uv(dir);
+                    assert_eq!(actual_face, face);
+                    assert!((u * size as f32 - 0.5 - x as f32).abs() < 1.0e-3);
+                    assert!((v * size as f32 - 0.5 - y as f32).abs() < 1.0e-3);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_linear_image_wrapping() {
+        // Top row is bright, bottom row is dark.
+        let texture = panorama(2, 2, |_, y| if y == 0 { [1.0; 3] } else { [0.0; 3] });
+        let image = LinearImage::new(&texture).unwrap();
+        // Panorama sampling clamps at the poles, repeat sampling blends with the opposite edge.
+        assert_eq!(image.sample_uv(0.25, 0.0), Vector3::repeat(1.0));
+        assert_eq!(image.sample_uv_repeat(0.25, 0.0), Vector3::repeat(0.5));
+        assert_eq!(image.sample_uv_repeat(1.25, 1.25), Vector3::repeat(1.0));
+    }
+
+    #[test]
+    fn test_constant_environment_irradiance() {
+        let color = Vector3::new(1.0, 0.5, 0.25);
+        let cube = CubeImage::from_fn(16, |_| color);
+        let harmonics = SphericalHarmonics::project(&cube);
+        for dir in [
+            Vector3::x(),
+            -Vector3::y(),
+            Vector3::new(1.0, 1.0, 1.0).normalize(),
+        ] {
+            let irradiance = harmonics.irradiance(dir);
+            assert!((irradiance - color).norm() < 1.0e-3, "{irradiance:?}");
+        }
+    }
+
+    #[test]
+    fn test_equirectangular_orientation() {
+        // Upper half is bright, lower half is dark.
+        let texture = panorama(16, 8, |_, y| if y < 4 { [1.0; 3] } else { [0.0; 3] });
+        let cube = CubeImage::from_equirectangular(&texture, 8).unwrap();
+        assert_eq!(cube.sample(Vector3::y()), Vector3::repeat(1.0));
+        assert_eq!(cube.sample(-Vector3::y()), Vector3::repeat(0.0));
+
+        let maps = IblMaps::from_cube_image(&cube);
+        let irradiance = maps.irradiance.data_ref();
+        let top = irradiance
+            .mip_level_data_of_type::<[f32; 3]>(0)
+            .unwrap()
+            .chunks(32 * 32)
+            .nth(2)
+            .unwrap()[0];
+        let bottom = irradiance
+            .mip_level_data_of_type::<[f32; 3]>(0)
+            .unwrap()
+            .chunks(32 * 32)
+            .nth(3)
+            .unwrap()[0];
+        assert!(top[0] > bottom[0]);
+    }
+
+    #[test]
+    fn test_specular_mip_chain() {
+        let cube = CubeImage::from_fn(32, |dir| Vector3::repeat(dir.y.max(0.0)));
+        let maps = IblMaps::from_cube_image(&cube);
+        let specular = maps.specular.data_ref();
+        assert_eq!(specular.mip_count(), 5);
+        assert!(matches!(
+            specular.kind(),
+            TextureKind::Cube {
+                width: 32,
+                height: 32
+            }
+        ));
+        assert_eq!(
+            specular.data().len(),
+            (0..5)
+                .map(|level| 6 * 12 * (32usize >> level).pow(2))
+                .sum::<usize>()
+        );
+    }
+}
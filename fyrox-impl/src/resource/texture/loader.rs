@@ -23,7 +23,7 @@ pub struct TextureLoader {
 impl ResourceLoader for TextureLoader {
     fn extensions(&self) -> &[&str] {
         &[
-            "jpg", "jpeg", "tga", "gif", "bmp", "png", "tiff", "tif", "dds",
+            "jpg", "jpeg", "tga", "gif", "bmp", "png", "tiff", "tif", "dds", "hdr", "exr",
         ]
     }
 
@@ -7,7 +7,9 @@
 //! ## Supported formats
 //!
 //! To load images and decode them, Fyrox uses image and ddsfile crates. Here is the list of
-//! supported formats: png, tga, bmp, dds, jpg, gif, tiff, dds.
+//! supported formats: png, tga, bmp, dds, jpg, gif, tiff, dds, hdr, exr. High dynamic range images
+//! (hdr, exr) are loaded as floating-point textures and could be used as a source for image-based
+//! lighting, see [`ibl`] module docs for more info.
 //!
 //! ## Compressed textures
 //!
@@ -24,6 +26,7 @@ use crate::{
     asset::{options::ImportOptions, Resource, ResourceData, TEXTURE_RESOURCE_UUID},
     core::{
         algebra::{Vector2, Vector3},
+        color::ColorSpace,
         futures::io::Error,
         io::FileLoadError,
         reflect::prelude::*,
@@ -55,6 +58,7 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 
+pub mod ibl;
 pub mod loader;
 
 /// Texture kind.
@@ -256,6 +260,7 @@ pub struct Texture {
     t_wrap_mode: TextureWrapMode,
     mip_count: u32,
     anisotropy: f32,
+    color_space: ColorSpace,
     modifications_counter: u64,
     is_render_target: bool,
     #[doc(hidden)]
@@ -347,6 +352,7 @@ impl Visit for Texture {
         self.t_wrap_mode.visit("TWrapMode", &mut region)?;
         self.mip_count.visit("MipCount", &mut region)?;
         self.kind.visit("Kind", &mut region)?;
+        let _ = self.color_space.visit("ColorSpace", &mut region);
         let mut bytes_view = PodVecView::from_pod_vec(&mut self.bytes);
         let _ = bytes_view.visit("Data", &mut region);
 
@@ -372,6 +378,7 @@ impl Default for Texture {
             t_wrap_mode: TextureWrapMode::Repeat,
             mip_count: 1,
             anisotropy: 16.0,
+            color_space: ColorSpace::Srgb,
             modifications_counter: 0,
             is_render_target: false,
             cache_index: Default::default(),
@@ -450,6 +457,8 @@ pub struct TextureImportOptions {
     pub(crate) mip_filter: MipFilter,
     #[serde(default)]
     pub(crate) flip_green_channel: bool,
+    #[serde(default)]
+    pub(crate) color_space: ColorSpace,
 }
 
 impl Default for TextureImportOptions {
@@ -463,6 +472,7 @@ impl Default for TextureImportOptions {
             compression: CompressionOptions::default(),
             mip_filter: Default::default(),
             flip_green_channel: false,
+            color_space: ColorSpace::Srgb,
         }
     }
 }
@@ -551,6 +561,19 @@ impl TextureImportOptions {
     pub fn set_compression(&mut self, compression: CompressionOptions) {
         self.compression = compression;
     }
+
+    /// Sets color space of the texture data. Color textures (albedo, emission, etc.) are usually
+    /// stored in sRGB, while data textures (normal maps, roughness maps, etc.) are linear.
+    pub fn with_color_space(mut self, color_space: ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+
+    /// Sets color space of the texture data. Color textures (albedo, emission, etc.) are usually
+    /// stored in sRGB, while data textures (normal maps, roughness maps, etc.) are linear.
+    pub fn set_color_space(&mut self, color_space: ColorSpace) {
+        self.color_space = color_space;
+    }
 }
 
 lazy_static! {
@@ -625,6 +648,7 @@ impl TextureResourceExtension for TextureResource {
                 t_wrap_mode: TextureWrapMode::Repeat,
                 mip_count: 1,
                 anisotropy: 1.0,
+                color_space: ColorSpace::Srgb,
                 modifications_counter: 0,
                 is_render_target: true,
                 cache_index: Default::default(),
@@ -1265,6 +1289,8 @@ fn convert_pixel_type_enum(pixel_kind: TexturePixelKind) -> fr::PixelType {
         TexturePixelKind::RGB16 => fr::PixelType::U16x3,
         TexturePixelKind::RGBA16 => fr::PixelType::U16x4,
         TexturePixelKind::R32F => fr::PixelType::F32,
+        TexturePixelKind::RGB32F => fr::PixelType::F32x3,
+        TexturePixelKind::RGBA32F => fr::PixelType::F32x4,
         _ => unreachable!(),
     }
 }
@@ -1370,6 +1396,7 @@ impl Texture {
                 s_wrap_mode: import_options.s_wrap_mode,
                 t_wrap_mode: import_options.t_wrap_mode,
                 anisotropy: import_options.anisotropy,
+                color_space: import_options.color_space,
                 mip_count,
                 bytes: bytes.into(),
                 kind: if dds.header.caps2 & Caps2::CUBEMAP == Caps2::CUBEMAP {
@@ -1521,6 +1548,7 @@ impl Texture {
                 s_wrap_mode: import_options.s_wrap_mode,
                 t_wrap_mode: import_options.t_wrap_mode,
                 anisotropy: import_options.anisotropy,
+                color_space: import_options.color_space,
                 is_render_target: false,
                 cache_index: Default::default(),
             })
@@ -1704,6 +1732,16 @@ impl Texture {
         self.anisotropy
     }
 
+    /// Sets color space of the texture data. See [`ColorSpace`] docs for more info.
+    pub fn set_color_space(&mut self, color_space: ColorSpace) {
+        self.color_space = color_space;
+    }
+
+    /// Returns color space of the texture data.
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+
     /// Returns a special reference holder that provides mutable access to content of the
     /// texture and automatically calculates hash of the data in its destructor.
     pub fn modify(&mut self) -> TextureDataRefMut<'_> {
@@ -0,0 +1,140 @@
+//! USDZ package reader. USDZ is a zip archive without compression and encryption, so its files
+//! could be read directly from the archive data.
+
+use crate::resource::usd::UsdLoadError;
+
+const LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x04034b50;
+const CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06054b50;
+
+/// A file stored in a USDZ package.
+pub struct ArchiveEntry<'a> {
+    /// Path of the file relative to the root of the package.
+    pub name: String,
+    /// Content of the file.
+    pub data: &'a [u8],
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, UsdLoadError> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| UsdLoadError::Archive("Unexpected end of archive".to_string()))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, UsdLoadError> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| UsdLoadError::Archive("Unexpected end of archive".to_string()))
+}
+
+/// Reads all files from a USDZ package, the files are returned in the same order as they're
+/// stored in the package. The first USD file is the root layer of the package.
+pub fn read_entries(data: &[u8]) -> Result<Vec<ArchiveEntry<'_>>, UsdLoadError> {
+    // End of central directory record is at least 22 bytes long and could be followed by
+    // a comment of up to 65535 bytes.
+    let search_start = data.len().saturating_sub(22 + u16::MAX as usize);
+    let end_of_directory = (search_start..data.len().saturating_sub(21))
+        .rev()
+        .find(|&offset| read_u32(data, offset).ok() == Some(END_OF_CENTRAL_DIRECTORY_SIGNATURE))
+        .ok_or_else(|| UsdLoadError::Archive("Not a zip archive".to_string()))?;
+
+    let entry_count = read_u16(data, end_of_directory + 10)? as usize;
+    let mut offset = read_u32(data, end_of_directory + 16)? as usize;
+
+    let mut entries = Vec::with_capacity(entry_count);
+    for _ in 0..entry_count {
+        if read_u32(data, offset)? != CENTRAL_DIRECTORY_SIGNATURE {
+            return Err(UsdLoadError::Archive(
+                "Invalid central directory".to_string(),
+            ));
+        }
+        let compression = read_u16(data, offset + 10)?;
+        let size = read_u32(data, offset + 20)? as usize;
+        let name_length = read_u16(data, offset + 28)? as usize;
+        let extra_length = read_u16(data, offset + 30)? as usize;
+        let comment_length = read_u16(data, offset + 32)? as usize;
+        let local_header = read_u32(data, offset + 42)? as usize;
+        let name = data
+            .get(offset + 46..offset + 46 + name_length)
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .ok_or_else(|| UsdLoadError::Archive("Unexpected end of archive".to_string()))?;
+        offset += 46 + name_length + extra_length + comment_length;
+
+        if compression != 0 {
+            return Err(UsdLoadError::Archive(format!(
+                "File {name} is compressed, USDZ packages must not contain compressed files"
+            )));
+        }
+
+        if read_u32(data, local_header)? != LOCAL_FILE_HEADER_SIGNATURE {
+            return Err(UsdLoadError::Archive(format!(
+                "Invalid local header of {name}"
+            )));
+        }
+        let local_name_length = read_u16(data, local_header + 26)? as usize;
+        let local_extra_length = read_u16(data, local_header + 28)? as usize;
+        let start = local_header + 30 + local_name_length + local_extra_length;
+        let data = data
+            .get(start..start + size)
+            .ok_or_else(|| UsdLoadError::Archive(format!("Unexpected end of archive in {name}")))?;
+
+        entries.push(ArchiveEntry { name, data });
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::resource::usd::archive::read_entries;
+
+    // Writes a zip archive without compression.
+    fn write_archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut directory = Vec::new();
+        for (name, content) in files {
+            let offset = data.len() as u32;
+            data.extend_from_slice(&0x04034b50u32.to_le_bytes());
+            data.extend_from_slice(&[0; 14]);
+            data.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            data.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            data.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            data.extend_from_slice(&0u16.to_le_bytes());
+            data.extend_from_slice(name.as_bytes());
+            data.extend_from_slice(content);
+
+            directory.extend_from_slice(&0x02014b50u32.to_le_bytes());
+            directory.extend_from_slice(&[0; 16]);
+            directory.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            directory.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            directory.extend_from_slice(&[0; 12]);
+            directory.extend_from_slice(&offset.to_le_bytes());
+            directory.extend_from_slice(name.as_bytes());
+        }
+        let directory_offset = data.len() as u32;
+        data.extend_from_slice(&directory);
+        data.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        data.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        data.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        data.extend_from_slice(&directory_offset.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_read_entries() {
+        let archive = write_archive(&[
+            ("scene.usda", b"#usda 1.0"),
+            ("textures/albedo.png", &[1, 2, 3]),
+        ]);
+        let entries = read_entries(&archive).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "scene.usda");
+        assert_eq!(entries[0].data, b"#usda 1.0");
+        assert_eq!(entries[1].name, "textures/albedo.png");
+        assert_eq!(entries[1].data, &[1, 2, 3]);
+    }
+}
@@ -0,0 +1,407 @@
+//! Conversion of a USD stage to a scene graph.
+
+use std::path::PathBuf;
+
+use crate::asset::manager::ResourceManager;
+use crate::core::algebra::{
+    Matrix3, Matrix4, Quaternion, Rotation3, Unit, UnitQuaternion, Vector3,
+};
+use crate::core::pool::Handle;
+use crate::fxhash::{FxHashMap, FxHashSet};
+use crate::graph::BaseSceneGraph;
+use crate::material::MaterialResource;
+use crate::resource::model::MaterialSearchOptions;
+use crate::resource::usd::mesh::{convert_gprim, convert_mesh};
+use crate::resource::usd::parser::{Prim, Specifier, Stage, Value};
+use crate::scene::base::BaseBuilder;
+use crate::scene::graph::Graph;
+use crate::scene::node::Node;
+use crate::scene::pivot::PivotBuilder;
+use crate::scene::transform::{Transform, TransformBuilder};
+
+pub struct ImportContext<'a> {
+    pub stage: &'a Stage,
+    pub resource_manager: ResourceManager,
+    pub model_path: PathBuf,
+    pub search_options: MaterialSearchOptions,
+    /// Files of a USDZ package (if any).
+    pub embedded_files: Vec<(String, Vec<u8>)>,
+    /// Material prim path -> converted material.
+    pub materials: FxHashMap<String, MaterialResource>,
+    /// Descriptions of everything, that was skipped during conversion.
+    pub unsupported: FxHashSet<String>,
+}
+
+/// Converts every prim of the stage and links them to the root of the graph.
+pub async fn convert(graph: &mut Graph, context: &mut ImportContext<'_>) {
+    let stage = context.stage;
+
+    // Prototypes are converted for each instance of a point instancer.
+    let mut prototypes = FxHashSet::default();
+    for prim in stage.prims.iter() {
+        collect_prototypes(prim, &mut prototypes);
+    }
+
+    let mut correction = Matrix4::identity();
+    if let Some(Value::Number(meters_per_unit)) = stage.metadata("metersPerUnit") {
+        correction = Matrix4::new_scaling(*meters_per_unit as f32);
+    }
+    if let Some("Z") = stage.metadata("upAxis").and_then(Value::as_str) {
+        // The engine uses Y axis as up vector.
+        correction *= Matrix4::from_axis_angle(&Vector3::x_axis(), -std::f32::consts::FRAC_PI_2);
+    }
+
+    if stage.metadata("subLayers").is_some() {
+        context.unsupported.insert("Sub layers".to_string());
+    }
+
+    let root = graph.get_root();
+    for prim in stage.prims.iter() {
+        if let Some(node) = Box::pin(convert_prim(
+            prim,
+            graph,
+            context,
+            &prototypes,
+            &correction,
+            None,
+        ))
+        .await
+        {
+            graph.link_nodes(node, root);
+        }
+    }
+}
+
+fn collect_prototypes(prim: &Prim, prototypes: &mut FxHashSet<String>) {
+    if prim.type_name == "PointInstancer" {
+        for path in prim.relationship("prototypes") {
+            prototypes.insert(path.to_string());
+        }
+    }
+    for child in prim.children.iter() {
+        collect_prototypes(child, prototypes);
+    }
+}
+
+fn rotation_about(axis: char, angle: f32) -> Matrix4<f32> {
+    let axis = match axis {
+        'X' => Vector3::x_axis(),
+        'Y' => Vector3::y_axis(),
+        _ => Vector3::z_axis(),
+    };
+    Matrix4::from_axis_angle(&axis, angle.to_radians())
+}
+
+fn xform_op_matrix(op: &str, value: &Value) -> Option<Matrix4<f32>> {
+    // `xformOp:<kind>[:<suffix>]`
+    let kind = op.strip_prefix("xformOp:")?.split(':').next()?;
+    match kind {
+        "translate" => {
+            let v = value.as_floats()?;
+            Some(Matrix4::new_translation(&Vector3::new(
+                *v.first()?,
+                *v.get(1)?,
+                *v.get(2)?,
+            )))
+        }
+        "scale" => {
+            let v = value.as_floats()?;
+            Some(Matrix4::new_nonuniform_scaling(&Vector3::new(
+                *v.first()?,
+                *v.get(1)?,
+                *v.get(2)?,
+            )))
+        }
+        "orient" => {
+            let v = value.as_floats()?;
+            let q = Quaternion::new(*v.first()?, *v.get(1)?, *v.get(2)?, *v.get(3)?);
+            Some(Unit::new_normalize(q).to_homogeneous())
+        }
+        "transform" => {
+            let Value::Tuple(rows) = value else {
+                return None;
+            };
+            let values = rows
+                .iter()
+                .map(Value::as_floats)
+                .collect::<Option<Vec<_>>>()?
+                .concat();
+            // USD uses row vectors, so the matrix is stored transposed.
+            (values.len() == 16).then(|| Matrix4::from_column_slice(&values))
+        }
+        "rotateX" | "rotateY" | "rotateZ" => {
+            let axis = kind.chars().last()?;
+            Some(rotation_about(axis, value.as_f32()?))
+        }
+        _ => {
+            // rotateXYZ, rotateZYX, etc. The first axis in the name is applied first.
+            let axes = kind.strip_prefix("rotate")?;
+            let angles = value.as_floats()?;
+            if axes.len() != 3 || angles.len() != 3 {
+                return None;
+            }
+            let mut matrix = Matrix4::identity();
+            for axis in axes.chars() {
+                let angle = match axis {
+                    'X' => angles[0],
+                    'Y' => angles[1],
+                    _ => angles[2],
+                };
+                matrix = rotation_about(axis, angle) * matrix;
+            }
+            Some(matrix)
+        }
+    }
+}
+
+/// Calculates a local transform matrix of a prim using its transform operations.
+pub fn local_matrix(prim: &Prim, context: &mut ImportContext) -> Matrix4<f32> {
+    let mut matrix = Matrix4::identity();
+    let Some(order) = prim.attribute("xformOpOrder") else {
+        return matrix;
+    };
+    for op in order.elements().iter().filter_map(Value::as_str) {
+        if op == "!resetXformStack!" {
+            continue;
+        }
+        let (op, invert) = match op.strip_prefix("!invert!") {
+            Some(op) => (op, true),
+            None => (op, false),
+        };
+        match prim
+            .attribute(op)
+            .and_then(|value| xform_op_matrix(op, value))
+        {
+            Some(op_matrix) => {
+                let op_matrix = if invert {
+                    op_matrix.try_inverse().unwrap_or_default()
+                } else {
+                    op_matrix
+                };
+                matrix *= op_matrix;
+            }
+            None => {
+                context
+                    .unsupported
+                    .insert(format!("Transform operation {op}"));
+            }
+        }
+    }
+    matrix
+}
+
+/// Decomposes a matrix into translation, rotation and scale. Shear is lost.
+pub fn matrix_to_transform(matrix: &Matrix4<f32>) -> Transform {
+    let translation = Vector3::new(matrix[(0, 3)], matrix[(1, 3)], matrix[(2, 3)]);
+    let basis = matrix.fixed_view::<3, 3>(0, 0).into_owned();
+    let mut scale = Vector3::new(
+        basis.column(0).norm(),
+        basis.column(1).norm(),
+        basis.column(2).norm(),
+    );
+    if basis.determinant() < 0.0 {
+        scale.x = -scale.x;
+    }
+    let safe = |s: f32| if s.abs() > f32::EPSILON { s } else { 1.0 };
+    let rotation_matrix = Matrix3::from_columns(&[
+        basis.column(0) / safe(scale.x),
+        basis.column(1) / safe(scale.y),
+        basis.column(2) / safe(scale.z),
+    ]);
+    let rotation =
+        UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(rotation_matrix));
+    TransformBuilder::new()
+        .with_local_position(translation)
+        .with_local_rotation(rotation)
+        .with_local_scale(scale)
+        .build()
+}
+
+async fn convert_prim(
+    prim: &Prim,
+    graph: &mut Graph,
+    context: &mut ImportContext<'_>,
+    prototypes: &FxHashSet<String>,
+    correction: &Matrix4<f32>,
+    inherited_material: Option<&str>,
+) -> Option<Handle<Node>> {
+    // Overrides and classes have no effect without composition.
+    if prim.specifier != Specifier::Def
+        || prim.metadata("active") == Some(&Value::Bool(false))
+        || prototypes.contains(&prim.path)
+    {
+        return None;
+    }
+
+    for arc in ["references", "payload", "inherits", "specializes"] {
+        if prim.metadata(arc).is_some() {
+            context.unsupported.insert(format!("Composition arc {arc}"));
+        }
+    }
+    if prim.variant_set_count > 0 {
+        context.unsupported.insert("Variant sets".to_string());
+    }
+
+    // Material bindings are inherited by descendant prims.
+    let material = prim
+        .relationship("material:binding")
+        .first()
+        .cloned()
+        .or(inherited_material);
+
+    let matrix = correction * local_matrix(prim, context);
+    let base = BaseBuilder::new()
+        .with_name(prim.name.as_str())
+        .with_local_transform(matrix_to_transform(&matrix))
+        .with_visibility(prim.attribute("visibility").and_then(Value::as_str) != Some("invisible"));
+
+    let node = match prim.type_name.as_str() {
+        // Materials are converted on demand, when a mesh is bound to a material.
+        "Material" | "Shader" | "NodeGraph" | "GeomSubset" => return None,
+        "Mesh" => convert_mesh(prim, base, graph, context, material).await,
+        "Cube" | "Sphere" => convert_gprim(prim, base, graph, context, material).await,
+        "PointInstancer" => {
+            let node = PivotBuilder::new(base).build(graph);
+            convert_point_instancer(prim, node, graph, context, material).await;
+            node
+        }
+        "" | "Xform" | "Scope" => PivotBuilder::new(base).build(graph),
+        type_name => {
+            context.unsupported.insert(format!("{type_name} prims"));
+            PivotBuilder::new(base).build(graph)
+        }
+    };
+
+    for child in prim.children.iter() {
+        if let Some(child) = Box::pin(convert_prim(
+            child,
+            graph,
+            context,
+            prototypes,
+            &Matrix4::identity(),
+            material,
+        ))
+        .await
+        {
+            graph.link_nodes(child, node);
+        }
+    }
+
+    Some(node)
+}
+
+async fn convert_point_instancer(
+    prim: &Prim,
+    instancer: Handle<Node>,
+    graph: &mut Graph,
+    context: &mut ImportContext<'_>,
+    material: Option<&str>,
+) {
+    let stage = context.stage;
+
+    // Convert each prototype once, instances are copies of them.
+    let mut prototypes = Vec::new();
+    for path in prim.relationship("prototypes") {
+        let prototype = match stage.find(path) {
+            Some(prototype_prim) => {
+                Box::pin(convert_prim(
+                    prototype_prim,
+                    graph,
+                    context,
+                    &Default::default(),
+                    &Matrix4::identity(),
+                    material,
+                ))
+                .await
+            }
+            None => None,
+        };
+        prototypes.push(prototype.unwrap_or_default());
+    }
+
+    let get_floats = |name: &str| -> Vec<Vec<f32>> {
+        prim.attribute(name)
+            .map(|value| {
+                value
+                    .elements()
+                    .iter()
+                    .filter_map(Value::as_floats)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let positions = get_floats("positions");
+    let orientations = get_floats("orientations");
+    let scales = get_floats("scales");
+    let invisible_ids = prim
+        .attribute("invisibleIds")
+        .map(|value| {
+            value
+                .elements()
+                .iter()
+                .filter_map(Value::as_f32)
+                .map(|id| id as usize)
+                .collect::<FxHashSet<_>>()
+        })
+        .unwrap_or_default();
+    let ids = prim
+        .attribute("ids")
+        .map(|value| {
+            value
+                .elements()
+                .iter()
+                .filter_map(Value::as_f32)
+                .map(|id| id as usize)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let proto_indices = prim
+        .attribute("protoIndices")
+        .map(|value| {
+            value
+                .elements()
+                .iter()
+                .filter_map(Value::as_f32)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    for (i, proto_index) in proto_indices.into_iter().enumerate() {
+        let id = ids.get(i).cloned().unwrap_or(i);
+        let Some(&prototype) = prototypes.get(proto_index as usize) else {
+            continue;
+        };
+        if prototype.is_none() || invisible_ids.contains(&id) {
+            continue;
+        }
+
+        let mut transform = TransformBuilder::new();
+        if let Some(&[x, y, z]) = positions.get(i).map(Vec::as_slice) {
+            transform = transform.with_local_position(Vector3::new(x, y, z));
+        }
+        if let Some(&[w, x, y, z]) = orientations.get(i).map(Vec::as_slice) {
+            transform =
+                transform.with_local_rotation(Unit::new_normalize(Quaternion::new(w, x, y, z)));
+        }
+        if let Some(&[x, y, z]) = scales.get(i).map(Vec::as_slice) {
+            transform = transform.with_local_scale(Vector3::new(x, y, z));
+        }
+
+        let instance = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name(format!("{}_{}", prim.name, id))
+                .with_local_transform(transform.build()),
+        )
+        .build(graph);
+        let (copy, _) = graph.copy_node_inplace(prototype, &mut |_, _| true);
+        graph.link_nodes(copy, instance);
+        graph.link_nodes(instance, instancer);
+    }
+
+    for prototype in prototypes {
+        if prototype.is_some() {
+            graph.remove_node(prototype);
+        }
+    }
+}
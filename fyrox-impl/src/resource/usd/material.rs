@@ -0,0 +1,394 @@
+//! Conversion of `UsdPreviewSurface` materials.
+
+use std::path::{Path, PathBuf};
+
+use crate::asset::Resource;
+use crate::core::algebra::{Vector3, Vector4};
+use crate::core::color::Color;
+use crate::core::log::Log;
+use crate::core::sstorage::ImmutableString;
+use crate::material::shader::SamplerFallback;
+use crate::material::{Material, MaterialResource, PropertyValue};
+use crate::resource::model::MaterialSearchOptions;
+use crate::resource::texture::{
+    Texture, TextureImportOptions, TextureKind, TexturePixelKind, TextureResource,
+};
+use crate::resource::usd::convert::ImportContext;
+use crate::resource::usd::parser::{Prim, Stage, Value};
+use fyrox_resource::untyped::ResourceKind;
+
+/// Maximum length of a chain of connections, it protects from cyclic connections.
+const MAX_CONNECTION_DEPTH: usize = 16;
+
+enum Input<'a> {
+    Value(&'a Value),
+    Texture(&'a Prim),
+}
+
+fn split_property_path(path: &str) -> Option<(&str, &str)> {
+    path.rsplit_once('.')
+}
+
+/// Follows connections of an input until a value or a texture is found.
+fn resolve_input<'a>(
+    stage: &'a Stage,
+    prim: &'a Prim,
+    input: &str,
+    unsupported: &mut Vec<String>,
+) -> Option<Input<'a>> {
+    let mut prim = prim;
+    let mut property = input.to_string();
+    for _ in 0..MAX_CONNECTION_DEPTH {
+        let Some(source) = prim.connection(&property) else {
+            return prim.attribute(&property).map(Input::Value);
+        };
+        let (source_prim, source_property) = split_property_path(source)?;
+        let source_prim = stage.find(source_prim)?;
+        if source_prim.type_name == "Shader" {
+            return match source_prim.attribute("info:id").and_then(Value::as_str) {
+                Some("UsdUVTexture") => Some(Input::Texture(source_prim)),
+                id => {
+                    unsupported.push(format!("Shader {}", id.unwrap_or("without id")));
+                    None
+                }
+            };
+        }
+        // Outputs of node graphs and materials are connected to other shaders.
+        prim = source_prim;
+        property = source_property.to_string();
+    }
+    None
+}
+
+fn find_surface_shader<'a>(stage: &'a Stage, material: &'a Prim) -> Option<&'a Prim> {
+    let is_preview_surface = |prim: &Prim| {
+        prim.attribute("info:id").and_then(Value::as_str) == Some("UsdPreviewSurface")
+    };
+
+    let mut prim = material;
+    let mut property = "outputs:surface".to_string();
+    for _ in 0..MAX_CONNECTION_DEPTH {
+        let Some((source_prim, source_property)) =
+            prim.connection(&property).and_then(split_property_path)
+        else {
+            break;
+        };
+        let source_prim = stage.find(source_prim)?;
+        if is_preview_surface(source_prim) {
+            return Some(source_prim);
+        }
+        prim = source_prim;
+        property = source_property.to_string();
+    }
+
+    // Some exporters do not connect the surface output.
+    material
+        .children
+        .iter()
+        .find(|child| is_preview_surface(child))
+}
+
+fn value_to_color(value: &Value) -> Option<Color> {
+    match value.as_floats()?.as_slice() {
+        &[r, g, b] | &[r, g, b, _] => Some(Color::from(Vector4::new(r, g, b, 1.0))),
+        _ => None,
+    }
+}
+
+fn constant_texture(pixel: [u8; 4]) -> Option<TextureResource> {
+    let texture = Texture::from_bytes(
+        TextureKind::Rectangle {
+            width: 1,
+            height: 1,
+        },
+        TexturePixelKind::RGBA8,
+        pixel.to_vec(),
+    )?;
+    Some(Resource::new_ok(ResourceKind::Embedded, texture))
+}
+
+fn set_property(material: &mut Material, name: &'static str, value: PropertyValue) {
+    if let Err(err) = material.set_property(&ImmutableString::new(name), value) {
+        Log::err(format!(
+            "Unable to set material property {} for USD material! Reason: {:?}",
+            name, err
+        ));
+    }
+}
+
+fn set_texture(
+    material: &mut Material,
+    name: &'static str,
+    texture: Option<TextureResource>,
+    fallback: SamplerFallback,
+) {
+    if let Some(texture) = texture {
+        set_property(
+            material,
+            name,
+            PropertyValue::Sampler {
+                value: Some(texture),
+                fallback,
+            },
+        );
+    }
+}
+
+async fn search_for_path(filename: &Path, context: &ImportContext<'_>) -> Option<PathBuf> {
+    let io = context.resource_manager.resource_io();
+
+    // Asset paths are relative to the layer.
+    let relative = context.model_path.parent()?.join(filename);
+    if io.exists(&relative).await {
+        return Some(relative);
+    }
+
+    let filename = filename.file_name()?;
+    match context.search_options {
+        MaterialSearchOptions::MaterialsDirectory(ref directory) => Some(directory.join(filename)),
+        MaterialSearchOptions::RecursiveUp => {
+            let mut path = context.model_path.clone();
+            while let Some(parent) = path.parent() {
+                let candidate = parent.join(filename);
+                if io.exists(&candidate).await {
+                    return Some(candidate);
+                }
+                path.pop();
+            }
+            None
+        }
+        MaterialSearchOptions::WorkingDirectory => {
+            if let Ok(iter) = io.walk_directory(Path::new(".")).await {
+                for dir in iter {
+                    if io.is_dir(&dir).await {
+                        let candidate = dir.join(filename);
+                        if io.exists(&candidate).await {
+                            return Some(candidate);
+                        }
+                    }
+                }
+            }
+            None
+        }
+        MaterialSearchOptions::UsePathDirectly => Some(relative),
+    }
+}
+
+async fn load_texture(texture: &Prim, context: &mut ImportContext<'_>) -> Option<TextureResource> {
+    let asset = texture.attribute("inputs:file").and_then(Value::as_str)?;
+    if asset.contains("<UDIM>") {
+        context.unsupported.insert("UDIM textures".to_string());
+        return None;
+    }
+    let asset = asset.replace('\\', "/");
+    let asset = asset.trim_start_matches("./");
+
+    // Textures of a package are stored in the package itself.
+    if let Some((_, data)) = context
+        .embedded_files
+        .iter()
+        .find(|(name, _)| name == asset)
+    {
+        return match Texture::load_from_memory(data, TextureImportOptions::default()) {
+            Ok(texture) => Some(Resource::new_ok(ResourceKind::Embedded, texture)),
+            Err(err) => {
+                Log::err(format!(
+                    "Unable to load embedded texture {asset}. Reason: {err:?}"
+                ));
+                None
+            }
+        };
+    }
+
+    match search_for_path(Path::new(asset), context).await {
+        Some(path) => Some(context.resource_manager.request(path)),
+        None => {
+            Log::warn(format!(
+                "Unable to find a texture {} for USD stage {} using {:?} option!",
+                asset,
+                context.model_path.display(),
+                context.search_options
+            ));
+            None
+        }
+    }
+}
+
+async fn convert_material(prim: &Prim, context: &mut ImportContext<'_>) -> Material {
+    let stage = context.stage;
+    let mut material = Material::standard();
+
+    let Some(shader) = find_surface_shader(stage, prim) else {
+        context
+            .unsupported
+            .insert("Materials without UsdPreviewSurface".to_string());
+        return material;
+    };
+
+    let mut unsupported = Vec::new();
+
+    let opacity = match resolve_input(stage, shader, "inputs:opacity", &mut unsupported) {
+        Some(Input::Value(value)) => value.as_f32().unwrap_or(1.0),
+        _ => 1.0,
+    };
+    let mut diffuse_color = Color::WHITE;
+    match resolve_input(stage, shader, "inputs:diffuseColor", &mut unsupported) {
+        Some(Input::Value(value)) => {
+            diffuse_color = value_to_color(value).unwrap_or(Color::WHITE);
+        }
+        Some(Input::Texture(texture)) => {
+            let texture = load_texture(texture, context).await;
+            set_texture(
+                &mut material,
+                "diffuseTexture",
+                texture,
+                SamplerFallback::White,
+            );
+        }
+        None => (),
+    }
+    diffuse_color.a = (opacity.clamp(0.0, 1.0) * 255.0) as u8;
+    set_property(
+        &mut material,
+        "diffuseColor",
+        PropertyValue::Color(diffuse_color),
+    );
+
+    match resolve_input(stage, shader, "inputs:emissiveColor", &mut unsupported) {
+        Some(Input::Value(value)) => {
+            if let Some(color) = value.as_floats().filter(|c| c.len() >= 3) {
+                if color.iter().any(|c| *c > 0.0) {
+                    set_texture(
+                        &mut material,
+                        "emissionTexture",
+                        constant_texture([255; 4]),
+                        SamplerFallback::Black,
+                    );
+                    set_property(
+                        &mut material,
+                        "emissionStrength",
+                        PropertyValue::Vector3(Vector3::new(color[0], color[1], color[2])),
+                    );
+                }
+            }
+        }
+        Some(Input::Texture(texture)) => {
+            let texture = load_texture(texture, context).await;
+            set_texture(
+                &mut material,
+                "emissionTexture",
+                texture,
+                SamplerFallback::Black,
+            );
+            set_property(
+                &mut material,
+                "emissionStrength",
+                PropertyValue::Vector3(Vector3::repeat(1.0)),
+            );
+        }
+        None => (),
+    }
+
+    // Metallic and roughness are scalars in USD, but the standard shader takes them from
+    // textures only.
+    for (input, property, default, fallback) in [
+        (
+            "inputs:metallic",
+            "metallicTexture",
+            0.0,
+            SamplerFallback::Black,
+        ),
+        (
+            "inputs:roughness",
+            "roughnessTexture",
+            0.5,
+            SamplerFallback::White,
+        ),
+    ] {
+        match resolve_input(stage, shader, input, &mut unsupported) {
+            Some(Input::Value(value)) => {
+                let value = value.as_f32().unwrap_or(default).clamp(0.0, 1.0);
+                let byte = (value * 255.0) as u8;
+                set_texture(
+                    &mut material,
+                    property,
+                    constant_texture([byte; 4]),
+                    fallback,
+                );
+            }
+            Some(Input::Texture(texture)) => {
+                let texture = load_texture(texture, context).await;
+                set_texture(&mut material, property, texture, fallback);
+            }
+            None => {
+                let byte = (default * 255.0) as u8;
+                set_texture(
+                    &mut material,
+                    property,
+                    constant_texture([byte; 4]),
+                    fallback,
+                );
+            }
+        }
+    }
+
+    for (input, property, fallback) in [
+        ("inputs:normal", "normalTexture", SamplerFallback::Normal),
+        ("inputs:occlusion", "aoTexture", SamplerFallback::White),
+        (
+            "inputs:displacement",
+            "heightTexture",
+            SamplerFallback::Black,
+        ),
+    ] {
+        if let Some(Input::Texture(texture)) = resolve_input(stage, shader, input, &mut unsupported)
+        {
+            let texture = load_texture(texture, context).await;
+            set_texture(&mut material, property, texture, fallback);
+        }
+    }
+
+    context.unsupported.extend(unsupported);
+
+    material
+}
+
+/// Returns a material for the given material prim path. Materials are cached, so every mesh bound
+/// to the same material shares it. Meshes without a material use their display color (if any).
+pub async fn resolve_material(
+    path: Option<&str>,
+    display_color: Option<Color>,
+    context: &mut ImportContext<'_>,
+) -> MaterialResource {
+    let stage = context.stage;
+    if let Some(prim) = path
+        .and_then(|path| stage.find(path))
+        .filter(|prim| prim.type_name == "Material")
+    {
+        if let Some(material) = context.materials.get(&prim.path) {
+            return material.clone();
+        }
+        let material = MaterialResource::new_ok(
+            ResourceKind::Embedded,
+            convert_material(prim, context).await,
+        );
+        context
+            .materials
+            .insert(prim.path.clone(), material.clone());
+        return material;
+    }
+
+    let mut material = Material::standard();
+    if let Some(color) = display_color {
+        set_property(&mut material, "diffuseColor", PropertyValue::Color(color));
+    }
+    MaterialResource::new_ok(ResourceKind::Embedded, material)
+}
+
+/// Converts a value of display color primvar to a color.
+pub fn display_color(prim: &Prim) -> Option<Color> {
+    prim.attribute("primvars:displayColor")?
+        .elements()
+        .first()
+        .and_then(value_to_color)
+}
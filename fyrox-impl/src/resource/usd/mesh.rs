@@ -0,0 +1,295 @@
+//! Conversion of USD geometry (meshes and simple geometric primitives).
+
+use crate::core::algebra::{Matrix4, Vector2, Vector3, Vector4};
+use crate::core::log::Log;
+use crate::core::math::triangulator::triangulate;
+use crate::core::pool::Handle;
+use crate::resource::usd::convert::ImportContext;
+use crate::resource::usd::material::{display_color, resolve_material};
+use crate::resource::usd::parser::{Prim, Value};
+use crate::scene::base::BaseBuilder;
+use crate::scene::graph::Graph;
+use crate::scene::mesh::surface::{Surface, SurfaceData, SurfaceResource};
+use crate::scene::mesh::vertex::StaticVertex;
+use crate::scene::mesh::MeshBuilder;
+use crate::scene::node::Node;
+use crate::utils::raw_mesh::RawMeshBuilder;
+use fyrox_resource::untyped::ResourceKind;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Interpolation {
+    Constant,
+    Uniform,
+    Vertex,
+    FaceVarying,
+}
+
+/// Primitive variable - a value, that is interpolated over the surface of a mesh.
+struct Primvar {
+    values: Vec<Vec<f32>>,
+    indices: Option<Vec<usize>>,
+    interpolation: Interpolation,
+}
+
+impl Primvar {
+    fn read(prim: &Prim, name: &str, topology: &Topology) -> Option<Self> {
+        let values = prim
+            .attribute(name)?
+            .elements()
+            .iter()
+            .filter_map(|value| {
+                value
+                    .as_floats()
+                    .or_else(|| value.as_f32().map(|v| vec![v]))
+            })
+            .collect::<Vec<_>>();
+        let indices = prim.attribute(&format!("{name}:indices")).map(read_indices);
+        let count = indices.as_ref().map_or(values.len(), Vec::len);
+        let interpolation = match prim
+            .attribute_metadata(name, "interpolation")
+            .and_then(Value::as_str)
+        {
+            Some("constant") => Interpolation::Constant,
+            Some("uniform") => Interpolation::Uniform,
+            Some("vertex" | "varying") => Interpolation::Vertex,
+            Some("faceVarying") => Interpolation::FaceVarying,
+            // Guess interpolation by the amount of values.
+            _ if count == topology.face_vertex_indices.len() => Interpolation::FaceVarying,
+            _ if count == topology.points.len() => Interpolation::Vertex,
+            _ if count == topology.face_vertex_counts.len() => Interpolation::Uniform,
+            _ => Interpolation::Constant,
+        };
+        Some(Self {
+            values,
+            indices,
+            interpolation,
+        })
+    }
+
+    fn get(&self, face: usize, point: usize, face_vertex: usize) -> Option<&[f32]> {
+        let index = match self.interpolation {
+            Interpolation::Constant => 0,
+            Interpolation::Uniform => face,
+            Interpolation::Vertex => point,
+            Interpolation::FaceVarying => face_vertex,
+        };
+        let index = match self.indices {
+            Some(ref indices) => *indices.get(index)?,
+            None => index,
+        };
+        self.values.get(index).map(Vec::as_slice)
+    }
+}
+
+struct Topology {
+    points: Vec<Vector3<f32>>,
+    face_vertex_counts: Vec<usize>,
+    face_vertex_indices: Vec<usize>,
+}
+
+fn read_indices(value: &Value) -> Vec<usize> {
+    value
+        .elements()
+        .iter()
+        .filter_map(Value::as_f32)
+        .map(|i| i.max(0.0) as usize)
+        .collect()
+}
+
+fn read_topology(prim: &Prim) -> Topology {
+    Topology {
+        points: prim
+            .attribute("points")
+            .map(|value| {
+                value
+                    .elements()
+                    .iter()
+                    .filter_map(Value::as_floats)
+                    .filter_map(|p| Some(Vector3::new(*p.first()?, *p.get(1)?, *p.get(2)?)))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        face_vertex_counts: prim
+            .attribute("faceVertexCounts")
+            .map(read_indices)
+            .unwrap_or_default(),
+        face_vertex_indices: prim
+            .attribute("faceVertexIndices")
+            .map(read_indices)
+            .unwrap_or_default(),
+    }
+}
+
+/// Searches for texture coordinates primvar. `st` is the standard name, but DCC tools often use
+/// their own names (`UVMap`, `map1`, etc.).
+fn find_uv_primvar(prim: &Prim) -> Option<&str> {
+    if prim.attribute("primvars:st").is_some() {
+        return Some("primvars:st");
+    }
+    prim.properties
+        .iter()
+        .find(|p| {
+            p.name.starts_with("primvars:")
+                && !p.name.ends_with(":indices")
+                && (p.type_name.starts_with("texCoord2") || p.type_name.starts_with("float2"))
+        })
+        .map(|p| p.name.as_str())
+}
+
+fn make_surface(data: SurfaceData, context: &ImportContext) -> Surface {
+    Surface::new(SurfaceResource::new_ok(
+        ResourceKind::External(context.model_path.clone()),
+        data,
+    ))
+}
+
+pub async fn convert_mesh(
+    prim: &Prim,
+    base: BaseBuilder,
+    graph: &mut Graph,
+    context: &mut ImportContext<'_>,
+    material: Option<&str>,
+) -> Handle<Node> {
+    let topology = read_topology(prim);
+    let normals = Primvar::read(prim, "primvars:normals", &topology)
+        .or_else(|| Primvar::read(prim, "normals", &topology));
+    let uvs = find_uv_primvar(prim).and_then(|name| Primvar::read(prim, name, &topology));
+    let left_handed = prim.attribute("orientation").and_then(Value::as_str) == Some("leftHanded");
+
+    // Geometry subsets split faces of a mesh in groups with their own materials.
+    let mut groups = vec![(material, RawMeshBuilder::<StaticVertex>::new(1024, 1024))];
+    let mut face_groups = vec![0; topology.face_vertex_counts.len()];
+    for subset in prim
+        .children
+        .iter()
+        .filter(|child| child.type_name == "GeomSubset")
+    {
+        let family = subset.attribute("familyName").and_then(Value::as_str);
+        let subset_material = subset.relationship("material:binding").first().cloned();
+        if family.is_some_and(|f| f != "materialBind") && subset_material.is_none() {
+            continue;
+        }
+        groups.push((
+            subset_material.or(material),
+            RawMeshBuilder::<StaticVertex>::new(1024, 1024),
+        ));
+        let group = groups.len() - 1;
+        for face in subset
+            .attribute("indices")
+            .map(read_indices)
+            .unwrap_or_default()
+        {
+            if let Some(face_group) = face_groups.get_mut(face) {
+                *face_group = group;
+            }
+        }
+    }
+
+    let mut polygon = Vec::new();
+    let mut triangles = Vec::new();
+    let mut invalid_faces = 0;
+    let mut offset = 0;
+    for (face, &count) in topology.face_vertex_counts.iter().enumerate() {
+        let face_vertices = offset..offset + count;
+        offset += count;
+
+        let Some(points) = topology.face_vertex_indices.get(face_vertices.clone()) else {
+            invalid_faces += 1;
+            continue;
+        };
+        polygon.clear();
+        for &point in points {
+            match topology.points.get(point) {
+                Some(position) => polygon.push(*position),
+                None => break,
+            }
+        }
+        if count < 3 || polygon.len() != count {
+            invalid_faces += 1;
+            continue;
+        }
+        triangulate(&polygon, &mut triangles);
+
+        let builder = &mut groups[face_groups[face]].1;
+        for triangle in triangles.iter() {
+            let triangle = if left_handed {
+                [triangle[0], triangle[2], triangle[1]]
+            } else {
+                *triangle
+            };
+            for k in triangle {
+                let face_vertex = face_vertices.start + k;
+                let point = points[k];
+                let tex_coord = uvs
+                    .as_ref()
+                    .and_then(|uvs| uvs.get(face, point, face_vertex))
+                    .and_then(|uv| Some(Vector2::new(*uv.first()?, 1.0 - *uv.get(1)?)))
+                    .unwrap_or_default();
+                let normal = normals
+                    .as_ref()
+                    .and_then(|normals| normals.get(face, point, face_vertex))
+                    .and_then(|n| Some(Vector3::new(*n.first()?, *n.get(1)?, *n.get(2)?)))
+                    .unwrap_or_default();
+                builder.insert(StaticVertex {
+                    position: polygon[k],
+                    tex_coord,
+                    normal,
+                    tangent: Vector4::default(),
+                });
+            }
+        }
+    }
+
+    if invalid_faces > 0 {
+        Log::warn(format!(
+            "USD mesh {} has {} invalid faces, they were skipped.",
+            prim.path, invalid_faces
+        ));
+    }
+
+    let color = display_color(prim);
+    let mut surfaces = Vec::new();
+    for (material, builder) in groups {
+        if builder.vertex_count() == 0 {
+            continue;
+        }
+        let mut data = SurfaceData::from_raw_mesh(builder.build());
+        if normals.is_none() {
+            data.calculate_normals().unwrap();
+        }
+        data.calculate_tangents().unwrap();
+        let mut surface = make_surface(data, context);
+        surface.set_material(resolve_material(material, color, context).await);
+        surfaces.push(surface);
+    }
+
+    MeshBuilder::new(base).with_surfaces(surfaces).build(graph)
+}
+
+/// Converts simple geometric primitives (cubes and spheres).
+pub async fn convert_gprim(
+    prim: &Prim,
+    base: BaseBuilder,
+    graph: &mut Graph,
+    context: &mut ImportContext<'_>,
+    material: Option<&str>,
+) -> Handle<Node> {
+    let data = if prim.type_name == "Cube" {
+        let size = prim
+            .attribute("size")
+            .and_then(Value::as_f32)
+            .unwrap_or(2.0);
+        SurfaceData::make_cube(Matrix4::new_scaling(size))
+    } else {
+        let radius = prim
+            .attribute("radius")
+            .and_then(Value::as_f32)
+            .unwrap_or(1.0);
+        SurfaceData::make_sphere(16, 16, radius, &Matrix4::identity())
+    };
+    let mut surface = make_surface(data, context);
+    surface.set_material(resolve_material(material, display_color(prim), context).await);
+    MeshBuilder::new(base)
+        .with_surfaces(vec![surface])
+        .build(graph)
+}
@@ -0,0 +1,199 @@
+//! [UsdLoader] enables the importing of static scenes in OpenUSD format: text layers (`*.usda`) and
+//! packages (`*.usdz`). This requires the "usd" feature.
+//!
+//! The importer converts prims hierarchy with transforms, meshes (including geometry subsets with
+//! their own materials), `UsdPreviewSurface` materials and point instancers. Binary layers
+//! (`*.usdc`), composition arcs (references, payloads, variants), skeletons and animations are not
+//! supported, every unsupported feature of a stage is listed in the log after import.
+
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::asset::io::ResourceIo;
+use crate::asset::loader;
+use crate::asset::manager::ResourceManager;
+use crate::asset::options;
+use crate::asset::state::LoadError;
+use crate::core::log::Log;
+use crate::core::TypeUuidProvider;
+use crate::graph::NodeMapping;
+use crate::gui::core::io::FileLoadError;
+use crate::resource::model::{Model, ModelImportOptions};
+use crate::scene::Scene;
+
+mod archive;
+mod convert;
+mod material;
+mod mesh;
+mod parser;
+
+use convert::ImportContext;
+pub use parser::{Prim, Property, PropertyKind, Specifier, Stage, Value};
+
+/// All possible errors, that may occur during USD import.
+#[derive(Debug)]
+pub enum UsdLoadError {
+    /// An i/o error has occurred.
+    File(FileLoadError),
+    /// A layer has invalid syntax.
+    Parse {
+        /// Line, where the error has occurred.
+        line: usize,
+        /// Description of the error.
+        message: String,
+    },
+    /// A USDZ package is malformed.
+    Archive(String),
+    /// A layer is stored in binary format (`*.usdc`), that is not supported.
+    UnsupportedCrateFormat,
+    /// A USDZ package does not contain any USD layer.
+    NoRootLayer,
+    /// A layer is not a valid UTF-8 text.
+    InvalidText,
+}
+
+impl Display for UsdLoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UsdLoadError::File(v) => write!(f, "A file load error has occurred {v:?}"),
+            UsdLoadError::Parse { line, message } => {
+                write!(f, "USD parsing error at line {line}: {message}")
+            }
+            UsdLoadError::Archive(v) => write!(f, "Invalid USDZ package: {v}"),
+            UsdLoadError::UnsupportedCrateFormat => write!(
+                f,
+                "Binary USD layers (usdc) are not supported, convert them to usda first."
+            ),
+            UsdLoadError::NoRootLayer => write!(f, "USDZ package does not contain a USD layer."),
+            UsdLoadError::InvalidText => write!(f, "USD layer is not a valid UTF-8 text."),
+        }
+    }
+}
+
+impl From<FileLoadError> for UsdLoadError {
+    fn from(error: FileLoadError) -> Self {
+        UsdLoadError::File(error)
+    }
+}
+
+/// This object performs the loading of files in OpenUSD format with extension "usda" or "usdz".
+pub struct UsdLoader {
+    /// ResourceManager is needed so that textures could be loaded from additional resources.
+    pub resource_manager: ResourceManager,
+    /// Import options control where this loader should search for additional resources.
+    pub default_import_options: ModelImportOptions,
+}
+
+impl loader::ResourceLoader for UsdLoader {
+    fn extensions(&self) -> &[&str] {
+        &["usda", "usdz"]
+    }
+
+    fn data_type_uuid(&self) -> crate::core::type_traits::prelude::Uuid {
+        Model::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> loader::BoxedLoaderFuture {
+        let resource_manager = self.resource_manager.clone();
+        let default_import_options = self.default_import_options.clone();
+
+        Box::pin(async move {
+            let import_options = options::try_get_import_settings(&path, io.as_ref())
+                .await
+                .unwrap_or(default_import_options);
+
+            let model = load(path, io, resource_manager, import_options)
+                .await
+                .map_err(LoadError::new)?;
+
+            Ok(loader::LoaderPayload::new(model))
+        })
+    }
+
+    fn try_load_import_settings(
+        &self,
+        resource_path: PathBuf,
+        io: Arc<dyn ResourceIo>,
+    ) -> loader::BoxedImportOptionsLoaderFuture {
+        Box::pin(async move {
+            options::try_get_import_settings_opaque::<ModelImportOptions>(&resource_path, &*io)
+                .await
+        })
+    }
+
+    fn default_import_options(&self) -> Option<Box<dyn options::BaseImportOptions>> {
+        Some(Box::<ModelImportOptions>::default())
+    }
+}
+
+fn parse_layer(data: &[u8]) -> Result<Stage, UsdLoadError> {
+    if data.starts_with(b"PXR-USDC") {
+        return Err(UsdLoadError::UnsupportedCrateFormat);
+    }
+    Stage::parse(std::str::from_utf8(data).map_err(|_| UsdLoadError::InvalidText)?)
+}
+
+async fn load(
+    path: PathBuf,
+    io: Arc<dyn ResourceIo>,
+    resource_manager: ResourceManager,
+    options: ModelImportOptions,
+) -> Result<Model, UsdLoadError> {
+    let data = io.load_file(&path).await?;
+
+    let is_package = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("usdz"));
+
+    let mut embedded_files = Vec::new();
+    let stage = if is_package {
+        let entries = archive::read_entries(&data)?;
+        // The first USD layer of a package is its root layer.
+        let root_layer = entries
+            .iter()
+            .find(|entry| {
+                let name = entry.name.to_lowercase();
+                name.ends_with(".usda") || name.ends_with(".usdc") || name.ends_with(".usd")
+            })
+            .ok_or(UsdLoadError::NoRootLayer)?;
+        let stage = parse_layer(root_layer.data)?;
+        embedded_files = entries
+            .iter()
+            .map(|entry| (entry.name.clone(), entry.data.to_vec()))
+            .collect();
+        stage
+    } else {
+        parse_layer(&data)?
+    };
+
+    let mut scene = Scene::new();
+    if let Some(filename) = path.file_name() {
+        let root = scene.graph.get_root();
+        scene.graph[root].set_name(filename.to_string_lossy());
+    }
+
+    let mut context = ImportContext {
+        stage: &stage,
+        resource_manager,
+        model_path: path.clone(),
+        search_options: options.material_search_options,
+        embedded_files,
+        materials: Default::default(),
+        unsupported: Default::default(),
+    };
+    convert::convert(&mut scene.graph, &mut context).await;
+
+    if !context.unsupported.is_empty() {
+        let mut unsupported = context.unsupported.into_iter().collect::<Vec<_>>();
+        unsupported.sort();
+        Log::warn(format!(
+            "Some parts of USD stage {} were not imported: {}",
+            path.display(),
+            unsupported.join(", ")
+        ));
+    }
+
+    // USD prims have unique paths, but names could be repeated in different branches.
+    Ok(Model::new(NodeMapping::UseNames, scene))
+}
@@ -0,0 +1,765 @@
+//! A parser of text USD layers (`.usda`). It builds a tree of prims with their properties, while
+//! composition arcs (references, payloads, variants) are parsed, but not resolved.
+
+use crate::resource::usd::UsdLoadError;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    String(String),
+    Asset(String),
+    Path(String),
+    Punct(char),
+}
+
+struct Tokenizer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            chars: source.chars().peekable(),
+            line: 1,
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> UsdLoadError {
+        UsdLoadError::Parse {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        while let Some(&c) = self.chars.peek() {
+            if c == '#' {
+                while let Some(c) = self.bump() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            } else if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn read_delimited(&mut self, delimiter: char) -> Result<String, UsdLoadError> {
+        let mut result = String::new();
+        loop {
+            match self.bump() {
+                Some(c) if c == delimiter => return Ok(result),
+                Some('\\') if delimiter != '@' => match self.bump() {
+                    Some('n') => result.push('\n'),
+                    Some('t') => result.push('\t'),
+                    Some(c) => result.push(c),
+                    None => break,
+                },
+                Some(c) => result.push(c),
+                None => break,
+            }
+        }
+        Err(self.error(format!("Unterminated literal, expected {delimiter}")))
+    }
+
+    /// Reads a literal, that could be enclosed in triple delimiters (`"""text"""` or `@@@path@@@`).
+    fn read_quoted(&mut self, delimiter: char) -> Result<String, UsdLoadError> {
+        if self.chars.peek() != Some(&delimiter) {
+            return self.read_delimited(delimiter);
+        }
+        self.bump();
+        if self.chars.peek() != Some(&delimiter) {
+            // Empty literal.
+            return Ok(String::new());
+        }
+        self.bump();
+        let mut result = String::new();
+        let mut closing = 0;
+        while let Some(c) = self.bump() {
+            if c == delimiter {
+                closing += 1;
+                if closing == 3 {
+                    return Ok(result);
+                }
+            } else {
+                result.extend(std::iter::repeat(delimiter).take(closing));
+                closing = 0;
+                result.push(c);
+            }
+        }
+        Err(self.error("Unterminated literal"))
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, UsdLoadError> {
+        self.skip_whitespace_and_comments();
+        let Some(&c) = self.chars.peek() else {
+            return Ok(None);
+        };
+        let token = match c {
+            '"' | '\'' => {
+                self.bump();
+                Token::String(self.read_quoted(c)?)
+            }
+            '@' => {
+                self.bump();
+                Token::Asset(self.read_quoted('@')?)
+            }
+            '<' => {
+                self.bump();
+                Token::Path(self.read_delimited('>')?)
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => {
+                let mut text = String::new();
+                while let Some(&c) = self.chars.peek() {
+                    if c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.') {
+                        text.push(c);
+                        self.bump();
+                    } else {
+                        break;
+                    }
+                }
+                match text.as_str() {
+                    "-inf" => Token::Number(f64::NEG_INFINITY),
+                    "+inf" => Token::Number(f64::INFINITY),
+                    _ => Token::Number(
+                        text.parse()
+                            .map_err(|_| self.error(format!("Invalid number {text}")))?,
+                    ),
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut text = String::new();
+                while let Some(&c) = self.chars.peek() {
+                    if c.is_alphanumeric() || matches!(c, '_' | ':' | '.') {
+                        text.push(c);
+                        self.bump();
+                    } else {
+                        break;
+                    }
+                }
+                Token::Ident(text)
+            }
+            c => {
+                self.bump();
+                Token::Punct(c)
+            }
+        };
+        Ok(Some(token))
+    }
+}
+
+/// A value of a property or a metadata entry.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// Explicitly blocked value (`None`) or a value, that is not supported (dictionaries).
+    None,
+    /// Boolean value.
+    Bool(bool),
+    /// Any number, USD has lots of numeric types, but they're all stored as `f64`.
+    Number(f64),
+    /// String value.
+    String(String),
+    /// Token or any other identifier.
+    Token(String),
+    /// Asset path (`@path@`).
+    Asset(String),
+    /// Path to a prim or a property (`</Prim.property>`).
+    Path(String),
+    /// Tuple of values, for example a vector (`(1, 2, 3)`).
+    Tuple(Vec<Value>),
+    /// Array of values (`[1, 2, 3]`).
+    Array(Vec<Value>),
+}
+
+impl Value {
+    /// Tries to interpret the value as a number.
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            Value::Number(n) => Some(*n as f32),
+            Value::Bool(b) => Some(*b as u8 as f32),
+            _ => None,
+        }
+    }
+
+    /// Tries to interpret the value as a string-like value (string, token or asset path).
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) | Value::Token(s) | Value::Asset(s) | Value::Path(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Tries to interpret the value as a tuple of numbers.
+    pub fn as_floats(&self) -> Option<Vec<f32>> {
+        match self {
+            Value::Tuple(values) => values.iter().map(Value::as_f32).collect(),
+            _ => None,
+        }
+    }
+
+    /// Returns elements of an array value, or a single-element slice for any other value.
+    pub fn elements(&self) -> &[Value] {
+        match self {
+            Value::Array(values) => values,
+            Value::None => &[],
+            _ => std::slice::from_ref(self),
+        }
+    }
+}
+
+/// A kind of the opinion of a property.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PropertyKind {
+    /// Attribute with a default value.
+    Attribute,
+    /// Connection of an attribute to another attribute (`.connect`).
+    Connection,
+    /// Animated value of an attribute (`.timeSamples`). Only the first sample is stored.
+    TimeSamples,
+    /// Relationship with other prims.
+    Relationship,
+}
+
+/// Attribute or relationship of a prim.
+#[derive(Clone, Debug)]
+pub struct Property {
+    /// Namespaced name of the property (`primvars:st`).
+    pub name: String,
+    /// Type name of the property (`point3f[]`), it is empty for relationships.
+    pub type_name: String,
+    /// Kind of the property opinion.
+    pub kind: PropertyKind,
+    /// Value of the property.
+    pub value: Value,
+    /// Metadata of the property (`interpolation`, `elementSize`, etc.).
+    pub metadata: Vec<(String, Value)>,
+}
+
+/// Specifier of a prim.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Specifier {
+    /// Concrete prim (`def`).
+    Def,
+    /// Override of a prim defined elsewhere (`over`).
+    Over,
+    /// Abstract prim, that is used as a base for other prims (`class`).
+    Class,
+}
+
+/// A node of the scene hierarchy.
+#[derive(Clone, Debug)]
+pub struct Prim {
+    /// Specifier of the prim.
+    pub specifier: Specifier,
+    /// Schema type of the prim (`Xform`, `Mesh`, etc.), could be empty.
+    pub type_name: String,
+    /// Name of the prim.
+    pub name: String,
+    /// Full path of the prim (`/World/Mesh`).
+    pub path: String,
+    /// Metadata of the prim.
+    pub metadata: Vec<(String, Value)>,
+    /// Properties of the prim.
+    pub properties: Vec<Property>,
+    /// Child prims.
+    pub children: Vec<Prim>,
+    /// Amount of variant sets of the prim, their content is not composed.
+    pub variant_set_count: usize,
+}
+
+impl Prim {
+    fn property(&self, name: &str, kind: PropertyKind) -> Option<&Property> {
+        self.properties
+            .iter()
+            .find(|p| p.name == name && p.kind == kind)
+    }
+
+    /// Returns a value of an attribute. If an attribute has no default value, the first time
+    /// sample is returned.
+    pub fn attribute(&self, name: &str) -> Option<&Value> {
+        self.property(name, PropertyKind::Attribute)
+            .filter(|p| p.value != Value::None)
+            .or_else(|| self.property(name, PropertyKind::TimeSamples))
+            .map(|p| &p.value)
+    }
+
+    /// Returns metadata of an attribute.
+    pub fn attribute_metadata(&self, name: &str, key: &str) -> Option<&Value> {
+        self.properties
+            .iter()
+            .filter(|p| p.name == name)
+            .flat_map(|p| p.metadata.iter())
+            .find_map(|(k, v)| (k == key).then_some(v))
+    }
+
+    /// Returns a path of an attribute, that is connected to the given attribute.
+    pub fn connection(&self, name: &str) -> Option<&str> {
+        self.property(name, PropertyKind::Connection)
+            .and_then(|p| p.value.elements().first())
+            .and_then(Value::as_str)
+    }
+
+    /// Returns paths of the targets of a relationship.
+    pub fn relationship(&self, name: &str) -> Vec<&str> {
+        self.property(name, PropertyKind::Relationship)
+            .map(|p| {
+                p.value
+                    .elements()
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns a value of a metadata entry.
+    pub fn metadata(&self, key: &str) -> Option<&Value> {
+        self.metadata
+            .iter()
+            .find_map(|(k, v)| (k == key).then_some(v))
+    }
+
+    /// Recursively searches for a prim with the given path.
+    pub fn find(&self, path: &str) -> Option<&Prim> {
+        if self.path == path {
+            return Some(self);
+        }
+        self.children
+            .iter()
+            .filter(|child| {
+                path.strip_prefix(child.path.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+            .find_map(|child| child.find(path))
+    }
+}
+
+/// Parsed USD layer.
+#[derive(Clone, Debug, Default)]
+pub struct Stage {
+    /// Metadata of the layer (`upAxis`, `metersPerUnit`, etc.).
+    pub metadata: Vec<(String, Value)>,
+    /// Root prims of the layer.
+    pub prims: Vec<Prim>,
+}
+
+impl Stage {
+    /// Parses a text USD layer.
+    pub fn parse(source: &str) -> Result<Self, UsdLoadError> {
+        if !source.starts_with("#usda") {
+            return Err(UsdLoadError::Parse {
+                line: 1,
+                message: "Expected #usda header".to_string(),
+            });
+        }
+        let mut parser = Parser::new(source)?;
+        let mut stage = Stage::default();
+        if parser.eat_punct('(')? {
+            stage.metadata = parser.parse_metadata()?;
+        }
+        while parser.peek.is_some() {
+            stage.prims.push(parser.parse_prim("")?);
+        }
+        Ok(stage)
+    }
+
+    /// Returns a value of a layer metadata entry.
+    pub fn metadata(&self, key: &str) -> Option<&Value> {
+        self.metadata
+            .iter()
+            .find_map(|(k, v)| (k == key).then_some(v))
+    }
+
+    /// Searches for a prim with the given path.
+    pub fn find(&self, path: &str) -> Option<&Prim> {
+        self.prims.iter().find_map(|prim| prim.find(path))
+    }
+}
+
+struct Parser<'a> {
+    tokenizer: Tokenizer<'a>,
+    peek: Option<Token>,
+}
+
+const LIST_OPS: [&str; 6] = ["prepend", "append", "add", "delete", "reorder", "explicit"];
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Result<Self, UsdLoadError> {
+        let mut tokenizer = Tokenizer::new(source);
+        let peek = tokenizer.next_token()?;
+        Ok(Self { tokenizer, peek })
+    }
+
+    fn error(&self, message: impl Into<String>) -> UsdLoadError {
+        self.tokenizer.error(message)
+    }
+
+    fn next(&mut self) -> Result<Token, UsdLoadError> {
+        let next = self.tokenizer.next_token()?;
+        std::mem::replace(&mut self.peek, next).ok_or_else(|| self.error("Unexpected end of file"))
+    }
+
+    fn eat_punct(&mut self, punct: char) -> Result<bool, UsdLoadError> {
+        if self.peek == Some(Token::Punct(punct)) {
+            self.next()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn expect_punct(&mut self, punct: char) -> Result<(), UsdLoadError> {
+        match self.next()? {
+            Token::Punct(c) if c == punct => Ok(()),
+            token => Err(self.error(format!("Expected {punct}, got {token:?}"))),
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String, UsdLoadError> {
+        match self.next()? {
+            Token::Ident(ident) => Ok(ident),
+            token => Err(self.error(format!("Expected identifier, got {token:?}"))),
+        }
+    }
+
+    fn peek_ident(&self) -> Option<&str> {
+        match self.peek {
+            Some(Token::Ident(ref ident)) => Some(ident),
+            _ => None,
+        }
+    }
+
+    /// Skips everything until a closing bracket, that matches an already consumed opening one.
+    fn skip_block(&mut self, open: char, close: char) -> Result<(), UsdLoadError> {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next()? {
+                Token::Punct(c) if c == open => depth += 1,
+                Token::Punct(c) if c == close => depth -= 1,
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_value(&mut self) -> Result<Value, UsdLoadError> {
+        Ok(match self.next()? {
+            Token::Number(n) => Value::Number(n),
+            Token::String(s) => Value::String(s),
+            Token::Asset(a) => {
+                // Composition arcs could specify a prim path after an asset path.
+                if let Some(Token::Path(_)) = self.peek {
+                    self.next()?;
+                }
+                Value::Asset(a)
+            }
+            Token::Path(p) => Value::Path(p),
+            Token::Ident(ident) => match ident.as_str() {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                "None" => Value::None,
+                "inf" => Value::Number(f64::INFINITY),
+                "nan" => Value::Number(f64::NAN),
+                _ => Value::Token(ident),
+            },
+            Token::Punct('(') => Value::Tuple(self.parse_sequence(')')?),
+            Token::Punct('[') => Value::Array(self.parse_sequence(']')?),
+            Token::Punct('{') => {
+                self.skip_block('{', '}')?;
+                Value::None
+            }
+            token => return Err(self.error(format!("Unexpected token {token:?}"))),
+        })
+    }
+
+    fn parse_sequence(&mut self, close: char) -> Result<Vec<Value>, UsdLoadError> {
+        let mut values = Vec::new();
+        loop {
+            if self.eat_punct(close)? {
+                return Ok(values);
+            }
+            values.push(self.parse_value()?);
+            if !self.eat_punct(',')? {
+                self.expect_punct(close)?;
+                return Ok(values);
+            }
+        }
+    }
+
+    /// Parses a metadata block, the opening parenthesis must be already consumed.
+    fn parse_metadata(&mut self) -> Result<Vec<(String, Value)>, UsdLoadError> {
+        let mut metadata = Vec::new();
+        loop {
+            match self.next()? {
+                Token::Punct(')') => return Ok(metadata),
+                Token::Punct(';') => (),
+                // Documentation string.
+                Token::String(doc) => metadata.push(("doc".to_string(), Value::String(doc))),
+                Token::Ident(mut key) => {
+                    if LIST_OPS.contains(&key.as_str()) {
+                        key = self.expect_ident()?;
+                    }
+                    if self.eat_punct('=')? {
+                        let value = self.parse_value()?;
+                        metadata.push((key, value));
+                    } else if key == "dictionary" {
+                        // `dictionary name = { ... }`
+                        self.expect_ident()?;
+                        self.expect_punct('=')?;
+                        self.parse_value()?;
+                    }
+                }
+                token => return Err(self.error(format!("Unexpected token {token:?}"))),
+            }
+        }
+    }
+
+    fn parse_prim(&mut self, parent_path: &str) -> Result<Prim, UsdLoadError> {
+        let specifier = match self.expect_ident()?.as_str() {
+            "def" => Specifier::Def,
+            "over" => Specifier::Over,
+            "class" => Specifier::Class,
+            other => return Err(self.error(format!("Expected prim specifier, got {other}"))),
+        };
+        let type_name = match self.peek {
+            Some(Token::Ident(_)) => self.expect_ident()?,
+            _ => String::new(),
+        };
+        let name = match self.next()? {
+            Token::String(name) => name,
+            token => return Err(self.error(format!("Expected prim name, got {token:?}"))),
+        };
+        let mut prim = Prim {
+            specifier,
+            type_name,
+            path: format!("{parent_path}/{name}"),
+            name,
+            metadata: Default::default(),
+            properties: Default::default(),
+            children: Default::default(),
+            variant_set_count: 0,
+        };
+        if self.eat_punct('(')? {
+            prim.metadata = self.parse_metadata()?;
+        }
+        self.expect_punct('{')?;
+        self.parse_prim_body(&mut prim)?;
+        Ok(prim)
+    }
+
+    /// Parses the content of a prim (or a variant), the opening brace must be already consumed.
+    fn parse_prim_body(&mut self, prim: &mut Prim) -> Result<(), UsdLoadError> {
+        loop {
+            if self.eat_punct('}')? {
+                return Ok(());
+            }
+            if self.eat_punct(';')? {
+                continue;
+            }
+            match self.peek_ident() {
+                Some("def" | "over" | "class") => {
+                    let child = self.parse_prim(&prim.path)?;
+                    prim.children.push(child);
+                }
+                Some("variantSet") => {
+                    self.next()?;
+                    self.next()?;
+                    self.expect_punct('=')?;
+                    self.expect_punct('{')?;
+                    self.skip_block('{', '}')?;
+                    prim.variant_set_count += 1;
+                }
+                Some("reorder") => {
+                    // `reorder nameChildren = [...]`
+                    self.next()?;
+                    self.expect_ident()?;
+                    self.expect_punct('=')?;
+                    self.parse_value()?;
+                }
+                Some(_) => {
+                    let property = self.parse_property()?;
+                    prim.properties.push(property);
+                }
+                None => {
+                    let token = self.next()?;
+                    return Err(self.error(format!("Unexpected token {token:?}")));
+                }
+            }
+        }
+    }
+
+    fn parse_property(&mut self) -> Result<Property, UsdLoadError> {
+        let mut ident = self.expect_ident()?;
+        while LIST_OPS.contains(&ident.as_str())
+            || matches!(ident.as_str(), "custom" | "uniform" | "varying" | "config")
+        {
+            ident = self.expect_ident()?;
+        }
+
+        let (type_name, is_relationship) = if ident == "rel" {
+            (String::new(), true)
+        } else {
+            let mut type_name = ident;
+            if self.eat_punct('[')? {
+                self.expect_punct(']')?;
+                type_name.push_str("[]");
+            }
+            (type_name, false)
+        };
+
+        let full_name = self.expect_ident()?;
+        let (name, kind) = if is_relationship {
+            (full_name, PropertyKind::Relationship)
+        } else if let Some(name) = full_name.strip_suffix(".connect") {
+            (name.to_string(), PropertyKind::Connection)
+        } else if let Some(name) = full_name.strip_suffix(".timeSamples") {
+            (name.to_string(), PropertyKind::TimeSamples)
+        } else if let Some(name) = full_name.strip_suffix(".spline") {
+            (name.to_string(), PropertyKind::TimeSamples)
+        } else {
+            (full_name, PropertyKind::Attribute)
+        };
+
+        let mut value = Value::None;
+        if self.eat_punct('=')? {
+            value = if kind == PropertyKind::TimeSamples {
+                self.parse_first_time_sample()?
+            } else {
+                self.parse_value()?
+            };
+        }
+
+        let mut metadata = Vec::new();
+        if self.eat_punct('(')? {
+            metadata = self.parse_metadata()?;
+        }
+
+        Ok(Property {
+            name,
+            type_name,
+            kind,
+            value,
+            metadata,
+        })
+    }
+
+    /// Parses time samples (`{ 0: value, 1: value }`) and returns a value of the first sample.
+    fn parse_first_time_sample(&mut self) -> Result<Value, UsdLoadError> {
+        self.expect_punct('{')?;
+        let mut first = None;
+        loop {
+            if self.eat_punct('}')? {
+                return Ok(first.unwrap_or(Value::None));
+            }
+            match self.next()? {
+                Token::Number(_) => (),
+                token => return Err(self.error(format!("Expected time, got {token:?}"))),
+            }
+            self.expect_punct(':')?;
+            let value = self.parse_value()?;
+            first.get_or_insert(value);
+            self.eat_punct(',')?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::resource::usd::parser::{Specifier, Stage, Value};
+
+    #[test]
+    fn test_parse_stage() {
+        let stage = Stage::parse(
+            r#"#usda 1.0
+(
+    defaultPrim = "World"
+    upAxis = "Z"
+)
+
+def Xform "World" (
+    kind = "component"
+)
+{
+    double3 xformOp:translate = (0, 1, 0)
+    uniform token[] xformOpOrder = ["xformOp:translate"]
+
+    def Mesh "Quad" (
+        prepend apiSchemas = ["MaterialBindingAPI"]
+    )
+    {
+        int[] faceVertexCounts = [4]
+        int[] faceVertexIndices = [0, 1, 2, 3]
+        point3f[] points = [(0, 0, 0), (1, 0, 0), (1, 1, 0), (0, 1, 0)]
+        texCoord2f[] primvars:st = [(0, 0), (1, 0), (1, 1), (0, 1)] (
+            interpolation = "vertex"
+        )
+        rel material:binding = </World/Material>
+        float3 xformOp:scale.timeSamples = {
+            0: (1, 1, 1),
+            10: (2, 2, 2),
+        }
+    }
+
+    variantSet "color" = {
+        "red" { }
+    }
+
+    over "Material" {
+        token outputs:surface.connect = </World/Material/Shader.outputs:surface>
+        asset inputs:file = @textures/albedo.png@
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(stage.metadata("upAxis"), Some(&Value::String("Z".into())));
+
+        let world = stage.find("/World").unwrap();
+        assert_eq!(world.type_name, "Xform");
+        assert_eq!(world.variant_set_count, 1);
+        assert_eq!(
+            world.attribute("xformOp:translate").unwrap().as_floats(),
+            Some(vec![0.0, 1.0, 0.0])
+        );
+
+        let quad = stage.find("/World/Quad").unwrap();
+        assert_eq!(quad.attribute("points").unwrap().elements().len(), 4);
+        assert_eq!(
+            quad.attribute_metadata("primvars:st", "interpolation"),
+            Some(&Value::String("vertex".into()))
+        );
+        assert_eq!(
+            quad.relationship("material:binding"),
+            vec!["/World/Material"]
+        );
+        assert_eq!(
+            quad.attribute("xformOp:scale").unwrap().as_floats(),
+            Some(vec![1.0, 1.0, 1.0])
+        );
+
+        let material = stage.find("/World/Material").unwrap();
+        assert_eq!(material.specifier, Specifier::Over);
+        assert_eq!(
+            material.connection("outputs:surface"),
+            Some("/World/Material/Shader.outputs:surface")
+        );
+        assert_eq!(
+            material.attribute("inputs:file"),
+            Some(&Value::Asset("textures/albedo.png".into()))
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! AV1 video decoder, it uses [dav1d](https://code.videolan.org/videolan/dav1d) library to decode AV1
+//! video streams stored in IVF container. Available only with `video_av1` feature.
+
+use crate::{
+    core::algebra::Vector2,
+    resource::video::{ivf::IvfReader, yuv::YuvImage, VideoDecoder, VideoError},
+};
+use dav1d::{Decoder, PixelLayout, PlanarImageComponent};
+use std::fmt::{Debug, Formatter};
+
+/// AV1 video decoder, that reads an AV1 video stream from IVF container. Only 8-bit videos are supported.
+pub struct Av1IvfDecoder {
+    reader: IvfReader,
+    decoder: Decoder,
+}
+
+impl Debug for Av1IvfDecoder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Av1IvfDecoder")
+            .field("Header", self.reader.header())
+            .finish()
+    }
+}
+
+impl From<dav1d::Error> for VideoError {
+    fn from(e: dav1d::Error) -> Self {
+        VideoError::Decoder(e.to_string())
+    }
+}
+
+impl Av1IvfDecoder {
+    /// Creates new decoder from the given content of an IVF file.
+    pub fn new(data: Vec<u8>) -> Result<Self, VideoError> {
+        let reader = IvfReader::new(data)?;
+        if &reader.header().fourcc != b"AV01" {
+            return Err(VideoError::UnsupportedCodec(
+                String::from_utf8_lossy(&reader.header().fourcc).to_string(),
+            ));
+        }
+        Ok(Self {
+            reader,
+            decoder: Decoder::new()?,
+        })
+    }
+
+    fn try_get_picture(&mut self) -> Result<Option<dav1d::Picture>, VideoError> {
+        match self.decoder.get_picture() {
+            Ok(picture) => Ok(Some(picture)),
+            Err(dav1d::Error::Again) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn next_picture(&mut self) -> Result<Option<dav1d::Picture>, VideoError> {
+        loop {
+            match self.decoder.send_pending_data() {
+                Ok(()) => (),
+                Err(dav1d::Error::Again) => {
+                    // The decoder is full, take a decoded picture first to free some space.
+                    if let Some(picture) = self.try_get_picture()? {
+                        return Ok(Some(picture));
+                    }
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            }
+
+            if let Some(picture) = self.try_get_picture()? {
+                return Ok(Some(picture));
+            }
+
+            let Some(frame) = self.reader.next_frame() else {
+                // Drain the frames that are still in the decoder.
+                return self.try_get_picture();
+            };
+
+            let data = frame.data.to_vec();
+            let timestamp = frame.timestamp as i64;
+            match self.decoder.send_data(data, None, Some(timestamp), None) {
+                // Remaining data will be sent on next iteration.
+                Ok(()) | Err(dav1d::Error::Again) => (),
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+impl VideoDecoder for Av1IvfDecoder {
+    fn frame_size(&self) -> Vector2<u32> {
+        let header = self.reader.header();
+        Vector2::new(header.width as u32, header.height as u32)
+    }
+
+    fn frame_duration(&self) -> f32 {
+        self.reader.header().frame_duration()
+    }
+
+    fn decode_next_frame(&mut self, rgba: &mut [u8]) -> Result<bool, VideoError> {
+        let Some(picture) = self.next_picture()? else {
+            return Ok(false);
+        };
+
+        if picture.bit_depth() != 8 {
+            return Err(VideoError::UnsupportedCodec(format!(
+                "{}-bit AV1 video",
+                picture.bit_depth()
+            )));
+        }
+
+        let size = self.frame_size();
+        let width = picture.width().min(size.x) as usize;
+        let height = picture.height().min(size.y) as usize;
+
+        let chroma_shift = match picture.pixel_layout() {
+            PixelLayout::I400 | PixelLayout::I444 => (0, 0),
+            PixelLayout::I420 => (1, 1),
+            PixelLayout::I422 => (1, 0),
+        };
+        let monochrome = picture.pixel_layout() == PixelLayout::I400;
+
+        let y_plane = picture.plane(PlanarImageComponent::Y);
+        let uv_planes = if monochrome {
+            None
+        } else {
+            Some((
+                picture.plane(PlanarImageComponent::U),
+                picture.plane(PlanarImageComponent::V),
+            ))
+        };
+
+        YuvImage {
+            width,
+            height,
+            y: &y_plane,
+            y_stride: picture.stride(PlanarImageComponent::Y) as usize,
+            uv: uv_planes.as_ref().map(|(u, v)| (&u[..], &v[..])),
+            uv_stride: picture.stride(PlanarImageComponent::U) as usize,
+            chroma_shift,
+        }
+        .write_rgba(rgba, size.x as usize);
+
+        Ok(true)
+    }
+
+    fn rewind(&mut self) -> Result<(), VideoError> {
+        self.reader.rewind();
+        self.decoder.flush();
+        Ok(())
+    }
+}
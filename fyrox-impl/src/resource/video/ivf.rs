@@ -0,0 +1,193 @@
+//! A reader of IVF container. IVF is a very simple container format, that is used to store VP8, VP9 and AV1
+//! video streams. See [`IvfReader`] docs for more info.
+
+use crate::resource::video::VideoError;
+
+/// Header of an IVF file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IvfHeader {
+    /// Four-character code of the codec, for example `AV01` for AV1 or `VP90` for VP9.
+    pub fourcc: [u8; 4],
+    /// Width of the video in pixels.
+    pub width: u16,
+    /// Height of the video in pixels.
+    pub height: u16,
+    /// Denominator of the time base (usually it is frame rate).
+    pub time_base_denominator: u32,
+    /// Numerator of the time base (usually it is 1).
+    pub time_base_numerator: u32,
+    /// Total amount of frames in the file.
+    pub frame_count: u32,
+}
+
+impl IvfHeader {
+    /// Reads the header from the beginning of the given content of an IVF file. Fails if the data does
+    /// not have a valid IVF header.
+    pub fn read(data: &[u8]) -> Result<Self, VideoError> {
+        if data.len() < 32 || &data[0..4] != IvfReader::SIGNATURE {
+            return Err(VideoError::InvalidContainer(
+                "Not an IVF file - signature mismatch.".to_string(),
+            ));
+        }
+
+        let header_size = read_u16(data, 6) as usize;
+        if header_size < 32 || header_size > data.len() {
+            return Err(VideoError::InvalidContainer(format!(
+                "Invalid IVF header size {header_size}."
+            )));
+        }
+
+        Ok(Self {
+            fourcc: [data[8], data[9], data[10], data[11]],
+            width: read_u16(data, 12),
+            height: read_u16(data, 14),
+            time_base_denominator: read_u32(data, 16),
+            time_base_numerator: read_u32(data, 20),
+            frame_count: read_u32(data, 24),
+        })
+    }
+
+    /// Returns duration of a single frame in seconds.
+    pub fn frame_duration(&self) -> f32 {
+        if self.time_base_denominator == 0 {
+            0.0
+        } else {
+            self.time_base_numerator as f32 / self.time_base_denominator as f32
+        }
+    }
+}
+
+/// A single frame of an IVF file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IvfFrame<'a> {
+    /// Presentation timestamp of the frame (in time base units).
+    pub timestamp: u64,
+    /// Encoded data of the frame.
+    pub data: &'a [u8],
+}
+
+/// A reader of IVF container, it reads the header of the file and then allows you to iterate over
+/// encoded frames.
+#[derive(Debug, Clone)]
+pub struct IvfReader {
+    data: Vec<u8>,
+    header: IvfHeader,
+    position: usize,
+}
+
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}
+
+impl IvfReader {
+    const SIGNATURE: &'static [u8] = b"DKIF";
+    const FRAME_HEADER_SIZE: usize = 12;
+
+    /// Creates new reader from the given data. Fails if the data does not have a valid IVF header.
+    pub fn new(data: Vec<u8>) -> Result<Self, VideoError> {
+        let header = IvfHeader::read(&data)?;
+        Ok(Self {
+            position: read_u16(&data, 6) as usize,
+            data,
+            header,
+        })
+    }
+
+    /// Returns the header of the file.
+    pub fn header(&self) -> &IvfHeader {
+        &self.header
+    }
+
+    /// Reads next frame from the file. Returns `None` if there are no more frames.
+    pub fn next_frame(&mut self) -> Option<IvfFrame<'_>> {
+        let frame_data_start = self.position + Self::FRAME_HEADER_SIZE;
+        if frame_data_start > self.data.len() {
+            return None;
+        }
+
+        let size = read_u32(&self.data, self.position) as usize;
+        let timestamp = u64::from_le_bytes(
+            self.data[self.position + 4..frame_data_start]
+                .try_into()
+                .ok()?,
+        );
+
+        let frame_data_end = frame_data_start.checked_add(size)?;
+        if frame_data_end > self.data.len() {
+            return None;
+        }
+
+        self.position = frame_data_end;
+
+        Some(IvfFrame {
+            timestamp,
+            data: &self.data[frame_data_start..frame_data_end],
+        })
+    }
+
+    /// Moves the reader to the first frame of the file.
+    pub fn rewind(&mut self) {
+        self.position = read_u16(&self.data, 6) as usize;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::resource::video::ivf::IvfReader;
+
+    fn make_ivf(frames: &[&[u8]]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(b"DKIF");
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&32u16.to_le_bytes());
+        data.extend_from_slice(b"AV01");
+        data.extend_from_slice(&320u16.to_le_bytes());
+        data.extend_from_slice(&240u16.to_le_bytes());
+        data.extend_from_slice(&30u32.to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&(frames.len() as u32).to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        for (i, frame) in frames.iter().enumerate() {
+            data.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            data.extend_from_slice(&(i as u64).to_le_bytes());
+            data.extend_from_slice(frame);
+        }
+        data
+    }
+
+    #[test]
+    fn test_ivf_reader() {
+        let mut reader = IvfReader::new(make_ivf(&[&[1, 2, 3], &[4, 5]])).unwrap();
+
+        let header = reader.header();
+        assert_eq!(&header.fourcc, b"AV01");
+        assert_eq!(header.width, 320);
+        assert_eq!(header.height, 240);
+        assert_eq!(header.frame_count, 2);
+        assert_eq!(header.frame_duration(), 1.0 / 30.0);
+
+        assert_eq!(reader.next_frame().unwrap().data, &[1, 2, 3]);
+        let frame = reader.next_frame().unwrap();
+        assert_eq!(frame.data, &[4, 5]);
+        assert_eq!(frame.timestamp, 1);
+        assert!(reader.next_frame().is_none());
+
+        reader.rewind();
+        assert_eq!(reader.next_frame().unwrap().data, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_ivf_reader_invalid_data() {
+        assert!(IvfReader::new(vec![0; 16]).is_err());
+        assert!(IvfReader::new(b"RIFF0000000000000000000000000000".to_vec()).is_err());
+    }
+}
@@ -0,0 +1,34 @@
+//! Video loader.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+    },
+    core::{uuid::Uuid, TypeUuidProvider},
+    resource::video::Video,
+};
+use fyrox_resource::state::LoadError;
+use std::{path::PathBuf, sync::Arc};
+
+/// Default implementation for video loading.
+pub struct VideoLoader;
+
+impl ResourceLoader for VideoLoader {
+    fn extensions(&self) -> &[&str] {
+        &["ivf"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <Video as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let video = Video::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(video))
+        })
+    }
+}
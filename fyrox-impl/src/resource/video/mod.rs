@@ -0,0 +1,474 @@
+//! Video playback. Video is decoded frame-by-frame into a texture, that can be used as any other texture;
+//! in materials or in user interface images. It could be used for intro movies, animated menus,
+//! in-world screens, etc. See [`VideoPlayer`] docs for more info.
+//!
+//! # Resources
+//!
+//! Video files (`*.ivf`) are loaded as [`VideoResource`] via the resource manager, like any other
+//! asset. The resource holds encoded video stream, each [`VideoPlayer`] created from it decodes the
+//! stream independently.
+//!
+//! # Codecs
+//!
+//! The engine does not decode any video format by default, because video codecs are quite heavy. AV1 video
+//! streams in IVF container are supported via `video_av1` feature (see [`av1::Av1IvfDecoder`]), VP9 video
+//! streams in IVF container are supported via `video_vp9` feature (see [`vp9::Vp9IvfDecoder`]), `video`
+//! feature enables both. Any other format could be supported by implementing [`VideoDecoder`] trait.
+//!
+//! Unlike the rest of the engine, the codecs link to system libraries, which must be installed to build
+//! the engine with these features: dav1d for `video_av1`, FFmpeg (libavcodec, libavutil) and libclang
+//! for `video_vp9`. On Debian-based distributions the packages are `libdav1d-dev`, `libavcodec-dev`,
+//! `libavutil-dev` and `libclang-dev`.
+
+use crate::{
+    asset::{io::ResourceIo, Resource, ResourceData},
+    core::{
+        algebra::Vector2, io::FileLoadError, reflect::prelude::*, type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    resource::texture::{TextureKind, TexturePixelKind, TextureResource, TextureResourceExtension},
+};
+use fyrox_resource::untyped::ResourceKind;
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+    path::Path,
+};
+
+#[cfg(feature = "video_av1")]
+pub mod av1;
+pub mod ivf;
+pub mod loader;
+#[cfg(feature = "video_vp9")]
+pub mod vp9;
+#[cfg(any(feature = "video_av1", feature = "video_vp9"))]
+mod yuv;
+
+/// An error that may occur during video decoding.
+#[derive(Debug)]
+pub enum VideoError {
+    /// An i/o error has occurred.
+    Io(std::io::Error),
+    /// The container of the video stream is malformed.
+    InvalidContainer(String),
+    /// The codec of the video stream is not supported.
+    UnsupportedCodec(String),
+    /// The codec has failed to decode a frame.
+    Decoder(String),
+}
+
+impl Display for VideoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VideoError::Io(v) => {
+                write!(f, "An i/o error has occurred: {v}")
+            }
+            VideoError::InvalidContainer(v) => {
+                write!(f, "Invalid video container: {v}")
+            }
+            VideoError::UnsupportedCodec(v) => {
+                write!(f, "Unsupported video codec: {v}")
+            }
+            VideoError::Decoder(v) => {
+                write!(f, "Unable to decode a video frame: {v}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VideoError {}
+
+impl From<std::io::Error> for VideoError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<FileLoadError> for VideoError {
+    fn from(e: FileLoadError) -> Self {
+        match e {
+            FileLoadError::Io(e) => Self::Io(e),
+            FileLoadError::Custom(e) => Self::Io(std::io::Error::new(std::io::ErrorKind::Other, e)),
+        }
+    }
+}
+
+/// Video decoder is a source of video frames for [`VideoPlayer`].
+pub trait VideoDecoder: Send + Debug {
+    /// Returns size of each frame of the video in pixels.
+    fn frame_size(&self) -> Vector2<u32>;
+
+    /// Returns duration of a single frame in seconds.
+    fn frame_duration(&self) -> f32;
+
+    /// Decodes next frame of the video and writes its pixels to the given buffer in RGBA8 format. The
+    /// buffer has enough space to store a frame of the size returned by [`Self::frame_size`]. Returns
+    /// `Ok(false)` when there are no more frames in the video.
+    fn decode_next_frame(&mut self, rgba: &mut [u8]) -> Result<bool, VideoError>;
+
+    /// Moves the decoder to the first frame of the video.
+    fn rewind(&mut self) -> Result<(), VideoError>;
+}
+
+/// Video resource holds an encoded video stream in IVF container. It does not decode anything by
+/// itself, use [`VideoPlayer::from_resource`] to play it.
+#[derive(Debug, Default, Clone, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "35839e0e-8b74-436e-92a9-68fd797b7a9a")]
+pub struct Video {
+    #[reflect(hidden)]
+    data: Vec<u8>,
+}
+
+impl ResourceData for Video {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        std::fs::write(path, &self.data)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+impl Video {
+    /// Creates new video from the given content of an IVF file. Fails if the data is not a valid IVF
+    /// file, the codec of the video stream is not checked.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, VideoError> {
+        ivf::IvfHeader::read(&data)?;
+        Ok(Self { data })
+    }
+
+    /// Loads a video from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, VideoError> {
+        Self::from_bytes(io.load_file(path).await?)
+    }
+
+    /// Returns encoded content of the video.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the header of the video, that contains its codec, size, frame rate, etc.
+    pub fn header(&self) -> Result<ivf::IvfHeader, VideoError> {
+        ivf::IvfHeader::read(&self.data)
+    }
+
+    /// Creates a new decoder for the video stream, the decoder is chosen by the codec of the stream.
+    /// Fails if the codec is not supported (see [module docs](self) for more info).
+    pub fn make_decoder(&self) -> Result<Box<dyn VideoDecoder>, VideoError> {
+        make_decoder(self.data.clone())
+    }
+}
+
+/// Type alias for video resources.
+pub type VideoResource = Resource<Video>;
+
+#[cfg_attr(
+    not(any(feature = "video_av1", feature = "video_vp9")),
+    allow(unused_variables)
+)]
+fn make_decoder(data: Vec<u8>) -> Result<Box<dyn VideoDecoder>, VideoError> {
+    let fourcc = ivf::IvfHeader::read(&data)?.fourcc;
+    match &fourcc {
+        #[cfg(feature = "video_av1")]
+        b"AV01" => Ok(Box::new(av1::Av1IvfDecoder::new(data)?)),
+        #[cfg(feature = "video_vp9")]
+        b"VP90" => Ok(Box::new(vp9::Vp9IvfDecoder::new(data)?)),
+        _ => Err(VideoError::UnsupportedCodec(
+            String::from_utf8_lossy(&fourcc).to_string(),
+        )),
+    }
+}
+
+/// Playback status of a [`VideoPlayer`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum VideoStatus {
+    /// The video is stopped and rewound to its first frame.
+    #[default]
+    Stopped,
+    /// The video is playing.
+    Playing,
+    /// The video is paused on its current frame.
+    Paused,
+}
+
+/// Video player decodes video frames and writes them into a texture. The texture could be used as any
+/// other texture, for example in materials or in user interface images. The player must be updated
+/// each frame, for example in a script or a plugin.
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox_impl::resource::video::{VideoDecoder, VideoError, VideoPlayer};
+/// fn play_movie(decoder: Box<dyn VideoDecoder>) -> VideoPlayer {
+///     let mut player = VideoPlayer::new(decoder);
+///     player.set_looping(true);
+///     player.play();
+///     // Use `player.texture()` in a material or an image widget.
+///     player
+/// }
+///
+/// fn update_movie(player: &mut VideoPlayer, dt: f32) -> Result<(), VideoError> {
+///     player.update(dt)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct VideoPlayer {
+    decoder: Box<dyn VideoDecoder>,
+    texture: TextureResource,
+    status: VideoStatus,
+    looping: bool,
+    frame_time: f32,
+    first_frame_pending: bool,
+}
+
+impl VideoPlayer {
+    /// Creates a new video player that will use the given decoder as a source of frames. The texture
+    /// of the player is filled with black color until the first frame is decoded.
+    pub fn new(decoder: Box<dyn VideoDecoder>) -> Self {
+        let size = decoder.frame_size();
+        let texture = TextureResource::from_bytes(
+            TextureKind::Rectangle {
+                width: size.x,
+                height: size.y,
+            },
+            TexturePixelKind::RGBA8,
+            vec![0; size.x as usize * size.y as usize * 4],
+            ResourceKind::Embedded,
+        )
+        .expect("Size of the data must match the size of the texture!");
+
+        Self {
+            decoder,
+            texture,
+            status: Default::default(),
+            looping: false,
+            frame_time: 0.0,
+            first_frame_pending: true,
+        }
+    }
+
+    /// Creates a new video player that plays a video stream from the given IVF file. The decoder is
+    /// chosen by the codec of the stream.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, VideoError> {
+        let data = std::fs::read(path)?;
+        Ok(Self::new(make_decoder(data)?))
+    }
+
+    /// Creates a new video player that plays the given video resource. Fails if the resource is not
+    /// loaded (yet or due to a loading error) or the codec of the video is not supported.
+    pub fn from_resource(video: &VideoResource) -> Result<Self, VideoError> {
+        let mut state = video.state();
+        let Some(video) = state.data() else {
+            return Err(VideoError::InvalidContainer(
+                "The video resource is not loaded.".to_string(),
+            ));
+        };
+        Ok(Self::new(video.make_decoder()?))
+    }
+
+    /// Returns a texture into which the frames of the video are written.
+    pub fn texture(&self) -> TextureResource {
+        self.texture.clone()
+    }
+
+    /// Starts or resumes the playback.
+    pub fn play(&mut self) {
+        self.status = VideoStatus::Playing;
+    }
+
+    /// Pauses the playback. The texture will keep the current frame.
+    pub fn pause(&mut self) {
+        if self.status == VideoStatus::Playing {
+            self.status = VideoStatus::Paused;
+        }
+    }
+
+    /// Stops the playback and rewinds the video to its beginning.
+    pub fn stop(&mut self) -> Result<(), VideoError> {
+        self.status = VideoStatus::Stopped;
+        self.frame_time = 0.0;
+        self.first_frame_pending = true;
+        self.decoder.rewind()
+    }
+
+    /// Returns current playback status.
+    pub fn status(&self) -> VideoStatus {
+        self.status
+    }
+
+    /// Defines whether the video should start over when it reaches its end or not.
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    /// Returns `true` if the video is looping, `false` - otherwise.
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Returns a reference to the decoder of the player.
+    pub fn decoder(&self) -> &dyn VideoDecoder {
+        &*self.decoder
+    }
+
+    /// Advances the playback by the given amount of time (in seconds) and writes new frames into the
+    /// texture if needed.
+    pub fn update(&mut self, dt: f32) -> Result<(), VideoError> {
+        if self.status != VideoStatus::Playing {
+            return Ok(());
+        }
+
+        let frame_duration = self.decoder.frame_duration();
+
+        // Decode the very first frame immediately, otherwise there will be a black frame at the
+        // beginning.
+        let mut frames_to_decode = if self.first_frame_pending { 1 } else { 0 };
+        self.first_frame_pending = false;
+        self.frame_time += dt;
+        if frame_duration > 0.0 {
+            while self.frame_time >= frame_duration {
+                self.frame_time -= frame_duration;
+                frames_to_decode += 1;
+            }
+        }
+
+        if frames_to_decode == 0 {
+            return Ok(());
+        }
+
+        let mut state = self.texture.state();
+        let Some(texture) = state.data() else {
+            return Ok(());
+        };
+        let mut texture = texture.modify();
+        let pixels = texture.data_mut();
+
+        // Skip frames if the playback is too slow, only the last one is visible anyway.
+        for _ in 0..frames_to_decode {
+            if !self.decoder.decode_next_frame(pixels)? {
+                if self.looping {
+                    self.decoder.rewind()?;
+                    self.decoder.decode_next_frame(pixels)?;
+                } else {
+                    self.status = VideoStatus::Stopped;
+                    self.frame_time = 0.0;
+                    self.first_frame_pending = true;
+                    self.decoder.rewind()?;
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::{untyped::ResourceKind, Resource},
+        core::algebra::Vector2,
+        resource::video::{Video, VideoDecoder, VideoError, VideoPlayer, VideoStatus},
+    };
+
+    #[derive(Debug)]
+    struct CountingDecoder {
+        frame: u8,
+        frame_count: u8,
+    }
+
+    impl VideoDecoder for CountingDecoder {
+        fn frame_size(&self) -> Vector2<u32> {
+            Vector2::new(1, 1)
+        }
+
+        fn frame_duration(&self) -> f32 {
+            0.5
+        }
+
+        fn decode_next_frame(&mut self, rgba: &mut [u8]) -> Result<bool, VideoError> {
+            if self.frame == self.frame_count {
+                return Ok(false);
+            }
+            self.frame += 1;
+            rgba.fill(self.frame);
+            Ok(true)
+        }
+
+        fn rewind(&mut self) -> Result<(), VideoError> {
+            self.frame = 0;
+            Ok(())
+        }
+    }
+
+    fn current_pixel(player: &VideoPlayer) -> u8 {
+        player.texture().data_ref().data()[0]
+    }
+
+    #[test]
+    fn test_video_player() {
+        let mut player = VideoPlayer::new(Box::new(CountingDecoder {
+            frame: 0,
+            frame_count: 2,
+        }));
+
+        player.update(0.1).unwrap();
+        assert_eq!(current_pixel(&player), 0);
+
+        player.play();
+        player.update(0.1).unwrap();
+        assert_eq!(current_pixel(&player), 1);
+
+        player.update(0.5).unwrap();
+        assert_eq!(current_pixel(&player), 2);
+
+        player.update(0.5).unwrap();
+        assert_eq!(player.status(), VideoStatus::Stopped);
+
+        player.set_looping(true);
+        player.play();
+        player.update(0.1).unwrap();
+        player.update(0.5).unwrap();
+        player.update(0.5).unwrap();
+        assert_eq!(player.status(), VideoStatus::Playing);
+        assert_eq!(current_pixel(&player), 1);
+    }
+
+    #[test]
+    fn test_video_resource() {
+        assert!(Video::from_bytes(vec![0; 8]).is_err());
+
+        // Header of an IVF file with VP8 video stream, which is not supported.
+        let mut data = vec![0; 32];
+        data[0..4].copy_from_slice(b"DKIF");
+        data[6..8].copy_from_slice(&32u16.to_le_bytes());
+        data[8..12].copy_from_slice(b"VP80");
+        data[12..14].copy_from_slice(&2u16.to_le_bytes());
+        data[14..16].copy_from_slice(&2u16.to_le_bytes());
+
+        let video = Video::from_bytes(data).unwrap();
+        let header = video.header().unwrap();
+        assert_eq!(&header.fourcc, b"VP80");
+        assert_eq!((header.width, header.height), (2, 2));
+
+        let resource = Resource::new_ok(ResourceKind::Embedded, video);
+        assert!(matches!(
+            VideoPlayer::from_resource(&resource),
+            Err(VideoError::UnsupportedCodec(_))
+        ));
+    }
+}
@@ -0,0 +1,138 @@
+//! VP9 video decoder, it uses [FFmpeg](https://ffmpeg.org) libraries to decode VP9 video streams
+//! stored in IVF container. Available only with `video_vp9` feature.
+
+use crate::{
+    core::algebra::Vector2,
+    resource::video::{ivf::IvfReader, yuv::YuvImage, VideoDecoder, VideoError},
+};
+use ffmpeg_next::{codec, decoder, error::EAGAIN, format::Pixel, frame, Error, Packet};
+use std::fmt::{Debug, Formatter};
+
+/// VP9 video decoder, that reads a VP9 video stream from IVF container. Only 8-bit videos are supported.
+pub struct Vp9IvfDecoder {
+    reader: IvfReader,
+    decoder: decoder::Video,
+    frame: frame::Video,
+    end_of_stream_sent: bool,
+}
+
+impl Debug for Vp9IvfDecoder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Vp9IvfDecoder")
+            .field("Header", self.reader.header())
+            .finish()
+    }
+}
+
+impl From<Error> for VideoError {
+    fn from(e: Error) -> Self {
+        VideoError::Decoder(e.to_string())
+    }
+}
+
+impl Vp9IvfDecoder {
+    /// Creates new decoder from the given content of an IVF file.
+    pub fn new(data: Vec<u8>) -> Result<Self, VideoError> {
+        let reader = IvfReader::new(data)?;
+        if &reader.header().fourcc != b"VP90" {
+            return Err(VideoError::UnsupportedCodec(
+                String::from_utf8_lossy(&reader.header().fourcc).to_string(),
+            ));
+        }
+
+        ffmpeg_next::init()?;
+        let codec = decoder::find(codec::Id::VP9).ok_or_else(|| {
+            VideoError::UnsupportedCodec("FFmpeg is built without VP9 decoder".to_string())
+        })?;
+        let decoder = codec::Context::new_with_codec(codec).decoder().video()?;
+
+        Ok(Self {
+            reader,
+            decoder,
+            frame: frame::Video::empty(),
+            end_of_stream_sent: false,
+        })
+    }
+
+    fn next_frame(&mut self) -> Result<bool, VideoError> {
+        loop {
+            match self.decoder.receive_frame(&mut self.frame) {
+                Ok(()) => return Ok(true),
+                Err(Error::Eof) => return Ok(false),
+                // The decoder needs more data to produce a frame.
+                Err(Error::Other { errno: EAGAIN }) => (),
+                Err(e) => return Err(e.into()),
+            }
+
+            if let Some(frame) = self.reader.next_frame() {
+                let mut packet = Packet::copy(frame.data);
+                packet.set_pts(Some(frame.timestamp as i64));
+                self.decoder.send_packet(&packet)?;
+            } else if !self.end_of_stream_sent {
+                // Drain the frames that are still in the decoder.
+                self.decoder.send_eof()?;
+                self.end_of_stream_sent = true;
+            } else {
+                return Ok(false);
+            }
+        }
+    }
+}
+
+impl VideoDecoder for Vp9IvfDecoder {
+    fn frame_size(&self) -> Vector2<u32> {
+        let header = self.reader.header();
+        Vector2::new(header.width as u32, header.height as u32)
+    }
+
+    fn frame_duration(&self) -> f32 {
+        self.reader.header().frame_duration()
+    }
+
+    fn decode_next_frame(&mut self, rgba: &mut [u8]) -> Result<bool, VideoError> {
+        if !self.next_frame()? {
+            return Ok(false);
+        }
+
+        let chroma_shift = match self.frame.format() {
+            Pixel::GRAY8 | Pixel::YUV444P => (0, 0),
+            Pixel::YUV420P | Pixel::YUVJ420P => (1, 1),
+            Pixel::YUV422P => (1, 0),
+            Pixel::YUV440P => (0, 1),
+            format => {
+                return Err(VideoError::UnsupportedCodec(format!(
+                    "VP9 video with {format:?} pixel format"
+                )))
+            }
+        };
+        let (uv, uv_stride) = if self.frame.format() == Pixel::GRAY8 {
+            (None, 0)
+        } else {
+            (
+                Some((self.frame.data(1), self.frame.data(2))),
+                self.frame.stride(1),
+            )
+        };
+
+        let size = self.frame_size();
+        YuvImage {
+            width: self.frame.width().min(size.x) as usize,
+            height: self.frame.height().min(size.y) as usize,
+            y: self.frame.data(0),
+            y_stride: self.frame.stride(0),
+            uv,
+            uv_stride,
+            chroma_shift,
+        }
+        .write_rgba(rgba, size.x as usize);
+
+        Ok(true)
+    }
+
+    fn rewind(&mut self) -> Result<(), VideoError> {
+        self.reader.rewind();
+        self.decoder.flush();
+        self.end_of_stream_sent = false;
+        Ok(())
+    }
+}
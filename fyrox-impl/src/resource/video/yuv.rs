@@ -0,0 +1,52 @@
+//! Conversion of planar YUV images, that are produced by video codecs, into RGBA8 pixels.
+
+/// Planar 8-bit YUV image.
+pub(crate) struct YuvImage<'a> {
+    /// Width of the image in pixels.
+    pub width: usize,
+    /// Height of the image in pixels.
+    pub height: usize,
+    /// Luma plane.
+    pub y: &'a [u8],
+    /// Length of a row of the luma plane in bytes.
+    pub y_stride: usize,
+    /// Chroma planes (U and V), `None` for monochrome images.
+    pub uv: Option<(&'a [u8], &'a [u8])>,
+    /// Length of a row of the chroma planes in bytes.
+    pub uv_stride: usize,
+    /// Horizontal and vertical subsampling of the chroma planes as power of two, for example `(1, 1)`
+    /// for 4:2:0 images.
+    pub chroma_shift: (usize, usize),
+}
+
+fn yuv_to_rgb(y: u8, u: u8, v: u8) -> [u8; 3] {
+    // BT.709 limited range.
+    let y = (y as f32 - 16.0) * 1.164;
+    let u = u as f32 - 128.0;
+    let v = v as f32 - 128.0;
+    [
+        (y + 1.793 * v).clamp(0.0, 255.0) as u8,
+        (y - 0.213 * u - 0.533 * v).clamp(0.0, 255.0) as u8,
+        (y + 2.112 * u).clamp(0.0, 255.0) as u8,
+    ]
+}
+
+impl YuvImage<'_> {
+    /// Writes the image into the given RGBA8 buffer, rows of the buffer are `row_length` pixels long.
+    pub fn write_rgba(&self, rgba: &mut [u8], row_length: usize) {
+        let (chroma_shift_x, chroma_shift_y) = self.chroma_shift;
+        for row in 0..self.height {
+            for column in 0..self.width {
+                let y = self.y[row * self.y_stride + column];
+                let uv_index =
+                    (row >> chroma_shift_y) * self.uv_stride + (column >> chroma_shift_x);
+                let (u, v) = self
+                    .uv
+                    .map_or((128, 128), |(u, v)| (u[uv_index], v[uv_index]));
+                let [r, g, b] = yuv_to_rgb(y, u, v);
+                let i = (row * row_length + column) * 4;
+                rgba[i..i + 4].copy_from_slice(&[r, g, b, 255]);
+            }
+        }
+    }
+}
@@ -16,13 +16,16 @@ use crate::{
     scene::{
         base::{Base, BaseBuilder},
         graph::{Graph, NodePool},
-        node::{Node, NodeTrait, UpdateContext},
+        node::{Node, NodeTrait, ParallelUpdateContext, UpdateContext},
     },
 };
 use fyrox_graph::BaseSceneGraph;
 use std::ops::{Deref, DerefMut};
 
 pub mod absm;
+pub mod pose;
+pub mod sequencer;
+pub mod sprite;
 pub mod spritesheet;
 
 /// Scene specific animation.
@@ -56,15 +59,33 @@ pub trait AnimationContainerExt {
     /// Updates all animations in the container and applies their poses to respective nodes. This method is intended to
     /// be used only by the internals of the engine!
     fn update_animations(&mut self, nodes: &mut NodePool, apply: bool, dt: f32);
+
+    /// Updates all animations in the container, without applying their poses. This method is intended to be used
+    /// only by the internals of the engine!
+    fn tick_animations(&mut self, dt: f32);
+
+    /// Applies poses of all animations in the container to respective nodes. This method is intended to be used
+    /// only by the internals of the engine!
+    fn apply_animations(&self, nodes: &mut NodePool);
 }
 
 impl AnimationContainerExt for AnimationContainer {
     fn update_animations(&mut self, nodes: &mut NodePool, apply: bool, dt: f32) {
+        self.tick_animations(dt);
+        if apply {
+            self.apply_animations(nodes);
+        }
+    }
+
+    fn tick_animations(&mut self, dt: f32) {
         for animation in self.iter_mut().filter(|anim| anim.is_enabled()) {
             animation.tick(dt);
-            if apply {
-                animation.pose().apply_internal(nodes);
-            }
+        }
+    }
+
+    fn apply_animations(&self, nodes: &mut NodePool) {
+        for animation in self.iter().filter(|anim| anim.is_enabled()) {
+            animation.pose().apply_internal(nodes);
         }
     }
 }
@@ -311,12 +332,16 @@ impl NodeTrait for AnimationPlayer {
         Self::type_uuid()
     }
 
+    fn parallel_update(&mut self, context: &ParallelUpdateContext) {
+        self.animations
+            .get_value_mut_silent()
+            .tick_animations(context.dt);
+    }
+
     fn update(&mut self, context: &mut UpdateContext) {
-        self.animations.get_value_mut_silent().update_animations(
-            context.nodes,
-            self.auto_apply,
-            context.dt,
-        );
+        if self.auto_apply {
+            self.animations.apply_animations(context.nodes);
+        }
     }
 }
 
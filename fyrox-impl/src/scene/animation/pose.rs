@@ -0,0 +1,352 @@
+//! Pose library is a resource, that contains named snapshots of bone transforms (poses), that could be
+//! applied to any model with the same bone names. See [`PoseLibrary`] docs for more info.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+        state::LoadError,
+        Resource, ResourceData,
+    },
+    core::{
+        algebra::{UnitQuaternion, Vector2, Vector3},
+        io::FileLoadError,
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::{graph::Graph, node::Node},
+};
+use fyrox_graph::{BaseSceneGraph, SceneGraph};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// An error that may occur during pose library resource loading.
+#[derive(Debug)]
+pub enum PoseLibraryError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for PoseLibraryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            Self::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for PoseLibraryError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for PoseLibraryError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// Local transform of a bone. Bones are identified by names, so a pose could be applied to any
+/// instance of a model.
+#[derive(Clone, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "6cf6905e-617b-49de-bb43-f2d65ddc7675")]
+pub struct BoneTransform {
+    /// Name of the bone.
+    pub name: String,
+    /// Local position of the bone.
+    pub position: Vector3<f32>,
+    /// Local rotation of the bone.
+    pub rotation: UnitQuaternion<f32>,
+    /// Local scale of the bone.
+    pub scale: Vector3<f32>,
+}
+
+impl Default for BoneTransform {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            position: Default::default(),
+            rotation: Default::default(),
+            scale: Vector3::repeat(1.0),
+        }
+    }
+}
+
+impl BoneTransform {
+    /// Returns a transform, that is a blend of `self` and `other` transforms. `weight` defines how
+    /// much of `other` is in the result.
+    pub fn blend(&self, other: &BoneTransform, weight: f32) -> BoneTransform {
+        BoneTransform {
+            name: self.name.clone(),
+            position: self.position.lerp(&other.position, weight),
+            rotation: self.rotation.nlerp(&other.rotation, weight),
+            scale: self.scale.lerp(&other.scale, weight),
+        }
+    }
+}
+
+/// A named snapshot of bone transforms.
+#[derive(Clone, Default, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "440a4ccc-a409-42cb-90af-315b653a94e0")]
+pub struct PoseSnapshot {
+    /// Unique name of the pose.
+    pub name: String,
+    /// Local transforms of the bones.
+    pub bones: Vec<BoneTransform>,
+    /// Pairs of points in `[0; 1]` range, that forms lines between bones and their parents
+    /// projected on the XY plane. It is used to draw a thumbnail of the pose.
+    pub thumbnail: Vec<Vector2<f32>>,
+}
+
+impl PoseSnapshot {
+    /// Captures local transforms of the given bones.
+    pub fn capture(name: &str, graph: &Graph, bones: &[Handle<Node>]) -> Self {
+        let bones = bones
+            .iter()
+            .filter_map(|&handle| graph.try_get(handle).map(|node| (handle, node)))
+            .collect::<Vec<_>>();
+
+        let mut thumbnail = Vec::new();
+        for &(_, node) in bones.iter() {
+            let parent = bones
+                .iter()
+                .find(|(handle, _)| *handle == node.parent())
+                .map_or(node, |(_, parent)| parent);
+            thumbnail.push(parent.global_position().xy());
+            thumbnail.push(node.global_position().xy());
+        }
+
+        // Fit the thumbnail in the unit square, but keep its aspect ratio.
+        if let Some(first) = thumbnail.first().cloned() {
+            let (min, max) = thumbnail
+                .iter()
+                .fold((first, first), |(min, max), p| (min.inf(p), max.sup(p)));
+            let size = max - min;
+            let scale = size.x.max(size.y).max(f32::EPSILON);
+            let offset = (Vector2::repeat(scale) - size).scale(0.5);
+            for point in thumbnail.iter_mut() {
+                *point = (*point - min + offset).scale(1.0 / scale);
+            }
+        }
+
+        Self {
+            name: name.to_string(),
+            bones: bones
+                .iter()
+                .map(|(_, node)| {
+                    let transform = node.local_transform();
+                    BoneTransform {
+                        name: node.name().to_string(),
+                        position: **transform.position(),
+                        rotation: **transform.rotation(),
+                        scale: **transform.scale(),
+                    }
+                })
+                .collect(),
+            thumbnail,
+        }
+    }
+
+    /// Searches for the bones of the pose in the hierarchy starting from `root` and returns a blend
+    /// of their current local transforms and the transforms of the pose. `weight` defines how much
+    /// of the pose is in the result. Bones, that do not exist in the hierarchy, are ignored.
+    pub fn blended_transforms(
+        &self,
+        graph: &Graph,
+        root: Handle<Node>,
+        weight: f32,
+    ) -> Vec<(Handle<Node>, BoneTransform)> {
+        self.bones
+            .iter()
+            .filter_map(|bone| {
+                let (handle, node) = graph.find_by_name(root, &bone.name)?;
+                let transform = node.local_transform();
+                let current = BoneTransform {
+                    name: bone.name.clone(),
+                    position: **transform.position(),
+                    rotation: **transform.rotation(),
+                    scale: **transform.scale(),
+                };
+                Some((handle, current.blend(bone, weight)))
+            })
+            .collect()
+    }
+
+    /// Applies the pose to the hierarchy starting from `root`. `weight` defines how much of the pose
+    /// is blended with the current transforms of the bones.
+    pub fn apply(&self, graph: &mut Graph, root: Handle<Node>, weight: f32) {
+        for (handle, transform) in self.blended_transforms(graph, root, weight) {
+            graph[handle]
+                .local_transform_mut()
+                .set_position(transform.position)
+                .set_rotation(transform.rotation)
+                .set_scale(transform.scale);
+        }
+    }
+}
+
+/// A set of named poses. Poses store bone names, so a library could be used with every model, that
+/// has the same skeleton.
+#[derive(Clone, Default, Debug, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "e7224b77-dfca-4ba0-8c95-00bef1dc7bef")]
+pub struct PoseLibrary {
+    /// Poses of the library.
+    pub poses: Vec<PoseSnapshot>,
+}
+
+impl PoseLibrary {
+    /// Tries to find a pose with the given name.
+    pub fn find(&self, name: &str) -> Option<&PoseSnapshot> {
+        self.poses.iter().find(|pose| pose.name == name)
+    }
+
+    /// Adds a new pose or replaces an existing one with the same name. Returns the old pose (if any).
+    pub fn set(&mut self, pose: PoseSnapshot) -> Option<PoseSnapshot> {
+        match self.poses.iter_mut().find(|p| p.name == pose.name) {
+            Some(existing) => Some(std::mem::replace(existing, pose)),
+            None => {
+                self.poses.push(pose);
+                None
+            }
+        }
+    }
+
+    /// Removes a pose with the given name and returns it (if any).
+    pub fn remove(&mut self, name: &str) -> Option<PoseSnapshot> {
+        let index = self.poses.iter().position(|pose| pose.name == name)?;
+        Some(self.poses.remove(index))
+    }
+
+    /// Load a pose library resource from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, PoseLibraryError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut library = PoseLibrary::default();
+        library.visit("PoseLibrary", &mut visitor)?;
+        Ok(library)
+    }
+}
+
+impl ResourceData for PoseLibrary {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("PoseLibrary", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+/// A resource, that contains a pose library.
+pub type PoseLibraryResource = Resource<PoseLibrary>;
+
+/// Pose library loader.
+pub struct PoseLibraryLoader;
+
+impl ResourceLoader for PoseLibraryLoader {
+    fn extensions(&self) -> &[&str] {
+        &["poses"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <PoseLibrary as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let library = PoseLibrary::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(library))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, visitor::prelude::*},
+        scene::{
+            animation::pose::{PoseLibrary, PoseSnapshot},
+            base::BaseBuilder,
+            graph::Graph,
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_pose_library() {
+        let mut graph = Graph::new();
+        let bone = PivotBuilder::new(
+            BaseBuilder::new().with_name("Bone").with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(2.0, 0.0, 0.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+
+        let mut library = PoseLibrary::default();
+        assert_eq!(
+            library.set(PoseSnapshot::capture("Pose", &graph, &[bone])),
+            None
+        );
+        assert_eq!(library.find("Pose").unwrap().bones.len(), 1);
+
+        let mut visitor = Visitor::new();
+        library.visit("PoseLibrary", &mut visitor).unwrap();
+        let bytes = visitor.save_binary_to_vec().unwrap();
+        let mut visitor = Visitor::load_from_memory(&bytes).unwrap();
+        let mut loaded = PoseLibrary::default();
+        loaded.visit("PoseLibrary", &mut visitor).unwrap();
+        assert_eq!(loaded.poses, library.poses);
+
+        graph[bone]
+            .local_transform_mut()
+            .set_position(Vector3::new(0.0, 0.0, 0.0));
+        let root = graph.get_root();
+        library.find("Pose").unwrap().apply(&mut graph, root, 0.5);
+        assert_eq!(
+            **graph[bone].local_transform().position(),
+            Vector3::new(1.0, 0.0, 0.0)
+        );
+
+        assert!(library.remove("Pose").is_some());
+        assert!(library.find("Pose").is_none());
+    }
+}
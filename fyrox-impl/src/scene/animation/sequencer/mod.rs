@@ -0,0 +1,614 @@
+//! Sequence player is a node that plays a [`sequence::Sequence`] (a cutscene timeline) on scene nodes. See
+//! [`SequencePlayer`] docs for more info.
+
+use crate::{
+    core::{
+        math::aabb::AxisAlignedBoundingBox, pool::Handle, reflect::prelude::*,
+        type_traits::prelude::*, variable::InheritableVariable, visitor::prelude::*,
+    },
+    generic_animation::value::{BoundValue, BoundValueCollection},
+    scene::{
+        animation::{AnimationPlayer, AnimationPose, BoundValueCollectionExt},
+        base::{Base, BaseBuilder},
+        camera::Camera,
+        graph::{Graph, NodePool},
+        node::{Node, NodeTrait, UpdateContext},
+        sound::{Sound, Status},
+    },
+};
+use fyrox_graph::BaseSceneGraph;
+use std::ops::{Deref, DerefMut};
+
+pub mod sequence;
+
+use sequence::{active_camera_cut, AudioSection, Sequence, SequenceResource, SequenceTrackKind};
+
+/// Maps a name, that is used by the tracks of a sequence, to a scene node.
+#[derive(Clone, Default, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "9a4c2e6f-3b81-4d7a-8e05-6f1b9c3d7a28")]
+pub struct SequenceBinding {
+    /// Name of the binding.
+    pub name: String,
+    /// A node the name points to.
+    pub node: Handle<Node>,
+}
+
+/// Provides mutable access to scene nodes for [`sample_sequence`]. It is implemented for [`Graph`] and for
+/// [`NodePool`], so a sequence could be sampled both from outside of a graph and from a node update.
+pub trait SequenceTarget {
+    /// Tries to borrow a node by its handle.
+    fn node_mut(&mut self, handle: Handle<Node>) -> Option<&mut Node>;
+}
+
+impl SequenceTarget for Graph {
+    fn node_mut(&mut self, handle: Handle<Node>) -> Option<&mut Node> {
+        self.try_get_mut(handle)
+    }
+}
+
+impl SequenceTarget for NodePool {
+    fn node_mut(&mut self, handle: Handle<Node>) -> Option<&mut Node> {
+        self.try_borrow_mut(handle)
+    }
+}
+
+fn find_binding(bindings: &[SequenceBinding], name: &str) -> Handle<Node> {
+    bindings
+        .iter()
+        .find(|binding| binding.name == name)
+        .map(|binding| binding.node)
+        .unwrap_or_default()
+}
+
+fn active_audio_section(sections: &[AudioSection], time: f32) -> Option<&AudioSection> {
+    sections
+        .iter()
+        .find(|section| time >= section.start && time < section.start + section.duration)
+}
+
+/// Applies the state of the given sequence at `time` to the scene nodes. `prev_time` is the time of the
+/// previous sampling, it is used to decide whether sounds should be started or stopped. `None` means that
+/// the sequence was not sampled before (or it was rewound), so every sound, that should be playing at
+/// `time`, will be started from the respective position.
+pub fn sample_sequence(
+    sequence: &Sequence,
+    bindings: &[SequenceBinding],
+    prev_time: Option<f32>,
+    time: f32,
+    target: &mut dyn SequenceTarget,
+) {
+    for track in sequence.tracks.iter().filter(|track| track.enabled) {
+        let node = find_binding(bindings, &track.binding);
+
+        match track.kind {
+            SequenceTrackKind::CameraCuts(ref cuts) => {
+                let Some(active) = active_camera_cut(cuts, time) else {
+                    continue;
+                };
+
+                for cut in cuts.iter() {
+                    let camera = find_binding(bindings, &cut.camera);
+                    if let Some(camera) = target
+                        .node_mut(camera)
+                        .and_then(|node| node.cast_mut::<Camera>())
+                    {
+                        camera.set_enabled(cut.camera == active.camera);
+                    }
+                }
+            }
+            SequenceTrackKind::Animation(ref sections) => {
+                let mut pose = AnimationPose::default();
+                for section in sections.iter() {
+                    if time < section.start || time > section.start + section.duration {
+                        continue;
+                    }
+
+                    let Some(animation_player) = target
+                        .node_mut(node)
+                        .and_then(|node| node.cast_mut::<AnimationPlayer>())
+                    else {
+                        break;
+                    };
+
+                    if let Some((_, animation)) = animation_player
+                        .animations_mut()
+                        .get_value_mut_silent()
+                        .find_by_name_mut(&section.animation)
+                    {
+                        animation.set_time_position(
+                            section.offset + (time - section.start) * section.speed,
+                        );
+                        // Zero time step just calculates the pose at the current time position without
+                        // firing any signals.
+                        animation.tick(0.0);
+                        animation.pose().clone_into(&mut pose);
+                    }
+                }
+
+                for (handle, local_pose) in pose.poses() {
+                    if let Some(node) = target.node_mut(*handle) {
+                        local_pose.values.apply(node);
+                    }
+                }
+            }
+            SequenceTrackKind::Audio(ref sections) => {
+                let Some(sound) = target
+                    .node_mut(node)
+                    .and_then(|node| node.cast_mut::<Sound>())
+                else {
+                    continue;
+                };
+
+                let active = active_audio_section(sections, time);
+                let was_active = prev_time.and_then(|prev| active_audio_section(sections, prev));
+                let jumped_back = match prev_time {
+                    Some(prev) => time < prev,
+                    None => true,
+                };
+
+                match active {
+                    Some(section) => {
+                        if was_active != Some(section) || jumped_back {
+                            sound.set_playback_time(section.offset + time - section.start);
+                            sound.play();
+                        }
+                    }
+                    None => {
+                        if was_active.is_some() && sound.status() == Status::Playing {
+                            sound.stop();
+                        }
+                    }
+                }
+            }
+            SequenceTrackKind::Property(ref property) => {
+                let Some(node) = target.node_mut(node) else {
+                    continue;
+                };
+
+                if let Some(value) = property.curves.fetch(time) {
+                    BoundValueCollection {
+                        values: vec![BoundValue {
+                            binding: property.binding.clone(),
+                            value,
+                        }],
+                    }
+                    .apply(node);
+                }
+            }
+        }
+    }
+}
+
+/// Sequence player is a node that plays a [`Sequence`] - a timeline of a cutscene. Tracks of the sequence
+/// refer to scene nodes by names, and the player maps these names to actual nodes using a list of
+/// [`SequenceBinding`]. This way the same sequence could be played on different nodes.
+///
+/// Animations, that are played by a sequence, should be disabled in their animation player, otherwise the
+/// animation player will play them on its own as well. The sequence player samples such animations directly.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::pool::Handle,
+/// #     scene::{animation::sequencer::SequencePlayer, graph::Graph, node::Node},
+/// # };
+/// fn play_cutscene(player: Handle<Node>, graph: &mut Graph) {
+///     let player = graph[player].cast_mut::<SequencePlayer>().unwrap();
+///     player.play();
+/// }
+///
+/// fn is_cutscene_finished(player: Handle<Node>, graph: &Graph) -> bool {
+///     graph[player].cast::<SequencePlayer>().unwrap().is_finished()
+/// }
+/// ```
+#[derive(Visit, Reflect, Clone, Debug)]
+pub struct SequencePlayer {
+    base: Base,
+
+    #[reflect(setter = "set_sequence")]
+    sequence: InheritableVariable<Option<SequenceResource>>,
+
+    #[reflect(setter = "set_bindings")]
+    bindings: InheritableVariable<Vec<SequenceBinding>>,
+
+    #[reflect(setter = "set_playing")]
+    playing: InheritableVariable<bool>,
+
+    #[reflect(setter = "set_looping")]
+    looping: InheritableVariable<bool>,
+
+    #[reflect(setter = "set_speed")]
+    speed: InheritableVariable<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    time: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    last_sampled_time: Option<f32>,
+}
+
+impl Default for SequencePlayer {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            sequence: Default::default(),
+            bindings: Default::default(),
+            playing: false.into(),
+            looping: false.into(),
+            speed: 1.0.into(),
+            time: 0.0,
+            last_sampled_time: None,
+        }
+    }
+}
+
+impl TypeUuidProvider for SequencePlayer {
+    fn type_uuid() -> Uuid {
+        uuid!("d1e7a3b9-5c2f-4e86-a0d4-8b3f6c1e9a75")
+    }
+}
+
+impl Deref for SequencePlayer {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for SequencePlayer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl SequencePlayer {
+    /// Sets new sequence.
+    pub fn set_sequence(&mut self, sequence: Option<SequenceResource>) -> Option<SequenceResource> {
+        self.last_sampled_time = None;
+        self.sequence.set_value_and_mark_modified(sequence)
+    }
+
+    /// Returns current sequence.
+    pub fn sequence(&self) -> Option<SequenceResource> {
+        (*self.sequence).clone()
+    }
+
+    /// Sets new bindings.
+    pub fn set_bindings(&mut self, bindings: Vec<SequenceBinding>) -> Vec<SequenceBinding> {
+        self.bindings.set_value_and_mark_modified(bindings)
+    }
+
+    /// Returns current bindings.
+    pub fn bindings(&self) -> &[SequenceBinding] {
+        &self.bindings
+    }
+
+    /// Binds a name to the given node. Replaces previous binding with the same name (if any).
+    pub fn bind(&mut self, name: &str, node: Handle<Node>) {
+        let bindings = self.bindings.get_value_mut_and_mark_modified();
+        if let Some(binding) = bindings.iter_mut().find(|binding| binding.name == name) {
+            binding.node = node;
+        } else {
+            bindings.push(SequenceBinding {
+                name: name.to_string(),
+                node,
+            });
+        }
+    }
+
+    /// Returns a node bound to the given name.
+    pub fn binding(&self, name: &str) -> Handle<Node> {
+        find_binding(&self.bindings, name)
+    }
+
+    /// Starts playing the sequence from the beginning.
+    pub fn play(&mut self) {
+        self.rewind();
+        self.set_playing(true);
+    }
+
+    /// Stops the playback and rewinds the sequence.
+    pub fn stop(&mut self) {
+        self.set_playing(false);
+        self.rewind();
+    }
+
+    /// Pauses or resumes the playback.
+    pub fn set_playing(&mut self, playing: bool) -> bool {
+        self.playing.set_value_and_mark_modified(playing)
+    }
+
+    /// Returns `true` if the player is playing the sequence, `false` - otherwise.
+    pub fn is_playing(&self) -> bool {
+        *self.playing
+    }
+
+    /// Defines whether the sequence should be restarted when it reaches its end or not.
+    pub fn set_looping(&mut self, looping: bool) -> bool {
+        self.looping.set_value_and_mark_modified(looping)
+    }
+
+    /// Returns `true` if the sequence is restarted when it reaches its end, `false` - otherwise.
+    pub fn is_looping(&self) -> bool {
+        *self.looping
+    }
+
+    /// Sets playback speed multiplier.
+    pub fn set_speed(&mut self, speed: f32) -> f32 {
+        self.speed.set_value_and_mark_modified(speed)
+    }
+
+    /// Returns playback speed multiplier.
+    pub fn speed(&self) -> f32 {
+        *self.speed
+    }
+
+    /// Moves the playback position to the given time (in seconds). The state of the scene nodes will be
+    /// updated on the next update of the player, even if it is paused.
+    pub fn set_time(&mut self, time: f32) {
+        self.time = time.max(0.0);
+        self.last_sampled_time = None;
+    }
+
+    /// Returns current playback position (in seconds).
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Moves the playback position to the beginning of the sequence.
+    pub fn rewind(&mut self) {
+        self.set_time(0.0);
+    }
+
+    /// Returns `true` if a non-looping sequence has reached its end.
+    pub fn is_finished(&self) -> bool {
+        let Some(sequence) = self.sequence.as_ref() else {
+            return false;
+        };
+        let mut state = sequence.state();
+        let Some(sequence) = state.data() else {
+            return false;
+        };
+        !*self.looping && self.time >= sequence.duration
+    }
+
+    /// Advances the playback position by the given time step and applies the state of the sequence to the
+    /// scene nodes.
+    fn advance(&mut self, dt: f32, target: &mut dyn SequenceTarget) {
+        let Some(sequence) = self.sequence.as_ref() else {
+            return;
+        };
+        let mut state = sequence.state();
+        let Some(sequence) = state.data() else {
+            return;
+        };
+
+        let paused = !*self.playing || self.time >= sequence.duration && !*self.looping;
+        if paused && self.last_sampled_time.is_some() {
+            return;
+        }
+
+        if !paused {
+            self.time += dt * *self.speed;
+            if self.time >= sequence.duration {
+                if *self.looping && sequence.duration > 0.0 {
+                    self.time %= sequence.duration;
+                } else {
+                    self.time = sequence.duration;
+                }
+            }
+        }
+
+        sample_sequence(
+            sequence,
+            &self.bindings,
+            self.last_sampled_time,
+            self.time,
+            target,
+        );
+        self.last_sampled_time = Some(self.time);
+    }
+}
+
+impl NodeTrait for SequencePlayer {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        self.advance(context.dt, context.nodes);
+    }
+}
+
+/// A builder for [`SequencePlayer`] node.
+pub struct SequencePlayerBuilder {
+    base_builder: BaseBuilder,
+    sequence: Option<SequenceResource>,
+    bindings: Vec<SequenceBinding>,
+    playing: bool,
+    looping: bool,
+    speed: f32,
+}
+
+impl SequencePlayerBuilder {
+    /// Creates new builder instance.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            sequence: None,
+            bindings: Default::default(),
+            playing: false,
+            looping: false,
+            speed: 1.0,
+        }
+    }
+
+    /// Sets desired sequence.
+    pub fn with_sequence(mut self, sequence: Option<SequenceResource>) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Sets desired bindings.
+    pub fn with_bindings(mut self, bindings: Vec<SequenceBinding>) -> Self {
+        self.bindings = bindings;
+        self
+    }
+
+    /// Sets whether the player should play the sequence or not.
+    pub fn with_playing(mut self, playing: bool) -> Self {
+        self.playing = playing;
+        self
+    }
+
+    /// Sets whether the sequence should be restarted when it reaches its end or not.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Sets desired playback speed.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Creates an instance of [`SequencePlayer`] node.
+    pub fn build_node(self) -> Node {
+        Node::new(SequencePlayer {
+            base: self.base_builder.build_base(),
+            sequence: self.sequence.into(),
+            bindings: self.bindings.into(),
+            playing: self.playing.into(),
+            looping: self.looping.into(),
+            speed: self.speed.into(),
+            time: 0.0,
+            last_sampled_time: None,
+        })
+    }
+
+    /// Creates an instance of [`SequencePlayer`] node and adds it to the given scene graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::{untyped::ResourceKind, Resource},
+        core::{
+            algebra::Vector2,
+            math::curve::{Curve, CurveKey, CurveKeyKind},
+            pool::Handle,
+        },
+        generic_animation::{
+            container::{TrackDataContainer, TrackValueKind},
+            value::{ValueBinding, ValueType},
+        },
+        scene::{
+            animation::sequencer::{
+                sequence::{CameraCut, PropertyTrack, Sequence, SequenceTrack, SequenceTrackKind},
+                SequenceBinding, SequencePlayer, SequencePlayerBuilder,
+            },
+            base::BaseBuilder,
+            camera::{Camera, CameraBuilder},
+            graph::Graph,
+            node::Node,
+            pivot::PivotBuilder,
+        },
+    };
+
+    #[test]
+    fn test_sequence_player() {
+        let mut graph = Graph::new();
+        let wide = CameraBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let close_up = CameraBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let actor = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        let mut curve = Curve::default();
+        curve.add_key(CurveKey::new(0.0, 0.0, CurveKeyKind::Linear));
+        curve.add_key(CurveKey::new(2.0, 4.0, CurveKeyKind::Linear));
+        let mut curves = TrackDataContainer::new(TrackValueKind::Real);
+        curves.curves_mut()[0] = curve;
+
+        let sequence = Resource::new_ok(
+            ResourceKind::Embedded,
+            Sequence {
+                duration: 2.0,
+                tracks: vec![
+                    SequenceTrack {
+                        kind: SequenceTrackKind::CameraCuts(vec![
+                            CameraCut {
+                                time: 0.0,
+                                camera: "Wide".to_string(),
+                            },
+                            CameraCut {
+                                time: 1.0,
+                                camera: "CloseUp".to_string(),
+                            },
+                        ]),
+                        ..Default::default()
+                    },
+                    SequenceTrack {
+                        binding: "Actor".to_string(),
+                        kind: SequenceTrackKind::Property(PropertyTrack {
+                            binding: ValueBinding::Property {
+                                name: "base.visibility".to_string(),
+                                value_type: ValueType::Bool,
+                            },
+                            curves,
+                        }),
+                        ..Default::default()
+                    },
+                ],
+            },
+        );
+
+        let binding = |name: &str, node| SequenceBinding {
+            name: name.to_string(),
+            node,
+        };
+        let player = SequencePlayerBuilder::new(BaseBuilder::new())
+            .with_sequence(Some(sequence))
+            .with_bindings(vec![
+                binding("Wide", wide),
+                binding("CloseUp", close_up),
+                binding("Actor", actor),
+            ])
+            .with_playing(true)
+            .build(&mut graph);
+
+        let enabled = |graph: &Graph, camera: Handle<Node>| {
+            graph[camera].cast::<Camera>().unwrap().is_enabled()
+        };
+
+        graph.update(Vector2::new(100.0, 100.0), 0.0, Default::default());
+        assert!(enabled(&graph, wide));
+        assert!(!enabled(&graph, close_up));
+        assert!(!graph[actor].visibility());
+
+        graph.update(Vector2::new(100.0, 100.0), 1.5, Default::default());
+        assert!(!enabled(&graph, wide));
+        assert!(enabled(&graph, close_up));
+        assert!(graph[actor].visibility());
+
+        // Non-looping sequence must stop at its end.
+        graph.update(Vector2::new(100.0, 100.0), 1.0, Default::default());
+        let player = graph[player].cast::<SequencePlayer>().unwrap();
+        assert_eq!(player.time(), 2.0);
+        assert!(player.is_finished());
+    }
+}
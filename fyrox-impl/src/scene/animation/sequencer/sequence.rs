@@ -0,0 +1,364 @@
+//! Sequence resource is a timeline of a cutscene, it consists of multiple tracks that switch cameras, play
+//! animations and sounds, and animate properties of scene nodes. See [`Sequence`] docs for more info.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+        state::LoadError,
+        Resource, ResourceData,
+    },
+    core::{io::FileLoadError, reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*},
+    generic_animation::{
+        container::{TrackDataContainer, TrackValueKind},
+        value::ValueBinding,
+    },
+};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// An error that may occur during sequence resource loading.
+#[derive(Debug)]
+pub enum SequenceError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for SequenceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            Self::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for SequenceError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for SequenceError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// A switch to a camera at a specific time.
+#[derive(Clone, Default, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "a4f1d3c6-2b7e-4f0a-8c59-1e6d7b3a9f42")]
+pub struct CameraCut {
+    /// Time (in seconds) at which the camera becomes active.
+    #[reflect(min_value = 0.0)]
+    pub time: f32,
+    /// Name of a binding of a sequence player, that points to a camera.
+    pub camera: String,
+}
+
+/// Returns a cut, that is active at the given time.
+pub fn active_camera_cut(cuts: &[CameraCut], time: f32) -> Option<&CameraCut> {
+    cuts.iter()
+        .filter(|cut| cut.time <= time)
+        .max_by(|a, b| a.time.total_cmp(&b.time))
+}
+
+/// A part of a timeline, during which an animation of an animation player is played.
+#[derive(Clone, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "3e8b5a27-9c14-4d6f-b0a2-7f4c1d8e6a53")]
+pub struct AnimationSection {
+    /// Name of an animation of the bound animation player.
+    pub animation: String,
+    /// Start time of the section (in seconds).
+    #[reflect(min_value = 0.0)]
+    pub start: f32,
+    /// Duration of the section (in seconds).
+    #[reflect(min_value = 0.0)]
+    pub duration: f32,
+    /// Time position of the animation at the start of the section.
+    pub offset: f32,
+    /// Playback speed of the animation.
+    pub speed: f32,
+}
+
+impl Default for AnimationSection {
+    fn default() -> Self {
+        Self {
+            animation: Default::default(),
+            start: 0.0,
+            duration: 1.0,
+            offset: 0.0,
+            speed: 1.0,
+        }
+    }
+}
+
+/// A part of a timeline, during which the bound sound is played.
+#[derive(Clone, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "c7d2e9f0-4a63-4b1e-9d85-2f0a6c3b8e17")]
+pub struct AudioSection {
+    /// Start time of the section (in seconds).
+    #[reflect(min_value = 0.0)]
+    pub start: f32,
+    /// Duration of the section (in seconds).
+    #[reflect(min_value = 0.0)]
+    pub duration: f32,
+    /// Playback position of the sound at the start of the section.
+    #[reflect(min_value = 0.0)]
+    pub offset: f32,
+}
+
+impl Default for AudioSection {
+    fn default() -> Self {
+        Self {
+            start: 0.0,
+            duration: 1.0,
+            offset: 0.0,
+        }
+    }
+}
+
+/// A set of parametric curves, that animates a property of the bound node. It works exactly the same as a
+/// track of an animation.
+#[derive(Clone, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "5b0e7c4d-1f98-4a2c-b6e3-9d7a2f5c0e81")]
+pub struct PropertyTrack {
+    /// A property to animate.
+    #[reflect(hidden)]
+    pub binding: ValueBinding,
+    /// Curves, that defines the values of the property.
+    #[reflect(hidden)]
+    pub curves: TrackDataContainer,
+}
+
+impl Default for PropertyTrack {
+    fn default() -> Self {
+        Self {
+            binding: ValueBinding::Position,
+            curves: TrackDataContainer::new(TrackValueKind::Vector3),
+        }
+    }
+}
+
+/// Actual content of a sequence track.
+#[derive(
+    Clone, Debug, PartialEq, Reflect, Visit, AsRefStr, EnumString, VariantNames, TypeUuidProvider,
+)]
+#[type_uuid(id = "8f3c6a1e-7d24-4b9f-a0e5-3c8b1d6f2a94")]
+pub enum SequenceTrackKind {
+    /// Switches the active camera. Cameras are referenced by cuts directly, the binding of the track is
+    /// ignored.
+    CameraCuts(Vec<CameraCut>),
+    /// Plays animations of the bound animation player.
+    Animation(Vec<AnimationSection>),
+    /// Plays the bound sound.
+    Audio(Vec<AudioSection>),
+    /// Animates a property of the bound node.
+    Property(PropertyTrack),
+}
+
+impl Default for SequenceTrackKind {
+    fn default() -> Self {
+        Self::CameraCuts(Default::default())
+    }
+}
+
+/// A single track of a sequence.
+#[derive(Clone, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "2d9a4e7b-6c31-4f8e-b1d0-5a7e3c9f4b26")]
+pub struct SequenceTrack {
+    /// Name of the track.
+    pub name: String,
+    /// Disabled tracks are ignored during playback.
+    pub enabled: bool,
+    /// Name of a binding of a sequence player, that points to a scene node the track works with.
+    pub binding: String,
+    /// Actual content of the track.
+    pub kind: SequenceTrackKind,
+}
+
+impl Default for SequenceTrack {
+    fn default() -> Self {
+        Self {
+            name: "Track".to_string(),
+            enabled: true,
+            binding: Default::default(),
+            kind: Default::default(),
+        }
+    }
+}
+
+impl SequenceTrack {
+    /// Returns time (in seconds) at which the content of the track ends.
+    pub fn time_length(&self) -> f32 {
+        match self.kind {
+            SequenceTrackKind::CameraCuts(ref cuts) => {
+                cuts.iter().map(|cut| cut.time).fold(0.0, f32::max)
+            }
+            SequenceTrackKind::Animation(ref sections) => sections
+                .iter()
+                .map(|section| section.start + section.duration)
+                .fold(0.0, f32::max),
+            SequenceTrackKind::Audio(ref sections) => sections
+                .iter()
+                .map(|section| section.start + section.duration)
+                .fold(0.0, f32::max),
+            SequenceTrackKind::Property(ref property) => property.curves.time_length(),
+        }
+    }
+}
+
+/// Sequence is a timeline of a cutscene. It consists of multiple tracks, each track works with a scene
+/// node using a named binding. Bindings are defined by [`super::SequencePlayer`], this way the same
+/// sequence could be used with different scene nodes. There are four kinds of tracks:
+///
+/// - Camera cuts - switches the active camera at specific times. Only the camera of the current cut is
+///   enabled, every other camera that is used by the cuts is disabled.
+/// - Animation - plays animations of an animation player during specific sections of the timeline.
+/// - Audio - plays a sound during specific sections of the timeline.
+/// - Property - animates an arbitrary numeric property of a scene node using parametric curves.
+///
+/// Sequences are usually made in the sequencer of the editor.
+///
+/// ```rust
+/// # use fyrox_impl::scene::animation::sequencer::sequence::{
+/// #     CameraCut, Sequence, SequenceTrack, SequenceTrackKind,
+/// # };
+/// fn make_sequence() -> Sequence {
+///     Sequence {
+///         duration: 5.0,
+///         tracks: vec![SequenceTrack {
+///             name: "Cameras".to_string(),
+///             kind: SequenceTrackKind::CameraCuts(vec![
+///                 CameraCut {
+///                     time: 0.0,
+///                     camera: "Wide".to_string(),
+///                 },
+///                 CameraCut {
+///                     time: 2.5,
+///                     camera: "CloseUp".to_string(),
+///                 },
+///             ]),
+///             ..Default::default()
+///         }],
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "6e1b8d3f-4c72-4a95-9e0b-7d2f5a8c1e63")]
+pub struct Sequence {
+    /// Total duration of the sequence (in seconds).
+    #[reflect(min_value = 0.0)]
+    pub duration: f32,
+    /// Tracks of the sequence.
+    pub tracks: Vec<SequenceTrack>,
+}
+
+impl Default for Sequence {
+    fn default() -> Self {
+        Self {
+            duration: 10.0,
+            tracks: Default::default(),
+        }
+    }
+}
+
+impl Sequence {
+    /// Load a sequence resource from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, SequenceError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut sequence = Sequence::default();
+        sequence.visit("Sequence", &mut visitor)?;
+        Ok(sequence)
+    }
+
+    /// Returns the name of the camera binding, that is active at the given time. Only the first enabled
+    /// camera cuts track is taken into account.
+    pub fn active_camera(&self, time: f32) -> Option<&str> {
+        self.tracks
+            .iter()
+            .filter(|track| track.enabled)
+            .find_map(|track| match track.kind {
+                SequenceTrackKind::CameraCuts(ref cuts) => Some(cuts),
+                _ => None,
+            })
+            .and_then(|cuts| active_camera_cut(cuts, time))
+            .map(|cut| cut.camera.as_str())
+    }
+
+    /// Sets the duration of the sequence to the end of its content.
+    pub fn fit_duration_to_content(&mut self) {
+        self.duration = self
+            .tracks
+            .iter()
+            .map(|track| track.time_length())
+            .fold(0.0, f32::max);
+    }
+}
+
+impl ResourceData for Sequence {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("Sequence", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+/// Type alias for sequence resources.
+pub type SequenceResource = Resource<Sequence>;
+
+/// Default implementation for sequence loading.
+pub struct SequenceLoader;
+
+impl ResourceLoader for SequenceLoader {
+    fn extensions(&self) -> &[&str] {
+        &["sequence"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <Sequence as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let sequence = Sequence::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(sequence))
+        })
+    }
+}
@@ -0,0 +1,440 @@
+//! Sprite animation player is a node that plays clips of a sprite sheet on a sprite or a rectangle. See
+//! [`SpriteAnimationPlayer`] docs for more info.
+
+use crate::{
+    core::{
+        math::{aabb::AxisAlignedBoundingBox, Rect},
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        dim2::rectangle::Rectangle,
+        graph::Graph,
+        node::{Node, NodeTrait, UpdateContext},
+        sprite::Sprite,
+    },
+};
+use fyrox_graph::BaseSceneGraph;
+use std::{
+    collections::VecDeque,
+    ops::{Deref, DerefMut},
+};
+
+pub mod sheet;
+
+use sheet::{SpriteClip, SpriteSheetResource};
+
+/// An event, that was fired by a clip.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpriteAnimationEvent {
+    /// Name of the clip.
+    pub clip: String,
+    /// Name of the event.
+    pub name: String,
+}
+
+/// Sprite animation player plays clips of a [`sheet::SpriteSheet`] resource on a target node. The target
+/// node must be either [`Sprite`] or [`Rectangle`], the player changes its texture coordinates every
+/// frame. The texture of the target's material must be the texture of the sprite sheet.
+///
+/// ```rust
+/// # use fyrox_impl::scene::{animation::sprite::SpriteAnimationPlayer, graph::Graph, node::Node};
+/// # use fyrox_impl::core::pool::Handle;
+/// fn jump(player: Handle<Node>, graph: &mut Graph) {
+///     let player = graph[player].cast_mut::<SpriteAnimationPlayer>().unwrap();
+///     player.play("Jump");
+///
+///     // Events are collected and should be handled by a game.
+///     while let Some(event) = player.pop_event() {
+///         println!("{} fired {}", event.clip, event.name);
+///     }
+/// }
+/// ```
+#[derive(Visit, Reflect, Clone, Debug)]
+pub struct SpriteAnimationPlayer {
+    base: Base,
+
+    #[reflect(setter = "set_sprite_sheet")]
+    sprite_sheet: InheritableVariable<Option<SpriteSheetResource>>,
+
+    #[reflect(setter = "set_target")]
+    target: InheritableVariable<Handle<Node>>,
+
+    #[reflect(setter = "set_clip")]
+    clip: InheritableVariable<String>,
+
+    #[reflect(setter = "set_playing")]
+    playing: InheritableVariable<bool>,
+
+    #[reflect(setter = "set_speed")]
+    speed: InheritableVariable<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    time: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    current_frame: Option<usize>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    events: VecDeque<SpriteAnimationEvent>,
+}
+
+impl Default for SpriteAnimationPlayer {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            sprite_sheet: Default::default(),
+            target: Default::default(),
+            clip: Default::default(),
+            playing: true.into(),
+            speed: 1.0.into(),
+            time: 0.0,
+            current_frame: None,
+            events: Default::default(),
+        }
+    }
+}
+
+impl TypeUuidProvider for SpriteAnimationPlayer {
+    fn type_uuid() -> Uuid {
+        uuid!("0a8b3b2e-5f0c-4f8a-9a3e-6a2b8d1f4c57")
+    }
+}
+
+impl Deref for SpriteAnimationPlayer {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for SpriteAnimationPlayer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+fn clip_frame(clip: &SpriteClip, time: f32) -> usize {
+    let frame = (time * clip.fps).max(0.0) as usize;
+    if clip.looping {
+        frame % clip.frames.len()
+    } else {
+        frame.min(clip.frames.len() - 1)
+    }
+}
+
+impl SpriteAnimationPlayer {
+    /// Sets new sprite sheet.
+    pub fn set_sprite_sheet(
+        &mut self,
+        sprite_sheet: Option<SpriteSheetResource>,
+    ) -> Option<SpriteSheetResource> {
+        self.sprite_sheet.set_value_and_mark_modified(sprite_sheet)
+    }
+
+    /// Returns current sprite sheet.
+    pub fn sprite_sheet(&self) -> Option<SpriteSheetResource> {
+        (*self.sprite_sheet).clone()
+    }
+
+    /// Sets new target node, it must be either [`Sprite`] or [`Rectangle`].
+    pub fn set_target(&mut self, target: Handle<Node>) -> Handle<Node> {
+        self.target.set_value_and_mark_modified(target)
+    }
+
+    /// Returns current target node.
+    pub fn target(&self) -> Handle<Node> {
+        *self.target
+    }
+
+    /// Sets the name of the current clip. Unlike [`Self::play`], it does not rewind the clip if it is already
+    /// the current one.
+    pub fn set_clip(&mut self, clip: String) -> String {
+        if *self.clip != clip {
+            self.time = 0.0;
+            self.current_frame = None;
+        }
+        self.clip.set_value_and_mark_modified(clip)
+    }
+
+    /// Returns the name of the current clip.
+    pub fn clip(&self) -> &str {
+        &self.clip
+    }
+
+    /// Starts playing the clip with the given name from the beginning.
+    pub fn play(&mut self, clip: &str) {
+        self.set_clip(clip.to_string());
+        self.time = 0.0;
+        self.current_frame = None;
+        self.set_playing(true);
+    }
+
+    /// Pauses or resumes the playback.
+    pub fn set_playing(&mut self, playing: bool) -> bool {
+        self.playing.set_value_and_mark_modified(playing)
+    }
+
+    /// Returns `true` if the player is playing a clip, `false` - otherwise.
+    pub fn is_playing(&self) -> bool {
+        *self.playing
+    }
+
+    /// Sets playback speed multiplier.
+    pub fn set_speed(&mut self, speed: f32) -> f32 {
+        self.speed.set_value_and_mark_modified(speed)
+    }
+
+    /// Returns playback speed multiplier.
+    pub fn speed(&self) -> f32 {
+        *self.speed
+    }
+
+    /// Returns current playback time of the current clip in seconds.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Returns index of the current frame in the current clip.
+    pub fn current_frame(&self) -> Option<usize> {
+        self.current_frame
+    }
+
+    /// Returns `true` if a non-looping clip has reached its last frame.
+    pub fn is_finished(&self) -> bool {
+        let Some(sheet) = self.sprite_sheet.as_ref() else {
+            return false;
+        };
+        let mut state = sheet.state();
+        let Some(sheet) = state.data() else {
+            return false;
+        };
+        sheet
+            .find_clip(&self.clip)
+            .is_some_and(|clip| !clip.looping && self.time >= clip.duration())
+    }
+
+    /// Takes the oldest event from the event queue.
+    pub fn pop_event(&mut self) -> Option<SpriteAnimationEvent> {
+        self.events.pop_front()
+    }
+
+    /// Advances the current clip by the given amount of time and returns texture coordinates of the current
+    /// frame (if any).
+    fn advance(&mut self, dt: f32) -> Option<Rect<f32>> {
+        let sheet = self.sprite_sheet.as_ref()?;
+        let mut state = sheet.state();
+        let sheet = state.data()?;
+        let clip = sheet.find_clip(&self.clip)?;
+        if clip.frames.is_empty() {
+            return None;
+        }
+
+        if *self.playing && self.current_frame.is_some() {
+            self.time += dt * *self.speed;
+            if !clip.looping {
+                self.time = self.time.min(clip.duration());
+            }
+        }
+
+        let frame = clip_frame(clip, self.time);
+        if self.current_frame != Some(frame) {
+            // Fire events of every frame passed since the last update, so fast clips won't skip events.
+            let mut index = self.current_frame.map_or(frame, |last| last + 1);
+            loop {
+                index %= clip.frames.len();
+                for event in clip.events.iter().filter(|e| e.frame as usize == index) {
+                    self.events.push_back(SpriteAnimationEvent {
+                        clip: clip.name.clone(),
+                        name: event.name.clone(),
+                    });
+                }
+                if index == frame {
+                    break;
+                }
+                index += 1;
+            }
+            self.current_frame = Some(frame);
+        }
+
+        sheet.frame_uv_rect(clip.frames[frame] as usize)
+    }
+}
+
+impl NodeTrait for SpriteAnimationPlayer {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        let Some(uv_rect) = self.advance(context.dt) else {
+            return;
+        };
+
+        if let Some(target) = context.nodes.try_borrow_mut(*self.target) {
+            if let Some(sprite) = target.cast_mut::<Sprite>() {
+                sprite.set_uv_rect(uv_rect);
+            } else if let Some(rectangle) = target.cast_mut::<Rectangle>() {
+                rectangle.set_uv_rect(uv_rect);
+            }
+        }
+    }
+}
+
+/// A builder for [`SpriteAnimationPlayer`] node.
+pub struct SpriteAnimationPlayerBuilder {
+    base_builder: BaseBuilder,
+    sprite_sheet: Option<SpriteSheetResource>,
+    target: Handle<Node>,
+    clip: String,
+    playing: bool,
+    speed: f32,
+}
+
+impl SpriteAnimationPlayerBuilder {
+    /// Creates new builder instance.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            sprite_sheet: None,
+            target: Handle::NONE,
+            clip: Default::default(),
+            playing: true,
+            speed: 1.0,
+        }
+    }
+
+    /// Sets desired sprite sheet.
+    pub fn with_sprite_sheet(mut self, sprite_sheet: Option<SpriteSheetResource>) -> Self {
+        self.sprite_sheet = sprite_sheet;
+        self
+    }
+
+    /// Sets desired target node.
+    pub fn with_target(mut self, target: Handle<Node>) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Sets desired clip.
+    pub fn with_clip(mut self, clip: String) -> Self {
+        self.clip = clip;
+        self
+    }
+
+    /// Sets whether the player should play the clip or not.
+    pub fn with_playing(mut self, playing: bool) -> Self {
+        self.playing = playing;
+        self
+    }
+
+    /// Sets desired playback speed.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Creates an instance of [`SpriteAnimationPlayer`] node.
+    pub fn build_node(self) -> Node {
+        Node::new(SpriteAnimationPlayer {
+            base: self.base_builder.build_base(),
+            sprite_sheet: self.sprite_sheet.into(),
+            target: self.target.into(),
+            clip: self.clip.into(),
+            playing: self.playing.into(),
+            speed: self.speed.into(),
+            time: 0.0,
+            current_frame: None,
+            events: Default::default(),
+        })
+    }
+
+    /// Creates an instance of [`SpriteAnimationPlayer`] node and adds it to the given scene graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::{untyped::ResourceKind, Resource},
+        core::{algebra::Vector2, math::Rect},
+        scene::{
+            animation::sprite::{
+                sheet::{slice_grid, SpriteClip, SpriteClipEvent, SpriteSheet},
+                SpriteAnimationEvent, SpriteAnimationPlayer, SpriteAnimationPlayerBuilder,
+            },
+            base::BaseBuilder,
+            graph::Graph,
+            sprite::{Sprite, SpriteBuilder},
+        },
+    };
+
+    #[test]
+    fn test_sprite_animation_player() {
+        let size = Vector2::new(64, 16);
+        let sheet = Resource::new_ok(
+            ResourceKind::Embedded,
+            SpriteSheet {
+                texture: None,
+                image_size: size,
+                frames: slice_grid(size, 4, 1),
+                clips: vec![SpriteClip {
+                    name: "Run".to_string(),
+                    frames: vec![1, 2, 3],
+                    fps: 10.0,
+                    looping: true,
+                    events: vec![SpriteClipEvent {
+                        frame: 2,
+                        name: "Step".to_string(),
+                    }],
+                }],
+            },
+        );
+
+        let mut graph = Graph::new();
+        let sprite = SpriteBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let player = SpriteAnimationPlayerBuilder::new(BaseBuilder::new())
+            .with_sprite_sheet(Some(sheet))
+            .with_target(sprite)
+            .with_clip("Run".to_string())
+            .build(&mut graph);
+
+        let uv = |graph: &Graph| graph[sprite].cast::<Sprite>().unwrap().uv_rect();
+
+        graph.update(Vector2::new(100.0, 100.0), 0.0, Default::default());
+        assert_eq!(uv(&graph), Rect::new(0.25, 0.0, 0.25, 1.0));
+
+        // Skip two frames at once, the event must not be lost.
+        graph.update(Vector2::new(100.0, 100.0), 0.25, Default::default());
+        assert_eq!(uv(&graph), Rect::new(0.75, 0.0, 0.25, 1.0));
+        let player = graph[player].cast_mut::<SpriteAnimationPlayer>().unwrap();
+        assert_eq!(
+            player.pop_event(),
+            Some(SpriteAnimationEvent {
+                clip: "Run".to_string(),
+                name: "Step".to_string()
+            })
+        );
+        assert_eq!(player.pop_event(), None);
+    }
+}
@@ -0,0 +1,360 @@
+//! Sprite sheet resource holds frames of a sprite sheet (an image with multiple sprites) and named animation clips
+//! that consist of these frames. See [`SpriteSheet`] docs for more info.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+        state::LoadError,
+        Resource, ResourceData,
+    },
+    core::{
+        algebra::Vector2, io::FileLoadError, math::Rect, reflect::prelude::*,
+        type_traits::prelude::*, visitor::prelude::*,
+    },
+    resource::texture::{Texture, TextureKind, TexturePixelKind, TextureResource},
+};
+use std::{
+    any::Any,
+    collections::VecDeque,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// An error that may occur during sprite sheet resource loading.
+#[derive(Debug)]
+pub enum SpriteSheetError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for SpriteSheetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            Self::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for SpriteSheetError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for SpriteSheetError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// A named event, that is fired when a clip reaches a specific frame.
+#[derive(Clone, Default, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "5c1e0f8a-2d7b-4c3e-9f6a-8b4d2e1c7a90")]
+pub struct SpriteClipEvent {
+    /// Index of a frame in the clip (not in the sprite sheet!).
+    pub frame: u32,
+    /// Name of the event.
+    pub name: String,
+}
+
+/// A named sequence of sprite sheet frames, played with a fixed frame rate.
+#[derive(Clone, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "e7a9b3c2-6f1d-4b8e-a5c4-3d2f1e0b9a87")]
+pub struct SpriteClip {
+    /// Name of the clip.
+    pub name: String,
+    /// Indices of sprite sheet frames.
+    pub frames: Vec<u32>,
+    /// Playback speed in frames per second.
+    #[reflect(min_value = 0.0)]
+    pub fps: f32,
+    /// Whether the clip should be restarted when it reaches the last frame or not.
+    pub looping: bool,
+    /// Events of the clip.
+    pub events: Vec<SpriteClipEvent>,
+}
+
+impl Default for SpriteClip {
+    fn default() -> Self {
+        Self {
+            name: "Clip".to_string(),
+            frames: Default::default(),
+            fps: 10.0,
+            looping: true,
+            events: Default::default(),
+        }
+    }
+}
+
+impl SpriteClip {
+    /// Returns duration of the clip in seconds.
+    pub fn duration(&self) -> f32 {
+        if self.fps > 0.0 {
+            self.frames.len() as f32 / self.fps
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Sprite sheet is an image with multiple sprites (frames) and a set of named animation clips, that consist of
+/// the frames. Frames are defined as rectangles in pixels, they could be created manually or using
+/// [`slice_grid`] and [`detect_frames`] functions. Sprite sheets are played by
+/// [`super::SpriteAnimationPlayer`] node.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::algebra::Vector2,
+/// #     scene::animation::sprite::sheet::{slice_grid, SpriteClip, SpriteSheet},
+/// # };
+/// fn make_sheet() -> SpriteSheet {
+///     let size = Vector2::new(256, 64);
+///     SpriteSheet {
+///         texture: None,
+///         image_size: size,
+///         // 4 frames in a single row.
+///         frames: slice_grid(size, 4, 1),
+///         clips: vec![SpriteClip {
+///             name: "Run".to_string(),
+///             frames: vec![0, 1, 2, 3],
+///             ..Default::default()
+///         }],
+///     }
+/// }
+/// ```
+#[derive(Clone, Default, Debug, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "b3dbd4b9-7c5c-4dd0-9e4b-b2a6d5b8a8c2")]
+pub struct SpriteSheet {
+    /// An image that contains every frame.
+    pub texture: Option<TextureResource>,
+    /// Size of the image in pixels. It is used to calculate texture coordinates of frames without accessing the
+    /// texture itself.
+    pub image_size: Vector2<u32>,
+    /// Frames of the sprite sheet in pixels.
+    pub frames: Vec<Rect<u32>>,
+    /// Animation clips.
+    pub clips: Vec<SpriteClip>,
+}
+
+impl SpriteSheet {
+    /// Load a sprite sheet resource from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, SpriteSheetError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut sheet = SpriteSheet::default();
+        sheet.visit("SpriteSheet", &mut visitor)?;
+        Ok(sheet)
+    }
+
+    /// Tries to find a clip with the given name.
+    pub fn find_clip(&self, name: &str) -> Option<&SpriteClip> {
+        self.clips.iter().find(|clip| clip.name == name)
+    }
+
+    /// Returns texture coordinates of a frame with the given index.
+    pub fn frame_uv_rect(&self, index: usize) -> Option<Rect<f32>> {
+        let frame = self.frames.get(index)?;
+        if self.image_size.x == 0 || self.image_size.y == 0 {
+            return None;
+        }
+        let width = self.image_size.x as f32;
+        let height = self.image_size.y as f32;
+        Some(Rect::new(
+            frame.position.x as f32 / width,
+            frame.position.y as f32 / height,
+            frame.size.x as f32 / width,
+            frame.size.y as f32 / height,
+        ))
+    }
+}
+
+impl ResourceData for SpriteSheet {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("SpriteSheet", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+/// Type alias for sprite sheet resources.
+pub type SpriteSheetResource = Resource<SpriteSheet>;
+
+/// Default implementation for sprite sheet loading.
+pub struct SpriteSheetLoader;
+
+impl ResourceLoader for SpriteSheetLoader {
+    fn extensions(&self) -> &[&str] {
+        &["spritesheet"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <SpriteSheet as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let sheet = SpriteSheet::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(sheet))
+        })
+    }
+}
+
+/// Slices an image of the given size into a grid of frames of equal size. Frames are ordered from left to right
+/// and from top to bottom.
+pub fn slice_grid(image_size: Vector2<u32>, columns: u32, rows: u32) -> Vec<Rect<u32>> {
+    if columns == 0 || rows == 0 {
+        return Vec::new();
+    }
+
+    let width = image_size.x / columns;
+    let height = image_size.y / rows;
+    let mut frames = Vec::with_capacity((columns * rows) as usize);
+    for row in 0..rows {
+        for column in 0..columns {
+            frames.push(Rect::new(column * width, row * height, width, height));
+        }
+    }
+    frames
+}
+
+/// Detects frames in an image by searching for connected groups of opaque pixels. `is_opaque` is a row-major
+/// mask of the image. Frames are ordered from top to bottom and from left to right.
+pub fn detect_frames(image_size: Vector2<u32>, is_opaque: &[bool]) -> Vec<Rect<u32>> {
+    let width = image_size.x as usize;
+    let height = image_size.y as usize;
+    assert_eq!(is_opaque.len(), width * height);
+
+    let mut visited = vec![false; is_opaque.len()];
+    let mut frames = Vec::new();
+    let mut queue = VecDeque::new();
+
+    for start in 0..is_opaque.len() {
+        if !is_opaque[start] || visited[start] {
+            continue;
+        }
+
+        let mut min = Vector2::new(usize::MAX, usize::MAX);
+        let mut max = Vector2::new(0, 0);
+
+        visited[start] = true;
+        queue.push_back(start);
+        while let Some(index) = queue.pop_front() {
+            let x = index % width;
+            let y = index / width;
+            min = Vector2::new(min.x.min(x), min.y.min(y));
+            max = Vector2::new(max.x.max(x), max.y.max(y));
+
+            // Diagonal neighbours are connected too.
+            for ny in y.saturating_sub(1)..(y + 2).min(height) {
+                for nx in x.saturating_sub(1)..(x + 2).min(width) {
+                    let neighbour = ny * width + nx;
+                    if is_opaque[neighbour] && !visited[neighbour] {
+                        visited[neighbour] = true;
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+        }
+
+        frames.push(Rect::new(
+            min.x as u32,
+            min.y as u32,
+            (max.x - min.x + 1) as u32,
+            (max.y - min.y + 1) as u32,
+        ));
+    }
+
+    frames.sort_by_key(|frame| (frame.position.y, frame.position.x));
+    frames
+}
+
+/// Detects frames in the given texture using its alpha channel, see [`detect_frames`] for more info. Pixels
+/// with alpha bigger than `alpha_threshold` are considered opaque. Returns `None` if the texture is not a
+/// rectangular RGBA8 texture.
+pub fn detect_texture_frames(texture: &Texture, alpha_threshold: u8) -> Option<Vec<Rect<u32>>> {
+    let TextureKind::Rectangle { width, height } = texture.kind() else {
+        return None;
+    };
+    if texture.pixel_kind() != TexturePixelKind::RGBA8 {
+        return None;
+    }
+
+    let pixel_count = (width * height) as usize;
+    let mask = texture
+        .mip_level_data(0)
+        .chunks_exact(4)
+        .take(pixel_count)
+        .map(|pixel| pixel[3] > alpha_threshold)
+        .collect::<Vec<_>>();
+    if mask.len() != pixel_count {
+        return None;
+    }
+
+    Some(detect_frames(Vector2::new(width, height), &mask))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{detect_frames, slice_grid};
+    use crate::core::{algebra::Vector2, math::Rect};
+
+    #[test]
+    fn test_slicing() {
+        let frames = slice_grid(Vector2::new(64, 32), 2, 2);
+        assert_eq!(
+            frames,
+            vec![
+                Rect::new(0, 0, 32, 16),
+                Rect::new(32, 0, 32, 16),
+                Rect::new(0, 16, 32, 16),
+                Rect::new(32, 16, 32, 16),
+            ]
+        );
+
+        #[rustfmt::skip]
+        let mask = [
+            0, 0, 0, 0, 0, 0,
+            0, 1, 0, 0, 1, 1,
+            0, 0, 1, 0, 0, 1,
+            0, 0, 0, 0, 0, 0,
+        ]
+        .map(|v| v == 1);
+        assert_eq!(
+            detect_frames(Vector2::new(6, 4), &mask),
+            vec![Rect::new(1, 1, 2, 2), Rect::new(4, 1, 2, 2)]
+        );
+    }
+}
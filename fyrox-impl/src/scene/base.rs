@@ -17,7 +17,7 @@ use crate::{
     engine::SerializationContext,
     graph::BaseSceneGraph,
     resource::model::ModelResource,
-    scene::{node::Node, transform::Transform},
+    scene::{node::Node, replication::ReplicationSettings, transform::Transform},
     script::{Script, ScriptTrait},
 };
 use serde::{Deserialize, Serialize};
@@ -29,23 +29,29 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 
-/// Level of detail is a collection of objects for given normalized distance range.
-/// Objects will be rendered **only** if they're in specified range.
-/// Normalized distance is a distance in (0; 1) range where 0 - closest to camera,
-/// 1 - farthest. Real distance can be obtained by multiplying normalized distance
-/// with z_far of current projection matrix.
+/// Total amount of layers, that could be assigned to scene nodes. See [`Base::set_layer`] for more
+/// info.
+pub const LAYER_COUNT: u8 = 32;
+
+/// Level of detail is a collection of objects for given normalized range of a metric of its
+/// LOD group (see [`LodMetric`]). Objects will be rendered **only** if they're in specified range.
+/// With [`LodMetric::Distance`], normalized distance is a distance in (0; 1) range where 0 -
+/// closest to camera, 1 - farthest. Real distance can be obtained by multiplying normalized
+/// distance with z_far of current projection matrix. With [`LodMetric::ScreenSize`], the range
+/// is a fraction of the screen height that is occupied by the object, where 0 - invisibly small,
+/// 1 - fills the entire screen.
 #[derive(Debug, Default, Clone, Visit, Reflect, PartialEq, TypeUuidProvider)]
 #[type_uuid(id = "576b31a2-2b39-4c79-95dd-26aeaf381d8b")]
 pub struct LevelOfDetail {
     #[reflect(
         description = "Beginning of the range in which the level will be visible. \
-    It is expressed in normalized coordinates: where 0.0 - closest to camera, 1.0 - \
-    farthest from camera."
+    It is expressed in normalized coordinates: where 0.0 - closest to camera (or the smallest \
+    screen size), 1.0 - farthest from camera (or the entire screen)."
     )]
     begin: f32,
     #[reflect(description = "End of the range in which the level will be visible. \
-    It is expressed in normalized coordinates: where 0.0 - closest to camera, 1.0 - \
-    farthest from camera.")]
+    It is expressed in normalized coordinates: where 0.0 - closest to camera (or the smallest \
+    screen size), 1.0 - farthest from camera (or the entire screen).")]
     end: f32,
     /// List of objects, where each object represents level of detail of parent's
     /// LOD group.
@@ -112,6 +118,90 @@ impl LevelOfDetail {
 pub struct LodGroup {
     /// Set of cascades.
     pub levels: Vec<LevelOfDetail>,
+    /// A metric, that is used to select a level. See [`LodMetric`] docs for more info.
+    #[visit(optional)]
+    #[reflect(description = "A metric, that is used to select a level of detail.")]
+    pub metric: LodMetric,
+}
+
+/// Defines how the ranges of the levels of a [`LodGroup`] are interpreted.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "0d3c6b4e-6a0b-4c2e-9d4f-5f1b3e8a7c21")]
+pub enum LodMetric {
+    /// Normalized distance from the camera to an object, where 0.0 - the camera position and
+    /// 1.0 - the far clipping plane.
+    #[default]
+    Distance,
+    /// A fraction of the screen height, that is occupied by the bounding sphere of an object.
+    /// 0.0 - invisibly small object, 1.0 - the object fills the entire screen. Unlike the distance,
+    /// this metric takes the size of objects and the field of view of the camera into account, so
+    /// the same thresholds work for objects of any size.
+    ScreenSize,
+}
+
+impl LodGroup {
+    /// Calculates a fraction of the screen height (in 0.0..1.0 range), that is occupied by the
+    /// bounding sphere of the given bounding box, when it is viewed from the given position using
+    /// the given projection matrix.
+    pub fn screen_size(
+        projection_matrix: &Matrix4<f32>,
+        observer_position: &Vector3<f32>,
+        bounds: &AxisAlignedBoundingBox,
+    ) -> f32 {
+        let radius = bounds.half_extents().norm();
+        let scale = projection_matrix[(1, 1)].abs();
+        let size = if projection_matrix[(3, 3)] == 0.0 {
+            // Perspective projection - the size decreases with the distance.
+            let distance = observer_position.metric_distance(&bounds.center());
+            if distance <= radius {
+                return 1.0;
+            }
+            radius * scale / distance
+        } else {
+            // Orthographic projection - the size does not depend on the distance.
+            radius * scale
+        };
+        size.clamp(0.0, 1.0)
+    }
+
+    /// Calculates normalized value of the metric of the group for the given object.
+    pub fn normalized_metric(
+        &self,
+        object: &Node,
+        observer_position: &Vector3<f32>,
+        projection_matrix: &Matrix4<f32>,
+        z_near: f32,
+        z_far: f32,
+    ) -> f32 {
+        match self.metric {
+            LodMetric::Distance => {
+                let distance = observer_position.metric_distance(&object.global_position());
+                (distance - z_near) / (z_far - z_near)
+            }
+            LodMetric::ScreenSize => {
+                let bounds = object.world_bounding_box();
+                if bounds.is_invalid_or_degenerate() {
+                    // Objects without volume (such as pivots) are treated as infinitely small.
+                    0.0
+                } else {
+                    Self::screen_size(projection_matrix, observer_position, &bounds)
+                }
+            }
+        }
+    }
 }
 
 /// Mobility defines a group for scene node which has direct impact on performance
@@ -376,12 +466,31 @@ pub struct Base {
     #[reflect(setter = "set_lod_group")]
     lod_group: InheritableVariable<Option<LodGroup>>,
 
+    // Non-serializable, used by the editor to preview a specific level of detail.
+    #[reflect(hidden)]
+    forced_lod_level: Option<usize>,
+
     #[reflect(setter = "set_mobility")]
     mobility: InheritableVariable<Mobility>,
 
     #[reflect(setter = "set_tag")]
     tag: InheritableVariable<String>,
 
+    #[reflect(
+        setter = "set_tags",
+        description = "A set of additional tags, that could be used to find and group scene nodes."
+    )]
+    tags: InheritableVariable<Vec<String>>,
+
+    #[reflect(
+        setter = "set_layer",
+        description = "Numbered layer of the node. Layers could be hidden at once, see `Graph::set_layer_visibility`.",
+        min_value = 0.0,
+        max_value = 31.0,
+        step = 1.0
+    )]
+    layer: InheritableVariable<u8>,
+
     #[reflect(setter = "set_cast_shadows")]
     cast_shadows: InheritableVariable<bool>,
 
@@ -394,6 +503,12 @@ pub struct Base {
     #[reflect(setter = "set_frustum_culling")]
     frustum_culling: InheritableVariable<bool>,
 
+    #[reflect(setter = "set_replication")]
+    replication: InheritableVariable<ReplicationSettings>,
+
+    #[reflect(setter = "set_persistent")]
+    persistent: InheritableVariable<bool>,
+
     #[reflect(hidden)]
     pub(crate) transform_modified: Cell<bool>,
 
@@ -709,6 +824,21 @@ impl Base {
         self.lod_group.get_value_mut_and_mark_modified().as_mut()
     }
 
+    /// Forces the lod group of the node to show only the level with the given index, regardless
+    /// of the metric of the group. `None` restores automatic level selection. This setting is not
+    /// serialized, its main use is to preview levels of detail.
+    #[inline]
+    pub fn force_lod_level(&mut self, level: Option<usize>) {
+        self.forced_lod_level = level;
+    }
+
+    /// Returns the index of the forced level of detail, if any. See [`Self::force_lod_level`]
+    /// for more info.
+    #[inline]
+    pub fn forced_lod_level(&self) -> Option<usize> {
+        self.forced_lod_level
+    }
+
     /// Returns node tag.
     #[inline]
     pub fn tag(&self) -> &str {
@@ -727,6 +857,63 @@ impl Base {
         self.tag.set_value_and_mark_modified(tag)
     }
 
+    /// Returns additional tags of the node. See also [`Self::tag`].
+    #[inline]
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Sets new additional tags of the node, returns old tags.
+    #[inline]
+    pub fn set_tags(&mut self, tags: Vec<String>) -> Vec<String> {
+        self.tags.set_value_and_mark_modified(tags)
+    }
+
+    /// Adds an additional tag to the node, if it does not have it already.
+    #[inline]
+    pub fn add_tag<S: AsRef<str>>(&mut self, tag: S) {
+        let tag = tag.as_ref();
+        if !self.tags.iter().any(|t| t == tag) {
+            self.tags
+                .get_value_mut_and_mark_modified()
+                .push(tag.to_owned());
+        }
+    }
+
+    /// Removes an additional tag from the node. Returns `true` if the tag was removed.
+    #[inline]
+    pub fn remove_tag<S: AsRef<str>>(&mut self, tag: S) -> bool {
+        let tag = tag.as_ref();
+        if let Some(position) = self.tags.iter().position(|t| t == tag) {
+            self.tags.get_value_mut_and_mark_modified().remove(position);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns `true` if the node has the given tag, either as its main tag or as one of its
+    /// additional tags.
+    #[inline]
+    pub fn has_tag<S: AsRef<str>>(&self, tag: S) -> bool {
+        let tag = tag.as_ref();
+        *self.tag == tag || self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Returns layer of the node.
+    #[inline]
+    pub fn layer(&self) -> u8 {
+        *self.layer
+    }
+
+    /// Sets new layer of the node, returns old layer. The value is clamped to `[0; LAYER_COUNT)`
+    /// range.
+    #[inline]
+    pub fn set_layer(&mut self, layer: u8) -> u8 {
+        self.layer
+            .set_value_and_mark_modified(layer.min(LAYER_COUNT - 1))
+    }
+
     /// Return the frustum_culling flag
     #[inline]
     pub fn frustum_culling(&self) -> bool {
@@ -740,6 +927,31 @@ impl Base {
             .set_value_and_mark_modified(frustum_culling)
     }
 
+    /// Returns replication settings of the node.
+    #[inline]
+    pub fn replication(&self) -> &ReplicationSettings {
+        &self.replication
+    }
+
+    /// Sets new replication settings of the node. See [`crate::scene::replication`] module docs for more info.
+    #[inline]
+    pub fn set_replication(&mut self, replication: ReplicationSettings) -> ReplicationSettings {
+        self.replication.set_value_and_mark_modified(replication)
+    }
+
+    /// Returns `true` if the state of the node is stored in savegames, `false` - otherwise.
+    #[inline]
+    pub fn is_persistent(&self) -> bool {
+        *self.persistent
+    }
+
+    /// Sets whether the state of the node (including its scripts) should be stored in savegames or not. See
+    /// [`crate::scene::savegame`] module docs for more info.
+    #[inline]
+    pub fn set_persistent(&mut self, persistent: bool) -> bool {
+        self.persistent.set_value_and_mark_modified(persistent)
+    }
+
     /// Returns true if the node should cast shadows, false - otherwise.
     #[inline]
     pub fn cast_shadows(&self) -> bool {
@@ -1096,6 +1308,10 @@ impl Visit for Base {
         let _ = self.cast_shadows.visit("CastShadows", &mut region);
         let _ = self.instance_id.visit("InstanceId", &mut region);
         let _ = self.enabled.visit("Enabled", &mut region);
+        let _ = self.replication.visit("Replication", &mut region);
+        let _ = self.persistent.visit("Persistent", &mut region);
+        let _ = self.tags.visit("Tags", &mut region);
+        let _ = self.layer.visit("Layer", &mut region);
 
         // Script visiting may fail for various reasons:
         //
@@ -1133,11 +1349,15 @@ pub struct BaseBuilder {
     mobility: Mobility,
     inv_bind_pose_transform: Matrix4<f32>,
     tag: String,
+    tags: Vec<String>,
+    layer: u8,
     frustum_culling: bool,
     cast_shadows: bool,
     scripts: Vec<ScriptRecord>,
     instance_id: SceneNodeId,
     enabled: bool,
+    replication: ReplicationSettings,
+    persistent: bool,
 }
 
 impl Default for BaseBuilder {
@@ -1161,11 +1381,15 @@ impl BaseBuilder {
             mobility: Default::default(),
             inv_bind_pose_transform: Matrix4::identity(),
             tag: Default::default(),
+            tags: Default::default(),
+            layer: 0,
             frustum_culling: true,
             cast_shadows: true,
             scripts: vec![],
             instance_id: SceneNodeId(Uuid::new_v4()),
             enabled: true,
+            replication: Default::default(),
+            persistent: false,
         }
     }
 
@@ -1252,6 +1476,20 @@ impl BaseBuilder {
         self
     }
 
+    /// Sets desired additional tags.
+    #[inline]
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Sets desired layer. The value is clamped to `[0; LAYER_COUNT)` range.
+    #[inline]
+    pub fn with_layer(mut self, layer: u8) -> Self {
+        self.layer = layer.min(LAYER_COUNT - 1);
+        self
+    }
+
     /// Sets desired frustum_culling flag.
     #[inline]
     pub fn with_frustum_culling(mut self, frustum_culling: bool) -> Self {
@@ -1282,6 +1520,20 @@ impl BaseBuilder {
         self
     }
 
+    /// Sets desired replication settings.
+    #[inline]
+    pub fn with_replication(mut self, replication: ReplicationSettings) -> Self {
+        self.replication = replication;
+        self
+    }
+
+    /// Sets whether the state of the node should be stored in savegames or not.
+    #[inline]
+    pub fn with_persistent(mut self, persistent: bool) -> Self {
+        self.persistent = persistent;
+        self
+    }
+
     /// Creates an instance of [`Base`].
     #[inline]
     pub fn build_base(self) -> Base {
@@ -1302,16 +1554,55 @@ impl BaseBuilder {
             is_resource_instance_root: false,
             depth_offset: self.depth_offset.into(),
             lod_group: self.lod_group.into(),
+            forced_lod_level: None,
             mobility: self.mobility.into(),
             tag: self.tag.into(),
+            tags: self.tags.into(),
+            layer: self.layer.into(),
             properties: Default::default(),
             transform_modified: Cell::new(false),
             frustum_culling: self.frustum_culling.into(),
             cast_shadows: self.cast_shadows.into(),
             scripts: self.scripts,
-            instance_id: SceneNodeId(Uuid::new_v4()),
+            instance_id: self.instance_id,
             enabled: self.enabled.into(),
             global_enabled: Cell::new(true),
+            replication: self.replication.into(),
+            persistent: self.persistent.into(),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Matrix4, Vector3},
+            math::aabb::AxisAlignedBoundingBox,
+        },
+        scene::base::LodGroup,
+    };
+
+    #[test]
+    fn test_lod_screen_size() {
+        let projection = Matrix4::new_perspective(1.0, 90.0f32.to_radians(), 0.1, 100.0);
+        let bounds = AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(-1.0, -1.0, -1.0),
+            Vector3::new(1.0, 1.0, 1.0),
+        );
+        let near = LodGroup::screen_size(&projection, &Vector3::new(0.0, 0.0, 5.0), &bounds);
+        let far = LodGroup::screen_size(&projection, &Vector3::new(0.0, 0.0, 50.0), &bounds);
+        assert!(near > far && far > 0.0);
+        // Observer inside the bounding sphere.
+        assert_eq!(
+            LodGroup::screen_size(&projection, &Vector3::zeros(), &bounds),
+            1.0
+        );
+
+        let ortho = Matrix4::new_orthographic(-10.0, 10.0, -10.0, 10.0, 0.0, 100.0);
+        assert_eq!(
+            LodGroup::screen_size(&ortho, &Vector3::new(0.0, 0.0, 5.0), &bounds),
+            LodGroup::screen_size(&ortho, &Vector3::new(0.0, 0.0, 50.0), &bounds)
+        );
+    }
+}
@@ -17,10 +17,11 @@ use crate::{
         TypeUuidProvider,
     },
     resource::texture::{
+        ibl::{IblError, IblMaps},
         TextureKind, TexturePixelKind, TextureResource, TextureResourceExtension, TextureWrapMode,
     },
     scene::{
-        base::{Base, BaseBuilder},
+        base::{Base, BaseBuilder, LAYER_COUNT},
         debug::SceneDrawingContext,
         graph::Graph,
         node::{Node, NodeTrait, UpdateContext},
@@ -286,6 +287,53 @@ impl Default for Exposure {
     }
 }
 
+/// Defines what a camera clears before rendering. It is mostly used for camera stacking, when
+/// multiple cameras render into the same target one after another. See [`Camera`] docs for more
+/// info.
+#[derive(
+    Visit,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    Default,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum CameraClearFlags {
+    /// Color is cleared with the clear color of the scene and the skybox of the camera (if any) is
+    /// drawn on top of it. This is default option.
+    #[default]
+    Skybox,
+    /// Color is cleared with the clear color of the scene, the skybox is not drawn.
+    Color,
+    /// Color is preserved, only depth is cleared. The camera renders on top of the image of the
+    /// previous cameras and its image is composited with them before post-processing. It could be
+    /// used to render overlays, such as first-person weapon models with their own field of view
+    /// that never intersect the level geometry.
+    DepthOnly,
+}
+
+uuid_provider!(CameraClearFlags = "b3f1c5a2-7d4e-4c8b-9a6f-2e1d0c9b8a74");
+
+impl CameraClearFlags {
+    /// Returns `true` if the camera clears the color of its render target.
+    #[inline]
+    pub fn clears_color(self) -> bool {
+        self != Self::DepthOnly
+    }
+}
+
+/// Returns `true` if the given layer bit (`1 << layer`) is set in the mask.
+#[inline]
+pub fn is_layer_in_mask(mask: u32, layer: u8) -> bool {
+    layer < LAYER_COUNT && mask & (1 << layer) != 0
+}
+
 /// Camera allows you to see world from specific point in world. You must have at least one camera in
 /// your scene to see anything.
 ///
@@ -304,6 +352,56 @@ impl Default for Exposure {
 ///
 /// Fyrox supports multiple cameras per scene, it means that you can create split screen games, make
 /// picture-in-picture insertions in your main camera view and any other combinations you need.
+/// Cameras are rendered in ascending order of their render order (see [`Camera::set_render_order`]),
+/// cameras with the same render order are rendered from the largest viewport to the smallest one.
+///
+/// ## Camera stacking
+///
+/// Multiple cameras could render into the same image one after another. Every camera renders only
+/// the nodes, that are on the layers from its layer mask (see [`Camera::set_layer_mask`] and
+/// [`crate::scene::base::Base::set_layer`]). A camera with [`CameraClearFlags::DepthOnly`] clear
+/// flags keeps the image of the previous cameras and draws its own objects on top of it. Such
+/// cameras form a stack with the previous camera, the whole stack is post-processed (bloom, tone
+/// mapping, color grading, anti-aliasing) at once using the settings of the first camera in the
+/// stack. A typical example is a first-person weapon:
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::pool::Handle,
+/// #     scene::{
+/// #         base::BaseBuilder,
+/// #         camera::{CameraBuilder, CameraClearFlags, PerspectiveProjection, Projection, SkyBoxKind},
+/// #         graph::Graph,
+/// #         node::Node,
+/// #     },
+/// # };
+/// const WEAPON_LAYER: u8 = 1;
+///
+/// fn create_cameras(graph: &mut Graph) -> (Handle<Node>, Handle<Node>) {
+///     // The main camera renders everything except the weapon.
+///     let main = CameraBuilder::new(BaseBuilder::new())
+///         .with_layer_mask(!(1 << WEAPON_LAYER))
+///         .build(graph);
+///
+///     // The weapon camera renders only the weapon with its own field of view on top of the
+///     // main camera.
+///     let weapon = CameraBuilder::new(BaseBuilder::new())
+///         .with_projection(Projection::Perspective(PerspectiveProjection {
+///             fov: 55.0f32.to_radians(),
+///             z_near: 0.01,
+///             z_far: 10.0,
+///         }))
+///         .with_specific_skybox(SkyBoxKind::None)
+///         .with_layer_mask(1 << WEAPON_LAYER)
+///         .with_clear_flags(CameraClearFlags::DepthOnly)
+///         .with_render_order(1)
+///         .build(graph);
+///
+///     (main, weapon)
+/// }
+/// ```
+///
+/// Layer masks do not affect lights and shadows - every camera is lit by all the lights of the scene.
 ///
 /// ## Performance
 ///
@@ -337,6 +435,18 @@ pub struct Camera {
     #[reflect(setter = "set_color_grading_enabled")]
     color_grading_enabled: InheritableVariable<bool>,
 
+    #[visit(optional)]
+    #[reflect(setter = "set_layer_mask")]
+    layer_mask: InheritableVariable<u32>,
+
+    #[visit(optional)]
+    #[reflect(setter = "set_clear_flags")]
+    clear_flags: InheritableVariable<CameraClearFlags>,
+
+    #[visit(optional)]
+    #[reflect(setter = "set_render_order")]
+    render_order: InheritableVariable<i32>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     view_matrix: Matrix4<f32>,
@@ -414,6 +524,9 @@ impl Camera {
     /// Why not just use pixels directly? Because you can change resolution while
     /// your application is running and you'd be force to manually recalculate
     /// pixel values everytime when resolution changes.
+    ///
+    /// Cameras with larger viewports are rendered first, so a camera with a smaller viewport is
+    /// always drawn on top of a camera with a larger one (picture-in-picture, for example).
     pub fn set_viewport(&mut self, mut viewport: Rect<f32>) -> Rect<f32> {
         viewport.position.x = viewport.position.x.clamp(0.0, 1.0);
         viewport.position.y = viewport.position.y.clamp(0.0, 1.0);
@@ -689,6 +802,45 @@ impl Camera {
     pub fn exposure(&self) -> Exposure {
         *self.exposure
     }
+
+    /// Sets new layer mask of the camera, returns old mask. The camera renders only the nodes, whose
+    /// layer bit (`1 << layer`) is set in the mask. Default mask is `u32::MAX` - all layers are
+    /// rendered.
+    pub fn set_layer_mask(&mut self, mask: u32) -> u32 {
+        self.layer_mask.set_value_and_mark_modified(mask)
+    }
+
+    /// Returns current layer mask of the camera.
+    pub fn layer_mask(&self) -> u32 {
+        *self.layer_mask
+    }
+
+    /// Returns `true` if the camera renders the nodes on the given layer.
+    pub fn renders_layer(&self, layer: u8) -> bool {
+        is_layer_in_mask(*self.layer_mask, layer)
+    }
+
+    /// Sets new clear flags of the camera, returns old flags. See [`CameraClearFlags`] docs for more
+    /// info.
+    pub fn set_clear_flags(&mut self, clear_flags: CameraClearFlags) -> CameraClearFlags {
+        self.clear_flags.set_value_and_mark_modified(clear_flags)
+    }
+
+    /// Returns current clear flags of the camera.
+    pub fn clear_flags(&self) -> CameraClearFlags {
+        *self.clear_flags
+    }
+
+    /// Sets new render order of the camera, returns old value. Cameras with lower values are
+    /// rendered first. Default value is zero.
+    pub fn set_render_order(&mut self, render_order: i32) -> i32 {
+        self.render_order.set_value_and_mark_modified(render_order)
+    }
+
+    /// Returns current render order of the camera.
+    pub fn render_order(&self) -> i32 {
+        *self.render_order
+    }
 }
 
 impl NodeTrait for Camera {
@@ -1016,6 +1168,9 @@ pub struct CameraBuilder {
     color_grading_lut: Option<ColorGradingLut>,
     color_grading_enabled: bool,
     projection: Projection,
+    layer_mask: u32,
+    clear_flags: CameraClearFlags,
+    render_order: i32,
 }
 
 impl CameraBuilder {
@@ -1034,6 +1189,9 @@ impl CameraBuilder {
             color_grading_lut: None,
             color_grading_enabled: false,
             projection: Projection::default(),
+            layer_mask: u32::MAX,
+            clear_flags: Default::default(),
+            render_order: 0,
         }
     }
 
@@ -1109,6 +1267,24 @@ impl CameraBuilder {
         self
     }
 
+    /// Sets desired layer mask. See [`Camera::set_layer_mask`] for more info.
+    pub fn with_layer_mask(mut self, layer_mask: u32) -> Self {
+        self.layer_mask = layer_mask;
+        self
+    }
+
+    /// Sets desired clear flags. See [`CameraClearFlags`] for more info.
+    pub fn with_clear_flags(mut self, clear_flags: CameraClearFlags) -> Self {
+        self.clear_flags = clear_flags;
+        self
+    }
+
+    /// Sets desired render order. See [`Camera::set_render_order`] for more info.
+    pub fn with_render_order(mut self, render_order: i32) -> Self {
+        self.render_order = render_order;
+        self
+    }
+
     /// Creates new instance of camera.
     pub fn build_camera(self) -> Camera {
         Camera {
@@ -1129,6 +1305,9 @@ impl CameraBuilder {
             exposure: self.exposure.into(),
             color_grading_lut: self.color_grading_lut.into(),
             color_grading_enabled: self.color_grading_enabled.into(),
+            layer_mask: self.layer_mask.into(),
+            clear_flags: self.clear_flags.into(),
+            render_order: self.render_order.into(),
         }
     }
 
@@ -1205,7 +1384,7 @@ impl SkyBoxBuilder {
             bottom: self.bottom,
             front: self.front,
             back: self.back,
-            cubemap: None,
+            ..Default::default()
         };
 
         skybox.create_cubemap()?;
@@ -1219,7 +1398,16 @@ impl SkyBoxBuilder {
 /// skies and/or some other objects (mountains, buildings, etc.). Usually skyboxes used
 /// in outdoor scenes, however real use of it limited only by your imagination. Skybox
 /// will be drawn first, none of objects could be drawn before skybox.
-#[derive(Debug, Clone, Default, PartialEq, Reflect, Visit, Eq)]
+///
+/// ## High dynamic range images
+///
+/// Instead of six face textures, a skybox could be made of a single equirectangular panorama (usually
+/// a high dynamic range image in .hdr or .exr format), see [`SkyBox::from_hdri`]. In this case the
+/// panorama is converted to a cube map and additional irradiance and prefiltered specular maps are
+/// generated from it (see [`crate::resource::texture::ibl`] module docs). These maps are used by the
+/// renderer to light the scene (image-based lighting), adding environment lighting to the ambient
+/// term of the lighting.
+#[derive(Debug, Clone, PartialEq, Reflect, Visit)]
 pub struct SkyBox {
     /// Texture for front face.
     #[reflect(setter = "set_front")]
@@ -1245,14 +1433,62 @@ pub struct SkyBox {
     #[reflect(setter = "set_bottom")]
     pub(crate) bottom: Option<TextureResource>,
 
+    /// Equirectangular panorama (usually a high dynamic range image), that will be used instead of
+    /// face textures if set.
+    #[reflect(setter = "set_hdri")]
+    #[visit(optional)]
+    pub(crate) hdri: Option<TextureResource>,
+
+    /// Brightness multiplier of the skybox and the environment lighting produced by it.
+    #[reflect(min_value = 0.0, step = 0.1, setter = "set_exposure")]
+    #[visit(optional)]
+    pub(crate) exposure: f32,
+
+    /// Rotation of the skybox around vertical axis (in radians).
+    #[reflect(setter = "set_rotation")]
+    #[visit(optional)]
+    pub(crate) rotation: f32,
+
     /// Cubemap texture
     #[reflect(hidden)]
     #[visit(skip)]
     pub(crate) cubemap: Option<TextureResource>,
+
+    /// Irradiance cube map, that is generated from the panorama.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub(crate) irradiance_map: Option<TextureResource>,
+
+    /// Prefiltered specular cube map, that is generated from the panorama.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub(crate) specular_map: Option<TextureResource>,
 }
 
 uuid_provider!(SkyBox = "45f359f1-e26f-4ace-81df-097f63474c72");
 
+impl Default for SkyBox {
+    fn default() -> Self {
+        Self {
+            front: None,
+            back: None,
+            left: None,
+            right: None,
+            top: None,
+            bottom: None,
+            hdri: None,
+            exposure: 1.0,
+            rotation: 0.0,
+            cubemap: None,
+            irradiance_map: None,
+            specular_map: None,
+        }
+    }
+}
+
+/// Max size of a face of the cube map, that is generated from a panorama.
+pub const MAX_HDRI_FACE_SIZE: u32 = 512;
+
 /// An error that may occur during skybox creation.
 #[derive(Debug)]
 pub enum SkyBoxError {
@@ -1291,6 +1527,10 @@ pub enum SkyBoxError {
         /// Index of the faulty input texture.
         index: usize,
     },
+    /// Occurs when the panorama is either still loading or failed to load.
+    HdriIsNotReady,
+    /// Panorama cannot be converted to a cube map.
+    Ibl(IblError),
 }
 
 impl SkyBox {
@@ -1304,6 +1544,92 @@ impl SkyBox {
         self.cubemap.as_ref()
     }
 
+    /// Creates a new skybox from the given equirectangular panorama. The texture must be loaded. See
+    /// [`SkyBox`] docs for more info.
+    pub fn from_hdri(hdri: TextureResource) -> Result<Self, SkyBoxError> {
+        let mut skybox = SkyBox {
+            hdri: Some(hdri),
+            ..Default::default()
+        };
+        skybox.create_cubemap()?;
+        Ok(skybox)
+    }
+
+    /// Sets new panorama, that will be used instead of face textures. Cube maps will be regenerated
+    /// immediately, so the texture must be loaded.
+    pub fn set_hdri(&mut self, hdri: Option<TextureResource>) -> Option<TextureResource> {
+        let prev = std::mem::replace(&mut self.hdri, hdri);
+        Log::verify(self.create_cubemap());
+        prev
+    }
+
+    /// Returns current panorama (if any).
+    pub fn hdri(&self) -> Option<TextureResource> {
+        self.hdri.clone()
+    }
+
+    /// Sets new exposure of the skybox. It scales brightness of both the skybox and the environment
+    /// lighting.
+    pub fn set_exposure(&mut self, exposure: f32) -> f32 {
+        std::mem::replace(&mut self.exposure, exposure.max(0.0))
+    }
+
+    /// Returns current exposure of the skybox.
+    pub fn exposure(&self) -> f32 {
+        self.exposure
+    }
+
+    /// Sets new rotation of the skybox around vertical axis (in radians).
+    pub fn set_rotation(&mut self, rotation: f32) -> f32 {
+        std::mem::replace(&mut self.rotation, rotation)
+    }
+
+    /// Returns current rotation of the skybox around vertical axis (in radians).
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    /// Returns irradiance cube map, that is used for diffuse image-based lighting. It exists only
+    /// if the skybox was made of a panorama.
+    pub fn irradiance_map_ref(&self) -> Option<&TextureResource> {
+        self.irradiance_map.as_ref()
+    }
+
+    /// Returns prefiltered specular cube map, that is used for specular image-based lighting. It
+    /// exists only if the skybox was made of a panorama.
+    pub fn specular_map_ref(&self) -> Option<&TextureResource> {
+        self.specular_map.as_ref()
+    }
+
+    fn create_cubemap_from_hdri(&mut self, hdri: &TextureResource) -> Result<(), SkyBoxError> {
+        let mut state = hdri.state();
+        let Some(texture) = state.data() else {
+            return Err(SkyBoxError::HdriIsNotReady);
+        };
+
+        let face_size = match texture.kind() {
+            TextureKind::Rectangle { width, .. } => {
+                let size = (width / 4).clamp(1, MAX_HDRI_FACE_SIZE);
+                1 << size.ilog2()
+            }
+            kind => return Err(SkyBoxError::UnsupportedTextureKind(kind)),
+        };
+
+        let maps = IblMaps::from_equirectangular(texture, face_size).map_err(SkyBoxError::Ibl)?;
+
+        self.cubemap = Some(maps.environment);
+        self.irradiance_map = Some(maps.irradiance);
+        self.specular_map = Some(maps.specular);
+
+        Ok(())
+    }
+
+    /// Returns true if the cube map of the skybox is stored in linear color space (which is the case
+    /// for skyboxes, that were made of a panorama).
+    pub fn is_linear(&self) -> bool {
+        self.hdri.is_some() && self.cubemap.is_some()
+    }
+
     /// Validates input set of texture and checks if it possible to create a cube map from them.
     /// There are two main conditions for successful cube map creation:
     /// - All textures must have same width and height, and width must be equal to height.
@@ -1367,6 +1693,14 @@ impl SkyBox {
     ///
     /// It will fail if provided face's kind is not TextureKind::Rectangle.
     pub fn create_cubemap(&mut self) -> Result<(), SkyBoxError> {
+        self.irradiance_map = None;
+        self.specular_map = None;
+
+        if let Some(hdri) = self.hdri.clone() {
+            self.cubemap = None;
+            return self.create_cubemap_from_hdri(&hdri);
+        }
+
         self.validate()?;
 
         let (kind, pixel_kind, bytes_per_face) =
@@ -1535,3 +1869,31 @@ impl SkyBox {
         self.back.clone()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::scene::{
+        base::{BaseBuilder, LAYER_COUNT},
+        camera::{is_layer_in_mask, CameraBuilder, CameraClearFlags},
+    };
+
+    #[test]
+    fn test_layer_mask() {
+        let camera = CameraBuilder::new(BaseBuilder::new()).build_camera();
+        assert!((0..LAYER_COUNT).all(|layer| camera.renders_layer(layer)));
+        assert!(camera.clear_flags().clears_color());
+
+        let mut camera = CameraBuilder::new(BaseBuilder::new())
+            .with_layer_mask(1 << 3)
+            .with_clear_flags(CameraClearFlags::DepthOnly)
+            .build_camera();
+        assert!(camera.renders_layer(3));
+        assert!(!camera.renders_layer(0));
+        assert!(!camera.clear_flags().clears_color());
+
+        assert_eq!(camera.set_layer_mask(0), 1 << 3);
+        assert!(!camera.renders_layer(3));
+
+        assert!(!is_layer_in_mask(u32::MAX, LAYER_COUNT));
+    }
+}
@@ -556,6 +556,17 @@ pub struct Collider {
     #[reflect(setter = "set_restitution_combine_rule")]
     pub(crate) restitution_combine_rule: InheritableVariable<CoefficientCombineRule>,
 
+    #[visit(optional)]
+    #[reflect(
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05,
+        setter = "set_sound_absorption",
+        description = "Defines how much of sound energy will be absorbed by the collider when a \
+        sound passes through it. It is used to calculate occlusion of sound sources."
+    )]
+    pub(crate) sound_absorption: InheritableVariable<f32>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     pub(crate) native: Cell<ColliderHandle>,
@@ -574,6 +585,7 @@ impl Default for Collider {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            sound_absorption: InheritableVariable::new_modified(0.5),
             native: Cell::new(ColliderHandle::invalid()),
         }
     }
@@ -606,6 +618,7 @@ impl Clone for Collider {
             solver_groups: self.solver_groups.clone(),
             friction_combine_rule: self.friction_combine_rule.clone(),
             restitution_combine_rule: self.restitution_combine_rule.clone(),
+            sound_absorption: self.sound_absorption.clone(),
             // Do not copy. The copy will have its own native representation (for example - Rapier's collider)
             native: Cell::new(ColliderHandle::invalid()),
         }
@@ -669,6 +682,20 @@ impl Collider {
         *self.restitution
     }
 
+    /// Sets the new sound absorption coefficient of the collider. The value must be in `[0..1]` range,
+    /// where 0.0 means that sound passes through the collider freely and 1.0 - the collider fully
+    /// blocks any sound. It is used to calculate occlusion of sound sources (see
+    /// [`crate::scene::sound::Sound::set_occlusion_enabled`] docs for more info).
+    pub fn set_sound_absorption(&mut self, sound_absorption: f32) -> f32 {
+        self.sound_absorption
+            .set_value_and_mark_modified(sound_absorption.clamp(0.0, 1.0))
+    }
+
+    /// Returns current sound absorption coefficient of the collider.
+    pub fn sound_absorption(&self) -> f32 {
+        *self.sound_absorption
+    }
+
     /// Sets the new density value of the collider. Density defines actual mass of the rigid body to
     /// which the collider is attached. Final mass will be a sum of `ColliderVolume * ColliderDensity`
     /// of each collider. In case if density is undefined, the mass of the collider will be zero,
@@ -897,6 +924,7 @@ pub struct ColliderBuilder {
     solver_groups: InteractionGroups,
     friction_combine_rule: CoefficientCombineRule,
     restitution_combine_rule: CoefficientCombineRule,
+    sound_absorption: f32,
 }
 
 impl ColliderBuilder {
@@ -913,6 +941,7 @@ impl ColliderBuilder {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            sound_absorption: 0.5,
         }
     }
 
@@ -970,6 +999,12 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets desired sound absorption coefficient. See [`Collider::set_sound_absorption`] for more info.
+    pub fn with_sound_absorption(mut self, sound_absorption: f32) -> Self {
+        self.sound_absorption = sound_absorption;
+        self
+    }
+
     /// Creates collider node, but does not add it to a graph.
     pub fn build_collider(self) -> Collider {
         Collider {
@@ -983,6 +1018,7 @@ impl ColliderBuilder {
             solver_groups: self.solver_groups.into(),
             friction_combine_rule: self.friction_combine_rule.into(),
             restitution_combine_rule: self.restitution_combine_rule.into(),
+            sound_absorption: self.sound_absorption.into(),
             native: Cell::new(ColliderHandle::invalid()),
         }
     }
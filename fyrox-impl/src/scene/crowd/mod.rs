@@ -0,0 +1,402 @@
+//! Crowd is a scene node, that renders lots of animated copies of a surface using vertex animation
+//! textures. See [`Crowd`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    graph::BaseSceneGraph,
+    material::{Material, MaterialResource},
+    renderer::{
+        self,
+        bundle::{
+            PersistentIdentifier, RenderContext, SurfaceInstanceData, VertexAnimationInstanceData,
+        },
+        framework::geometry_buffer::ElementRange,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        crowd::vat::VertexAnimationResource,
+        graph::Graph,
+        mesh::RenderPath,
+        node::{Node, NodeTrait, RdcControlFlow, UpdateContext},
+    },
+};
+use fyrox_resource::untyped::ResourceKind;
+use std::ops::{Deref, DerefMut};
+
+pub mod vat;
+
+/// A single animated copy of the surface of a [`Crowd`].
+#[derive(Clone, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "5d8e1a7c-2f4b-4c93-b6e0-8a1f3d5c7e29")]
+pub struct CrowdInstance {
+    /// Position of the instance relative to the crowd node.
+    pub position: Vector3<f32>,
+    /// Rotation of the instance relative to the crowd node.
+    pub rotation: UnitQuaternion<f32>,
+    /// Scale of the instance relative to the crowd node.
+    pub scale: Vector3<f32>,
+    /// Index of a clip of the vertex animation, that is played by the instance.
+    pub clip: u32,
+    /// Time offset (in seconds) of the instance. Different offsets prevent instances from moving in sync.
+    pub time_offset: f32,
+    /// Playback speed multiplier of the instance.
+    pub speed: f32,
+}
+
+impl Default for CrowdInstance {
+    fn default() -> Self {
+        Self {
+            position: Default::default(),
+            rotation: Default::default(),
+            scale: Vector3::repeat(1.0),
+            clip: 0,
+            time_offset: 0.0,
+            speed: 1.0,
+        }
+    }
+}
+
+impl CrowdInstance {
+    /// Returns local transform matrix of the instance.
+    pub fn local_transform(&self) -> Matrix4<f32> {
+        Matrix4::new_translation(&self.position)
+            * self.rotation.to_homogeneous()
+            * Matrix4::new_nonuniform_scaling(&self.scale)
+    }
+}
+
+/// Crowd renders lots of copies (instances) of an animated surface, each instance has its own transform,
+/// animation clip and time offset. Animations are baked into a [`vat::VertexAnimation`] resource, which
+/// stores positions and normals of every vertex in every frame in a texture. Vertex shader fetches two
+/// frames of an instance from the texture and blends them, there's no skinning and no per-instance
+/// updates on CPU, which makes crowds suitable for hundreds or thousands of background characters.
+///
+/// Crowd material must use a shader that supports vertex animation textures, for example the standard
+/// vertex animation shader (see [`Material::standard_vertex_animation`]). Every instance is culled
+/// individually using the bounds of the vertex animation.
+///
+/// ## Limitations
+///
+/// Instances are drawn with separate draw calls, but they share the same surface, material and texture,
+/// so there are no state changes between them. Instances can't blend between clips and can't be
+/// attached to other scene nodes.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::{algebra::Vector3, pool::Handle},
+/// #     scene::{
+/// #         base::BaseBuilder,
+/// #         crowd::{vat::VertexAnimationResource, CrowdBuilder, CrowdInstance},
+/// #         graph::Graph,
+/// #         node::Node,
+/// #     },
+/// # };
+/// fn create_crowd(animation: VertexAnimationResource, graph: &mut Graph) -> Handle<Node> {
+///     let instances = (0..100)
+///         .map(|i| CrowdInstance {
+///             position: Vector3::new((i % 10) as f32, 0.0, (i / 10) as f32),
+///             time_offset: i as f32 * 0.37,
+///             ..Default::default()
+///         })
+///         .collect();
+///
+///     CrowdBuilder::new(BaseBuilder::new())
+///         .with_animation(animation)
+///         .with_instances(instances)
+///         .build(graph)
+/// }
+/// ```
+#[derive(Clone, Debug, Reflect, Visit, ComponentProvider)]
+pub struct Crowd {
+    base: Base,
+
+    #[reflect(setter = "set_animation")]
+    animation: InheritableVariable<Option<VertexAnimationResource>>,
+
+    #[reflect(setter = "set_material")]
+    material: InheritableVariable<MaterialResource>,
+
+    #[reflect(setter = "set_instances")]
+    instances: InheritableVariable<Vec<CrowdInstance>>,
+
+    #[reflect(setter = "set_playing")]
+    playing: InheritableVariable<bool>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    time: f32,
+}
+
+impl Default for Crowd {
+    fn default() -> Self {
+        CrowdBuilder::new(BaseBuilder::new()).build_crowd()
+    }
+}
+
+impl Deref for Crowd {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Crowd {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for Crowd {
+    fn type_uuid() -> Uuid {
+        uuid!("b2c7e4f1-9a3d-4f6b-8e15-0d4a7c9b2e58")
+    }
+}
+
+impl Crowd {
+    /// Sets new vertex animation of the crowd.
+    pub fn set_animation(
+        &mut self,
+        animation: Option<VertexAnimationResource>,
+    ) -> Option<VertexAnimationResource> {
+        self.animation.set_value_and_mark_modified(animation)
+    }
+
+    /// Returns current vertex animation of the crowd.
+    pub fn animation(&self) -> Option<&VertexAnimationResource> {
+        self.animation.as_ref()
+    }
+
+    /// Sets new material of the crowd. The material must use a shader, that supports vertex animation
+    /// textures.
+    pub fn set_material(&mut self, material: MaterialResource) -> MaterialResource {
+        self.material.set_value_and_mark_modified(material)
+    }
+
+    /// Returns current material of the crowd.
+    pub fn material(&self) -> &MaterialResource {
+        &self.material
+    }
+
+    /// Sets new instances of the crowd.
+    pub fn set_instances(&mut self, instances: Vec<CrowdInstance>) -> Vec<CrowdInstance> {
+        self.instances.set_value_and_mark_modified(instances)
+    }
+
+    /// Returns a reference to the instances of the crowd.
+    pub fn instances(&self) -> &[CrowdInstance] {
+        &self.instances
+    }
+
+    /// Returns a reference to the instances of the crowd, that could be modified.
+    pub fn instances_mut(&mut self) -> &mut Vec<CrowdInstance> {
+        self.instances.get_value_mut_and_mark_modified()
+    }
+
+    /// Starts or stops playback of the animations of every instance.
+    pub fn set_playing(&mut self, playing: bool) -> bool {
+        self.playing.set_value_and_mark_modified(playing)
+    }
+
+    /// Returns `true` if the animations are playing, `false` - otherwise.
+    pub fn is_playing(&self) -> bool {
+        *self.playing
+    }
+
+    /// Sets current playback time of the crowd (in seconds). Every instance adds its own time offset to it.
+    pub fn set_time(&mut self, time: f32) {
+        self.time = time;
+    }
+
+    /// Returns current playback time of the crowd (in seconds).
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+}
+
+impl NodeTrait for Crowd {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        let Some(bounds) = self
+            .animation
+            .as_ref()
+            .and_then(|animation| animation.state().data().map(|data| data.bounds()))
+        else {
+            return AxisAlignedBoundingBox::unit();
+        };
+
+        let mut aabb = AxisAlignedBoundingBox::default();
+        for instance in self.instances.iter() {
+            aabb.add_box(bounds.transform(&instance.local_transform()));
+        }
+        if aabb.is_valid() {
+            aabb
+        } else {
+            AxisAlignedBoundingBox::unit()
+        }
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        if *self.playing {
+            self.time += context.dt;
+        }
+    }
+
+    fn collect_render_data(&self, ctx: &mut RenderContext) -> RdcControlFlow {
+        if !self.global_visibility() || !self.is_globally_enabled() {
+            return RdcControlFlow::Continue;
+        }
+
+        if renderer::is_shadow_pass(ctx.render_pass_name) && !self.cast_shadows() {
+            return RdcControlFlow::Continue;
+        }
+
+        let Some(animation) = self.animation.as_ref() else {
+            return RdcControlFlow::Continue;
+        };
+        let mut animation_state = animation.state();
+        let Some(animation) = animation_state.data() else {
+            return RdcControlFlow::Continue;
+        };
+
+        let global_transform = self.global_transform();
+        let bounds = animation.bounds();
+        let surface = animation.surface();
+        let sort_index = self.material.key();
+
+        for (index, instance) in self.instances.iter().enumerate() {
+            let world_transform = global_transform * instance.local_transform();
+
+            if self.frustum_culling()
+                && !ctx.frustum.map_or(true, |f| {
+                    f.is_intersects_aabb(&bounds.transform(&world_transform))
+                })
+            {
+                continue;
+            }
+
+            let time = self.time * instance.speed + instance.time_offset;
+            let Some(sample) = animation.sample(instance.clip as usize, time) else {
+                continue;
+            };
+
+            ctx.storage.push(
+                surface,
+                &self.material,
+                RenderPath::Deferred,
+                0,
+                sort_index,
+                SurfaceInstanceData {
+                    world_transform,
+                    bone_matrices: Default::default(),
+                    depth_offset: self.depth_offset_factor(),
+                    blend_shapes_weights: Default::default(),
+                    element_range: ElementRange::Full,
+                    persistent_identifier: PersistentIdentifier::new_combined(
+                        surface,
+                        self.self_handle,
+                        index,
+                    ),
+                    node_handle: self.self_handle,
+                    vertex_animation: Some(VertexAnimationInstanceData {
+                        texture: animation.texture().clone(),
+                        vertex_count: animation.vertex_count(),
+                        frames: sample.frames,
+                        blend: sample.blend,
+                    }),
+                },
+            );
+        }
+
+        RdcControlFlow::Continue
+    }
+}
+
+/// Allows you to create crowds in declarative manner.
+pub struct CrowdBuilder {
+    base_builder: BaseBuilder,
+    animation: Option<VertexAnimationResource>,
+    material: MaterialResource,
+    instances: Vec<CrowdInstance>,
+    playing: bool,
+}
+
+impl CrowdBuilder {
+    /// Creates new builder instance.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            animation: None,
+            material: MaterialResource::new_ok(
+                ResourceKind::Embedded,
+                Material::standard_vertex_animation(),
+            ),
+            instances: Default::default(),
+            playing: true,
+        }
+    }
+
+    /// Sets desired vertex animation of the crowd.
+    pub fn with_animation(mut self, animation: VertexAnimationResource) -> Self {
+        self.animation = Some(animation);
+        self
+    }
+
+    /// Sets desired material of the crowd.
+    pub fn with_material(mut self, material: MaterialResource) -> Self {
+        self.material = material;
+        self
+    }
+
+    /// Sets desired instances of the crowd.
+    pub fn with_instances(mut self, instances: Vec<CrowdInstance>) -> Self {
+        self.instances = instances;
+        self
+    }
+
+    /// Sets whether the animations should be played or not.
+    pub fn with_playing(mut self, playing: bool) -> Self {
+        self.playing = playing;
+        self
+    }
+
+    /// Creates new crowd.
+    pub fn build_crowd(self) -> Crowd {
+        Crowd {
+            base: self.base_builder.build_base(),
+            animation: self.animation.into(),
+            material: self.material.into(),
+            instances: self.instances.into(),
+            playing: self.playing.into(),
+            time: 0.0,
+        }
+    }
+
+    /// Creates new crowd node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_crowd())
+    }
+
+    /// Creates new crowd node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
@@ -0,0 +1,721 @@
+//! Vertex animation texture (VAT) resource stores positions and normals of every vertex of a surface in
+//! every frame of a set of animations. See [`VertexAnimation`] docs for more info.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+        state::LoadError,
+        untyped::ResourceKind,
+        Resource, ResourceData,
+    },
+    core::{
+        algebra::{Matrix3, Matrix4, Point3, Vector3},
+        io::FileLoadError,
+        math::{aabb::AxisAlignedBoundingBox, Matrix4Ext},
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        visitor::{prelude::*, PodVecView},
+    },
+    graph::{BaseSceneGraph, SceneGraph},
+    resource::texture::{
+        Texture, TextureKind, TextureMagnificationFilter, TextureMinificationFilter,
+        TexturePixelKind, TextureResource,
+    },
+    scene::{
+        animation::{Animation, AnimationPoseExt},
+        graph::Graph,
+        mesh::{
+            buffer::{VertexAttributeUsage, VertexReadTrait},
+            surface::{SurfaceData, SurfaceResource},
+            Mesh,
+        },
+        node::Node,
+    },
+};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Maximum width of a vertex animation texture (in texels).
+pub const MAX_TEXTURE_WIDTH: u32 = 2048;
+
+/// Maximum height of a vertex animation texture (in texels).
+pub const MAX_TEXTURE_HEIGHT: u32 = 16384;
+
+/// An error that may occur during vertex animation resource loading.
+#[derive(Debug)]
+pub enum VertexAnimationError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for VertexAnimationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            Self::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for VertexAnimationError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for VertexAnimationError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// An error that may occur during baking of a vertex animation.
+#[derive(Debug)]
+pub enum VertexAnimationBakeError {
+    /// There's no mesh node with the given handle.
+    InvalidMesh(Handle<Node>),
+    /// The mesh does not have a surface with the given index.
+    InvalidSurface(usize),
+    /// Frame rate must be greater than zero.
+    InvalidFrameRate(f32),
+    /// There's nothing to bake.
+    NoAnimations,
+    /// Baked data does not fit into a single texture.
+    TooLarge {
+        /// Amount of texels, that is needed to store the data.
+        texel_count: usize,
+    },
+}
+
+impl Display for VertexAnimationBakeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidMesh(handle) => {
+                write!(f, "There's no mesh node with {handle} handle.")
+            }
+            Self::InvalidSurface(index) => {
+                write!(f, "The mesh does not have a surface with {index} index.")
+            }
+            Self::InvalidFrameRate(frame_rate) => {
+                write!(f, "Frame rate must be greater than zero, got {frame_rate}.")
+            }
+            Self::NoAnimations => {
+                write!(f, "There are no animations to bake.")
+            }
+            Self::TooLarge { texel_count } => {
+                write!(
+                    f,
+                    "Baked data requires {texel_count} texels, which does not fit into a \
+                    {MAX_TEXTURE_WIDTH}x{MAX_TEXTURE_HEIGHT} texture. Reduce frame rate, \
+                    amount of animations or amount of vertices."
+                )
+            }
+        }
+    }
+}
+
+impl Error for VertexAnimationBakeError {}
+
+/// A named range of frames in a [`VertexAnimation`], that corresponds to a single source animation.
+#[derive(Clone, Debug, Default, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "c4a7e2d9-6b1f-4e85-9d3c-7f0b2a8e6c14")]
+pub struct VertexAnimationClip {
+    /// Name of the clip. Usually it is the name of the source animation.
+    pub name: String,
+    /// Index of the first frame of the clip.
+    pub first_frame: u32,
+    /// Amount of frames in the clip.
+    pub frame_count: u32,
+    /// Whether the clip should be repeated or not.
+    pub looped: bool,
+}
+
+impl VertexAnimationClip {
+    /// Returns duration of the clip (in seconds) at the given frame rate.
+    pub fn duration(&self, frame_rate: f32) -> f32 {
+        self.frame_count.saturating_sub(1) as f32 / frame_rate
+    }
+}
+
+/// A pair of frames, that should be blended to get the state of a clip at some time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VertexAnimationSample {
+    /// Indices of two frames that will be blended.
+    pub frames: [u32; 2],
+    /// Blending factor between the frames in `[0; 1]` range.
+    pub blend: f32,
+}
+
+/// Vertex animation is a set of animations of a surface, that were baked into a texture (vertex animation
+/// texture or VAT). Each frame of each animation stores local position and normal of every vertex of the
+/// surface, so playing an animation is just a matter of fetching two frames from the texture in a vertex
+/// shader and blending them. Unlike skeletal animation, vertex animation does not need any bones or any
+/// per-frame work on CPU, which makes it very cheap to render hundreds or thousands of animated characters
+/// sharing the same animation set. See [`crate::scene::crowd::Crowd`] for more info.
+///
+/// ## Layout
+///
+/// The texture has [`TexturePixelKind::RGBA32F`] format, each vertex takes two texels (position and
+/// normal), frames are stored one after another. Texel index of a vertex is calculated as
+/// `(frame * vertex_count + vertex_index) * 2`, and then it is wrapped into rows of the texture.
+///
+/// ## Baking
+///
+/// Vertex animations are usually baked in the editor (`Utils -> Vertex Animation Baker`) from existing
+/// skeletal animations, but it is possible to bake them from code using [`VertexAnimation::bake`].
+#[derive(Debug, Clone, Reflect, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "3f6a9c1e-7b2d-4e58-a0c4-91d7e25b8f36")]
+pub struct VertexAnimation {
+    #[reflect(read_only)]
+    frame_rate: f32,
+    #[reflect(read_only)]
+    vertex_count: u32,
+    #[reflect(read_only)]
+    clips: Vec<VertexAnimationClip>,
+    #[reflect(hidden)]
+    frames: Vec<f32>,
+    #[reflect(hidden)]
+    bounds: AxisAlignedBoundingBox,
+    #[reflect(hidden)]
+    surface: SurfaceResource,
+    #[reflect(hidden)]
+    texture: TextureResource,
+}
+
+impl Default for VertexAnimation {
+    fn default() -> Self {
+        Self {
+            frame_rate: 30.0,
+            vertex_count: 0,
+            clips: Default::default(),
+            frames: Default::default(),
+            bounds: AxisAlignedBoundingBox::collapsed(),
+            surface: SurfaceResource::new_ok(ResourceKind::Embedded, SurfaceData::default()),
+            texture: make_texture(&[]),
+        }
+    }
+}
+
+impl Visit for VertexAnimation {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.frame_rate.visit("FrameRate", &mut region)?;
+        self.vertex_count.visit("VertexCount", &mut region)?;
+        self.clips.visit("Clips", &mut region)?;
+        self.bounds.min.visit("BoundsMin", &mut region)?;
+        self.bounds.max.visit("BoundsMax", &mut region)?;
+
+        let mut view = PodVecView::from_pod_vec(&mut self.frames);
+        view.visit("Frames", &mut region)?;
+
+        // The surface is stored directly, because embedded resources require a resource manager.
+        if region.is_reading() {
+            let mut surface_data = SurfaceData::default();
+            surface_data.visit("SurfaceData", &mut region)?;
+            self.surface = SurfaceResource::new_ok(ResourceKind::Embedded, surface_data);
+            self.texture = make_texture(&self.frames);
+        } else {
+            self.surface.data_ref().visit("SurfaceData", &mut region)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ResourceData for VertexAnimation {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("VertexAnimation", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+fn texture_size(texel_count: usize) -> (u32, u32) {
+    let width = (texel_count as u32).clamp(1, MAX_TEXTURE_WIDTH);
+    let height = ((texel_count as u32 + width - 1) / width).max(1);
+    (width, height)
+}
+
+fn make_texture(frames: &[f32]) -> TextureResource {
+    let texel_count = frames.len() / 4;
+    let (width, height) = texture_size(texel_count);
+
+    let mut data = frames.to_vec();
+    data.resize((width * height * 4) as usize, 0.0);
+    let bytes = data
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect::<Vec<_>>();
+
+    let mut texture = Texture::from_bytes(
+        TextureKind::Rectangle { width, height },
+        TexturePixelKind::RGBA32F,
+        bytes,
+    )
+    .expect("vertex animation texture size must match its data");
+    // Texels are fetched directly, there's nothing to filter.
+    texture.set_minification_filter(TextureMinificationFilter::Nearest);
+    texture.set_magnification_filter(TextureMagnificationFilter::Nearest);
+
+    Resource::new_ok(ResourceKind::Embedded, texture)
+}
+
+impl VertexAnimation {
+    /// Load a vertex animation resource from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, VertexAnimationError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut vertex_animation = VertexAnimation::default();
+        vertex_animation.visit("VertexAnimation", &mut visitor)?;
+        Ok(vertex_animation)
+    }
+
+    /// Bakes the given animations of a surface of a mesh into a new vertex animation. Every animation is
+    /// sampled with the given frame rate from the beginning to the end of its time slice, each animation
+    /// becomes a separate clip with the same name. Positions and normals are stored in the local space of
+    /// the mesh, as it was right before baking.
+    ///
+    /// Animations are applied to the graph directly, local transforms of every animated node are restored
+    /// after baking. Other animated properties are left as they are at the end of the last animation.
+    pub fn bake(
+        graph: &mut Graph,
+        mesh: Handle<Node>,
+        surface_index: usize,
+        animations: &[&Animation],
+        frame_rate: f32,
+    ) -> Result<Self, VertexAnimationBakeError> {
+        if frame_rate.is_nan() || frame_rate <= 0.0 {
+            return Err(VertexAnimationBakeError::InvalidFrameRate(frame_rate));
+        }
+        if animations.is_empty() {
+            return Err(VertexAnimationBakeError::NoAnimations);
+        }
+
+        let mesh_ref = graph
+            .try_get_of_type::<Mesh>(mesh)
+            .ok_or(VertexAnimationBakeError::InvalidMesh(mesh))?;
+        let surface = mesh_ref
+            .surfaces()
+            .get(surface_index)
+            .ok_or(VertexAnimationBakeError::InvalidSurface(surface_index))?;
+        let surface_resource = surface.data();
+        let bones = surface.bones().to_vec();
+        let inv_reference = mesh_ref
+            .global_transform()
+            .try_inverse()
+            .unwrap_or_default();
+
+        let mut surface_data = surface_resource.data_ref().clone();
+        // Blend shapes are not baked.
+        surface_data.blend_shapes_container = None;
+        let vertex_count = surface_data.vertex_buffer.vertex_count();
+
+        let clips = {
+            let mut first_frame = 0;
+            animations
+                .iter()
+                .map(|animation| {
+                    let frame_count = (animation.length() * frame_rate).round() as u32 + 1;
+                    let clip = VertexAnimationClip {
+                        name: animation.name().to_string(),
+                        first_frame,
+                        frame_count,
+                        looped: animation.is_loop(),
+                    };
+                    first_frame += frame_count;
+                    clip
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let frame_count = clips.iter().map(|c| c.frame_count as usize).sum::<usize>();
+        let texel_count = frame_count * vertex_count as usize * 2;
+        let (width, height) = texture_size(texel_count);
+        if height > MAX_TEXTURE_HEIGHT {
+            return Err(VertexAnimationBakeError::TooLarge { texel_count });
+        }
+
+        let animated_nodes = animations
+            .iter()
+            .flat_map(|animation| animation.tracks().iter().map(|track| track.target()))
+            .filter_map(|handle| {
+                graph
+                    .try_get(handle)
+                    .map(|node| (handle, node.local_transform().clone()))
+            })
+            .collect::<Vec<_>>();
+
+        let mut frames = Vec::with_capacity((width * height * 4) as usize);
+        let mut bounds = AxisAlignedBoundingBox::default();
+        for (animation, clip) in animations.iter().zip(clips.iter()) {
+            let mut animation = (*animation).clone();
+            // The last frame must be exactly at the end of the time slice, looping would wrap it.
+            animation.set_loop(false);
+            let start = animation.time_slice().start;
+            for frame in 0..clip.frame_count {
+                animation.set_time_position(start + frame as f32 / frame_rate);
+                animation.tick(0.0);
+                animation.pose().apply(graph);
+                graph.update_hierarchical_data();
+
+                let (position_transform, normal_transform) = if bones.is_empty() {
+                    let transform = inv_reference * graph[mesh].global_transform();
+                    (vec![transform], vec![normal_matrix(&transform)])
+                } else {
+                    let transforms = bones
+                        .iter()
+                        .map(|bone| {
+                            graph.try_get(*bone).map_or(Matrix4::identity(), |bone| {
+                                inv_reference
+                                    * bone.global_transform()
+                                    * bone.inv_bind_pose_transform()
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    let normals = transforms.iter().map(normal_matrix).collect();
+                    (transforms, normals)
+                };
+
+                for vertex in surface_data.vertex_buffer.iter() {
+                    let position = vertex
+                        .read_3_f32(VertexAttributeUsage::Position)
+                        .unwrap_or_default();
+                    let normal = vertex
+                        .read_3_f32(VertexAttributeUsage::Normal)
+                        .unwrap_or_default();
+
+                    let (position, normal) = if bones.is_empty() {
+                        (
+                            position_transform[0]
+                                .transform_point(&Point3::from(position))
+                                .coords,
+                            normal_transform[0] * normal,
+                        )
+                    } else {
+                        let weights = vertex
+                            .read_4_f32(VertexAttributeUsage::BoneWeight)
+                            .unwrap_or_default();
+                        let indices = vertex
+                            .read_4_u8(VertexAttributeUsage::BoneIndices)
+                            .unwrap_or_default();
+                        let mut skinned_position = Vector3::default();
+                        let mut skinned_normal = Vector3::default();
+                        for (weight, index) in weights.iter().zip(indices.iter()) {
+                            let index = *index as usize;
+                            if let (Some(transform), Some(normal_transform)) =
+                                (position_transform.get(index), normal_transform.get(index))
+                            {
+                                skinned_position += transform
+                                    .transform_point(&Point3::from(position))
+                                    .coords
+                                    .scale(*weight);
+                                skinned_normal += (normal_transform * normal).scale(*weight);
+                            }
+                        }
+                        (skinned_position, skinned_normal)
+                    };
+
+                    let normal = normal.try_normalize(f32::EPSILON).unwrap_or_default();
+                    bounds.add_point(position);
+                    frames.extend_from_slice(&[position.x, position.y, position.z, 1.0]);
+                    frames.extend_from_slice(&[normal.x, normal.y, normal.z, 0.0]);
+                }
+            }
+        }
+
+        for (handle, transform) in animated_nodes {
+            *graph[handle].local_transform_mut() = transform;
+        }
+        graph.update_hierarchical_data();
+
+        if !bounds.is_valid() {
+            bounds = AxisAlignedBoundingBox::collapsed();
+        }
+
+        let texture = make_texture(&frames);
+        Ok(Self {
+            frame_rate,
+            vertex_count,
+            clips,
+            frames,
+            bounds,
+            surface: SurfaceResource::new_ok(ResourceKind::Embedded, surface_data),
+            texture,
+        })
+    }
+
+    /// Returns frame rate, that was used to bake the animation.
+    pub fn frame_rate(&self) -> f32 {
+        self.frame_rate
+    }
+
+    /// Returns amount of vertices in a single frame.
+    pub fn vertex_count(&self) -> u32 {
+        self.vertex_count
+    }
+
+    /// Returns total amount of frames of every clip.
+    pub fn frame_count(&self) -> u32 {
+        if self.vertex_count == 0 {
+            0
+        } else {
+            (self.frames.len() / (self.vertex_count as usize * 8)) as u32
+        }
+    }
+
+    /// Returns a list of clips of the animation.
+    pub fn clips(&self) -> &[VertexAnimationClip] {
+        &self.clips
+    }
+
+    /// Tries to find a clip with the given name and returns its index.
+    pub fn clip_index(&self, name: &str) -> Option<usize> {
+        self.clips.iter().position(|clip| clip.name == name)
+    }
+
+    /// Sets whether the clip with the given index should be repeated or not.
+    pub fn set_clip_looped(&mut self, index: usize, looped: bool) {
+        if let Some(clip) = self.clips.get_mut(index) {
+            clip.looped = looped;
+        }
+    }
+
+    /// Returns local-space bounding box of the surface, that encloses every frame of every clip.
+    pub fn bounds(&self) -> AxisAlignedBoundingBox {
+        self.bounds
+    }
+
+    /// Returns a surface with the topology and texture coordinates of the animated surface.
+    pub fn surface(&self) -> &SurfaceResource {
+        &self.surface
+    }
+
+    /// Returns vertex animation texture.
+    pub fn texture(&self) -> &TextureResource {
+        &self.texture
+    }
+
+    /// Calculates a pair of frames and a blending factor between them for the given clip at the given
+    /// time. Looped clips are wrapped, other clips are clamped. Returns [`None`] if there's no such clip.
+    pub fn sample(&self, clip: usize, time: f32) -> Option<VertexAnimationSample> {
+        let clip = self.clips.get(clip)?;
+        if clip.frame_count == 0 {
+            return None;
+        }
+
+        let last = clip.frame_count - 1;
+        let duration = clip.duration(self.frame_rate);
+        if last == 0 || duration <= 0.0 {
+            return Some(VertexAnimationSample {
+                frames: [clip.first_frame; 2],
+                blend: 0.0,
+            });
+        }
+
+        let time = if clip.looped {
+            time.rem_euclid(duration)
+        } else {
+            time.clamp(0.0, duration)
+        };
+        let position = time * self.frame_rate;
+        let frame = (position.floor() as u32).min(last);
+        let next = (frame + 1).min(last);
+
+        Some(VertexAnimationSample {
+            frames: [clip.first_frame + frame, clip.first_frame + next],
+            blend: (position - frame as f32).clamp(0.0, 1.0),
+        })
+    }
+}
+
+fn normal_matrix(transform: &Matrix4<f32>) -> Matrix3<f32> {
+    transform
+        .basis()
+        .try_inverse()
+        .unwrap_or_default()
+        .transpose()
+}
+
+/// Type alias for vertex animation resources.
+pub type VertexAnimationResource = Resource<VertexAnimation>;
+
+/// Default implementation for vertex animation loading.
+pub struct VertexAnimationLoader;
+
+impl ResourceLoader for VertexAnimationLoader {
+    fn extensions(&self) -> &[&str] {
+        &["vat"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <VertexAnimation as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let vertex_animation = VertexAnimation::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(vertex_animation))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::untyped::ResourceKind,
+        core::{
+            algebra::{Matrix4, Vector3},
+            math::curve::{CurveKey, CurveKeyKind},
+        },
+        generic_animation::{
+            container::{TrackDataContainer, TrackValueKind},
+            track::Track,
+            value::ValueBinding,
+        },
+        scene::{
+            animation::Animation,
+            base::BaseBuilder,
+            crowd::vat::{VertexAnimation, VertexAnimationClip, VertexAnimationSample},
+            graph::Graph,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceResource},
+                MeshBuilder,
+            },
+        },
+    };
+
+    fn vertex_animation() -> VertexAnimation {
+        VertexAnimation {
+            frame_rate: 10.0,
+            vertex_count: 1,
+            clips: vec![
+                VertexAnimationClip {
+                    name: "Idle".to_string(),
+                    first_frame: 0,
+                    frame_count: 11,
+                    looped: true,
+                },
+                VertexAnimationClip {
+                    name: "Death".to_string(),
+                    first_frame: 11,
+                    frame_count: 6,
+                    looped: false,
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sample() {
+        let animation = vertex_animation();
+
+        assert_eq!(animation.clip_index("Death"), Some(1));
+        assert_eq!(animation.sample(2, 0.0), None);
+
+        let sample = animation.sample(0, 0.25).unwrap();
+        assert_eq!(sample.frames, [2, 3]);
+        assert!((sample.blend - 0.5).abs() < 0.001);
+
+        // Looped clips wrap around.
+        let sample = animation.sample(0, 1.05).unwrap();
+        assert_eq!(sample.frames, [0, 1]);
+        assert!((sample.blend - 0.5).abs() < 0.001);
+
+        // Other clips are clamped.
+        assert_eq!(
+            animation.sample(1, 10.0),
+            Some(VertexAnimationSample {
+                frames: [16, 16],
+                blend: 0.0
+            })
+        );
+        assert_eq!(
+            animation.sample(1, -1.0),
+            Some(VertexAnimationSample {
+                frames: [11, 12],
+                blend: 0.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_bake() {
+        let mut graph = Graph::new();
+        let mesh = MeshBuilder::new(BaseBuilder::new())
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceResource::new_ok(
+                ResourceKind::Embedded,
+                SurfaceData::make_cube(Matrix4::identity()),
+            ))
+            .build()])
+            .build(&mut graph);
+        graph.update_hierarchical_data();
+
+        let mut container = TrackDataContainer::new(TrackValueKind::Vector3);
+        for (time, x) in [(0.0, 0.0), (1.0, 2.0)] {
+            let curves = container.curves_mut();
+            curves[0].add_key(CurveKey::new(time, x, CurveKeyKind::Linear));
+            curves[1].add_key(CurveKey::new(time, 0.0, CurveKeyKind::Linear));
+            curves[2].add_key(CurveKey::new(time, 0.0, CurveKeyKind::Linear));
+        }
+        let mut track = Track::new(container, ValueBinding::Position);
+        track.set_target(mesh);
+        let mut animation = Animation::default();
+        animation.set_name("Move");
+        animation.add_track(track);
+        animation.set_time_slice(0.0..1.0);
+
+        let baked = VertexAnimation::bake(&mut graph, mesh, 0, &[&animation], 2.0).unwrap();
+
+        assert_eq!(baked.clips().len(), 1);
+        assert_eq!(baked.clips()[0].frame_count, 3);
+        assert_eq!(baked.frame_count(), 3);
+        assert_eq!(baked.vertex_count(), 24);
+
+        // Every frame is offset by the animated position of the mesh.
+        let stride = baked.vertex_count() as usize * 8;
+        for (frame, offset) in [0.0, 1.0, 2.0].into_iter().enumerate() {
+            assert!((baked.frames[frame * stride] - baked.frames[0] - offset).abs() < 0.001);
+        }
+
+        // Local transform of the mesh is restored.
+        assert_eq!(graph[mesh].global_position(), Vector3::default());
+    }
+}
@@ -6,7 +6,9 @@ use crate::core::{
     algebra::{Matrix4, Point3, UnitQuaternion, Vector2, Vector3},
     color::{Color, Hsl},
     math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, Matrix4Ext},
+    reflect::prelude::*,
 };
+use serde::{Deserialize, Serialize};
 use std::ops::Range;
 
 /// Colored line between two points.
@@ -102,6 +104,115 @@ impl rapier3d::pipeline::DebugRenderBackend for SceneDrawingContext {
     }
 }
 
+/// Defines which parts of a physics world will be drawn and what colors will be used for them. It is used
+/// by `draw_with_settings` methods of 2D and 3D physics worlds.
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicsDebugDrawSettings {
+    /// Draw shapes of colliders.
+    pub draw_collider_shapes: bool,
+    /// Draw bounding boxes of colliders.
+    pub draw_collider_bounds: bool,
+    /// Draw local axes of rigid bodies.
+    pub draw_rigid_body_axes: bool,
+    /// Draw anchors of joints and lines between their frames.
+    pub draw_joints: bool,
+    /// Draw contact points and contact normals.
+    pub draw_contacts: bool,
+    /// Draw colliders of sleeping rigid bodies with darker colors.
+    pub highlight_sleeping_bodies: bool,
+    /// Color of colliders attached to dynamic rigid bodies.
+    pub dynamic_collider_color: Color,
+    /// Color of colliders attached to kinematic rigid bodies.
+    pub kinematic_collider_color: Color,
+    /// Color of colliders attached to static rigid bodies or colliders without a rigid body.
+    pub fixed_collider_color: Color,
+    /// Color of collider bounding boxes.
+    pub collider_bounds_color: Color,
+    /// Color of joint anchors.
+    pub joint_anchor_color: Color,
+    /// Color of lines between joint frames.
+    pub joint_separation_color: Color,
+    /// Color of lines between contact points.
+    pub contact_color: Color,
+    /// Color of contact normals.
+    pub contact_normal_color: Color,
+}
+
+impl Default for PhysicsDebugDrawSettings {
+    fn default() -> Self {
+        // Matches default style of the physics engine.
+        Self {
+            draw_collider_shapes: true,
+            draw_collider_bounds: false,
+            draw_rigid_body_axes: true,
+            draw_joints: true,
+            draw_contacts: false,
+            highlight_sleeping_bodies: true,
+            dynamic_collider_color: Color::from(Hsl::new(340.0, 1.0, 0.3)),
+            kinematic_collider_color: Color::from(Hsl::new(20.0, 1.0, 0.3)),
+            fixed_collider_color: Color::from(Hsl::new(30.0, 1.0, 0.4)),
+            collider_bounds_color: Color::from(Hsl::new(124.0, 1.0, 0.4)),
+            joint_anchor_color: Color::from(Hsl::new(240.0, 0.5, 0.4)),
+            joint_separation_color: Color::from(Hsl::new(0.0, 0.5, 0.4)),
+            contact_color: Color::from(Hsl::new(120.0, 1.0, 0.4)),
+            contact_normal_color: Color::from(Hsl::new(0.0, 1.0, 1.0)),
+        }
+    }
+}
+
+fn debug_color(color: Color) -> [f32; 4] {
+    let hsl = Hsl::from(color);
+    [hsl.hue(), hsl.saturation(), hsl.lightness(), 1.0]
+}
+
+macro_rules! define_debug_render_conversion {
+    ($name:ident, $rapier:ident) => {
+        pub(crate) fn $name(
+            &self,
+        ) -> (
+            $rapier::pipeline::DebugRenderMode,
+            $rapier::pipeline::DebugRenderStyle,
+        ) {
+            use $rapier::pipeline::{DebugRenderMode, DebugRenderStyle};
+
+            let mut mode = DebugRenderMode::empty();
+            mode.set(DebugRenderMode::COLLIDER_SHAPES, self.draw_collider_shapes);
+            mode.set(DebugRenderMode::COLLIDER_AABBS, self.draw_collider_bounds);
+            mode.set(DebugRenderMode::RIGID_BODY_AXES, self.draw_rigid_body_axes);
+            mode.set(DebugRenderMode::JOINTS, self.draw_joints);
+            mode.set(DebugRenderMode::CONTACTS, self.draw_contacts);
+
+            let style = DebugRenderStyle {
+                collider_dynamic_color: debug_color(self.dynamic_collider_color),
+                collider_fixed_color: debug_color(self.fixed_collider_color),
+                collider_kinematic_color: debug_color(self.kinematic_collider_color),
+                collider_parentless_color: debug_color(self.fixed_collider_color),
+                impulse_joint_anchor_color: debug_color(self.joint_anchor_color),
+                impulse_joint_separation_color: debug_color(self.joint_separation_color),
+                multibody_joint_anchor_color: debug_color(self.joint_anchor_color),
+                multibody_joint_separation_color: debug_color(self.joint_separation_color),
+                sleep_color_multiplier: if self.highlight_sleeping_bodies {
+                    [1.0, 1.0, 0.2, 1.0]
+                } else {
+                    [1.0; 4]
+                },
+                contact_depth_color: debug_color(self.contact_color),
+                contact_normal_color: debug_color(self.contact_normal_color),
+                collider_aabb_color: debug_color(self.collider_bounds_color),
+                ..Default::default()
+            };
+
+            (mode, style)
+        }
+    };
+}
+
+impl PhysicsDebugDrawSettings {
+    define_debug_render_conversion!(rapier2d_mode_and_style, rapier2d);
+    define_debug_render_conversion!(rapier3d_mode_and_style, rapier3d);
+}
+
 impl SceneDrawingContext {
     /// Draws frustum with given color.
     pub fn draw_frustum(&mut self, frustum: &Frustum, color: Color) {
@@ -0,0 +1,469 @@
+//! Debug drawing for game code. See [`DebugDraw`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector2, Vector3},
+        color::Color,
+        math::{aabb::AxisAlignedBoundingBox, Matrix4Ext},
+    },
+    scene::debug::{Line, SceneDrawingContext},
+};
+
+/// Amount of segments of wire spheres.
+const SPHERE_SEGMENTS: usize = 16;
+
+/// Ends of the segments of the line font. Every glyph lies in `[0; 2] x [0; 2]` box, segments are
+/// named by the letters of the alphabet, starting from `a`.
+#[rustfmt::skip]
+const FONT_SEGMENTS: [([f32; 2], [f32; 2]); 20] = [
+    ([0.0, 2.0], [1.0, 2.0]),  // a - top left
+    ([1.0, 2.0], [2.0, 2.0]),  // b - top right
+    ([2.0, 2.0], [2.0, 1.0]),  // c - right top
+    ([2.0, 1.0], [2.0, 0.0]),  // d - right bottom
+    ([2.0, 0.0], [1.0, 0.0]),  // e - bottom right
+    ([1.0, 0.0], [0.0, 0.0]),  // f - bottom left
+    ([0.0, 0.0], [0.0, 1.0]),  // g - left bottom
+    ([0.0, 1.0], [0.0, 2.0]),  // h - left top
+    ([0.0, 1.0], [1.0, 1.0]),  // i - middle left
+    ([1.0, 1.0], [2.0, 1.0]),  // j - middle right
+    ([0.0, 2.0], [1.0, 1.0]),  // k - diagonal top left
+    ([1.0, 2.0], [1.0, 1.0]),  // l - center top
+    ([2.0, 2.0], [1.0, 1.0]),  // m - diagonal top right
+    ([1.0, 1.0], [0.0, 0.0]),  // n - diagonal bottom left
+    ([1.0, 1.0], [1.0, 0.0]),  // o - center bottom
+    ([1.0, 1.0], [2.0, 0.0]),  // p - diagonal bottom right
+    ([0.0, 2.0], [1.0, 0.0]),  // q - long diagonal left
+    ([2.0, 2.0], [1.0, 0.0]),  // r - long diagonal right
+    ([1.0, 0.0], [1.0, 0.25]), // s - bottom dot
+    ([1.0, 1.0], [1.0, 1.25]), // t - middle dot
+];
+
+/// Returns the segments of the line font, that form the given character. Lowercase letters are
+/// drawn as uppercase, unsupported characters have no segments.
+fn glyph_segments(c: char) -> &'static str {
+    match c.to_ascii_uppercase() {
+        '0' => "abcdefghmn",
+        '1' => "cd",
+        '2' => "abcjigfe",
+        '3' => "abcdefj",
+        '4' => "hijcd",
+        '5' | 'S' => "abhijdef",
+        '6' => "abhgfedij",
+        '7' => "abcd",
+        '8' => "abcdefghij",
+        '9' => "abhcijdef",
+        'A' => "abcdghij",
+        'B' => "abcdefjlo",
+        'C' => "abhgfe",
+        'D' => "abcdefol",
+        'E' => "abhgfei",
+        'F' => "abhgi",
+        'G' => "abhgfedj",
+        'H' => "cdghij",
+        'I' => "ablofe",
+        'J' => "cdefg",
+        'K' => "hgimp",
+        'L' => "hgfe",
+        'M' => "cdghkm",
+        'N' => "cdghkp",
+        'O' => "abcdefgh",
+        'P' => "abchgij",
+        'Q' => "abcdefghp",
+        'R' => "abchgijp",
+        'T' => "ablo",
+        'U' => "cdefgh",
+        'V' => "qr",
+        'W' => "cdghnp",
+        'X' => "kmnp",
+        'Y' => "kmo",
+        'Z' => "abmnfe",
+        '-' => "ij",
+        '+' => "ijlo",
+        '*' => "ijklmnop",
+        '=' => "ijef",
+        '_' => "ef",
+        '/' => "mn",
+        '\\' => "kp",
+        '|' => "lo",
+        '<' => "mp",
+        '>' => "kn",
+        '.' | ',' => "s",
+        ':' => "st",
+        '!' => "ls",
+        '?' => "abcjs",
+        '\'' => "l",
+        _ => "",
+    }
+}
+
+/// Draws the given text using the line font. The text is centered at the given position, `side`
+/// and `up` vectors define the plane of the text.
+fn draw_text(
+    ctx: &mut SceneDrawingContext,
+    position: Vector3<f32>,
+    side: Vector3<f32>,
+    up: Vector3<f32>,
+    height: f32,
+    text: &str,
+    color: Color,
+) {
+    // Every glyph is two units wide and two units tall, glyphs and lines are separated by one unit.
+    let unit = height * 0.5;
+    let line_count = text.lines().count();
+    let block_height = (line_count * 3).saturating_sub(1) as f32;
+    for (line_index, line) in text.lines().enumerate() {
+        let line_width = (line.chars().count() * 3).saturating_sub(1) as f32;
+        let origin = Vector2::new(
+            -line_width * 0.5,
+            block_height * 0.5 - 2.0 - line_index as f32 * 3.0,
+        );
+        for (char_index, c) in line.chars().enumerate() {
+            let offset = origin + Vector2::new(char_index as f32 * 3.0, 0.0);
+            for segment in glyph_segments(c).bytes() {
+                let (begin, end) = FONT_SEGMENTS[(segment - b'a') as usize];
+                let to_world = |p: [f32; 2]| {
+                    position
+                        + side.scale((offset.x + p[0]) * unit)
+                        + up.scale((offset.y + p[1]) * unit)
+                };
+                ctx.add_line(Line {
+                    begin: to_world(begin),
+                    end: to_world(end),
+                    color,
+                });
+            }
+        }
+    }
+}
+
+/// A shape, that could be drawn by [`DebugDraw`].
+#[derive(Clone, Debug)]
+pub enum DebugShape {
+    /// A line between two points.
+    Line {
+        /// Beginning of the line.
+        begin: Vector3<f32>,
+        /// End of the line.
+        end: Vector3<f32>,
+    },
+    /// An arrow, that points from `begin` to `end`.
+    Arrow {
+        /// Beginning of the arrow.
+        begin: Vector3<f32>,
+        /// End of the arrow (where its head is).
+        end: Vector3<f32>,
+    },
+    /// Axis-aligned box.
+    Aabb(AxisAlignedBoundingBox),
+    /// Wireframe sphere.
+    Sphere {
+        /// Center of the sphere.
+        center: Vector3<f32>,
+        /// Radius of the sphere.
+        radius: f32,
+    },
+    /// Basis of the given transform (X, Y, Z axes drawn with red, green and blue colors).
+    Transform(Matrix4<f32>),
+    /// Camera-facing text, that is drawn using a simple line font. Only latin letters, digits and
+    /// a few punctuation characters are supported.
+    Text {
+        /// Center of the text.
+        position: Vector3<f32>,
+        /// The text.
+        text: String,
+        /// Height of a line of the text in world units.
+        height: f32,
+    },
+}
+
+/// A shape with its drawing parameters.
+#[derive(Clone, Debug)]
+pub struct DebugDrawItem {
+    /// The shape.
+    pub shape: DebugShape,
+    /// Color of the shape.
+    pub color: Color,
+    /// Remaining time (in seconds) of the shape.
+    pub time_left: f32,
+}
+
+/// Debug drawing for game code. It allows you to draw lines, arrows, boxes, spheres and text from
+/// any place in your code (scripts, plugins, etc.) with a duration. Shapes with zero duration are
+/// drawn for a single frame (immediate mode), shapes with a positive duration stay for the given
+/// amount of seconds (retained mode), and shapes with infinite duration stay until
+/// [`DebugDraw::clear`] is called. Every scene has its own instance of debug drawing in
+/// [`crate::scene::Scene::debug_draw`].
+///
+/// Unlike [`SceneDrawingContext`], there's no need to clear shapes manually at the beginning of
+/// each frame, the scene removes expired shapes automatically on update.
+///
+/// ## Toggle
+///
+/// Debug drawing works in any build, not only in the editor, but it is disabled by default in
+/// release builds. It could be enabled or disabled at any time using [`DebugDraw::set_enabled`]
+/// (for example, by a console command). Drawing methods do nothing when debug drawing is disabled,
+/// so they could be left in the shipping code.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::{algebra::Vector3, color::Color},
+/// #     scene::Scene,
+/// # };
+/// fn debug_shot(scene: &mut Scene, from: Vector3<f32>, hit: Vector3<f32>, damage: f32) {
+///     // Draw the trajectory of the shot for two seconds.
+///     scene.debug_draw.arrow(from, hit, Color::RED, 2.0);
+///     // Draw the damage at the hit point for one second.
+///     scene
+///         .debug_draw
+///         .text(hit, format!("{damage:.1}"), 0.2, Color::WHITE, 1.0);
+///     // Draw a sphere for a single frame.
+///     scene.debug_draw.sphere(hit, 0.1, Color::GREEN, 0.0);
+/// }
+/// ```
+///
+/// ## Rendering
+///
+/// Shapes are drawn as lines by the same renderer as [`SceneDrawingContext`], so all the shapes of
+/// a scene are rendered in a single draw call.
+#[derive(Clone, Debug)]
+pub struct DebugDraw {
+    enabled: bool,
+    items: Vec<DebugDrawItem>,
+}
+
+impl Default for DebugDraw {
+    fn default() -> Self {
+        Self {
+            enabled: cfg!(debug_assertions),
+            items: Default::default(),
+        }
+    }
+}
+
+impl DebugDraw {
+    /// Enables or disables debug drawing. Disabling removes every existing shape.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.items.clear();
+        }
+    }
+
+    /// Returns `true` if debug drawing is enabled, `false` - otherwise.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Adds a new shape with the given color and duration (in seconds). See [`DebugDraw`] docs for
+    /// more info about durations.
+    pub fn add(&mut self, shape: DebugShape, color: Color, duration: f32) {
+        if self.enabled {
+            self.items.push(DebugDrawItem {
+                shape,
+                color,
+                time_left: duration.max(0.0),
+            });
+        }
+    }
+
+    /// Draws a line between two points.
+    pub fn line(&mut self, begin: Vector3<f32>, end: Vector3<f32>, color: Color, duration: f32) {
+        self.add(DebugShape::Line { begin, end }, color, duration)
+    }
+
+    /// Draws an arrow, that points from `begin` to `end`.
+    pub fn arrow(&mut self, begin: Vector3<f32>, end: Vector3<f32>, color: Color, duration: f32) {
+        self.add(DebugShape::Arrow { begin, end }, color, duration)
+    }
+
+    /// Draws an axis-aligned box.
+    pub fn aabb(&mut self, aabb: AxisAlignedBoundingBox, color: Color, duration: f32) {
+        self.add(DebugShape::Aabb(aabb), color, duration)
+    }
+
+    /// Draws a wireframe sphere.
+    pub fn sphere(&mut self, center: Vector3<f32>, radius: f32, color: Color, duration: f32) {
+        self.add(DebugShape::Sphere { center, radius }, color, duration)
+    }
+
+    /// Draws the basis of the given transform. The color is ignored, axes are always drawn with
+    /// red, green and blue colors.
+    pub fn transform(&mut self, transform: Matrix4<f32>, duration: f32) {
+        self.add(DebugShape::Transform(transform), Color::WHITE, duration)
+    }
+
+    /// Draws camera-facing text centered at the given position. `height` is the height of a line of
+    /// the text in world units.
+    pub fn text(
+        &mut self,
+        position: Vector3<f32>,
+        text: impl Into<String>,
+        height: f32,
+        color: Color,
+        duration: f32,
+    ) {
+        self.add(
+            DebugShape::Text {
+                position,
+                text: text.into(),
+                height,
+            },
+            color,
+            duration,
+        )
+    }
+
+    /// Returns a slice with every shape, that will be drawn.
+    pub fn items(&self) -> &[DebugDrawItem] {
+        &self.items
+    }
+
+    /// Removes every shape.
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Removes expired shapes and decreases the remaining time of other shapes. It is called
+    /// automatically by the scene at the beginning of each frame, shapes with zero duration, that
+    /// were added during the previous frame, are removed here.
+    pub fn update(&mut self, dt: f32) {
+        self.items.retain_mut(|item| {
+            if item.time_left <= 0.0 {
+                false
+            } else {
+                item.time_left -= dt;
+                true
+            }
+        });
+    }
+
+    /// Converts every shape to lines and puts them in the given drawing context. The view matrix is
+    /// used to orient text towards the camera.
+    pub fn collect_lines(&self, view_matrix: &Matrix4<f32>, ctx: &mut SceneDrawingContext) {
+        if !self.enabled {
+            return;
+        }
+
+        let inv_view = view_matrix.try_inverse().unwrap_or_default();
+        let side = inv_view
+            .side()
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_default();
+        let up = inv_view
+            .up()
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_default();
+
+        for item in self.items.iter() {
+            let color = item.color;
+            match item.shape {
+                DebugShape::Line { begin, end } => ctx.add_line(Line { begin, end, color }),
+                DebugShape::Arrow { begin, end } => {
+                    ctx.add_line(Line { begin, end, color });
+                    let direction = end - begin;
+                    let length = direction.norm();
+                    if length > f32::EPSILON {
+                        let direction = direction.scale(1.0 / length);
+                        // Any vector, that is not collinear with the direction.
+                        let reference = if direction.y.abs() < 0.9 {
+                            Vector3::y()
+                        } else {
+                            Vector3::x()
+                        };
+                        let a = direction.cross(&reference).normalize();
+                        let b = direction.cross(&a);
+                        let head_length = length * 0.2;
+                        let base = end - direction.scale(head_length);
+                        for offset in [a, -a, b, -b] {
+                            ctx.add_line(Line {
+                                begin: end,
+                                end: base + offset.scale(head_length * 0.5),
+                                color,
+                            });
+                        }
+                    }
+                }
+                DebugShape::Aabb(ref aabb) => ctx.draw_aabb(aabb, color),
+                DebugShape::Sphere { center, radius } => {
+                    ctx.draw_wire_sphere(center, radius, SPHERE_SEGMENTS, color)
+                }
+                DebugShape::Transform(transform) => ctx.draw_transform(transform),
+                DebugShape::Text {
+                    position,
+                    ref text,
+                    height,
+                } => draw_text(ctx, position, side, up, height, text, color),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Matrix4, algebra::Vector3, color::Color},
+        scene::{
+            debug::SceneDrawingContext,
+            debug_draw::{DebugDraw, FONT_SEGMENTS},
+        },
+    };
+
+    fn enabled() -> DebugDraw {
+        let mut debug_draw = DebugDraw::default();
+        debug_draw.set_enabled(true);
+        debug_draw
+    }
+
+    #[test]
+    fn test_durations() {
+        let mut debug_draw = enabled();
+        debug_draw.line(Vector3::default(), Vector3::x(), Color::RED, 0.0);
+        debug_draw.line(Vector3::default(), Vector3::y(), Color::RED, 0.25);
+        debug_draw.line(Vector3::default(), Vector3::z(), Color::RED, f32::INFINITY);
+
+        // Immediate shape is removed at the beginning of the next frame.
+        debug_draw.update(0.1);
+        assert_eq!(debug_draw.items().len(), 2);
+        debug_draw.update(0.1);
+        debug_draw.update(0.1);
+        assert_eq!(debug_draw.items().len(), 2);
+        debug_draw.update(0.1);
+        assert_eq!(debug_draw.items().len(), 1);
+        for _ in 0..100 {
+            debug_draw.update(1.0);
+        }
+        assert_eq!(debug_draw.items().len(), 1);
+        debug_draw.clear();
+        assert!(debug_draw.items().is_empty());
+
+        debug_draw.set_enabled(false);
+        debug_draw.sphere(Vector3::default(), 1.0, Color::GREEN, 1.0);
+        assert!(debug_draw.items().is_empty());
+    }
+
+    #[test]
+    fn test_collect_lines() {
+        let mut debug_draw = enabled();
+        debug_draw.arrow(Vector3::default(), Vector3::x(), Color::RED, 0.0);
+        debug_draw.text(Vector3::default(), "Hi 1", 1.0, Color::WHITE, 0.0);
+
+        let mut ctx = SceneDrawingContext::default();
+        debug_draw.collect_lines(&Matrix4::identity(), &mut ctx);
+        // Arrow: body and four lines of the head. Text: "H" - 6 lines, "I" - 6 lines, "1" - 2
+        // lines, space has no lines.
+        assert_eq!(ctx.lines.len(), 5 + 14);
+
+        // Text is centered.
+        let text_lines = &ctx.lines[5..];
+        let min_x = text_lines
+            .iter()
+            .map(|l| l.begin.x.min(l.end.x))
+            .fold(f32::MAX, f32::min);
+        let max_x = text_lines
+            .iter()
+            .map(|l| l.begin.x.max(l.end.x))
+            .fold(f32::MIN, f32::max);
+        assert_eq!(min_x, -max_x);
+        assert_eq!(FONT_SEGMENTS.len(), (b't' - b'a' + 1) as usize);
+    }
+}
@@ -149,6 +149,17 @@ pub struct HeightfieldShape {
     pub geometry_source: GeometrySource,
 }
 
+/// A shape, that consists of merged solid tiles of a tile map. Solid tiles (tiles with
+/// [`crate::scene::tilemap::tileset::TileCollider::Rectangle`] collider) of every chunk of the tile map
+/// are merged into a few rectangles, so even huge tile maps produce a single collider with a small
+/// amount of sub-shapes. The shape is rebuilt automatically when tiles of the tile map change, only
+/// changed chunks are re-merged (see [`crate::scene::tilemap::TileMap::set_tile`]).
+#[derive(Default, Clone, Debug, PartialEq, Visit, Reflect, Eq)]
+pub struct TileMapShape {
+    /// A handle to tile map scene node.
+    pub tile_map: GeometrySource,
+}
+
 /// Possible collider shapes.
 #[derive(Clone, Debug, Visit, Reflect, AsRefStr, PartialEq, EnumString, VariantNames)]
 pub enum ColliderShape {
@@ -166,6 +177,8 @@ pub enum ColliderShape {
     Trimesh(TrimeshShape),
     /// See [`HeightfieldShape`] docs.
     Heightfield(HeightfieldShape),
+    /// See [`TileMapShape`] docs.
+    TileMap(TileMapShape),
 }
 
 uuid_provider!(ColliderShape = "4615485f-f8db-4405-b4a5-437e74b3f5b8");
@@ -228,6 +241,11 @@ impl ColliderShape {
     pub fn heightfield(geometry_source: GeometrySource) -> Self {
         Self::Heightfield(HeightfieldShape { geometry_source })
     }
+
+    /// Initializes a tile map shape defined by a handle to tile map node.
+    pub fn tile_map(tile_map: GeometrySource) -> Self {
+        Self::TileMap(TileMapShape { tile_map })
+    }
 }
 
 /// Collider is a geometric entity that can be attached to a rigid body to allow participate it
@@ -266,6 +284,10 @@ pub struct Collider {
     #[visit(skip)]
     #[reflect(hidden)]
     pub(crate) native: Cell<ColliderHandle>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) tile_map_revision: Cell<u64>,
 }
 
 impl Default for Collider {
@@ -282,6 +304,7 @@ impl Default for Collider {
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
             native: Cell::new(ColliderHandle::invalid()),
+            tile_map_revision: Default::default(),
         }
     }
 }
@@ -315,6 +338,7 @@ impl Clone for Collider {
             restitution_combine_rule: self.restitution_combine_rule.clone(),
             // Do not copy. The copy will have its own native representation.
             native: Cell::new(ColliderHandle::invalid()),
+            tile_map_revision: Default::default(),
         }
     }
 }
@@ -691,6 +715,7 @@ impl ColliderBuilder {
             friction_combine_rule: self.friction_combine_rule.into(),
             restitution_combine_rule: self.restitution_combine_rule.into(),
             native: Cell::new(ColliderHandle::invalid()),
+            tile_map_revision: Default::default(),
         }
     }
 
@@ -708,15 +733,17 @@ impl ColliderBuilder {
 #[cfg(test)]
 mod test {
 
-    use crate::core::algebra::Vector2;
+    use crate::core::algebra::{Point2, Vector2};
     use crate::scene::{
         base::BaseBuilder,
         dim2::{
-            collider::{ColliderBuilder, ColliderShape},
+            collider::{ColliderBuilder, ColliderShape, GeometrySource},
+            physics::RayCastOptions,
             rigidbody::RigidBodyBuilder,
         },
         graph::Graph,
         rigidbody::RigidBodyType,
+        tilemap::{Tile, TileMap, TileMapBuilder},
     };
 
     #[test]
@@ -776,4 +803,64 @@ mod test {
                 .count()
         );
     }
+
+    #[test]
+    fn test_tile_map_shape() {
+        let mut graph = Graph::new();
+
+        let tile_map = TileMapBuilder::new(BaseBuilder::new())
+            .with_tiles(vec![
+                Tile::new(Vector2::new(0, 0), 0),
+                Tile::new(Vector2::new(1, 0), 0),
+            ])
+            .build(&mut graph);
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::tile_map(GeometrySource(tile_map)))
+            .build(&mut graph);
+
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[collider]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+        let hits = |graph: &Graph, x: f32| {
+            let mut intersections = Vec::new();
+            graph.physics2d.cast_ray(
+                RayCastOptions {
+                    ray_origin: Point2::new(x, 5.0),
+                    ray_direction: Vector2::new(0.0, -1.0),
+                    max_len: 10.0,
+                    groups: Default::default(),
+                    sort_results: true,
+                },
+                &mut intersections,
+            );
+            intersections
+        };
+
+        // Need to call two times for the physics engine to execute.
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+
+        let intersections = hits(&graph, 1.5);
+        assert_eq!(intersections.len(), 1);
+        assert!((intersections[0].position.y - 1.0).abs() < 0.001);
+        assert!(hits(&graph, 3.5).is_empty());
+
+        graph[tile_map]
+            .cast_mut::<TileMap>()
+            .unwrap()
+            .set_tile(Vector2::new(3, 0), Some(0));
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+
+        assert_eq!(hits(&graph, 3.5).len(), 1);
+
+        graph[tile_map]
+            .cast_mut::<TileMap>()
+            .unwrap()
+            .set_tiles(Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+
+        assert!(hits(&graph, 1.5).is_empty());
+    }
 }
@@ -0,0 +1,434 @@
+//! 2D light is a light source, that lights sprites, skinned sprites and tile maps in the XY plane. See
+//! [`Light2D`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::Vector3, color::Color, math::aabb::AxisAlignedBoundingBox, pool::Handle,
+        reflect::prelude::*, type_traits::prelude::*, uuid_provider, variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        debug::SceneDrawingContext,
+        graph::Graph,
+        node::{Node, NodeTrait},
+    },
+};
+use fyrox_graph::BaseSceneGraph;
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Defines the shape of the lit area of a 2D light.
+#[derive(
+    Copy, Clone, Default, Debug, PartialEq, Eq, Reflect, Visit, AsRefStr, EnumString, VariantNames,
+)]
+pub enum Light2DKind {
+    /// The light emits light in all directions.
+    #[default]
+    Point,
+    /// The light emits light in a cone, which is directed along the local Y axis of the light.
+    Spot,
+}
+
+uuid_provider!(Light2DKind = "36cfea57-5dc3-43aa-b77d-9738d35b5b9f");
+
+/// 2D light is a light source, that lights everything that uses the standard 2D shader (rectangles, skinned
+/// sprites and tile maps). Intensity of the light decreases from the center to the [`Light2D::radius`] using
+/// a configurable falloff. The light can be either a point or a spot light (see [`Light2DKind`]).
+///
+/// ## Normal mapping
+///
+/// The standard 2D shader has `normalTexture` property, that could be used to give sprites a sense of depth.
+/// 2D lights are placed above the XY plane at the [`Light2D::height`], so flat areas of a sprite are lit
+/// more, when the light is close to them.
+///
+/// ## Shadows
+///
+/// A 2D light could cast soft shadows from [`super::occluder::Occluder2D`] nodes. Shadows are calculated per
+/// pixel by tracing rays from the light to the pixel, so keep the amount of occluder segments near lights
+/// reasonable. Shadow softness defines the size of the light source, larger sources produce wider penumbras.
+///
+/// ## Limitations
+///
+/// All 2D lights are rendered in the forward pass, which supports a limited amount of lights per frame (16 at
+/// the moment, including 3D lights) and a limited amount of occluder segments (see
+/// [`crate::renderer::MAX_OCCLUDER_SEGMENTS`]).
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::{color::Color, pool::Handle},
+/// #     scene::{base::BaseBuilder, dim2::light::Light2DBuilder, graph::Graph, node::Node},
+/// # };
+/// fn create_torch(graph: &mut Graph) -> Handle<Node> {
+///     Light2DBuilder::new(BaseBuilder::new())
+///         .with_color(Color::opaque(255, 180, 90))
+///         .with_radius(6.0)
+///         .with_falloff(2.0)
+///         .with_cast_shadows(true)
+///         .with_shadow_softness(0.25)
+///         .build(graph)
+/// }
+/// ```
+#[derive(Clone, Debug, Reflect, Visit, ComponentProvider)]
+pub struct Light2D {
+    base: Base,
+
+    #[reflect(setter = "set_kind")]
+    kind: InheritableVariable<Light2DKind>,
+
+    #[reflect(setter = "set_color")]
+    color: InheritableVariable<Color>,
+
+    #[reflect(min_value = 0.0, step = 0.1)]
+    #[reflect(setter = "set_intensity")]
+    intensity: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.1)]
+    #[reflect(setter = "set_radius")]
+    radius: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.01, step = 0.1)]
+    #[reflect(setter = "set_falloff")]
+    falloff: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.1)]
+    #[reflect(setter = "set_height")]
+    height: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, max_value = 6.2831, step = 0.1)]
+    #[reflect(setter = "set_hotspot_cone_angle")]
+    hotspot_cone_angle: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.1)]
+    #[reflect(setter = "set_falloff_angle_delta")]
+    falloff_angle_delta: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_cast_shadows")]
+    cast_shadows: InheritableVariable<bool>,
+
+    #[reflect(min_value = 0.0, step = 0.05)]
+    #[reflect(setter = "set_shadow_softness")]
+    shadow_softness: InheritableVariable<f32>,
+}
+
+impl Default for Light2D {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            kind: Default::default(),
+            color: Color::WHITE.into(),
+            intensity: 1.0.into(),
+            radius: 5.0.into(),
+            falloff: 2.0.into(),
+            height: 1.0.into(),
+            hotspot_cone_angle: std::f32::consts::FRAC_PI_2.into(),
+            falloff_angle_delta: 0.2.into(),
+            cast_shadows: true.into(),
+            shadow_softness: 0.1.into(),
+        }
+    }
+}
+
+impl Deref for Light2D {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Light2D {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for Light2D {
+    fn type_uuid() -> Uuid {
+        uuid!("8996a079-5327-42cd-93c4-323c4cdc1503")
+    }
+}
+
+impl Light2D {
+    /// Sets new kind of the light.
+    pub fn set_kind(&mut self, kind: Light2DKind) -> Light2DKind {
+        self.kind.set_value_and_mark_modified(kind)
+    }
+
+    /// Returns current kind of the light.
+    pub fn kind(&self) -> Light2DKind {
+        *self.kind
+    }
+
+    /// Sets new color of the light.
+    pub fn set_color(&mut self, color: Color) -> Color {
+        self.color.set_value_and_mark_modified(color)
+    }
+
+    /// Returns current color of the light.
+    pub fn color(&self) -> Color {
+        *self.color
+    }
+
+    /// Sets new intensity of the light. Intensity is a multiplier for the color of the light, it could be
+    /// greater than one.
+    pub fn set_intensity(&mut self, intensity: f32) -> f32 {
+        self.intensity
+            .set_value_and_mark_modified(intensity.max(0.0))
+    }
+
+    /// Returns current intensity of the light.
+    pub fn intensity(&self) -> f32 {
+        *self.intensity
+    }
+
+    /// Sets new radius of the light. The light does not affect anything outside of the radius.
+    pub fn set_radius(&mut self, radius: f32) -> f32 {
+        self.radius.set_value_and_mark_modified(radius.abs())
+    }
+
+    /// Returns current radius of the light.
+    pub fn radius(&self) -> f32 {
+        *self.radius
+    }
+
+    /// Sets new falloff exponent of the light. Intensity at distance `d` is `(1 - d / radius) ^ falloff`,
+    /// which means that `1.0` gives linear falloff, and higher values make the light more concentrated
+    /// near its center.
+    pub fn set_falloff(&mut self, falloff: f32) -> f32 {
+        self.falloff.set_value_and_mark_modified(falloff.max(0.01))
+    }
+
+    /// Returns current falloff exponent of the light.
+    pub fn falloff(&self) -> f32 {
+        *self.falloff
+    }
+
+    /// Sets new height of the light above the XY plane. It is used only for normal mapping, higher values
+    /// make normal maps less pronounced. Zero height disables normal mapping for this light.
+    pub fn set_height(&mut self, height: f32) -> f32 {
+        self.height.set_value_and_mark_modified(height.max(0.0))
+    }
+
+    /// Returns current height of the light.
+    pub fn height(&self) -> f32 {
+        *self.height
+    }
+
+    /// Sets new hotspot angle of the spot light. The light has full intensity inside the hotspot.
+    pub fn set_hotspot_cone_angle(&mut self, angle: f32) -> f32 {
+        self.hotspot_cone_angle
+            .set_value_and_mark_modified(angle.abs())
+    }
+
+    /// Returns current hotspot angle of the spot light.
+    pub fn hotspot_cone_angle(&self) -> f32 {
+        *self.hotspot_cone_angle
+    }
+
+    /// Sets new falloff angle range of the spot light. Intensity of the light smoothly decreases from the
+    /// hotspot to the edge of the full cone.
+    pub fn set_falloff_angle_delta(&mut self, delta: f32) -> f32 {
+        self.falloff_angle_delta
+            .set_value_and_mark_modified(delta.abs())
+    }
+
+    /// Returns current falloff angle range of the spot light.
+    pub fn falloff_angle_delta(&self) -> f32 {
+        *self.falloff_angle_delta
+    }
+
+    /// Returns full angle of the cone of the spot light.
+    pub fn full_cone_angle(&self) -> f32 {
+        *self.hotspot_cone_angle + *self.falloff_angle_delta
+    }
+
+    /// Enables or disables shadows from [`super::occluder::Occluder2D`] nodes.
+    pub fn set_cast_shadows(&mut self, cast_shadows: bool) -> bool {
+        self.cast_shadows.set_value_and_mark_modified(cast_shadows)
+    }
+
+    /// Returns `true` if the light casts shadows, `false` - otherwise.
+    pub fn is_cast_shadows(&self) -> bool {
+        *self.cast_shadows
+    }
+
+    /// Sets new size of the light source, that is used to calculate soft shadows. Zero gives hard shadows.
+    pub fn set_shadow_softness(&mut self, softness: f32) -> f32 {
+        self.shadow_softness
+            .set_value_and_mark_modified(softness.max(0.0))
+    }
+
+    /// Returns current size of the light source, that is used to calculate soft shadows.
+    pub fn shadow_softness(&self) -> f32 {
+        *self.shadow_softness
+    }
+}
+
+impl NodeTrait for Light2D {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        let radius = *self.radius;
+        AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(-radius, -radius, -0.5),
+            Vector3::new(radius, radius, 0.5),
+        )
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        let transform = self.global_transform();
+        match *self.kind {
+            Light2DKind::Point => ctx.draw_circle(
+                Default::default(),
+                *self.radius,
+                32,
+                transform,
+                Color::GREEN,
+            ),
+            Light2DKind::Spot => {
+                let half_angle = self.full_cone_angle() * 0.5;
+                ctx.draw_circle_segment(
+                    Default::default(),
+                    *self.radius,
+                    32,
+                    std::f32::consts::FRAC_PI_2 - half_angle,
+                    std::f32::consts::FRAC_PI_2 + half_angle,
+                    transform,
+                    Color::GREEN,
+                )
+            }
+        }
+    }
+}
+
+/// Allows you to create 2D lights in declarative manner.
+pub struct Light2DBuilder {
+    base_builder: BaseBuilder,
+    kind: Light2DKind,
+    color: Color,
+    intensity: f32,
+    radius: f32,
+    falloff: f32,
+    height: f32,
+    hotspot_cone_angle: f32,
+    falloff_angle_delta: f32,
+    cast_shadows: bool,
+    shadow_softness: f32,
+}
+
+impl Light2DBuilder {
+    /// Creates new builder instance.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        let default = Light2D::default();
+        Self {
+            base_builder,
+            kind: *default.kind,
+            color: *default.color,
+            intensity: *default.intensity,
+            radius: *default.radius,
+            falloff: *default.falloff,
+            height: *default.height,
+            hotspot_cone_angle: *default.hotspot_cone_angle,
+            falloff_angle_delta: *default.falloff_angle_delta,
+            cast_shadows: *default.cast_shadows,
+            shadow_softness: *default.shadow_softness,
+        }
+    }
+
+    /// Sets desired kind of the light.
+    pub fn with_kind(mut self, kind: Light2DKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets desired color of the light.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets desired intensity of the light.
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    /// Sets desired radius of the light.
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets desired falloff exponent of the light.
+    pub fn with_falloff(mut self, falloff: f32) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Sets desired height of the light above the XY plane.
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets desired hotspot angle of the spot light.
+    pub fn with_hotspot_cone_angle(mut self, angle: f32) -> Self {
+        self.hotspot_cone_angle = angle;
+        self
+    }
+
+    /// Sets desired falloff angle range of the spot light.
+    pub fn with_falloff_angle_delta(mut self, delta: f32) -> Self {
+        self.falloff_angle_delta = delta;
+        self
+    }
+
+    /// Enables or disables shadows.
+    pub fn with_cast_shadows(mut self, cast_shadows: bool) -> Self {
+        self.cast_shadows = cast_shadows;
+        self
+    }
+
+    /// Sets desired size of the light source, that is used to calculate soft shadows.
+    pub fn with_shadow_softness(mut self, softness: f32) -> Self {
+        self.shadow_softness = softness;
+        self
+    }
+
+    /// Creates new 2D light.
+    pub fn build_light(self) -> Light2D {
+        Light2D {
+            base: self.base_builder.build_base(),
+            kind: self.kind.into(),
+            color: self.color.into(),
+            intensity: self.intensity.max(0.0).into(),
+            radius: self.radius.abs().into(),
+            falloff: self.falloff.max(0.01).into(),
+            height: self.height.max(0.0).into(),
+            hotspot_cone_angle: self.hotspot_cone_angle.abs().into(),
+            falloff_angle_delta: self.falloff_angle_delta.abs().into(),
+            cast_shadows: self.cast_shadows.into(),
+            shadow_softness: self.shadow_softness.max(0.0).into(),
+        }
+    }
+
+    /// Creates new 2D light node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_light())
+    }
+
+    /// Creates new 2D light node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
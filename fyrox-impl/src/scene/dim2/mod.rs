@@ -3,6 +3,10 @@
 
 pub mod collider;
 pub mod joint;
+pub mod light;
+pub mod occluder;
+pub mod parallax;
 pub mod physics;
 pub mod rectangle;
 pub mod rigidbody;
+pub mod skin;
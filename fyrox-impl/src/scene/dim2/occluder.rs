@@ -0,0 +1,346 @@
+//! 2D occluder is a set of segments, that blocks light from [`super::light::Light2D`]. See [`Occluder2D`]
+//! docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Point3, Vector2},
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        debug::{Line, SceneDrawingContext},
+        graph::Graph,
+        node::{Node, NodeTrait},
+        tilemap::TileMap,
+    },
+};
+use fyrox_graph::BaseSceneGraph;
+use std::ops::{Deref, DerefMut};
+
+/// A segment of an occluder in the local space of the occluder node.
+#[derive(Clone, Default, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "70274e90-959d-4108-bd94-d5aeacef2425")]
+pub struct OccluderSegment {
+    /// Beginning of the segment.
+    pub begin: Vector2<f32>,
+    /// End of the segment.
+    pub end: Vector2<f32>,
+}
+
+impl OccluderSegment {
+    /// Creates new segment.
+    pub fn new(begin: Vector2<f32>, end: Vector2<f32>) -> Self {
+        Self { begin, end }
+    }
+
+    /// Returns the outward normal of the segment (not normalized). Segments of counter-clockwise polygons
+    /// have their normals directed outside of the polygon.
+    pub fn normal(&self) -> Vector2<f32> {
+        let edge = self.end - self.begin;
+        Vector2::new(edge.y, -edge.x)
+    }
+
+    /// Returns a copy of the segment with swapped ends.
+    pub fn reversed(&self) -> Self {
+        Self {
+            begin: self.end,
+            end: self.begin,
+        }
+    }
+}
+
+/// 2D occluder is a set of segments, that blocks light from [`super::light::Light2D`] nodes, which have shadow
+/// casting enabled.
+///
+/// ## Winding
+///
+/// Only the segments, which face away from a light, cast shadows. This way closed shapes do not shadow their
+/// own insides (which is usually covered by a sprite), but everything that is behind them. This requires
+/// shapes to be defined in counter-clockwise order. Use [`Occluder2D::set_two_sided`] for open shapes (a
+/// fence, for example), which should block light from both sides.
+///
+/// ## Tile maps
+///
+/// Occluders can be derived from tile colliders of a tile map using [`tile_map_outline`] or
+/// [`Occluder2DBuilder::with_tile_map_outline`]. The occluder should be a child of the tile map with the
+/// identity local transform, so the outline matches the tiles.
+#[derive(Clone, Debug, Reflect, Visit, ComponentProvider)]
+pub struct Occluder2D {
+    base: Base,
+
+    #[reflect(setter = "set_segments")]
+    segments: InheritableVariable<Vec<OccluderSegment>>,
+
+    #[reflect(setter = "set_two_sided")]
+    two_sided: InheritableVariable<bool>,
+}
+
+impl Default for Occluder2D {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            segments: Default::default(),
+            two_sided: false.into(),
+        }
+    }
+}
+
+impl Deref for Occluder2D {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Occluder2D {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for Occluder2D {
+    fn type_uuid() -> Uuid {
+        uuid!("24ee3e10-85e6-49c5-a4e3-d56fc51955fc")
+    }
+}
+
+impl Occluder2D {
+    /// Sets new set of segments of the occluder.
+    pub fn set_segments(&mut self, segments: Vec<OccluderSegment>) -> Vec<OccluderSegment> {
+        self.segments.set_value_and_mark_modified(segments)
+    }
+
+    /// Returns a slice with the segments of the occluder.
+    pub fn segments(&self) -> &[OccluderSegment] {
+        &self.segments
+    }
+
+    /// Defines whether the occluder blocks light from both sides of its segments or not.
+    pub fn set_two_sided(&mut self, two_sided: bool) -> bool {
+        self.two_sided.set_value_and_mark_modified(two_sided)
+    }
+
+    /// Returns `true` if the occluder blocks light from both sides of its segments, `false` - otherwise.
+    pub fn is_two_sided(&self) -> bool {
+        *self.two_sided
+    }
+
+    /// Returns an iterator over the segments of the occluder in world space. Two-sided occluders yield every
+    /// segment twice with opposite directions.
+    pub fn world_segments(&self) -> impl Iterator<Item = OccluderSegment> + '_ {
+        let transform = self.global_transform();
+        let two_sided = *self.two_sided;
+        self.segments.iter().flat_map(move |segment| {
+            let transform_point = |p: Vector2<f32>| {
+                transform
+                    .transform_point(&Point3::new(p.x, p.y, 0.0))
+                    .coords
+                    .xy()
+            };
+            let world =
+                OccluderSegment::new(transform_point(segment.begin), transform_point(segment.end));
+            let reversed = two_sided.then(|| world.reversed());
+            std::iter::once(world).chain(reversed)
+        })
+    }
+}
+
+impl NodeTrait for Occluder2D {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        let mut aabb = AxisAlignedBoundingBox::default();
+        for segment in self.segments.iter() {
+            aabb.add_point(segment.begin.to_homogeneous());
+            aabb.add_point(segment.end.to_homogeneous());
+        }
+        aabb
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        for segment in self.world_segments() {
+            ctx.add_line(Line {
+                begin: segment.begin.to_homogeneous(),
+                end: segment.end.to_homogeneous(),
+                color: Color::DARK_ORANGE,
+            });
+
+            // Show the side, that casts shadows.
+            let center = (segment.begin + segment.end).scale(0.5);
+            let normal = segment
+                .normal()
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default();
+            ctx.add_line(Line {
+                begin: center.to_homogeneous(),
+                end: (center - normal.scale(0.1)).to_homogeneous(),
+                color: Color::DARK_ORANGE,
+            });
+        }
+    }
+}
+
+/// Creates a set of segments, that outlines all tiles with rectangle colliders of the given tile map (see
+/// [`TileMap::solid_tile_positions`]). Segments are defined in the local space of the tile map and in
+/// counter-clockwise order. Edges between adjacent tiles are skipped, so only the outer contour of solid
+/// areas blocks light. Mesh colliders are ignored, because they do not have any geometry yet.
+pub fn tile_map_outline(tile_map: &TileMap) -> Vec<OccluderSegment> {
+    let solid = tile_map.solid_tile_positions();
+
+    let mut segments = Vec::new();
+    for position in solid.iter() {
+        let p = position.cast::<f32>();
+        let corners = [
+            p,
+            p + Vector2::new(1.0, 0.0),
+            p + Vector2::new(1.0, 1.0),
+            p + Vector2::new(0.0, 1.0),
+        ];
+        let neighbours = [
+            Vector2::new(0, -1),
+            Vector2::new(1, 0),
+            Vector2::new(0, 1),
+            Vector2::new(-1, 0),
+        ];
+        for (i, offset) in neighbours.iter().enumerate() {
+            if !solid.contains(&(position + offset)) {
+                segments.push(OccluderSegment::new(corners[i], corners[(i + 1) % 4]));
+            }
+        }
+    }
+    segments
+}
+
+/// Allows you to create 2D occluders in declarative manner.
+pub struct Occluder2DBuilder {
+    base_builder: BaseBuilder,
+    segments: Vec<OccluderSegment>,
+    two_sided: bool,
+}
+
+impl Occluder2DBuilder {
+    /// Creates new builder instance.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            segments: Default::default(),
+            two_sided: false,
+        }
+    }
+
+    /// Sets desired segments of the occluder.
+    pub fn with_segments(mut self, segments: Vec<OccluderSegment>) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets segments of the occluder from a closed polygon. Points must be in counter-clockwise order.
+    pub fn with_polygon(mut self, points: &[Vector2<f32>]) -> Self {
+        self.segments = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(begin, end)| OccluderSegment::new(*begin, *end))
+            .collect();
+        self
+    }
+
+    /// Sets segments of the occluder from a rectangle with the given size, centered at the origin.
+    pub fn with_rectangle(self, size: Vector2<f32>) -> Self {
+        let half = size.scale(0.5);
+        self.with_polygon(&[
+            Vector2::new(-half.x, -half.y),
+            Vector2::new(half.x, -half.y),
+            Vector2::new(half.x, half.y),
+            Vector2::new(-half.x, half.y),
+        ])
+    }
+
+    /// Sets segments of the occluder from the tile colliders of the given tile map. See [`tile_map_outline`]
+    /// for more info.
+    pub fn with_tile_map_outline(mut self, tile_map: &TileMap) -> Self {
+        self.segments = tile_map_outline(tile_map);
+        self
+    }
+
+    /// Defines whether the occluder blocks light from both sides of its segments or not.
+    pub fn with_two_sided(mut self, two_sided: bool) -> Self {
+        self.two_sided = two_sided;
+        self
+    }
+
+    /// Creates new 2D occluder.
+    pub fn build_occluder(self) -> Occluder2D {
+        Occluder2D {
+            base: self.base_builder.build_base(),
+            segments: self.segments.into(),
+            two_sided: self.two_sided.into(),
+        }
+    }
+
+    /// Creates new 2D occluder node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_occluder())
+    }
+
+    /// Creates new 2D occluder node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        scene::{
+            base::BaseBuilder,
+            dim2::occluder::{tile_map_outline, OccluderSegment},
+            tilemap::{Tile, TileMapBuilder},
+        },
+    };
+
+    #[test]
+    fn test_tile_map_outline() {
+        // Two adjacent tiles form a 2x1 rectangle, the shared edge must be skipped.
+        let tile_map = TileMapBuilder::new(BaseBuilder::new())
+            .with_tiles(vec![
+                Tile::new(Vector2::new(0, 0), 0),
+                Tile::new(Vector2::new(1, 0), 0),
+            ])
+            .build_tile_map();
+
+        let outline = tile_map_outline(&tile_map);
+        assert_eq!(outline.len(), 6);
+        assert!(!outline.contains(&OccluderSegment::new(
+            Vector2::new(1.0, 0.0),
+            Vector2::new(1.0, 1.0)
+        )));
+        assert!(!outline.contains(&OccluderSegment::new(
+            Vector2::new(1.0, 1.0),
+            Vector2::new(1.0, 0.0)
+        )));
+
+        // Every normal must point outside.
+        let center = Vector2::new(1.0, 0.5);
+        for segment in outline {
+            let mid = (segment.begin + segment.end).scale(0.5);
+            assert!(segment.normal().dot(&(mid - center)) > 0.0);
+        }
+    }
+}
@@ -0,0 +1,451 @@
+//! Parallax layer scrolls its descendants relative to the camera to create an illusion of depth in 2D scenes.
+//! See [`ParallaxLayer`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector2, Vector3},
+        math::{aabb::AxisAlignedBoundingBox, frustum::Frustum},
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    material::MaterialResource,
+    renderer::bundle::{RenderContext, RenderDataBundleStorageTrait, SurfaceInstanceData},
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        mesh::{
+            buffer::{
+                TriangleBufferRefMut, VertexAttributeDescriptor, VertexAttributeUsage,
+                VertexBufferRefMut, VertexReadTrait, VertexWriteTrait,
+            },
+            surface::SurfaceResource,
+            RenderPath,
+        },
+        node::{Node, NodeTrait, RdcControlFlow},
+    },
+};
+use fyrox_graph::BaseSceneGraph;
+use std::ops::{Deref, DerefMut};
+
+/// Maximum amount of copies of a repeated layer, that could be rendered in a single frame.
+const MAX_COPIES: usize = 256;
+
+/// Parallax layer scrolls its descendants (sprites, tile maps, etc.) with a configurable ratio relative to the
+/// camera, which creates an illusion of depth in 2D scenes. Distant backgrounds should have small ratios, so
+/// they move slower than the rest of the world.
+///
+/// ## Scrolling
+///
+/// The offset of the layer is calculated as `(camera - layer) * (1 - scroll_ratio)` (per axis), where
+/// `camera` and `layer` are global positions of the camera and the layer node respectively. It means that the
+/// content of the layer is rendered at its actual position, when the camera is right above the layer. A ratio
+/// of `1.0` makes the content to move together with the world, and a ratio of `0.0` attaches the content to the
+/// camera.
+///
+/// ## Repeat
+///
+/// The layer could be repeated infinitely along X and/or Y axes. The content of the layer must fit into a
+/// rectangle of [`ParallaxLayer::repeat_size`] size, centered at the layer's position, and the layer renders
+/// as many copies of the content as needed to fill the view.
+///
+/// ## Important notes
+///
+/// The offset is applied at render time only, which means that it does not change actual positions of the
+/// descendant nodes. Physics, picking in the editor and other things, that use global positions of the nodes
+/// are not affected by the layer. The offset is calculated for every observer, so the editor camera shows the
+/// actual parallax effect as it moves.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::{algebra::Vector2, pool::Handle},
+/// #     scene::{base::BaseBuilder, dim2::parallax::ParallaxLayerBuilder, graph::Graph, node::Node},
+/// # };
+/// fn create_mountains(mountains: Handle<Node>, graph: &mut Graph) -> Handle<Node> {
+///     ParallaxLayerBuilder::new(BaseBuilder::new().with_children(&[mountains]))
+///         .with_scroll_ratio(Vector2::new(0.25, 1.0))
+///         .with_repeat_x(true)
+///         .with_repeat_size(Vector2::new(20.0, 10.0))
+///         .build(graph)
+/// }
+/// ```
+#[derive(Clone, Debug, Reflect, Visit, ComponentProvider)]
+pub struct ParallaxLayer {
+    base: Base,
+
+    #[reflect(setter = "set_scroll_ratio")]
+    scroll_ratio: InheritableVariable<Vector2<f32>>,
+
+    #[reflect(setter = "set_repeat_x")]
+    repeat_x: InheritableVariable<bool>,
+
+    #[reflect(setter = "set_repeat_y")]
+    repeat_y: InheritableVariable<bool>,
+
+    #[reflect(min_value = 0.01, step = 0.1)]
+    #[reflect(setter = "set_repeat_size")]
+    repeat_size: InheritableVariable<Vector2<f32>>,
+}
+
+impl Default for ParallaxLayer {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            scroll_ratio: Vector2::repeat(0.5).into(),
+            repeat_x: false.into(),
+            repeat_y: false.into(),
+            repeat_size: Vector2::repeat(10.0).into(),
+        }
+    }
+}
+
+impl Deref for ParallaxLayer {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for ParallaxLayer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for ParallaxLayer {
+    fn type_uuid() -> Uuid {
+        uuid!("5f0c2b8e-3d1a-4c6f-9e27-8b4a1d7c5e93")
+    }
+}
+
+impl ParallaxLayer {
+    /// Sets new scroll ratio of the layer. See [`ParallaxLayer`] docs for more info.
+    pub fn set_scroll_ratio(&mut self, ratio: Vector2<f32>) -> Vector2<f32> {
+        self.scroll_ratio.set_value_and_mark_modified(ratio)
+    }
+
+    /// Returns current scroll ratio of the layer.
+    pub fn scroll_ratio(&self) -> Vector2<f32> {
+        *self.scroll_ratio
+    }
+
+    /// Enables or disables infinite horizontal repeat of the layer.
+    pub fn set_repeat_x(&mut self, repeat: bool) -> bool {
+        self.repeat_x.set_value_and_mark_modified(repeat)
+    }
+
+    /// Returns `true` if the layer is repeated horizontally, `false` - otherwise.
+    pub fn is_repeat_x(&self) -> bool {
+        *self.repeat_x
+    }
+
+    /// Enables or disables infinite vertical repeat of the layer.
+    pub fn set_repeat_y(&mut self, repeat: bool) -> bool {
+        self.repeat_y.set_value_and_mark_modified(repeat)
+    }
+
+    /// Returns `true` if the layer is repeated vertically, `false` - otherwise.
+    pub fn is_repeat_y(&self) -> bool {
+        *self.repeat_y
+    }
+
+    /// Sets new size of a single copy of the layer's content, that is used for repetition.
+    pub fn set_repeat_size(&mut self, size: Vector2<f32>) -> Vector2<f32> {
+        self.repeat_size
+            .set_value_and_mark_modified(size.map(|c| c.max(0.01)))
+    }
+
+    /// Returns current size of a single copy of the layer's content.
+    pub fn repeat_size(&self) -> Vector2<f32> {
+        *self.repeat_size
+    }
+
+    /// Calculates world-space offset of the content of the layer for a camera at the given position.
+    pub fn parallax_offset(&self, camera_position: Vector3<f32>) -> Vector3<f32> {
+        let delta = (camera_position - self.global_position()).xy();
+        let ratio = Vector2::repeat(1.0) - *self.scroll_ratio;
+        Vector3::new(delta.x * ratio.x, delta.y * ratio.y, 0.0)
+    }
+
+    /// Calculates offsets of every copy of the content of the layer, that should be rendered to fill the view
+    /// with the given half extents (in world units) around the camera.
+    fn copy_offsets(
+        &self,
+        camera_position: Vector3<f32>,
+        half_extents: Vector2<f32>,
+    ) -> Vec<Vector3<f32>> {
+        let offset = self.parallax_offset(camera_position);
+        let center = self.global_position() + offset;
+        let size = self.repeat_size.map(|c| c.max(0.01));
+
+        let range = |repeat: bool, camera: f32, center: f32, size: f32, half_extent: f32| {
+            if repeat {
+                let nearest = ((camera - center) / size).round() as i32;
+                let count = (half_extent / size).ceil() as i32;
+                (nearest - count)..=(nearest + count)
+            } else {
+                0..=0
+            }
+        };
+
+        let mut offsets = Vec::new();
+        for y in range(
+            *self.repeat_y,
+            camera_position.y,
+            center.y,
+            size.y,
+            half_extents.y,
+        ) {
+            for x in range(
+                *self.repeat_x,
+                camera_position.x,
+                center.x,
+                size.x,
+                half_extents.x,
+            ) {
+                if offsets.len() == MAX_COPIES {
+                    return offsets;
+                }
+                offsets.push(offset + Vector3::new(x as f32 * size.x, y as f32 * size.y, 0.0));
+            }
+        }
+        offsets
+    }
+}
+
+/// Calculates half extents of the view of an observer in the plane at the given point.
+fn view_half_extents(ctx: &RenderContext, point: Vector3<f32>) -> Vector2<f32> {
+    let projection = ctx.projection_matrix;
+    let scale = if projection[(3, 3)] == 1.0 {
+        // Orthographic projection.
+        1.0
+    } else {
+        ctx.view_matrix.transform_point(&point.into()).z.abs()
+    };
+    Vector2::new(
+        scale / projection[(0, 0)].abs().max(f32::EPSILON),
+        scale / projection[(1, 1)].abs().max(f32::EPSILON),
+    )
+}
+
+/// Bundle storage, that offsets everything that is pushed into it.
+struct OffsetStorage<'a> {
+    inner: &'a mut dyn RenderDataBundleStorageTrait,
+    offset: Vector3<f32>,
+}
+
+impl RenderDataBundleStorageTrait for OffsetStorage<'_> {
+    fn push_triangles(
+        &mut self,
+        layout: &[VertexAttributeDescriptor],
+        material: &MaterialResource,
+        render_path: RenderPath,
+        decal_layer_index: u8,
+        sort_index: u64,
+        is_skinned: bool,
+        node_handle: Handle<Node>,
+        func: &mut dyn FnMut(VertexBufferRefMut, TriangleBufferRefMut),
+    ) {
+        let offset = self.offset;
+        self.inner.push_triangles(
+            layout,
+            material,
+            render_path,
+            decal_layer_index,
+            sort_index,
+            is_skinned,
+            node_handle,
+            &mut |mut vertex_buffer, mut triangle_buffer| {
+                let start = vertex_buffer.vertex_count() as usize;
+                func(vertex_buffer.modify(), triangle_buffer.modify());
+                // Vertices of dynamic surfaces are in world space already.
+                for mut vertex in vertex_buffer.iter_mut().skip(start) {
+                    if let Ok(position) = vertex.read_3_f32(VertexAttributeUsage::Position) {
+                        let _ =
+                            vertex.write_3_f32(VertexAttributeUsage::Position, position + offset);
+                    }
+                }
+            },
+        )
+    }
+
+    fn push(
+        &mut self,
+        data: &SurfaceResource,
+        material: &MaterialResource,
+        render_path: RenderPath,
+        decal_layer_index: u8,
+        sort_index: u64,
+        mut instance_data: SurfaceInstanceData,
+    ) {
+        instance_data.world_transform =
+            Matrix4::new_translation(&self.offset) * instance_data.world_transform;
+        self.inner.push(
+            data,
+            material,
+            render_path,
+            decal_layer_index,
+            sort_index,
+            instance_data,
+        )
+    }
+}
+
+impl NodeTrait for ParallaxLayer {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::unit()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn collect_render_data(&self, ctx: &mut RenderContext) -> RdcControlFlow {
+        let half_extents = view_half_extents(ctx, self.global_position());
+
+        for offset in self.copy_offsets(*ctx.observer_position, half_extents) {
+            // Instead of moving every descendant, move the observer in the opposite direction. This way
+            // culling and sorting of descendants work as usual.
+            let view_matrix = ctx.view_matrix * Matrix4::new_translation(&offset);
+            let frustum = Frustum::from_view_projection_matrix(ctx.projection_matrix * view_matrix);
+            let observer_position = ctx.observer_position - offset;
+            let mut storage = OffsetStorage {
+                inner: &mut *ctx.storage,
+                offset,
+            };
+            let mut layer_ctx = RenderContext {
+                observer_position: &observer_position,
+                z_near: ctx.z_near,
+                z_far: ctx.z_far,
+                view_matrix: &view_matrix,
+                projection_matrix: ctx.projection_matrix,
+                frustum: frustum.as_ref(),
+                storage: &mut storage,
+                graph: ctx.graph,
+                render_pass_name: ctx.render_pass_name,
+            };
+
+            let mut stack = self.children().to_vec();
+            while let Some(handle) = stack.pop() {
+                let Some(node) = ctx.graph.try_get(handle) else {
+                    continue;
+                };
+                if let RdcControlFlow::Continue = node.collect_render_data(&mut layer_ctx) {
+                    stack.extend_from_slice(node.children());
+                }
+            }
+        }
+
+        // Descendants are rendered already.
+        RdcControlFlow::Break
+    }
+}
+
+/// Allows you to create parallax layers in declarative manner.
+pub struct ParallaxLayerBuilder {
+    base_builder: BaseBuilder,
+    scroll_ratio: Vector2<f32>,
+    repeat_x: bool,
+    repeat_y: bool,
+    repeat_size: Vector2<f32>,
+}
+
+impl ParallaxLayerBuilder {
+    /// Creates new builder instance.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            scroll_ratio: Vector2::repeat(0.5),
+            repeat_x: false,
+            repeat_y: false,
+            repeat_size: Vector2::repeat(10.0),
+        }
+    }
+
+    /// Sets desired scroll ratio of the layer.
+    pub fn with_scroll_ratio(mut self, ratio: Vector2<f32>) -> Self {
+        self.scroll_ratio = ratio;
+        self
+    }
+
+    /// Enables or disables infinite horizontal repeat of the layer.
+    pub fn with_repeat_x(mut self, repeat: bool) -> Self {
+        self.repeat_x = repeat;
+        self
+    }
+
+    /// Enables or disables infinite vertical repeat of the layer.
+    pub fn with_repeat_y(mut self, repeat: bool) -> Self {
+        self.repeat_y = repeat;
+        self
+    }
+
+    /// Sets desired size of a single copy of the layer's content.
+    pub fn with_repeat_size(mut self, size: Vector2<f32>) -> Self {
+        self.repeat_size = size;
+        self
+    }
+
+    /// Creates new parallax layer.
+    pub fn build_parallax_layer(self) -> ParallaxLayer {
+        ParallaxLayer {
+            base: self.base_builder.build_base(),
+            scroll_ratio: self.scroll_ratio.into(),
+            repeat_x: self.repeat_x.into(),
+            repeat_y: self.repeat_y.into(),
+            repeat_size: self.repeat_size.map(|c| c.max(0.01)).into(),
+        }
+    }
+
+    /// Creates new parallax layer node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_parallax_layer())
+    }
+
+    /// Creates new parallax layer node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{base::BaseBuilder, dim2::parallax::ParallaxLayerBuilder},
+    };
+
+    #[test]
+    fn test_parallax_offset_and_repeat() {
+        let layer = ParallaxLayerBuilder::new(BaseBuilder::new())
+            .with_scroll_ratio(Vector2::new(0.25, 1.0))
+            .with_repeat_x(true)
+            .with_repeat_size(Vector2::new(10.0, 10.0))
+            .build_parallax_layer();
+
+        let camera = Vector3::new(100.0, 4.0, -5.0);
+        assert_eq!(layer.parallax_offset(camera), Vector3::new(75.0, 0.0, 0.0));
+
+        // The content is at x = 75 now, so the nearest copy to the camera is at x = 105 and the view of
+        // 12 units wide needs one extra copy on each side.
+        let offsets = layer.copy_offsets(camera, Vector2::new(6.0, 6.0));
+        assert_eq!(
+            offsets,
+            vec![
+                Vector3::new(95.0, 0.0, 0.0),
+                Vector3::new(105.0, 0.0, 0.0),
+                Vector3::new(115.0, 0.0, 0.0),
+            ]
+        );
+    }
+}
@@ -3,7 +3,7 @@
 use crate::{
     core::{
         algebra::{
-            Isometry2, Isometry3, Matrix4, Point2, Rotation3, Translation2, Translation3,
+            Isometry2, Isometry3, Matrix4, Point2, Point3, Rotation3, Translation2, Translation3,
             UnitComplex, UnitQuaternion, UnitVector2, Vector2, Vector3,
         },
         arrayvec::ArrayVec,
@@ -13,6 +13,7 @@ use crate::{
         parking_lot::Mutex,
         pool::Handle,
         reflect::prelude::*,
+        scope_profile,
         variable::VariableFlags,
         visitor::prelude::*,
         BiDirHashMap,
@@ -21,17 +22,19 @@ use crate::{
     scene::{
         self,
         collider::{self},
-        debug::SceneDrawingContext,
+        debug::{PhysicsDebugDrawSettings, SceneDrawingContext},
         dim2::{
             self, collider::ColliderShape, joint::JointLocalFrames, joint::JointParams,
             rigidbody::ApplyAction,
         },
         graph::Graph,
         graph::{
+            isometric_global_transform,
             physics::{FeatureId, IntegrationParameters, PhysicsPerformanceStatistics},
             NodePool,
         },
         node::{Node, NodeTrait},
+        tilemap::TileMap,
     },
 };
 use fyrox_core::variable::InheritableVariable;
@@ -302,8 +305,52 @@ fn convert_joint_params(
     joint
 }
 
+// Creates a compound shape of merged rectangles of solid tiles. Rectangles are transformed into the
+// local space of the owner collider.
+fn make_tile_map_shape(
+    owner_inv_global_transform: Matrix4<f32>,
+    tile_map: &TileMap,
+) -> Option<SharedShape> {
+    let transform = owner_inv_global_transform * tile_map.global_transform();
+    let x_axis = transform.transform_vector(&Vector3::x());
+    let y_axis = transform.transform_vector(&Vector3::y());
+    let rotation = UnitComplex::from_angle(x_axis.y.atan2(x_axis.x));
+    let scale = Vector2::new(x_axis.xy().norm(), y_axis.xy().norm());
+
+    let shapes = tile_map
+        .collider_rectangles()
+        .into_iter()
+        .map(|rect| {
+            let center = rect.center();
+            let center = transform.transform_point(&Point3::new(center.x, center.y, 0.0));
+            (
+                Isometry2 {
+                    rotation,
+                    translation: Translation2 {
+                        vector: center.coords.xy(),
+                    },
+                },
+                SharedShape::cuboid(
+                    rect.half_extents().x * scale.x,
+                    rect.half_extents().y * scale.y,
+                ),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if shapes.is_empty() {
+        None
+    } else {
+        Some(SharedShape::compound(shapes))
+    }
+}
+
 // Converts descriptor in a shared shape.
-fn collider_shape_into_native_shape(shape: &ColliderShape) -> Option<SharedShape> {
+fn collider_shape_into_native_shape(
+    shape: &ColliderShape,
+    owner_inv_global_transform: Matrix4<f32>,
+    pool: &NodePool,
+) -> Option<SharedShape> {
     match shape {
         ColliderShape::Ball(ball) => Some(SharedShape::ball(ball.radius)),
         ColliderShape::Cuboid(cuboid) => {
@@ -329,9 +376,32 @@ fn collider_shape_into_native_shape(shape: &ColliderShape) -> Option<SharedShape
         ColliderShape::Heightfield(_) => {
             None // TODO
         }
+        ColliderShape::TileMap(tile_map) => pool
+            .try_borrow(tile_map.tile_map.0)
+            .and_then(|n| n.cast::<TileMap>())
+            .and_then(|tile_map| make_tile_map_shape(owner_inv_global_transform, tile_map)),
     }
 }
 
+// Checks whether merged colliders of a tile map, that is used by a tile map shape of the collider, were
+// changed since the last check.
+fn tile_map_shape_modified(
+    nodes: &NodePool,
+    collider_node: &scene::dim2::collider::Collider,
+) -> bool {
+    let ColliderShape::TileMap(ref shape) = *collider_node.shape else {
+        return false;
+    };
+    let Some(tile_map) = nodes
+        .try_borrow(shape.tile_map.0)
+        .and_then(|n| n.cast::<TileMap>())
+    else {
+        return false;
+    };
+    let revision = tile_map.update_colliders();
+    collider_node.tile_map_revision.replace(revision) != revision
+}
+
 fn isometry2_to_mat4(isometry: &Isometry2<f32>) -> Matrix4<f32> {
     Isometry3 {
         rotation: UnitQuaternion::from_euler_angles(0.0, 0.0, isometry.rotation.angle()),
@@ -510,6 +580,8 @@ impl PhysicsWorld {
     }
 
     pub(crate) fn update(&mut self, dt: f32) {
+        scope_profile!();
+
         let time = instant::Instant::now();
 
         if *self.enabled {
@@ -620,7 +692,19 @@ impl PhysicsWorld {
     /// Draws physics world. Very useful for debugging, it allows you to see where are
     /// rigid bodies, which colliders they have and so on.
     pub fn draw(&self, context: &mut SceneDrawingContext) {
-        self.debug_render_pipeline.lock().render(
+        self.draw_with_settings(context, &Default::default())
+    }
+
+    /// Draws physics world using the given settings. The settings define which parts of the world
+    /// will be drawn and what colors will be used for them.
+    pub fn draw_with_settings(
+        &self,
+        context: &mut SceneDrawingContext,
+        settings: &PhysicsDebugDrawSettings,
+    ) {
+        let mut pipeline = self.debug_render_pipeline.lock();
+        (pipeline.mode, pipeline.style) = settings.rapier2d_mode_and_style();
+        pipeline.render(
             context,
             &self.bodies,
             &self.colliders,
@@ -783,6 +867,21 @@ impl PhysicsWorld {
             })
     }
 
+    /// Teleports every native rigid body and collider by the negated offset, so the physics world
+    /// matches the scene after [`crate::scene::graph::Graph::shift_origin`]. Velocities are kept
+    /// intact and kinematic bodies keep their pending movement.
+    pub(crate) fn shift_origin(&mut self, offset: &Vector3<f32>) {
+        let offset = offset.xy();
+        for (_, body) in self.bodies.iter_mut() {
+            let next_translation = body.next_position().translation.vector - offset;
+            body.set_translation(body.translation() - offset, false);
+            body.set_next_kinematic_translation(next_translation);
+        }
+        for (_, collider) in self.colliders.iter_mut() {
+            collider.set_translation(collider.translation() - offset);
+        }
+    }
+
     pub(crate) fn set_rigid_body_position(
         &mut self,
         rigid_body: &scene::dim2::rigidbody::RigidBody,
@@ -990,8 +1089,10 @@ impl PhysicsWorld {
             return;
         }
 
-        let anything_changed =
-            collider_node.transform_modified.get() || collider_node.needs_sync_model();
+        let tile_map_modified = tile_map_shape_modified(nodes, collider_node);
+        let anything_changed = collider_node.transform_modified.get()
+            || collider_node.needs_sync_model()
+            || tile_map_modified;
 
         // Important notes!
         // 1) The collider node may lack backing native physics collider in case if it
@@ -1012,11 +1113,28 @@ impl PhysicsWorld {
                         });
                     }
 
-                    collider_node.shape.try_sync_model(|v| {
-                        if let Some(shape) = collider_shape_into_native_shape(&v) {
-                            native.set_shape(shape);
+                    let shape_modified = collider_node.shape.try_sync_model(|_| ());
+                    if shape_modified || tile_map_modified {
+                        let inv_global_transform = isometric_global_transform(nodes, handle)
+                            .try_inverse()
+                            .unwrap_or_default();
+                        match collider_shape_into_native_shape(
+                            collider_node.shape(),
+                            inv_global_transform,
+                            nodes,
+                        ) {
+                            Some(shape) => {
+                                native.set_shape(shape);
+                                native.set_enabled(true);
+                            }
+                            // Tile maps may have no solid tiles at all, compound shapes can't be
+                            // empty, so the collider is disabled instead.
+                            None if matches!(*collider_node.shape, ColliderShape::TileMap(_)) => {
+                                native.set_enabled(false);
+                            }
+                            None => (),
                         }
-                    });
+                    }
                     collider_node
                         .restitution
                         .try_sync_model(|v| native.set_restitution(v));
@@ -1052,7 +1170,14 @@ impl PhysicsWorld {
         {
             if parent_body.native.get() != RigidBodyHandle::invalid() {
                 let rigid_body_native = parent_body.native.get();
-                if let Some(shape) = collider_shape_into_native_shape(collider_node.shape()) {
+                let inv_global_transform = isometric_global_transform(nodes, handle)
+                    .try_inverse()
+                    .unwrap_or_default();
+                if let Some(shape) = collider_shape_into_native_shape(
+                    collider_node.shape(),
+                    inv_global_transform,
+                    nodes,
+                ) {
                     let mut builder = ColliderBuilder::new(shape)
                         .position(Isometry2 {
                             rotation: UnitComplex::from_angle(
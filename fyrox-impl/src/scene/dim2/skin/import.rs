@@ -0,0 +1,616 @@
+//! Optional converter of 2D skeletons from Spine and DragonBones JSON formats. It creates a hierarchy of bones
+//! and an animation player with every animation of the skeleton. Attachments (images, meshes) are not
+//! converted, they should be re-created using [`super::SkinnedSprite`] nodes bound to the imported bones.
+
+use crate::{
+    core::{
+        algebra::{UnitQuaternion, Vector2, Vector3},
+        math::curve::{CurveKey, CurveKeyKind},
+        pool::Handle,
+    },
+    fxhash::FxHashMap,
+    scene::{
+        animation::{prelude::*, AnimationPlayerBuilder},
+        base::BaseBuilder,
+        graph::Graph,
+        node::Node,
+        pivot::PivotBuilder,
+        transform::TransformBuilder,
+    },
+};
+use fyrox_graph::BaseSceneGraph;
+use std::fmt::{Display, Formatter};
+
+/// An error that may occur during skeleton import.
+#[derive(Debug)]
+pub enum Skeleton2DImportError {
+    /// The source is not a valid JSON or has unexpected structure.
+    Json(serde_json::Error),
+    /// The source is neither a Spine nor a DragonBones skeleton.
+    UnknownFormat,
+    /// A bone refers to a parent that does not exist.
+    UnknownBone(String),
+}
+
+impl Display for Skeleton2DImportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json(v) => write!(f, "Unable to parse skeleton. Reason: {v}"),
+            Self::UnknownFormat => write!(f, "Unknown skeleton format."),
+            Self::UnknownBone(v) => write!(f, "Unknown bone {v}."),
+        }
+    }
+}
+
+impl From<serde_json::Error> for Skeleton2DImportError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+/// A format of a skeleton source.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Skeleton2DFormat {
+    /// Spine JSON export.
+    Spine,
+    /// DragonBones JSON export (`_ske.json`).
+    DragonBones,
+}
+
+/// A result of skeleton import.
+#[derive(Clone, Debug)]
+pub struct ImportedSkeleton2D {
+    /// Root node of the skeleton. Every bone and the animation player are descendants of this node.
+    pub root: Handle<Node>,
+    /// Bones of the skeleton by their names.
+    pub bones: FxHashMap<String, Handle<Node>>,
+    /// An animation player with every animation of the skeleton.
+    pub animation_player: Handle<Node>,
+}
+
+// Intermediate, format-independent representation. Values are in engine units and orientation.
+
+struct BoneDefinition {
+    name: String,
+    parent: Option<String>,
+    position: Vector2<f32>,
+    rotation: f32,
+    scale: Vector2<f32>,
+}
+
+#[derive(Default)]
+struct BoneTimelines {
+    position: Vec<(f32, Vector2<f32>)>,
+    rotation: Vec<(f32, f32)>,
+    scale: Vec<(f32, Vector2<f32>)>,
+}
+
+struct AnimationDefinition {
+    name: String,
+    bones: Vec<(String, BoneTimelines)>,
+}
+
+struct SkeletonDefinition {
+    bones: Vec<BoneDefinition>,
+    animations: Vec<AnimationDefinition>,
+}
+
+fn one() -> f32 {
+    1.0
+}
+
+mod spine {
+    use super::one;
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Deserialize)]
+    pub struct Bone {
+        pub name: String,
+        #[serde(default)]
+        pub parent: Option<String>,
+        #[serde(default)]
+        pub x: f32,
+        #[serde(default)]
+        pub y: f32,
+        #[serde(default)]
+        pub rotation: f32,
+        #[serde(default = "one", rename = "scaleX")]
+        pub scale_x: f32,
+        #[serde(default = "one", rename = "scaleY")]
+        pub scale_y: f32,
+    }
+
+    #[derive(Deserialize)]
+    pub struct RotateKey {
+        #[serde(default)]
+        pub time: f32,
+        // Spine 3.x uses `angle`, while 4.x uses `value`.
+        #[serde(default, alias = "value")]
+        pub angle: f32,
+    }
+
+    #[derive(Deserialize)]
+    pub struct VectorKey {
+        #[serde(default)]
+        pub time: f32,
+        pub x: Option<f32>,
+        pub y: Option<f32>,
+    }
+
+    #[derive(Deserialize, Default)]
+    pub struct BoneTimeline {
+        #[serde(default)]
+        pub rotate: Vec<RotateKey>,
+        #[serde(default)]
+        pub translate: Vec<VectorKey>,
+        #[serde(default)]
+        pub scale: Vec<VectorKey>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Animation {
+        #[serde(default)]
+        pub bones: BTreeMap<String, BoneTimeline>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Skeleton {
+        pub bones: Vec<Bone>,
+        #[serde(default)]
+        pub animations: BTreeMap<String, Animation>,
+    }
+}
+
+mod dragon_bones {
+    use super::one;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Transform {
+        #[serde(default)]
+        pub x: f32,
+        #[serde(default)]
+        pub y: f32,
+        #[serde(default, rename = "skY")]
+        pub sk_y: f32,
+        #[serde(default = "one", rename = "scX")]
+        pub sc_x: f32,
+        #[serde(default = "one", rename = "scY")]
+        pub sc_y: f32,
+    }
+
+    impl Default for Transform {
+        fn default() -> Self {
+            Self {
+                x: 0.0,
+                y: 0.0,
+                sk_y: 0.0,
+                sc_x: 1.0,
+                sc_y: 1.0,
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct Bone {
+        pub name: String,
+        #[serde(default)]
+        pub parent: Option<String>,
+        #[serde(default)]
+        pub transform: Transform,
+    }
+
+    #[derive(Deserialize)]
+    pub struct VectorFrame {
+        #[serde(default)]
+        pub duration: u32,
+        pub x: Option<f32>,
+        pub y: Option<f32>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct RotateFrame {
+        #[serde(default)]
+        pub duration: u32,
+        #[serde(default)]
+        pub rotate: f32,
+    }
+
+    #[derive(Deserialize)]
+    pub struct BoneTimeline {
+        pub name: String,
+        #[serde(default, rename = "translateFrame")]
+        pub translate_frame: Vec<VectorFrame>,
+        #[serde(default, rename = "rotateFrame")]
+        pub rotate_frame: Vec<RotateFrame>,
+        #[serde(default, rename = "scaleFrame")]
+        pub scale_frame: Vec<VectorFrame>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Animation {
+        pub name: String,
+        #[serde(default)]
+        pub bone: Vec<BoneTimeline>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Armature {
+        #[serde(default, rename = "frameRate")]
+        pub frame_rate: Option<f32>,
+        #[serde(default)]
+        pub bone: Vec<Bone>,
+        #[serde(default)]
+        pub animation: Vec<Animation>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Skeleton {
+        #[serde(default, rename = "frameRate")]
+        pub frame_rate: Option<f32>,
+        pub armature: Vec<Armature>,
+    }
+}
+
+// Both formats use X axis that points right on screen, while in the engine X axis of 2D scenes points left.
+// This mirroring flips the direction of rotations as well.
+
+fn convert_spine(source: spine::Skeleton, pixels_per_unit: f32) -> SkeletonDefinition {
+    let to_position = |x: f32, y: f32| Vector2::new(-x, y) / pixels_per_unit;
+    let to_rotation = |angle: f32| -angle.to_radians();
+
+    let bones = source
+        .bones
+        .iter()
+        .map(|bone| BoneDefinition {
+            name: bone.name.clone(),
+            parent: bone.parent.clone(),
+            position: to_position(bone.x, bone.y),
+            rotation: to_rotation(bone.rotation),
+            scale: Vector2::new(bone.scale_x, bone.scale_y),
+        })
+        .collect::<Vec<_>>();
+
+    let animations = source
+        .animations
+        .into_iter()
+        .map(|(name, animation)| AnimationDefinition {
+            name,
+            bones: animation
+                .bones
+                .into_iter()
+                .filter_map(|(bone_name, timeline)| {
+                    // Timelines are relative to the setup pose.
+                    let setup = bones.iter().find(|b| b.name == bone_name)?;
+                    let timelines = BoneTimelines {
+                        position: timeline
+                            .translate
+                            .iter()
+                            .map(|k| {
+                                let offset = to_position(k.x.unwrap_or(0.0), k.y.unwrap_or(0.0));
+                                (k.time, setup.position + offset)
+                            })
+                            .collect(),
+                        rotation: timeline
+                            .rotate
+                            .iter()
+                            .map(|k| (k.time, setup.rotation + to_rotation(k.angle)))
+                            .collect(),
+                        scale: timeline
+                            .scale
+                            .iter()
+                            .map(|k| {
+                                let scale = Vector2::new(k.x.unwrap_or(1.0), k.y.unwrap_or(1.0));
+                                (k.time, setup.scale.component_mul(&scale))
+                            })
+                            .collect(),
+                    };
+                    Some((bone_name, timelines))
+                })
+                .collect(),
+        })
+        .collect();
+
+    SkeletonDefinition { bones, animations }
+}
+
+fn convert_dragon_bones(
+    source: dragon_bones::Skeleton,
+    pixels_per_unit: f32,
+) -> Option<SkeletonDefinition> {
+    let armature = source.armature.into_iter().next()?;
+    let frame_rate = armature
+        .frame_rate
+        .or(source.frame_rate)
+        .unwrap_or(24.0)
+        .max(1.0);
+
+    // DragonBones uses Y axis that points down on screen, which compensates the rotation flip.
+    let to_position = |x: f32, y: f32| Vector2::new(-x, -y) / pixels_per_unit;
+    let to_rotation = |angle: f32| angle.to_radians();
+
+    fn keys<F, T, V>(
+        frames: &[F],
+        frame_rate: f32,
+        duration: impl Fn(&F) -> u32,
+        value: V,
+    ) -> Vec<(f32, T)>
+    where
+        V: Fn(&F) -> T,
+    {
+        let mut frame = 0;
+        frames
+            .iter()
+            .map(|f| {
+                let key = (frame as f32 / frame_rate, value(f));
+                frame += duration(f);
+                key
+            })
+            .collect()
+    }
+
+    let bones = armature
+        .bone
+        .iter()
+        .map(|bone| BoneDefinition {
+            name: bone.name.clone(),
+            parent: bone.parent.clone(),
+            position: to_position(bone.transform.x, bone.transform.y),
+            rotation: to_rotation(bone.transform.sk_y),
+            scale: Vector2::new(bone.transform.sc_x, bone.transform.sc_y),
+        })
+        .collect::<Vec<_>>();
+
+    let animations = armature
+        .animation
+        .into_iter()
+        .map(|animation| AnimationDefinition {
+            name: animation.name,
+            bones: animation
+                .bone
+                .into_iter()
+                .filter_map(|timeline| {
+                    let setup = bones.iter().find(|b| b.name == timeline.name)?;
+                    let timelines = BoneTimelines {
+                        position: keys(
+                            &timeline.translate_frame,
+                            frame_rate,
+                            |f| f.duration,
+                            |f| {
+                                setup.position + to_position(f.x.unwrap_or(0.0), f.y.unwrap_or(0.0))
+                            },
+                        ),
+                        rotation: keys(
+                            &timeline.rotate_frame,
+                            frame_rate,
+                            |f| f.duration,
+                            |f| setup.rotation + to_rotation(f.rotate),
+                        ),
+                        scale: keys(
+                            &timeline.scale_frame,
+                            frame_rate,
+                            |f| f.duration,
+                            |f| {
+                                setup.scale.component_mul(&Vector2::new(
+                                    f.x.unwrap_or(1.0),
+                                    f.y.unwrap_or(1.0),
+                                ))
+                            },
+                        ),
+                    };
+                    Some((timeline.name, timelines))
+                })
+                .collect(),
+        })
+        .collect();
+
+    Some(SkeletonDefinition { bones, animations })
+}
+
+fn make_vector_track(
+    keys: &[(f32, Vector2<f32>)],
+    binding: ValueBinding,
+    z: f32,
+    target: Handle<Node>,
+) -> Track {
+    let mut container = TrackDataContainer::new(TrackValueKind::Vector3);
+    for (time, value) in keys {
+        let curves = container.curves_mut();
+        curves[0].add_key(CurveKey::new(*time, value.x, CurveKeyKind::Linear));
+        curves[1].add_key(CurveKey::new(*time, value.y, CurveKeyKind::Linear));
+        curves[2].add_key(CurveKey::new(*time, z, CurveKeyKind::Linear));
+    }
+    let mut track = Track::new(container, binding);
+    track.set_target(target);
+    track
+}
+
+fn instantiate(
+    definition: SkeletonDefinition,
+    graph: &mut Graph,
+) -> Result<ImportedSkeleton2D, Skeleton2DImportError> {
+    let root = PivotBuilder::new(BaseBuilder::new().with_name("Skeleton")).build(graph);
+
+    let mut bones = FxHashMap::default();
+    for bone in definition.bones.iter() {
+        let handle = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name(bone.name.as_str())
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(bone.position.to_homogeneous())
+                        .with_local_rotation(UnitQuaternion::from_axis_angle(
+                            &Vector3::z_axis(),
+                            bone.rotation,
+                        ))
+                        .with_local_scale(Vector3::new(bone.scale.x, bone.scale.y, 1.0))
+                        .build(),
+                ),
+        )
+        .build(graph);
+        bones.insert(bone.name.clone(), handle);
+    }
+
+    for bone in definition.bones.iter() {
+        let parent = match bone.parent.as_ref() {
+            Some(parent) => *bones
+                .get(parent)
+                .ok_or_else(|| Skeleton2DImportError::UnknownBone(parent.clone()))?,
+            None => root,
+        };
+        graph.link_nodes(bones[&bone.name], parent);
+    }
+
+    let mut animations = AnimationContainer::new();
+    for (index, definition) in definition.animations.into_iter().enumerate() {
+        let mut animation = Animation::default();
+        animation.set_name(definition.name);
+
+        for (bone_name, timelines) in definition.bones {
+            let Some(target) = bones.get(&bone_name).cloned() else {
+                continue;
+            };
+
+            if !timelines.position.is_empty() {
+                animation.add_track(make_vector_track(
+                    &timelines.position,
+                    ValueBinding::Position,
+                    0.0,
+                    target,
+                ));
+            }
+            if !timelines.scale.is_empty() {
+                animation.add_track(make_vector_track(
+                    &timelines.scale,
+                    ValueBinding::Scale,
+                    1.0,
+                    target,
+                ));
+            }
+            if !timelines.rotation.is_empty() {
+                // Rotation tracks store Euler angles, only Z axis is used in 2D.
+                let mut container = TrackDataContainer::new(TrackValueKind::Vector3);
+                for (time, angle) in timelines.rotation.iter() {
+                    let curves = container.curves_mut();
+                    curves[0].add_key(CurveKey::new(*time, 0.0, CurveKeyKind::Linear));
+                    curves[1].add_key(CurveKey::new(*time, 0.0, CurveKeyKind::Linear));
+                    curves[2].add_key(CurveKey::new(*time, *angle, CurveKeyKind::Linear));
+                }
+                let mut track = Track::new(container, ValueBinding::Rotation);
+                track.set_target(target);
+                animation.add_track(track);
+            }
+        }
+
+        animation.fit_length_to_content();
+        animation.set_loop(true);
+        // Only the first animation is playing by default.
+        animation.set_enabled(index == 0);
+        animations.add(animation);
+    }
+
+    let animation_player =
+        AnimationPlayerBuilder::new(BaseBuilder::new().with_name("Animation Player"))
+            .with_animations(animations)
+            .build(graph);
+    graph.link_nodes(animation_player, root);
+
+    Ok(ImportedSkeleton2D {
+        root,
+        bones,
+        animation_player,
+    })
+}
+
+/// Detects format of the given JSON source. Returns `None` if the format is unknown.
+pub fn detect_format(json: &str) -> Option<Skeleton2DFormat> {
+    let value = serde_json::from_str::<serde_json::Value>(json).ok()?;
+    if value.get("armature").is_some() {
+        Some(Skeleton2DFormat::DragonBones)
+    } else if value.get("bones").is_some() {
+        Some(Skeleton2DFormat::Spine)
+    } else {
+        None
+    }
+}
+
+/// Imports a skeleton from the given Spine or DragonBones JSON source and adds it to the graph. Positions
+/// in the source are divided by `pixels_per_unit`. Only the first armature of DragonBones sources is
+/// imported.
+pub fn import_skeleton_2d(
+    json: &str,
+    pixels_per_unit: f32,
+    graph: &mut Graph,
+) -> Result<ImportedSkeleton2D, Skeleton2DImportError> {
+    let pixels_per_unit = if pixels_per_unit > 0.0 {
+        pixels_per_unit
+    } else {
+        1.0
+    };
+
+    let definition = match detect_format(json).ok_or(Skeleton2DImportError::UnknownFormat)? {
+        Skeleton2DFormat::Spine => convert_spine(serde_json::from_str(json)?, pixels_per_unit),
+        Skeleton2DFormat::DragonBones => {
+            convert_dragon_bones(serde_json::from_str(json)?, pixels_per_unit)
+                .ok_or(Skeleton2DImportError::UnknownFormat)?
+        }
+    };
+
+    instantiate(definition, graph)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{detect_format, import_skeleton_2d, Skeleton2DFormat};
+    use crate::scene::{animation::AnimationPlayer, graph::Graph};
+
+    #[test]
+    fn test_import() {
+        let spine = r#"{
+            "bones": [
+                { "name": "root" },
+                { "name": "arm", "parent": "root", "x": 100, "rotation": 90 }
+            ],
+            "animations": {
+                "wave": { "bones": { "arm": { "rotate": [ { "time": 0 }, { "time": 1, "angle": 45 } ] } } }
+            }
+        }"#;
+        assert_eq!(detect_format(spine), Some(Skeleton2DFormat::Spine));
+
+        let mut graph = Graph::new();
+        let skeleton = import_skeleton_2d(spine, 100.0, &mut graph).unwrap();
+        let arm = skeleton.bones["arm"];
+        assert_eq!(graph[arm].parent(), skeleton.bones["root"]);
+        assert_eq!(graph[arm].local_transform().position().x, -1.0);
+
+        let player = graph[skeleton.animation_player]
+            .cast::<AnimationPlayer>()
+            .unwrap();
+        let animation = player.animations().iter().next().unwrap();
+        assert_eq!(animation.name(), "wave");
+        assert_eq!(animation.length(), 1.0);
+
+        let dragon_bones = r#"{
+            "frameRate": 10,
+            "armature": [{
+                "bone": [ { "name": "root" }, { "name": "leg", "parent": "root", "transform": { "y": 50 } } ],
+                "animation": [{
+                    "name": "kick",
+                    "bone": [ { "name": "leg", "translateFrame": [ { "duration": 5, "x": 0 }, { "x": 10 } ] } ]
+                }]
+            }]
+        }"#;
+        assert_eq!(
+            detect_format(dragon_bones),
+            Some(Skeleton2DFormat::DragonBones)
+        );
+        let skeleton = import_skeleton_2d(dragon_bones, 10.0, &mut graph).unwrap();
+        let leg = skeleton.bones["leg"];
+        assert_eq!(graph[leg].local_transform().position().y, -5.0);
+        let player = graph[skeleton.animation_player]
+            .cast::<AnimationPlayer>()
+            .unwrap();
+        assert_eq!(player.animations().iter().next().unwrap().length(), 0.5);
+    }
+}
@@ -0,0 +1,613 @@
+//! Skinned sprite is a 2D mesh, that is deformed by a 2D skeleton. See [`SkinnedSprite`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Vector2, Vector4},
+        color::Color,
+        math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        value_as_u8_slice,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    material::{Material, MaterialResource},
+    renderer::{self, bundle::RenderContext},
+    scene::{
+        base::{Base, BaseBuilder},
+        dim2::rectangle::RectangleVertex,
+        graph::Graph,
+        mesh::{buffer::VertexTrait, RenderPath},
+        node::{Node, NodeTrait, RdcControlFlow},
+    },
+};
+use fyrox_graph::BaseSceneGraph;
+use std::ops::{Deref, DerefMut};
+
+#[cfg(feature = "skeleton_2d_import")]
+pub mod import;
+
+/// Maximum amount of bones, that could affect a single vertex.
+pub const MAX_BONE_INFLUENCES: usize = 4;
+
+/// A bone of a skinned sprite. Bones are ordinary scene nodes, which means that they can be animated by
+/// the standard animation system (see [`crate::scene::animation::AnimationPlayer`]).
+#[derive(Clone, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "b1e4c6a2-7d3f-4e59-8a0b-2c9f6d1e3a47")]
+pub struct Bone2D {
+    /// A handle of a scene node, that is used as the bone.
+    pub node: Handle<Node>,
+    /// A matrix, that transforms a point from the local space of the skinned sprite to the local space
+    /// of the bone at the moment of binding.
+    pub inv_bind_pose: Matrix4<f32>,
+}
+
+impl Default for Bone2D {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            inv_bind_pose: Matrix4::identity(),
+        }
+    }
+}
+
+/// A vertex of a skinned sprite.
+#[derive(Clone, Default, Debug, PartialEq, Reflect, Visit)]
+pub struct SkinVertex {
+    /// Position of the vertex in the local space of the skinned sprite.
+    pub position: Vector2<f32>,
+    /// Texture coordinates of the vertex.
+    pub tex_coord: Vector2<f32>,
+    /// Indices of bones (in [`SkinnedSprite::bones`]), that affect the vertex.
+    pub bone_indices: [u8; MAX_BONE_INFLUENCES],
+    /// Weights of the bones. The vertex is not deformed if every weight is zero.
+    pub bone_weights: Vector4<f32>,
+}
+
+impl SkinVertex {
+    /// Adds the given amount of weight for the given bone and re-normalizes weights of the vertex, so their
+    /// sum is equal to one. Negative amount removes the weight. If the vertex is already affected by the
+    /// maximum amount of bones, the bone with the smallest weight is replaced.
+    pub fn add_weight(&mut self, bone: u8, amount: f32) {
+        let slot = match (0..MAX_BONE_INFLUENCES)
+            .find(|i| self.bone_indices[*i] == bone && self.bone_weights[*i] > 0.0)
+        {
+            Some(slot) => slot,
+            None => {
+                if amount <= 0.0 {
+                    return;
+                }
+                let slot = self.bone_weights.imin();
+                self.bone_indices[slot] = bone;
+                self.bone_weights[slot] = 0.0;
+                slot
+            }
+        };
+
+        self.bone_weights[slot] = (self.bone_weights[slot] + amount).clamp(0.0, 1.0);
+
+        let others = self.bone_weights.sum() - self.bone_weights[slot];
+        if others > 0.0 {
+            // Keep the painted weight and distribute the rest between other bones.
+            let scale = (1.0 - self.bone_weights[slot]) / others;
+            for i in (0..MAX_BONE_INFLUENCES).filter(|i| *i != slot) {
+                self.bone_weights[i] *= scale;
+            }
+        } else if self.bone_weights[slot] > 0.0 {
+            self.bone_weights[slot] = 1.0;
+        }
+    }
+}
+
+/// Skinned sprite is a textured 2D mesh, whose vertices are attached to a set of bones with weights. Bones
+/// are ordinary scene nodes (usually pivots), so a skeleton is just a hierarchy of nodes that could be
+/// animated by the standard animation system. Every frame, each vertex is transformed by the weighted sum of
+/// transforms of its bones relative to their bind pose.
+///
+/// Rigid parts of a character are made by attaching every vertex of a part to a single bone with weight 1.0,
+/// while soft parts (a torso, for example) are made by painting smooth weights between multiple bones.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::{algebra::Vector2, pool::Handle},
+/// #     scene::{
+/// #         base::BaseBuilder,
+/// #         dim2::skin::{bind, SkinnedSprite, SkinnedSpriteBuilder},
+/// #         graph::Graph,
+/// #         node::Node,
+/// #     },
+/// # };
+/// fn create_tail(bones: &[Handle<Node>], graph: &mut Graph) -> Handle<Node> {
+///     // More cells means smoother deformation.
+///     let tail = SkinnedSpriteBuilder::new(BaseBuilder::new())
+///         .with_grid(Vector2::new(4.0, 1.0), 8, 2)
+///         .build(graph);
+///
+///     // Bones are bound in their current pose.
+///     bind(tail, bones, graph);
+///
+///     graph[tail]
+///         .cast_mut::<SkinnedSprite>()
+///         .unwrap()
+///         .compute_weights_by_distance();
+///
+///     tail
+/// }
+/// ```
+#[derive(Clone, Debug, Reflect, Visit)]
+pub struct SkinnedSprite {
+    base: Base,
+
+    #[reflect(setter = "set_color")]
+    color: InheritableVariable<Color>,
+
+    material: InheritableVariable<MaterialResource>,
+
+    #[reflect(setter = "set_bones")]
+    bones: InheritableVariable<Vec<Bone2D>>,
+
+    #[reflect(hidden)]
+    vertices: InheritableVariable<Vec<SkinVertex>>,
+
+    #[reflect(hidden)]
+    triangles: InheritableVariable<Vec<TriangleDefinition>>,
+}
+
+impl Default for SkinnedSprite {
+    fn default() -> Self {
+        SkinnedSpriteBuilder::new(BaseBuilder::new()).build_skinned_sprite()
+    }
+}
+
+impl Deref for SkinnedSprite {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for SkinnedSprite {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for SkinnedSprite {
+    fn type_uuid() -> Uuid {
+        uuid!("6d2f1a8c-3b7e-4c05-9e4d-0a8b5f7c2e19")
+    }
+}
+
+impl SkinnedSprite {
+    /// Returns current color of the sprite.
+    pub fn color(&self) -> Color {
+        *self.color
+    }
+
+    /// Sets color of the sprite.
+    pub fn set_color(&mut self, color: Color) -> Color {
+        self.color.set_value_and_mark_modified(color)
+    }
+
+    /// Returns a reference to the current material used by the sprite.
+    pub fn material(&self) -> &InheritableVariable<MaterialResource> {
+        &self.material
+    }
+
+    /// Returns a reference to the current material used by the sprite.
+    pub fn material_mut(&mut self) -> &mut InheritableVariable<MaterialResource> {
+        &mut self.material
+    }
+
+    /// Returns a slice of bones of the sprite.
+    pub fn bones(&self) -> &[Bone2D] {
+        &self.bones
+    }
+
+    /// Sets new bones of the sprite. Keep in mind that vertices refer bones by their indices.
+    pub fn set_bones(&mut self, bones: Vec<Bone2D>) -> Vec<Bone2D> {
+        self.bones.set_value_and_mark_modified(bones)
+    }
+
+    /// Returns a slice of vertices of the sprite.
+    pub fn vertices(&self) -> &[SkinVertex] {
+        &self.vertices
+    }
+
+    /// Returns a mutable reference to vertices of the sprite.
+    pub fn vertices_mut(&mut self) -> &mut Vec<SkinVertex> {
+        self.vertices.get_value_mut_and_mark_modified()
+    }
+
+    /// Sets new vertices of the sprite.
+    pub fn set_vertices(&mut self, vertices: Vec<SkinVertex>) -> Vec<SkinVertex> {
+        self.vertices.set_value_and_mark_modified(vertices)
+    }
+
+    /// Returns a slice of triangles of the sprite.
+    pub fn triangles(&self) -> &[TriangleDefinition] {
+        &self.triangles
+    }
+
+    /// Sets new triangles of the sprite.
+    pub fn set_triangles(&mut self, triangles: Vec<TriangleDefinition>) -> Vec<TriangleDefinition> {
+        self.triangles.set_value_and_mark_modified(triangles)
+    }
+
+    /// Binds the sprite to the given bones. Every bone is defined by its handle and its global transform at
+    /// the moment of binding (bind pose), `global_transform` is the global transform of the sprite itself.
+    /// Weights of the vertices are left untouched.
+    pub fn bind(&mut self, bones: &[(Handle<Node>, Matrix4<f32>)], global_transform: Matrix4<f32>) {
+        let bones = bones
+            .iter()
+            .map(|(node, bone_global_transform)| Bone2D {
+                node: *node,
+                inv_bind_pose: bone_global_transform.try_inverse().unwrap_or_default()
+                    * global_transform,
+            })
+            .collect();
+        self.set_bones(bones);
+    }
+
+    /// Returns positions of the bones in the local space of the sprite at the moment of binding.
+    pub fn bind_positions(&self) -> Vec<Vector2<f32>> {
+        self.bones
+            .iter()
+            .map(|bone| {
+                let position = bone
+                    .inv_bind_pose
+                    .try_inverse()
+                    .unwrap_or_default()
+                    .transform_point(&Point3::origin());
+                Vector2::new(position.x, position.y)
+            })
+            .collect()
+    }
+
+    /// Computes weights of every vertex using distances to the bones in the bind pose. Each vertex is affected
+    /// by up to [`MAX_BONE_INFLUENCES`] closest bones, their weights are inversely proportional to squared
+    /// distances. The result is a good starting point for weight painting.
+    pub fn compute_weights_by_distance(&mut self) {
+        let bone_positions = self.bind_positions();
+        if bone_positions.is_empty() {
+            return;
+        }
+
+        for vertex in self.vertices.get_value_mut_and_mark_modified().iter_mut() {
+            let mut distances = bone_positions
+                .iter()
+                .enumerate()
+                .map(|(index, position)| (index, (position - vertex.position).norm_squared()))
+                .collect::<Vec<_>>();
+            distances.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+            vertex.bone_indices = Default::default();
+            vertex.bone_weights = Default::default();
+            for (slot, (index, distance)) in
+                distances.into_iter().take(MAX_BONE_INFLUENCES).enumerate()
+            {
+                vertex.bone_indices[slot] = index as u8;
+                vertex.bone_weights[slot] = 1.0 / distance.max(f32::EPSILON);
+            }
+            vertex.bone_weights /= vertex.bone_weights.sum();
+        }
+    }
+
+    /// Paints weight of the given bone on vertices inside a circle with the given center (in local space of
+    /// the sprite) and radius. The amount of weight decreases linearly to the border of the circle, negative
+    /// strength erases the weight.
+    pub fn paint_weight(&mut self, bone: u8, center: Vector2<f32>, radius: f32, strength: f32) {
+        if radius <= 0.0 {
+            return;
+        }
+
+        for vertex in self.vertices.get_value_mut_and_mark_modified().iter_mut() {
+            let distance = (vertex.position - center).norm();
+            if distance < radius {
+                vertex.add_weight(bone, strength * (1.0 - distance / radius));
+            }
+        }
+    }
+
+    fn skinning_matrices(&self, graph: &Graph) -> Vec<Matrix4<f32>> {
+        self.bones
+            .iter()
+            .map(|bone| {
+                graph
+                    .try_get(bone.node)
+                    .map(|node| node.global_transform() * bone.inv_bind_pose)
+                    .unwrap_or_else(|| self.global_transform())
+            })
+            .collect()
+    }
+}
+
+impl NodeTrait for SkinnedSprite {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        let mut bounding_box = AxisAlignedBoundingBox::default();
+        for vertex in self.vertices.iter() {
+            bounding_box.add_point(vertex.position.to_homogeneous());
+        }
+        bounding_box
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn collect_render_data(&self, ctx: &mut RenderContext) -> RdcControlFlow {
+        if !self.global_visibility()
+            || !self.is_globally_enabled()
+            || self.vertices.is_empty()
+            || renderer::is_shadow_pass(ctx.render_pass_name)
+        {
+            return RdcControlFlow::Continue;
+        }
+
+        let global_transform = self.global_transform();
+        let skinning_matrices = self.skinning_matrices(ctx.graph);
+
+        let vertices = self
+            .vertices
+            .iter()
+            .map(|vertex| {
+                let local_position = Point3::new(vertex.position.x, vertex.position.y, 0.0);
+
+                let mut position = Point3::origin();
+                let mut total_weight = 0.0;
+                for (index, weight) in vertex.bone_indices.iter().zip(vertex.bone_weights.iter()) {
+                    if *weight > 0.0 {
+                        if let Some(matrix) = skinning_matrices.get(*index as usize) {
+                            position += matrix.transform_point(&local_position).coords * *weight;
+                            total_weight += *weight;
+                        }
+                    }
+                }
+
+                let position = if total_weight > 0.0 {
+                    position.coords / total_weight
+                } else {
+                    global_transform.transform_point(&local_position).coords
+                };
+
+                RectangleVertex {
+                    position,
+                    tex_coord: vertex.tex_coord,
+                    color: *self.color,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let triangles = self.triangles.clone();
+        let sort_index = ctx.calculate_sorting_index(self.global_position());
+
+        ctx.storage.push_triangles(
+            RectangleVertex::layout(),
+            &self.material,
+            RenderPath::Forward,
+            0,
+            sort_index,
+            false,
+            self.self_handle,
+            &mut move |mut vertex_buffer, mut triangle_buffer| {
+                let start_vertex_index = vertex_buffer.vertex_count();
+
+                for vertex in vertices.iter() {
+                    vertex_buffer
+                        .push_vertex_raw(value_as_u8_slice(vertex))
+                        .unwrap();
+                }
+
+                triangle_buffer
+                    .push_triangles_iter_with_offset(start_vertex_index, triangles.iter().cloned());
+            },
+        );
+
+        RdcControlFlow::Continue
+    }
+}
+
+/// Binds a skinned sprite to the given bones using their current global transforms as the bind pose. See
+/// [`SkinnedSprite::bind`] for more info.
+pub fn bind(sprite: Handle<Node>, bones: &[Handle<Node>], graph: &mut Graph) {
+    graph.update_hierarchical_data();
+
+    let bind_poses = bones
+        .iter()
+        .filter_map(|bone| {
+            graph
+                .try_get(*bone)
+                .map(|node| (*bone, node.global_transform()))
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(sprite) = graph
+        .try_get_mut(sprite)
+        .and_then(|node| node.cast_mut::<SkinnedSprite>())
+    {
+        let global_transform = sprite.global_transform();
+        sprite.bind(&bind_poses, global_transform);
+    }
+}
+
+/// Creates a grid of vertices with the given size (in local units) and amount of cells. The grid is centered
+/// at the origin, texture coordinates are mapped the same way as in [`crate::scene::dim2::rectangle::Rectangle`].
+pub fn make_grid(
+    size: Vector2<f32>,
+    columns: u32,
+    rows: u32,
+) -> (Vec<SkinVertex>, Vec<TriangleDefinition>) {
+    let columns = columns.max(1);
+    let rows = rows.max(1);
+
+    let mut vertices = Vec::with_capacity(((columns + 1) * (rows + 1)) as usize);
+    for row in 0..=rows {
+        for column in 0..=columns {
+            let k = Vector2::new(column as f32 / columns as f32, row as f32 / rows as f32);
+            vertices.push(SkinVertex {
+                position: Vector2::new(
+                    (k.x - 0.5) * size.x,
+                    // Rows go from top to bottom.
+                    (0.5 - k.y) * size.y,
+                ),
+                tex_coord: Vector2::new(1.0 - k.x, k.y),
+                ..Default::default()
+            });
+        }
+    }
+
+    let mut triangles = Vec::with_capacity((columns * rows * 2) as usize);
+    for row in 0..rows {
+        for column in 0..columns {
+            let i0 = row * (columns + 1) + column;
+            let i1 = i0 + 1;
+            let i2 = i0 + columns + 1;
+            let i3 = i2 + 1;
+            triangles.push(TriangleDefinition([i0, i1, i3]));
+            triangles.push(TriangleDefinition([i3, i2, i0]));
+        }
+    }
+
+    (vertices, triangles)
+}
+
+/// Allows you to create skinned sprites in declarative manner.
+pub struct SkinnedSpriteBuilder {
+    base_builder: BaseBuilder,
+    color: Color,
+    material: MaterialResource,
+    bones: Vec<Bone2D>,
+    vertices: Vec<SkinVertex>,
+    triangles: Vec<TriangleDefinition>,
+}
+
+impl SkinnedSpriteBuilder {
+    /// Creates new skinned sprite builder. By default, the sprite is a single quad of unit size.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        let (vertices, triangles) = make_grid(Vector2::new(1.0, 1.0), 1, 1);
+        Self {
+            base_builder,
+            color: Color::WHITE,
+            material: MaterialResource::new_ok(Default::default(), Material::standard_2d()),
+            bones: Default::default(),
+            vertices,
+            triangles,
+        }
+    }
+
+    /// Sets desired color of the sprite.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the desired material of the sprite.
+    pub fn with_material(mut self, material: MaterialResource) -> Self {
+        self.material = material;
+        self
+    }
+
+    /// Sets the desired bones of the sprite.
+    pub fn with_bones(mut self, bones: Vec<Bone2D>) -> Self {
+        self.bones = bones;
+        self
+    }
+
+    /// Sets the desired geometry of the sprite.
+    pub fn with_geometry(
+        mut self,
+        vertices: Vec<SkinVertex>,
+        triangles: Vec<TriangleDefinition>,
+    ) -> Self {
+        self.vertices = vertices;
+        self.triangles = triangles;
+        self
+    }
+
+    /// Sets the geometry of the sprite to a grid, see [`make_grid`] for more info. More cells means smoother
+    /// deformation.
+    pub fn with_grid(self, size: Vector2<f32>, columns: u32, rows: u32) -> Self {
+        let (vertices, triangles) = make_grid(size, columns, rows);
+        self.with_geometry(vertices, triangles)
+    }
+
+    /// Creates new [`SkinnedSprite`] instance.
+    pub fn build_skinned_sprite(self) -> SkinnedSprite {
+        SkinnedSprite {
+            base: self.base_builder.build_base(),
+            color: self.color.into(),
+            material: self.material.into(),
+            bones: self.bones.into(),
+            vertices: self.vertices.into(),
+            triangles: self.triangles.into(),
+        }
+    }
+
+    /// Creates new [`SkinnedSprite`] instance.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_skinned_sprite())
+    }
+
+    /// Creates new [`SkinnedSprite`] instance and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SkinVertex, SkinnedSpriteBuilder};
+    use crate::{
+        core::{
+            algebra::{Matrix4, Vector2, Vector3},
+            pool::Handle,
+        },
+        scene::base::BaseBuilder,
+    };
+
+    #[test]
+    fn test_weights() {
+        let mut vertex = SkinVertex::default();
+        vertex.add_weight(0, 0.5);
+        assert_eq!(vertex.bone_weights.sum(), 1.0);
+        vertex.add_weight(1, 0.25);
+        assert!((vertex.bone_weights.sum() - 1.0).abs() < 1.0e-6);
+        assert!((vertex.bone_weights[1] - 0.25).abs() < 1.0e-6);
+        vertex.add_weight(1, -1.0);
+        assert_eq!(vertex.bone_weights[0], 1.0);
+
+        let mut sprite = SkinnedSpriteBuilder::new(BaseBuilder::new())
+            .with_grid(Vector2::new(2.0, 1.0), 2, 1)
+            .build_skinned_sprite();
+        sprite.bind(
+            &[
+                (
+                    Handle::NONE,
+                    Matrix4::new_translation(&Vector3::new(1.0, 0.0, 0.0)),
+                ),
+                (
+                    Handle::NONE,
+                    Matrix4::new_translation(&Vector3::new(-1.0, 0.0, 0.0)),
+                ),
+            ],
+            Matrix4::identity(),
+        );
+        sprite.compute_weights_by_distance();
+        for vertex in sprite.vertices() {
+            let closest = if vertex.position.x > 0.0 { 0 } else { 1 };
+            if vertex.position.x != 0.0 {
+                assert_eq!(vertex.bone_indices[0], closest);
+                assert!(vertex.bone_weights[0] > 0.5);
+            }
+        }
+    }
+}
@@ -0,0 +1,1049 @@
+//! Property drivers allow to bind a property of a scene node to an expression over properties of other
+//! nodes. See [`PropertyDrivers`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{UnitQuaternion, Vector2, Vector3, Vector4},
+        log::Log,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid_provider,
+        visitor::prelude::*,
+    },
+    graph::NodeHandleMap,
+    scene::{graph::Graph, node::Node},
+};
+use fyrox_graph::BaseSceneGraph;
+use std::{
+    f32::consts::{PI, TAU},
+    fmt::{Display, Formatter},
+};
+
+/// An error, that may occur when parsing an expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExpressionError {
+    /// The expression contains a character, that cannot be used in expressions.
+    UnexpectedCharacter {
+        /// Position of the character (in bytes).
+        position: usize,
+        /// The character itself.
+        character: char,
+    },
+    /// The expression ended, but more tokens were expected.
+    UnexpectedEnd,
+    /// A token at the given position is not expected there.
+    UnexpectedToken {
+        /// Position of the token (in bytes).
+        position: usize,
+    },
+    /// The expression uses an identifier, that is neither a variable nor a constant.
+    UnknownIdentifier(String),
+    /// The expression calls a function, that does not exist.
+    UnknownFunction(String),
+    /// A function is called with wrong amount of arguments.
+    WrongArgumentCount {
+        /// Name of the function.
+        function: String,
+        /// Amount of arguments the function expects.
+        expected: usize,
+        /// Actual amount of arguments.
+        actual: usize,
+    },
+}
+
+impl Display for ExpressionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpressionError::UnexpectedCharacter {
+                position,
+                character,
+            } => write!(f, "Unexpected character {character} at {position}"),
+            ExpressionError::UnexpectedEnd => write!(f, "Unexpected end of the expression"),
+            ExpressionError::UnexpectedToken { position } => {
+                write!(f, "Unexpected token at {position}")
+            }
+            ExpressionError::UnknownIdentifier(name) => write!(f, "Unknown identifier {name}"),
+            ExpressionError::UnknownFunction(name) => write!(f, "Unknown function {name}"),
+            ExpressionError::WrongArgumentCount {
+                function,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Function {function} expects {expected} arguments, but {actual} were given"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f32),
+    Identifier(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    OpenParen,
+    CloseParen,
+    Comma,
+}
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, ExpressionError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(position, character)) = chars.peek() {
+        let token = match character {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = position;
+                while let Some(&(i, c)) = chars.peek() {
+                    if c.is_ascii_digit() || c == '.' {
+                        end = i + c.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let number = source[position..end]
+                    .parse::<f32>()
+                    .map_err(|_| ExpressionError::UnexpectedToken { position })?;
+                tokens.push((position, Token::Number(number)));
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = position;
+                while let Some(&(i, c)) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' {
+                        end = i + c.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push((
+                    position,
+                    Token::Identifier(source[position..end].to_string()),
+                ));
+                continue;
+            }
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '%' => Token::Percent,
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            ',' => Token::Comma,
+            character => {
+                return Err(ExpressionError::UnexpectedCharacter {
+                    position,
+                    character,
+                })
+            }
+        };
+        chars.next();
+        tokens.push((position, token));
+    }
+    Ok(tokens)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Atan2,
+    Sqrt,
+    Pow,
+    Abs,
+    Sign,
+    Floor,
+    Ceil,
+    Round,
+    Min,
+    Max,
+    Clamp,
+    Lerp,
+    Radians,
+    Degrees,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "sin" => Self::Sin,
+            "cos" => Self::Cos,
+            "tan" => Self::Tan,
+            "asin" => Self::Asin,
+            "acos" => Self::Acos,
+            "atan" => Self::Atan,
+            "atan2" => Self::Atan2,
+            "sqrt" => Self::Sqrt,
+            "pow" => Self::Pow,
+            "abs" => Self::Abs,
+            "sign" => Self::Sign,
+            "floor" => Self::Floor,
+            "ceil" => Self::Ceil,
+            "round" => Self::Round,
+            "min" => Self::Min,
+            "max" => Self::Max,
+            "clamp" => Self::Clamp,
+            "lerp" => Self::Lerp,
+            "radians" => Self::Radians,
+            "degrees" => Self::Degrees,
+            _ => return None,
+        })
+    }
+
+    fn arity(self) -> usize {
+        match self {
+            Self::Atan2 | Self::Pow | Self::Min | Self::Max => 2,
+            Self::Clamp | Self::Lerp => 3,
+            _ => 1,
+        }
+    }
+
+    fn call(self, args: &[f32]) -> f32 {
+        match self {
+            Self::Sin => args[0].sin(),
+            Self::Cos => args[0].cos(),
+            Self::Tan => args[0].tan(),
+            Self::Asin => args[0].asin(),
+            Self::Acos => args[0].acos(),
+            Self::Atan => args[0].atan(),
+            Self::Atan2 => args[0].atan2(args[1]),
+            Self::Sqrt => args[0].sqrt(),
+            Self::Pow => args[0].powf(args[1]),
+            Self::Abs => args[0].abs(),
+            Self::Sign => {
+                if args[0] == 0.0 {
+                    0.0
+                } else {
+                    args[0].signum()
+                }
+            }
+            Self::Floor => args[0].floor(),
+            Self::Ceil => args[0].ceil(),
+            Self::Round => args[0].round(),
+            Self::Min => args[0].min(args[1]),
+            Self::Max => args[0].max(args[1]),
+            Self::Clamp => args[0].max(args[1]).min(args[2]),
+            Self::Lerp => args[0] + (args[1] - args[0]) * args[2],
+            Self::Radians => args[0].to_radians(),
+            Self::Degrees => args[0].to_degrees(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum ExpressionNode {
+    Constant(f32),
+    Variable(usize),
+    Negate(Box<ExpressionNode>),
+    Binary {
+        operator: BinaryOperator,
+        lhs: Box<ExpressionNode>,
+        rhs: Box<ExpressionNode>,
+    },
+    Call {
+        function: Function,
+        args: Vec<ExpressionNode>,
+    },
+}
+
+impl ExpressionNode {
+    fn evaluate(&self, variables: &[f32]) -> f32 {
+        match self {
+            ExpressionNode::Constant(value) => *value,
+            ExpressionNode::Variable(index) => variables.get(*index).cloned().unwrap_or_default(),
+            ExpressionNode::Negate(node) => -node.evaluate(variables),
+            ExpressionNode::Binary { operator, lhs, rhs } => {
+                let lhs = lhs.evaluate(variables);
+                let rhs = rhs.evaluate(variables);
+                match operator {
+                    BinaryOperator::Add => lhs + rhs,
+                    BinaryOperator::Subtract => lhs - rhs,
+                    BinaryOperator::Multiply => lhs * rhs,
+                    BinaryOperator::Divide => lhs / rhs,
+                    BinaryOperator::Remainder => lhs % rhs,
+                }
+            }
+            ExpressionNode::Call { function, args } => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.evaluate(variables))
+                    .collect::<Vec<_>>();
+                function.call(&args)
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    variables: &'a [&'a str],
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn next(&mut self) -> Result<(usize, Token), ExpressionError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(ExpressionError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ExpressionError> {
+        let (position, token) = self.next()?;
+        if token == expected {
+            Ok(())
+        } else {
+            Err(ExpressionError::UnexpectedToken { position })
+        }
+    }
+
+    fn binary(
+        &mut self,
+        operand: fn(&mut Self) -> Result<ExpressionNode, ExpressionError>,
+        operator: fn(&Token) -> Option<BinaryOperator>,
+    ) -> Result<ExpressionNode, ExpressionError> {
+        let mut lhs = operand(self)?;
+        while let Some(operator) = self.peek().and_then(operator) {
+            self.position += 1;
+            let rhs = operand(self)?;
+            lhs = ExpressionNode::Binary {
+                operator,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn sum(&mut self) -> Result<ExpressionNode, ExpressionError> {
+        self.binary(Self::product, |token| match token {
+            Token::Plus => Some(BinaryOperator::Add),
+            Token::Minus => Some(BinaryOperator::Subtract),
+            _ => None,
+        })
+    }
+
+    fn product(&mut self) -> Result<ExpressionNode, ExpressionError> {
+        self.binary(Self::unary, |token| match token {
+            Token::Star => Some(BinaryOperator::Multiply),
+            Token::Slash => Some(BinaryOperator::Divide),
+            Token::Percent => Some(BinaryOperator::Remainder),
+            _ => None,
+        })
+    }
+
+    fn unary(&mut self) -> Result<ExpressionNode, ExpressionError> {
+        match self.peek() {
+            Some(Token::Minus) => {
+                self.position += 1;
+                Ok(ExpressionNode::Negate(Box::new(self.unary()?)))
+            }
+            Some(Token::Plus) => {
+                self.position += 1;
+                self.unary()
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<ExpressionNode, ExpressionError> {
+        let (position, token) = self.next()?;
+        match token {
+            Token::Number(number) => Ok(ExpressionNode::Constant(number)),
+            Token::OpenParen => {
+                let node = self.sum()?;
+                self.expect(Token::CloseParen)?;
+                Ok(node)
+            }
+            Token::Identifier(name) => {
+                if let Some(Token::OpenParen) = self.peek() {
+                    self.position += 1;
+                    let function = Function::from_name(&name)
+                        .ok_or_else(|| ExpressionError::UnknownFunction(name.clone()))?;
+                    let mut args = Vec::new();
+                    if let Some(Token::CloseParen) = self.peek() {
+                        self.position += 1;
+                    } else {
+                        loop {
+                            args.push(self.sum()?);
+                            let (position, token) = self.next()?;
+                            match token {
+                                Token::Comma => (),
+                                Token::CloseParen => break,
+                                _ => return Err(ExpressionError::UnexpectedToken { position }),
+                            }
+                        }
+                    }
+                    if args.len() != function.arity() {
+                        return Err(ExpressionError::WrongArgumentCount {
+                            function: name,
+                            expected: function.arity(),
+                            actual: args.len(),
+                        });
+                    }
+                    Ok(ExpressionNode::Call { function, args })
+                } else if let Some(index) = self.variables.iter().position(|v| *v == name) {
+                    // Variables take precedence over the constants.
+                    Ok(ExpressionNode::Variable(index))
+                } else {
+                    match name.as_str() {
+                        "pi" => Ok(ExpressionNode::Constant(PI)),
+                        "tau" => Ok(ExpressionNode::Constant(TAU)),
+                        _ => Err(ExpressionError::UnknownIdentifier(name)),
+                    }
+                }
+            }
+            _ => Err(ExpressionError::UnexpectedToken { position }),
+        }
+    }
+}
+
+/// A parsed arithmetic expression over a set of named variables. Expressions support numbers, variables,
+/// `pi` and `tau` constants, `+ - * / %` operators, parentheses and the following functions: `sin`, `cos`,
+/// `tan`, `asin`, `acos`, `atan`, `atan2`, `sqrt`, `pow`, `abs`, `sign`, `floor`, `ceil`, `round`, `min`,
+/// `max`, `clamp(x, min, max)`, `lerp(a, b, t)`, `radians`, `degrees`.
+///
+/// ```rust
+/// # use fyrox_impl::scene::driver::Expression;
+/// let expression = Expression::parse("clamp(angle * 2, 0, pi / 2)", &["angle"]).unwrap();
+/// assert_eq!(expression.evaluate(&[0.25]), 0.5);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression {
+    root: ExpressionNode,
+}
+
+impl Expression {
+    /// Parses the given source string. `variables` is a list of names, that could be used in the
+    /// expression, their values must be passed to [`Self::evaluate`] in the same order.
+    pub fn parse(source: &str, variables: &[&str]) -> Result<Self, ExpressionError> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            position: 0,
+            variables,
+        };
+        let root = parser.sum()?;
+        if let Some((position, _)) = parser.tokens.get(parser.position) {
+            return Err(ExpressionError::UnexpectedToken {
+                position: *position,
+            });
+        }
+        Ok(Self { root })
+    }
+
+    /// Evaluates the expression using the given values of the variables.
+    pub fn evaluate(&self, variables: &[f32]) -> f32 {
+        self.root.evaluate(variables)
+    }
+}
+
+/// A named input of a driver expression. It reads a property of a node, the property must be numeric
+/// (any of the integer or floating point types or `bool`). A component of a vector property could be
+/// read by adding `.x`, `.y`, `.z` or `.w` suffix to its path, the same suffixes on a rotation give its
+/// Euler angles (in radians).
+#[derive(Clone, Debug, Default, Visit, Reflect, PartialEq)]
+pub struct DriverVariable {
+    /// Name of the variable, that is used in the expression.
+    #[reflect(description = "Name of the variable, that is used in the expression.")]
+    pub name: String,
+    /// A node, which property will be read.
+    #[reflect(description = "A node, which property will be read.")]
+    pub node: Handle<Node>,
+    /// A path to the property, for example `base.local_transform.local_rotation.x`.
+    #[reflect(
+        description = "A path to the property, for example `base.local_transform.local_rotation.x`."
+    )]
+    pub property: String,
+}
+
+uuid_provider!(DriverVariable = "ecd07c0e-4f44-48bf-a757-c0d20e13eb67");
+
+/// A driver sets a property of the target node to the value of an expression each frame. See
+/// [`PropertyDrivers`] docs for more info.
+#[derive(Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct PropertyDriver {
+    /// Whether the driver is enabled or not. Default is `true`.
+    #[reflect(description = "Whether the driver is enabled or not.")]
+    pub enabled: bool,
+    /// A node, which property will be set.
+    #[reflect(description = "A node, which property will be set.")]
+    pub target: Handle<Node>,
+    /// A path to the property, that will be set. It uses the same rules as [`DriverVariable::property`].
+    #[reflect(description = "A path to the property, that will be set.")]
+    pub property: String,
+    /// An expression, see [`Expression`] docs for supported syntax.
+    #[reflect(description = "An expression over the variables, for example `lever * 2.0`.")]
+    pub expression: String,
+    /// Variables of the expression.
+    #[reflect(description = "Variables of the expression.")]
+    pub variables: Vec<DriverVariable>,
+    #[visit(skip)]
+    #[reflect(
+        read_only,
+        description = "Result of the last evaluation of the driver (the value or an error)."
+    )]
+    status: String,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    compiled: Option<CompiledExpression>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct CompiledExpression {
+    source: String,
+    variables: Vec<String>,
+    expression: Result<Expression, ExpressionError>,
+}
+
+uuid_provider!(PropertyDriver = "0e2d04f3-8a3f-437a-8ad7-78d1c6b5ca36");
+
+impl Default for PropertyDriver {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            target: Default::default(),
+            property: Default::default(),
+            expression: Default::default(),
+            variables: Default::default(),
+            status: Default::default(),
+            compiled: None,
+        }
+    }
+}
+
+impl PropertyDriver {
+    /// Creates a new driver, that sets the given property of the target node.
+    pub fn new(target: Handle<Node>, property: &str, expression: &str) -> Self {
+        Self {
+            target,
+            property: property.to_string(),
+            expression: expression.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds a new variable to the driver.
+    pub fn with_variable(mut self, name: &str, node: Handle<Node>, property: &str) -> Self {
+        self.variables.push(DriverVariable {
+            name: name.to_string(),
+            node,
+            property: property.to_string(),
+        });
+        self
+    }
+
+    /// Returns a result of the last evaluation of the driver. It is either the value, that was set to
+    /// the property, or a description of an error.
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    fn compile(&mut self) -> Result<&Expression, String> {
+        let up_to_date = self.compiled.as_ref().is_some_and(|compiled| {
+            compiled.source == self.expression
+                && compiled
+                    .variables
+                    .iter()
+                    .eq(self.variables.iter().map(|v| &v.name))
+        });
+        if !up_to_date {
+            let names = self
+                .variables
+                .iter()
+                .map(|v| v.name.as_str())
+                .collect::<Vec<_>>();
+            self.compiled = Some(CompiledExpression {
+                source: self.expression.clone(),
+                variables: self.variables.iter().map(|v| v.name.clone()).collect(),
+                expression: Expression::parse(&self.expression, &names),
+            });
+        }
+        match self.compiled.as_ref().map(|compiled| &compiled.expression) {
+            Some(Ok(expression)) => Ok(expression),
+            Some(Err(err)) => Err(format!("Invalid expression: {err}")),
+            None => unreachable!(),
+        }
+    }
+
+    fn evaluate(&mut self, graph: &mut Graph) -> Result<f32, String> {
+        let inputs = self
+            .variables
+            .iter()
+            .map(|variable| read_property(graph, variable.node, &variable.property))
+            .collect::<Result<Vec<_>, _>>()?;
+        let value = self.compile()?.evaluate(&inputs);
+        write_property(graph, self.target, &self.property, value)?;
+        Ok(value)
+    }
+
+    fn set_status(&mut self, result: Result<f32, String>) {
+        match result {
+            Ok(value) => self.status = format!("Ok: {value}"),
+            Err(err) => {
+                // Report each error only once, otherwise the log will be flooded with the same message
+                // each frame.
+                if self.status != err {
+                    Log::err(format!(
+                        "Property driver of {} property of {} node failed: {}",
+                        self.property, self.target, err
+                    ));
+                }
+                self.status = err;
+            }
+        }
+    }
+}
+
+/// A set of property drivers of a scene. A driver binds a property of a node to an expression over
+/// properties of other nodes, for example a rotation of a door could be driven by an angle of a lever:
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::pool::Handle,
+/// #     scene::{driver::PropertyDriver, node::Node, Scene},
+/// # };
+/// fn rig_door(scene: &mut Scene, lever: Handle<Node>, door: Handle<Node>) {
+///     scene.drivers.drivers.push(
+///         PropertyDriver::new(door, "base.local_transform.local_rotation.y", "clamp(angle * 2.0, 0.0, pi / 2.0)")
+///             .with_variable("angle", lever, "base.local_transform.local_rotation.x"),
+///     );
+/// }
+/// ```
+///
+/// Drivers are evaluated at the beginning of each scene update (except paused updates), before the graph
+/// itself is updated, so the changes are visible in the same frame. Drivers are sorted by their
+/// dependencies: if a driver reads a property, that is set by some other driver, it will be evaluated
+/// after it. Drivers, that form a dependency cycle (including a driver that reads its own target
+/// property), are not evaluated at all and an error is written to the log.
+///
+/// Properties are set using reflection, which means that custom property setters are respected.
+/// Rotations are set using Euler angles, so the drivers should not be used for rotations about
+/// multiple axes at once - it may suffer from gimbal lock.
+#[derive(Clone, Debug, Default, Visit, Reflect, PartialEq)]
+pub struct PropertyDrivers {
+    /// A list of the drivers. The order of the drivers does not matter, they're sorted by their
+    /// dependencies before evaluation.
+    #[reflect(description = "A list of the drivers of the scene.")]
+    pub drivers: Vec<PropertyDriver>,
+}
+
+fn paths_overlap(a: &str, b: &str) -> bool {
+    fn is_prefix(prefix: &str, path: &str) -> bool {
+        path.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.') || rest.starts_with('['))
+    }
+
+    is_prefix(a, b) || is_prefix(b, a)
+}
+
+impl PropertyDrivers {
+    /// Returns indices of the enabled drivers in their evaluation order (first) and indices of the
+    /// drivers, that are part of a dependency cycle or depend on such cycle (second).
+    pub fn evaluation_order(&self) -> (Vec<usize>, Vec<usize>) {
+        let enabled = self
+            .drivers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, driver)| driver.enabled.then_some(i))
+            .collect::<Vec<_>>();
+
+        // An edge from i to j means that the driver j reads the property set by the driver i.
+        let depends_on = |j: usize, i: usize| {
+            let writer = &self.drivers[i];
+            self.drivers[j].variables.iter().any(|variable| {
+                variable.node == writer.target
+                    && paths_overlap(&variable.property, &writer.property)
+            })
+        };
+
+        let mut in_degree = enabled
+            .iter()
+            .map(|&j| enabled.iter().filter(|&&i| depends_on(j, i)).count())
+            .collect::<Vec<_>>();
+
+        let mut order = Vec::with_capacity(enabled.len());
+        let mut visited = vec![false; enabled.len()];
+        while let Some(k) = (0..enabled.len()).find(|&k| !visited[k] && in_degree[k] == 0) {
+            visited[k] = true;
+            order.push(enabled[k]);
+            for (n, &j) in enabled.iter().enumerate() {
+                if !visited[n] && depends_on(j, enabled[k]) {
+                    in_degree[n] -= 1;
+                }
+            }
+        }
+
+        let cyclic = enabled
+            .iter()
+            .zip(visited)
+            .filter_map(|(&i, visited)| (!visited).then_some(i))
+            .collect();
+
+        (order, cyclic)
+    }
+
+    pub(crate) fn remap_handles(&mut self, old_new_map: &NodeHandleMap<Node>) {
+        for driver in self.drivers.iter_mut() {
+            old_new_map.try_map(&mut driver.target);
+            for variable in driver.variables.iter_mut() {
+                old_new_map.try_map(&mut variable.node);
+            }
+        }
+    }
+
+    pub(crate) fn update(&mut self, graph: &mut Graph, paused: bool) {
+        if paused || self.drivers.is_empty() {
+            return;
+        }
+
+        let (order, cyclic) = self.evaluation_order();
+
+        for index in cyclic {
+            self.drivers[index]
+                .set_status(Err("The driver is a part of a dependency cycle".to_string()));
+        }
+
+        for index in order {
+            let driver = &mut self.drivers[index];
+            let result = driver.evaluate(graph);
+            driver.set_status(result);
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Component {
+    X,
+    Y,
+    Z,
+    W,
+}
+
+fn split_component(path: &str) -> Option<(&str, Component)> {
+    let (parent, component) = path.rsplit_once('.')?;
+    let component = match component {
+        "x" => Component::X,
+        "y" => Component::Y,
+        "z" => Component::Z,
+        "w" => Component::W,
+        _ => return None,
+    };
+    Some((parent, component))
+}
+
+fn read_scalar(property: &dyn Reflect) -> Option<f32> {
+    let mut value = None;
+    property.as_any(&mut |any| {
+        macro_rules! try_cast {
+            ($($ty:ty),*) => {
+                $(
+                    if let Some(v) = any.downcast_ref::<$ty>() {
+                        value = Some(*v as f32);
+                        return;
+                    }
+                )*
+            };
+        }
+
+        try_cast!(f32, f64, i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+
+        if let Some(v) = any.downcast_ref::<bool>() {
+            value = Some(if *v { 1.0 } else { 0.0 });
+        }
+    });
+    value
+}
+
+fn read_component(property: &dyn Reflect, component: Component) -> Option<f32> {
+    let mut value = None;
+    property.as_any(&mut |any| {
+        let index = component as usize;
+        if let Some(v) = any.downcast_ref::<Vector2<f32>>() {
+            value = v.get(index).cloned();
+        } else if let Some(v) = any.downcast_ref::<Vector3<f32>>() {
+            value = v.get(index).cloned();
+        } else if let Some(v) = any.downcast_ref::<Vector4<f32>>() {
+            value = v.get(index).cloned();
+        } else if let Some(v) = any.downcast_ref::<UnitQuaternion<f32>>() {
+            let (x, y, z) = v.euler_angles();
+            value = [x, y, z].get(index).cloned();
+        }
+    });
+    value
+}
+
+fn cast_scalar(property: &dyn Reflect, value: f32) -> Option<Box<dyn Reflect>> {
+    let mut result = None;
+    property.as_any(&mut |any| {
+        macro_rules! try_cast {
+            ($($ty:ty),*) => {
+                $(
+                    if any.is::<$ty>() {
+                        result = Some(Box::new(value as $ty) as Box<dyn Reflect>);
+                        return;
+                    }
+                )*
+            };
+        }
+
+        try_cast!(f32, f64, i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+
+        if any.is::<bool>() {
+            result = Some(Box::new(value != 0.0) as Box<dyn Reflect>);
+        }
+    });
+    result
+}
+
+fn cast_component(
+    property: &dyn Reflect,
+    component: Component,
+    value: f32,
+) -> Option<Box<dyn Reflect>> {
+    let mut result = None;
+    property.as_any(&mut |any| {
+        let index = component as usize;
+        if let Some(v) = any.downcast_ref::<Vector2<f32>>() {
+            let mut v = *v;
+            if let Some(c) = v.get_mut(index) {
+                *c = value;
+                result = Some(Box::new(v) as Box<dyn Reflect>);
+            }
+        } else if let Some(v) = any.downcast_ref::<Vector3<f32>>() {
+            let mut v = *v;
+            if let Some(c) = v.get_mut(index) {
+                *c = value;
+                result = Some(Box::new(v) as Box<dyn Reflect>);
+            }
+        } else if let Some(v) = any.downcast_ref::<Vector4<f32>>() {
+            let mut v = *v;
+            if let Some(c) = v.get_mut(index) {
+                *c = value;
+                result = Some(Box::new(v) as Box<dyn Reflect>);
+            }
+        } else if let Some(v) = any.downcast_ref::<UnitQuaternion<f32>>() {
+            let mut angles = <[f32; 3]>::from(v.euler_angles());
+            if let Some(c) = angles.get_mut(index) {
+                *c = value;
+                let [x, y, z] = angles;
+                result =
+                    Some(Box::new(UnitQuaternion::from_euler_angles(x, y, z)) as Box<dyn Reflect>);
+            }
+        }
+    });
+    result
+}
+
+fn read_property(graph: &Graph, handle: Handle<Node>, path: &str) -> Result<f32, String> {
+    let node = graph
+        .try_get(handle)
+        .ok_or_else(|| format!("Node {handle} does not exist"))?;
+
+    let mut value = None;
+    node.resolve_path(path, &mut |result| {
+        if let Ok(property) = result {
+            value = read_scalar(property);
+        }
+    });
+
+    if value.is_none() {
+        if let Some((parent, component)) = split_component(path) {
+            node.resolve_path(parent, &mut |result| {
+                if let Ok(property) = result {
+                    value = read_component(property, component);
+                }
+            });
+        }
+    }
+
+    value.ok_or_else(|| format!("Unable to read {path} property of {handle} node as a number"))
+}
+
+fn write_property(
+    graph: &mut Graph,
+    handle: Handle<Node>,
+    path: &str,
+    value: f32,
+) -> Result<(), String> {
+    let node = graph
+        .try_get_mut(handle)
+        .ok_or_else(|| format!("Node {handle} does not exist"))?;
+
+    // Find the actual type of the property first, the new value must have exactly the same type.
+    let mut new_value = None;
+    let mut target_path = path;
+    node.resolve_path(path, &mut |result| {
+        if let Ok(property) = result {
+            new_value = cast_scalar(property, value);
+        }
+    });
+    if new_value.is_none() {
+        if let Some((parent, component)) = split_component(path) {
+            node.resolve_path(parent, &mut |result| {
+                if let Ok(property) = result {
+                    new_value = cast_component(property, component, value);
+                }
+            });
+            target_path = parent;
+        }
+    }
+
+    let new_value = new_value
+        .ok_or_else(|| format!("Unable to set {path} property of {handle} node to a number"))?;
+
+    let mut result = Ok(());
+    let mut new_value = Some(new_value);
+    node.as_reflect_mut(&mut |object| {
+        object.set_field_by_path(target_path, new_value.take().unwrap(), &mut |r| {
+            if r.is_err() {
+                result = Err(format!("Unable to set {path} property of {handle} node"));
+            }
+        })
+    });
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, pool::Handle},
+        scene::{
+            base::BaseBuilder,
+            driver::{Expression, ExpressionError, PropertyDriver, PropertyDrivers},
+            graph::Graph,
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_expression() {
+        let eval = |source: &str, values: &[f32]| {
+            Expression::parse(source, &["a", "b"])
+                .unwrap()
+                .evaluate(values)
+        };
+
+        assert_eq!(eval("1 + 2 * 3", &[]), 7.0);
+        assert_eq!(eval("(1 + 2) * 3", &[]), 9.0);
+        assert_eq!(eval("-a + b", &[2.0, 5.0]), 3.0);
+        assert_eq!(eval("10 % 4 - 2 / 4", &[]), 1.5);
+        assert_eq!(eval("max(a, b) + min(a, b)", &[2.0, 5.0]), 7.0);
+        assert_eq!(eval("clamp(a, 0, 1) + lerp(0, 10, b)", &[3.0, 0.5]), 6.0);
+        assert_eq!(eval("abs(-2.5)", &[]), 2.5);
+
+        assert_eq!(
+            Expression::parse("c + 1", &["a"]),
+            Err(ExpressionError::UnknownIdentifier("c".to_string()))
+        );
+        assert_eq!(
+            Expression::parse("foo(1)", &[]),
+            Err(ExpressionError::UnknownFunction("foo".to_string()))
+        );
+        assert!(matches!(
+            Expression::parse("min(1)", &[]),
+            Err(ExpressionError::WrongArgumentCount { .. })
+        ));
+        assert_eq!(
+            Expression::parse("1 +", &[]),
+            Err(ExpressionError::UnexpectedEnd)
+        );
+        assert_eq!(
+            Expression::parse("1 2", &[]),
+            Err(ExpressionError::UnexpectedToken { position: 2 })
+        );
+        assert!(matches!(
+            Expression::parse("1 $ 2", &[]),
+            Err(ExpressionError::UnexpectedCharacter { character: '$', .. })
+        ));
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        let a = Handle::new(1, 1);
+        let b = Handle::new(2, 1);
+        let c = Handle::new(3, 1);
+        let drivers = PropertyDrivers {
+            drivers: vec![
+                // c.x = b.x, must be evaluated after the next one.
+                PropertyDriver::new(c, "foo.x", "v").with_variable("v", b, "foo.x"),
+                // b.x = a.x
+                PropertyDriver::new(b, "foo.x", "v").with_variable("v", a, "foo"),
+                // a.y = a.y + 1 - reads its own target.
+                PropertyDriver::new(a, "bar", "v + 1").with_variable("v", a, "bar"),
+                // Two drivers depending on each other.
+                PropertyDriver::new(a, "baz", "v").with_variable("v", c, "baz"),
+                PropertyDriver::new(c, "baz", "v").with_variable("v", a, "baz"),
+            ],
+        };
+
+        let (order, cyclic) = drivers.evaluation_order();
+        assert_eq!(order, vec![1, 0]);
+        assert_eq!(cyclic, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_driver_update() {
+        let mut graph = Graph::new();
+        let lever = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(1.0, 2.0, 3.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        let door = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        let mut drivers = PropertyDrivers {
+            drivers: vec![
+                PropertyDriver::new(door, "base.local_transform.local_rotation.y", "angle * 0.5")
+                    .with_variable("angle", door, "base.local_transform.local_position.x"),
+                PropertyDriver::new(door, "base.local_transform.local_position.x", "x + y")
+                    .with_variable("x", lever, "base.local_transform.local_position.x")
+                    .with_variable("y", lever, "base.local_transform.local_position.y"),
+                PropertyDriver::new(door, "base.visibility", "0"),
+            ],
+        };
+
+        drivers.update(&mut graph, false);
+
+        let door_ref = &graph[door];
+        assert_eq!(door_ref.local_transform().position().x, 3.0);
+        let (_, angle, _) = door_ref.local_transform().rotation().euler_angles();
+        assert!((angle - 1.5).abs() < 1.0e-5);
+        assert!(!door_ref.visibility());
+        assert_eq!(drivers.drivers[0].status(), "Ok: 1.5");
+    }
+}
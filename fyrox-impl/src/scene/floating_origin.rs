@@ -0,0 +1,115 @@
+//! Floating origin is used to avoid precision issues (jitter) in large worlds. See [`FloatingOrigin`] docs for
+//! more info.
+
+use crate::{
+    core::{algebra::Vector3, pool::Handle, reflect::prelude::*, visitor::prelude::*},
+    scene::{graph::Graph, node::Node},
+};
+use fyrox_graph::BaseSceneGraph;
+
+/// Floating origin periodically shifts the whole scene graph, so the anchor node (usually a camera or a player)
+/// will be at the origin of the world. It is used to keep coordinates near the origin in large open worlds, where
+/// precision of [`f32`] is not enough and objects far away from the origin start to jitter.
+///
+/// The shift is performed at the beginning of the scene update when the distance between the anchor and the origin
+/// exceeds the threshold. Native physics bodies, colliders and sound sources are shifted immediately, navigational
+/// meshes are shifted too (see [`crate::scene::graph::Graph::shift_origin`] for details). Any other world-space data
+/// stored outside the scene (for example, in scripts) must be shifted manually, use [`FloatingOrigin::last_shift`] to
+/// detect a shift:
+///
+/// ```rust
+/// # use fyrox_impl::{core::algebra::Vector3, scene::Scene};
+/// fn update_waypoints(scene: &Scene, waypoints: &mut [Vector3<f32>]) {
+///     if let Some(offset) = scene.floating_origin.last_shift() {
+///         for waypoint in waypoints {
+///             *waypoint -= offset;
+///         }
+///     }
+/// }
+/// ```
+///
+/// Origin shifting is performed only on full updates of the scene graph (when there's no node overrides in
+/// [`crate::scene::graph::GraphUpdateSwitches`] and the update is not paused), so the editor won't shift the
+/// scene while editing.
+#[derive(Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct FloatingOrigin {
+    /// Whether the origin shifting is enabled or not. Default is `false`.
+    #[reflect(description = "Whether the origin shifting is enabled or not.")]
+    pub enabled: bool,
+    /// A node, that will be moved to the origin. Usually it is an active camera or a player.
+    #[reflect(
+        description = "A node, that will be moved to the origin. Usually it is \
+        an active camera or a player."
+    )]
+    pub anchor: Handle<Node>,
+    /// The max distance between the anchor and the origin, when exceeded, the scene will be shifted.
+    #[reflect(
+        description = "The max distance between the anchor and the origin, when exceeded, \
+        the scene will be shifted.",
+        min_value = 0.0
+    )]
+    pub threshold: f32,
+    #[reflect(hidden)]
+    total_offset: Vector3<f64>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    last_shift: Option<Vector3<f32>>,
+}
+
+impl Default for FloatingOrigin {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            anchor: Default::default(),
+            threshold: 1000.0,
+            total_offset: Default::default(),
+            last_shift: None,
+        }
+    }
+}
+
+impl FloatingOrigin {
+    /// Returns the total offset of the origin, it is a sum of every shift performed so far. It is stored
+    /// with double precision, so it could be used to calculate "true" world-space positions.
+    pub fn total_offset(&self) -> Vector3<f64> {
+        self.total_offset
+    }
+
+    /// Returns an offset of the shift, that was performed in the current frame (if any).
+    pub fn last_shift(&self) -> Option<Vector3<f32>> {
+        self.last_shift
+    }
+
+    /// Converts the given position in the shifted coordinate system to the "true" world-space position.
+    pub fn to_absolute(&self, position: Vector3<f32>) -> Vector3<f64> {
+        self.total_offset + position.cast::<f64>()
+    }
+
+    /// Converts the given "true" world-space position to the shifted coordinate system.
+    pub fn to_relative(&self, position: Vector3<f64>) -> Vector3<f32> {
+        (position - self.total_offset).cast::<f32>()
+    }
+
+    /// Shifts the origin of the given graph by the given offset. Could be used to perform a shift manually,
+    /// for example when teleporting a player to a far location.
+    pub fn shift(&mut self, graph: &mut Graph, offset: Vector3<f32>) {
+        graph.shift_origin(offset);
+        self.total_offset += offset.cast::<f64>();
+        self.last_shift = Some(self.last_shift.unwrap_or_default() + offset);
+    }
+
+    pub(crate) fn update(&mut self, graph: &mut Graph, full_update: bool) {
+        self.last_shift = None;
+
+        if !self.enabled || !full_update {
+            return;
+        }
+
+        if let Some(anchor) = graph.try_get(self.anchor) {
+            let position = anchor.global_position();
+            if position.norm() > self.threshold {
+                self.shift(graph, position);
+            }
+        }
+    }
+}
@@ -31,6 +31,7 @@ use crate::{
         math::{aabb::AxisAlignedBoundingBox, Matrix4Ext},
         pool::{ErasedHandle, Handle, MultiBorrowContext, Pool, Ticket},
         reflect::prelude::*,
+        scope_profile,
         sstorage::ImmutableString,
         visitor::{Visit, VisitResult, Visitor},
     },
@@ -38,7 +39,7 @@ use crate::{
     material::{shader::SamplerFallback, MaterialResource, PropertyValue},
     resource::model::{Model, ModelResource, ModelResourceExtension},
     scene::{
-        base::{NodeScriptMessage, SceneNodeId},
+        base::{NodeScriptMessage, SceneNodeId, LAYER_COUNT},
         camera::Camera,
         dim2::{self},
         graph::{
@@ -47,13 +48,19 @@ use crate::{
         },
         mesh::Mesh,
         navmesh,
-        node::{container::NodeContainer, Node, NodeTrait, SyncContext, UpdateContext},
+        node::{
+            container::NodeContainer, Node, NodeTrait, ParallelUpdateContext, SyncContext,
+            UpdateContext,
+        },
         pivot::Pivot,
         sound::context::SoundContext,
         transform::TransformBuilder,
     },
     script::ScriptTrait,
-    utils::lightmap::{self, Lightmap},
+    utils::{
+        jobs::JobSystem,
+        lightmap::{self, Lightmap},
+    },
 };
 use fxhash::{FxHashMap, FxHashSet};
 use std::{
@@ -87,6 +94,10 @@ pub struct GraphPerformanceStatistics {
 
     /// A time which was required to render sounds.
     pub sound_update_time: Duration,
+
+    /// Amount of time that was needed to perform parallel update of the nodes. See
+    /// [`NodeTrait::parallel_update`] for more info.
+    pub parallel_update_time: Duration,
 }
 
 impl GraphPerformanceStatistics {
@@ -97,6 +108,7 @@ impl GraphPerformanceStatistics {
             + self.physics.total()
             + self.physics2d.total()
             + self.sound_update_time
+            + self.parallel_update_time
     }
 }
 
@@ -141,6 +153,22 @@ pub struct Graph {
     pub(crate) script_message_receiver: Receiver<NodeScriptMessage>,
 
     instance_id_map: FxHashMap<SceneNodeId, Handle<Node>>,
+
+    // Bit mask of hidden layers. It is a runtime-only state, which is not serialized.
+    #[reflect(hidden)]
+    hidden_layers: u32,
+}
+
+/// Creates a description of a node for the panic messages caused by dangling node handles (only
+/// with `pool_debug` feature).
+fn describe_node(node: &Node) -> String {
+    format!("{} node \"{}\"", node.type_name(), node.name())
+}
+
+fn new_node_pool() -> NodePool {
+    let mut pool = Pool::new();
+    pool.set_debug_describer(describe_node);
+    pool
 }
 
 impl Default for Graph {
@@ -151,7 +179,7 @@ impl Default for Graph {
             physics: PhysicsWorld::new(),
             physics2d: dim2::physics::PhysicsWorld::new(),
             root: Handle::NONE,
-            pool: Pool::new(),
+            pool: new_node_pool(),
             stack: Vec::new(),
             sound_context: Default::default(),
             performance_statistics: Default::default(),
@@ -160,6 +188,7 @@ impl Default for Graph {
             script_message_sender: tx,
             lightmap: None,
             instance_id_map: Default::default(),
+            hidden_layers: 0,
         }
     }
 }
@@ -204,7 +233,7 @@ fn isometric_local_transform(nodes: &NodePool, node: Handle<Node>) -> Matrix4<f3
         .matrix()
 }
 
-fn isometric_global_transform(nodes: &NodePool, node: Handle<Node>) -> Matrix4<f32> {
+pub(crate) fn isometric_global_transform(nodes: &NodePool, node: Handle<Node>) -> Matrix4<f32> {
     let parent = nodes[node].parent();
     if parent.is_some() {
         isometric_global_transform(nodes, parent) * isometric_local_transform(nodes, node)
@@ -237,6 +266,9 @@ pub struct GraphUpdateSwitches {
     /// Whether the graph update is paused or not. Paused graphs won't be updated and their sound content will be also paused
     /// so it won't emit any sounds.
     pub paused: bool,
+    /// Enables or disables parallel update of the nodes on worker threads of the global
+    /// [`JobSystem`]. If set to `false`, the parallel update will be done on the calling thread.
+    pub parallel: bool,
 }
 
 impl Default for GraphUpdateSwitches {
@@ -247,6 +279,7 @@ impl Default for GraphUpdateSwitches {
             node_overrides: Default::default(),
             delete_dead_nodes: true,
             paused: false,
+            parallel: true,
         }
     }
 }
@@ -264,7 +297,7 @@ impl Graph {
         root_node.set_name("__ROOT__");
 
         // Add it to the pool.
-        let mut pool = Pool::new();
+        let mut pool = new_node_pool();
         let root = pool.spawn(Node::new(root_node));
         pool[root].self_handle = root;
 
@@ -283,6 +316,7 @@ impl Graph {
             script_message_sender: tx,
             lightmap: None,
             instance_id_map,
+            hidden_layers: 0,
         }
     }
 
@@ -653,21 +687,7 @@ impl Graph {
         }
 
         // Sync materials with shaders.
-        let mut materials = FxHashSet::default();
-        for node in self.linear_iter_mut() {
-            (node as &mut dyn Reflect).enumerate_fields_recursively(
-                &mut |_, _, v| {
-                    v.downcast_ref::<MaterialResource>(&mut |material| {
-                        if let Some(material) = material {
-                            materials.insert(material.clone());
-                        }
-                    })
-                },
-                &[TypeId::of::<UntypedResource>()],
-            );
-        }
-
-        for material in materials {
+        for material in self.collect_used_materials() {
             let mut material_state = material.state();
             if let Some(material) = material_state.data() {
                 material.sync_to_shader(resource_manager);
@@ -679,6 +699,29 @@ impl Graph {
         Log::writeln(MessageKind::Information, "Graph resolved successfully!");
     }
 
+    /// Collects all materials used by the nodes of the graph. It uses reflection to "scan" the nodes,
+    /// so if some fields marked with `#[reflect(hidden)]` attribute, then such field will be ignored!
+    /// Every material is listed only once, even if it is shared by multiple nodes.
+    pub fn collect_used_materials(&self) -> Vec<MaterialResource> {
+        let mut keys = FxHashSet::default();
+        let mut materials = Vec::new();
+        for node in self.linear_iter() {
+            (node as &dyn Reflect).enumerate_fields_recursively(
+                &mut |_, _, v| {
+                    v.downcast_ref::<MaterialResource>(&mut |material| {
+                        if let Some(material) = material {
+                            if keys.insert(material.key()) {
+                                materials.push(material.clone());
+                            }
+                        }
+                    })
+                },
+                &[TypeId::of::<UntypedResource>()],
+            );
+        }
+        materials
+    }
+
     /// Tries to set new lightmap to scene.
     pub fn set_lightmap(&mut self, lightmap: Lightmap) -> Result<Option<Lightmap>, &'static str> {
         // Assign textures to surfaces.
@@ -885,6 +928,38 @@ impl Graph {
             &mut self.physics2d,
             node_handle,
         );
+        self.apply_layer_visibility();
+    }
+
+    /// Shifts the origin of the graph by the given offset, so every node will be moved by the negated offset. It is
+    /// used to keep the coordinates near the origin in large worlds, where precision of [`f32`] is not enough (see
+    /// [`crate::scene::floating_origin::FloatingOrigin`]). Only direct children of the root node are moved, every other
+    /// node is moved with its parent. Native rigid bodies and colliders (both 3D and 2D) are teleported, positions of
+    /// native sound sources and the listener are shifted too. Particles are simulated relative to their particle
+    /// system, so they're moved with it. Nodes with world-space data are notified using
+    /// [`NodeTrait::on_origin_shifted`].
+    pub fn shift_origin(&mut self, offset: Vector3<f32>) {
+        if offset == Vector3::default() {
+            return;
+        }
+
+        if let Some(root) = self.pool.try_borrow(self.root) {
+            for child in root.children().to_vec() {
+                let transform = self.pool[child].local_transform_mut();
+                let new_position = **transform.position() - offset;
+                transform.set_position(new_position);
+            }
+        }
+
+        self.physics.shift_origin(&offset);
+        self.physics2d.shift_origin(&offset);
+        self.sound_context.shift_origin(&offset);
+
+        for node in self.pool.iter_mut() {
+            node.on_origin_shifted(&offset);
+        }
+
+        self.update_hierarchical_data();
     }
 
     /// Calculates local and global transform, global visibility for each node in graph.
@@ -894,6 +969,8 @@ impl Graph {
     /// this method.
     #[inline]
     pub fn update_hierarchical_data(&mut self) {
+        scope_profile!();
+
         Self::update_hierarchical_data_recursively(
             &self.pool,
             &mut self.sound_context,
@@ -901,9 +978,47 @@ impl Graph {
             &mut self.physics2d,
             self.root,
         );
+        self.apply_layer_visibility();
+    }
+
+    fn apply_layer_visibility(&self) {
+        if self.hidden_layers == 0 {
+            return;
+        }
+
+        // Layers are not hierarchical, so only the nodes of a hidden layer become invisible, their
+        // descendants on other layers are still visible.
+        for node in self.pool.iter() {
+            if !self.is_layer_visible(node.layer()) {
+                node.global_visibility.set(false);
+            }
+        }
+    }
+
+    /// Shows or hides every node on the given layer. Hidden nodes are not rendered, but they're still
+    /// updated as usual. Layer visibility is a runtime-only state, it is not saved with the scene and
+    /// it is not copied when cloning the graph. Layers with indices out of `[0; LAYER_COUNT)` range
+    /// are ignored.
+    #[inline]
+    pub fn set_layer_visibility(&mut self, layer: u8, visible: bool) {
+        if layer < LAYER_COUNT {
+            if visible {
+                self.hidden_layers &= !(1 << layer);
+            } else {
+                self.hidden_layers |= 1 << layer;
+            }
+        }
+    }
+
+    /// Returns `true` if the nodes on the given layer are visible, `false` - otherwise.
+    #[inline]
+    pub fn is_layer_visible(&self, layer: u8) -> bool {
+        layer >= LAYER_COUNT || self.hidden_layers & (1 << layer) == 0
     }
 
     fn sync_native(&mut self, switches: &GraphUpdateSwitches) {
+        scope_profile!();
+
         let mut sync_context = SyncContext {
             nodes: &self.pool,
             physics: &mut self.physics,
@@ -957,6 +1072,38 @@ impl Graph {
         }
     }
 
+    fn parallel_update(
+        &mut self,
+        frame_size: Vector2<f32>,
+        dt: f32,
+        switches: &GraphUpdateSwitches,
+    ) {
+        scope_profile!();
+
+        // Mutable borrows of the nodes are disjoint, so every node could be safely sent to a worker
+        // thread.
+        let nodes = self
+            .pool
+            .pair_iter_mut()
+            .filter(|(handle, node)| {
+                node.is_globally_enabled()
+                    && switches
+                        .node_overrides
+                        .as_ref()
+                        .map_or(true, |overrides| overrides.contains(handle))
+            })
+            .map(|(_, node)| node)
+            .collect::<Vec<_>>();
+
+        let context = ParallelUpdateContext { frame_size, dt };
+        let job = |node: &mut Node| node.parallel_update(&context);
+        if switches.parallel {
+            JobSystem::global().for_each(nodes, job);
+        } else {
+            nodes.into_iter().for_each(job);
+        }
+    }
+
     /// Updates nodes in the graph using given delta time.
     ///
     /// # Update Switches
@@ -964,12 +1111,18 @@ impl Graph {
     /// Update switches allows you to disable update for parts of the update pipeline, it could be useful for editors
     /// where you need to have preview mode to update only specific set of nodes, etc.
     pub fn update(&mut self, frame_size: Vector2<f32>, dt: f32, switches: GraphUpdateSwitches) {
+        scope_profile!();
+
         self.sound_context.state().pause(switches.paused);
 
         if switches.paused {
             return;
         }
 
+        let last_time = instant::Instant::now();
+        self.parallel_update(frame_size, dt, &switches);
+        self.performance_statistics.parallel_update_time = instant::Instant::now() - last_time;
+
         let last_time = instant::Instant::now();
         self.update_hierarchical_data();
         self.performance_statistics.hierarchical_properties_time =
@@ -991,6 +1144,9 @@ impl Graph {
             self.performance_statistics.physics2d = self.physics2d.performance_statistics.clone();
         }
 
+        self.sound_context
+            .update_occlusion(&self.pool, &self.physics, dt);
+
         self.performance_statistics.sound_update_time =
             self.sound_context.state().full_render_duration();
 
@@ -1071,6 +1227,25 @@ impl Graph {
         self.pool.iter()
     }
 
+    /// Returns an iterator over handles of every node, that has the given tag either as its main tag
+    /// or as one of its additional tags.
+    #[inline]
+    pub fn nodes_with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = Handle<Node>> + 'a {
+        self.pool
+            .pair_iter()
+            .filter(move |(_, node)| node.has_tag(tag))
+            .map(|(handle, _)| handle)
+    }
+
+    /// Returns an iterator over handles of every node on the given layer.
+    #[inline]
+    pub fn nodes_on_layer(&self, layer: u8) -> impl Iterator<Item = Handle<Node>> + '_ {
+        self.pool
+            .pair_iter()
+            .filter(move |(_, node)| node.layer() == layer)
+            .map(|(handle, _)| handle)
+    }
+
     /// Creates new iterator that iterates over internal collection giving (handle; node) pairs.
     #[inline]
     pub fn pair_iter_mut(&mut self) -> impl Iterator<Item = (Handle<Node>, &mut Node)> {
@@ -1649,7 +1824,7 @@ mod test {
     use crate::{
         asset::{io::FsResourceIo, manager::ResourceManager},
         core::{
-            algebra::{Matrix4, Vector3},
+            algebra::{Matrix4, Point3, Vector2, Vector3},
             futures::executor::block_on,
             pool::Handle,
             reflect::prelude::*,
@@ -1658,16 +1833,19 @@ mod test {
         },
         engine::{self, SerializationContext},
         graph::{BaseSceneGraph, SceneGraph},
+        material::{Material, MaterialResource},
         resource::model::{Model, ModelResourceExtension},
         scene::{
             base::BaseBuilder,
-            graph::Graph,
+            collider::{ColliderBuilder, ColliderShape},
+            graph::{physics::RayCastOptions, Graph, GraphUpdateSwitches},
             mesh::{
                 surface::{SurfaceBuilder, SurfaceData, SurfaceResource},
                 MeshBuilder,
             },
             node::Node,
             pivot::{Pivot, PivotBuilder},
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
             transform::TransformBuilder,
             Scene, SceneLoader,
         },
@@ -1801,6 +1979,205 @@ mod test {
         assert_eq!(graph.pool.alive_count(), 4);
     }
 
+    #[test]
+    fn test_shift_origin() {
+        let mut scene = Scene::new();
+
+        let child = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 1.0, 0.0))
+                    .build(),
+            ),
+        )
+        .build(&mut scene.graph);
+        let parent = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[child])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(2000.0, 0.0, 0.0))
+                        .build(),
+                ),
+        )
+        .build(&mut scene.graph);
+        scene.graph.update_hierarchical_data();
+
+        scene.floating_origin.enabled = true;
+        scene.floating_origin.anchor = child;
+        scene.floating_origin.update(&mut scene.graph, true);
+
+        let offset = Vector3::new(2000.0, 1.0, 0.0);
+        assert_eq!(scene.floating_origin.last_shift(), Some(offset));
+        assert_eq!(scene.graph[child].global_position(), Vector3::default());
+        assert_eq!(
+            scene.graph[parent].global_position(),
+            Vector3::new(0.0, -1.0, 0.0)
+        );
+        // Local position of the child must stay the same.
+        assert_eq!(
+            **scene.graph[child].local_transform().position(),
+            Vector3::new(0.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            scene.floating_origin.to_absolute(Vector3::default()),
+            offset.cast::<f64>()
+        );
+
+        // The anchor is at the origin now, so there's nothing to shift.
+        scene.floating_origin.update(&mut scene.graph, true);
+        assert_eq!(scene.floating_origin.last_shift(), None);
+    }
+
+    #[test]
+    fn test_shift_origin_physics() {
+        let mut scene = Scene::new();
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(1.0))
+            .build(&mut scene.graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[collider])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(2000.0, 0.0, 0.0))
+                        .build(),
+                ),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut scene.graph);
+        // Native collider is created only when its native body already exists.
+        for _ in 0..2 {
+            scene
+                .graph
+                .update(Vector2::new(1.0, 1.0), 1.0 / 60.0, Default::default());
+        }
+
+        scene
+            .floating_origin
+            .shift(&mut scene.graph, Vector3::new(2000.0, 0.0, 0.0));
+
+        // The native collider must be moved immediately, without waiting for the next physics step.
+        let mut intersections = Vec::new();
+        scene.graph.physics.cast_ray(
+            RayCastOptions {
+                ray_origin: Point3::new(0.0, 10.0, 0.0),
+                ray_direction: Vector3::new(0.0, -1.0, 0.0),
+                max_len: 20.0,
+                groups: Default::default(),
+                sort_results: false,
+            },
+            &mut intersections,
+        );
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections[0].collider, collider);
+    }
+
+    #[test]
+    fn test_parallel_update() {
+        for parallel in [false, true] {
+            let mut graph = Graph::new();
+
+            let meshes = (0..100)
+                .map(|i| {
+                    MeshBuilder::new(
+                        BaseBuilder::new().with_local_transform(
+                            TransformBuilder::new()
+                                .with_local_position(Vector3::new(i as f32, 0.0, 0.0))
+                                .build(),
+                        ),
+                    )
+                    .with_surfaces(vec![SurfaceBuilder::new(SurfaceResource::new_ok(
+                        ResourceKind::Embedded,
+                        SurfaceData::make_cube(Matrix4::identity()),
+                    ))
+                    .build()])
+                    .build(&mut graph)
+                })
+                .collect::<Vec<_>>();
+
+            // World bounding boxes are calculated using global transforms from the previous frame.
+            for _ in 0..2 {
+                graph.update(
+                    Vector2::new(100.0, 100.0),
+                    1.0 / 60.0,
+                    GraphUpdateSwitches {
+                        parallel,
+                        ..Default::default()
+                    },
+                );
+            }
+
+            for (i, mesh) in meshes.into_iter().enumerate() {
+                assert_eq!(
+                    graph[mesh].world_bounding_box().center(),
+                    Vector3::new(i as f32, 0.0, 0.0)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_collect_used_materials() {
+        let mut graph = Graph::new();
+
+        let material = MaterialResource::new_ok(Default::default(), Material::standard());
+        for _ in 0..2 {
+            MeshBuilder::new(BaseBuilder::new())
+                .with_surfaces(vec![SurfaceBuilder::new(SurfaceResource::new_ok(
+                    ResourceKind::Embedded,
+                    SurfaceData::make_cube(Matrix4::identity()),
+                ))
+                .with_material(material.clone())
+                .build()])
+                .build(&mut graph);
+        }
+
+        let materials = graph.collect_used_materials();
+        assert_eq!(materials.len(), 1);
+        assert!(materials.contains(&material));
+    }
+
+    #[test]
+    fn test_layers_and_tags() {
+        let mut graph = Graph::new();
+
+        let child = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_tag("Enemy".to_string())
+                .with_tags(vec!["Boss".to_string()]),
+        )
+        .build(&mut graph);
+        let parent = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_layer(3)
+                .with_tags(vec!["Boss".to_string()])
+                .with_children(&[child]),
+        )
+        .build(&mut graph);
+
+        let mut bosses = graph.nodes_with_tag("Boss").collect::<Vec<_>>();
+        bosses.sort();
+        assert_eq!(bosses, vec![child, parent]);
+        assert_eq!(
+            graph.nodes_with_tag("Enemy").collect::<Vec<_>>(),
+            vec![child]
+        );
+        assert_eq!(graph.nodes_on_layer(3).collect::<Vec<_>>(), vec![parent]);
+
+        graph.set_layer_visibility(3, false);
+        graph.update_hierarchical_data();
+        assert!(!graph.is_layer_visible(3));
+        assert!(!graph[parent].global_visibility());
+        // Layers are not hierarchical.
+        assert!(graph[child].global_visibility());
+
+        graph.set_layer_visibility(3, true);
+        graph.update_hierarchical_data();
+        assert!(graph[parent].global_visibility());
+    }
+
     #[test]
     fn test_graph_search() {
         let mut graph = Graph::new();
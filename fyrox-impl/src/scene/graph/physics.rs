@@ -13,7 +13,7 @@ use crate::{
         parking_lot::Mutex,
         pool::Handle,
         reflect::prelude::*,
-        uuid_provider,
+        scope_profile, uuid_provider,
         variable::{InheritableVariable, VariableFlags},
         visitor::prelude::*,
         BiDirHashMap,
@@ -21,7 +21,7 @@ use crate::{
     scene::{
         self,
         collider::{self, ColliderShape, GeometrySource},
-        debug::SceneDrawingContext,
+        debug::{PhysicsDebugDrawSettings, SceneDrawingContext},
         graph::{isometric_global_transform, Graph, NodePool},
         joint::{JointLocalFrames, JointParams},
         mesh::{
@@ -1064,6 +1064,8 @@ impl PhysicsWorld {
     }
 
     pub(super) fn update(&mut self, dt: f32) {
+        scope_profile!();
+
         let time = instant::Instant::now();
 
         if *self.enabled {
@@ -1174,7 +1176,19 @@ impl PhysicsWorld {
     /// Draws physics world. Very useful for debugging, it allows you to see where are
     /// rigid bodies, which colliders they have and so on.
     pub fn draw(&self, context: &mut SceneDrawingContext) {
-        self.debug_render_pipeline.lock().render(
+        self.draw_with_settings(context, &Default::default())
+    }
+
+    /// Draws physics world using the given settings. The settings define which parts of the world
+    /// will be drawn and what colors will be used for them.
+    pub fn draw_with_settings(
+        &self,
+        context: &mut SceneDrawingContext,
+        settings: &PhysicsDebugDrawSettings,
+    ) {
+        let mut pipeline = self.debug_render_pipeline.lock();
+        (pipeline.mode, pipeline.style) = settings.rapier3d_mode_and_style();
+        pipeline.render(
             context,
             &self.bodies,
             &self.colliders,
@@ -1337,6 +1351,21 @@ impl PhysicsWorld {
             })
     }
 
+    /// Teleports every native rigid body and collider by the negated offset, so the physics world
+    /// matches the scene after [`crate::scene::graph::Graph::shift_origin`]. Velocities are kept
+    /// intact and kinematic bodies keep their pending movement.
+    pub(crate) fn shift_origin(&mut self, offset: &Vector3<f32>) {
+        let offset = *offset;
+        for (_, body) in self.bodies.iter_mut() {
+            let next_translation = body.next_position().translation.vector - offset;
+            body.set_translation(body.translation() - offset, false);
+            body.set_next_kinematic_translation(next_translation);
+        }
+        for (_, collider) in self.colliders.iter_mut() {
+            collider.set_translation(collider.translation() - offset);
+        }
+    }
+
     pub(crate) fn set_rigid_body_position(
         &mut self,
         rigid_body: &scene::rigidbody::RigidBody,
@@ -0,0 +1,391 @@
+//! Area light is a light source with a real emitting surface - it can be imagined as a
+//! fluorescent panel, a TV screen or a window. Unlike point and spot lights, area lights
+//! produce soft lighting and wide, stretched specular highlights, that take the shape of
+//! the emitting surface.
+//!
+//! # Shapes
+//!
+//! Area light can be either a rectangle or a disk (see [`AreaLightShape`]). The emitting surface
+//! lies in the local XZ plane of the light and emits light along negative local Y axis (the same
+//! direction as spot lights). Size of the surface is defined by [`AreaLight::size`] property
+//! and by the scale of the node.
+//!
+//! # Quality
+//!
+//! Specular part of the lighting is evaluated using linearly transformed cosines (LTC), which
+//! gives plausible highlights of any shape. When [`crate::renderer::QualitySettings::use_ltc_area_lights`]
+//! is off, the renderer uses cheaper "representative point" approximation instead.
+//!
+//! # Performance notes
+//!
+//! Area lights does not cast shadows and does not support light scattering. Each area light is
+//! more expensive to render than a point light, so use them carefully.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, UnitQuaternion, Vector2, Vector3},
+        color::Color,
+        math::{aabb::AxisAlignedBoundingBox, Matrix4Ext},
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        uuid_provider,
+        variable::InheritableVariable,
+        visitor::{Visit, VisitResult, Visitor},
+        TypeUuidProvider,
+    },
+    scene::{
+        base::Base,
+        debug::SceneDrawingContext,
+        graph::Graph,
+        light::{BaseLight, BaseLightBuilder},
+        node::{Node, NodeTrait},
+    },
+};
+use fyrox_graph::BaseSceneGraph;
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Amount of vertices of a polygon, that is used to approximate disk area lights.
+pub const DISK_SEGMENTS: usize = 8;
+
+/// Maximum amount of vertices in the emitting polygon of an area light.
+pub const MAX_AREA_LIGHT_VERTICES: usize = DISK_SEGMENTS;
+
+/// Shape of the emitting surface of an area light.
+#[derive(
+    Copy, Clone, Default, Debug, PartialEq, Eq, Reflect, Visit, AsRefStr, EnumString, VariantNames,
+)]
+pub enum AreaLightShape {
+    /// Rectangle with the width and the height defined by [`AreaLight::size`].
+    #[default]
+    Rectangle,
+    /// Disk (or an ellipse) inscribed in the rectangle defined by [`AreaLight::size`].
+    Disk,
+}
+
+uuid_provider!(AreaLightShape = "b7fb6a1a-d8c3-4b37-9d5c-2b1c0a6a8f4e");
+
+/// See module docs.
+#[derive(Debug, Reflect, Clone, Visit)]
+pub struct AreaLight {
+    base_light: BaseLight,
+
+    #[reflect(setter = "set_shape")]
+    shape: InheritableVariable<AreaLightShape>,
+
+    #[reflect(min_value = 0.0, step = 0.1, unit = "meters")]
+    #[reflect(setter = "set_size")]
+    size: InheritableVariable<Vector2<f32>>,
+
+    #[reflect(min_value = 0.0, step = 0.1, unit = "meters")]
+    #[reflect(setter = "set_radius")]
+    radius: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_two_sided")]
+    two_sided: InheritableVariable<bool>,
+}
+
+impl Deref for AreaLight {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base_light.base
+    }
+}
+
+impl DerefMut for AreaLight {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base_light.base
+    }
+}
+
+impl TypeUuidProvider for AreaLight {
+    fn type_uuid() -> Uuid {
+        uuid!("5f6e5a0c-0f0d-4a3b-93c8-b3c9c2f3e6a1")
+    }
+}
+
+impl AreaLight {
+    /// Returns a reference to base light.
+    pub fn base_light_ref(&self) -> &BaseLight {
+        &self.base_light
+    }
+
+    /// Returns a reference to base light.
+    pub fn base_light_mut(&mut self) -> &mut BaseLight {
+        &mut self.base_light
+    }
+
+    /// Sets new shape of the emitting surface.
+    pub fn set_shape(&mut self, shape: AreaLightShape) -> AreaLightShape {
+        self.shape.set_value_and_mark_modified(shape)
+    }
+
+    /// Returns current shape of the emitting surface.
+    pub fn shape(&self) -> AreaLightShape {
+        *self.shape
+    }
+
+    /// Sets new size (width along local X axis and height along local Z axis) of the emitting
+    /// surface.
+    pub fn set_size(&mut self, size: Vector2<f32>) -> Vector2<f32> {
+        self.size.set_value_and_mark_modified(size.map(|v| v.abs()))
+    }
+
+    /// Returns current size of the emitting surface.
+    pub fn size(&self) -> Vector2<f32> {
+        *self.size
+    }
+
+    /// Sets the distance at which the light will have zero intensity.
+    pub fn set_radius(&mut self, radius: f32) -> f32 {
+        self.radius.set_value_and_mark_modified(radius.abs())
+    }
+
+    /// Returns the distance at which the light will have zero intensity.
+    pub fn radius(&self) -> f32 {
+        *self.radius
+    }
+
+    /// Defines whether the light should be emitted from both sides of the surface or only
+    /// along negative local Y axis.
+    pub fn set_two_sided(&mut self, two_sided: bool) -> bool {
+        self.two_sided.set_value_and_mark_modified(two_sided)
+    }
+
+    /// Returns `true` if the light is emitted from both sides of the surface, `false` - otherwise.
+    pub fn is_two_sided(&self) -> bool {
+        *self.two_sided
+    }
+
+    /// Returns vertices of the emitting polygon in local coordinates of the light. Disks are
+    /// approximated by a polygon with [`DISK_SEGMENTS`] vertices with the same area.
+    pub fn local_vertices(&self) -> Vec<Vector3<f32>> {
+        let half_size = self.size() * 0.5;
+        match self.shape() {
+            AreaLightShape::Rectangle => vec![
+                Vector3::new(-half_size.x, 0.0, -half_size.y),
+                Vector3::new(half_size.x, 0.0, -half_size.y),
+                Vector3::new(half_size.x, 0.0, half_size.y),
+                Vector3::new(-half_size.x, 0.0, half_size.y),
+            ],
+            AreaLightShape::Disk => {
+                let step = std::f32::consts::TAU / DISK_SEGMENTS as f32;
+                // Area of the polygon must match the area of the disk.
+                let k = (std::f32::consts::PI / (0.5 * DISK_SEGMENTS as f32 * step.sin())).sqrt();
+                (0..DISK_SEGMENTS)
+                    .map(|i| {
+                        let angle = i as f32 * step;
+                        Vector3::new(
+                            half_size.x * k * angle.cos(),
+                            0.0,
+                            half_size.y * k * angle.sin(),
+                        )
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns vertices of the emitting polygon in world coordinates.
+    pub fn world_vertices(&self) -> Vec<Vector3<f32>> {
+        let transform = self.global_transform();
+        self.local_vertices()
+            .into_iter()
+            .map(|v| transform.transform_point(&Point3::from(v)).coords)
+            .collect()
+    }
+
+    /// Returns world-space direction in which the light is emitted.
+    pub fn emit_direction(&self) -> Vector3<f32> {
+        -self
+            .up_vector()
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector3::y)
+    }
+}
+
+impl NodeTrait for AreaLight {
+    crate::impl_query_component!(base_light: BaseLight);
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::unit()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        let transform = self.global_transform();
+        let half_size = self.size() * 0.5;
+        // Shapes are drawn in XY plane, rotate them to XZ plane.
+        let surface_transform = transform
+            * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), std::f32::consts::FRAC_PI_2)
+                .to_homogeneous();
+        match self.shape() {
+            AreaLightShape::Rectangle => {
+                ctx.draw_rectangle(half_size.x, half_size.y, surface_transform, Color::GREEN);
+            }
+            AreaLightShape::Disk => {
+                ctx.draw_circle(
+                    Vector3::default(),
+                    1.0,
+                    32,
+                    surface_transform
+                        * Matrix4::new_nonuniform_scaling(&Vector3::new(
+                            half_size.x,
+                            half_size.y,
+                            1.0,
+                        )),
+                    Color::GREEN,
+                );
+            }
+        }
+
+        // Show the direction of emission.
+        let arrow_length = 0.25 * half_size.x.max(half_size.y).max(0.1);
+        ctx.draw_arrow(
+            8,
+            Color::GREEN,
+            arrow_length,
+            arrow_length * 0.05,
+            Matrix4::new_translation(&self.global_position())
+                * UnitQuaternion::from_matrix_eps(
+                    &transform.basis(),
+                    f32::EPSILON,
+                    16,
+                    UnitQuaternion::identity(),
+                )
+                .to_homogeneous()
+                * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), std::f32::consts::PI)
+                    .to_homogeneous()
+                * Matrix4::new_translation(&Vector3::new(0.0, arrow_length * 0.5, 0.0)),
+        );
+
+        ctx.draw_wire_sphere(self.global_position(), self.radius(), 30, Color::GREEN);
+    }
+}
+
+impl Default for AreaLight {
+    fn default() -> Self {
+        Self {
+            base_light: Default::default(),
+            shape: Default::default(),
+            size: InheritableVariable::new_modified(Vector2::new(1.0, 1.0)),
+            radius: InheritableVariable::new_modified(10.0),
+            two_sided: InheritableVariable::new_modified(false),
+        }
+    }
+}
+
+/// Allows you to build area light in declarative manner.
+pub struct AreaLightBuilder {
+    base_light_builder: BaseLightBuilder,
+    shape: AreaLightShape,
+    size: Vector2<f32>,
+    radius: f32,
+    two_sided: bool,
+}
+
+impl AreaLightBuilder {
+    /// Creates new builder instance.
+    pub fn new(base_light_builder: BaseLightBuilder) -> Self {
+        Self {
+            base_light_builder,
+            shape: Default::default(),
+            size: Vector2::new(1.0, 1.0),
+            radius: 10.0,
+            two_sided: false,
+        }
+    }
+
+    /// Sets desired shape of the emitting surface.
+    pub fn with_shape(mut self, shape: AreaLightShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Sets desired size of the emitting surface.
+    pub fn with_size(mut self, size: Vector2<f32>) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets desired radius.
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets whether the light should be emitted from both sides of the surface or not.
+    pub fn with_two_sided(mut self, two_sided: bool) -> Self {
+        self.two_sided = two_sided;
+        self
+    }
+
+    /// Builds new instance of area light.
+    pub fn build_area_light(self) -> AreaLight {
+        AreaLight {
+            base_light: self.base_light_builder.build(),
+            shape: self.shape.into(),
+            size: self.size.into(),
+            radius: self.radius.into(),
+            two_sided: self.two_sided.into(),
+        }
+    }
+
+    /// Builds new instance of area light node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_area_light())
+    }
+
+    /// Builds new instance of area light and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::base::BaseBuilder,
+        scene::light::{
+            area::{AreaLightBuilder, AreaLightShape},
+            BaseLightBuilder,
+        },
+    };
+
+    fn polygon_area(vertices: &[Vector3<f32>]) -> f32 {
+        let mut sum = Vector3::default();
+        for (i, a) in vertices.iter().enumerate() {
+            sum += a.cross(&vertices[(i + 1) % vertices.len()]);
+        }
+        0.5 * sum.norm()
+    }
+
+    #[test]
+    fn test_emitting_polygon_area() {
+        let size = Vector2::new(2.0, 3.0);
+
+        let rect = AreaLightBuilder::new(BaseLightBuilder::new(BaseBuilder::new()))
+            .with_size(size)
+            .build_area_light();
+        assert!((polygon_area(&rect.local_vertices()) - 6.0).abs() < 1.0e-5);
+
+        let disk = AreaLightBuilder::new(BaseLightBuilder::new(BaseBuilder::new()))
+            .with_shape(AreaLightShape::Disk)
+            .with_size(size)
+            .build_area_light();
+        let vertices = disk.local_vertices();
+        assert!(vertices.iter().all(|v| v.y == 0.0));
+        assert!((polygon_area(&vertices) - std::f32::consts::PI * 1.0 * 1.5).abs() < 1.0e-4);
+    }
+}
@@ -5,6 +5,13 @@
 //!
 //! Directional light sources uses cascaded shadow maps for shadows. Each cascade has different position
 //! on the viewing frustum and overall split options can be changed by using [`FrustumSplitOptions`].
+//!
+//! ## Light shafts
+//!
+//! Directional light can produce volumetric light shafts (god rays), they're disabled by default and
+//! could be enabled by [`DirectionalLight::set_shafts_enabled`]. Density of the shafts is defined by
+//! scatter factor of the light (see [`BaseLight::set_scatter`]). Exact rendering method depends on
+//! [`crate::renderer::VolumetricLightQuality`].
 
 use crate::{
     core::{
@@ -110,6 +117,12 @@ pub struct DirectionalLight {
     base_light: BaseLight,
     /// See [`CsmOptions`].
     pub csm_options: InheritableVariable<CsmOptions>,
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_shafts_enabled",
+        description = "Whether the light produces volumetric light shafts or not."
+    )]
+    shafts_enabled: InheritableVariable<bool>,
 }
 
 impl From<BaseLight> for DirectionalLight {
@@ -117,6 +130,7 @@ impl From<BaseLight> for DirectionalLight {
         Self {
             base_light,
             csm_options: Default::default(),
+            shafts_enabled: Default::default(),
         }
     }
 }
@@ -151,6 +165,16 @@ impl DirectionalLight {
     pub fn base_light_mut(&mut self) -> &mut BaseLight {
         &mut self.base_light
     }
+
+    /// Enables or disables volumetric light shafts (god rays) of the light. Returns previous value.
+    pub fn set_shafts_enabled(&mut self, enabled: bool) -> bool {
+        self.shafts_enabled.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if the light produces volumetric light shafts, `false` - otherwise.
+    pub fn is_shafts_enabled(&self) -> bool {
+        *self.shafts_enabled
+    }
 }
 
 impl NodeTrait for DirectionalLight {
@@ -186,6 +210,7 @@ impl NodeTrait for DirectionalLight {
 pub struct DirectionalLightBuilder {
     base_light_builder: BaseLightBuilder,
     csm_options: CsmOptions,
+    shafts_enabled: bool,
 }
 
 impl DirectionalLightBuilder {
@@ -194,6 +219,7 @@ impl DirectionalLightBuilder {
         Self {
             base_light_builder,
             csm_options: Default::default(),
+            shafts_enabled: false,
         }
     }
 
@@ -202,6 +228,7 @@ impl DirectionalLightBuilder {
         DirectionalLight {
             base_light: self.base_light_builder.build(),
             csm_options: self.csm_options.into(),
+            shafts_enabled: self.shafts_enabled.into(),
         }
     }
 
@@ -211,6 +238,12 @@ impl DirectionalLightBuilder {
         self
     }
 
+    /// Enables or disables volumetric light shafts (god rays) of the light.
+    pub fn with_shafts_enabled(mut self, enabled: bool) -> Self {
+        self.shafts_enabled = enabled;
+        self
+    }
+
     /// Creates new instance of directional light node.
     pub fn build_node(self) -> Node {
         Node::new(self.build_directional_light())
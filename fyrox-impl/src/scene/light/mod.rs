@@ -1,12 +1,13 @@
 //! Contains all structures and methods to create and manage lights.
 //!
 //! Light sources arte basic building blocks of many scenes in games, it improves
-//! perception of scene and makes it look natural. Fyrox engine supports three kinds
+//! perception of scene and makes it look natural. Fyrox engine supports four kinds
 //! of light sources:
 //!
 //! 1) Directional - similar to sun in real life, its rays are parallel.
 //! 2) Spot - similar to flash light, it has cone light volume and circle spot.
 //! 3) Point - similar to light bulb, it has spherical light volume.
+//! 4) Area - similar to fluorescent panel, it has rectangular or disk emitting surface.
 //!
 //! Each kind of light source is suitable for specific conditions, for example
 //! spot light can be used if you have a character with flashlight, point - if
@@ -15,19 +16,32 @@
 //! Most of light sources supports shadows (via shadows maps) and light scattering,
 //! these are common effects for modern games but still can significantly impact
 //! performance.
+//!
+//! # Light linking
+//!
+//! Every light can be linked to a specific set of scene nodes, so it will affect only
+//! chosen objects (for example, a key light of a character or a highlight of a prop). See
+//! [`BaseLight::set_include_nodes`] and [`BaseLight::set_exclude_nodes`] for more info.
 
 use crate::{
     core::{
         algebra::Vector3,
         color::Color,
+        pool::Handle,
         reflect::prelude::*,
         variable::InheritableVariable,
         visitor::{Visit, VisitResult, Visitor},
     },
-    scene::base::{Base, BaseBuilder},
+    graph::BaseSceneGraph,
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        node::Node,
+    },
 };
 use std::ops::{Deref, DerefMut};
 
+pub mod area;
 pub mod directional;
 pub mod point;
 pub mod spot;
@@ -67,6 +81,21 @@ pub struct BaseLight {
     #[reflect(min_value = 0.0, step = 0.1)]
     #[reflect(setter = "set_intensity")]
     intensity: InheritableVariable<f32>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_include_nodes",
+        description = "A set of nodes (with their descendants), that will be lit by the light. \
+        Empty set means that every node is lit."
+    )]
+    include_nodes: InheritableVariable<Vec<Handle<Node>>>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_exclude_nodes",
+        description = "A set of nodes (with their descendants), that won't be lit by the light."
+    )]
+    exclude_nodes: InheritableVariable<Vec<Handle<Node>>>,
 }
 
 impl Deref for BaseLight {
@@ -96,6 +125,8 @@ impl Default for BaseLight {
             )),
             scatter_enabled: InheritableVariable::new_modified(true),
             intensity: InheritableVariable::new_modified(1.0),
+            include_nodes: Default::default(),
+            exclude_nodes: Default::default(),
         }
     }
 }
@@ -175,6 +206,56 @@ impl BaseLight {
     pub fn is_scatter_enabled(&self) -> bool {
         *self.scatter_enabled
     }
+
+    /// Sets a set of nodes that will be lit by the light, every other node won't be lit. Each node
+    /// includes its descendants as well, so it is enough to specify the root node of a character,
+    /// for example. Empty set means that the light affects every node.
+    pub fn set_include_nodes(&mut self, nodes: Vec<Handle<Node>>) -> Vec<Handle<Node>> {
+        self.include_nodes.set_value_and_mark_modified(nodes)
+    }
+
+    /// Returns a set of nodes that will be lit by the light.
+    pub fn include_nodes(&self) -> &[Handle<Node>] {
+        &self.include_nodes
+    }
+
+    /// Sets a set of nodes that won't be lit by the light. Each node excludes its descendants as
+    /// well. Exclusion has priority over inclusion.
+    pub fn set_exclude_nodes(&mut self, nodes: Vec<Handle<Node>>) -> Vec<Handle<Node>> {
+        self.exclude_nodes.set_value_and_mark_modified(nodes)
+    }
+
+    /// Returns a set of nodes that won't be lit by the light.
+    pub fn exclude_nodes(&self) -> &[Handle<Node>] {
+        &self.exclude_nodes
+    }
+
+    /// Returns `true` if the light is linked to a specific set of nodes (either by inclusion or
+    /// exclusion), `false` - otherwise.
+    pub fn is_linked(&self) -> bool {
+        !self.include_nodes.is_empty() || !self.exclude_nodes.is_empty()
+    }
+
+    /// Checks whether the light affects the given node, taking include and exclude sets into
+    /// account.
+    pub fn affects(&self, graph: &Graph, node: Handle<Node>) -> bool {
+        if !self.is_linked() {
+            return true;
+        }
+
+        let mut included = self.include_nodes.is_empty();
+        let mut current = node;
+        while let Some(current_ref) = graph.try_get(current) {
+            if self.exclude_nodes.contains(&current) {
+                return false;
+            }
+            if self.include_nodes.contains(&current) {
+                included = true;
+            }
+            current = current_ref.parent();
+        }
+        included
+    }
 }
 
 /// Light scene node builder. Provides easy declarative way of creating light scene
@@ -186,6 +267,8 @@ pub struct BaseLightBuilder {
     scatter_factor: Vector3<f32>,
     scatter_enabled: bool,
     intensity: f32,
+    include_nodes: Vec<Handle<Node>>,
+    exclude_nodes: Vec<Handle<Node>>,
 }
 
 impl BaseLightBuilder {
@@ -201,6 +284,8 @@ impl BaseLightBuilder {
             scatter_factor: Vector3::new(DEFAULT_SCATTER_R, DEFAULT_SCATTER_G, DEFAULT_SCATTER_B),
             scatter_enabled: true,
             intensity: 1.0,
+            include_nodes: Default::default(),
+            exclude_nodes: Default::default(),
         }
     }
 
@@ -234,6 +319,20 @@ impl BaseLightBuilder {
         self
     }
 
+    /// Sets a set of nodes that will be lit by the light. See [`BaseLight::set_include_nodes`]
+    /// for more info.
+    pub fn with_include_nodes(mut self, nodes: Vec<Handle<Node>>) -> Self {
+        self.include_nodes = nodes;
+        self
+    }
+
+    /// Sets a set of nodes that won't be lit by the light. See [`BaseLight::set_exclude_nodes`]
+    /// for more info.
+    pub fn with_exclude_nodes(mut self, nodes: Vec<Handle<Node>>) -> Self {
+        self.exclude_nodes = nodes;
+        self
+    }
+
     /// Creates new instance of base light.
     pub fn build(self) -> BaseLight {
         BaseLight {
@@ -243,6 +342,51 @@ impl BaseLightBuilder {
             scatter: self.scatter_factor.into(),
             scatter_enabled: self.scatter_enabled.into(),
             intensity: self.intensity.into(),
+            include_nodes: self.include_nodes.into(),
+            exclude_nodes: self.exclude_nodes.into(),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::scene::{
+        base::BaseBuilder, graph::Graph, light::BaseLightBuilder, pivot::PivotBuilder,
+    };
+
+    #[test]
+    fn test_light_linking() {
+        let mut graph = Graph::new();
+        let child = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let character =
+            PivotBuilder::new(BaseBuilder::new().with_children(&[child])).build(&mut graph);
+        let prop = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        let unlinked = BaseLightBuilder::new(BaseBuilder::new()).build();
+        assert!(!unlinked.is_linked());
+        assert!(unlinked.affects(&graph, child));
+        assert!(unlinked.affects(&graph, prop));
+
+        let key_light = BaseLightBuilder::new(BaseBuilder::new())
+            .with_include_nodes(vec![character])
+            .build();
+        assert!(key_light.affects(&graph, character));
+        assert!(key_light.affects(&graph, child));
+        assert!(!key_light.affects(&graph, prop));
+
+        let fill_light = BaseLightBuilder::new(BaseBuilder::new())
+            .with_exclude_nodes(vec![character])
+            .build();
+        assert!(!fill_light.affects(&graph, character));
+        assert!(!fill_light.affects(&graph, child));
+        assert!(fill_light.affects(&graph, prop));
+
+        // Exclusion has priority over inclusion.
+        let light = BaseLightBuilder::new(BaseBuilder::new())
+            .with_include_nodes(vec![character])
+            .with_exclude_nodes(vec![child])
+            .build();
+        assert!(light.affects(&graph, character));
+        assert!(!light.affects(&graph, child));
+    }
+}
@@ -48,7 +48,7 @@ pub struct PointLight {
     #[reflect(setter = "set_shadow_bias")]
     shadow_bias: InheritableVariable<f32>,
 
-    #[reflect(min_value = 0.0, step = 0.1)]
+    #[reflect(min_value = 0.0, step = 0.1, unit = "meters")]
     #[reflect(setter = "set_radius")]
     radius: InheritableVariable<f32>,
 }
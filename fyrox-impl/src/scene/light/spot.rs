@@ -63,7 +63,7 @@ pub struct SpotLight {
     #[reflect(setter = "set_shadow_bias")]
     shadow_bias: InheritableVariable<f32>,
 
-    #[reflect(min_value = 0.0, step = 0.1)]
+    #[reflect(min_value = 0.0, step = 0.1, unit = "meters")]
     #[reflect(setter = "set_distance")]
     distance: InheritableVariable<f32>,
 
@@ -35,7 +35,7 @@ use crate::{
             },
             surface::{BlendShape, Surface, SurfaceData, SurfaceResource},
         },
-        node::{Node, NodeTrait, RdcControlFlow, SyncContext},
+        node::{Node, NodeTrait, ParallelUpdateContext, RdcControlFlow, SyncContext},
     },
 };
 use fxhash::{FxHashMap, FxHasher};
@@ -575,6 +575,12 @@ impl NodeTrait for Mesh {
         Self::type_uuid()
     }
 
+    fn parallel_update(&mut self, _context: &ParallelUpdateContext) {
+        // Recalculation of the bounding box requires iterating over every vertex of every surface,
+        // do it here so the bounding box will be ready when the world bounding box is calculated.
+        self.local_bounding_box();
+    }
+
     fn sync_transform(&self, _new_global_transform: &Matrix4<f32>, context: &mut SyncContext) {
         if self.surfaces.iter().any(|s| !s.bones.is_empty()) {
             let mut world_aabb = self
@@ -640,6 +646,7 @@ impl NodeTrait for Mesh {
                             index,
                         ),
                         node_handle: self.self_handle,
+                        vertex_animation: None,
                     },
                 );
             }
@@ -706,6 +713,7 @@ impl NodeTrait for Mesh {
                                     index,
                                 ),
                                 node_handle: self.self_handle,
+                                vertex_animation: None,
                             },
                         );
                     }
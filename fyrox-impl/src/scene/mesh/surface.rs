@@ -1349,6 +1349,11 @@ impl Surface {
         &self.data
     }
 
+    /// Sets new data source of the surface and returns the old one.
+    pub fn set_data(&mut self, data: SurfaceResource) -> SurfaceResource {
+        self.data.set_value_and_mark_modified(data)
+    }
+
     /// Returns current material of the surface.
     pub fn material(&self) -> &MaterialResource {
         &self.material
@@ -9,9 +9,13 @@ pub mod animation;
 pub mod base;
 pub mod camera;
 pub mod collider;
+pub mod crowd;
 pub mod debug;
+pub mod debug_draw;
 pub mod decal;
 pub mod dim2;
+pub mod driver;
+pub mod floating_origin;
 pub mod graph;
 pub mod joint;
 pub mod light;
@@ -20,13 +24,20 @@ pub mod navmesh;
 pub mod node;
 pub mod particle_system;
 pub mod pivot;
+pub mod point_cloud;
 pub mod ragdoll;
+pub mod replication;
 pub mod rigidbody;
+pub mod savegame;
+pub mod snapshot;
 pub mod sound;
 pub mod sprite;
 pub mod terrain;
+pub mod text3d;
 pub mod tilemap;
 pub mod transform;
+pub mod water;
+pub mod weather;
 
 use crate::{
     asset::{self, manager::ResourceManager, untyped::UntypedResource},
@@ -37,16 +48,20 @@ use crate::{
         log::{Log, MessageKind},
         pool::{Handle, Pool, Ticket},
         reflect::prelude::*,
+        scope_profile,
         visitor::{Visit, VisitError, VisitResult, Visitor},
     },
     engine::SerializationContext,
     graph::NodeHandleMap,
     renderer::framework::state::PolygonFillMode,
-    resource::texture::TextureResource,
+    resource::{model::parameter::PrefabParameters, texture::TextureResource},
     scene::{
         base::BaseBuilder,
         camera::Camera,
         debug::SceneDrawingContext,
+        debug_draw::DebugDraw,
+        driver::PropertyDrivers,
+        floating_origin::FloatingOrigin,
         graph::{Graph, GraphPerformanceStatistics, GraphUpdateSwitches},
         navmesh::NavigationalMeshBuilder,
         node::Node,
@@ -201,6 +216,10 @@ pub struct Scene {
     #[reflect(hidden)]
     pub drawing_context: SceneDrawingContext,
 
+    /// Debug drawing with durations for game code. See [`DebugDraw`] docs for more info.
+    #[reflect(hidden)]
+    pub debug_draw: DebugDraw,
+
     /// Performance statistics from last `update` call.
     #[reflect(hidden)]
     pub performance_statistics: PerformanceStatistics,
@@ -213,6 +232,16 @@ pub struct Scene {
     /// to false for menu's scene and when you need to open a menu - set it to true and
     /// set `enabled` flag to false for level's scene.
     pub enabled: InheritableVariable<bool>,
+
+    /// Floating origin settings of the scene. See [`FloatingOrigin`] docs for more info.
+    pub floating_origin: FloatingOrigin,
+
+    /// Property drivers of the scene. See [`PropertyDrivers`] docs for more info.
+    pub drivers: PropertyDrivers,
+
+    /// Parameters of the scene, that are used when the scene is instantiated as a prefab. See
+    /// [`PrefabParameters`] docs for more info.
+    pub prefab_parameters: PrefabParameters,
 }
 
 impl Default for Scene {
@@ -221,8 +250,12 @@ impl Default for Scene {
             graph: Default::default(),
             rendering_options: Default::default(),
             drawing_context: Default::default(),
+            debug_draw: Default::default(),
             performance_statistics: Default::default(),
             enabled: true.into(),
+            floating_origin: Default::default(),
+            drivers: Default::default(),
+            prefab_parameters: Default::default(),
         }
     }
 }
@@ -247,7 +280,8 @@ impl Display for PerformanceStatistics {
             \tPhysics 2D: {:?}\n\
             \t\tSimulation: {:?}\n\
             \t\tRay cast: {:?}\n\
-            \tHierarchy: {:?}",
+            \tHierarchy: {:?}\n\
+            \tParallel Update: {:?}",
             self.graph.total(),
             self.graph.sync_time,
             self.graph.sound_update_time,
@@ -258,6 +292,7 @@ impl Display for PerformanceStatistics {
             self.graph.physics2d.step_time,
             self.graph.physics2d.total_ray_cast_time.get(),
             self.graph.hierarchical_properties_time,
+            self.graph.parallel_update_time,
         )
     }
 }
@@ -382,8 +417,12 @@ impl Scene {
             graph: Graph::new(),
             rendering_options: Default::default(),
             drawing_context: Default::default(),
+            debug_draw: Default::default(),
             performance_statistics: Default::default(),
             enabled: true.into(),
+            floating_origin: Default::default(),
+            drivers: Default::default(),
+            prefab_parameters: Default::default(),
         }
     }
 
@@ -408,6 +447,14 @@ impl Scene {
     /// it updates physics, animations, and each graph node. In most cases there is
     /// no need to call it directly, engine automatically updates all available scenes.
     pub fn update(&mut self, frame_size: Vector2<f32>, dt: f32, switches: GraphUpdateSwitches) {
+        scope_profile!();
+
+        self.floating_origin.update(
+            &mut self.graph,
+            !switches.paused && switches.node_overrides.is_none(),
+        );
+        self.drivers.update(&mut self.graph, switches.paused);
+        self.debug_draw.update(dt);
         self.graph.update(frame_size, dt, switches);
         self.performance_statistics.graph = self.graph.performance_statistics.clone();
     }
@@ -430,13 +477,20 @@ impl Scene {
             self.graph
                 .clone(root, filter, pre_process_callback, post_process_callback);
 
+        let mut drivers = self.drivers.clone();
+        drivers.remap_handles(&old_new_map);
+
         (
             Self {
                 graph,
                 rendering_options: self.rendering_options.clone(),
                 drawing_context: self.drawing_context.clone(),
+                debug_draw: self.debug_draw.clone(),
                 performance_statistics: Default::default(),
                 enabled: self.enabled.clone(),
+                floating_origin: self.floating_origin.clone(),
+                drivers,
+                prefab_parameters: self.prefab_parameters.clone(),
             },
             old_new_map,
         )
@@ -461,6 +515,11 @@ impl Scene {
         let _ = self
             .rendering_options
             .visit("RenderingOptions", &mut region);
+        let _ = self.floating_origin.visit("FloatingOrigin", &mut region);
+        let _ = self.drivers.visit("Drivers", &mut region);
+        let _ = self
+            .prefab_parameters
+            .visit("PrefabParameters", &mut region);
 
         // Backward compatibility.
         let mut navmeshes = NavMeshContainer::default();
@@ -3,6 +3,7 @@
 
 use crate::{
     core::{
+        algebra::Vector3,
         color::Color,
         math::aabb::AxisAlignedBoundingBox,
         parking_lot::RwLock,
@@ -172,6 +173,15 @@ impl NodeTrait for NavigationalMesh {
         Self::type_uuid()
     }
 
+    fn on_origin_shifted(&mut self, offset: &Vector3<f32>) {
+        // Navmesh vertices are stored in world-space.
+        let mut navmesh = self.navmesh_mut();
+        let mut modification = navmesh.modify();
+        for vertex in modification.vertices_mut() {
+            *vertex -= *offset;
+        }
+    }
+
     fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
         let navmesh = self.navmesh.0.read();
 
@@ -9,11 +9,16 @@ use crate::{
     },
     scene::{
         self,
-        animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
+        animation::{
+            absm::AnimationBlendingStateMachine, sequencer::SequencePlayer,
+            sprite::SpriteAnimationPlayer, AnimationPlayer,
+        },
         camera::Camera,
         decal::Decal,
         dim2::{self, rectangle::Rectangle},
-        light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
+        light::{
+            area::AreaLight, directional::DirectionalLight, point::PointLight, spot::SpotLight,
+        },
         mesh::Mesh,
         navmesh::NavigationalMesh,
         node::{Node, NodeTrait},
@@ -50,10 +55,15 @@ impl NodeConstructorContainer {
         container.add::<dim2::collider::Collider>();
         container.add::<dim2::joint::Joint>();
         container.add::<Rectangle>();
+        container.add::<dim2::skin::SkinnedSprite>();
+        container.add::<dim2::light::Light2D>();
+        container.add::<dim2::occluder::Occluder2D>();
+        container.add::<dim2::parallax::ParallaxLayer>();
         container.add::<dim2::rigidbody::RigidBody>();
         container.add::<DirectionalLight>();
         container.add::<PointLight>();
         container.add::<SpotLight>();
+        container.add::<AreaLight>();
         container.add::<Mesh>();
         container.add::<ParticleSystem>();
         container.add::<Sound>();
@@ -66,11 +76,18 @@ impl NodeConstructorContainer {
         container.add::<scene::rigidbody::RigidBody>();
         container.add::<Sprite>();
         container.add::<Terrain>();
+        container.add::<scene::text3d::Text3D>();
         container.add::<AnimationPlayer>();
+        container.add::<SpriteAnimationPlayer>();
+        container.add::<SequencePlayer>();
         container.add::<AnimationBlendingStateMachine>();
         container.add::<NavigationalMesh>();
         container.add::<Ragdoll>();
         container.add::<TileMap>();
+        container.add::<scene::crowd::Crowd>();
+        container.add::<scene::point_cloud::PointCloud>();
+        container.add::<scene::water::Water>();
+        container.add::<scene::weather::Weather>();
 
         container
     }
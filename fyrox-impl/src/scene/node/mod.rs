@@ -8,7 +8,7 @@ use crate::resource::model::Model;
 use crate::{
     asset::untyped::UntypedResource,
     core::{
-        algebra::{Matrix4, Vector2},
+        algebra::{Matrix4, Vector2, Vector3},
         math::aabb::AxisAlignedBoundingBox,
         pool::Handle,
         reflect::prelude::*,
@@ -29,7 +29,9 @@ use crate::{
         decal::Decal,
         dim2::{self, rectangle::Rectangle},
         graph::{self, Graph, GraphUpdateSwitches, NodePool},
-        light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
+        light::{
+            area::AreaLight, directional::DirectionalLight, point::PointLight, spot::SpotLight,
+        },
         mesh::Mesh,
         navmesh::NavigationalMesh,
         particle_system::ParticleSystem,
@@ -113,6 +115,14 @@ pub struct UpdateContext<'a> {
     pub sound_context: &'a mut SoundContext,
 }
 
+/// A data for parallel update tick. See [`NodeTrait::parallel_update`] for more info.
+pub struct ParallelUpdateContext {
+    /// Size of client area of the window.
+    pub frame_size: Vector2<f32>,
+    /// A time that have passed since last update call.
+    pub dt: f32,
+}
+
 /// Implements [`NodeTrait::query_component_ref`] and [`NodeTrait::query_component_mut`] in a much
 /// shorter way.
 #[macro_export]
@@ -211,6 +221,12 @@ pub trait NodeTrait: BaseNodeTrait + Reflect + Visit {
     ) {
     }
 
+    /// Called when the origin of the graph was shifted by the given offset (see [`Graph::shift_origin`]).
+    /// Transforms of the nodes are shifted automatically, this method should be used only by nodes that
+    /// store world-space data, that does not depend on their transform (navigational meshes, for example).
+    /// Such data must be shifted by the negated offset.
+    fn on_origin_shifted(&mut self, #[allow(unused_variables)] offset: &Vector3<f32>) {}
+
     /// The methods is used to manage lifetime of scene nodes, depending on their internal logic.
     fn is_alive(&self) -> bool {
         true
@@ -219,6 +235,17 @@ pub trait NodeTrait: BaseNodeTrait + Reflect + Visit {
     /// Updates internal state of the node.
     fn update(&mut self, #[allow(unused_variables)] context: &mut UpdateContext) {}
 
+    /// Updates the part of internal state of the node, that does not depend on other nodes. The method
+    /// is called on worker threads of [`crate::utils::jobs::JobSystem`] for multiple nodes at once, before
+    /// [`Self::update`] and before global transform of the node is calculated. It is well suited for heavy
+    /// computations like animation sampling, particles simulation, etc.
+    ///
+    /// # Important notes
+    ///
+    /// The method must not access anything except the node itself, any data that is shared between
+    /// nodes (scene graph, physics, sound) must be accessed in [`Self::update`].
+    fn parallel_update(&mut self, #[allow(unused_variables)] context: &ParallelUpdateContext) {}
+
     /// Allows the node to emit a set of render data. This is a high-level rendering method which can only
     /// do culling and provide render data. Render data is just a surface (vertex + index buffers) and a
     /// material.
@@ -564,6 +591,7 @@ impl Node {
     define_is_as!(SpotLight  => fn is_spot_light, fn as_spot_light, fn as_spot_light_mut);
     define_is_as!(PointLight  => fn is_point_light, fn as_point_light, fn as_point_light_mut);
     define_is_as!(DirectionalLight  => fn is_directional_light, fn as_directional_light, fn as_directional_light_mut);
+    define_is_as!(AreaLight  => fn is_area_light, fn as_area_light, fn as_area_light_mut);
     define_is_as!(ParticleSystem => fn is_particle_system, fn as_particle_system, fn as_particle_system_mut);
     define_is_as!(Sprite  => fn is_sprite, fn as_sprite, fn as_sprite_mut);
     define_is_as!(Terrain  => fn is_terrain, fn as_terrain, fn as_terrain_mut);
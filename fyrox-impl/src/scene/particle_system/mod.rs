@@ -24,7 +24,7 @@ use crate::{
         base::{Base, BaseBuilder},
         graph::Graph,
         mesh::RenderPath,
-        node::{Node, NodeTrait, UpdateContext},
+        node::{Node, NodeTrait, ParallelUpdateContext},
         particle_system::{
             draw::Vertex,
             emitter::{Emit, Emitter},
@@ -125,6 +125,13 @@ impl Visit for ParticleSystemRng {
 /// Particle system can contain multiple particle emitters, each emitter has its own
 /// set of properties and it defines law of change of particle parameters over time.
 ///
+/// # Soft particles
+///
+/// Particles of the standard particle system material fade out near intersections with opaque
+/// geometry, so smoke or dust do not have visible hard edges where they touch walls or floor. The
+/// fade distance (in world units) is defined by `softFadeDistance` property of the material, zero
+/// disables fading.
+///
 /// # Performance
 ///
 /// In general particle system can be considered as heavy visual effect, but total impact
@@ -265,15 +272,19 @@ impl Visit for ParticleSystem {
             self.material.visit("Material", &mut region)?;
         }
 
-        let mut soft_boundary_sharpness_factor = 100.0;
-        if soft_boundary_sharpness_factor
-            .visit("SoftBoundarySharpnessFactor", &mut region)
-            .is_ok()
-        {
-            Log::verify(self.material.data_ref().set_property(
-                &ImmutableString::new("softBoundarySharpnessFactor"),
-                PropertyValue::Float(soft_boundary_sharpness_factor),
-            ));
+        // Backward compatibility. Sharpness factor is the inverse of the fade distance.
+        if region.is_reading() {
+            let mut soft_boundary_sharpness_factor = 0.0f32;
+            if soft_boundary_sharpness_factor
+                .visit("SoftBoundarySharpnessFactor", &mut region)
+                .is_ok()
+                && soft_boundary_sharpness_factor > 0.0
+            {
+                Log::verify(self.material.data_ref().set_property(
+                    &ImmutableString::new("softFadeDistance"),
+                    PropertyValue::Float(1.0 / soft_boundary_sharpness_factor),
+                ));
+            }
         }
 
         Ok(())
@@ -456,11 +467,9 @@ impl NodeTrait for ParticleSystem {
         Self::type_uuid()
     }
 
-    fn update(&mut self, context: &mut UpdateContext) {
-        let dt = context.dt;
-
+    fn parallel_update(&mut self, context: &ParallelUpdateContext) {
         if *self.is_playing {
-            self.tick(dt);
+            self.tick(context.dt);
         }
     }
 
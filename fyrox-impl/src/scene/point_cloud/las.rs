@@ -0,0 +1,192 @@
+//! Reader of point clouds in ASPRS LAS format (`*.las`), versions 1.0 - 1.4. Compressed point clouds
+//! (LAZ) are not supported.
+
+use crate::{
+    core::{algebra::Vector3, color::Color},
+    scene::point_cloud::resource::{PointCloudError, PointCloudPoint},
+};
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N], PointCloudError> {
+    data.get(offset..offset + N)
+        .map(|b| b.try_into().unwrap())
+        .ok_or_else(|| PointCloudError::Format("Unexpected end of LAS data".to_string()))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, PointCloudError> {
+    read_bytes(data, offset).map(u16::from_le_bytes)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, PointCloudError> {
+    read_bytes(data, offset).map(u32::from_le_bytes)
+}
+
+fn read_i32(data: &[u8], offset: usize) -> Result<i32, PointCloudError> {
+    read_bytes(data, offset).map(i32::from_le_bytes)
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64, PointCloudError> {
+    read_bytes(data, offset).map(u64::from_le_bytes)
+}
+
+fn read_f64(data: &[u8], offset: usize) -> Result<f64, PointCloudError> {
+    read_bytes(data, offset).map(f64::from_le_bytes)
+}
+
+/// Returns the offset of RGB values in a point record of the given format.
+fn color_offset(format: u8) -> Option<usize> {
+    match format {
+        2 => Some(20),
+        3 | 5 => Some(28),
+        7 | 8 | 10 => Some(30),
+        _ => None,
+    }
+}
+
+/// Points of a LAS file, their positions are relative to the `origin`.
+pub struct LasPoints {
+    /// Points converted to Y-up coordinate system.
+    pub points: Vec<PointCloudPoint>,
+    /// Center of the bounds of the points in the source (Z-up) coordinate system. Geo-referenced
+    /// coordinates are too large for single precision, so the points are moved to the origin.
+    pub origin: Vector3<f64>,
+}
+
+/// Reads points from the given LAS data.
+pub fn read(data: &[u8]) -> Result<LasPoints, PointCloudError> {
+    if data.get(0..4) != Some(b"LASF") {
+        return Err(PointCloudError::Format("Not a LAS file".to_string()));
+    }
+
+    let point_data_offset = read_u32(data, 96)? as usize;
+    let format_byte = data
+        .get(104)
+        .copied()
+        .ok_or_else(|| PointCloudError::Format("Unexpected end of LAS data".to_string()))?;
+    if format_byte & 0xC0 != 0 {
+        return Err(PointCloudError::Format(
+            "Compressed LAS files (LAZ) are not supported".to_string(),
+        ));
+    }
+    let format = format_byte & 0x3F;
+    let record_length = read_u16(data, 105)? as usize;
+    let mut point_count = read_u32(data, 107)? as u64;
+    let minor_version = data.get(25).copied().unwrap_or(0);
+    if point_count == 0 && minor_version >= 4 {
+        point_count = read_u64(data, 247)?;
+    }
+
+    let scale = Vector3::new(
+        read_f64(data, 131)?,
+        read_f64(data, 139)?,
+        read_f64(data, 147)?,
+    );
+    let offset = Vector3::new(
+        read_f64(data, 155)?,
+        read_f64(data, 163)?,
+        read_f64(data, 171)?,
+    );
+    let max = Vector3::new(
+        read_f64(data, 179)?,
+        read_f64(data, 195)?,
+        read_f64(data, 211)?,
+    );
+    let min = Vector3::new(
+        read_f64(data, 187)?,
+        read_f64(data, 203)?,
+        read_f64(data, 219)?,
+    );
+    let origin = (min + max).scale(0.5);
+
+    let min_record_length = if format >= 6 { 30 } else { 20 };
+    if record_length < min_record_length {
+        return Err(PointCloudError::Format(format!(
+            "Invalid LAS point record length {record_length}"
+        )));
+    }
+
+    let color_offset = color_offset(format).filter(|o| o + 6 <= record_length);
+
+    // Colors are 16-bit, but lots of writers store 8-bit values in them.
+    let mut color_range = u8::MAX as f32;
+    if let Some(color_offset) = color_offset {
+        for i in 0..point_count as usize {
+            let record = point_data_offset + i * record_length + color_offset;
+            for channel in 0..3 {
+                if read_u16(data, record + channel * 2)? > u8::MAX as u16 {
+                    color_range = u16::MAX as f32;
+                }
+            }
+        }
+    }
+
+    let mut points = Vec::with_capacity(point_count as usize);
+    for i in 0..point_count as usize {
+        let record = point_data_offset + i * record_length;
+        let position = Vector3::new(
+            read_i32(data, record)? as f64,
+            read_i32(data, record + 4)? as f64,
+            read_i32(data, record + 8)? as f64,
+        )
+        .component_mul(&scale)
+            + offset
+            - origin;
+
+        let color = match color_offset {
+            Some(color_offset) => {
+                let channel = |index: usize| -> Result<u8, PointCloudError> {
+                    let value = read_u16(data, record + color_offset + index * 2)? as f32;
+                    Ok((value / color_range * 255.0).round().min(255.0) as u8)
+                };
+                Color::opaque(channel(0)?, channel(1)?, channel(2)?)
+            }
+            None => Color::WHITE,
+        };
+
+        points.push(PointCloudPoint {
+            // LAS uses Z-up coordinate system.
+            position: Vector3::new(position.x as f32, position.z as f32, -position.y as f32),
+            color,
+        });
+    }
+
+    Ok(LasPoints { points, origin })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::{algebra::Vector3, color::Color};
+    use crate::scene::point_cloud::las::read;
+
+    #[test]
+    fn test_read() {
+        let mut data = vec![0u8; 227];
+        data[0..4].copy_from_slice(b"LASF");
+        data[24] = 1;
+        data[25] = 2;
+        data[94..96].copy_from_slice(&227u16.to_le_bytes());
+        data[96..100].copy_from_slice(&227u32.to_le_bytes());
+        data[104] = 2;
+        data[105..107].copy_from_slice(&26u16.to_le_bytes());
+        data[107..111].copy_from_slice(&1u32.to_le_bytes());
+        for offset in [131, 139, 147] {
+            data[offset..offset + 8].copy_from_slice(&0.01f64.to_le_bytes());
+        }
+        // Bounds are [0; 2] along every axis.
+        for offset in [179, 195, 211] {
+            data[offset..offset + 8].copy_from_slice(&2.0f64.to_le_bytes());
+        }
+
+        let mut record = vec![0u8; 26];
+        record[0..4].copy_from_slice(&100i32.to_le_bytes());
+        record[4..8].copy_from_slice(&200i32.to_le_bytes());
+        record[8..12].copy_from_slice(&300i32.to_le_bytes());
+        record[20..22].copy_from_slice(&255u16.to_le_bytes());
+        data.extend_from_slice(&record);
+
+        let las = read(&data).unwrap();
+        assert_eq!(las.origin, Vector3::new(1.0, 1.0, 1.0));
+        assert_eq!(las.points.len(), 1);
+        assert_eq!(las.points[0].position, Vector3::new(0.0, 2.0, -1.0));
+        assert_eq!(las.points[0].color, Color::opaque(255, 0, 0));
+    }
+}
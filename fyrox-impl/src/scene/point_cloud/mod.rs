@@ -0,0 +1,482 @@
+//! Point cloud is a scene node, that renders large sets of colored points, for example photogrammetry
+//! or laser scans. See [`PointCloud`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Point3, Vector2, Vector3},
+        color::Color,
+        math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        value_as_u8_slice,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    graph::BaseSceneGraph,
+    material::{Material, MaterialResource},
+    renderer::{self, bundle::RenderContext},
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        mesh::{
+            buffer::{
+                VertexAttributeDataType, VertexAttributeDescriptor, VertexAttributeUsage,
+                VertexTrait,
+            },
+            RenderPath,
+        },
+        node::{Node, NodeTrait, RdcControlFlow},
+        point_cloud::resource::PointCloudResource,
+    },
+};
+use fyrox_resource::untyped::ResourceKind;
+use std::ops::{Deref, DerefMut};
+
+pub mod las;
+pub mod ply;
+pub mod resource;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+struct PointVertex {
+    position: Vector3<f32>,
+    tex_coord: Vector2<f32>,
+    size: f32,
+    size_attenuation: f32,
+    color: Color,
+}
+
+impl VertexTrait for PointVertex {
+    fn layout() -> &'static [VertexAttributeDescriptor] {
+        &[
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::Position,
+                data_type: VertexAttributeDataType::F32,
+                size: 3,
+                divisor: 0,
+                shader_location: 0,
+                normalized: false,
+            },
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::TexCoord0,
+                data_type: VertexAttributeDataType::F32,
+                size: 2,
+                divisor: 0,
+                shader_location: 1,
+                normalized: false,
+            },
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::Custom0,
+                data_type: VertexAttributeDataType::F32,
+                size: 1,
+                divisor: 0,
+                shader_location: 2,
+                normalized: false,
+            },
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::Custom1,
+                data_type: VertexAttributeDataType::F32,
+                size: 1,
+                divisor: 0,
+                shader_location: 3,
+                normalized: false,
+            },
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::Color,
+                data_type: VertexAttributeDataType::U8,
+                size: 4,
+                divisor: 0,
+                shader_location: 4,
+                normalized: true,
+            },
+        ]
+    }
+}
+
+/// Point cloud renders a [`resource::PointCloudData`] resource as a set of camera-facing round points.
+/// It is mostly used to show photogrammetry or laser scans (`*.ply` and `*.las` files) as a reference
+/// while building a level in the editor.
+///
+/// ## Level of detail
+///
+/// Points of the resource are split into chunks using an octree, every chunk is culled separately. Chunks
+/// that are further than [`PointCloud::lod_distance`] from the camera render only a part of their points,
+/// the part decreases with the distance, but never goes below [`PointCloud::min_lod_fraction`]. Size of
+/// the points of such chunks is increased to keep the density of the cloud on screen.
+///
+/// ## Point size
+///
+/// When size attenuation is enabled, [`PointCloud::point_size`] is the size of a point in world units,
+/// so distant points are smaller on screen. Otherwise, every point has the same on-screen size as a
+/// point with attenuation at 1 meter from the camera.
+///
+/// ## Performance
+///
+/// Points are uploaded to GPU every frame, so it is not suitable for clouds with tens of millions of
+/// points. Use level of detail settings to reduce the amount of rendered points.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::pool::Handle,
+/// #     scene::{
+/// #         base::BaseBuilder, graph::Graph, node::Node,
+/// #         point_cloud::{resource::PointCloudResource, PointCloudBuilder},
+/// #     },
+/// # };
+/// fn create_point_cloud(points: PointCloudResource, graph: &mut Graph) -> Handle<Node> {
+///     PointCloudBuilder::new(BaseBuilder::new())
+///         .with_points(points)
+///         .with_point_size(0.01)
+///         .with_lod_distance(30.0)
+///         .build(graph)
+/// }
+/// ```
+#[derive(Clone, Debug, Reflect, Visit, ComponentProvider)]
+pub struct PointCloud {
+    base: Base,
+
+    #[reflect(setter = "set_points")]
+    points: InheritableVariable<Option<PointCloudResource>>,
+
+    #[reflect(setter = "set_material")]
+    material: InheritableVariable<MaterialResource>,
+
+    #[reflect(min_value = 0.0, step = 0.001, setter = "set_point_size")]
+    point_size: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_size_attenuation")]
+    size_attenuation: InheritableVariable<bool>,
+
+    #[reflect(min_value = 0.0, step = 0.1, setter = "set_lod_distance")]
+    lod_distance: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.01,
+        setter = "set_min_lod_fraction"
+    )]
+    min_lod_fraction: InheritableVariable<f32>,
+}
+
+impl Default for PointCloud {
+    fn default() -> Self {
+        PointCloudBuilder::new(BaseBuilder::new()).build_point_cloud()
+    }
+}
+
+impl Deref for PointCloud {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for PointCloud {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for PointCloud {
+    fn type_uuid() -> Uuid {
+        uuid!("4a9d2c6e-1b8f-4e73-a5c0-7d3e9f1b6a24")
+    }
+}
+
+impl PointCloud {
+    /// Sets new point cloud resource.
+    pub fn set_points(&mut self, points: Option<PointCloudResource>) -> Option<PointCloudResource> {
+        self.points.set_value_and_mark_modified(points)
+    }
+
+    /// Returns current point cloud resource.
+    pub fn points(&self) -> Option<&PointCloudResource> {
+        self.points.as_ref()
+    }
+
+    /// Sets new material of the point cloud. The material must use a shader, that supports point
+    /// cloud vertex layout, for example the standard point cloud shader.
+    pub fn set_material(&mut self, material: MaterialResource) -> MaterialResource {
+        self.material.set_value_and_mark_modified(material)
+    }
+
+    /// Returns current material of the point cloud.
+    pub fn material(&self) -> &MaterialResource {
+        &self.material
+    }
+
+    /// Sets new size of the points. See [`PointCloud`] docs for more info.
+    pub fn set_point_size(&mut self, size: f32) -> f32 {
+        self.point_size.set_value_and_mark_modified(size.max(0.0))
+    }
+
+    /// Returns current size of the points.
+    pub fn point_size(&self) -> f32 {
+        *self.point_size
+    }
+
+    /// Enables or disables size attenuation of the points. See [`PointCloud`] docs for more info.
+    pub fn set_size_attenuation(&mut self, enabled: bool) -> bool {
+        self.size_attenuation.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if the size attenuation is enabled, `false` - otherwise.
+    pub fn is_size_attenuation_enabled(&self) -> bool {
+        *self.size_attenuation
+    }
+
+    /// Sets new distance, at which the chunks of the point cloud start to lose their points.
+    pub fn set_lod_distance(&mut self, distance: f32) -> f32 {
+        self.lod_distance
+            .set_value_and_mark_modified(distance.max(0.0))
+    }
+
+    /// Returns current distance, at which the chunks of the point cloud start to lose their points.
+    pub fn lod_distance(&self) -> f32 {
+        *self.lod_distance
+    }
+
+    /// Sets new minimum fraction of the points, that are rendered by distant chunks.
+    pub fn set_min_lod_fraction(&mut self, fraction: f32) -> f32 {
+        self.min_lod_fraction
+            .set_value_and_mark_modified(fraction.clamp(0.0, 1.0))
+    }
+
+    /// Returns current minimum fraction of the points, that are rendered by distant chunks.
+    pub fn min_lod_fraction(&self) -> f32 {
+        *self.min_lod_fraction
+    }
+
+    /// Calculates the fraction of the points of a chunk, that will be rendered at the given distance
+    /// from the camera.
+    pub fn lod_fraction(&self, distance: f32) -> f32 {
+        if distance <= *self.lod_distance {
+            1.0
+        } else {
+            (*self.lod_distance / distance).clamp(*self.min_lod_fraction, 1.0)
+        }
+    }
+}
+
+fn distance_to_box(point: Vector3<f32>, aabb: &AxisAlignedBoundingBox) -> f32 {
+    let closest = Vector3::new(
+        point.x.clamp(aabb.min.x, aabb.max.x),
+        point.y.clamp(aabb.min.y, aabb.max.y),
+        point.z.clamp(aabb.min.z, aabb.max.z),
+    );
+    (closest - point).norm()
+}
+
+impl NodeTrait for PointCloud {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.points
+            .as_ref()
+            .and_then(|points| points.state().data().map(|data| data.bounds()))
+            .filter(|bounds| bounds.is_valid())
+            .unwrap_or_else(AxisAlignedBoundingBox::unit)
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn collect_render_data(&self, ctx: &mut RenderContext) -> RdcControlFlow {
+        if !self.global_visibility() || !self.is_globally_enabled() {
+            return RdcControlFlow::Continue;
+        }
+
+        if renderer::is_shadow_pass(ctx.render_pass_name) {
+            return RdcControlFlow::Continue;
+        }
+
+        let Some(points) = self.points.as_ref() else {
+            return RdcControlFlow::Continue;
+        };
+        let mut points_state = points.state();
+        let Some(data) = points_state.data() else {
+            return RdcControlFlow::Continue;
+        };
+
+        let global_transform = self.global_transform();
+
+        // Visible chunks with amount of their points to render and their point size.
+        let mut visible_chunks = Vec::new();
+        let mut point_count = 0;
+        for chunk in data.chunks() {
+            let world_bounds = chunk.bounds.transform(&global_transform);
+            if self.frustum_culling()
+                && !ctx
+                    .frustum
+                    .map_or(true, |f| f.is_intersects_aabb(&world_bounds))
+            {
+                continue;
+            }
+
+            let fraction =
+                self.lod_fraction(distance_to_box(*ctx.observer_position, &world_bounds));
+            let count =
+                ((chunk.range.len() as f32 * fraction).ceil() as usize).clamp(1, chunk.range.len());
+            // Keep the density of the chunk on screen the same.
+            let size = *self.point_size * (chunk.range.len() as f32 / count as f32).sqrt();
+            visible_chunks.push((chunk.range.start..chunk.range.start + count, size));
+            point_count += count;
+        }
+
+        if point_count == 0 {
+            return RdcControlFlow::Continue;
+        }
+
+        let size_attenuation = if *self.size_attenuation { 1.0 } else { 0.0 };
+        let sort_index = ctx.calculate_sorting_index(self.global_position());
+        let points = data.points();
+
+        ctx.storage.push_triangles(
+            PointVertex::layout(),
+            &self.material,
+            RenderPath::Forward,
+            0,
+            sort_index,
+            false,
+            self.self_handle,
+            &mut move |mut vertex_buffer, mut triangle_buffer| {
+                let start_vertex_index = vertex_buffer.vertex_count();
+
+                for (range, size) in visible_chunks.iter() {
+                    for point in points[range.clone()].iter() {
+                        let position = global_transform
+                            .transform_point(&Point3::from(point.position))
+                            .coords;
+                        for tex_coord in [
+                            Vector2::new(0.0, 0.0),
+                            Vector2::new(1.0, 0.0),
+                            Vector2::new(1.0, 1.0),
+                            Vector2::new(0.0, 1.0),
+                        ] {
+                            let vertex = PointVertex {
+                                position,
+                                tex_coord,
+                                size: *size,
+                                size_attenuation,
+                                color: point.color,
+                            };
+                            vertex_buffer
+                                .push_vertex_raw(value_as_u8_slice(&vertex))
+                                .unwrap();
+                        }
+                    }
+                }
+
+                let triangles = (0..point_count).flat_map(|i| {
+                    let base_index = (i * 4) as u32;
+
+                    [
+                        TriangleDefinition([base_index, base_index + 1, base_index + 2]),
+                        TriangleDefinition([base_index, base_index + 2, base_index + 3]),
+                    ]
+                });
+
+                triangle_buffer.push_triangles_iter_with_offset(start_vertex_index, triangles)
+            },
+        );
+
+        RdcControlFlow::Continue
+    }
+}
+
+/// Allows you to create point clouds in declarative manner.
+pub struct PointCloudBuilder {
+    base_builder: BaseBuilder,
+    points: Option<PointCloudResource>,
+    material: MaterialResource,
+    point_size: f32,
+    size_attenuation: bool,
+    lod_distance: f32,
+    min_lod_fraction: f32,
+}
+
+impl PointCloudBuilder {
+    /// Creates new builder instance.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            points: None,
+            material: MaterialResource::new_ok(
+                ResourceKind::Embedded,
+                Material::standard_point_cloud(),
+            ),
+            point_size: 0.02,
+            size_attenuation: true,
+            lod_distance: 20.0,
+            min_lod_fraction: 0.05,
+        }
+    }
+
+    /// Sets desired point cloud resource.
+    pub fn with_points(mut self, points: PointCloudResource) -> Self {
+        self.points = Some(points);
+        self
+    }
+
+    /// Sets desired material of the point cloud.
+    pub fn with_material(mut self, material: MaterialResource) -> Self {
+        self.material = material;
+        self
+    }
+
+    /// Sets desired size of the points.
+    pub fn with_point_size(mut self, size: f32) -> Self {
+        self.point_size = size.max(0.0);
+        self
+    }
+
+    /// Sets whether the size of the points should be attenuated with distance or not.
+    pub fn with_size_attenuation(mut self, enabled: bool) -> Self {
+        self.size_attenuation = enabled;
+        self
+    }
+
+    /// Sets desired distance, at which the chunks of the point cloud start to lose their points.
+    pub fn with_lod_distance(mut self, distance: f32) -> Self {
+        self.lod_distance = distance.max(0.0);
+        self
+    }
+
+    /// Sets desired minimum fraction of the points, that are rendered by distant chunks.
+    pub fn with_min_lod_fraction(mut self, fraction: f32) -> Self {
+        self.min_lod_fraction = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Creates new point cloud.
+    pub fn build_point_cloud(self) -> PointCloud {
+        PointCloud {
+            base: self.base_builder.build_base(),
+            points: self.points.into(),
+            material: self.material.into(),
+            point_size: self.point_size.into(),
+            size_attenuation: self.size_attenuation.into(),
+            lod_distance: self.lod_distance.into(),
+            min_lod_fraction: self.min_lod_fraction.into(),
+        }
+    }
+
+    /// Creates new point cloud node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_point_cloud())
+    }
+
+    /// Creates new point cloud node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
@@ -0,0 +1,326 @@
+//! Reader of point clouds in Polygon File Format (`*.ply`). Only the `vertex` element is read, every
+//! other element (faces, edges, etc.) is skipped. ASCII and binary (both endianness) encodings are
+//! supported.
+
+use crate::{
+    core::{algebra::Vector3, color::Color},
+    scene::point_cloud::resource::{PointCloudError, PointCloudPoint},
+};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Encoding {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Scalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl Scalar {
+    fn parse(name: &str) -> Result<Self, PointCloudError> {
+        Ok(match name {
+            "char" | "int8" => Scalar::I8,
+            "uchar" | "uint8" => Scalar::U8,
+            "short" | "int16" => Scalar::I16,
+            "ushort" | "uint16" => Scalar::U16,
+            "int" | "int32" => Scalar::I32,
+            "uint" | "uint32" => Scalar::U32,
+            "float" | "float32" => Scalar::F32,
+            "double" | "float64" => Scalar::F64,
+            _ => return Err(PointCloudError::Format(format!("Unknown PLY type {name}"))),
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Scalar::I8 | Scalar::U8 => 1,
+            Scalar::I16 | Scalar::U16 => 2,
+            Scalar::I32 | Scalar::U32 | Scalar::F32 => 4,
+            Scalar::F64 => 8,
+        }
+    }
+
+    /// Returns the value, that corresponds to full intensity of a color channel of this type.
+    fn color_range(self) -> f64 {
+        match self {
+            Scalar::U8 | Scalar::I8 => u8::MAX as f64,
+            Scalar::U16 | Scalar::I16 => u16::MAX as f64,
+            Scalar::U32 | Scalar::I32 => u32::MAX as f64,
+            Scalar::F32 | Scalar::F64 => 1.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Property {
+    name: String,
+    scalar: Scalar,
+    /// Type of the length of a list property.
+    list_length: Option<Scalar>,
+}
+
+#[derive(Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+    encoding: Encoding,
+}
+
+impl Reader<'_> {
+    fn ascii_token(&mut self) -> Result<&str, PointCloudError> {
+        while self
+            .data
+            .get(self.position)
+            .is_some_and(|c| c.is_ascii_whitespace())
+        {
+            self.position += 1;
+        }
+        let start = self.position;
+        while self
+            .data
+            .get(self.position)
+            .is_some_and(|c| !c.is_ascii_whitespace())
+        {
+            self.position += 1;
+        }
+        if start == self.position {
+            return Err(PointCloudError::Format(
+                "Unexpected end of PLY data".to_string(),
+            ));
+        }
+        std::str::from_utf8(&self.data[start..self.position])
+            .map_err(|_| PointCloudError::Format("Invalid PLY value".to_string()))
+    }
+
+    fn read(&mut self, scalar: Scalar) -> Result<f64, PointCloudError> {
+        if self.encoding == Encoding::Ascii {
+            let token = self.ascii_token()?;
+            return token
+                .parse::<f64>()
+                .map_err(|_| PointCloudError::Format(format!("Invalid PLY value {token}")));
+        }
+
+        let size = scalar.size();
+        let bytes = self
+            .data
+            .get(self.position..self.position + size)
+            .ok_or_else(|| PointCloudError::Format("Unexpected end of PLY data".to_string()))?;
+        self.position += size;
+
+        let mut buffer = [0u8; 8];
+        buffer[..size].copy_from_slice(bytes);
+        if self.encoding == Encoding::BinaryBigEndian {
+            buffer[..size].reverse();
+        }
+        Ok(match scalar {
+            Scalar::I8 => buffer[0] as i8 as f64,
+            Scalar::U8 => buffer[0] as f64,
+            Scalar::I16 => i16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+            Scalar::U16 => u16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+            Scalar::I32 => i32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64,
+            Scalar::U32 => u32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64,
+            Scalar::F32 => f32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64,
+            Scalar::F64 => f64::from_le_bytes(buffer),
+        })
+    }
+
+    /// Reads a property and returns its value. Lists are skipped, their value is zero.
+    fn read_property(&mut self, property: &Property) -> Result<f64, PointCloudError> {
+        match property.list_length {
+            Some(length) => {
+                let length = self.read(length)? as usize;
+                for _ in 0..length {
+                    self.read(property.scalar)?;
+                }
+                Ok(0.0)
+            }
+            None => self.read(property.scalar),
+        }
+    }
+}
+
+fn parse_header(data: &[u8]) -> Result<(Encoding, Vec<Element>, usize), PointCloudError> {
+    const END_OF_HEADER: &[u8] = b"end_header";
+
+    let header_end = data
+        .windows(END_OF_HEADER.len())
+        .position(|w| w == END_OF_HEADER)
+        .ok_or_else(|| PointCloudError::Format("PLY header is not terminated".to_string()))?;
+    // Data starts right after the line with the end of the header.
+    let data_start = data[header_end..]
+        .iter()
+        .position(|c| *c == b'\n')
+        .map_or(data.len(), |p| header_end + p + 1);
+
+    let header = String::from_utf8_lossy(&data[..header_end]);
+    let mut lines = header.lines().map(str::trim);
+    if lines.next() != Some("ply") {
+        return Err(PointCloudError::Format("Not a PLY file".to_string()));
+    }
+
+    let mut encoding = None;
+    let mut elements = Vec::<Element>::new();
+    for line in lines {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        match words.as_slice() {
+            ["format", format, ..] => {
+                encoding = Some(match *format {
+                    "ascii" => Encoding::Ascii,
+                    "binary_little_endian" => Encoding::BinaryLittleEndian,
+                    "binary_big_endian" => Encoding::BinaryBigEndian,
+                    _ => {
+                        return Err(PointCloudError::Format(format!(
+                            "Unknown PLY format {format}"
+                        )))
+                    }
+                })
+            }
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count.parse().map_err(|_| {
+                    PointCloudError::Format(format!("Invalid element count {count}"))
+                })?,
+                properties: Default::default(),
+            }),
+            ["property", "list", length, scalar, name] => {
+                if let Some(element) = elements.last_mut() {
+                    element.properties.push(Property {
+                        name: name.to_string(),
+                        scalar: Scalar::parse(scalar)?,
+                        list_length: Some(Scalar::parse(length)?),
+                    });
+                }
+            }
+            ["property", scalar, name] => {
+                if let Some(element) = elements.last_mut() {
+                    element.properties.push(Property {
+                        name: name.to_string(),
+                        scalar: Scalar::parse(scalar)?,
+                        list_length: None,
+                    });
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let encoding =
+        encoding.ok_or_else(|| PointCloudError::Format("PLY format is not defined".to_string()))?;
+    Ok((encoding, elements, data_start))
+}
+
+/// Reads points from the given PLY data.
+pub fn read(data: &[u8]) -> Result<Vec<PointCloudPoint>, PointCloudError> {
+    let (encoding, elements, data_start) = parse_header(data)?;
+    let mut reader = Reader {
+        data,
+        position: data_start,
+        encoding,
+    };
+
+    for element in elements.iter() {
+        if element.name != "vertex" {
+            // Skip the element.
+            for _ in 0..element.count {
+                for property in element.properties.iter() {
+                    reader.read_property(property)?;
+                }
+            }
+            continue;
+        }
+
+        let find = |names: &[&str]| {
+            element
+                .properties
+                .iter()
+                .position(|p| names.contains(&p.name.as_str()))
+        };
+        let (Some(x), Some(y), Some(z)) = (find(&["x"]), find(&["y"]), find(&["z"])) else {
+            return Err(PointCloudError::Format(
+                "PLY vertices do not have positions".to_string(),
+            ));
+        };
+        let color = [
+            find(&["red", "r", "diffuse_red"]),
+            find(&["green", "g", "diffuse_green"]),
+            find(&["blue", "b", "diffuse_blue"]),
+            find(&["alpha", "a", "diffuse_alpha"]),
+        ];
+
+        let mut values = vec![0.0; element.properties.len()];
+        let mut points = Vec::with_capacity(element.count);
+        for _ in 0..element.count {
+            for (value, property) in values.iter_mut().zip(element.properties.iter()) {
+                *value = reader.read_property(property)?;
+            }
+            let channel = |index: Option<usize>| {
+                index.map_or(255, |i| {
+                    let range = element.properties[i].scalar.color_range();
+                    ((values[i] / range).clamp(0.0, 1.0) * 255.0).round() as u8
+                })
+            };
+            points.push(PointCloudPoint {
+                position: Vector3::new(values[x] as f32, values[y] as f32, values[z] as f32),
+                color: Color::from_rgba(
+                    channel(color[0]),
+                    channel(color[1]),
+                    channel(color[2]),
+                    channel(color[3]),
+                ),
+            });
+        }
+        return Ok(points);
+    }
+
+    Err(PointCloudError::Format(
+        "PLY file does not have vertices".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::{algebra::Vector3, color::Color};
+    use crate::scene::point_cloud::ply::read;
+
+    #[test]
+    fn test_ascii() {
+        let data = b"ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\n\
+            property float z\nproperty uchar red\nproperty uchar green\nproperty uchar blue\n\
+            element face 0\nproperty list uchar int vertex_indices\nend_header\n\
+            1 2 3 255 0 0\n-1 0.5 0 0 0 255\n";
+        let points = read(data).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].position, Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(points[0].color, Color::from_rgba(255, 0, 0, 255));
+        assert_eq!(points[1].color, Color::from_rgba(0, 0, 255, 255));
+    }
+
+    #[test]
+    fn test_binary() {
+        let mut data = b"ply\nformat binary_little_endian 1.0\nelement vertex 1\n\
+            property float x\nproperty float y\nproperty float z\nend_header\n"
+            .to_vec();
+        for v in [1.0f32, 2.0, 3.0] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        let points = read(&data).unwrap();
+        assert_eq!(points[0].position, Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(points[0].color, Color::WHITE);
+    }
+}
@@ -0,0 +1,330 @@
+//! Point cloud resource stores a set of colored points, split into spatial chunks. See [`PointCloudData`]
+//! docs for more info.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+        state::LoadError,
+        Resource, ResourceData,
+    },
+    core::{
+        algebra::Vector3,
+        color::Color,
+        io::FileLoadError,
+        math::aabb::AxisAlignedBoundingBox,
+        rand::{prelude::StdRng, seq::SliceRandom, SeedableRng},
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::point_cloud::{las, ply},
+};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Display, Formatter},
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Maximum amount of points in a single chunk of a point cloud.
+pub const MAX_CHUNK_POINTS: usize = 16384;
+
+/// Maximum depth of the octree, that is used to split a point cloud into chunks. It prevents endless
+/// splitting of dense clusters of coincident points.
+pub const MAX_OCTREE_DEPTH: usize = 12;
+
+/// An error that may occur during point cloud resource loading.
+#[derive(Debug)]
+pub enum PointCloudError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// The file has unsupported format or it is corrupted.
+    Format(String),
+
+    /// The file has unsupported extension.
+    UnsupportedExtension(PathBuf),
+}
+
+impl Display for PointCloudError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            Self::Format(v) => {
+                write!(f, "Invalid point cloud data: {v}")
+            }
+            Self::UnsupportedExtension(v) => {
+                write!(f, "Unsupported point cloud format: {}", v.display())
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for PointCloudError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// A single point of a point cloud.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Reflect, Visit)]
+pub struct PointCloudPoint {
+    /// Position of the point in local coordinates of a point cloud.
+    pub position: Vector3<f32>,
+    /// Color of the point.
+    pub color: Color,
+}
+
+/// A spatial chunk of a point cloud, it is a leaf of the octree, that was used to split the points.
+/// Points of a chunk are shuffled, so any prefix of the chunk is a uniform subset of its points - it
+/// is used for level-of-detail.
+#[derive(Clone, Debug)]
+pub struct PointCloudChunk {
+    /// Bounds of the points of the chunk.
+    pub bounds: AxisAlignedBoundingBox,
+    /// Range of the points of the chunk in [`PointCloudData::points`].
+    pub range: Range<usize>,
+}
+
+/// Point cloud is a set of colored points, usually it is a result of photogrammetry or laser scanning.
+/// Point clouds are loaded from Polygon File Format (`*.ply`) and ASPRS LAS (`*.las`) files and rendered
+/// using [`crate::scene::point_cloud::PointCloud`] scene node.
+///
+/// Points are split into chunks using an octree, each chunk has at most [`MAX_CHUNK_POINTS`] points.
+/// Chunks are culled individually, and the amount of rendered points of each chunk depends on its
+/// distance to the camera.
+///
+/// ## Coordinate system
+///
+/// LAS files use Z-up coordinate system, their points are converted to Y-up and moved to the origin,
+/// because geo-referenced coordinates are too large for single precision. The original center of the
+/// points could be fetched using [`PointCloudData::origin`]. PLY files are loaded as is.
+#[derive(Debug, Clone, Default, Reflect, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "8e3b5d1a-4c7f-4a29-b6d2-1f9e0c7a3b85")]
+pub struct PointCloudData {
+    #[reflect(read_only)]
+    point_count: usize,
+    #[reflect(read_only)]
+    origin: Vector3<f64>,
+    #[reflect(hidden)]
+    points: Vec<PointCloudPoint>,
+    #[reflect(hidden)]
+    chunks: Vec<PointCloudChunk>,
+    #[reflect(hidden)]
+    bounds: AxisAlignedBoundingBox,
+}
+
+impl Visit for PointCloudData {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.origin.visit("Origin", &mut region)?;
+        self.points.visit("Points", &mut region)?;
+
+        // Chunks are not stored, they're rebuilt from the points.
+        if region.is_reading() {
+            *self = Self::new(std::mem::take(&mut self.points), self.origin);
+        }
+
+        Ok(())
+    }
+}
+
+impl ResourceData for PointCloudData {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, _path: &Path) -> Result<(), Box<dyn Error>> {
+        Err("Saving of point clouds is not supported!".into())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        false
+    }
+}
+
+fn calculate_bounds(points: &[PointCloudPoint]) -> AxisAlignedBoundingBox {
+    let mut bounds = AxisAlignedBoundingBox::default();
+    for point in points {
+        bounds.add_point(point.position);
+    }
+    bounds
+}
+
+fn split(
+    points: Vec<PointCloudPoint>,
+    depth: usize,
+    output: &mut Vec<PointCloudPoint>,
+    chunks: &mut Vec<PointCloudChunk>,
+) {
+    if points.is_empty() {
+        return;
+    }
+
+    let bounds = calculate_bounds(&points);
+    if points.len() <= MAX_CHUNK_POINTS || depth >= MAX_OCTREE_DEPTH {
+        let start = output.len();
+        output.extend(points);
+        chunks.push(PointCloudChunk {
+            bounds,
+            range: start..output.len(),
+        });
+        return;
+    }
+
+    let center = bounds.center();
+    let mut octants: [Vec<PointCloudPoint>; 8] = Default::default();
+    for point in points {
+        let index = (point.position.x > center.x) as usize
+            | (((point.position.y > center.y) as usize) << 1)
+            | (((point.position.z > center.z) as usize) << 2);
+        octants[index].push(point);
+    }
+
+    for octant in octants {
+        split(octant, depth + 1, output, chunks);
+    }
+}
+
+impl PointCloudData {
+    /// Creates new point cloud from the given points. `origin` is the position of the local origin of
+    /// the points in the source coordinate system, it could be zero if there's no such position.
+    pub fn new(points: Vec<PointCloudPoint>, origin: Vector3<f64>) -> Self {
+        let point_count = points.len();
+        let bounds = calculate_bounds(&points);
+
+        let mut sorted = Vec::with_capacity(point_count);
+        let mut chunks = Vec::new();
+        split(points, 0, &mut sorted, &mut chunks);
+
+        // Fixed seed makes the level-of-detail stable between runs.
+        let mut rng = StdRng::seed_from_u64(0);
+        for chunk in chunks.iter() {
+            sorted[chunk.range.clone()].shuffle(&mut rng);
+        }
+
+        Self {
+            point_count,
+            origin,
+            points: sorted,
+            chunks,
+            bounds,
+        }
+    }
+
+    /// Creates new point cloud from the given data. The format of the data is defined by the extension
+    /// of the given path, it could be either `ply` or `las`.
+    pub fn from_bytes(data: &[u8], path: &Path) -> Result<Self, PointCloudError> {
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("ply") => Ok(Self::new(ply::read(data)?, Vector3::default())),
+            Some("las") => {
+                let las = las::read(data)?;
+                Ok(Self::new(las.points, las.origin))
+            }
+            _ => Err(PointCloudError::UnsupportedExtension(path.to_path_buf())),
+        }
+    }
+
+    /// Load a point cloud resource from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, PointCloudError> {
+        let bytes = io.load_file(path).await?;
+        Self::from_bytes(&bytes, path)
+    }
+
+    /// Returns every point of the point cloud. Points are grouped by chunks.
+    pub fn points(&self) -> &[PointCloudPoint] {
+        &self.points
+    }
+
+    /// Returns a list of spatial chunks of the point cloud.
+    pub fn chunks(&self) -> &[PointCloudChunk] {
+        &self.chunks
+    }
+
+    /// Returns local-space bounding box of every point of the point cloud.
+    pub fn bounds(&self) -> AxisAlignedBoundingBox {
+        self.bounds
+    }
+
+    /// Returns the position of the local origin of the points in the source coordinate system.
+    pub fn origin(&self) -> Vector3<f64> {
+        self.origin
+    }
+}
+
+/// Type alias for point cloud resources.
+pub type PointCloudResource = Resource<PointCloudData>;
+
+/// Default implementation for point cloud loading.
+pub struct PointCloudLoader;
+
+impl ResourceLoader for PointCloudLoader {
+    fn extensions(&self) -> &[&str] {
+        &["ply", "las"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <PointCloudData as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let point_cloud = PointCloudData::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(point_cloud))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, color::Color},
+        scene::point_cloud::resource::{PointCloudData, PointCloudPoint, MAX_CHUNK_POINTS},
+    };
+
+    #[test]
+    fn test_chunks() {
+        let points = (0..MAX_CHUNK_POINTS * 3)
+            .map(|i| PointCloudPoint {
+                position: Vector3::new(
+                    (i % 100) as f32,
+                    (i / 100 % 100) as f32,
+                    (i / 10000) as f32,
+                ),
+                color: Color::WHITE,
+            })
+            .collect::<Vec<_>>();
+        let data = PointCloudData::new(points, Vector3::default());
+
+        assert!(data.chunks().len() > 1);
+        let mut next = 0;
+        for chunk in data.chunks() {
+            assert_eq!(chunk.range.start, next);
+            assert!(chunk.range.len() <= MAX_CHUNK_POINTS);
+            for point in &data.points()[chunk.range.clone()] {
+                assert!(chunk.bounds.is_contains_point(point.position));
+            }
+            next = chunk.range.end;
+        }
+        assert_eq!(next, MAX_CHUNK_POINTS * 3);
+    }
+}
@@ -81,6 +81,7 @@ impl Reflect for Limb {
                 max_value: None,
                 step: None,
                 precision: None,
+                unit: None,
                 doc: "",
             },
             FieldInfo {
@@ -97,6 +98,7 @@ impl Reflect for Limb {
                 max_value: None,
                 step: None,
                 precision: None,
+                unit: None,
                 doc: "",
             },
             FieldInfo {
@@ -113,6 +115,7 @@ impl Reflect for Limb {
                 max_value: None,
                 step: None,
                 precision: None,
+                unit: None,
                 doc: "",
             },
         ])
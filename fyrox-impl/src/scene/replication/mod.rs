@@ -0,0 +1,480 @@
+//! Server-authoritative replication of scene nodes. See [`ReplicationServer`] and [`ReplicationClient`] docs
+//! for more info.
+//!
+//! Replication does not depend on any particular network library, it only produces and consumes byte
+//! packets. It is up to the game to send them over the network (usually via an unreliable channel).
+
+use crate::{
+    core::{pool::Handle, reflect::prelude::*, visitor::prelude::*},
+    scene::{base::SceneNodeId, graph::Graph, node::Node},
+};
+use fxhash::FxHashMap;
+use fyrox_graph::{BaseSceneGraph, SceneGraph};
+use std::{
+    collections::VecDeque,
+    fmt::{Display, Formatter},
+};
+
+pub mod snapshot;
+
+use snapshot::{EntityState, ReplicatedTransform, ReplicatedValue, Snapshot, SnapshotDelta};
+
+/// Replication settings of a scene node. Defines whether the node should be replicated and what parts of it
+/// must be sent to clients.
+#[derive(Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct ReplicationSettings {
+    /// Whether the node is replicated or not. Default is `false`.
+    #[reflect(description = "Whether the node is replicated or not.")]
+    pub enabled: bool,
+    /// Whether the local transform (position, rotation, scale) of the node is replicated or not.
+    /// Default is `true`.
+    #[reflect(description = "Whether the local transform of the node is replicated or not.")]
+    pub transform: bool,
+    /// Whether the replicated values should be smoothly interpolated on clients or not. Default is `true`.
+    #[reflect(
+        description = "Whether the replicated values should be smoothly interpolated \
+        on clients or not."
+    )]
+    pub interpolate: bool,
+    /// A list of reflection paths of additional properties of the node that must be replicated. Script
+    /// properties are accessible too, for example `base.scripts[0].script.Some@0.health`. Only a limited
+    /// set of property types is supported, see [`ReplicatedValue::from_reflect`].
+    #[reflect(
+        description = "A list of reflection paths of additional properties of the node \
+        that must be replicated. For example: `base.visibility` or \
+        `base.scripts[0].script.Some@0.health`."
+    )]
+    pub fields: Vec<String>,
+}
+
+impl Default for ReplicationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            transform: true,
+            interpolate: true,
+            fields: Default::default(),
+        }
+    }
+}
+
+/// An error that may occur when receiving a snapshot.
+#[derive(Debug)]
+pub enum ReplicationError {
+    /// The packet is malformed.
+    Visit(VisitError),
+    /// The packet is delta-compressed relative to a snapshot that the client doesn't have (anymore).
+    MissingBaseline(u64),
+}
+
+impl Display for ReplicationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplicationError::Visit(err) => write!(f, "Malformed snapshot: {err}"),
+            ReplicationError::MissingBaseline(tick) => {
+                write!(f, "Baseline snapshot {tick} is missing")
+            }
+        }
+    }
+}
+
+impl From<VisitError> for ReplicationError {
+    fn from(err: VisitError) -> Self {
+        Self::Visit(err)
+    }
+}
+
+const HISTORY_SIZE: usize = 64;
+
+fn read_field(node: &Node, path: &str) -> Option<ReplicatedValue> {
+    let mut value = None;
+    node.resolve_path(path, &mut |result| {
+        if let Ok(property) = result {
+            value = ReplicatedValue::from_reflect(property);
+        }
+    });
+    value
+}
+
+fn write_field(node: &mut Node, path: &str, value: ReplicatedValue) {
+    node.resolve_path_mut(path, &mut |result| {
+        if let Ok(property) = result {
+            let _ = property.set(value.clone().into_reflect());
+        }
+    });
+}
+
+/// Server side of the replication. It captures the state of replicated nodes (the ones with
+/// [`ReplicationSettings::enabled`] flag set) at fixed moments of time (ticks) and encodes them into
+/// packets for clients.
+///
+/// Every packet is delta-compressed relative to the last snapshot acknowledged by a client, so only
+/// the changed data is sent. Acknowledgement is up to the game: a client should send its
+/// [`ReplicationClient::last_received_tick`] back to the server from time to time.
+///
+/// ```rust
+/// # use fyrox_impl::scene::{graph::Graph, replication::{ReplicationClient, ReplicationServer}};
+/// fn server_tick(server: &mut ReplicationServer, graph: &Graph, dt: f32, ack: Option<u64>) -> Vec<u8> {
+///     server.capture(graph, dt);
+///     server.encode(ack).unwrap()
+/// }
+///
+/// fn client_frame(client: &mut ReplicationClient, graph: &mut Graph, packets: &[Vec<u8>], dt: f32) {
+///     for packet in packets {
+///         // Packets could be lost or reordered, this is fine.
+///         let _ = client.receive(packet);
+///     }
+///     client.update(graph, dt);
+/// }
+/// ```
+#[derive(Default, Debug)]
+pub struct ReplicationServer {
+    tick: u64,
+    time: f64,
+    history: VecDeque<Snapshot>,
+}
+
+impl ReplicationServer {
+    /// Creates new replication server.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the tick of the last captured snapshot.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Returns the last captured snapshot.
+    pub fn latest(&self) -> Option<&Snapshot> {
+        self.history.back()
+    }
+
+    /// Captures the state of every replicated node of the graph. `dt` is the time passed since the previous
+    /// capture.
+    pub fn capture(&mut self, graph: &Graph, dt: f32) {
+        self.tick += 1;
+        self.time += dt as f64;
+
+        let mut entities = FxHashMap::default();
+        for node in graph.linear_iter() {
+            let settings = node.replication();
+            if !settings.enabled {
+                continue;
+            }
+
+            let transform = settings.transform.then(|| {
+                let transform = node.local_transform();
+                ReplicatedTransform {
+                    position: **transform.position(),
+                    rotation: **transform.rotation(),
+                    scale: **transform.scale(),
+                }
+            });
+
+            let fields = settings
+                .fields
+                .iter()
+                .map(|path| read_field(node, path))
+                .collect();
+
+            entities.insert(node.instance_id(), EntityState { transform, fields });
+        }
+
+        self.history.push_back(Snapshot {
+            tick: self.tick,
+            time: self.time,
+            entities,
+        });
+        while self.history.len() > HISTORY_SIZE {
+            self.history.pop_front();
+        }
+    }
+
+    /// Encodes the last captured snapshot relative to the given baseline (the last snapshot acknowledged by
+    /// a client). The full snapshot is encoded if there's no baseline or it is too old.
+    pub fn encode(&self, baseline: Option<u64>) -> Result<Vec<u8>, VisitError> {
+        let Some(latest) = self.history.back() else {
+            return SnapshotDelta::default().encode();
+        };
+        let baseline = baseline.and_then(|tick| self.history.iter().find(|s| s.tick == tick));
+        SnapshotDelta::new(baseline, latest).encode()
+    }
+}
+
+/// Client side of the replication. It receives packets from [`ReplicationServer`], reconstructs full
+/// snapshots and applies them to the local scene graph. Values of nodes with
+/// [`ReplicationSettings::interpolate`] flag are smoothly interpolated between two snapshots, the client
+/// intentionally lags behind the server by [`ReplicationClient::interpolation_delay`] seconds for this.
+///
+/// Nodes are matched by their instance ids ([`crate::scene::base::Base::instance_id`]). Nodes loaded from
+/// the same scene have the same ids on both sides, nodes spawned at runtime should be created with the
+/// same id on clients using [`crate::scene::base::BaseBuilder::with_instance_id`]. Replication settings
+/// are taken from the client-side nodes.
+#[derive(Debug)]
+pub struct ReplicationClient {
+    /// Time (in seconds) by which the client lags behind the server. It should be bigger than the interval
+    /// between two packets. Default is 0.1 seconds.
+    pub interpolation_delay: f64,
+    history: VecDeque<Snapshot>,
+    render_time: Option<f64>,
+    handles: FxHashMap<SceneNodeId, Handle<Node>>,
+}
+
+impl Default for ReplicationClient {
+    fn default() -> Self {
+        Self {
+            interpolation_delay: 0.1,
+            history: Default::default(),
+            render_time: None,
+            handles: Default::default(),
+        }
+    }
+}
+
+impl ReplicationClient {
+    /// Creates new replication client.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the tick of the most recent received snapshot. It should be sent to the server as an
+    /// acknowledgement.
+    pub fn last_received_tick(&self) -> Option<u64> {
+        self.history.back().map(|snapshot| snapshot.tick)
+    }
+
+    /// Decodes the given packet and stores the snapshot. Outdated packets are ignored. Returns the tick of
+    /// the snapshot.
+    pub fn receive(&mut self, data: &[u8]) -> Result<u64, ReplicationError> {
+        let delta = SnapshotDelta::decode(data)?;
+        let tick = delta.tick;
+
+        if self
+            .last_received_tick()
+            .is_some_and(|last_tick| tick <= last_tick)
+        {
+            return Ok(tick);
+        }
+
+        let baseline = match delta.baseline {
+            Some(baseline_tick) => Some(
+                self.history
+                    .iter()
+                    .find(|snapshot| snapshot.tick == baseline_tick)
+                    .ok_or(ReplicationError::MissingBaseline(baseline_tick))?,
+            ),
+            None => None,
+        };
+
+        let snapshot = delta.apply(baseline);
+        self.history.push_back(snapshot);
+        while self.history.len() > HISTORY_SIZE {
+            self.history.pop_front();
+        }
+
+        Ok(tick)
+    }
+
+    fn find_node(&mut self, graph: &Graph, id: SceneNodeId) -> Option<Handle<Node>> {
+        if let Some(handle) = self.handles.get(&id) {
+            if graph
+                .try_get(*handle)
+                .is_some_and(|node| node.instance_id() == id)
+            {
+                return Some(*handle);
+            }
+        }
+
+        let (handle, _) = graph
+            .pair_iter()
+            .find(|(_, node)| node.instance_id() == id)?;
+        self.handles.insert(id, handle);
+        Some(handle)
+    }
+
+    /// Applies received snapshots to the graph. `dt` is the time passed since the previous call.
+    pub fn update(&mut self, graph: &mut Graph, dt: f32) {
+        let Some(latest) = self.history.back() else {
+            return;
+        };
+        let target_time = latest.time - self.interpolation_delay;
+        let oldest_time = self.history.front().map_or(target_time, |s| s.time);
+
+        // Advance the local clock and keep it near the target time, so the client won't drift away from
+        // the server.
+        let render_time = self
+            .render_time
+            .map_or(target_time, |time| time + dt as f64);
+        let render_time = if (render_time - target_time).abs() > self.interpolation_delay {
+            target_time
+        } else {
+            render_time
+        };
+        let render_time = render_time.clamp(oldest_time.min(latest.time), latest.time);
+        self.render_time = Some(render_time);
+
+        // Find two snapshots around the render time.
+        let next_index = self
+            .history
+            .iter()
+            .position(|snapshot| snapshot.time >= render_time)
+            .unwrap_or(self.history.len() - 1);
+        let prev_index = next_index.saturating_sub(1);
+        let prev = &self.history[prev_index];
+        let next = &self.history[next_index];
+        let t = if next.time > prev.time {
+            ((render_time - prev.time) / (next.time - prev.time)).clamp(0.0, 1.0) as f32
+        } else {
+            1.0
+        };
+
+        let states = next
+            .entities
+            .iter()
+            .map(|(id, next_state)| (*id, prev.entities.get(id).cloned(), next_state.clone()))
+            .collect::<Vec<_>>();
+
+        for (id, prev_state, next_state) in states {
+            let Some(handle) = self.find_node(graph, id) else {
+                continue;
+            };
+
+            let node = &mut graph[handle];
+            let interpolate = node.replication().interpolate;
+            let paths = node.replication().fields.clone();
+
+            let prev_state = prev_state
+                .as_ref()
+                .filter(|_| interpolate)
+                .unwrap_or(&next_state);
+
+            if let (Some(prev_transform), Some(next_transform)) =
+                (prev_state.transform.as_ref(), next_state.transform.as_ref())
+            {
+                let transform = prev_transform.interpolate(next_transform, t);
+                node.local_transform_mut()
+                    .set_position(transform.position)
+                    .set_rotation(transform.rotation)
+                    .set_scale(transform.scale);
+            }
+
+            for (index, path) in paths.iter().enumerate() {
+                let Some(Some(next_value)) = next_state.fields.get(index) else {
+                    continue;
+                };
+                let value = match prev_state.fields.get(index) {
+                    Some(Some(prev_value)) => prev_value.interpolate(next_value, t),
+                    _ => next_value.clone(),
+                };
+                write_field(node, path, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::Vector3, reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*,
+        },
+        scene::{
+            base::BaseBuilder,
+            graph::Graph,
+            pivot::PivotBuilder,
+            replication::{
+                ReplicationClient, ReplicationError, ReplicationServer, ReplicationSettings,
+            },
+            transform::TransformBuilder,
+        },
+        script::ScriptTrait,
+    };
+    use fyrox_graph::SceneGraph;
+
+    #[derive(Clone, Debug, PartialEq, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
+    #[type_uuid(id = "0c7e7b4b-3f5e-4d62-9a3c-2f1e5f1f7f3a")]
+    struct Health {
+        health: f32,
+    }
+
+    impl ScriptTrait for Health {}
+
+    fn make_graph(position: Vector3<f32>, visibility: bool, health: f32) -> Graph {
+        let mut graph = Graph::new();
+        PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Replicated")
+                .with_visibility(visibility)
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .build(),
+                )
+                .with_script(Health { health })
+                .with_replication(ReplicationSettings {
+                    enabled: true,
+                    fields: vec![
+                        "base.visibility".to_string(),
+                        "base.scripts[0].script.Some@0.health".to_string(),
+                    ],
+                    ..Default::default()
+                }),
+        )
+        .build(&mut graph);
+        graph
+    }
+
+    #[test]
+    fn test_replication() {
+        let mut server_graph = make_graph(Vector3::new(1.0, 2.0, 3.0), false, 50.0);
+
+        // Client scene is "loaded" from the same scene, so the ids must match.
+        let mut client_graph = make_graph(Default::default(), true, 100.0);
+        let server_node = server_graph.find_by_name_from_root("Replicated").unwrap().0;
+        let client_node = client_graph.find_by_name_from_root("Replicated").unwrap().0;
+        client_graph[client_node].instance_id = server_graph[server_node].instance_id;
+
+        let mut server = ReplicationServer::new();
+        let mut client = ReplicationClient::new();
+        client.interpolation_delay = 0.0;
+
+        server.capture(&server_graph, 0.05);
+        let full = server.encode(None).unwrap();
+        assert_eq!(client.receive(&full).unwrap(), 1);
+        client.update(&mut client_graph, 0.0);
+
+        let node = &client_graph[client_node];
+        assert_eq!(
+            **node.local_transform().position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+        assert!(!node.visibility());
+        assert_eq!(node.try_get_script::<Health>().unwrap().health, 50.0);
+
+        // Nothing changed - the delta must be smaller than the full snapshot.
+        server.capture(&server_graph, 0.05);
+        let delta = server.encode(client.last_received_tick()).unwrap();
+        assert!(delta.len() < full.len());
+        assert_eq!(client.receive(&delta).unwrap(), 2);
+
+        server_graph[server_node]
+            .local_transform_mut()
+            .set_position(Vector3::new(3.0, 2.0, 1.0));
+        server.capture(&server_graph, 0.05);
+        let delta = server.encode(client.last_received_tick()).unwrap();
+        assert_eq!(client.receive(&delta).unwrap(), 3);
+
+        client.update(&mut client_graph, 1.0);
+        assert_eq!(
+            **client_graph[client_node].local_transform().position(),
+            Vector3::new(3.0, 2.0, 1.0)
+        );
+
+        // A client that does not have the baseline.
+        assert!(matches!(
+            ReplicationClient::new().receive(&delta),
+            Err(ReplicationError::MissingBaseline(2))
+        ));
+    }
+}
@@ -0,0 +1,346 @@
+//! Snapshots of replicated state and their delta-compressed network representation.
+
+use crate::{
+    core::{
+        algebra::{UnitQuaternion, Vector2, Vector3, Vector4},
+        math::lerpf,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::base::SceneNodeId,
+};
+use fxhash::FxHashMap;
+use std::any::Any;
+
+/// A value of a replicated property. Only a limited set of types could be replicated, see
+/// [`ReplicatedValue::from_reflect`] for more info.
+#[derive(Clone, Debug, PartialEq, Visit)]
+pub enum ReplicatedValue {
+    /// Boolean value.
+    Bool(bool),
+    /// Signed 32-bit integer.
+    I32(i32),
+    /// Unsigned 32-bit integer.
+    U32(u32),
+    /// Signed 64-bit integer.
+    I64(i64),
+    /// Unsigned 64-bit integer.
+    U64(u64),
+    /// Real number.
+    F32(f32),
+    /// Double precision real number.
+    F64(f64),
+    /// String.
+    String(String),
+    /// Two-dimensional vector.
+    Vector2(Vector2<f32>),
+    /// Three-dimensional vector.
+    Vector3(Vector3<f32>),
+    /// Four-dimensional vector.
+    Vector4(Vector4<f32>),
+    /// Rotation.
+    Rotation(UnitQuaternion<f32>),
+}
+
+impl Default for ReplicatedValue {
+    fn default() -> Self {
+        Self::Bool(false)
+    }
+}
+
+impl ReplicatedValue {
+    /// Tries to create a replicated value from the given property. Supported types are: `bool`, `i32`, `u32`,
+    /// `i64`, `u64`, `f32`, `f64`, `String`, `Vector2<f32>`, `Vector3<f32>`, `Vector4<f32>`,
+    /// `UnitQuaternion<f32>`. Returns `None` for every other type.
+    pub fn from_reflect(property: &dyn Reflect) -> Option<Self> {
+        let mut value = None;
+        property.as_any(&mut |any| value = Self::from_any(any));
+        value
+    }
+
+    fn from_any(any: &dyn Any) -> Option<Self> {
+        macro_rules! try_cast {
+            ($($ty:ty => $variant:ident),*) => {
+                $(
+                    if let Some(value) = any.downcast_ref::<$ty>() {
+                        return Some(Self::$variant(value.clone()));
+                    }
+                )*
+            };
+        }
+
+        try_cast!(
+            bool => Bool,
+            i32 => I32,
+            u32 => U32,
+            i64 => I64,
+            u64 => U64,
+            f32 => F32,
+            f64 => F64,
+            String => String,
+            Vector2<f32> => Vector2,
+            Vector3<f32> => Vector3,
+            Vector4<f32> => Vector4,
+            UnitQuaternion<f32> => Rotation
+        );
+
+        None
+    }
+
+    /// Converts the value to a boxed reflectable value, that could be assigned to a property.
+    pub fn into_reflect(self) -> Box<dyn Reflect> {
+        match self {
+            Self::Bool(v) => Box::new(v),
+            Self::I32(v) => Box::new(v),
+            Self::U32(v) => Box::new(v),
+            Self::I64(v) => Box::new(v),
+            Self::U64(v) => Box::new(v),
+            Self::F32(v) => Box::new(v),
+            Self::F64(v) => Box::new(v),
+            Self::String(v) => Box::new(v),
+            Self::Vector2(v) => Box::new(v),
+            Self::Vector3(v) => Box::new(v),
+            Self::Vector4(v) => Box::new(v),
+            Self::Rotation(v) => Box::new(v),
+        }
+    }
+
+    /// Interpolates two values. Real numbers, vectors and rotations are interpolated smoothly, any other
+    /// values (or values of different kinds) are switched at `t == 1.0`.
+    pub fn interpolate(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (Self::F32(a), Self::F32(b)) => Self::F32(lerpf(*a, *b, t)),
+            (Self::F64(a), Self::F64(b)) => Self::F64(a + (b - a) * t as f64),
+            (Self::Vector2(a), Self::Vector2(b)) => Self::Vector2(a.lerp(b, t)),
+            (Self::Vector3(a), Self::Vector3(b)) => Self::Vector3(a.lerp(b, t)),
+            (Self::Vector4(a), Self::Vector4(b)) => Self::Vector4(a.lerp(b, t)),
+            (Self::Rotation(a), Self::Rotation(b)) => Self::Rotation(a.nlerp(b, t)),
+            _ => {
+                if t >= 1.0 {
+                    other.clone()
+                } else {
+                    self.clone()
+                }
+            }
+        }
+    }
+}
+
+/// Replicated local transform of a node.
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+pub struct ReplicatedTransform {
+    /// Local position of the node.
+    pub position: Vector3<f32>,
+    /// Local rotation of the node.
+    pub rotation: UnitQuaternion<f32>,
+    /// Local scale of the node.
+    pub scale: Vector3<f32>,
+}
+
+impl ReplicatedTransform {
+    /// Interpolates two transforms.
+    pub fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Self {
+            position: self.position.lerp(&other.position, t),
+            rotation: self.rotation.nlerp(&other.rotation, t),
+            scale: self.scale.lerp(&other.scale, t),
+        }
+    }
+}
+
+/// Replicated state of a single node.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EntityState {
+    /// Transform of the node, `None` if the transform is not replicated.
+    pub transform: Option<ReplicatedTransform>,
+    /// Values of replicated fields. Indices match the indices of field paths in the replication settings
+    /// of the node, `None` means that the field could not be read.
+    pub fields: Vec<Option<ReplicatedValue>>,
+}
+
+/// Replicated state of a whole scene at some moment of time.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snapshot {
+    /// Sequential number of the snapshot.
+    pub tick: u64,
+    /// Server time (in seconds) at the moment of the snapshot capture.
+    pub time: f64,
+    /// States of every replicated node.
+    pub entities: FxHashMap<SceneNodeId, EntityState>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+struct FieldDelta {
+    index: u32,
+    value: Option<ReplicatedValue>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+struct EntityDelta {
+    id: SceneNodeId,
+    position: Option<Vector3<f32>>,
+    rotation: Option<UnitQuaternion<f32>>,
+    scale: Option<Vector3<f32>>,
+    // Used only when the transform was not replicated in the baseline, but it is now.
+    full_transform: bool,
+    field_count: u32,
+    fields: Vec<FieldDelta>,
+}
+
+impl EntityDelta {
+    fn new(id: SceneNodeId, baseline: Option<&EntityState>, state: &EntityState) -> Option<Self> {
+        let mut delta = Self {
+            id,
+            field_count: state.fields.len() as u32,
+            ..Default::default()
+        };
+
+        if let Some(transform) = state.transform.as_ref() {
+            let old = baseline.and_then(|baseline| baseline.transform.as_ref());
+            delta.full_transform = old.is_none();
+            if old.map_or(true, |old| old.position != transform.position) {
+                delta.position = Some(transform.position);
+            }
+            if old.map_or(true, |old| old.rotation != transform.rotation) {
+                delta.rotation = Some(transform.rotation);
+            }
+            if old.map_or(true, |old| old.scale != transform.scale) {
+                delta.scale = Some(transform.scale);
+            }
+        }
+
+        for (index, value) in state.fields.iter().enumerate() {
+            let old = baseline.and_then(|baseline| baseline.fields.get(index));
+            if old != Some(value) {
+                delta.fields.push(FieldDelta {
+                    index: index as u32,
+                    value: value.clone(),
+                });
+            }
+        }
+
+        let changed = baseline.is_none()
+            || delta.position.is_some()
+            || delta.rotation.is_some()
+            || delta.scale.is_some()
+            || !delta.fields.is_empty()
+            || baseline.is_some_and(|baseline| {
+                baseline.fields.len() != state.fields.len()
+                    || baseline.transform.is_some() != state.transform.is_some()
+            });
+
+        changed.then_some(delta)
+    }
+
+    fn apply(self, baseline: Option<&EntityState>) -> EntityState {
+        let mut state = baseline.cloned().unwrap_or_default();
+
+        if self.full_transform
+            || self.position.is_some()
+            || self.rotation.is_some()
+            || self.scale.is_some()
+        {
+            let transform = state.transform.get_or_insert_with(Default::default);
+            if let Some(position) = self.position {
+                transform.position = position;
+            }
+            if let Some(rotation) = self.rotation {
+                transform.rotation = rotation;
+            }
+            if let Some(scale) = self.scale {
+                transform.scale = scale;
+            }
+        } else if baseline.map_or(true, |baseline| baseline.transform.is_none()) {
+            state.transform = None;
+        }
+
+        state.fields.resize(self.field_count as usize, None);
+        for field in self.fields {
+            if let Some(dest) = state.fields.get_mut(field.index as usize) {
+                *dest = field.value;
+            }
+        }
+
+        state
+    }
+}
+
+/// Delta-compressed representation of a snapshot, that is sent over the network. It contains only the data
+/// that was changed since the baseline snapshot (the last snapshot acknowledged by a client). If there's no
+/// baseline, it contains full state.
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+pub(super) struct SnapshotDelta {
+    pub tick: u64,
+    pub baseline: Option<u64>,
+    pub time: f64,
+    entities: Vec<EntityDelta>,
+    removed: Vec<SceneNodeId>,
+}
+
+impl SnapshotDelta {
+    pub fn new(baseline: Option<&Snapshot>, snapshot: &Snapshot) -> Self {
+        let mut entities = Vec::new();
+        for (id, state) in snapshot.entities.iter() {
+            let old = baseline.and_then(|baseline| baseline.entities.get(id));
+            if let Some(delta) = EntityDelta::new(*id, old, state) {
+                entities.push(delta);
+            }
+        }
+
+        let removed = baseline
+            .map(|baseline| {
+                baseline
+                    .entities
+                    .keys()
+                    .filter(|id| !snapshot.entities.contains_key(id))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            tick: snapshot.tick,
+            baseline: baseline.map(|baseline| baseline.tick),
+            time: snapshot.time,
+            entities,
+            removed,
+        }
+    }
+
+    /// Reconstructs full snapshot using the baseline. The baseline must be the snapshot with the tick
+    /// stored in the delta.
+    pub fn apply(self, baseline: Option<&Snapshot>) -> Snapshot {
+        let mut entities = baseline
+            .map(|baseline| baseline.entities.clone())
+            .unwrap_or_default();
+
+        for id in self.removed.iter() {
+            entities.remove(id);
+        }
+
+        for delta in self.entities {
+            let id = delta.id;
+            let state = delta.apply(entities.get(&id));
+            entities.insert(id, state);
+        }
+
+        Snapshot {
+            tick: self.tick,
+            time: self.time,
+            entities,
+        }
+    }
+
+    pub fn encode(mut self) -> Result<Vec<u8>, VisitError> {
+        let mut visitor = Visitor::new();
+        self.visit("Snapshot", &mut visitor)?;
+        visitor.save_binary_to_vec()
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::load_from_memory(data)?;
+        let mut delta = Self::default();
+        delta.visit("Snapshot", &mut visitor)?;
+        Ok(delta)
+    }
+}
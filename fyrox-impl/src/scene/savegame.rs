@@ -0,0 +1,522 @@
+//! Savegame layer captures the state of persistent scene nodes (see [`crate::scene::base::Base::is_persistent`])
+//! and their scripts into a versioned save format. See [`SaveGame`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        io::{self, FileLoadError},
+        log::Log,
+        pool::Handle,
+        uuid::Uuid,
+        visitor::{prelude::*, PodVecView},
+    },
+    scene::{base::SceneNodeId, graph::Graph, node::Node},
+};
+use fxhash::FxHashMap;
+use fyrox_graph::SceneGraph;
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+/// An error that may occur during saving or loading a savegame.
+#[derive(Debug)]
+pub enum SaveGameError {
+    /// Serialization error.
+    Visit(VisitError),
+    /// File system error.
+    Io(std::io::Error),
+    /// Unable to read a save file.
+    FileLoad(FileLoadError),
+    /// The save was made by a newer version of the game.
+    UnsupportedVersion {
+        /// Version of the save.
+        version: u32,
+        /// Current version of the save format.
+        current: u32,
+    },
+    /// There's no migration from the given version to the next one.
+    MissingMigration(u32),
+    /// A migration function has failed.
+    Migration {
+        /// Version the migration was converting from.
+        version: u32,
+        /// Error message.
+        message: String,
+    },
+}
+
+impl Display for SaveGameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveGameError::Visit(err) => write!(f, "Serialization error: {err}"),
+            SaveGameError::Io(err) => write!(f, "Io error: {err}"),
+            SaveGameError::FileLoad(err) => write!(f, "Unable to load a save file: {err:?}"),
+            SaveGameError::UnsupportedVersion { version, current } => write!(
+                f,
+                "Save version {version} is newer than the current version {current}"
+            ),
+            SaveGameError::MissingMigration(version) => {
+                write!(f, "There's no migration from version {version}")
+            }
+            SaveGameError::Migration { version, message } => {
+                write!(f, "Migration from version {version} failed: {message}")
+            }
+        }
+    }
+}
+
+impl From<VisitError> for SaveGameError {
+    fn from(err: VisitError) -> Self {
+        Self::Visit(err)
+    }
+}
+
+impl From<std::io::Error> for SaveGameError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<FileLoadError> for SaveGameError {
+    fn from(err: FileLoadError) -> Self {
+        Self::FileLoad(err)
+    }
+}
+
+/// Serialized state of a script.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SavedScript {
+    /// Type uuid of the script.
+    pub type_uuid: Uuid,
+    /// Serialized script data.
+    pub data: Vec<u8>,
+}
+
+impl Visit for SavedScript {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+        self.type_uuid.visit("TypeUuid", &mut region)?;
+        PodVecView::from_pod_vec(&mut self.data).visit("Data", &mut region)?;
+        Ok(())
+    }
+}
+
+impl SavedScript {
+    fn capture<T: Visit + ?Sized>(object: &mut T, type_uuid: Uuid) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::new();
+        object.visit("Data", &mut visitor)?;
+        Ok(Self {
+            type_uuid,
+            data: visitor.save_binary_to_vec()?,
+        })
+    }
+
+    /// Reads the serialized data into the given object. It could be used in migrations to read the script
+    /// data into a type that mirrors an older version of the script.
+    pub fn read_into<T: Visit + ?Sized>(&self, object: &mut T) -> Result<(), VisitError> {
+        let mut visitor = Visitor::load_from_memory(&self.data)?;
+        object.visit("Data", &mut visitor)
+    }
+
+    /// Replaces the serialized data with the data of the given object. It could be used in migrations to
+    /// write the script data converted to a newer version of the script.
+    pub fn write_from<T: Visit + ?Sized>(&mut self, object: &mut T) -> Result<(), VisitError> {
+        *self = Self::capture(object, self.type_uuid)?;
+        Ok(())
+    }
+}
+
+/// Saved state of a single scene node.
+#[derive(Default, Debug, Clone, PartialEq, Visit)]
+pub struct SavedNode {
+    /// Instance id of the node, it is used to find the node when the save is applied.
+    pub id: SceneNodeId,
+    /// Name of the node. It is not used when the save is applied, but it could be useful in migrations.
+    pub name: String,
+    /// Local position of the node.
+    pub position: Vector3<f32>,
+    /// Local rotation of the node.
+    pub rotation: UnitQuaternion<f32>,
+    /// Local scale of the node.
+    pub scale: Vector3<f32>,
+    /// Visibility of the node.
+    pub visibility: bool,
+    /// Whether the node is enabled or not.
+    pub enabled: bool,
+    /// Saved scripts of the node, in the same order as the scripts of the node.
+    pub scripts: Vec<SavedScript>,
+}
+
+/// A savegame is a snapshot of the state of every persistent node of a scene (nodes with
+/// [`crate::scene::base::Base::is_persistent`] flag set). It stores local transform, visibility, enabled
+/// state and scripts of such nodes. Script fields could be excluded from a save using `#[visit(skip)]`
+/// attribute.
+///
+/// Save is applied on top of a freshly loaded scene, nodes are matched by their instance ids. Nodes that
+/// were spawned at runtime must be re-created by the game itself (with the same instance ids) before the
+/// save is applied.
+///
+/// Every save has a version, the game should increase it every time when the save data changes
+/// incompatibly (for example, a script field was renamed). Older saves are converted to the current version
+/// using a chain of migrations, see [`SaveGameMigrations`].
+///
+/// ```rust,no_run
+/// # use fyrox_impl::scene::{graph::Graph, savegame::{SaveGame, SaveGameMigrations}};
+/// const VERSION: u32 = 2;
+///
+/// fn migrations() -> SaveGameMigrations {
+///     SaveGameMigrations::new(VERSION).with_migration(1, |save| {
+///         // Player was renamed in version 2.
+///         for node in save.nodes.iter_mut() {
+///             if node.name == "Hero" {
+///                 node.name = "Player".to_string();
+///             }
+///         }
+///         Ok(())
+///     })
+/// }
+///
+/// async fn save(graph: &Graph) {
+///     let save = SaveGame::capture(graph, VERSION).unwrap();
+///     save.save("save1.bin").await.unwrap();
+/// }
+///
+/// async fn load(graph: &mut Graph) {
+///     let save = SaveGame::load("save1.bin", &migrations()).await.unwrap();
+///     save.apply(graph).unwrap();
+/// }
+/// ```
+///
+/// `save` and `load` methods are async, use [`crate::plugin::PluginContext::task_pool`] to run them in the
+/// background without blocking the game loop.
+#[derive(Default, Debug, Clone, PartialEq, Visit)]
+pub struct SaveGame {
+    /// Version of the save.
+    pub version: u32,
+    /// Arbitrary metadata of the save, for example name of a level, play time, etc.
+    pub metadata: FxHashMap<String, String>,
+    /// Saved nodes.
+    pub nodes: Vec<SavedNode>,
+}
+
+impl SaveGame {
+    /// Captures the state of every persistent node of the graph.
+    pub fn capture(graph: &Graph, version: u32) -> Result<Self, VisitError> {
+        let mut nodes = Vec::new();
+        for node in graph.linear_iter() {
+            if !node.is_persistent() {
+                continue;
+            }
+
+            let transform = node.local_transform();
+            let mut saved = SavedNode {
+                id: node.instance_id(),
+                name: node.name_owned(),
+                position: **transform.position(),
+                rotation: **transform.rotation(),
+                scale: **transform.scale(),
+                visibility: node.visibility(),
+                enabled: node.is_enabled(),
+                scripts: Vec::new(),
+            };
+
+            for script in node.scripts() {
+                // Visitor requires mutable access even for writing, so visit a copy of the script.
+                let mut instance = script.clone_box();
+                saved
+                    .scripts
+                    .push(SavedScript::capture(&mut *instance, script.id())?);
+            }
+
+            nodes.push(saved);
+        }
+
+        Ok(Self {
+            version,
+            metadata: Default::default(),
+            nodes,
+        })
+    }
+
+    /// Applies the save to the graph. Nodes that cannot be found in the graph are skipped, scripts are
+    /// applied only if their types match. Returns an error if a script data is malformed.
+    pub fn apply(&self, graph: &mut Graph) -> Result<(), VisitError> {
+        let handles = graph
+            .pair_iter()
+            .map(|(handle, node)| (node.instance_id(), handle))
+            .collect::<FxHashMap<SceneNodeId, Handle<Node>>>();
+
+        for saved in self.nodes.iter() {
+            let Some(handle) = handles.get(&saved.id) else {
+                Log::warn(format!(
+                    "Unable to apply a saved state of {} node: there's no such node.",
+                    saved.name
+                ));
+                continue;
+            };
+
+            let node = &mut graph[*handle];
+            node.local_transform_mut()
+                .set_position(saved.position)
+                .set_rotation(saved.rotation)
+                .set_scale(saved.scale);
+            node.set_visibility(saved.visibility);
+            node.set_enabled(saved.enabled);
+
+            for (index, saved_script) in saved.scripts.iter().enumerate() {
+                match node.script_mut(index) {
+                    Some(script) if script.id() == saved_script.type_uuid => {
+                        saved_script.read_into(&mut **script)?;
+                    }
+                    _ => Log::warn(format!(
+                        "Unable to apply a saved state of script {index} of {} node: script type mismatch.",
+                        saved.name
+                    )),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Serializes the save into a binary blob.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, VisitError> {
+        let mut visitor = Visitor::new();
+        self.clone().visit("SaveGame", &mut visitor)?;
+        visitor.save_binary_to_vec()
+    }
+
+    /// Deserializes a save from the given binary blob and migrates it to the current version.
+    pub fn from_bytes(data: &[u8], migrations: &SaveGameMigrations) -> Result<Self, SaveGameError> {
+        let mut visitor = Visitor::load_from_memory(data)?;
+        let mut save = Self::default();
+        save.visit("SaveGame", &mut visitor)?;
+        migrations.migrate(&mut save)?;
+        Ok(save)
+    }
+
+    /// Writes the save to the given file.
+    pub async fn save(self, path: impl AsRef<Path>) -> Result<(), SaveGameError> {
+        let data = self.to_bytes()?;
+        let path = path.as_ref();
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Write to a temporary file first, so an existing save won't be corrupted if something goes wrong.
+        let temp_path = PathBuf::from(format!("{}.tmp", path.display()));
+        std::fs::write(&temp_path, data)?;
+        std::fs::rename(temp_path, path)?;
+        Ok(())
+    }
+
+    /// Loads a save from the given file and migrates it to the current version.
+    pub async fn load(
+        path: impl AsRef<Path>,
+        migrations: &SaveGameMigrations,
+    ) -> Result<Self, SaveGameError> {
+        let data = io::load_file(path).await?;
+        Self::from_bytes(&data, migrations)
+    }
+}
+
+/// A function that converts a save from some version to the next one.
+pub type SaveGameMigration = dyn Fn(&mut SaveGame) -> Result<(), String> + Send + Sync;
+
+/// A chain of migrations, that converts older saves to the current version.
+pub struct SaveGameMigrations {
+    current_version: u32,
+    migrations: BTreeMap<u32, Box<SaveGameMigration>>,
+}
+
+impl SaveGameMigrations {
+    /// Creates a new chain for the given current version of the save format.
+    pub fn new(current_version: u32) -> Self {
+        Self {
+            current_version,
+            migrations: Default::default(),
+        }
+    }
+
+    /// Returns the current version of the save format.
+    pub fn current_version(&self) -> u32 {
+        self.current_version
+    }
+
+    /// Registers a migration that converts a save of `from_version` to `from_version + 1`.
+    pub fn add_migration<F>(&mut self, from_version: u32, migration: F)
+    where
+        F: Fn(&mut SaveGame) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.migrations.insert(from_version, Box::new(migration));
+    }
+
+    /// Registers a migration that converts a save of `from_version` to `from_version + 1`.
+    pub fn with_migration<F>(mut self, from_version: u32, migration: F) -> Self
+    where
+        F: Fn(&mut SaveGame) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.add_migration(from_version, migration);
+        self
+    }
+
+    /// Converts the save to the current version, by applying migrations one after another.
+    pub fn migrate(&self, save: &mut SaveGame) -> Result<(), SaveGameError> {
+        if save.version > self.current_version {
+            return Err(SaveGameError::UnsupportedVersion {
+                version: save.version,
+                current: self.current_version,
+            });
+        }
+
+        while save.version < self.current_version {
+            let version = save.version;
+            let migration = self
+                .migrations
+                .get(&version)
+                .ok_or(SaveGameError::MissingMigration(version))?;
+            migration(save).map_err(|message| SaveGameError::Migration { version, message })?;
+            save.version = version + 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::Vector3, reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*,
+        },
+        scene::{
+            base::BaseBuilder,
+            graph::Graph,
+            pivot::PivotBuilder,
+            savegame::{SaveGame, SaveGameError, SaveGameMigrations},
+        },
+        script::ScriptTrait,
+    };
+    use fyrox_graph::SceneGraph;
+
+    #[derive(
+        Clone, Debug, Default, PartialEq, Reflect, Visit, TypeUuidProvider, ComponentProvider,
+    )]
+    #[type_uuid(id = "4d0a2bf5-6a3b-4f9e-8a45-0c8c3d2f6e11")]
+    struct Inventory {
+        gold: u32,
+    }
+
+    impl ScriptTrait for Inventory {}
+
+    #[derive(Default, Visit)]
+    struct InventoryV1 {
+        coins: u32,
+    }
+
+    fn make_graph(persistent: bool) -> Graph {
+        let mut graph = Graph::new();
+        PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Player")
+                .with_persistent(persistent)
+                .with_script(Inventory { gold: 0 }),
+        )
+        .build(&mut graph);
+        graph
+    }
+
+    #[test]
+    fn test_savegame() {
+        let mut graph = make_graph(true);
+        let player = graph.find_by_name_from_root("Player").unwrap().0;
+        graph[player]
+            .local_transform_mut()
+            .set_position(Vector3::new(1.0, 2.0, 3.0));
+        graph[player]
+            .try_get_script_mut::<Inventory>()
+            .unwrap()
+            .gold = 100;
+
+        let save = SaveGame::capture(&graph, 1).unwrap();
+        assert_eq!(save.nodes.len(), 1);
+        let data = save.to_bytes().unwrap();
+
+        // Simulate the scene reload.
+        let id = graph[player].instance_id;
+        let mut graph = make_graph(true);
+        let player = graph.find_by_name_from_root("Player").unwrap().0;
+        graph[player].instance_id = id;
+
+        let loaded = SaveGame::from_bytes(&data, &SaveGameMigrations::new(1)).unwrap();
+        assert_eq!(loaded, save);
+        loaded.apply(&mut graph).unwrap();
+        assert_eq!(
+            **graph[player].local_transform().position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+        assert_eq!(
+            graph[player].try_get_script::<Inventory>().unwrap().gold,
+            100
+        );
+
+        // Non-persistent nodes are not saved.
+        assert!(SaveGame::capture(&make_graph(false), 1)
+            .unwrap()
+            .nodes
+            .is_empty());
+    }
+
+    #[test]
+    fn test_savegame_migrations() {
+        let mut save = SaveGame {
+            version: 1,
+            ..Default::default()
+        };
+        let mut graph = make_graph(true);
+        let player = graph.find_by_name_from_root("Player").unwrap().0;
+        let mut saved = SaveGame::capture(&graph, 1).unwrap().nodes.remove(0);
+        // In version 1 the field was called `coins`.
+        saved.scripts[0]
+            .write_from(&mut InventoryV1 { coins: 42 })
+            .unwrap();
+        save.nodes.push(saved);
+        let data = save.to_bytes().unwrap();
+
+        let migrations = SaveGameMigrations::new(3)
+            .with_migration(1, |save| {
+                for node in save.nodes.iter_mut() {
+                    for script in node.scripts.iter_mut() {
+                        let mut old = InventoryV1::default();
+                        script.read_into(&mut old).map_err(|e| e.to_string())?;
+                        script
+                            .write_from(&mut Inventory { gold: old.coins })
+                            .map_err(|e| e.to_string())?;
+                    }
+                }
+                Ok(())
+            })
+            .with_migration(2, |_| Ok(()));
+
+        let loaded = SaveGame::from_bytes(&data, &migrations).unwrap();
+        assert_eq!(loaded.version, 3);
+        loaded.apply(&mut graph).unwrap();
+        assert_eq!(
+            graph[player].try_get_script::<Inventory>().unwrap().gold,
+            42
+        );
+
+        assert!(matches!(
+            SaveGame::from_bytes(&data, &SaveGameMigrations::new(2)),
+            Err(SaveGameError::MissingMigration(1))
+        ));
+        assert!(matches!(
+            SaveGame::from_bytes(&data, &SaveGameMigrations::new(0)),
+            Err(SaveGameError::UnsupportedVersion { .. })
+        ));
+    }
+}
@@ -0,0 +1,580 @@
+//! Scene snapshots are canonical text representations of the logical state of a scene graph. They
+//! are built using reflection, so every reflected property of every node is included, while
+//! runtime caches (which are hidden from reflection) are not. Snapshots are deterministic: nodes
+//! are listed in hierarchical order, hash maps are sorted by their keys, floating-point numbers are
+//! rounded, handles are replaced with paths of the nodes they point to. This makes snapshots
+//! suitable for regression tests, for example, to check that a command restores the exact state
+//! of a scene when it is reverted:
+//!
+//! ```rust
+//! # use fyrox_impl::{
+//! #     graph::BaseSceneGraph,
+//! #     scene::{base::BaseBuilder, graph::Graph, pivot::PivotBuilder, snapshot::SceneSnapshot},
+//! # };
+//! let mut graph = Graph::new();
+//! let before = SceneSnapshot::new(&graph);
+//!
+//! let pivot = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+//! assert_ne!(SceneSnapshot::new(&graph), before);
+//!
+//! graph.remove_node(pivot);
+//! SceneSnapshot::new(&graph).assert_eq(&before);
+//! ```
+//!
+//! A snapshot consists of lines in `path = value` format, each path starts with a path of a node
+//! (names of the node and its ancestors) followed by a path of a property. Differences between two
+//! snapshots could be printed using [`SceneSnapshot::diff`].
+
+use crate::{
+    asset::untyped::UntypedResource,
+    core::{
+        algebra::{UnitQuaternion, Vector2, Vector3, Vector4},
+        color::Color,
+        math::Rect,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::Uuid,
+        ImmutableString,
+    },
+    fxhash::{FxHashMap, FxHashSet},
+    scene::{
+        base::Mobility,
+        graph::{physics::CoefficientCombineRule, Graph},
+        mesh::{BatchingMode, RenderPath},
+        node::Node,
+        rigidbody::RigidBodyType,
+        sound::Status,
+    },
+};
+use std::{
+    any::{Any, TypeId},
+    fmt::{Debug, Display, Formatter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Maximum nesting of properties. Deeper properties are not included in snapshots, it prevents
+/// infinite recursion on cyclic data.
+const MAX_DEPTH: usize = 32;
+
+type ValueFormatter = Box<dyn Fn(&dyn Any) -> Option<String>>;
+
+/// Settings of snapshots.
+pub struct SnapshotSettings {
+    /// Amount of decimal places of floating-point numbers. Default is 4.
+    pub precision: usize,
+    /// Names of properties, that will not be included in snapshots.
+    pub ignored_properties: FxHashSet<String>,
+    formatters: FxHashMap<TypeId, ValueFormatter>,
+}
+
+impl Default for SnapshotSettings {
+    fn default() -> Self {
+        Self {
+            precision: 4,
+            ignored_properties: Default::default(),
+            formatters: Default::default(),
+        }
+        .with_debug_formatter::<Mobility>()
+        .with_debug_formatter::<RenderPath>()
+        .with_debug_formatter::<BatchingMode>()
+        .with_debug_formatter::<RigidBodyType>()
+        .with_debug_formatter::<CoefficientCombineRule>()
+        .with_debug_formatter::<Status>()
+    }
+}
+
+impl SnapshotSettings {
+    /// Excludes properties with the given name from snapshots.
+    pub fn with_ignored_property(mut self, name: &str) -> Self {
+        self.ignored_properties.insert(name.to_string());
+        self
+    }
+
+    /// Sets a formatter for values of the given type. It is useful for types, that do not expose
+    /// their state via reflection, such as enumerations without fields.
+    pub fn with_formatter<T: 'static>(mut self, formatter: fn(&T) -> String) -> Self {
+        self.formatters.insert(
+            TypeId::of::<T>(),
+            Box::new(move |any| any.downcast_ref::<T>().map(formatter)),
+        );
+        self
+    }
+
+    /// Formats values of the given type using their [`Debug`] implementation.
+    pub fn with_debug_formatter<T: Debug + 'static>(self) -> Self {
+        self.with_formatter::<T>(|value| format!("{value:?}"))
+    }
+}
+
+/// See [module docs](self).
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SceneSnapshot {
+    entries: Vec<(String, String)>,
+}
+
+impl SceneSnapshot {
+    /// Creates a snapshot of the given graph using the default settings.
+    pub fn new(graph: &Graph) -> Self {
+        Self::with_settings(graph, &SnapshotSettings::default())
+    }
+
+    /// Creates a snapshot of the given graph.
+    pub fn with_settings(graph: &Graph, settings: &SnapshotSettings) -> Self {
+        let paths = node_paths(graph);
+        let mut writer = SnapshotWriter {
+            settings,
+            paths: &paths,
+            entries: Vec::new(),
+        };
+
+        let mut stack = vec![graph.get_root()];
+        while let Some(handle) = stack.pop() {
+            let node = &graph[handle];
+            let path = &paths[&handle];
+            // Type of the node is written first, so replacement of a node with a node of other
+            // type is visible in diffs.
+            let type_name = node.type_name();
+            writer.push(
+                path,
+                type_name
+                    .rsplit("::")
+                    .next()
+                    .unwrap_or(type_name)
+                    .to_string(),
+            );
+            writer.write(path, node as &dyn Reflect, 0);
+            stack.extend(node.children().iter().rev());
+        }
+
+        Self {
+            entries: writer.entries,
+        }
+    }
+
+    /// Returns `(path, value)` pairs of the snapshot.
+    pub fn entries(&self) -> &[(String, String)] {
+        &self.entries
+    }
+
+    /// Returns a value of the property with the given path.
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(p, _)| p == path)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Parses a snapshot from its text representation (see [`Display`] implementation).
+    pub fn from_text(text: &str) -> Self {
+        Self {
+            entries: text
+                .lines()
+                .filter_map(|line| line.split_once(" = "))
+                .map(|(path, value)| (path.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Returns a human-readable list of differences between the snapshots, or `None` if the
+    /// snapshots are equal. Removed lines start with `-`, added lines start with `+`.
+    pub fn diff(&self, other: &SceneSnapshot) -> Option<String> {
+        let other_values = other
+            .entries
+            .iter()
+            .map(|(p, v)| (p.as_str(), v.as_str()))
+            .collect::<FxHashMap<_, _>>();
+        let self_values = self
+            .entries
+            .iter()
+            .map(|(p, v)| (p.as_str(), v.as_str()))
+            .collect::<FxHashMap<_, _>>();
+
+        let mut diff = String::new();
+        for (path, value) in self.entries.iter() {
+            match other_values.get(path.as_str()) {
+                Some(other_value) if *other_value == value => (),
+                Some(other_value) => {
+                    let _ = writeln!(diff, "- {path} = {value}\n+ {path} = {other_value}");
+                }
+                None => {
+                    let _ = writeln!(diff, "- {path} = {value}");
+                }
+            }
+        }
+        for (path, value) in other.entries.iter() {
+            if !self_values.contains_key(path.as_str()) {
+                let _ = writeln!(diff, "+ {path} = {value}");
+            }
+        }
+
+        if diff.is_empty() {
+            None
+        } else {
+            Some(diff)
+        }
+    }
+
+    /// Panics with a list of differences, if the snapshots are not equal.
+    #[track_caller]
+    pub fn assert_eq(&self, expected: &SceneSnapshot) {
+        if let Some(diff) = expected.diff(self) {
+            panic!("Snapshots are not equal:\n{diff}");
+        }
+    }
+
+    /// Compares the snapshot with the one stored in the given file and panics with a list of
+    /// differences, if they're not equal. The file is created if it does not exist, or overwritten
+    /// if `FYROX_UPDATE_SNAPSHOTS` environment variable is set.
+    #[track_caller]
+    pub fn assert_matches_file(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        if !path.exists() || std::env::var_os("FYROX_UPDATE_SNAPSHOTS").is_some() {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).unwrap();
+            }
+            std::fs::write(path, self.to_string()).unwrap();
+            return;
+        }
+
+        let expected = SceneSnapshot::from_text(&std::fs::read_to_string(path).unwrap());
+        if let Some(diff) = expected.diff(self) {
+            panic!(
+                "Snapshot does not match {}:\n{diff}\nSet FYROX_UPDATE_SNAPSHOTS environment \
+                variable to update the file.",
+                path.display()
+            );
+        }
+    }
+}
+
+impl Display for SceneSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (path, value) in self.entries.iter() {
+            writeln!(f, "{path} = {value}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for SceneSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// Creates unique paths of every node of the graph. Siblings with the same name are distinguished
+/// by their index (`Name#1`, `Name#2`, etc.).
+fn node_paths(graph: &Graph) -> FxHashMap<Handle<Node>, String> {
+    let mut paths = FxHashMap::default();
+    let root = graph.get_root();
+    paths.insert(root, graph[root].name().to_string());
+
+    let mut stack = vec![root];
+    while let Some(handle) = stack.pop() {
+        let mut name_counters = FxHashMap::<&str, usize>::default();
+        let parent_path = paths[&handle].clone();
+        for &child in graph[handle].children() {
+            let name = graph[child].name();
+            let counter = name_counters.entry(name).or_default();
+            let path = if *counter == 0 {
+                format!("{parent_path}/{name}")
+            } else {
+                format!("{parent_path}/{name}#{counter}")
+            };
+            *counter += 1;
+            paths.insert(child, path);
+            stack.push(child);
+        }
+    }
+
+    paths
+}
+
+fn format_float(value: f64, precision: usize) -> String {
+    let text = format!("{value:.precision$}");
+    // Negative zero must not differ from zero.
+    if text
+        .trim_start_matches('-')
+        .chars()
+        .all(|c| c == '0' || c == '.')
+    {
+        text.trim_start_matches('-').to_string()
+    } else {
+        text
+    }
+}
+
+fn format_floats(values: &[f32], precision: usize) -> String {
+    let values = values
+        .iter()
+        .map(|v| format_float(*v as f64, precision))
+        .collect::<Vec<_>>();
+    format!("({})", values.join(", "))
+}
+
+fn format_debug<T: Debug + 'static>(any: &dyn Any) -> Option<String> {
+    any.downcast_ref::<T>().map(|v| format!("{v:?}"))
+}
+
+fn format_integers<T: Display + 'static>(values: &[T]) -> String {
+    let values = values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+    format!("({})", values.join(", "))
+}
+
+struct SnapshotWriter<'a> {
+    settings: &'a SnapshotSettings,
+    paths: &'a FxHashMap<Handle<Node>, String>,
+    entries: Vec<(String, String)>,
+}
+
+impl SnapshotWriter<'_> {
+    fn format_leaf(&self, any: &dyn Any) -> Option<String> {
+        let precision = self.settings.precision;
+
+        if let Some(formatter) = self.settings.formatters.get(&any.type_id()) {
+            return formatter(any);
+        }
+        if let Some(v) = any.downcast_ref::<f32>() {
+            return Some(format_float(*v as f64, precision));
+        }
+        if let Some(v) = any.downcast_ref::<f64>() {
+            return Some(format_float(*v, precision));
+        }
+        if let Some(v) = any.downcast_ref::<Vector2<f32>>() {
+            return Some(format_floats(v.as_slice(), precision));
+        }
+        if let Some(v) = any.downcast_ref::<Vector3<f32>>() {
+            return Some(format_floats(v.as_slice(), precision));
+        }
+        if let Some(v) = any.downcast_ref::<Vector4<f32>>() {
+            return Some(format_floats(v.as_slice(), precision));
+        }
+        if let Some(v) = any.downcast_ref::<Rect<f32>>() {
+            return Some(format!(
+                "{} {}",
+                format_floats(v.position.as_slice(), precision),
+                format_floats(v.size.as_slice(), precision)
+            ));
+        }
+        if let Some(v) = any.downcast_ref::<Vector2<i32>>() {
+            return Some(format_integers(v.as_slice()));
+        }
+        if let Some(v) = any.downcast_ref::<Vector3<i32>>() {
+            return Some(format_integers(v.as_slice()));
+        }
+        if let Some(v) = any.downcast_ref::<Vector2<u32>>() {
+            return Some(format_integers(v.as_slice()));
+        }
+        if let Some(v) = any.downcast_ref::<UnitQuaternion<f32>>() {
+            // `q` and `-q` represent the same rotation.
+            let coords = if v.w < 0.0 { -v.coords } else { v.coords };
+            return Some(format_floats(coords.as_slice(), precision));
+        }
+        if let Some(handle) = any.downcast_ref::<Handle<Node>>() {
+            return Some(if handle.is_none() {
+                "None".to_string()
+            } else if let Some(path) = self.paths.get(handle) {
+                format!("Node({path})")
+            } else {
+                format!("Invalid({handle})")
+            });
+        }
+        if let Some(resource) = any.downcast_ref::<UntypedResource>() {
+            return Some(format!("Resource({})", resource.kind()));
+        }
+
+        format_debug::<bool>(any)
+            .or_else(|| format_debug::<char>(any))
+            .or_else(|| format_debug::<u8>(any))
+            .or_else(|| format_debug::<u16>(any))
+            .or_else(|| format_debug::<u32>(any))
+            .or_else(|| format_debug::<u64>(any))
+            .or_else(|| format_debug::<usize>(any))
+            .or_else(|| format_debug::<i8>(any))
+            .or_else(|| format_debug::<i16>(any))
+            .or_else(|| format_debug::<i32>(any))
+            .or_else(|| format_debug::<i64>(any))
+            .or_else(|| format_debug::<isize>(any))
+            .or_else(|| format_debug::<String>(any))
+            .or_else(|| format_debug::<ImmutableString>(any))
+            .or_else(|| format_debug::<PathBuf>(any))
+            .or_else(|| format_debug::<Duration>(any))
+            .or_else(|| format_debug::<Uuid>(any))
+            .or_else(|| format_debug::<Color>(any))
+    }
+
+    fn push(&mut self, path: &str, value: String) {
+        self.entries.push((path.to_string(), value));
+    }
+
+    fn write(&mut self, path: &str, value: &dyn Reflect, depth: usize) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+
+        let mut leaf = None;
+        value.as_any(&mut |any| leaf = self.format_leaf(any));
+        if let Some(leaf) = leaf {
+            self.push(path, leaf);
+            return;
+        }
+
+        let mut handled = false;
+        value.as_inheritable_variable(&mut |variable| {
+            if let Some(variable) = variable {
+                self.write(path, variable.inner_value_ref(), depth + 1);
+                handled = true;
+            }
+        });
+        if handled {
+            return;
+        }
+
+        value.as_array(&mut |array| {
+            if let Some(array) = array {
+                self.push(path, format!("[{}]", array.reflect_len()));
+                for i in 0..array.reflect_len() {
+                    if let Some(item) = array.reflect_index(i) {
+                        self.write(&format!("{path}[{i}]"), item, depth + 1);
+                    }
+                }
+                handled = true;
+            }
+        });
+        if handled {
+            return;
+        }
+
+        value.as_hash_map(&mut |map| {
+            if let Some(map) = map {
+                let mut items = (0..map.reflect_len())
+                    .filter_map(|i| map.reflect_get_at(i))
+                    .map(|(key, value)| {
+                        let mut key_text = None;
+                        key.as_any(&mut |any| key_text = self.format_leaf(any));
+                        (
+                            key_text.unwrap_or_else(|| key.type_name().to_string()),
+                            value,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                items.sort_by(|a, b| a.0.cmp(&b.0));
+                self.push(path, format!("{{{}}}", items.len()));
+                for (key, value) in items {
+                    self.write(&format!("{path}{{{key}}}"), value, depth + 1);
+                }
+                handled = true;
+            }
+        });
+        if handled {
+            return;
+        }
+
+        let mut has_fields = false;
+        value.fields_info(&mut |fields| {
+            for field in fields {
+                has_fields = true;
+                if self.settings.ignored_properties.contains(field.name) {
+                    continue;
+                }
+                let field_path = if path.is_empty() {
+                    field.name.to_string()
+                } else {
+                    format!("{path}.{}", field.name)
+                };
+                self.write(&field_path, field.reflect_value, depth + 1);
+            }
+        });
+
+        if !has_fields {
+            // There's no way to get a state of an opaque value (for example, an enumeration
+            // without fields), so only its type is written. Use `SnapshotSettings::with_formatter`
+            // to format such values.
+            let type_name = value.type_name();
+            if type_name.starts_with("core::option::Option<") {
+                self.push(path, "None".to_string());
+            } else {
+                self.push(path, format!("<{type_name}>"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        graph::SceneGraph,
+        scene::{
+            base::BaseBuilder, graph::Graph, pivot::PivotBuilder, snapshot::SceneSnapshot,
+            transform::TransformBuilder,
+        },
+    };
+
+    fn make_graph() -> Graph {
+        let mut graph = Graph::new();
+        let child = PivotBuilder::new(BaseBuilder::new().with_name("Child")).build(&mut graph);
+        PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name("Parent")
+                .with_children(&[child])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(1.0, -0.0, 2.5))
+                        .build(),
+                ),
+        )
+        .build(&mut graph);
+        PivotBuilder::new(BaseBuilder::new().with_name("Parent")).build(&mut graph);
+        graph
+    }
+
+    #[test]
+    fn test_snapshot_is_deterministic() {
+        let snapshot = SceneSnapshot::new(&make_graph());
+        assert_eq!(snapshot, SceneSnapshot::new(&make_graph()));
+        assert_eq!(
+            SceneSnapshot::from_text(&snapshot.to_string()),
+            snapshot,
+            "text representation must be lossless"
+        );
+
+        let text = snapshot.to_string();
+        assert!(text.contains("__ROOT__/Parent/Child = Pivot"));
+        assert!(text.contains("__ROOT__/Parent/Child.base.name = \"Child\""));
+        assert!(text.contains("__ROOT__/Parent#1.base.name = \"Parent\""));
+        assert_eq!(
+            snapshot.get("__ROOT__/Parent.base.local_transform.local_position"),
+            Some("(1.0000, 0.0000, 2.5000)")
+        );
+    }
+
+    #[test]
+    fn test_snapshot_diff() {
+        let mut graph = make_graph();
+        let before = SceneSnapshot::new(&graph);
+        assert!(before.diff(&before).is_none());
+
+        let (child, _) = graph.find_by_name_from_root("Child").unwrap();
+        graph[child]
+            .local_transform_mut()
+            .set_position(Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(
+            before.diff(&SceneSnapshot::new(&graph)).unwrap(),
+            "- __ROOT__/Parent/Child.base.local_transform.local_position = (0.0000, 0.0000, 0.0000)\n\
+             + __ROOT__/Parent/Child.base.local_transform.local_position = (0.0000, 1.0000, 0.0000)\n"
+        );
+
+        graph[child].set_name("Renamed");
+        let diff = before.diff(&SceneSnapshot::new(&graph)).unwrap();
+        assert!(diff.contains("- __ROOT__/Parent/Child.base.name = \"Child\""));
+        assert!(diff.contains("+ __ROOT__/Parent/Renamed.base.name = \"Renamed\""));
+
+        graph[child].set_name("Child");
+        graph[child]
+            .local_transform_mut()
+            .set_position(Vector3::default());
+        SceneSnapshot::new(&graph).assert_eq(&before);
+    }
+}
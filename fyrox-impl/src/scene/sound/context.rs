@@ -2,26 +2,75 @@
 
 use crate::{
     core::{
+        algebra::{Point3, Vector3},
         log::{Log, MessageKind},
         pool::Handle,
+        reflect::prelude::*,
+        scope_profile,
         visitor::prelude::*,
     },
-    scene::{node::Node, sound::Sound},
+    scene::{
+        collider::{Collider, InteractionGroups},
+        graph::{
+            physics::{Intersection, PhysicsWorld, RayCastOptions},
+            NodePool,
+        },
+        node::Node,
+        sound::Sound,
+    },
 };
 use fxhash::FxHashSet;
 use fyrox_sound::{
     bus::AudioBusGraph,
-    context::DistanceModel,
+    context::{DistanceModel, SAMPLE_RATE},
     renderer::Renderer,
     source::{SoundSource, SoundSourceBuilder, Status},
 };
 use std::{sync::MutexGuard, time::Duration};
 
+/// Defines how the sounds with enabled occlusion (see [`Sound::set_occlusion_enabled`]) are affected
+/// by the obstacles between them and the listener.
+#[derive(Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct SoundOcclusionSettings {
+    /// Gain of a fully occluded sound.
+    pub occluded_gain: f32,
+    /// Cutoff frequency (in Hertz) of the lowpass filter applied to a fully occluded sound.
+    pub occluded_cutoff_frequency_hz: f32,
+    /// Speed of transition between occlusion states. The higher the value, the faster a sound reacts
+    /// to the changes in the environment.
+    pub transition_speed: f32,
+    /// Collision groups of colliders that are taken into account when calculating the occlusion.
+    pub groups: InteractionGroups,
+}
+
+impl Default for SoundOcclusionSettings {
+    fn default() -> Self {
+        Self {
+            occluded_gain: 0.1,
+            occluded_cutoff_frequency_hz: 800.0,
+            transition_speed: 8.0,
+            groups: Default::default(),
+        }
+    }
+}
+
 /// Sound context.
 #[derive(Debug, Visit)]
 pub struct SoundContext {
     #[visit(optional)]
     pub(crate) native: fyrox_sound::context::SoundContext,
+
+    /// Occlusion settings, that will be used for every sound with enabled occlusion.
+    #[visit(optional)]
+    pub occlusion_settings: SoundOcclusionSettings,
+
+    #[visit(skip)]
+    query_buffer: Vec<Intersection>,
+
+    /// Handles of sound nodes with native sound sources, used to avoid scanning the entire graph
+    /// when updating the occlusion.
+    #[visit(skip)]
+    sound_nodes: FxHashSet<Handle<Node>>,
 }
 
 /// Proxy for guarded access to the sound context.
@@ -60,6 +109,11 @@ impl<'a> SoundContextGuard<'a> {
         self.guard.distance_model()
     }
 
+    /// Returns current position of the listener in world coordinates.
+    pub fn listener_position(&self) -> Vector3<f32> {
+        self.guard.listener().position()
+    }
+
     /// Normalizes given frequency using context's sampling rate. Normalized frequency then can be used
     /// to create filters.
     pub fn normalize_frequency(&self, f: f32) -> f32 {
@@ -104,7 +158,12 @@ impl Default for SoundContext {
         // There's no need to serialize native sources, because they'll be re-created automatically.
         state.serialization_options.skip_sources = true;
         drop(state);
-        Self { native }
+        Self {
+            native,
+            occlusion_settings: Default::default(),
+            query_buffer: Default::default(),
+            sound_nodes: Default::default(),
+        }
     }
 }
 
@@ -117,6 +176,9 @@ impl SoundContext {
     pub fn deep_clone(&self) -> Self {
         Self {
             native: self.native.deep_clone(),
+            occlusion_settings: self.occlusion_settings.clone(),
+            query_buffer: Default::default(),
+            sound_nodes: Default::default(),
         }
     }
 
@@ -145,6 +207,105 @@ impl SoundContext {
         }
     }
 
+    pub(crate) fn shift_origin(&mut self, offset: &Vector3<f32>) {
+        let mut state = self.native.state();
+        for source in state.sources_mut().iter_mut() {
+            let position = source.position() - offset;
+            source.set_position(position);
+        }
+        let listener = state.listener_mut();
+        let position = listener.position() - offset;
+        listener.set_position(position);
+    }
+
+    pub(crate) fn update_occlusion(&mut self, nodes: &NodePool, physics: &PhysicsWorld, dt: f32) {
+        scope_profile!();
+
+        let mut state = self.native.state();
+        let listener_position = Point3::from(state.listener().position());
+        let settings = &self.occlusion_settings;
+        let nyquist_frequency = (SAMPLE_RATE / 2) as f32;
+        let occluded_cutoff_frequency_hz = settings
+            .occluded_cutoff_frequency_hz
+            .clamp(1.0, nyquist_frequency);
+        let k = (settings.transition_speed * dt).clamp(0.0, 1.0);
+
+        // Sound nodes could be removed from the graph without notifying the context.
+        self.sound_nodes.retain(|handle| {
+            nodes
+                .try_borrow(*handle)
+                .is_some_and(|n| n.cast::<Sound>().is_some())
+        });
+
+        for sound in self
+            .sound_nodes
+            .iter()
+            .filter_map(|handle| nodes.try_borrow(*handle).and_then(|n| n.cast::<Sound>()))
+        {
+            let current_occlusion = sound.occlusion.get();
+
+            let position = sound.global_position();
+            let ray_direction = position - listener_position.coords;
+            let max_len = ray_direction.norm();
+
+            // There's nothing in between the listener and a sound that is at the listener's position.
+            let target_occlusion = if sound.is_occlusion_enabled() && max_len > f32::EPSILON {
+                physics.cast_ray(
+                    RayCastOptions {
+                        ray_origin: listener_position,
+                        ray_direction,
+                        max_len,
+                        groups: settings.groups,
+                        sort_results: false,
+                    },
+                    &mut self.query_buffer,
+                );
+
+                let mut transmission = 1.0;
+                for intersection in self.query_buffer.iter() {
+                    // Ignore colliders that contain the listener (for example when the listener is
+                    // attached to a camera inside of a character's capsule) or touch the sound.
+                    if intersection.toi <= f32::EPSILON.sqrt()
+                        || intersection.toi >= max_len - f32::EPSILON.sqrt()
+                    {
+                        continue;
+                    }
+
+                    if let Some(collider) = nodes
+                        .try_borrow(intersection.collider)
+                        .and_then(|n| n.cast::<Collider>())
+                    {
+                        transmission *= 1.0 - collider.sound_absorption();
+                    }
+                }
+
+                1.0 - transmission
+            } else {
+                0.0
+            };
+
+            if current_occlusion == target_occlusion && target_occlusion == 0.0 {
+                continue;
+            }
+
+            let occlusion = if (target_occlusion - current_occlusion).abs() < 0.001 {
+                target_occlusion
+            } else {
+                current_occlusion + (target_occlusion - current_occlusion) * k
+            };
+            sound.occlusion.set(occlusion);
+
+            if let Some(source) = state.try_get_source_mut(sound.native.get()) {
+                let gain = 1.0 - occlusion * (1.0 - settings.occluded_gain.clamp(0.0, 1.0));
+                // Interpolate the cutoff frequency in logarithmic scale, which matches the human
+                // perception of frequencies.
+                let cutoff_frequency_hz = occluded_cutoff_frequency_hz
+                    * (nyquist_frequency / occluded_cutoff_frequency_hz).powf(1.0 - occlusion);
+                source.set_occlusion(gain, cutoff_frequency_hz);
+            }
+        }
+    }
+
     pub(crate) fn sync_with_sound(&self, sound: &mut Sound) {
         if let Some(source) = self.native.state().try_get_source_mut(sound.native.get()) {
             // Sync back.
@@ -166,9 +327,12 @@ impl SoundContext {
         {
             self.remove_sound(sound.native.get(), &sound.name);
             sound.native.set(Default::default());
+            self.sound_nodes.remove(&sound_handle);
             return;
         }
 
+        self.sound_nodes.insert(sound_handle);
+
         if sound.native.get().is_some() {
             let mut state = self.native.state();
             let source = state.source_mut(sound.native.get());
@@ -78,11 +78,11 @@ pub struct Sound {
     #[reflect(setter = "set_pitch")]
     pitch: InheritableVariable<f64>,
 
-    #[reflect(min_value = 0.0, step = 0.05)]
+    #[reflect(min_value = 0.0, step = 0.05, unit = "meters")]
     #[reflect(setter = "set_radius")]
     radius: InheritableVariable<f32>,
 
-    #[reflect(min_value = 0.0, step = 0.05)]
+    #[reflect(min_value = 0.0, step = 0.05, unit = "meters")]
     #[reflect(setter = "set_max_distance")]
     max_distance: InheritableVariable<f32>,
 
@@ -91,7 +91,7 @@ pub struct Sound {
     rolloff_factor: InheritableVariable<f32>,
 
     #[visit(optional)]
-    #[reflect(setter = "set_playback_time", min_value = 0.0)]
+    #[reflect(setter = "set_playback_time", min_value = 0.0, unit = "seconds")]
     playback_time: InheritableVariable<f32>,
 
     #[reflect(setter = "set_spatial_blend")]
@@ -103,6 +103,17 @@ pub struct Sound {
     )]
     audio_bus: InheritableVariable<String>,
 
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_occlusion_enabled",
+        description = "Whether the sound should be occluded by colliders between it and the listener or not."
+    )]
+    occlusion_enabled: InheritableVariable<bool>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub(crate) occlusion: Cell<f32>,
+
     #[reflect(hidden)]
     #[visit(skip)]
     pub(crate) native: Cell<Handle<SoundSource>>,
@@ -139,6 +150,8 @@ impl Default for Sound {
             playback_time: Default::default(),
             spatial_blend: InheritableVariable::new_modified(1.0),
             audio_bus: InheritableVariable::new_modified(AudioBusGraph::PRIMARY_BUS.to_string()),
+            occlusion_enabled: InheritableVariable::new_modified(false),
+            occlusion: Default::default(),
             native: Default::default(),
         }
     }
@@ -161,6 +174,8 @@ impl Clone for Sound {
             playback_time: self.playback_time.clone(),
             spatial_blend: self.spatial_blend.clone(),
             audio_bus: self.audio_bus.clone(),
+            occlusion_enabled: self.occlusion_enabled.clone(),
+            occlusion: self.occlusion.clone(),
             // Do not copy. The copy will have its own native representation.
             native: Default::default(),
         }
@@ -363,6 +378,30 @@ impl Sound {
     pub fn audio_bus(&self) -> &str {
         &self.audio_bus
     }
+
+    /// Enables or disables occlusion of the sound. Occluded sound is muffled and attenuated by the colliders
+    /// that are located between the sound and the listener. The amount of occlusion depends on the sound
+    /// absorption coefficient of each collider (see [`crate::scene::collider::Collider::set_sound_absorption`])
+    /// and on the occlusion settings of the sound context (see [`context::SoundOcclusionSettings`]).
+    ///
+    /// # Performance
+    ///
+    /// Occlusion requires a ray cast from the listener to the sound each frame, avoid enabling it for
+    /// large amounts of sounds.
+    pub fn set_occlusion_enabled(&mut self, enabled: bool) -> bool {
+        self.occlusion_enabled.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if the occlusion is enabled for the sound, `false` - otherwise.
+    pub fn is_occlusion_enabled(&self) -> bool {
+        *self.occlusion_enabled
+    }
+
+    /// Returns current amount of occlusion of the sound in `[0..1]` range, where 0.0 means that there is
+    /// no obstacles between the sound and the listener, and 1.0 means that the sound is fully occluded.
+    pub fn occlusion(&self) -> f32 {
+        self.occlusion.get()
+    }
 }
 
 impl NodeTrait for Sound {
@@ -452,6 +491,7 @@ pub struct SoundBuilder {
     playback_time: Duration,
     spatial_blend: f32,
     audio_bus: String,
+    occlusion_enabled: bool,
 }
 
 impl SoundBuilder {
@@ -472,6 +512,7 @@ impl SoundBuilder {
             spatial_blend: 1.0,
             playback_time: Default::default(),
             audio_bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            occlusion_enabled: false,
         }
     }
 
@@ -540,6 +581,11 @@ impl SoundBuilder {
         fn with_audio_bus(audio_bus: String)
     );
 
+    define_with!(
+        /// Enables or disables occlusion. See [`Sound::set_occlusion_enabled`] for more info.
+        fn with_occlusion_enabled(occlusion_enabled: bool)
+    );
+
     /// Creates a new [`Sound`] node.
     #[must_use]
     pub fn build_sound(self) -> Sound {
@@ -558,6 +604,8 @@ impl SoundBuilder {
             playback_time: self.playback_time.as_secs_f32().into(),
             spatial_blend: self.spatial_blend.into(),
             audio_bus: self.audio_bus.into(),
+            occlusion_enabled: self.occlusion_enabled.into(),
+            occlusion: Default::default(),
             native: Default::default(),
         }
     }
@@ -96,6 +96,12 @@ impl VertexTrait for SpriteVertex {
 /// Sprites are **not** depth-sorted so there could be some blending issues if multiple sprites are
 /// stacked one behind another.
 ///
+/// # Soft sprites
+///
+/// Sprites are flat, so they're clipped by opaque geometry they intersect with. The standard sprite
+/// material has `softFadeDistance` property (in world units, disabled by default), that fades out
+/// a sprite near intersections. It is useful for smoke, fog and light glows.
+///
 /// # Performance
 ///
 /// Sprites rendering uses batching to reduce amount of draw calls - it basically merges multiple
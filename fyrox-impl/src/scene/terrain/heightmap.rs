@@ -0,0 +1,281 @@
+//! Exchange of terrain data with external tools (World Machine, Gaea, etc.). Height maps could be
+//! read from and written to 16-bit PNG, raw 16-bit (`*.raw`, `*.r16`) and EXR files; splat maps
+//! are RGBA images, where each channel holds a blending mask of a terrain layer.
+//! See [`super::Terrain::import_height_map`] and [`super::Terrain::import_splat_map`].
+
+use image::{DynamicImage, ImageBuffer, ImageError, Luma, Rgb, Rgba};
+use std::{
+    fmt::{Display, Formatter},
+    path::Path,
+};
+
+/// All possible errors, that may occur during import or export of terrain images.
+#[derive(Debug)]
+pub enum TerrainImageError {
+    /// An i/o error has occurred.
+    Io(std::io::Error),
+    /// An image has unsupported format or it is corrupted.
+    Image(ImageError),
+    /// A raw height map must be a square image of 16-bit pixels, but the file has the given
+    /// size in bytes.
+    InvalidRawSize(usize),
+}
+
+impl Display for TerrainImageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TerrainImageError::Io(v) => write!(f, "An i/o error has occurred: {v}"),
+            TerrainImageError::Image(v) => write!(f, "An image error has occurred: {v}"),
+            TerrainImageError::InvalidRawSize(v) => write!(
+                f,
+                "Raw height map must be a square 16-bit image, but the file size is {v} bytes."
+            ),
+        }
+    }
+}
+
+impl From<std::io::Error> for TerrainImageError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<ImageError> for TerrainImageError {
+    fn from(value: ImageError) -> Self {
+        Self::Image(value)
+    }
+}
+
+fn is_raw(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("raw") || ext.eq_ignore_ascii_case("r16"))
+}
+
+fn is_exr(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("exr"))
+}
+
+/// Bilinear sampling of an image, `u` and `v` are in `[0; 1]` range and they're mapped on centers
+/// of edge pixels.
+fn sample<T, F>(width: u32, height: u32, pixels: &[T], u: f32, v: f32, mut fetch: F) -> f32
+where
+    F: FnMut(&T) -> f32,
+{
+    let x = u.clamp(0.0, 1.0) * width.saturating_sub(1) as f32;
+    let y = v.clamp(0.0, 1.0) * height.saturating_sub(1) as f32;
+    let x0 = x.floor() as usize;
+    let y0 = y.floor() as usize;
+    let x1 = (x0 + 1).min(width.saturating_sub(1) as usize);
+    let y1 = (y0 + 1).min(height.saturating_sub(1) as usize);
+    let tx = x - x0 as f32;
+    let ty = y - y0 as f32;
+    let mut at = |x: usize, y: usize| fetch(&pixels[y * width as usize + x]);
+    let top = at(x0, y0) * (1.0 - tx) + at(x1, y0) * tx;
+    let bottom = at(x0, y1) * (1.0 - tx) + at(x1, y1) * tx;
+    top * (1.0 - ty) + bottom * ty
+}
+
+/// Single-channel height map. Integer images are normalized to `[0; 1]` range, float images (EXR)
+/// keep their values as is. The first row of the image corresponds to the beginning of the terrain
+/// along its length axis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeightMapImage {
+    /// Width of the image in pixels.
+    pub width: u32,
+    /// Height of the image in pixels.
+    pub height: u32,
+    /// Height values, row by row.
+    pub pixels: Vec<f32>,
+}
+
+impl HeightMapImage {
+    /// Reads a height map from a file. The format is defined by the extension of the file: `raw`
+    /// and `r16` are raw little-endian 16-bit square images, everything else is decoded as a
+    /// regular image (16-bit PNG is the recommended format).
+    pub fn from_file(path: &Path) -> Result<Self, TerrainImageError> {
+        if is_raw(path) {
+            Self::from_raw16(&std::fs::read(path)?)
+        } else {
+            Ok(Self::from_image(image::open(path)?))
+        }
+    }
+
+    /// Reads a square height map of raw little-endian 16-bit pixels.
+    pub fn from_raw16(data: &[u8]) -> Result<Self, TerrainImageError> {
+        let pixel_count = data.len() / 2;
+        let size = (pixel_count as f64).sqrt() as usize;
+        if data.len() % 2 != 0 || size * size != pixel_count || size == 0 {
+            return Err(TerrainImageError::InvalidRawSize(data.len()));
+        }
+        Ok(Self {
+            width: size as u32,
+            height: size as u32,
+            pixels: data
+                .chunks_exact(2)
+                .map(|p| u16::from_le_bytes([p[0], p[1]]) as f32 / u16::MAX as f32)
+                .collect(),
+        })
+    }
+
+    /// Converts a decoded image to a height map. Only the first channel of the image is used.
+    pub fn from_image(image: DynamicImage) -> Self {
+        let (width, height) = (image.width(), image.height());
+        let pixels = match image {
+            DynamicImage::ImageRgb32F(image) => image.pixels().map(|p| p.0[0]).collect(),
+            DynamicImage::ImageRgba32F(image) => image.pixels().map(|p| p.0[0]).collect(),
+            image => image
+                .into_luma16()
+                .into_raw()
+                .into_iter()
+                .map(|p| p as f32 / u16::MAX as f32)
+                .collect(),
+        };
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Writes the height map to a file. The format is defined by the extension of the file: `raw`
+    /// and `r16` produce raw little-endian 16-bit data, `exr` keeps float values as is, any other
+    /// extension produces a 16-bit grayscale image. Values are clamped to `[0; 1]` range for
+    /// every 16-bit format.
+    pub fn save(&self, path: &Path) -> Result<(), TerrainImageError> {
+        let to_u16 = |v: &f32| (v.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16;
+        if is_raw(path) {
+            let data = self
+                .pixels
+                .iter()
+                .flat_map(|v| to_u16(v).to_le_bytes())
+                .collect::<Vec<_>>();
+            std::fs::write(path, data)?;
+        } else if is_exr(path) {
+            // EXR encoder supports only RGB(A) images.
+            let image = ImageBuffer::<Rgb<f32>, _>::from_raw(
+                self.width,
+                self.height,
+                self.pixels.iter().flat_map(|v| [*v; 3]).collect::<Vec<_>>(),
+            )
+            .expect("Pixel count must match image size!");
+            image.save(path)?;
+        } else {
+            let image = ImageBuffer::<Luma<u16>, _>::from_raw(
+                self.width,
+                self.height,
+                self.pixels.iter().map(to_u16).collect::<Vec<_>>(),
+            )
+            .expect("Pixel count must match image size!");
+            image.save(path)?;
+        }
+        Ok(())
+    }
+
+    /// Returns bilinearly interpolated height at the given normalized coordinates.
+    pub fn sample(&self, u: f32, v: f32) -> f32 {
+        sample(self.width, self.height, &self.pixels, u, v, |p| *p)
+    }
+}
+
+/// RGBA splat map, each channel defines a blending mask of a terrain layer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplatMapImage {
+    /// Width of the image in pixels.
+    pub width: u32,
+    /// Height of the image in pixels.
+    pub height: u32,
+    /// Pixels of the image, row by row.
+    pub pixels: Vec<[u8; 4]>,
+}
+
+impl SplatMapImage {
+    /// Reads a splat map from an image file. Images without alpha channel have fully opaque alpha,
+    /// so the fourth layer will be fully visible - use RGBA images to control four layers.
+    pub fn from_file(path: &Path) -> Result<Self, TerrainImageError> {
+        let image = image::open(path)?.into_rgba8();
+        Ok(Self {
+            width: image.width(),
+            height: image.height(),
+            pixels: image.pixels().map(|p| p.0).collect(),
+        })
+    }
+
+    /// Writes the splat map to an RGBA image file.
+    pub fn save(&self, path: &Path) -> Result<(), TerrainImageError> {
+        let image = ImageBuffer::<Rgba<u8>, _>::from_raw(
+            self.width,
+            self.height,
+            self.pixels.iter().flatten().cloned().collect::<Vec<_>>(),
+        )
+        .expect("Pixel count must match image size!");
+        image.save(path)?;
+        Ok(())
+    }
+
+    /// Returns bilinearly interpolated value of the given channel at the given normalized
+    /// coordinates.
+    pub fn sample(&self, u: f32, v: f32, channel: usize) -> f32 {
+        sample(self.width, self.height, &self.pixels, u, v, |p| {
+            p[channel] as f32
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::algebra::Vector2;
+    use crate::scene::base::BaseBuilder;
+    use crate::scene::terrain::heightmap::{HeightMapImage, SplatMapImage};
+    use crate::scene::terrain::{Layer, TerrainBuilder};
+
+    #[test]
+    fn test_raw16() {
+        let data = [0u16, u16::MAX, u16::MAX, 0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect::<Vec<_>>();
+        let image = HeightMapImage::from_raw16(&data).unwrap();
+        assert_eq!(image.width, 2);
+        assert_eq!(image.pixels, vec![0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(image.sample(0.5, 0.5), 0.5);
+        assert!(HeightMapImage::from_raw16(&data[..6]).is_err());
+    }
+
+    #[test]
+    fn test_terrain_round_trip() {
+        let node = TerrainBuilder::new(BaseBuilder::new())
+            .with_width_chunks(0..2)
+            .with_length_chunks(0..2)
+            .with_height_map_size(Vector2::new(5, 5))
+            .with_mask_size(Vector2::new(5, 5))
+            .with_layers(vec![Layer::default(), Layer::default()])
+            .build_node();
+        let mut terrain = node
+            .cast::<crate::scene::terrain::Terrain>()
+            .unwrap()
+            .clone();
+
+        // 2 chunks with 5 pixels each share one column of pixels.
+        let height_map = HeightMapImage {
+            width: 9,
+            height: 9,
+            pixels: (0..81).map(|i| (i % 9) as f32 / 8.0).collect(),
+        };
+        terrain.import_height_map(&height_map, 10.0, 1.0);
+        let exported = terrain.export_height_map(10.0, 1.0);
+        assert_eq!(exported.width, 9);
+        assert_eq!(exported.height, 9);
+        for (a, b) in exported.pixels.iter().zip(height_map.pixels.iter()) {
+            assert!((a - b).abs() < 1.0e-5);
+        }
+
+        let splat_map = SplatMapImage {
+            width: 9,
+            height: 9,
+            pixels: (0..81).map(|i| [(i * 3) as u8, 255, 0, 0]).collect(),
+        };
+        terrain.import_splat_map(&splat_map, 0);
+        assert_eq!(terrain.export_splat_map(0), splat_map.clone());
+    }
+}
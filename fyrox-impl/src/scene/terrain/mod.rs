@@ -34,7 +34,11 @@ use crate::{
         graph::Graph,
         mesh::RenderPath,
         node::{Node, NodeTrait},
-        terrain::{geometry::TerrainGeometry, quadtree::QuadTree},
+        terrain::{
+            geometry::TerrainGeometry,
+            heightmap::{HeightMapImage, SplatMapImage},
+            quadtree::QuadTree,
+        },
     },
 };
 use fyrox_core::uuid_provider;
@@ -50,6 +54,7 @@ use std::{
 };
 
 mod geometry;
+pub mod heightmap;
 mod quadtree;
 
 /// Current implementation version marker.
@@ -1417,6 +1422,159 @@ impl Terrain {
         self.bounding_box_dirty.set(true);
     }
 
+    /// Returns normalized coordinates of a pixel of a chunk within the whole terrain, the pixels at
+    /// the edges of chunks are mapped exactly at the edges of the chunks.
+    fn chunk_pixel_uv(
+        &self,
+        chunk: &Chunk,
+        pixel: Vector2<u32>,
+        size: Vector2<u32>,
+    ) -> Vector2<f32> {
+        let cx = (chunk.grid_position.x - self.width_chunks.start) as f32;
+        let cz = (chunk.grid_position.y - self.length_chunks.start) as f32;
+        let kx = pixel.x as f32 / size.x.saturating_sub(1).max(1) as f32;
+        let kz = pixel.y as f32 / size.y.saturating_sub(1).max(1) as f32;
+        Vector2::new(
+            (cx + kx) / self.width_chunks.len().max(1) as f32,
+            (cz + kz) / self.length_chunks.len().max(1) as f32,
+        )
+    }
+
+    /// Returns the size of an image, that covers the whole terrain with the given per-chunk size.
+    /// Adjacent chunks share their edge pixels.
+    fn total_image_size(&self, size: Vector2<u32>) -> Vector2<u32> {
+        let step = size.map(|s| s.saturating_sub(1).max(1));
+        Vector2::new(
+            self.width_chunks.len() as u32 * step.x + 1,
+            self.length_chunks.len() as u32 * step.y + 1,
+        )
+    }
+
+    /// Calls the given function for each pixel of the image, that covers the whole terrain with
+    /// the given per-chunk size. The function receives the chunk and the position of the pixel
+    /// in the chunk.
+    fn for_each_total_image_pixel<F>(&self, size: Vector2<u32>, mut func: F)
+    where
+        F: FnMut(&Chunk, usize),
+    {
+        let total_size = self.total_image_size(size);
+        let step = size.map(|s| s.saturating_sub(1).max(1));
+        let width_chunks = self.width_chunks.len();
+        for y in 0..total_size.y {
+            let cz = ((y / step.y) as usize).min(self.length_chunks.len() - 1);
+            let iy = y - cz as u32 * step.y;
+            for x in 0..total_size.x {
+                let cx = ((x / step.x) as usize).min(width_chunks - 1);
+                let ix = x - cx as u32 * step.x;
+                func(
+                    &self.chunks[cz * width_chunks + cx],
+                    (iy * size.x + ix) as usize,
+                );
+            }
+        }
+    }
+
+    /// Replaces height maps of every chunk with the given height map, that covers the whole terrain.
+    /// The height map is resampled to fit the terrain, and every height is computed as
+    /// `offset + scale * value`. Use [`Self::export_height_map`] with the same parameters to get the
+    /// source height map back.
+    pub fn import_height_map(&mut self, height_map: &HeightMapImage, scale: f32, offset: f32) {
+        let mut new_height_maps = Vec::with_capacity(self.chunks.len());
+        for chunk in self.chunks.iter() {
+            let size = chunk.height_map_size;
+            let mut heights = Vec::with_capacity((size.x * size.y) as usize);
+            for iy in 0..size.y {
+                for ix in 0..size.x {
+                    let uv = self.chunk_pixel_uv(chunk, Vector2::new(ix, iy), size);
+                    heights.push(offset + scale * height_map.sample(uv.x, uv.y));
+                }
+            }
+            new_height_maps.push(heights);
+        }
+
+        for (chunk, heights) in self.chunks.iter_mut().zip(new_height_maps) {
+            chunk.heightmap = Some(make_height_map_texture(heights, chunk.height_map_size));
+            chunk.quad_tree =
+                make_quad_tree(&chunk.heightmap, chunk.height_map_size, chunk.block_size);
+        }
+
+        self.bounding_box_dirty.set(true);
+    }
+
+    /// Combines height maps of every chunk into a single height map, that covers the whole terrain.
+    /// Every value of the height map is computed as `(height - offset) / scale`.
+    pub fn export_height_map(&self, scale: f32, offset: f32) -> HeightMapImage {
+        let size = *self.height_map_size;
+        let total_size = self.total_image_size(size);
+        let scale = if scale == 0.0 { 1.0 } else { scale };
+        let mut pixels = Vec::with_capacity((total_size.x * total_size.y) as usize);
+        if !self.chunks.is_empty() {
+            self.for_each_total_image_pixel(size, |chunk, index| {
+                let texture = chunk.heightmap().data_ref();
+                let heights = texture.data_of_type::<f32>().unwrap();
+                pixels.push((heights[index] - offset) / scale);
+            });
+        }
+        HeightMapImage {
+            width: total_size.x,
+            height: total_size.y,
+            pixels,
+        }
+    }
+
+    /// Writes channels of the given splat map, that covers the whole terrain, to the blending
+    /// masks of up to four layers, starting from the given one. Red channel goes to `first_layer`,
+    /// green - to `first_layer + 1` and so on. Channels without respective layers are ignored.
+    pub fn import_splat_map(&mut self, splat_map: &SplatMapImage, first_layer: usize) {
+        let size = *self.mask_size;
+        let layer_count = self.layers.len();
+        for chunk_index in 0..self.chunks.len() {
+            let chunk = &self.chunks[chunk_index];
+            for channel in 0..4 {
+                let layer = first_layer + channel;
+                if layer >= layer_count {
+                    break;
+                }
+                let mut mask = Vec::with_capacity((size.x * size.y) as usize);
+                for iy in 0..size.y {
+                    for ix in 0..size.x {
+                        let uv = self.chunk_pixel_uv(chunk, Vector2::new(ix, iy), size);
+                        mask.push(splat_map.sample(uv.x, uv.y, channel).round() as u8);
+                    }
+                }
+                let mut texture_data = chunk.layer_masks[layer].data_ref();
+                let mut modifier = texture_data.modify();
+                for (pixel, value) in modifier.data_mut().iter_mut().zip(mask) {
+                    *pixel = value;
+                }
+            }
+        }
+    }
+
+    /// Combines blending masks of up to four layers, starting from the given one, into a single
+    /// splat map, that covers the whole terrain. Channels without respective layers are zero.
+    pub fn export_splat_map(&self, first_layer: usize) -> SplatMapImage {
+        let size = *self.mask_size;
+        let total_size = self.total_image_size(size);
+        let mut pixels = Vec::with_capacity((total_size.x * total_size.y) as usize);
+        if !self.chunks.is_empty() {
+            self.for_each_total_image_pixel(size, |chunk, index| {
+                let mut pixel = [0; 4];
+                for (channel, value) in pixel.iter_mut().enumerate() {
+                    if let Some(mask) = chunk.layer_masks.get(first_layer + channel) {
+                        *value = mask.data_ref().data()[index];
+                    }
+                }
+                pixels.push(pixel);
+            });
+        }
+        SplatMapImage {
+            width: total_size.x,
+            height: total_size.y,
+            pixels,
+        }
+    }
+
     /// Returns data for rendering (vertex and index buffers).
     pub fn geometry(&self) -> &TerrainGeometry {
         &self.geometry
@@ -1596,6 +1754,7 @@ impl NodeTrait for Terrain {
                                     node.persistent_index,
                                 ),
                                 node_handle: self.self_handle,
+                                vertex_animation: None,
                             },
                         );
                     } else {
@@ -1619,6 +1778,7 @@ impl NodeTrait for Terrain {
                                             node.persistent_index,
                                         ),
                                         node_handle: self.self_handle,
+                                        vertex_animation: None,
                                     },
                                 );
                             }
@@ -0,0 +1,816 @@
+//! 3D text is a scene node, that renders a string in world space using glyphs of a font atlas. See
+//! [`Text3D`] docs for more info.
+
+use crate::{
+    asset::untyped::ResourceKind,
+    core::{
+        algebra::{Matrix4, Point3, Vector2, Vector3, Vector4},
+        color::Color,
+        math::{aabb::AxisAlignedBoundingBox, Matrix4Ext, Rect, TriangleDefinition},
+        parking_lot::Mutex,
+        pool::Handle,
+        reflect::prelude::*,
+        sstorage::ImmutableString,
+        type_traits::prelude::*,
+        value_as_u8_slice,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    fxhash::FxHashMap,
+    graph::BaseSceneGraph,
+    gui::font::{Font, FontResource, BUILT_IN_FONT},
+    material::{
+        shader::{ShaderResource, ShaderResourceExtension},
+        Material, MaterialResource, PropertyValue,
+    },
+    renderer::{self, bundle::RenderContext},
+    resource::texture::{Texture, TextureKind, TexturePixelKind, TextureResource},
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        mesh::{
+            buffer::{
+                VertexAttributeDataType, VertexAttributeDescriptor, VertexAttributeUsage,
+                VertexTrait,
+            },
+            RenderPath,
+        },
+        node::{Node, NodeTrait, RdcControlFlow},
+    },
+};
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+struct TextVertex {
+    position: Vector3<f32>,
+    tex_coord: Vector2<f32>,
+    glyph_bounds: Vector4<f32>,
+    color: Color,
+}
+
+impl VertexTrait for TextVertex {
+    fn layout() -> &'static [VertexAttributeDescriptor] {
+        &[
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::Position,
+                data_type: VertexAttributeDataType::F32,
+                size: 3,
+                divisor: 0,
+                shader_location: 0,
+                normalized: false,
+            },
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::TexCoord0,
+                data_type: VertexAttributeDataType::F32,
+                size: 2,
+                divisor: 0,
+                shader_location: 1,
+                normalized: false,
+            },
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::Custom0,
+                data_type: VertexAttributeDataType::F32,
+                size: 4,
+                divisor: 0,
+                shader_location: 2,
+                normalized: false,
+            },
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::Color,
+                data_type: VertexAttributeDataType::U8,
+                size: 4,
+                divisor: 0,
+                shader_location: 3,
+                normalized: true,
+            },
+        ]
+    }
+}
+
+/// Defines how 3D text is oriented in the world.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Reflect,
+    Visit,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "0c7b1f4e-93d2-4a65-b8e1-5f2a6d9c3b70")]
+pub enum Text3DOrientation {
+    /// The text always faces the camera. Rotation and scale of the node are ignored.
+    #[default]
+    Billboard,
+    /// The text lies in XY plane of the node and is transformed by its global transform. It is
+    /// readable for a camera, that looks in the same direction as the node (along its positive Z
+    /// axis).
+    Fixed,
+}
+
+/// A glyph of laid out text.
+#[derive(Clone, Debug, PartialEq)]
+struct LayoutGlyph {
+    /// Bounds of the glyph (in pixels) relative to the center of the text. Position of the rect is
+    /// the left-bottom corner of the glyph, Y axis points up.
+    bounds: Rect<f32>,
+    /// Texture coordinates of the glyph in the atlas page: left-top, right-top, right-bottom,
+    /// left-bottom.
+    tex_coords: [Vector2<f32>; 4],
+    page_index: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct TextLayout {
+    glyphs: Vec<LayoutGlyph>,
+    /// Size of the text block in pixels.
+    size: Vector2<f32>,
+}
+
+/// Lays out the glyphs of the given text, every line is centered horizontally and the text block is
+/// centered vertically around the origin.
+fn layout_text(font: &mut Font, text: &str, font_size: f32) -> TextLayout {
+    if text.is_empty() {
+        return Default::default();
+    }
+
+    let ascender = font.ascender(font_size);
+    let line_height = ascender - font.descender(font_size);
+
+    let mut glyphs = Vec::<LayoutGlyph>::new();
+    let mut width = 0.0f32;
+    let mut line_count = 0;
+    for (line_index, line) in text.split('\n').enumerate() {
+        let baseline = -(line_index as f32) * line_height - ascender;
+        let line_start = glyphs.len();
+        let mut x = 0.0;
+        for unicode in line.chars() {
+            match font.glyph(unicode, font_size) {
+                Some(glyph) => {
+                    if glyph.bitmap_width > 0 && glyph.bitmap_height > 0 {
+                        glyphs.push(LayoutGlyph {
+                            bounds: Rect::new(
+                                x + glyph.left,
+                                baseline + glyph.top,
+                                glyph.bitmap_width as f32,
+                                glyph.bitmap_height as f32,
+                            ),
+                            tex_coords: glyph.tex_coords,
+                            page_index: glyph.page_index,
+                        });
+                    }
+                    x += glyph.advance;
+                }
+                None => x += font_size,
+            }
+        }
+        for glyph in &mut glyphs[line_start..] {
+            glyph.bounds.position.x -= x * 0.5;
+        }
+        width = width.max(x);
+        line_count += 1;
+    }
+
+    let height = line_count as f32 * line_height;
+    for glyph in glyphs.iter_mut() {
+        glyph.bounds.position.y += height * 0.5;
+    }
+
+    TextLayout {
+        glyphs,
+        size: Vector2::new(width, height),
+    }
+}
+
+/// Makes sure, that the given atlas page has an up-to-date texture and returns it.
+fn page_texture(font: &mut Font, font_size: f32, page_index: usize) -> Option<TextureResource> {
+    let page_size = font.page_size() as u32;
+    let page = font
+        .atlases
+        .get_mut(&font_size.into())?
+        .pages
+        .get_mut(page_index)?;
+    if page.texture.is_none() || page.modified {
+        let texture = Texture::from_bytes(
+            TextureKind::Rectangle {
+                width: page_size,
+                height: page_size,
+            },
+            TexturePixelKind::R8,
+            page.pixels.clone(),
+        )?;
+        page.texture = Some(TextureResource::new_ok(ResourceKind::Embedded, texture).into());
+        page.modified = false;
+    }
+    page.texture.as_ref()?.try_cast::<Texture>()
+}
+
+#[derive(Debug, Clone)]
+struct PageMaterial {
+    texture: TextureResource,
+    material: MaterialResource,
+}
+
+/// Materials of the atlas pages, they're created on demand when the text is rendered.
+#[derive(Debug, Default)]
+struct MaterialCache(Mutex<FxHashMap<(usize, bool), PageMaterial>>);
+
+impl Clone for MaterialCache {
+    fn clone(&self) -> Self {
+        // Materials are not shared between copies, because they contain per-node properties.
+        Self::default()
+    }
+}
+
+/// 3D text renders a string in world space, it is useful for damage numbers, labels above
+/// characters or objects, signs, etc. The text uses the same fonts (and font atlases) as the user
+/// interface, so there's no need to build UI overlays and project world positions on screen.
+///
+/// ## Size
+///
+/// Glyphs are rasterized using [`Text3D::font_size`] (in pixels), and the text is scaled so its
+/// line has [`Text3D::height`] height in world units. Larger font size gives sharper glyphs at
+/// the cost of atlas space.
+///
+/// ## Orientation
+///
+/// The text could always face the camera or be transformed by the node transform, see
+/// [`Text3DOrientation`] docs for more info. In both cases the text is centered at the position of
+/// the node, and every line is centered horizontally.
+///
+/// ## Outline and depth test
+///
+/// The text could have an outline of the given color and thickness (in pixels of the atlas), which
+/// helps to read it on any background. When depth test is disabled, the text is drawn on top of any
+/// other geometry, which is useful for labels, that must always be visible.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::{color::Color, pool::Handle},
+/// #     scene::{base::BaseBuilder, graph::Graph, node::Node, text3d::Text3DBuilder},
+/// # };
+/// fn create_damage_number(damage: u32, graph: &mut Graph) -> Handle<Node> {
+///     Text3DBuilder::new(BaseBuilder::new())
+///         .with_text(damage.to_string())
+///         .with_color(Color::RED)
+///         .with_height(0.25)
+///         .with_outline(Color::BLACK, 2.0)
+///         .with_depth_test(false)
+///         .build(graph)
+/// }
+/// ```
+#[derive(Clone, Debug, Reflect, Visit, ComponentProvider)]
+pub struct Text3D {
+    base: Base,
+
+    #[reflect(setter = "set_text")]
+    text: InheritableVariable<String>,
+
+    #[reflect(setter = "set_font")]
+    font: InheritableVariable<FontResource>,
+
+    #[reflect(min_value = 1.0, step = 1.0, setter = "set_font_size")]
+    font_size: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.01, setter = "set_height")]
+    height: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_color")]
+    color: InheritableVariable<Color>,
+
+    #[reflect(setter = "set_orientation")]
+    orientation: InheritableVariable<Text3DOrientation>,
+
+    #[reflect(setter = "set_outline_color")]
+    outline_color: InheritableVariable<Color>,
+
+    #[reflect(min_value = 0.0, step = 0.1, setter = "set_outline_thickness")]
+    outline_thickness: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_depth_test")]
+    depth_test: InheritableVariable<bool>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    materials: MaterialCache,
+}
+
+impl Default for Text3D {
+    fn default() -> Self {
+        Text3DBuilder::new(BaseBuilder::new()).build_text3d()
+    }
+}
+
+impl Deref for Text3D {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Text3D {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for Text3D {
+    fn type_uuid() -> Uuid {
+        uuid!("d5e3a8c1-6f2b-4c97-9a04-8b1e7f3d2c65")
+    }
+}
+
+impl Text3D {
+    /// Sets new text.
+    pub fn set_text(&mut self, text: String) -> String {
+        self.text.set_value_and_mark_modified(text)
+    }
+
+    /// Returns current text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Sets new font of the text.
+    pub fn set_font(&mut self, font: FontResource) -> FontResource {
+        self.font.set_value_and_mark_modified(font)
+    }
+
+    /// Returns current font of the text.
+    pub fn font(&self) -> &FontResource {
+        &self.font
+    }
+
+    /// Sets new size (in pixels), that is used to rasterize glyphs of the text.
+    pub fn set_font_size(&mut self, font_size: f32) -> f32 {
+        self.font_size
+            .set_value_and_mark_modified(font_size.max(1.0))
+    }
+
+    /// Returns current size (in pixels), that is used to rasterize glyphs of the text.
+    pub fn font_size(&self) -> f32 {
+        *self.font_size
+    }
+
+    /// Sets new height of a line of the text in world units.
+    pub fn set_height(&mut self, height: f32) -> f32 {
+        self.height.set_value_and_mark_modified(height.max(0.0))
+    }
+
+    /// Returns current height of a line of the text in world units.
+    pub fn height(&self) -> f32 {
+        *self.height
+    }
+
+    /// Sets new color of the text.
+    pub fn set_color(&mut self, color: Color) -> Color {
+        self.color.set_value_and_mark_modified(color)
+    }
+
+    /// Returns current color of the text.
+    pub fn color(&self) -> Color {
+        *self.color
+    }
+
+    /// Sets new orientation of the text. See [`Text3DOrientation`] docs for more info.
+    pub fn set_orientation(&mut self, orientation: Text3DOrientation) -> Text3DOrientation {
+        self.orientation.set_value_and_mark_modified(orientation)
+    }
+
+    /// Returns current orientation of the text.
+    pub fn orientation(&self) -> Text3DOrientation {
+        *self.orientation
+    }
+
+    /// Sets new color of the outline.
+    pub fn set_outline_color(&mut self, color: Color) -> Color {
+        self.outline_color.set_value_and_mark_modified(color)
+    }
+
+    /// Returns current color of the outline.
+    pub fn outline_color(&self) -> Color {
+        *self.outline_color
+    }
+
+    /// Sets new thickness of the outline in pixels of the font atlas. Zero thickness disables the
+    /// outline.
+    pub fn set_outline_thickness(&mut self, thickness: f32) -> f32 {
+        self.outline_thickness
+            .set_value_and_mark_modified(thickness.max(0.0))
+    }
+
+    /// Returns current thickness of the outline.
+    pub fn outline_thickness(&self) -> f32 {
+        *self.outline_thickness
+    }
+
+    /// Enables or disables depth test. Text without depth test is drawn on top of other geometry.
+    pub fn set_depth_test(&mut self, enabled: bool) -> bool {
+        self.depth_test.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if the depth test is enabled, `false` - otherwise.
+    pub fn is_depth_test_enabled(&self) -> bool {
+        *self.depth_test
+    }
+
+    fn scale(&self) -> f32 {
+        *self.height / *self.font_size
+    }
+
+    fn page_material(&self, page_index: usize, texture: TextureResource) -> MaterialResource {
+        let mut materials = self.materials.0.lock();
+        let entry = materials
+            .entry((page_index, *self.depth_test))
+            .or_insert_with(|| {
+                let shader = if *self.depth_test {
+                    ShaderResource::standard_text3d()
+                } else {
+                    ShaderResource::standard_text3d_overlay()
+                };
+                PageMaterial {
+                    texture: texture.clone(),
+                    material: MaterialResource::new_ok(
+                        ResourceKind::Embedded,
+                        Material::from_shader(shader, None),
+                    ),
+                }
+            });
+        entry.texture = texture;
+
+        let mut material = entry.material.data_ref();
+        for (name, value) in [
+            (
+                "fontTexture",
+                PropertyValue::Sampler {
+                    value: Some(entry.texture.clone()),
+                    fallback: Default::default(),
+                },
+            ),
+            ("outlineColor", PropertyValue::Color(*self.outline_color)),
+            (
+                "outlineThickness",
+                PropertyValue::Float(*self.outline_thickness),
+            ),
+        ] {
+            let _ = material.set_property(&ImmutableString::new(name), value);
+        }
+        drop(material);
+
+        entry.material.clone()
+    }
+}
+
+impl NodeTrait for Text3D {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        let size = self
+            .font
+            .state()
+            .data()
+            .map(|font| layout_text(font, &self.text, *self.font_size).size)
+            .unwrap_or_default()
+            * self.scale();
+        match *self.orientation {
+            // Billboard could have any rotation.
+            Text3DOrientation::Billboard => AxisAlignedBoundingBox::from_radius(size.norm() * 0.5),
+            Text3DOrientation::Fixed => AxisAlignedBoundingBox::from_min_max(
+                Vector3::new(-size.x * 0.5, -size.y * 0.5, 0.0),
+                Vector3::new(size.x * 0.5, size.y * 0.5, 0.0),
+            ),
+        }
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        match *self.orientation {
+            Text3DOrientation::Billboard => self
+                .local_bounding_box()
+                .transform(&Matrix4::new_translation(&self.global_position())),
+            Text3DOrientation::Fixed => self
+                .local_bounding_box()
+                .transform(&self.global_transform()),
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn collect_render_data(&self, ctx: &mut RenderContext) -> RdcControlFlow {
+        if !self.global_visibility()
+            || !self.is_globally_enabled()
+            || self.text.is_empty()
+            || renderer::is_shadow_pass(ctx.render_pass_name)
+            || (self.frustum_culling()
+                && !ctx
+                    .frustum
+                    .map_or(true, |f| f.is_intersects_aabb(&self.world_bounding_box())))
+        {
+            return RdcControlFlow::Continue;
+        }
+
+        let mut font_state = self.font.state();
+        let Some(font) = font_state.data() else {
+            return RdcControlFlow::Continue;
+        };
+
+        let font_size = *self.font_size;
+        let layout = layout_text(font, &self.text, font_size);
+
+        let scale = self.scale();
+        let origin = self.global_position();
+        let (side, up) = match *self.orientation {
+            Text3DOrientation::Billboard => {
+                let inv_view = ctx.view_matrix.try_inverse().unwrap_or_default();
+                (
+                    inv_view
+                        .side()
+                        .try_normalize(f32::EPSILON)
+                        .unwrap_or_default(),
+                    inv_view
+                        .up()
+                        .try_normalize(f32::EPSILON)
+                        .unwrap_or_default(),
+                )
+            }
+            Text3DOrientation::Fixed => (-Vector3::x(), Vector3::y()),
+        };
+        let global_transform = self.global_transform();
+        let to_world = |x: f32, y: f32| match *self.orientation {
+            Text3DOrientation::Billboard => origin + side.scale(x * scale) + up.scale(y * scale),
+            Text3DOrientation::Fixed => {
+                global_transform
+                    .transform_point(&Point3::from(side.scale(x * scale) + up.scale(y * scale)))
+                    .coords
+            }
+        };
+
+        let page_size = font.page_size() as f32;
+        let outline = *self.outline_thickness;
+        let uv_outline = outline / page_size;
+        let sort_index = ctx.calculate_sorting_index(origin);
+
+        let mut pages = layout
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.page_index)
+            .collect::<Vec<_>>();
+        pages.sort_unstable();
+        pages.dedup();
+
+        for page_index in pages {
+            let Some(texture) = page_texture(font, font_size, page_index) else {
+                continue;
+            };
+            let material = self.page_material(page_index, texture);
+
+            let mut vertices = Vec::new();
+            let mut triangles = Vec::new();
+            for glyph in layout.glyphs.iter().filter(|g| g.page_index == page_index) {
+                // Quads are expanded by the thickness of the outline, so it is not clipped.
+                let left = glyph.bounds.x() - outline;
+                let right = glyph.bounds.x() + glyph.bounds.w() + outline;
+                let bottom = glyph.bounds.y() - outline;
+                let top = glyph.bounds.y() + glyph.bounds.h() + outline;
+
+                let uv_min = glyph.tex_coords[0];
+                let uv_max = glyph.tex_coords[2];
+                let glyph_bounds = Vector4::new(uv_min.x, uv_min.y, uv_max.x, uv_max.y);
+
+                let first = vertices.len() as u32;
+                for (position, tex_coord) in [
+                    (
+                        to_world(left, top),
+                        Vector2::new(uv_min.x - uv_outline, uv_min.y - uv_outline),
+                    ),
+                    (
+                        to_world(right, top),
+                        Vector2::new(uv_max.x + uv_outline, uv_min.y - uv_outline),
+                    ),
+                    (
+                        to_world(right, bottom),
+                        Vector2::new(uv_max.x + uv_outline, uv_max.y + uv_outline),
+                    ),
+                    (
+                        to_world(left, bottom),
+                        Vector2::new(uv_min.x - uv_outline, uv_max.y + uv_outline),
+                    ),
+                ] {
+                    vertices.push(TextVertex {
+                        position,
+                        tex_coord,
+                        glyph_bounds,
+                        color: *self.color,
+                    });
+                }
+                triangles.push(TriangleDefinition([first, first + 1, first + 2]));
+                triangles.push(TriangleDefinition([first + 2, first + 3, first]));
+            }
+
+            ctx.storage.push_triangles(
+                TextVertex::layout(),
+                &material,
+                RenderPath::Forward,
+                0,
+                sort_index,
+                false,
+                self.self_handle,
+                &mut move |mut vertex_buffer, mut triangle_buffer| {
+                    let start_vertex_index = vertex_buffer.vertex_count();
+
+                    for vertex in vertices.iter() {
+                        vertex_buffer
+                            .push_vertex_raw(value_as_u8_slice(vertex))
+                            .unwrap();
+                    }
+
+                    triangle_buffer.push_triangles_iter_with_offset(
+                        start_vertex_index,
+                        triangles.iter().cloned(),
+                    );
+                },
+            );
+        }
+
+        RdcControlFlow::Continue
+    }
+}
+
+/// Allows you to create 3D text in declarative manner.
+pub struct Text3DBuilder {
+    base_builder: BaseBuilder,
+    text: String,
+    font: FontResource,
+    font_size: f32,
+    height: f32,
+    color: Color,
+    orientation: Text3DOrientation,
+    outline_color: Color,
+    outline_thickness: f32,
+    depth_test: bool,
+}
+
+impl Text3DBuilder {
+    /// Creates new builder with default state (empty white camera-facing text of 0.2 height, built-in
+    /// font, no outline, depth test enabled).
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            text: Default::default(),
+            font: BUILT_IN_FONT.clone(),
+            font_size: 32.0,
+            height: 0.2,
+            color: Color::WHITE,
+            orientation: Default::default(),
+            outline_color: Color::BLACK,
+            outline_thickness: 0.0,
+            depth_test: true,
+        }
+    }
+
+    /// Sets the desired text.
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Sets the desired font.
+    pub fn with_font(mut self, font: FontResource) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Sets the desired size (in pixels), that is used to rasterize glyphs.
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the desired height of a line of the text in world units.
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets the desired color of the text.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the desired orientation of the text.
+    pub fn with_orientation(mut self, orientation: Text3DOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Sets the desired color and thickness (in pixels of the font atlas) of the outline.
+    pub fn with_outline(mut self, color: Color, thickness: f32) -> Self {
+        self.outline_color = color;
+        self.outline_thickness = thickness;
+        self
+    }
+
+    /// Enables or disables depth test.
+    pub fn with_depth_test(mut self, enabled: bool) -> Self {
+        self.depth_test = enabled;
+        self
+    }
+
+    fn build_text3d(self) -> Text3D {
+        Text3D {
+            base: self.base_builder.build_base(),
+            text: self.text.into(),
+            font: self.font.into(),
+            font_size: self.font_size.max(1.0).into(),
+            height: self.height.max(0.0).into(),
+            color: self.color.into(),
+            orientation: self.orientation.into(),
+            outline_color: self.outline_color.into(),
+            outline_thickness: self.outline_thickness.max(0.0).into(),
+            depth_test: self.depth_test.into(),
+            materials: Default::default(),
+        }
+    }
+
+    /// Creates new 3D text instance.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_text3d())
+    }
+
+    /// Creates new 3D text instance and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        gui::font::BUILT_IN_FONT,
+        scene::{
+            base::BaseBuilder,
+            node::NodeTrait,
+            text3d::{layout_text, Text3DBuilder},
+        },
+    };
+
+    #[test]
+    fn test_layout_text() {
+        let mut font = BUILT_IN_FONT.data_ref();
+
+        let layout = layout_text(&mut font, "Hi there", 32.0);
+        // Spaces have no glyphs.
+        assert_eq!(layout.glyphs.len(), 7);
+        let min_x = layout
+            .glyphs
+            .iter()
+            .map(|g| g.bounds.x())
+            .fold(f32::MAX, f32::min);
+        let max_x = layout
+            .glyphs
+            .iter()
+            .map(|g| g.bounds.x() + g.bounds.w())
+            .fold(f32::MIN, f32::max);
+        // The line is centered.
+        assert!((min_x + max_x).abs() < 4.0);
+        assert!(layout.size.x >= max_x - min_x);
+
+        let two_lines = layout_text(&mut font, "A\nA", 32.0);
+        assert_eq!(two_lines.glyphs.len(), 2);
+        assert_eq!(two_lines.size.y, layout.size.y * 2.0);
+        let (first, second) = (&two_lines.glyphs[0], &two_lines.glyphs[1]);
+        assert_eq!(first.bounds.x(), second.bounds.x());
+        assert_eq!(first.bounds.y() - second.bounds.y(), layout.size.y);
+        assert!(first.bounds.y() > 0.0 && second.bounds.y() < 0.0);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let text = Text3DBuilder::new(BaseBuilder::new())
+            .with_text("Label")
+            .with_height(0.5)
+            .build_text3d();
+        let size = text.local_bounding_box().max - text.local_bounding_box().min;
+        assert!(size.x > 0.5 && size.y > 0.5);
+        assert_eq!(size.x, size.y);
+        assert_eq!(
+            Text3DBuilder::new(BaseBuilder::new())
+                .build_text3d()
+                .local_bounding_box()
+                .min
+                .x,
+            0.0
+        );
+    }
+}
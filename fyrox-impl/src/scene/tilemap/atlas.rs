@@ -0,0 +1,425 @@
+//! Texture atlas repacking for tile sets. Tiles of a tile set may use multiple materials with separate
+//! textures (pages), which breaks batching and wastes texture memory. Repacking copies every used
+//! region of every page into a single atlas texture. See [`repack_atlas`] docs for more info.
+
+use crate::{
+    asset::untyped::ResourceKind,
+    core::{
+        algebra::Vector2, log::Log, math::Rect, rectpack::RectPacker, sstorage::ImmutableString,
+    },
+    material::{Material, MaterialResource},
+    resource::texture::{Texture, TextureKind, TexturePixelKind, TextureResource},
+    scene::tilemap::{
+        lod::TexelSampler,
+        tileset::{TileDefinition, TileSet},
+    },
+};
+use fxhash::FxHashMap;
+use std::{
+    collections::hash_map::Entry,
+    fmt::{Display, Formatter},
+};
+
+/// An error that may occur during tile set atlas repacking.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TileSetAtlasError {
+    /// There's no tiles, which materials have a diffuse texture with pixels available on CPU.
+    NothingToRepack,
+    /// Repacked tiles do not fit into an atlas of the maximum size.
+    TooLarge {
+        /// Maximum size of the atlas.
+        max_size: u32,
+    },
+}
+
+impl Display for TileSetAtlasError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NothingToRepack => {
+                write!(
+                    f,
+                    "There's no tiles with diffuse textures, that could be repacked."
+                )
+            }
+            Self::TooLarge { max_size } => {
+                write!(
+                    f,
+                    "Tiles do not fit into an atlas of {max_size}x{max_size} pixels."
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for TileSetAtlasError {}
+
+/// Options of tile set atlas repacking.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TileSetAtlasOptions {
+    /// Amount of pixels around every tile, that will be filled with the edge pixels of the tile
+    /// (extrusion). It prevents colors of neighbouring tiles from bleeding into each other when the atlas
+    /// is filtered or mip-mapped.
+    pub padding: u32,
+    /// Maximum size (in pixels along each axis) of the atlas.
+    pub max_size: u32,
+}
+
+impl Default for TileSetAtlasOptions {
+    fn default() -> Self {
+        Self {
+            padding: 2,
+            max_size: 4096,
+        }
+    }
+}
+
+/// Result of tile set atlas repacking.
+pub struct TileSetAtlas {
+    /// Atlas texture in RGBA8 format.
+    pub texture: Texture,
+    /// A copy of the tile definitions of the tile set with rewritten texture coordinates. Materials of
+    /// the tiles are not changed yet, use [`Self::into_tiles`] to assign the atlas material to them.
+    pub tiles: Vec<TileDefinition>,
+    /// Indices of the tiles, that were repacked into the atlas.
+    pub repacked: Vec<usize>,
+}
+
+impl TileSetAtlas {
+    /// Creates a material for the atlas and assigns it to every repacked tile. The material is a copy of
+    /// the material of the first repacked tile (so its shader and other properties are preserved) with
+    /// the diffuse texture replaced with the given atlas texture.
+    pub fn into_tiles(mut self, atlas_texture: TextureResource) -> Vec<TileDefinition> {
+        let Some(first) = self.repacked.first() else {
+            return self.tiles;
+        };
+
+        let mut material = self.tiles[*first]
+            .material
+            .state()
+            .data()
+            .map(|material| material.clone())
+            .unwrap_or_else(Material::standard_2d);
+        if let Err(err) = material.set_texture(&diffuse_texture_name(), Some(atlas_texture)) {
+            Log::err(format!(
+                "Unable to set atlas texture to the material. Reason: {err:?}"
+            ));
+        }
+        let material = MaterialResource::new_ok(ResourceKind::Embedded, material);
+
+        for index in self.repacked.iter() {
+            self.tiles[*index].material = material.clone();
+        }
+
+        self.tiles
+    }
+}
+
+fn diffuse_texture_name() -> ImmutableString {
+    ImmutableString::new("diffuseTexture")
+}
+
+fn tile_texture(definition: &TileDefinition) -> Option<TextureResource> {
+    definition
+        .material
+        .state()
+        .data()
+        .and_then(|material| material.property_ref(&diffuse_texture_name()).cloned())
+        .and_then(|property| property.as_sampler())
+}
+
+// A region of a page in pixels.
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+struct Region {
+    page: u64,
+    position: Vector2<u32>,
+    size: Vector2<u32>,
+}
+
+fn uv_rect_to_region(page: u64, uv_rect: &Rect<f32>, sampler: &TexelSampler) -> Region {
+    let page_size = Vector2::new(sampler.width as f32, sampler.height as f32);
+    let min = uv_rect.position.component_mul(&page_size);
+    let max = (uv_rect.position + uv_rect.size).component_mul(&page_size);
+    let clamp = |v: f32, size: u32| (v.round().max(0.0) as u32).min(size);
+    let x0 = clamp(min.x.min(max.x), sampler.width - 1);
+    let y0 = clamp(min.y.min(max.y), sampler.height - 1);
+    let x1 = clamp(min.x.max(max.x), sampler.width).max(x0 + 1);
+    let y1 = clamp(min.y.max(max.y), sampler.height).max(y0 + 1);
+    Region {
+        page,
+        position: Vector2::new(x0, y0),
+        size: Vector2::new(x1 - x0, y1 - y0),
+    }
+}
+
+fn pack(regions: &[Region], padding: u32, size: u32) -> Option<Vec<Vector2<u32>>> {
+    let mut packer = RectPacker::new(size, size);
+    regions
+        .iter()
+        .map(|region| {
+            packer
+                .find_free(region.size.x + padding * 2, region.size.y + padding * 2)
+                .map(|rect| rect.position)
+        })
+        .collect()
+}
+
+/// Repacks regions of textures, that are used by the tiles of the given tile set, into a single atlas.
+/// Only tiles, which materials have a diffuse texture with pixels available on CPU in RGB(A)8 or BGR(A)8
+/// format, are repacked. Identical regions are stored in the atlas only once. Every region is surrounded
+/// with [`TileSetAtlasOptions::padding`] pixels, that are filled with the edge pixels of the region.
+/// The atlas is square, its size is the smallest power of two, that fits every region.
+///
+/// Texture coordinates of the repacked tiles are rewritten to point to the atlas, the tile set itself is
+/// not modified. Keep in mind that the mirrored texture coordinates (when the left side of a UV rect is
+/// greater than its right side) are not preserved.
+pub fn repack_atlas(
+    tile_set: &TileSet,
+    options: &TileSetAtlasOptions,
+) -> Result<TileSetAtlas, TileSetAtlasError> {
+    let mut samplers = FxHashMap::<u64, TexelSampler>::default();
+    let mut regions = Vec::<Region>::new();
+    let mut region_indices = FxHashMap::<Region, usize>::default();
+    let mut tile_regions = Vec::new();
+
+    for (tile_index, definition) in tile_set.tiles.iter().enumerate() {
+        let Some(texture) = tile_texture(definition) else {
+            continue;
+        };
+        let page = texture.key();
+        let sampler = match samplers.entry(page) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                match texture.state().data().and_then(|t| TexelSampler::new(t)) {
+                    Some(sampler) => entry.insert(sampler),
+                    None => continue,
+                }
+            }
+        };
+
+        let region = uv_rect_to_region(page, &definition.uv_rect, sampler);
+        let region_index = *region_indices.entry(region).or_insert_with(|| {
+            regions.push(region);
+            regions.len() - 1
+        });
+        tile_regions.push((tile_index, region_index));
+    }
+
+    if regions.is_empty() {
+        return Err(TileSetAtlasError::NothingToRepack);
+    }
+
+    // Taller regions first, it gives denser packing.
+    let mut order = (0..regions.len()).collect::<Vec<_>>();
+    order.sort_by_key(|i| std::cmp::Reverse((regions[*i].size.y, regions[*i].size.x)));
+    let sorted_regions = order.iter().map(|i| regions[*i]).collect::<Vec<_>>();
+
+    let padding = options.padding;
+    let area = sorted_regions
+        .iter()
+        .map(|r| ((r.size.x + padding * 2) * (r.size.y + padding * 2)) as f32)
+        .sum::<f32>();
+    let mut size = (area.sqrt().ceil() as u32).max(1).next_power_of_two();
+
+    let positions = loop {
+        if size > options.max_size {
+            return Err(TileSetAtlasError::TooLarge {
+                max_size: options.max_size,
+            });
+        }
+        if let Some(positions) = pack(&sorted_regions, padding, size) {
+            break positions;
+        }
+        size *= 2;
+    };
+
+    let mut atlas_positions = vec![Vector2::default(); regions.len()];
+    for (sorted_index, region_index) in order.iter().enumerate() {
+        atlas_positions[*region_index] = positions[sorted_index];
+    }
+
+    let mut bytes = vec![0u8; (size * size * 4) as usize];
+    for (region, atlas_position) in regions.iter().zip(atlas_positions.iter()) {
+        let sampler = &samplers[&region.page];
+        let padded_size = region.size + Vector2::repeat(padding * 2);
+        for y in 0..padded_size.y {
+            for x in 0..padded_size.x {
+                // Padding is filled with the edge pixels of the region (extrusion).
+                let src_x = region.position.x + x.saturating_sub(padding).min(region.size.x - 1);
+                let src_y = region.position.y + y.saturating_sub(padding).min(region.size.y - 1);
+                let color = sampler.pixel(src_x, src_y);
+                let offset = (((atlas_position.y + y) * size + atlas_position.x + x) * 4) as usize;
+                bytes[offset..offset + 4].copy_from_slice(&[color.r, color.g, color.b, color.a]);
+            }
+        }
+    }
+
+    let texture = Texture::from_bytes(
+        TextureKind::Rectangle {
+            width: size,
+            height: size,
+        },
+        TexturePixelKind::RGBA8,
+        bytes,
+    )
+    .expect("atlas size must match its data");
+
+    let mut tiles = tile_set.tiles.clone();
+    let mut repacked = Vec::with_capacity(tile_regions.len());
+    for (tile_index, region_index) in tile_regions {
+        let region = &regions[region_index];
+        let position = atlas_positions[region_index] + Vector2::repeat(padding);
+        tiles[tile_index].uv_rect = Rect::new(
+            position.x as f32 / size as f32,
+            position.y as f32 / size as f32,
+            region.size.x as f32 / size as f32,
+            region.size.y as f32 / size as f32,
+        );
+        repacked.push(tile_index);
+    }
+
+    Ok(TileSetAtlas {
+        texture,
+        tiles,
+        repacked,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::{untyped::ResourceKind, Resource},
+        core::{math::Rect, sstorage::ImmutableString},
+        material::{Material, MaterialResource},
+        resource::texture::{Texture, TextureKind, TexturePixelKind},
+        scene::tilemap::{
+            atlas::{repack_atlas, TileSetAtlasError, TileSetAtlasOptions},
+            tileset::{TileDefinition, TileSet},
+        },
+    };
+
+    fn make_page(width: u32, height: u32, fill: impl Fn(u32, u32) -> [u8; 4]) -> MaterialResource {
+        let mut bytes = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                bytes.extend_from_slice(&fill(x, y));
+            }
+        }
+        let texture = Texture::from_bytes(
+            TextureKind::Rectangle { width, height },
+            TexturePixelKind::RGBA8,
+            bytes,
+        )
+        .unwrap();
+        let mut material = Material::standard_2d();
+        material
+            .set_texture(
+                &ImmutableString::new("diffuseTexture"),
+                Some(Resource::new_ok(ResourceKind::Embedded, texture)),
+            )
+            .unwrap();
+        MaterialResource::new_ok(ResourceKind::Embedded, material)
+    }
+
+    fn tile(material: &MaterialResource, uv_rect: Rect<f32>) -> TileDefinition {
+        TileDefinition {
+            material: material.clone(),
+            uv_rect,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_repack_atlas() {
+        // Left half of the first page is red, right half is green.
+        let page_a = make_page(4, 2, |x, _| {
+            if x < 2 {
+                [255, 0, 0, 255]
+            } else {
+                [0, 255, 0, 255]
+            }
+        });
+        let page_b = make_page(2, 2, |_, _| [0, 0, 255, 255]);
+
+        let tile_set = TileSet {
+            tiles: vec![
+                tile(&page_a, Rect::new(0.0, 0.0, 0.5, 1.0)),
+                tile(&page_a, Rect::new(0.5, 0.0, 0.5, 1.0)),
+                tile(&page_b, Rect::new(0.0, 0.0, 1.0, 1.0)),
+                // Same region as the first tile, must not take extra space.
+                tile(&page_a, Rect::new(0.0, 0.0, 0.5, 1.0)),
+                // No texture, must be left untouched.
+                TileDefinition::default(),
+            ],
+            ..Default::default()
+        };
+
+        let options = TileSetAtlasOptions {
+            padding: 1,
+            max_size: 64,
+        };
+        let atlas = repack_atlas(&tile_set, &options).unwrap();
+        assert_eq!(atlas.repacked, vec![0, 1, 2, 3]);
+        assert_eq!(atlas.tiles[0].uv_rect, atlas.tiles[3].uv_rect);
+        assert_eq!(atlas.tiles[4].uv_rect, TileDefinition::default().uv_rect);
+
+        let TextureKind::Rectangle { width, height } = atlas.texture.kind() else {
+            unreachable!()
+        };
+        // Three 4x4 padded regions.
+        assert_eq!((width, height), (8, 8));
+
+        let bytes = atlas.texture.mip_level_data(0).to_vec();
+        let pixel = |x: u32, y: u32| {
+            let offset = ((y * width + x) * 4) as usize;
+            [
+                bytes[offset],
+                bytes[offset + 1],
+                bytes[offset + 2],
+                bytes[offset + 3],
+            ]
+        };
+        for (index, expected) in [
+            (0, [255, 0, 0, 255]),
+            (1, [0, 255, 0, 255]),
+            (2, [0, 0, 255, 255]),
+        ] {
+            let rect = atlas.tiles[index].uv_rect;
+            let x = (rect.position.x * width as f32) as u32;
+            let y = (rect.position.y * height as f32) as u32;
+            assert_eq!(rect.size.x * width as f32, 2.0);
+            assert_eq!(pixel(x, y), expected);
+            // Padding must be extruded.
+            assert_eq!(pixel(x - 1, y - 1), expected);
+        }
+
+        let tiles = atlas.into_tiles(Resource::new_ok(ResourceKind::Embedded, Texture::default()));
+        assert_eq!(tiles[0].material, tiles[2].material);
+        assert_ne!(tiles[0].material, tiles[4].material);
+    }
+
+    #[test]
+    fn test_repack_atlas_errors() {
+        let tile_set = TileSet {
+            tiles: vec![TileDefinition::default()],
+            ..Default::default()
+        };
+        assert_eq!(
+            repack_atlas(&tile_set, &Default::default()).err(),
+            Some(TileSetAtlasError::NothingToRepack)
+        );
+
+        let page = make_page(16, 16, |_, _| [255; 4]);
+        let tile_set = TileSet {
+            tiles: vec![tile(&page, Rect::new(0.0, 0.0, 1.0, 1.0))],
+            ..Default::default()
+        };
+        let options = TileSetAtlasOptions {
+            padding: 2,
+            max_size: 16,
+        };
+        assert_eq!(
+            repack_atlas(&tile_set, &options).err(),
+            Some(TileSetAtlasError::TooLarge { max_size: 16 })
+        );
+    }
+}
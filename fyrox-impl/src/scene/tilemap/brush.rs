@@ -0,0 +1,433 @@
+//! Brushes (stamps) for tile maps and symmetric drawing. See [`TileMapBrush`] and [`TileMapSymmetry`]
+//! docs for more info.
+
+use crate::rand::Rng;
+use crate::{
+    core::{
+        algebra::Vector2, reflect::prelude::*, type_traits::prelude::*, uuid_provider,
+        visitor::prelude::*,
+    },
+    scene::tilemap::Tile,
+};
+use fxhash::FxHashSet;
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// A single tile of a brush.
+#[derive(Clone, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "c3d9e1a2-7b4f-4e0d-a5c8-2f6b1e9d7a43")]
+pub struct BrushTile {
+    /// Position of the tile relative to the anchor (origin) of the brush.
+    pub position: Vector2<i32>,
+    /// Index of a tile definition in the tile set of a tile map.
+    pub definition_index: usize,
+    /// Relative probability of the tile to be picked in random mode (see [`TileMapBrush::random_tile`]).
+    /// For example, a tile with weight `3.0` is picked three times more often than a tile with weight
+    /// `1.0`. Tiles with zero weight are never picked.
+    #[reflect(min_value = 0.0)]
+    #[visit(optional)]
+    pub weight: f32,
+}
+
+impl Default for BrushTile {
+    fn default() -> Self {
+        Self {
+            position: Default::default(),
+            definition_index: 0,
+            weight: 1.0,
+        }
+    }
+}
+
+/// A brush (also known as a stamp) is a set of tiles, that is painted on a tile map at once.
+#[derive(Clone, Debug, Default, PartialEq, Reflect, Visit)]
+pub struct TileMapBrush {
+    /// Tiles of the brush.
+    pub tiles: Vec<BrushTile>,
+}
+
+impl TileMapBrush {
+    /// Creates a brush, that consists of a single tile.
+    pub fn single(definition_index: usize) -> Self {
+        Self {
+            tiles: vec![BrushTile {
+                definition_index,
+                ..Default::default()
+            }],
+        }
+    }
+
+    /// Picks a random tile of the brush, taking weights of the tiles into account. Returns `None` if
+    /// the brush is empty or if every tile of it has zero weight.
+    pub fn random_tile<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<&BrushTile> {
+        let total = self
+            .tiles
+            .iter()
+            .map(|tile| tile.weight.max(0.0))
+            .sum::<f32>();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut point = rng.gen_range(0.0..total);
+        let mut last = None;
+        for tile in self.tiles.iter().filter(|tile| tile.weight > 0.0) {
+            if point < tile.weight {
+                return Some(tile);
+            }
+            point -= tile.weight;
+            last = Some(tile);
+        }
+        // Rounding errors could leave a tiny remainder.
+        last
+    }
+}
+
+/// A transform of a brush. Flipping is applied first and then the brush is rotated around its anchor.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Reflect, Visit)]
+pub struct BrushTransform {
+    /// Mirrors the brush horizontally.
+    pub flip_x: bool,
+    /// Mirrors the brush vertically.
+    pub flip_y: bool,
+    /// Amount of counterclockwise quarter turns (90 degrees each).
+    #[reflect(min_value = 0.0, max_value = 3.0)]
+    pub rotation: u8,
+}
+
+impl BrushTransform {
+    /// Transforms the given position of a brush tile relative to the anchor of the brush.
+    pub fn transform(&self, mut position: Vector2<i32>) -> Vector2<i32> {
+        if self.flip_x {
+            position.x = -position.x;
+        }
+        if self.flip_y {
+            position.y = -position.y;
+        }
+        for _ in 0..self.rotation % 4 {
+            position = Vector2::new(-position.y, position.x);
+        }
+        position
+    }
+}
+
+/// Defines how strokes are replicated while drawing on a tile map.
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, Reflect, Visit, AsRefStr, EnumString, VariantNames,
+)]
+pub enum SymmetryMode {
+    /// No symmetry, strokes are painted as is.
+    #[default]
+    None,
+    /// Strokes are mirrored across the vertical axis, that goes through the origin.
+    MirrorX,
+    /// Strokes are mirrored across the horizontal axis, that goes through the origin.
+    MirrorY,
+    /// Strokes are mirrored across both axes, that go through the origin, giving four copies in total.
+    MirrorBoth,
+    /// Strokes are rotated by 180 degrees around the origin, giving two copies in total.
+    Rotational2,
+    /// Strokes are rotated by 90, 180 and 270 degrees around the origin, giving four copies in total.
+    Rotational4,
+}
+
+uuid_provider!(SymmetryMode = "6b2f0a8e-3c1d-4f57-9a4e-8d1c2b7e5f30");
+
+/// Symmetry settings of tile map drawing tools. Allows you to paint symmetric levels (arenas, for
+/// example) only once, every stroke is replicated according to the [`SymmetryMode`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Reflect, Visit)]
+pub struct TileMapSymmetry {
+    /// Symmetry mode.
+    pub mode: SymmetryMode,
+    /// Origin of the symmetry in cells. Integer coordinates lie on borders of cells, so `(0.0, 0.0)`
+    /// defines symmetry between the cells `(-1, -1)` and `(0, 0)`, while `(0.5, 0.5)` defines symmetry
+    /// around the center of the cell `(0, 0)`.
+    pub origin: Vector2<f32>,
+}
+
+impl TileMapSymmetry {
+    /// Returns positions of every image of the given cell, including the cell itself (it always comes
+    /// first). Duplicates (cells that lie on the symmetry axes) are removed.
+    pub fn images(&self, cell: Vector2<i32>) -> Vec<Vector2<i32>> {
+        let o = self.origin;
+        let c = Vector2::new(cell.x as f32 + 0.5 - o.x, cell.y as f32 + 0.5 - o.y);
+        let offsets: &[Vector2<f32>] = match self.mode {
+            SymmetryMode::None => &[c],
+            SymmetryMode::MirrorX => &[c, Vector2::new(-c.x, c.y)],
+            SymmetryMode::MirrorY => &[c, Vector2::new(c.x, -c.y)],
+            SymmetryMode::MirrorBoth => &[
+                c,
+                Vector2::new(-c.x, c.y),
+                Vector2::new(c.x, -c.y),
+                Vector2::new(-c.x, -c.y),
+            ],
+            SymmetryMode::Rotational2 => &[c, Vector2::new(-c.x, -c.y)],
+            SymmetryMode::Rotational4 => &[
+                c,
+                Vector2::new(-c.y, c.x),
+                Vector2::new(-c.x, -c.y),
+                Vector2::new(c.y, -c.x),
+            ],
+        };
+
+        let mut images = Vec::with_capacity(offsets.len());
+        for offset in offsets {
+            // Centers of the images are always in the middle of a cell (for integer and half-integer
+            // origins), so flooring is robust against rounding errors.
+            let image = Vector2::new(
+                (o.x + offset.x).floor() as i32,
+                (o.y + offset.y).floor() as i32,
+            );
+            if !images.contains(&image) {
+                images.push(image);
+            }
+        }
+        images
+    }
+}
+
+/// Creates a set of tiles, that should be painted when the given brush is stamped at the given position.
+/// The brush is transformed first and then every tile of it is replicated according to the symmetry
+/// settings, so mirrored strokes respect flipping and rotation of the brush. When several tiles are
+/// painted in the same cell, the first one wins (tiles of the original stroke have priority).
+pub fn stamp(
+    brush: &TileMapBrush,
+    transform: &BrushTransform,
+    symmetry: &TileMapSymmetry,
+    position: Vector2<i32>,
+) -> Vec<Tile> {
+    let mut painted = FxHashSet::default();
+    let mut tiles = Vec::new();
+    let mut push = |position: Vector2<i32>, definition_index: usize| {
+        if painted.insert(position) {
+            tiles.push(Tile::new(position, definition_index));
+        }
+    };
+
+    let cells = brush
+        .tiles
+        .iter()
+        .map(|tile| {
+            (
+                symmetry.images(position + transform.transform(tile.position)),
+                tile.definition_index,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // Original stroke first, then every image in order.
+    for (images, definition_index) in cells.iter() {
+        push(images[0], *definition_index);
+    }
+    for (images, definition_index) in cells.iter() {
+        for image in images.iter().skip(1) {
+            push(*image, *definition_index);
+        }
+    }
+
+    tiles
+}
+
+/// Same as [`stamp`], but paints a single randomly picked tile of the brush (see
+/// [`TileMapBrush::random_tile`]) at the given position. Mirrored images use the same tile.
+pub fn random_stamp<R: Rng + ?Sized>(
+    brush: &TileMapBrush,
+    symmetry: &TileMapSymmetry,
+    position: Vector2<i32>,
+    rng: &mut R,
+) -> Vec<Tile> {
+    brush
+        .random_tile(rng)
+        .map(|tile| {
+            symmetry
+                .images(position)
+                .into_iter()
+                .map(|image| Tile::new(image, tile.definition_index))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        rand::{rngs::StdRng, SeedableRng},
+        scene::tilemap::brush::{
+            random_stamp, stamp, BrushTile, BrushTransform, SymmetryMode, TileMapBrush,
+            TileMapSymmetry,
+        },
+    };
+
+    fn positions(
+        brush: &TileMapBrush,
+        transform: &BrushTransform,
+        symmetry: &TileMapSymmetry,
+        position: Vector2<i32>,
+    ) -> Vec<(i32, i32)> {
+        let mut positions = stamp(brush, transform, symmetry, position)
+            .iter()
+            .map(|t| (t.position().x, t.position().y))
+            .collect::<Vec<_>>();
+        positions.sort();
+        positions
+    }
+
+    #[test]
+    fn test_brush_transform() {
+        let p = Vector2::new(2, 1);
+        assert_eq!(BrushTransform::default().transform(p), p);
+        let flip = BrushTransform {
+            flip_x: true,
+            ..Default::default()
+        };
+        assert_eq!(flip.transform(p), Vector2::new(-2, 1));
+        let rotate = BrushTransform {
+            rotation: 1,
+            ..Default::default()
+        };
+        assert_eq!(rotate.transform(p), Vector2::new(-1, 2));
+        let rotate = BrushTransform {
+            rotation: 6,
+            ..Default::default()
+        };
+        assert_eq!(rotate.transform(p), Vector2::new(-2, -1));
+    }
+
+    #[test]
+    fn test_symmetry_images() {
+        let symmetry = TileMapSymmetry {
+            mode: SymmetryMode::MirrorX,
+            origin: Vector2::new(0.0, 0.0),
+        };
+        assert_eq!(
+            symmetry.images(Vector2::new(0, 3)),
+            vec![Vector2::new(0, 3), Vector2::new(-1, 3)]
+        );
+
+        // A cell on the axis has no separate image.
+        let symmetry = TileMapSymmetry {
+            mode: SymmetryMode::MirrorBoth,
+            origin: Vector2::new(0.5, 0.5),
+        };
+        assert_eq!(
+            symmetry.images(Vector2::new(0, 0)),
+            vec![Vector2::new(0, 0)]
+        );
+        assert_eq!(symmetry.images(Vector2::new(0, 2)).len(), 2);
+
+        let symmetry = TileMapSymmetry {
+            mode: SymmetryMode::Rotational4,
+            origin: Vector2::new(0.0, 0.0),
+        };
+        assert_eq!(
+            symmetry.images(Vector2::new(1, 0)),
+            vec![
+                Vector2::new(1, 0),
+                Vector2::new(-1, 1),
+                Vector2::new(-2, -1),
+                Vector2::new(0, -2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stamp_respects_transform() {
+        // An L-shaped brush: ##
+        //                    #
+        let brush = TileMapBrush {
+            tiles: vec![
+                BrushTile {
+                    position: Vector2::new(0, 0),
+                    definition_index: 0,
+                    ..Default::default()
+                },
+                BrushTile {
+                    position: Vector2::new(1, 0),
+                    definition_index: 1,
+                    ..Default::default()
+                },
+                BrushTile {
+                    position: Vector2::new(0, -1),
+                    definition_index: 2,
+                    ..Default::default()
+                },
+            ],
+        };
+        let transform = BrushTransform {
+            flip_x: true,
+            ..Default::default()
+        };
+        let symmetry = TileMapSymmetry {
+            mode: SymmetryMode::MirrorX,
+            origin: Vector2::new(0.0, 0.0),
+        };
+
+        // Mirrored copy of a flipped brush is the non-flipped brush stamped at the mirrored anchor.
+        let mirrored = positions(&brush, &transform, &symmetry, Vector2::new(5, 0));
+        let mut expected = positions(
+            &brush,
+            &transform,
+            &TileMapSymmetry::default(),
+            Vector2::new(5, 0),
+        );
+        expected.extend(positions(
+            &brush,
+            &BrushTransform::default(),
+            &TileMapSymmetry::default(),
+            Vector2::new(-6, 0),
+        ));
+        expected.sort();
+        assert_eq!(mirrored, expected);
+
+        // Overlapping images must not produce duplicates and the original stroke wins.
+        let tiles = stamp(
+            &TileMapBrush::single(3),
+            &BrushTransform::default(),
+            &TileMapSymmetry {
+                mode: SymmetryMode::Rotational4,
+                origin: Vector2::new(0.5, 0.5),
+            },
+            Vector2::new(0, 0),
+        );
+        assert_eq!(tiles.len(), 1);
+        assert_eq!(tiles[0].definition_index(), 3);
+    }
+
+    #[test]
+    fn test_random_tile_weights() {
+        let tile = |definition_index, weight| BrushTile {
+            definition_index,
+            weight,
+            ..Default::default()
+        };
+        let brush = TileMapBrush {
+            tiles: vec![tile(0, 9.0), tile(1, 1.0), tile(2, 0.0)],
+        };
+
+        let mut rng = StdRng::seed_from_u64(123);
+        let mut counts = [0; 3];
+        for _ in 0..10000 {
+            counts[brush.random_tile(&mut rng).unwrap().definition_index] += 1;
+        }
+        assert_eq!(counts[2], 0);
+        assert!(counts[0] > 8500 && counts[0] < 9500, "{counts:?}");
+        assert!(counts[1] > 500 && counts[1] < 1500, "{counts:?}");
+
+        let empty = TileMapBrush {
+            tiles: vec![tile(0, 0.0)],
+        };
+        assert!(empty.random_tile(&mut rng).is_none());
+
+        let tiles = random_stamp(
+            &brush,
+            &TileMapSymmetry {
+                mode: SymmetryMode::MirrorX,
+                origin: Vector2::new(0.0, 0.0),
+            },
+            Vector2::new(2, 0),
+            &mut rng,
+        );
+        assert_eq!(tiles.len(), 2);
+        assert_eq!(tiles[0].definition_index(), tiles[1].definition_index());
+    }
+}
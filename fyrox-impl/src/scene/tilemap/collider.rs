@@ -0,0 +1,247 @@
+//! Merged colliders of tile maps. Instead of one collider per tile, solid tiles of every chunk are
+//! merged into a few rectangles using greedy rectangle merging. See [`merge_solid_tiles`] docs for more
+//! info.
+
+use crate::core::algebra::Vector2;
+use fxhash::{FxHashMap, FxHashSet};
+
+/// A rectangle of solid cells of a tile map.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TileRect {
+    /// Position of the bottom-left cell of the rectangle.
+    pub position: Vector2<i32>,
+    /// Size of the rectangle in cells.
+    pub size: Vector2<i32>,
+}
+
+impl TileRect {
+    /// Returns the center of the rectangle in the local space of its tile map.
+    pub fn center(&self) -> Vector2<f32> {
+        self.position.cast::<f32>() + self.size.cast::<f32>().scale(0.5)
+    }
+
+    /// Returns half extents of the rectangle in the local space of its tile map.
+    pub fn half_extents(&self) -> Vector2<f32> {
+        self.size.cast::<f32>().scale(0.5)
+    }
+}
+
+/// Returns the position of a chunk of the given size, that contains a cell at the given position.
+pub fn chunk_position(cell_position: Vector2<i32>, chunk_size: u32) -> Vector2<i32> {
+    let size = chunk_size.max(1) as i32;
+    Vector2::new(
+        cell_position.x.div_euclid(size),
+        cell_position.y.div_euclid(size),
+    )
+}
+
+/// Merges solid cells of a chunk into a set of non-overlapping rectangles. The algorithm is greedy: it
+/// scans the chunk row by row and every time it finds a cell, that is not covered yet, it first extends
+/// a new rectangle as far as possible to the right and then as far as possible upwards. The result is not
+/// guaranteed to be minimal, but it is usually very close to it and the algorithm is linear in the
+/// amount of cells of the chunk.
+pub fn merge_solid_tiles(
+    solid: &FxHashSet<Vector2<i32>>,
+    chunk_position: Vector2<i32>,
+    chunk_size: u32,
+) -> Vec<TileRect> {
+    let size = chunk_size.max(1) as i32;
+    let origin = chunk_position * size;
+    let index = |x: i32, y: i32| (y * size + x) as usize;
+    let is_free = |covered: &[bool], x: i32, y: i32| {
+        !covered[index(x, y)] && solid.contains(&(origin + Vector2::new(x, y)))
+    };
+
+    let mut covered = vec![false; (size * size) as usize];
+    let mut rects = Vec::new();
+
+    for y in 0..size {
+        for x in 0..size {
+            if !is_free(&covered, x, y) {
+                continue;
+            }
+
+            let mut width = 1;
+            while x + width < size && is_free(&covered, x + width, y) {
+                width += 1;
+            }
+
+            let mut height = 1;
+            while y + height < size && (x..x + width).all(|cx| is_free(&covered, cx, y + height)) {
+                height += 1;
+            }
+
+            for cy in y..y + height {
+                for cx in x..x + width {
+                    covered[index(cx, cy)] = true;
+                }
+            }
+
+            rects.push(TileRect {
+                position: origin + Vector2::new(x, y),
+                size: Vector2::new(width, height),
+            });
+        }
+    }
+
+    rects
+}
+
+/// Merged rectangles of every chunk of a tile map. Chunks are re-merged only when they're marked as
+/// dirty, so editing a few tiles does not cause the whole tile map to be processed again.
+#[derive(Default, Clone, Debug)]
+pub(super) struct TileMapColliderCache {
+    chunks: FxHashMap<Vector2<i32>, Vec<TileRect>>,
+    dirty: FxHashSet<Vector2<i32>>,
+    all_dirty: bool,
+    tile_set_key: Option<u64>,
+    chunk_size: u32,
+    revision: u64,
+}
+
+impl TileMapColliderCache {
+    pub(super) fn mark_dirty(&mut self, cell_position: Vector2<i32>) {
+        self.dirty
+            .insert(chunk_position(cell_position, self.chunk_size));
+    }
+
+    pub(super) fn mark_all_dirty(&mut self) {
+        self.all_dirty = true;
+    }
+
+    /// Re-merges dirty chunks and returns the revision of the rectangles. The revision changes only when
+    /// at least one chunk was re-merged.
+    pub(super) fn update<F>(&mut self, tile_set_key: Option<u64>, chunk_size: u32, solid: F) -> u64
+    where
+        F: FnOnce() -> FxHashSet<Vector2<i32>>,
+    {
+        let chunk_size = chunk_size.max(1);
+
+        if self.all_dirty || self.tile_set_key != tile_set_key || self.chunk_size != chunk_size {
+            let solid = solid();
+
+            self.chunks.clear();
+            self.dirty.clear();
+            self.all_dirty = false;
+            self.tile_set_key = tile_set_key;
+            self.chunk_size = chunk_size;
+
+            let chunks = solid
+                .iter()
+                .map(|position| chunk_position(*position, chunk_size))
+                .collect::<FxHashSet<_>>();
+            for chunk in chunks {
+                self.chunks
+                    .insert(chunk, merge_solid_tiles(&solid, chunk, chunk_size));
+            }
+
+            self.revision += 1;
+        } else if !self.dirty.is_empty() {
+            let solid = solid();
+
+            for chunk in self.dirty.drain() {
+                let rects = merge_solid_tiles(&solid, chunk, chunk_size);
+                if rects.is_empty() {
+                    self.chunks.remove(&chunk);
+                } else {
+                    self.chunks.insert(chunk, rects);
+                }
+            }
+
+            self.revision += 1;
+        }
+
+        self.revision
+    }
+
+    pub(super) fn rects(&self) -> impl Iterator<Item = &TileRect> {
+        self.chunks.values().flatten()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        scene::tilemap::collider::{merge_solid_tiles, TileMapColliderCache, TileRect},
+    };
+    use fxhash::FxHashSet;
+
+    fn covered_cells(rects: &[TileRect]) -> Vec<Vector2<i32>> {
+        let mut cells = Vec::new();
+        for rect in rects {
+            for y in 0..rect.size.y {
+                for x in 0..rect.size.x {
+                    cells.push(rect.position + Vector2::new(x, y));
+                }
+            }
+        }
+        cells.sort_by_key(|c| (c.x, c.y));
+        cells
+    }
+
+    #[test]
+    fn test_merge_solid_tiles() {
+        // ##..
+        // ####
+        // ####
+        let mut solid = FxHashSet::default();
+        for x in 0..4 {
+            solid.insert(Vector2::new(x, 0));
+            solid.insert(Vector2::new(x, 1));
+        }
+        solid.insert(Vector2::new(0, 2));
+        solid.insert(Vector2::new(1, 2));
+        // A cell of another chunk must be ignored.
+        solid.insert(Vector2::new(4, 0));
+
+        let rects = merge_solid_tiles(&solid, Vector2::new(0, 0), 4);
+        assert_eq!(
+            rects,
+            vec![
+                TileRect {
+                    position: Vector2::new(0, 0),
+                    size: Vector2::new(4, 2)
+                },
+                TileRect {
+                    position: Vector2::new(0, 2),
+                    size: Vector2::new(2, 1)
+                }
+            ]
+        );
+
+        let mut expected = solid
+            .iter()
+            .filter(|c| c.x < 4)
+            .cloned()
+            .collect::<Vec<_>>();
+        expected.sort_by_key(|c| (c.x, c.y));
+        assert_eq!(covered_cells(&rects), expected);
+    }
+
+    #[test]
+    fn test_incremental_update() {
+        let mut solid = FxHashSet::default();
+        solid.insert(Vector2::new(0, 0));
+        solid.insert(Vector2::new(5, 0));
+
+        let mut cache = TileMapColliderCache::default();
+        let revision = cache.update(None, 4, || solid.clone());
+        assert_eq!(cache.rects().count(), 2);
+
+        // Nothing is dirty, the solid cells must not be requested.
+        assert_eq!(cache.update(None, 4, || unreachable!()), revision);
+
+        solid.insert(Vector2::new(1, 0));
+        cache.mark_dirty(Vector2::new(1, 0));
+        let new_revision = cache.update(None, 4, || solid.clone());
+        assert_ne!(new_revision, revision);
+        assert_eq!(cache.rects().count(), 2);
+        assert!(cache.rects().any(|r| r.size == Vector2::new(2, 1)));
+
+        solid.remove(&Vector2::new(5, 0));
+        cache.mark_dirty(Vector2::new(5, 0));
+        cache.update(None, 4, || solid.clone());
+        assert_eq!(cache.rects().count(), 1);
+    }
+}
@@ -0,0 +1,307 @@
+//! Raw tile grids, that could be used to exchange tile maps with external tools (level editors,
+//! procedural generators, spreadsheets, etc.). See [`TileGrid`] docs for more info.
+
+use crate::{core::algebra::Vector2, scene::tilemap::Tile};
+use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// A value of an empty cell of a grid.
+pub const EMPTY_CELL: i64 = -1;
+
+/// An error, that may occur during conversion of tile grids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TileGridError {
+    /// A value of a CSV cell is not an integer number.
+    Parse {
+        /// Line number (starting from 1).
+        line: usize,
+        /// The value, that cannot be parsed.
+        value: String,
+    },
+    /// A grid contains a handle, that is not in the mapping table.
+    UnknownHandle(i64),
+    /// A tile map contains a tile definition index, that is not in the mapping table.
+    UnmappedIndex(usize),
+}
+
+impl Display for TileGridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse { line, value } => {
+                write!(f, "Unable to parse {value} at line {line} as an integer.")
+            }
+            Self::UnknownHandle(handle) => {
+                write!(f, "There's no tile index for {handle} handle.")
+            }
+            Self::UnmappedIndex(index) => {
+                write!(f, "There's no handle for {index} tile index.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TileGridError {}
+
+/// Maps a handle (an arbitrary number used by an external tool) to an index of a tile definition in a
+/// tile set.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TileGridMapping {
+    /// A handle used by an external tool.
+    pub handle: i64,
+    /// Index of a tile definition in a tile set.
+    pub index: usize,
+}
+
+/// A rectangular grid of tile handles. Rows go from top to bottom, [`EMPTY_CELL`] means that there's
+/// no tile in a cell. Handles are converted to tile definition indices using the mapping table, an empty
+/// table means that handles are tile definition indices.
+///
+/// The grid could be stored as JSON (using `serde`) with its origin and mapping table, or as plain
+/// CSV (see [`Self::to_csv`] and [`Self::from_csv`]), for example:
+///
+/// ```text
+/// -1,-1,3
+/// 0,1,2
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TileGrid {
+    /// Position of the bottom-left cell of the grid in a tile map.
+    pub origin: [i32; 2],
+    /// Rows of handles from top to bottom.
+    pub rows: Vec<Vec<i64>>,
+    /// Handle-to-index mapping table.
+    #[serde(default)]
+    pub mapping: Vec<TileGridMapping>,
+}
+
+impl TileGrid {
+    /// Creates a grid, that covers the bounding rectangle of the given tiles. Tile definition indices
+    /// are converted to handles using the given mapping table (see [`TileGrid`] docs).
+    pub fn from_tiles(
+        tiles: &[Tile],
+        mapping: Vec<TileGridMapping>,
+    ) -> Result<Self, TileGridError> {
+        let Some(first) = tiles.first() else {
+            return Ok(Self {
+                mapping,
+                ..Default::default()
+            });
+        };
+
+        let (min, max) = tiles
+            .iter()
+            .fold((first.position(), first.position()), |(min, max), tile| {
+                (min.inf(&tile.position()), max.sup(&tile.position()))
+            });
+        let size = max - min + Vector2::repeat(1);
+
+        let handles = mapping
+            .iter()
+            .map(|m| (m.index, m.handle))
+            .collect::<FxHashMap<_, _>>();
+
+        let mut rows = vec![vec![EMPTY_CELL; size.x as usize]; size.y as usize];
+        for tile in tiles {
+            let handle = if mapping.is_empty() {
+                tile.definition_index() as i64
+            } else {
+                *handles
+                    .get(&tile.definition_index())
+                    .ok_or(TileGridError::UnmappedIndex(tile.definition_index()))?
+            };
+            let cell = tile.position() - min;
+            rows[(size.y - 1 - cell.y) as usize][cell.x as usize] = handle;
+        }
+
+        Ok(Self {
+            origin: [min.x, min.y],
+            rows,
+            mapping,
+        })
+    }
+
+    /// Converts the grid to a set of tiles. Handles are converted to tile definition indices using the
+    /// mapping table (see [`TileGrid`] docs).
+    pub fn to_tiles(&self) -> Result<Vec<Tile>, TileGridError> {
+        let indices = self
+            .mapping
+            .iter()
+            .map(|m| (m.handle, m.index))
+            .collect::<FxHashMap<_, _>>();
+
+        let height = self.rows.len() as i32;
+        let mut tiles = Vec::new();
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let y = self.origin[1] + height - 1 - row_index as i32;
+            for (column, &handle) in row.iter().enumerate() {
+                if handle == EMPTY_CELL {
+                    continue;
+                }
+                let index = if self.mapping.is_empty() {
+                    usize::try_from(handle).map_err(|_| TileGridError::UnknownHandle(handle))?
+                } else {
+                    *indices
+                        .get(&handle)
+                        .ok_or(TileGridError::UnknownHandle(handle))?
+                };
+                tiles.push(Tile::new(
+                    Vector2::new(self.origin[0] + column as i32, y),
+                    index,
+                ));
+            }
+        }
+        Ok(tiles)
+    }
+
+    /// Writes rows of the grid as CSV. The origin and the mapping table are not written.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        for row in self.rows.iter() {
+            let line = row
+                .iter()
+                .map(|handle| handle.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            csv.push_str(&line);
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Reads rows of a grid from CSV. Both `,` and `;` are accepted as separators, empty lines are
+    /// ignored and empty values are treated as empty cells. Rows could have different lengths.
+    pub fn from_csv(
+        csv: &str,
+        origin: Vector2<i32>,
+        mapping: Vec<TileGridMapping>,
+    ) -> Result<Self, TileGridError> {
+        Ok(Self {
+            origin: [origin.x, origin.y],
+            rows: parse_csv(csv)?,
+            mapping,
+        })
+    }
+}
+
+/// Reads a mapping table from CSV with two columns: a handle and a tile definition index.
+pub fn mapping_from_csv(csv: &str) -> Result<Vec<TileGridMapping>, TileGridError> {
+    parse_csv(csv)?
+        .into_iter()
+        .enumerate()
+        .filter(|(_, row)| !row.is_empty())
+        .map(|(line, row)| match row.as_slice() {
+            [handle, index] if *index >= 0 => Ok(TileGridMapping {
+                handle: *handle,
+                index: *index as usize,
+            }),
+            _ => Err(TileGridError::Parse {
+                line: line + 1,
+                value: row
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            }),
+        })
+        .collect()
+}
+
+fn parse_csv(csv: &str) -> Result<Vec<Vec<i64>>, TileGridError> {
+    csv.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_index, line)| {
+            line.split([',', ';'])
+                .map(|value| {
+                    let value = value.trim();
+                    if value.is_empty() {
+                        Ok(EMPTY_CELL)
+                    } else {
+                        value.parse::<i64>().map_err(|_| TileGridError::Parse {
+                            line: line_index + 1,
+                            value: value.to_string(),
+                        })
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        scene::tilemap::{
+            grid::{mapping_from_csv, TileGrid, TileGridError, TileGridMapping},
+            Tile,
+        },
+    };
+
+    fn sorted(mut tiles: Vec<Tile>) -> Vec<(i32, i32, usize)> {
+        let mut tiles = tiles
+            .drain(..)
+            .map(|t| (t.position().x, t.position().y, t.definition_index()))
+            .collect::<Vec<_>>();
+        tiles.sort();
+        tiles
+    }
+
+    #[test]
+    fn test_grid_round_trip() {
+        let tiles = vec![
+            Tile::new(Vector2::new(-1, 0), 0),
+            Tile::new(Vector2::new(0, 0), 1),
+            Tile::new(Vector2::new(1, 1), 2),
+        ];
+
+        let grid = TileGrid::from_tiles(&tiles, Vec::new()).unwrap();
+        assert_eq!(grid.origin, [-1, 0]);
+        assert_eq!(grid.to_csv(), "-1,-1,2\n0,1,-1\n");
+        assert_eq!(sorted(grid.to_tiles().unwrap()), sorted(tiles.clone()));
+
+        let csv = grid.to_csv();
+        let parsed = TileGrid::from_csv(&csv, Vector2::new(-1, 0), Vec::new()).unwrap();
+        assert_eq!(parsed, grid);
+
+        // Handles of an external tool.
+        let mapping = mapping_from_csv("100,0\n200,1\n300;2\n").unwrap();
+        assert_eq!(
+            mapping[1],
+            TileGridMapping {
+                handle: 200,
+                index: 1
+            }
+        );
+        let grid = TileGrid::from_tiles(&tiles, mapping.clone()).unwrap();
+        assert_eq!(grid.to_csv(), "-1,-1,300\n100,200,-1\n");
+        assert_eq!(sorted(grid.to_tiles().unwrap()), sorted(tiles));
+    }
+
+    #[test]
+    fn test_grid_errors() {
+        assert_eq!(
+            TileGrid::from_csv("1,2\n3,x", Vector2::default(), Vec::new()),
+            Err(TileGridError::Parse {
+                line: 2,
+                value: "x".to_string()
+            })
+        );
+
+        let mapping = vec![TileGridMapping {
+            handle: 7,
+            index: 0,
+        }];
+        let grid = TileGrid::from_csv("7,8", Vector2::default(), mapping.clone()).unwrap();
+        assert_eq!(grid.to_tiles(), Err(TileGridError::UnknownHandle(8)));
+        assert_eq!(
+            TileGrid::from_tiles(&[Tile::new(Vector2::default(), 1)], mapping),
+            Err(TileGridError::UnmappedIndex(1))
+        );
+
+        // Empty values are empty cells.
+        let grid = TileGrid::from_csv("0,,1\n\n", Vector2::default(), Vec::new()).unwrap();
+        assert_eq!(grid.to_tiles().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,492 @@
+//! Level-of-detail for tile maps. When a tile map is zoomed out far enough, every N×N cells of it are
+//! collapsed into a single quad with a pre-rendered (baked) texture. See [`TileMapLod`] docs for more info.
+
+use crate::{
+    asset::{untyped::ResourceKind, Resource},
+    core::{
+        algebra::{Matrix4, Vector2, Vector3},
+        color::Color,
+        reflect::prelude::*,
+        sstorage::ImmutableString,
+        visitor::prelude::*,
+    },
+    material::{Material, MaterialResource},
+    resource::texture::{Texture, TextureKind, TexturePixelKind, TextureResource},
+    scene::tilemap::{
+        tileset::{TileDefinition, TileSet},
+        Tile,
+    },
+};
+use fxhash::FxHashMap;
+
+/// Level-of-detail settings of a tile map. Large tile maps consist of thousands of tiles, each tile is a
+/// separate quad, which is fine when the camera is close to the map, because only a small part of it is
+/// visible. When the camera zooms out, more and more tiles become visible and most of them are just a
+/// few pixels in size. To keep rendering fast, the tile map splits itself into chunks of
+/// [`Self::chunk_size`] × [`Self::chunk_size`] cells and renders every chunk as a single quad with a
+/// texture, that contains pre-rendered tiles of the chunk.
+///
+/// Chunk textures are baked on CPU on first use, so diffuse textures of tile materials must have their
+/// pixels available on CPU (which is the default for textures loaded from files). Tiles with
+/// unsupported texture formats are baked using their color only.
+#[derive(Clone, Debug, PartialEq, Reflect, Visit)]
+pub struct TileMapLod {
+    /// A flag, that defines whether the level-of-detail is enabled or not.
+    #[reflect(description = "A flag, that defines whether the level-of-detail is enabled or not.")]
+    pub enabled: bool,
+
+    /// Amount of cells (along each axis) that will be collapsed into a single chunk.
+    #[reflect(
+        description = "Amount of cells (along each axis) that will be collapsed into a single chunk.",
+        min_value = 2.0
+    )]
+    pub chunk_size: u32,
+
+    /// Amount of texels (along each axis) of a chunk texture per single cell.
+    #[reflect(
+        description = "Amount of texels (along each axis) of a chunk texture per single cell. Higher \
+    values give sharper chunks, but take more memory.",
+        min_value = 1.0,
+        max_value = 64.0
+    )]
+    pub texels_per_cell: u32,
+
+    /// Screen-space size of a cell (as a fraction of the screen height) below which the chunks will be
+    /// used instead of separate tiles.
+    #[reflect(
+        description = "Screen-space size of a cell (as a fraction of the screen height) below which \
+    the chunks will be used instead of separate tiles.",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    pub screen_size_threshold: f32,
+}
+
+impl Default for TileMapLod {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            chunk_size: 16,
+            texels_per_cell: 4,
+            screen_size_threshold: 0.01,
+        }
+    }
+}
+
+impl TileMapLod {
+    /// Returns `true` if a cell with the given screen-space size (as a fraction of the screen height)
+    /// should be rendered as a part of a chunk.
+    pub fn is_active(&self, cell_screen_size: f32) -> bool {
+        self.enabled && self.chunk_size > 1 && cell_screen_size < self.screen_size_threshold
+    }
+
+    /// Returns the position of a chunk, that contains a cell at the given position.
+    pub fn chunk_position(&self, cell_position: Vector2<i32>) -> Vector2<i32> {
+        let size = self.chunk_size.max(1) as i32;
+        Vector2::new(
+            cell_position.x.div_euclid(size),
+            cell_position.y.div_euclid(size),
+        )
+    }
+}
+
+/// Calculates the screen-space size of a cell of a tile map (as a fraction of the screen height) using
+/// the given transform of the tile map and view and projection matrices of an observer.
+pub fn cell_screen_size(
+    global_transform: &Matrix4<f32>,
+    view_matrix: &Matrix4<f32>,
+    projection_matrix: &Matrix4<f32>,
+) -> f32 {
+    let origin = global_transform.transform_point(&Default::default());
+    let cell_height = global_transform.transform_vector(&Vector3::y()).norm();
+
+    let view_origin = view_matrix.transform_point(&origin);
+    let view_top = view_origin + Vector3::new(0.0, cell_height, 0.0);
+
+    let ndc_origin = projection_matrix.transform_point(&view_origin);
+    let ndc_top = projection_matrix.transform_point(&view_top);
+
+    // Normalized device coordinates are in [-1; 1] range, so the screen height is 2.
+    let size = (ndc_top.y - ndc_origin.y).abs() * 0.5;
+    if size.is_finite() {
+        size
+    } else {
+        f32::MAX
+    }
+}
+
+/// A baked chunk of a tile map.
+#[derive(Clone, Debug)]
+pub struct TileMapChunk {
+    /// Position of the chunk in chunks (not in cells).
+    pub position: Vector2<i32>,
+    /// A material, that contains pre-rendered tiles of the chunk as a diffuse texture.
+    pub material: MaterialResource,
+}
+
+#[derive(Default, Clone, Debug)]
+pub(super) struct TileMapLodCache {
+    pub(super) chunks: Vec<TileMapChunk>,
+    tile_set_key: Option<u64>,
+    settings: Option<TileMapLod>,
+}
+
+impl TileMapLodCache {
+    pub(super) fn invalidate(&mut self) {
+        self.chunks.clear();
+        self.settings = None;
+    }
+
+    pub(super) fn is_valid(&self, tile_set_key: u64, settings: &TileMapLod) -> bool {
+        self.tile_set_key == Some(tile_set_key) && self.settings.as_ref() == Some(settings)
+    }
+
+    pub(super) fn rebuild(
+        &mut self,
+        tile_set_key: u64,
+        tile_set: &TileSet,
+        tiles: &[Tile],
+        settings: &TileMapLod,
+    ) {
+        self.chunks = bake_chunks(tile_set, tiles, settings);
+        self.tile_set_key = Some(tile_set_key);
+        self.settings = Some(settings.clone());
+    }
+}
+
+/// Bakes all non-empty chunks of the given tiles.
+pub fn bake_chunks(tile_set: &TileSet, tiles: &[Tile], settings: &TileMapLod) -> Vec<TileMapChunk> {
+    let mut chunk_tiles = FxHashMap::<Vector2<i32>, Vec<&Tile>>::default();
+    for tile in tiles {
+        chunk_tiles
+            .entry(settings.chunk_position(tile.position()))
+            .or_default()
+            .push(tile);
+    }
+
+    let diffuse_texture = ImmutableString::new("diffuseTexture");
+    let mut samplers = FxHashMap::default();
+
+    chunk_tiles
+        .into_iter()
+        .map(|(position, tiles)| {
+            let texture = bake_chunk_texture(
+                tile_set,
+                &tiles,
+                position,
+                settings,
+                &diffuse_texture,
+                &mut samplers,
+            );
+
+            let mut material = Material::standard_2d();
+            material
+                .set_texture(&diffuse_texture, Some(texture))
+                .expect("standard 2d material must have a diffuse texture");
+
+            TileMapChunk {
+                position,
+                material: MaterialResource::new_ok(ResourceKind::Embedded, material),
+            }
+        })
+        .collect()
+}
+
+pub(super) struct TexelSampler {
+    pub(super) width: u32,
+    pub(super) height: u32,
+    bytes_per_pixel: usize,
+    swizzle: bool,
+    bytes: Vec<u8>,
+}
+
+impl TexelSampler {
+    pub(super) fn new(texture: &Texture) -> Option<Self> {
+        let TextureKind::Rectangle { width, height } = texture.kind() else {
+            return None;
+        };
+        let (bytes_per_pixel, swizzle) = match texture.pixel_kind() {
+            TexturePixelKind::RGBA8 => (4, false),
+            TexturePixelKind::BGRA8 => (4, true),
+            TexturePixelKind::RGB8 => (3, false),
+            TexturePixelKind::BGR8 => (3, true),
+            _ => return None,
+        };
+        let bytes = texture.mip_level_data(0).to_vec();
+        if width == 0 || height == 0 || bytes.len() < (width * height) as usize * bytes_per_pixel {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            bytes_per_pixel,
+            swizzle,
+            bytes,
+        })
+    }
+
+    fn sample(&self, uv: Vector2<f32>) -> Color {
+        let x = ((uv.x * self.width as f32).floor() as i64).rem_euclid(self.width as i64) as u32;
+        let y = ((uv.y * self.height as f32).floor() as i64).rem_euclid(self.height as i64) as u32;
+        self.pixel(x, y)
+    }
+
+    pub(super) fn pixel(&self, x: u32, y: u32) -> Color {
+        let offset = (y as usize * self.width as usize + x as usize) * self.bytes_per_pixel;
+        let texel = &self.bytes[offset..offset + self.bytes_per_pixel];
+        let (r, b) = if self.swizzle {
+            (texel[2], texel[0])
+        } else {
+            (texel[0], texel[2])
+        };
+        let a = if self.bytes_per_pixel == 4 {
+            texel[3]
+        } else {
+            255
+        };
+        Color::from_rgba(r, texel[1], b, a)
+    }
+}
+
+fn tile_sampler<'a>(
+    definition: &TileDefinition,
+    diffuse_texture: &ImmutableString,
+    samplers: &'a mut FxHashMap<u64, Option<TexelSampler>>,
+) -> Option<&'a TexelSampler> {
+    let texture = definition
+        .material
+        .state()
+        .data()
+        .and_then(|material| material.property_ref(diffuse_texture).cloned())
+        .and_then(|property| property.as_sampler())?;
+
+    samplers
+        .entry(texture.key())
+        .or_insert_with(|| texture.state().data().and_then(|t| TexelSampler::new(t)))
+        .as_ref()
+}
+
+fn bake_chunk_texture(
+    tile_set: &TileSet,
+    tiles: &[&Tile],
+    chunk_position: Vector2<i32>,
+    settings: &TileMapLod,
+    diffuse_texture: &ImmutableString,
+    samplers: &mut FxHashMap<u64, Option<TexelSampler>>,
+) -> TextureResource {
+    let chunk_size = settings.chunk_size.max(1);
+    // Chunk quads use the same mirrored mapping as tiles.
+    render_tiles(
+        tile_set,
+        tiles,
+        chunk_position * chunk_size as i32,
+        Vector2::repeat(chunk_size),
+        settings.texels_per_cell,
+        true,
+        diffuse_texture,
+        samplers,
+    )
+}
+
+/// Renders a region of the given size (in cells) with the given origin (position of the bottom-left
+/// cell) into a texture. Tiles outside of the region are ignored. Rows of the texture go from top to
+/// bottom, columns go from left to right, unless `mirror_x` is set.
+#[allow(clippy::too_many_arguments)]
+fn render_tiles(
+    tile_set: &TileSet,
+    tiles: &[&Tile],
+    origin: Vector2<i32>,
+    size: Vector2<u32>,
+    texels_per_cell: u32,
+    mirror_x: bool,
+    diffuse_texture: &ImmutableString,
+    samplers: &mut FxHashMap<u64, Option<TexelSampler>>,
+) -> TextureResource {
+    let texels_per_cell = texels_per_cell.max(1);
+    let width = size.x.max(1) * texels_per_cell;
+    let height = size.y.max(1) * texels_per_cell;
+    let mut bytes = vec![0u8; (width * height * 4) as usize];
+
+    for tile in tiles {
+        let Some(definition) = tile_set.tiles.get(tile.definition_index()) else {
+            continue;
+        };
+        let cell = tile.position() - origin;
+        if cell.x < 0 || cell.y < 0 || cell.x as u32 >= size.x || cell.y as u32 >= size.y {
+            continue;
+        }
+        let cell = cell.map(|c| c as u32);
+        let sampler = tile_sampler(definition, diffuse_texture, samplers);
+        let uv_rect = &definition.uv_rect;
+
+        for sy in 0..texels_per_cell {
+            for sx in 0..texels_per_cell {
+                let local = Vector2::new(
+                    (sx as f32 + 0.5) / texels_per_cell as f32,
+                    (sy as f32 + 0.5) / texels_per_cell as f32,
+                );
+
+                // Tiles are mapped mirrored, see `TileMap::collect_render_data`.
+                let uv = Vector2::new(
+                    uv_rect.position.x + uv_rect.size.x * (1.0 - local.x),
+                    uv_rect.position.y + uv_rect.size.y * (1.0 - local.y),
+                );
+
+                let texel = sampler.map_or(Color::WHITE, |sampler| sampler.sample(uv));
+                let color =
+                    Color::from(texel.as_frgba().component_mul(&definition.color.as_frgba()));
+
+                let mut x = cell.x * texels_per_cell + sx;
+                if mirror_x {
+                    x = width - 1 - x;
+                }
+                let y = height - 1 - (cell.y * texels_per_cell + sy);
+                let offset = ((y * width + x) * 4) as usize;
+                bytes[offset..offset + 4].copy_from_slice(&[color.r, color.g, color.b, color.a]);
+            }
+        }
+    }
+
+    let texture = Texture::from_bytes(
+        TextureKind::Rectangle { width, height },
+        TexturePixelKind::RGBA8,
+        bytes,
+    )
+    .expect("tile texture size must match its data");
+
+    Resource::new_ok(ResourceKind::Embedded, texture)
+}
+
+/// Renders tiles of a region with the given origin (position of the bottom-left cell) and size (in
+/// cells) into a small preview texture, every cell takes `texels_per_cell`×`texels_per_cell` texels.
+/// Could be used to show thumbnails of tile maps or parts of them.
+pub fn render_preview(
+    tile_set: &TileSet,
+    tiles: &[Tile],
+    origin: Vector2<i32>,
+    size: Vector2<u32>,
+    texels_per_cell: u32,
+) -> TextureResource {
+    render_tiles(
+        tile_set,
+        &tiles.iter().collect::<Vec<_>>(),
+        origin,
+        size,
+        texels_per_cell,
+        false,
+        &ImmutableString::new("diffuseTexture"),
+        &mut Default::default(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::untyped::ResourceKind,
+        core::{algebra::Vector2, color::Color, math::Rect},
+        material::{Material, MaterialResource},
+        scene::tilemap::{
+            lod::{bake_chunks, render_preview, TileMapLod},
+            tileset::{TileDefinition, TileSet},
+            Tile,
+        },
+    };
+
+    #[test]
+    fn test_chunk_position() {
+        let lod = TileMapLod {
+            chunk_size: 4,
+            ..Default::default()
+        };
+        assert_eq!(lod.chunk_position(Vector2::new(0, 3)), Vector2::new(0, 0));
+        assert_eq!(lod.chunk_position(Vector2::new(4, 7)), Vector2::new(1, 1));
+        assert_eq!(
+            lod.chunk_position(Vector2::new(-1, -4)),
+            Vector2::new(-1, -1)
+        );
+        assert_eq!(lod.chunk_position(Vector2::new(-5, 0)), Vector2::new(-2, 0));
+    }
+
+    #[test]
+    fn test_bake_chunks() {
+        let tile_set = TileSet {
+            tiles: vec![TileDefinition {
+                material: MaterialResource::new_ok(ResourceKind::Embedded, Material::standard_2d()),
+                uv_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+                collider: Default::default(),
+                color: Color::RED,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let tiles = vec![
+            Tile::new(Vector2::new(0, 0), 0),
+            Tile::new(Vector2::new(1, 1), 0),
+            Tile::new(Vector2::new(5, 0), 0),
+        ];
+        let lod = TileMapLod {
+            chunk_size: 4,
+            texels_per_cell: 1,
+            ..Default::default()
+        };
+
+        let mut chunks = bake_chunks(&tile_set, &tiles, &lod);
+        chunks.sort_by_key(|chunk| chunk.position.x);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].position, Vector2::new(0, 0));
+        assert_eq!(chunks[1].position, Vector2::new(1, 0));
+
+        let mut material = chunks[0].material.state();
+        let texture = material
+            .data()
+            .unwrap()
+            .property_ref(&"diffuseTexture".into())
+            .unwrap()
+            .as_sampler()
+            .unwrap();
+        let texture = texture.data_ref();
+        let bytes = texture.mip_level_data(0);
+        let texel = |x: usize, y: usize| {
+            let offset = (y * 4 + x) * 4;
+            [
+                bytes[offset],
+                bytes[offset + 1],
+                bytes[offset + 2],
+                bytes[offset + 3],
+            ]
+        };
+        // Cells are mirrored, so the cell (0, 0) is at the texel (3, 3).
+        assert_eq!(texel(3, 3), [255, 0, 0, 255]);
+        assert_eq!(texel(2, 2), [255, 0, 0, 255]);
+        assert_eq!(texel(0, 0), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_render_preview() {
+        let tile_set = TileSet {
+            tiles: vec![TileDefinition {
+                uv_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+                color: Color::GREEN,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let tiles = vec![
+            Tile::new(Vector2::new(0, 0), 0),
+            Tile::new(Vector2::new(2, 1), 0),
+            // Outside of the region.
+            Tile::new(Vector2::new(3, 0), 0),
+        ];
+
+        let preview = render_preview(&tile_set, &tiles, Vector2::new(0, 0), Vector2::new(3, 2), 1);
+        let preview = preview.data_ref();
+        let bytes = preview.mip_level_data(0);
+        assert_eq!(bytes.len(), 3 * 2 * 4);
+        let alpha = |x: usize, y: usize| bytes[(y * 3 + x) * 4 + 3];
+        // The first row of the preview is the top row of the region.
+        assert_eq!(alpha(0, 1), 255);
+        assert_eq!(alpha(2, 0), 255);
+        assert_eq!(alpha(0, 0), 0);
+        assert_eq!(alpha(2, 1), 0);
+    }
+}
@@ -1,12 +1,20 @@
 #![allow(missing_docs)] // TODO
 
+pub mod atlas;
+pub mod brush;
+pub mod collider;
+pub mod grid;
+pub mod lod;
+pub mod stamp;
 pub mod tileset;
 
 use crate::{
     asset::untyped::ResourceKind,
     core::{
         algebra::{Vector2, Vector3},
+        color::Color,
         math::{aabb::AxisAlignedBoundingBox, Rect, TriangleDefinition},
+        parking_lot::Mutex,
         pool::Handle,
         reflect::prelude::*,
         type_traits::prelude::*,
@@ -17,15 +25,21 @@ use crate::{
     graph::BaseSceneGraph,
     material::{Material, MaterialResource},
     renderer::{self, bundle::RenderContext},
+    resource::texture::TextureResource,
     scene::{
         base::{Base, BaseBuilder},
         dim2::rectangle::RectangleVertex,
         graph::Graph,
         mesh::{buffer::VertexTrait, RenderPath},
         node::{Node, NodeTrait, RdcControlFlow},
-        tilemap::tileset::{TileDefinition, TileSet, TileSetResource},
+        tilemap::{
+            collider::{TileMapColliderCache, TileRect},
+            lod::{cell_screen_size, TileMapLod, TileMapLodCache},
+            tileset::{TileCollider, TileDefinition, TileSet, TileSetResource},
+        },
     },
 };
+use fxhash::FxHashSet;
 use std::ops::{Deref, DerefMut};
 
 #[derive(Clone, Reflect, Default, Debug, PartialEq, Visit, ComponentProvider, TypeUuidProvider)]
@@ -35,6 +49,23 @@ pub struct Tile {
     definition_index: usize,
 }
 
+impl Tile {
+    pub fn new(position: Vector2<i32>, definition_index: usize) -> Self {
+        Self {
+            position,
+            definition_index,
+        }
+    }
+
+    pub fn position(&self) -> Vector2<i32> {
+        self.position
+    }
+
+    pub fn definition_index(&self) -> usize {
+        self.definition_index
+    }
+}
+
 #[derive(Clone, Reflect, Debug, Visit, ComponentProvider, TypeUuidProvider)]
 #[type_uuid(id = "aa9a3385-a4af-4faf-a69a-8d3af1a3aa67")]
 pub struct TileMap {
@@ -43,8 +74,37 @@ pub struct TileMap {
     #[reflect(read_only)]
     tiles: InheritableVariable<Vec<Tile>>,
     tile_scale: InheritableVariable<Vector2<f32>>,
+    #[visit(optional)]
+    #[reflect(setter = "set_lod")]
+    lod: InheritableVariable<TileMapLod>,
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_collider_chunk_size",
+        min_value = 1.0,
+        description = "Amount of cells (along each axis) of a chunk, which solid tiles are merged \
+    into a few rectangles of a tile map collider shape."
+    )]
+    collider_chunk_size: InheritableVariable<u32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    lod_cache: TileMapCache<TileMapLodCache>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collider_cache: TileMapCache<TileMapColliderCache>,
+}
+
+#[derive(Debug, Default)]
+struct TileMapCache<T>(Mutex<T>);
+
+impl<T: Clone> Clone for TileMapCache<T> {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.0.lock().clone()))
+    }
 }
 
+/// Default amount of cells (along each axis) of a chunk of merged colliders.
+pub const DEFAULT_COLLIDER_CHUNK_SIZE: u32 = 16;
+
 impl Default for TileMap {
     fn default() -> Self {
         Self {
@@ -52,10 +112,250 @@ impl Default for TileMap {
             tile_set: Default::default(),
             tiles: Default::default(),
             tile_scale: Vector2::repeat(1.0).into(),
+            lod: Default::default(),
+            collider_chunk_size: DEFAULT_COLLIDER_CHUNK_SIZE.into(),
+            lod_cache: Default::default(),
+            collider_cache: Default::default(),
         }
     }
 }
 
+impl TileMap {
+    pub fn tile_set(&self) -> Option<&TileSetResource> {
+        self.tile_set.as_ref()
+    }
+
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    /// Returns a tile at the given position (if any).
+    pub fn tile(&self, position: Vector2<i32>) -> Option<&Tile> {
+        self.tiles.iter().find(|tile| tile.position == position)
+    }
+
+    /// Sets new tiles of the tile map. Baked level-of-detail chunks and merged colliders will be
+    /// rebuilt on next use.
+    pub fn set_tiles(&mut self, tiles: Vec<Tile>) -> Vec<Tile> {
+        self.invalidate_lod();
+        self.collider_cache.0.lock().mark_all_dirty();
+        self.tiles.set_value_and_mark_modified(tiles)
+    }
+
+    /// Puts a tile with the given definition index at the given position, or removes a tile at the
+    /// position if the index is `None`. Returns a tile, that was at the position before. Only the chunk
+    /// that contains the position will be re-merged for tile map colliders.
+    pub fn set_tile(
+        &mut self,
+        position: Vector2<i32>,
+        definition_index: Option<usize>,
+    ) -> Option<Tile> {
+        self.invalidate_lod();
+        self.collider_cache.0.lock().mark_dirty(position);
+
+        let tiles = self.tiles.get_value_mut_and_mark_modified();
+        let index = tiles.iter().position(|tile| tile.position == position);
+        match (index, definition_index) {
+            (Some(index), Some(definition_index)) => Some(std::mem::replace(
+                &mut tiles[index],
+                Tile::new(position, definition_index),
+            )),
+            (Some(index), None) => Some(tiles.swap_remove(index)),
+            (None, Some(definition_index)) => {
+                tiles.push(Tile::new(position, definition_index));
+                None
+            }
+            (None, None) => None,
+        }
+    }
+
+    /// Switches a tile at the given position to an alternate definition, that represents the tile in
+    /// the given state (see [`tileset::TileStateMapping`] docs). Colliders and level-of-detail chunks are
+    /// updated the same way as with [`Self::set_tile`], properties of the tile are taken from the new
+    /// definition. Returns the index of the new definition or `None` if there's no tile at the position,
+    /// the tile set is not loaded or the tile does not have the state.
+    pub fn set_tile_state(&mut self, position: Vector2<i32>, state: &str) -> Option<usize> {
+        let definition_index = self.tile(position)?.definition_index;
+        let new_definition_index = {
+            let tile_set = self.tile_set.as_ref()?;
+            let mut tile_set_state = tile_set.state();
+            tile_set_state
+                .data()?
+                .state_variant(definition_index, state)?
+        };
+        self.set_tile(position, Some(new_definition_index));
+        Some(new_definition_index)
+    }
+
+    /// Sets new size (in cells along each axis) of chunks, which solid tiles are merged into a few
+    /// rectangles of tile map collider shapes.
+    pub fn set_collider_chunk_size(&mut self, size: u32) -> u32 {
+        self.collider_chunk_size
+            .set_value_and_mark_modified(size.max(1))
+    }
+
+    /// Returns current size (in cells along each axis) of chunks of merged colliders.
+    pub fn collider_chunk_size(&self) -> u32 {
+        *self.collider_chunk_size
+    }
+
+    /// Re-merges solid tiles of every chunk, that was changed since last call, and returns current
+    /// revision of the merged rectangles. The revision changes every time when at least one chunk was
+    /// re-merged, it could be used to check whether colliders, that use the tile map, must be rebuilt.
+    pub fn update_colliders(&self) -> u64 {
+        let tile_set_key = self
+            .tile_set
+            .as_ref()
+            .filter(|tile_set| tile_set.is_ok())
+            .map(|tile_set| tile_set.key());
+
+        self.collider_cache
+            .0
+            .lock()
+            .update(tile_set_key, *self.collider_chunk_size, || {
+                self.solid_tile_positions()
+            })
+    }
+
+    /// Returns merged rectangles of solid tiles of every chunk of the tile map. Every rectangle is
+    /// defined in the local space of the tile map. See [`collider::merge_solid_tiles`] docs for more
+    /// info.
+    pub fn collider_rectangles(&self) -> Vec<TileRect> {
+        self.update_colliders();
+        self.collider_cache.0.lock().rects().cloned().collect()
+    }
+
+    /// Sets new level-of-detail settings of the tile map. See [`TileMapLod`] docs for more info.
+    pub fn set_lod(&mut self, lod: TileMapLod) -> TileMapLod {
+        self.lod.set_value_and_mark_modified(lod)
+    }
+
+    /// Returns current level-of-detail settings of the tile map.
+    pub fn lod(&self) -> &TileMapLod {
+        &self.lod
+    }
+
+    /// Forces the tile map to rebake its level-of-detail chunks on next use. Chunks are rebaked
+    /// automatically when tiles, tile set or level-of-detail settings change, but not when the
+    /// contents of the tile set or its materials change - call this method in this case.
+    pub fn invalidate_lod(&self) {
+        self.lod_cache.0.lock().invalidate();
+    }
+
+    /// Renders a region of the tile map with the given origin (position of the bottom-left cell) and
+    /// size (in cells) into a small preview texture. See [`lod::render_preview`] docs for more info.
+    /// Returns `None` if the tile set of the tile map is not loaded.
+    pub fn render_preview(
+        &self,
+        origin: Vector2<i32>,
+        size: Vector2<u32>,
+        texels_per_cell: u32,
+    ) -> Option<TextureResource> {
+        let tile_set = self.tile_set.as_ref()?;
+        let mut state = tile_set.state();
+        let tile_set = state.data()?;
+        Some(lod::render_preview(
+            tile_set,
+            &self.tiles,
+            origin,
+            size,
+            texels_per_cell,
+        ))
+    }
+
+    fn collect_lod_render_data(
+        &self,
+        tile_set_key: u64,
+        tile_set: &TileSet,
+        ctx: &mut RenderContext,
+    ) {
+        let mut cache = self.lod_cache.0.lock();
+        if !cache.is_valid(tile_set_key, &self.lod) {
+            cache.rebuild(tile_set_key, tile_set, &self.tiles, &self.lod);
+        }
+
+        let global_transform = self.global_transform();
+        let sort_index = ctx.calculate_sorting_index(self.global_position());
+        let chunk_size = self.lod.chunk_size as f32;
+
+        for chunk in cache.chunks.iter() {
+            let origin = (chunk.position.cast::<f32>() * chunk_size).to_homogeneous();
+
+            if self.frustum_culling() {
+                let bounds = AxisAlignedBoundingBox::from_min_max(
+                    origin,
+                    origin + Vector3::new(chunk_size, chunk_size, 0.0),
+                )
+                .transform(&global_transform);
+                if !ctx.frustum.map_or(true, |f| f.is_intersects_aabb(&bounds)) {
+                    continue;
+                }
+            }
+
+            type Vertex = RectangleVertex;
+
+            let make_vertex = |offset: Vector3<f32>, tex_coord: Vector2<f32>| Vertex {
+                position: global_transform
+                    .transform_point(&(origin + offset * chunk_size).into())
+                    .coords,
+                tex_coord,
+                color: Color::WHITE,
+            };
+
+            let vertices = [
+                make_vertex(Vector3::new(0.0, 1.0, 0.0), Vector2::new(1.0, 0.0)),
+                make_vertex(Vector3::new(1.0, 1.0, 0.0), Vector2::new(0.0, 0.0)),
+                make_vertex(Vector3::new(1.0, 0.0, 0.0), Vector2::new(0.0, 1.0)),
+                make_vertex(Vector3::new(0.0, 0.0, 0.0), Vector2::new(1.0, 1.0)),
+            ];
+
+            let triangles = [TriangleDefinition([0, 1, 2]), TriangleDefinition([2, 3, 0])];
+
+            ctx.storage.push_triangles(
+                RectangleVertex::layout(),
+                &chunk.material,
+                RenderPath::Forward,
+                0,
+                sort_index,
+                false,
+                self.self_handle,
+                &mut move |mut vertex_buffer, mut triangle_buffer| {
+                    let start_vertex_index = vertex_buffer.vertex_count();
+
+                    for vertex in vertices.iter() {
+                        vertex_buffer
+                            .push_vertex_raw(value_as_u8_slice(vertex))
+                            .unwrap();
+                    }
+
+                    triangle_buffer
+                        .push_triangles_iter_with_offset(start_vertex_index, triangles.into_iter());
+                },
+            );
+        }
+    }
+
+    /// Returns positions of all tiles, that have rectangle colliders.
+    pub fn solid_tile_positions(&self) -> FxHashSet<Vector2<i32>> {
+        let Some(tile_set) = self.tile_set.as_ref().filter(|tile_set| tile_set.is_ok()) else {
+            return Default::default();
+        };
+
+        let tile_set = tile_set.data_ref();
+
+        self.tiles
+            .iter()
+            .filter(|tile| {
+                tile_set
+                    .tiles
+                    .get(tile.definition_index)
+                    .is_some_and(|definition| definition.collider == TileCollider::Rectangle)
+            })
+            .map(|tile| tile.position)
+            .collect()
+    }
+}
+
 impl Deref for TileMap {
     type Target = Base;
 
@@ -111,6 +411,15 @@ impl NodeTrait for TileMap {
 
         let tile_set = tile_set_resource.data_ref();
 
+        if self.lod.is_active(cell_screen_size(
+            &self.global_transform(),
+            ctx.view_matrix,
+            ctx.projection_matrix,
+        )) {
+            self.collect_lod_render_data(tile_set_resource.key(), &tile_set, ctx);
+            return RdcControlFlow::Continue;
+        }
+
         for tile in self.tiles.iter() {
             let Some(tile_definition) = tile_set.tiles.get(tile.definition_index) else {
                 continue;
@@ -189,6 +498,8 @@ pub struct TileMapBuilder {
     tile_set: Option<TileSetResource>,
     tiles: Vec<Tile>,
     tile_scale: Vector2<f32>,
+    lod: TileMapLod,
+    collider_chunk_size: u32,
 }
 
 impl TileMapBuilder {
@@ -200,7 +511,9 @@ impl TileMapBuilder {
                 uv_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
                 collider: Default::default(),
                 color: Default::default(),
+                ..Default::default()
             }],
+            ..Default::default()
         };
 
         let tile_set = Some(TileSetResource::new_ok(ResourceKind::Embedded, tile_set));
@@ -226,6 +539,8 @@ impl TileMapBuilder {
             tile_set,
             tiles,
             tile_scale: Vector2::repeat(1.0),
+            lod: Default::default(),
+            collider_chunk_size: DEFAULT_COLLIDER_CHUNK_SIZE,
         }
     }
 
@@ -244,13 +559,33 @@ impl TileMapBuilder {
         self
     }
 
-    pub fn build_node(self) -> Node {
-        Node::new(TileMap {
+    /// Sets desired level-of-detail settings of the tile map.
+    pub fn with_lod(mut self, lod: TileMapLod) -> Self {
+        self.lod = lod;
+        self
+    }
+
+    /// Sets desired size (in cells along each axis) of chunks of merged colliders.
+    pub fn with_collider_chunk_size(mut self, size: u32) -> Self {
+        self.collider_chunk_size = size.max(1);
+        self
+    }
+
+    pub fn build_tile_map(self) -> TileMap {
+        TileMap {
             base: self.base_builder.build_base(),
             tile_set: self.tile_set.into(),
             tiles: self.tiles.into(),
             tile_scale: self.tile_scale.into(),
-        })
+            lod: self.lod.into(),
+            collider_chunk_size: self.collider_chunk_size.into(),
+            lod_cache: Default::default(),
+            collider_cache: Default::default(),
+        }
+    }
+
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_tile_map())
     }
 
     pub fn build(self, graph: &mut Graph) -> Handle<Node> {
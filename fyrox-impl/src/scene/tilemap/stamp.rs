@@ -0,0 +1,156 @@
+//! Stamp library is a resource, that contains named brushes (stamps), that could be shared between
+//! tile maps and between people working on the same project. See [`TileMapStampLibrary`] docs for more
+//! info.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+        state::LoadError,
+        Resource, ResourceData,
+    },
+    core::{reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*},
+    scene::tilemap::{brush::TileMapBrush, tileset::TileSetResourceError},
+};
+use std::{
+    any::Any,
+    error::Error,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// A named brush of a stamp library.
+#[derive(Clone, Default, Debug, PartialEq, Reflect, Visit, TypeUuidProvider)]
+#[type_uuid(id = "5d0e4f7a-9b2c-4a61-8e3d-1c7f2b6a9e05")]
+pub struct TileMapStamp {
+    /// Unique name of the stamp.
+    pub name: String,
+    /// Tiles of the stamp.
+    pub brush: TileMapBrush,
+}
+
+/// A set of named stamps. Tile definition indices of stamps refer to a tile set, so a library is
+/// meant to be used with tile maps that share the same tile set.
+#[derive(Clone, Default, Debug, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "e2a7c5b1-4d8f-4f3a-b0e9-6a1d3c8f7b24")]
+pub struct TileMapStampLibrary {
+    /// Stamps of the library.
+    pub stamps: Vec<TileMapStamp>,
+}
+
+impl TileMapStampLibrary {
+    /// Tries to find a stamp with the given name.
+    pub fn find(&self, name: &str) -> Option<&TileMapStamp> {
+        self.stamps.iter().find(|stamp| stamp.name == name)
+    }
+
+    /// Adds a new stamp with the given name or replaces the brush of an existing one. Returns the old
+    /// brush of the stamp (if any).
+    pub fn set(&mut self, name: &str, brush: TileMapBrush) -> Option<TileMapBrush> {
+        match self.stamps.iter_mut().find(|stamp| stamp.name == name) {
+            Some(stamp) => Some(std::mem::replace(&mut stamp.brush, brush)),
+            None => {
+                self.stamps.push(TileMapStamp {
+                    name: name.to_string(),
+                    brush,
+                });
+                None
+            }
+        }
+    }
+
+    /// Removes a stamp with the given name and returns it (if any).
+    pub fn remove(&mut self, name: &str) -> Option<TileMapStamp> {
+        let index = self.stamps.iter().position(|stamp| stamp.name == name)?;
+        Some(self.stamps.remove(index))
+    }
+
+    /// Load a stamp library resource from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, TileSetResourceError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut library = TileMapStampLibrary::default();
+        library.visit("TileMapStampLibrary", &mut visitor)?;
+        Ok(library)
+    }
+}
+
+impl ResourceData for TileMapStampLibrary {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("TileMapStampLibrary", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+pub type TileMapStampLibraryResource = Resource<TileMapStampLibrary>;
+
+pub struct TileMapStampLibraryLoader;
+
+impl ResourceLoader for TileMapStampLibraryLoader {
+    fn extensions(&self) -> &[&str] {
+        &["stamps"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <TileMapStampLibrary as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let library = TileMapStampLibrary::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(library))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::visitor::prelude::*,
+        scene::tilemap::{brush::TileMapBrush, stamp::TileMapStampLibrary},
+    };
+
+    #[test]
+    fn test_stamp_library() {
+        let mut library = TileMapStampLibrary::default();
+        assert_eq!(library.set("Tree", TileMapBrush::single(1)), None);
+        assert_eq!(library.set("Rock", TileMapBrush::single(2)), None);
+        assert_eq!(
+            library.set("Tree", TileMapBrush::single(3)),
+            Some(TileMapBrush::single(1))
+        );
+        assert_eq!(library.stamps.len(), 2);
+        assert_eq!(library.find("Tree").unwrap().brush, TileMapBrush::single(3));
+
+        let mut visitor = Visitor::new();
+        library.visit("TileMapStampLibrary", &mut visitor).unwrap();
+        let bytes = visitor.save_binary_to_vec().unwrap();
+        let mut visitor = Visitor::load_from_memory(&bytes).unwrap();
+        let mut loaded = TileMapStampLibrary::default();
+        loaded.visit("TileMapStampLibrary", &mut visitor).unwrap();
+        assert_eq!(loaded.stamps, library.stamps);
+
+        assert!(library.remove("Rock").is_some());
+        assert!(library.find("Rock").is_none());
+    }
+}
@@ -87,15 +87,160 @@ pub struct TileDefinition {
     pub uv_rect: Rect<f32>,
     pub collider: TileCollider,
     pub color: Color,
+    /// A name of the tile, that is used to search tiles in large tile sets.
+    #[visit(optional)]
+    pub name: String,
+    /// A set of arbitrary tags of the tile (for example `grass`, `wall`, `animated`), that is used to
+    /// search tiles in large tile sets.
+    #[visit(optional)]
+    pub tags: Vec<String>,
+    /// A flag, that defines whether the tile is one of the favorite tiles or not. Favorite tiles could
+    /// be quickly found using [`TileSet::search`].
+    #[visit(optional)]
+    pub favorite: bool,
+    /// Values of property layers of the tile. See [`TileSetPropertyLayer`] docs for more info.
+    #[visit(optional)]
+    pub properties: Vec<TileProperty>,
+}
+
+/// A value of a property layer of a tile.
+#[derive(Clone, Default, PartialEq, Debug, Reflect, Visit)]
+pub struct TileProperty {
+    /// Name of a property layer of the tile set.
+    pub name: String,
+    /// Value of the property.
+    pub value: f32,
+}
+
+/// Defines an alternate definition of a tile in some state, for example a damaged wall or an opened
+/// door. See [`TileSet::state_variant`] docs for more info.
+#[derive(Clone, Default, PartialEq, Debug, Reflect, Visit)]
+pub struct TileStateMapping {
+    /// Name of the state (for example `damaged`, `opened`, `burned`).
+    pub state: String,
+    /// Index of the tile definition, that could be switched to the state.
+    pub from: usize,
+    /// Index of the tile definition, that represents the tile in the state.
+    pub to: usize,
+}
+
+/// A property layer defines a named numeric property of every tile of a tile set, for example movement
+/// cost or damage per second. Tiles store their values in [`TileDefinition::properties`], tiles that
+/// do not have a value use the default value of the layer.
+#[derive(Clone, Default, PartialEq, Debug, Reflect, Visit)]
+pub struct TileSetPropertyLayer {
+    /// Unique name of the layer.
+    pub name: String,
+    /// A value of the property for tiles, that do not have their own value.
+    pub default_value: f32,
+}
+
+impl TileDefinition {
+    /// Checks whether the tile matches the given search query. The query is split into words and every
+    /// word must match either the name or one of the tags of the tile (case-insensitive substring
+    /// search). Words that start with `#` must match a tag exactly (case-insensitive), for example
+    /// `#wall stone` matches tiles tagged with `wall`, which name or another tag contains `stone`. An
+    /// empty query matches every tile.
+    pub fn matches(&self, query: &str) -> bool {
+        let name = self.name.to_lowercase();
+        let tags = self
+            .tags
+            .iter()
+            .map(|tag| tag.to_lowercase())
+            .collect::<Vec<_>>();
+
+        query.split_whitespace().all(|word| {
+            let word = word.to_lowercase();
+            if let Some(tag) = word.strip_prefix('#') {
+                tags.iter().any(|t| *t == tag)
+            } else {
+                name.contains(&word) || tags.iter().any(|t| t.contains(&word))
+            }
+        })
+    }
 }
 
 #[derive(Clone, Default, Debug, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
 #[type_uuid(id = "7b7e057b-a41e-4150-ab3b-0ae99f4024f0")]
 pub struct TileSet {
     pub tiles: Vec<TileDefinition>,
+    /// Property layers of the tile set. See [`TileSetPropertyLayer`] docs for more info.
+    #[visit(optional)]
+    pub properties: Vec<TileSetPropertyLayer>,
+    /// A mapping table of tile states. See [`TileStateMapping`] docs for more info.
+    #[visit(optional)]
+    pub states: Vec<TileStateMapping>,
 }
 
 impl TileSet {
+    /// Returns an index of the tile definition, that represents a tile with the given definition index
+    /// in the given state. `None` is returned if there's no such state for the tile.
+    pub fn state_variant(&self, tile_index: usize, state: &str) -> Option<usize> {
+        self.states
+            .iter()
+            .find(|mapping| mapping.from == tile_index && mapping.state == state)
+            .map(|mapping| mapping.to)
+    }
+
+    /// Tries to find a property layer with the given name.
+    pub fn property_layer(&self, name: &str) -> Option<&TileSetPropertyLayer> {
+        self.properties.iter().find(|layer| layer.name == name)
+    }
+
+    /// Returns a value of the property layer with the given name for a tile with the given index. The
+    /// default value of the layer is returned if the tile does not have its own value. `None` is returned
+    /// if there's no such layer or tile.
+    pub fn tile_property(&self, tile_index: usize, name: &str) -> Option<f32> {
+        let layer = self.property_layer(name)?;
+        let tile = self.tiles.get(tile_index)?;
+        Some(
+            tile.properties
+                .iter()
+                .find(|property| property.name == name)
+                .map_or(layer.default_value, |property| property.value),
+        )
+    }
+
+    /// Returns the minimum and the maximum values of the property layer with the given name across
+    /// every tile of the tile set (including the default value of the layer).
+    pub fn property_range(&self, name: &str) -> Option<(f32, f32)> {
+        let layer = self.property_layer(name)?;
+        Some(
+            (0..self.tiles.len())
+                .filter_map(|index| self.tile_property(index, name))
+                .fold(
+                    (layer.default_value, layer.default_value),
+                    |(min, max), v| (min.min(v), max.max(v)),
+                ),
+        )
+    }
+
+    /// Returns indices of the tiles, that match the given search query (see [`TileDefinition::matches`]
+    /// for the query syntax). If `favorites_only` is `true`, only favorite tiles are returned.
+    pub fn search<'a>(
+        &'a self,
+        query: &'a str,
+        favorites_only: bool,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(move |(_, tile)| (!favorites_only || tile.favorite) && tile.matches(query))
+            .map(|(index, _)| index)
+    }
+
+    /// Returns all unique tags of the tiles of the tile set in sorted order.
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags = self
+            .tiles
+            .iter()
+            .flat_map(|tile| tile.tags.iter().cloned())
+            .collect::<Vec<_>>();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
     /// Load a tile set resource from the specific file path.
     pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, TileSetResourceError> {
         let bytes = io.load_file(path).await?;
@@ -153,3 +298,149 @@ impl ResourceLoader for TileSetLoader {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::{untyped::ResourceKind, Resource},
+        core::algebra::Vector2,
+        scene::{
+            base::BaseBuilder,
+            tilemap::{
+                tileset::{
+                    TileCollider, TileDefinition, TileProperty, TileSet, TileSetPropertyLayer,
+                    TileStateMapping,
+                },
+                Tile, TileMapBuilder,
+            },
+        },
+    };
+
+    fn tile(name: &str, tags: &[&str], favorite: bool) -> TileDefinition {
+        TileDefinition {
+            name: name.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            favorite,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        let tile_set = TileSet {
+            tiles: vec![
+                tile("Stone Wall", &["wall", "stone"], false),
+                tile("Grass", &["ground", "Nature"], true),
+                tile("Wooden Wall", &["wall"], true),
+                tile("Wallpaper", &["decor"], false),
+            ],
+            ..Default::default()
+        };
+
+        let search = |query: &str, favorites_only: bool| {
+            tile_set.search(query, favorites_only).collect::<Vec<_>>()
+        };
+
+        assert_eq!(search("", false), vec![0, 1, 2, 3]);
+        assert_eq!(search("", true), vec![1, 2]);
+        assert_eq!(search("wall", false), vec![0, 2, 3]);
+        assert_eq!(search("#wall", false), vec![0, 2]);
+        assert_eq!(search("#WALL wood", false), vec![2]);
+        assert_eq!(search("nature", false), vec![1]);
+        assert_eq!(search("#wall", true), vec![2]);
+        assert_eq!(search("lava", false), Vec::<usize>::new());
+
+        assert_eq!(
+            tile_set.tags(),
+            vec!["Nature", "decor", "ground", "stone", "wall"]
+        );
+    }
+
+    #[test]
+    fn test_property_layers() {
+        let mut tile_set = TileSet {
+            tiles: vec![TileDefinition::default(), TileDefinition::default()],
+            properties: vec![TileSetPropertyLayer {
+                name: "Cost".to_string(),
+                default_value: 1.0,
+            }],
+            ..Default::default()
+        };
+        tile_set.tiles[1].properties.push(TileProperty {
+            name: "Cost".to_string(),
+            value: 5.0,
+        });
+
+        assert_eq!(tile_set.tile_property(0, "Cost"), Some(1.0));
+        assert_eq!(tile_set.tile_property(1, "Cost"), Some(5.0));
+        assert_eq!(tile_set.tile_property(2, "Cost"), None);
+        assert_eq!(tile_set.tile_property(0, "Damage"), None);
+        assert_eq!(tile_set.property_range("Cost"), Some((1.0, 5.0)));
+        assert_eq!(tile_set.property_range("Damage"), None);
+    }
+
+    #[test]
+    fn test_tile_states() {
+        let state = |state: &str, from, to| TileStateMapping {
+            state: state.to_string(),
+            from,
+            to,
+        };
+        let tile_set = TileSet {
+            tiles: vec![
+                // Wall
+                TileDefinition::default(),
+                // Damaged wall
+                TileDefinition::default(),
+                // Rubble
+                TileDefinition {
+                    collider: TileCollider::None,
+                    ..Default::default()
+                },
+            ],
+            states: vec![
+                state("damaged", 0, 1),
+                state("destroyed", 0, 2),
+                state("destroyed", 1, 2),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(tile_set.state_variant(0, "damaged"), Some(1));
+        assert_eq!(tile_set.state_variant(1, "damaged"), None);
+        assert_eq!(tile_set.state_variant(1, "destroyed"), Some(2));
+
+        let mut tile_map = TileMapBuilder::new(BaseBuilder::new())
+            .with_tile_set(Resource::new_ok(ResourceKind::Embedded, tile_set))
+            .with_tiles(vec![
+                Tile::new(Vector2::new(0, 0), 0),
+                Tile::new(Vector2::new(1, 0), 0),
+            ])
+            .build_tile_map();
+        tile_map.update_colliders();
+        assert_eq!(tile_map.collider_rectangles().len(), 1);
+
+        assert_eq!(
+            tile_map.set_tile_state(Vector2::new(0, 0), "damaged"),
+            Some(1)
+        );
+        assert_eq!(tile_map.set_tile_state(Vector2::new(0, 0), "damaged"), None);
+        assert_eq!(tile_map.set_tile_state(Vector2::new(5, 5), "damaged"), None);
+        assert_eq!(
+            tile_map
+                .tile(Vector2::new(0, 0))
+                .unwrap()
+                .definition_index(),
+            1
+        );
+
+        // Colliders must be updated automatically, rubble is not solid.
+        assert_eq!(
+            tile_map.set_tile_state(Vector2::new(1, 0), "destroyed"),
+            Some(2)
+        );
+        tile_map.update_colliders();
+        let rects = tile_map.collider_rectangles();
+        assert_eq!(rects.len(), 1);
+        assert_eq!(rects[0].size, Vector2::new(1, 1));
+    }
+}
@@ -0,0 +1,367 @@
+//! Statistical ocean waves, that are simulated using inverse fast Fourier transform of Phillips
+//! spectrum (Tessendorf, "Simulating Ocean Water"). See [`FftWaveSettings`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Complex, Vector2, Vector3},
+        rand::{rngs::StdRng, Rng, SeedableRng},
+        reflect::prelude::*,
+        uuid_provider,
+        visitor::prelude::*,
+    },
+    scene::water::GRAVITY,
+};
+use std::f32::consts::TAU;
+
+/// Minimum resolution of the simulation grid.
+pub const MIN_FFT_RESOLUTION: u32 = 8;
+
+/// Maximum resolution of the simulation grid.
+pub const MAX_FFT_RESOLUTION: u32 = 512;
+
+/// Settings of the waves, that are simulated using the Phillips spectrum. The simulation produces
+/// a periodic patch of waves, that is tiled over the surface of the water.
+#[derive(Clone, Debug, PartialEq, Reflect, Visit)]
+pub struct FftWaveSettings {
+    /// Resolution of the simulation grid. It must be a power of two, otherwise it will be rounded
+    /// up to the next power of two.
+    #[reflect(min_value = 8.0, max_value = 512.0)]
+    pub resolution: u32,
+
+    /// Size of the simulated patch of the waves in meters. The patch repeats itself over the
+    /// surface of the water, so larger patches hide tiling better.
+    #[reflect(min_value = 1.0, step = 1.0)]
+    pub patch_size: f32,
+
+    /// Speed of the wind in meters per second. Stronger wind produces longer and higher waves.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub wind_speed: f32,
+
+    /// Direction of the wind in XZ plane of the water.
+    pub wind_direction: Vector2<f32>,
+
+    /// Scale of the heights of the waves.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub amplitude: f32,
+
+    /// Amount of horizontal displacement, that makes the crests of the waves sharper. Zero means
+    /// round waves, too large values make the surface intersect itself.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub choppiness: f32,
+
+    /// Seed of the random generator, that is used to create the spectrum.
+    pub seed: u64,
+}
+
+impl Default for FftWaveSettings {
+    fn default() -> Self {
+        Self {
+            resolution: 64,
+            patch_size: 64.0,
+            wind_speed: 10.0,
+            wind_direction: Vector2::new(1.0, 0.0),
+            amplitude: 1.0,
+            choppiness: 1.0,
+            seed: 0,
+        }
+    }
+}
+
+uuid_provider!(FftWaveSettings = "c7e9a3b5-2f61-4d8c-b0a4-9e5d1f7c3b28");
+
+impl FftWaveSettings {
+    /// Returns the resolution of the simulation grid, rounded to the next power of two and clamped
+    /// to [`MIN_FFT_RESOLUTION`]..[`MAX_FFT_RESOLUTION`] range.
+    pub fn effective_resolution(&self) -> usize {
+        self.resolution
+            .clamp(MIN_FFT_RESOLUTION, MAX_FFT_RESOLUTION)
+            .next_power_of_two() as usize
+    }
+}
+
+/// Phillips constant, that gives waves of realistic heights when amplitude is 1.0.
+const PHILLIPS_CONSTANT: f32 = 0.0016;
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FftWaveSimulation {
+    settings: Option<FftWaveSettings>,
+    resolution: usize,
+    patch_size: f32,
+    h0: Vec<Complex<f32>>,
+    h0_conj_minus: Vec<Complex<f32>>,
+    omega: Vec<f32>,
+    height: Vec<Complex<f32>>,
+    displacement_x: Vec<Complex<f32>>,
+    displacement_z: Vec<Complex<f32>>,
+    displacement: Vec<Vector3<f32>>,
+}
+
+fn phillips(k: Vector2<f32>, wind_direction: Vector2<f32>, wind_speed: f32) -> f32 {
+    let k_length_squared = k.norm_squared();
+    if k_length_squared < 1.0e-8 {
+        return 0.0;
+    }
+
+    // Largest possible wave from the given wind speed.
+    let l = (wind_speed * wind_speed / GRAVITY).max(1.0e-4);
+    let k_dot_w = k.dot(&wind_direction) / k_length_squared.sqrt();
+    // Suppress very small waves, that cannot be represented by the grid.
+    let small_waves = 0.001 * l;
+
+    PHILLIPS_CONSTANT * (-1.0 / (k_length_squared * l * l)).exp() / (k_length_squared.powi(2))
+        * k_dot_w.powi(2)
+        * (-k_length_squared * small_waves * small_waves).exp()
+}
+
+fn gaussian(rng: &mut StdRng) -> f32 {
+    // Box-Muller transform.
+    let u1 = rng.gen_range(f32::EPSILON..1.0);
+    let u2 = rng.gen_range(0.0..1.0);
+    (-2.0 * u1.ln()).sqrt() * (TAU * u2).cos()
+}
+
+/// Inverse discrete Fourier transform of a sequence with power of two length (iterative radix-2
+/// Cooley-Tukey algorithm). The result is not normalized.
+pub(crate) fn inverse_fft(data: &mut [Complex<f32>]) {
+    let n = data.len();
+    debug_assert!(n.is_power_of_two());
+
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let mut length = 2;
+    while length <= n {
+        let angle = TAU / length as f32;
+        let w_step = Complex::new(angle.cos(), angle.sin());
+        for chunk in data.chunks_mut(length) {
+            let (left, right) = chunk.split_at_mut(length / 2);
+            let mut w = Complex::new(1.0, 0.0);
+            for (a, b) in left.iter_mut().zip(right.iter_mut()) {
+                let u = *a;
+                let v = *b * w;
+                *a = u + v;
+                *b = u - v;
+                w *= w_step;
+            }
+        }
+        length <<= 1;
+    }
+}
+
+fn inverse_fft_2d(data: &mut [Complex<f32>], n: usize, column: &mut Vec<Complex<f32>>) {
+    for row in data.chunks_mut(n) {
+        inverse_fft(row);
+    }
+
+    column.resize(n, Default::default());
+    for x in 0..n {
+        for (z, value) in column.iter_mut().enumerate() {
+            *value = data[z * n + x];
+        }
+        inverse_fft(column);
+        for (z, value) in column.iter().enumerate() {
+            data[z * n + x] = *value;
+        }
+    }
+}
+
+impl FftWaveSimulation {
+    fn wave_vector(&self, x: usize, z: usize) -> Vector2<f32> {
+        let half = self.resolution as f32 * 0.5;
+        Vector2::new(
+            TAU * (x as f32 - half) / self.patch_size,
+            TAU * (z as f32 - half) / self.patch_size,
+        )
+    }
+
+    fn rebuild_spectrum(&mut self, settings: &FftWaveSettings) {
+        let n = settings.effective_resolution();
+        self.resolution = n;
+        self.patch_size = settings.patch_size.max(1.0);
+
+        let wind_direction = settings
+            .wind_direction
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector2::x);
+        // Amplitude of every harmonic is defined by the spectrum density multiplied by the area of
+        // a cell in the frequency domain.
+        let dk = TAU / self.patch_size;
+
+        let mut rng = StdRng::seed_from_u64(settings.seed);
+        self.h0.clear();
+        self.omega.clear();
+        for z in 0..n {
+            for x in 0..n {
+                let k = self.wave_vector(x, z);
+                let amplitude = (phillips(k, wind_direction, settings.wind_speed) * dk * dk * 0.5)
+                    .sqrt()
+                    * settings.amplitude;
+                self.h0.push(Complex::new(
+                    gaussian(&mut rng) * amplitude,
+                    gaussian(&mut rng) * amplitude,
+                ));
+                // Dispersion relation for deep water.
+                self.omega.push((GRAVITY * k.norm()).sqrt());
+            }
+        }
+
+        self.h0_conj_minus.clear();
+        for z in 0..n {
+            for x in 0..n {
+                let minus_k = ((n - z) % n) * n + (n - x) % n;
+                self.h0_conj_minus.push(self.h0[minus_k].conj());
+            }
+        }
+
+        self.settings = Some(settings.clone());
+    }
+
+    /// Updates the state of the waves at the given time. The spectrum is rebuilt only if the
+    /// settings were changed.
+    pub fn update(&mut self, settings: &FftWaveSettings, time: f32) {
+        if self.settings.as_ref() != Some(settings) {
+            self.rebuild_spectrum(settings);
+        }
+
+        let n = self.resolution;
+        let count = n * n;
+        self.height.resize(count, Default::default());
+        self.displacement_x.resize(count, Default::default());
+        self.displacement_z.resize(count, Default::default());
+
+        for z in 0..n {
+            for x in 0..n {
+                let i = z * n + x;
+                let k = self.wave_vector(x, z);
+                let (sin, cos) = (self.omega[i] * time).sin_cos();
+                let h = self.h0[i] * Complex::new(cos, sin)
+                    + self.h0_conj_minus[i] * Complex::new(cos, -sin);
+                self.height[i] = h;
+
+                let k_length = k.norm();
+                if k_length > 1.0e-6 {
+                    // -i * k / |k| * h
+                    self.displacement_x[i] = Complex::new(0.0, -k.x / k_length) * h;
+                    self.displacement_z[i] = Complex::new(0.0, -k.y / k_length) * h;
+                } else {
+                    self.displacement_x[i] = Default::default();
+                    self.displacement_z[i] = Default::default();
+                }
+            }
+        }
+
+        let mut column = Vec::new();
+        inverse_fft_2d(&mut self.height, n, &mut column);
+        inverse_fft_2d(&mut self.displacement_x, n, &mut column);
+        inverse_fft_2d(&mut self.displacement_z, n, &mut column);
+
+        let choppiness = settings.choppiness;
+        self.displacement.clear();
+        for z in 0..n {
+            for x in 0..n {
+                let i = z * n + x;
+                // Compensates the shift of the wave vectors to the center of the grid.
+                let sign = if (x + z) % 2 == 0 { 1.0 } else { -1.0 };
+                self.displacement.push(Vector3::new(
+                    sign * choppiness * self.displacement_x[i].re,
+                    sign * self.height[i].re,
+                    sign * choppiness * self.displacement_z[i].re,
+                ));
+            }
+        }
+    }
+
+    /// Samples the displacement of the surface at the given point in local XZ plane of the water.
+    /// The patch of waves is tiled infinitely.
+    pub fn sample(&self, point: Vector2<f32>) -> Vector3<f32> {
+        let n = self.resolution;
+        if n == 0 || self.displacement.len() != n * n {
+            return Vector3::default();
+        }
+
+        let x = point.x / self.patch_size * n as f32;
+        let z = point.y / self.patch_size * n as f32;
+        let (x0, z0) = (x.floor(), z.floor());
+        let (tx, tz) = (x - x0, z - z0);
+        let wrap = |v: f32| (v as i64).rem_euclid(n as i64) as usize;
+        let (x0, z0) = (wrap(x0), wrap(z0));
+        let (x1, z1) = ((x0 + 1) % n, (z0 + 1) % n);
+
+        let d00 = self.displacement[z0 * n + x0];
+        let d10 = self.displacement[z0 * n + x1];
+        let d01 = self.displacement[z1 * n + x0];
+        let d11 = self.displacement[z1 * n + x1];
+
+        d00.lerp(&d10, tx).lerp(&d01.lerp(&d11, tx), tz)
+    }
+
+    /// Returns the maximum height of the waves at the current time.
+    pub fn max_height(&self) -> f32 {
+        self.displacement
+            .iter()
+            .map(|d| d.y.abs())
+            .fold(0.0, f32::max)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Complex,
+        scene::water::fft::{inverse_fft, FftWaveSettings, FftWaveSimulation},
+    };
+    use std::f32::consts::TAU;
+
+    #[test]
+    fn test_inverse_fft() {
+        let input = (0..16)
+            .map(|i| Complex::new((i as f32 * 0.7).sin(), (i as f32 * 0.3).cos()))
+            .collect::<Vec<_>>();
+
+        let n = input.len();
+        let expected = (0..n)
+            .map(|x| {
+                input
+                    .iter()
+                    .enumerate()
+                    .map(|(k, value)| {
+                        let angle = TAU * (k * x) as f32 / n as f32;
+                        value * Complex::new(angle.cos(), angle.sin())
+                    })
+                    .sum::<Complex<f32>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut actual = input;
+        inverse_fft(&mut actual);
+
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).norm() < 1.0e-4);
+        }
+    }
+
+    #[test]
+    fn test_fft_waves_are_periodic() {
+        let settings = FftWaveSettings {
+            resolution: 32,
+            ..Default::default()
+        };
+        let mut simulation = FftWaveSimulation::default();
+        simulation.update(&settings, 1.5);
+
+        assert!(simulation.max_height() > 0.0);
+        let a = simulation.sample(Default::default());
+        let b = simulation.sample([settings.patch_size, settings.patch_size].into());
+        assert!((a - b).norm() < 1.0e-4);
+    }
+}
@@ -0,0 +1,826 @@
+//! Water is a scene node, that renders an animated surface of water with refraction and foam, and
+//! allows physics objects to float on it. See [`Water`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Vector2, Vector3},
+        math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        uuid_provider, value_as_u8_slice,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    graph::BaseSceneGraph,
+    material::{Material, MaterialResource},
+    renderer::{self, bundle::RenderContext},
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        mesh::{
+            buffer::{
+                VertexAttributeDataType, VertexAttributeDescriptor, VertexAttributeUsage,
+                VertexTrait,
+            },
+            RenderPath,
+        },
+        node::{Node, NodeTrait, ParallelUpdateContext, RdcControlFlow},
+        water::fft::{FftWaveSettings, FftWaveSimulation},
+    },
+};
+use fyrox_resource::untyped::ResourceKind;
+use std::{
+    f32::consts::{PI, TAU},
+    ops::{Deref, DerefMut},
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+pub mod fft;
+
+/// Gravitational acceleration, that is used for the dispersion of the waves and for buoyancy.
+pub const GRAVITY: f32 = 9.81;
+
+/// Maximum resolution of the surface grid.
+pub const MAX_WATER_RESOLUTION: u32 = 1024;
+
+/// Amount of iterations, that is used to find an undisplaced point of the surface, that moves to
+/// the given point when the horizontal displacement is applied.
+const HEIGHT_QUERY_ITERATIONS: usize = 4;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+struct WaterVertex {
+    position: Vector3<f32>,
+    normal: Vector3<f32>,
+    tex_coord: Vector2<f32>,
+    foam: f32,
+}
+
+impl VertexTrait for WaterVertex {
+    fn layout() -> &'static [VertexAttributeDescriptor] {
+        &[
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::Position,
+                data_type: VertexAttributeDataType::F32,
+                size: 3,
+                divisor: 0,
+                shader_location: 0,
+                normalized: false,
+            },
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::Normal,
+                data_type: VertexAttributeDataType::F32,
+                size: 3,
+                divisor: 0,
+                shader_location: 1,
+                normalized: false,
+            },
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::TexCoord0,
+                data_type: VertexAttributeDataType::F32,
+                size: 2,
+                divisor: 0,
+                shader_location: 2,
+                normalized: false,
+            },
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::Custom0,
+                data_type: VertexAttributeDataType::F32,
+                size: 1,
+                divisor: 0,
+                shader_location: 3,
+                normalized: false,
+            },
+        ]
+    }
+}
+
+/// A single trochoidal (Gerstner) wave.
+#[derive(Clone, Debug, PartialEq, Reflect, Visit)]
+pub struct GerstnerWave {
+    /// Direction of the wave in XZ plane of the water.
+    pub direction: Vector2<f32>,
+
+    /// Distance between two crests of the wave in meters. Speed of the wave is defined by its
+    /// length, longer waves are faster.
+    #[reflect(min_value = 0.01, step = 0.1)]
+    pub wavelength: f32,
+
+    /// Height of the wave in meters (distance from the calm surface to the crest).
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub amplitude: f32,
+
+    /// Sharpness of the crests in `[0; 1]` range. Zero means sine wave, one means the sharpest
+    /// possible crests.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub steepness: f32,
+}
+
+impl Default for GerstnerWave {
+    fn default() -> Self {
+        Self {
+            direction: Vector2::new(1.0, 0.0),
+            wavelength: 10.0,
+            amplitude: 0.25,
+            steepness: 0.5,
+        }
+    }
+}
+
+uuid_provider!(GerstnerWave = "5a2c8e14-7b3f-4d69-9e01-c4f6b8d2a735");
+
+impl GerstnerWave {
+    fn displacement(&self, point: Vector2<f32>, time: f32, wave_count: usize) -> Vector3<f32> {
+        let direction = self
+            .direction
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_else(Vector2::x);
+        let k = TAU / self.wavelength.max(0.01);
+        // Dispersion relation for deep water.
+        let omega = (GRAVITY * k).sqrt();
+        let (sin, cos) = (k * direction.dot(&point) - omega * time).sin_cos();
+        // Horizontal amplitude is limited, so the sum of the waves never intersects itself.
+        let horizontal = self.steepness.clamp(0.0, 1.0) / (k * wave_count.max(1) as f32);
+        Vector3::new(
+            direction.x * horizontal * cos,
+            self.amplitude * sin,
+            direction.y * horizontal * cos,
+        )
+    }
+}
+
+/// Defines how the waves of the water are simulated.
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, Reflect, Visit, AsRefStr, EnumString, VariantNames,
+)]
+pub enum WaveModel {
+    /// A sum of a few [Gerstner waves](GerstnerWave). It is cheap and fully controllable, but
+    /// repetitive. Suitable for lakes, pools, rivers.
+    #[default]
+    Gerstner,
+    /// Statistical ocean waves, that are simulated using fast Fourier transform. See
+    /// [`FftWaveSettings`] for more info.
+    Fft,
+}
+
+uuid_provider!(WaveModel = "1d6e3c4a-92b7-4f0e-8a5d-6c2f9b17e3d8");
+
+#[derive(Clone, Debug, Default)]
+struct WaterSurface {
+    vertices: Vec<WaterVertex>,
+    bounds: AxisAlignedBoundingBox,
+}
+
+/// Water is an animated rectangular surface of water, that lies in XZ plane of the node and is
+/// centered at its origin.
+///
+/// ## Waves
+///
+/// Waves are simulated on CPU either by a sum of [Gerstner waves](GerstnerWave) or by inverse fast
+/// Fourier transform of an ocean spectrum (see [`FftWaveSettings`]). The model can be selected by
+/// [`Water::set_wave_model`]. The surface is a grid with [`Water::resolution`] cells per side, the
+/// waves move the vertices of the grid both vertically and horizontally (towards the crests), so
+/// the crests are sharper than the troughs.
+///
+/// ## Rendering
+///
+/// By default, the water uses the standard water material (see [`Material::standard_water`]), that
+/// is drawn in the forward pass after opaque geometry. It refracts the scene behind the surface in
+/// screen space using the copy of the frame and the depth buffer of the scene, absorbs light with
+/// the depth of the water, reflects the sky and adds specular highlights of the lights. Keep in mind,
+/// that the objects drawn in the forward pass (particles, other transparent objects) are not visible
+/// through the water.
+///
+/// ## Foam
+///
+/// Foam appears on the crests of the waves, where the surface is compressed by horizontal displacement
+/// (the Jacobian of the displacement is less than [`Water::foam_threshold`]). The standard material
+/// also adds foam at the shore line, where the water is shallow.
+///
+/// ## Buoyancy
+///
+/// [`Water::height_at`], [`Water::normal_at`], [`Water::depth_at`] and [`Water::buoyancy_force`]
+/// allow you to query the animated surface, for example to make rigid bodies float. The queries
+/// use the same waves as the rendered surface and work outside of the rectangle of the surface too.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::pool::Handle,
+/// #     graph::SceneGraph,
+/// #     scene::{graph::Graph, node::Node, rigidbody::RigidBody, water::Water},
+/// # };
+/// fn float(graph: &mut Graph, water: Handle<Node>, body: Handle<Node>) {
+///     let Some(water) = graph.try_get_of_type::<Water>(water) else {
+///         return;
+///     };
+///     let position = graph[body].global_position();
+///     // Treat the body as a sphere with half a meter radius.
+///     let force = water.buoyancy_force(position, 0.5);
+///     if let Some(body) = graph.try_get_mut_of_type::<RigidBody>(body) {
+///         body.apply_force(force);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Reflect, Visit, ComponentProvider)]
+pub struct Water {
+    base: Base,
+
+    #[reflect(setter = "set_size")]
+    size: InheritableVariable<Vector2<f32>>,
+
+    #[reflect(min_value = 1.0, max_value = 1024.0, setter = "set_resolution")]
+    resolution: InheritableVariable<u32>,
+
+    #[reflect(setter = "set_material")]
+    material: InheritableVariable<MaterialResource>,
+
+    #[reflect(setter = "set_wave_model")]
+    wave_model: InheritableVariable<WaveModel>,
+
+    #[reflect(setter = "set_gerstner_waves")]
+    gerstner_waves: InheritableVariable<Vec<GerstnerWave>>,
+
+    #[reflect(setter = "set_fft_waves")]
+    fft_waves: InheritableVariable<FftWaveSettings>,
+
+    #[reflect(min_value = 0.0, step = 0.01, setter = "set_foam_threshold")]
+    foam_threshold: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 1.0, setter = "set_density")]
+    density: InheritableVariable<f32>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    time: f32,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    fft_simulation: FftWaveSimulation,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    surface: WaterSurface,
+}
+
+impl Default for Water {
+    fn default() -> Self {
+        WaterBuilder::new(BaseBuilder::new()).build_water()
+    }
+}
+
+impl Deref for Water {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Water {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for Water {
+    fn type_uuid() -> Uuid {
+        uuid!("8e3b5f71-c2d4-4a96-b0e8-3f7a1d6c9b52")
+    }
+}
+
+impl Water {
+    /// Sets new size of the surface in XZ plane of the node.
+    pub fn set_size(&mut self, size: Vector2<f32>) -> Vector2<f32> {
+        self.size
+            .set_value_and_mark_modified(size.sup(&Vector2::repeat(0.0)))
+    }
+
+    /// Returns current size of the surface.
+    pub fn size(&self) -> Vector2<f32> {
+        *self.size
+    }
+
+    /// Sets new amount of cells of the surface grid per side. Higher values give more detailed
+    /// waves, but the grid is rebuilt on CPU every frame, so keep it reasonable.
+    pub fn set_resolution(&mut self, resolution: u32) -> u32 {
+        self.resolution
+            .set_value_and_mark_modified(resolution.clamp(1, MAX_WATER_RESOLUTION))
+    }
+
+    /// Returns current amount of cells of the surface grid per side.
+    pub fn resolution(&self) -> u32 {
+        *self.resolution
+    }
+
+    /// Sets new material of the water. The material must use a shader, that supports the vertex
+    /// layout of the water, for example the standard water shader.
+    pub fn set_material(&mut self, material: MaterialResource) -> MaterialResource {
+        self.material.set_value_and_mark_modified(material)
+    }
+
+    /// Returns current material of the water.
+    pub fn material(&self) -> &MaterialResource {
+        &self.material
+    }
+
+    /// Sets new wave model. See [`WaveModel`] docs for more info.
+    pub fn set_wave_model(&mut self, model: WaveModel) -> WaveModel {
+        self.wave_model.set_value_and_mark_modified(model)
+    }
+
+    /// Returns current wave model.
+    pub fn wave_model(&self) -> WaveModel {
+        *self.wave_model
+    }
+
+    /// Sets new set of Gerstner waves. They're used only with [`WaveModel::Gerstner`].
+    pub fn set_gerstner_waves(&mut self, waves: Vec<GerstnerWave>) -> Vec<GerstnerWave> {
+        self.gerstner_waves.set_value_and_mark_modified(waves)
+    }
+
+    /// Returns current set of Gerstner waves.
+    pub fn gerstner_waves(&self) -> &[GerstnerWave] {
+        &self.gerstner_waves
+    }
+
+    /// Sets new settings of the ocean waves. They're used only with [`WaveModel::Fft`].
+    pub fn set_fft_waves(&mut self, settings: FftWaveSettings) -> FftWaveSettings {
+        self.fft_waves.set_value_and_mark_modified(settings)
+    }
+
+    /// Returns current settings of the ocean waves.
+    pub fn fft_waves(&self) -> &FftWaveSettings {
+        &self.fft_waves
+    }
+
+    /// Sets new foam threshold. Foam appears where the area of the surface is compressed by the
+    /// waves below this ratio, so higher values give more foam. Zero disables the foam on the crests.
+    pub fn set_foam_threshold(&mut self, threshold: f32) -> f32 {
+        self.foam_threshold
+            .set_value_and_mark_modified(threshold.max(0.0))
+    }
+
+    /// Returns current foam threshold.
+    pub fn foam_threshold(&self) -> f32 {
+        *self.foam_threshold
+    }
+
+    /// Sets new density of the water in kg/m³. It is used by [`Self::buoyancy_force`].
+    pub fn set_density(&mut self, density: f32) -> f32 {
+        self.density.set_value_and_mark_modified(density.max(0.0))
+    }
+
+    /// Returns current density of the water.
+    pub fn density(&self) -> f32 {
+        *self.density
+    }
+
+    /// Returns current time of the simulation in seconds.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Sets new time of the simulation. It could be useful to synchronize the waves over network.
+    pub fn set_time(&mut self, time: f32) {
+        self.time = time;
+        self.update_waves();
+    }
+
+    fn update_waves(&mut self) {
+        if *self.wave_model == WaveModel::Fft {
+            self.fft_simulation.update(&self.fft_waves, self.time);
+        }
+    }
+
+    /// Returns the displacement of the given point of the calm surface (in local XZ plane).
+    fn displacement(&self, point: Vector2<f32>) -> Vector3<f32> {
+        match *self.wave_model {
+            WaveModel::Gerstner => self
+                .gerstner_waves
+                .iter()
+                .map(|wave| wave.displacement(point, self.time, self.gerstner_waves.len()))
+                .sum(),
+            WaveModel::Fft => self.fft_simulation.sample(point),
+        }
+    }
+
+    /// Returns the height of the surface above the given point in local XZ plane.
+    fn local_height_at(&self, point: Vector2<f32>) -> f32 {
+        // The waves move the points of the calm surface horizontally, so we need to find the
+        // point, that ends up above the given one.
+        let mut source = point;
+        for _ in 0..HEIGHT_QUERY_ITERATIONS {
+            let displacement = self.displacement(source);
+            source = point - Vector2::new(displacement.x, displacement.z);
+        }
+        self.displacement(source).y
+    }
+
+    /// Returns the maximum height of the waves above the calm surface.
+    pub fn max_wave_height(&self) -> f32 {
+        match *self.wave_model {
+            WaveModel::Gerstner => self.gerstner_waves.iter().map(|wave| wave.amplitude).sum(),
+            WaveModel::Fft => self.fft_simulation.max_height(),
+        }
+    }
+
+    fn inv_global_transform(&self) -> Matrix4<f32> {
+        self.global_transform()
+            .try_inverse()
+            .unwrap_or_else(Matrix4::identity)
+    }
+
+    /// Returns world-space height of the animated surface at XZ coordinates of the given world-space
+    /// position.
+    pub fn height_at(&self, position: Vector3<f32>) -> f32 {
+        let local = self
+            .inv_global_transform()
+            .transform_point(&Point3::from(position));
+        let height = self.local_height_at(Vector2::new(local.x, local.z));
+        self.global_transform()
+            .transform_point(&Point3::new(local.x, height, local.z))
+            .y
+    }
+
+    /// Returns world-space normal of the animated surface at XZ coordinates of the given world-space
+    /// position.
+    pub fn normal_at(&self, position: Vector3<f32>) -> Vector3<f32> {
+        const EPSILON: f32 = 0.1;
+        let dx = Vector3::new(EPSILON, 0.0, 0.0);
+        let dz = Vector3::new(0.0, 0.0, EPSILON);
+        Vector3::new(
+            self.height_at(position - dx) - self.height_at(position + dx),
+            2.0 * EPSILON,
+            self.height_at(position - dz) - self.height_at(position + dz),
+        )
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(Vector3::y)
+    }
+
+    /// Returns the depth of the given world-space point under the animated surface. Negative values
+    /// mean that the point is above the surface.
+    pub fn depth_at(&self, position: Vector3<f32>) -> f32 {
+        self.height_at(position) - position.y
+    }
+
+    /// Returns `true` if the given world-space point is under the animated surface.
+    pub fn is_under_water(&self, position: Vector3<f32>) -> bool {
+        self.depth_at(position) > 0.0
+    }
+
+    /// Calculates the buoyancy force (in Newtons), that acts on a sphere with the given center and
+    /// radius. The force is proportional to the submerged part of the sphere and points up. Apply it
+    /// to a rigid body at the center of the sphere, use a few spheres per body to make it tilt with
+    /// the waves.
+    pub fn buoyancy_force(&self, center: Vector3<f32>, radius: f32) -> Vector3<f32> {
+        let radius = radius.max(f32::EPSILON);
+        let depth = self.depth_at(center);
+        // Volume of the spherical cap under the surface.
+        let h = (depth + radius).clamp(0.0, 2.0 * radius);
+        let submerged_volume = PI * h * h * (3.0 * radius - h) / 3.0;
+        Vector3::new(0.0, *self.density * GRAVITY * submerged_volume, 0.0)
+    }
+
+    fn rebuild_surface(&mut self) {
+        let resolution = *self.resolution as usize;
+        let row = resolution + 1;
+        let size = *self.size;
+        let cell = Vector2::new(size.x / resolution as f32, size.y / resolution as f32);
+        let origin = size.scale(-0.5);
+
+        let mut bounds = AxisAlignedBoundingBox::default();
+        let mut vertices = std::mem::take(&mut self.surface.vertices);
+        vertices.clear();
+        for z in 0..row {
+            for x in 0..row {
+                let point = origin + Vector2::new(x as f32 * cell.x, z as f32 * cell.y);
+                let position = Vector3::new(point.x, 0.0, point.y) + self.displacement(point);
+                bounds.add_point(position);
+                vertices.push(WaterVertex {
+                    position,
+                    normal: Vector3::y(),
+                    tex_coord: Vector2::new(
+                        x as f32 / resolution as f32,
+                        z as f32 / resolution as f32,
+                    ),
+                    foam: 0.0,
+                });
+            }
+        }
+
+        // Normals and foam are calculated using finite differences of the displaced grid.
+        let threshold = *self.foam_threshold;
+        for z in 0..row {
+            for x in 0..row {
+                let (x0, x1) = (x.saturating_sub(1), (x + 1).min(resolution));
+                let (z0, z1) = (z.saturating_sub(1), (z + 1).min(resolution));
+                let tangent = vertices[z * row + x1].position - vertices[z * row + x0].position;
+                let bitangent = vertices[z1 * row + x].position - vertices[z0 * row + x].position;
+                let normal = bitangent
+                    .cross(&tangent)
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(Vector3::y);
+
+                // Jacobian of the horizontal displacement - less than one where the surface is
+                // compressed (crests of the waves), negative where it folds over.
+                let du = ((x1 - x0) as f32 * cell.x).max(f32::EPSILON);
+                let dv = ((z1 - z0) as f32 * cell.y).max(f32::EPSILON);
+                let jacobian =
+                    (tangent.x / du) * (bitangent.z / dv) - (tangent.z / du) * (bitangent.x / dv);
+                let foam = if threshold > 0.0 {
+                    ((threshold - jacobian) / threshold).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+
+                let vertex = &mut vertices[z * row + x];
+                vertex.normal = normal;
+                vertex.foam = foam;
+            }
+        }
+
+        self.surface = WaterSurface { vertices, bounds };
+    }
+}
+
+impl NodeTrait for Water {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        if self.surface.bounds.is_valid() {
+            self.surface.bounds
+        } else {
+            let half_size = self.size.scale(0.5);
+            let height = self.max_wave_height();
+            AxisAlignedBoundingBox::from_min_max(
+                Vector3::new(-half_size.x, -height, -half_size.y),
+                Vector3::new(half_size.x, height, half_size.y),
+            )
+        }
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn parallel_update(&mut self, context: &ParallelUpdateContext) {
+        self.time += context.dt;
+        self.update_waves();
+        if self.visibility() && self.is_enabled() {
+            self.rebuild_surface();
+        }
+    }
+
+    fn collect_render_data(&self, ctx: &mut RenderContext) -> RdcControlFlow {
+        if !self.global_visibility() || !self.is_globally_enabled() {
+            return RdcControlFlow::Continue;
+        }
+
+        if renderer::is_shadow_pass(ctx.render_pass_name) {
+            return RdcControlFlow::Continue;
+        }
+
+        if self.surface.vertices.is_empty()
+            || (self.frustum_culling()
+                && !ctx
+                    .frustum
+                    .map_or(true, |f| f.is_intersects_aabb(&self.world_bounding_box())))
+        {
+            return RdcControlFlow::Continue;
+        }
+
+        let global_transform = self.global_transform();
+        let sort_index = ctx.calculate_sorting_index(self.global_position());
+        let resolution = *self.resolution;
+        let row = resolution + 1;
+        let vertices = &self.surface.vertices;
+
+        ctx.storage.push_triangles(
+            WaterVertex::layout(),
+            &self.material,
+            RenderPath::Forward,
+            0,
+            sort_index,
+            false,
+            self.self_handle,
+            &mut move |mut vertex_buffer, mut triangle_buffer| {
+                let start_vertex_index = vertex_buffer.vertex_count();
+
+                for vertex in vertices.iter() {
+                    let vertex = WaterVertex {
+                        position: global_transform
+                            .transform_point(&Point3::from(vertex.position))
+                            .coords,
+                        normal: global_transform
+                            .transform_vector(&vertex.normal)
+                            .try_normalize(f32::EPSILON)
+                            .unwrap_or_else(Vector3::y),
+                        ..*vertex
+                    };
+                    vertex_buffer
+                        .push_vertex_raw(value_as_u8_slice(&vertex))
+                        .unwrap();
+                }
+
+                let triangles = (0..resolution).flat_map(|z| {
+                    (0..resolution).flat_map(move |x| {
+                        let i0 = z * row + x;
+                        let i1 = i0 + 1;
+                        let i2 = i0 + row;
+                        let i3 = i2 + 1;
+                        [
+                            TriangleDefinition([i0, i2, i1]),
+                            TriangleDefinition([i1, i2, i3]),
+                        ]
+                    })
+                });
+
+                triangle_buffer.push_triangles_iter_with_offset(start_vertex_index, triangles)
+            },
+        );
+
+        RdcControlFlow::Continue
+    }
+}
+
+/// Allows you to create water in declarative manner.
+pub struct WaterBuilder {
+    base_builder: BaseBuilder,
+    size: Vector2<f32>,
+    resolution: u32,
+    material: MaterialResource,
+    wave_model: WaveModel,
+    gerstner_waves: Vec<GerstnerWave>,
+    fft_waves: FftWaveSettings,
+    foam_threshold: f32,
+    density: f32,
+}
+
+impl WaterBuilder {
+    /// Creates new builder instance.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            size: Vector2::new(100.0, 100.0),
+            resolution: 128,
+            material: MaterialResource::new_ok(ResourceKind::Embedded, Material::standard_water()),
+            wave_model: WaveModel::Gerstner,
+            gerstner_waves: vec![
+                GerstnerWave {
+                    direction: Vector2::new(1.0, 0.0),
+                    wavelength: 16.0,
+                    amplitude: 0.35,
+                    steepness: 0.6,
+                },
+                GerstnerWave {
+                    direction: Vector2::new(0.8, 0.6),
+                    wavelength: 7.0,
+                    amplitude: 0.15,
+                    steepness: 0.5,
+                },
+                GerstnerWave {
+                    direction: Vector2::new(0.3, -0.95),
+                    wavelength: 3.0,
+                    amplitude: 0.05,
+                    steepness: 0.4,
+                },
+            ],
+            fft_waves: Default::default(),
+            foam_threshold: 0.7,
+            density: 1000.0,
+        }
+    }
+
+    /// Sets desired size of the surface.
+    pub fn with_size(mut self, size: Vector2<f32>) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets desired amount of cells of the surface grid per side.
+    pub fn with_resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Sets desired material of the water.
+    pub fn with_material(mut self, material: MaterialResource) -> Self {
+        self.material = material;
+        self
+    }
+
+    /// Sets desired wave model.
+    pub fn with_wave_model(mut self, model: WaveModel) -> Self {
+        self.wave_model = model;
+        self
+    }
+
+    /// Sets desired set of Gerstner waves.
+    pub fn with_gerstner_waves(mut self, waves: Vec<GerstnerWave>) -> Self {
+        self.gerstner_waves = waves;
+        self
+    }
+
+    /// Sets desired settings of the ocean waves.
+    pub fn with_fft_waves(mut self, settings: FftWaveSettings) -> Self {
+        self.fft_waves = settings;
+        self
+    }
+
+    /// Sets desired foam threshold.
+    pub fn with_foam_threshold(mut self, threshold: f32) -> Self {
+        self.foam_threshold = threshold;
+        self
+    }
+
+    /// Sets desired density of the water.
+    pub fn with_density(mut self, density: f32) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Creates new water.
+    pub fn build_water(self) -> Water {
+        let mut water = Water {
+            base: self.base_builder.build_base(),
+            size: self.size.sup(&Vector2::repeat(0.0)).into(),
+            resolution: self.resolution.clamp(1, MAX_WATER_RESOLUTION).into(),
+            material: self.material.into(),
+            wave_model: self.wave_model.into(),
+            gerstner_waves: self.gerstner_waves.into(),
+            fft_waves: self.fft_waves.into(),
+            foam_threshold: self.foam_threshold.max(0.0).into(),
+            density: self.density.max(0.0).into(),
+            time: 0.0,
+            fft_simulation: Default::default(),
+            surface: Default::default(),
+        };
+        water.update_waves();
+        water
+    }
+
+    /// Creates new water node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_water())
+    }
+
+    /// Creates new water node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            water::{fft::FftWaveSettings, WaterBuilder, WaveModel},
+        },
+    };
+
+    #[test]
+    fn test_water_height_query() {
+        let mut water = WaterBuilder::new(BaseBuilder::new()).build_water();
+        water.set_time(2.5);
+
+        // A vertex of the displaced surface must be on the queried surface.
+        let point = Vector2::new(3.0, -1.5);
+        let displaced = Vector3::new(point.x, 0.0, point.y) + water.displacement(point);
+        assert!((water.height_at(displaced) - displaced.y).abs() < 0.01);
+
+        let wave_height = water
+            .gerstner_waves()
+            .iter()
+            .map(|w| w.amplitude)
+            .sum::<f32>();
+        assert_eq!(
+            water.buoyancy_force(Vector3::new(0.0, wave_height + 1.0, 0.0), 0.5),
+            Vector3::default()
+        );
+        assert!(water.is_under_water(Vector3::new(0.0, -wave_height - 1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_fft_water_height_query() {
+        let mut water = WaterBuilder::new(BaseBuilder::new())
+            .with_wave_model(WaveModel::Fft)
+            .with_fft_waves(FftWaveSettings {
+                resolution: 32,
+                ..Default::default()
+            })
+            .build_water();
+        water.set_time(1.0);
+
+        let point = Vector2::new(10.0, 4.0);
+        let displaced = Vector3::new(point.x, 0.0, point.y) + water.displacement(point);
+        assert!((water.height_at(displaced) - displaced.y).abs() < 0.05);
+    }
+}
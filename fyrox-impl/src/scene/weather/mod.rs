@@ -0,0 +1,961 @@
+//! Weather is a scene node, that renders a dynamic sky with the sun, the moon and the stars, drives a
+//! directional light and renders rain or snow around the camera. See [`Weather`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector2, Vector3},
+        color::Color,
+        log::Log,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        sstorage::ImmutableString,
+        type_traits::prelude::*,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    graph::BaseSceneGraph,
+    material::{Material, MaterialResource, PropertyValue},
+    renderer::{
+        self,
+        bundle::{PersistentIdentifier, RenderContext, SurfaceInstanceData},
+        framework::geometry_buffer::ElementRange,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        light::directional::DirectionalLight,
+        mesh::{
+            surface::{SurfaceData, SurfaceResource},
+            RenderPath,
+        },
+        node::{Node, NodeTrait, RdcControlFlow, UpdateContext},
+    },
+};
+use fyrox_resource::untyped::ResourceKind;
+use lazy_static::lazy_static;
+use std::{
+    f32::consts::{PI, TAU},
+    ops::{Deref, DerefMut},
+};
+
+pub mod precipitation;
+pub mod sky;
+
+use precipitation::{make_particles, PrecipitationKind, PrecipitationLayer};
+
+/// Amount of days between two full moons.
+pub const LUNAR_MONTH: f32 = 29.53;
+
+/// Intensity of the light of the full moon relative to the light of the sun.
+const MOON_LIGHT_FACTOR: f32 = 0.03;
+
+lazy_static! {
+    static ref SKY_DOME: SurfaceResource = SurfaceResource::new_ok(
+        ResourceKind::Embedded,
+        SurfaceData::make_sphere(32, 16, 1.0, &Matrix4::identity()),
+    );
+}
+
+#[derive(Clone, Debug, Default)]
+struct PrecipitationLayerState {
+    offset: Vector3<f32>,
+    time: f32,
+    surface: Option<(u32, SurfaceResource)>,
+}
+
+/// Weather is a node, that simulates the sky and the precipitation of a scene.
+///
+/// ## Sky
+///
+/// Positions of the sun and the moon are calculated using a simplified astronomical model from the
+/// time of the day (in hours), the day of the year and the latitude of the scene (see [`sky`] module
+/// docs). Local axes of the node define the cardinal directions: +X is east, +Y is up, +Z is north,
+/// so rotate the node to rotate the sky. The time flows with the speed defined by
+/// [`Weather::set_time_scale`], the day of the year and the phase of the moon advance automatically
+/// when the time passes midnight.
+///
+/// The sky is drawn by the standard sky material (see [`Material::standard_sky`]) in the background
+/// render queue. It calculates Rayleigh and Mie scattering of the light of the sun and the moon in
+/// the atmosphere, draws the disks of the sun and the moon (with its phase) and the stars, that are
+/// visible only at night. Keep in mind, that the sky is drawn on the far plane of the camera, so the
+/// camera should not have a skybox, otherwise the skybox will hide the sky.
+///
+/// ## Sun light
+///
+/// The weather can drive a directional light (see [`Weather::set_sun_light`]). The light follows the
+/// sun during the day and the moon at night, its color and intensity are defined by the transmittance
+/// of the atmosphere, so the light is dimmer and redder at dawn and dusk. The weather sets the local
+/// rotation of the light, so the light should be a child of the weather node.
+///
+/// ## Precipitation
+///
+/// Rain and snow are rendered by a set of [precipitation layers](PrecipitationLayer). Particles are
+/// animated entirely on GPU, so there could be hundreds of thousands of them. Colliding layers use
+/// a top-down depth map of the scene around the camera, so the particles do not fall through roofs
+/// and leave splashes on the surfaces.
+///
+/// ## Scripting and animation
+///
+/// Every property of the weather could be changed from scripts using respective setters. Numeric
+/// properties (time of the day, overcast, intensity of the precipitation layers, etc.) could also be
+/// keyed in animations and sequences using property bindings, for example `time_of_day` or
+/// `precipitation[0].intensity`.
+///
+/// ```rust
+/// # use fyrox_impl::scene::{
+/// #     base::BaseBuilder, graph::Graph, node::Node, weather::{precipitation::PrecipitationLayer, WeatherBuilder},
+/// # };
+/// # use fyrox_impl::core::pool::Handle;
+/// fn create_rainy_evening(graph: &mut Graph) -> Handle<Node> {
+///     WeatherBuilder::new(BaseBuilder::new())
+///         .with_time_of_day(19.5)
+///         .with_overcast(0.6)
+///         .with_precipitation(vec![PrecipitationLayer::rain()])
+///         .build(graph)
+/// }
+/// ```
+#[derive(Clone, Debug, Reflect, Visit, ComponentProvider)]
+pub struct Weather {
+    base: Base,
+
+    #[reflect(
+        min_value = 0.0,
+        max_value = 24.0,
+        step = 0.1,
+        setter = "set_time_of_day"
+    )]
+    time_of_day: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        max_value = 365.0,
+        step = 1.0,
+        setter = "set_day_of_year"
+    )]
+    day_of_year: InheritableVariable<f32>,
+
+    #[reflect(min_value = -90.0, max_value = 90.0, step = 0.1, setter = "set_latitude")]
+    latitude: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 1.0, setter = "set_time_scale")]
+    time_scale: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.01,
+        setter = "set_moon_phase"
+    )]
+    moon_phase: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.1, setter = "set_sun_intensity")]
+    sun_intensity: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.01, setter = "set_rayleigh_scale")]
+    rayleigh_scale: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.01, setter = "set_mie_scale")]
+    mie_scale: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        max_value = 0.99,
+        step = 0.01,
+        setter = "set_mie_anisotropy"
+    )]
+    mie_anisotropy: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01, setter = "set_overcast")]
+    overcast: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, step = 0.1, setter = "set_star_intensity")]
+    star_intensity: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_sky_material")]
+    sky_material: InheritableVariable<MaterialResource>,
+
+    #[reflect(setter = "set_sun_light")]
+    sun_light: InheritableVariable<Handle<Node>>,
+
+    #[reflect(min_value = 0.0, step = 0.1, setter = "set_sun_light_intensity")]
+    sun_light_intensity: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_wind")]
+    wind: InheritableVariable<Vector2<f32>>,
+
+    #[reflect(setter = "set_precipitation")]
+    precipitation: InheritableVariable<Vec<PrecipitationLayer>>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    layer_states: Vec<PrecipitationLayerState>,
+}
+
+impl Default for Weather {
+    fn default() -> Self {
+        WeatherBuilder::new(BaseBuilder::new()).build_weather()
+    }
+}
+
+impl Deref for Weather {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Weather {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for Weather {
+    fn type_uuid() -> Uuid {
+        uuid!("4f1c7a92-3e6d-4b08-a5c1-8d2e9f6b3a70")
+    }
+}
+
+impl Weather {
+    /// Sets new local solar time in hours, `[0; 24)` range. 12 is noon.
+    pub fn set_time_of_day(&mut self, time_of_day: f32) -> f32 {
+        self.time_of_day
+            .set_value_and_mark_modified(time_of_day.rem_euclid(24.0))
+    }
+
+    /// Returns current local solar time in hours.
+    pub fn time_of_day(&self) -> f32 {
+        *self.time_of_day
+    }
+
+    /// Sets new day of the year, `[0; 365)` range. The day defines the height of the sun at noon and
+    /// the length of the day.
+    pub fn set_day_of_year(&mut self, day: f32) -> f32 {
+        self.day_of_year
+            .set_value_and_mark_modified(day.rem_euclid(sky::DAYS_PER_YEAR))
+    }
+
+    /// Returns current day of the year.
+    pub fn day_of_year(&self) -> f32 {
+        *self.day_of_year
+    }
+
+    /// Sets new latitude of the scene in degrees, `[-90; 90]` range. Positive values are in the
+    /// northern hemisphere.
+    pub fn set_latitude(&mut self, latitude: f32) -> f32 {
+        self.latitude
+            .set_value_and_mark_modified(latitude.clamp(-90.0, 90.0))
+    }
+
+    /// Returns current latitude of the scene.
+    pub fn latitude(&self) -> f32 {
+        *self.latitude
+    }
+
+    /// Sets new speed of the time - amount of in-game seconds, that pass per one second of real
+    /// time. For example, 72 means that a day lasts 20 minutes. Zero stops the time.
+    pub fn set_time_scale(&mut self, time_scale: f32) -> f32 {
+        self.time_scale
+            .set_value_and_mark_modified(time_scale.max(0.0))
+    }
+
+    /// Returns current speed of the time.
+    pub fn time_scale(&self) -> f32 {
+        *self.time_scale
+    }
+
+    /// Sets new phase of the moon in `[0; 1]` range, where 0 is the new moon and 0.5 is the full
+    /// moon.
+    pub fn set_moon_phase(&mut self, phase: f32) -> f32 {
+        self.moon_phase
+            .set_value_and_mark_modified(phase.rem_euclid(1.0))
+    }
+
+    /// Returns current phase of the moon.
+    pub fn moon_phase(&self) -> f32 {
+        *self.moon_phase
+    }
+
+    /// Sets new intensity of the sun light, that is scattered in the atmosphere. It defines the
+    /// brightness of the sky.
+    pub fn set_sun_intensity(&mut self, intensity: f32) -> f32 {
+        self.sun_intensity
+            .set_value_and_mark_modified(intensity.max(0.0))
+    }
+
+    /// Returns current intensity of the sun light in the atmosphere.
+    pub fn sun_intensity(&self) -> f32 {
+        *self.sun_intensity
+    }
+
+    /// Sets new multiplier of Rayleigh scattering (scattering on air molecules), that makes the sky
+    /// blue and the sunsets red.
+    pub fn set_rayleigh_scale(&mut self, scale: f32) -> f32 {
+        self.rayleigh_scale
+            .set_value_and_mark_modified(scale.max(0.0))
+    }
+
+    /// Returns current multiplier of Rayleigh scattering.
+    pub fn rayleigh_scale(&self) -> f32 {
+        *self.rayleigh_scale
+    }
+
+    /// Sets new multiplier of Mie scattering (scattering on aerosols), that makes the halo around
+    /// the sun. Higher values make the air hazy.
+    pub fn set_mie_scale(&mut self, scale: f32) -> f32 {
+        self.mie_scale.set_value_and_mark_modified(scale.max(0.0))
+    }
+
+    /// Returns current multiplier of Mie scattering.
+    pub fn mie_scale(&self) -> f32 {
+        *self.mie_scale
+    }
+
+    /// Sets new anisotropy of Mie scattering in `[0; 1)` range. Higher values make the halo around
+    /// the sun smaller and brighter.
+    pub fn set_mie_anisotropy(&mut self, anisotropy: f32) -> f32 {
+        self.mie_anisotropy
+            .set_value_and_mark_modified(anisotropy.clamp(0.0, 0.99))
+    }
+
+    /// Returns current anisotropy of Mie scattering.
+    pub fn mie_anisotropy(&self) -> f32 {
+        *self.mie_anisotropy
+    }
+
+    /// Sets new amount of clouds in `[0; 1]` range. Overcast sky is grey, hides the sun, the moon
+    /// and the stars and dims the sun light.
+    pub fn set_overcast(&mut self, overcast: f32) -> f32 {
+        self.overcast
+            .set_value_and_mark_modified(overcast.clamp(0.0, 1.0))
+    }
+
+    /// Returns current amount of clouds.
+    pub fn overcast(&self) -> f32 {
+        *self.overcast
+    }
+
+    /// Sets new brightness of the stars.
+    pub fn set_star_intensity(&mut self, intensity: f32) -> f32 {
+        self.star_intensity
+            .set_value_and_mark_modified(intensity.max(0.0))
+    }
+
+    /// Returns current brightness of the stars.
+    pub fn star_intensity(&self) -> f32 {
+        *self.star_intensity
+    }
+
+    /// Sets new material of the sky. The material must use a shader, that has the same properties
+    /// as the standard sky shader.
+    pub fn set_sky_material(&mut self, material: MaterialResource) -> MaterialResource {
+        self.sky_material.set_value_and_mark_modified(material)
+    }
+
+    /// Returns current material of the sky.
+    pub fn sky_material(&self) -> &MaterialResource {
+        &self.sky_material
+    }
+
+    /// Sets new directional light, that will be driven by the weather. See [`Weather`] docs for more
+    /// info.
+    pub fn set_sun_light(&mut self, light: Handle<Node>) -> Handle<Node> {
+        self.sun_light.set_value_and_mark_modified(light)
+    }
+
+    /// Returns current directional light, that is driven by the weather.
+    pub fn sun_light(&self) -> Handle<Node> {
+        *self.sun_light
+    }
+
+    /// Sets new intensity of the driven directional light, when the sun is at zenith and the sky is
+    /// clear.
+    pub fn set_sun_light_intensity(&mut self, intensity: f32) -> f32 {
+        self.sun_light_intensity
+            .set_value_and_mark_modified(intensity.max(0.0))
+    }
+
+    /// Returns current intensity of the driven directional light.
+    pub fn sun_light_intensity(&self) -> f32 {
+        *self.sun_light_intensity
+    }
+
+    /// Sets new horizontal velocity of the wind (in local XZ plane of the node) in meters per second.
+    /// It pushes the precipitation particles.
+    pub fn set_wind(&mut self, wind: Vector2<f32>) -> Vector2<f32> {
+        self.wind.set_value_and_mark_modified(wind)
+    }
+
+    /// Returns current velocity of the wind.
+    pub fn wind(&self) -> Vector2<f32> {
+        *self.wind
+    }
+
+    /// Sets new set of precipitation layers.
+    pub fn set_precipitation(
+        &mut self,
+        layers: Vec<PrecipitationLayer>,
+    ) -> Vec<PrecipitationLayer> {
+        self.precipitation.set_value_and_mark_modified(layers)
+    }
+
+    /// Returns current set of precipitation layers.
+    pub fn precipitation(&self) -> &[PrecipitationLayer] {
+        &self.precipitation
+    }
+
+    /// Returns current set of precipitation layers for modification. Could be used, for example, to
+    /// change the intensity of the rain from a script.
+    pub fn precipitation_mut(&mut self) -> &mut Vec<PrecipitationLayer> {
+        self.precipitation.get_value_mut_and_mark_modified()
+    }
+
+    /// Returns a direction to the sun in the local space of the node.
+    pub fn local_sun_direction(&self) -> Vector3<f32> {
+        sky::sun_direction(*self.time_of_day, *self.day_of_year, *self.latitude)
+    }
+
+    /// Returns a direction to the moon in the local space of the node.
+    pub fn local_moon_direction(&self) -> Vector3<f32> {
+        sky::moon_direction(
+            *self.time_of_day,
+            *self.day_of_year,
+            *self.latitude,
+            *self.moon_phase,
+        )
+    }
+
+    fn to_world_direction(&self, direction: Vector3<f32>) -> Vector3<f32> {
+        self.global_transform()
+            .transform_vector(&direction)
+            .try_normalize(f32::EPSILON)
+            .unwrap_or(direction)
+    }
+
+    /// Returns a direction to the sun in world space.
+    pub fn sun_direction(&self) -> Vector3<f32> {
+        self.to_world_direction(self.local_sun_direction())
+    }
+
+    /// Returns a direction to the moon in world space.
+    pub fn moon_direction(&self) -> Vector3<f32> {
+        self.to_world_direction(self.local_moon_direction())
+    }
+
+    /// Returns `true` if the sun is above the horizon.
+    pub fn is_day(&self) -> bool {
+        self.local_sun_direction().y > 0.0
+    }
+
+    /// Returns a size of the area around the camera, that must be covered by the precipitation
+    /// occlusion map, or `None` if there's no colliding precipitation.
+    pub(crate) fn precipitation_collision_extent(&self) -> Option<f32> {
+        if !self.global_visibility() {
+            return None;
+        }
+        self.precipitation
+            .iter()
+            .filter(|layer| layer.collision && layer.active_particles() > 0)
+            .map(|layer| layer.area_size.max())
+            .max_by(|a, b| a.total_cmp(b))
+    }
+
+    /// Calculates a local rotation of the driven light, its color and intensity.
+    fn sun_light_state(&self) -> (UnitQuaternion<f32>, Color, f32) {
+        let sun = self.local_sun_direction();
+        let moon = self.local_moon_direction();
+        let (direction, intensity) = if sun.y > 0.0 || moon.y <= 0.0 {
+            (sun, *self.sun_light_intensity)
+        } else {
+            (
+                moon,
+                *self.sun_light_intensity
+                    * MOON_LIGHT_FACTOR
+                    * sky::moon_illumination(*self.moon_phase),
+            )
+        };
+
+        let transmittance =
+            sky::atmospheric_transmittance(direction, *self.rayleigh_scale, *self.mie_scale);
+        let brightness = transmittance.max();
+        let color = if brightness > 0.0 {
+            Color::from(transmittance / brightness)
+        } else {
+            Color::WHITE
+        };
+        let intensity = intensity * brightness * (1.0 - 0.8 * *self.overcast);
+
+        // Directional light shines against its up vector.
+        let rotation = UnitQuaternion::rotation_between(&Vector3::y(), &direction)
+            .unwrap_or_else(|| UnitQuaternion::from_axis_angle(&Vector3::x_axis(), PI));
+
+        (rotation, color, intensity)
+    }
+
+    fn advance_time(&mut self, dt: f32) {
+        let time_of_day = *self.time_of_day + dt * *self.time_scale / 3600.0;
+        if time_of_day >= 24.0 {
+            let days = (time_of_day / 24.0).floor();
+            self.set_day_of_year(*self.day_of_year + days);
+            self.set_moon_phase(*self.moon_phase + days / LUNAR_MONTH);
+        }
+        self.set_time_of_day(time_of_day);
+    }
+
+    fn update_precipitation(&mut self, dt: f32) {
+        let wind = *self.wind;
+        self.layer_states
+            .resize_with(self.precipitation.len(), Default::default);
+        for (layer, state) in self.precipitation.iter().zip(self.layer_states.iter_mut()) {
+            let velocity = self
+                .base
+                .global_transform()
+                .transform_vector(&layer.velocity(wind));
+            state.time += dt;
+            // Keep the offset in the bounds of the area, otherwise the particles will lose
+            // precision over time.
+            state.offset = (state.offset + velocity * dt).zip_map(&layer.area_size, |o, s| {
+                if s > 0.0 {
+                    o.rem_euclid(s)
+                } else {
+                    0.0
+                }
+            });
+
+            let max_particles = layer
+                .max_particles
+                .min(precipitation::MAX_PRECIPITATION_PARTICLES);
+            if state
+                .surface
+                .as_ref()
+                .map_or(true, |(count, _)| *count != max_particles)
+            {
+                state.surface = Some((max_particles, make_particles(max_particles)));
+            }
+
+            let mut material = layer.material.data_ref();
+            for (name, value) in [
+                ("color", PropertyValue::Color(layer.color)),
+                ("areaSize", PropertyValue::Vector3(layer.area_size)),
+                ("fallOffset", PropertyValue::Vector3(state.offset)),
+                ("velocity", PropertyValue::Vector3(velocity)),
+                ("particleSize", PropertyValue::Vector2(layer.particle_size)),
+                ("sway", PropertyValue::Float(layer.sway)),
+                ("time", PropertyValue::Float(state.time % TAU)),
+                (
+                    "isSnow",
+                    PropertyValue::Bool(layer.kind == PrecipitationKind::Snow),
+                ),
+                ("useCollision", PropertyValue::Bool(layer.collision)),
+            ] {
+                Log::verify(material.set_property(&ImmutableString::new(name), value));
+            }
+        }
+    }
+
+    fn update_sky_material(&self) {
+        let sun = self.local_sun_direction();
+        let moon = self.local_moon_direction();
+        let pole = sky::celestial_pole(*self.latitude);
+        // The stars make a full turn in a sidereal day, which is slightly shorter than a solar day.
+        let sidereal_angle =
+            (*self.time_of_day / 24.0 + *self.day_of_year / sky::DAYS_PER_YEAR) * TAU;
+        let moon_intensity =
+            *self.sun_intensity * MOON_LIGHT_FACTOR * sky::moon_illumination(*self.moon_phase);
+
+        let mut material = self.sky_material.data_ref();
+        for (name, value) in [
+            (
+                "sunDirection",
+                PropertyValue::Vector3(self.to_world_direction(sun)),
+            ),
+            (
+                "moonDirection",
+                PropertyValue::Vector3(self.to_world_direction(moon)),
+            ),
+            (
+                "celestialPole",
+                PropertyValue::Vector3(self.to_world_direction(pole)),
+            ),
+            ("siderealAngle", PropertyValue::Float(sidereal_angle)),
+            ("sunIntensity", PropertyValue::Float(*self.sun_intensity)),
+            ("moonIntensity", PropertyValue::Float(moon_intensity)),
+            ("rayleighScale", PropertyValue::Float(*self.rayleigh_scale)),
+            ("mieScale", PropertyValue::Float(*self.mie_scale)),
+            ("mieAnisotropy", PropertyValue::Float(*self.mie_anisotropy)),
+            ("overcast", PropertyValue::Float(*self.overcast)),
+            ("starIntensity", PropertyValue::Float(*self.star_intensity)),
+        ] {
+            Log::verify(material.set_property(&ImmutableString::new(name), value));
+        }
+    }
+}
+
+impl NodeTrait for Weather {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        self.advance_time(context.dt);
+        self.update_precipitation(context.dt);
+        self.update_sky_material();
+
+        if let Some(light) = context
+            .nodes
+            .try_borrow_mut(*self.sun_light)
+            .and_then(|node| node.cast_mut::<DirectionalLight>())
+        {
+            let (rotation, color, intensity) = self.sun_light_state();
+            light.local_transform_mut().set_rotation(rotation);
+            light.base_light_mut().set_color(color);
+            light.base_light_mut().set_intensity(intensity);
+        }
+    }
+
+    fn collect_render_data(&self, ctx: &mut RenderContext) -> RdcControlFlow {
+        if !self.global_visibility() || !self.is_globally_enabled() {
+            return RdcControlFlow::Continue;
+        }
+
+        if renderer::is_shadow_pass(ctx.render_pass_name) {
+            return RdcControlFlow::Continue;
+        }
+
+        ctx.storage.push(
+            &SKY_DOME,
+            &self.sky_material,
+            RenderPath::Forward,
+            0,
+            0,
+            SurfaceInstanceData {
+                world_transform: Matrix4::identity(),
+                bone_matrices: Default::default(),
+                depth_offset: 0.0,
+                blend_shapes_weights: Default::default(),
+                element_range: ElementRange::Full,
+                persistent_identifier: PersistentIdentifier::new_combined(
+                    &SKY_DOME,
+                    self.self_handle,
+                    0,
+                ),
+                node_handle: self.self_handle,
+                vertex_animation: None,
+            },
+        );
+
+        let sort_index = ctx.calculate_sorting_index(*ctx.observer_position);
+        for (index, (layer, state)) in self
+            .precipitation
+            .iter()
+            .zip(self.layer_states.iter())
+            .enumerate()
+        {
+            let Some((max_particles, ref surface)) = state.surface else {
+                continue;
+            };
+            let count = layer.active_particles().min(max_particles);
+            if count == 0 {
+                continue;
+            }
+
+            ctx.storage.push(
+                surface,
+                &layer.material,
+                RenderPath::Forward,
+                0,
+                sort_index,
+                SurfaceInstanceData {
+                    world_transform: Matrix4::identity(),
+                    bone_matrices: Default::default(),
+                    depth_offset: 0.0,
+                    blend_shapes_weights: Default::default(),
+                    element_range: ElementRange::Specific {
+                        offset: 0,
+                        count: count as usize * 2,
+                    },
+                    persistent_identifier: PersistentIdentifier::new_combined(
+                        surface,
+                        self.self_handle,
+                        index + 1,
+                    ),
+                    node_handle: self.self_handle,
+                    vertex_animation: None,
+                },
+            );
+        }
+
+        RdcControlFlow::Continue
+    }
+}
+
+/// Allows you to create weather in declarative manner.
+pub struct WeatherBuilder {
+    base_builder: BaseBuilder,
+    time_of_day: f32,
+    day_of_year: f32,
+    latitude: f32,
+    time_scale: f32,
+    moon_phase: f32,
+    sun_intensity: f32,
+    rayleigh_scale: f32,
+    mie_scale: f32,
+    mie_anisotropy: f32,
+    overcast: f32,
+    star_intensity: f32,
+    sky_material: MaterialResource,
+    sun_light: Handle<Node>,
+    sun_light_intensity: f32,
+    wind: Vector2<f32>,
+    precipitation: Vec<PrecipitationLayer>,
+}
+
+impl WeatherBuilder {
+    /// Creates new builder instance.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            time_of_day: 10.0,
+            day_of_year: 172.0,
+            latitude: 45.0,
+            time_scale: 0.0,
+            moon_phase: 0.5,
+            sun_intensity: 22.0,
+            rayleigh_scale: 1.0,
+            mie_scale: 1.0,
+            mie_anisotropy: 0.76,
+            overcast: 0.0,
+            star_intensity: 1.0,
+            sky_material: MaterialResource::new_ok(
+                ResourceKind::Embedded,
+                Material::standard_sky(),
+            ),
+            sun_light: Default::default(),
+            sun_light_intensity: 1.0,
+            wind: Default::default(),
+            precipitation: Default::default(),
+        }
+    }
+
+    /// Sets desired local solar time in hours.
+    pub fn with_time_of_day(mut self, time_of_day: f32) -> Self {
+        self.time_of_day = time_of_day;
+        self
+    }
+
+    /// Sets desired day of the year.
+    pub fn with_day_of_year(mut self, day: f32) -> Self {
+        self.day_of_year = day;
+        self
+    }
+
+    /// Sets desired latitude in degrees.
+    pub fn with_latitude(mut self, latitude: f32) -> Self {
+        self.latitude = latitude;
+        self
+    }
+
+    /// Sets desired speed of the time.
+    pub fn with_time_scale(mut self, time_scale: f32) -> Self {
+        self.time_scale = time_scale;
+        self
+    }
+
+    /// Sets desired phase of the moon.
+    pub fn with_moon_phase(mut self, phase: f32) -> Self {
+        self.moon_phase = phase;
+        self
+    }
+
+    /// Sets desired intensity of the sun light in the atmosphere.
+    pub fn with_sun_intensity(mut self, intensity: f32) -> Self {
+        self.sun_intensity = intensity;
+        self
+    }
+
+    /// Sets desired multiplier of Rayleigh scattering.
+    pub fn with_rayleigh_scale(mut self, scale: f32) -> Self {
+        self.rayleigh_scale = scale;
+        self
+    }
+
+    /// Sets desired multiplier of Mie scattering.
+    pub fn with_mie_scale(mut self, scale: f32) -> Self {
+        self.mie_scale = scale;
+        self
+    }
+
+    /// Sets desired anisotropy of Mie scattering.
+    pub fn with_mie_anisotropy(mut self, anisotropy: f32) -> Self {
+        self.mie_anisotropy = anisotropy;
+        self
+    }
+
+    /// Sets desired amount of clouds.
+    pub fn with_overcast(mut self, overcast: f32) -> Self {
+        self.overcast = overcast;
+        self
+    }
+
+    /// Sets desired brightness of the stars.
+    pub fn with_star_intensity(mut self, intensity: f32) -> Self {
+        self.star_intensity = intensity;
+        self
+    }
+
+    /// Sets desired material of the sky.
+    pub fn with_sky_material(mut self, material: MaterialResource) -> Self {
+        self.sky_material = material;
+        self
+    }
+
+    /// Sets desired directional light, that will be driven by the weather.
+    pub fn with_sun_light(mut self, light: Handle<Node>) -> Self {
+        self.sun_light = light;
+        self
+    }
+
+    /// Sets desired intensity of the driven directional light.
+    pub fn with_sun_light_intensity(mut self, intensity: f32) -> Self {
+        self.sun_light_intensity = intensity;
+        self
+    }
+
+    /// Sets desired velocity of the wind.
+    pub fn with_wind(mut self, wind: Vector2<f32>) -> Self {
+        self.wind = wind;
+        self
+    }
+
+    /// Sets desired set of precipitation layers.
+    pub fn with_precipitation(mut self, layers: Vec<PrecipitationLayer>) -> Self {
+        self.precipitation = layers;
+        self
+    }
+
+    /// Creates new weather.
+    pub fn build_weather(self) -> Weather {
+        Weather {
+            base: self.base_builder.build_base(),
+            time_of_day: self.time_of_day.rem_euclid(24.0).into(),
+            day_of_year: self.day_of_year.rem_euclid(sky::DAYS_PER_YEAR).into(),
+            latitude: self.latitude.clamp(-90.0, 90.0).into(),
+            time_scale: self.time_scale.max(0.0).into(),
+            moon_phase: self.moon_phase.rem_euclid(1.0).into(),
+            sun_intensity: self.sun_intensity.max(0.0).into(),
+            rayleigh_scale: self.rayleigh_scale.max(0.0).into(),
+            mie_scale: self.mie_scale.max(0.0).into(),
+            mie_anisotropy: self.mie_anisotropy.clamp(0.0, 0.99).into(),
+            overcast: self.overcast.clamp(0.0, 1.0).into(),
+            star_intensity: self.star_intensity.max(0.0).into(),
+            sky_material: self.sky_material.into(),
+            sun_light: self.sun_light.into(),
+            sun_light_intensity: self.sun_light_intensity.max(0.0).into(),
+            wind: self.wind.into(),
+            precipitation: self.precipitation.into(),
+            layer_states: Default::default(),
+        }
+    }
+
+    /// Creates new weather node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_weather())
+    }
+
+    /// Creates new weather node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        generic_animation::value::{
+            BoundValue, BoundValueCollection, TrackValue, ValueBinding, ValueType,
+        },
+        scene::{
+            animation::BoundValueCollectionExt,
+            base::BaseBuilder,
+            node::Node,
+            weather::{precipitation::PrecipitationLayer, Weather, WeatherBuilder},
+        },
+    };
+
+    #[test]
+    fn test_weather_time_advance() {
+        let mut weather = WeatherBuilder::new(BaseBuilder::new())
+            .with_time_of_day(23.0)
+            .with_day_of_year(364.5)
+            .with_moon_phase(0.0)
+            .with_time_scale(3600.0)
+            .build_weather();
+        weather.advance_time(2.0);
+        assert!((weather.time_of_day() - 1.0).abs() < 1.0e-3);
+        assert!(weather.day_of_year() < 1.0);
+        assert!(weather.moon_phase() > 0.0);
+    }
+
+    #[test]
+    fn test_weather_sun_light() {
+        let noon = WeatherBuilder::new(BaseBuilder::new())
+            .with_time_of_day(12.0)
+            .build_weather();
+        let (rotation, _, noon_intensity) = noon.sun_light_state();
+        // The light shines down from the sun.
+        let light_direction = -(rotation * crate::core::algebra::Vector3::y());
+        assert!(light_direction.dot(&noon.local_sun_direction()) < -0.99);
+
+        let night = WeatherBuilder::new(BaseBuilder::new())
+            .with_time_of_day(0.0)
+            .build_weather();
+        let (_, _, night_intensity) = night.sun_light_state();
+        assert!(night_intensity < noon_intensity * 0.1);
+    }
+
+    #[test]
+    fn test_weather_properties_are_animatable() {
+        let mut node = Node::new(
+            WeatherBuilder::new(BaseBuilder::new())
+                .with_precipitation(vec![PrecipitationLayer::snow()])
+                .build_weather(),
+        );
+
+        BoundValueCollection {
+            values: vec![
+                BoundValue {
+                    binding: ValueBinding::Property {
+                        name: "time_of_day".to_string(),
+                        value_type: ValueType::F32,
+                    },
+                    value: TrackValue::Real(18.5),
+                },
+                BoundValue {
+                    binding: ValueBinding::Property {
+                        name: "precipitation[0].intensity".to_string(),
+                        value_type: ValueType::F32,
+                    },
+                    value: TrackValue::Real(0.25),
+                },
+            ],
+        }
+        .apply(&mut node);
+
+        let weather = node.cast::<Weather>().unwrap();
+        assert_eq!(weather.time_of_day(), 18.5);
+        assert_eq!(weather.precipitation()[0].intensity, 0.25);
+        assert_eq!(weather.precipitation_collision_extent(), Some(30.0));
+    }
+}
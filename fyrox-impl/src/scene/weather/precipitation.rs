@@ -0,0 +1,222 @@
+//! Precipitation layers of the weather. See [`PrecipitationLayer`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Vector2, Vector3},
+        color::Color,
+        math::TriangleDefinition,
+        rand::{rngs::StdRng, Rng, SeedableRng},
+        reflect::prelude::*,
+        uuid_provider,
+        visitor::prelude::*,
+    },
+    material::{Material, MaterialResource},
+    scene::mesh::{
+        buffer::{
+            TriangleBuffer, VertexAttributeDataType, VertexAttributeDescriptor,
+            VertexAttributeUsage, VertexBuffer, VertexTrait,
+        },
+        surface::{SurfaceData, SurfaceResource},
+    },
+};
+use fyrox_resource::untyped::ResourceKind;
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// Maximum amount of particles of a single precipitation layer.
+pub const MAX_PRECIPITATION_PARTICLES: u32 = 262144;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+struct PrecipitationVertex {
+    // Position of the particle in the precipitation area, in [0; 1] range.
+    seed: Vector3<f32>,
+    // Corner of the particle quad in [-1; 1] range.
+    corner: Vector2<f32>,
+    // Random phase of the particle in [0; 1] range.
+    phase: f32,
+}
+
+impl VertexTrait for PrecipitationVertex {
+    fn layout() -> &'static [VertexAttributeDescriptor] {
+        &[
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::Position,
+                data_type: VertexAttributeDataType::F32,
+                size: 3,
+                divisor: 0,
+                shader_location: 0,
+                normalized: false,
+            },
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::TexCoord0,
+                data_type: VertexAttributeDataType::F32,
+                size: 2,
+                divisor: 0,
+                shader_location: 1,
+                normalized: false,
+            },
+            VertexAttributeDescriptor {
+                usage: VertexAttributeUsage::Custom0,
+                data_type: VertexAttributeDataType::F32,
+                size: 1,
+                divisor: 0,
+                shader_location: 2,
+                normalized: false,
+            },
+        ]
+    }
+}
+
+/// Creates a surface with the given amount of particle quads, that are randomly placed in a unit
+/// cube. The particles are animated entirely on GPU, so the surface never changes.
+pub(crate) fn make_particles(count: u32) -> SurfaceResource {
+    let mut rng = StdRng::seed_from_u64(count as u64);
+    let mut vertices = Vec::with_capacity(count as usize * 4);
+    let mut triangles = Vec::with_capacity(count as usize * 2);
+    for i in 0..count {
+        let seed = Vector3::new(rng.gen(), rng.gen(), rng.gen());
+        let phase = rng.gen();
+        for corner in [
+            Vector2::new(-1.0, -1.0),
+            Vector2::new(1.0, -1.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(-1.0, 1.0),
+        ] {
+            vertices.push(PrecipitationVertex {
+                seed,
+                corner,
+                phase,
+            });
+        }
+        let base = i * 4;
+        triangles.push(TriangleDefinition([base, base + 1, base + 2]));
+        triangles.push(TriangleDefinition([base, base + 2, base + 3]));
+    }
+    SurfaceResource::new_ok(
+        ResourceKind::Embedded,
+        SurfaceData::new(
+            VertexBuffer::new(vertices.len(), vertices).unwrap(),
+            TriangleBuffer::new(triangles),
+        ),
+    )
+}
+
+/// Kind of the precipitation, that defines the look and the motion of the particles.
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, Reflect, Visit, AsRefStr, EnumString, VariantNames,
+)]
+pub enum PrecipitationKind {
+    /// Fast streaks, that are stretched along the direction of the fall and leave splashes on the
+    /// surfaces.
+    #[default]
+    Rain,
+    /// Slow round flakes, that sway while falling.
+    Snow,
+}
+
+uuid_provider!(PrecipitationKind = "c7a1e5d3-4b2f-4e86-9a0d-5f3b8c1e7d24");
+
+/// A layer of rain or snow particles. Particles are placed in a box of [`Self::area_size`] around
+/// the camera, the box is anchored to the world, so the particles do not follow the camera, but wrap
+/// around the edges of the box. Use a few layers with different sizes and speeds to add depth to
+/// the precipitation.
+#[derive(Clone, Debug, PartialEq, Reflect, Visit)]
+pub struct PrecipitationLayer {
+    /// Kind of the precipitation.
+    pub kind: PrecipitationKind,
+
+    /// Density of the precipitation in `[0; 1]` range. Defines the fraction of
+    /// [`Self::max_particles`], that is drawn. Could be animated to start or stop the precipitation
+    /// smoothly.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub intensity: f32,
+
+    /// Amount of particles at full intensity.
+    #[reflect(min_value = 0.0, max_value = 262144.0)]
+    pub max_particles: u32,
+
+    /// Size of the box around the camera, that contains the particles.
+    pub area_size: Vector3<f32>,
+
+    /// Speed of the fall in meters per second.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub fall_speed: f32,
+
+    /// Size of a particle - width and length (for rain) in meters.
+    pub particle_size: Vector2<f32>,
+
+    /// Amplitude of horizontal swaying of the particles in meters. Suitable for snow.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub sway: f32,
+
+    /// Color of the particles, alpha defines their opacity.
+    pub color: Color,
+
+    /// Whether the particles collide with the scene or not. Colliding particles disappear under
+    /// roofs and leave splashes on the surfaces.
+    pub collision: bool,
+
+    /// Material of the particles. It must use a shader, that supports the vertex layout of the
+    /// precipitation, for example the standard precipitation shader.
+    pub material: MaterialResource,
+}
+
+impl Default for PrecipitationLayer {
+    fn default() -> Self {
+        Self::rain()
+    }
+}
+
+uuid_provider!(PrecipitationLayer = "2f8d6b41-93c7-4a5e-b1f0-7e4c2d9a6b58");
+
+impl PrecipitationLayer {
+    /// Creates a layer of rain with reasonable defaults.
+    pub fn rain() -> Self {
+        Self {
+            kind: PrecipitationKind::Rain,
+            intensity: 1.0,
+            max_particles: 16384,
+            area_size: Vector3::new(40.0, 30.0, 40.0),
+            fall_speed: 9.0,
+            particle_size: Vector2::new(0.01, 0.4),
+            sway: 0.0,
+            color: Color::from_rgba(200, 210, 225, 110),
+            collision: true,
+            material: MaterialResource::new_ok(
+                ResourceKind::Embedded,
+                Material::standard_precipitation(),
+            ),
+        }
+    }
+
+    /// Creates a layer of snow with reasonable defaults.
+    pub fn snow() -> Self {
+        Self {
+            kind: PrecipitationKind::Snow,
+            intensity: 1.0,
+            max_particles: 16384,
+            area_size: Vector3::new(30.0, 20.0, 30.0),
+            fall_speed: 1.0,
+            particle_size: Vector2::new(0.03, 0.03),
+            sway: 0.4,
+            color: Color::from_rgba(255, 255, 255, 220),
+            collision: true,
+            material: MaterialResource::new_ok(
+                ResourceKind::Embedded,
+                Material::standard_precipitation(),
+            ),
+        }
+    }
+
+    /// Returns amount of particles, that is drawn at the current intensity.
+    pub fn active_particles(&self) -> u32 {
+        (self.intensity.clamp(0.0, 1.0)
+            * self.max_particles.min(MAX_PRECIPITATION_PARTICLES) as f32)
+            .ceil() as u32
+    }
+
+    /// Returns velocity of the particles for the given horizontal wind velocity (in XZ plane).
+    pub fn velocity(&self, wind: Vector2<f32>) -> Vector3<f32> {
+        Vector3::new(wind.x, -self.fall_speed, wind.y)
+    }
+}
@@ -0,0 +1,140 @@
+//! Simplified astronomy and atmosphere model of the weather. All directions are in the local space of
+//! the weather node: +X is east, +Y is up, +Z is north.
+
+use crate::core::algebra::Vector3;
+use std::f32::consts::{PI, TAU};
+
+/// Amount of days in a year.
+pub const DAYS_PER_YEAR: f32 = 365.0;
+
+/// Tilt of the rotation axis of the planet relative to its orbit, in radians.
+const AXIAL_TILT: f32 = 23.44 * PI / 180.0;
+
+/// Rayleigh scattering coefficients of the atmosphere at sea level (per meter), multiplied by the
+/// scale height of the Rayleigh scattering (8 km).
+const RAYLEIGH_OPTICAL_DEPTH: Vector3<f32> = Vector3::new(0.044, 0.104, 0.179);
+
+/// Mie scattering coefficient of the atmosphere at sea level (per meter), multiplied by the scale
+/// height of the Mie scattering (1.2 km).
+const MIE_OPTICAL_DEPTH: f32 = 0.0252;
+
+/// Returns declination of the sun (angle between the sun and the celestial equator) in radians for
+/// the given day of the year.
+pub fn sun_declination(day_of_year: f32) -> f32 {
+    -AXIAL_TILT * (TAU * (day_of_year + 10.0) / DAYS_PER_YEAR).cos()
+}
+
+/// Returns a direction to a celestial body with the given declination and hour angle (both in
+/// radians), observed at the given latitude (in degrees).
+pub fn celestial_direction(declination: f32, hour_angle: f32, latitude: f32) -> Vector3<f32> {
+    let latitude = latitude.to_radians();
+    let (sin_dec, cos_dec) = declination.sin_cos();
+    let (sin_hour, cos_hour) = hour_angle.sin_cos();
+    let (sin_lat, cos_lat) = latitude.sin_cos();
+    Vector3::new(
+        -cos_dec * sin_hour,
+        sin_lat * sin_dec + cos_lat * cos_dec * cos_hour,
+        sin_dec * cos_lat - cos_dec * cos_hour * sin_lat,
+    )
+    .try_normalize(f32::EPSILON)
+    .unwrap_or_else(Vector3::y)
+}
+
+/// Returns a direction to the sun for the given local solar time (in hours), day of the year and
+/// latitude (in degrees).
+pub fn sun_direction(time_of_day: f32, day_of_year: f32, latitude: f32) -> Vector3<f32> {
+    let hour_angle = (time_of_day - 12.0) / 24.0 * TAU;
+    celestial_direction(sun_declination(day_of_year), hour_angle, latitude)
+}
+
+/// Returns a direction to the moon for the given local solar time (in hours), day of the year,
+/// latitude (in degrees) and phase of the moon. Phase is in `[0; 1]` range, where 0 is the new moon
+/// (the moon is next to the sun) and 0.5 is the full moon (the moon is opposite to the sun).
+pub fn moon_direction(
+    time_of_day: f32,
+    day_of_year: f32,
+    latitude: f32,
+    moon_phase: f32,
+) -> Vector3<f32> {
+    let hour_angle = (time_of_day - 12.0) / 24.0 * TAU - moon_phase * TAU;
+    // The orbit of the moon is close to the orbit of the planet, so the full moon is on the opposite
+    // side of the celestial equator.
+    let declination = sun_declination(day_of_year) * (moon_phase * TAU).cos();
+    celestial_direction(declination, hour_angle, latitude)
+}
+
+/// Returns illuminated fraction of the visible disk of the moon for the given phase.
+pub fn moon_illumination(moon_phase: f32) -> f32 {
+    0.5 * (1.0 - (moon_phase * TAU).cos())
+}
+
+/// Returns a direction to the celestial pole (the axis, that the sky rotates around) at the given
+/// latitude (in degrees).
+pub fn celestial_pole(latitude: f32) -> Vector3<f32> {
+    let (sin_lat, cos_lat) = latitude.to_radians().sin_cos();
+    Vector3::new(0.0, sin_lat, cos_lat)
+}
+
+/// Returns a fraction of the light of each color channel (red, green, blue), that reaches the ground
+/// through the atmosphere from the given direction. The light of a body near the horizon passes
+/// through thicker layer of the air, so more blue light is scattered out and the light is reddish.
+pub fn atmospheric_transmittance(
+    direction: Vector3<f32>,
+    rayleigh_scale: f32,
+    mie_scale: f32,
+) -> Vector3<f32> {
+    if direction.y <= 0.0 {
+        return Vector3::default();
+    }
+    // Relative air mass (Kasten-Young formula).
+    let zenith_angle = direction.y.clamp(-1.0, 1.0).acos().to_degrees();
+    let air_mass =
+        1.0 / (direction.y + 0.50572 * (96.07995 - zenith_angle).max(f32::EPSILON).powf(-1.6364));
+    let optical_depth = (RAYLEIGH_OPTICAL_DEPTH * rayleigh_scale)
+        .add_scalar(MIE_OPTICAL_DEPTH * mie_scale)
+        * air_mass;
+    optical_depth.map(|d| (-d).exp())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::scene::weather::sky::{
+        atmospheric_transmittance, moon_direction, moon_illumination, sun_direction,
+    };
+
+    #[test]
+    fn test_sun_position() {
+        // Equinox at the equator: the sun rises in the east and is at zenith at noon.
+        let noon = sun_direction(12.0, 80.0, 0.0);
+        assert!(noon.y > 0.99);
+        let morning = sun_direction(9.0, 80.0, 0.0);
+        assert!(morning.x > 0.0 && morning.y > 0.0);
+        assert!(sun_direction(0.0, 80.0, 0.0).y < -0.99);
+
+        // In the northern hemisphere, the noon sun is in the south and higher in summer.
+        let summer = sun_direction(12.0, 172.0, 45.0);
+        let winter = sun_direction(12.0, 355.0, 45.0);
+        assert!(summer.z < 0.0 && winter.z < 0.0);
+        assert!(summer.y > winter.y);
+    }
+
+    #[test]
+    fn test_moon_position() {
+        // Full moon is opposite to the sun.
+        let sun = sun_direction(22.0, 100.0, 30.0);
+        let moon = moon_direction(22.0, 100.0, 30.0, 0.5);
+        assert!(sun.dot(&moon) < -0.9);
+        assert!(moon_illumination(0.5) > 0.99);
+        assert!(moon_illumination(0.0) < 0.01);
+    }
+
+    #[test]
+    fn test_atmospheric_transmittance() {
+        let zenith = atmospheric_transmittance(sun_direction(12.0, 80.0, 0.0), 1.0, 1.0);
+        let horizon = atmospheric_transmittance(sun_direction(17.9, 80.0, 0.0), 1.0, 1.0);
+        assert!(zenith.x > 0.9);
+        // Light near the horizon is dimmer and redder.
+        assert!(horizon.x < zenith.x);
+        assert!(horizon.z / horizon.x < zenith.z / zenith.x);
+    }
+}
@@ -0,0 +1,135 @@
+//! Job system allows to process independent pieces of data on multiple threads. See [`JobSystem`] docs
+//! for more info.
+
+#![warn(missing_docs)]
+
+use lazy_static::lazy_static;
+use rayon::prelude::*;
+
+/// Minimal amount of items processed by a single job. Splitting the work into smaller pieces costs
+/// more than it gains.
+pub const MIN_JOB_SIZE: usize = 32;
+
+lazy_static! {
+    static ref GLOBAL: JobSystem = JobSystem::new(0);
+}
+
+/// Job system is a pool of worker threads, that is used to process independent pieces of data in
+/// parallel. It is used by the engine to update scene nodes, for example. If the platform does
+/// not support threads (WebAssembly), or the job system was created with a single thread, every
+/// job is executed on the calling thread.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_impl::utils::jobs::JobSystem;
+/// let mut items = vec![1, 2, 3, 4];
+/// JobSystem::global().for_each(items.iter_mut().collect(), |item| *item *= 2);
+/// assert_eq!(items, [2, 4, 6, 8]);
+/// ```
+pub struct JobSystem {
+    pool: Option<rayon::ThreadPool>,
+}
+
+impl JobSystem {
+    /// Creates new job system with the given amount of worker threads. Zero means that the amount
+    /// of threads will be equal to the amount of logical cores of the CPU.
+    pub fn new(thread_count: usize) -> Self {
+        let pool = if thread_count == 1 {
+            None
+        } else {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(thread_count)
+                .thread_name(|i| format!("FyroxJobWorker{i}"))
+                .build()
+                .ok()
+        };
+
+        Self { pool }
+    }
+
+    /// Returns a reference to the job system, that is shared across the engine.
+    pub fn global() -> &'static JobSystem {
+        &GLOBAL
+    }
+
+    /// Returns the amount of worker threads.
+    pub fn thread_count(&self) -> usize {
+        self.pool
+            .as_ref()
+            .map_or(1, |pool| pool.current_num_threads())
+    }
+
+    /// Executes the given job for every item in parallel and blocks until every job is finished.
+    /// The order of execution is undefined.
+    pub fn for_each<T, F>(&self, items: Vec<T>, job: F)
+    where
+        T: Send,
+        F: Fn(T) + Send + Sync,
+    {
+        match self.pool.as_ref() {
+            Some(pool) if items.len() > MIN_JOB_SIZE => pool.install(|| {
+                items
+                    .into_par_iter()
+                    .with_min_len(MIN_JOB_SIZE)
+                    .for_each(job)
+            }),
+            _ => items.into_iter().for_each(job),
+        }
+    }
+
+    /// Executes the given job on a worker thread, while the `main` closure is executed on the
+    /// calling thread, and blocks until both are finished. Unlike other jobs, the main closure does
+    /// not need to be [`Send`], so it could access the data, that is bound to the calling thread.
+    /// If there's no worker threads, the job is executed on the calling thread first.
+    pub fn overlap<T, R, J, M>(&self, job: J, main: M) -> (T, R)
+    where
+        T: Send,
+        J: FnOnce() -> T + Send,
+        M: FnOnce() -> R,
+    {
+        match self.pool.as_ref() {
+            Some(pool) => {
+                let mut job_result = None;
+                let main_result = pool.in_place_scope(|scope| {
+                    scope.spawn(|_| job_result = Some(job()));
+                    main()
+                });
+                (
+                    job_result.expect("The scope must wait until the job is finished!"),
+                    main_result,
+                )
+            }
+            None => (job(), main()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::utils::jobs::{JobSystem, MIN_JOB_SIZE};
+
+    #[test]
+    fn test_for_each() {
+        for job_system in [JobSystem::new(1), JobSystem::new(4)] {
+            let mut items = (0..MIN_JOB_SIZE * 10).collect::<Vec<_>>();
+            job_system.for_each(items.iter_mut().collect(), |item| *item *= 2);
+            assert!(items.iter().enumerate().all(|(i, item)| *item == i * 2));
+        }
+    }
+
+    #[test]
+    fn test_overlap() {
+        for job_system in [JobSystem::new(1), JobSystem::new(4)] {
+            let mut items = vec![1, 2, 3];
+            let main_thread = std::thread::current().id();
+            let (sum, main_thread_id) = job_system.overlap(
+                || items.iter_mut().map(std::mem::take).sum::<i32>(),
+                || std::thread::current().id(),
+            );
+            assert_eq!(sum, 6);
+            assert_eq!(items, [0, 0, 0]);
+            assert_eq!(main_thread_id, main_thread);
+        }
+    }
+}
@@ -10,7 +10,8 @@ use crate::{
     asset::manager::{ResourceManager, ResourceRegistrationError},
     core::{
         algebra::{Matrix3, Matrix4, Point3, Vector2, Vector3},
-        math::{Matrix4Ext, TriangleDefinition},
+        log::Log,
+        math::{self, Matrix4Ext, TriangleDefinition},
         pool::Handle,
         reflect::prelude::*,
         sstorage::ImmutableString,
@@ -18,9 +19,11 @@ use crate::{
     },
     graph::SceneGraph,
     material::PropertyValue,
-    resource::texture::{Texture, TextureKind, TexturePixelKind, TextureResource},
+    resource::texture::{
+        ibl::LinearImage, Texture, TextureKind, TexturePixelKind, TextureResource,
+    },
     scene::{
-        light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
+        light::{directional::DirectionalLight, point::PointLight, spot::SpotLight, BaseLight},
         mesh::{
             buffer::{
                 VertexAttributeDataType, VertexAttributeDescriptor, VertexAttributeUsage,
@@ -152,8 +155,204 @@ pub struct Lightmap {
     pub patches: FxHashMap<u64, SurfaceDataPatchWrapper>,
 }
 
+/// Defines how emissive materials contribute light to a lightmap. Every surface with an emissive material
+/// (a material with `emissionTexture` property set) is approximated by a set of virtual light sources,
+/// scattered over the surface. Each virtual light emits light in the hemisphere around the surface normal,
+/// its color is taken from the emission texture (multiplied by `emissionStrength` property) at the point
+/// and its intensity is proportional to the area of the surface that it covers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmissionBakeSettings {
+    /// Whether the emissive materials should contribute light or not. Default is `true`.
+    pub enabled: bool,
+    /// Amount of virtual lights per unit of area (square meters) of an emissive surface. Default is 16.
+    pub lights_per_square_unit: f32,
+    /// Max amount of virtual lights per single surface. Default is 64.
+    pub max_lights_per_surface: usize,
+    /// Distance at which the light of an emissive surface fades out completely. Default is 4.0.
+    pub range: f32,
+    /// Multiplier of the intensity of the emitted light. Default is 1.0.
+    pub intensity: f32,
+}
+
+impl Default for EmissionBakeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            lights_per_square_unit: 16.0,
+            max_lights_per_surface: 64,
+            range: 4.0,
+            intensity: 1.0,
+        }
+    }
+}
+
+/// Offset of virtual lights along the normal of an emissive surface. It prevents the surface from
+/// shadowing its own light.
+const EMITTER_OFFSET: f32 = 0.02;
+
+/// Creates a set of virtual lights for each emissive surface of the given mesh.
+fn gather_emitters(
+    mesh: &Mesh,
+    settings: &EmissionBakeSettings,
+    lights: &mut Vec<LightDefinition>,
+) {
+    let global_transform = mesh.global_transform();
+    let normal_matrix = global_transform
+        .basis()
+        .try_inverse()
+        .map(|m| m.transpose())
+        .unwrap_or_else(Matrix3::identity);
+
+    for surface in mesh.surfaces() {
+        let mut material_state = surface.material().state();
+        let Some(material) = material_state.data() else {
+            continue;
+        };
+
+        let Some(PropertyValue::Sampler {
+            value: Some(emission_texture),
+            ..
+        }) = material.property_ref(&ImmutableString::new("emissionTexture"))
+        else {
+            continue;
+        };
+
+        let strength = match material.property_ref(&ImmutableString::new("emissionStrength")) {
+            Some(PropertyValue::Vector3(strength)) => *strength,
+            Some(PropertyValue::Float(strength)) => Vector3::repeat(*strength),
+            _ => Vector3::repeat(1.0),
+        };
+        let tex_coord_scale = match material.property_ref(&ImmutableString::new("texCoordScale")) {
+            Some(PropertyValue::Vector2(scale)) => *scale,
+            _ => Vector2::repeat(1.0),
+        };
+
+        let image = {
+            let mut texture_state = emission_texture.state();
+            let Some(texture) = texture_state.data() else {
+                Log::warn(format!(
+                    "Emission texture {} is not loaded and will be ignored by the lightmapper.",
+                    emission_texture.kind()
+                ));
+                continue;
+            };
+            match LinearImage::new(texture) {
+                Ok(image) => image,
+                Err(err) => {
+                    Log::warn(format!(
+                        "Emission texture {} cannot be used by the lightmapper. Reason: {err}",
+                        emission_texture.kind()
+                    ));
+                    continue;
+                }
+            }
+        };
+
+        struct EmissiveTriangle {
+            vertices: [(Vector3<f32>, Vector3<f32>, Vector2<f32>); 3],
+            area: f32,
+        }
+
+        let data = surface.data();
+        let data = data.data_ref();
+        let mut total_area = 0.0;
+        let mut triangles = Vec::with_capacity(data.geometry_buffer.len());
+        for triangle in data.geometry_buffer.iter() {
+            let mut vertices = [(Vector3::default(), Vector3::default(), Vector2::default()); 3];
+            for (vertex, &index) in vertices.iter_mut().zip(triangle.0.iter()) {
+                let Some(view) = data.vertex_buffer.get(index as usize) else {
+                    continue;
+                };
+                let position = view
+                    .read_3_f32(VertexAttributeUsage::Position)
+                    .unwrap_or_default();
+                let normal = view
+                    .read_3_f32(VertexAttributeUsage::Normal)
+                    .unwrap_or_default();
+                *vertex = (
+                    global_transform
+                        .transform_point(&Point3::from(position))
+                        .coords,
+                    normal_matrix * normal,
+                    view.read_2_f32(VertexAttributeUsage::TexCoord0)
+                        .unwrap_or_default(),
+                );
+            }
+            let area = math::triangle_area(vertices[0].0, vertices[1].0, vertices[2].0);
+            if area > f32::EPSILON {
+                total_area += area;
+                triangles.push(EmissiveTriangle { vertices, area });
+            }
+        }
+
+        if triangles.is_empty() {
+            continue;
+        }
+
+        let count = ((total_area * settings.lights_per_square_unit).ceil() as usize)
+            .clamp(1, settings.max_lights_per_surface.max(1));
+        let intensity = settings.intensity * total_area / count as f32;
+
+        // Stratified sampling over the whole area of the surface.
+        let mut triangle_index = 0;
+        let mut area_offset = 0.0;
+        for i in 0..count {
+            let target = (i as f32 + 0.5) / count as f32 * total_area;
+            while triangle_index + 1 < triangles.len()
+                && area_offset + triangles[triangle_index].area < target
+            {
+                area_offset += triangles[triangle_index].area;
+                triangle_index += 1;
+            }
+
+            let triangle = &triangles[triangle_index];
+            let r1 = ((target - area_offset) / triangle.area).clamp(0.0, 1.0);
+            let r2 = (i as f32 * 0.618_034).fract();
+            let sqrt_r1 = r1.sqrt();
+            let weights = [1.0 - sqrt_r1, sqrt_r1 * (1.0 - r2), sqrt_r1 * r2];
+
+            let mut position = Vector3::default();
+            let mut normal = Vector3::default();
+            let mut tex_coord = Vector2::default();
+            for ((p, n, t), w) in triangle.vertices.iter().zip(weights) {
+                position += p * w;
+                normal += n * w;
+                tex_coord += t * w;
+            }
+            let [(a, _, _), (b, _, _), (c, _, _)] = &triangle.vertices;
+            let normal = normal
+                .try_normalize(f32::EPSILON)
+                .or_else(|| (b - a).cross(&(c - a)).try_normalize(f32::EPSILON))
+                .unwrap_or_else(Vector3::y);
+
+            let color = strength.component_mul(&image.sample_uv_repeat(
+                tex_coord.x * tex_coord_scale.x,
+                tex_coord.y * tex_coord_scale.y,
+            ));
+            if color.max() <= 0.001 {
+                continue;
+            }
+
+            lights.push(LightDefinition::Spot(SpotLightDefinition {
+                intensity,
+                color,
+                // Spot light direction points towards the light source.
+                direction: -normal,
+                position: position + normal * EMITTER_OFFSET,
+                distance: settings.range,
+                sqr_distance: settings.range * settings.range,
+                // Hemispherical emission with smooth fade near the surface plane.
+                edge0: 0.0,
+                edge1: 80.0f32.to_radians().cos(),
+            }));
+        }
+    }
+}
+
 struct Instance {
     owner: Handle<Node>,
+    // Lights that affect the instance, respecting light linking.
+    lights: Vec<Handle<Node>>,
     source_data: SurfaceResource,
     data: Option<lightmap::input::Mesh>,
     transform: Matrix4<f32>,
@@ -317,13 +516,37 @@ pub struct LightmapInputData {
     data_set: FxHashMap<u64, SurfaceResource>,
     instances: Vec<Instance>,
     lights: FxHashMap<Handle<Node>, LightDefinition>,
+    emitters: Vec<LightDefinition>,
 }
 
 impl LightmapInputData {
-    /// Creates a new input data that can be later used to generate a lightmap.
+    /// Creates a new input data that can be later used to generate a lightmap. Emissive materials
+    /// are baked using default [`EmissionBakeSettings`].
     pub fn from_scene<F>(
+        scene: &Scene,
+        filter: F,
+        cancellation_token: CancellationToken,
+        progress_indicator: ProgressIndicator,
+    ) -> Result<Self, LightmapGenerationError>
+    where
+        F: FnMut(Handle<Node>, &Node) -> bool,
+    {
+        Self::from_scene_with_emission(
+            scene,
+            filter,
+            &EmissionBakeSettings::default(),
+            cancellation_token,
+            progress_indicator,
+        )
+    }
+
+    /// Creates a new input data that can be later used to generate a lightmap. `emission` defines
+    /// how emissive materials contribute light to the lightmap, see [`EmissionBakeSettings`] docs
+    /// for more info.
+    pub fn from_scene_with_emission<F>(
         scene: &Scene,
         mut filter: F,
+        emission: &EmissionBakeSettings,
         cancellation_token: CancellationToken,
         progress_indicator: ProgressIndicator,
     ) -> Result<Self, LightmapGenerationError>
@@ -415,6 +638,7 @@ impl LightmapInputData {
 
         let mut instances = Vec::new();
         let mut data_set = FxHashMap::default();
+        let mut emitters = Vec::new();
 
         'node_loop: for (handle, node) in scene.graph.pair_iter() {
             if !filter(handle, node) {
@@ -425,7 +649,19 @@ impl LightmapInputData {
                 if !mesh.global_visibility() || !mesh.is_globally_enabled() {
                     continue;
                 }
+                if emission.enabled {
+                    gather_emitters(mesh, emission, &mut emitters);
+                }
                 let global_transform = mesh.global_transform();
+                let mesh_lights = lights
+                    .keys()
+                    .filter(|light| {
+                        scene.graph[**light]
+                            .query_component_ref::<BaseLight>()
+                            .map_or(true, |base_light| base_light.affects(&scene.graph, handle))
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
                 'surface_loop: for surface in mesh.surfaces() {
                     // Check material for compatibility.
 
@@ -448,6 +684,7 @@ impl LightmapInputData {
 
                     instances.push(Instance {
                         owner: handle,
+                        lights: mesh_lights.clone(),
                         source_data: data.clone(),
                         transform: global_transform,
                         // Calculated down below.
@@ -461,6 +698,7 @@ impl LightmapInputData {
             data_set,
             instances,
             lights,
+            emitters,
         })
     }
 }
@@ -506,6 +744,7 @@ impl Lightmap {
             data_set,
             mut instances,
             lights,
+            emitters,
         } = data;
 
         progress_indicator.set_stage(ProgressStage::UvGeneration, data_set.len() as u32);
@@ -604,16 +843,22 @@ impl Lightmap {
             .iter_mut()
             .filter_map(|i| i.data.take())
             .collect::<Vec<_>>();
-        let light_definitions = lights.values().cloned().collect::<Vec<_>>();
         for (mesh, instance) in meshes.iter().zip(instances.iter()) {
             if cancellation_token.is_cancelled() {
                 return Err(LightmapGenerationError::Cancelled);
             }
 
+            let light_definitions = instance
+                .lights
+                .iter()
+                .filter_map(|light| lights.get(light).cloned())
+                .chain(emitters.iter().cloned())
+                .collect::<Vec<_>>();
+
             let lightmap = generate_lightmap(mesh, &meshes, &light_definitions, texels_per_unit);
             map.entry(instance.owner).or_default().push(LightmapEntry {
                 texture: Some(TextureResource::new_ok(Default::default(), lightmap)),
-                lights: lights.keys().cloned().collect(),
+                lights: instance.lights.clone(),
             });
 
             progress_indicator.advance_progress();
@@ -679,7 +924,12 @@ fn generate_lightmap(
 mod test {
     use crate::{
         asset::ResourceData,
-        core::algebra::{Matrix4, Vector3},
+        core::{
+            algebra::{Matrix4, Vector3},
+            sstorage::ImmutableString,
+        },
+        material::{Material, MaterialResource, PropertyValue},
+        resource::texture::{Texture, TextureKind, TexturePixelKind, TextureResource},
         scene::{
             base::BaseBuilder,
             light::{point::PointLightBuilder, BaseLightBuilder},
@@ -745,4 +995,82 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_bake_emissive_surface() {
+        let mut scene = Scene::new();
+
+        let floor = MeshBuilder::new(BaseBuilder::new())
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceResource::new_ok(
+                ResourceKind::Embedded,
+                SurfaceData::make_quad(
+                    &(Matrix4::new_scaling(4.0)
+                        * Matrix4::from_axis_angle(
+                            &Vector3::x_axis(),
+                            std::f32::consts::FRAC_PI_2,
+                        )),
+                ),
+            ))
+            .build()])
+            .build(&mut scene.graph);
+
+        let emission_texture = TextureResource::new_ok(
+            ResourceKind::Embedded,
+            Texture::from_bytes(
+                TextureKind::Rectangle {
+                    width: 1,
+                    height: 1,
+                },
+                TexturePixelKind::RGBA8,
+                vec![255; 4],
+            )
+            .unwrap(),
+        );
+        let mut material = Material::standard();
+        material
+            .set_property(
+                &ImmutableString::new("emissionTexture"),
+                PropertyValue::Sampler {
+                    value: Some(emission_texture),
+                    fallback: Default::default(),
+                },
+            )
+            .unwrap();
+
+        // Emissive panel above the floor, facing down.
+        MeshBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 1.0, 0.0))
+                    .build(),
+            ),
+        )
+        .with_surfaces(vec![SurfaceBuilder::new(SurfaceResource::new_ok(
+            ResourceKind::Embedded,
+            SurfaceData::make_quad(&Matrix4::from_axis_angle(
+                &Vector3::x_axis(),
+                -std::f32::consts::FRAC_PI_2,
+            )),
+        ))
+        .with_material(MaterialResource::new_ok(ResourceKind::Embedded, material))
+        .build()])
+        .build(&mut scene.graph);
+
+        scene.graph.update_hierarchical_data();
+
+        let data = LightmapInputData::from_scene(
+            &scene,
+            |_, _| true,
+            Default::default(),
+            Default::default(),
+        )
+        .unwrap();
+        assert!(!data.emitters.is_empty());
+
+        let lightmap =
+            Lightmap::new(data, 16, 0.005, Default::default(), Default::default()).unwrap();
+
+        let texture = lightmap.map[&floor][0].texture.as_ref().unwrap();
+        assert!(texture.data_ref().data().iter().any(|&byte| byte > 0));
+    }
 }
@@ -0,0 +1,325 @@
+//! Line server is a small request-response server for local tools (DCC bridges, the editor, etc.),
+//! that uses a line-based JSON protocol over TCP. See [`LineServer`] docs for more info.
+
+#![warn(missing_docs)]
+
+use crate::core::{log::Log, uuid::Uuid};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// A response of a line server.
+pub trait LineResponse: Serialize + Send + 'static {
+    /// Creates a response, that tells a client that its request was accepted.
+    fn success() -> Self;
+
+    /// Creates a response, that tells a client that its request has failed with the given error.
+    fn failure(error: String) -> Self;
+}
+
+/// The first line, that a client must send after connecting to a line server.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Handshake {
+    /// The token of the server.
+    pub token: String,
+}
+
+/// Generates a new random token, that could be used to start a line server.
+pub fn generate_token() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// A request, that was received by a line server and waits for a response.
+pub struct LineRequest<Req, Resp> {
+    /// The request of the client.
+    pub request: Req,
+    /// Sends a response to the client.
+    pub responder: LineResponder<Resp>,
+}
+
+/// Sends a response to the client, that is waiting for it.
+pub struct LineResponder<Resp> {
+    sender: Sender<Resp>,
+}
+
+impl<Resp> LineResponder<Resp> {
+    /// Sends the given response to the client.
+    pub fn respond(self, response: Resp) {
+        // The client could be disconnected already, it is fine.
+        let _ = self.sender.send(response);
+    }
+}
+
+/// Line server accepts clients on a port of the local host. Each request and each response is a
+/// single line with a JSON object. Every connection must start with a [`Handshake`] line, that
+/// contains the token of the server (`{"token":"..."}`), the server answers it with a success
+/// response. Requests are executed by the owner of the server, it fetches them using
+/// [`LineServer::try_recv`] and responds using [`LineResponder::respond`].
+///
+/// The connection is closed on the first line, that is not a valid handshake or request. The port
+/// is reachable by any local process, including web browsers, which could be tricked by a web page
+/// into sending an HTTP request to the port. Such a request could carry a valid request line in its
+/// body, but it won't pass the handshake and the server won't look at the following lines.
+pub struct LineServer<Req, Resp> {
+    port: u16,
+    stop: Arc<AtomicBool>,
+    receiver: Receiver<LineRequest<Req, Resp>>,
+}
+
+impl<Req, Resp> LineServer<Req, Resp>
+where
+    Req: DeserializeOwned + Send + 'static,
+    Resp: LineResponse,
+{
+    const POLL_INTERVAL: Duration = Duration::from_millis(50);
+    const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Starts listening on the given port of the local host (zero port means any free port). The
+    /// name is used for the name of the server thread and in log messages. The token must not be
+    /// empty.
+    pub fn start(name: &str, port: u16, token: String) -> std::io::Result<Self> {
+        if token.is_empty() {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                "The token of the server must not be empty.",
+            ));
+        }
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        listener.set_nonblocking(true)?;
+        let port = listener.local_addr()?.port();
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = mpsc::channel();
+
+        let thread_stop = stop.clone();
+        let name = name.to_string();
+        thread::Builder::new().name(name.clone()).spawn(move || {
+            while !thread_stop.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let sender = sender.clone();
+                        let stop = thread_stop.clone();
+                        let token = token.clone();
+                        let name = name.clone();
+                        thread::spawn(move || {
+                            if let Err(err) = Self::serve_client(stream, &token, sender, stop) {
+                                Log::warn(format!("{name} client error: {err}"));
+                            }
+                        });
+                    }
+                    Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                        thread::sleep(Self::POLL_INTERVAL)
+                    }
+                    Err(err) => {
+                        Log::err(format!("{name} error: {err}"));
+                        break;
+                    }
+                }
+            }
+        })?;
+
+        Ok(Self {
+            port,
+            stop,
+            receiver,
+        })
+    }
+
+    /// Returns the port, that is used by the server.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns the next pending request, if any.
+    pub fn try_recv(&self) -> Option<LineRequest<Req, Resp>> {
+        self.receiver.try_recv().ok()
+    }
+
+    fn send_response(writer: &mut TcpStream, response: &Resp) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(response)?;
+        line.push('\n');
+        writer.write_all(line.as_bytes())
+    }
+
+    fn serve_client(
+        stream: TcpStream,
+        token: &str,
+        sender: Sender<LineRequest<Req, Resp>>,
+        stop: Arc<AtomicBool>,
+    ) -> std::io::Result<()> {
+        stream.set_nonblocking(false)?;
+        let mut writer = stream.try_clone()?;
+        let mut lines = BufReader::new(stream).lines();
+
+        match lines.next().transpose()? {
+            Some(line)
+                if serde_json::from_str::<Handshake>(&line)
+                    .is_ok_and(|handshake| handshake.token == token) =>
+            {
+                Self::send_response(&mut writer, &Resp::success())?;
+            }
+            Some(_) => {
+                return Self::send_response(
+                    &mut writer,
+                    &Resp::failure("Handshake failed.".to_string()),
+                );
+            }
+            None => return Ok(()),
+        }
+
+        for line in lines {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let request = match serde_json::from_str::<Req>(&line?) {
+                Ok(request) => request,
+                Err(err) => {
+                    // Close the connection, the client does not speak the protocol.
+                    return Self::send_response(
+                        &mut writer,
+                        &Resp::failure(format!("Malformed request: {err}")),
+                    );
+                }
+            };
+
+            let (response_sender, response_receiver) = mpsc::channel();
+            if sender
+                .send(LineRequest {
+                    request,
+                    responder: LineResponder {
+                        sender: response_sender,
+                    },
+                })
+                .is_err()
+            {
+                break;
+            }
+            let response = response_receiver
+                .recv_timeout(Self::RESPONSE_TIMEOUT)
+                .unwrap_or_else(|_| Resp::failure("Request timed out.".to_string()));
+            Self::send_response(&mut writer, &response)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Req, Resp> Drop for LineServer<Req, Resp> {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::utils::line_server::{generate_token, LineResponse, LineServer};
+    use serde::{Deserialize, Serialize};
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::{Ipv4Addr, TcpStream},
+        thread,
+        time::Duration,
+    };
+
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    struct Request {
+        value: u32,
+    }
+
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    struct Response {
+        ok: bool,
+    }
+
+    impl LineResponse for Response {
+        fn success() -> Self {
+            Self { ok: true }
+        }
+
+        fn failure(_error: String) -> Self {
+            Self { ok: false }
+        }
+    }
+
+    struct Client {
+        writer: TcpStream,
+        reader: BufReader<TcpStream>,
+    }
+
+    impl Client {
+        fn connect(port: u16) -> Self {
+            let writer = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+            writer
+                .set_read_timeout(Some(Duration::from_secs(10)))
+                .unwrap();
+            let reader = BufReader::new(writer.try_clone().unwrap());
+            Self { writer, reader }
+        }
+
+        fn send(&mut self, line: &str) {
+            self.writer.write_all(line.as_bytes()).unwrap();
+            self.writer.write_all(b"\n").unwrap();
+        }
+
+        fn receive(&mut self) -> Option<Response> {
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) | Err(_) => None,
+                Ok(_) => Some(serde_json::from_str(&line).unwrap()),
+            }
+        }
+    }
+
+    fn respond_to_one(server: &LineServer<Request, Response>) -> Request {
+        loop {
+            if let Some(pending) = server.try_recv() {
+                pending.responder.respond(Response::success());
+                return pending.request;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_line_server() {
+        let token = generate_token();
+        let server = LineServer::<Request, Response>::start("Test", 0, token.clone()).unwrap();
+        assert!(LineServer::<Request, Response>::start("Test", 0, String::new()).is_err());
+
+        // Wrong token closes the connection.
+        let mut client = Client::connect(server.port());
+        client.send(r#"{"token":"wrong"}"#);
+        assert_eq!(client.receive(), Some(Response { ok: false }));
+        assert_eq!(client.receive(), None);
+
+        // Foreign protocol is rejected before any request is accepted.
+        let mut client = Client::connect(server.port());
+        client.send("POST / HTTP/1.1");
+        client.send(r#"{"value":1}"#);
+        assert_eq!(client.receive(), Some(Response { ok: false }));
+        assert_eq!(client.receive(), None);
+
+        let mut client = Client::connect(server.port());
+        client.send(&format!(r#"{{"token":"{token}"}}"#));
+        assert_eq!(client.receive(), Some(Response { ok: true }));
+        client.send(r#"{"value":2}"#);
+        assert_eq!(respond_to_one(&server), Request { value: 2 });
+        assert_eq!(client.receive(), Some(Response { ok: true }));
+
+        // Malformed request closes the connection.
+        client.send("{}");
+        assert_eq!(client.receive(), Some(Response { ok: false }));
+        assert_eq!(client.receive(), None);
+        assert!(server.try_recv().is_none());
+    }
+}
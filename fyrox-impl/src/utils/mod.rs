@@ -4,8 +4,12 @@
 
 pub mod astar;
 pub mod behavior;
+pub mod jobs;
 pub mod lightmap;
+pub mod line_server;
 pub mod navmesh;
+pub mod navmesh_baker;
+pub mod navmesh_crowd;
 pub mod raw_mesh;
 pub mod uvgen;
 
@@ -558,6 +562,67 @@ pub fn translate_event(event: &WindowEvent) -> Option<OsEvent> {
     }
 }
 
+/// Translates gamepad button to fyrox-ui gamepad button. Returns `None` for buttons that do not
+/// have a counterpart in fyrox-ui.
+#[cfg(feature = "gamepad")]
+pub fn translate_gamepad_button(button: gilrs::Button) -> Option<message::GamepadButton> {
+    use message::GamepadButton;
+    match button {
+        gilrs::Button::South => Some(GamepadButton::South),
+        gilrs::Button::East => Some(GamepadButton::East),
+        gilrs::Button::North => Some(GamepadButton::North),
+        gilrs::Button::West => Some(GamepadButton::West),
+        gilrs::Button::DPadUp => Some(GamepadButton::DPadUp),
+        gilrs::Button::DPadDown => Some(GamepadButton::DPadDown),
+        gilrs::Button::DPadLeft => Some(GamepadButton::DPadLeft),
+        gilrs::Button::DPadRight => Some(GamepadButton::DPadRight),
+        // gilrs names shoulder buttons as triggers and triggers as second triggers.
+        gilrs::Button::LeftTrigger => Some(GamepadButton::LeftShoulder),
+        gilrs::Button::RightTrigger => Some(GamepadButton::RightShoulder),
+        gilrs::Button::LeftTrigger2 => Some(GamepadButton::LeftTrigger),
+        gilrs::Button::RightTrigger2 => Some(GamepadButton::RightTrigger),
+        gilrs::Button::Select => Some(GamepadButton::Select),
+        gilrs::Button::Start => Some(GamepadButton::Start),
+        gilrs::Button::LeftThumb => Some(GamepadButton::LeftThumb),
+        gilrs::Button::RightThumb => Some(GamepadButton::RightThumb),
+        _ => None,
+    }
+}
+
+/// Translates gamepad axis to fyrox-ui gamepad axis. Returns `None` for axes that do not have a
+/// counterpart in fyrox-ui.
+#[cfg(feature = "gamepad")]
+pub fn translate_gamepad_axis(axis: gilrs::Axis) -> Option<message::GamepadAxis> {
+    use message::GamepadAxis;
+    match axis {
+        gilrs::Axis::LeftStickX => Some(GamepadAxis::LeftStickX),
+        gilrs::Axis::LeftStickY => Some(GamepadAxis::LeftStickY),
+        gilrs::Axis::RightStickX => Some(GamepadAxis::RightStickX),
+        gilrs::Axis::RightStickY => Some(GamepadAxis::RightStickY),
+        _ => None,
+    }
+}
+
+/// Translates gamepad event to fyrox-ui event.
+#[cfg(feature = "gamepad")]
+pub fn translate_gamepad_event(event: &gilrs::EventType) -> Option<OsEvent> {
+    match *event {
+        gilrs::EventType::ButtonPressed(button, _) => Some(OsEvent::GamepadButton {
+            button: translate_gamepad_button(button)?,
+            state: ButtonState::Pressed,
+        }),
+        gilrs::EventType::ButtonReleased(button, _) => Some(OsEvent::GamepadButton {
+            button: translate_gamepad_button(button)?,
+            state: ButtonState::Released,
+        }),
+        gilrs::EventType::AxisChanged(axis, value, _) => Some(OsEvent::GamepadAxis {
+            axis: translate_gamepad_axis(axis)?,
+            value,
+        }),
+        _ => None,
+    }
+}
+
 /// Translates keyboard modifiers to fyrox-ui keyboard modifiers.
 pub fn translate_keyboard_modifiers(modifiers: ModifiersState) -> KeyboardModifiers {
     KeyboardModifiers {
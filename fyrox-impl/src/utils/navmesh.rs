@@ -645,6 +645,9 @@ pub struct NavmeshAgent {
     path_dirty: bool,
     radius: f32,
     interpolator: f32,
+    priority: f32,
+    #[visit(skip)]
+    velocity: Vector3<f32>,
 }
 
 impl Default for NavmeshAgent {
@@ -668,6 +671,8 @@ impl NavmeshAgent {
             path_dirty: true,
             radius: 0.2,
             interpolator: 0.0,
+            priority: 1.0,
+            velocity: Default::default(),
         }
     }
 
@@ -716,6 +721,24 @@ impl NavmeshAgent {
     pub fn radius(&self) -> f32 {
         self.radius
     }
+
+    /// Sets a new priority of the agent. The priority is used by local avoidance (see
+    /// [`crate::utils::navmesh_crowd::NavmeshCrowd`]) - when two agents are about to collide, the agent
+    /// with lower priority will make more effort to avoid the collision. The default value is 1.0.
+    pub fn set_priority(&mut self, priority: f32) {
+        self.priority = priority.max(0.0);
+    }
+
+    /// Returns the current priority of the agent. See [`Self::set_priority`] for more info.
+    pub fn priority(&self) -> f32 {
+        self.priority
+    }
+
+    /// Returns the velocity of the agent, that was used on the last update. It is non-zero only if
+    /// the agent is moved by [`Self::update_with_velocity`].
+    pub fn velocity(&self) -> Vector3<f32> {
+        self.velocity
+    }
 }
 
 impl NavmeshAgent {
@@ -873,6 +896,90 @@ impl NavmeshAgent {
         Ok(PathKind::Full)
     }
 
+    /// Recalculates the path of the agent if its target or position has significantly changed.
+    pub fn update_path(&mut self, navmesh: &Navmesh) -> Result<PathKind, PathError> {
+        if self.path_dirty {
+            self.path_dirty = false;
+            self.calculate_path(navmesh, self.position, self.target)
+        } else {
+            Ok(PathKind::Full)
+        }
+    }
+
+    /// Returns a velocity, that moves the agent to its steering target with its maximum speed.
+    /// The agent slows down when it approaches the last point of the path, so it won't overshoot
+    /// the target in the given time step. This velocity could be adjusted (for example by local
+    /// avoidance) and then passed to [`Self::update_with_velocity`].
+    pub fn desired_velocity(&self, dt: f32) -> Vector3<f32> {
+        let Some(steering_target) = self.steering_target() else {
+            return Vector3::default();
+        };
+
+        let delta = steering_target - self.position;
+        let distance = delta.norm();
+        if distance <= f32::EPSILON {
+            return Vector3::default();
+        }
+
+        let is_last_point = self.current as usize + 2 >= self.path.len();
+        let speed = if is_last_point && dt > 0.0 {
+            self.speed.min(distance / dt)
+        } else {
+            self.speed
+        };
+
+        delta.scale(speed / distance)
+    }
+
+    /// Performs single update tick that moves the agent using the given velocity, instead of moving it
+    /// strictly along the path. The agent is kept on the navmesh and its path is recalculated if the
+    /// agent has moved too far from it. This method is used by local avoidance, see
+    /// [`crate::utils::navmesh_crowd::NavmeshCrowd`] for more info.
+    pub fn update_with_velocity(
+        &mut self,
+        dt: f32,
+        navmesh: &Navmesh,
+        velocity: Vector3<f32>,
+    ) -> Result<PathKind, PathError> {
+        let path_kind = self.update_path(navmesh)?;
+
+        self.velocity = velocity;
+        let new_position = self.position + velocity.scale(dt);
+        self.position = navmesh
+            .query_closest(new_position)
+            .map_or(new_position, |(point, _)| point);
+        self.last_warp_position = self.position;
+
+        // Switch to the next path point as soon as the agent reached the current one.
+        let reach_distance = self.radius.max(self.speed * dt);
+        while self.current as usize + 2 < self.path.len()
+            && self.path[self.current as usize + 1].metric_distance(&self.position)
+                <= reach_distance
+        {
+            self.current += 1;
+        }
+
+        // The agent could be pushed away from its path by other agents, rebuild the path in this case.
+        if let (Some(begin), Some(end)) = (
+            self.path.get(self.current as usize),
+            self.path.get(self.current as usize + 1),
+        ) {
+            let segment = end - begin;
+            let length_squared = segment.norm_squared();
+            let t = if length_squared > f32::EPSILON {
+                ((self.position - begin).dot(&segment) / length_squared).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let distance = (begin + segment.scale(t)).metric_distance(&self.position);
+            if distance > self.recalculation_threshold + self.radius {
+                self.path_dirty = true;
+            }
+        }
+
+        Ok(path_kind)
+    }
+
     /// Returns current steering target which in most cases next path point from which
     /// agent is close to.
     pub fn steering_target(&self) -> Option<Vector3<f32>> {
@@ -914,6 +1021,8 @@ pub struct NavmeshAgentBuilder {
     target: Vector3<f32>,
     recalculation_threshold: f32,
     speed: f32,
+    radius: f32,
+    priority: f32,
 }
 
 impl Default for NavmeshAgentBuilder {
@@ -930,6 +1039,8 @@ impl NavmeshAgentBuilder {
             target: Default::default(),
             recalculation_threshold: 0.25,
             speed: 1.5,
+            radius: 0.2,
+            priority: 1.0,
         }
     }
 
@@ -957,6 +1068,19 @@ impl NavmeshAgentBuilder {
         self
     }
 
+    /// Sets new desired radius of the agent being built.
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets new desired priority of the agent being built. See [`NavmeshAgent::set_priority`] for
+    /// more info.
+    pub fn with_priority(mut self, priority: f32) -> Self {
+        self.priority = priority.max(0.0);
+        self
+    }
+
     /// Build the agent.
     pub fn build(self) -> NavmeshAgent {
         NavmeshAgent {
@@ -966,6 +1090,8 @@ impl NavmeshAgentBuilder {
             last_target_position: self.target,
             recalculation_threshold: self.recalculation_threshold,
             speed: self.speed,
+            radius: self.radius,
+            priority: self.priority,
             ..Default::default()
         }
     }
@@ -0,0 +1,1052 @@
+//! Automatic navigational mesh generation from scene geometry. See [`NavmeshBakingInput`] docs for more info.
+//!
+//! The baking process is similar to the one used in [Recast](https://github.com/recastnavigation/recastnavigation):
+//!
+//! 1) Input triangles are voxelized into a height field - a grid of columns, where each column contains a set of
+//!    solid spans.
+//! 2) Tops of solid spans, that are not too steep and that have enough free space above them to fit an agent,
+//!    are marked as walkable and connected with each other if an agent is able to step from one to another.
+//! 3) Walkable area is eroded by the agent radius, so agents won't clip through walls.
+//! 4) Remaining walkable cells are merged in rectangles and triangulated.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{
+        algebra::{Point3, Vector3},
+        math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+        pool::Handle,
+        reflect::prelude::*,
+    },
+    scene::{
+        mesh::{
+            buffer::{VertexAttributeUsage, VertexReadTrait},
+            Mesh,
+        },
+        node::Node,
+        Scene,
+    },
+    utils::{lightmap::CancellationToken, navmesh::Navmesh},
+};
+use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fmt::{Display, Formatter},
+    ops::Deref,
+    sync::{
+        atomic::{self, AtomicU32},
+        Arc,
+    },
+};
+
+/// A set of parameters, that defines how a navigational mesh will be generated.
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NavmeshBakingSettings {
+    /// Horizontal size of a voxel.
+    #[reflect(
+        description = "Horizontal size of a voxel (in meters). The lower the value, the more detailed \
+        the navmesh will be, but the longer it will take to generate it.",
+        min_value = 0.01
+    )]
+    pub cell_size: f32,
+    /// Vertical size of a voxel.
+    #[reflect(
+        description = "Vertical size of a voxel (in meters).",
+        min_value = 0.01
+    )]
+    pub cell_height: f32,
+    /// Radius of an agent.
+    #[reflect(
+        description = "Radius of an agent (in meters). Walkable area will be shrunk by this \
+        value, so agents won't clip through walls.",
+        min_value = 0.0
+    )]
+    pub agent_radius: f32,
+    /// Height of an agent.
+    #[reflect(
+        description = "Height of an agent (in meters). Areas with a lower ceiling will be \
+        excluded from the navmesh.",
+        min_value = 0.0
+    )]
+    pub agent_height: f32,
+    /// Maximum walkable slope angle in degrees.
+    #[reflect(
+        description = "Maximum angle (in degrees) of a slope, that an agent can walk on.",
+        min_value = 0.0,
+        max_value = 90.0
+    )]
+    pub max_slope_angle: f32,
+    /// Maximum height of a step, that an agent can climb on.
+    #[reflect(
+        description = "Maximum height (in meters) of a step (ledge), that an agent can climb on.",
+        min_value = 0.0
+    )]
+    pub max_step_height: f32,
+}
+
+impl Default for NavmeshBakingSettings {
+    fn default() -> Self {
+        Self {
+            cell_size: 0.25,
+            cell_height: 0.1,
+            agent_radius: 0.4,
+            agent_height: 1.8,
+            max_slope_angle: 45.0,
+            max_step_height: 0.4,
+        }
+    }
+}
+
+/// Navmesh baking stage.
+#[derive(Copy, Clone, PartialOrd, PartialEq, Ord, Eq, Debug)]
+#[repr(u32)]
+pub enum NavmeshBakingStage {
+    /// Converting input triangles into voxels.
+    Voxelization = 0,
+    /// Searching for walkable surfaces and connecting them.
+    WalkableAreaSearch = 1,
+    /// Shrinking walkable area by the agent radius.
+    Erosion = 2,
+    /// Building the final mesh.
+    MeshGeneration = 3,
+}
+
+impl Display for NavmeshBakingStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NavmeshBakingStage::Voxelization => {
+                write!(f, "Voxelization")
+            }
+            NavmeshBakingStage::WalkableAreaSearch => {
+                write!(f, "Searching Walkable Area")
+            }
+            NavmeshBakingStage::Erosion => {
+                write!(f, "Erosion")
+            }
+            NavmeshBakingStage::MeshGeneration => {
+                write!(f, "Generating Mesh")
+            }
+        }
+    }
+}
+
+/// Progress internals.
+#[derive(Default, Debug)]
+pub struct NavmeshBakingProgressData {
+    stage: AtomicU32,
+    // Range is [0; max_iterations]
+    progress: AtomicU32,
+    max_iterations: AtomicU32,
+}
+
+impl NavmeshBakingProgressData {
+    /// Returns progress percentage of the current stage in [0; 100] range.
+    pub fn progress_percent(&self) -> u32 {
+        let iterations = self.max_iterations.load(atomic::Ordering::SeqCst);
+        (self.progress.load(atomic::Ordering::SeqCst) * 100)
+            .checked_div(iterations)
+            .unwrap_or_default()
+    }
+
+    /// Returns current stage.
+    pub fn stage(&self) -> NavmeshBakingStage {
+        match self.stage.load(atomic::Ordering::SeqCst) {
+            0 => NavmeshBakingStage::Voxelization,
+            1 => NavmeshBakingStage::WalkableAreaSearch,
+            2 => NavmeshBakingStage::Erosion,
+            3 => NavmeshBakingStage::MeshGeneration,
+            _ => unreachable!(),
+        }
+    }
+
+    fn set_stage(&self, stage: NavmeshBakingStage, max_iterations: usize) {
+        self.max_iterations
+            .store(max_iterations as u32, atomic::Ordering::SeqCst);
+        self.progress.store(0, atomic::Ordering::SeqCst);
+        self.stage.store(stage as u32, atomic::Ordering::SeqCst);
+    }
+
+    fn advance_progress(&self) {
+        self.progress.fetch_add(1, atomic::Ordering::SeqCst);
+    }
+}
+
+/// Small helper that allows you to track progress of navmesh baking.
+#[derive(Clone, Default, Debug)]
+pub struct NavmeshBakingProgress(pub Arc<NavmeshBakingProgressData>);
+
+impl NavmeshBakingProgress {
+    /// Creates new progress indicator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Deref for NavmeshBakingProgress {
+    type Target = NavmeshBakingProgressData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// An error that may occur during navmesh baking.
+#[derive(Debug)]
+pub enum NavmeshBakingError {
+    /// Baking was cancelled by user.
+    Cancelled,
+    /// There is no walkable area in the input geometry.
+    NoWalkableArea,
+    /// The input geometry is too large for the given cell size.
+    TooLarge {
+        /// Amount of cells along X axis.
+        width: usize,
+        /// Amount of cells along Z axis.
+        depth: usize,
+    },
+}
+
+impl Display for NavmeshBakingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NavmeshBakingError::Cancelled => {
+                write!(f, "Navmesh baking was cancelled by the user.")
+            }
+            NavmeshBakingError::NoWalkableArea => {
+                write!(f, "There is no walkable area in the input geometry.")
+            }
+            NavmeshBakingError::TooLarge { width, depth } => {
+                write!(
+                    f,
+                    "The input geometry is too large ({width}x{depth} cells), \
+                    try to increase cell size."
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for NavmeshBakingError {}
+
+/// Data set required to bake a navmesh. It could be produced from a scene using [`NavmeshBakingInput::from_scene`]
+/// method. It is used to split preparation step from the actual baking; to be able to put heavy baking in a
+/// separate thread.
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     scene::Scene,
+/// #     utils::{
+/// #         lightmap::CancellationToken,
+/// #         navmesh::Navmesh,
+/// #         navmesh_baker::{NavmeshBakingInput, NavmeshBakingProgress, NavmeshBakingSettings},
+/// #     },
+/// # };
+/// fn bake_navmesh(scene: &Scene) -> Option<Navmesh> {
+///     NavmeshBakingInput::from_scene(scene, |_, _| true)
+///         .bake(
+///             &NavmeshBakingSettings::default(),
+///             CancellationToken::new(),
+///             NavmeshBakingProgress::new(),
+///         )
+///         .ok()
+/// }
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct NavmeshBakingInput {
+    triangles: Vec<[Vector3<f32>; 3]>,
+}
+
+impl NavmeshBakingInput {
+    /// Creates new input data from the given set of triangles in world space.
+    pub fn from_triangles(triangles: Vec<[Vector3<f32>; 3]>) -> Self {
+        Self { triangles }
+    }
+
+    /// Collects triangles of every mesh in the given scene, that passes the filter. If a node does not pass
+    /// the filter, then its descendants will be excluded as well.
+    pub fn from_scene<F>(scene: &Scene, mut filter: F) -> Self
+    where
+        F: FnMut(Handle<Node>, &Node) -> bool,
+    {
+        let mut triangles = Vec::new();
+
+        let mut stack = vec![scene.graph.get_root()];
+        while let Some(handle) = stack.pop() {
+            let node = &scene.graph[handle];
+            if !filter(handle, node) {
+                continue;
+            }
+
+            stack.extend_from_slice(node.children());
+
+            let Some(mesh) = node.cast::<Mesh>() else {
+                continue;
+            };
+
+            let global_transform = mesh.global_transform();
+            for surface in mesh.surfaces() {
+                let data = surface.data();
+                let data = data.data_ref();
+                let vertex_buffer = &data.vertex_buffer;
+                let position = |index: u32| {
+                    vertex_buffer
+                        .get(index as usize)
+                        .and_then(|v| v.read_3_f32(VertexAttributeUsage::Position).ok())
+                        .map(|p| global_transform.transform_point(&Point3::from(p)).coords)
+                };
+                for triangle in data.geometry_buffer.iter() {
+                    if let (Some(a), Some(b), Some(c)) = (
+                        position(triangle[0]),
+                        position(triangle[1]),
+                        position(triangle[2]),
+                    ) {
+                        triangles.push([a, b, c]);
+                    }
+                }
+            }
+        }
+
+        Self { triangles }
+    }
+
+    /// Returns a set of triangles of the input data.
+    pub fn triangles(&self) -> &[[Vector3<f32>; 3]] {
+        &self.triangles
+    }
+
+    /// Bakes a navigational mesh from the input data using the given settings. This method could take
+    /// quite some time for large scenes, so it should be called from a separate thread.
+    pub fn bake(
+        &self,
+        settings: &NavmeshBakingSettings,
+        cancellation_token: CancellationToken,
+        progress: NavmeshBakingProgress,
+    ) -> Result<Navmesh, NavmeshBakingError> {
+        let heightfield =
+            Heightfield::new(&self.triangles, settings, &cancellation_token, &progress)?;
+        let mut surface =
+            WalkableSurface::new(&heightfield, settings, &cancellation_token, &progress)?;
+        surface.erode(settings, &cancellation_token, &progress)?;
+        surface.build_navmesh(&cancellation_token, &progress)
+    }
+}
+
+/// Solid span in a column of a height field. Heights are in cell height units.
+#[derive(Copy, Clone, Debug)]
+struct Span {
+    min: i32,
+    max: i32,
+    walkable: bool,
+}
+
+struct Heightfield {
+    origin: Vector3<f32>,
+    cell_size: f32,
+    cell_height: f32,
+    width: usize,
+    depth: usize,
+    columns: Vec<Vec<Span>>,
+}
+
+const MAX_CELLS: usize = 1 << 26;
+
+fn clip_polygon(
+    polygon: &[Vector3<f32>],
+    axis: usize,
+    value: f32,
+    keep_greater: bool,
+) -> Vec<Vector3<f32>> {
+    let distance = |p: &Vector3<f32>| {
+        if keep_greater {
+            p[axis] - value
+        } else {
+            value - p[axis]
+        }
+    };
+
+    let mut result = Vec::with_capacity(polygon.len() + 2);
+    for (i, current) in polygon.iter().enumerate() {
+        let next = &polygon[(i + 1) % polygon.len()];
+        let current_distance = distance(current);
+        let next_distance = distance(next);
+        if current_distance >= 0.0 {
+            result.push(*current);
+        }
+        if (current_distance >= 0.0) != (next_distance >= 0.0) {
+            let t = current_distance / (current_distance - next_distance);
+            result.push(current.lerp(next, t));
+        }
+    }
+    result
+}
+
+impl Heightfield {
+    fn new(
+        triangles: &[[Vector3<f32>; 3]],
+        settings: &NavmeshBakingSettings,
+        cancellation_token: &CancellationToken,
+        progress: &NavmeshBakingProgress,
+    ) -> Result<Self, NavmeshBakingError> {
+        let cell_size = settings.cell_size.max(0.01);
+        let cell_height = settings.cell_height.max(0.01);
+
+        let mut bounds = AxisAlignedBoundingBox::default();
+        for triangle in triangles {
+            for vertex in triangle {
+                bounds.add_point(*vertex);
+            }
+        }
+        if triangles.is_empty() {
+            return Err(NavmeshBakingError::NoWalkableArea);
+        }
+
+        let size = bounds.max - bounds.min;
+        let width = (size.x / cell_size).ceil().max(1.0) as usize;
+        let depth = (size.z / cell_size).ceil().max(1.0) as usize;
+        if width.saturating_mul(depth) > MAX_CELLS {
+            return Err(NavmeshBakingError::TooLarge { width, depth });
+        }
+
+        let mut heightfield = Self {
+            origin: bounds.min,
+            cell_size,
+            cell_height,
+            width,
+            depth,
+            columns: vec![Vec::new(); width * depth],
+        };
+
+        let walkable_threshold = settings.max_slope_angle.to_radians().cos();
+        let merge_threshold = (settings.max_step_height / cell_height).floor() as i32;
+
+        progress.set_stage(NavmeshBakingStage::Voxelization, triangles.len());
+
+        for triangle in triangles {
+            if cancellation_token.is_cancelled() {
+                return Err(NavmeshBakingError::Cancelled);
+            }
+
+            let normal = (triangle[1] - triangle[0]).cross(&(triangle[2] - triangle[0]));
+            let walkable = normal
+                .try_normalize(f32::EPSILON)
+                .is_some_and(|n| n.y.abs() >= walkable_threshold);
+
+            heightfield.rasterize_triangle(triangle, walkable, merge_threshold);
+
+            progress.advance_progress();
+        }
+
+        Ok(heightfield)
+    }
+
+    fn rasterize_triangle(
+        &mut self,
+        triangle: &[Vector3<f32>; 3],
+        walkable: bool,
+        merge_threshold: i32,
+    ) {
+        let min = triangle[0].inf(&triangle[1]).inf(&triangle[2]) - self.origin;
+        let max = triangle[0].sup(&triangle[1]).sup(&triangle[2]) - self.origin;
+
+        let z_begin = ((min.z / self.cell_size).floor() as isize).max(0) as usize;
+        let z_end = ((max.z / self.cell_size).floor() as isize).min(self.depth as isize - 1);
+        let x_begin = ((min.x / self.cell_size).floor() as isize).max(0) as usize;
+        let x_end = ((max.x / self.cell_size).floor() as isize).min(self.width as isize - 1);
+        if z_end < 0 || x_end < 0 {
+            return;
+        }
+
+        let polygon = triangle.to_vec();
+        for z in z_begin..=z_end as usize {
+            let z_min = self.origin.z + z as f32 * self.cell_size;
+            let row = clip_polygon(&polygon, 2, z_min, true);
+            let row = clip_polygon(&row, 2, z_min + self.cell_size, false);
+            if row.is_empty() {
+                continue;
+            }
+
+            for x in x_begin..=x_end as usize {
+                let x_min = self.origin.x + x as f32 * self.cell_size;
+                let cell = clip_polygon(&row, 0, x_min, true);
+                let cell = clip_polygon(&cell, 0, x_min + self.cell_size, false);
+                if cell.is_empty() {
+                    continue;
+                }
+
+                let (y_min, y_max) = cell.iter().fold((f32::MAX, f32::MIN), |(min, max), p| {
+                    (min.min(p.y), max.max(p.y))
+                });
+
+                let span_min = ((y_min - self.origin.y) / self.cell_height).floor() as i32;
+                let span_max =
+                    (((y_max - self.origin.y) / self.cell_height).ceil() as i32).max(span_min + 1);
+
+                self.add_span(
+                    x,
+                    z,
+                    Span {
+                        min: span_min,
+                        max: span_max,
+                        walkable,
+                    },
+                    merge_threshold,
+                );
+            }
+        }
+    }
+
+    fn add_span(&mut self, x: usize, z: usize, mut span: Span, merge_threshold: i32) {
+        let column = &mut self.columns[z * self.width + x];
+
+        // Spans are sorted by their min height and do not overlap, merge the new span with every
+        // overlapping span.
+        let mut i = 0;
+        while i < column.len() {
+            let existing = column[i];
+            if existing.min > span.max {
+                break;
+            }
+            if existing.max < span.min {
+                i += 1;
+                continue;
+            }
+
+            // Walkable flag is taken from the span with the highest top.
+            if (existing.max - span.max).abs() <= merge_threshold {
+                span.walkable |= existing.walkable;
+            } else if existing.max > span.max {
+                span.walkable = existing.walkable;
+            }
+
+            span.min = span.min.min(existing.min);
+            span.max = span.max.max(existing.max);
+            column.remove(i);
+        }
+
+        column.insert(i, span);
+    }
+}
+
+const NO_NEIGHBOR: u32 = u32::MAX;
+
+// Offsets of neighbor cells along X and Z axes.
+const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+const POSITIVE_X: usize = 2;
+const POSITIVE_Z: usize = 1;
+
+/// A walkable top surface of a solid span. Heights are in cell height units.
+#[derive(Clone, Debug)]
+struct OpenSpan {
+    x: usize,
+    z: usize,
+    floor: i32,
+    ceiling: i32,
+    neighbors: [u32; 4],
+    removed: bool,
+}
+
+struct WalkableSurface {
+    origin: Vector3<f32>,
+    cell_size: f32,
+    cell_height: f32,
+    width: usize,
+    depth: usize,
+    // Range of spans of each column.
+    columns: Vec<(u32, u32)>,
+    spans: Vec<OpenSpan>,
+}
+
+impl WalkableSurface {
+    fn new(
+        heightfield: &Heightfield,
+        settings: &NavmeshBakingSettings,
+        cancellation_token: &CancellationToken,
+        progress: &NavmeshBakingProgress,
+    ) -> Result<Self, NavmeshBakingError> {
+        let agent_height = (settings.agent_height / heightfield.cell_height).ceil() as i32;
+        let max_climb = (settings.max_step_height / heightfield.cell_height).floor() as i32;
+
+        progress.set_stage(
+            NavmeshBakingStage::WalkableAreaSearch,
+            heightfield.columns.len() * 2,
+        );
+
+        let mut columns = Vec::with_capacity(heightfield.columns.len());
+        let mut spans = Vec::new();
+        for (index, column) in heightfield.columns.iter().enumerate() {
+            if index % heightfield.width == 0 && cancellation_token.is_cancelled() {
+                return Err(NavmeshBakingError::Cancelled);
+            }
+
+            let start = spans.len() as u32;
+            for (i, span) in column.iter().enumerate() {
+                if !span.walkable {
+                    continue;
+                }
+                let ceiling = column.get(i + 1).map_or(i32::MAX, |next| next.min);
+                if ceiling.saturating_sub(span.max) >= agent_height {
+                    spans.push(OpenSpan {
+                        x: index % heightfield.width,
+                        z: index / heightfield.width,
+                        floor: span.max,
+                        ceiling,
+                        neighbors: [NO_NEIGHBOR; 4],
+                        removed: false,
+                    });
+                }
+            }
+            columns.push((start, spans.len() as u32 - start));
+
+            progress.advance_progress();
+        }
+
+        if spans.is_empty() {
+            return Err(NavmeshBakingError::NoWalkableArea);
+        }
+
+        let mut surface = Self {
+            origin: heightfield.origin,
+            cell_size: heightfield.cell_size,
+            cell_height: heightfield.cell_height,
+            width: heightfield.width,
+            depth: heightfield.depth,
+            columns,
+            spans,
+        };
+
+        for index in 0..surface.columns.len() {
+            if index % surface.width == 0 && cancellation_token.is_cancelled() {
+                return Err(NavmeshBakingError::Cancelled);
+            }
+
+            let (start, count) = surface.columns[index];
+            for span_index in start..start + count {
+                for (direction, offset) in DIRECTIONS.iter().enumerate() {
+                    let span = &surface.spans[span_index as usize];
+                    let Some((neighbor_start, neighbor_count)) =
+                        surface.column_at(span.x, span.z, *offset)
+                    else {
+                        continue;
+                    };
+
+                    let neighbor = (neighbor_start..neighbor_start + neighbor_count).find(|n| {
+                        let neighbor = &surface.spans[*n as usize];
+                        let gap =
+                            span.ceiling.min(neighbor.ceiling) - span.floor.max(neighbor.floor);
+                        gap >= agent_height && (neighbor.floor - span.floor).abs() <= max_climb
+                    });
+
+                    if let Some(neighbor) = neighbor {
+                        surface.spans[span_index as usize].neighbors[direction] = neighbor;
+                    }
+                }
+            }
+
+            progress.advance_progress();
+        }
+
+        Ok(surface)
+    }
+
+    fn column_at(&self, x: usize, z: usize, offset: (isize, isize)) -> Option<(u32, u32)> {
+        let x = x.checked_add_signed(offset.0)?;
+        let z = z.checked_add_signed(offset.1)?;
+        if x < self.width && z < self.depth {
+            Some(self.columns[z * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    fn neighbor(&self, span: u32, direction: usize) -> Option<u32> {
+        let neighbor = self.spans[span as usize].neighbors[direction];
+        if neighbor != NO_NEIGHBOR && !self.spans[neighbor as usize].removed {
+            Some(neighbor)
+        } else {
+            None
+        }
+    }
+
+    fn erode(
+        &mut self,
+        settings: &NavmeshBakingSettings,
+        cancellation_token: &CancellationToken,
+        progress: &NavmeshBakingProgress,
+    ) -> Result<(), NavmeshBakingError> {
+        let radius = (settings.agent_radius / self.cell_size).ceil() as u32;
+        if radius == 0 {
+            return Ok(());
+        }
+
+        progress.set_stage(NavmeshBakingStage::Erosion, self.spans.len());
+
+        // Calculate distance to the closest border of walkable area for each span.
+        let mut distances = vec![u32::MAX; self.spans.len()];
+        let mut queue = VecDeque::new();
+        for (index, span) in self.spans.iter().enumerate() {
+            if span.neighbors.contains(&NO_NEIGHBOR) {
+                distances[index] = 0;
+                queue.push_back(index as u32);
+            }
+        }
+
+        while let Some(index) = queue.pop_front() {
+            if cancellation_token.is_cancelled() {
+                return Err(NavmeshBakingError::Cancelled);
+            }
+
+            let distance = distances[index as usize] + 1;
+            for neighbor in self.spans[index as usize].neighbors {
+                if neighbor != NO_NEIGHBOR && distances[neighbor as usize] > distance {
+                    distances[neighbor as usize] = distance;
+                    queue.push_back(neighbor);
+                }
+            }
+
+            progress.advance_progress();
+        }
+
+        let mut any_left = false;
+        for (span, distance) in self.spans.iter_mut().zip(distances) {
+            span.removed = distance < radius;
+            any_left |= !span.removed;
+        }
+
+        if any_left {
+            Ok(())
+        } else {
+            Err(NavmeshBakingError::NoWalkableArea)
+        }
+    }
+
+    fn can_merge(&self, span: Option<u32>, floor: i32, used: &[bool]) -> Option<u32> {
+        span.filter(|s| !used[*s as usize] && self.spans[*s as usize].floor == floor)
+    }
+
+    fn build_navmesh(
+        &self,
+        cancellation_token: &CancellationToken,
+        progress: &NavmeshBakingProgress,
+    ) -> Result<Navmesh, NavmeshBakingError> {
+        progress.set_stage(NavmeshBakingStage::MeshGeneration, self.depth * 2);
+
+        let mut welder = VertexWelder::new(self.spans.len());
+
+        for z in 0..self.depth {
+            if cancellation_token.is_cancelled() {
+                return Err(NavmeshBakingError::Cancelled);
+            }
+
+            for x in 0..self.width {
+                let (start, count) = self.columns[z * self.width + x];
+                for span in start..start + count {
+                    if self.spans[span as usize].removed {
+                        continue;
+                    }
+                    for (direction, (dx, dz)) in DIRECTIONS.iter().enumerate() {
+                        let Some(neighbor) = self.neighbor(span, direction) else {
+                            continue;
+                        };
+                        for (corner, (cx, cz)) in CORNERS.iter().enumerate() {
+                            if let Some(neighbor_corner) = CORNERS
+                                .iter()
+                                .position(|(nx, nz)| nx + dx == *cx && nz + dz == *cz)
+                            {
+                                welder.weld(span, corner, neighbor, neighbor_corner);
+                            }
+                        }
+                    }
+                }
+            }
+
+            progress.advance_progress();
+        }
+
+        welder.calculate_heights(self);
+
+        // Merge flat regions in rectangles to reduce the amount of triangles.
+        let mut triangles = Vec::new();
+        let mut used = vec![false; self.spans.len()];
+        for z in 0..self.depth {
+            if cancellation_token.is_cancelled() {
+                return Err(NavmeshBakingError::Cancelled);
+            }
+
+            for x in 0..self.width {
+                let (start, count) = self.columns[z * self.width + x];
+                for first in start..start + count {
+                    if used[first as usize] || self.spans[first as usize].removed {
+                        continue;
+                    }
+                    let floor = self.spans[first as usize].floor;
+
+                    let mut first_row = vec![first];
+                    used[first as usize] = true;
+                    while let Some(next) = self.can_merge(
+                        self.neighbor(*first_row.last().unwrap(), POSITIVE_X),
+                        floor,
+                        &used,
+                    ) {
+                        used[next as usize] = true;
+                        first_row.push(next);
+                    }
+
+                    let mut rows = vec![first_row];
+                    'row_loop: loop {
+                        let last_row = rows.last().unwrap();
+                        let mut row = Vec::with_capacity(last_row.len());
+                        for span in last_row.iter() {
+                            let Some(next) =
+                                self.can_merge(self.neighbor(*span, POSITIVE_Z), floor, &used)
+                            else {
+                                break 'row_loop;
+                            };
+                            if let Some(prev) = row.last() {
+                                if self.neighbor(*prev, POSITIVE_X) != Some(next) {
+                                    break 'row_loop;
+                                }
+                            }
+                            row.push(next);
+                        }
+                        for span in row.iter() {
+                            used[*span as usize] = true;
+                        }
+                        rows.push(row);
+                    }
+
+                    self.triangulate_rectangle(&rows, &mut welder, &mut triangles);
+                }
+            }
+
+            progress.advance_progress();
+        }
+
+        Ok(Navmesh::new(triangles, welder.vertices))
+    }
+
+    fn triangulate_rectangle(
+        &self,
+        rows: &[Vec<u32>],
+        welder: &mut VertexWelder,
+        triangles: &mut Vec<TriangleDefinition>,
+    ) {
+        // Collect every vertex on the perimeter of the rectangle, this way there won't be any
+        // T-junctions between adjacent rectangles.
+        let width = rows[0].len();
+        let height = rows.len();
+        let mut perimeter = Vec::with_capacity(2 * (width + height));
+        for span in rows[0].iter() {
+            perimeter.push(welder.vertex(self, *span, 0));
+        }
+        for row in rows.iter() {
+            perimeter.push(welder.vertex(self, row[width - 1], 1));
+        }
+        for span in rows[height - 1].iter().rev() {
+            perimeter.push(welder.vertex(self, *span, 2));
+        }
+        for row in rows.iter().rev() {
+            perimeter.push(welder.vertex(self, row[0], 3));
+        }
+
+        if perimeter.len() == 4 {
+            triangles.push(TriangleDefinition([
+                perimeter[0],
+                perimeter[1],
+                perimeter[2],
+            ]));
+            triangles.push(TriangleDefinition([
+                perimeter[0],
+                perimeter[2],
+                perimeter[3],
+            ]));
+        } else {
+            let first = &self.spans[rows[0][0] as usize];
+            welder.vertices.push(Vector3::new(
+                self.origin.x + (first.x as f32 + width as f32 * 0.5) * self.cell_size,
+                self.origin.y + first.floor as f32 * self.cell_height,
+                self.origin.z + (first.z as f32 + height as f32 * 0.5) * self.cell_size,
+            ));
+            let center = welder.vertices.len() as u32 - 1;
+            for i in 0..perimeter.len() {
+                triangles.push(TriangleDefinition([
+                    center,
+                    perimeter[i],
+                    perimeter[(i + 1) % perimeter.len()],
+                ]));
+            }
+        }
+    }
+}
+
+// Corner positions within a cell: (0, 0), (1, 0), (1, 1), (0, 1).
+const CORNERS: [(isize, isize); 4] = [(0, 0), (1, 0), (1, 1), (0, 1)];
+
+/// Each span has four corners, corners of connected spans are merged together (using union-find)
+/// to form shared vertices.
+struct VertexWelder {
+    corners: Vec<u32>,
+    heights: FxHashMap<u32, f32>,
+    vertex_map: FxHashMap<u32, u32>,
+    vertices: Vec<Vector3<f32>>,
+}
+
+impl VertexWelder {
+    fn new(span_count: usize) -> Self {
+        Self {
+            corners: (0..span_count as u32 * 4).collect(),
+            heights: Default::default(),
+            vertex_map: Default::default(),
+            vertices: Default::default(),
+        }
+    }
+
+    fn find(&mut self, mut i: u32) -> u32 {
+        while self.corners[i as usize] != i {
+            self.corners[i as usize] = self.corners[self.corners[i as usize] as usize];
+            i = self.corners[i as usize];
+        }
+        i
+    }
+
+    fn weld(&mut self, span_a: u32, corner_a: usize, span_b: u32, corner_b: usize) {
+        let a = self.find(span_a * 4 + corner_a as u32);
+        let b = self.find(span_b * 4 + corner_b as u32);
+        self.corners[a as usize] = b;
+    }
+
+    // Height of a shared vertex is an average height of every corner, that forms it.
+    fn calculate_heights(&mut self, surface: &WalkableSurface) {
+        let mut sums = FxHashMap::<u32, (f32, u32)>::default();
+        for (index, span) in surface.spans.iter().enumerate() {
+            if span.removed {
+                continue;
+            }
+            for corner in 0..4 {
+                let root = self.find(index as u32 * 4 + corner);
+                let entry = sums.entry(root).or_default();
+                entry.0 += span.floor as f32;
+                entry.1 += 1;
+            }
+        }
+        self.heights = sums
+            .into_iter()
+            .map(|(root, (sum, count))| (root, sum / count as f32))
+            .collect();
+    }
+
+    fn vertex(&mut self, surface: &WalkableSurface, span: u32, corner: usize) -> u32 {
+        let root = self.find(span * 4 + corner as u32);
+        if let Some(index) = self.vertex_map.get(&root) {
+            return *index;
+        }
+
+        let s = &surface.spans[span as usize];
+        let (cx, cz) = CORNERS[corner];
+        self.vertices.push(Vector3::new(
+            surface.origin.x + (s.x as isize + cx) as f32 * surface.cell_size,
+            surface.origin.y + self.heights[&root] * surface.cell_height,
+            surface.origin.z + (s.z as isize + cz) as f32 * surface.cell_size,
+        ));
+        let index = self.vertices.len() as u32 - 1;
+        self.vertex_map.insert(root, index);
+        index
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        utils::{
+            astar::PathKind,
+            lightmap::CancellationToken,
+            navmesh_baker::{
+                NavmeshBakingError, NavmeshBakingInput, NavmeshBakingProgress,
+                NavmeshBakingSettings,
+            },
+        },
+    };
+
+    fn quad(min: Vector3<f32>, max: Vector3<f32>) -> [[Vector3<f32>; 3]; 2] {
+        let a = min;
+        let b = Vector3::new(max.x, min.y, min.z);
+        let c = max;
+        let d = Vector3::new(min.x, max.y, max.z);
+        [[a, b, c], [a, c, d]]
+    }
+
+    fn bake(
+        triangles: Vec<[Vector3<f32>; 3]>,
+    ) -> Result<crate::utils::navmesh::Navmesh, NavmeshBakingError> {
+        NavmeshBakingInput::from_triangles(triangles).bake(
+            &NavmeshBakingSettings {
+                cell_size: 0.5,
+                cell_height: 0.1,
+                agent_radius: 0.5,
+                agent_height: 2.0,
+                max_slope_angle: 45.0,
+                max_step_height: 0.3,
+            },
+            CancellationToken::new(),
+            NavmeshBakingProgress::new(),
+        )
+    }
+
+    #[test]
+    fn test_bake_flat_floor() {
+        let navmesh =
+            bake(quad(Vector3::new(0.0, 0.0, 0.0), Vector3::new(5.0, 0.0, 5.0)).to_vec()).unwrap();
+
+        assert!(!navmesh.triangles().is_empty());
+        for vertex in navmesh.vertices() {
+            assert!(vertex.y.abs() <= 0.15);
+            // Walkable area must be shrunk by the agent radius.
+            assert!(vertex.x >= 0.5 && vertex.x <= 4.5);
+            assert!(vertex.z >= 0.5 && vertex.z <= 4.5);
+        }
+
+        // Every part of the floor must be reachable.
+        let mut path = Vec::new();
+        assert_eq!(
+            navmesh
+                .build_path(0, navmesh.triangles().len() - 1, &mut path)
+                .unwrap(),
+            PathKind::Full
+        );
+    }
+
+    #[test]
+    fn test_bake_low_ceiling_and_steep_slope() {
+        let mut triangles = Vec::new();
+        // Floor with a low ceiling above it.
+        triangles.extend(quad(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(5.0, 0.0, 5.0),
+        ));
+        triangles.extend(quad(
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(5.0, 1.0, 5.0),
+        ));
+        // Steep slope.
+        triangles.extend(quad(
+            Vector3::new(10.0, 0.0, 0.0),
+            Vector3::new(15.0, 10.0, 5.0),
+        ));
+
+        let navmesh = bake(triangles).unwrap();
+
+        // Only the top of the ceiling is walkable.
+        for vertex in navmesh.vertices() {
+            assert!((vertex.y - 1.0).abs() <= 0.15);
+        }
+
+        assert!(matches!(
+            bake(quad(Vector3::new(0.0, 0.0, 0.0), Vector3::new(5.0, 10.0, 5.0)).to_vec()),
+            Err(NavmeshBakingError::NoWalkableArea)
+        ));
+    }
+}
@@ -0,0 +1,593 @@
+//! Local avoidance for navmesh agents. See [`NavmeshCrowd`] docs for more info.
+//!
+//! The avoidance is based on Optimal Reciprocal Collision Avoidance (ORCA) algorithm, the implementation
+//! closely follows the one from [RVO2 library](https://gamma.cs.unc.edu/RVO2/). All the computations are
+//! done on XZ plane, vertical axis is ignored.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector2, Vector3},
+        color::Color,
+        pool::{Handle, Pool},
+    },
+    scene::debug::{Line, SceneDrawingContext},
+    utils::navmesh::{Navmesh, NavmeshAgent},
+};
+
+const EPSILON: f32 = 0.00001;
+
+/// A container of navmesh agents, that moves the agents along their paths and prevents them from
+/// passing through each other.
+///
+/// Each update the crowd calculates desired velocity of every agent (see [`NavmeshAgent::desired_velocity`])
+/// and then adjusts it, so the agent won't collide with its neighbours in the next [`Self::time_horizon`]
+/// seconds. The agents share the effort of avoiding a collision with each other according to their
+/// priorities (see [`NavmeshAgent::set_priority`]) - an agent with higher priority is less likely to
+/// give way to agents with lower priority.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_impl::{
+///     core::algebra::Vector3,
+///     utils::{
+///         navmesh::{Navmesh, NavmeshAgentBuilder},
+///         navmesh_crowd::NavmeshCrowd,
+///     },
+/// };
+///
+/// fn update_crowd(navmesh: &Navmesh, dt: f32) {
+///     let mut crowd = NavmeshCrowd::new();
+///
+///     let agent = crowd.add_agent(
+///         NavmeshAgentBuilder::new()
+///             .with_position(Vector3::new(0.0, 0.0, 0.0))
+///             .with_target(Vector3::new(10.0, 0.0, 0.0))
+///             .with_radius(0.5)
+///             .with_priority(2.0)
+///             .build(),
+///     );
+///
+///     crowd.update(dt, navmesh);
+///
+///     println!("Agent position: {:?}", crowd.agent(agent).unwrap().position());
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct NavmeshCrowd {
+    agents: Pool<NavmeshAgent>,
+    time_horizon: f32,
+    neighbor_distance: f32,
+    max_neighbors: usize,
+}
+
+impl Default for NavmeshCrowd {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct OrcaLine {
+    point: Vector2<f32>,
+    direction: Vector2<f32>,
+}
+
+#[derive(Copy, Clone)]
+struct AgentState {
+    handle: Handle<NavmeshAgent>,
+    position: Vector2<f32>,
+    velocity: Vector2<f32>,
+    desired_velocity: Vector2<f32>,
+    radius: f32,
+    priority: f32,
+    max_speed: f32,
+}
+
+fn xz(v: Vector3<f32>) -> Vector2<f32> {
+    Vector2::new(v.x, v.z)
+}
+
+fn det(a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+/// Finds a point on the given line, that is closest to the optimal velocity and satisfies all the
+/// constraints of the previous lines.
+fn linear_program1(
+    lines: &[OrcaLine],
+    line_no: usize,
+    radius: f32,
+    opt_velocity: Vector2<f32>,
+    direction_opt: bool,
+    result: &mut Vector2<f32>,
+) -> bool {
+    let line = lines[line_no];
+    let dot_product = line.point.dot(&line.direction);
+    let discriminant = dot_product * dot_product + radius * radius - line.point.norm_squared();
+
+    if discriminant < 0.0 {
+        // Max speed circle fully invalidates the line.
+        return false;
+    }
+
+    let sqrt_discriminant = discriminant.sqrt();
+    let mut t_left = -dot_product - sqrt_discriminant;
+    let mut t_right = -dot_product + sqrt_discriminant;
+
+    for other in &lines[..line_no] {
+        let denominator = det(line.direction, other.direction);
+        let numerator = det(other.direction, line.point - other.point);
+
+        if denominator.abs() <= EPSILON {
+            // The lines are almost parallel.
+            if numerator < 0.0 {
+                return false;
+            }
+            continue;
+        }
+
+        let t = numerator / denominator;
+        if denominator >= 0.0 {
+            t_right = t_right.min(t);
+        } else {
+            t_left = t_left.max(t);
+        }
+
+        if t_left > t_right {
+            return false;
+        }
+    }
+
+    if direction_opt {
+        if opt_velocity.dot(&line.direction) > 0.0 {
+            *result = line.point + line.direction.scale(t_right);
+        } else {
+            *result = line.point + line.direction.scale(t_left);
+        }
+    } else {
+        let t = line.direction.dot(&(opt_velocity - line.point));
+        *result = line.point + line.direction.scale(t.clamp(t_left, t_right));
+    }
+
+    true
+}
+
+/// Finds a velocity, that is closest to the optimal velocity and satisfies all the constraints. Returns
+/// the index of the first line that cannot be satisfied or the number of lines on success.
+fn linear_program2(
+    lines: &[OrcaLine],
+    radius: f32,
+    opt_velocity: Vector2<f32>,
+    direction_opt: bool,
+    result: &mut Vector2<f32>,
+) -> usize {
+    if direction_opt {
+        *result = opt_velocity.scale(radius);
+    } else if opt_velocity.norm_squared() > radius * radius {
+        *result = opt_velocity.normalize().scale(radius);
+    } else {
+        *result = opt_velocity;
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        if det(line.direction, line.point - *result) > 0.0 {
+            let temp_result = *result;
+            if !linear_program1(lines, i, radius, opt_velocity, direction_opt, result) {
+                *result = temp_result;
+                return i;
+            }
+        }
+    }
+
+    lines.len()
+}
+
+/// Finds a velocity, that minimizes the maximum penetration into the constraints. It is used when
+/// there is no velocity that satisfies all the constraints (for example, when the agents are already
+/// overlapping).
+fn linear_program3(lines: &[OrcaLine], begin_line: usize, radius: f32, result: &mut Vector2<f32>) {
+    let mut distance = 0.0;
+    let mut projected_lines = Vec::new();
+
+    for (i, line) in lines.iter().enumerate().skip(begin_line) {
+        if det(line.direction, line.point - *result) <= distance {
+            continue;
+        }
+
+        projected_lines.clear();
+        for other in &lines[..i] {
+            let determinant = det(line.direction, other.direction);
+            let point = if determinant.abs() <= EPSILON {
+                if line.direction.dot(&other.direction) > 0.0 {
+                    // The lines are pointing in the same direction.
+                    continue;
+                }
+                (line.point + other.point).scale(0.5)
+            } else {
+                line.point
+                    + line
+                        .direction
+                        .scale(det(other.direction, line.point - other.point) / determinant)
+            };
+
+            projected_lines.push(OrcaLine {
+                point,
+                direction: (other.direction - line.direction)
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_default(),
+            });
+        }
+
+        let temp_result = *result;
+        if linear_program2(
+            &projected_lines,
+            radius,
+            Vector2::new(-line.direction.y, line.direction.x),
+            true,
+            result,
+        ) < projected_lines.len()
+        {
+            // This should in principle not happen, the result is by definition already in the
+            // feasible region of this linear program. If it fails, it is due to small floating
+            // point errors, and the current result is kept.
+            *result = temp_result;
+        }
+
+        distance = det(line.direction, line.point - *result);
+    }
+}
+
+impl NavmeshCrowd {
+    /// Creates new empty crowd.
+    pub fn new() -> Self {
+        Self {
+            agents: Default::default(),
+            time_horizon: 2.0,
+            neighbor_distance: 5.0,
+            max_neighbors: 10,
+        }
+    }
+
+    /// Adds new agent to the crowd and returns its handle.
+    pub fn add_agent(&mut self, agent: NavmeshAgent) -> Handle<NavmeshAgent> {
+        self.agents.spawn(agent)
+    }
+
+    /// Removes the agent from the crowd and returns it. Returns [`None`] if the handle is invalid.
+    pub fn remove_agent(&mut self, handle: Handle<NavmeshAgent>) -> Option<NavmeshAgent> {
+        if self.agents.is_valid_handle(handle) {
+            Some(self.agents.free(handle))
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the agent with the given handle.
+    pub fn agent(&self, handle: Handle<NavmeshAgent>) -> Option<&NavmeshAgent> {
+        self.agents.try_borrow(handle)
+    }
+
+    /// Returns a reference to the agent with the given handle.
+    pub fn agent_mut(&mut self, handle: Handle<NavmeshAgent>) -> Option<&mut NavmeshAgent> {
+        self.agents.try_borrow_mut(handle)
+    }
+
+    /// Returns an iterator over all the agents of the crowd.
+    pub fn agents(&self) -> impl Iterator<Item = (Handle<NavmeshAgent>, &NavmeshAgent)> {
+        self.agents.pair_iter()
+    }
+
+    /// Sets the amount of time (in seconds), for which the velocities of the agents are guaranteed to
+    /// be collision-free. Larger values make the agents react to each other sooner, but also make them
+    /// less "brave" in dense crowds. Default value is 2.0 seconds.
+    pub fn set_time_horizon(&mut self, time_horizon: f32) {
+        self.time_horizon = time_horizon.max(EPSILON);
+    }
+
+    /// Returns current time horizon. See [`Self::set_time_horizon`] for more info.
+    pub fn time_horizon(&self) -> f32 {
+        self.time_horizon
+    }
+
+    /// Sets the maximum distance (between the centers of agents) at which the agents are taken into
+    /// account when avoiding collisions. Default value is 5.0 meters.
+    pub fn set_neighbor_distance(&mut self, distance: f32) {
+        self.neighbor_distance = distance.max(0.0);
+    }
+
+    /// Returns current neighbor distance. See [`Self::set_neighbor_distance`] for more info.
+    pub fn neighbor_distance(&self) -> f32 {
+        self.neighbor_distance
+    }
+
+    /// Sets the maximum number of the closest neighbours, that are taken into account when avoiding
+    /// collisions. Default value is 10.
+    pub fn set_max_neighbors(&mut self, max_neighbors: usize) {
+        self.max_neighbors = max_neighbors;
+    }
+
+    /// Returns maximum number of neighbours. See [`Self::set_max_neighbors`] for more info.
+    pub fn max_neighbors(&self) -> usize {
+        self.max_neighbors
+    }
+
+    /// Performs single update tick of the crowd - recalculates paths of the agents (if needed), finds
+    /// collision-free velocities for them and moves them. Agents, which paths cannot be built, will
+    /// stay still, but they still could be pushed by other agents.
+    pub fn update(&mut self, dt: f32, navmesh: &Navmesh) {
+        let mut states = Vec::with_capacity(self.agents.alive_count() as usize);
+        for (handle, agent) in self.agents.pair_iter_mut() {
+            let desired_velocity = if agent.update_path(navmesh).is_ok() {
+                agent.desired_velocity(dt)
+            } else {
+                Default::default()
+            };
+            states.push(AgentState {
+                handle,
+                position: xz(agent.position()),
+                velocity: xz(agent.velocity()),
+                desired_velocity: xz(desired_velocity),
+                radius: agent.radius(),
+                priority: agent.priority(),
+                max_speed: agent.speed(),
+            });
+        }
+
+        let mut neighbors = Vec::new();
+        let mut lines = Vec::new();
+        for state in states.iter() {
+            self.collect_neighbors(state, &states, &mut neighbors);
+            self.build_orca_lines(state, &neighbors, dt, &mut lines);
+
+            let max_speed = state.max_speed.max(state.desired_velocity.norm());
+            let mut new_velocity = Vector2::default();
+            let line_fail = linear_program2(
+                &lines,
+                max_speed,
+                state.desired_velocity,
+                false,
+                &mut new_velocity,
+            );
+            if line_fail < lines.len() {
+                linear_program3(&lines, line_fail, max_speed, &mut new_velocity);
+            }
+
+            let agent = &mut self.agents[state.handle];
+            let _ = agent.update_with_velocity(
+                dt,
+                navmesh,
+                Vector3::new(new_velocity.x, 0.0, new_velocity.y),
+            );
+        }
+    }
+
+    fn collect_neighbors<'a>(
+        &self,
+        state: &AgentState,
+        states: &'a [AgentState],
+        neighbors: &mut Vec<(f32, &'a AgentState)>,
+    ) {
+        neighbors.clear();
+        let max_distance_sqr = self.neighbor_distance * self.neighbor_distance;
+        for other in states {
+            if other.handle == state.handle {
+                continue;
+            }
+            let distance_sqr = (other.position - state.position).norm_squared();
+            if distance_sqr < max_distance_sqr {
+                neighbors.push((distance_sqr, other));
+            }
+        }
+        neighbors.sort_by(|a, b| a.0.total_cmp(&b.0));
+        neighbors.truncate(self.max_neighbors);
+    }
+
+    fn build_orca_lines(
+        &self,
+        state: &AgentState,
+        neighbors: &[(f32, &AgentState)],
+        dt: f32,
+        lines: &mut Vec<OrcaLine>,
+    ) {
+        lines.clear();
+        let inv_time_horizon = 1.0 / self.time_horizon;
+        for (distance_sqr, other) in neighbors {
+            let relative_position = other.position - state.position;
+            let relative_velocity = state.velocity - other.velocity;
+            let combined_radius = state.radius + other.radius;
+            let combined_radius_sqr = combined_radius * combined_radius;
+
+            let (direction, u) = if *distance_sqr > combined_radius_sqr {
+                // No collision, w is a vector from cutoff center to relative velocity.
+                let w = relative_velocity - relative_position.scale(inv_time_horizon);
+                let w_length_sqr = w.norm_squared();
+                let dot_product = w.dot(&relative_position);
+
+                if dot_product < 0.0
+                    && dot_product * dot_product > combined_radius_sqr * w_length_sqr
+                {
+                    // Project on cut-off circle.
+                    let w_length = w_length_sqr.sqrt();
+                    let unit_w = w.scale(1.0 / w_length.max(EPSILON));
+                    (
+                        Vector2::new(unit_w.y, -unit_w.x),
+                        unit_w.scale(combined_radius * inv_time_horizon - w_length),
+                    )
+                } else {
+                    // Project on legs.
+                    let leg = (distance_sqr - combined_radius_sqr).sqrt();
+                    let direction = if det(relative_position, w) > 0.0 {
+                        // Left leg.
+                        Vector2::new(
+                            relative_position.x * leg - relative_position.y * combined_radius,
+                            relative_position.x * combined_radius + relative_position.y * leg,
+                        )
+                    } else {
+                        // Right leg.
+                        -Vector2::new(
+                            relative_position.x * leg + relative_position.y * combined_radius,
+                            -relative_position.x * combined_radius + relative_position.y * leg,
+                        )
+                    }
+                    .scale(1.0 / distance_sqr);
+
+                    (
+                        direction,
+                        direction.scale(relative_velocity.dot(&direction)) - relative_velocity,
+                    )
+                }
+            } else {
+                // Collision, project on cut-off circle of the time step.
+                let inv_time_step = 1.0 / dt.max(EPSILON);
+                let w = relative_velocity - relative_position.scale(inv_time_step);
+                let w_length = w.norm();
+                let unit_w = w.scale(1.0 / w_length.max(EPSILON));
+                (
+                    Vector2::new(unit_w.y, -unit_w.x),
+                    unit_w.scale(combined_radius * inv_time_step - w_length),
+                )
+            };
+
+            let priority_sum = state.priority + other.priority;
+            let responsibility = if priority_sum > 0.0 {
+                other.priority / priority_sum
+            } else {
+                0.5
+            };
+
+            lines.push(OrcaLine {
+                point: state.velocity + u.scale(responsibility),
+                direction,
+            });
+        }
+    }
+
+    /// Draws the agents of the crowd - a circle of agent's radius, its current velocity (green) and its
+    /// desired velocity (yellow).
+    pub fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        for agent in self.agents.iter() {
+            let position = agent.position();
+
+            ctx.draw_circle(
+                Vector3::default(),
+                agent.radius(),
+                16,
+                Matrix4::new_translation(&position)
+                    * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 90.0f32.to_radians())
+                        .to_homogeneous(),
+                Color::opaque(0, 200, 255),
+            );
+
+            ctx.add_line(Line {
+                begin: position,
+                end: position + agent.velocity(),
+                color: Color::GREEN,
+            });
+
+            if let Some(steering_target) = agent.steering_target() {
+                let direction = (steering_target - position)
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_default();
+                ctx.add_line(Line {
+                    begin: position,
+                    end: position + direction.scale(agent.speed()),
+                    color: Color::opaque(255, 255, 0),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, math::TriangleDefinition},
+        utils::{
+            navmesh::{Navmesh, NavmeshAgentBuilder},
+            navmesh_crowd::NavmeshCrowd,
+        },
+    };
+
+    fn make_navmesh() -> Navmesh {
+        Navmesh::new(
+            vec![TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 2, 3])],
+            vec![
+                Vector3::new(-10.0, 0.0, -10.0),
+                Vector3::new(-10.0, 0.0, 10.0),
+                Vector3::new(10.0, 0.0, 10.0),
+                Vector3::new(10.0, 0.0, -10.0),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_agents_do_not_overlap() {
+        let navmesh = make_navmesh();
+        let mut crowd = NavmeshCrowd::new();
+
+        let a = crowd.add_agent(
+            NavmeshAgentBuilder::new()
+                .with_position(Vector3::new(-5.0, 0.0, 0.0))
+                .with_target(Vector3::new(5.0, 0.0, 0.0))
+                .with_radius(0.5)
+                .build(),
+        );
+        let b = crowd.add_agent(
+            NavmeshAgentBuilder::new()
+                .with_position(Vector3::new(5.0, 0.0, 0.01))
+                .with_target(Vector3::new(-5.0, 0.0, 0.01))
+                .with_radius(0.5)
+                .build(),
+        );
+
+        for _ in 0..600 {
+            crowd.update(1.0 / 60.0, &navmesh);
+
+            let pa = crowd.agent(a).unwrap().position();
+            let pb = crowd.agent(b).unwrap().position();
+            assert!(pa.metric_distance(&pb) >= 0.99);
+        }
+
+        let pa = crowd.agent(a).unwrap().position();
+        let pb = crowd.agent(b).unwrap().position();
+        assert!(pa.metric_distance(&Vector3::new(5.0, 0.0, 0.0)) < 0.1);
+        assert!(pb.metric_distance(&Vector3::new(-5.0, 0.0, 0.01)) < 0.1);
+    }
+
+    #[test]
+    fn test_priority() {
+        let navmesh = make_navmesh();
+        let mut crowd = NavmeshCrowd::new();
+
+        let low = crowd.add_agent(
+            NavmeshAgentBuilder::new()
+                .with_position(Vector3::new(-5.0, 0.0, 0.0))
+                .with_target(Vector3::new(5.0, 0.0, 0.0))
+                .with_radius(0.5)
+                .with_priority(0.0)
+                .build(),
+        );
+        let high = crowd.add_agent(
+            NavmeshAgentBuilder::new()
+                .with_position(Vector3::new(5.0, 0.0, 0.01))
+                .with_target(Vector3::new(-5.0, 0.0, 0.01))
+                .with_radius(0.5)
+                .with_priority(1.0)
+                .build(),
+        );
+
+        let mut max_low_deviation = 0.0f32;
+        let mut max_high_deviation = 0.0f32;
+        for _ in 0..600 {
+            crowd.update(1.0 / 60.0, &navmesh);
+            max_low_deviation = max_low_deviation.max(crowd.agent(low).unwrap().position().z.abs());
+            max_high_deviation =
+                max_high_deviation.max((crowd.agent(high).unwrap().position().z - 0.01).abs());
+        }
+
+        assert!(max_low_deviation > max_high_deviation);
+    }
+}
@@ -41,8 +41,11 @@ pub mod io;
 pub mod loader;
 pub mod manager;
 pub mod options;
+pub mod preload;
 pub mod state;
 pub mod untyped;
+#[cfg(target_arch = "wasm32")]
+pub mod web;
 
 /// Type UUID of texture resource. It is defined here to load old versions of resources.
 pub const TEXTURE_RESOURCE_UUID: Uuid = uuid!("02c23a44-55fa-411a-bc39-eb7a5eadf15c");
@@ -117,6 +120,14 @@ where
             None
         }
     }
+
+    pub fn data_ref(&self) -> Option<&T> {
+        if let ResourceState::Ok(ref data) = self.guard.state {
+            ResourceData::as_any(&**data).downcast_ref::<T>()
+        } else {
+            None
+        }
+    }
 }
 
 /// A resource of particular data type. It is a typed wrapper around [`UntypedResource`] which
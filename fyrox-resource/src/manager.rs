@@ -9,7 +9,9 @@ use crate::{
         futures::future::join_all,
         io::FileLoadError,
         log::Log,
-        make_relative_path, notify,
+        make_relative_path,
+        memory::{self, MemorySubsystem},
+        notify,
         parking_lot::{Mutex, MutexGuard},
         task::TaskPool,
         watcher::FileSystemWatcher,
@@ -23,10 +25,11 @@ use crate::{
     state::{LoadError, ResourceState},
     Resource, ResourceData, TypedResourceData, UntypedResource,
 };
-use fxhash::{FxHashMap, FxHashSet};
+use fxhash::{FxHashMap, FxHashSet, FxHasher64};
 use rayon::prelude::*;
 use std::{
     fmt::{Debug, Display, Formatter},
+    hash::{Hash, Hasher},
     marker::PhantomData,
     path::{Path, PathBuf},
     sync::Arc,
@@ -246,26 +249,26 @@ impl ResourceManager {
         }
     }
 
-    /// Attempts to move a resource from its current location to the new path.
-    pub async fn move_resource(
+    /// Collects all resources from the given directory (except the ones rejected by the filter or
+    /// listed in `exclude`), that reference resources located at any of the given paths. Returns
+    /// pairs of a referencing resource and a set of the referenced resources.
+    async fn collect_references(
         &self,
-        resource: UntypedResource,
-        new_path: impl AsRef<Path>,
-        working_directory: impl AsRef<Path>,
+        paths: &[PathBuf],
+        exclude: &[UntypedResource],
+        working_directory: &Path,
         mut filter: impl FnMut(&UntypedResource) -> bool,
-    ) -> Result<(), FileLoadError> {
-        let new_path = new_path.as_ref().to_owned();
+    ) -> Result<Vec<(UntypedResource, FxHashSet<UntypedResource>)>, FileLoadError> {
         let io = self.state().resource_io.clone();
-        let existing_path = resource
-            .kind()
-            .into_path()
-            .ok_or_else(|| FileLoadError::Custom("Cannot move embedded resource!".to_string()))?;
 
-        let canonical_existing_path = io.canonicalize_path(&existing_path).await?;
+        let mut canonical_paths = Vec::with_capacity(paths.len());
+        for path in paths {
+            canonical_paths.push(io.canonicalize_path(path).await?);
+        }
 
         // Collect all resources referencing the resource.
         let resources = io
-            .walk_directory(working_directory.as_ref())
+            .walk_directory(working_directory)
             .await?
             .map(|p| self.request_untyped(p))
             .collect::<Vec<_>>();
@@ -274,7 +277,7 @@ impl ResourceManager {
             .await
             .into_iter()
             .filter_map(|r| r.ok())
-            .filter(|r| r != &resource && filter(r))
+            .filter(|r| !exclude.contains(r) && filter(r))
             .collect::<Vec<_>>();
 
         // Do the heavy work in parallel.
@@ -287,14 +290,14 @@ impl ResourceManager {
                     (**data).as_reflect(&mut |reflect| {
                         collect_used_resources(reflect, &mut used_resources);
                     });
-                    Some((loaded_resource, used_resources))
+                    Some((loaded_resource.clone(), used_resources))
                 } else {
                     None
                 }
             })
             .collect::<Vec<_>>();
 
-        // Filter out all resources that does not have references to the moved resource.
+        // Filter out all resources that does not have references to the given resources.
         for (_, used_resources) in pairs.iter_mut() {
             let mut used_resources_with_references = FxHashSet::default();
             for resource in used_resources.iter() {
@@ -305,7 +308,7 @@ impl ResourceManager {
                         // same path. Remember that there could be any number of paths leading to the
                         // same file (i.e. "foo/bar/baz.txt" and "foo/bar/../bar/baz.txt" leads to the
                         // same file, but the paths are different).
-                        if canonical_resource_path == canonical_existing_path {
+                        if canonical_paths.contains(&canonical_resource_path) {
                             used_resources_with_references.insert(resource.clone());
                         }
                     }
@@ -314,30 +317,58 @@ impl ResourceManager {
             *used_resources = used_resources_with_references;
         }
 
-        for (loaded_resource, used_resources) in pairs {
-            if !used_resources.is_empty() {
-                for resource in used_resources {
-                    resource.set_kind(ResourceKind::External(new_path.clone()));
-                }
+        pairs.retain(|(_, used_resources)| !used_resources.is_empty());
 
-                let mut header = loaded_resource.0.lock();
-                if let Some(loaded_resource_path) = header.kind.path_owned() {
-                    if let ResourceState::Ok(ref mut data) = header.state {
-                        // Save the resource back.
-                        match data.save(&loaded_resource_path) {
-                            Ok(_) => Log::info(format!(
-                                "Resource {} was saved successfully!",
-                                header.kind
-                            )),
-                            Err(err) => Log::err(format!(
-                                "Unable to save {} resource. Reason: {:?}",
-                                header.kind, err
-                            )),
-                        };
-                    }
-                }
+        Ok(pairs)
+    }
+
+    /// Saves the given resource back to its file.
+    fn save_resource(resource: &UntypedResource) {
+        let mut header = resource.0.lock();
+        if let Some(loaded_resource_path) = header.kind.path_owned() {
+            if let ResourceState::Ok(ref mut data) = header.state {
+                // Save the resource back.
+                match data.save(&loaded_resource_path) {
+                    Ok(_) => Log::info(format!("Resource {} was saved successfully!", header.kind)),
+                    Err(err) => Log::err(format!(
+                        "Unable to save {} resource. Reason: {:?}",
+                        header.kind, err
+                    )),
+                };
             }
         }
+    }
+
+    /// Attempts to move a resource from its current location to the new path.
+    pub async fn move_resource(
+        &self,
+        resource: UntypedResource,
+        new_path: impl AsRef<Path>,
+        working_directory: impl AsRef<Path>,
+        filter: impl FnMut(&UntypedResource) -> bool,
+    ) -> Result<(), FileLoadError> {
+        let new_path = new_path.as_ref().to_owned();
+        let io = self.state().resource_io.clone();
+        let existing_path = resource
+            .kind()
+            .into_path()
+            .ok_or_else(|| FileLoadError::Custom("Cannot move embedded resource!".to_string()))?;
+
+        let references = self
+            .collect_references(
+                std::slice::from_ref(&existing_path),
+                std::slice::from_ref(&resource),
+                working_directory.as_ref(),
+                filter,
+            )
+            .await?;
+
+        for (loaded_resource, used_resources) in references {
+            for resource in used_resources {
+                resource.set_kind(ResourceKind::External(new_path.clone()));
+            }
+            Self::save_resource(&loaded_resource);
+        }
 
         // Move the file with its optional import options.
         io.move_file(&existing_path, &new_path).await?;
@@ -350,6 +381,144 @@ impl ResourceManager {
         Ok(())
     }
 
+    /// Searches the given directory for files of supported resources with identical content.
+    /// Returns groups of paths, where each group contains at least two files with the same
+    /// content. Groups and paths in them are sorted, so the result is stable.
+    pub async fn find_duplicates(
+        &self,
+        working_directory: impl AsRef<Path>,
+    ) -> Result<Vec<Vec<PathBuf>>, FileLoadError> {
+        let io = self.state().resource_io.clone();
+
+        let mut paths = io
+            .walk_directory(working_directory.as_ref())
+            .await?
+            .filter(|path| {
+                path.extension().is_some_and(|ext| {
+                    let ext = ext.to_string_lossy();
+                    self.state()
+                        .loaders
+                        .iter()
+                        .any(|loader| loader.supports_extension(&ext))
+                })
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        // Group the files by their size and hash first, and then compare the content of the files
+        // in each group byte-by-byte to rule out hash collisions.
+        let mut candidates = FxHashMap::<(usize, u64), Vec<PathBuf>>::default();
+        for path in paths {
+            let Ok(content) = io.load_file(&path).await else {
+                continue;
+            };
+            let mut hasher = FxHasher64::default();
+            content.hash(&mut hasher);
+            candidates
+                .entry((content.len(), hasher.finish()))
+                .or_default()
+                .push(path);
+        }
+
+        let mut duplicates = Vec::new();
+        for (_, mut group) in candidates {
+            while group.len() > 1 {
+                let first = group.remove(0);
+                let first_content = io.load_file(&first).await?;
+                let mut same = vec![first];
+                let mut different = Vec::new();
+                for path in group {
+                    if io.load_file(&path).await? == first_content {
+                        same.push(path);
+                    } else {
+                        different.push(path);
+                    }
+                }
+                if same.len() > 1 {
+                    duplicates.push(same);
+                }
+                group = different;
+            }
+        }
+        duplicates.sort();
+
+        Ok(duplicates)
+    }
+
+    /// Collects all resources from the given directory (except the ones rejected by the filter),
+    /// that reference the given resource.
+    pub async fn find_references(
+        &self,
+        resource: &UntypedResource,
+        working_directory: impl AsRef<Path>,
+        filter: impl FnMut(&UntypedResource) -> bool,
+    ) -> Result<Vec<UntypedResource>, FileLoadError> {
+        let path = resource.kind().into_path().ok_or_else(|| {
+            FileLoadError::Custom("Embedded resources cannot be referenced!".to_string())
+        })?;
+        Ok(self
+            .collect_references(
+                &[path],
+                std::slice::from_ref(resource),
+                working_directory.as_ref(),
+                filter,
+            )
+            .await?
+            .into_iter()
+            .map(|(resource, _)| resource)
+            .collect())
+    }
+
+    /// Replaces every reference to the given duplicates in the resources from the given directory
+    /// (except the ones rejected by the filter) with a reference to the canonical resource and
+    /// saves the modified resources. Duplicates are not deleted, they just become unused.
+    pub async fn consolidate_resources(
+        &self,
+        canonical: &UntypedResource,
+        duplicates: &[UntypedResource],
+        working_directory: impl AsRef<Path>,
+        filter: impl FnMut(&UntypedResource) -> bool,
+    ) -> Result<(), FileLoadError> {
+        let canonical_path = canonical.kind().into_path().ok_or_else(|| {
+            FileLoadError::Custom("Cannot consolidate embedded resource!".to_string())
+        })?;
+        let duplicate_paths = duplicates
+            .iter()
+            .filter_map(|duplicate| duplicate.kind().into_path())
+            .filter(|path| *path != canonical_path)
+            .collect::<Vec<_>>();
+
+        let mut exclude = duplicates.to_vec();
+        exclude.push(canonical.clone());
+
+        let references = self
+            .collect_references(
+                &duplicate_paths,
+                &exclude,
+                working_directory.as_ref(),
+                filter,
+            )
+            .await?;
+
+        // References are redirected by changing the paths of the duplicates, so they must not be
+        // found by their old paths anymore.
+        {
+            let mut state = self.state();
+            for path in duplicate_paths.iter() {
+                state.unregister(path);
+            }
+        }
+
+        for (loaded_resource, used_resources) in references {
+            for resource in used_resources {
+                resource.set_kind(ResourceKind::External(canonical_path.clone()));
+            }
+            Self::save_resource(&loaded_resource);
+        }
+
+        Ok(())
+    }
+
     /// Reloads all loaded resources. Normally it should never be called, because it is **very** heavy
     /// method! This method is asynchronous, it uses all available CPU power to reload resources as
     /// fast as possible.
@@ -591,37 +760,39 @@ impl ResourceManagerState {
     ) {
         let event_broadcaster = self.event_broadcaster.clone();
         let loader_future = loader.load(path.clone(), self.resource_io.clone());
-        self.task_pool.spawn_task(async move {
-            match loader_future.await {
-                Ok(data) => {
-                    let data = data.0;
+        // Loading tasks are executed by a thread pool, so they must be tracked as futures.
+        self.task_pool
+            .spawn_task(memory::tracked(MemorySubsystem::Resources, async move {
+                match loader_future.await {
+                    Ok(data) => {
+                        let data = data.0;
 
-                    Log::info(format!(
-                        "Resource {} was loaded successfully!",
-                        path.display()
-                    ));
-
-                    // Separate scope to keep mutex locking time at minimum.
-                    {
-                        let mut mutex_guard = resource.0.lock();
-                        assert_eq!(mutex_guard.type_uuid, data.type_uuid());
-                        assert!(mutex_guard.kind.is_external());
-                        mutex_guard.state.commit(ResourceState::Ok(data));
+                        Log::info(format!(
+                            "Resource {} was loaded successfully!",
+                            path.display()
+                        ));
+
+                        // Separate scope to keep mutex locking time at minimum.
+                        {
+                            let mut mutex_guard = resource.0.lock();
+                            assert_eq!(mutex_guard.type_uuid, data.type_uuid());
+                            assert!(mutex_guard.kind.is_external());
+                            mutex_guard.state.commit(ResourceState::Ok(data));
+                        }
+
+                        event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
                     }
+                    Err(error) => {
+                        Log::info(format!(
+                            "Resource {} failed to load. Reason: {:?}",
+                            path.display(),
+                            error
+                        ));
 
-                    event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
-                }
-                Err(error) => {
-                    Log::info(format!(
-                        "Resource {} failed to load. Reason: {:?}",
-                        path.display(),
-                        error
-                    ));
-
-                    resource.commit_error(error);
+                        resource.commit_error(error);
+                    }
                 }
-            }
-        });
+            }));
     }
 
     /// Reloads a single resource.
@@ -710,6 +881,7 @@ mod test {
 
     use fyrox_core::uuid::{uuid, Uuid};
     use fyrox_core::{
+        futures,
         reflect::{FieldInfo, Reflect},
         visitor::{Visit, VisitResult, Visitor},
         TypeUuidProvider,
@@ -998,6 +1170,29 @@ mod test {
         assert_eq!(res, resource);
     }
 
+    #[test]
+    fn resource_manager_find_duplicates() {
+        let dir = std::env::temp_dir().join("fyrox_resource_manager_find_duplicates");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("a.txt"), "foo").unwrap();
+        std::fs::write(dir.join("sub/b.txt"), "foo").unwrap();
+        std::fs::write(dir.join("c.txt"), "bar").unwrap();
+        // Unsupported files must be ignored.
+        std::fs::write(dir.join("d.bin"), "foo").unwrap();
+
+        let manager = ResourceManager::new(Arc::new(Default::default()));
+        manager.state().loaders.set(Stub {});
+
+        let duplicates = futures::executor::block_on(manager.find_duplicates(&dir)).unwrap();
+        assert_eq!(
+            duplicates,
+            vec![vec![dir.join("a.txt"), dir.join("sub/b.txt")]]
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn display_for_resource_registration_error() {
         assert_eq!(
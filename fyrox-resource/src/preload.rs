@@ -0,0 +1,194 @@
+//! Asset preload manifest is a list of assets of a game, that should be streamed in advance on
+//! platforms where file access is slow (such as WebAssembly, where every file is fetched over the
+//! network). Every asset has its own streaming priority, so the assets that are needed first
+//! (scenes, user interface, shaders, etc.) are streamed before the others.
+
+use fxhash::FxHasher64;
+use serde::{Deserialize, Serialize};
+use std::{
+    hash::Hasher,
+    path::{Path, PathBuf},
+};
+
+/// Name of the file with preload manifest, that is placed at the root folder of a game build.
+pub const PRELOAD_MANIFEST_FILE: &str = "preload.ron";
+
+/// Defines an order in which the assets will be streamed. Assets with higher priority are streamed
+/// first.
+#[derive(
+    Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum StreamingPriority {
+    /// Assets, that are needed to show anything at all (scenes, user interface, shaders, fonts).
+    Critical,
+    /// Assets, that are needed right after the start (textures and materials).
+    High,
+    /// Everything else.
+    #[default]
+    Normal,
+    /// Assets, that could be streamed last (sounds).
+    Low,
+}
+
+impl StreamingPriority {
+    /// Picks a priority for a file with the given extension.
+    pub fn from_extension(extension: &str) -> Self {
+        match extension.to_lowercase().as_str() {
+            "rgs" | "ui" | "shader" | "ttf" | "otf" => Self::Critical,
+            "material" | "png" | "jpg" | "jpeg" | "tga" | "bmp" | "dds" | "gif" | "tif"
+            | "tiff" | "hdr" => Self::High,
+            "wav" | "ogg" => Self::Low,
+            _ => Self::Normal,
+        }
+    }
+}
+
+/// A single asset in a preload manifest.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreloadEntry {
+    /// Path of the asset relative to the root folder of a game build.
+    pub path: PathBuf,
+    /// Size of the asset in bytes.
+    pub size: u64,
+    /// Streaming priority of the asset.
+    pub priority: StreamingPriority,
+}
+
+/// A list of assets, that should be streamed in advance. See the module docs for more info.
+#[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreloadManifest {
+    /// A hash of the content of every asset in the manifest. It changes every time when any of the
+    /// assets is changed, which allows to invalidate cached assets.
+    pub version: String,
+    /// Assets sorted by their streaming priority.
+    pub entries: Vec<PreloadEntry>,
+}
+
+impl PreloadManifest {
+    /// Creates a manifest for every file in the given folders. Folders must be relative to the
+    /// `root` folder, paths of the entries will be relative to it as well.
+    pub fn scan(root: &Path, folders: &[PathBuf]) -> std::io::Result<Self> {
+        let mut entries = Vec::new();
+        for folder in folders {
+            for entry in walkdir::WalkDir::new(root.join(folder)) {
+                let entry = entry?;
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+
+                let Ok(path) = entry.path().strip_prefix(root) else {
+                    continue;
+                };
+
+                // Make the paths look the same as the paths used in the code.
+                let path = path
+                    .components()
+                    .filter(|c| !matches!(c, std::path::Component::CurDir))
+                    .collect::<PathBuf>();
+
+                entries.push(PreloadEntry {
+                    priority: path
+                        .extension()
+                        .map(|ext| StreamingPriority::from_extension(&ext.to_string_lossy()))
+                        .unwrap_or_default(),
+                    size: entry.metadata()?.len(),
+                    path,
+                });
+            }
+        }
+
+        entries.sort_by(|a, b| {
+            a.priority
+                .cmp(&b.priority)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        entries.dedup_by(|a, b| a.path == b.path);
+
+        let mut hasher = FxHasher64::default();
+        for entry in entries.iter() {
+            hasher.write(entry.path.to_string_lossy().as_bytes());
+            hasher.write(&std::fs::read(root.join(&entry.path))?);
+        }
+
+        Ok(Self {
+            version: format!("{:016x}", hasher.finish()),
+            entries,
+        })
+    }
+
+    /// Tries to deserialize a manifest from the given data.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ron::error::SpannedError> {
+        ron::de::from_bytes(data)
+    }
+
+    /// Serializes the manifest into a string.
+    pub fn to_string_pretty(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, Default::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn streaming_priority_from_extension() {
+        assert_eq!(
+            StreamingPriority::from_extension("rgs"),
+            StreamingPriority::Critical
+        );
+        assert_eq!(
+            StreamingPriority::from_extension("PNG"),
+            StreamingPriority::High
+        );
+        assert_eq!(
+            StreamingPriority::from_extension("fbx"),
+            StreamingPriority::Normal
+        );
+        assert_eq!(
+            StreamingPriority::from_extension("ogg"),
+            StreamingPriority::Low
+        );
+    }
+
+    #[test]
+    fn preload_manifest_scan() {
+        let dir = std::env::temp_dir().join("fyrox_preload_manifest_scan");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("data/sounds")).unwrap();
+        std::fs::write(dir.join("data/sounds/music.ogg"), "music").unwrap();
+        std::fs::write(dir.join("data/scene.rgs"), "scene").unwrap();
+        std::fs::write(dir.join("data/model.fbx"), "model").unwrap();
+        std::fs::write(dir.join("data/diffuse.png"), "texture").unwrap();
+
+        let manifest = PreloadManifest::scan(&dir, &["./data/".into()]).unwrap();
+        assert_eq!(
+            manifest
+                .entries
+                .iter()
+                .map(|e| (e.path.clone(), e.priority))
+                .collect::<Vec<_>>(),
+            vec![
+                (PathBuf::from("data/scene.rgs"), StreamingPriority::Critical),
+                (PathBuf::from("data/diffuse.png"), StreamingPriority::High),
+                (PathBuf::from("data/model.fbx"), StreamingPriority::Normal),
+                (
+                    PathBuf::from("data/sounds/music.ogg"),
+                    StreamingPriority::Low
+                ),
+            ]
+        );
+        assert_eq!(manifest.entries[0].size, 5);
+
+        let loaded =
+            PreloadManifest::from_bytes(manifest.to_string_pretty().unwrap().as_bytes()).unwrap();
+        assert_eq!(loaded, manifest);
+
+        // Version must change when the content of any asset changes.
+        std::fs::write(dir.join("data/model.fbx"), "changed").unwrap();
+        let changed = PreloadManifest::scan(&dir, &["./data/".into()]).unwrap();
+        assert_ne!(changed.version, manifest.version);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
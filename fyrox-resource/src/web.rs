@@ -0,0 +1,276 @@
+//! Resource IO for WebAssembly, that caches fetched files in the IndexedDB storage of a browser.
+//! Every file is fetched over the network only once (until the assets of the game are changed), so
+//! the next launches of the game do not stall on network requests. It also streams the assets
+//! listed in a [`PreloadManifest`] in the background.
+
+use crate::{
+    core::{
+        futures::future::join_all,
+        io::{self, FileLoadError},
+        js_sys::{Function, Promise, Uint8Array},
+        log::Log,
+        wasm_bindgen::{closure::Closure, JsCast, JsValue},
+        wasm_bindgen_futures::JsFuture,
+        web_sys::{self, IdbDatabase, IdbObjectStore, IdbRequest, IdbTransactionMode},
+    },
+    io::{ResourceIo, ResourceIoFuture},
+    preload::{PreloadEntry, PreloadManifest},
+};
+use fxhash::FxHashMap;
+use std::{
+    cell::RefCell,
+    path::{Component, Path},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+const FILES_STORE: &str = "files";
+const META_STORE: &str = "meta";
+const VERSION_KEY: &str = "version";
+
+thread_local! {
+    // Opened databases. WebAssembly is single-threaded, so it is fine to keep them here.
+    static DATABASES: RefCell<FxHashMap<String, IdbDatabase>> = Default::default();
+}
+
+/// Waits until the given request is finished and returns its result.
+async fn wait(request: &IdbRequest) -> Result<JsValue, FileLoadError> {
+    let mut callbacks: Option<(Function, Function)> = None;
+    let promise = Promise::new(&mut |resolve, reject| callbacks = Some((resolve, reject)));
+    let (resolve, reject) = callbacks.expect("Promise callback must be called immediately!");
+
+    let success_request = request.clone();
+    let on_success: Closure<dyn FnMut()> = Closure::once(move || {
+        let result = success_request.result().unwrap_or(JsValue::UNDEFINED);
+        let _ = resolve.call1(&JsValue::UNDEFINED, &result);
+    });
+    let on_error: Closure<dyn FnMut()> = Closure::once(move || {
+        let error = JsValue::from_str("IndexedDB request has failed.");
+        let _ = reject.call1(&JsValue::UNDEFINED, &error);
+    });
+    request.set_onsuccess(Some(on_success.as_ref().unchecked_ref()));
+    request.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+    let result = JsFuture::from(promise).await;
+
+    request.set_onsuccess(None);
+    request.set_onerror(None);
+
+    Ok(result?)
+}
+
+/// Makes a key for the given path. It is the same for `./data/foo.png` and `data/foo.png`.
+fn cache_key(path: &Path) -> JsValue {
+    let key = path
+        .components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    JsValue::from_str(&key)
+}
+
+/// Resource IO, that caches the files in the IndexedDB storage. See the module docs for more info.
+pub struct WebResourceIo {
+    database: String,
+    // Cached files could be used only after their version was checked, otherwise the files of the
+    // previous version of the game could be loaded.
+    validated: AtomicBool,
+}
+
+impl WebResourceIo {
+    /// Creates new resource IO, that stores the files in the database with the given name.
+    pub fn new(database: impl Into<String>) -> Self {
+        Self {
+            database: database.into(),
+            validated: AtomicBool::new(false),
+        }
+    }
+
+    async fn open(&self) -> Result<IdbDatabase, FileLoadError> {
+        if let Some(database) = DATABASES.with(|dbs| dbs.borrow().get(&self.database).cloned()) {
+            return Ok(database);
+        }
+
+        let factory = web_sys::window()
+            .ok_or_else(|| FileLoadError::Custom("Window not found!".to_owned()))?
+            .indexed_db()?
+            .ok_or_else(|| FileLoadError::Custom("IndexedDB is not supported!".to_owned()))?;
+
+        let request = factory.open_with_u32(&self.database, 1)?;
+        let upgrade_request = request.clone();
+        let on_upgrade_needed: Closure<dyn FnMut()> = Closure::once(move || {
+            if let Ok(database) = upgrade_request
+                .result()
+                .and_then(|result| result.dyn_into::<IdbDatabase>())
+            {
+                for store in [FILES_STORE, META_STORE] {
+                    Log::verify(database.create_object_store(store));
+                }
+            }
+        });
+        request.set_onupgradeneeded(Some(on_upgrade_needed.as_ref().unchecked_ref()));
+        let result = wait(&request).await;
+        request.set_onupgradeneeded(None);
+
+        let database = result?.dyn_into::<IdbDatabase>()?;
+        DATABASES.with(|dbs| {
+            dbs.borrow_mut()
+                .insert(self.database.clone(), database.clone())
+        });
+        Ok(database)
+    }
+
+    async fn store(
+        &self,
+        name: &str,
+        mode: IdbTransactionMode,
+    ) -> Result<IdbObjectStore, FileLoadError> {
+        let database = self.open().await?;
+        let transaction = database.transaction_with_str_and_mode(name, mode)?;
+        Ok(transaction.object_store(name)?)
+    }
+
+    async fn read_cached(&self, path: &Path) -> Result<Option<Vec<u8>>, FileLoadError> {
+        let store = self
+            .store(FILES_STORE, IdbTransactionMode::Readonly)
+            .await?;
+        let value = wait(&store.get(&cache_key(path))?).await?;
+        if value.is_undefined() {
+            Ok(None)
+        } else {
+            Ok(Some(Uint8Array::new(&value).to_vec()))
+        }
+    }
+
+    async fn is_cached(&self, path: &Path) -> Result<bool, FileLoadError> {
+        let store = self
+            .store(FILES_STORE, IdbTransactionMode::Readonly)
+            .await?;
+        let count = wait(&store.count_with_key(&cache_key(path))?).await?;
+        Ok(count.as_f64().unwrap_or_default() > 0.0)
+    }
+
+    async fn write_cached(&self, path: &Path, data: &[u8]) -> Result<(), FileLoadError> {
+        let store = self
+            .store(FILES_STORE, IdbTransactionMode::Readwrite)
+            .await?;
+        wait(&store.put_with_key(&Uint8Array::from(data), &cache_key(path))?).await?;
+        Ok(())
+    }
+
+    /// Removes every cached file if the cache was made for other version of the assets.
+    async fn validate(&self, version: &str) -> Result<(), FileLoadError> {
+        let meta = self.store(META_STORE, IdbTransactionMode::Readonly).await?;
+        let current = wait(&meta.get(&JsValue::from_str(VERSION_KEY))?).await?;
+        if current.as_string().as_deref() != Some(version) {
+            Log::info("Assets were changed, clearing the cache...");
+
+            let files = self
+                .store(FILES_STORE, IdbTransactionMode::Readwrite)
+                .await?;
+            wait(&files.clear()?).await?;
+
+            let meta = self
+                .store(META_STORE, IdbTransactionMode::Readwrite)
+                .await?;
+            wait(&meta.put_with_key(&JsValue::from_str(version), &JsValue::from_str(VERSION_KEY))?)
+                .await?;
+        }
+
+        self.validated.store(true, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    async fn preload_entry(&self, entry: &PreloadEntry) {
+        if let Ok(true) = self.is_cached(&entry.path).await {
+            return;
+        }
+
+        match io::load_file(&entry.path).await {
+            Ok(data) => Log::verify(self.write_cached(&entry.path, &data).await),
+            Err(err) => Log::warn(format!(
+                "Unable to preload {}. Reason: {:?}",
+                entry.path.display(),
+                err
+            )),
+        }
+    }
+
+    /// Streams every asset from the given manifest into the cache. Assets with the same priority
+    /// are streamed simultaneously, assets with lower priority are streamed only when every asset
+    /// with higher priority is streamed.
+    pub async fn preload(&self, manifest: &PreloadManifest) -> Result<(), FileLoadError> {
+        self.validate(&manifest.version).await?;
+
+        let mut entries = manifest.entries.iter().peekable();
+        while let Some(first) = entries.peek() {
+            let priority = first.priority;
+            let mut group = Vec::new();
+            while let Some(entry) = entries.next_if(|entry| entry.priority == priority) {
+                group.push(self.preload_entry(entry));
+            }
+            join_all(group).await;
+        }
+
+        Log::info(format!("{} assets were preloaded.", manifest.entries.len()));
+
+        Ok(())
+    }
+
+    /// Loads a manifest from the given path and streams every asset from it into the cache. See
+    /// [`Self::preload`] for more info.
+    pub async fn preload_from_file(&self, path: &Path) -> Result<(), FileLoadError> {
+        let data = io::load_file(path).await?;
+        let manifest = PreloadManifest::from_bytes(&data)
+            .map_err(|err| FileLoadError::Custom(err.to_string()))?;
+        self.preload(&manifest).await
+    }
+}
+
+impl ResourceIo for WebResourceIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(async move {
+            if self.validated.load(Ordering::Relaxed) {
+                if let Ok(Some(data)) = self.read_cached(path).await {
+                    return Ok(data);
+                }
+            }
+
+            let data = io::load_file(path).await?;
+            Log::verify(self.write_cached(path, &data).await);
+            Ok(data)
+        })
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        _source: &'a Path,
+        _dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(async move {
+            Err(FileLoadError::Custom(
+                "Files cannot be moved on WebAssembly!".to_owned(),
+            ))
+        })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            (self.validated.load(Ordering::Relaxed)
+                && matches!(self.is_cached(path).await, Ok(true)))
+                || io::exists(path).await
+        })
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        self.exists(path)
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(io::is_dir(path))
+    }
+}
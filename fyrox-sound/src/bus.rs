@@ -1,13 +1,21 @@
 //! Everything related to audio buses and audio bus graphs. See docs of [`AudioBus`] and [`AudioBusGraph`]
 //! for more info and examples
 
-use crate::effects::{Effect, EffectRenderTrait};
+use crate::{
+    context::SAMPLE_RATE,
+    effects::{Effect, EffectRenderTrait},
+};
 use fyrox_core::{
     pool::{Handle, Pool, Ticket},
     reflect::prelude::*,
+    uuid_provider,
     visitor::prelude::*,
 };
-use std::fmt::{Debug, Formatter};
+use std::{
+    collections::hash_map::DefaultHasher,
+    fmt::{Debug, Formatter},
+    hash::{Hash, Hasher},
+};
 
 #[derive(Default, Clone)]
 struct PingPongBuffer {
@@ -76,6 +84,63 @@ impl PingPongBuffer {
     }
 }
 
+/// Send is an additional output of an audio bus. It routes a copy of the processed samples of the bus to
+/// some other audio bus (for example a shared reverb bus) while the bus still outputs its samples to its
+/// parent bus. The target bus is referenced by its name, the same way as sound sources do.
+#[derive(Debug, Reflect, Visit, Clone, PartialEq)]
+pub struct AudioBusSend {
+    /// Name of an audio bus to which the samples will be sent.
+    pub bus: String,
+    /// Amount of the signal that will be sent to the target bus.
+    #[reflect(min_value = 0.0)]
+    pub gain: f32,
+}
+
+uuid_provider!(AudioBusSend = "a297d0ae-3da5-4051-be84-d6bdd4f6dcac");
+
+impl Default for AudioBusSend {
+    fn default() -> Self {
+        Self {
+            bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            gain: 1.0,
+        }
+    }
+}
+
+impl AudioBusSend {
+    /// Creates a new send to an audio bus with the given name.
+    pub fn new<S: AsRef<str>>(bus: S, gain: f32) -> Self {
+        Self {
+            bus: bus.as_ref().to_owned(),
+            gain,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ResolvedSend {
+    target: Handle<AudioBus>,
+    // Index of the send in the list of sends of the sending bus.
+    index: usize,
+}
+
+/// Order in which audio buses are processed by the mixer. It is computed only when the routing of the
+/// graph changes, not on every render.
+#[derive(Debug, Clone)]
+struct ProcessingOrder {
+    // Hash of everything that defines the order: handles, names, parents and sends of the buses.
+    routing_hash: u64,
+    sends: Vec<(Handle<AudioBus>, Vec<ResolvedSend>)>,
+    hierarchy: Vec<Handle<AudioBus>>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct GainRamp {
+    target: f32,
+    step: f32,
+    samples_left: usize,
+}
+
 /// Audio bus is a top-level audio processing unit. It takes data from multiple audio sources and passes their
 /// samples through a chain of effects. Output signal is then can be either sent to an audio playback device or
 /// to some other audio bus and be processed again, but with different sound effects (this can be done via
@@ -86,6 +151,13 @@ pub struct AudioBus {
     effects: Vec<Effect>,
     gain: f32,
 
+    #[visit(optional)]
+    sends: Vec<AudioBusSend>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    gain_ramp: Option<GainRamp>,
+
     #[reflect(hidden)]
     child_buses: Vec<Handle<AudioBus>>,
 
@@ -104,6 +176,8 @@ impl Default for AudioBus {
             child_buses: Default::default(),
             effects: Default::default(),
             gain: 1.0,
+            sends: Default::default(),
+            gain_ramp: None,
             ping_pong_buffer: Default::default(),
             parent_bus: Default::default(),
         }
@@ -142,9 +216,33 @@ impl AudioBus {
         &self.child_buses
     }
 
-    /// Sets new gain of the audio bus.
+    /// Sets new gain of the audio bus. Any gain change started by [`Self::set_gain_over_time`] will be
+    /// cancelled.
     pub fn set_gain(&mut self, gain: f32) {
         self.gain = gain;
+        self.gain_ramp = None;
+    }
+
+    /// Smoothly changes the gain of the audio bus to the given value in the given amount of time (in
+    /// seconds). The gain is changed on per-sample basis in the mixer thread, which makes it suitable
+    /// for runtime automation of volume (for example to fade out music when a dialogue starts) without
+    /// audible clicks.
+    pub fn set_gain_over_time(&mut self, gain: f32, time: f32) {
+        let samples = (time.max(0.0) * SAMPLE_RATE as f32) as usize;
+        if samples == 0 {
+            self.set_gain(gain);
+        } else {
+            self.gain_ramp = Some(GainRamp {
+                target: gain,
+                step: (gain - self.gain) / samples as f32,
+                samples_left: samples,
+            });
+        }
+    }
+
+    /// Returns `true` if the gain of the audio bus is being changed over time, `false` - otherwise.
+    pub fn is_gain_changing(&self) -> bool {
+        self.gain_ramp.is_some()
     }
 
     /// Returns current gain of the audio bus.
@@ -202,6 +300,54 @@ impl AudioBus {
     pub fn effects_mut(&mut self) -> impl Iterator<Item = &mut Effect> {
         self.effects.iter_mut()
     }
+
+    /// Adds a new send to the audio bus. See [`AudioBusSend`] docs for more info.
+    pub fn add_send(&mut self, send: AudioBusSend) {
+        self.sends.push(send)
+    }
+
+    /// Removes a send at the given index.
+    pub fn remove_send(&mut self, index: usize) -> AudioBusSend {
+        self.sends.remove(index)
+    }
+
+    /// Inserts a send at the given index. The index must be less or equal to the amount of sends,
+    /// otherwise the method will panic.
+    pub fn insert_send(&mut self, index: usize, send: AudioBusSend) {
+        self.sends.insert(index, send)
+    }
+
+    /// Returns a slice with every send of the audio bus.
+    pub fn sends(&self) -> &[AudioBusSend] {
+        &self.sends
+    }
+
+    /// Returns a mutable reference to a send at the given index.
+    pub fn send_mut(&mut self, index: usize) -> Option<&mut AudioBusSend> {
+        self.sends.get_mut(index)
+    }
+
+    /// Returns the gain of the audio bus at the given sample of the current buffer, taking any gain change
+    /// started by [`Self::set_gain_over_time`] into account.
+    fn sample_gain(&self, sample: usize) -> f32 {
+        match self.gain_ramp.as_ref() {
+            Some(ramp) if sample < ramp.samples_left => self.gain + ramp.step * (sample + 1) as f32,
+            Some(ramp) => ramp.target,
+            None => self.gain,
+        }
+    }
+
+    fn advance_gain_ramp(&mut self, sample_count: usize) {
+        if let Some(ramp) = self.gain_ramp.as_mut() {
+            if sample_count < ramp.samples_left {
+                self.gain += ramp.step * sample_count as f32;
+                ramp.samples_left -= sample_count;
+            } else {
+                self.gain = ramp.target;
+                self.gain_ramp = None;
+            }
+        }
+    }
 }
 
 /// Audio bus graph is a complex audio data processing entity; it allows you to route samples from
@@ -253,6 +399,11 @@ impl AudioBus {
 /// current audio context. This data is then passed through a set of effects, which could include various
 /// filters (lowpass, highpass, bandpass, shelf filters, etc.) and complex effects such as reverberation.
 ///
+/// In addition to its parent, each audio bus can output its samples to any number of other buses using
+/// sends (see [`AudioBusSend`] docs for more info). Sent samples are mixed with the samples of the target
+/// bus before its effects are applied, which is useful to share a single expensive effect (such as
+/// reverberation) between multiple audio buses.
+///
 /// By default, each audio bus graph has a single audio bus called Primary. It is mandatory to at least one
 /// audio bus. Primary bus is responsible for outputting the data to an audio playback device.
 ///
@@ -287,6 +438,9 @@ impl AudioBus {
 pub struct AudioBusGraph {
     buses: Pool<AudioBus>,
     root: Handle<AudioBus>,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    processing_order: Option<ProcessingOrder>,
 }
 
 impl AudioBusGraph {
@@ -299,7 +453,11 @@ impl AudioBusGraph {
         let root = AudioBus::new(Self::PRIMARY_BUS.to_string());
         let mut buses = Pool::new();
         let root = buses.spawn(root);
-        Self { buses, root }
+        Self {
+            buses,
+            root,
+            processing_order: None,
+        }
     }
 
     /// Adds a new audio bus to the graph and attaches it to the given parent. `parent` handle must be
@@ -459,47 +617,183 @@ impl AudioBusGraph {
         }
     }
 
-    pub(crate) fn end_render(&mut self, output_device_buffer: &mut [(f32, f32)]) {
-        let mut leafs = Vec::new();
-        for (handle, bus) in self.buses.pair_iter_mut() {
-            bus.apply_effects();
+    /// Returns a list of handles of the audio buses sorted in the order in which their effects must be
+    /// applied - each bus goes after every bus that sends its samples to it. Each bus is paired with a
+    /// list of handles of the buses to which it sends its samples.
+    fn send_order(&self) -> Vec<(Handle<AudioBus>, Vec<ResolvedSend>)> {
+        fn visit(
+            graph: &AudioBusGraph,
+            handle: Handle<AudioBus>,
+            visited: &mut Vec<Handle<AudioBus>>,
+            order: &mut Vec<(Handle<AudioBus>, Vec<ResolvedSend>)>,
+        ) {
+            // Already processed bus or a loop of sends.
+            if visited.contains(&handle) {
+                return;
+            }
+            visited.push(handle);
+
+            let bus = &graph.buses[handle];
 
-            if bus.child_buses.is_empty() {
-                leafs.push(handle);
+            for (sender_handle, sender) in graph.buses.pair_iter() {
+                if sender.sends.iter().any(|send| send.bus == bus.name) {
+                    visit(graph, sender_handle, visited, order);
+                }
             }
+
+            let sends = bus
+                .sends
+                .iter()
+                .enumerate()
+                .filter_map(|(index, send)| {
+                    graph
+                        .buses
+                        .pair_iter()
+                        .find(|(target_handle, target)| {
+                            *target_handle != handle && target.name == send.bus
+                        })
+                        .map(|(target_handle, _)| ResolvedSend {
+                            target: target_handle,
+                            index,
+                        })
+                })
+                .collect();
+
+            order.push((handle, sends));
+        }
+
+        let mut visited = Vec::with_capacity(self.buses.alive_count() as usize);
+        let mut order = Vec::with_capacity(self.buses.alive_count() as usize);
+        for (handle, _) in self.buses.pair_iter() {
+            visit(self, handle, &mut visited, &mut order);
+        }
+        order
+    }
+
+    /// Returns a list of handles of the audio buses, where each bus goes after all its descendants.
+    fn hierarchy_order(&self) -> Vec<Handle<AudioBus>> {
+        fn visit(
+            graph: &AudioBusGraph,
+            handle: Handle<AudioBus>,
+            order: &mut Vec<Handle<AudioBus>>,
+        ) {
+            for child in graph.buses[handle].child_buses.iter() {
+                visit(graph, *child, order);
+            }
+            order.push(handle);
         }
 
-        for mut leaf in leafs {
-            while leaf.is_some() {
-                let ctx = self.buses.begin_multi_borrow();
-
-                let leaf_ref = ctx.try_get_mut(leaf).expect("Malformed bus graph!");
-
-                let input_buffer = leaf_ref.ping_pong_buffer.input_ref();
-                let leaf_gain = leaf_ref.gain;
-                let mut parent_buffer = ctx.try_get_mut(leaf_ref.parent_bus);
-                let output_buffer = parent_buffer
-                    .as_mut()
-                    .map(|parent| parent.ping_pong_buffer.input_mut())
-                    // Special case for the root bus - it writes directly to the output device buffer.
-                    .unwrap_or(&mut *output_device_buffer);
-                for ((input_left, input_right), (output_left, output_right)) in
-                    input_buffer.iter().zip(output_buffer)
-                {
-                    *output_left += *input_left * leaf_gain;
-                    *output_right += *input_right * leaf_gain;
+        let mut order = Vec::with_capacity(self.buses.alive_count() as usize);
+        visit(self, self.root, &mut order);
+        order
+    }
+
+    /// Calculates a hash of everything that defines the processing order of the audio buses. It does not
+    /// allocate, so it is cheap enough to be checked on every render. Changes made by any means (including
+    /// reflection) will be detected.
+    fn routing_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for (handle, bus) in self.buses.pair_iter() {
+            handle.hash(&mut hasher);
+            bus.name.hash(&mut hasher);
+            bus.parent_bus.hash(&mut hasher);
+            bus.child_buses.hash(&mut hasher);
+            for send in bus.sends.iter() {
+                send.bus.hash(&mut hasher);
+            }
+            // Separates the sends of adjacent buses.
+            bus.sends.len().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    fn update_processing_order(&mut self) {
+        let routing_hash = self.routing_hash();
+        if self
+            .processing_order
+            .as_ref()
+            .map_or(true, |order| order.routing_hash != routing_hash)
+        {
+            self.processing_order = Some(ProcessingOrder {
+                routing_hash,
+                sends: self.send_order(),
+                hierarchy: self.hierarchy_order(),
+            });
+        }
+    }
+
+    pub(crate) fn end_render(&mut self, output_device_buffer: &mut [(f32, f32)]) {
+        self.update_processing_order();
+        let Self {
+            buses,
+            processing_order,
+            ..
+        } = self;
+        let processing_order = processing_order
+            .as_ref()
+            .expect("Processing order must be computed!");
+
+        // Effects of each bus are applied to the samples of its sound sources and the samples sent to
+        // it by other buses, so every sender must be processed before the buses it sends to.
+        for (handle, sends) in processing_order.sends.iter() {
+            let ctx = buses.begin_multi_borrow();
+
+            let mut bus_ref = ctx.try_get_mut(*handle).expect("Malformed bus graph!");
+            bus_ref.apply_effects();
+
+            let input_buffer = bus_ref.ping_pong_buffer.input_ref();
+            for send in sends {
+                let Some(send_gain) = bus_ref.sends.get(send.index).map(|send| send.gain) else {
+                    continue;
+                };
+                if let Ok(mut target) = ctx.try_get_mut(send.target) {
+                    for (i, ((input_left, input_right), (output_left, output_right))) in
+                        input_buffer
+                            .iter()
+                            .zip(target.ping_pong_buffer.input_mut())
+                            .enumerate()
+                    {
+                        let gain = bus_ref.sample_gain(i) * send_gain;
+                        *output_left += *input_left * gain;
+                        *output_right += *input_right * gain;
+                    }
                 }
+            }
+        }
 
-                leaf = leaf_ref.parent_bus;
+        // Output of each bus is mixed with the output of its parent bus (after the effects of the parent
+        // bus), children are processed first so their samples will be passed to the root bus.
+        for handle in processing_order.hierarchy.iter() {
+            let ctx = buses.begin_multi_borrow();
+
+            let bus_ref = ctx.try_get_mut(*handle).expect("Malformed bus graph!");
+
+            let input_buffer = bus_ref.ping_pong_buffer.input_ref();
+            let mut parent_buffer = ctx.try_get_mut(bus_ref.parent_bus);
+            let output_buffer = parent_buffer
+                .as_mut()
+                .map(|parent| parent.ping_pong_buffer.input_mut())
+                // Special case for the root bus - it writes directly to the output device buffer.
+                .unwrap_or(&mut *output_device_buffer);
+            for (i, ((input_left, input_right), (output_left, output_right))) in
+                input_buffer.iter().zip(output_buffer).enumerate()
+            {
+                let gain = bus_ref.sample_gain(i);
+                *output_left += *input_left * gain;
+                *output_right += *input_right * gain;
             }
         }
+
+        for bus in buses.iter_mut() {
+            bus.advance_gain_ramp(output_device_buffer.len());
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
     use crate::{
-        bus::{AudioBus, AudioBusGraph},
+        bus::{AudioBus, AudioBusGraph, AudioBusSend, GainRamp},
         effects::{Attenuate, Effect},
     };
 
@@ -582,4 +876,94 @@ mod test {
 
         assert_eq!(output_buffer[0], (0.75, 0.75));
     }
+
+    #[test]
+    fn test_send_data_flow() {
+        let mut output_buffer = [(0.0f32, 0.0f32)];
+
+        let mut graph = AudioBusGraph::new();
+
+        let mut reverb = AudioBus::new("Reverb".to_string());
+        reverb.add_effect(Effect::Attenuate(Attenuate::new(0.5)));
+        let reverb = graph.add_bus(reverb, graph.root);
+
+        let mut sfx = AudioBus::new("SFX".to_string());
+        sfx.add_send(AudioBusSend::new("Reverb", 0.5));
+        let sfx = graph.add_bus(sfx, graph.root);
+
+        graph.begin_render(output_buffer.len());
+
+        for (left, right) in graph.buses[sfx].input_buffer() {
+            *left = 1.0;
+            *right = 1.0;
+        }
+
+        graph.end_render(&mut output_buffer);
+
+        // Direct output of SFX bus (1.0) plus the send processed by the effects of Reverb bus (0.25).
+        assert_eq!(output_buffer[0], (1.25, 1.25));
+        assert!(graph.buses[reverb].sends().is_empty());
+    }
+
+    #[test]
+    fn test_processing_order_update() {
+        let mut graph = AudioBusGraph::new();
+
+        let mut reverb = AudioBus::new("Reverb".to_string());
+        reverb.add_effect(Effect::Attenuate(Attenuate::new(0.5)));
+        graph.add_bus(reverb, graph.root);
+
+        let mut sfx = AudioBus::new("SFX".to_string());
+        sfx.add_send(AudioBusSend::new("Reverb", 0.5));
+        let sfx = graph.add_bus(sfx, graph.root);
+
+        let render = |graph: &mut AudioBusGraph| {
+            let mut output_buffer = [(0.0f32, 0.0f32)];
+            graph.begin_render(output_buffer.len());
+            for (left, right) in graph.buses[sfx].input_buffer() {
+                *left = 1.0;
+                *right = 1.0;
+            }
+            graph.end_render(&mut output_buffer);
+            output_buffer[0]
+        };
+
+        assert_eq!(render(&mut graph), (1.25, 1.25));
+
+        // Gain of a send does not affect the order, but must be applied anyway.
+        graph.buses[sfx].send_mut(0).unwrap().gain = 1.0;
+        assert_eq!(render(&mut graph), (1.5, 1.5));
+
+        // Re-routing the send must rebuild the order.
+        graph.buses[sfx].send_mut(0).unwrap().bus = "Unknown".to_string();
+        assert_eq!(render(&mut graph), (1.0, 1.0));
+    }
+
+    #[test]
+    fn test_gain_over_time() {
+        let mut output_buffer = [(0.0f32, 0.0f32); 4];
+
+        let mut graph = AudioBusGraph::new();
+
+        graph.primary_bus_mut().gain_ramp = Some(GainRamp {
+            target: 0.0,
+            step: -0.5,
+            samples_left: 2,
+        });
+
+        graph.begin_render(output_buffer.len());
+
+        for (left, right) in graph.buses[graph.root].input_buffer() {
+            *left = 1.0;
+            *right = 1.0;
+        }
+
+        graph.end_render(&mut output_buffer);
+
+        assert_eq!(
+            output_buffer,
+            [(0.5, 0.5), (0.0, 0.0), (0.0, 0.0), (0.0, 0.0)]
+        );
+        assert!(!graph.primary_bus_ref().is_gain_changing());
+    }
 }
@@ -0,0 +1,226 @@
+//! Dynamic range compressor effect. It reduces the volume of loud sounds, which could be useful to keep
+//! the overall mix at a stable level; for example to prevent explosions from drowning out the dialogues.
+
+use crate::{context::SAMPLE_RATE, effects::EffectRenderTrait};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+
+fn time_to_coefficient(time: f32) -> f32 {
+    if time <= 0.0 {
+        0.0
+    } else {
+        (-1.0 / (time * SAMPLE_RATE as f32)).exp()
+    }
+}
+
+fn linear_to_db(value: f32) -> f32 {
+    20.0 * value.max(f32::EPSILON).log10()
+}
+
+fn db_to_linear(value: f32) -> f32 {
+    10.0f32.powf(value / 20.0)
+}
+
+/// Compressor lowers the amplitude of the signal which exceeds the threshold. The amount of reduction is
+/// defined by the ratio, for example a ratio of 4.0 means that each 4 dB above the threshold will be
+/// turned into 1 dB. Both channels are compressed by the same amount (linked stereo), so the stereo image
+/// is preserved.
+#[derive(Debug, Clone, Reflect, PartialEq)]
+pub struct Compressor {
+    #[reflect(
+        description = "A level (in decibels) above which the signal will be compressed.",
+        max_value = 0.0
+    )]
+    threshold_db: f32,
+
+    #[reflect(
+        description = "Amount of compression. For example a ratio of 4.0 turns each 4 dB above \
+        the threshold into 1 dB.",
+        setter = "set_ratio",
+        min_value = 1.0
+    )]
+    ratio: f32,
+
+    #[reflect(
+        description = "Time (in seconds) that it takes for the compressor to react on a signal \
+        that exceeds the threshold.",
+        setter = "set_attack_time",
+        min_value = 0.0
+    )]
+    attack_time: f32,
+
+    #[reflect(
+        description = "Time (in seconds) that it takes for the compressor to stop compressing \
+        after the signal fell below the threshold.",
+        setter = "set_release_time",
+        min_value = 0.0
+    )]
+    release_time: f32,
+
+    #[reflect(
+        description = "Additional gain (in decibels) that is applied after the compression.",
+        min_value = 0.0
+    )]
+    makeup_gain_db: f32,
+
+    #[reflect(hidden)]
+    attack_coefficient: f32,
+    #[reflect(hidden)]
+    release_coefficient: f32,
+    #[reflect(hidden)]
+    envelope_db: f32,
+}
+
+impl Visit for Compressor {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.threshold_db.visit("Threshold", &mut region)?;
+        self.ratio.visit("Ratio", &mut region)?;
+        self.attack_time.visit("AttackTime", &mut region)?;
+        self.release_time.visit("ReleaseTime", &mut region)?;
+        self.makeup_gain_db.visit("MakeupGain", &mut region)?;
+
+        if region.is_reading() {
+            self.update();
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Compressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Compressor {
+    /// Creates new compressor with -12 dB threshold, 4:1 ratio, 5 ms attack time and 100 ms release
+    /// time.
+    pub fn new() -> Self {
+        let mut compressor = Self {
+            threshold_db: -12.0,
+            ratio: 4.0,
+            attack_time: 0.005,
+            release_time: 0.1,
+            makeup_gain_db: 0.0,
+            attack_coefficient: 0.0,
+            release_coefficient: 0.0,
+            envelope_db: 0.0,
+        };
+        compressor.update();
+        compressor
+    }
+
+    /// Sets a level (in decibels) above which the signal will be compressed. The value is usually
+    /// in `[-60.0..0.0]` range.
+    pub fn set_threshold_db(&mut self, threshold_db: f32) {
+        self.threshold_db = threshold_db;
+    }
+
+    /// Returns current threshold in decibels.
+    pub fn threshold_db(&self) -> f32 {
+        self.threshold_db
+    }
+
+    /// Sets compression ratio. Ratio of 1.0 means no compression.
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.max(1.0);
+    }
+
+    /// Returns current compression ratio.
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Sets attack time in seconds.
+    pub fn set_attack_time(&mut self, attack_time: f32) {
+        self.attack_time = attack_time.max(0.0);
+        self.update();
+    }
+
+    /// Returns attack time in seconds.
+    pub fn attack_time(&self) -> f32 {
+        self.attack_time
+    }
+
+    /// Sets release time in seconds.
+    pub fn set_release_time(&mut self, release_time: f32) {
+        self.release_time = release_time.max(0.0);
+        self.update();
+    }
+
+    /// Returns release time in seconds.
+    pub fn release_time(&self) -> f32 {
+        self.release_time
+    }
+
+    /// Sets additional gain (in decibels) that is applied to the compressed signal. It is used to
+    /// compensate the volume loss caused by the compression.
+    pub fn set_makeup_gain_db(&mut self, makeup_gain_db: f32) {
+        self.makeup_gain_db = makeup_gain_db;
+    }
+
+    /// Returns current makeup gain in decibels.
+    pub fn makeup_gain_db(&self) -> f32 {
+        self.makeup_gain_db
+    }
+
+    fn update(&mut self) {
+        self.attack_coefficient = time_to_coefficient(self.attack_time);
+        self.release_coefficient = time_to_coefficient(self.release_time);
+    }
+}
+
+impl EffectRenderTrait for Compressor {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        for ((input_left, input_right), (output_left, output_right)) in input.iter().zip(output) {
+            let level_db = linear_to_db(input_left.abs().max(input_right.abs()));
+
+            let overshoot_db = (level_db - self.threshold_db).max(0.0);
+            let target_reduction_db = overshoot_db - overshoot_db / self.ratio;
+
+            let coefficient = if target_reduction_db > self.envelope_db {
+                self.attack_coefficient
+            } else {
+                self.release_coefficient
+            };
+            self.envelope_db =
+                target_reduction_db + coefficient * (self.envelope_db - target_reduction_db);
+
+            let gain = db_to_linear(self.makeup_gain_db - self.envelope_db);
+
+            *output_left = *input_left * gain;
+            *output_right = *input_right * gain;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::effects::{compressor::Compressor, EffectRenderTrait};
+
+    #[test]
+    fn test_quiet_signal_is_not_compressed() {
+        let mut compressor = Compressor::new();
+        let input = [(0.1, -0.1); 16];
+        let mut output = [(0.0, 0.0); 16];
+        compressor.render(&input, &mut output);
+        for (left, right) in output {
+            assert!((left - 0.1).abs() < 1.0e-5);
+            assert!((right + 0.1).abs() < 1.0e-5);
+        }
+    }
+
+    #[test]
+    fn test_loud_signal_is_compressed() {
+        let mut compressor = Compressor::new();
+        compressor.set_attack_time(0.0);
+        let input = [(1.0, 1.0); 16];
+        let mut output = [(0.0, 0.0); 16];
+        compressor.render(&input, &mut output);
+        // 0 dB input, -12 dB threshold, 4:1 ratio gives -9 dB output.
+        let expected = 10.0f32.powf(-9.0 / 20.0);
+        assert!((output[15].0 - expected).abs() < 1.0e-4);
+    }
+}
@@ -0,0 +1,245 @@
+//! Three-band equalizer effect. It could be used to shape the tone of a group of sounds, for example
+//! to cut low frequencies of a voice bus or to boost high frequencies of an ambience bus.
+
+use crate::{
+    context::SAMPLE_RATE,
+    dsp::filters::{Biquad, BiquadKind},
+    effects::EffectRenderTrait,
+};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+
+#[derive(Default, Debug, Clone, PartialEq, Visit)]
+struct ChannelEqualizer {
+    low: Biquad,
+    mid: Biquad,
+    high: Biquad,
+}
+
+impl ChannelEqualizer {
+    fn feed(&mut self, sample: f32) -> f32 {
+        self.high.feed(self.mid.feed(self.low.feed(sample)))
+    }
+}
+
+/// Three-band equalizer, that consists of low shelf, peak and high shelf filters connected in series.
+/// Each band has its own gain, which is a linear amplitude multiplier: values less than 1.0 cut
+/// the band and values greater than 1.0 boost it.
+#[derive(Debug, Clone, Reflect, PartialEq)]
+pub struct Equalizer {
+    #[reflect(
+        description = "Cutoff frequency of the low band in Hertz.",
+        setter = "set_low_frequency_hz",
+        min_value = 0.0
+    )]
+    low_frequency_hz: f32,
+
+    #[reflect(
+        description = "Gain of the low band.",
+        setter = "set_low_gain",
+        min_value = 0.0
+    )]
+    low_gain: f32,
+
+    #[reflect(
+        description = "Center frequency of the mid band in Hertz.",
+        setter = "set_mid_frequency_hz",
+        min_value = 0.0
+    )]
+    mid_frequency_hz: f32,
+
+    #[reflect(
+        description = "Gain of the mid band.",
+        setter = "set_mid_gain",
+        min_value = 0.0
+    )]
+    mid_gain: f32,
+
+    #[reflect(
+        description = "Band width of the mid band, the higher the value the narrower the band.",
+        setter = "set_mid_quality",
+        min_value = 0.0
+    )]
+    mid_quality: f32,
+
+    #[reflect(
+        description = "Cutoff frequency of the high band in Hertz.",
+        setter = "set_high_frequency_hz",
+        min_value = 0.0
+    )]
+    high_frequency_hz: f32,
+
+    #[reflect(
+        description = "Gain of the high band.",
+        setter = "set_high_gain",
+        min_value = 0.0
+    )]
+    high_gain: f32,
+
+    #[reflect(hidden)]
+    left: ChannelEqualizer,
+    #[reflect(hidden)]
+    right: ChannelEqualizer,
+}
+
+impl Visit for Equalizer {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.low_frequency_hz.visit("LowFrequency", &mut region)?;
+        self.low_gain.visit("LowGain", &mut region)?;
+        self.mid_frequency_hz.visit("MidFrequency", &mut region)?;
+        self.mid_gain.visit("MidGain", &mut region)?;
+        self.mid_quality.visit("MidQuality", &mut region)?;
+        self.high_frequency_hz.visit("HighFrequency", &mut region)?;
+        self.high_gain.visit("HighGain", &mut region)?;
+
+        if region.is_reading() {
+            self.update();
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Equalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Equalizer {
+    /// Quality of shelf filters, it gives the steepest slope without overshoot.
+    const SHELF_QUALITY: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+    /// Creates new equalizer with low band at 250 Hz, mid band at 1 kHz and high band at 4 kHz.
+    /// All bands have unit gain, which means that the equalizer does not change the signal.
+    pub fn new() -> Self {
+        let mut equalizer = Self {
+            low_frequency_hz: 250.0,
+            low_gain: 1.0,
+            mid_frequency_hz: 1000.0,
+            mid_gain: 1.0,
+            mid_quality: 0.7,
+            high_frequency_hz: 4000.0,
+            high_gain: 1.0,
+            left: Default::default(),
+            right: Default::default(),
+        };
+        equalizer.update();
+        equalizer
+    }
+
+    /// Sets cutoff frequency of the low band in Hertz.
+    pub fn set_low_frequency_hz(&mut self, frequency: f32) {
+        self.low_frequency_hz = frequency.max(0.0);
+        self.update();
+    }
+
+    /// Returns cutoff frequency of the low band in Hertz.
+    pub fn low_frequency_hz(&self) -> f32 {
+        self.low_frequency_hz
+    }
+
+    /// Sets gain of the low band.
+    pub fn set_low_gain(&mut self, gain: f32) {
+        self.low_gain = gain.max(0.0);
+        self.update();
+    }
+
+    /// Returns gain of the low band.
+    pub fn low_gain(&self) -> f32 {
+        self.low_gain
+    }
+
+    /// Sets center frequency of the mid band in Hertz.
+    pub fn set_mid_frequency_hz(&mut self, frequency: f32) {
+        self.mid_frequency_hz = frequency.max(0.0);
+        self.update();
+    }
+
+    /// Returns center frequency of the mid band in Hertz.
+    pub fn mid_frequency_hz(&self) -> f32 {
+        self.mid_frequency_hz
+    }
+
+    /// Sets gain of the mid band.
+    pub fn set_mid_gain(&mut self, gain: f32) {
+        self.mid_gain = gain.max(0.0);
+        self.update();
+    }
+
+    /// Returns gain of the mid band.
+    pub fn mid_gain(&self) -> f32 {
+        self.mid_gain
+    }
+
+    /// Sets quality of the mid band. See [`Biquad::tune`] docs for more info.
+    pub fn set_mid_quality(&mut self, quality: f32) {
+        self.mid_quality = quality.max(f32::EPSILON);
+        self.update();
+    }
+
+    /// Returns quality of the mid band.
+    pub fn mid_quality(&self) -> f32 {
+        self.mid_quality
+    }
+
+    /// Sets cutoff frequency of the high band in Hertz.
+    pub fn set_high_frequency_hz(&mut self, frequency: f32) {
+        self.high_frequency_hz = frequency.max(0.0);
+        self.update();
+    }
+
+    /// Returns cutoff frequency of the high band in Hertz.
+    pub fn high_frequency_hz(&self) -> f32 {
+        self.high_frequency_hz
+    }
+
+    /// Sets gain of the high band.
+    pub fn set_high_gain(&mut self, gain: f32) {
+        self.high_gain = gain.max(0.0);
+        self.update();
+    }
+
+    /// Returns gain of the high band.
+    pub fn high_gain(&self) -> f32 {
+        self.high_gain
+    }
+
+    fn update(&mut self) {
+        // Shelf and peak filters expect the amplitude in "square root" form.
+        let low_gain = self.low_gain.sqrt().max(f32::EPSILON);
+        let mid_gain = self.mid_gain.sqrt().max(f32::EPSILON);
+        let high_gain = self.high_gain.sqrt().max(f32::EPSILON);
+
+        for channel in [&mut self.left, &mut self.right] {
+            channel.low.tune(
+                BiquadKind::LowShelf,
+                self.low_frequency_hz / SAMPLE_RATE as f32,
+                low_gain,
+                Self::SHELF_QUALITY,
+            );
+            channel.mid.tune(
+                BiquadKind::Peak,
+                self.mid_frequency_hz / SAMPLE_RATE as f32,
+                mid_gain,
+                self.mid_quality,
+            );
+            channel.high.tune(
+                BiquadKind::HighShelf,
+                self.high_frequency_hz / SAMPLE_RATE as f32,
+                high_gain,
+                Self::SHELF_QUALITY,
+            );
+        }
+    }
+}
+
+impl EffectRenderTrait for Equalizer {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        for ((input_left, input_right), (output_left, output_right)) in input.iter().zip(output) {
+            *output_left = self.left.feed(*input_left);
+            *output_right = self.right.feed(*input_right);
+        }
+    }
+}
@@ -31,7 +31,8 @@
 use crate::{
     buffer::{streaming::StreamingBuffer, SoundBuffer, SoundBufferResource},
     bus::AudioBusGraph,
-    context::DistanceModel,
+    context::{DistanceModel, SAMPLE_RATE},
+    dsp::filters::OnePole,
     error::SoundError,
     listener::Listener,
 };
@@ -138,6 +139,15 @@ pub struct SoundSource {
     #[reflect(hidden)]
     #[visit(skip)]
     pub(crate) prev_distance_gain: Option<f32>,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    occlusion_gain: f32,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    occlusion_cutoff_frequency_hz: f32,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    occlusion_filters: (OnePole, OnePole),
 }
 
 impl Default for SoundSource {
@@ -168,6 +178,9 @@ impl Default for SoundSource {
             prev_right_samples: Default::default(),
             prev_sampling_vector: Vector3::new(0.0, 0.0, 1.0),
             prev_distance_gain: None,
+            occlusion_gain: 1.0,
+            occlusion_cutoff_frequency_hz: f32::MAX,
+            occlusion_filters: Default::default(),
         }
     }
 }
@@ -405,6 +418,48 @@ impl SoundSource {
         &self.bus
     }
 
+    /// Sets occlusion parameters of the source. Occlusion is used to simulate obstacles between the source
+    /// and the listener; the signal of the source will be multiplied by the given `gain` and then passed
+    /// through a lowpass filter with the given cutoff frequency (in Hertz). Use `f32::MAX` as the cutoff
+    /// frequency to disable the filter. These parameters are usually set by a higher-level code, that
+    /// knows about scene geometry.
+    pub fn set_occlusion(&mut self, gain: f32, cutoff_frequency_hz: f32) {
+        self.occlusion_gain = gain.max(0.0);
+        self.occlusion_cutoff_frequency_hz = cutoff_frequency_hz.max(0.0);
+        let fc = self.occlusion_cutoff_frequency_hz / SAMPLE_RATE as f32;
+        self.occlusion_filters.0.set_fc(fc);
+        self.occlusion_filters.1.set_fc(fc);
+    }
+
+    /// Returns occlusion gain of the source. See [`Self::set_occlusion`] docs for more info.
+    pub fn occlusion_gain(&self) -> f32 {
+        self.occlusion_gain
+    }
+
+    /// Returns cutoff frequency (in Hertz) of the occlusion lowpass filter. See [`Self::set_occlusion`]
+    /// docs for more info.
+    pub fn occlusion_cutoff_frequency_hz(&self) -> f32 {
+        self.occlusion_cutoff_frequency_hz
+    }
+
+    fn apply_occlusion(&mut self) {
+        // Filtering above Nyquist frequency makes no sense.
+        let filter = self.occlusion_cutoff_frequency_hz < (SAMPLE_RATE / 2) as f32;
+        if !filter && self.occlusion_gain == 1.0 {
+            return;
+        }
+
+        let (left_filter, right_filter) = &mut self.occlusion_filters;
+        for (left, right) in self.frame_samples.iter_mut() {
+            if filter {
+                *left = left_filter.feed(*left);
+                *right = right_filter.feed(*right);
+            }
+            *left *= self.occlusion_gain;
+            *right *= self.occlusion_gain;
+        }
+    }
+
     // Distance models were taken from OpenAL Specification because it looks like they're
     // standard in industry and there is no need to reinvent it.
     // https://www.openal.org/documentation/openal-1.1-specification.pdf
@@ -506,6 +561,8 @@ impl SoundSource {
         }
         // Fill the remaining part of frame_samples.
         self.frame_samples.resize(amount, (0.0, 0.0));
+
+        self.apply_occlusion();
     }
 
     fn render_playing(&mut self, buffer: &mut SoundBuffer, amount: usize) {
@@ -862,6 +919,7 @@ impl SoundSourceBuilder {
             prev_left_samples: Default::default(),
             prev_right_samples: Default::default(),
             bus: self.bus,
+            occlusion_filters: Default::default(),
             ..Default::default()
         };
 
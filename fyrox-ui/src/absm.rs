@@ -241,6 +241,9 @@ pub enum EventKind {
     TouchMoved,
     TouchCancelled,
     DoubleTap,
+    Tap,
+    Pan,
+    Pinch,
 }
 
 #[derive(Visit, Reflect, Clone, Debug, Default, PartialEq, TypeUuidProvider)]
@@ -300,6 +303,9 @@ impl Control for AbsmEventProvider {
             Msg::TouchMoved { .. } => self.on_event(ui, EventKind::TouchMoved),
             Msg::TouchCancelled { .. } => self.on_event(ui, EventKind::TouchCancelled),
             Msg::DoubleTap { .. } => self.on_event(ui, EventKind::DoubleTap),
+            Msg::Tap { .. } => self.on_event(ui, EventKind::Tap),
+            Msg::Pan { .. } => self.on_event(ui, EventKind::Pan),
+            Msg::Pinch { .. } => self.on_event(ui, EventKind::Pinch),
             Msg::KeyUp(key) => self.on_event(ui, EventKind::KeyUp(*key)),
             Msg::KeyDown(key) => self.on_event(ui, EventKind::KeyDown(*key)),
             _ => (),
@@ -251,3 +251,72 @@ where
         ctx.add_node(UiNode::new(canvas))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        bit::{BitField, BitFieldBuilder, BitFieldMessage},
+        check_box::CheckBox,
+        core::algebra::Vector2,
+        testing::UiTestHarness,
+        widget::WidgetBuilder,
+        MouseButton,
+    };
+    use fyrox_graph::BaseSceneGraph;
+
+    fn is_checked(
+        harness: &UiTestHarness,
+        switch: crate::core::pool::Handle<crate::UiNode>,
+    ) -> bool {
+        *harness.ui.node(switch).cast::<CheckBox>().unwrap().checked == Some(true)
+    }
+
+    #[test]
+    fn test_bit_field_interaction() {
+        let mut harness = UiTestHarness::new(Vector2::new(500.0, 500.0));
+        let bit_field = BitFieldBuilder::<u8>::new(WidgetBuilder::new())
+            .with_value(0b0001)
+            .build(&mut harness.build_ctx());
+        harness.update();
+
+        let switches = harness
+            .ui
+            .node(bit_field)
+            .cast::<BitField<u8>>()
+            .unwrap()
+            .bit_switches
+            .clone();
+        assert_eq!(switches.len(), 8);
+
+        // Left click toggles a single bit.
+        harness.click_widget(switches[2]);
+        assert_eq!(
+            harness.find_message::<BitFieldMessage<u8>>(bit_field),
+            Some(&BitFieldMessage::Value(0b0101))
+        );
+        harness.click_widget(switches[0]);
+        assert_eq!(
+            harness.find_message::<BitFieldMessage<u8>>(bit_field),
+            Some(&BitFieldMessage::Value(0b0100))
+        );
+
+        // Right click leaves only the clicked bit.
+        harness.clear_messages();
+        harness.click_widget_with(switches[3], MouseButton::Right);
+        assert_eq!(
+            harness.find_message::<BitFieldMessage<u8>>(bit_field),
+            Some(&BitFieldMessage::Value(0b1000))
+        );
+        assert!(is_checked(&harness, switches[3]));
+        assert!(!is_checked(&harness, switches[2]));
+
+        // Right click on the only set bit inverts the selection.
+        harness.click_widget_with(switches[3], MouseButton::Right);
+        assert_eq!(
+            harness.find_message::<BitFieldMessage<u8>>(bit_field),
+            Some(&BitFieldMessage::Value(!0b1000))
+        );
+        assert!(!is_checked(&harness, switches[3]));
+        assert!(is_checked(&harness, switches[7]));
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
     decorator::DecoratorBuilder,
     define_constructor,
     font::FontResource,
-    message::{KeyCode, MessageDirection, UiMessage},
+    message::{GamepadButton, KeyCode, MessageDirection, UiMessage},
     text::TextBuilder,
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, Thickness, UiNode, UserInterface,
@@ -167,6 +167,15 @@ impl Control for Button {
                             message.set_handled(true);
                         }
                     }
+                    WidgetMessage::GamepadButtonDown(GamepadButton::South)
+                        if !message.handled() =>
+                    {
+                        ui.send_message(ButtonMessage::click(
+                            self.handle,
+                            MessageDirection::FromWidget,
+                        ));
+                        message.set_handled(true);
+                    }
                     _ => (),
                 }
             }
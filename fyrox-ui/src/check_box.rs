@@ -13,7 +13,7 @@ use crate::{
     },
     define_constructor,
     grid::{Column, GridBuilder, Row},
-    message::{KeyCode, MessageDirection, UiMessage},
+    message::{GamepadButton, KeyCode, MessageDirection, UiMessage},
     vector_image::{Primitive, VectorImageBuilder},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, MouseButton, Thickness, UiNode, UserInterface,
@@ -192,6 +192,14 @@ impl Control for CheckBox {
                         message.set_handled(true);
                     }
                 }
+                WidgetMessage::GamepadButtonDown(GamepadButton::South) if !message.handled() => {
+                    ui.send_message(CheckBoxMessage::checked(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        self.checked.map(|checked| !checked),
+                    ));
+                    message.set_handled(true);
+                }
                 _ => (),
             }
         } else if let Some(&CheckBoxMessage::Check(value)) = message.data::<CheckBoxMessage>() {
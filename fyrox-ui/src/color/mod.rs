@@ -1,9 +1,10 @@
 use crate::{
     border::BorderBuilder,
     brush::Brush,
+    button::{ButtonBuilder, ButtonMessage},
     core::{
         algebra::Vector2,
-        color::{Color, Hsv},
+        color::{Color, ColorSpace, Hsv},
         math::Rect,
         pool::Handle,
         reflect::prelude::*,
@@ -15,13 +16,16 @@ use crate::{
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, MouseButton, UiMessage},
     numeric::{NumericUpDownBuilder, NumericUpDownMessage},
-    popup::{Placement, PopupBuilder, PopupMessage},
-    text::TextBuilder,
+    popup::{Placement, Popup, PopupBuilder, PopupMessage},
+    stack_panel::StackPanelBuilder,
+    text::{TextBuilder, TextMessage},
     widget::{Widget, WidgetBuilder, WidgetMessage},
+    wrap_panel::WrapPanelBuilder,
     BuildContext, Control, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
 };
 use fyrox_core::uuid_provider;
 use fyrox_graph::BaseSceneGraph;
+use serde::{Deserialize, Serialize};
 use std::{
     ops::{Deref, DerefMut},
     sync::mpsc::Sender,
@@ -75,22 +79,75 @@ impl SaturationBrightnessFieldMessage {
     define_constructor!(SaturationBrightnessFieldMessage:Brightness => fn brightness(f32), layout: false);
 }
 
+/// A named set of colors, that is shown in a color picker. Colors of a palette are always in sRGB
+/// space.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ColorPalette {
+    /// Name of the palette.
+    pub name: String,
+    /// Colors of the palette.
+    pub colors: Vec<Color>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ColorPickerMessage {
-    /// Sets color in RGB.
+    /// Sets color in RGB. The color is in the color space of the picker.
     ///
     /// Direction: **To/From Widget**.
     Color(Color),
 
-    /// Sets color in HSV.
+    /// Sets color in HSV. HSV values are always in sRGB space.
     ///
     /// Direction: **To Widget**.
     Hsv(Hsv),
+
+    /// Sets color space of the color of the picker. The picker shows the color correctly on screen
+    /// regardless of its color space, RGB fields show the values in the color space of the picker.
+    ///
+    /// Direction: **To/From Widget**.
+    ColorSpace(ColorSpace),
+
+    /// Sets a color, that was sampled from the screen (for example, by an eyedropper) or taken from
+    /// a palette. The color must be in sRGB space, the picker converts it to its own color space.
+    /// Alpha of the current color is preserved.
+    ///
+    /// Direction: **To Widget**.
+    SampledColor(Color),
+
+    /// Emitted when a user clicks the eyedropper button. The picker cannot sample the screen on its
+    /// own, so the application should handle this message and send [`Self::SampledColor`] back.
+    ///
+    /// Direction: **From Widget**.
+    EyedropperRequested,
+
+    /// Sets palettes, that will be shown in the picker.
+    ///
+    /// Direction: **To Widget**.
+    Palettes(Vec<ColorPalette>),
+
+    /// Emitted when a user adds the current color (in sRGB space) to a palette. The picker does not
+    /// modify its palettes, the application should update them and send [`Self::Palettes`] back.
+    ///
+    /// Direction: **From Widget**.
+    AddToPalette { palette: usize, color: Color },
+
+    /// Emitted when a user removes a color from a palette (by right click on a swatch). The picker
+    /// does not modify its palettes, the application should update them and send [`Self::Palettes`]
+    /// back.
+    ///
+    /// Direction: **From Widget**.
+    RemoveFromPalette { palette: usize, index: usize },
 }
 
 impl ColorPickerMessage {
     define_constructor!(ColorPickerMessage:Color => fn color(Color), layout: false);
     define_constructor!(ColorPickerMessage:Hsv => fn hsv(Hsv), layout: false);
+    define_constructor!(ColorPickerMessage:ColorSpace => fn color_space(ColorSpace), layout: false);
+    define_constructor!(ColorPickerMessage:SampledColor => fn sampled_color(Color), layout: false);
+    define_constructor!(ColorPickerMessage:EyedropperRequested => fn eyedropper_requested(), layout: false);
+    define_constructor!(ColorPickerMessage:Palettes => fn palettes(Vec<ColorPalette>), layout: false);
+    define_constructor!(ColorPickerMessage:AddToPalette => fn add_to_palette(palette: usize, color: Color), layout: false);
+    define_constructor!(ColorPickerMessage:RemoveFromPalette => fn remove_from_palette(palette: usize, index: usize), layout: false);
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -732,16 +789,137 @@ pub struct ColorPicker {
     pub color_mark: Handle<UiNode>,
     pub color: Color,
     pub hsv: Hsv,
+    #[visit(optional)]
+    pub color_space: ColorSpace,
+    #[visit(optional)]
+    pub color_space_text: Handle<UiNode>,
+    #[visit(optional)]
+    pub eyedropper: Handle<UiNode>,
+    #[visit(optional)]
+    pub palettes_panel: Handle<UiNode>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    palettes: Vec<ColorPalette>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    swatches: Vec<PaletteSwatch>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    add_to_palette_buttons: Vec<Handle<UiNode>>,
+}
+
+/// A widget, that shows a color of a palette.
+#[derive(Default, Clone, Debug, PartialEq)]
+struct PaletteSwatch {
+    handle: Handle<UiNode>,
+    palette: usize,
+    index: usize,
+    color: Color,
 }
 
 crate::define_widget_deref!(ColorPicker);
 
+fn color_space_name(color_space: ColorSpace) -> &'static str {
+    match color_space {
+        ColorSpace::Srgb => "sRGB",
+        ColorSpace::Linear => "Linear",
+    }
+}
+
 fn mark_handled(message: UiMessage) -> UiMessage {
     message.set_handled(true);
     message
 }
 
 impl ColorPicker {
+    /// Returns the color of the picker in sRGB space, that is suitable to be shown on screen.
+    pub fn display_color(&self) -> Color {
+        self.color
+            .convert_color_space(self.color_space, ColorSpace::Srgb)
+    }
+
+    fn rebuild_palettes(&mut self, ui: &mut UserInterface) {
+        for &child in ui.node(self.palettes_panel).children() {
+            ui.send_message(WidgetMessage::remove(child, MessageDirection::ToWidget));
+        }
+
+        self.swatches.clear();
+        self.add_to_palette_buttons.clear();
+
+        let ctx = &mut ui.build_ctx();
+        let mut rows = Vec::new();
+        for (palette_index, palette) in self.palettes.iter().enumerate() {
+            let mut swatches = Vec::new();
+            for (index, color) in palette.colors.iter().enumerate() {
+                let handle = BorderBuilder::new(
+                    WidgetBuilder::new()
+                        .with_width(16.0)
+                        .with_height(16.0)
+                        .with_margin(Thickness::uniform(1.0))
+                        .with_background(Brush::Solid(*color))
+                        .with_foreground(Brush::Solid(Color::opaque(90, 90, 90))),
+                )
+                .with_stroke_thickness(Thickness::uniform(1.0))
+                .build(ctx);
+                swatches.push(handle);
+                self.swatches.push(PaletteSwatch {
+                    handle,
+                    palette: palette_index,
+                    index,
+                    color: *color,
+                });
+            }
+
+            let add = ButtonBuilder::new(
+                WidgetBuilder::new()
+                    .with_width(18.0)
+                    .with_height(18.0)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .with_text("+")
+            .build(ctx);
+            swatches.push(add);
+            self.add_to_palette_buttons.push(add);
+
+            rows.push(
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_text(&palette.name)
+                            .build(ctx),
+                        )
+                        .with_child(
+                            WrapPanelBuilder::new(WidgetBuilder::new().with_children(swatches))
+                                .with_orientation(Orientation::Horizontal)
+                                .build(ctx),
+                        ),
+                )
+                .build(ctx),
+            );
+        }
+
+        for row in rows {
+            ui.send_message(WidgetMessage::link(
+                row,
+                MessageDirection::ToWidget,
+                self.palettes_panel,
+            ));
+        }
+    }
+
+    fn set_sampled_color(&self, ui: &UserInterface, color: Color) {
+        ui.send_message(ColorPickerMessage::color(
+            self.handle,
+            MessageDirection::ToWidget,
+            color
+                .convert_color_space(ColorSpace::Srgb, self.color_space)
+                .with_new_alpha(self.color.a),
+        ));
+    }
+
     fn sync_fields(&self, ui: &mut UserInterface, color: Color, hsv: Hsv) {
         ui.send_message(mark_handled(NumericUpDownMessage::value(
             self.hue,
@@ -788,7 +966,7 @@ impl ColorPicker {
         ui.send_message(mark_handled(WidgetMessage::background(
             self.color_mark,
             MessageDirection::ToWidget,
-            Brush::Solid(color),
+            Brush::Solid(self.display_color()),
         )));
     }
 }
@@ -901,6 +1079,42 @@ impl Control for ColorPicker {
                     ));
                 }
             }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.eyedropper {
+                ui.send_message(ColorPickerMessage::eyedropper_requested(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                ));
+            } else if let Some(palette) = self
+                .add_to_palette_buttons
+                .iter()
+                .position(|b| *b == message.destination())
+            {
+                ui.send_message(ColorPickerMessage::add_to_palette(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                    palette,
+                    self.display_color(),
+                ));
+            }
+        } else if let Some(&WidgetMessage::MouseDown { button, .. }) = message.data() {
+            if let Some(swatch) = self
+                .swatches
+                .iter()
+                .find(|s| s.handle == message.destination())
+            {
+                match button {
+                    MouseButton::Left => self.set_sampled_color(ui, swatch.color),
+                    MouseButton::Right => ui.send_message(ColorPickerMessage::remove_from_palette(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        swatch.palette,
+                        swatch.index,
+                    )),
+                    _ => (),
+                }
+                message.set_handled(true);
+            }
         } else if let Some(msg) = message.data::<ColorPickerMessage>() {
             if message.destination() == self.handle
                 && message.direction() == MessageDirection::ToWidget
@@ -909,7 +1123,7 @@ impl Control for ColorPicker {
                     ColorPickerMessage::Color(color) => {
                         if self.color != color {
                             self.color = color;
-                            self.hsv = Hsv::from(color);
+                            self.hsv = Hsv::from(self.display_color());
 
                             self.sync_fields(ui, color, self.hsv);
 
@@ -919,7 +1133,8 @@ impl Control for ColorPicker {
                     ColorPickerMessage::Hsv(hsv) => {
                         if self.hsv != hsv {
                             self.hsv = hsv;
-                            let opaque = Color::from(hsv);
+                            let opaque = Color::from(hsv)
+                                .convert_color_space(ColorSpace::Srgb, self.color_space);
                             self.color =
                                 Color::from_rgba(opaque.r, opaque.g, opaque.b, self.color.a);
 
@@ -928,6 +1143,33 @@ impl Control for ColorPicker {
                             ui.send_message(message.reverse());
                         }
                     }
+                    ColorPickerMessage::ColorSpace(color_space) => {
+                        if self.color_space != color_space {
+                            self.color_space = color_space;
+                            self.hsv = Hsv::from(self.display_color());
+
+                            self.sync_fields(ui, self.color, self.hsv);
+                            ui.send_message(TextMessage::text(
+                                self.color_space_text,
+                                MessageDirection::ToWidget,
+                                color_space_name(color_space).to_string(),
+                            ));
+
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    ColorPickerMessage::SampledColor(color) => {
+                        self.set_sampled_color(ui, color);
+                    }
+                    ColorPickerMessage::Palettes(ref palettes) => {
+                        if &self.palettes != palettes {
+                            self.palettes.clone_from(palettes);
+                            self.rebuild_palettes(ui);
+                        }
+                    }
+                    ColorPickerMessage::EyedropperRequested
+                    | ColorPickerMessage::AddToPalette { .. }
+                    | ColorPickerMessage::RemoveFromPalette { .. } => (),
                 }
             }
         }
@@ -937,6 +1179,8 @@ impl Control for ColorPicker {
 pub struct ColorPickerBuilder {
     widget_builder: WidgetBuilder,
     color: Color,
+    color_space: ColorSpace,
+    palettes: Vec<ColorPalette>,
 }
 
 fn make_text_mark(ctx: &mut BuildContext, text: &str, row: usize, column: usize) -> Handle<UiNode> {
@@ -976,6 +1220,8 @@ impl ColorPickerBuilder {
         Self {
             widget_builder,
             color: Color::WHITE,
+            color_space: ColorSpace::Srgb,
+            palettes: Default::default(),
         }
     }
 
@@ -984,6 +1230,19 @@ impl ColorPickerBuilder {
         self
     }
 
+    /// Sets color space of the color of the picker. See [`ColorPickerMessage::ColorSpace`] for
+    /// more info.
+    pub fn with_color_space(mut self, color_space: ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+
+    /// Sets palettes, that will be shown in the picker.
+    pub fn with_palettes(mut self, palettes: Vec<ColorPalette>) -> Self {
+        self.palettes = palettes;
+        self
+    }
+
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let hue_bar;
         let alpha_bar;
@@ -996,7 +1255,13 @@ impl ColorPickerBuilder {
         let brightness;
         let color_mark;
         let alpha;
-        let hsv = Hsv::from(self.color);
+        let color_space_text;
+        let eyedropper;
+        let palettes_panel;
+        let display_color = self
+            .color
+            .convert_color_space(self.color_space, ColorSpace::Srgb);
+        let hsv = Hsv::from(display_color);
 
         let numerics_grid = GridBuilder::new(
             WidgetBuilder::new()
@@ -1035,6 +1300,18 @@ impl ColorPickerBuilder {
                 .with_child({
                     alpha = make_input_field(ctx, self.color.a as f32, 255.0, 3, 1);
                     alpha
+                })
+                .with_child({
+                    color_space_text = TextBuilder::new(
+                        WidgetBuilder::new()
+                            .with_vertical_alignment(VerticalAlignment::Center)
+                            .with_margin(Thickness::left(2.0))
+                            .on_row(3)
+                            .on_column(3),
+                    )
+                    .with_text(color_space_name(self.color_space))
+                    .build(ctx);
+                    color_space_text
                 }),
         )
         .add_column(Column::strict(10.0))
@@ -1048,66 +1325,89 @@ impl ColorPickerBuilder {
         .add_row(Row::stretch())
         .build(ctx);
 
+        let tools = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_child({
+                    eyedropper = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_height(22.0)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .with_text("Eyedropper")
+                    .build(ctx);
+                    eyedropper
+                })
+                .with_child({
+                    palettes_panel = StackPanelBuilder::new(WidgetBuilder::new()).build(ctx);
+                    palettes_panel
+                }),
+        )
+        .build(ctx);
+
+        let colors = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child({
+                    saturation_brightness_field = SaturationBrightnessFieldBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .on_column(0),
+                    )
+                    .build(ctx);
+                    saturation_brightness_field
+                })
+                .with_child({
+                    hue_bar = HueBarBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .on_column(1),
+                    )
+                    .build(ctx);
+                    hue_bar
+                })
+                .with_child({
+                    alpha_bar = AlphaBarBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .on_column(2),
+                    )
+                    .with_alpha(self.color.a as f32)
+                    .build(ctx);
+                    alpha_bar
+                })
+                .with_child(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(3)
+                            .with_child({
+                                color_mark = BorderBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_row(0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_background(Brush::Solid(display_color)),
+                                )
+                                .build(ctx);
+                                color_mark
+                            })
+                            .with_child(numerics_grid),
+                    )
+                    .add_row(Row::strict(25.0))
+                    .add_row(Row::stretch())
+                    .add_column(Column::stretch())
+                    .build(ctx),
+                ),
+        )
+        .add_column(Column::stretch())
+        .add_column(Column::strict(20.0))
+        .add_column(Column::strict(20.0))
+        .add_column(Column::strict(100.0))
+        .add_row(Row::auto())
+        .build(ctx);
+
         let widget = self
             .widget_builder
             .with_child(
-                GridBuilder::new(
-                    WidgetBuilder::new()
-                        .with_child({
-                            saturation_brightness_field = SaturationBrightnessFieldBuilder::new(
-                                WidgetBuilder::new()
-                                    .with_margin(Thickness::uniform(1.0))
-                                    .on_column(0),
-                            )
-                            .build(ctx);
-                            saturation_brightness_field
-                        })
-                        .with_child({
-                            hue_bar = HueBarBuilder::new(
-                                WidgetBuilder::new()
-                                    .with_margin(Thickness::uniform(1.0))
-                                    .on_column(1),
-                            )
-                            .build(ctx);
-                            hue_bar
-                        })
-                        .with_child({
-                            alpha_bar = AlphaBarBuilder::new(
-                                WidgetBuilder::new()
-                                    .with_margin(Thickness::uniform(1.0))
-                                    .on_column(2),
-                            )
-                            .with_alpha(self.color.a as f32)
-                            .build(ctx);
-                            alpha_bar
-                        })
-                        .with_child(
-                            GridBuilder::new(
-                                WidgetBuilder::new()
-                                    .on_column(3)
-                                    .with_child({
-                                        color_mark = BorderBuilder::new(
-                                            WidgetBuilder::new()
-                                                .on_row(0)
-                                                .with_margin(Thickness::uniform(1.0)),
-                                        )
-                                        .build(ctx);
-                                        color_mark
-                                    })
-                                    .with_child(numerics_grid),
-                            )
-                            .add_row(Row::strict(25.0))
-                            .add_row(Row::stretch())
-                            .add_column(Column::stretch())
-                            .build(ctx),
-                        ),
-                )
-                .add_column(Column::stretch())
-                .add_column(Column::strict(20.0))
-                .add_column(Column::strict(20.0))
-                .add_column(Column::strict(100.0))
-                .add_row(Row::auto())
-                .build(ctx),
+                StackPanelBuilder::new(WidgetBuilder::new().with_child(colors).with_child(tools))
+                    .build(ctx),
             )
             .build();
 
@@ -1126,8 +1426,23 @@ impl ColorPickerBuilder {
             hsv,
             alpha_bar,
             alpha,
+            color_space: self.color_space,
+            color_space_text,
+            eyedropper,
+            palettes_panel,
+            palettes: Default::default(),
+            swatches: Default::default(),
+            add_to_palette_buttons: Default::default(),
         };
-        ctx.add_node(UiNode::new(picker))
+        let handle = ctx.add_node(UiNode::new(picker));
+        if !self.palettes.is_empty() {
+            ctx.inner().send_message(ColorPickerMessage::palettes(
+                handle,
+                MessageDirection::ToWidget,
+                self.palettes,
+            ));
+        }
+        handle
     }
 }
 
@@ -1137,6 +1452,8 @@ pub struct ColorField {
     pub popup: Handle<UiNode>,
     pub picker: Handle<UiNode>,
     pub color: Color,
+    #[visit(optional)]
+    pub color_space: ColorSpace,
 }
 
 crate::define_widget_deref!(ColorField);
@@ -1161,7 +1478,10 @@ impl Control for ColorField {
         drawing_context.push_rect_filled(&bounds, None);
         drawing_context.commit(
             self.clip_bounds(),
-            Brush::Solid(self.color),
+            Brush::Solid(
+                self.color
+                    .convert_color_space(self.color_space, ColorSpace::Srgb),
+            ),
             CommandTexture::None,
             None,
         );
@@ -1225,6 +1545,22 @@ impl Control for ColorField {
                     picker.color,
                 ));
             }
+        } else if let Some(&ColorPickerMessage::Color(color)) = message.data() {
+            // The color of the picker could be changed while the popup is closed (for example, by
+            // an eyedropper), commit such changes immediately.
+            if message.destination() == self.picker
+                && message.direction() == MessageDirection::FromWidget
+                && ui
+                    .try_get(self.popup)
+                    .and_then(|p| p.query_component::<Popup>())
+                    .is_some_and(|p| !*p.is_open)
+            {
+                ui.send_message(ColorFieldMessage::color(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    color,
+                ));
+            }
         }
     }
 }
@@ -1232,6 +1568,7 @@ impl Control for ColorField {
 pub struct ColorFieldBuilder {
     widget_builder: WidgetBuilder,
     color: Color,
+    color_space: ColorSpace,
 }
 
 impl ColorFieldBuilder {
@@ -1239,6 +1576,7 @@ impl ColorFieldBuilder {
         Self {
             widget_builder,
             color: Color::WHITE,
+            color_space: ColorSpace::Srgb,
         }
     }
 
@@ -1247,12 +1585,20 @@ impl ColorFieldBuilder {
         self
     }
 
+    /// Sets color space of the color of the field. See [`ColorPickerMessage::ColorSpace`] for
+    /// more info.
+    pub fn with_color_space(mut self, color_space: ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let picker;
         let popup = PopupBuilder::new(WidgetBuilder::new())
             .with_content({
                 picker = ColorPickerBuilder::new(WidgetBuilder::new())
                     .with_color(self.color)
+                    .with_color_space(self.color_space)
                     .build(ctx);
                 picker
             })
@@ -1263,7 +1609,69 @@ impl ColorFieldBuilder {
             popup,
             picker,
             color: self.color,
+            color_space: self.color_space,
         };
         ctx.add_node(UiNode::new(field))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        color::{ColorPalette, ColorPicker, ColorPickerBuilder, ColorPickerMessage},
+        core::{algebra::Vector2, color::Color},
+        testing::UiTestHarness,
+        widget::WidgetBuilder,
+        MouseButton,
+    };
+    use fyrox_graph::BaseSceneGraph;
+
+    #[test]
+    fn test_palette_interaction() {
+        let mut harness = UiTestHarness::new(Vector2::new(1000.0, 1000.0));
+        let picker = ColorPickerBuilder::new(WidgetBuilder::new())
+            .with_color(Color::opaque(0, 0, 255))
+            .with_palettes(vec![ColorPalette {
+                name: "Test".to_string(),
+                colors: vec![Color::opaque(255, 0, 0), Color::opaque(0, 255, 0)],
+            }])
+            .build(&mut harness.build_ctx());
+        harness.update();
+
+        let (swatches, add) = {
+            let picker = harness.ui.node(picker).cast::<ColorPicker>().unwrap();
+            (
+                picker.swatches.iter().map(|s| s.handle).collect::<Vec<_>>(),
+                picker.add_to_palette_buttons[0],
+            )
+        };
+        assert_eq!(swatches.len(), 2);
+
+        // Left click on a swatch picks its color.
+        harness.click_widget(swatches[1]);
+        assert_eq!(
+            harness.find_message::<ColorPickerMessage>(picker),
+            Some(&ColorPickerMessage::Color(Color::opaque(0, 255, 0)))
+        );
+
+        // Right click asks to remove the color from the palette.
+        harness.click_widget_with(swatches[0], MouseButton::Right);
+        assert_eq!(
+            harness.find_message::<ColorPickerMessage>(picker),
+            Some(&ColorPickerMessage::RemoveFromPalette {
+                palette: 0,
+                index: 0
+            })
+        );
+
+        // "+" button asks to add the current color.
+        harness.click_widget(add);
+        assert_eq!(
+            harness.find_message::<ColorPickerMessage>(picker),
+            Some(&ColorPickerMessage::AddToPalette {
+                palette: 0,
+                color: Color::opaque(0, 255, 0)
+            })
+        );
+    }
+}
@@ -54,6 +54,8 @@ pub enum CurveEditorMessage {
         after_layout: bool,
     },
     HighlightZones(Vec<HighlightZone>),
+    /// Sets a waveform that is drawn behind the curves, `None` removes current waveform.
+    Waveform(Option<Waveform>),
 
     // Internal messages. Use only when you know what you're doing.
     // These are internal because you must use Sync message to request changes
@@ -74,6 +76,7 @@ impl CurveEditorMessage {
     define_constructor!(CurveEditorMessage:Zoom => fn zoom(Vector2<f32>), layout: false);
     define_constructor!(CurveEditorMessage:ZoomToFit => fn zoom_to_fit(after_layout: bool), layout: true);
     define_constructor!(CurveEditorMessage:HighlightZones => fn hightlight_zones(Vec<HighlightZone>), layout: false);
+    define_constructor!(CurveEditorMessage:Waveform => fn waveform(Option<Waveform>), layout: false);
     // Internal. Use only when you know what you're doing.
     define_constructor!(CurveEditorMessage:RemoveSelection => fn remove_selection(), layout: false);
     define_constructor!(CurveEditorMessage:ChangeSelectedKeysKind => fn change_selected_keys_kind(CurveKeyKind), layout: false);
@@ -89,6 +92,19 @@ pub struct HighlightZone {
     pub brush: Brush,
 }
 
+/// Waveform of an audio signal, that is drawn behind the curves. It is laid out along X axis in values
+/// space and always takes the full height of the editor along Y axis.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Waveform {
+    /// Location of the first peak along X axis in values space.
+    pub start: f32,
+    /// Distance between two adjacent peaks along X axis in values space.
+    pub step: f32,
+    /// Peak amplitudes of the signal in `[0; 1]` range.
+    pub peaks: Vec<f32>,
+    pub brush: Brush,
+}
+
 #[derive(Debug, Default)]
 pub struct CurveTransformCell(Mutex<CurveTransform>);
 
@@ -427,6 +443,9 @@ pub struct CurveEditor {
     #[visit(skip)]
     #[reflect(hidden)]
     zoom_to_fit_timer: Option<usize>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    waveform: Option<Waveform>,
 }
 
 crate::define_widget_deref!(CurveEditor);
@@ -515,6 +534,7 @@ impl Control for CurveEditor {
         self.curve_transform.update_transform();
         self.draw_background(ctx);
         self.draw_highlight_zones(ctx);
+        self.draw_waveform(ctx);
         self.draw_grid(ctx);
         self.draw_curves(&self.background_curves, ctx);
         self.draw_curves(&self.curves, ctx);
@@ -809,6 +829,15 @@ impl Control for CurveEditor {
 
                         message.set_handled(true);
                     }
+                    WidgetMessage::Pinch { scale, .. } => {
+                        ui.send_message(CurveEditorMessage::zoom(
+                            self.handle,
+                            MessageDirection::ToWidget,
+                            self.curve_transform.scale() * *scale,
+                        ));
+
+                        message.set_handled(true);
+                    }
                     _ => {}
                 }
             } else if let Some(msg) = message.data::<CurveEditorMessage>() {
@@ -902,6 +931,9 @@ impl Control for CurveEditor {
                         CurveEditorMessage::HighlightZones(zones) => {
                             self.highlight_zones.clone_from(zones);
                         }
+                        CurveEditorMessage::Waveform(waveform) => {
+                            self.waveform.clone_from(waveform);
+                        }
                     }
                 }
             }
@@ -1321,6 +1353,34 @@ impl CurveEditor {
         }
     }
 
+    fn draw_waveform(&self, ctx: &mut DrawingContext) {
+        let Some(waveform) = self.waveform.as_ref() else {
+            return;
+        };
+
+        let screen_bounds = self.screen_bounds();
+        let center_y = screen_bounds.y() + screen_bounds.h() * 0.5;
+        let width = (waveform.step * self.curve_transform.scale().x).max(1.0);
+
+        for (i, peak) in waveform.peaks.iter().enumerate() {
+            let location = waveform.start + i as f32 * waveform.step;
+            let x = self.point_to_screen_space(Vector2::new(location, 0.0)).x;
+            if x + width < screen_bounds.x() || x > screen_bounds.x() + screen_bounds.w() {
+                continue;
+            }
+
+            let height = peak.clamp(0.0, 1.0) * screen_bounds.h();
+            ctx.push_rect_filled(&Rect::new(x, center_y - height * 0.5, width, height), None);
+        }
+
+        ctx.commit(
+            self.clip_bounds(),
+            waveform.brush.clone(),
+            CommandTexture::None,
+            None,
+        );
+    }
+
     fn draw_grid(&self, ctx: &mut DrawingContext) {
         let screen_bounds = self.screen_bounds();
 
@@ -1869,6 +1929,7 @@ impl CurveEditorBuilder {
             min_zoom: self.min_zoom,
             max_zoom: self.max_zoom,
             highlight_zones: self.highlight_zones,
+            waveform: None,
             zoom_to_fit_timer: None,
         };
 
@@ -31,6 +31,11 @@ pub use tile::*;
 #[derive(Debug, Clone, PartialEq)]
 pub enum DockingManagerMessage {
     Layout(DockingManagerLayoutDescriptor),
+    /// Registers a window as a floating window of the docking manager. Placement of such windows is
+    /// saved in the layout of the docking manager. The window must have a unique name.
+    AddFloatingWindow(Handle<UiNode>),
+    /// Removes a window from the list of floating windows of the docking manager.
+    RemoveFloatingWindow(Handle<UiNode>),
 }
 
 impl DockingManagerMessage {
@@ -38,6 +43,14 @@ impl DockingManagerMessage {
         /// Creates a new [Self::Layout] message.
         DockingManagerMessage:Layout => fn layout(DockingManagerLayoutDescriptor), layout: false
     );
+    define_constructor!(
+        /// Creates a new [Self::AddFloatingWindow] message.
+        DockingManagerMessage:AddFloatingWindow => fn add_floating_window(Handle<UiNode>), layout: false
+    );
+    define_constructor!(
+        /// Creates a new [Self::RemoveFloatingWindow] message.
+        DockingManagerMessage:RemoveFloatingWindow => fn remove_floating_window(Handle<UiNode>), layout: false
+    );
 }
 
 #[derive(Default, Clone, Visit, Reflect, Debug, ComponentProvider)]
@@ -56,88 +69,99 @@ impl Control for DockingManager {
 
         if message.destination() == self.handle && message.direction() == MessageDirection::ToWidget
         {
-            if let Some(DockingManagerMessage::Layout(layout_descriptor)) = message.data() {
-                if let Some(root_tile_handle) = self.children.first().cloned() {
-                    let mut windows = Vec::new();
-                    let mut stack = vec![root_tile_handle];
-                    while let Some(tile_handle) = stack.pop() {
-                        if let Some(tile) = ui
-                            .try_get(tile_handle)
-                            .and_then(|n| n.query_component::<Tile>())
-                        {
-                            match tile.content {
-                                TileContent::Window(window) => {
-                                    if ui.try_get(window).is_some() {
+            match message.data() {
+                Some(DockingManagerMessage::Layout(layout_descriptor)) => {
+                    if let Some(root_tile_handle) = self.children.first().cloned() {
+                        let mut windows = Vec::new();
+                        let mut stack = vec![root_tile_handle];
+                        while let Some(tile_handle) = stack.pop() {
+                            if let Some(tile) = ui
+                                .try_get(tile_handle)
+                                .and_then(|n| n.query_component::<Tile>())
+                            {
+                                match tile.content {
+                                    TileContent::Window(window) if ui.try_get(window).is_some() => {
                                         windows.push(window);
                                     }
+                                    TileContent::VerticalTiles { tiles, .. }
+                                    | TileContent::HorizontalTiles { tiles, .. } => {
+                                        stack.extend_from_slice(&tiles);
+                                    }
+                                    _ => (),
                                 }
-                                TileContent::VerticalTiles { tiles, .. }
-                                | TileContent::HorizontalTiles { tiles, .. } => {
-                                    stack.extend_from_slice(&tiles);
-                                }
-                                _ => (),
                             }
                         }
-                    }
 
-                    // Destroy the root tile with all descendant tiles.
-                    ui.send_message(WidgetMessage::remove(
-                        root_tile_handle,
-                        MessageDirection::ToWidget,
-                    ));
-
-                    // Re-create the tiles according to the layout and attach it to the docking manager.
-                    if let Some(root_tile_descriptor) =
-                        layout_descriptor.root_tile_descriptor.as_ref()
-                    {
-                        let root_tile = root_tile_descriptor.create_tile(ui, &windows);
-                        ui.send_message(WidgetMessage::link(
-                            root_tile,
+                        // Destroy the root tile with all descendant tiles.
+                        ui.send_message(WidgetMessage::remove(
+                            root_tile_handle,
                             MessageDirection::ToWidget,
-                            self.handle,
                         ));
-                    }
 
-                    // Restore floating windows.
-                    self.floating_windows.borrow_mut().clear();
-                    for floating_window_desc in layout_descriptor.floating_windows.iter() {
-                        if floating_window_desc.name.is_empty() {
-                            Log::warn(
+                        // Re-create the tiles according to the layout and attach it to the docking manager.
+                        if let Some(root_tile_descriptor) =
+                            layout_descriptor.root_tile_descriptor.as_ref()
+                        {
+                            let root_tile = root_tile_descriptor.create_tile(ui, &windows);
+                            ui.send_message(WidgetMessage::link(
+                                root_tile,
+                                MessageDirection::ToWidget,
+                                self.handle,
+                            ));
+                        }
+
+                        // Restore floating windows.
+                        self.floating_windows.borrow_mut().clear();
+                        for floating_window_desc in layout_descriptor.floating_windows.iter() {
+                            if floating_window_desc.name.is_empty() {
+                                Log::warn(
                                 "Floating window name is empty, wrong widget will be used as a \
                         floating window. Assign a unique name to the floating window used in a docking \
                         manager!",
                             );
-                        }
-
-                        let floating_window =
-                            ui.find_handle(ui.root(), &mut |n| n.name == floating_window_desc.name);
-                        if floating_window.is_some() {
-                            self.floating_windows.borrow_mut().push(floating_window);
+                            }
 
-                            ui.send_message(WidgetMessage::desired_position(
-                                floating_window,
-                                MessageDirection::ToWidget,
-                                floating_window_desc.position,
-                            ));
+                            let floating_window = ui.find_handle(ui.root(), &mut |n| {
+                                n.name == floating_window_desc.name
+                            });
+                            if floating_window.is_some() {
+                                self.floating_windows.borrow_mut().push(floating_window);
 
-                            if floating_window_desc.size.x != 0.0 {
-                                ui.send_message(WidgetMessage::width(
+                                ui.send_message(WidgetMessage::desired_position(
                                     floating_window,
                                     MessageDirection::ToWidget,
-                                    floating_window_desc.size.x,
+                                    floating_window_desc.position,
                                 ));
-                            }
 
-                            if floating_window_desc.size.y != 0.0 {
-                                ui.send_message(WidgetMessage::height(
-                                    floating_window,
-                                    MessageDirection::ToWidget,
-                                    floating_window_desc.size.y,
-                                ));
+                                if floating_window_desc.size.x != 0.0 {
+                                    ui.send_message(WidgetMessage::width(
+                                        floating_window,
+                                        MessageDirection::ToWidget,
+                                        floating_window_desc.size.x,
+                                    ));
+                                }
+
+                                if floating_window_desc.size.y != 0.0 {
+                                    ui.send_message(WidgetMessage::height(
+                                        floating_window,
+                                        MessageDirection::ToWidget,
+                                        floating_window_desc.size.y,
+                                    ));
+                                }
                             }
                         }
                     }
                 }
+                Some(DockingManagerMessage::AddFloatingWindow(window)) => {
+                    let mut floating_windows = self.floating_windows.borrow_mut();
+                    if !floating_windows.contains(window) {
+                        floating_windows.push(*window);
+                    }
+                }
+                Some(DockingManagerMessage::RemoveFloatingWindow(window)) => {
+                    self.floating_windows.borrow_mut().retain(|w| w != window);
+                }
+                None => (),
             }
         }
     }
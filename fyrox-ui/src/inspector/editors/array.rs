@@ -116,6 +116,7 @@ where
         max_value: array_property_info.max_value,
         step: array_property_info.step,
         precision: array_property_info.precision,
+        unit: array_property_info.unit,
         description: array_property_info.description,
         type_name: array_property_info.type_name,
         doc: array_property_info.doc,
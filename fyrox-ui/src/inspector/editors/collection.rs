@@ -236,6 +236,7 @@ where
         max_value: collection_property_info.max_value,
         step: collection_property_info.step,
         precision: collection_property_info.precision,
+        unit: collection_property_info.unit,
         description: collection_property_info.description,
         type_name: collection_property_info.type_name,
         doc: collection_property_info.doc,
@@ -211,6 +211,7 @@ where
         max_value: property_info.max_value,
         step: property_info.step,
         precision: property_info.precision,
+        unit: property_info.unit,
         description: property_info.description,
         type_name: property_info.type_name,
         doc: property_info.doc,
@@ -65,10 +65,12 @@ where
             .with_step(
                 ctx.property_info
                     .step
+                    .or_else(|| ctx.property_info.unit.map(|u| u.default_step()))
                     .and_then(NumCast::from)
                     .unwrap_or_else(T::one),
             )
             .with_precision(ctx.property_info.precision.unwrap_or(3))
+            .with_unit(ctx.property_info.unit)
             .with_value(*value)
             .build(ctx.build_context),
         })
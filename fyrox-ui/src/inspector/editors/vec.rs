@@ -59,9 +59,11 @@ impl<T: NumericType, const D: usize> PropertyEditorDefinition
             .with_step(SVector::repeat(
                 ctx.property_info
                     .step
+                    .or_else(|| ctx.property_info.unit.map(|u| u.default_step()))
                     .and_then(NumCast::from)
                     .unwrap_or_else(T::one),
             ))
+            .with_unit(ctx.property_info.unit)
             .with_value(*value)
             .build(ctx.build_context),
         })
@@ -4,9 +4,12 @@
 
 use crate::{
     border::BorderBuilder,
+    brush::Brush,
+    button::{ButtonBuilder, ButtonMessage},
     check_box::CheckBoxBuilder,
     core::{
         algebra::Vector2,
+        color::Color,
         pool::Handle,
         reflect::{prelude::*, CastError, Reflect},
         type_traits::prelude::*,
@@ -29,6 +32,7 @@ use crate::{
     utils::{make_arrow, make_simple_tooltip, ArrowDirection},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, RcUiNodeHandle, Thickness, UiNode, UserInterface, VerticalAlignment,
+    BRUSH_TEXT,
 };
 use copypasta::ClipboardProvider;
 use fyrox_graph::{BaseSceneGraph, SceneGraph};
@@ -356,11 +360,37 @@ pub enum InspectorMessage {
     /// Message sent from the inspector to notify the world that the object has been edited according to the
     /// given PropertyChanged struct.
     PropertyChanged(PropertyChanged),
+    /// Message sent to the inspector to show or hide "record" buttons next to simple properties. The
+    /// flag is applied to every nested inspector as well.
+    RecordButtonsVisibility(bool),
+    /// Message sent from the inspector when a "record" button of a property was clicked. Contains
+    /// the path of the property (in the same format as [`PropertyChanged::path`]).
+    RecordProperty(String),
+    /// Message sent to the inspector to show only the properties, which display names contain the
+    /// given text (case-insensitive). Properties with matching nested properties are shown as well.
+    /// Empty text shows every property.
+    Search(String),
+    /// Message sent to the inspector to set the names of favorite properties. Favorite properties
+    /// are shown at the top of the inspector.
+    Favorites(Vec<String>),
+    /// Message sent from the inspector when a user clicks "Add/Remove Favorite" in the context menu
+    /// of a property. Contains the name of the property.
+    ToggleFavorite(String),
+    /// Message sent to the inspector to highlight the properties, that have different values in
+    /// multiple inspected objects. Contains the paths of the properties (in the same format as
+    /// [`PropertyChanged::path`]).
+    MixedValues(Vec<String>),
 }
 
 impl InspectorMessage {
     define_constructor!(InspectorMessage:Context => fn context(InspectorContext), layout: false);
     define_constructor!(InspectorMessage:PropertyChanged => fn property_changed(PropertyChanged), layout: false);
+    define_constructor!(InspectorMessage:RecordButtonsVisibility => fn record_buttons_visibility(bool), layout: false);
+    define_constructor!(InspectorMessage:RecordProperty => fn record_property(String), layout: false);
+    define_constructor!(InspectorMessage:Search => fn search(String), layout: false);
+    define_constructor!(InspectorMessage:Favorites => fn favorites(Vec<String>), layout: false);
+    define_constructor!(InspectorMessage:ToggleFavorite => fn toggle_favorite(String), layout: false);
+    define_constructor!(InspectorMessage:MixedValues => fn mixed_values(Vec<String>), layout: false);
 }
 
 /// This trait allows dynamically typed context information to be
@@ -458,6 +488,118 @@ pub struct Inspector {
     #[reflect(hidden)]
     #[visit(skip)]
     pub context: InspectorContext,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub record_buttons_visible: bool,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub search_text: String,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub favorites: Vec<String>,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    mixed_value_marks: Vec<Handle<UiNode>>,
+}
+
+/// Color of the names of the properties, that have different values in multiple inspected objects.
+pub const MIXED_VALUE_COLOR: Color = Color::opaque(255, 170, 60);
+
+/// Short description of an inspector entry, that is used to walk through nested inspectors.
+struct EntryInfo {
+    name: String,
+    display_name: String,
+    container: Handle<UiNode>,
+    name_text: Handle<UiNode>,
+}
+
+impl EntryInfo {
+    fn collect(context: &InspectorContext) -> Vec<Self> {
+        context
+            .entries
+            .iter()
+            .map(|entry| Self {
+                name: entry.property_name.clone(),
+                display_name: entry.property_display_name.to_lowercase(),
+                container: entry.property_container,
+                name_text: entry.property_name_text,
+            })
+            .collect()
+    }
+}
+
+/// Returns the entries of the given inspector.
+fn inspector_entries(ui: &UserInterface, inspector: Handle<UiNode>) -> Vec<EntryInfo> {
+    ui.try_get(inspector)
+        .and_then(|n| n.cast::<Inspector>())
+        .map(|inspector| EntryInfo::collect(&inspector.context))
+        .unwrap_or_default()
+}
+
+/// Returns the inspectors, that are nested in the given property container. Inspectors of deeper
+/// levels are not included.
+fn find_nested_inspectors(ui: &UserInterface, container: Handle<UiNode>) -> Vec<Handle<UiNode>> {
+    let mut inspectors = Vec::new();
+    let mut stack = vec![container];
+    while let Some(handle) = stack.pop() {
+        if let Some(node) = ui.try_get(handle) {
+            if handle != container && node.has_component::<Inspector>() {
+                inspectors.push(handle);
+            } else {
+                stack.extend_from_slice(node.children());
+            }
+        }
+    }
+    inspectors
+}
+
+/// Shows the entries, which names contain the given text (or have such nested entries) and hides
+/// the rest of them. Returns `true` if at least one entry is visible.
+fn apply_search(ui: &mut UserInterface, entries: Vec<EntryInfo>, text: &str) -> bool {
+    let mut any_visible = false;
+    for entry in entries {
+        let matches = text.is_empty() || entry.display_name.contains(text);
+        let mut visible = matches;
+        for nested in find_nested_inspectors(ui, entry.container) {
+            let nested_entries = inspector_entries(ui, nested);
+            // Every nested property of a matching property is shown.
+            visible |= apply_search(ui, nested_entries, if matches { "" } else { text });
+        }
+        ui.send_message(WidgetMessage::visibility(
+            entry.container,
+            MessageDirection::ToWidget,
+            visible,
+        ));
+        any_visible |= visible;
+    }
+    any_visible
+}
+
+/// Collects the name texts of the entries with the given paths.
+fn collect_mixed_value_marks(
+    ui: &UserInterface,
+    entries: &[EntryInfo],
+    paths: &[&str],
+    marks: &mut Vec<Handle<UiNode>>,
+) {
+    for entry in entries {
+        let mut nested_paths = Vec::new();
+        for path in paths {
+            match path.split_once('.') {
+                Some((name, rest)) if name == entry.name => nested_paths.push(rest),
+                None if *path == entry.name && entry.name_text.is_some() => {
+                    marks.push(entry.name_text)
+                }
+                _ => (),
+            }
+        }
+        if !nested_paths.is_empty() {
+            for nested in find_nested_inspectors(ui, entry.container) {
+                let nested_entries = inspector_entries(ui, nested);
+                collect_mixed_value_marks(ui, &nested_entries, &nested_paths, marks);
+            }
+        }
+    }
 }
 
 crate::define_widget_deref!(Inspector);
@@ -466,6 +608,86 @@ impl Inspector {
     pub fn context(&self) -> &InspectorContext {
         &self.context
     }
+
+    /// Moves the containers of favorite properties to the top of the inspector.
+    fn apply_favorites(&self, ui: &UserInterface) {
+        let (favorites, rest): (Vec<_>, Vec<_>) = self
+            .context
+            .entries
+            .iter()
+            .partition(|entry| self.favorites.contains(&entry.property_name));
+        for entry in favorites.into_iter().chain(rest) {
+            ui.send_message(WidgetMessage::link(
+                entry.property_container,
+                MessageDirection::ToWidget,
+                self.context.stack_panel,
+            ));
+        }
+    }
+
+    fn apply_mixed_values(&mut self, ui: &UserInterface, paths: &[String]) {
+        for mark in self.mixed_value_marks.drain(..) {
+            ui.send_message(WidgetMessage::foreground(
+                mark,
+                MessageDirection::ToWidget,
+                BRUSH_TEXT,
+            ));
+        }
+
+        let paths = paths.iter().map(|p| p.as_str()).collect::<Vec<_>>();
+        let entries = EntryInfo::collect(&self.context);
+        collect_mixed_value_marks(ui, &entries, &paths, &mut self.mixed_value_marks);
+
+        for mark in self.mixed_value_marks.iter() {
+            ui.send_message(WidgetMessage::foreground(
+                *mark,
+                MessageDirection::ToWidget,
+                Brush::Solid(MIXED_VALUE_COLOR),
+            ));
+        }
+    }
+
+    /// Shows or hides "record" buttons of this inspector and every nested inspector.
+    fn apply_record_buttons_visibility(&self, ui: &mut UserInterface) {
+        let mut record_buttons = self
+            .context
+            .entries
+            .iter()
+            .map(|entry| entry.record_button)
+            .collect::<Vec<_>>();
+
+        if ui.is_valid_handle(self.context.stack_panel) {
+            let nested_inspectors = ui
+                .traverse_handle_iter(self.context.stack_panel)
+                .filter(|handle| ui.node(*handle).has_component::<Inspector>())
+                .collect::<Vec<_>>();
+
+            for nested_inspector in nested_inspectors {
+                if let Some(nested_inspector) =
+                    ui.node_mut(nested_inspector).cast_mut::<Inspector>()
+                {
+                    nested_inspector.record_buttons_visible = self.record_buttons_visible;
+                    record_buttons.extend(
+                        nested_inspector
+                            .context
+                            .entries
+                            .iter()
+                            .map(|entry| entry.record_button),
+                    );
+                }
+            }
+        }
+
+        for record_button in record_buttons {
+            if record_button.is_some() {
+                ui.send_message(WidgetMessage::visibility(
+                    record_button,
+                    MessageDirection::ToWidget,
+                    self.record_buttons_visible,
+                ));
+            }
+        }
+    }
 }
 
 /// The width of editor name labels to ensure inspectors have a uniform appearance.
@@ -519,6 +741,15 @@ pub struct ContextEntry {
     /// Storing the handle here allows us to which editor the user is indicating if the mouse is over the area
     /// surrounding the editor instead of the editor itself.
     pub property_container: Handle<UiNode>,
+    /// The "record" button next to the editor. It is hidden by default and can be shown by
+    /// [`InspectorMessage::RecordButtonsVisibility`]. Only simple properties have the button, for the
+    /// rest of them the handle is `Handle::NONE`.
+    pub record_button: Handle<UiNode>,
+    /// The name of the field being edited, as found in [FieldInfo::display_name].
+    pub property_display_name: String,
+    /// The text widget with the name of the property. Only simple properties have it, for the
+    /// rest of them the handle is `Handle::NONE`.
+    pub property_name_text: Handle<UiNode>,
 }
 
 impl PartialEq for ContextEntry {
@@ -540,6 +771,8 @@ impl PartialEq for ContextEntry {
 pub struct Menu {
     /// The handle of the "Copy Value as String" menu item.
     pub copy_value_as_string: Handle<UiNode>,
+    /// The handle of the "Add/Remove Favorite" menu item.
+    pub toggle_favorite: Handle<UiNode>,
     /// The reference-counted handle of the menu as a whole.
     pub menu: Option<RcUiNodeHandle>,
 }
@@ -712,9 +945,26 @@ fn make_tooltip(ctx: &mut BuildContext, text: &str) -> Option<RcUiNodeHandle> {
     }
 }
 
+fn make_record_button(ctx: &mut BuildContext) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_visibility(false)
+            .with_width(16.0)
+            .with_height(16.0)
+            .with_vertical_alignment(VerticalAlignment::Top)
+            .with_tooltip(make_simple_tooltip(ctx, "Record Animation Key"))
+            .with_margin(Thickness::uniform(1.0))
+            .on_row(0)
+            .on_column(2),
+    )
+    .with_text("K")
+    .build(ctx)
+}
+
 fn make_simple_property_container(
     title: Handle<UiNode>,
     editor: Handle<UiNode>,
+    record_button: Handle<UiNode>,
     description: &str,
     ctx: &mut BuildContext,
 ) -> Handle<UiNode> {
@@ -723,9 +973,18 @@ fn make_simple_property_container(
     let tooltip = make_tooltip(ctx, description);
     ctx[title].set_tooltip(tooltip);
 
-    GridBuilder::new(WidgetBuilder::new().with_child(title).with_child(editor))
+    let mut children = vec![title, editor];
+    if record_button.is_some() {
+        children.push(record_button);
+    }
+
+    GridBuilder::new(WidgetBuilder::new().with_children(children))
         .add_row(Row::auto())
-        .add_columns(vec![Column::strict(NAME_COLUMN_WIDTH), Column::stretch()])
+        .add_columns(vec![
+            Column::strict(NAME_COLUMN_WIDTH),
+            Column::stretch(),
+            Column::auto(),
+        ])
         .build(ctx)
 }
 
@@ -838,18 +1097,26 @@ impl InspectorContext {
                         },
                     ) {
                         Ok(instance) => {
-                            let (container, editor) = match instance {
-                                PropertyEditorInstance::Simple { editor } => (
-                                    make_simple_property_container(
-                                        create_header(ctx, info.display_name, layer_index),
+                            let (container, editor, record_button, name_text) = match instance {
+                                PropertyEditorInstance::Simple { editor } => {
+                                    let record_button = make_record_button(ctx);
+                                    let name_text =
+                                        create_header(ctx, info.display_name, layer_index);
+                                    (
+                                        make_simple_property_container(
+                                            name_text,
+                                            editor,
+                                            record_button,
+                                            &description,
+                                            ctx,
+                                        ),
                                         editor,
-                                        &description,
-                                        ctx,
-                                    ),
-                                    editor,
-                                ),
+                                        record_button,
+                                        name_text,
+                                    )
+                                }
                                 PropertyEditorInstance::Custom { container, editor } => {
-                                    (container, editor)
+                                    (container, editor, Handle::NONE, Handle::NONE)
                                 }
                             };
 
@@ -861,6 +1128,9 @@ impl InspectorContext {
                                 property_owner_type_id: info.owner_type_id,
                                 property_debug_output: field_text.clone(),
                                 property_container: container,
+                                record_button,
+                                property_display_name: info.display_name.to_string(),
+                                property_name_text: name_text,
                             });
 
                             if info.read_only {
@@ -880,6 +1150,7 @@ impl InspectorContext {
                                     e
                                 ))
                                 .build(ctx),
+                            Handle::NONE,
                             &description,
                             ctx,
                         ),
@@ -897,6 +1168,7 @@ impl InspectorContext {
                                 info.type_name
                             ))
                             .build(ctx),
+                        Handle::NONE,
                         &description,
                         ctx,
                     ));
@@ -905,14 +1177,24 @@ impl InspectorContext {
         });
 
         let copy_value_as_string;
+        let toggle_favorite;
         let menu = ContextMenuBuilder::new(
             PopupBuilder::new(WidgetBuilder::new().with_visibility(false)).with_content(
-                StackPanelBuilder::new(WidgetBuilder::new().with_child({
-                    copy_value_as_string = MenuItemBuilder::new(WidgetBuilder::new())
-                        .with_content(MenuItemContent::text("Copy Value as String"))
-                        .build(ctx);
-                    copy_value_as_string
-                }))
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            copy_value_as_string = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Copy Value as String"))
+                                .build(ctx);
+                            copy_value_as_string
+                        })
+                        .with_child({
+                            toggle_favorite = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Add/Remove Favorite"))
+                                .build(ctx);
+                            toggle_favorite
+                        }),
+                )
                 .build(ctx),
             ),
         )
@@ -935,6 +1217,7 @@ impl InspectorContext {
             stack_panel,
             menu: Menu {
                 copy_value_as_string,
+                toggle_favorite,
                 menu: Some(menu),
             },
             entries,
@@ -1066,6 +1349,81 @@ impl Control for Inspector {
                 ));
 
                 self.context = ctx.clone();
+
+                self.mixed_value_marks.clear();
+
+                if self.record_buttons_visible {
+                    self.apply_record_buttons_visibility(ui);
+                }
+
+                if !self.favorites.is_empty() {
+                    self.apply_favorites(ui);
+                }
+
+                if !self.search_text.is_empty() {
+                    apply_search(ui, EntryInfo::collect(&self.context), &self.search_text);
+                }
+            } else if let Some(InspectorMessage::RecordButtonsVisibility(visible)) =
+                message.data::<InspectorMessage>()
+            {
+                self.record_buttons_visible = *visible;
+                self.apply_record_buttons_visibility(ui);
+            } else if let Some(InspectorMessage::Search(text)) = message.data::<InspectorMessage>()
+            {
+                self.search_text = text.to_lowercase();
+                apply_search(ui, EntryInfo::collect(&self.context), &self.search_text);
+            } else if let Some(InspectorMessage::Favorites(favorites)) =
+                message.data::<InspectorMessage>()
+            {
+                self.favorites.clone_from(favorites);
+                self.apply_favorites(ui);
+            } else if let Some(InspectorMessage::MixedValues(paths)) =
+                message.data::<InspectorMessage>()
+            {
+                self.apply_mixed_values(ui, paths);
+            }
+        }
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            if let Some(entry) = self
+                .context
+                .entries
+                .iter()
+                .find(|entry| entry.record_button == message.destination())
+            {
+                ui.send_message(InspectorMessage::record_property(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                    entry.property_name.clone(),
+                ));
+            }
+        }
+
+        if let Some(InspectorMessage::RecordProperty(path)) = message.data::<InspectorMessage>() {
+            if message.direction() == MessageDirection::FromWidget
+                && message.destination() != self.handle
+                && !message.handled()
+            {
+                // The message came from a nested inspector, crawl up from it to find the entry that
+                // owns the nested inspector and prefix the path with the name of the property.
+                let mut parent_handle = message.destination();
+                'crawl: while let Some(parent) = ui.try_get(parent_handle) {
+                    for entry in self.context.entries.iter() {
+                        if entry.property_editor == parent_handle
+                            || entry.property_container == parent_handle
+                        {
+                            message.set_handled(true);
+                            ui.send_message(InspectorMessage::record_property(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                format!("{}.{}", entry.property_name, path),
+                            ));
+                            break 'crawl;
+                        }
+                    }
+
+                    parent_handle = parent.parent;
+                }
             }
         }
 
@@ -1088,6 +1446,24 @@ impl Control for Inspector {
                             }
                         }
 
+                        parent_handle = parent.parent;
+                    }
+                }
+            } else if popup_message.destination() == self.context.menu.toggle_favorite {
+                if let Some(MenuItemMessage::Click) = popup_message.data() {
+                    let mut parent_handle = message.destination();
+                    'crawl: while let Some(parent) = ui.try_get(parent_handle) {
+                        for entry in self.context.entries.iter() {
+                            if entry.property_container == parent_handle {
+                                ui.send_message(InspectorMessage::toggle_favorite(
+                                    self.handle,
+                                    MessageDirection::FromWidget,
+                                    entry.property_name.clone(),
+                                ));
+                                break 'crawl;
+                            }
+                        }
+
                         parent_handle = parent.parent;
                     }
                 }
@@ -1163,6 +1539,10 @@ impl InspectorBuilder {
                 .with_child(self.context.stack_panel)
                 .build(),
             context: self.context,
+            record_buttons_visible: false,
+            search_text: Default::default(),
+            favorites: Default::default(),
+            mixed_value_marks: Default::default(),
         };
         ctx.add_node(UiNode::new(canvas))
     }
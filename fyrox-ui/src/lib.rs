@@ -135,6 +135,7 @@
 //! linear, cubic) between them.
 //! * [`crate::inspector::Inspector`]: The Inspector automatically creates and handles the input of UI elements based on a populated Inspector
 //! Context given to it allowing the user to adjust values of a variety of models without manually creating UI's for each type.
+//! * [`crate::virtual_controls`]: The Virtual Joystick, D-Pad and Touch Button are on-screen controls for touch screens.
 //!
 //! ## Examples
 //!
@@ -211,6 +212,8 @@ pub mod inspector;
 pub mod key;
 pub mod list_view;
 pub mod loader;
+pub mod localization;
+pub mod localized_text;
 pub mod menu;
 pub mod message;
 pub mod messagebox;
@@ -231,6 +234,7 @@ pub mod searchbar;
 pub mod selector;
 pub mod stack_panel;
 pub mod tab_control;
+pub mod testing;
 pub mod text;
 pub mod text_box;
 mod thickness;
@@ -239,6 +243,7 @@ pub mod utils;
 pub mod uuid;
 pub mod vec;
 pub mod vector_image;
+pub mod virtual_controls;
 pub mod widget;
 pub mod window;
 pub mod wrap_panel;
@@ -262,11 +267,14 @@ use crate::{
     draw::{CommandTexture, Draw, DrawingContext},
     font::FontResource,
     font::BUILT_IN_FONT,
+    localization::Localization,
     message::{
-        ButtonState, CursorIcon, KeyboardModifiers, MessageDirection, MouseButton, OsEvent,
-        UiMessage,
+        ButtonState, CursorIcon, GamepadAxis, KeyboardModifiers, MessageDirection, MouseButton,
+        OsEvent, UiMessage,
     },
+    navigation::{find_focus_candidate, FocusIndicatorStyle, NavigationDirection},
     popup::{Placement, PopupMessage},
+    scroll_viewer::{ScrollViewer, ScrollViewerMessage},
     widget::{Widget, WidgetBuilder, WidgetMessage},
 };
 use copypasta::ClipboardContext;
@@ -534,6 +542,61 @@ pub struct MouseState {
     // TODO Add rest of buttons
 }
 
+/// A finger, that currently touches the screen.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct ActiveTouch {
+    start_position: Vector2<f32>,
+    position: Vector2<f32>,
+    age: f32,
+    // A touch stops being a tap once the finger moved too far or a second finger was placed.
+    is_tap: bool,
+    // A widget, that receives every event of the touch. Captured touches do not take part in the
+    // gestures.
+    captured: Handle<UiNode>,
+}
+
+/// Touch gestures state, that is used to recognize taps, pans and pinches.
+#[derive(Clone, Debug, Default)]
+struct TouchGestures {
+    touches: FxHashMap<u64, ActiveTouch>,
+    target: Handle<UiNode>,
+}
+
+impl TouchGestures {
+    /// Maximum distance (in pixels) a finger could move and still be considered as a tap.
+    const TAP_DISTANCE: f32 = 10.0;
+    /// Maximum duration (in seconds) of a tap.
+    const TAP_DURATION: f32 = 0.3;
+
+    /// Returns a point between the first two fingers and the distance between them.
+    fn pinch_span(&self) -> Option<(Vector2<f32>, f32)> {
+        let mut ids = self
+            .touches
+            .iter()
+            .filter_map(|(id, touch)| touch.captured.is_none().then_some(*id))
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        let a = self.touches.get(ids.first()?)?.position;
+        let b = self.touches.get(ids.get(1)?)?.position;
+        Some(((a + b).scale(0.5), (a - b).norm()))
+    }
+}
+
+/// Gamepad stick state, that is used to move keyboard focus with the left stick.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+struct GamepadStickState {
+    position: Vector2<f32>,
+    direction: Option<NavigationDirection>,
+    repeat_timer: f32,
+}
+
+impl GamepadStickState {
+    /// A stick must be pushed further than this value to move focus.
+    const THRESHOLD: f32 = 0.5;
+    /// Delay before focus is moved again while the stick is held.
+    const REPEAT_DELAY: f32 = 0.25;
+}
+
 impl Default for MouseState {
     fn default() -> Self {
         Self {
@@ -694,6 +757,15 @@ pub struct UserInterface {
     #[reflect(hidden)]
     double_click_entries: FxHashMap<MouseButton, DoubleClickEntry>,
     pub double_click_time_slice: f32,
+    #[reflect(hidden)]
+    localization: Localization,
+    /// Defines the look of the indicator, that is drawn around a widget with keyboard focus.
+    #[reflect(hidden)]
+    pub focus_indicator: FocusIndicatorStyle,
+    #[reflect(hidden)]
+    gamepad_stick: GamepadStickState,
+    #[reflect(hidden)]
+    touch_gestures: TouchGestures,
 }
 
 impl Visit for UserInterface {
@@ -775,6 +847,10 @@ impl Clone for UserInterface {
             default_font: self.default_font.clone(),
             double_click_entries: self.double_click_entries.clone(),
             double_click_time_slice: self.double_click_time_slice,
+            localization: self.localization.clone(),
+            focus_indicator: self.focus_indicator.clone(),
+            gamepad_stick: self.gamepad_stick,
+            touch_gestures: self.touch_gestures.clone(),
         }
     }
 }
@@ -1073,6 +1149,10 @@ impl UserInterface {
             default_font: BUILT_IN_FONT.clone(),
             double_click_entries: Default::default(),
             double_click_time_slice: 0.5, // 500 ms is standard in most operating systems.
+            localization: Default::default(),
+            focus_indicator: Default::default(),
+            gamepad_stick: Default::default(),
+            touch_gestures: Default::default(),
         };
         ui.root_canvas = ui.add_node(UiNode::new(Canvas {
             widget: WidgetBuilder::new().build(),
@@ -1081,6 +1161,18 @@ impl UserInterface {
         ui
     }
 
+    /// Returns a reference to the localization of the user interface. It is used by
+    /// [`localized_text::LocalizedText`] widgets to fetch translated strings.
+    pub fn localization(&self) -> &Localization {
+        &self.localization
+    }
+
+    /// Sets a new localization of the user interface. Localization is a shared handle, so the same
+    /// instance could be used by multiple user interfaces.
+    pub fn set_localization(&mut self, localization: Localization) {
+        self.localization = localization;
+    }
+
     pub fn keyboard_modifiers(&self) -> KeyboardModifiers {
         self.keyboard_modifiers
     }
@@ -1104,6 +1196,39 @@ impl UserInterface {
         self.captured_node = Handle::NONE;
     }
 
+    /// Forces every event of the touch with the given id to be sent to the given node, no matter
+    /// where the finger is. Unlike the mouse capture, every touch could be captured by its own node,
+    /// which allows multiple widgets to be used simultaneously with different fingers. The capture
+    /// is released automatically when the touch ends. Returns `false` if there's no such touch or
+    /// it is already captured.
+    #[inline]
+    pub fn capture_touch(&mut self, id: u64, node: Handle<UiNode>) -> bool {
+        match self.touch_gestures.touches.get_mut(&id) {
+            Some(touch) if touch.captured.is_none() => {
+                touch.captured = node;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Releases the capture of the touch with the given id. See [`Self::capture_touch`] for more
+    /// info.
+    #[inline]
+    pub fn release_touch_capture(&mut self, id: u64) {
+        if let Some(touch) = self.touch_gestures.touches.get_mut(&id) {
+            touch.captured = Handle::NONE;
+        }
+    }
+
+    /// Returns a node, that should receive events of the touch with the given id.
+    fn touch_receiver(&self, id: u64) -> Handle<UiNode> {
+        match self.touch_gestures.touches.get(&id) {
+            Some(touch) if self.nodes.is_valid_handle(touch.captured) => touch.captured,
+            _ => self.picked_node,
+        }
+    }
+
     #[inline]
     pub fn get_drawing_context(&self) -> &DrawingContext {
         &self.drawing_context
@@ -1259,8 +1384,14 @@ impl UserInterface {
             entry.timer -= dt;
         }
 
+        for touch in self.touch_gestures.touches.values_mut() {
+            touch.age += dt;
+        }
+
         self.update_layout(screen_size);
 
+        self.update_gamepad_stick(dt);
+
         if let Some(node_overrides) = switches.node_overrides.as_ref() {
             for &handle in node_overrides.iter() {
                 let (ticket, mut node) = self.nodes.take_reserve(handle);
@@ -1356,12 +1487,23 @@ impl UserInterface {
         }
 
         if let Some(keyboard_focus_node) = self.nodes.try_borrow(self.keyboard_focus_node) {
-            if keyboard_focus_node.global_visibility && keyboard_focus_node.accepts_input {
-                let bounds = keyboard_focus_node.screen_bounds().inflate(1.0, 1.0);
-                self.drawing_context.push_rounded_rect(&bounds, 1.0, 2.0, 6);
+            let style = &self.focus_indicator;
+            if style.enabled
+                && keyboard_focus_node.global_visibility
+                && keyboard_focus_node.accepts_input
+            {
+                let bounds = keyboard_focus_node
+                    .screen_bounds()
+                    .inflate(style.offset, style.offset);
+                self.drawing_context.push_rounded_rect(
+                    &bounds,
+                    style.thickness,
+                    style.corner_radius,
+                    6,
+                );
                 self.drawing_context.commit(
                     bounds,
-                    Brush::Solid(COLOR_BRIGHT_BLUE),
+                    style.brush.clone(),
                     CommandTexture::None,
                     None,
                 );
@@ -1805,6 +1947,18 @@ impl UserInterface {
                                 self.request_focus(message.destination());
                             }
                         }
+                        // Directional pad moves focus, unless the focused widget (or any of its parents)
+                        // handled the button by itself.
+                        WidgetMessage::GamepadButtonDown(button)
+                            if !message.handled()
+                                && message.direction() == MessageDirection::FromWidget =>
+                        {
+                            if let Some(direction) =
+                                NavigationDirection::from_gamepad_button(*button)
+                            {
+                                self.move_focus(direction);
+                            }
+                        }
                         WidgetMessage::Unfocus => {
                             if self.nodes.is_valid_handle(message.destination())
                                 && message.direction() == MessageDirection::ToWidget
@@ -2170,6 +2324,126 @@ impl UserInterface {
         }
     }
 
+    /// Moves keyboard focus to the closest widget in the given direction (see [`navigation::find_focus_candidate`]
+    /// for more info) and brings it into view if it is inside a scroll viewer. This method is used for
+    /// gamepad navigation, but it could also be used to implement custom navigation schemes. Returns a
+    /// handle of the newly focused widget, or `None` if there's no suitable widget in the given direction.
+    pub fn move_focus(&mut self, direction: NavigationDirection) -> Option<Handle<UiNode>> {
+        let candidate = find_focus_candidate(self, self.keyboard_focus_node, direction);
+        if candidate.is_none() {
+            return None;
+        }
+
+        self.send_message(WidgetMessage::focus(candidate, MessageDirection::ToWidget));
+
+        if let Some((scroll_viewer, _)) = self.find_component_up::<ScrollViewer>(candidate) {
+            self.send_message(ScrollViewerMessage::bring_into_view(
+                scroll_viewer,
+                MessageDirection::ToWidget,
+                candidate,
+            ));
+        }
+
+        Some(candidate)
+    }
+
+    /// Moves focus when the left stick is pushed in some direction and repeats the movement while the stick
+    /// is held. Returns `true` if the focus was moved.
+    fn update_gamepad_stick(&mut self, dt: f32) -> bool {
+        let direction = NavigationDirection::from_stick(
+            self.gamepad_stick.position,
+            GamepadStickState::THRESHOLD,
+        );
+
+        let move_focus = if direction != self.gamepad_stick.direction {
+            self.gamepad_stick.direction = direction;
+            self.gamepad_stick.repeat_timer = GamepadStickState::REPEAT_DELAY;
+            true
+        } else {
+            self.gamepad_stick.repeat_timer -= dt;
+            if self.gamepad_stick.repeat_timer <= 0.0 {
+                self.gamepad_stick.repeat_timer += GamepadStickState::REPEAT_DELAY;
+                true
+            } else {
+                false
+            }
+        };
+
+        match direction {
+            Some(direction) if move_focus => self.move_focus(direction).is_some(),
+            _ => false,
+        }
+    }
+
+    /// Updates the position of the given touch and emits pan or pinch messages. Returns `true` if
+    /// any message was emitted.
+    fn handle_touch_gestures(&mut self, id: u64, position: Vector2<f32>) -> bool {
+        let target = self.touch_gestures.target;
+        let old_span = self.touch_gestures.pinch_span();
+
+        let Some(touch) = self.touch_gestures.touches.get_mut(&id) else {
+            return false;
+        };
+        let delta = position - touch.position;
+        touch.position = position;
+        if touch.captured.is_some() {
+            return false;
+        }
+        if (position - touch.start_position).norm() > TouchGestures::TAP_DISTANCE {
+            touch.is_tap = false;
+        }
+        let is_tap = touch.is_tap;
+
+        if target.is_none() {
+            return false;
+        }
+
+        if let (Some((_, old_distance)), Some((center, new_distance))) =
+            (old_span, self.touch_gestures.pinch_span())
+        {
+            if old_distance > f32::EPSILON && (new_distance - old_distance).abs() > f32::EPSILON {
+                self.send_message(WidgetMessage::pinch(
+                    target,
+                    MessageDirection::FromWidget,
+                    center,
+                    new_distance / old_distance,
+                ));
+                return true;
+            }
+        } else if !is_tap && !self.drag_context.is_dragging {
+            self.send_message(WidgetMessage::pan(
+                target,
+                MessageDirection::FromWidget,
+                delta,
+            ));
+            return true;
+        }
+
+        false
+    }
+
+    /// Cancels every touch, that is currently active. It should be called when the application is
+    /// suspended, because the operating system may not send the events for the fingers, that were
+    /// on the screen at the moment of suspension.
+    pub fn cancel_touches(&mut self) {
+        for (id, touch) in std::mem::take(&mut self.touch_gestures.touches) {
+            let receiver = if self.nodes.is_valid_handle(touch.captured) {
+                touch.captured
+            } else {
+                self.picked_node
+            };
+            if receiver.is_some() {
+                self.send_message(WidgetMessage::touch_cancelled(
+                    receiver,
+                    MessageDirection::FromWidget,
+                    touch.position,
+                    id,
+                ));
+            }
+        }
+        self.touch_gestures.target = Handle::NONE;
+    }
+
     fn request_focus(&mut self, new_focused: Handle<UiNode>) {
         if self.keyboard_focus_node != new_focused {
             if self.keyboard_focus_node.is_some() {
@@ -2448,6 +2722,41 @@ impl UserInterface {
                     }
                 }
             }
+            &OsEvent::GamepadButton { button, state } => {
+                let focus_visible = self
+                    .try_get(self.keyboard_focus_node)
+                    .is_some_and(|node| node.is_globally_visible());
+                if focus_visible {
+                    self.send_message(match state {
+                        ButtonState::Pressed => WidgetMessage::gamepad_button_down(
+                            self.keyboard_focus_node,
+                            MessageDirection::FromWidget,
+                            button,
+                        ),
+                        ButtonState::Released => WidgetMessage::gamepad_button_up(
+                            self.keyboard_focus_node,
+                            MessageDirection::FromWidget,
+                            button,
+                        ),
+                    });
+                    event_processed = true;
+                } else if state == ButtonState::Pressed {
+                    if let Some(direction) = NavigationDirection::from_gamepad_button(button) {
+                        event_processed = self.move_focus(direction).is_some();
+                    }
+                }
+            }
+            &OsEvent::GamepadAxis { axis, value } => match axis {
+                GamepadAxis::LeftStickX => {
+                    self.gamepad_stick.position.x = value;
+                    event_processed = self.update_gamepad_stick(0.0);
+                }
+                GamepadAxis::LeftStickY => {
+                    self.gamepad_stick.position.y = value;
+                    event_processed = self.update_gamepad_stick(0.0);
+                }
+                GamepadAxis::RightStickX | GamepadAxis::RightStickY => {}
+            },
             &OsEvent::KeyboardModifiers(modifiers) => {
                 // TODO: Is message needed for focused node?
                 self.keyboard_modifiers = modifiers;
@@ -2510,6 +2819,30 @@ impl UserInterface {
 
                     self.request_focus(self.picked_node);
 
+                    let is_first = self
+                        .touch_gestures
+                        .touches
+                        .values()
+                        .all(|touch| touch.captured.is_some());
+                    if is_first {
+                        self.touch_gestures.target = self.picked_node;
+                    } else {
+                        // Multi-touch gestures cannot be taps.
+                        for touch in self.touch_gestures.touches.values_mut() {
+                            touch.is_tap = false;
+                        }
+                    }
+                    self.touch_gestures.touches.insert(
+                        *id,
+                        ActiveTouch {
+                            start_position: *location,
+                            position: *location,
+                            age: 0.0,
+                            is_tap: is_first,
+                            captured: Handle::NONE,
+                        },
+                    );
+
                     if self.picked_node.is_some() {
                         self.send_message(WidgetMessage::touch_started(
                             self.picked_node,
@@ -2555,9 +2888,10 @@ impl UserInterface {
 
                     self.request_focus(self.picked_node);
 
-                    if self.picked_node.is_some() {
+                    let receiver = self.touch_receiver(*id);
+                    if receiver.is_some() {
                         self.send_message(WidgetMessage::touch_moved(
-                            self.picked_node,
+                            receiver,
                             MessageDirection::FromWidget,
                             self.cursor_position,
                             *force,
@@ -2565,16 +2899,36 @@ impl UserInterface {
                         ));
                         event_processed = true;
                     }
+
+                    event_processed |= self.handle_touch_gestures(*id, *location);
                 }
                 TouchPhase::Ended => {
-                    if self.picked_node.is_some() {
+                    let receiver = self.touch_receiver(*id);
+                    if let Some(touch) = self.touch_gestures.touches.remove(id) {
+                        if touch.is_tap
+                            && touch.captured.is_none()
+                            && touch.age <= TouchGestures::TAP_DURATION
+                            && self.touch_gestures.target.is_some()
+                        {
+                            self.send_message(WidgetMessage::tap(
+                                self.touch_gestures.target,
+                                MessageDirection::FromWidget,
+                                *location,
+                            ));
+                        }
+                    }
+
+                    if receiver.is_some() {
                         self.send_message(WidgetMessage::touch_ended(
-                            self.picked_node,
+                            receiver,
                             MessageDirection::FromWidget,
                             self.cursor_position,
                             *id,
                         ));
+                        event_processed = true;
+                    }
 
+                    if self.picked_node.is_some() {
                         if self.drag_context.is_dragging {
                             self.drag_context.is_dragging = false;
 
@@ -2606,14 +2960,20 @@ impl UserInterface {
                     }
                 }
                 TouchPhase::Cancelled => {
-                    if self.picked_node.is_some() {
+                    let receiver = self.touch_receiver(*id);
+                    self.touch_gestures.touches.remove(id);
+
+                    if receiver.is_some() {
                         self.send_message(WidgetMessage::touch_cancelled(
-                            self.picked_node,
+                            receiver,
                             MessageDirection::FromWidget,
                             self.cursor_position,
                             *id,
                         ));
+                        event_processed = true;
+                    }
 
+                    if self.picked_node.is_some() {
                         if self.drag_context.is_dragging {
                             self.drag_context.is_dragging = false;
                             self.cursor_icon = CursorIcon::Default;
@@ -3412,15 +3772,17 @@ impl ResourceData for UserInterface {
 
 #[cfg(test)]
 mod test {
-    use crate::message::{ButtonState, KeyCode};
+    use crate::message::{ButtonState, GamepadButton, KeyCode, TouchPhase};
     use crate::{
         border::BorderBuilder,
+        button::ButtonBuilder,
         core::algebra::{Rotation2, UnitComplex, Vector2},
         message::MessageDirection,
+        stack_panel::StackPanelBuilder,
         text_box::TextBoxBuilder,
         transform_size,
         widget::{WidgetBuilder, WidgetMessage},
-        OsEvent, UserInterface,
+        Orientation, OsEvent, UserInterface,
     };
     use fyrox_graph::BaseSceneGraph;
 
@@ -3508,4 +3870,121 @@ mod test {
 
         assert!(ui.poll_message().is_none());
     }
+
+    #[test]
+    fn test_gamepad_navigation() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let ctx = &mut ui.build_ctx();
+        let buttons = (0..3)
+            .map(|_| {
+                ButtonBuilder::new(WidgetBuilder::new().with_width(100.0).with_height(30.0))
+                    .build(ctx)
+            })
+            .collect::<Vec<_>>();
+        StackPanelBuilder::new(WidgetBuilder::new().with_children(buttons.iter().cloned()))
+            .with_orientation(Orientation::Horizontal)
+            .build(ctx);
+
+        ui.update(screen_size, 0.0, &Default::default());
+
+        let press = |ui: &mut UserInterface, button| {
+            ui.process_os_event(&OsEvent::GamepadButton {
+                button,
+                state: ButtonState::Pressed,
+            });
+            while ui.poll_message().is_some() {}
+        };
+
+        // Nothing is focused, so the top-left widget must be selected.
+        press(&mut ui, GamepadButton::DPadRight);
+        assert_eq!(ui.keyboard_focus_node, buttons[0]);
+
+        press(&mut ui, GamepadButton::DPadRight);
+        assert_eq!(ui.keyboard_focus_node, buttons[1]);
+
+        press(&mut ui, GamepadButton::DPadRight);
+        press(&mut ui, GamepadButton::DPadRight);
+        assert_eq!(ui.keyboard_focus_node, buttons[2]);
+
+        press(&mut ui, GamepadButton::DPadLeft);
+        assert_eq!(ui.keyboard_focus_node, buttons[1]);
+
+        // There's nothing below.
+        press(&mut ui, GamepadButton::DPadDown);
+        assert_eq!(ui.keyboard_focus_node, buttons[1]);
+    }
+
+    #[test]
+    fn test_touch_gestures() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let border = BorderBuilder::new(WidgetBuilder::new().with_width(500.0).with_height(500.0))
+            .build(&mut ui.build_ctx());
+
+        // Make sure layout was calculated and the widget could be picked.
+        ui.update(screen_size, 0.0, &Default::default());
+        ui.draw();
+
+        let touch = |ui: &mut UserInterface, phase, id, location| {
+            ui.process_os_event(&OsEvent::Touch {
+                phase,
+                location,
+                force: None,
+                id,
+            });
+            let mut messages = Vec::new();
+            while let Some(message) = ui.poll_message() {
+                if let Some(msg) = message.data::<WidgetMessage>() {
+                    if matches!(
+                        msg,
+                        WidgetMessage::Tap { .. }
+                            | WidgetMessage::Pan { .. }
+                            | WidgetMessage::Pinch { .. }
+                    ) {
+                        assert_eq!(message.destination(), border);
+                        messages.push(msg.clone());
+                    }
+                }
+            }
+            messages
+        };
+
+        // Tap.
+        touch(&mut ui, TouchPhase::Started, 0, Vector2::new(10.0, 10.0));
+        assert_eq!(
+            touch(&mut ui, TouchPhase::Ended, 0, Vector2::new(10.0, 10.0)),
+            vec![WidgetMessage::Tap {
+                pos: Vector2::new(10.0, 10.0)
+            }]
+        );
+
+        // Pan, the finger moved too far to be a tap.
+        touch(&mut ui, TouchPhase::Started, 1, Vector2::new(10.0, 10.0));
+        assert_eq!(
+            touch(&mut ui, TouchPhase::Moved, 1, Vector2::new(10.0, 50.0)),
+            vec![WidgetMessage::Pan {
+                delta: Vector2::new(0.0, 40.0)
+            }]
+        );
+        assert!(touch(&mut ui, TouchPhase::Ended, 1, Vector2::new(10.0, 50.0)).is_empty());
+
+        // Pinch.
+        touch(&mut ui, TouchPhase::Started, 2, Vector2::new(100.0, 100.0));
+        touch(&mut ui, TouchPhase::Started, 3, Vector2::new(200.0, 100.0));
+        assert_eq!(
+            touch(&mut ui, TouchPhase::Moved, 3, Vector2::new(300.0, 100.0)),
+            vec![WidgetMessage::Pinch {
+                center: Vector2::new(200.0, 100.0),
+                scale: 2.0
+            }]
+        );
+
+        // Suspension must reset the gestures.
+        ui.cancel_touches();
+        while ui.poll_message().is_some() {}
+        assert!(ui.touch_gestures.touches.is_empty());
+    }
 }
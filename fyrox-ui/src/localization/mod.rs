@@ -0,0 +1,559 @@
+//! Localization subsystem. It allows you to store translated strings in string tables (one table per language)
+//! and switch between languages at runtime. See [`StringTable`] and [`Localization`] docs for more info.
+
+#![warn(missing_docs)]
+
+use crate::core::{
+    io::FileLoadError, parking_lot::Mutex, reflect::prelude::*, type_traits::prelude::*,
+    visitor::prelude::*,
+};
+use fxhash::FxHashMap;
+use fyrox_resource::{io::ResourceIo, Resource, ResourceData};
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+    path::Path,
+    sync::Arc,
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+pub mod loader;
+
+/// An error that may occur during string table resource loading.
+#[derive(Debug)]
+pub enum StringTableError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for StringTableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            Self::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for StringTableError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for StringTableError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// A rule, that defines which plural form should be used for a given number of items. Languages
+/// have different amount of plural forms, for example English has two (`1 apple`, `2 apples`),
+/// while Russian has three. Plural forms of a string are separated with `|` character in string
+/// tables, for example: `{count} apple|{count} apples`.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+pub enum PluralRule {
+    /// There's only one form for any number of items. Chinese, Japanese, Korean, Thai, etc.
+    Invariant,
+    /// Singular form is used only for one item, plural - for everything else. English, German,
+    /// Spanish, Italian, etc.
+    #[default]
+    OneOther,
+    /// Singular form is used for zero and one item, plural - for everything else. French,
+    /// Brazilian Portuguese.
+    ZeroOneOther,
+    /// Three forms - for numbers ending with 1 (except 11), for numbers ending with 2-4 (except
+    /// 12-14), and for everything else. Russian, Ukrainian, Belarusian, Serbian, Croatian.
+    EastSlavic,
+    /// Three forms - for one item, for numbers ending with 2-4 (except 12-14), and for everything
+    /// else. Polish.
+    Polish,
+    /// Three forms - for one item, for 2-4 items, and for everything else. Czech, Slovak.
+    Czech,
+}
+
+impl PluralRule {
+    /// Returns an index of a plural form for the given number of items.
+    pub fn form_index(self, count: u64) -> usize {
+        let ends_with_2_to_4 =
+            (2..=4).contains(&(count % 10)) && !(12..=14).contains(&(count % 100));
+        match self {
+            PluralRule::Invariant => 0,
+            PluralRule::OneOther => usize::from(count != 1),
+            PluralRule::ZeroOneOther => usize::from(count > 1),
+            PluralRule::EastSlavic => {
+                if count % 10 == 1 && count % 100 != 11 {
+                    0
+                } else if ends_with_2_to_4 {
+                    1
+                } else {
+                    2
+                }
+            }
+            PluralRule::Polish => {
+                if count == 1 {
+                    0
+                } else if ends_with_2_to_4 {
+                    1
+                } else {
+                    2
+                }
+            }
+            PluralRule::Czech => match count {
+                1 => 0,
+                2..=4 => 1,
+                _ => 2,
+            },
+        }
+    }
+}
+
+/// String table is a set of translated strings for a single language, where each string is identified
+/// by a unique key. A string could contain named parameters in curly braces (for example, `Hello, {name}!`),
+/// that are substituted at runtime (see [`format_string`]). A string also could have multiple plural forms
+/// separated with `|` (for example, `{count} apple|{count} apples`), the form is selected using the
+/// [`PluralRule`] of the table.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_ui::localization::StringTable;
+///
+/// let mut table = StringTable::new("en");
+/// table.set("greeting", "Hello, {name}!");
+/// table.set("apples", "{count} apple|{count} apples");
+///
+/// assert_eq!(table.get("greeting"), Some("Hello, {name}!"));
+/// assert_eq!(table.get_plural("apples", 1), Some("{count} apple"));
+/// assert_eq!(table.get_plural("apples", 5), Some("{count} apples"));
+/// ```
+#[derive(Clone, Default, Debug, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "57ba537a-8a1a-4999-b2cb-5eaa964516d8")]
+pub struct StringTable {
+    /// Language code of the table (for example, `en`, `de`, `pt-BR`).
+    pub language: String,
+    /// Plural rule of the language.
+    pub plural_rule: PluralRule,
+    /// Translated strings of the table.
+    pub entries: FxHashMap<String, String>,
+}
+
+impl ResourceData for StringTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("StringTable", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+/// Type alias for string table resources.
+pub type StringTableResource = Resource<StringTable>;
+
+impl StringTable {
+    /// Creates a new empty string table for the given language.
+    pub fn new<S: AsRef<str>>(language: S) -> Self {
+        Self {
+            language: language.as_ref().to_owned(),
+            plural_rule: Default::default(),
+            entries: Default::default(),
+        }
+    }
+
+    /// Load a string table resource from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, StringTableError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut table = StringTable::default();
+        table.visit("StringTable", &mut visitor)?;
+        Ok(table)
+    }
+
+    /// Returns a string with the given key. If the string has multiple plural forms, the first one
+    /// is returned.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .get(key)
+            .map(|value| value.split('|').next().unwrap_or_default())
+    }
+
+    /// Returns a plural form of a string with the given key for the given number of items. If the
+    /// string does not have enough plural forms, the last one is returned.
+    pub fn get_plural(&self, key: &str, count: u64) -> Option<&str> {
+        let value = self.entries.get(key)?;
+        let index = self.plural_rule.form_index(count);
+        value.split('|').take(index + 1).last()
+    }
+
+    /// Sets a new value of a string with the given key. Returns the old value, if any.
+    pub fn set<K: AsRef<str>, V: AsRef<str>>(&mut self, key: K, value: V) -> Option<String> {
+        self.entries
+            .insert(key.as_ref().to_owned(), value.as_ref().to_owned())
+    }
+
+    /// Removes a string with the given key. Returns the old value, if any.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.entries.remove(key)
+    }
+
+    /// Returns `true` if the table contains a string with the given key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Returns an iterator over all the keys of the table.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(|key| key.as_str())
+    }
+
+    /// Returns a sorted list of keys, that are present in the `reference` table, but either missing or
+    /// empty in this table. It could be used to find untranslated strings.
+    pub fn missing_keys(&self, reference: &StringTable) -> Vec<String> {
+        let mut missing = reference
+            .keys()
+            .filter(|key| {
+                self.entries
+                    .get(*key)
+                    .map_or(true, |value| value.is_empty())
+            })
+            .map(|key| key.to_owned())
+            .collect::<Vec<_>>();
+        missing.sort();
+        missing
+    }
+}
+
+/// Substitutes named parameters in the given string. Parameters are defined using curly braces, for
+/// example `Hello, {name}!`. Use `{{` and `}}` to insert curly braces. Parameters without a matching
+/// argument are left as is.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_ui::localization::format_string;
+///
+/// assert_eq!(
+///     format_string("{name} has {count} apples", &[("name", "Bob"), ("count", "3")]),
+///     "Bob has 3 apples"
+/// );
+/// ```
+pub fn format_string(template: &str, args: &[(&str, &str)]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut chars = template.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '{' if matches!(chars.peek(), Some((_, '{'))) => {
+                chars.next();
+                result.push('{');
+            }
+            '}' if matches!(chars.peek(), Some((_, '}'))) => {
+                chars.next();
+                result.push('}');
+            }
+            '{' => {
+                if let Some(length) = template[i + 1..].find('}') {
+                    let name = &template[i + 1..i + 1 + length];
+                    if let Some((_, value)) = args.iter().find(|(arg, _)| *arg == name) {
+                        result.push_str(value);
+                    } else {
+                        result.push_str(&template[i..i + length + 2]);
+                    }
+                    // Skip the name and the closing brace.
+                    for _ in 0..name.chars().count() + 1 {
+                        chars.next();
+                    }
+                } else {
+                    result.push(c);
+                }
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+#[derive(Default, Debug)]
+struct LocalizationState {
+    tables: Vec<StringTableResource>,
+    language: String,
+    fallback_language: Option<String>,
+    generation: u64,
+}
+
+impl LocalizationState {
+    fn find<F, R>(&self, language: &str, mut func: F) -> Option<R>
+    where
+        F: FnMut(&StringTable) -> Option<R>,
+    {
+        for table in self.tables.iter() {
+            let mut state = table.state();
+            if let Some(table) = state.data() {
+                if table.language == language {
+                    if let Some(result) = func(table) {
+                        return Some(result);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn find_with_fallback<F, R>(&self, mut func: F) -> Option<R>
+    where
+        F: FnMut(&StringTable) -> Option<R>,
+    {
+        self.find(&self.language, &mut func).or_else(|| {
+            self.fallback_language
+                .as_ref()
+                .and_then(|fallback| self.find(fallback, &mut func))
+        })
+    }
+}
+
+/// Localization is a set of string tables with a currently selected language. It is a shared handle, which
+/// means that its clones point to the same data, so changing the language on one of the clones affects all
+/// of them. Every [`crate::UserInterface`] has its own instance, which is used by [`crate::localized_text::LocalizedText`]
+/// widgets. If you have multiple user interfaces, share one instance between them using
+/// [`crate::UserInterface::set_localization`].
+///
+/// Every modification of the localization (changing language or adding/removing string tables) increases
+/// its generation counter, which could be used to find when localized strings need to be fetched again.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_resource::{untyped::ResourceKind, Resource};
+/// use fyrox_ui::localization::{Localization, StringTable};
+///
+/// let mut english = StringTable::new("en");
+/// english.set("greeting", "Hello, {name}!");
+/// let mut german = StringTable::new("de");
+/// german.set("greeting", "Hallo, {name}!");
+///
+/// let localization = Localization::default();
+/// localization.add_table(Resource::new_ok(ResourceKind::Embedded, english));
+/// localization.add_table(Resource::new_ok(ResourceKind::Embedded, german));
+///
+/// localization.set_language("de");
+/// assert_eq!(
+///     localization.format("greeting", None, &[("name", "Welt")]).as_deref(),
+///     Some("Hallo, Welt!")
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct Localization {
+    state: Arc<Mutex<LocalizationState>>,
+}
+
+impl Debug for Localization {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let state = self.state.lock();
+        f.debug_struct("Localization")
+            .field("Language", &state.language)
+            .field("FallbackLanguage", &state.fallback_language)
+            .field("TableCount", &state.tables.len())
+            .finish()
+    }
+}
+
+impl PartialEq for Localization {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+impl Localization {
+    /// Adds a new string table to the localization. The table could be still loading, it will be used
+    /// as soon as it is loaded.
+    pub fn add_table(&self, table: StringTableResource) {
+        let mut state = self.state.lock();
+        state.tables.push(table);
+        state.generation += 1;
+    }
+
+    /// Removes the given string table from the localization.
+    pub fn remove_table(&self, table: &StringTableResource) {
+        let mut state = self.state.lock();
+        state.tables.retain(|t| t != table);
+        state.generation += 1;
+    }
+
+    /// Returns a list of all string tables of the localization.
+    pub fn tables(&self) -> Vec<StringTableResource> {
+        self.state.lock().tables.clone()
+    }
+
+    /// Returns a sorted list of languages of all loaded string tables.
+    pub fn languages(&self) -> Vec<String> {
+        let state = self.state.lock();
+        let mut languages = Vec::new();
+        for table in state.tables.iter() {
+            if let Some(table) = table.state().data() {
+                if !languages.contains(&table.language) {
+                    languages.push(table.language.clone());
+                }
+            }
+        }
+        languages.sort();
+        languages
+    }
+
+    /// Sets current language of the localization. All [`crate::localized_text::LocalizedText`] widgets
+    /// will be automatically updated to use the new language.
+    pub fn set_language<S: AsRef<str>>(&self, language: S) {
+        let mut state = self.state.lock();
+        if state.language != language.as_ref() {
+            state.language = language.as_ref().to_owned();
+            state.generation += 1;
+        }
+    }
+
+    /// Returns current language of the localization.
+    pub fn language(&self) -> String {
+        self.state.lock().language.clone()
+    }
+
+    /// Sets a language, that will be used if a string is missing in the current language.
+    pub fn set_fallback_language(&self, language: Option<String>) {
+        let mut state = self.state.lock();
+        if state.fallback_language != language {
+            state.fallback_language = language;
+            state.generation += 1;
+        }
+    }
+
+    /// Returns current fallback language of the localization.
+    pub fn fallback_language(&self) -> Option<String> {
+        self.state.lock().fallback_language.clone()
+    }
+
+    /// Returns current generation of the localization. See [`Localization`] docs for more info.
+    pub fn generation(&self) -> u64 {
+        self.state.lock().generation
+    }
+
+    /// Returns a string with the given key in current language (or in the fallback language, if the
+    /// string is missing).
+    pub fn translate(&self, key: &str) -> Option<String> {
+        self.state
+            .lock()
+            .find_with_fallback(|table| table.get(key).map(|s| s.to_owned()))
+    }
+
+    /// Returns a plural form of a string with the given key in current language (or in the fallback
+    /// language, if the string is missing).
+    pub fn translate_plural(&self, key: &str, count: u64) -> Option<String> {
+        self.state
+            .lock()
+            .find_with_fallback(|table| table.get_plural(key, count).map(|s| s.to_owned()))
+    }
+
+    /// Returns a string with the given key with all the parameters substituted (see [`format_string`]).
+    /// If `count` is specified, then a respective plural form is selected and `{count}` parameter is
+    /// substituted automatically.
+    pub fn format(&self, key: &str, count: Option<u64>, args: &[(&str, &str)]) -> Option<String> {
+        match count {
+            Some(count) => {
+                let template = self.translate_plural(key, count)?;
+                let count = count.to_string();
+                let mut args = args.to_vec();
+                args.push(("count", &count));
+                Some(format_string(&template, &args))
+            }
+            None => {
+                let template = self.translate(key)?;
+                Some(format_string(&template, args))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::localization::{format_string, PluralRule, StringTable};
+
+    #[test]
+    fn test_plural_rules() {
+        let forms = |rule: PluralRule| {
+            [0, 1, 2, 5, 11, 12, 21, 22, 25, 101]
+                .iter()
+                .map(|n| rule.form_index(*n))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(forms(PluralRule::Invariant), [0; 10]);
+        assert_eq!(forms(PluralRule::OneOther), [1, 0, 1, 1, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(
+            forms(PluralRule::ZeroOneOther),
+            [0, 0, 1, 1, 1, 1, 1, 1, 1, 1]
+        );
+        assert_eq!(
+            forms(PluralRule::EastSlavic),
+            [2, 0, 1, 2, 2, 2, 0, 1, 2, 0]
+        );
+        assert_eq!(forms(PluralRule::Polish), [2, 0, 1, 2, 2, 2, 2, 1, 2, 2]);
+        assert_eq!(forms(PluralRule::Czech), [2, 0, 1, 2, 2, 2, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn test_format_string() {
+        assert_eq!(
+            format_string("{a}, {b}!", &[("a", "x"), ("b", "y")]),
+            "x, y!"
+        );
+        assert_eq!(format_string("{{a}} {a}", &[("a", "x")]), "{a} x");
+        assert_eq!(format_string("{unknown} {", &[]), "{unknown} {");
+        assert_eq!(format_string("{имя}ж", &[("имя", "Ø")]), "Øж");
+    }
+
+    #[test]
+    fn test_missing_keys() {
+        let mut reference = StringTable::new("en");
+        reference.set("a", "A");
+        reference.set("b", "B");
+        reference.set("c", "C");
+        let mut table = StringTable::new("de");
+        table.set("a", "A");
+        table.set("c", "");
+        assert_eq!(table.missing_keys(&reference), ["b", "c"]);
+    }
+}
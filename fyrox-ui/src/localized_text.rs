@@ -0,0 +1,294 @@
+//! Localized text is a text widget, that shows a string from the localization of the user interface. See
+//! [`LocalizedText`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{
+        pool::Handle, reflect::prelude::*, type_traits::prelude::*, variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    define_constructor,
+    font::FontResource,
+    formatted_text::WrapMode,
+    message::{MessageDirection, UiMessage},
+    text::{TextBuilder, TextMessage},
+    widget::{Widget, WidgetBuilder},
+    BuildContext, Control, HorizontalAlignment, UiNode, UserInterface, VerticalAlignment,
+};
+use fyrox_core::uuid_provider;
+use std::ops::{Deref, DerefMut};
+
+/// A named argument of a localized string, see [`crate::localization::format_string`] for more info.
+#[derive(Default, Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct LocalizedTextArgument {
+    /// Name of the argument (without curly braces).
+    pub name: String,
+    /// Value of the argument.
+    pub value: String,
+}
+
+impl LocalizedTextArgument {
+    /// Creates a new argument.
+    pub fn new<N: AsRef<str>, V: AsRef<str>>(name: N, value: V) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            value: value.as_ref().to_owned(),
+        }
+    }
+}
+
+/// A set of messages that can be used to modify the state of a localized text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LocalizedTextMessage {
+    /// Sets a new key of the string.
+    Key(String),
+    /// Sets a new number of items, that is used to select a plural form of the string.
+    Count(Option<u64>),
+    /// Sets new arguments of the string.
+    Arguments(Vec<LocalizedTextArgument>),
+}
+
+impl LocalizedTextMessage {
+    define_constructor!(
+        /// Creates [`LocalizedTextMessage::Key`].
+        LocalizedTextMessage:Key => fn key(String), layout: false
+    );
+    define_constructor!(
+        /// Creates [`LocalizedTextMessage::Count`].
+        LocalizedTextMessage:Count => fn count(Option<u64>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`LocalizedTextMessage::Arguments`].
+        LocalizedTextMessage:Arguments => fn arguments(Vec<LocalizedTextArgument>), layout: false
+    );
+}
+
+/// Localized text is a text widget, that shows a string with the given key from the localization of the
+/// user interface (see [`UserInterface::localization`]). The text is automatically updated when the language
+/// is changed. If the string is missing, the key itself is shown instead.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     localized_text::{LocalizedTextArgument, LocalizedTextBuilder},
+/// #     widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// fn create_apples_counter(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     // Assuming that the string table contains the following string:
+///     // "apples" => "{name} has {count} apple|{name} has {count} apples"
+///     LocalizedTextBuilder::new(WidgetBuilder::new())
+///         .with_key("apples")
+///         .with_count(3)
+///         .with_argument(LocalizedTextArgument::new("name", "Bob"))
+///         .build(ctx)
+/// }
+/// ```
+#[derive(Default, Clone, Debug, Visit, Reflect, ComponentProvider)]
+pub struct LocalizedText {
+    /// Base widget of the localized text.
+    pub widget: Widget,
+    /// Handle of an inner text widget, that is used to show the string.
+    pub text: InheritableVariable<Handle<UiNode>>,
+    /// A key of the string in string tables.
+    pub key: InheritableVariable<String>,
+    /// Number of items, that is used to select a plural form of the string.
+    pub count: InheritableVariable<Option<u64>>,
+    /// Named arguments, that will be substituted into the string.
+    pub arguments: InheritableVariable<Vec<LocalizedTextArgument>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    generation: Option<u64>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    resolved: bool,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    shown_text: String,
+}
+
+crate::define_widget_deref!(LocalizedText);
+
+uuid_provider!(LocalizedText = "0e5890a8-19b4-4521-a791-c75df7a454d4");
+
+impl LocalizedText {
+    fn invalidate(&mut self) {
+        self.generation = None;
+    }
+}
+
+impl Control for LocalizedText {
+    fn update(&mut self, _dt: f32, ui: &mut UserInterface) {
+        let localization = ui.localization();
+        let generation = localization.generation();
+        // Missing strings are requested on every update, because their string tables could still be loading.
+        if self.generation == Some(generation) && self.resolved {
+            return;
+        }
+        self.generation = Some(generation);
+
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|arg| (arg.name.as_str(), arg.value.as_str()))
+            .collect::<Vec<_>>();
+        let text = localization.format(&self.key, *self.count, &arguments);
+        self.resolved = text.is_some();
+        let text = text.unwrap_or_else(|| (*self.key).clone());
+
+        if text != self.shown_text {
+            ui.send_message(TextMessage::text(
+                *self.text,
+                MessageDirection::ToWidget,
+                text.clone(),
+            ));
+            self.shown_text = text;
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if message.destination() == self.handle && message.direction() == MessageDirection::ToWidget
+        {
+            if let Some(msg) = message.data::<LocalizedTextMessage>() {
+                match msg {
+                    LocalizedTextMessage::Key(key) => {
+                        if &*self.key != key {
+                            self.key.set_value_and_mark_modified(key.clone());
+                            self.invalidate();
+                        }
+                    }
+                    LocalizedTextMessage::Count(count) => {
+                        if &*self.count != count {
+                            self.count.set_value_and_mark_modified(*count);
+                            self.invalidate();
+                        }
+                    }
+                    LocalizedTextMessage::Arguments(arguments) => {
+                        if &*self.arguments != arguments {
+                            self.arguments
+                                .set_value_and_mark_modified(arguments.clone());
+                            self.invalidate();
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Localized text builder creates localized text instances and adds them to the UI.
+pub struct LocalizedTextBuilder {
+    widget_builder: WidgetBuilder,
+    key: String,
+    count: Option<u64>,
+    arguments: Vec<LocalizedTextArgument>,
+    font: Option<FontResource>,
+    font_size: f32,
+    vertical_text_alignment: VerticalAlignment,
+    horizontal_text_alignment: HorizontalAlignment,
+    wrap: WrapMode,
+}
+
+impl LocalizedTextBuilder {
+    /// Creates new builder instance.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            key: Default::default(),
+            count: None,
+            arguments: Default::default(),
+            font: None,
+            font_size: 14.0,
+            vertical_text_alignment: VerticalAlignment::Top,
+            horizontal_text_alignment: HorizontalAlignment::Left,
+            wrap: WrapMode::NoWrap,
+        }
+    }
+
+    /// Sets the desired key of the string.
+    pub fn with_key<S: AsRef<str>>(mut self, key: S) -> Self {
+        self.key = key.as_ref().to_owned();
+        self
+    }
+
+    /// Sets the desired number of items, that will be used to select a plural form of the string.
+    pub fn with_count(mut self, count: u64) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    /// Adds a new named argument, that will be substituted into the string.
+    pub fn with_argument(mut self, argument: LocalizedTextArgument) -> Self {
+        self.arguments.push(argument);
+        self
+    }
+
+    /// Sets the desired named arguments, that will be substituted into the string.
+    pub fn with_arguments(mut self, arguments: Vec<LocalizedTextArgument>) -> Self {
+        self.arguments = arguments;
+        self
+    }
+
+    /// Sets the desired font of the text.
+    pub fn with_font(mut self, font: FontResource) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Sets the desired height of the text.
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the desired vertical alignment of the text.
+    pub fn with_vertical_text_alignment(mut self, valign: VerticalAlignment) -> Self {
+        self.vertical_text_alignment = valign;
+        self
+    }
+
+    /// Sets the desired horizontal alignment of the text.
+    pub fn with_horizontal_text_alignment(mut self, halign: HorizontalAlignment) -> Self {
+        self.horizontal_text_alignment = halign;
+        self
+    }
+
+    /// Sets the desired word wrapping mode of the text.
+    pub fn with_wrap(mut self, wrap: WrapMode) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Finishes localized text creation and adds the new instance to the user interface.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let text = TextBuilder::new(WidgetBuilder::new())
+            .with_opt_font(self.font)
+            .with_font_size(self.font_size)
+            .with_vertical_text_alignment(self.vertical_text_alignment)
+            .with_horizontal_text_alignment(self.horizontal_text_alignment)
+            .with_wrap(self.wrap)
+            .build(ctx);
+
+        let localized_text = LocalizedText {
+            widget: self
+                .widget_builder
+                .with_need_update(true)
+                .with_child(text)
+                .build(),
+            text: text.into(),
+            key: self.key.into(),
+            count: self.count.into(),
+            arguments: self.arguments.into(),
+            generation: None,
+            resolved: false,
+            shown_text: Default::default(),
+        };
+
+        ctx.add_node(UiNode::new(localized_text))
+    }
+}
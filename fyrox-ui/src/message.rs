@@ -440,6 +440,59 @@ pub enum ButtonState {
     Released,
 }
 
+/// A set of possible gamepad buttons. Names of the face buttons are position-based (Xbox `A` or
+/// PlayStation `Cross` is [`GamepadButton::South`]), so they're the same for any kind of gamepad.
+#[derive(Debug, Hash, Ord, PartialOrd, PartialEq, Eq, Clone, Copy, Default, Visit, Reflect)]
+pub enum GamepadButton {
+    /// Bottom face button (Xbox `A`, PlayStation `Cross`). It is used to activate focused widgets.
+    #[default]
+    South,
+    /// Right face button (Xbox `B`, PlayStation `Circle`).
+    East,
+    /// Top face button (Xbox `Y`, PlayStation `Triangle`).
+    North,
+    /// Left face button (Xbox `X`, PlayStation `Square`).
+    West,
+    /// Up button of the directional pad.
+    DPadUp,
+    /// Down button of the directional pad.
+    DPadDown,
+    /// Left button of the directional pad.
+    DPadLeft,
+    /// Right button of the directional pad.
+    DPadRight,
+    /// Left shoulder button.
+    LeftShoulder,
+    /// Right shoulder button.
+    RightShoulder,
+    /// Left trigger.
+    LeftTrigger,
+    /// Right trigger.
+    RightTrigger,
+    /// Select (Back, Share) button.
+    Select,
+    /// Start (Menu, Options) button.
+    Start,
+    /// Left stick button.
+    LeftThumb,
+    /// Right stick button.
+    RightThumb,
+}
+
+/// A set of possible gamepad axes.
+#[derive(Debug, Hash, Ord, PartialOrd, PartialEq, Eq, Clone, Copy, Default, Visit, Reflect)]
+pub enum GamepadAxis {
+    /// Horizontal axis of the left stick, positive values point to the right.
+    #[default]
+    LeftStickX,
+    /// Vertical axis of the left stick, positive values point up.
+    LeftStickY,
+    /// Horizontal axis of the right stick, positive values point to the right.
+    RightStickX,
+    /// Vertical axis of the right stick, positive values point up.
+    RightStickY,
+}
+
 /// A set of possible mouse buttons.
 #[derive(Debug, Hash, Ord, PartialOrd, PartialEq, Eq, Clone, Copy, Default, Visit, Reflect)]
 pub enum MouseButton {
@@ -569,6 +622,21 @@ pub enum OsEvent {
         /// Unique touch event identifier to distinguish between fingers, for example
         id: u64,
     },
+    /// Gamepad button event. Directional pad buttons move keyboard focus between widgets, the south
+    /// button activates focused widget.
+    GamepadButton {
+        /// Gamepad button.
+        button: GamepadButton,
+        /// Button state.
+        state: ButtonState,
+    },
+    /// Gamepad axis event. Left stick moves keyboard focus between widgets.
+    GamepadAxis {
+        /// Gamepad axis.
+        axis: GamepadAxis,
+        /// New value of the axis in `[-1; 1]` range.
+        value: f32,
+    },
 }
 
 /// A set of possible keyboard modifiers.
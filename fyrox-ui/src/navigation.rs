@@ -1,17 +1,20 @@
 //! A widget, that handles keyboard navigation on its descendant widgets using Tab key. See [`NavigationLayer`]
-//! docs for more info and usage examples.
+//! docs for more info and usage examples. This module also contains directional (gamepad) focus navigation,
+//! see [`NavigationDirection`] and [`UserInterface::move_focus`].
 
 #![warn(missing_docs)]
 
 use crate::{
+    brush::Brush,
+    core::algebra::Vector2,
     core::{
         pool::Handle, reflect::prelude::*, type_traits::prelude::*, variable::InheritableVariable,
         visitor::prelude::*,
     },
-    message::{KeyCode, MessageDirection, UiMessage},
+    message::{GamepadButton, KeyCode, MessageDirection, UiMessage},
     scroll_viewer::{ScrollViewer, ScrollViewerMessage},
     widget::{Widget, WidgetBuilder, WidgetMessage},
-    BuildContext, Control, UiNode, UserInterface,
+    BuildContext, Control, UiNode, UserInterface, COLOR_BRIGHT_BLUE,
 };
 use fyrox_graph::{BaseSceneGraph, SceneGraph};
 use std::ops::{Deref, DerefMut};
@@ -166,3 +169,148 @@ impl NavigationLayerBuilder {
         ui.add_node(UiNode::new(navigation_layer))
     }
 }
+
+/// A direction of focus movement, that is used for directional (gamepad) navigation. See
+/// [`UserInterface::move_focus`] for more info.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NavigationDirection {
+    /// Moves focus to the closest widget above the focused one.
+    Up,
+    /// Moves focus to the closest widget below the focused one.
+    Down,
+    /// Moves focus to the closest widget to the left of the focused one.
+    Left,
+    /// Moves focus to the closest widget to the right of the focused one.
+    Right,
+}
+
+impl NavigationDirection {
+    /// Maps the given gamepad button to a navigation direction. Only directional pad buttons have
+    /// a direction, any other button will give `None`.
+    pub fn from_gamepad_button(button: GamepadButton) -> Option<Self> {
+        match button {
+            GamepadButton::DPadUp => Some(Self::Up),
+            GamepadButton::DPadDown => Some(Self::Down),
+            GamepadButton::DPadLeft => Some(Self::Left),
+            GamepadButton::DPadRight => Some(Self::Right),
+            _ => None,
+        }
+    }
+
+    /// Maps the given stick position to a navigation direction. The dominant axis of the stick
+    /// defines the direction, positive `y` values point up. Returns `None` if the stick is inside
+    /// the given dead zone.
+    pub fn from_stick(stick: Vector2<f32>, dead_zone: f32) -> Option<Self> {
+        if stick.x.abs().max(stick.y.abs()) < dead_zone {
+            None
+        } else if stick.x.abs() > stick.y.abs() {
+            Some(if stick.x > 0.0 {
+                Self::Right
+            } else {
+                Self::Left
+            })
+        } else {
+            Some(if stick.y > 0.0 { Self::Up } else { Self::Down })
+        }
+    }
+
+    /// Returns a unit vector of the direction in screen space (`y` axis points down).
+    pub fn screen_space_vector(self) -> Vector2<f32> {
+        match self {
+            Self::Up => Vector2::new(0.0, -1.0),
+            Self::Down => Vector2::new(0.0, 1.0),
+            Self::Left => Vector2::new(-1.0, 0.0),
+            Self::Right => Vector2::new(1.0, 0.0),
+        }
+    }
+}
+
+/// Defines the look of the indicator, that is drawn around a widget with keyboard focus. The indicator
+/// is drawn only for widgets that accepts input (see [`Widget::accepts_input`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FocusIndicatorStyle {
+    /// Whether the indicator should be drawn or not.
+    pub enabled: bool,
+    /// A brush, that will be used to draw the indicator.
+    pub brush: Brush,
+    /// Thickness of the indicator's border.
+    pub thickness: f32,
+    /// Corner radius of the indicator.
+    pub corner_radius: f32,
+    /// Distance between widget bounds and the indicator.
+    pub offset: f32,
+}
+
+impl Default for FocusIndicatorStyle {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            brush: Brush::Solid(COLOR_BRIGHT_BLUE),
+            thickness: 1.0,
+            corner_radius: 2.0,
+            offset: 1.0,
+        }
+    }
+}
+
+fn is_globally_enabled(ui: &UserInterface, mut handle: Handle<UiNode>) -> bool {
+    while let Some(node) = ui.try_get(handle) {
+        if !node.enabled() {
+            return false;
+        }
+        handle = node.parent();
+    }
+    true
+}
+
+/// Searches for a widget, that should receive keyboard focus when it is moved from the `from` widget in the
+/// given direction. Only visible, enabled widgets that accepts input are considered. If there's an active
+/// picking restriction (a modal window or an opened menu for example), only its descendants are considered.
+/// If `from` is not set or it does not accept input, the top-left-most widget will be selected. Returns [`Handle::NONE`] if there's no
+/// suitable widget.
+pub fn find_focus_candidate(
+    ui: &UserInterface,
+    from: Handle<UiNode>,
+    direction: NavigationDirection,
+) -> Handle<UiNode> {
+    let restriction = ui.top_picking_restriction().map(|entry| entry.handle);
+    // Containers (like the root canvas) could have focus too, but they're not an appropriate origin.
+    let origin = ui
+        .try_get(from)
+        .filter(|node| node.accepts_input)
+        .map(|node| node.screen_bounds().center());
+    let dir = direction.screen_space_vector();
+
+    let mut best = None;
+    for (handle, node) in ui.nodes().pair_iter() {
+        if handle == from
+            || !node.accepts_input
+            || !node.is_globally_visible()
+            || !is_globally_enabled(ui, handle)
+            || restriction.is_some_and(|root| root != handle && !ui.is_node_child_of(handle, root))
+        {
+            continue;
+        }
+
+        let center = node.screen_bounds().center();
+        let score = if let Some(origin) = origin {
+            let offset = center - origin;
+            let distance = offset.dot(&dir);
+            if distance <= f32::EPSILON {
+                continue;
+            }
+            // Lateral offset is penalized more, so the widgets that are "in line" with the focused one
+            // will be preferred.
+            let lateral = (offset - dir.scale(distance)).norm();
+            distance + 2.0 * lateral
+        } else {
+            center.x + center.y
+        };
+
+        if best.map_or(true, |(_, best_score)| score < best_score) {
+            best = Some((handle, score));
+        }
+    }
+
+    best.map(|(handle, _)| handle).unwrap_or_default()
+}
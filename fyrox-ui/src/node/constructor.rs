@@ -21,6 +21,7 @@ use crate::{
     inspector::Inspector,
     key::{HotKeyEditor, KeyBindingEditor},
     list_view::{ListView, ListViewItem},
+    localized_text::LocalizedText,
     menu::{ContextMenu, Menu, MenuItem},
     messagebox::MessageBox,
     nine_patch::NinePatch,
@@ -44,6 +45,7 @@ use crate::{
     uuid::UuidEditor,
     vec::VecEditor,
     vector_image::VectorImage,
+    virtual_controls::{dpad::VirtualDPad, joystick::VirtualJoystick, touch_button::TouchButton},
     window::Window,
     wrap_panel::WrapPanel,
     Control, UiNode,
@@ -103,6 +105,7 @@ impl WidgetConstructorContainer {
         container.add::<KeyBindingEditor>();
         container.add::<ListViewItem>();
         container.add::<ListView>();
+        container.add::<LocalizedText>();
         container.add::<Menu>();
         container.add::<MenuItem>();
         container.add::<ContextMenu>();
@@ -155,6 +158,9 @@ impl WidgetConstructorContainer {
         container.add::<UuidEditor>();
 
         container.add::<VectorImage>();
+        container.add::<VirtualJoystick>();
+        container.add::<VirtualDPad>();
+        container.add::<TouchButton>();
         container.add::<Window>();
         container.add::<WrapPanel>();
         container.add::<ColorGradientField>();
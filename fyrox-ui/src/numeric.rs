@@ -10,7 +10,7 @@ use crate::{
         color::Color,
         num_traits::{clamp, Bounded, NumAssign, NumCast, NumOps},
         pool::Handle,
-        reflect::{prelude::*, Reflect},
+        reflect::{prelude::*, PropertyUnit, Reflect},
         type_traits::prelude::*,
         visitor::prelude::*,
     },
@@ -271,6 +271,9 @@ pub struct NumericUpDown<T: NumericType> {
     pub drag_context: Option<DragContext<T>>,
     /// Defines how movement in Y axis will be translated in the actual value change. It is some sort of a scaling modifier.
     pub drag_value_scaling: InheritableVariable<f32>,
+    /// Optional unit of the value. If set, the unit suffix is shown next to the value and the values typed in
+    /// other units (for example `cm` for meters) are converted automatically.
+    pub unit: InheritableVariable<Option<PropertyUnit>>,
 }
 
 impl<T: NumericType> Deref for NumericUpDown<T> {
@@ -298,7 +301,7 @@ impl<T: NumericType> NumericUpDown<T> {
         let msg = TextMessage::text(
             *self.field,
             MessageDirection::ToWidget,
-            format!("{:.1$}", *self.value, *self.precision),
+            format_value(*self.value, *self.precision, *self.unit),
         );
         msg.set_handled(true);
         ui.send_message(msg);
@@ -318,7 +321,7 @@ impl<T: NumericType> NumericUpDown<T> {
     fn try_parse_value(&mut self, ui: &UserInterface) {
         // Parse input only when focus is lost from text field.
         if let Some(field) = ui.node(*self.field).cast::<TextBox>() {
-            if let Ok(value) = field.text().parse::<T>() {
+            if let Some(value) = parse_value::<T>(&field.text(), *self.unit) {
                 // If the value we got from the text box has changed since the last time
                 // we parsed it, then the value has been edited through the text box,
                 // and the change was meaningful enough to change the result of parsing.
@@ -339,6 +342,39 @@ impl<T: NumericType> NumericUpDown<T> {
     }
 }
 
+fn format_value<T: NumericType>(value: T, precision: usize, unit: Option<PropertyUnit>) -> String {
+    match unit {
+        Some(unit) => format!("{:.1$}{2}", value, precision, unit.suffix()),
+        None => format!("{:.1$}", value, precision),
+    }
+}
+
+fn parse_value<T: NumericType>(text: &str, unit: Option<PropertyUnit>) -> Option<T> {
+    match unit {
+        Some(unit) => {
+            let text = text.trim();
+            // Fast path for values in the unit of the widget, it keeps the precision of integers.
+            match text
+                .strip_suffix(unit.suffix())
+                .unwrap_or(text)
+                .trim()
+                .parse::<T>()
+            {
+                Ok(value) => Some(value),
+                Err(_) => unit.parse(text).and_then(NumCast::from),
+            }
+        }
+        None => text.parse::<T>().ok(),
+    }
+}
+
+fn unit_drag_value_scaling(unit: PropertyUnit) -> f32 {
+    match unit {
+        PropertyUnit::Meters | PropertyUnit::Degrees | PropertyUnit::Seconds => 0.5,
+        PropertyUnit::Percent => 0.25,
+    }
+}
+
 fn saturating_sub<T>(a: T, b: T) -> T
 where
     T: NumericType,
@@ -449,17 +485,17 @@ impl<T: NumericType> Control for NumericUpDown<T> {
                                 ui.send_message(TextMessage::text(
                                     *self.field,
                                     MessageDirection::ToWidget,
-                                    format!(
-                                        "{:.1$}",
+                                    format_value(
                                         calculate_value_by_offset(
                                             *start_value,
                                             ((*start_mouse_pos - pos.y) * *self.drag_value_scaling)
                                                 as i32,
                                             *self.step,
                                             *self.min_value,
-                                            *self.max_value
+                                            *self.max_value,
                                         ),
-                                        *self.precision
+                                        *self.precision,
+                                        *self.unit,
                                     ),
                                 ));
                             }
@@ -586,6 +622,7 @@ pub struct NumericUpDownBuilder<T: NumericType> {
     precision: usize,
     editable: bool,
     drag_value_scaling: f32,
+    unit: Option<PropertyUnit>,
 }
 
 fn make_button(
@@ -634,6 +671,7 @@ impl<T: NumericType> NumericUpDownBuilder<T> {
             precision: 3,
             editable: true,
             drag_value_scaling: 0.1,
+            unit: None,
         }
     }
 
@@ -689,6 +727,17 @@ impl<T: NumericType> NumericUpDownBuilder<T> {
         self
     }
 
+    /// Sets the desired unit of the value. The unit suffix will be shown next to the value and the values typed in
+    /// other units will be converted to this unit. This method also sets drag value scaling, that is appropriate for
+    /// the unit, use [`Self::with_drag_value_scaling`] after this method to override it.
+    pub fn with_unit(mut self, unit: Option<PropertyUnit>) -> Self {
+        if let Some(unit) = unit {
+            self.drag_value_scaling = unit_drag_value_scaling(unit);
+        }
+        self.unit = unit;
+        self
+    }
+
     /// Finishes [`NumericUpDown`] widget creation and adds the new instance to the user interface and returns a handle to it.
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let increase;
@@ -706,6 +755,7 @@ impl<T: NumericType> NumericUpDownBuilder<T> {
 
         let text = format!("{:.1$}", self.value, self.precision);
         let formatted_value = text.parse::<T>().unwrap_or(self.value);
+        let text = format_value(self.value, self.precision, self.unit);
         let grid = GridBuilder::new(
             WidgetBuilder::new()
                 .with_child({
@@ -763,6 +813,7 @@ impl<T: NumericType> NumericUpDownBuilder<T> {
             precision: self.precision.into(),
             drag_context: None,
             drag_value_scaling: self.drag_value_scaling.into(),
+            unit: self.unit.into(),
         };
 
         ctx.add_node(UiNode::new(node))
@@ -771,7 +822,10 @@ impl<T: NumericType> NumericUpDownBuilder<T> {
 
 #[cfg(test)]
 mod test {
-    use crate::numeric::{saturating_add, saturating_sub};
+    use crate::{
+        core::reflect::PropertyUnit,
+        numeric::{format_value, parse_value, saturating_add, saturating_sub},
+    };
 
     #[test]
     fn test_saturating_add() {
@@ -817,4 +871,40 @@ mod test {
         assert_eq!(saturating_sub(f32::MIN, 1.0), f32::MIN);
         assert_eq!(saturating_sub(f32::MAX, 1.0), f32::MAX - 1.0);
     }
+
+    #[test]
+    fn test_unit_format_and_parse() {
+        assert_eq!(format_value(1.5f32, 2, Some(PropertyUnit::Meters)), "1.50m");
+        assert_eq!(format_value(1.5f32, 2, None), "1.50");
+
+        assert_eq!(
+            parse_value::<f32>("1.50m", Some(PropertyUnit::Meters)),
+            Some(1.5)
+        );
+        assert_eq!(
+            parse_value::<f32>("25cm", Some(PropertyUnit::Meters)),
+            Some(0.25)
+        );
+        assert_eq!(
+            parse_value::<f32>("5mm", Some(PropertyUnit::Meters)),
+            Some(0.005)
+        );
+        assert_eq!(
+            parse_value::<f32>("90°", Some(PropertyUnit::Degrees)),
+            Some(90.0)
+        );
+        assert_eq!(
+            parse_value::<f32>("45deg", Some(PropertyUnit::Degrees)),
+            Some(45.0)
+        );
+        assert_eq!(
+            parse_value::<i32>("50%", Some(PropertyUnit::Percent)),
+            Some(50)
+        );
+        assert_eq!(parse_value::<f32>("25cm", None), None);
+        assert_eq!(
+            parse_value::<f32>("10deg", Some(PropertyUnit::Meters)),
+            None
+        );
+    }
 }
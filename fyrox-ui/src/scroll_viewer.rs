@@ -234,6 +234,27 @@ impl Control for ScrollViewer {
                     ));
                 }
             }
+        } else if let Some(WidgetMessage::Pan { delta }) = message.data::<WidgetMessage>() {
+            if !message.handled() {
+                // Content follows user's finger.
+                for (scroll_bar, offset) in
+                    [(self.h_scroll_bar, delta.x), (self.v_scroll_bar, delta.y)]
+                {
+                    if let Some(scroll_bar) = ui.node(scroll_bar).cast::<ScrollBar>() {
+                        let old_value = *scroll_bar.value;
+                        let new_value =
+                            (old_value - offset).clamp(*scroll_bar.min, *scroll_bar.max);
+                        if (old_value - new_value).abs() > f32::EPSILON {
+                            message.set_handled(true);
+                        }
+                        ui.send_message(ScrollBarMessage::value(
+                            scroll_bar.handle,
+                            MessageDirection::ToWidget,
+                            new_value,
+                        ));
+                    }
+                }
+            }
         } else if let Some(msg) = message.data::<ScrollPanelMessage>() {
             if message.destination() == self.scroll_panel {
                 let msg = match *msg {
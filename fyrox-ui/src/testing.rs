@@ -0,0 +1,300 @@
+//! Headless test harness for widgets. It wraps a [`UserInterface`] and allows you to emulate user
+//! input (mouse and keyboard), run layout and check the messages, that were emitted by widgets in
+//! response. It does not need a window or a graphics context, so it could be used in unit tests:
+//!
+//! ```rust
+//! # use fyrox_ui::{
+//! #     button::{ButtonBuilder, ButtonMessage},
+//! #     core::algebra::Vector2,
+//! #     testing::UiTestHarness,
+//! #     widget::WidgetBuilder,
+//! # };
+//! let mut harness = UiTestHarness::new(Vector2::new(200.0, 200.0));
+//! let button = ButtonBuilder::new(WidgetBuilder::new().with_width(50.0).with_height(20.0))
+//!     .build(&mut harness.build_ctx());
+//! harness.update();
+//!
+//! harness.click_widget(button);
+//! assert!(harness.find_message::<ButtonMessage>(button).is_some());
+//! ```
+//!
+//! Every message, that was processed by the user interface, is recorded by the harness until it is
+//! cleared by [`UiTestHarness::clear_messages`] or taken by [`UiTestHarness::take_messages`].
+
+use crate::{
+    core::{algebra::Vector2, pool::Handle},
+    message::{
+        ButtonState, KeyCode, KeyboardModifiers, MessageData, MessageDirection, MouseButton,
+        OsEvent, UiMessage,
+    },
+    BuildContext, UiNode, UiUpdateSwitches, UserInterface,
+};
+use fyrox_graph::BaseSceneGraph;
+
+/// See [module docs](self).
+pub struct UiTestHarness {
+    /// The user interface under test. It could be used directly, but every message sent to it
+    /// should be processed by [`UiTestHarness::process_messages`] to be recorded.
+    pub ui: UserInterface,
+    screen_size: Vector2<f32>,
+    messages: Vec<UiMessage>,
+}
+
+impl UiTestHarness {
+    /// Creates a new harness with an empty user interface of the given size.
+    pub fn new(screen_size: Vector2<f32>) -> Self {
+        Self {
+            ui: UserInterface::new(screen_size),
+            screen_size,
+            messages: Default::default(),
+        }
+    }
+
+    /// Returns build context of the user interface, it should be used to create widgets.
+    pub fn build_ctx(&mut self) -> BuildContext<'_> {
+        self.ui.build_ctx()
+    }
+
+    /// Processes every pending message, updates layout and draws the user interface, so widgets
+    /// get their final bounds and could be picked by the cursor. Any messages, that were produced
+    /// during the update, are processed as well.
+    pub fn update(&mut self) {
+        self.update_with_dt(0.0);
+    }
+
+    /// Same as [`Self::update`], but also advances time by the given amount of seconds. It is
+    /// useful to test widgets with animations or timers.
+    pub fn update_with_dt(&mut self, dt: f32) {
+        self.process_messages();
+        self.ui
+            .update(self.screen_size, dt, &UiUpdateSwitches::default());
+        self.ui.draw();
+        self.process_messages();
+    }
+
+    /// Processes every pending message of the user interface and records them.
+    pub fn process_messages(&mut self) {
+        while let Some(message) = self.ui.poll_message() {
+            self.messages.push(message);
+        }
+    }
+
+    /// Sends the given message and processes it, as well as any messages, that were produced in
+    /// response.
+    pub fn send_message(&mut self, message: UiMessage) {
+        self.ui.send_message(message);
+        self.process_messages();
+    }
+
+    /// Passes the given event to the user interface and processes the messages, that were
+    /// produced by it. Returns `true` if the event was handled by the user interface.
+    pub fn process_os_event(&mut self, event: OsEvent) -> bool {
+        let handled = self.ui.process_os_event(&event);
+        self.process_messages();
+        handled
+    }
+
+    /// Moves the cursor to the given position (in screen coordinates).
+    pub fn mouse_move(&mut self, position: Vector2<f32>) {
+        self.process_os_event(OsEvent::CursorMoved { position });
+    }
+
+    /// Presses the given mouse button at the current position of the cursor.
+    pub fn mouse_down(&mut self, button: MouseButton) {
+        self.process_os_event(OsEvent::MouseInput {
+            button,
+            state: ButtonState::Pressed,
+        });
+    }
+
+    /// Releases the given mouse button at the current position of the cursor.
+    pub fn mouse_up(&mut self, button: MouseButton) {
+        self.process_os_event(OsEvent::MouseInput {
+            button,
+            state: ButtonState::Released,
+        });
+    }
+
+    /// Moves the cursor to the given position and clicks the given mouse button there.
+    pub fn click_at(&mut self, position: Vector2<f32>, button: MouseButton) {
+        self.mouse_move(position);
+        self.mouse_down(button);
+        self.mouse_up(button);
+    }
+
+    /// Returns the center of the given widget in screen coordinates. The widget must be updated
+    /// at least once (see [`Self::update`]) to have valid bounds.
+    pub fn widget_center(&self, widget: Handle<UiNode>) -> Vector2<f32> {
+        self.ui.node(widget).screen_bounds().center()
+    }
+
+    /// Clicks the left mouse button at the center of the given widget.
+    pub fn click_widget(&mut self, widget: Handle<UiNode>) {
+        self.click_at(self.widget_center(widget), MouseButton::Left);
+    }
+
+    /// Clicks the given mouse button at the center of the given widget.
+    pub fn click_widget_with(&mut self, widget: Handle<UiNode>, button: MouseButton) {
+        self.click_at(self.widget_center(widget), button);
+    }
+
+    /// Drags the cursor from one position to another with the left mouse button pressed. The
+    /// cursor is moved in the given amount of steps.
+    pub fn drag(&mut self, from: Vector2<f32>, to: Vector2<f32>, steps: usize) {
+        self.mouse_move(from);
+        self.mouse_down(MouseButton::Left);
+        let steps = steps.max(1);
+        for i in 1..=steps {
+            self.mouse_move(from.lerp(&to, i as f32 / steps as f32));
+        }
+        self.mouse_up(MouseButton::Left);
+    }
+
+    /// Sets the state of keyboard modifiers (Ctrl, Alt, Shift, etc.).
+    pub fn set_modifiers(&mut self, modifiers: KeyboardModifiers) {
+        self.process_os_event(OsEvent::KeyboardModifiers(modifiers));
+    }
+
+    /// Presses the given key.
+    pub fn key_down(&mut self, key: KeyCode) {
+        self.process_os_event(OsEvent::KeyboardInput {
+            button: key,
+            state: ButtonState::Pressed,
+            text: Default::default(),
+        });
+    }
+
+    /// Releases the given key.
+    pub fn key_up(&mut self, key: KeyCode) {
+        self.process_os_event(OsEvent::KeyboardInput {
+            button: key,
+            state: ButtonState::Released,
+            text: Default::default(),
+        });
+    }
+
+    /// Presses and releases the given key.
+    pub fn press_key(&mut self, key: KeyCode) {
+        self.key_down(key);
+        self.key_up(key);
+    }
+
+    /// Types the given text into the widget, that has keyboard focus. Every character is sent as
+    /// a separate key press.
+    pub fn type_text(&mut self, text: &str) {
+        for char in text.chars() {
+            self.process_os_event(OsEvent::KeyboardInput {
+                button: KeyCode::Unknown,
+                state: ButtonState::Pressed,
+                text: char.to_string(),
+            });
+        }
+    }
+
+    /// Returns every recorded message.
+    pub fn messages(&self) -> &[UiMessage] {
+        &self.messages
+    }
+
+    /// Returns every recorded message and clears the record.
+    pub fn take_messages(&mut self) -> Vec<UiMessage> {
+        std::mem::take(&mut self.messages)
+    }
+
+    /// Clears the record of messages.
+    pub fn clear_messages(&mut self) {
+        self.messages.clear();
+    }
+
+    /// Returns data of every recorded message of the given type, that was sent from the given
+    /// widget (`FromWidget` direction).
+    pub fn find_messages<T: MessageData>(&self, widget: Handle<UiNode>) -> Vec<&T> {
+        self.messages
+            .iter()
+            .filter(|m| m.destination() == widget && m.direction() == MessageDirection::FromWidget)
+            .filter_map(|m| m.data::<T>())
+            .collect()
+    }
+
+    /// Returns data of the last recorded message of the given type, that was sent from the given
+    /// widget (`FromWidget` direction).
+    pub fn find_message<T: MessageData>(&self, widget: Handle<UiNode>) -> Option<&T> {
+        self.find_messages(widget).last().copied()
+    }
+
+    /// Checks whether the given message was recorded. Messages are compared by their destination,
+    /// direction and data.
+    pub fn has_message(&self, message: &UiMessage) -> bool {
+        self.messages.iter().any(|m| m == message)
+    }
+
+    /// Panics with a list of the recorded messages if the given message was not recorded.
+    #[track_caller]
+    pub fn assert_message(&self, message: &UiMessage) {
+        assert!(
+            self.has_message(message),
+            "{message:?} was not emitted. Recorded messages: {:#?}",
+            self.messages
+        );
+    }
+
+    /// Panics with a list of the recorded messages if a message of the given type was sent from
+    /// the given widget.
+    #[track_caller]
+    pub fn assert_no_message<T: MessageData>(&self, widget: Handle<UiNode>) {
+        assert!(
+            self.find_messages::<T>(widget).is_empty(),
+            "Unexpected {} message from {widget}. Recorded messages: {:#?}",
+            std::any::type_name::<T>(),
+            self.messages
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        button::{ButtonBuilder, ButtonMessage},
+        core::algebra::Vector2,
+        testing::UiTestHarness,
+        text_box::TextBoxBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        MessageDirection,
+    };
+
+    #[test]
+    fn test_click() {
+        let mut harness = UiTestHarness::new(Vector2::new(200.0, 200.0));
+        let button = ButtonBuilder::new(WidgetBuilder::new().with_width(50.0).with_height(20.0))
+            .build(&mut harness.build_ctx());
+        harness.update();
+
+        harness.click_at(Vector2::new(150.0, 150.0), crate::MouseButton::Left);
+        harness.assert_no_message::<ButtonMessage>(button);
+
+        harness.click_widget(button);
+        assert_eq!(
+            harness.find_message::<ButtonMessage>(button),
+            Some(&ButtonMessage::Click)
+        );
+    }
+
+    #[test]
+    fn test_typing() {
+        let mut harness = UiTestHarness::new(Vector2::new(200.0, 200.0));
+        let text_box = TextBoxBuilder::new(WidgetBuilder::new().with_width(100.0))
+            .build(&mut harness.build_ctx());
+        harness.update();
+
+        harness.send_message(WidgetMessage::focus(text_box, MessageDirection::ToWidget));
+        harness.type_text("Hi");
+        assert_eq!(
+            harness
+                .find_messages::<WidgetMessage>(text_box)
+                .into_iter()
+                .filter(|m| matches!(m, WidgetMessage::Text(_)))
+                .count(),
+            2
+        );
+    }
+}
@@ -2,8 +2,13 @@ use crate::{
     border::BorderBuilder,
     brush::Brush,
     core::{
-        algebra::SVector, color::Color, num_traits, pool::Handle, reflect::prelude::*,
-        type_traits::prelude::*, visitor::prelude::*,
+        algebra::SVector,
+        color::Color,
+        num_traits,
+        pool::Handle,
+        reflect::{prelude::*, PropertyUnit},
+        type_traits::prelude::*,
+        visitor::prelude::*,
     },
     define_constructor,
     grid::{Column, GridBuilder, Row},
@@ -23,6 +28,7 @@ fn make_numeric_input<T: NumericType>(
     step: T,
     editable: bool,
     precision: usize,
+    unit: Option<PropertyUnit>,
 ) -> Handle<UiNode> {
     NumericUpDownBuilder::new(
         WidgetBuilder::new()
@@ -41,6 +47,7 @@ fn make_numeric_input<T: NumericType>(
     .with_max_value(max)
     .with_step(step)
     .with_editable(editable)
+    .with_unit(unit)
     .build(ctx)
 }
 
@@ -227,6 +234,7 @@ where
     max: SVector<T, D>,
     step: SVector<T, D>,
     precision: usize,
+    unit: Option<PropertyUnit>,
 }
 
 impl<T, const D: usize> VecEditorBuilder<T, D>
@@ -242,6 +250,7 @@ where
             max: SVector::repeat(T::max_value()),
             step: SVector::repeat(T::one()),
             precision: 3,
+            unit: None,
         }
     }
 
@@ -275,6 +284,11 @@ where
         self
     }
 
+    pub fn with_unit(mut self, unit: Option<PropertyUnit>) -> Self {
+        self.unit = unit;
+        self
+    }
+
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let mut fields = Vec::new();
         let mut children = Vec::new();
@@ -305,6 +319,7 @@ where
                 self.step[i],
                 self.editable,
                 self.precision,
+                self.unit,
             );
             children.push(field);
             fields.push(field);
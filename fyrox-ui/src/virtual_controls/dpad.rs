@@ -0,0 +1,371 @@
+//! Virtual directional pad is an on-screen cross-shaped control with four buttons. See
+//! [`VirtualDPad`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    border::BorderBuilder,
+    brush::Brush,
+    core::{
+        algebra::Vector2, pool::Handle, reflect::prelude::*, type_traits::prelude::*,
+        variable::InheritableVariable, visitor::prelude::*,
+    },
+    grid::{Column, GridBuilder, Row},
+    message::{ButtonState, MessageDirection, UiMessage},
+    virtual_controls::{self, PointerEvent},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, Thickness, UiNode, UserInterface, BRUSH_DARKER, BRUSH_LIGHT,
+    BRUSH_LIGHTEST,
+};
+use fyrox_core::uuid_provider;
+use std::ops::{Deref, DerefMut};
+
+/// A direction of a directional pad.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DPadDirection {
+    /// Up direction.
+    Up,
+    /// Down direction.
+    Down,
+    /// Left direction.
+    Left,
+    /// Right direction.
+    Right,
+}
+
+impl DPadDirection {
+    /// Every direction in the order, that is used by [`VirtualDPad::indicators`].
+    pub const ALL: [DPadDirection; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    /// Returns the directions, that are pressed when a finger is at the given position relative to
+    /// the center of a directional pad (see [`virtual_controls::normalized_position`] for the
+    /// coordinate system). Diagonals press two adjacent directions at once. Nothing is pressed
+    /// inside the dead zone.
+    pub fn pressed_by(position: Vector2<f32>, dead_zone: f32) -> [bool; 4] {
+        let length = position.norm();
+        if length <= dead_zone.clamp(0.0, 1.0) || length <= f32::EPSILON {
+            return [false; 4];
+        }
+        // Every direction covers a sector of 135 degrees, so the sectors of adjacent directions
+        // overlap and form diagonals. cos(67.5°) ≈ 0.383
+        const THRESHOLD: f32 = 0.383;
+        let direction = position.scale(1.0 / length);
+        [
+            direction.y > THRESHOLD,
+            direction.y < -THRESHOLD,
+            direction.x < -THRESHOLD,
+            direction.x > THRESHOLD,
+        ]
+    }
+}
+
+/// Virtual directional pad is an on-screen cross-shaped control with four buttons. Unlike
+/// [`super::touch_button::TouchButton`], a finger could slide over the pad to change the pressed
+/// direction without lifting. Every direction has its own action name, the pad emits
+/// [`virtual_controls::VirtualControlMessage::Action`] every time when a direction is pressed or
+/// released. Diagonals press two directions at once.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, virtual_controls::dpad::VirtualDPadBuilder, widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// fn create_dpad(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     VirtualDPadBuilder::new(WidgetBuilder::new().with_width(150.0).with_height(150.0))
+///         .with_actions("MoveUp", "MoveDown", "MoveLeft", "MoveRight")
+///         .build(ctx)
+/// }
+/// ```
+///
+/// ## Dead zone
+///
+/// Dead zone is a part of the pad around its center (in `[0; 1]` range relative to the half-size
+/// of the pad), where no direction is pressed.
+///
+/// ## Skins
+///
+/// The pad consists of a base and four indicators, that are shown when their direction is pressed.
+/// They could be set with [`VirtualDPadBuilder::with_base`] and
+/// [`VirtualDPadBuilder::with_indicators`].
+#[derive(Default, Clone, Debug, Visit, Reflect, ComponentProvider)]
+pub struct VirtualDPad {
+    /// Base widget of the pad.
+    pub widget: Widget,
+    /// Name of the action, that is emitted by the up direction.
+    pub up_action: InheritableVariable<String>,
+    /// Name of the action, that is emitted by the down direction.
+    pub down_action: InheritableVariable<String>,
+    /// Name of the action, that is emitted by the left direction.
+    pub left_action: InheritableVariable<String>,
+    /// Name of the action, that is emitted by the right direction.
+    pub right_action: InheritableVariable<String>,
+    /// Dead zone of the pad in `[0; 1]` range.
+    #[reflect(min_value = 0.0, max_value = 1.0)]
+    pub dead_zone: InheritableVariable<f32>,
+    /// A widget, that is used as the base of the pad.
+    pub base: InheritableVariable<Handle<UiNode>>,
+    /// Widgets, that are shown when their direction is pressed. They're stored in the order of
+    /// [`DPadDirection::ALL`].
+    pub indicators: InheritableVariable<Vec<Handle<UiNode>>>,
+    /// An id of the pointer, that currently holds the pad.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub pointer: Option<u64>,
+    /// Pressed state of every direction in the order of [`DPadDirection::ALL`].
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub pressed: [bool; 4],
+}
+
+crate::define_widget_deref!(VirtualDPad);
+
+uuid_provider!(VirtualDPad = "e2a8f6b3-0d47-4c19-9f5e-71b3c8d24a06");
+
+impl VirtualDPad {
+    /// Returns the name of the action of the given direction.
+    pub fn action(&self, direction: DPadDirection) -> &str {
+        match direction {
+            DPadDirection::Up => &self.up_action,
+            DPadDirection::Down => &self.down_action,
+            DPadDirection::Left => &self.left_action,
+            DPadDirection::Right => &self.right_action,
+        }
+    }
+
+    /// Returns `true` if the given direction is pressed.
+    pub fn is_pressed(&self, direction: DPadDirection) -> bool {
+        self.pressed[direction as usize]
+    }
+
+    fn set_pressed(&mut self, ui: &UserInterface, pressed: [bool; 4]) {
+        for (index, direction) in DPadDirection::ALL.into_iter().enumerate() {
+            if self.pressed[index] == pressed[index] {
+                continue;
+            }
+            self.pressed[index] = pressed[index];
+
+            if let Some(indicator) = self.indicators.get(index) {
+                ui.send_message(WidgetMessage::visibility(
+                    *indicator,
+                    MessageDirection::ToWidget,
+                    pressed[index],
+                ));
+            }
+
+            virtual_controls::send_action(
+                ui,
+                self.handle,
+                self.action(direction),
+                if pressed[index] {
+                    ButtonState::Pressed
+                } else {
+                    ButtonState::Released
+                },
+            );
+        }
+    }
+
+    fn update_pressed(&mut self, ui: &UserInterface, screen_position: Vector2<f32>) {
+        let position =
+            virtual_controls::normalized_position(&self.screen_bounds(), screen_position);
+        self.set_pressed(ui, DPadDirection::pressed_by(position, *self.dead_zone));
+    }
+}
+
+impl Control for VirtualDPad {
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if message.handled() {
+            return;
+        }
+
+        let Some(event) = message
+            .data::<WidgetMessage>()
+            .and_then(PointerEvent::from_message)
+        else {
+            return;
+        };
+
+        match event {
+            PointerEvent::Pressed { id, pos } => {
+                if self.pointer.is_none() && virtual_controls::capture_pointer(ui, id, self.handle)
+                {
+                    self.pointer = Some(id);
+                    self.update_pressed(ui, pos);
+                    message.set_handled(true);
+                }
+            }
+            PointerEvent::Moved { id, pos } => {
+                if self.pointer == Some(id) {
+                    self.update_pressed(ui, pos);
+                    message.set_handled(true);
+                }
+            }
+            PointerEvent::Released { id } => {
+                if self.pointer == Some(id) {
+                    virtual_controls::release_pointer(ui, id);
+                    self.pointer = None;
+                    self.set_pressed(ui, [false; 4]);
+                    message.set_handled(true);
+                }
+            }
+        }
+    }
+}
+
+/// Virtual directional pad builder creates [`VirtualDPad`] instances and adds them to the user
+/// interface.
+pub struct VirtualDPadBuilder {
+    widget_builder: WidgetBuilder,
+    actions: [String; 4],
+    dead_zone: f32,
+    base: Option<Handle<UiNode>>,
+    indicators: Option<[Handle<UiNode>; 4]>,
+}
+
+impl VirtualDPadBuilder {
+    /// Creates new builder instance.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            actions: Default::default(),
+            dead_zone: 0.2,
+            base: None,
+            indicators: None,
+        }
+    }
+
+    /// Sets the names of the actions of every direction.
+    pub fn with_actions(
+        mut self,
+        up: impl Into<String>,
+        down: impl Into<String>,
+        left: impl Into<String>,
+        right: impl Into<String>,
+    ) -> Self {
+        self.actions = [up.into(), down.into(), left.into(), right.into()];
+        self
+    }
+
+    /// Sets the dead zone of the pad. The value will be clamped to `[0; 1]` range.
+    pub fn with_dead_zone(mut self, dead_zone: f32) -> Self {
+        self.dead_zone = dead_zone.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets a widget, that will be used as the base of the pad. It is stretched to the size of the
+    /// pad.
+    pub fn with_base(mut self, base: Handle<UiNode>) -> Self {
+        self.base = Some(base);
+        self
+    }
+
+    /// Sets the widgets, that will be shown when their direction is pressed. The widgets must be
+    /// in the order of [`DPadDirection::ALL`].
+    pub fn with_indicators(mut self, indicators: [Handle<UiNode>; 4]) -> Self {
+        self.indicators = Some(indicators);
+        self
+    }
+
+    /// Finishes pad creation and adds the new instance to the user interface.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        // Cells of the default 3x3 grid, that are used by every direction.
+        const CELLS: [(usize, usize); 4] = [(0, 1), (2, 1), (1, 0), (1, 2)];
+
+        let make_cell = |ctx: &mut BuildContext, (row, column): (usize, usize), brush: Brush| {
+            BorderBuilder::new(
+                WidgetBuilder::new()
+                    .on_row(row)
+                    .on_column(column)
+                    .with_margin(Thickness::uniform(2.0))
+                    .with_background(brush),
+            )
+            .with_corner_radius(4.0)
+            .build(ctx)
+        };
+
+        let custom_indicators = self.indicators.is_some();
+        let indicators = self
+            .indicators
+            .unwrap_or_else(|| CELLS.map(|cell| make_cell(ctx, cell, BRUSH_LIGHTEST)));
+
+        let base = self.base.unwrap_or_else(|| {
+            let buttons = CELLS.map(|cell| make_cell(ctx, cell, BRUSH_LIGHT));
+            let mut grid_builder = WidgetBuilder::new().with_children(buttons);
+            if !custom_indicators {
+                grid_builder = grid_builder.with_children(indicators);
+            }
+            let grid = GridBuilder::new(grid_builder)
+                .add_rows(vec![Row::stretch(); 3])
+                .add_columns(vec![Column::stretch(); 3])
+                .build(ctx);
+
+            BorderBuilder::new(
+                WidgetBuilder::new()
+                    .with_background(BRUSH_DARKER)
+                    .with_child(grid),
+            )
+            .with_corner_radius(8.0)
+            .build(ctx)
+        });
+
+        for indicator in indicators {
+            ctx[indicator].set_visibility(false);
+        }
+
+        // Indicators, that are not a part of the base, are attached to the pad itself.
+        let mut widget_builder = self.widget_builder.with_child(base);
+        for indicator in indicators {
+            if ctx[indicator].parent().is_none() {
+                widget_builder = widget_builder.with_child(indicator);
+            }
+        }
+
+        let [up_action, down_action, left_action, right_action] = self.actions;
+
+        let dpad = VirtualDPad {
+            widget: widget_builder.build(),
+            up_action: up_action.into(),
+            down_action: down_action.into(),
+            left_action: left_action.into(),
+            right_action: right_action.into(),
+            dead_zone: self.dead_zone.into(),
+            base: base.into(),
+            indicators: indicators.to_vec().into(),
+            pointer: None,
+            pressed: Default::default(),
+        };
+
+        ctx.add_node(UiNode::new(dpad))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DPadDirection;
+    use crate::core::algebra::Vector2;
+
+    #[test]
+    fn test_dpad_pressed_directions() {
+        assert_eq!(
+            DPadDirection::pressed_by(Vector2::new(0.1, 0.0), 0.2),
+            [false; 4]
+        );
+        assert_eq!(
+            DPadDirection::pressed_by(Vector2::new(0.0, 0.8), 0.2),
+            [true, false, false, false]
+        );
+        assert_eq!(
+            DPadDirection::pressed_by(Vector2::new(-0.9, 0.1), 0.2),
+            [false, false, true, false]
+        );
+        // Diagonal.
+        assert_eq!(
+            DPadDirection::pressed_by(Vector2::new(0.5, -0.5), 0.2),
+            [false, true, false, true]
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! Virtual joystick is an on-screen analog stick. See [`VirtualJoystick`] docs for more info and
+//! usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    border::BorderBuilder,
+    core::{
+        algebra::Vector2, math::Rect, pool::Handle, reflect::prelude::*, type_traits::prelude::*,
+        variable::InheritableVariable, visitor::prelude::*,
+    },
+    message::{MessageDirection, UiMessage},
+    virtual_controls::{self, PointerEvent, VirtualControlMessage},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, UiNode, UserInterface, BRUSH_DARKER, BRUSH_LIGHTER,
+};
+use fyrox_core::uuid_provider;
+use fyrox_graph::BaseSceneGraph;
+use std::ops::{Deref, DerefMut};
+
+/// Virtual joystick is an on-screen analog stick, that consists of a base and a knob. The knob
+/// follows a finger, that touched the joystick, and the joystick emits
+/// [`VirtualControlMessage::Axis`] every time when its value changes. The value is `(0, 0)` when
+/// the knob is at the center and it is `(1, 0)` when the knob is at the right edge of the base.
+/// When the finger is lifted, the knob returns to the center.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, virtual_controls::joystick::VirtualJoystickBuilder,
+/// #     widget::WidgetBuilder, BuildContext, UiNode,
+/// # };
+/// fn create_joystick(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     VirtualJoystickBuilder::new(WidgetBuilder::new().with_width(150.0).with_height(150.0))
+///         .with_action("Move")
+///         // Ignore small movements of the finger.
+///         .with_dead_zone(0.15)
+///         .build(ctx)
+/// }
+/// ```
+///
+/// ## Dead zone
+///
+/// Dead zone is a part of the base around its center (in `[0; 1]` range relative to the radius of
+/// the base), that does not change the value of the joystick. It prevents the value from jittering
+/// when a finger is resting on the joystick. See [`virtual_controls::apply_dead_zone`] for more info.
+///
+/// ## Skins
+///
+/// The base and the knob could be any widgets, they could be set with
+/// [`VirtualJoystickBuilder::with_base`] and [`VirtualJoystickBuilder::with_knob`]. The base is
+/// stretched to the size of the joystick, the knob keeps its own size.
+#[derive(Default, Clone, Debug, Visit, Reflect, ComponentProvider)]
+pub struct VirtualJoystick {
+    /// Base widget of the joystick.
+    pub widget: Widget,
+    /// Name of the action, that is emitted by the joystick.
+    pub action: InheritableVariable<String>,
+    /// Dead zone of the joystick in `[0; 1]` range.
+    #[reflect(min_value = 0.0, max_value = 1.0)]
+    pub dead_zone: InheritableVariable<f32>,
+    /// A widget, that is used as the base of the joystick.
+    pub base: InheritableVariable<Handle<UiNode>>,
+    /// A widget, that is used as the knob of the joystick.
+    pub knob: InheritableVariable<Handle<UiNode>>,
+    /// An id of the pointer, that currently holds the joystick.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub pointer: Option<u64>,
+    /// Position of the knob relative to the center of the base. Its length never exceeds one.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub knob_position: Vector2<f32>,
+    /// Current value of the joystick.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub value: Vector2<f32>,
+}
+
+crate::define_widget_deref!(VirtualJoystick);
+
+uuid_provider!(VirtualJoystick = "9d0c7a41-52e6-4b8f-b3d1-6a8e0f247c15");
+
+impl VirtualJoystick {
+    fn set_knob_position(&mut self, ui: &UserInterface, position: Vector2<f32>) {
+        let length = position.norm();
+        let knob_position = if length > 1.0 {
+            position.scale(1.0 / length)
+        } else {
+            position
+        };
+        if knob_position != self.knob_position {
+            self.knob_position = knob_position;
+            self.invalidate_arrange();
+        }
+
+        let value = virtual_controls::apply_dead_zone(knob_position, *self.dead_zone);
+        if value != self.value {
+            self.value = value;
+            if !self.action.is_empty() {
+                ui.send_message(VirtualControlMessage::axis(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                    self.action.clone_inner(),
+                    value,
+                ));
+            }
+        }
+    }
+}
+
+impl Control for VirtualJoystick {
+    fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
+        let full_rect = Rect::new(0.0, 0.0, final_size.x, final_size.y);
+        for &child in self.children() {
+            if child != *self.knob {
+                ui.arrange_node(child, &full_rect);
+            }
+        }
+
+        if let Some(knob) = ui.try_get(*self.knob) {
+            let knob_size = knob.desired_size();
+            let center = final_size.scale(0.5);
+            // The knob must stay inside the base.
+            let radius = (final_size - knob_size).scale(0.5).sup(&Vector2::default());
+            let offset = Vector2::new(
+                self.knob_position.x * radius.x,
+                -self.knob_position.y * radius.y,
+            );
+            let position = center + offset - knob_size.scale(0.5);
+            ui.arrange_node(
+                *self.knob,
+                &Rect::new(position.x, position.y, knob_size.x, knob_size.y),
+            );
+        }
+
+        final_size
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if message.handled() {
+            return;
+        }
+
+        let Some(event) = message
+            .data::<WidgetMessage>()
+            .and_then(PointerEvent::from_message)
+        else {
+            return;
+        };
+
+        match event {
+            PointerEvent::Pressed { id, pos } => {
+                if self.pointer.is_none() && virtual_controls::capture_pointer(ui, id, self.handle)
+                {
+                    self.pointer = Some(id);
+                    let position =
+                        virtual_controls::normalized_position(&self.screen_bounds(), pos);
+                    self.set_knob_position(ui, position);
+                    message.set_handled(true);
+                }
+            }
+            PointerEvent::Moved { id, pos } => {
+                if self.pointer == Some(id) {
+                    let position =
+                        virtual_controls::normalized_position(&self.screen_bounds(), pos);
+                    self.set_knob_position(ui, position);
+                    message.set_handled(true);
+                }
+            }
+            PointerEvent::Released { id } => {
+                if self.pointer == Some(id) {
+                    virtual_controls::release_pointer(ui, id);
+                    self.pointer = None;
+                    self.set_knob_position(ui, Vector2::default());
+                    message.set_handled(true);
+                }
+            }
+        }
+    }
+}
+
+/// Virtual joystick builder creates [`VirtualJoystick`] instances and adds them to the user
+/// interface.
+pub struct VirtualJoystickBuilder {
+    widget_builder: WidgetBuilder,
+    action: String,
+    dead_zone: f32,
+    base: Option<Handle<UiNode>>,
+    knob: Option<Handle<UiNode>>,
+}
+
+impl VirtualJoystickBuilder {
+    /// Creates new builder instance.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            action: Default::default(),
+            dead_zone: 0.1,
+            base: None,
+            knob: None,
+        }
+    }
+
+    /// Sets the name of the action, that will be emitted by the joystick.
+    pub fn with_action(mut self, action: impl Into<String>) -> Self {
+        self.action = action.into();
+        self
+    }
+
+    /// Sets the dead zone of the joystick. The value will be clamped to `[0; 1]` range.
+    pub fn with_dead_zone(mut self, dead_zone: f32) -> Self {
+        self.dead_zone = dead_zone.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets a widget, that will be used as the base of the joystick.
+    pub fn with_base(mut self, base: Handle<UiNode>) -> Self {
+        self.base = Some(base);
+        self
+    }
+
+    /// Sets a widget, that will be used as the knob of the joystick. The widget should have its own
+    /// size.
+    pub fn with_knob(mut self, knob: Handle<UiNode>) -> Self {
+        self.knob = Some(knob);
+        self
+    }
+
+    /// Finishes joystick creation and adds the new instance to the user interface.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let base = self.base.unwrap_or_else(|| {
+            BorderBuilder::new(WidgetBuilder::new().with_background(BRUSH_DARKER))
+                .with_corner_radius(16.0)
+                .build(ctx)
+        });
+
+        let knob = self.knob.unwrap_or_else(|| {
+            BorderBuilder::new(
+                WidgetBuilder::new()
+                    .with_width(48.0)
+                    .with_height(48.0)
+                    .with_background(BRUSH_LIGHTER),
+            )
+            .with_corner_radius(24.0)
+            .build(ctx)
+        });
+
+        let joystick = VirtualJoystick {
+            widget: self
+                .widget_builder
+                .with_child(base)
+                .with_child(knob)
+                .build(),
+            action: self.action.into(),
+            dead_zone: self.dead_zone.into(),
+            base: base.into(),
+            knob: knob.into(),
+            pointer: None,
+            knob_position: Default::default(),
+            value: Default::default(),
+        };
+
+        ctx.add_node(UiNode::new(joystick))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        message::{MessageDirection, OsEvent, TouchPhase},
+        virtual_controls::{joystick::VirtualJoystickBuilder, VirtualControlMessage},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+
+    #[test]
+    fn test_virtual_joystick() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let joystick =
+            VirtualJoystickBuilder::new(WidgetBuilder::new().with_width(100.0).with_height(100.0))
+                .with_action("Move")
+                .with_dead_zone(0.5)
+                .build(&mut ui.build_ctx());
+
+        ui.update(screen_size, 0.0, &Default::default());
+        ui.draw();
+
+        let touch = |ui: &mut UserInterface, phase, location| {
+            ui.process_os_event(&OsEvent::Touch {
+                phase,
+                location,
+                force: None,
+                id: 0,
+            });
+            let mut messages = Vec::new();
+            while let Some(message) = ui.poll_message() {
+                if message.data::<VirtualControlMessage>().is_some() {
+                    messages.push(message);
+                }
+            }
+            messages
+        };
+
+        // Inside the dead zone.
+        assert!(touch(&mut ui, TouchPhase::Started, Vector2::new(60.0, 50.0)).is_empty());
+
+        // The finger is outside of the joystick, but it still controls it.
+        assert_eq!(
+            touch(&mut ui, TouchPhase::Moved, Vector2::new(50.0, 500.0)),
+            vec![VirtualControlMessage::axis(
+                joystick,
+                MessageDirection::FromWidget,
+                "Move".to_string(),
+                Vector2::new(0.0, -1.0)
+            )]
+        );
+
+        assert_eq!(
+            touch(&mut ui, TouchPhase::Ended, Vector2::new(50.0, 500.0)),
+            vec![VirtualControlMessage::axis(
+                joystick,
+                MessageDirection::FromWidget,
+                "Move".to_string(),
+                Vector2::new(0.0, 0.0)
+            )]
+        );
+    }
+}
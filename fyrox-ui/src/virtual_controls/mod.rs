@@ -0,0 +1,183 @@
+//! Virtual controls are on-screen widgets, that are used to control a game on devices with touch
+//! screens: [`joystick::VirtualJoystick`], [`dpad::VirtualDPad`] and [`touch_button::TouchButton`].
+//! Every control has a name of an action (or a few of them), and it emits [`VirtualControlMessage`]
+//! with this name, so a game could handle on-screen controls the same way as keyboard or gamepad
+//! input. Every control tracks its own finger, which means that any number of controls could be used
+//! simultaneously. Controls are usable with a mouse as well, which is handy for testing on PC.
+//!
+//! Visual representation (skin) of every control consists of arbitrary widgets, that are passed to
+//! the builder of the control. Default skins are made of simple borders.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{algebra::Vector2, math::Rect},
+    define_constructor,
+    message::{ButtonState, MessageDirection, MouseButton, UiMessage},
+    widget::WidgetMessage,
+    UiNode, UserInterface,
+};
+use fyrox_core::pool::Handle;
+
+pub mod dpad;
+pub mod joystick;
+pub mod touch_button;
+
+/// A set of messages, that are emitted by virtual controls.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VirtualControlMessage {
+    /// Emitted when an action of a control was pressed or released.
+    Action {
+        /// Name of the action.
+        action: String,
+        /// New state of the action.
+        state: ButtonState,
+    },
+    /// Emitted when the value of an analog control was changed.
+    Axis {
+        /// Name of the action.
+        action: String,
+        /// New value of the control. Every component is in `[-1; 1]` range, positive values point
+        /// right and up (the same as gamepad sticks).
+        value: Vector2<f32>,
+    },
+}
+
+impl VirtualControlMessage {
+    define_constructor!(
+        /// Creates [`VirtualControlMessage::Action`] message.
+        VirtualControlMessage:Action => fn action(action: String, state: ButtonState), layout: false
+    );
+    define_constructor!(
+        /// Creates [`VirtualControlMessage::Axis`] message.
+        VirtualControlMessage:Axis => fn axis(action: String, value: Vector2<f32>), layout: false
+    );
+}
+
+/// An id of the pointer, that is used when a control is used with a mouse.
+pub(crate) const MOUSE_POINTER: u64 = u64::MAX;
+
+/// Applies radial dead zone to the given position of a stick. Positions, that are closer to the
+/// center than the dead zone, become zero. Positions outside the dead zone are rescaled, so the
+/// value grows smoothly from zero at the edge of the dead zone to one at the edge of the control.
+/// The length of the result never exceeds one.
+pub fn apply_dead_zone(position: Vector2<f32>, dead_zone: f32) -> Vector2<f32> {
+    let length = position.norm();
+    let dead_zone = dead_zone.clamp(0.0, 1.0);
+    if length <= dead_zone || dead_zone >= 1.0 {
+        return Vector2::default();
+    }
+    let scaled_length = (length.min(1.0) - dead_zone) / (1.0 - dead_zone);
+    position.scale(scaled_length / length)
+}
+
+/// Transforms a position on the screen into a position relative to the center of the given screen
+/// bounds of a widget, where `(-1, -1)` is the bottom-left corner and `(1, 1)` is the top-right
+/// corner of the widget. The Y axis points up, so the result could be used the same way as the
+/// position of a gamepad stick.
+pub fn normalized_position(bounds: &Rect<f32>, screen_position: Vector2<f32>) -> Vector2<f32> {
+    let half_size = bounds.size.scale(0.5);
+    let offset = screen_position - (bounds.position + half_size);
+    Vector2::new(
+        offset.x / half_size.x.max(f32::EPSILON),
+        -offset.y / half_size.y.max(f32::EPSILON),
+    )
+}
+
+/// Sends [`VirtualControlMessage::Action`] from the given control, if the action is not empty.
+pub(crate) fn send_action(
+    ui: &UserInterface,
+    control: Handle<UiNode>,
+    action: &str,
+    state: ButtonState,
+) {
+    if !action.is_empty() {
+        ui.send_message(VirtualControlMessage::action(
+            control,
+            MessageDirection::FromWidget,
+            action.to_string(),
+            state,
+        ));
+    }
+}
+
+/// A pointer (a finger or a mouse) event, that is used to control virtual controls.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum PointerEvent {
+    Pressed { id: u64, pos: Vector2<f32> },
+    Moved { id: u64, pos: Vector2<f32> },
+    Released { id: u64 },
+}
+
+impl PointerEvent {
+    /// Converts mouse and touch widget messages into pointer events.
+    pub(crate) fn from_message(msg: &WidgetMessage) -> Option<Self> {
+        match *msg {
+            WidgetMessage::MouseDown {
+                pos,
+                button: MouseButton::Left,
+            } => Some(Self::Pressed {
+                id: MOUSE_POINTER,
+                pos,
+            }),
+            WidgetMessage::MouseMove { pos, .. } => Some(Self::Moved {
+                id: MOUSE_POINTER,
+                pos,
+            }),
+            WidgetMessage::MouseUp {
+                button: MouseButton::Left,
+                ..
+            } => Some(Self::Released { id: MOUSE_POINTER }),
+            WidgetMessage::TouchStarted { pos, id, .. } => Some(Self::Pressed { id, pos }),
+            WidgetMessage::TouchMoved { pos, id, .. } => Some(Self::Moved { id, pos }),
+            WidgetMessage::TouchEnded { id, .. } | WidgetMessage::TouchCancelled { id, .. } => {
+                Some(Self::Released { id })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Makes sure, that every event of the given pointer will be sent to the given node.
+pub(crate) fn capture_pointer(ui: &mut UserInterface, id: u64, node: Handle<UiNode>) -> bool {
+    if id == MOUSE_POINTER {
+        ui.capture_mouse(node)
+    } else {
+        ui.capture_touch(id, node)
+    }
+}
+
+/// Releases the capture, that was made by [`capture_pointer`].
+pub(crate) fn release_pointer(ui: &mut UserInterface, id: u64) {
+    if id == MOUSE_POINTER {
+        ui.release_mouse_capture();
+    } else {
+        ui.release_touch_capture(id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_apply_dead_zone() {
+        assert_eq!(
+            apply_dead_zone(Vector2::new(0.1, 0.0), 0.2),
+            Vector2::default()
+        );
+        assert_eq!(
+            apply_dead_zone(Vector2::new(0.75, 0.0), 0.5),
+            Vector2::new(0.5, 0.0)
+        );
+        // Values outside of the control are clamped.
+        assert_eq!(
+            apply_dead_zone(Vector2::new(0.0, -3.0), 0.5),
+            Vector2::new(0.0, -1.0)
+        );
+        assert_eq!(
+            apply_dead_zone(Vector2::new(1.0, 0.0), 0.0),
+            Vector2::new(1.0, 0.0)
+        );
+    }
+}
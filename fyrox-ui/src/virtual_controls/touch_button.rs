@@ -0,0 +1,217 @@
+//! Touch button is an on-screen button, that emits an action while it is held. See [`TouchButton`]
+//! docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    border::BorderBuilder,
+    core::{
+        pool::Handle, reflect::prelude::*, type_traits::prelude::*, variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    message::{ButtonState, MessageDirection, UiMessage},
+    virtual_controls::{self, PointerEvent},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, UiNode, UserInterface, BRUSH_LIGHT, BRUSH_LIGHTEST,
+};
+use fyrox_core::uuid_provider;
+use std::ops::{Deref, DerefMut};
+
+/// Touch button is an on-screen button, that emits [`virtual_controls::VirtualControlMessage::Action`]
+/// with [`ButtonState::Pressed`] when a finger touches it and with [`ButtonState::Released`] when
+/// the finger is lifted. Unlike [`crate::button::Button`], it does not steal input from other
+/// widgets, so it could be held while other virtual controls are used.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, virtual_controls::touch_button::TouchButtonBuilder,
+/// #     widget::WidgetBuilder, BuildContext, UiNode,
+/// # };
+/// fn create_jump_button(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     TouchButtonBuilder::new(WidgetBuilder::new().with_width(80.0).with_height(80.0))
+///         .with_action("Jump")
+///         .build(ctx)
+/// }
+/// ```
+///
+/// ## Skins
+///
+/// Appearance of the button is defined by two arbitrary widgets: the first one is shown when the
+/// button is released, the second one - when it is pressed. They could be set with
+/// [`TouchButtonBuilder::with_normal_skin`] and [`TouchButtonBuilder::with_pressed_skin`].
+#[derive(Default, Clone, Debug, Visit, Reflect, ComponentProvider)]
+pub struct TouchButton {
+    /// Base widget of the button.
+    pub widget: Widget,
+    /// Name of the action, that is emitted by the button.
+    pub action: InheritableVariable<String>,
+    /// A widget, that is shown when the button is released.
+    pub normal_skin: InheritableVariable<Handle<UiNode>>,
+    /// A widget, that is shown when the button is pressed.
+    pub pressed_skin: InheritableVariable<Handle<UiNode>>,
+    /// An id of the pointer, that currently holds the button.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub pointer: Option<u64>,
+}
+
+crate::define_widget_deref!(TouchButton);
+
+uuid_provider!(TouchButton = "3c6f1d2e-8b0a-4f57-a4c9-2e7d51b9f604");
+
+impl TouchButton {
+    /// Returns `true` if the button is held.
+    pub fn is_pressed(&self) -> bool {
+        self.pointer.is_some()
+    }
+
+    fn set_pressed(&mut self, ui: &UserInterface, pointer: Option<u64>) {
+        let was_pressed = self.is_pressed();
+        self.pointer = pointer;
+        let pressed = self.is_pressed();
+        if was_pressed == pressed {
+            return;
+        }
+
+        ui.send_message(WidgetMessage::visibility(
+            *self.normal_skin,
+            MessageDirection::ToWidget,
+            !pressed,
+        ));
+        ui.send_message(WidgetMessage::visibility(
+            *self.pressed_skin,
+            MessageDirection::ToWidget,
+            pressed,
+        ));
+
+        virtual_controls::send_action(
+            ui,
+            self.handle,
+            &self.action,
+            if pressed {
+                ButtonState::Pressed
+            } else {
+                ButtonState::Released
+            },
+        );
+    }
+}
+
+impl Control for TouchButton {
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if message.handled() {
+            return;
+        }
+
+        let Some(event) = message
+            .data::<WidgetMessage>()
+            .and_then(PointerEvent::from_message)
+        else {
+            return;
+        };
+
+        match event {
+            PointerEvent::Pressed { id, .. } => {
+                if self.pointer.is_none() && virtual_controls::capture_pointer(ui, id, self.handle)
+                {
+                    self.set_pressed(ui, Some(id));
+                    message.set_handled(true);
+                }
+            }
+            PointerEvent::Moved { id, .. } => {
+                if self.pointer == Some(id) {
+                    message.set_handled(true);
+                }
+            }
+            PointerEvent::Released { id } => {
+                if self.pointer == Some(id) {
+                    virtual_controls::release_pointer(ui, id);
+                    self.set_pressed(ui, None);
+                    message.set_handled(true);
+                }
+            }
+        }
+    }
+}
+
+/// Touch button builder creates [`TouchButton`] instances and adds them to the user interface.
+pub struct TouchButtonBuilder {
+    widget_builder: WidgetBuilder,
+    action: String,
+    normal_skin: Option<Handle<UiNode>>,
+    pressed_skin: Option<Handle<UiNode>>,
+    content: Handle<UiNode>,
+}
+
+impl TouchButtonBuilder {
+    /// Creates new builder instance.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            action: Default::default(),
+            normal_skin: None,
+            pressed_skin: None,
+            content: Default::default(),
+        }
+    }
+
+    /// Sets the name of the action, that will be emitted by the button.
+    pub fn with_action(mut self, action: impl Into<String>) -> Self {
+        self.action = action.into();
+        self
+    }
+
+    /// Sets a widget, that will be shown when the button is released.
+    pub fn with_normal_skin(mut self, skin: Handle<UiNode>) -> Self {
+        self.normal_skin = Some(skin);
+        self
+    }
+
+    /// Sets a widget, that will be shown when the button is pressed.
+    pub fn with_pressed_skin(mut self, skin: Handle<UiNode>) -> Self {
+        self.pressed_skin = Some(skin);
+        self
+    }
+
+    /// Sets a widget, that will be shown on top of the skins (an icon or a text, for example).
+    pub fn with_content(mut self, content: Handle<UiNode>) -> Self {
+        self.content = content;
+        self
+    }
+
+    /// Finishes button creation and adds the new instance to the user interface.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let normal_skin = self.normal_skin.unwrap_or_else(|| {
+            BorderBuilder::new(WidgetBuilder::new().with_background(BRUSH_LIGHT))
+                .with_corner_radius(8.0)
+                .build(ctx)
+        });
+        ctx[normal_skin].set_visibility(true);
+
+        let pressed_skin = self.pressed_skin.unwrap_or_else(|| {
+            BorderBuilder::new(WidgetBuilder::new().with_background(BRUSH_LIGHTEST))
+                .with_corner_radius(8.0)
+                .build(ctx)
+        });
+        ctx[pressed_skin].set_visibility(false);
+
+        let button = TouchButton {
+            widget: self
+                .widget_builder
+                .with_child(normal_skin)
+                .with_child(pressed_skin)
+                .with_child(self.content)
+                .build(),
+            action: self.action.into(),
+            normal_skin: normal_skin.into(),
+            pressed_skin: pressed_skin.into(),
+            pointer: None,
+        };
+
+        ctx.add_node(UiNode::new(button))
+    }
+}
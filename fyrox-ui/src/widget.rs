@@ -15,7 +15,7 @@ use crate::{
         ImmutableString,
     },
     define_constructor,
-    message::{CursorIcon, Force, KeyCode, MessageDirection, UiMessage},
+    message::{CursorIcon, Force, GamepadButton, KeyCode, MessageDirection, UiMessage},
     HorizontalAlignment, LayoutEvent, MouseButton, MouseState, RcUiNodeHandle, Thickness, UiNode,
     UserInterface, VerticalAlignment, BRUSH_FOREGROUND, BRUSH_PRIMARY,
 };
@@ -97,6 +97,16 @@ pub enum WidgetMessage {
     /// Direction: **From UI**.
     KeyUp(KeyCode),
 
+    /// Initiated when widget is in focus and user presses a button on a gamepad.
+    ///
+    /// Direction: **From UI**.
+    GamepadButtonDown(GamepadButton),
+
+    /// Initiated when widget is in focus and user releases a button on a gamepad.
+    ///
+    /// Direction: **From UI**.
+    GamepadButtonUp(GamepadButton),
+
     /// Initiated when widget received focus (when direction is [`MessageDirection::FromWidget`]). In most cases focus is received
     /// by clicking on widget. You can request focus explicitly by sending this message to a widget with [`MessageDirection::ToWidget`]
     ///
@@ -374,6 +384,35 @@ pub enum WidgetMessage {
         /// unique identifier for touch event
         id: u64,
     },
+
+    /// Initiated when user quickly touches the screen and removes the finger without moving it.
+    ///
+    /// Direction: **From UI**.
+    Tap {
+        /// position of user's finger
+        pos: Vector2<f32>,
+    },
+
+    /// Initiated when user drags a single finger across the screen. Scroll viewers use this message
+    /// to scroll their content.
+    ///
+    /// Direction: **From UI**.
+    Pan {
+        /// offset of user's finger since the previous message
+        delta: Vector2<f32>,
+    },
+
+    /// Initiated when user moves two fingers towards or away from each other. Zoomable widgets
+    /// (such as curve editor) use this message to change their zoom.
+    ///
+    /// Direction: **From UI**.
+    Pinch {
+        /// a point between user's fingers
+        center: Vector2<f32>,
+        /// ratio of the current distance between user's fingers and the distance in the previous
+        /// message, values greater than 1.0 mean zoom in
+        scale: f32,
+    },
 }
 
 impl WidgetMessage {
@@ -607,6 +646,18 @@ impl WidgetMessage {
         WidgetMessage:KeyUp => fn key_up(KeyCode), layout: false
     );
 
+    define_constructor!(
+        /// Creates [`WidgetMessage::GamepadButtonDown`] message. This method is for internal use only, and should not
+        /// be used anywhere else.
+        WidgetMessage:GamepadButtonDown => fn gamepad_button_down(GamepadButton), layout: false
+    );
+
+    define_constructor!(
+        /// Creates [`WidgetMessage::GamepadButtonUp`] message. This method is for internal use only, and should not
+        /// be used anywhere else.
+        WidgetMessage:GamepadButtonUp => fn gamepad_button_up(GamepadButton), layout: false
+    );
+
     define_constructor!(
         /// Creates [`WidgetMessage::DragStarted`] message. This method is for internal use only, and should not
         /// be used anywhere else.
@@ -660,6 +711,24 @@ impl WidgetMessage {
         /// be used anywhere else.
         WidgetMessage:DoubleTap => fn double_tap(pos: Vector2<f32>, force: Option<Force>, id: u64), layout: false
     );
+
+    define_constructor!(
+        /// Creates [`WidgetMessage::Tap`] message. This method is for internal use only, and should not
+        /// be used anywhere else.
+        WidgetMessage:Tap => fn tap(pos: Vector2<f32>), layout: false
+    );
+
+    define_constructor!(
+        /// Creates [`WidgetMessage::Pan`] message. This method is for internal use only, and should not
+        /// be used anywhere else.
+        WidgetMessage:Pan => fn pan(delta: Vector2<f32>), layout: false
+    );
+
+    define_constructor!(
+        /// Creates [`WidgetMessage::Pinch`] message. This method is for internal use only, and should not
+        /// be used anywhere else.
+        WidgetMessage:Pinch => fn pinch(center: Vector2<f32>, scale: f32), layout: false
+    );
 }
 
 /// Widget is a base UI element, that is always used to build derived, more complex, widgets. In general, it is a container
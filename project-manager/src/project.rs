@@ -8,6 +8,7 @@ use fyrox::{
     gui::{
         button::ButtonMessage,
         dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        formatted_text::WrapMode,
         grid::{Column, GridBuilder, Row},
         message::{MessageDirection, UiMessage},
         path::{PathEditorBuilder, PathEditorMessage},
@@ -20,30 +21,9 @@ use fyrox::{
         VerticalAlignment,
     },
 };
+use fyrox_template_core::Template;
 use std::path::PathBuf;
 
-enum Style {
-    TwoD,
-    ThreeD,
-}
-
-impl Style {
-    fn from_index(index: usize) -> Self {
-        match index {
-            0 => Self::TwoD,
-            1 => Self::ThreeD,
-            _ => unreachable!(),
-        }
-    }
-
-    fn as_str(&self) -> &'static str {
-        match self {
-            Style::TwoD => "2d",
-            Style::ThreeD => "3d",
-        }
-    }
-}
-
 enum Vcs {
     None,
     Git,
@@ -81,10 +61,11 @@ pub struct ProjectWizard {
     cancel: Handle<UiNode>,
     path_field: Handle<UiNode>,
     name_field: Handle<UiNode>,
-    style_field: Handle<UiNode>,
+    template_field: Handle<UiNode>,
+    template_description: Handle<UiNode>,
     vcs_field: Handle<UiNode>,
     name: String,
-    style: Style,
+    template: Template,
     vcs: Vcs,
     path: PathBuf,
 }
@@ -126,25 +107,43 @@ impl ProjectWizard {
         .with_text("MyProject")
         .build(ctx);
 
-        let style_field = DropdownListBuilder::new(
+        let template = Template::ThreeD;
+        let template_field = DropdownListBuilder::new(
             WidgetBuilder::new()
                 .with_margin(Thickness::uniform(1.0))
                 .with_height(22.0)
                 .on_row(2)
                 .on_column(1),
         )
-        .with_items(vec![
-            make_dropdown_list_option(ctx, "2D"),
-            make_dropdown_list_option(ctx, "3D"),
-        ])
-        .with_selected(1)
+        .with_items(
+            Template::ALL
+                .iter()
+                .map(|template| make_dropdown_list_option(ctx, template.name()))
+                .collect(),
+        )
+        .with_selected(
+            Template::ALL
+                .iter()
+                .position(|t| *t == template)
+                .unwrap_or_default(),
+        )
+        .build(ctx);
+
+        let template_description = TextBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(1.0))
+                .on_row(3)
+                .on_column(1),
+        )
+        .with_wrap(WrapMode::Word)
+        .with_text(template.description())
         .build(ctx);
 
         let vcs_field = DropdownListBuilder::new(
             WidgetBuilder::new()
                 .with_margin(Thickness::uniform(1.0))
                 .with_height(22.0)
-                .on_row(3)
+                .on_row(4)
                 .on_column(1),
         )
         .with_items(vec![
@@ -176,12 +175,14 @@ impl ProjectWizard {
                 .with_child(path_field)
                 .with_child(make_text("Name", 1, ctx))
                 .with_child(name_field)
-                .with_child(make_text("Style", 2, ctx))
-                .with_child(style_field)
-                .with_child(make_text("Version Control", 3, ctx))
+                .with_child(make_text("Template", 2, ctx))
+                .with_child(template_field)
+                .with_child(template_description)
+                .with_child(make_text("Version Control", 4, ctx))
                 .with_child(vcs_field),
         )
         .add_row(Row::auto())
+        .add_row(Row::strict(48.0))
         .add_row(Row::auto())
         .add_row(Row::auto())
         .add_row(Row::auto())
@@ -197,7 +198,7 @@ impl ProjectWizard {
                 .add_column(Column::auto())
                 .build(ctx);
 
-        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(160.0))
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(350.0).with_height(210.0))
             .with_content(outer_grid)
             .open(false)
             .with_title(WindowTitle::text("Project Wizard"))
@@ -215,13 +216,14 @@ impl ProjectWizard {
         Self {
             window,
             name: "MyProject".to_string(),
-            style: Style::ThreeD,
+            template,
             vcs: Vcs::Git,
             create,
             cancel,
             path_field,
             name_field,
-            style_field,
+            template_field,
+            template_description,
             vcs_field,
             path: Default::default(),
         }
@@ -249,7 +251,7 @@ impl ProjectWizard {
                 let _ = fyrox_template_core::init_project(
                     &self.path,
                     &self.name,
-                    self.style.as_str(),
+                    self.template.id(),
                     self.vcs.as_str(),
                     true,
                 );
@@ -283,8 +285,15 @@ impl ProjectWizard {
             }
         } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
             if message.direction() == MessageDirection::FromWidget {
-                if message.destination() == self.style_field {
-                    self.style = Style::from_index(*index);
+                if message.destination() == self.template_field {
+                    if let Some(template) = Template::ALL.get(*index) {
+                        self.template = *template;
+                        ui.send_message(TextMessage::text(
+                            self.template_description,
+                            MessageDirection::ToWidget,
+                            template.description().to_string(),
+                        ));
+                    }
                 } else if message.destination() == self.vcs_field {
                     self.vcs = Vcs::from_index(*index);
                 }
@@ -85,37 +85,108 @@ fn check_name(name: &str) -> Result<&str, NameErrors> {
     Ok(name)
 }
 
-fn init_game(base_path: &Path, name: &str) -> Result<(), String> {
-    Command::new("cargo")
-        .args(["init", "--lib", "--vcs", "none"])
-        .arg(base_path.join("game"))
-        .output()
-        .map_err(|e| e.to_string())?;
+/// Project template, that defines initial content (scenes and scripts) of a new project.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Template {
+    /// An empty 2D scene.
+    TwoD,
+    /// An empty 3D scene.
+    ThreeD,
+    /// A 2D platformer with a tile map level and a player that can run and jump.
+    Platformer,
+    /// A 3D first-person shooter with a simple level and a first-person player controller.
+    Fps,
+    /// An application without scenes, that has only user interface.
+    Ui,
+}
 
-    // Write Cargo.toml
-    write_file(
-        base_path.join("game/Cargo.toml"),
-        format!(
-            r#"
-[package]
-name = "{name}"
-version = "0.1.0"
-edition = "2021"
+impl Template {
+    /// All available templates.
+    pub const ALL: [Template; 5] = [
+        Template::TwoD,
+        Template::ThreeD,
+        Template::Platformer,
+        Template::Fps,
+        Template::Ui,
+    ];
 
-[dependencies]
-fyrox = {{workspace = true}}
+    /// Tries to find a template by its id (see [`Self::id`]).
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|template| template.id() == id)
+    }
 
-[features]
-default = ["fyrox/default"]
-dylib-engine = ["fyrox/dylib"]
-"#,
-        ),
-    )?;
+    /// Unique id of the template, that is used in the command line interface.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Template::TwoD => "2d",
+            Template::ThreeD => "3d",
+            Template::Platformer => "platformer",
+            Template::Fps => "fps",
+            Template::Ui => "ui",
+        }
+    }
 
-    // Write lib.rs
-    write_file(
-        base_path.join("game/src/lib.rs"),
-        r#"//! Game project.
+    /// Human-readable name of the template.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Template::TwoD => "2D",
+            Template::ThreeD => "3D",
+            Template::Platformer => "2D Platformer",
+            Template::Fps => "3D First-Person Shooter",
+            Template::Ui => "UI Application",
+        }
+    }
+
+    /// Short description of the content of the template.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Template::TwoD => "An empty 2D scene.",
+            Template::ThreeD => "An empty 3D scene.",
+            Template::Platformer => {
+                "A tile map level with a player, that runs with A/D and jumps with Space."
+            }
+            Template::Fps => {
+                "A level with a few physical boxes and a first-person player, that walks with \
+                WASD, looks around with the mouse and jumps with Space."
+            }
+            Template::Ui => "An application without scenes with a window of widgets.",
+        }
+    }
+
+    /// Returns a list of pairs of a file name and its content, that form the source code of the
+    /// game crate of the template.
+    fn game_sources(&self) -> Vec<(&'static str, &'static str)> {
+        match self {
+            Template::TwoD | Template::ThreeD => vec![("lib.rs", DEFAULT_GAME_SOURCE)],
+            Template::Platformer => vec![
+                ("lib.rs", include_str!("templates/platformer/lib.rs")),
+                ("player.rs", include_str!("templates/platformer/player.rs")),
+            ],
+            Template::Fps => vec![
+                ("lib.rs", include_str!("templates/fps/lib.rs")),
+                ("player.rs", include_str!("templates/fps/player.rs")),
+            ],
+            Template::Ui => vec![("lib.rs", include_str!("templates/ui/lib.rs"))],
+        }
+    }
+
+    /// Returns the content of the scene of the template, if any.
+    fn scene(&self) -> Option<&'static [u8]> {
+        match self {
+            Template::TwoD | Template::Platformer => Some(include_bytes!("2d.rgs")),
+            Template::ThreeD | Template::Fps => Some(include_bytes!("3d.rgs")),
+            Template::Ui => None,
+        }
+    }
+}
+
+impl Display for Template {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+const DEFAULT_GAME_SOURCE: &str = r#"//! Game project.
 use fyrox::{
     core::pool::Handle, core::visitor::prelude::*, core::reflect::prelude::*,
     event::Event,
@@ -184,8 +255,41 @@ impl Plugin for Game {
         self.scene = scene;
     }
 }
+"#;
+
+fn init_game(base_path: &Path, name: &str, template: Template) -> Result<(), String> {
+    Command::new("cargo")
+        .args(["init", "--lib", "--vcs", "none"])
+        .arg(base_path.join("game"))
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    // Write Cargo.toml
+    write_file(
+        base_path.join("game/Cargo.toml"),
+        format!(
+            r#"
+[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+fyrox = {{workspace = true}}
+
+[features]
+default = ["fyrox/default"]
+dylib-engine = ["fyrox/dylib"]
 "#,
-    )
+        ),
+    )?;
+
+    // Write sources.
+    for (file_name, content) in template.game_sources() {
+        write_file(base_path.join("game/src").join(file_name), content)?;
+    }
+
+    Ok(())
 }
 
 fn init_executor(base_path: &Path, name: &str) -> Result<(), String> {
@@ -329,32 +433,28 @@ pub fn main() {{
         ),
     )?;
 
-    // Write "entry" point stuff. This includes:
-    //
-    // - Index page with a "Start" button. The button is needed to solve sound issues in some browsers.
-    //   Some browsers (mostly Chrome) prevent sound from playing until user click on something on the
-    //   game page.
-    // - Entry JavaScript code - basically a web launcher for your game.
-    // - Styles - to make "Start" button to look decent.
-    // - A readme file with build instructions.
-    write_file_binary(
-        base_path.join("executor-wasm/index.html"),
-        include_bytes!("wasm/index.html"),
-    )?;
-    write_file_binary(
-        base_path.join("executor-wasm/styles.css"),
-        include_bytes!("wasm/styles.css"),
-    )?;
-    write_file_binary(
-        base_path.join("executor-wasm/main.js"),
-        include_bytes!("wasm/main.js"),
-    )?;
+    init_web_shell(&base_path.join("executor-wasm"))?;
+
     write_file_binary(
         base_path.join("executor-wasm/README.md"),
         include_bytes!("wasm/README.md"),
     )
 }
 
+/// Writes "entry" point stuff of the WebAssembly version of a game into the given folder. This
+/// includes:
+///
+/// - Index page with a "Start" button. The button is needed to solve sound issues in some browsers.
+///   Some browsers (mostly Chrome) prevent sound from playing until user click on something on the
+///   game page.
+/// - Entry JavaScript code - basically a web launcher for your game.
+/// - Styles - to make "Start" button to look decent.
+pub fn init_web_shell(path: &Path) -> Result<(), String> {
+    write_file_binary(path.join("index.html"), include_bytes!("wasm/index.html"))?;
+    write_file_binary(path.join("styles.css"), include_bytes!("wasm/styles.css"))?;
+    write_file_binary(path.join("main.js"), include_bytes!("wasm/main.js"))
+}
+
 fn init_editor(base_path: &Path, name: &str) -> Result<(), String> {
     Command::new("cargo")
         .args(["init", "--bin", "--vcs", "none"])
@@ -601,6 +701,7 @@ opt-level = 3
             r#"
 /target
 *.log
+/.recovery
 "#,
         )?;
     }
@@ -608,15 +709,13 @@ opt-level = 3
     Ok(())
 }
 
-fn init_data(base_path: &Path, style: &str) -> Result<(), String> {
+fn init_data(base_path: &Path, template: Template) -> Result<(), String> {
     let data_path = base_path.join("data");
     create_dir_all(&data_path).map_err(|e| e.to_string())?;
 
-    let scene_path = data_path.join("scene.rgs");
-    match style {
-        "2d" => write_file_binary(scene_path, include_bytes!("2d.rgs")),
-        "3d" => write_file_binary(scene_path, include_bytes!("3d.rgs")),
-        _ => Err(format!("Unknown style: {}. Use either `2d` or `3d`", style)),
+    match template.scene() {
+        Some(scene) => write_file_binary(data_path.join("scene.rgs"), scene),
+        None => Ok(()),
     }
 }
 
@@ -698,6 +797,18 @@ pub fn init_project(
         }
     };
 
+    let template = Template::from_id(style).ok_or_else(|| {
+        format!(
+            "Unknown style: {}. Use one of the following: {}",
+            style,
+            Template::ALL
+                .iter()
+                .map(|template| format!("`{}`", template.id()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })?;
+
     let base_path = root_path.join(name);
     let base_path = &base_path;
 
@@ -716,8 +827,8 @@ pub fn init_project(
     }
 
     init_workspace(base_path, vcs)?;
-    init_data(base_path, style)?;
-    init_game(base_path, name)?;
+    init_data(base_path, template)?;
+    init_game(base_path, name, template)?;
     init_game_dylib(base_path, name)?;
     init_editor(base_path, name)?;
     init_executor(base_path, name)?;
@@ -0,0 +1,180 @@
+//! 3D first-person shooter game project.
+use crate::player::Player;
+use fyrox::{
+    asset::untyped::ResourceKind,
+    core::{
+        algebra::{Matrix4, Vector3},
+        pool::Handle,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    graph::SceneGraph,
+    plugin::{Plugin, PluginContext, PluginRegistrationContext},
+    scene::{
+        base::BaseBuilder,
+        camera::{Camera, CameraBuilder},
+        collider::{ColliderBuilder, ColliderShape},
+        graph::Graph,
+        mesh::{
+            surface::{SurfaceBuilder, SurfaceData, SurfaceResource},
+            MeshBuilder,
+        },
+        node::Node,
+        pivot::PivotBuilder,
+        rigidbody::{RigidBodyBuilder, RigidBodyType},
+        transform::TransformBuilder,
+        Scene,
+    },
+};
+use std::path::Path;
+
+pub mod player;
+
+// Re-export the engine.
+pub use fyrox;
+
+/// Name of the root node of the level, that is built by [`build_level`].
+const LEVEL_NAME: &str = "Level";
+
+/// Creates a box of the given size, that is simulated by physics.
+fn make_box(
+    graph: &mut Graph,
+    name: &str,
+    position: Vector3<f32>,
+    size: Vector3<f32>,
+    body_type: RigidBodyType,
+) -> Handle<Node> {
+    let mesh = MeshBuilder::new(BaseBuilder::new())
+        .with_surfaces(vec![SurfaceBuilder::new(SurfaceResource::new_ok(
+            ResourceKind::Embedded,
+            SurfaceData::make_cube(Matrix4::new_nonuniform_scaling(&size)),
+        ))
+        .build()])
+        .build(graph);
+    let collider = ColliderBuilder::new(BaseBuilder::new())
+        .with_shape(ColliderShape::cuboid(
+            size.x * 0.5,
+            size.y * 0.5,
+            size.z * 0.5,
+        ))
+        .build(graph);
+    RigidBodyBuilder::new(
+        BaseBuilder::new()
+            .with_name(name)
+            .with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .build(),
+            )
+            .with_children(&[mesh, collider]),
+    )
+    .with_body_type(body_type)
+    .build(graph)
+}
+
+/// Builds a simple level with a few boxes and spawns the player on it. The level is built from code,
+/// so the project could be launched right away. Once you've made your own level in the editor, remove
+/// the call of this function.
+fn build_level(graph: &mut Graph) {
+    let mut children = vec![make_box(
+        graph,
+        "Ground",
+        Vector3::new(0.0, -0.25, 0.0),
+        Vector3::new(40.0, 0.5, 40.0),
+        RigidBodyType::Static,
+    )];
+    for i in 0..5 {
+        children.push(make_box(
+            graph,
+            "Crate",
+            Vector3::new(i as f32 * 2.0 - 4.0, 0.5 + i as f32, 6.0),
+            Vector3::new(1.0, 1.0, 1.0),
+            RigidBodyType::Dynamic,
+        ));
+    }
+
+    // Use player's camera instead of the cameras of the scene.
+    let cameras = graph
+        .pair_iter()
+        .filter_map(|(handle, node)| node.cast::<Camera>().is_some().then_some(handle))
+        .collect::<Vec<_>>();
+    for camera in cameras {
+        graph[camera].set_enabled(false);
+    }
+
+    let camera = CameraBuilder::new(
+        BaseBuilder::new().with_local_transform(
+            TransformBuilder::new()
+                .with_local_position(Vector3::new(0.0, 0.6, 0.0))
+                .build(),
+        ),
+    )
+    .build(graph);
+    let collider = ColliderBuilder::new(BaseBuilder::new())
+        .with_shape(ColliderShape::capsule_y(0.5, 0.3))
+        .build(graph);
+    children.push(
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_name("Player")
+                .with_script(Player::default())
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 1.0, 0.0))
+                        .build(),
+                )
+                .with_children(&[camera, collider]),
+        )
+        .with_locked_rotations(true)
+        .with_can_sleep(false)
+        .build(graph),
+    );
+
+    PivotBuilder::new(
+        BaseBuilder::new()
+            .with_name(LEVEL_NAME)
+            .with_children(&children),
+    )
+    .build(graph);
+}
+
+#[derive(Default, Visit, Reflect, Debug)]
+pub struct Game {
+    scene: Handle<Scene>,
+}
+
+impl Plugin for Game {
+    fn register(&self, context: PluginRegistrationContext) {
+        context
+            .serialization_context
+            .script_constructors
+            .add::<Player>("Player");
+    }
+
+    fn init(&mut self, scene_path: Option<&str>, context: PluginContext) {
+        context
+            .async_scene_loader
+            .request(scene_path.unwrap_or("data/scene.rgs"));
+    }
+
+    fn on_scene_begin_loading(&mut self, _path: &Path, ctx: &mut PluginContext) {
+        if self.scene.is_some() {
+            ctx.scenes.remove(self.scene);
+        }
+    }
+
+    fn on_scene_loaded(
+        &mut self,
+        _path: &Path,
+        scene: Handle<Scene>,
+        _data: &[u8],
+        context: &mut PluginContext,
+    ) {
+        self.scene = scene;
+
+        let graph = &mut context.scenes[scene].graph;
+        if graph.find_by_name_from_root(LEVEL_NAME).is_none() {
+            build_level(graph);
+        }
+    }
+}
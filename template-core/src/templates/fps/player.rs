@@ -0,0 +1,153 @@
+//! First-person player controller.
+use fyrox::{
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    event::{DeviceEvent, ElementState, Event, WindowEvent},
+    graph::SceneGraph,
+    keyboard::{KeyCode, PhysicalKey},
+    scene::{camera::Camera, rigidbody::RigidBody},
+    script::{ScriptContext, ScriptTrait},
+};
+
+#[derive(Visit, Reflect, Debug, Clone, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "6f5b2ca1-33c8-4b0e-9a24-5d7e1f0c8b93")]
+#[visit(optional)]
+pub struct Player {
+    /// Walking speed of the player (in units per second).
+    pub speed: f32,
+    /// Initial vertical speed of the player when it jumps (in units per second).
+    pub jump_speed: f32,
+    /// Mouse sensitivity (in degrees per pixel).
+    pub sensitivity: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    yaw: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pitch: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    move_forward: bool,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    move_backward: bool,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    move_left: bool,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    move_right: bool,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    jump: bool,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self {
+            speed: 4.0,
+            jump_speed: 5.0,
+            sensitivity: 0.3,
+            yaw: 0.0,
+            pitch: 0.0,
+            move_forward: false,
+            move_backward: false,
+            move_left: false,
+            move_right: false,
+            jump: false,
+        }
+    }
+}
+
+impl ScriptTrait for Player {
+    fn on_os_event(&mut self, event: &Event<()>, _context: &mut ScriptContext) {
+        match event {
+            Event::WindowEvent {
+                event: WindowEvent::KeyboardInput { event, .. },
+                ..
+            } => {
+                let pressed = event.state == ElementState::Pressed;
+                if let PhysicalKey::Code(code) = event.physical_key {
+                    match code {
+                        KeyCode::KeyW => self.move_forward = pressed,
+                        KeyCode::KeyS => self.move_backward = pressed,
+                        KeyCode::KeyA => self.move_left = pressed,
+                        KeyCode::KeyD => self.move_right = pressed,
+                        KeyCode::Space => self.jump = pressed,
+                        _ => (),
+                    }
+                }
+            }
+            Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta },
+                ..
+            } => {
+                self.yaw -= delta.0 as f32 * self.sensitivity;
+                self.pitch = (self.pitch + delta.1 as f32 * self.sensitivity).clamp(-89.0, 89.0);
+            }
+            _ => (),
+        }
+    }
+
+    fn on_update(&mut self, context: &mut ScriptContext) {
+        let graph = &mut context.scene.graph;
+
+        // Look up and down with the camera.
+        let camera = graph
+            .find(context.handle, &mut |node| node.cast::<Camera>().is_some())
+            .map(|(handle, _)| handle);
+        if let Some(camera) = camera {
+            graph[camera]
+                .local_transform_mut()
+                .set_rotation(UnitQuaternion::from_axis_angle(
+                    &Vector3::x_axis(),
+                    self.pitch.to_radians(),
+                ));
+        }
+
+        if let Some(rigid_body) = graph.try_get_mut_of_type::<RigidBody>(context.handle) {
+            // Turn left and right with the whole body.
+            rigid_body
+                .local_transform_mut()
+                .set_rotation(UnitQuaternion::from_axis_angle(
+                    &Vector3::y_axis(),
+                    self.yaw.to_radians(),
+                ));
+
+            let look = rigid_body.look_vector();
+            let side = rigid_body.side_vector();
+            let mut direction = Vector3::default();
+            if self.move_forward {
+                direction += look;
+            }
+            if self.move_backward {
+                direction -= look;
+            }
+            if self.move_left {
+                direction += side;
+            }
+            if self.move_right {
+                direction -= side;
+            }
+            let direction = direction
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default()
+                .scale(self.speed);
+
+            let velocity = rigid_body.lin_vel();
+            // The player stands on something when it does not move vertically.
+            let on_ground = velocity.y.abs() < 0.01;
+            let vertical_speed = if self.jump && on_ground {
+                self.jump_speed
+            } else {
+                velocity.y
+            };
+
+            rigid_body.set_lin_vel(Vector3::new(direction.x, vertical_speed, direction.z));
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! 2D platformer game project.
+use crate::player::Player;
+use fyrox::{
+    asset::untyped::ResourceKind,
+    core::{
+        algebra::{Vector2, Vector3},
+        color::Color,
+        math::Rect,
+        pool::Handle,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    graph::{BaseSceneGraph, SceneGraph},
+    material::{Material, MaterialResource},
+    plugin::{Plugin, PluginContext, PluginRegistrationContext},
+    scene::{
+        base::BaseBuilder,
+        camera::Camera,
+        dim2::{
+            collider::{ColliderBuilder, ColliderShape, GeometrySource},
+            rectangle::RectangleBuilder,
+            rigidbody::RigidBodyBuilder,
+        },
+        graph::Graph,
+        pivot::PivotBuilder,
+        rigidbody::RigidBodyType,
+        tilemap::{
+            tileset::{TileDefinition, TileSet, TileSetResource},
+            Tile, TileMapBuilder,
+        },
+        transform::TransformBuilder,
+        Scene,
+    },
+};
+use std::path::Path;
+
+pub mod player;
+
+// Re-export the engine.
+pub use fyrox;
+
+/// Name of the root node of the level, that is built by [`build_level`].
+const LEVEL_NAME: &str = "Level";
+
+/// Builds a small level out of tiles and spawns the player on it. The level is built from code, so
+/// the project could be launched right away. Once you've made your own level in the editor, remove
+/// the call of this function.
+fn build_level(graph: &mut Graph) {
+    let tile = |color: Color| TileDefinition {
+        material: MaterialResource::new_ok(ResourceKind::Embedded, Material::standard_2d()),
+        uv_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+        color,
+        ..Default::default()
+    };
+    let tile_set = TileSetResource::new_ok(
+        ResourceKind::Embedded,
+        TileSet {
+            tiles: vec![
+                tile(Color::opaque(120, 80, 40)),
+                tile(Color::opaque(60, 160, 60)),
+            ],
+            ..Default::default()
+        },
+    );
+
+    let mut tiles = Vec::new();
+    for x in -12..12 {
+        // Ground.
+        tiles.push(Tile::new(Vector2::new(x, -4), 0));
+        tiles.push(Tile::new(Vector2::new(x, -3), 1));
+    }
+    for x in 2..6 {
+        // A platform to jump on.
+        tiles.push(Tile::new(Vector2::new(x, 0), 1));
+    }
+
+    let tile_map = TileMapBuilder::new(BaseBuilder::new().with_name("TileMap"))
+        .with_tile_set(tile_set)
+        .with_tiles(tiles)
+        .build(graph);
+    let tile_map_collider = ColliderBuilder::new(BaseBuilder::new())
+        .with_shape(ColliderShape::tile_map(GeometrySource(tile_map)))
+        .build(graph);
+    let ground = RigidBodyBuilder::new(
+        BaseBuilder::new()
+            .with_name("Ground")
+            .with_children(&[tile_map_collider]),
+    )
+    .with_body_type(RigidBodyType::Static)
+    .build(graph);
+
+    let sprite = RectangleBuilder::new(
+        BaseBuilder::new().with_local_transform(
+            TransformBuilder::new()
+                .with_local_scale(Vector3::new(0.8, 0.8, 1.0))
+                .build(),
+        ),
+    )
+    .with_color(Color::opaque(230, 90, 60))
+    .build(graph);
+    let player_collider = ColliderBuilder::new(BaseBuilder::new())
+        .with_shape(ColliderShape::cuboid(0.4, 0.4))
+        .build(graph);
+    let player = RigidBodyBuilder::new(
+        BaseBuilder::new()
+            .with_name("Player")
+            .with_script(Player::default())
+            .with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(-4.0, 0.0, 0.0))
+                    .build(),
+            )
+            .with_children(&[sprite, player_collider]),
+    )
+    .with_rotation_locked(true)
+    .with_can_sleep(false)
+    .build(graph);
+
+    // Make the camera follow the player.
+    if let Some((camera, _)) = graph.find_from_root(&mut |node| node.cast::<Camera>().is_some()) {
+        graph.link_nodes(camera, player);
+        let position = **graph[camera].local_transform().position();
+        graph[camera]
+            .local_transform_mut()
+            .set_position(Vector3::new(0.0, 0.0, position.z));
+    }
+
+    PivotBuilder::new(
+        BaseBuilder::new()
+            .with_name(LEVEL_NAME)
+            .with_children(&[tile_map, ground, player]),
+    )
+    .build(graph);
+}
+
+#[derive(Default, Visit, Reflect, Debug)]
+pub struct Game {
+    scene: Handle<Scene>,
+}
+
+impl Plugin for Game {
+    fn register(&self, context: PluginRegistrationContext) {
+        context
+            .serialization_context
+            .script_constructors
+            .add::<Player>("Player");
+    }
+
+    fn init(&mut self, scene_path: Option<&str>, context: PluginContext) {
+        context
+            .async_scene_loader
+            .request(scene_path.unwrap_or("data/scene.rgs"));
+    }
+
+    fn on_scene_begin_loading(&mut self, _path: &Path, ctx: &mut PluginContext) {
+        if self.scene.is_some() {
+            ctx.scenes.remove(self.scene);
+        }
+    }
+
+    fn on_scene_loaded(
+        &mut self,
+        _path: &Path,
+        scene: Handle<Scene>,
+        _data: &[u8],
+        context: &mut PluginContext,
+    ) {
+        self.scene = scene;
+
+        let graph = &mut context.scenes[scene].graph;
+        if graph.find_by_name_from_root(LEVEL_NAME).is_none() {
+            build_level(graph);
+        }
+    }
+}
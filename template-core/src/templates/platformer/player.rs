@@ -0,0 +1,87 @@
+//! Player controller of the platformer.
+use fyrox::{
+    core::{algebra::Vector2, reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*},
+    event::{ElementState, Event, WindowEvent},
+    graph::SceneGraph,
+    keyboard::{KeyCode, PhysicalKey},
+    scene::dim2::rigidbody::RigidBody,
+    script::{ScriptContext, ScriptTrait},
+};
+
+#[derive(Visit, Reflect, Debug, Clone, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "c5671d19-9f1a-4286-8486-add4ebaadaec")]
+#[visit(optional)]
+pub struct Player {
+    /// Horizontal speed of the player (in units per second).
+    pub speed: f32,
+    /// Initial vertical speed of the player when it jumps (in units per second).
+    pub jump_speed: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    move_left: bool,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    move_right: bool,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    jump: bool,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self {
+            speed: 5.0,
+            jump_speed: 7.0,
+            move_left: false,
+            move_right: false,
+            jump: false,
+        }
+    }
+}
+
+impl ScriptTrait for Player {
+    fn on_os_event(&mut self, event: &Event<()>, _context: &mut ScriptContext) {
+        if let Event::WindowEvent {
+            event: WindowEvent::KeyboardInput { event, .. },
+            ..
+        } = event
+        {
+            let pressed = event.state == ElementState::Pressed;
+            if let PhysicalKey::Code(code) = event.physical_key {
+                match code {
+                    KeyCode::KeyA | KeyCode::ArrowLeft => self.move_left = pressed,
+                    KeyCode::KeyD | KeyCode::ArrowRight => self.move_right = pressed,
+                    KeyCode::Space | KeyCode::KeyW | KeyCode::ArrowUp => self.jump = pressed,
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    fn on_update(&mut self, context: &mut ScriptContext) {
+        if let Some(rigid_body) = context
+            .scene
+            .graph
+            .try_get_mut_of_type::<RigidBody>(context.handle)
+        {
+            let mut direction = 0.0;
+            if self.move_left {
+                direction -= 1.0;
+            }
+            if self.move_right {
+                direction += 1.0;
+            }
+
+            let velocity = rigid_body.lin_vel();
+            // The player stands on something when it does not move vertically.
+            let on_ground = velocity.y.abs() < 0.01;
+            let vertical_speed = if self.jump && on_ground {
+                self.jump_speed
+            } else {
+                velocity.y
+            };
+
+            rigid_body.set_lin_vel(Vector2::new(direction * self.speed, vertical_speed));
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! UI application project.
+use fyrox::{
+    core::{pool::Handle, reflect::prelude::*, visitor::prelude::*},
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        HorizontalAlignment, Thickness, UiNode, VerticalAlignment,
+    },
+    plugin::{Plugin, PluginContext, PluginRegistrationContext},
+};
+
+// Re-export the engine.
+pub use fyrox;
+
+#[derive(Default, Visit, Reflect, Debug)]
+pub struct Game {
+    counter_text: Handle<UiNode>,
+    button: Handle<UiNode>,
+    clicks: u32,
+}
+
+impl Game {
+    fn counter_text(&self) -> String {
+        format!("The button was clicked {} time(s).", self.clicks)
+    }
+}
+
+impl Plugin for Game {
+    fn register(&self, _context: PluginRegistrationContext) {
+        // Register your custom widgets here.
+    }
+
+    fn init(&mut self, _scene_path: Option<&str>, context: PluginContext) {
+        // Build the user interface. It could also be loaded from a file, that is made in the editor.
+        let text = self.counter_text();
+        let ctx = &mut context.user_interfaces.first_mut().build_ctx();
+        self.counter_text = TextBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(4.0))
+                .with_horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .with_text(text)
+        .build(ctx);
+        self.button = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .with_width(120.0)
+                .with_height(30.0)
+                .with_margin(Thickness::uniform(4.0)),
+        )
+        .with_text("Click Me")
+        .build(ctx);
+        StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_horizontal_alignment(HorizontalAlignment::Center)
+                .with_vertical_alignment(VerticalAlignment::Center)
+                .with_child(self.counter_text)
+                .with_child(self.button),
+        )
+        .build(ctx);
+    }
+
+    fn on_ui_message(&mut self, context: &mut PluginContext, message: &UiMessage) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.button {
+                self.clicks += 1;
+                context
+                    .user_interfaces
+                    .first()
+                    .send_message(TextMessage::text(
+                        self.counter_text,
+                        MessageDirection::ToWidget,
+                        self.counter_text(),
+                    ));
+            }
+        }
+    }
+}
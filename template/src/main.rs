@@ -18,6 +18,8 @@ enum Commands {
         #[clap(short, long, default_value = "my_game")]
         name: String,
 
+        /// Template of the project: `2d` or `3d` (empty scenes), `platformer` (2D platformer),
+        /// `fps` (3D first-person shooter) or `ui` (application with user interface only).
         #[clap(short, long, default_value = "3d")]
         style: String,
 